@@ -4,7 +4,7 @@
 //!
 //! - [`Claims`]: Access token claims with full user information
 //! - [`RefreshTokenClaims`]: Refresh token claims for token renewal
-//! - [`MfaTempClaims`]: Temporary token claims for MFA verification flow
+//! - [`KioskClaims`]: Operation token claims for shared kiosk devices
 
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -20,6 +20,8 @@ use uuid::Uuid;
 /// - `sub`: User ID (subject)
 /// - `email`: User's email address
 /// - `school_id`: School scope (None for system admins)
+/// - `group_id`: School group scope (only for group admins)
+/// - `login_id`: Account that signed in, when acting as a linked profile
 /// - `role_ids`: List of assigned role UUIDs
 /// - `permissions`: List of permission strings derived from roles
 /// - `exp`: Token expiration timestamp
@@ -32,6 +34,13 @@ pub struct Claims {
     pub email: String,
     /// User's school_id for scoping (None for system admins)
     pub school_id: Option<Uuid>,
+    /// School group a group admin administers; absent for everyone else
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<Uuid>,
+    /// Account that signed in, when this token acts as one of its linked
+    /// profiles; absent otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub login_id: Option<Uuid>,
     /// Role IDs assigned to the user
     pub role_ids: Vec<Uuid>,
     /// Permission names granted to the user (derived from roles)
@@ -42,41 +51,48 @@ pub struct Claims {
     pub iat: usize,
 }
 
-/// JWT claims for MFA temporary tokens.
+/// JWT claims for refresh tokens.
 ///
-/// Issued after successful password authentication when MFA is enabled.
-/// This token has a short lifetime (10 minutes) and can only be used
-/// to complete the MFA verification flow.
+/// Refresh tokens are long-lived and used to obtain new access tokens
+/// without requiring the user to re-authenticate with their password.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MfaTempClaims {
+pub struct RefreshTokenClaims {
     /// User ID (subject claim)
     pub sub: String,
     /// User's email address
     pub email: String,
-    /// Flag indicating MFA verification is pending
-    pub mfa_pending: bool,
     /// Token expiration timestamp (Unix timestamp)
     pub exp: usize,
     /// Token issued-at timestamp (Unix timestamp)
     pub iat: usize,
+    /// Unique token identifier (JWT ID) to ensure token uniqueness
+    pub jti: String,
+    /// Token family (device session) the token belongs to
+    pub fam: Uuid,
+    /// Account that signed in, for a linked profile's session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub login_id: Option<Uuid>,
 }
 
-/// JWT claims for refresh tokens.
+/// JWT claims for kiosk operation tokens.
 ///
-/// Refresh tokens are long-lived and used to obtain new access tokens
-/// without requiring the user to re-authenticate with their password.
+/// Issued to a staff member who unlocks a registered kiosk device with their
+/// PIN. The token is bound to that device, carries no roles or permissions,
+/// and is only accepted by the kiosk endpoints.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RefreshTokenClaims {
-    /// User ID (subject claim)
+pub struct KioskClaims {
+    /// Staff user ID (subject claim)
     pub sub: String,
-    /// User's email address
-    pub email: String,
+    /// School the device is registered to
+    pub school_id: Uuid,
+    /// Branch the device is registered to
+    pub branch_id: Uuid,
+    /// Kiosk device the token was issued on
+    pub device_id: Uuid,
     /// Token expiration timestamp (Unix timestamp)
     pub exp: usize,
     /// Token issued-at timestamp (Unix timestamp)
     pub iat: usize,
-    /// Unique token identifier (JWT ID) to ensure token uniqueness
-    pub jti: String,
 }
 
 #[cfg(test)]
@@ -89,6 +105,8 @@ mod tests {
             sub: "user-id-123".to_string(),
             email: "test@example.com".to_string(),
             school_id: None,
+            group_id: None,
+            login_id: None,
             role_ids: vec![],
             permissions: vec!["users:read".to_string()],
             exp: 1234567890,
@@ -115,6 +133,8 @@ mod tests {
             sub: "user-id-789".to_string(),
             email: "clone@example.com".to_string(),
             school_id: None,
+            group_id: None,
+            login_id: None,
             role_ids: vec![],
             permissions: vec![],
             exp: 1234567890,
@@ -132,6 +152,8 @@ mod tests {
             sub: "user-123".to_string(),
             email: "user@school.com".to_string(),
             school_id: Some(school_id),
+            group_id: None,
+            login_id: None,
             role_ids: vec![Uuid::new_v4()],
             permissions: vec!["users:read".to_string(), "users:create".to_string()],
             exp: 1234567890,
@@ -142,17 +164,21 @@ mod tests {
     }
 
     #[test]
-    fn test_mfa_temp_claims_serialize() {
-        let claims = MfaTempClaims {
-            sub: "user-mfa-123".to_string(),
-            email: "mfa@test.com".to_string(),
-            mfa_pending: true,
-            exp: 1234567890,
-            iat: 1234567800,
+    fn test_claims_group_id() {
+        // Tokens issued before school groups have no group_id
+        let json = r#"{"sub":"user-id-456","email":"user@test.com","school_id":null,"role_ids":[],"permissions":[],"exp":9999999999,"iat":9999999900}"#;
+        let claims: Claims = serde_json::from_str(json).unwrap();
+        assert_eq!(claims.group_id, None);
+        assert!(!serde_json::to_string(&claims).unwrap().contains("group_id"));
+
+        let group_id = Uuid::new_v4();
+        let claims = Claims {
+            group_id: Some(group_id),
+            ..claims
         };
-        let serialized = serde_json::to_string(&claims).unwrap();
-        assert!(serialized.contains(r#""mfa_pending":true"#));
-        assert!(serialized.contains(r#""email":"mfa@test.com""#));
+        let round_trip: Claims =
+            serde_json::from_str(&serde_json::to_string(&claims).unwrap()).unwrap();
+        assert_eq!(round_trip.group_id, Some(group_id));
     }
 
     #[test]
@@ -163,6 +189,8 @@ mod tests {
             exp: 1234567890,
             iat: 1234567800,
             jti: "test-jti-123".to_string(),
+            fam: Uuid::nil(),
+            login_id: None,
         };
         let serialized = serde_json::to_string(&claims).unwrap();
         assert!(serialized.contains(r#""sub":"user-123""#));
@@ -177,23 +205,11 @@ mod tests {
             exp: 1234567890,
             iat: 1234567800,
             jti: "test-jti-456".to_string(),
+            fam: Uuid::nil(),
+            login_id: None,
         };
         let cloned = claims.clone();
         assert_eq!(claims.sub, cloned.sub);
         assert_eq!(claims.email, cloned.email);
     }
-
-    #[test]
-    fn test_mfa_temp_claims_clone() {
-        let claims = MfaTempClaims {
-            sub: "user-789".to_string(),
-            email: "mfa-clone@test.com".to_string(),
-            mfa_pending: true,
-            exp: 1234567890,
-            iat: 1234567800,
-        };
-        let cloned = claims.clone();
-        assert_eq!(claims.sub, cloned.sub);
-        assert!(cloned.mfa_pending);
-    }
 }
@@ -4,7 +4,7 @@
 //!
 //! This crate provides:
 //!
-//! - [`claims`]: JWT claim structures for access, refresh, and MFA tokens
+//! - [`claims`]: JWT claim structures for access, refresh, and kiosk tokens
 //! - [`jwt`]: Token creation and verification utilities
 //!
 //! # Token Types
@@ -13,7 +13,7 @@
 //!
 //! - **Access Token** ([`Claims`]): Short-lived token for API authentication
 //! - **Refresh Token** ([`RefreshTokenClaims`]): Long-lived token for obtaining new access tokens
-//! - **MFA Temp Token** ([`MfaTempClaims`]): Temporary token for MFA verification flow
+//! - **Kiosk Token** ([`KioskClaims`]): Short-lived token for a staff member on a shared device
 //!
 //! # Example
 //!
@@ -28,6 +28,7 @@
 //!     user_id,
 //!     "user@example.com",
 //!     Some(school_id),
+//!     None,
 //!     vec![role_id],
 //!     vec!["users:read".to_string()],
 //!     &config,
@@ -42,8 +43,9 @@ pub mod claims;
 pub mod jwt;
 
 // Re-export commonly used types at crate root
-pub use claims::{Claims, MfaTempClaims, RefreshTokenClaims};
+pub use claims::{Claims, KioskClaims, RefreshTokenClaims};
 pub use jwt::{
-    create_access_token, create_mfa_temp_token, create_refresh_token, verify_mfa_temp_token,
-    verify_refresh_token, verify_token,
+    KIOSK_TOKEN_EXPIRY, access_claims, create_access_token, create_kiosk_token,
+    create_refresh_token, encode_access_token, encode_refresh_token, refresh_claims,
+    verify_kiosk_token, verify_refresh_token, verify_token,
 };
@@ -12,6 +12,8 @@ use std::env;
 /// - `REDIS_URL`: Redis connection URL (default: `redis://127.0.0.1:6379`)
 /// - `CACHE_TTL_SECONDS`: Default TTL for cached items in seconds (default: `300`)
 /// - `CACHE_PREFIX`: Prefix for all cache keys (default: `chalkbyte`)
+/// - `SCHOOL_STATS_TTL_SECONDS`: How long school dashboard statistics are
+///   cached (default: `60`)
 #[derive(Clone, Debug)]
 pub struct CacheConfig {
     /// Redis connection URL.
@@ -22,6 +24,9 @@ pub struct CacheConfig {
 
     /// Prefix for all cache keys to avoid collisions.
     pub key_prefix: String,
+
+    /// Time-to-live for school dashboard statistics in seconds.
+    pub school_stats_ttl_seconds: u64,
 }
 
 impl CacheConfig {
@@ -32,14 +37,23 @@ impl CacheConfig {
     /// - `REDIS_URL`: `redis://127.0.0.1:6379`
     /// - `CACHE_TTL_SECONDS`: `300` (5 minutes)
     /// - `CACHE_PREFIX`: `chalkbyte`
+    /// - `SCHOOL_STATS_TTL_SECONDS`: `60`
     pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Load configuration from the variables `lookup` returns, with the
+    /// same defaults as [`from_env`](Self::from_env).
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
         Self {
-            redis_url: env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".into()),
-            default_ttl_seconds: env::var("CACHE_TTL_SECONDS")
-                .ok()
+            redis_url: lookup("REDIS_URL").unwrap_or_else(|| "redis://127.0.0.1:6379".into()),
+            default_ttl_seconds: lookup("CACHE_TTL_SECONDS")
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(300),
-            key_prefix: env::var("CACHE_PREFIX").unwrap_or_else(|_| "chalkbyte".into()),
+            key_prefix: lookup("CACHE_PREFIX").unwrap_or_else(|| "chalkbyte".into()),
+            school_stats_ttl_seconds: lookup("SCHOOL_STATS_TTL_SECONDS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
         }
     }
 
@@ -63,6 +77,7 @@ impl Default for CacheConfig {
             redis_url: "redis://127.0.0.1:6379".into(),
             default_ttl_seconds: 300,
             key_prefix: "chalkbyte".into(),
+            school_stats_ttl_seconds: 60,
         }
     }
 }
@@ -0,0 +1,215 @@
+//! Redis health tracking and degradation policy.
+//!
+//! The process keeps one [`CacheHealth`] state machine, shared by every
+//! Redis-backed component:
+//!
+//! ```text
+//! Healthy --(FAILURE_THRESHOLD consecutive failures)--> Degraded
+//! Degraded --(health probe PING succeeds)--> Healthy
+//! ```
+//!
+//! While degraded the application keeps serving requests without Redis:
+//!
+//! - [`RedisCache`](crate::RedisCache) skips Redis entirely, so reads miss
+//!   and values are computed uncached; writes and invalidations are dropped
+//! - [`etag_middleware`](crate::etag_middleware) stops buffering responses
+//!   to hash ETags, leaving the database more headroom for uncached load
+//! - rate limiting stays process-local (it never depends on Redis)
+//!
+//! Because invalidations are dropped, the health probe flushes every cache
+//! key before reporting Redis healthy again.
+//!
+//! Metrics:
+//!
+//! - `cache_degraded` (gauge): 1 while degraded
+//! - `cache_degradations_total` (counter): times Redis became unavailable
+//! - `cache_degraded_milliseconds_total` (counter): time spent degraded
+
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::{info, warn};
+
+/// Consecutive failed Redis calls that switch the process to degraded mode.
+pub const FAILURE_THRESHOLD: u32 = 3;
+
+/// How often a degraded process probes Redis.
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+static CACHE_HEALTH: LazyLock<CacheHealth> = LazyLock::new(CacheHealth::new);
+
+/// Returns the process-wide Redis health state.
+pub fn cache_health() -> &'static CacheHealth {
+    &CACHE_HEALTH
+}
+
+/// Whether Redis is currently usable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheState {
+    Healthy,
+    Degraded,
+}
+
+/// Point-in-time view of the health state, for logs and status endpoints.
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheHealthSnapshot {
+    pub state: CacheState,
+    /// Times Redis has become unavailable since startup
+    pub degradations: u64,
+    /// Total time spent degraded since startup, including the current period
+    pub degraded_ms: u64,
+}
+
+/// Redis health state machine.
+#[derive(Debug)]
+pub struct CacheHealth {
+    degraded: AtomicBool,
+    consecutive_failures: AtomicU32,
+    degradations: AtomicU64,
+    /// Time spent in completed degraded periods
+    degraded_ms: AtomicU64,
+    degraded_since: Mutex<Option<Instant>>,
+}
+
+impl CacheHealth {
+    fn new() -> Self {
+        Self {
+            degraded: AtomicBool::new(false),
+            consecutive_failures: AtomicU32::new(0),
+            degradations: AtomicU64::new(0),
+            degraded_ms: AtomicU64::new(0),
+            degraded_since: Mutex::new(None),
+        }
+    }
+
+    /// Whether Redis should be bypassed.
+    pub fn is_degraded(&self) -> bool {
+        self.degraded.load(Ordering::Acquire)
+    }
+
+    /// Current state.
+    pub fn state(&self) -> CacheState {
+        if self.is_degraded() {
+            CacheState::Degraded
+        } else {
+            CacheState::Healthy
+        }
+    }
+
+    /// Records a successful Redis call.
+    pub fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+    }
+
+    /// Records a failed Redis call, switching to degraded mode once
+    /// [`FAILURE_THRESHOLD`] calls in a row have failed.
+    pub fn record_failure(&self) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures >= FAILURE_THRESHOLD {
+            self.enter_degraded();
+        }
+    }
+
+    fn enter_degraded(&self) {
+        let mut since = self
+            .degraded_since
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if since.is_some() {
+            return;
+        }
+        *since = Some(Instant::now());
+        self.degraded.store(true, Ordering::Release);
+        self.degradations.fetch_add(1, Ordering::Relaxed);
+
+        metrics::gauge!("cache_degraded").set(1.0);
+        metrics::counter!("cache_degradations_total").increment(1);
+        warn!("Redis unavailable, serving uncached until it recovers");
+    }
+
+    /// Switches back to healthy after a successful probe.
+    pub fn recover(&self) {
+        let mut since = self
+            .degraded_since
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let Some(started) = since.take() else {
+            return;
+        };
+        let elapsed = duration_ms(started.elapsed());
+        self.degraded_ms.fetch_add(elapsed, Ordering::Relaxed);
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        self.degraded.store(false, Ordering::Release);
+
+        metrics::gauge!("cache_degraded").set(0.0);
+        metrics::counter!("cache_degraded_milliseconds_total").increment(elapsed);
+        info!(degraded_ms = elapsed, "Redis recovered, caching resumed");
+    }
+
+    /// Current state and degradation totals.
+    pub fn snapshot(&self) -> CacheHealthSnapshot {
+        let since = *self
+            .degraded_since
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let current = since.map_or(0, |started| duration_ms(started.elapsed()));
+
+        CacheHealthSnapshot {
+            state: self.state(),
+            degradations: self.degradations.load(Ordering::Relaxed),
+            degraded_ms: self.degraded_ms.load(Ordering::Relaxed) + current,
+        }
+    }
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degrades_after_consecutive_failures() {
+        let health = CacheHealth::new();
+
+        health.record_failure();
+        health.record_failure();
+        health.record_success();
+        health.record_failure();
+        health.record_failure();
+        assert_eq!(health.state(), CacheState::Healthy);
+
+        health.record_failure();
+        assert_eq!(health.state(), CacheState::Degraded);
+        assert_eq!(health.snapshot().degradations, 1);
+
+        // Further failures do not count as a new degradation
+        health.record_failure();
+        assert_eq!(health.snapshot().degradations, 1);
+    }
+
+    #[test]
+    fn test_recover_accumulates_degraded_time() {
+        let health = CacheHealth::new();
+        for _ in 0..FAILURE_THRESHOLD {
+            health.record_failure();
+        }
+
+        std::thread::sleep(Duration::from_millis(5));
+        health.recover();
+
+        let snapshot = health.snapshot();
+        assert_eq!(snapshot.state, CacheState::Healthy);
+        assert!(snapshot.degraded_ms >= 5);
+
+        // Recovering while healthy changes nothing
+        health.recover();
+        assert_eq!(health.snapshot().degraded_ms, snapshot.degraded_ms);
+    }
+}
@@ -33,6 +33,18 @@ pub mod schools {
         build_key(&["school", &school_id.to_string(), "full"])
     }
 
+    /// Key for a school's timezone and locale.
+    pub fn settings(school_id: Uuid) -> String {
+        build_key(&["school", &school_id.to_string(), "settings"])
+    }
+
+    /// Key for a school's public profile by slug.
+    ///
+    /// Shares the `school` prefix so school invalidation clears it too.
+    pub fn public_profile(slug: &str) -> String {
+        build_key(&["school", "public", slug])
+    }
+
     /// Pattern to invalidate all school-related keys.
     pub fn invalidation_pattern() -> String {
         format!("{}:school*", CACHE_PREFIX)
@@ -88,6 +100,16 @@ pub mod levels {
         build_key(&["school", &school_id.to_string(), "levels"])
     }
 
+    /// Key for a level with its student count, as seen from a school.
+    pub fn stats(level_id: Uuid, school_id: Uuid) -> String {
+        build_key(&[
+            "level",
+            &level_id.to_string(),
+            "stats",
+            &school_id.to_string(),
+        ])
+    }
+
     /// Pattern to invalidate all level-related keys.
     pub fn invalidation_pattern() -> String {
         format!("{}:level*", CACHE_PREFIX)
@@ -113,6 +135,16 @@ pub mod branches {
         build_key(&["level", &level_id.to_string(), "branches"])
     }
 
+    /// Key for a branch with its student count, as seen from a school.
+    pub fn stats(branch_id: Uuid, school_id: Uuid) -> String {
+        build_key(&[
+            "branch",
+            &branch_id.to_string(),
+            "stats",
+            &school_id.to_string(),
+        ])
+    }
+
     /// Pattern to invalidate all branch-related keys.
     pub fn invalidation_pattern() -> String {
         format!("{}:branch*", CACHE_PREFIX)
@@ -149,6 +181,114 @@ pub mod roles {
     }
 }
 
+/// Cache keys for attendance analytics.
+pub mod attendance {
+    use super::*;
+
+    /// Key for one attendance report (e.g. "trends") with a filters hash.
+    pub fn analytics(school_id: Uuid, report: &str, filters_hash: &str) -> String {
+        build_key(&[
+            "school",
+            &school_id.to_string(),
+            "attendance",
+            report,
+            filters_hash,
+        ])
+    }
+}
+
+/// Cache keys for the school dashboard.
+pub mod dashboard {
+    use super::*;
+
+    /// Key for one dashboard widget (e.g. "assets").
+    ///
+    /// Shares the `school` prefix so school invalidation clears it too.
+    pub fn widget(school_id: Uuid, widget: &str) -> String {
+        build_key(&["school", &school_id.to_string(), "dashboard", widget])
+    }
+
+    /// Key for a school's aggregate statistics.
+    pub fn stats(school_id: Uuid) -> String {
+        build_key(&["school", &school_id.to_string(), "stats"])
+    }
+}
+
+/// Cache keys for feature flags.
+pub mod feature_flags {
+    use super::*;
+
+    /// Key for the flags in effect for a school, or for callers without a
+    /// school when `school_id` is `None`.
+    pub fn effective(school_id: Option<Uuid>) -> String {
+        match school_id {
+            Some(id) => build_key(&["flags", "school", &id.to_string()]),
+            None => build_key(&["flags", "default"]),
+        }
+    }
+
+    /// Pattern to invalidate every school's flags.
+    pub fn invalidation_pattern() -> String {
+        format!("{}:flags*", CACHE_PREFIX)
+    }
+}
+
+/// Cache keys for conditional list responses (see [`crate::ListEtag`]).
+///
+/// Each list has a version per school, one across schools, and a global one.
+/// Changing a school's data replaces its version and the cross-school one,
+/// so stored ETags of the old version stop matching.
+pub mod lists {
+    use super::*;
+
+    /// Branch lists, which include student counts.
+    pub const BRANCHES: &str = "branches";
+    /// Level lists, which include student counts.
+    pub const LEVELS: &str = "levels";
+    /// Student lists.
+    pub const STUDENTS: &str = "students";
+
+    fn scope(school_id: Option<Uuid>) -> String {
+        school_id.map_or_else(|| "all".to_string(), |id| id.to_string())
+    }
+
+    /// Key for the version every school's copy of a list depends on.
+    pub fn global_version(list: &str) -> String {
+        build_key(&["list", list, "version"])
+    }
+
+    /// Key for the version of a list as seen from one school, or across
+    /// schools when `school_id` is `None`.
+    pub fn version(list: &str, school_id: Option<Uuid>) -> String {
+        build_key(&["list", list, &scope(school_id), "version"])
+    }
+
+    /// Key for the stored ETag of one list request.
+    pub fn etag(list: &str, school_id: Option<Uuid>, request_hash: &str) -> String {
+        build_key(&["list", list, &scope(school_id), "etag", request_hash])
+    }
+}
+
+/// Rate limit bucket keys (see [`crate::rate_limit`]).
+pub mod rate_limit {
+    use super::*;
+
+    /// Key for one client's bucket on a route with its own limit.
+    pub fn route(route: &str, client: &str) -> String {
+        build_key(&["ratelimit", "route", route, client])
+    }
+
+    /// Key for a user's bucket.
+    pub fn user(user_id: Uuid) -> String {
+        build_key(&["ratelimit", "user", &user_id.to_string()])
+    }
+
+    /// Key for the bucket shared by a school's users.
+    pub fn school(school_id: Uuid) -> String {
+        build_key(&["ratelimit", "school", &school_id.to_string()])
+    }
+}
+
 /// Generates a hash from filter parameters for cache key uniqueness.
 ///
 /// Uses a simple hash to create a short, consistent key component from
@@ -200,6 +340,14 @@ pub mod invalidate {
     pub async fn user(cache: Option<&RedisCache>, user_id: Option<Uuid>, school_id: Option<Uuid>) {
         let Some(cache) = cache else { return };
 
+        // Student counts appear in level and branch lists
+        lists(
+            Some(cache),
+            &[lists::STUDENTS, lists::LEVELS, lists::BRANCHES],
+            school_id,
+        )
+        .await;
+
         // Invalidate specific user if ID provided
         if let Some(id) = user_id
             && let Err(e) = cache.invalidate(&users::by_id(id)).await
@@ -235,6 +383,13 @@ pub mod invalidate {
     ) {
         let Some(cache) = cache else { return };
 
+        lists(
+            Some(cache),
+            &[lists::LEVELS, lists::BRANCHES, lists::STUDENTS],
+            school_id,
+        )
+        .await;
+
         // Invalidate specific level if ID provided
         if let Some(id) = level_id
             && let Err(e) = cache.invalidate(&levels::by_id(id)).await
@@ -260,14 +415,19 @@ pub mod invalidate {
 
     /// Invalidate all branch-related caches.
     ///
-    /// Call this after creating, updating, or deleting a branch.
+    /// Call this after creating, updating, or deleting a branch. Pass the
+    /// branch's school when known; without it every school's branch and
+    /// student lists are invalidated.
     pub async fn branch(
         cache: Option<&RedisCache>,
         branch_id: Option<Uuid>,
         level_id: Option<Uuid>,
+        school_id: Option<Uuid>,
     ) {
         let Some(cache) = cache else { return };
 
+        lists(Some(cache), &[lists::BRANCHES, lists::STUDENTS], school_id).await;
+
         // Invalidate specific branch if ID provided
         if let Some(id) = branch_id
             && let Err(e) = cache.invalidate(&branches::by_id(id)).await
@@ -331,6 +491,40 @@ pub mod invalidate {
             warn!(error = %e, user_id = %user_id, "Failed to invalidate user permissions cache");
         }
     }
+
+    /// Invalidate the stored ETags of the given lists.
+    ///
+    /// Replaces the lists' versions for `school_id` and across schools, or
+    /// the global versions when `school_id` is `None`.
+    pub async fn lists(cache: Option<&RedisCache>, names: &[&str], school_id: Option<Uuid>) {
+        let Some(cache) = cache else { return };
+
+        for list in names {
+            let keys = match school_id {
+                Some(id) => vec![lists::version(list, Some(id)), lists::version(list, None)],
+                None => vec![lists::global_version(list)],
+            };
+            for key in keys {
+                if let Err(e) = cache.bump_version(&key).await {
+                    warn!(error = %e, list = %list, "Failed to invalidate list version");
+                }
+            }
+        }
+    }
+
+    /// Invalidate the cached flags of every school.
+    ///
+    /// Call this after changing a flag or a school's override of it.
+    pub async fn feature_flags(cache: Option<&RedisCache>) {
+        let Some(cache) = cache else { return };
+
+        if let Err(e) = cache
+            .invalidate_pattern(&feature_flags::invalidation_pattern())
+            .await
+        {
+            warn!(error = %e, "Failed to invalidate feature flag caches");
+        }
+    }
 }
 
 #[cfg(test)]
@@ -345,6 +539,38 @@ mod tests {
         assert!(key.contains(&id.to_string()));
     }
 
+    #[test]
+    fn test_school_public_profile_key_matches_invalidation_pattern() {
+        let key = schools::public_profile("greenfield-academy");
+        assert_eq!(key, "chalkbyte:school:public:greenfield-academy");
+
+        let pattern = schools::invalidation_pattern();
+        assert!(key.starts_with(pattern.trim_end_matches('*')));
+    }
+
+    #[test]
+    fn test_dashboard_widget_key_matches_school_invalidation_pattern() {
+        let id = Uuid::nil();
+        let key = dashboard::widget(id, "assets");
+        assert_eq!(key, format!("chalkbyte:school:{id}:dashboard:assets"));
+
+        let pattern = schools::invalidation_pattern();
+        assert!(key.starts_with(pattern.trim_end_matches('*')));
+        assert!(dashboard::stats(id).starts_with(pattern.trim_end_matches('*')));
+    }
+
+    #[test]
+    fn test_feature_flag_keys_match_invalidation_pattern() {
+        let pattern = feature_flags::invalidation_pattern();
+
+        for key in [
+            feature_flags::effective(Some(Uuid::nil())),
+            feature_flags::effective(None),
+        ] {
+            assert!(key.starts_with(pattern.trim_end_matches('*')));
+        }
+    }
+
     #[test]
     fn test_user_key_generation() {
         let id = Uuid::nil();
@@ -352,6 +578,60 @@ mod tests {
         assert!(key.starts_with("chalkbyte:user:"));
     }
 
+    #[test]
+    fn test_stats_keys_match_invalidation_patterns() {
+        let id = Uuid::nil();
+
+        let level_key = levels::stats(id, id);
+        assert!(level_key.starts_with(levels::invalidation_pattern().trim_end_matches('*')));
+
+        let branch_key = branches::stats(id, id);
+        assert!(branch_key.starts_with(branches::invalidation_pattern().trim_end_matches('*')));
+    }
+
+    #[test]
+    fn test_rate_limit_keys() {
+        let id = Uuid::nil();
+        assert_eq!(
+            rate_limit::route("login", "10.0.0.1"),
+            "chalkbyte:ratelimit:route:login:10.0.0.1"
+        );
+        assert_eq!(
+            rate_limit::user(id),
+            format!("chalkbyte:ratelimit:user:{id}")
+        );
+        assert_eq!(
+            rate_limit::school(id),
+            format!("chalkbyte:ratelimit:school:{id}")
+        );
+    }
+
+    #[test]
+    fn test_list_keys_avoid_invalidation_patterns() {
+        let id = Uuid::nil();
+        let patterns = [
+            schools::invalidation_pattern(),
+            users::invalidation_pattern(),
+            levels::invalidation_pattern(),
+            branches::invalidation_pattern(),
+        ];
+
+        for key in [
+            lists::global_version(lists::LEVELS),
+            lists::version(lists::LEVELS, Some(id)),
+            lists::etag(lists::LEVELS, None, "abc"),
+        ] {
+            assert!(key.starts_with("chalkbyte:list:levels:"));
+            for pattern in &patterns {
+                assert!(!key.starts_with(pattern.trim_end_matches('*')));
+            }
+        }
+        assert_eq!(
+            lists::etag(lists::STUDENTS, None, "abc"),
+            "chalkbyte:list:students:all:etag:abc"
+        );
+    }
+
     #[test]
     fn test_hash_filters_consistency() {
         let filters = ("test", 123, true);
@@ -4,10 +4,18 @@
 //!
 //! This crate provides:
 //! - Redis connection management
+//! - Health tracking that bypasses Redis while it is unavailable
 //! - Cache operations (get, set, delete, invalidate by prefix)
+//! - Single-flight `get_or_compute` with stale-while-revalidate
+//! - `peek` and background `refresh` for callers that manage staleness themselves
 //! - Cache configuration from environment variables
 //! - HTTP caching middleware (ETag, Cache-Control)
+//! - Versioned list ETags that answer `If-None-Match` before the list query runs
+//! - Sliding window rate limits shared across instances
 //! - Cache key generation utilities
+//! - Refresh token storage with rotation and per-device token families
+//! - Server-side state for in-progress MFA logins
+//! - Latency and hit/miss metrics for every Redis call
 //!
 //! # Example
 //!
@@ -30,13 +38,27 @@
 //! ```
 
 pub mod config;
+pub mod health;
 pub mod keys;
+pub mod list_etag;
+pub mod mfa_challenge;
 pub mod middleware;
+pub mod rate_limit;
 pub mod redis;
+mod timing;
+pub mod token_store;
 
 pub use config::CacheConfig;
-pub use keys::{hash_filters, invalidate};
+pub use health::{CacheHealth, CacheHealthSnapshot, CacheState, cache_health};
+pub use keys::{hash_filters, invalidate, lists};
+pub use list_etag::ListEtag;
+pub use mfa_challenge::{MfaChallenge, MfaChallengeStore, RedisMfaChallengeStore};
 pub use middleware::{
     CacheControlConfig, CacheableRoute, cache_control, cache_control_duration, etag_middleware,
 };
-pub use redis::{CacheError, RedisCache};
+pub use rate_limit::{RateLimit, RateLimitBucket, RateLimitStatus};
+pub use redis::{CacheError, CachedEntry, RedisCache};
+pub use token_store::{
+    ActiveFamily, CachedTokenStore, Rotation, SessionClient, TokenFamily, TokenStore,
+    TokenStoreError, TokenStoreFuture,
+};
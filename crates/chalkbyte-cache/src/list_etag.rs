@@ -0,0 +1,204 @@
+//! Conditional GET for list endpoints without running their queries.
+//!
+//! [`etag_middleware`](crate::etag_middleware) can only hash a response after
+//! the handler has built it. For lists that are expensive to query, the ETag
+//! of each request is stored in Redis next to the version of the list it was
+//! computed from (see [`keys::lists`]). A request whose `If-None-Match`
+//! holds the stored ETag gets a 304 before the query runs, as long as the
+//! list's version has not changed since. Writes bump the version through
+//! [`invalidate`](crate::invalidate), which retires every stored ETag of the
+//! list at once.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let etag = ListEtag::lookup(cache, lists::LEVELS, Some(school_id), &(uri, user_id)).await;
+//! if let Some(not_modified) = etag.not_modified(&headers) {
+//!     return Ok(not_modified);
+//! }
+//! let levels = LevelService::get_levels_by_school(&db, school_id, filters).await?;
+//! Ok(etag.store(Json(levels).into_response()).await)
+//! ```
+
+use std::hash::Hash;
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    http::{
+        HeaderMap, HeaderValue, StatusCode,
+        header::{ETAG, IF_NONE_MATCH},
+    },
+    response::{IntoResponse, Response},
+};
+use http_body_util::BodyExt;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::keys::{hash_filters, lists};
+use crate::middleware::{generate_etag, if_none_match_matches};
+use crate::redis::RedisCache;
+
+/// How long a stored ETag is kept after the request that computed it.
+const ETAG_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// ETag of one list request and the list version it was computed from.
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredEtag {
+    version: String,
+    etag: String,
+}
+
+/// The stored ETag state of one list request.
+pub struct ListEtag<'a> {
+    cache: Option<&'a RedisCache>,
+    key: String,
+    /// Current version of the list; `None` without Redis
+    version: Option<String>,
+    /// Stored ETag, if it was computed from the current version
+    current: Option<String>,
+}
+
+impl<'a> ListEtag<'a> {
+    /// Looks up the stored ETag of a list request.
+    ///
+    /// `school_id` is the school the caller sees the list from, or `None`
+    /// for callers that see more than one school. `request` must identify
+    /// everything else the response depends on, such as the URI with its
+    /// query string and the caller.
+    pub async fn lookup(
+        cache: Option<&'a RedisCache>,
+        list: &str,
+        school_id: Option<Uuid>,
+        request: &impl Hash,
+    ) -> Self {
+        let key = lists::etag(list, school_id, &hash_filters(request));
+
+        let Some(redis) = cache else {
+            return Self {
+                cache,
+                key,
+                version: None,
+                current: None,
+            };
+        };
+
+        let version = redis
+            .versions(&[lists::global_version(list), lists::version(list, school_id)])
+            .await;
+        let current = match &version {
+            Some(version) => redis
+                .get::<StoredEtag>(&key)
+                .await
+                .filter(|stored| &stored.version == version)
+                .map(|stored| stored.etag),
+            None => None,
+        };
+
+        Self {
+            cache,
+            key,
+            version,
+            current,
+        }
+    }
+
+    /// A 304 response when the request's `If-None-Match` holds the stored
+    /// ETag, so the handler can skip building the list.
+    pub fn not_modified(&self, headers: &HeaderMap) -> Option<Response> {
+        let etag = self.current.as_deref()?;
+        let if_none_match = headers.get(IF_NONE_MATCH)?.to_str().ok()?;
+
+        if !if_none_match_matches(if_none_match, etag) {
+            return None;
+        }
+
+        let mut response = StatusCode::NOT_MODIFIED.into_response();
+        if let Ok(value) = HeaderValue::from_str(etag) {
+            response.headers_mut().insert(ETAG, value);
+        }
+        Some(response)
+    }
+
+    /// Sets the response's ETag and stores it for the current list version.
+    ///
+    /// Error responses are returned unchanged.
+    pub async fn store(self, response: Response) -> Response {
+        if !response.status().is_success() {
+            return response;
+        }
+
+        let (mut parts, body) = response.into_parts();
+        let bytes = match body.collect().await {
+            Ok(collected) => collected.to_bytes(),
+            Err(_) => return (parts, Body::empty()).into_response(),
+        };
+
+        let etag = generate_etag(&bytes);
+        if let Ok(value) = HeaderValue::from_str(&etag) {
+            parts.headers.insert(ETAG, value);
+        }
+
+        if let (Some(cache), Some(version)) = (self.cache, self.version)
+            && self.current.as_deref() != Some(etag.as_str())
+        {
+            let stored = StoredEtag { version, etag };
+            if let Err(e) = cache.set_with_ttl(&self.key, &stored, ETAG_TTL).await {
+                warn!(error = %e, cache.key = %self.key, "Failed to store list ETag");
+            }
+        }
+
+        Response::from_parts(parts, Body::from(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uncached() -> ListEtag<'static> {
+        ListEtag {
+            cache: None,
+            key: lists::etag(lists::LEVELS, None, "abc"),
+            version: None,
+            current: None,
+        }
+    }
+
+    #[test]
+    fn test_not_modified_needs_a_stored_etag() {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("\"abc\""));
+
+        assert!(uncached().not_modified(&headers).is_none());
+
+        let etag = ListEtag {
+            current: Some("\"abc\"".to_string()),
+            ..uncached()
+        };
+        let response = etag.not_modified(&headers).unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[ETAG], "\"abc\"");
+
+        assert!(etag.not_modified(&HeaderMap::new()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_store_sets_etag_header() {
+        let response = uncached().store("[1,2,3]".into_response()).await;
+
+        assert_eq!(response.headers()[ETAG], generate_etag(b"[1,2,3]").as_str());
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"[1,2,3]");
+    }
+
+    #[tokio::test]
+    async fn test_store_leaves_errors_alone() {
+        let response = uncached()
+            .store(StatusCode::NOT_FOUND.into_response())
+            .await;
+
+        assert!(!response.headers().contains_key(ETAG));
+    }
+}
@@ -0,0 +1,312 @@
+//! Server-side state for in-progress MFA logins.
+//!
+//! When a user with MFA signs in with their password they receive an opaque
+//! random handle instead of a session. The challenge behind the handle records
+//! which second factors are allowed and how many wrong codes may still be
+//! tried. Keeping it server-side means attempts can be limited and a login can
+//! be cancelled before it completes.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_cache::{MfaChallenge, MfaChallengeStore, RedisMfaChallengeStore};
+//!
+//! let store = RedisMfaChallengeStore::from_cache(&cache);
+//!
+//! store.create(&handle, &challenge, ttl).await?;
+//!
+//! if code_is_wrong {
+//!     let attempts_left = store.record_failure(&handle).await?;
+//! } else if let Some(challenge) = store.complete(&handle).await? {
+//!     // issue tokens for challenge.user_id
+//! }
+//! ```
+
+use std::time::Duration;
+
+use redis::{AsyncCommands, Script, aio::ConnectionManager};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::redis::RedisCache;
+use crate::token_store::{TokenStoreError, TokenStoreFuture};
+
+/// An in-progress MFA login.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MfaChallenge {
+    pub user_id: Uuid,
+    /// Whether a recovery code may be used instead of a TOTP code
+    pub allow_recovery_code: bool,
+    /// Wrong codes that may still be submitted before the login is cancelled
+    pub attempts_left: u32,
+}
+
+/// Abstract trait for MFA challenge storage backends.
+///
+/// Implementations can be swapped without changing business logic.
+pub trait MfaChallengeStore: Send + Sync {
+    /// Store a challenge under `handle` until `ttl` elapses.
+    fn create<'a>(
+        &'a self,
+        handle: &'a str,
+        challenge: &'a MfaChallenge,
+        ttl: Duration,
+    ) -> TokenStoreFuture<'a, ()>;
+
+    /// Look up a live challenge.
+    fn get<'a>(&'a self, handle: &'a str) -> TokenStoreFuture<'a, Option<MfaChallenge>>;
+
+    /// Count a wrong code and return the attempts left.
+    ///
+    /// The challenge is removed once no attempts are left. Returns `None` if
+    /// the challenge no longer exists.
+    fn record_failure<'a>(&'a self, handle: &'a str) -> TokenStoreFuture<'a, Option<u32>>;
+
+    /// Remove and return the challenge so the handle can complete only once.
+    fn complete<'a>(&'a self, handle: &'a str) -> TokenStoreFuture<'a, Option<MfaChallenge>>;
+
+    /// Cancel every in-progress login for a user.
+    fn cancel_all<'a>(&'a self, user_id: Uuid) -> TokenStoreFuture<'a, ()>;
+}
+
+/// Redis-backed [`MfaChallengeStore`].
+///
+/// Each challenge is a hash under `auth:mfa:{handle}` expiring with the
+/// challenge. A set under `auth:user:{user_id}:mfa` indexes a user's handles.
+#[derive(Clone)]
+pub struct RedisMfaChallengeStore {
+    conn: ConnectionManager,
+}
+
+impl std::fmt::Debug for RedisMfaChallengeStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisMfaChallengeStore")
+            .finish_non_exhaustive()
+    }
+}
+
+fn challenge_key(handle: &str) -> String {
+    format!("auth:mfa:{}", handle)
+}
+
+fn user_challenges_key(user_id: Uuid) -> String {
+    format!("auth:user:{}:mfa", user_id)
+}
+
+// KEYS[1] challenge hash; returns attempts left, or -1 if the challenge is gone
+const RECORD_FAILURE_SCRIPT: &str = r#"
+if redis.call('EXISTS', KEYS[1]) == 0 then
+    return -1
+end
+local left = redis.call('HINCRBY', KEYS[1], 'attempts_left', -1)
+if left <= 0 then
+    redis.call('DEL', KEYS[1])
+    return 0
+end
+return left
+"#;
+
+// KEYS[1] challenge hash; returns the fields and deletes the hash
+const COMPLETE_SCRIPT: &str = r#"
+local fields = redis.call('HMGET', KEYS[1], 'user_id', 'allow_recovery_code', 'attempts_left')
+redis.call('DEL', KEYS[1])
+return fields
+"#;
+
+// KEYS[1] user index
+const CANCEL_ALL_SCRIPT: &str = r#"
+for _, handle in ipairs(redis.call('SMEMBERS', KEYS[1])) do
+    redis.call('DEL', 'auth:mfa:' .. handle)
+end
+redis.call('DEL', KEYS[1])
+return 1
+"#;
+
+fn parse_challenge(
+    user_id: Option<String>,
+    allow_recovery_code: Option<String>,
+    attempts_left: Option<u32>,
+) -> Option<MfaChallenge> {
+    Some(MfaChallenge {
+        user_id: Uuid::parse_str(&user_id?).ok()?,
+        allow_recovery_code: allow_recovery_code? == "1",
+        attempts_left: attempts_left?,
+    })
+}
+
+impl RedisMfaChallengeStore {
+    /// Creates a challenge store sharing the cache's Redis connection.
+    pub fn from_cache(cache: &RedisCache) -> Self {
+        Self {
+            conn: cache.connection(),
+        }
+    }
+}
+
+impl MfaChallengeStore for RedisMfaChallengeStore {
+    fn create<'a>(
+        &'a self,
+        handle: &'a str,
+        challenge: &'a MfaChallenge,
+        ttl: Duration,
+    ) -> TokenStoreFuture<'a, ()> {
+        Box::pin(async move {
+            let mut conn = self.conn.clone();
+            let key = challenge_key(handle);
+            let index = user_challenges_key(challenge.user_id);
+
+            redis::pipe()
+                .atomic()
+                .hset_multiple(
+                    &key,
+                    &[
+                        ("user_id", challenge.user_id.to_string()),
+                        (
+                            "allow_recovery_code",
+                            u8::from(challenge.allow_recovery_code).to_string(),
+                        ),
+                        ("attempts_left", challenge.attempts_left.to_string()),
+                    ],
+                )
+                .expire(&key, ttl.as_secs() as i64)
+                .sadd(&index, handle)
+                .expire(&index, ttl.as_secs() as i64)
+                .query_async::<()>(&mut conn)
+                .await?;
+
+            debug!(user.id = %challenge.user_id, "MFA challenge created");
+            Ok(())
+        })
+    }
+
+    fn get<'a>(&'a self, handle: &'a str) -> TokenStoreFuture<'a, Option<MfaChallenge>> {
+        Box::pin(async move {
+            let mut conn = self.conn.clone();
+            let (user_id, allow_recovery_code, attempts_left): (
+                Option<String>,
+                Option<String>,
+                Option<u32>,
+            ) = conn
+                .hget(
+                    challenge_key(handle),
+                    &["user_id", "allow_recovery_code", "attempts_left"],
+                )
+                .await?;
+
+            Ok(parse_challenge(user_id, allow_recovery_code, attempts_left))
+        })
+    }
+
+    fn record_failure<'a>(&'a self, handle: &'a str) -> TokenStoreFuture<'a, Option<u32>> {
+        Box::pin(async move {
+            let mut conn = self.conn.clone();
+            let left: i64 = Script::new(RECORD_FAILURE_SCRIPT)
+                .key(challenge_key(handle))
+                .invoke_async(&mut conn)
+                .await?;
+
+            Ok(u32::try_from(left).ok())
+        })
+    }
+
+    fn complete<'a>(&'a self, handle: &'a str) -> TokenStoreFuture<'a, Option<MfaChallenge>> {
+        Box::pin(async move {
+            let mut conn = self.conn.clone();
+            let (user_id, allow_recovery_code, attempts_left): (
+                Option<String>,
+                Option<String>,
+                Option<u32>,
+            ) = Script::new(COMPLETE_SCRIPT)
+                .key(challenge_key(handle))
+                .invoke_async(&mut conn)
+                .await?;
+
+            let challenge = parse_challenge(user_id, allow_recovery_code, attempts_left);
+            if let Some(challenge) = &challenge {
+                conn.srem::<_, _, ()>(user_challenges_key(challenge.user_id), handle)
+                    .await?;
+            }
+            Ok(challenge)
+        })
+    }
+
+    fn cancel_all<'a>(&'a self, user_id: Uuid) -> TokenStoreFuture<'a, ()> {
+        Box::pin(async move {
+            let mut conn = self.conn.clone();
+            Script::new(CANCEL_ALL_SCRIPT)
+                .key(user_challenges_key(user_id))
+                .invoke_async::<()>(&mut conn)
+                .await
+                .map_err(TokenStoreError::from)?;
+
+            debug!(user.id = %user_id, "MFA challenges cancelled");
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Integration tests require a running Redis instance
+
+    async fn store() -> RedisMfaChallengeStore {
+        let cache = RedisCache::new("redis://localhost:6379", Duration::from_secs(60))
+            .await
+            .unwrap();
+        RedisMfaChallengeStore::from_cache(&cache)
+    }
+
+    fn challenge(attempts_left: u32) -> MfaChallenge {
+        MfaChallenge {
+            user_id: Uuid::new_v4(),
+            allow_recovery_code: true,
+            attempts_left,
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_failures_exhaust_challenge() {
+        let store = store().await;
+        let handle = Uuid::new_v4().to_string();
+        let ttl = Duration::from_secs(60);
+
+        store.create(&handle, &challenge(2), ttl).await.unwrap();
+        assert_eq!(store.record_failure(&handle).await.unwrap(), Some(1));
+        assert_eq!(store.record_failure(&handle).await.unwrap(), Some(0));
+        assert_eq!(store.get(&handle).await.unwrap(), None);
+        assert_eq!(store.record_failure(&handle).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_complete_is_single_use() {
+        let store = store().await;
+        let handle = Uuid::new_v4().to_string();
+        let challenge = challenge(5);
+
+        store
+            .create(&handle, &challenge, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(store.complete(&handle).await.unwrap(), Some(challenge));
+        assert_eq!(store.complete(&handle).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_cancel_all() {
+        let store = store().await;
+        let handle = Uuid::new_v4().to_string();
+        let challenge = challenge(5);
+
+        store
+            .create(&handle, &challenge, Duration::from_secs(60))
+            .await
+            .unwrap();
+        store.cancel_all(challenge.user_id).await.unwrap();
+        assert_eq!(store.get(&handle).await.unwrap(), None);
+    }
+}
@@ -27,6 +27,8 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use http_body_util::BodyExt;
+
+use crate::health::cache_health;
 use sha2::{Digest, Sha256};
 use std::time::Duration;
 use tower_http::set_header::SetResponseHeaderLayer;
@@ -221,7 +223,7 @@ pub fn cache_control_duration(
 }
 
 /// Generate an ETag from response body bytes.
-fn generate_etag(body: &[u8]) -> String {
+pub(crate) fn generate_etag(body: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(body);
     let hash = hasher.finalize();
@@ -235,6 +237,15 @@ fn etags_match(client_etag: &str, server_etag: &str) -> bool {
     client == server
 }
 
+/// Whether an `If-None-Match` header value (`*` or a comma-separated list)
+/// matches the server's ETag.
+pub(crate) fn if_none_match_matches(if_none_match: &str, server_etag: &str) -> bool {
+    if_none_match.trim() == "*"
+        || if_none_match
+            .split(',')
+            .any(|tag| etags_match(tag, server_etag))
+}
+
 /// ETag middleware for conditional GET requests.
 ///
 /// This middleware:
@@ -247,6 +258,9 @@ fn etags_match(client_etag: &str, server_etag: &str) -> bool {
 /// This middleware buffers the entire response body, so it's best suited
 /// for smaller responses. For large responses, consider using pre-computed ETags.
 ///
+/// While Redis is degraded (see [`crate::health`]) responses pass through
+/// without an ETag.
+///
 /// # Example
 ///
 /// ```ignore
@@ -266,6 +280,10 @@ pub async fn etag_middleware(request: Request, next: Next) -> Response {
 
     let response = next.run(request).await;
 
+    if cache_health().is_degraded() {
+        return response;
+    }
+
     // Only process successful GET responses
     if !response.status().is_success() {
         return response;
@@ -275,7 +293,7 @@ pub async fn etag_middleware(request: Request, next: Next) -> Response {
     if response.headers().contains_key(ETAG) {
         if let Some(client_etag) = if_none_match
             && let Some(server_etag) = response.headers().get(ETAG).and_then(|v| v.to_str().ok())
-            && etags_match(&client_etag, server_etag)
+            && if_none_match_matches(&client_etag, server_etag)
         {
             return StatusCode::NOT_MODIFIED.into_response();
         }
@@ -294,15 +312,10 @@ pub async fn etag_middleware(request: Request, next: Next) -> Response {
     let etag = generate_etag(&bytes);
 
     // Check If-None-Match
-    if let Some(client_etag) = if_none_match {
-        // Handle multiple ETags in If-None-Match (comma-separated)
-        let matches = client_etag
-            .split(',')
-            .any(|tag| etags_match(tag.trim(), &etag));
-
-        if matches {
-            return StatusCode::NOT_MODIFIED.into_response();
-        }
+    if let Some(client_etag) = if_none_match
+        && if_none_match_matches(&client_etag, &etag)
+    {
+        return StatusCode::NOT_MODIFIED.into_response();
     }
 
     // Build response with ETag header
@@ -391,4 +404,11 @@ mod tests {
         assert!(etags_match("\"abc123\"", "W/\"abc123\""));
         assert!(!etags_match("\"abc123\"", "\"xyz789\""));
     }
+
+    #[test]
+    fn test_if_none_match_lists() {
+        assert!(if_none_match_matches("\"a\", W/\"b\"", "\"b\""));
+        assert!(if_none_match_matches(" * ", "\"b\""));
+        assert!(!if_none_match_matches("\"a\", \"c\"", "\"b\""));
+    }
 }
@@ -0,0 +1,279 @@
+//! Sliding window rate limits shared by every API instance.
+//!
+//! Each bucket is a Redis sorted set holding one entry per admitted request
+//! in the last window, scored by the Redis server's clock. A request is
+//! checked against all of its buckets in one script: it is admitted only if
+//! every bucket has room, and then counted in all of them. Rejected requests
+//! are not counted, so a client that keeps retrying recovers once the window
+//! slides past its earlier requests.
+//!
+//! Rate limiting fails open: without Redis, or while it is degraded, every
+//! request is admitted.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_cache::{RateLimit, RateLimitBucket, keys};
+//!
+//! let buckets = [RateLimitBucket::new(
+//!     keys::rate_limit::user(user_id),
+//!     RateLimit::per_minute(300),
+//! )];
+//! if let Some(status) = cache.check_rate_limits(&buckets).await {
+//!     let mut response = if status.allowed { next.run(request).await } else { too_many() };
+//!     status.apply_headers(response.headers_mut());
+//! }
+//! ```
+
+use std::time::Duration;
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue, header::RETRY_AFTER};
+use redis::Script;
+use tracing::{error, instrument};
+use uuid::Uuid;
+
+use crate::redis::{CacheError, RedisCache};
+use crate::timing::CacheTimer;
+
+/// `RateLimit-Limit` response header.
+pub const RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+/// `RateLimit-Remaining` response header.
+pub const RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+/// `RateLimit-Reset` response header, in seconds.
+pub const RATE_LIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+/// Checks every bucket and counts the request in all of them only if each
+/// has room. Returns whether it was admitted, then for each bucket the
+/// requests counted in its window and the milliseconds until its oldest one
+/// leaves the window.
+///
+/// KEYS: the buckets. ARGV[1]: a unique member for this request, then the
+/// limit and window in milliseconds of each bucket.
+const SLIDING_WINDOW_SCRIPT: &str = r#"
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local allowed = 1
+local buckets = {}
+for i, key in ipairs(KEYS) do
+    local limit = tonumber(ARGV[i * 2])
+    local window = tonumber(ARGV[i * 2 + 1])
+    redis.call('ZREMRANGEBYSCORE', key, '-inf', now - window)
+    local count = redis.call('ZCARD', key)
+    local reset = window
+    local oldest = redis.call('ZRANGE', key, 0, 0, 'WITHSCORES')
+    if oldest[2] then
+        reset = tonumber(oldest[2]) + window - now
+    end
+    if count >= limit then
+        allowed = 0
+    end
+    buckets[i] = {count, reset}
+end
+if allowed == 1 then
+    for i, key in ipairs(KEYS) do
+        redis.call('ZADD', key, now, ARGV[1])
+        redis.call('PEXPIRE', key, tonumber(ARGV[i * 2 + 1]))
+        buckets[i][1] = buckets[i][1] + 1
+    end
+end
+return {allowed, buckets}
+"#;
+
+/// How many requests a bucket admits per window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: u32,
+    pub window: Duration,
+}
+
+impl RateLimit {
+    /// `limit` requests per minute.
+    pub const fn per_minute(limit: u32) -> Self {
+        Self {
+            limit,
+            window: Duration::from_secs(60),
+        }
+    }
+
+    /// `limit` requests per hour.
+    pub const fn per_hour(limit: u32) -> Self {
+        Self {
+            limit,
+            window: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// A Redis key and the limit it enforces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitBucket {
+    pub key: String,
+    pub limit: RateLimit,
+}
+
+impl RateLimitBucket {
+    pub fn new(key: String, limit: RateLimit) -> Self {
+        Self { key, limit }
+    }
+}
+
+/// Outcome of checking a request against its buckets, reported from the
+/// bucket closest to its limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// Whether the request was admitted
+    pub allowed: bool,
+    pub limit: u32,
+    /// Requests left in the window after this one
+    pub remaining: u32,
+    /// Time until the window has room again
+    pub reset: Duration,
+}
+
+impl RateLimitStatus {
+    /// Picks the bucket with the fewest requests left from `(limit, count,
+    /// reset)` of each bucket.
+    fn tightest(
+        allowed: bool,
+        buckets: impl IntoIterator<Item = (RateLimit, u64, u64)>,
+    ) -> Option<Self> {
+        buckets
+            .into_iter()
+            .map(|(limit, count, reset_ms)| Self {
+                allowed,
+                limit: limit.limit,
+                remaining: limit
+                    .limit
+                    .saturating_sub(count.min(u32::MAX as u64) as u32),
+                reset: Duration::from_millis(reset_ms),
+            })
+            .min_by_key(|status| (status.remaining, std::cmp::Reverse(status.reset)))
+    }
+
+    /// Seconds until the window has room again, rounded up.
+    pub fn reset_seconds(&self) -> u64 {
+        self.reset.as_millis().div_ceil(1000) as u64
+    }
+
+    /// Sets the `RateLimit-*` headers on a response, and `Retry-After` when
+    /// the request was rejected.
+    pub fn apply_headers(&self, headers: &mut HeaderMap) {
+        headers.insert(RATE_LIMIT_LIMIT, HeaderValue::from(self.limit));
+        headers.insert(RATE_LIMIT_REMAINING, HeaderValue::from(self.remaining));
+        headers.insert(RATE_LIMIT_RESET, HeaderValue::from(self.reset_seconds()));
+        if !self.allowed {
+            headers.insert(RETRY_AFTER, HeaderValue::from(self.reset_seconds()));
+        }
+    }
+}
+
+impl RedisCache {
+    /// Counts a request against `buckets`, unless one of them is full.
+    ///
+    /// Returns `None` when there are no buckets or Redis is unavailable, in
+    /// which case the request should be admitted.
+    #[instrument(skip(self, buckets), fields(cache.operation = "RATE_LIMIT"))]
+    pub async fn check_rate_limits(&self, buckets: &[RateLimitBucket]) -> Option<RateLimitStatus> {
+        if buckets.is_empty() {
+            return None;
+        }
+
+        let mut conn = self.connection();
+        let script = Script::new(SLIDING_WINDOW_SCRIPT);
+        let mut invocation = script.prepare_invoke();
+        invocation.arg(Uuid::new_v4().to_string());
+        for bucket in buckets {
+            invocation
+                .key(&bucket.key)
+                .arg(bucket.limit.limit)
+                .arg(bucket.limit.window.as_millis() as u64);
+        }
+
+        let timer = CacheTimer::start("rate_limit");
+        let result = self
+            .call(invocation.invoke_async::<(i64, Vec<(u64, u64)>)>(&mut conn))
+            .await;
+        timer.finish(&result);
+
+        match result {
+            Ok((allowed, counts)) => RateLimitStatus::tightest(
+                allowed == 1,
+                buckets
+                    .iter()
+                    .zip(counts)
+                    .map(|(bucket, (count, reset))| (bucket.limit, count, reset)),
+            ),
+            Err(CacheError::Degraded) => None,
+            Err(e) => {
+                error!(error = %e, "Redis rate limit error");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tightest_bucket_is_reported() {
+        let status = RateLimitStatus::tightest(
+            true,
+            [
+                (RateLimit::per_minute(100), 10, 30_000),
+                (RateLimit::per_hour(5), 4, 1_800_500),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(status.limit, 5);
+        assert_eq!(status.remaining, 1);
+        assert_eq!(status.reset_seconds(), 1801);
+    }
+
+    #[test]
+    fn test_headers() {
+        let mut status = RateLimitStatus {
+            allowed: true,
+            limit: 5,
+            remaining: 0,
+            reset: Duration::from_millis(2_100),
+        };
+        let mut headers = HeaderMap::new();
+        status.apply_headers(&mut headers);
+
+        assert_eq!(headers[RATE_LIMIT_LIMIT], "5");
+        assert_eq!(headers[RATE_LIMIT_REMAINING], "0");
+        assert_eq!(headers[RATE_LIMIT_RESET], "3");
+        assert!(!headers.contains_key(RETRY_AFTER));
+
+        status.allowed = false;
+        status.apply_headers(&mut headers);
+        assert_eq!(headers[RETRY_AFTER], "3");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_bucket_fills_up() {
+        let cache = RedisCache::new("redis://localhost:6379", Duration::from_secs(60))
+            .await
+            .unwrap();
+        let bucket = RateLimitBucket::new(
+            format!("chalkbyte:ratelimit:test:{}", Uuid::new_v4()),
+            RateLimit::per_minute(2),
+        );
+        let buckets = [bucket];
+
+        let first = cache.check_rate_limits(&buckets).await.unwrap();
+        assert!(first.allowed);
+        assert_eq!(first.remaining, 1);
+
+        assert!(cache.check_rate_limits(&buckets).await.unwrap().allowed);
+
+        let third = cache.check_rate_limits(&buckets).await.unwrap();
+        assert!(!third.allowed);
+        assert_eq!(third.remaining, 0);
+        assert!(third.reset <= Duration::from_secs(60));
+    }
+}
@@ -1,11 +1,114 @@
 //! Redis cache client for distributed caching.
 //!
 //! Provides async Redis operations with JSON serialization for cached values.
+//!
+//! Every call reports to the process-wide [`cache_health`] state. Once Redis
+//! is degraded, calls skip it entirely: reads miss and writes and
+//! invalidations are dropped, until the health probe sees Redis again.
+
+use redis::{AsyncCommands, Client, Script, aio::ConnectionManager};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, error, instrument, warn};
+use uuid::Uuid;
+
+use crate::health::{PROBE_INTERVAL, cache_health};
+use crate::timing::CacheTimer;
+
+/// Longest a cache call waits for Redis before counting as a failure.
+const OPERATION_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Longest a key scan waits for Redis before counting as a failure.
+const SCAN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pattern matching every key written through [`RedisCache`].
+const CACHE_KEY_PATTERN: &str = "chalkbyte:*";
+
+/// How long a recompute lock is held before Redis expires it.
+const COMPUTE_LOCK_TTL: Duration = Duration::from_secs(5);
+
+/// How often a caller without the lock re-checks for a value on a cold miss.
+const COMPUTE_WAIT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How many times a caller without the lock re-checks before computing itself.
+const COMPUTE_WAIT_ATTEMPTS: u32 = 20;
+
+/// Releases a lock only if it still holds the caller's token.
+const RELEASE_LOCK_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("DEL", KEYS[1])
+end
+return 0
+"#;
+
+/// Reads version keys, giving missing ones the fresh value passed for them.
+const VERSIONS_SCRIPT: &str = r#"
+local versions = {}
+for i, key in ipairs(KEYS) do
+    local version = redis.call("GET", key)
+    if not version then
+        version = ARGV[i]
+        redis.call("SET", key, version)
+    end
+    versions[i] = version
+end
+return versions
+"#;
+
+/// Value stored by [`RedisCache::get_or_compute`].
+///
+/// The Redis key outlives `fresh_until` by one TTL so that stale values can
+/// be served while a single caller recomputes.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CachedEntry<T> {
+    pub value: T,
+    /// Unix time in milliseconds after which the value is stale
+    pub fresh_until_ms: u64,
+}
+
+impl<T> CachedEntry<T> {
+    /// Whether the value is still within its TTL.
+    pub fn is_fresh(&self) -> bool {
+        now_ms() < self.fresh_until_ms
+    }
+}
+
+/// Deletes every key matching `pattern`, returning how many were deleted.
+///
+/// Uses SCAN so Redis is never blocked on a large keyspace.
+async fn delete_matching(conn: &mut ConnectionManager, pattern: &str) -> redis::RedisResult<u64> {
+    let mut cursor: u64 = 0;
+    let mut deleted: u64 = 0;
+
+    loop {
+        let (next_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(100)
+            .query_async(conn)
+            .await?;
+
+        if !keys.is_empty() {
+            let count: u64 = conn.del(&keys).await?;
+            deleted += count;
+        }
+
+        cursor = next_cursor;
+        if cursor == 0 {
+            return Ok(deleted);
+        }
+    }
+}
 
-use redis::{AsyncCommands, Client, aio::ConnectionManager};
-use serde::{Deserialize, Serialize};
-use std::time::Duration;
-use tracing::{debug, error, instrument};
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
 
 /// Redis cache client with connection pooling.
 #[derive(Clone)]
@@ -33,6 +136,12 @@ pub enum CacheError {
 
     #[error("Cache miss")]
     Miss,
+
+    #[error("Redis call timed out")]
+    Timeout,
+
+    #[error("Redis is degraded, cache bypassed")]
+    Degraded,
 }
 
 impl RedisCache {
@@ -53,6 +162,83 @@ impl RedisCache {
         Ok(Self { conn, default_ttl })
     }
 
+    /// Returns a handle to the shared connection for other Redis-backed stores.
+    pub(crate) fn connection(&self) -> ConnectionManager {
+        self.conn.clone()
+    }
+
+    /// Runs a Redis call under the degradation policy.
+    ///
+    /// Fails fast with [`CacheError::Degraded`] while degraded. Timeouts and
+    /// connection errors count towards degrading; command errors do not.
+    pub(crate) async fn call<T, F>(&self, op: F) -> Result<T, CacheError>
+    where
+        F: Future<Output = redis::RedisResult<T>>,
+    {
+        self.call_with_timeout(OPERATION_TIMEOUT, op).await
+    }
+
+    async fn call_with_timeout<T, F>(&self, timeout: Duration, op: F) -> Result<T, CacheError>
+    where
+        F: Future<Output = redis::RedisResult<T>>,
+    {
+        let health = cache_health();
+        if health.is_degraded() {
+            return Err(CacheError::Degraded);
+        }
+
+        match tokio::time::timeout(timeout, op).await {
+            Ok(Ok(value)) => {
+                health.record_success();
+                Ok(value)
+            }
+            Ok(Err(e)) => {
+                if e.is_io_error()
+                    || e.is_connection_dropped()
+                    || e.is_connection_refusal()
+                    || e.is_timeout()
+                {
+                    health.record_failure();
+                }
+                Err(e.into())
+            }
+            Err(_) => {
+                health.record_failure();
+                Err(CacheError::Timeout)
+            }
+        }
+    }
+
+    /// Start a background task that probes Redis while it is degraded.
+    ///
+    /// Invalidations are dropped while degraded, so the probe deletes every
+    /// cache key before caching resumes.
+    pub fn spawn_health_probe(&self) {
+        let mut conn = self.conn.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(PROBE_INTERVAL);
+            loop {
+                interval.tick().await;
+                if !cache_health().is_degraded() {
+                    continue;
+                }
+
+                let probe = async {
+                    redis::cmd("PING").query_async::<()>(&mut conn).await?;
+                    delete_matching(&mut conn, CACHE_KEY_PATTERN).await
+                };
+                match tokio::time::timeout(SCAN_TIMEOUT, probe).await {
+                    Ok(Ok(flushed)) => {
+                        debug!(cache.deleted = %flushed, "Flushed cache keys after outage");
+                        cache_health().recover();
+                    }
+                    Ok(Err(e)) => debug!(error = %e, "Redis health probe failed"),
+                    Err(_) => debug!("Redis health probe timed out"),
+                }
+            }
+        });
+    }
+
     /// Gets a cached value by key.
     ///
     /// Returns `None` if the key doesn't exist or deserialization fails.
@@ -63,7 +249,11 @@ impl RedisCache {
     {
         let mut conn = self.conn.clone();
 
-        match conn.get::<_, Option<String>>(key).await {
+        let timer = CacheTimer::start("get");
+        let result = self.call(conn.get::<_, Option<String>>(key)).await;
+        timer.lookup(&result);
+
+        match result {
             Ok(Some(value)) => {
                 debug!(cache.key = %key, "Cache hit");
                 match serde_json::from_str(&value) {
@@ -78,6 +268,7 @@ impl RedisCache {
                 debug!(cache.key = %key, "Cache miss");
                 None
             }
+            Err(CacheError::Degraded) => None,
             Err(e) => {
                 error!(cache.key = %key, error = %e, "Redis GET error");
                 None
@@ -108,19 +299,259 @@ impl RedisCache {
         let mut conn = self.conn.clone();
         let json = serde_json::to_string(value)?;
 
-        conn.set_ex::<_, _, ()>(key, json, ttl.as_secs()).await?;
+        let timer = CacheTimer::start("set");
+        let result = self
+            .call(conn.set_ex::<_, _, ()>(key, json, ttl.as_secs()))
+            .await;
+        timer.finish(&result);
+
+        match result {
+            Err(CacheError::Degraded) => return Ok(()),
+            result => result?,
+        }
 
         debug!(cache.key = %key, cache.ttl_secs = %ttl.as_secs(), "Cache set");
 
         Ok(())
     }
 
+    /// Gets a cached value, computing and storing it when missing or expired.
+    ///
+    /// Only one caller recomputes an expired entry: it takes a short lock
+    /// (`SET NX PX`) while the others keep serving the stale value until the
+    /// new one is stored. On a cold miss, callers without the lock wait briefly
+    /// for the lock holder before falling back to computing themselves.
+    ///
+    /// Entries are kept for twice `ttl` and considered fresh for the first
+    /// `ttl`. Keys used here must not be read with [`get`](Self::get), since
+    /// they are stored with freshness metadata. Redis errors never fail the
+    /// call; the value is computed directly instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `compute` when it fails.
+    #[instrument(skip(self, compute), fields(cache.operation = "GET_OR_COMPUTE"))]
+    pub async fn get_or_compute<T, E, F, Fut>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        compute: F,
+    ) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let stale = match self.get::<CachedEntry<T>>(key).await {
+            Some(entry) if entry.is_fresh() => return Ok(entry.value),
+            entry => entry,
+        };
+
+        let lock_key = format!("{key}:lock");
+        let token = Uuid::new_v4().to_string();
+
+        if !self.try_lock(&lock_key, &token).await {
+            if let Some(entry) = stale {
+                debug!(cache.key = %key, "Serving stale value while another caller recomputes");
+                return Ok(entry.value);
+            }
+
+            for _ in 0..COMPUTE_WAIT_ATTEMPTS {
+                tokio::time::sleep(COMPUTE_WAIT_INTERVAL).await;
+                if let Some(entry) = self.get::<CachedEntry<T>>(key).await {
+                    return Ok(entry.value);
+                }
+            }
+
+            debug!(cache.key = %key, "Timed out waiting for recompute");
+            return compute().await;
+        }
+
+        self.compute_and_store(key, ttl, &lock_key, &token, compute)
+            .await
+    }
+
+    /// Reads an entry stored by [`get_or_compute`](Self::get_or_compute) or
+    /// [`refresh`](Self::refresh) without computing it, fresh or stale.
+    #[instrument(skip(self), fields(cache.operation = "PEEK"))]
+    pub async fn peek<T>(&self, key: &str) -> Option<CachedEntry<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.get::<CachedEntry<T>>(key).await
+    }
+
+    /// Recomputes and stores an entry for a
+    /// [`get_or_compute`](Self::get_or_compute) key, unless another caller is
+    /// already recomputing it.
+    ///
+    /// Returns `None` without calling `compute` when the recompute lock is
+    /// held elsewhere, so callers can fire this in the background on every
+    /// stale read without piling up work.
+    #[instrument(skip(self, compute), fields(cache.operation = "REFRESH"))]
+    pub async fn refresh<T, E, F, Fut>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        compute: F,
+    ) -> Option<Result<T, E>>
+    where
+        T: Serialize,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let lock_key = format!("{key}:lock");
+        let token = Uuid::new_v4().to_string();
+
+        if !self.try_lock(&lock_key, &token).await {
+            debug!(cache.key = %key, "Skipping refresh while another caller recomputes");
+            return None;
+        }
+
+        Some(
+            self.compute_and_store(key, ttl, &lock_key, &token, compute)
+                .await,
+        )
+    }
+
+    /// Computes a value under a held lock, stores it, and releases the lock.
+    async fn compute_and_store<T, E, F, Fut>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        lock_key: &str,
+        token: &str,
+        compute: F,
+    ) -> Result<T, E>
+    where
+        T: Serialize,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let result = compute().await;
+
+        if let Ok(value) = &result {
+            let entry = CachedEntry {
+                value,
+                fresh_until_ms: now_ms() + ttl.as_millis() as u64,
+            };
+            if let Err(e) = self.set_with_ttl(key, &entry, ttl * 2).await {
+                warn!(cache.key = %key, error = %e, "Failed to store computed value");
+            }
+        }
+
+        self.release_lock(lock_key, token).await;
+
+        result
+    }
+
+    /// Tries to take a recompute lock. Redis errors count as acquired.
+    async fn try_lock(&self, lock_key: &str, token: &str) -> bool {
+        let mut conn = self.conn.clone();
+
+        let result = self
+            .call(
+                redis::cmd("SET")
+                    .arg(lock_key)
+                    .arg(token)
+                    .arg("NX")
+                    .arg("PX")
+                    .arg(COMPUTE_LOCK_TTL.as_millis() as u64)
+                    .query_async::<Option<String>>(&mut conn),
+            )
+            .await;
+
+        match result {
+            Ok(reply) => reply.is_some(),
+            Err(CacheError::Degraded) => true,
+            Err(e) => {
+                error!(cache.key = %lock_key, error = %e, "Redis SET NX error");
+                true
+            }
+        }
+    }
+
+    /// Releases a recompute lock if it is still held with `token`.
+    async fn release_lock(&self, lock_key: &str, token: &str) {
+        let mut conn = self.conn.clone();
+
+        let script = Script::new(RELEASE_LOCK_SCRIPT);
+        let mut invocation = script.key(lock_key);
+        invocation.arg(token);
+        let result = self.call(invocation.invoke_async::<i64>(&mut conn)).await;
+
+        if let Err(e) = result
+            && !matches!(e, CacheError::Degraded)
+        {
+            warn!(cache.key = %lock_key, error = %e, "Failed to release recompute lock");
+        }
+    }
+
+    /// Reads a set of version keys, joined into one token.
+    ///
+    /// Missing keys get a new random version rather than a fixed default, so
+    /// a version lost to eviction or a flush never matches anything stored
+    /// under its old value. Returns `None` when Redis is unavailable.
+    #[instrument(skip(self), fields(cache.operation = "VERSIONS"))]
+    pub async fn versions(&self, keys: &[String]) -> Option<String> {
+        let mut conn = self.conn.clone();
+
+        let script = Script::new(VERSIONS_SCRIPT);
+        let mut invocation = script.prepare_invoke();
+        for key in keys {
+            invocation.key(key).arg(Uuid::new_v4().to_string());
+        }
+
+        let timer = CacheTimer::start("versions");
+        let result = self
+            .call(invocation.invoke_async::<Vec<String>>(&mut conn))
+            .await;
+        timer.finish(&result);
+
+        match result {
+            Ok(versions) => Some(versions.join(".")),
+            Err(CacheError::Degraded) => None,
+            Err(e) => {
+                error!(error = %e, "Redis version read error");
+                None
+            }
+        }
+    }
+
+    /// Replaces a version key with a new random version.
+    #[instrument(skip(self), fields(cache.operation = "SET"))]
+    pub async fn bump_version(&self, key: &str) -> Result<(), CacheError> {
+        let mut conn = self.conn.clone();
+
+        let timer = CacheTimer::start("bump_version");
+        let result = self
+            .call(conn.set::<_, _, ()>(key, Uuid::new_v4().to_string()))
+            .await;
+        timer.finish(&result);
+
+        match result {
+            Err(CacheError::Degraded) => return Ok(()),
+            result => result?,
+        }
+
+        debug!(cache.key = %key, "Version bumped");
+
+        Ok(())
+    }
+
     /// Invalidates (deletes) a cached key.
     #[instrument(skip(self), fields(cache.operation = "DEL"))]
     pub async fn invalidate(&self, key: &str) -> Result<(), CacheError> {
         let mut conn = self.conn.clone();
 
-        conn.del::<_, ()>(key).await?;
+        let timer = CacheTimer::start("delete");
+        let result = self.call(conn.del::<_, ()>(key)).await;
+        timer.finish(&result);
+
+        match result {
+            Err(CacheError::Degraded) => return Ok(()),
+            result => result?,
+        }
 
         debug!(cache.key = %key, "Cache invalidated");
 
@@ -135,29 +566,17 @@ impl RedisCache {
     #[instrument(skip(self), fields(cache.operation = "SCAN_DEL"))]
     pub async fn invalidate_pattern(&self, pattern: &str) -> Result<u64, CacheError> {
         let mut conn = self.conn.clone();
-        let mut cursor: u64 = 0;
-        let mut deleted: u64 = 0;
-
-        loop {
-            let (next_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
-                .arg(cursor)
-                .arg("MATCH")
-                .arg(pattern)
-                .arg("COUNT")
-                .arg(100)
-                .query_async(&mut conn)
-                .await?;
-
-            if !keys.is_empty() {
-                let count: u64 = conn.del(&keys).await?;
-                deleted += count;
-            }
 
-            cursor = next_cursor;
-            if cursor == 0 {
-                break;
-            }
-        }
+        let timer = CacheTimer::start("delete_pattern");
+        let result = self
+            .call_with_timeout(SCAN_TIMEOUT, delete_matching(&mut conn, pattern))
+            .await;
+        timer.finish(&result);
+
+        let deleted = match result {
+            Err(CacheError::Degraded) => return Ok(0),
+            result => result?,
+        };
 
         debug!(cache.pattern = %pattern, cache.deleted = %deleted, "Pattern invalidation complete");
 
@@ -169,8 +588,13 @@ impl RedisCache {
     pub async fn exists(&self, key: &str) -> bool {
         let mut conn = self.conn.clone();
 
-        match conn.exists::<_, bool>(key).await {
+        let timer = CacheTimer::start("exists");
+        let result = self.call(conn.exists::<_, bool>(key)).await;
+        timer.finish(&result);
+
+        match result {
             Ok(exists) => exists,
+            Err(CacheError::Degraded) => false,
             Err(e) => {
                 error!(cache.key = %key, error = %e, "Redis EXISTS error");
                 false
@@ -185,9 +609,14 @@ impl RedisCache {
     pub async fn ttl(&self, key: &str) -> Option<i64> {
         let mut conn = self.conn.clone();
 
-        match conn.ttl::<_, i64>(key).await {
+        let timer = CacheTimer::start("ttl");
+        let result = self.call(conn.ttl::<_, i64>(key)).await;
+        timer.finish(&result);
+
+        match result {
             Ok(ttl) if ttl > 0 => Some(ttl),
             Ok(_) => None, // -1 (no expiry) or -2 (doesn't exist)
+            Err(CacheError::Degraded) => None,
             Err(e) => {
                 error!(cache.key = %key, error = %e, "Redis TTL error");
                 None
@@ -228,4 +657,102 @@ mod tests {
 
         cache.invalidate("test:key").await.unwrap();
     }
+
+    #[test]
+    fn test_cached_entry_freshness() {
+        let fresh = CachedEntry {
+            value: 1,
+            fresh_until_ms: now_ms() + 60_000,
+        };
+        assert!(fresh.is_fresh());
+
+        let stale = CachedEntry {
+            value: 1,
+            fresh_until_ms: now_ms().saturating_sub(1),
+        };
+        assert!(!stale.is_fresh());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_get_or_compute_serves_stale_while_locked() {
+        let cache = RedisCache::new("redis://localhost:6379", Duration::from_secs(60))
+            .await
+            .unwrap();
+        let key = format!("test:get_or_compute:{}", Uuid::new_v4());
+        let ttl = Duration::from_secs(1);
+
+        let first: Result<i32, ()> = cache.get_or_compute(&key, ttl, || async { Ok(1) }).await;
+        assert_eq!(first, Ok(1));
+
+        // Fresh entries are not recomputed
+        let cached: Result<i32, ()> = cache.get_or_compute(&key, ttl, || async { Ok(2) }).await;
+        assert_eq!(cached, Ok(1));
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        // Another caller holds the lock, so the stale value is served
+        assert!(cache.try_lock(&format!("{key}:lock"), "other").await);
+        let stale: Result<i32, ()> = cache.get_or_compute(&key, ttl, || async { Ok(3) }).await;
+        assert_eq!(stale, Ok(1));
+
+        cache.release_lock(&format!("{key}:lock"), "other").await;
+        let recomputed: Result<i32, ()> = cache.get_or_compute(&key, ttl, || async { Ok(4) }).await;
+        assert_eq!(recomputed, Ok(4));
+
+        cache.invalidate(&key).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_refresh_skips_while_locked() {
+        let cache = RedisCache::new("redis://localhost:6379", Duration::from_secs(60))
+            .await
+            .unwrap();
+        let key = format!("test:refresh:{}", Uuid::new_v4());
+        let ttl = Duration::from_secs(60);
+
+        assert!(cache.peek::<i32>(&key).await.is_none());
+
+        let refreshed: Option<Result<i32, ()>> = cache.refresh(&key, ttl, || async { Ok(1) }).await;
+        assert_eq!(refreshed, Some(Ok(1)));
+
+        let entry = cache.peek::<i32>(&key).await.unwrap();
+        assert_eq!(entry.value, 1);
+        assert!(entry.is_fresh());
+
+        assert!(cache.try_lock(&format!("{key}:lock"), "other").await);
+        let skipped: Option<Result<i32, ()>> = cache.refresh(&key, ttl, || async { Ok(2) }).await;
+        assert_eq!(skipped, None);
+
+        cache.release_lock(&format!("{key}:lock"), "other").await;
+        cache.invalidate(&key).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_versions_change_when_bumped() {
+        let cache = RedisCache::new("redis://localhost:6379", Duration::from_secs(60))
+            .await
+            .unwrap();
+        let keys = [
+            format!("test:version:{}", Uuid::new_v4()),
+            format!("test:version:{}", Uuid::new_v4()),
+        ];
+
+        let first = cache.versions(&keys).await.unwrap();
+        assert_eq!(cache.versions(&keys).await.unwrap(), first);
+
+        cache.bump_version(&keys[1]).await.unwrap();
+        assert_ne!(cache.versions(&keys).await.unwrap(), first);
+
+        // A lost version comes back as a new one, not a default
+        let second = cache.versions(&keys).await.unwrap();
+        cache.invalidate(&keys[0]).await.unwrap();
+        assert_ne!(cache.versions(&keys).await.unwrap(), second);
+
+        for key in &keys {
+            cache.invalidate(key).await.unwrap();
+        }
+    }
 }
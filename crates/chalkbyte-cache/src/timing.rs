@@ -0,0 +1,52 @@
+//! Latency and hit/miss metrics for Redis calls.
+//!
+//! Each [`RedisCache`](crate::RedisCache) call starts a [`CacheTimer`] and
+//! finishes it with its result, which reports to
+//! `chalkbyte_observability::track_cache_operation`. Calls skipped while
+//! Redis is degraded are not recorded, since they never reach Redis.
+
+use std::time::Instant;
+
+use chalkbyte_observability::track_cache_operation;
+
+use crate::redis::CacheError;
+
+/// Times one cache call.
+pub(crate) struct CacheTimer {
+    operation: &'static str,
+    started: Instant,
+}
+
+impl CacheTimer {
+    pub(crate) fn start(operation: &'static str) -> Self {
+        Self {
+            operation,
+            started: Instant::now(),
+        }
+    }
+
+    /// Records a lookup as a hit or a miss.
+    pub(crate) fn lookup<T>(self, result: &Result<Option<T>, CacheError>) {
+        let outcome = match result {
+            Ok(Some(_)) => "hit",
+            Ok(None) => "miss",
+            Err(CacheError::Degraded) => return,
+            Err(_) => "error",
+        };
+        self.record(outcome);
+    }
+
+    /// Records any other call as succeeded or failed.
+    pub(crate) fn finish<T>(self, result: &Result<T, CacheError>) {
+        let outcome = match result {
+            Ok(_) => "ok",
+            Err(CacheError::Degraded) => return,
+            Err(_) => "error",
+        };
+        self.record(outcome);
+    }
+
+    fn record(self, outcome: &'static str) {
+        track_cache_operation(self.operation, outcome, self.started.elapsed());
+    }
+}
@@ -0,0 +1,512 @@
+//! Refresh token storage with rotation and per-device token families.
+//!
+//! Every login starts a *token family*: the chain of refresh tokens issued to
+//! one device. Only the most recent token in a family is valid. Presenting it
+//! rotates the family to a new token; presenting an older one means the chain
+//! has leaked, so the whole family is revoked.
+//!
+//! Stores only ever see token IDs (the `jti` claim), never the tokens themselves.
+//! Alongside each family they keep the client it was issued to and when it
+//! was last refreshed, so users can review their sessions and revoke one.
+//!
+//! The database is the source of truth for families. [`CachedTokenStore`]
+//! puts Redis in front of it as a cache of revocations only.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_cache::{CachedTokenStore, Rotation, TokenFamily, TokenStore};
+//!
+//! let store = CachedTokenStore::new(Arc::new(PgTokenStore::new(db)), &cache);
+//!
+//! store.start_family(&family, &jti, ttl).await?;
+//!
+//! match store.rotate(user_id, family_id, &presented_jti, &next_jti, &client, ttl).await? {
+//!     Rotation::Rotated => { /* hand out the next token */ }
+//!     Rotation::Reused | Rotation::Revoked => { /* reject */ }
+//! }
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use redis::Script;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::redis::RedisCache;
+
+/// Boxed future returned by [`TokenStore`] methods.
+pub type TokenStoreFuture<'a, T> =
+    Pin<Box<dyn Future<Output = Result<T, TokenStoreError>> + Send + 'a>>;
+
+/// Error raised by a token store backend.
+#[derive(Debug, thiserror::Error)]
+#[error("Token store error: {0}")]
+pub struct TokenStoreError(#[source] Box<dyn std::error::Error + Send + Sync>);
+
+impl TokenStoreError {
+    /// Wraps a backend-specific error.
+    pub fn new(error: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self(Box::new(error))
+    }
+}
+
+impl From<redis::RedisError> for TokenStoreError {
+    fn from(error: redis::RedisError) -> Self {
+        Self::new(error)
+    }
+}
+
+/// The client presenting a family's tokens, as seen on the request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionClient {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+/// The chain of refresh tokens issued to one device of one user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenFamily {
+    pub family_id: Uuid,
+    pub user_id: Uuid,
+    pub device_id: String,
+    pub client: SessionClient,
+}
+
+/// A live token family, as listed by [`TokenStore::list_families`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveFamily {
+    pub family_id: Uuid,
+    pub device_id: String,
+    /// The client that last refreshed the family, or signed in if it never has
+    pub client: SessionClient,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Result of presenting a refresh token for rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    /// The token was current and the family now points at the next token.
+    Rotated,
+    /// The token had already been rotated away; the family has been revoked.
+    Reused,
+    /// The family was logged out or has expired.
+    Revoked,
+}
+
+/// Abstract trait for refresh token storage backends.
+///
+/// Implementations can be swapped without changing business logic.
+pub trait TokenStore: Send + Sync {
+    /// Start a new family whose current token is `jti`.
+    ///
+    /// Any other live family for the same user and device is revoked, so a
+    /// device holds at most one valid refresh token at a time.
+    fn start_family<'a>(
+        &'a self,
+        family: &'a TokenFamily,
+        jti: &'a str,
+        ttl: Duration,
+    ) -> TokenStoreFuture<'a, ()>;
+
+    /// Atomically replace `presented_jti` with `next_jti` if it is the
+    /// family's current token, revoking the family if it is not.
+    ///
+    /// A successful rotation records `client` and the time as last seen.
+    fn rotate<'a>(
+        &'a self,
+        user_id: Uuid,
+        family_id: Uuid,
+        presented_jti: &'a str,
+        next_jti: &'a str,
+        client: &'a SessionClient,
+        ttl: Duration,
+    ) -> TokenStoreFuture<'a, Rotation>;
+
+    /// List a user's live families, most recently seen first.
+    fn list_families<'a>(&'a self, user_id: Uuid) -> TokenStoreFuture<'a, Vec<ActiveFamily>>;
+
+    /// Revoke a single family, logging out one device.
+    fn revoke_family<'a>(&'a self, user_id: Uuid, family_id: Uuid) -> TokenStoreFuture<'a, ()>;
+
+    /// Revoke every family belonging to a user, logging out all devices.
+    fn revoke_all<'a>(&'a self, user_id: Uuid) -> TokenStoreFuture<'a, ()>;
+}
+
+/// [`TokenStore`] caching revoked families in Redis in front of an
+/// authoritative store.
+///
+/// Every call goes to the wrapped store, which alone decides whether a token
+/// rotates, so flushing or losing Redis never logs anyone out or brings a
+/// revoked session back. Redis only remembers which families have been
+/// revoked, letting replayed and logged-out tokens be turned away without a
+/// database round trip. A cached marker can reject a token but never accept
+/// one.
+///
+/// Markers live under `auth:user:{<user_id>}:revoked:<family_id>` and expire
+/// with the family. The hash tag keeps a user's markers in one cluster slot.
+#[derive(Clone)]
+pub struct CachedTokenStore {
+    store: Arc<dyn TokenStore>,
+    cache: RedisCache,
+}
+
+impl std::fmt::Debug for CachedTokenStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedTokenStore").finish_non_exhaustive()
+    }
+}
+
+fn revoked_key(user_id: Uuid, family_id: Uuid) -> String {
+    format!("auth:user:{{{}}}:revoked:{}", user_id, family_id)
+}
+
+// KEYS: revoked markers; ARGV[i] the TTL of KEYS[i]
+const MARK_REVOKED_SCRIPT: &str = r#"
+for i, key in ipairs(KEYS) do
+    redis.call('SET', key, 1, 'EX', ARGV[i])
+end
+return 1
+"#;
+
+impl CachedTokenStore {
+    /// Wraps `store`, caching its revocations in `cache`.
+    pub fn new(store: Arc<dyn TokenStore>, cache: &RedisCache) -> Self {
+        Self {
+            store,
+            cache: cache.clone(),
+        }
+    }
+
+    /// Records revoked families, each until it would have expired.
+    ///
+    /// Failures are only logged: the wrapped store has already revoked them.
+    async fn mark_revoked(&self, user_id: Uuid, families: &[(Uuid, Duration)]) {
+        if families.is_empty() {
+            return;
+        }
+
+        let script = Script::new(MARK_REVOKED_SCRIPT);
+        let mut invocation = script.prepare_invoke();
+        for (family_id, ttl) in families {
+            invocation
+                .key(revoked_key(user_id, *family_id))
+                .arg(ttl.as_secs().max(1));
+        }
+
+        let mut conn = self.cache.connection();
+        if let Err(e) = self
+            .cache
+            .call(invocation.invoke_async::<()>(&mut conn))
+            .await
+        {
+            warn!(user.id = %user_id, error = %e, "Failed to cache token family revocation");
+        }
+    }
+
+    /// The user's live families with the time each has left.
+    async fn live_families(&self, user_id: Uuid) -> Result<Vec<(Uuid, Duration)>, TokenStoreError> {
+        let now = Utc::now();
+        Ok(self
+            .store
+            .list_families(user_id)
+            .await?
+            .into_iter()
+            .map(|family| {
+                let left = (family.expires_at - now).to_std().unwrap_or_default();
+                (family.family_id, left)
+            })
+            .collect())
+    }
+}
+
+impl TokenStore for CachedTokenStore {
+    fn start_family<'a>(
+        &'a self,
+        family: &'a TokenFamily,
+        jti: &'a str,
+        ttl: Duration,
+    ) -> TokenStoreFuture<'a, ()> {
+        // Families this replaces on the device are left unmarked; the wrapped
+        // store rejects their tokens
+        self.store.start_family(family, jti, ttl)
+    }
+
+    fn rotate<'a>(
+        &'a self,
+        user_id: Uuid,
+        family_id: Uuid,
+        presented_jti: &'a str,
+        next_jti: &'a str,
+        client: &'a SessionClient,
+        ttl: Duration,
+    ) -> TokenStoreFuture<'a, Rotation> {
+        Box::pin(async move {
+            if self.cache.exists(&revoked_key(user_id, family_id)).await {
+                debug!(auth.family_id = %family_id, "Revoked token family rejected from cache");
+                return Ok(Rotation::Revoked);
+            }
+
+            let rotation = self
+                .store
+                .rotate(user_id, family_id, presented_jti, next_jti, client, ttl)
+                .await?;
+
+            // Revoked and expired families never come back, so the outcome
+            // is safe to remember
+            if rotation != Rotation::Rotated {
+                self.mark_revoked(user_id, &[(family_id, ttl)]).await;
+            }
+            Ok(rotation)
+        })
+    }
+
+    fn list_families<'a>(&'a self, user_id: Uuid) -> TokenStoreFuture<'a, Vec<ActiveFamily>> {
+        self.store.list_families(user_id)
+    }
+
+    fn revoke_family<'a>(&'a self, user_id: Uuid, family_id: Uuid) -> TokenStoreFuture<'a, ()> {
+        Box::pin(async move {
+            let mut families = self.live_families(user_id).await?;
+            families.retain(|(id, _)| *id == family_id);
+
+            self.store.revoke_family(user_id, family_id).await?;
+            self.mark_revoked(user_id, &families).await;
+
+            debug!(auth.family_id = %family_id, "Token family revoked");
+            Ok(())
+        })
+    }
+
+    fn revoke_all<'a>(&'a self, user_id: Uuid) -> TokenStoreFuture<'a, ()> {
+        Box::pin(async move {
+            let families = self.live_families(user_id).await?;
+
+            self.store.revoke_all(user_id).await?;
+            self.mark_revoked(user_id, &families).await;
+
+            debug!(user.id = %user_id, "All token families revoked");
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Authoritative store kept in memory, counting rotations it sees.
+    #[derive(Default)]
+    struct MemoryStore {
+        families: Mutex<HashMap<Uuid, (TokenFamily, String, bool)>>,
+        rotations: AtomicUsize,
+    }
+
+    impl TokenStore for MemoryStore {
+        fn start_family<'a>(
+            &'a self,
+            family: &'a TokenFamily,
+            jti: &'a str,
+            _ttl: Duration,
+        ) -> TokenStoreFuture<'a, ()> {
+            let mut families = self.families.lock().unwrap();
+            families.insert(family.family_id, (family.clone(), jti.to_string(), false));
+            Box::pin(async { Ok(()) })
+        }
+
+        fn rotate<'a>(
+            &'a self,
+            user_id: Uuid,
+            family_id: Uuid,
+            presented_jti: &'a str,
+            next_jti: &'a str,
+            _client: &'a SessionClient,
+            _ttl: Duration,
+        ) -> TokenStoreFuture<'a, Rotation> {
+            self.rotations.fetch_add(1, Ordering::SeqCst);
+            let mut families = self.families.lock().unwrap();
+            let rotation = match families.get_mut(&family_id) {
+                Some((family, jti, revoked)) if family.user_id == user_id && !*revoked => {
+                    if jti == presented_jti {
+                        *jti = next_jti.to_string();
+                        Rotation::Rotated
+                    } else {
+                        *revoked = true;
+                        Rotation::Reused
+                    }
+                }
+                _ => Rotation::Revoked,
+            };
+            Box::pin(async move { Ok(rotation) })
+        }
+
+        fn list_families<'a>(&'a self, user_id: Uuid) -> TokenStoreFuture<'a, Vec<ActiveFamily>> {
+            let now = Utc::now();
+            let families = self
+                .families
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|(family, _, revoked)| family.user_id == user_id && !revoked)
+                .map(|(family, _, _)| ActiveFamily {
+                    family_id: family.family_id,
+                    device_id: family.device_id.clone(),
+                    client: family.client.clone(),
+                    created_at: now,
+                    last_seen_at: now,
+                    expires_at: now + chrono::Duration::seconds(60),
+                })
+                .collect();
+            Box::pin(async move { Ok(families) })
+        }
+
+        fn revoke_family<'a>(&'a self, user_id: Uuid, family_id: Uuid) -> TokenStoreFuture<'a, ()> {
+            let mut families = self.families.lock().unwrap();
+            if let Some((family, _, revoked)) = families.get_mut(&family_id)
+                && family.user_id == user_id
+            {
+                *revoked = true;
+            }
+            Box::pin(async { Ok(()) })
+        }
+
+        fn revoke_all<'a>(&'a self, user_id: Uuid) -> TokenStoreFuture<'a, ()> {
+            let mut families = self.families.lock().unwrap();
+            for (family, _, revoked) in families.values_mut() {
+                if family.user_id == user_id {
+                    *revoked = true;
+                }
+            }
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    // Integration tests require a running Redis instance
+
+    async fn cache() -> RedisCache {
+        RedisCache::new("redis://localhost:6379", Duration::from_secs(60))
+            .await
+            .unwrap()
+    }
+
+    fn family(user_id: Uuid, device_id: &str) -> TokenFamily {
+        TokenFamily {
+            family_id: Uuid::new_v4(),
+            user_id,
+            device_id: device_id.to_string(),
+            client: SessionClient {
+                user_agent: Some("test-agent".to_string()),
+                ip_address: Some("203.0.113.7".to_string()),
+            },
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_rotation_and_reuse_detection() {
+        let backing = Arc::new(MemoryStore::default());
+        let store = CachedTokenStore::new(backing.clone(), &cache().await);
+        let ttl = Duration::from_secs(60);
+        let family = family(Uuid::new_v4(), "phone");
+        let client = SessionClient::default();
+
+        store.start_family(&family, "a", ttl).await.unwrap();
+        assert_eq!(
+            store
+                .rotate(family.user_id, family.family_id, "a", "b", &client, ttl)
+                .await
+                .unwrap(),
+            Rotation::Rotated
+        );
+        assert_eq!(
+            store
+                .rotate(family.user_id, family.family_id, "a", "c", &client, ttl)
+                .await
+                .unwrap(),
+            Rotation::Reused
+        );
+
+        // The reuse is cached, so the current token is turned away by Redis
+        assert_eq!(
+            store
+                .rotate(family.user_id, family.family_id, "b", "c", &client, ttl)
+                .await
+                .unwrap(),
+            Rotation::Revoked
+        );
+        assert_eq!(backing.rotations.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_revoke_all_ends_every_device() {
+        let store = CachedTokenStore::new(Arc::new(MemoryStore::default()), &cache().await);
+        let ttl = Duration::from_secs(60);
+        let user_id = Uuid::new_v4();
+        let phone = family(user_id, "phone");
+        let laptop = family(user_id, "laptop");
+        let client = SessionClient::default();
+
+        store.start_family(&phone, "p", ttl).await.unwrap();
+        store.start_family(&laptop, "l", ttl).await.unwrap();
+        store.revoke_all(user_id).await.unwrap();
+
+        assert_eq!(
+            store
+                .rotate(user_id, phone.family_id, "p", "p2", &client, ttl)
+                .await
+                .unwrap(),
+            Rotation::Revoked
+        );
+        assert_eq!(
+            store
+                .rotate(user_id, laptop.family_id, "l", "l2", &client, ttl)
+                .await
+                .unwrap(),
+            Rotation::Revoked
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_losing_the_cache_keeps_revocations() {
+        let cache = cache().await;
+        let store = CachedTokenStore::new(Arc::new(MemoryStore::default()), &cache);
+        let ttl = Duration::from_secs(60);
+        let user_id = Uuid::new_v4();
+        let phone = family(user_id, "phone");
+        let laptop = family(user_id, "laptop");
+        let client = SessionClient::default();
+
+        store.start_family(&phone, "p", ttl).await.unwrap();
+        store.start_family(&laptop, "l", ttl).await.unwrap();
+        store
+            .revoke_family(user_id, laptop.family_id)
+            .await
+            .unwrap();
+        cache
+            .invalidate(&revoked_key(user_id, laptop.family_id))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store
+                .rotate(user_id, laptop.family_id, "l", "l2", &client, ttl)
+                .await
+                .unwrap(),
+            Rotation::Revoked
+        );
+        let families = store.list_families(user_id).await.unwrap();
+        assert_eq!(families.len(), 1);
+        assert_eq!(families[0].family_id, phone.family_id);
+    }
+}
@@ -1,5 +1,9 @@
-use chalkbyte_cli::seeder::{self, LevelsPerSchool, SeedConfig, UsersPerSchool};
-use chalkbyte_models::ids::{BranchId, LevelId, SchoolId};
+use std::path::PathBuf;
+
+use chalkbyte_cli::seeder::{self, BranchContext, LevelsPerSchool, SeedConfig, UsersPerSchool};
+use chalkbyte_config::ChalkbyteConfig;
+use chalkbyte_db::migrations::{self, MigrateOptions, MigrationState, MigrationStatus};
+use chalkbyte_models::ids::{LevelId, SchoolId};
 use clap::{Parser, Subcommand};
 use dialoguer::{Input, Password};
 use dotenvy::dotenv;
@@ -8,6 +12,11 @@ use dotenvy::dotenv;
 #[command(name = "chalkbyte-cli")]
 #[command(about = "Chalkbyte CLI - Administrative tools for Chalkbyte", long_about = None)]
 struct Cli {
+    /// Config file (TOML or YAML); defaults to chalkbyte.toml or config.yaml
+    /// if present. Environment variables override it.
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -92,6 +101,20 @@ enum Commands {
         #[arg(long, default_value = "25")]
         students: usize,
     },
+    /// Apply pending database migrations
+    Migrate {
+        /// Show what would be applied without changing the database
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Apply migrations up to and including this version
+        #[arg(long, value_name = "VERSION")]
+        target: Option<i64>,
+
+        /// Only show the state of each migration
+        #[arg(long, conflicts_with_all = ["dry_run", "target"])]
+        status: bool,
+    },
     /// Clear all seeded data (keeps system admins)
     ClearSeed,
     /// Clear only seeded users
@@ -104,16 +127,19 @@ enum Commands {
 async fn main() {
     dotenv().ok();
 
-    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let cli = Cli::parse();
+
+    let config = ChalkbyteConfig::load(cli.config.as_deref()).unwrap_or_else(|e| {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    });
 
     let pool = sqlx::postgres::PgPoolOptions::new()
         .max_connections(5)
-        .connect(&database_url)
+        .connect(&config.database.database_url)
         .await
         .expect("Failed to connect to database");
 
-    let cli = Cli::parse();
-
     match cli.command {
         Commands::CreateSysadmin {
             first_name,
@@ -136,6 +162,11 @@ async fn main() {
             handle_seed_staff(&pool, admins, teachers).await
         }
         Commands::SeedStudents { students } => handle_seed_students(&pool, students).await,
+        Commands::Migrate {
+            dry_run,
+            target,
+            status,
+        } => handle_migrate(&pool, dry_run, target, status).await,
         Commands::ClearSeed => handle_clear_seed(&pool).await,
         Commands::ClearUsers => handle_clear_users(&pool).await,
         Commands::ClearSchools => handle_clear_schools(&pool).await,
@@ -328,20 +359,14 @@ async fn handle_seed_students(pool: &sqlx::postgres::PgPool, students_per_branch
         std::process::exit(1);
     }
 
-    let branches_with_context: Vec<(BranchId, LevelId, SchoolId)> = rows
+    let branches: Vec<BranchContext> = rows
         .iter()
-        .map(|r| {
-            (
-                BranchId::from(r.branch_id),
-                LevelId::from(r.level_id),
-                SchoolId::from(r.school_id),
-            )
-        })
+        .map(|r| BranchContext::from_uuids(r.branch_id, r.level_id, r.school_id))
         .collect();
 
-    match seeder::seed_students_only(pool, &branches_with_context, students_per_branch).await {
+    match seeder::seed_students_only(pool, &branches, students_per_branch).await {
         Ok(_) => {
-            let total = branches_with_context.len() * students_per_branch;
+            let total = branches.len() * students_per_branch;
             println!("✅ Created {} students", total);
         }
         Err(e) => {
@@ -351,6 +376,63 @@ async fn handle_seed_students(pool: &sqlx::postgres::PgPool, students_per_branch
     }
 }
 
+async fn handle_migrate(
+    pool: &sqlx::postgres::PgPool,
+    dry_run: bool,
+    target: Option<i64>,
+    status_only: bool,
+) {
+    let statuses = match migrations::migration_status(pool).await {
+        Ok(statuses) => statuses,
+        Err(e) => {
+            eprintln!("\n❌ Error reading migration status: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let count = |state| statuses.iter().filter(|s| s.state == state).count();
+    println!(
+        "📋 Migrations: {} applied, {} pending",
+        count(MigrationState::Applied),
+        count(MigrationState::Pending)
+    );
+    for status in &statuses {
+        print_migration(status);
+    }
+
+    if status_only {
+        return;
+    }
+
+    match migrations::migrate(pool, MigrateOptions { target, dry_run }).await {
+        Ok(done) if done.is_empty() => println!("\n✅ Database is up to date"),
+        Ok(done) => {
+            if dry_run {
+                println!("\n🔍 Dry run: would apply {} migrations", done.len());
+            } else {
+                println!("\n✅ Applied {} migrations", done.len());
+            }
+            for status in &done {
+                print_migration(status);
+            }
+        }
+        Err(e) => {
+            eprintln!("\n❌ Error running migrations: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn print_migration(status: &MigrationStatus) {
+    let marker = match status.state {
+        MigrationState::Applied => "✅",
+        MigrationState::Pending => "⏳",
+        MigrationState::Modified => "⚠️  modified",
+        MigrationState::Missing => "❌ missing",
+    };
+    println!("   {} {} {}", marker, status.version, status.description);
+}
+
 async fn handle_clear_seed(pool: &sqlx::postgres::PgPool) {
     match seeder::clear_all(pool).await {
         Ok(_) => {}
@@ -3,6 +3,7 @@
 //! Provides functions for generating and inserting fake branch data
 //! into the database.
 
+use chalkbyte_models::levels::{DEFAULT_BRANCH_NAME_TEMPLATE, render_name_template};
 use chalkbyte_models::{BranchId, LevelId, SchoolId};
 use rayon::prelude::*;
 use sqlx::{PgPool, Postgres, Transaction};
@@ -10,17 +11,20 @@ use std::time::Instant;
 
 use super::models::BranchSeed;
 
-const BRANCH_NAMES: [&str; 10] = ["A", "B", "C", "D", "E", "F", "G", "H", "I", "J"];
+/// Branches named by letter before falling back to "Section {n}".
+const LETTERED_BRANCHES: usize = 26;
 
-/// Generates branch data for levels
+/// Generates branch data for levels, named by the default branch name template
 pub fn generate_branches(level_ids: &[LevelId], branches_per_level: usize) -> Vec<BranchSeed> {
     level_ids
         .par_iter()
         .flat_map(|&level_id| {
             (0..branches_per_level)
                 .map(|i| {
-                    let name = if i < BRANCH_NAMES.len() {
-                        BRANCH_NAMES[i].to_string()
+                    // The default template only uses the branch letter, so the
+                    // level number is irrelevant here
+                    let name = if i < LETTERED_BRANCHES {
+                        render_name_template(DEFAULT_BRANCH_NAME_TEMPLATE, 1, Some(i as u32))
                     } else {
                         format!("Section {}", i + 1)
                     };
@@ -3,6 +3,7 @@
 //! Provides functions for generating and inserting fake level data
 //! into the database.
 
+use chalkbyte_models::levels::{DEFAULT_LEVEL_NAME_TEMPLATE, render_name_template};
 use chalkbyte_models::{LevelId, SchoolId};
 use rayon::prelude::*;
 use sqlx::{PgPool, Postgres, Transaction};
@@ -10,29 +11,16 @@ use std::time::Instant;
 
 use super::models::LevelSeed;
 
-const LEVEL_NAMES: [&str; 12] = [
-    "Grade 1", "Grade 2", "Grade 3", "Grade 4", "Grade 5", "Grade 6", "Grade 7", "Grade 8",
-    "Grade 9", "Grade 10", "Grade 11", "Grade 12",
-];
-
-/// Generates level data for schools
+/// Generates level data for schools, named by the default level name template
 pub fn generate_levels(school_ids: &[SchoolId], levels_per_school: usize) -> Vec<LevelSeed> {
     school_ids
         .par_iter()
         .flat_map(|&school_id| {
             (0..levels_per_school)
-                .map(|i| {
-                    let name = if i < LEVEL_NAMES.len() {
-                        LEVEL_NAMES[i].to_string()
-                    } else {
-                        format!("Grade {}", i + 1)
-                    };
-
-                    LevelSeed {
-                        name,
-                        description: None,
-                        school_id,
-                    }
+                .map(|i| LevelSeed {
+                    name: render_name_template(DEFAULT_LEVEL_NAME_TEMPLATE, i as u32 + 1, None),
+                    description: None,
+                    school_id,
                 })
                 .collect::<Vec<_>>()
         })
@@ -9,6 +9,7 @@
 //! - [`levels`] - Level/grade generation and insertion
 //! - [`branches`] - Branch/section generation and insertion
 //! - [`users`] - User generation (staff and students) with role assignment
+//! - [`truncate`] - Fast clearing with `TRUNCATE ... CASCADE`
 //! - [`models`] - Data structures for seeding configuration
 //!
 //! # Usage
@@ -31,6 +32,10 @@
 //!
 //! // Then branches
 //! let branch_ids = seed_branches_only(&db, &level_ids, 3).await?;
+//!
+//! // Then students, placed using each branch's level and school
+//! let branches = build_branch_context(&school_ids, &level_ids, &branch_ids, 6, 3)?;
+//! seed_students_only(&db, &branches, 25).await?;
 //! ```
 //!
 //! # Performance
@@ -39,14 +44,17 @@
 //! - Batch inserts with multi-value INSERT statements
 //! - Single bcrypt hash reused for all users (cost 4 for speed)
 //! - Pre-allocated vectors to avoid reallocation overhead
+//! - [`clear_all`] truncates instead of deleting when only seed data and
+//!   system admins would be lost
 
 pub mod branches;
 pub mod levels;
 pub mod models;
 pub mod schools;
+pub mod truncate;
 pub mod users;
 
-pub use models::{LevelsPerSchool, SeedConfig, UsersPerSchool};
+pub use models::{BranchContext, LevelsPerSchool, SeedConfig, UsersPerSchool};
 
 use bcrypt::hash;
 use chalkbyte_models::{BranchId, LevelId, SchoolId};
@@ -91,7 +99,7 @@ pub async fn seed_all(db: &PgPool, config: SeedConfig) -> Result<(), Box<dyn std
         &branch_ids,
         config.levels_per_school.count,
         config.levels_per_school.branches_per_level,
-    );
+    )?;
 
     // Step 5: Seed staff users (admins and teachers)
     let staff_roles = users::seed_staff_users(
@@ -180,26 +188,33 @@ pub async fn seed_staff_only(
 /// Seeds students for existing branches
 pub async fn seed_students_only(
     db: &PgPool,
-    branches_with_context: &[(BranchId, LevelId, SchoolId)],
+    branches: &[BranchContext],
     students_per_branch: usize,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let password_hash = hash_password()?;
-    let user_roles = users::seed_students(
-        db,
-        branches_with_context,
-        students_per_branch,
-        &password_hash,
-    )
-    .await?;
+    let user_roles =
+        users::seed_students(db, branches, students_per_branch, &password_hash).await?;
     users::assign_roles_batch(db, &user_roles).await?;
     Ok(())
 }
 
 /// Clears all seeded data from the database
+///
+/// Truncates when that is safe (see [`truncate`]) and deletes table by table
+/// otherwise.
 pub async fn clear_all(db: &PgPool) -> Result<(), Box<dyn std::error::Error>> {
     let start_time = Instant::now();
     println!("🗑️  Clearing all seeded data...");
 
+    match truncate::truncate_all(db).await {
+        Ok(Some(_)) => {
+            println!("✅ All seeded data cleared in {:?}", start_time.elapsed());
+            return Ok(());
+        }
+        Ok(None) => println!("   Deleting instead..."),
+        Err(e) => println!("   ⚠ Truncate failed ({}), deleting instead...", e),
+    }
+
     // Order matters due to foreign keys: users -> branches -> levels -> schools
     users::clear_users(db).await?;
     branches::clear_branches(db).await?;
@@ -245,14 +260,40 @@ fn hash_password() -> Result<String, Box<dyn std::error::Error>> {
     Ok(hash)
 }
 
-/// Builds (branch_id, level_id, school_id) tuples for student assignment
-fn build_branch_context(
+/// Pairs each branch with its level and school for student assignment
+///
+/// Expects the IDs in the order [`seed_levels_only`] and
+/// [`seed_branches_only`] return them: `levels_per_school` levels per school,
+/// then `branches_per_level` branches per level. Fails when the ID counts do
+/// not match that layout.
+pub fn build_branch_context(
     school_ids: &[SchoolId],
     level_ids: &[LevelId],
     branch_ids: &[BranchId],
     levels_per_school: usize,
     branches_per_level: usize,
-) -> Vec<(BranchId, LevelId, SchoolId)> {
+) -> Result<Vec<BranchContext>, Box<dyn std::error::Error>> {
+    if level_ids.len() != school_ids.len() * levels_per_school {
+        return Err(format!(
+            "Expected {} levels for {} schools with {} levels each, got {}",
+            school_ids.len() * levels_per_school,
+            school_ids.len(),
+            levels_per_school,
+            level_ids.len()
+        )
+        .into());
+    }
+    if branch_ids.len() != level_ids.len() * branches_per_level {
+        return Err(format!(
+            "Expected {} branches for {} levels with {} branches each, got {}",
+            level_ids.len() * branches_per_level,
+            level_ids.len(),
+            branches_per_level,
+            branch_ids.len()
+        )
+        .into());
+    }
+
     let mut result = Vec::with_capacity(branch_ids.len());
 
     for (school_idx, &school_id) in school_ids.iter().enumerate() {
@@ -264,10 +305,56 @@ fn build_branch_context(
             let branch_end = branch_start + branches_per_level;
 
             for &branch_id in &branch_ids[branch_start..branch_end] {
-                result.push((branch_id, level_id, school_id));
+                result.push(BranchContext::new(branch_id, level_id, school_id));
             }
         }
     }
 
-    result
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids<T: From<uuid::Uuid>>(count: usize) -> Vec<T> {
+        (0..count).map(|_| T::from(uuid::Uuid::new_v4())).collect()
+    }
+
+    #[test]
+    fn test_build_branch_context_pairs_branches_with_their_level() {
+        let school_ids: Vec<SchoolId> = ids(2);
+        let level_ids: Vec<LevelId> = ids(4);
+        let branch_ids: Vec<BranchId> = ids(12);
+
+        let branches = build_branch_context(&school_ids, &level_ids, &branch_ids, 2, 3).unwrap();
+
+        assert_eq!(branches.len(), 12);
+        assert_eq!(
+            branches[0],
+            BranchContext::new(branch_ids[0], level_ids[0], school_ids[0])
+        );
+        assert_eq!(
+            branches[11],
+            BranchContext::new(branch_ids[11], level_ids[3], school_ids[1])
+        );
+    }
+
+    #[test]
+    fn test_build_branch_context_rejects_mismatched_levels() {
+        let school_ids: Vec<SchoolId> = ids(2);
+        let level_ids: Vec<LevelId> = ids(3);
+        let branch_ids: Vec<BranchId> = ids(9);
+
+        assert!(build_branch_context(&school_ids, &level_ids, &branch_ids, 2, 3).is_err());
+    }
+
+    #[test]
+    fn test_build_branch_context_rejects_mismatched_branches() {
+        let school_ids: Vec<SchoolId> = ids(2);
+        let level_ids: Vec<LevelId> = ids(4);
+        let branch_ids: Vec<BranchId> = ids(11);
+
+        assert!(build_branch_context(&school_ids, &level_ids, &branch_ids, 2, 3).is_err());
+    }
 }
@@ -4,6 +4,7 @@
 //! test data is generated during seeding operations.
 
 use chalkbyte_models::{BranchId, LevelId, RoleId, SchoolId};
+use uuid::Uuid;
 
 /// Seed data for creating a school.
 pub struct SchoolSeed {
@@ -25,6 +26,32 @@ pub struct BranchSeed {
     pub level_id: LevelId,
 }
 
+/// A branch with the level and school it belongs to, for seeding students.
+///
+/// Named fields rather than a tuple, so the three IDs can't be passed in the
+/// wrong order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BranchContext {
+    pub branch_id: BranchId,
+    pub level_id: LevelId,
+    pub school_id: SchoolId,
+}
+
+impl BranchContext {
+    pub fn new(branch_id: BranchId, level_id: LevelId, school_id: SchoolId) -> Self {
+        Self {
+            branch_id,
+            level_id,
+            school_id,
+        }
+    }
+
+    /// Wraps raw IDs, e.g. from a query, in their newtypes.
+    pub fn from_uuids(branch_id: Uuid, level_id: Uuid, school_id: Uuid) -> Self {
+        Self::new(branch_id.into(), level_id.into(), school_id.into())
+    }
+}
+
 /// Seed data for creating a user.
 pub struct UserSeed {
     pub first_name: String,
@@ -88,6 +115,23 @@ impl SeedConfig {
         }
     }
 
+    /// The demo profile: a small but complete dataset for local development.
+    ///
+    /// Two schools, each with one admin, three teachers, and three levels of
+    /// two branches holding ten students each.
+    pub fn demo() -> Self {
+        Self::new(2)
+            .with_users(UsersPerSchool {
+                admins: 1,
+                teachers: 3,
+            })
+            .with_levels(LevelsPerSchool {
+                count: 3,
+                branches_per_level: 2,
+                students_per_branch: 10,
+            })
+    }
+
     /// Sets the users per school configuration.
     pub fn with_users(mut self, users: UsersPerSchool) -> Self {
         self.users_per_school = users;
@@ -0,0 +1,325 @@
+//! Bulk clearing with `TRUNCATE ... CASCADE`.
+//!
+//! Deleting a large seed row by row fires every `ON DELETE` action and
+//! takes minutes; truncating takes seconds. `TRUNCATE schools, users, roles
+//! CASCADE` also empties every table referencing them, including the system
+//! roles and system admins, so those are copied to temporary tables first
+//! and put back in the same transaction.
+//!
+//! Truncating is only safe when nothing but seed data and system admins
+//! would be lost. When other accounts exist, a table the truncate would
+//! reach holds anything besides seed data and sign-in state, or the database
+//! user may not truncate, [`truncate_all`] leaves the database unchanged and
+//! callers fall back to deleting.
+
+use chalkbyte_models::users::system_roles;
+use sqlx::{PgConnection, PgPool};
+use std::time::Instant;
+
+/// Seeded users are created with `@example.com` addresses.
+const SEED_EMAIL_PATTERN: &str = "%@example.com";
+
+/// Tables the seeder fills, plus the system roles and system admins that are
+/// put back.
+const SEEDED_TABLES: &[&str] = &[
+    "schools",
+    "levels",
+    "branches",
+    "users",
+    "roles",
+    "role_permissions",
+    "user_roles",
+];
+
+/// Sign-in state system admins get back by signing in again.
+const SIGN_IN_TABLES: &[&str] = &[
+    "refresh_token_families",
+    "mfa_login_challenges",
+    "webauthn_ceremonies",
+    "oidc_login_states",
+    "password_reset_tokens",
+];
+
+/// Truncates all schools, users, and roles, keeping system admins and the
+/// roles without a school.
+///
+/// Returns the number of system admins kept, or `None` without changing
+/// anything if accounts other than seeded users and system admins exist, or
+/// the truncate would empty a table outside [`SEEDED_TABLES`] and
+/// [`SIGN_IN_TABLES`] that has rows. System admins keep their accounts and
+/// roles but are signed out.
+pub async fn truncate_all(db: &PgPool) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+    let start_time = Instant::now();
+    let system_admin = system_roles::SYSTEM_ADMIN.into_inner();
+
+    let mut tx = db.begin().await?;
+
+    // Don't wait behind a running server's locks; deleting still works then
+    sqlx::query("SET LOCAL lock_timeout = '5s'")
+        .execute(&mut *tx)
+        .await?;
+
+    let other_accounts: i64 = sqlx::query_scalar(
+        r#"SELECT COUNT(*) FROM users u
+        WHERE u.email NOT LIKE $1
+        AND NOT EXISTS (
+            SELECT 1 FROM user_roles ur
+            WHERE ur.user_id = u.id
+            AND ur.role_id = $2
+        )"#,
+    )
+    .bind(SEED_EMAIL_PATTERN)
+    .bind(system_admin)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if other_accounts > 0 {
+        println!(
+            "   ⚠ {} accounts are not seed data or system admins, not truncating",
+            other_accounts
+        );
+        return Ok(None);
+    }
+
+    if let Some(table) = first_table_with_other_data(&mut tx).await? {
+        println!(
+            "   ⚠ {} has rows that are not seed data, not truncating",
+            table
+        );
+        return Ok(None);
+    }
+
+    // Utility statements take no bind parameters; the role ID is a constant
+    for statement in [
+        "CREATE TEMP TABLE keep_roles ON COMMIT DROP AS \
+         SELECT * FROM roles WHERE school_id IS NULL"
+            .to_string(),
+        "CREATE TEMP TABLE keep_role_permissions ON COMMIT DROP AS \
+         SELECT rp.* FROM role_permissions rp JOIN keep_roles r ON r.id = rp.role_id"
+            .to_string(),
+        format!(
+            "CREATE TEMP TABLE keep_users ON COMMIT DROP AS \
+             SELECT u.* FROM users u WHERE EXISTS ( \
+                 SELECT 1 FROM user_roles ur \
+                 WHERE ur.user_id = u.id AND ur.role_id = '{system_admin}')"
+        ),
+        "CREATE TEMP TABLE keep_user_roles ON COMMIT DROP AS \
+         SELECT ur.* FROM user_roles ur \
+         JOIN keep_users u ON u.id = ur.user_id \
+         JOIN keep_roles r ON r.id = ur.role_id"
+            .to_string(),
+        // The rows they pointed at are about to go
+        "UPDATE keep_users SET school_id = NULL, level_id = NULL, branch_id = NULL, group_id = NULL"
+            .to_string(),
+        "UPDATE keep_user_roles SET assigned_by = NULL \
+         WHERE assigned_by NOT IN (SELECT id FROM keep_users)"
+            .to_string(),
+        "TRUNCATE schools, users, roles CASCADE".to_string(),
+    ] {
+        sqlx::query(&statement).execute(&mut *tx).await?;
+    }
+
+    for table in ["roles", "role_permissions", "users", "user_roles"] {
+        restore(&mut tx, table).await?;
+    }
+
+    let kept: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    println!(
+        "   ✓ Truncated seeded data, keeping {} system admins, in {:?}",
+        kept,
+        start_time.elapsed()
+    );
+
+    Ok(Some(kept as u64))
+}
+
+/// Finds a table `TRUNCATE schools, users, roles CASCADE` would empty that
+/// has rows and is neither seeded nor sign-in state.
+///
+/// The tables are looked up from the foreign keys, so ones added by later
+/// migrations are covered too.
+async fn first_table_with_other_data(
+    conn: &mut PgConnection,
+) -> Result<Option<String>, sqlx::Error> {
+    let reached: Vec<String> = sqlx::query_scalar(
+        r#"WITH RECURSIVE reached(rel) AS (
+            SELECT unnest(ARRAY['schools'::regclass, 'users'::regclass, 'roles'::regclass])
+            UNION
+            SELECT c.conrelid::regclass FROM pg_constraint c
+            JOIN reached r ON c.confrelid = r.rel
+            WHERE c.contype = 'f'
+        )
+        SELECT rel::text FROM reached ORDER BY 1"#,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for table in reached {
+        if SEEDED_TABLES.contains(&table.as_str()) || SIGN_IN_TABLES.contains(&table.as_str()) {
+            continue;
+        }
+        // regclass text is already quoted where needed
+        let has_rows: bool = sqlx::query_scalar(&format!("SELECT EXISTS (SELECT 1 FROM {table})"))
+            .fetch_one(&mut *conn)
+            .await?;
+        if has_rows {
+            return Ok(Some(table));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Copies `keep_<table>` back into `table`, naming the columns so the copy
+/// does not depend on their order.
+async fn restore(conn: &mut PgConnection, table: &str) -> Result<(), sqlx::Error> {
+    let columns: String = sqlx::query_scalar(
+        r#"SELECT string_agg(quote_ident(column_name), ', ' ORDER BY ordinal_position)
+        FROM information_schema.columns
+        WHERE table_schema = current_schema()
+        AND table_name = $1
+        AND is_generated = 'NEVER'"#,
+    )
+    .bind(table)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query(&format!(
+        "INSERT INTO {table} ({columns}) SELECT {columns} FROM keep_{table}"
+    ))
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    async fn create_user(db: &PgPool, email: &str, role: Uuid, school_id: Option<Uuid>) -> Uuid {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (first_name, last_name, email, password, school_id) \
+             VALUES ('Test', 'User', $1, 'hash', $2) RETURNING id",
+        )
+        .bind(email)
+        .bind(school_id)
+        .fetch_one(db)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(role)
+            .execute(db)
+            .await
+            .unwrap();
+        user_id
+    }
+
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn test_truncate_keeps_system_admins_and_system_roles(db: PgPool) {
+        let school_id: Uuid =
+            sqlx::query_scalar("INSERT INTO schools (name) VALUES ('Seed School') RETURNING id")
+                .fetch_one(&db)
+                .await
+                .unwrap();
+        let admin_id = create_user(
+            &db,
+            "root@chalkbyte.test",
+            system_roles::SYSTEM_ADMIN.into_inner(),
+            None,
+        )
+        .await;
+        create_user(
+            &db,
+            "teacher@example.com",
+            system_roles::TEACHER.into_inner(),
+            Some(school_id),
+        )
+        .await;
+        let permissions_before: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM role_permissions rp \
+             JOIN roles r ON r.id = rp.role_id WHERE r.school_id IS NULL",
+        )
+        .fetch_one(&db)
+        .await
+        .unwrap();
+
+        assert_eq!(truncate_all(&db).await.unwrap(), Some(1));
+
+        let users: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM users")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(users, vec![admin_id]);
+
+        let admin_roles: Vec<Uuid> =
+            sqlx::query_scalar("SELECT role_id FROM user_roles WHERE user_id = $1")
+                .bind(admin_id)
+                .fetch_all(&db)
+                .await
+                .unwrap();
+        assert_eq!(admin_roles, vec![system_roles::SYSTEM_ADMIN.into_inner()]);
+
+        for role in system_roles::all() {
+            let exists: bool =
+                sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM roles WHERE id = $1)")
+                    .bind(role.into_inner())
+                    .fetch_one(&db)
+                    .await
+                    .unwrap();
+            assert!(exists, "system role {role} was kept");
+        }
+        let permissions_after: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM role_permissions rp \
+             JOIN roles r ON r.id = rp.role_id WHERE r.school_id IS NULL",
+        )
+        .fetch_one(&db)
+        .await
+        .unwrap();
+        assert_eq!(permissions_after, permissions_before);
+
+        let schools: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM schools")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(schools, 0);
+    }
+
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn test_truncate_refuses_when_other_tables_have_data(db: PgPool) {
+        create_user(
+            &db,
+            "teacher@example.com",
+            system_roles::TEACHER.into_inner(),
+            None,
+        )
+        .await;
+        sqlx::query(
+            "INSERT INTO email_outbox (to_email, from_name, subject, text_body, html_body) \
+             VALUES ('someone@chalkbyte.test', 'Chalkbyte', 'Hello', 'Hi', '<p>Hi</p>')",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+
+        assert_eq!(truncate_all(&db).await.unwrap(), None);
+
+        let queued: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM email_outbox")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(queued, 1);
+        let users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(users, 1);
+    }
+}
@@ -11,7 +11,7 @@ use rayon::prelude::*;
 use sqlx::{PgPool, Postgres, Transaction};
 use std::time::Instant;
 
-use super::models::UserSeed;
+use super::models::{BranchContext, UserSeed};
 
 /// Generates admin and teacher users for schools
 pub fn generate_staff_users(
@@ -61,21 +61,21 @@ pub fn generate_staff_users(
 
 /// Generates student users assigned to branches and levels
 pub fn generate_students(
-    branches_with_levels: &[(BranchId, LevelId, SchoolId)], // (branch_id, level_id, school_id)
+    branches: &[BranchContext],
     students_per_branch: usize,
     password_hash: &str,
 ) -> Vec<UserSeed> {
-    branches_with_levels
+    branches
         .par_iter()
         .enumerate()
-        .flat_map(|(branch_idx, &(branch_id, level_id, school_id))| {
+        .flat_map(|(branch_idx, branch)| {
             (0..students_per_branch)
                 .map(|student_idx| {
                     generate_user(
                         system_roles::STUDENT,
-                        Some(school_id),
-                        Some(level_id),
-                        Some(branch_id),
+                        Some(branch.school_id),
+                        Some(branch.level_id),
+                        Some(branch.branch_id),
                         branch_idx,
                         student_idx,
                         "student",
@@ -156,18 +156,18 @@ pub async fn seed_staff_users(
 /// Seeds student users into the database
 pub async fn seed_students(
     db: &PgPool,
-    branches_with_levels: &[(BranchId, LevelId, SchoolId)], // (branch_id, level_id, school_id)
+    branches: &[BranchContext],
     students_per_branch: usize,
     password_hash: &str,
 ) -> Result<Vec<(UserId, RoleId)>, Box<dyn std::error::Error>> {
     let start_time = Instant::now();
-    let total_students = branches_with_levels.len() * students_per_branch;
+    let total_students = branches.len() * students_per_branch;
     println!(
         "🎓 Seeding {} students ({} per branch)...",
         total_students, students_per_branch
     );
 
-    let users = generate_students(branches_with_levels, students_per_branch, password_hash);
+    let users = generate_students(branches, students_per_branch, password_hash);
     let user_roles = insert_users_batch(db, &users).await?;
 
     println!(
@@ -0,0 +1,183 @@
+//! The complete application configuration.
+//!
+//! [`ChalkbyteConfig`] gathers every `*Config` the API and CLI need and loads
+//! them together from a [`ConfigSource`], so a config file and environment
+//! overrides apply to all of them and every problem is reported at once.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_config::ChalkbyteConfig;
+//!
+//! // Reads chalkbyte.toml or config.yaml if present, then the environment
+//! let config = ChalkbyteConfig::load(None).unwrap_or_else(|e| {
+//!     eprintln!("❌ {e}");
+//!     std::process::exit(1);
+//! });
+//! println!("Listening on port {}", config.server.port);
+//! ```
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use chalkbyte_cache::CacheConfig;
+use chalkbyte_core::PasswordPolicy;
+use chalkbyte_db::DbConfig;
+use chalkbyte_observability::ObservabilityConfig;
+
+use crate::source::{ConfigError, ConfigSource};
+use crate::{
+    CorsConfig, DocsConfig, EmailConfig, JwtConfig, OidcConfig, RateLimitConfig, StorageConfig,
+    WebauthnConfig,
+};
+
+/// Ports the servers listen on.
+///
+/// # Environment Variables
+///
+/// - `PORT`: API port (default: `3000`)
+/// - `METRICS_PORT`: Prometheus metrics port, used with the `observability`
+///   feature (default: `3001`)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServerConfig {
+    pub port: u16,
+    pub metrics_port: u16,
+}
+
+impl ServerConfig {
+    /// Load configuration from environment variables.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Load configuration from the variables `lookup` returns.
+    #[must_use]
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let port = |name: &str, default| {
+            lookup(name)
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default)
+        };
+
+        Self {
+            port: port("PORT", 3000),
+            metrics_port: port("METRICS_PORT", 3001),
+        }
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            port: 3000,
+            metrics_port: 3001,
+        }
+    }
+}
+
+/// Every setting the API and CLI read at startup.
+#[derive(Clone, Debug)]
+pub struct ChalkbyteConfig {
+    /// The config file the settings came from, if any.
+    pub file: Option<PathBuf>,
+    pub server: ServerConfig,
+    pub database: DbConfig,
+    pub cache: CacheConfig,
+    pub jwt: JwtConfig,
+    pub email: EmailConfig,
+    pub cors: CorsConfig,
+    pub rate_limit: RateLimitConfig,
+    pub password_policy: PasswordPolicy,
+    pub webauthn: WebauthnConfig,
+    pub oidc: OidcConfig,
+    pub docs: DocsConfig,
+    pub storage: StorageConfig,
+    pub observability: ObservabilityConfig,
+}
+
+impl ChalkbyteConfig {
+    /// Loads and validates the configuration.
+    ///
+    /// `path` is the `--config` argument; without it the file is discovered
+    /// as described in [`crate::source`]. Environment variables override the
+    /// file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError`] if the file cannot be read or parsed, or
+    /// [`ConfigError::Invalid`] listing every missing or invalid key.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        Self::validated(&ConfigSource::load(path)?)
+    }
+
+    /// Validates `source` and builds the configuration from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] listing every missing or invalid key.
+    pub fn validated(source: &ConfigSource) -> Result<Self, ConfigError> {
+        source.validate()?;
+        Ok(Self::from_source(source))
+    }
+
+    /// Builds the configuration from environment variables alone, without
+    /// validation, falling back to defaults like each `from_env`.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_source(&ConfigSource::env())
+    }
+
+    /// Builds the configuration from `source` without validating it.
+    #[must_use]
+    pub fn from_source(source: &ConfigSource) -> Self {
+        let lookup = |name: &str| source.get(name);
+
+        Self {
+            file: source.path().map(Path::to_path_buf),
+            server: ServerConfig::from_lookup(lookup),
+            database: DbConfig::from_lookup(lookup),
+            cache: CacheConfig::from_lookup(lookup),
+            jwt: JwtConfig::from_lookup(lookup),
+            email: EmailConfig::from_lookup(lookup),
+            cors: CorsConfig::from_lookup(lookup),
+            rate_limit: RateLimitConfig::from_lookup(lookup),
+            password_policy: PasswordPolicy::from_lookup(lookup),
+            webauthn: WebauthnConfig::from_lookup(lookup),
+            oidc: OidcConfig::from_lookup(lookup),
+            docs: DocsConfig::from_lookup(lookup),
+            storage: StorageConfig::from_lookup(lookup),
+            observability: ObservabilityConfig::from_lookup(lookup),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_source_reads_file_values() {
+        let source = ConfigSource::parse(
+            Path::new("chalkbyte.toml"),
+            r#"
+            port = 8080
+            database_url = "postgres://localhost/chalkbyte"
+
+            [db]
+            max_connections = 20
+
+            [password]
+            require_digit = true
+            "#,
+        )
+        .unwrap()
+        .with_env(|_| None);
+        let config = ChalkbyteConfig::from_source(&source);
+
+        assert_eq!(config.file.as_deref(), Some(Path::new("chalkbyte.toml")));
+        assert_eq!(config.database.max_connections, 20);
+        assert!(config.password_policy.require_digit);
+        assert_eq!(config.server.port, 8080);
+    }
+}
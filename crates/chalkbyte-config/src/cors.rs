@@ -7,8 +7,12 @@ pub struct CorsConfig {
 
 impl CorsConfig {
     pub fn from_env() -> Self {
-        let allowed_origins = env::var("ALLOWED_ORIGINS")
-            .unwrap_or_else(|_| "http://localhost:3000,http://localhost:5173".to_string())
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let allowed_origins = lookup("ALLOWED_ORIGINS")
+            .unwrap_or_else(|| "http://localhost:3000,http://localhost:5173".to_string())
             .split(',')
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty())
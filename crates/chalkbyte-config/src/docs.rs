@@ -0,0 +1,124 @@
+//! API documentation access configuration.
+//!
+//! Controls who can read the OpenAPI spec and the Scalar UI. A reduced
+//! public spec without admin-only endpoints is served whenever docs are
+//! enabled; this setting decides who gets the full one.
+//!
+//! # Environment Variables
+//!
+//! - `API_DOCS_ACCESS`: One of `public`, `authenticated`, or `disabled`
+//!   (default: `authenticated` when `ENVIRONMENT=production`, otherwise `public`)
+//!
+//! # Access Levels
+//!
+//! | Level           | Public spec | Full spec          | Scalar UI    |
+//! |-----------------|-------------|--------------------|--------------|
+//! | `public`        | Anyone      | Anyone             | Full spec    |
+//! | `authenticated` | Anyone      | Admin bearer token | Public spec  |
+//! | `disabled`      | Not served  | Not served         | Not served   |
+//!
+//! # Example
+//!
+//! ```ignore
+//! use crate::config::docs::{DocsAccess, DocsConfig};
+//!
+//! let config = DocsConfig::from_env();
+//! if config.access == DocsAccess::Disabled {
+//!     // skip the docs routes
+//! }
+//! ```
+
+use std::env;
+use std::str::FromStr;
+
+/// Who may read the API documentation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocsAccess {
+    /// The full spec and UI are open to anyone.
+    Public,
+    /// The full spec needs an admin's bearer token; the UI shows the
+    /// public spec.
+    Authenticated,
+    /// No documentation routes are served.
+    Disabled,
+}
+
+impl FromStr for DocsAccess {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "public" => Ok(Self::Public),
+            "authenticated" => Ok(Self::Authenticated),
+            "disabled" => Ok(Self::Disabled),
+            other => Err(format!(
+                "Invalid API_DOCS_ACCESS '{other}'; expected public, authenticated, or disabled"
+            )),
+        }
+    }
+}
+
+/// API documentation settings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocsConfig {
+    /// Who may read the full spec and UI.
+    pub access: DocsAccess,
+}
+
+impl DocsConfig {
+    /// Creates a new `DocsConfig` from environment variables.
+    ///
+    /// An unrecognised `API_DOCS_ACCESS` falls back to `authenticated`, so a
+    /// typo never exposes the full spec.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Creates a new `DocsConfig` from the variables `lookup` returns.
+    #[must_use]
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let production = lookup("ENVIRONMENT").is_some_and(|value| value == "production");
+        Self {
+            access: Self::access_for(lookup("API_DOCS_ACCESS").as_deref(), production),
+        }
+    }
+
+    fn access_for(value: Option<&str>, production: bool) -> DocsAccess {
+        match value {
+            Some(value) => value.parse().unwrap_or(DocsAccess::Authenticated),
+            None if production => DocsAccess::Authenticated,
+            None => DocsAccess::Public,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_defaults_by_environment() {
+        assert_eq!(DocsConfig::access_for(None, false), DocsAccess::Public);
+        assert_eq!(
+            DocsConfig::access_for(None, true),
+            DocsAccess::Authenticated
+        );
+    }
+
+    #[test]
+    fn test_access_from_value() {
+        assert_eq!(
+            DocsConfig::access_for(Some("Disabled"), false),
+            DocsAccess::Disabled
+        );
+        assert_eq!(
+            DocsConfig::access_for(Some(" public "), true),
+            DocsAccess::Public
+        );
+        assert_eq!(
+            DocsConfig::access_for(Some("open"), false),
+            DocsAccess::Authenticated
+        );
+    }
+}
@@ -15,22 +15,24 @@ pub struct EmailConfig {
 
 impl EmailConfig {
     pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
         Self {
-            enabled: env::var("SMTP_ENABLED")
+            enabled: lookup("SMTP_ENABLED")
                 .map(|v| v.to_lowercase() == "true" || v == "1")
                 .unwrap_or(false),
-            smtp_host: env::var("SMTP_HOST").unwrap_or_else(|_| "localhost".to_string()),
-            smtp_port: env::var("SMTP_PORT")
-                .ok()
+            smtp_host: lookup("SMTP_HOST").unwrap_or_else(|| "localhost".to_string()),
+            smtp_port: lookup("SMTP_PORT")
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(1025),
-            smtp_username: env::var("SMTP_USERNAME").unwrap_or_else(|_| "".to_string()),
-            smtp_password: env::var("SMTP_PASSWORD").unwrap_or_else(|_| "".to_string()),
-            from_email: env::var("FROM_EMAIL")
-                .unwrap_or_else(|_| "noreply@chalkbyte.com".to_string()),
-            from_name: env::var("FROM_NAME").unwrap_or_else(|_| "Chalkbyte".to_string()),
-            frontend_url: env::var("FRONTEND_URL")
-                .unwrap_or_else(|_| "http://localhost:3000".to_string()),
+            smtp_username: lookup("SMTP_USERNAME").unwrap_or_default(),
+            smtp_password: lookup("SMTP_PASSWORD").unwrap_or_default(),
+            from_email: lookup("FROM_EMAIL").unwrap_or_else(|| "noreply@chalkbyte.com".to_string()),
+            from_name: lookup("FROM_NAME").unwrap_or_else(|| "Chalkbyte".to_string()),
+            frontend_url: lookup("FRONTEND_URL")
+                .unwrap_or_else(|| "http://localhost:3000".to_string()),
         }
     }
 }
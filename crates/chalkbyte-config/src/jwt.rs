@@ -8,12 +8,20 @@
 //! - `JWT_SECRET`: Secret key for signing tokens (required in production)
 //! - `JWT_ACCESS_EXPIRY`: Access token expiry in seconds (default: 3600 = 1 hour)
 //! - `JWT_REFRESH_EXPIRY`: Refresh token expiry in seconds (default: 604800 = 7 days)
+//! - `JWT_CARD_SECRET`: Secret student card codes are signed with (default:
+//!   `JWT_SECRET`)
+//!
+//! Signing keys, key rotation, and RS256/EdDSA are configured with the
+//! variables listed in [`jwt_keys`](crate::jwt_keys).
 //!
 //! # Security Considerations
 //!
 //! - **Always set `JWT_SECRET` in production** - the default value is insecure
 //! - Use a cryptographically random string of at least 32 characters
-//! - Keep the secret key confidential and rotate it periodically
+//! - Keep the secret key confidential and rotate it periodically, moving the
+//!   old secret to `JWT_PREVIOUS_SECRETS` so issued tokens stay valid
+//! - Printed student cards outlive any token, so set `JWT_CARD_SECRET` to the
+//!   old secret before rotating `JWT_SECRET` to keep them scannable
 //! - Access tokens should be short-lived; refresh tokens can be longer
 //!
 //! # Example
@@ -25,7 +33,7 @@
 //! let config = JwtConfig::from_env();
 //!
 //! // Use in token creation
-//! let token = create_access_token(user_id, email, school_id, roles, perms, &config)?;
+//! let token = create_access_token(user_id, email, school_id, group_id, roles, perms, &config)?;
 //! ```
 //!
 //! # Token Lifetimes
@@ -40,6 +48,8 @@
 
 use std::env;
 
+use crate::jwt_keys::JwtKeySet;
+
 /// JWT configuration containing secret key and token expiry settings.
 ///
 /// This struct holds all JWT-related configuration values loaded from
@@ -48,7 +58,9 @@ use std::env;
 ///
 /// # Fields
 ///
-/// - `secret`: The secret key used for signing and verifying JWTs
+/// - `secret`: The HS256 secret, also used for HMACs outside of tokens
+/// - `card_secret`: The secret student card codes are signed with
+/// - `keys`: The keys tokens are signed and verified with
 /// - `access_token_expiry`: How long access tokens remain valid (in seconds)
 /// - `refresh_token_expiry`: How long refresh tokens remain valid (in seconds)
 ///
@@ -72,6 +84,17 @@ pub struct JwtConfig {
     /// The default value is insecure and should never be used in production.
     pub secret: String,
 
+    /// Secret for the codes printed on student cards.
+    ///
+    /// Kept apart from `secret` so rotating token keys does not invalidate
+    /// cards that are already printed.
+    pub card_secret: String,
+
+    /// Keys tokens are signed and verified with.
+    ///
+    /// Built from `secret` unless `JWT_ALGORITHM` selects RS256 or EdDSA.
+    pub keys: JwtKeySet,
+
     /// Access token expiry time in seconds.
     ///
     /// Access tokens are used for API authentication. They should be
@@ -95,7 +118,9 @@ impl JwtConfig {
     ///
     /// # Environment Variables
     ///
-    /// - `JWT_SECRET`: Secret key (default: "your-secret-key-change-in-production")
+    /// - `JWT_SECRET`: Secret key (default: "your-secret-key-change-in-production";
+    ///   required when `JWT_ALGORITHM` is RS256 or EdDSA)
+    /// - `JWT_CARD_SECRET`: Student card signing secret (default: `JWT_SECRET`)
     /// - `JWT_ACCESS_EXPIRY`: Access token expiry in seconds (default: 3600)
     /// - `JWT_REFRESH_EXPIRY`: Refresh token expiry in seconds (default: 604800)
     ///
@@ -116,15 +141,37 @@ impl JwtConfig {
     /// environments as the default value is insecure.
     #[must_use]
     pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Creates a new `JwtConfig` from the variables `lookup` returns.
+    ///
+    /// Used by [`ChalkbyteConfig`](crate::ChalkbyteConfig) to read from a
+    /// config file with environment overrides; `from_env` passes
+    /// `std::env::var`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the signing keys cannot be loaded. `ChalkbyteConfig::load`
+    /// reports the problem as a config error before this is reached.
+    #[must_use]
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let keys = JwtKeySet::from_lookup(&lookup)
+            .unwrap_or_else(|e| panic!("invalid JWT key configuration: {e}"));
+
+        let secret = lookup("JWT_SECRET")
+            .unwrap_or_else(|| "your-secret-key-change-in-production".to_string());
+
         Self {
-            secret: env::var("JWT_SECRET")
-                .unwrap_or_else(|_| "your-secret-key-change-in-production".to_string()),
-            access_token_expiry: env::var("JWT_ACCESS_EXPIRY")
-                .ok()
+            keys,
+            card_secret: lookup("JWT_CARD_SECRET")
+                .filter(|s| !s.trim().is_empty())
+                .unwrap_or_else(|| secret.clone()),
+            secret,
+            access_token_expiry: lookup("JWT_ACCESS_EXPIRY")
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(3600), // 1 hour
-            refresh_token_expiry: env::var("JWT_REFRESH_EXPIRY")
-                .ok()
+            refresh_token_expiry: lookup("JWT_REFRESH_EXPIRY")
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(604800), // 7 days
         }
@@ -141,8 +188,12 @@ impl Default for JwtConfig {
     ///
     /// The default secret key is insecure. Always set `JWT_SECRET` in production.
     fn default() -> Self {
+        let secret = "your-secret-key-change-in-production";
+
         Self {
-            secret: "your-secret-key-change-in-production".to_string(),
+            keys: JwtKeySet::hmac(secret),
+            secret: secret.to_string(),
+            card_secret: secret.to_string(),
             access_token_expiry: 3600,
             refresh_token_expiry: 604800,
         }
@@ -182,5 +233,30 @@ mod tests {
         let debug_str = format!("{:?}", config);
         assert!(debug_str.contains("JwtConfig"));
         assert!(debug_str.contains("access_token_expiry"));
+        assert!(debug_str.contains("JwtKeySet"));
+    }
+
+    #[test]
+    fn test_default_keys_match_secret() {
+        let config = JwtConfig::default();
+        assert_eq!(config.keys.kid(), JwtKeySet::hmac(&config.secret).kid());
+    }
+
+    #[test]
+    fn test_card_secret_survives_rotation() {
+        let config = JwtConfig::from_lookup(|name| match name {
+            "JWT_SECRET" => Some("secret-one".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.card_secret, "secret-one");
+
+        let rotated = JwtConfig::from_lookup(|name| match name {
+            "JWT_SECRET" => Some("secret-two".to_string()),
+            "JWT_PREVIOUS_SECRETS" => Some("secret-one".to_string()),
+            "JWT_CARD_SECRET" => Some("secret-one".to_string()),
+            _ => None,
+        });
+        assert_eq!(rotated.secret, "secret-two");
+        assert_eq!(rotated.card_secret, "secret-one");
     }
 }
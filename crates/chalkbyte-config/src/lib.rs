@@ -4,10 +4,18 @@
 //!
 //! This crate provides configuration structures loaded from environment variables:
 //!
+//! - [`app`]: [`ChalkbyteConfig`], every setting loaded together
+//! - [`source`]: Config files (`chalkbyte.toml`, `config.yaml`) with
+//!   environment overrides and validation
 //! - [`jwt`]: JWT authentication configuration
+//! - [`jwt_keys`]: JWT signing keys, rotation, and the published key set
+//! - [`oidc`]: OpenID Connect (SSO) configuration
 //! - [`cors`]: CORS (Cross-Origin Resource Sharing) configuration
+//! - [`docs`]: API documentation access configuration
 //! - [`email`]: Email/SMTP configuration
 //! - [`rate_limit`]: API rate limiting configuration
+//! - [`storage`]: File storage backend and school quota configuration
+//! - [`webauthn`]: WebAuthn (passkey) relying party configuration
 //!
 //! # Example
 //!
@@ -19,15 +27,33 @@
 //! let cors_config = CorsConfig::from_env();
 //! let email_config = EmailConfig::from_env();
 //! let rate_limit_config = RateLimitConfig::from_env();
+//!
+//! // Or load everything from chalkbyte.toml with environment overrides,
+//! // failing with a list of every missing or invalid key
+//! let config = chalkbyte_config::ChalkbyteConfig::load(None)?;
 //! ```
 
+pub mod app;
 pub mod cors;
+pub mod docs;
 pub mod email;
 pub mod jwt;
+pub mod jwt_keys;
+pub mod oidc;
 pub mod rate_limit;
+pub mod source;
+pub mod storage;
+pub mod webauthn;
 
 // Re-export commonly used types at crate root
+pub use app::{ChalkbyteConfig, ServerConfig};
 pub use cors::CorsConfig;
+pub use docs::{DocsAccess, DocsConfig};
 pub use email::EmailConfig;
 pub use jwt::JwtConfig;
+pub use jwt_keys::{JwtKeyError, JwtKeySet};
+pub use oidc::OidcConfig;
 pub use rate_limit::RateLimitConfig;
+pub use source::{ConfigError, ConfigIssue, ConfigSource};
+pub use storage::{S3StorageConfig, StorageConfig, StorageDriver};
+pub use webauthn::WebauthnConfig;
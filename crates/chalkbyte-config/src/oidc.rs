@@ -0,0 +1,65 @@
+//! OpenID Connect (SSO) configuration.
+//!
+//! Identity providers themselves are configured per school through the API;
+//! this only holds the settings shared by all of them.
+//!
+//! # Environment Variables
+//!
+//! - `OIDC_REDIRECT_BASE_URL`: Public base URL of this API, used to build the
+//!   callback URL registered with each provider (default: "http://localhost:3000")
+//! - `OIDC_ALLOW_LOCAL_ISSUERS`: `true` to let issuers run on loopback
+//!   or private addresses over plain HTTP, e.g. a local Keycloak
+//!   (default: false). Never enable it in production: school admins choose
+//!   the issuer, and the server fetches from it.
+//!
+//! The callback URL for a provider is
+//! `{OIDC_REDIRECT_BASE_URL}/api/auth/oidc/{provider}/callback`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use crate::config::oidc::OidcConfig;
+//!
+//! let config = OidcConfig::from_env();
+//! let callback = config.callback_url("acme-google");
+//! ```
+
+use std::env;
+
+/// Settings shared by all OIDC identity providers.
+#[derive(Clone, Debug)]
+pub struct OidcConfig {
+    /// Public base URL of the API, without a trailing slash.
+    pub redirect_base_url: String,
+    /// Whether issuers may be on loopback or private addresses.
+    pub allow_local_issuers: bool,
+}
+
+impl OidcConfig {
+    /// Creates a new `OidcConfig` from environment variables.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Creates a new `OidcConfig` from the variables `lookup` returns.
+    #[must_use]
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let redirect_base_url =
+            lookup("OIDC_REDIRECT_BASE_URL").unwrap_or_else(|| "http://localhost:3000".to_string());
+
+        let allow_local_issuers =
+            lookup("OIDC_ALLOW_LOCAL_ISSUERS").is_some_and(|v| v.trim() == "true");
+
+        Self {
+            redirect_base_url: redirect_base_url.trim_end_matches('/').to_string(),
+            allow_local_issuers,
+        }
+    }
+
+    /// The callback URL to register with the provider identified by `slug`.
+    #[must_use]
+    pub fn callback_url(&self, slug: &str) -> String {
+        format!("{}/api/auth/oidc/{}/callback", self.redirect_base_url, slug)
+    }
+}
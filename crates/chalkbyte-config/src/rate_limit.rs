@@ -1,7 +1,8 @@
 //! Rate limiting configuration for API endpoints.
 //!
-//! This module provides configuration for rate limiting using the Governor crate.
-//! Rate limits help protect the API from abuse and ensure fair usage.
+//! This module provides configuration for rate limiting using the Governor crate,
+//! and for the Redis-backed limits shared across instances. Rate limits help
+//! protect the API from abuse and ensure fair usage.
 //!
 //! # Configuration
 //!
@@ -11,6 +12,13 @@
 //! - `RATE_LIMIT_GENERAL_BURST_SIZE`: Burst size for general endpoints (default: 30)
 //! - `RATE_LIMIT_AUTH_PER_SECOND`: Requests per second for auth endpoints (default: 10)
 //! - `RATE_LIMIT_AUTH_BURST_SIZE`: Burst size for auth endpoints (default: 5)
+//! - `RATE_LIMIT_PUBLIC_PER_SECOND`: Requests per second for public endpoints (default: 6)
+//! - `RATE_LIMIT_PUBLIC_BURST_SIZE`: Burst size for public endpoints (default: 10)
+//! - `RATE_LIMIT_USER_PER_MINUTE`: Requests per minute for each user (default: 300)
+//! - `RATE_LIMIT_SCHOOL_PER_MINUTE`: Requests per minute for all users of a school (default: 3000)
+//! - `RATE_LIMIT_LOGIN_PER_MINUTE`: Login attempts per minute for each client IP (default: 5)
+//! - `RATE_LIMIT_PASSWORD_RESET_PER_HOUR`: Password reset requests per hour for each client IP (default: 5)
+//! - `RATE_LIMIT_MESSAGES_PER_MINUTE`: Messages sent per minute by each user (default: 10)
 //!
 //! # Rate Limiting Strategy
 //!
@@ -21,6 +29,10 @@
 //! - Burst size defines the maximum tokens that can accumulate
 //! - Requests are rejected when no tokens are available
 //!
+//! The per-user, per-school, login, password reset, and messaging limits
+//! are kept in Redis as sliding windows, so they hold across instances (see
+//! `chalkbyte_cache::rate_limit`).
+//!
 //! # Example
 //!
 //! ```ignore
@@ -32,13 +44,15 @@
 //! let governor = config.general_governor_config();
 //! ```
 
+use chalkbyte_cache::RateLimit;
 use tower_governor::governor::{GovernorConfig, GovernorConfigBuilder};
 use tower_governor::key_extractor::PeerIpKeyExtractor;
 
 /// Rate limit configuration for the API.
 ///
-/// Defines separate rate limits for general API endpoints and authentication
-/// endpoints (which typically need stricter limits to prevent brute-force attacks).
+/// Defines separate rate limits for general API endpoints, authentication
+/// endpoints (which typically need stricter limits to prevent brute-force attacks),
+/// and unauthenticated public endpoints.
 ///
 /// # Fields
 ///
@@ -46,6 +60,13 @@ use tower_governor::key_extractor::PeerIpKeyExtractor;
 /// - `general_burst_size`: Maximum token accumulation for general endpoints
 /// - `auth_per_second`: Token replenishment rate for auth endpoints
 /// - `auth_burst_size`: Maximum token accumulation for auth endpoints
+/// - `public_per_second`: Token replenishment rate for public endpoints
+/// - `public_burst_size`: Maximum token accumulation for public endpoints
+/// - `user_per_minute`: Sliding window limit for each user
+/// - `school_per_minute`: Sliding window limit shared by a school's users
+/// - `login_per_minute`: Sliding window limit on logins for each client IP
+/// - `password_reset_per_hour`: Sliding window limit on password resets for each client IP
+/// - `messages_per_minute`: Sliding window limit on messages sent by each user
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RateLimitConfig {
     /// Requests per second for general endpoints.
@@ -74,6 +95,34 @@ pub struct RateLimitConfig {
     /// against rapid-fire authentication attempts.
     #[allow(dead_code)]
     pub auth_burst_size: u32,
+
+    /// Requests per second for public endpoints.
+    ///
+    /// Public endpoints need no login, so they are limited more tightly
+    /// than general endpoints to discourage scraping.
+    #[allow(dead_code)]
+    pub public_per_second: u64,
+
+    /// Burst size for public endpoints.
+    #[allow(dead_code)]
+    pub public_burst_size: u32,
+
+    /// Requests per minute for each authenticated user, across instances.
+    pub user_per_minute: u32,
+
+    /// Requests per minute for all users of a school together, across
+    /// instances.
+    pub school_per_minute: u32,
+
+    /// Login attempts per minute for each client IP, across instances.
+    pub login_per_minute: u32,
+
+    /// Forgot and reset password requests per hour for each client IP,
+    /// across instances.
+    pub password_reset_per_hour: u32,
+
+    /// Messages sent per minute by each user, across instances.
+    pub messages_per_minute: u32,
 }
 
 impl Default for RateLimitConfig {
@@ -83,6 +132,13 @@ impl Default for RateLimitConfig {
             general_burst_size: 30,
             auth_per_second: 10,
             auth_burst_size: 5,
+            public_per_second: 6,
+            public_burst_size: 10,
+            user_per_minute: 300,
+            school_per_minute: 3000,
+            login_per_minute: 5,
+            password_reset_per_hour: 5,
+            messages_per_minute: 10,
         }
     }
 }
@@ -99,28 +155,89 @@ impl RateLimitConfig {
     /// - `RATE_LIMIT_GENERAL_BURST_SIZE`: Default 30
     /// - `RATE_LIMIT_AUTH_PER_SECOND`: Default 10
     /// - `RATE_LIMIT_AUTH_BURST_SIZE`: Default 5
+    /// - `RATE_LIMIT_PUBLIC_PER_SECOND`: Default 6
+    /// - `RATE_LIMIT_PUBLIC_BURST_SIZE`: Default 10
+    /// - `RATE_LIMIT_USER_PER_MINUTE`: Default 300
+    /// - `RATE_LIMIT_SCHOOL_PER_MINUTE`: Default 3000
+    /// - `RATE_LIMIT_LOGIN_PER_MINUTE`: Default 5
+    /// - `RATE_LIMIT_PASSWORD_RESET_PER_HOUR`: Default 5
+    /// - `RATE_LIMIT_MESSAGES_PER_MINUTE`: Default 10
     #[must_use]
     pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Creates a new `RateLimitConfig` from the variables `lookup` returns,
+    /// with the same defaults as [`RateLimitConfig::from_env`].
+    #[must_use]
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
         Self {
-            general_per_second: std::env::var("RATE_LIMIT_GENERAL_PER_SECOND")
-                .ok()
+            general_per_second: lookup("RATE_LIMIT_GENERAL_PER_SECOND")
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(2),
-            general_burst_size: std::env::var("RATE_LIMIT_GENERAL_BURST_SIZE")
-                .ok()
+            general_burst_size: lookup("RATE_LIMIT_GENERAL_BURST_SIZE")
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(30),
-            auth_per_second: std::env::var("RATE_LIMIT_AUTH_PER_SECOND")
-                .ok()
+            auth_per_second: lookup("RATE_LIMIT_AUTH_PER_SECOND")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            auth_burst_size: lookup("RATE_LIMIT_AUTH_BURST_SIZE")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            public_per_second: lookup("RATE_LIMIT_PUBLIC_PER_SECOND")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(6),
+            public_burst_size: lookup("RATE_LIMIT_PUBLIC_BURST_SIZE")
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(10),
-            auth_burst_size: std::env::var("RATE_LIMIT_AUTH_BURST_SIZE")
-                .ok()
+            user_per_minute: lookup("RATE_LIMIT_USER_PER_MINUTE")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            school_per_minute: lookup("RATE_LIMIT_SCHOOL_PER_MINUTE")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3000),
+            login_per_minute: lookup("RATE_LIMIT_LOGIN_PER_MINUTE")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            password_reset_per_hour: lookup("RATE_LIMIT_PASSWORD_RESET_PER_HOUR")
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(5),
+            messages_per_minute: lookup("RATE_LIMIT_MESSAGES_PER_MINUTE")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
         }
     }
 
+    /// Limit for each authenticated user.
+    #[must_use]
+    pub fn user_limit(&self) -> RateLimit {
+        RateLimit::per_minute(self.user_per_minute)
+    }
+
+    /// Limit shared by all users of a school.
+    #[must_use]
+    pub fn school_limit(&self) -> RateLimit {
+        RateLimit::per_minute(self.school_per_minute)
+    }
+
+    /// Limit on login attempts from one client IP.
+    #[must_use]
+    pub fn login_limit(&self) -> RateLimit {
+        RateLimit::per_minute(self.login_per_minute)
+    }
+
+    /// Limit on forgot and reset password requests from one client IP.
+    #[must_use]
+    pub fn password_reset_limit(&self) -> RateLimit {
+        RateLimit::per_hour(self.password_reset_per_hour)
+    }
+
+    /// Limit on messages sent by one user.
+    #[must_use]
+    pub fn message_limit(&self) -> RateLimit {
+        RateLimit::per_minute(self.messages_per_minute)
+    }
+
     /// Creates a `GovernorConfig` for general API endpoints.
     ///
     /// The returned config uses the peer IP address as the rate limit key,
@@ -186,6 +303,26 @@ impl RateLimitConfig {
             .finish()
             .expect("Failed to build auth rate limiter config")
     }
+
+    /// Creates a `GovernorConfig` for unauthenticated public endpoints.
+    ///
+    /// The returned config uses the peer IP address as the rate limit key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the governor configuration cannot be built.
+    #[allow(dead_code)]
+    #[must_use]
+    pub fn public_governor_config(
+        &self,
+    ) -> GovernorConfig<PeerIpKeyExtractor, ::governor::middleware::NoOpMiddleware> {
+        GovernorConfigBuilder::default()
+            .per_second(self.public_per_second)
+            .burst_size(self.public_burst_size)
+            .key_extractor(PeerIpKeyExtractor)
+            .finish()
+            .expect("Failed to build public rate limiter config")
+    }
 }
 
 #[cfg(test)]
@@ -199,6 +336,26 @@ mod tests {
         assert_eq!(config.general_burst_size, 30);
         assert_eq!(config.auth_per_second, 10);
         assert_eq!(config.auth_burst_size, 5);
+        assert_eq!(config.public_per_second, 6);
+        assert_eq!(config.public_burst_size, 10);
+        assert_eq!(config.user_per_minute, 300);
+        assert_eq!(config.school_per_minute, 3000);
+        assert_eq!(config.login_per_minute, 5);
+        assert_eq!(config.password_reset_per_hour, 5);
+        assert_eq!(config.messages_per_minute, 10);
+    }
+
+    #[test]
+    fn test_redis_limits_from_lookup() {
+        let config = RateLimitConfig::from_lookup(|name| match name {
+            "RATE_LIMIT_LOGIN_PER_MINUTE" => Some("3".to_string()),
+            "RATE_LIMIT_PASSWORD_RESET_PER_HOUR" => Some("2".to_string()),
+            _ => None,
+        });
+
+        assert_eq!(config.login_limit(), RateLimit::per_minute(3));
+        assert_eq!(config.password_reset_limit(), RateLimit::per_hour(2));
+        assert_eq!(config.user_limit(), RateLimit::per_minute(300));
     }
 
     #[test]
@@ -0,0 +1,668 @@
+//! Config file loading with environment overrides.
+//!
+//! A config file uses the same names as the environment variables, written
+//! in lower case and optionally grouped into tables: nested keys are joined
+//! with `_`, so these are all `JWT_ACCESS_EXPIRY`:
+//!
+//! ```toml
+//! jwt_access_expiry = 1800
+//!
+//! [jwt]
+//! access_expiry = 1800
+//! ```
+//!
+//! Lists such as `allowed_origins = ["https://a", "https://b"]` become the
+//! comma-separated form the variables use. An environment variable, including
+//! one set in `.env`, always wins over the file.
+//!
+//! # File Discovery
+//!
+//! [`ConfigSource::load`] reads the path it is given, then `CHALKBYTE_CONFIG`,
+//! then the first of `chalkbyte.toml`, `config.yaml`, or `config.yml` in the
+//! working directory. Without any of those, only the environment is used.
+//!
+//! # Validation
+//!
+//! [`ConfigSource::validate`] checks every known key at once and returns all
+//! problems in a single [`ConfigError::Invalid`]: missing required keys,
+//! values of the wrong type, and keys in the file that no setting reads.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::jwt_keys::{JWT_ALGORITHMS, JwtKeyError, JwtKeySet};
+
+/// Files looked for in the working directory when no path is given.
+pub const DEFAULT_CONFIG_FILES: &[&str] = &["chalkbyte.toml", "config.yaml", "config.yml"];
+
+/// The shape a setting's value must have.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Text,
+    /// `true` or `false`
+    Bool,
+    /// A whole number, zero or more
+    Count,
+    /// A whole number above zero
+    Positive,
+    /// A TCP port
+    Port,
+    /// A decimal number between 0 and 1
+    Ratio,
+    /// One of a fixed set of words, compared case-insensitively
+    OneOf(&'static [&'static str]),
+}
+
+impl Kind {
+    fn accepts(self, value: &str) -> bool {
+        let value = value.trim();
+        match self {
+            Self::Text => true,
+            Self::Bool => matches!(value, "true" | "false"),
+            Self::Count => value.parse::<u64>().is_ok(),
+            Self::Positive => value.parse::<u64>().is_ok_and(|n| n > 0),
+            Self::Port => value.parse::<u16>().is_ok_and(|n| n > 0),
+            Self::Ratio => value.parse::<f64>().is_ok_and(|r| (0.0..=1.0).contains(&r)),
+            Self::OneOf(words) => words.iter().any(|w| w.eq_ignore_ascii_case(value)),
+        }
+    }
+
+    fn expected(self) -> String {
+        match self {
+            Self::Text => "text".to_string(),
+            Self::Bool => "true or false".to_string(),
+            Self::Count => "a whole number".to_string(),
+            Self::Positive => "a whole number above 0".to_string(),
+            Self::Port => "a port number from 1 to 65535".to_string(),
+            Self::Ratio => "a number from 0 to 1".to_string(),
+            Self::OneOf(words) => format!("one of {}", words.join(", ")),
+        }
+    }
+}
+
+/// Every setting [`ChalkbyteConfig`](crate::ChalkbyteConfig) reads.
+const KEYS: &[(&str, Kind)] = &[
+    ("ENVIRONMENT", Kind::Text),
+    ("PORT", Kind::Port),
+    ("METRICS_PORT", Kind::Port),
+    // Database
+    ("DATABASE_URL", Kind::Text),
+    ("DATABASE_REPLICA_URLS", Kind::Text),
+    ("DB_MAX_CONNECTIONS", Kind::Positive),
+    ("DB_MIN_CONNECTIONS", Kind::Count),
+    ("DB_ACQUIRE_TIMEOUT_SECONDS", Kind::Count),
+    ("DB_IDLE_TIMEOUT_SECONDS", Kind::Count),
+    ("DB_STATEMENT_TIMEOUT_MS", Kind::Count),
+    // Cache
+    ("REDIS_URL", Kind::Text),
+    ("CACHE_TTL_SECONDS", Kind::Count),
+    ("CACHE_PREFIX", Kind::Text),
+    ("SCHOOL_STATS_TTL_SECONDS", Kind::Count),
+    // JWT
+    ("JWT_SECRET", Kind::Text),
+    ("JWT_ACCESS_EXPIRY", Kind::Positive),
+    ("JWT_REFRESH_EXPIRY", Kind::Positive),
+    ("JWT_ALGORITHM", Kind::OneOf(JWT_ALGORITHMS)),
+    ("JWT_KEY_ID", Kind::Text),
+    ("JWT_PREVIOUS_SECRETS", Kind::Text),
+    ("JWT_CARD_SECRET", Kind::Text),
+    ("JWT_PRIVATE_KEY", Kind::Text),
+    ("JWT_PRIVATE_KEY_FILE", Kind::Text),
+    ("JWT_PUBLIC_KEY", Kind::Text),
+    ("JWT_PUBLIC_KEY_FILE", Kind::Text),
+    ("JWT_PREVIOUS_PUBLIC_KEYS", Kind::Text),
+    ("JWT_PREVIOUS_PUBLIC_KEYS_FILE", Kind::Text),
+    // Email
+    ("SMTP_ENABLED", Kind::Bool),
+    ("SMTP_HOST", Kind::Text),
+    ("SMTP_PORT", Kind::Port),
+    ("SMTP_USERNAME", Kind::Text),
+    ("SMTP_PASSWORD", Kind::Text),
+    ("FROM_EMAIL", Kind::Text),
+    ("FROM_NAME", Kind::Text),
+    ("FRONTEND_URL", Kind::Text),
+    // CORS
+    ("ALLOWED_ORIGINS", Kind::Text),
+    // Rate limiting; zero would make the limiter fail to build
+    ("RATE_LIMIT_GENERAL_PER_SECOND", Kind::Positive),
+    ("RATE_LIMIT_GENERAL_BURST_SIZE", Kind::Positive),
+    ("RATE_LIMIT_AUTH_PER_SECOND", Kind::Positive),
+    ("RATE_LIMIT_AUTH_BURST_SIZE", Kind::Positive),
+    ("RATE_LIMIT_PUBLIC_PER_SECOND", Kind::Positive),
+    ("RATE_LIMIT_PUBLIC_BURST_SIZE", Kind::Positive),
+    ("RATE_LIMIT_USER_PER_MINUTE", Kind::Positive),
+    ("RATE_LIMIT_SCHOOL_PER_MINUTE", Kind::Positive),
+    ("RATE_LIMIT_LOGIN_PER_MINUTE", Kind::Positive),
+    ("RATE_LIMIT_PASSWORD_RESET_PER_HOUR", Kind::Positive),
+    ("RATE_LIMIT_MESSAGES_PER_MINUTE", Kind::Positive),
+    // File storage
+    ("STORAGE_BACKEND", Kind::OneOf(&["local", "s3"])),
+    ("STORAGE_LOCAL_DIR", Kind::Text),
+    ("STORAGE_PUBLIC_URL", Kind::Text),
+    ("STORAGE_S3_ENDPOINT", Kind::Text),
+    ("STORAGE_S3_REGION", Kind::Text),
+    ("STORAGE_S3_BUCKET", Kind::Text),
+    ("STORAGE_S3_ACCESS_KEY_ID", Kind::Text),
+    ("STORAGE_S3_SECRET_ACCESS_KEY", Kind::Text),
+    ("STORAGE_S3_PATH_STYLE", Kind::Bool),
+    ("STORAGE_URL_TTL_SECONDS", Kind::Positive),
+    ("STORAGE_SIGNING_SECRET", Kind::Text),
+    ("STORAGE_SCHOOL_QUOTA_MB", Kind::Positive),
+    // Password policy
+    ("PASSWORD_MIN_LENGTH", Kind::Count),
+    ("PASSWORD_REQUIRE_UPPERCASE", Kind::Bool),
+    ("PASSWORD_REQUIRE_LOWERCASE", Kind::Bool),
+    ("PASSWORD_REQUIRE_DIGIT", Kind::Bool),
+    ("PASSWORD_REQUIRE_SYMBOL", Kind::Bool),
+    ("PASSWORD_BAN_COMMON", Kind::Bool),
+    ("PASSWORD_DISALLOW_PERSONAL_INFO", Kind::Bool),
+    ("PASSWORD_HISTORY_SIZE", Kind::Count),
+    // Passkeys and SSO
+    ("WEBAUTHN_RP_ID", Kind::Text),
+    ("WEBAUTHN_RP_ORIGIN", Kind::Text),
+    ("WEBAUTHN_RP_NAME", Kind::Text),
+    ("OIDC_REDIRECT_BASE_URL", Kind::Text),
+    // API docs
+    (
+        "API_DOCS_ACCESS",
+        Kind::OneOf(&["public", "authenticated", "disabled"]),
+    ),
+    // Observability
+    ("OBSERVABILITY_ENABLED", Kind::Text),
+    ("LOG_FORMAT", Kind::OneOf(&["pretty", "json"])),
+    ("OTEL_SERVICE_NAME", Kind::Text),
+    ("OTEL_EXPORTER_OTLP_ENDPOINT", Kind::Text),
+    ("OTEL_EXPORTER_OTLP_HEADERS", Kind::Text),
+    ("OTEL_EXPORTER_OTLP_TIMEOUT", Kind::Count),
+    (
+        "OTEL_TRACES_SAMPLER",
+        Kind::OneOf(&[
+            "always_on",
+            "always_off",
+            "traceidratio",
+            "parentbased_always_on",
+            "parentbased_always_off",
+            "parentbased_traceidratio",
+        ]),
+    ),
+    ("OTEL_TRACES_SAMPLER_ARG", Kind::Ratio),
+    ("OTEL_TRACES_SAMPLER_ROUTES", Kind::Text),
+];
+
+/// Errors from loading or validating configuration.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Failed to read config file {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Unsupported config file {}; use .toml, .yaml, or .yml", path.display())]
+    UnsupportedFormat { path: PathBuf },
+
+    #[error("Failed to parse config file {}: {message}", path.display())]
+    Parse { path: PathBuf, message: String },
+
+    #[error("Invalid configuration:{}", list_issues(.0))]
+    Invalid(Vec<ConfigIssue>),
+}
+
+/// One problem found by [`ConfigSource::validate`].
+///
+/// `key` is the environment variable name, followed by where the file set it
+/// when the value came from a config file.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ConfigIssue {
+    #[error("{key} is required {reason}")]
+    Missing { key: String, reason: &'static str },
+
+    #[error("{key} has invalid value {value:?}; expected {expected}")]
+    Invalid {
+        key: String,
+        value: String,
+        expected: String,
+    },
+
+    #[error("{key} is not a known setting")]
+    Unknown { key: String },
+
+    #[error(transparent)]
+    JwtKeys(#[from] JwtKeyError),
+}
+
+fn list_issues(issues: &[ConfigIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| format!("\n  - {issue}"))
+        .collect()
+}
+
+/// A value read from the config file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FileValue {
+    /// The key as written, e.g. `jwt.access_expiry`
+    path: String,
+    value: String,
+}
+
+/// Reads one environment variable.
+type EnvLookup = Box<dyn Fn(&str) -> Option<String>>;
+
+/// Settings from an optional config file, overridden by the environment.
+pub struct ConfigSource {
+    path: Option<PathBuf>,
+    file: BTreeMap<String, FileValue>,
+    env: EnvLookup,
+    require_database: bool,
+}
+
+impl ConfigSource {
+    /// Only the process environment, as every `from_env` reads it.
+    #[must_use]
+    pub fn env() -> Self {
+        Self {
+            path: None,
+            file: BTreeMap::new(),
+            env: Box::new(|name| env::var(name).ok()),
+            require_database: true,
+        }
+    }
+
+    /// Loads `path`, or the discovered config file, under the process
+    /// environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError`] if the file cannot be read or parsed. A given
+    /// path that does not exist is an error; a default file that does not
+    /// exist is skipped.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let path = path
+            .map(Path::to_path_buf)
+            .or_else(|| env::var_os("CHALKBYTE_CONFIG").map(PathBuf::from))
+            .or_else(|| {
+                DEFAULT_CONFIG_FILES
+                    .iter()
+                    .map(PathBuf::from)
+                    .find(|path| path.is_file())
+            });
+
+        let Some(path) = path else {
+            return Ok(Self::env());
+        };
+        let contents = std::fs::read_to_string(&path).map_err(|source| ConfigError::Read {
+            path: path.clone(),
+            source,
+        })?;
+        Self::parse(&path, &contents)
+    }
+
+    /// Parses `contents` as the config file at `path` under the process
+    /// environment. The format follows the extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError`] for an unknown extension or malformed file.
+    pub fn parse(path: &Path, contents: &str) -> Result<Self, ConfigError> {
+        let parse_error = |message: String| ConfigError::Parse {
+            path: path.to_path_buf(),
+            message,
+        };
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        let document: Value = match extension.as_deref() {
+            Some("toml") => toml::from_str(contents).map_err(|e| parse_error(e.to_string()))?,
+            Some("yaml" | "yml") => {
+                serde_yaml::from_str(contents).map_err(|e| parse_error(e.to_string()))?
+            }
+            _ => {
+                return Err(ConfigError::UnsupportedFormat {
+                    path: path.to_path_buf(),
+                });
+            }
+        };
+
+        let mut file = BTreeMap::new();
+        match document {
+            Value::Object(table) => flatten(&table, "", "", &mut file).map_err(parse_error)?,
+            Value::Null => {}
+            _ => return Err(parse_error("expected a table of settings".to_string())),
+        }
+
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            file,
+            env: Box::new(|name| env::var(name).ok()),
+            require_database: true,
+        })
+    }
+
+    /// Stops [`validate`](Self::validate) requiring `DATABASE_URL`, for
+    /// callers that bring their own database, like mock mode.
+    #[must_use]
+    pub fn database_optional(mut self) -> Self {
+        self.require_database = false;
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_env(mut self, env: impl Fn(&str) -> Option<String> + 'static) -> Self {
+        self.env = Box::new(env);
+        self
+    }
+
+    /// The config file in use, if any.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The value of `name`, from the environment or else the file.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<String> {
+        (self.env)(name).or_else(|| self.file.get(name).map(|v| v.value.clone()))
+    }
+
+    /// Where `name` was set, for error messages.
+    fn describe(&self, name: &str) -> String {
+        if (self.env)(name).is_some() {
+            return name.to_string();
+        }
+        match (self.file.get(name), &self.path) {
+            (Some(value), Some(path)) => format!("{name} ({} in {})", value.path, path.display()),
+            _ => name.to_string(),
+        }
+    }
+
+    /// Checks every setting and reports all problems together.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] listing missing required keys,
+    /// malformed values, and unknown keys in the file.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut issues = Vec::new();
+
+        if self.require_database
+            && self
+                .get("DATABASE_URL")
+                .is_none_or(|url| url.trim().is_empty())
+        {
+            issues.push(ConfigIssue::Missing {
+                key: "DATABASE_URL".to_string(),
+                reason: "to connect to PostgreSQL",
+            });
+        }
+        let production = self.get("ENVIRONMENT").is_some_and(|v| v == "production");
+        if production && self.get("JWT_SECRET").is_none_or(|s| s.trim().is_empty()) {
+            issues.push(ConfigIssue::Missing {
+                key: "JWT_SECRET".to_string(),
+                reason: "when ENVIRONMENT=production",
+            });
+        }
+
+        if self.get("STORAGE_BACKEND").is_some_and(|v| v == "s3") {
+            for key in [
+                "STORAGE_S3_BUCKET",
+                "STORAGE_S3_ACCESS_KEY_ID",
+                "STORAGE_S3_SECRET_ACCESS_KEY",
+            ] {
+                if self.get(key).is_none_or(|v| v.trim().is_empty()) {
+                    issues.push(ConfigIssue::Missing {
+                        key: key.to_string(),
+                        reason: "when STORAGE_BACKEND=s3",
+                    });
+                }
+            }
+        }
+
+        // An unsupported JWT_ALGORITHM is reported with the other values below
+        if let Err(e) = JwtKeySet::from_lookup(|name| self.get(name))
+            && !matches!(e, JwtKeyError::UnsupportedAlgorithm(_))
+        {
+            issues.push(e.into());
+        }
+
+        for (name, kind) in KEYS {
+            if let Some(value) = self.get(name)
+                && !kind.accepts(&value)
+            {
+                issues.push(ConfigIssue::Invalid {
+                    key: self.describe(name),
+                    value,
+                    expected: kind.expected(),
+                });
+            }
+        }
+
+        for (name, value) in &self.file {
+            if !KEYS.iter().any(|(key, _)| key == name) {
+                issues.push(ConfigIssue::Unknown {
+                    key: match &self.path {
+                        Some(path) => format!("{} in {}", value.path, path.display()),
+                        None => value.path.clone(),
+                    },
+                });
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(issues))
+        }
+    }
+}
+
+/// Flattens nested tables into variable names, `[jwt] secret` becoming
+/// `JWT_SECRET`.
+fn flatten(
+    table: &serde_json::Map<String, Value>,
+    name_prefix: &str,
+    path_prefix: &str,
+    out: &mut BTreeMap<String, FileValue>,
+) -> Result<(), String> {
+    for (key, value) in table {
+        let name = format!(
+            "{name_prefix}{}",
+            key.to_ascii_uppercase().replace('-', "_")
+        );
+        let path = format!("{path_prefix}{key}");
+        let value = match value {
+            Value::Object(table) => {
+                flatten(table, &format!("{name}_"), &format!("{path}."), out)?;
+                continue;
+            }
+            Value::Null => continue,
+            Value::Array(items) => items
+                .iter()
+                .map(|item| scalar(item).ok_or_else(|| format!("{path} must be a list of values")))
+                .collect::<Result<Vec<_>, _>>()?
+                .join(","),
+            other => scalar(other).unwrap_or_default(),
+        };
+        out.insert(name, FileValue { path, value });
+    }
+    Ok(())
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(file: &str, contents: &str, env: &'static [(&str, &str)]) -> ConfigSource {
+        ConfigSource::parse(Path::new(file), contents)
+            .unwrap()
+            .with_env(|name| {
+                env.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            })
+    }
+
+    fn issues(source: &ConfigSource) -> Vec<ConfigIssue> {
+        match source.validate() {
+            Ok(()) => Vec::new(),
+            Err(ConfigError::Invalid(issues)) => issues,
+            Err(other) => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn test_toml_tables_flatten_to_variable_names() {
+        let source = source(
+            "chalkbyte.toml",
+            r#"
+            database_url = "postgres://localhost/chalkbyte"
+            allowed_origins = ["https://a.example", "https://b.example"]
+
+            [jwt]
+            access_expiry = 1800
+
+            [rate_limit.auth]
+            burst_size = 3
+            "#,
+            &[],
+        );
+
+        assert_eq!(source.get("JWT_ACCESS_EXPIRY").as_deref(), Some("1800"));
+        assert_eq!(
+            source.get("RATE_LIMIT_AUTH_BURST_SIZE").as_deref(),
+            Some("3")
+        );
+        assert_eq!(
+            source.get("ALLOWED_ORIGINS").as_deref(),
+            Some("https://a.example,https://b.example")
+        );
+        assert!(issues(&source).is_empty());
+    }
+
+    #[test]
+    fn test_environment_overrides_file() {
+        let source = source(
+            "config.yaml",
+            "database_url: postgres://file/chalkbyte\nsmtp:\n  port: 2525\n",
+            &[("DATABASE_URL", "postgres://env/chalkbyte")],
+        );
+
+        assert_eq!(
+            source.get("DATABASE_URL").as_deref(),
+            Some("postgres://env/chalkbyte")
+        );
+        assert_eq!(source.get("SMTP_PORT").as_deref(), Some("2525"));
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let source = source(
+            "chalkbyte.toml",
+            r#"
+            environment = "production"
+            port = 70000
+            log_format = "xml"
+
+            [jwt]
+            acess_expiry = 60
+            "#,
+            &[("SMTP_ENABLED", "yes")],
+        );
+
+        assert_eq!(
+            issues(&source),
+            vec![
+                ConfigIssue::Missing {
+                    key: "DATABASE_URL".to_string(),
+                    reason: "to connect to PostgreSQL",
+                },
+                ConfigIssue::Missing {
+                    key: "JWT_SECRET".to_string(),
+                    reason: "when ENVIRONMENT=production",
+                },
+                ConfigIssue::Invalid {
+                    key: "PORT (port in chalkbyte.toml)".to_string(),
+                    value: "70000".to_string(),
+                    expected: "a port number from 1 to 65535".to_string(),
+                },
+                ConfigIssue::Invalid {
+                    key: "SMTP_ENABLED".to_string(),
+                    value: "yes".to_string(),
+                    expected: "true or false".to_string(),
+                },
+                ConfigIssue::Invalid {
+                    key: "LOG_FORMAT (log_format in chalkbyte.toml)".to_string(),
+                    value: "xml".to_string(),
+                    expected: "one of pretty, json".to_string(),
+                },
+                ConfigIssue::Unknown {
+                    key: "jwt.acess_expiry in chalkbyte.toml".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reports_jwt_key_problems() {
+        let source = source(
+            "chalkbyte.toml",
+            r#"
+            database_url = "postgres://localhost/chalkbyte"
+
+            [jwt]
+            algorithm = "RS256"
+            private_key_file = "/nonexistent/jwt.pem"
+            "#,
+            &[],
+        );
+
+        assert_eq!(
+            issues(&source),
+            vec![ConfigIssue::JwtKeys(JwtKeyError::Read {
+                key: "JWT_PRIVATE_KEY_FILE",
+                path: "/nonexistent/jwt.pem".to_string(),
+                reason: "No such file or directory (os error 2)".to_string(),
+            })]
+        );
+    }
+
+    #[test]
+    fn test_example_file_is_valid() {
+        let source = source(
+            "chalkbyte.example.toml",
+            include_str!("../../../chalkbyte.example.toml"),
+            &[],
+        );
+        assert_eq!(issues(&source), Vec::new());
+    }
+
+    #[test]
+    fn test_unsupported_and_malformed_files() {
+        assert!(matches!(
+            ConfigSource::parse(Path::new("chalkbyte.ini"), ""),
+            Err(ConfigError::UnsupportedFormat { .. })
+        ));
+        assert!(matches!(
+            ConfigSource::parse(Path::new("chalkbyte.toml"), "port = "),
+            Err(ConfigError::Parse { .. })
+        ));
+        assert!(matches!(
+            ConfigSource::parse(Path::new("config.yaml"), "cors:\n  - a: 1\n"),
+            Err(ConfigError::Parse { .. })
+        ));
+    }
+}
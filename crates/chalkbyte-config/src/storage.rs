@@ -0,0 +1,206 @@
+//! File storage configuration.
+//!
+//! Avatars and student documents are private files kept by a storage
+//! backend: a directory on local disk, or an S3-compatible bucket. Clients
+//! download them through presigned URLs that expire after a short time.
+//!
+//! # Environment Variables
+//!
+//! - `STORAGE_BACKEND`: `local` or `s3` (default: `local`)
+//! - `STORAGE_LOCAL_DIR`: Directory of the local backend (default: `./storage`)
+//! - `STORAGE_PUBLIC_URL`: Public base URL of this API, used to build the
+//!   local backend's download links (default: `http://localhost:3000`)
+//! - `STORAGE_S3_BUCKET`: Bucket name; required with `STORAGE_BACKEND=s3`
+//! - `STORAGE_S3_REGION`: Bucket region (default: `us-east-1`)
+//! - `STORAGE_S3_ENDPOINT`: Endpoint of an S3-compatible service such as
+//!   MinIO (default: `https://s3.{region}.amazonaws.com`)
+//! - `STORAGE_S3_ACCESS_KEY_ID`, `STORAGE_S3_SECRET_ACCESS_KEY`: Credentials;
+//!   required with `STORAGE_BACKEND=s3`
+//! - `STORAGE_S3_PATH_STYLE`: Address the bucket as `{endpoint}/{bucket}`
+//!   instead of `{bucket}.{endpoint host}`, as most self-hosted services
+//!   need (default: `false`)
+//! - `STORAGE_URL_TTL_SECONDS`: How long download URLs stay valid
+//!   (default: `300`)
+//! - `STORAGE_SIGNING_SECRET`: Secret the local backend signs download links
+//!   with (default: `JWT_SECRET`), so rotating token keys does not need to
+//!   break links that are still valid
+//! - `STORAGE_SCHOOL_QUOTA_MB`: Storage each school may use unless a system
+//!   admin sets its own quota (default: `1024`)
+//!
+//! # Example
+//!
+//! ```ignore
+//! use crate::config::storage::{StorageConfig, StorageDriver};
+//!
+//! let config = StorageConfig::from_env();
+//! if config.driver == StorageDriver::S3 {
+//!     // connect to the bucket
+//! }
+//! ```
+
+use std::env;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Where private files are kept.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StorageDriver {
+    /// A directory on the API server's disk.
+    #[default]
+    Local,
+    /// An S3-compatible bucket.
+    S3,
+}
+
+impl FromStr for StorageDriver {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "s3" => Ok(Self::S3),
+            other => Err(format!(
+                "Invalid STORAGE_BACKEND '{other}'; expected local or s3"
+            )),
+        }
+    }
+}
+
+/// Settings of the S3-compatible backend.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct S3StorageConfig {
+    /// Endpoint URL, without a trailing slash.
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Whether the bucket is part of the path rather than the host name.
+    pub path_style: bool,
+}
+
+/// File storage settings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageConfig {
+    pub driver: StorageDriver,
+    /// Directory of the local backend.
+    pub local_dir: PathBuf,
+    /// Public base URL of the API, without a trailing slash.
+    pub public_url: String,
+    pub s3: S3StorageConfig,
+    /// How long download URLs stay valid.
+    pub url_ttl: Duration,
+    /// Secret the local backend signs download links with.
+    pub signing_secret: String,
+    /// Bytes each school may store unless it has its own quota.
+    pub default_school_quota_bytes: i64,
+}
+
+impl StorageConfig {
+    /// Creates a new `StorageConfig` from environment variables.
+    ///
+    /// An unrecognised `STORAGE_BACKEND` falls back to `local`; validation
+    /// reports it before the API starts.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Creates a new `StorageConfig` from the variables `lookup` returns.
+    #[must_use]
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let number = |name: &str, default: u64| {
+            lookup(name)
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .unwrap_or(default)
+        };
+        let region = lookup("STORAGE_S3_REGION").unwrap_or_else(|| "us-east-1".to_string());
+        let endpoint = lookup("STORAGE_S3_ENDPOINT")
+            .unwrap_or_else(|| format!("https://s3.{region}.amazonaws.com"));
+
+        Self {
+            driver: lookup("STORAGE_BACKEND")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_default(),
+            local_dir: lookup("STORAGE_LOCAL_DIR")
+                .map_or_else(|| PathBuf::from("./storage"), PathBuf::from),
+            public_url: lookup("STORAGE_PUBLIC_URL")
+                .unwrap_or_else(|| "http://localhost:3000".to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            s3: S3StorageConfig {
+                endpoint: endpoint.trim_end_matches('/').to_string(),
+                region,
+                bucket: lookup("STORAGE_S3_BUCKET").unwrap_or_default(),
+                access_key_id: lookup("STORAGE_S3_ACCESS_KEY_ID").unwrap_or_default(),
+                secret_access_key: lookup("STORAGE_S3_SECRET_ACCESS_KEY").unwrap_or_default(),
+                path_style: lookup("STORAGE_S3_PATH_STYLE").is_some_and(|v| v.trim() == "true"),
+            },
+            url_ttl: Duration::from_secs(number("STORAGE_URL_TTL_SECONDS", 300)),
+            signing_secret: lookup("STORAGE_SIGNING_SECRET")
+                .filter(|s| !s.trim().is_empty())
+                .or_else(|| lookup("JWT_SECRET"))
+                .unwrap_or_else(|| "your-secret-key-change-in-production".to_string()),
+            default_school_quota_bytes: i64::try_from(
+                number("STORAGE_SCHOOL_QUOTA_MB", 1024).saturating_mul(1024 * 1024),
+            )
+            .unwrap_or(i64::MAX),
+        }
+    }
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self::from_lookup(|_| None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let config = StorageConfig::default();
+
+        assert_eq!(config.driver, StorageDriver::Local);
+        assert_eq!(config.s3.endpoint, "https://s3.us-east-1.amazonaws.com");
+        assert_eq!(config.url_ttl, Duration::from_secs(300));
+        assert_eq!(config.default_school_quota_bytes, 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_s3_from_lookup() {
+        let config = StorageConfig::from_lookup(|name| match name {
+            "STORAGE_BACKEND" => Some("S3".to_string()),
+            "STORAGE_S3_ENDPOINT" => Some("http://minio:9000/".to_string()),
+            "STORAGE_S3_BUCKET" => Some("chalkbyte".to_string()),
+            "STORAGE_S3_PATH_STYLE" => Some("true".to_string()),
+            "STORAGE_SCHOOL_QUOTA_MB" => Some("5".to_string()),
+            _ => None,
+        });
+
+        assert_eq!(config.driver, StorageDriver::S3);
+        assert_eq!(config.s3.endpoint, "http://minio:9000");
+        assert_eq!(config.s3.region, "us-east-1");
+        assert!(config.s3.path_style);
+        assert_eq!(config.default_school_quota_bytes, 5 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_signing_secret_falls_back_to_jwt_secret() {
+        let config = StorageConfig::from_lookup(|name| match name {
+            "JWT_SECRET" => Some("jwt-secret".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.signing_secret, "jwt-secret");
+
+        let config = StorageConfig::from_lookup(|name| match name {
+            "JWT_SECRET" => Some("jwt-secret".to_string()),
+            "STORAGE_SIGNING_SECRET" => Some("storage-secret".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.signing_secret, "storage-secret");
+    }
+}
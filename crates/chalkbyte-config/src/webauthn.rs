@@ -0,0 +1,56 @@
+//! WebAuthn (passkey) configuration.
+//!
+//! Passkeys are bound to a relying party: the domain the browser checks
+//! credentials against and the origin the frontend is served from.
+//!
+//! # Environment Variables
+//!
+//! - `WEBAUTHN_RP_ID`: Relying party ID, the registrable domain (default: "localhost")
+//! - `WEBAUTHN_RP_ORIGIN`: Origin of the frontend (default: "http://localhost:3000")
+//! - `WEBAUTHN_RP_NAME`: Name shown by authenticators (default: "Chalkbyte")
+//!
+//! Changing `WEBAUTHN_RP_ID` invalidates every registered passkey.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use crate::config::webauthn::WebauthnConfig;
+//!
+//! let config = WebauthnConfig::from_env();
+//! ```
+
+use std::env;
+
+/// Relying party settings for passkey registration and login.
+#[derive(Clone, Debug)]
+pub struct WebauthnConfig {
+    /// Domain passkeys are scoped to, e.g. `chalkbyte.com`.
+    pub rp_id: String,
+
+    /// Origin the browser reports, e.g. `https://app.chalkbyte.com`.
+    ///
+    /// Must be the relying party domain or one of its subdomains.
+    pub rp_origin: String,
+
+    /// Human-readable name shown when registering a passkey.
+    pub rp_name: String,
+}
+
+impl WebauthnConfig {
+    /// Creates a new `WebauthnConfig` from environment variables.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Creates a new `WebauthnConfig` from the variables `lookup` returns.
+    #[must_use]
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            rp_id: lookup("WEBAUTHN_RP_ID").unwrap_or_else(|| "localhost".to_string()),
+            rp_origin: lookup("WEBAUTHN_RP_ORIGIN")
+                .unwrap_or_else(|| "http://localhost:3000".to_string()),
+            rp_name: lookup("WEBAUTHN_RP_NAME").unwrap_or_else(|| "Chalkbyte".to_string()),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Error message catalog.
+//!
+//! Maps each [`ErrorCode`] to its default English message and holds the
+//! localized message bundles registered at startup. An error response uses
+//! the bundle for the request's locale (from `Accept-Language`, see
+//! [`request_context::current_locale`]) when the bundle has a message for the
+//! error's code, and the error's own English message otherwise.
+//!
+//! Bundles implement [`MessageBundle`]. A `HashMap<ErrorCode, String>` is
+//! one, so a bundle can be loaded from a JSON object keyed by code:
+//!
+//! ```ignore
+//! use std::collections::HashMap;
+//! use chalkbyte_core::catalog;
+//! use chalkbyte_core::ErrorCode;
+//!
+//! let french: HashMap<ErrorCode, String> =
+//!     serde_json::from_str(r#"{"LEVEL_NOT_FOUND": "Niveau introuvable"}"#)?;
+//! catalog::register_bundle("fr", french);
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::error_codes::ErrorCode;
+use crate::request_context;
+
+/// Localized messages for one locale.
+pub trait MessageBundle: Send + Sync {
+    /// The message for `code`, or `None` to fall back to the English message.
+    fn message(&self, code: ErrorCode) -> Option<String>;
+}
+
+impl MessageBundle for HashMap<ErrorCode, String> {
+    fn message(&self, code: ErrorCode) -> Option<String> {
+        self.get(&code).cloned()
+    }
+}
+
+type Bundles = RwLock<HashMap<String, Arc<dyn MessageBundle>>>;
+
+fn bundles() -> &'static Bundles {
+    static BUNDLES: OnceLock<Bundles> = OnceLock::new();
+    BUNDLES.get_or_init(Default::default)
+}
+
+/// Registers the message bundle for a locale, replacing any previous one.
+///
+/// Locales are language tags such as `fr` or `pt-BR`, matched
+/// case-insensitively. A request for `pt-BR` falls back to the `pt` bundle.
+pub fn register_bundle(locale: &str, bundle: impl MessageBundle + 'static) {
+    bundles()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(locale.to_ascii_lowercase(), Arc::new(bundle));
+}
+
+/// The localized message for `code` in `locale`, if a bundle provides one.
+#[must_use]
+pub fn localized_message(code: ErrorCode, locale: &str) -> Option<String> {
+    let locale = locale.to_ascii_lowercase();
+    let language = locale.split('-').next().unwrap_or(&locale);
+    let bundles = bundles().read().unwrap_or_else(|e| e.into_inner());
+
+    [locale.as_str(), language]
+        .into_iter()
+        .find_map(|tag| bundles.get(tag).and_then(|bundle| bundle.message(code)))
+}
+
+/// The localized message for `code` in the current request's locale, if any.
+#[must_use]
+pub fn current_localized_message(code: ErrorCode) -> Option<String> {
+    localized_message(code, &request_context::current_locale()?)
+}
+
+/// The default English message for a code.
+#[must_use]
+pub fn default_message(code: ErrorCode) -> &'static str {
+    match code {
+        ErrorCode::BadRequest => "Bad request",
+        ErrorCode::Unauthorized => "Unauthorized",
+        ErrorCode::Forbidden => "Access denied",
+        ErrorCode::NotFound => "Resource not found",
+        ErrorCode::Conflict => "The resource was changed by another request",
+        ErrorCode::PayloadTooLarge => "Request body is too large",
+        ErrorCode::UnprocessableEntity => "The request could not be processed",
+        ErrorCode::TooManyRequests => "Too many requests, please try again later",
+        ErrorCode::InternalError => "Internal server error",
+        ErrorCode::ServiceUnavailable => "Service temporarily unavailable",
+        ErrorCode::ValidationFailed => "Validation error",
+        ErrorCode::InvalidQuery => "Query parameter error",
+        ErrorCode::InvalidForm => "Form parsing error",
+        ErrorCode::InvalidSort => "Invalid sort field",
+        ErrorCode::InvalidFilter => "Invalid filter expression",
+        ErrorCode::InvalidCredentials => "Invalid email or password",
+        ErrorCode::InvalidToken => "Invalid or expired token",
+        ErrorCode::InvalidRefreshToken => "Invalid or expired refresh token",
+        ErrorCode::NoAssociatedSchool => "User has no associated school",
+        ErrorCode::SessionNotFound => "Session not found",
+        ErrorCode::SchoolNotFound => "School not found",
+        ErrorCode::SchoolNameConflict => "School name already exists",
+        ErrorCode::UserNotFound => "User not found",
+        ErrorCode::EmailTaken => "User with this email already exists",
+        ErrorCode::StudentNotFound => "Student not found",
+        ErrorCode::LevelNotFound => "Level not found",
+        ErrorCode::LevelNameConflict => "A level with this name already exists in this school",
+        ErrorCode::BranchNotFound => "Branch not found",
+        ErrorCode::BranchNameConflict => "Branch with this name already exists for this level",
+        ErrorCode::AcademicSessionNotFound => "Academic session not found",
+        ErrorCode::AcademicSessionNameConflict => {
+            "An academic session with this name already exists in this school"
+        }
+        ErrorCode::TermNotFound => "Term not found",
+        ErrorCode::AttendanceCorrectionWindowClosed => {
+            "The correction window for this attendance date has closed"
+        }
+        ErrorCode::FeatureFlagNotFound => "Feature flag not found",
+        ErrorCode::FeatureFlagKeyConflict => "A feature flag with this key already exists",
+        ErrorCode::FeatureDisabled => "This feature is not enabled for your school",
+        ErrorCode::DocumentNotFound => "Document not found",
+        ErrorCode::StorageQuotaExceeded => "The school's storage quota is used up",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_code_has_a_message() {
+        for code in ErrorCode::ALL {
+            assert!(!default_message(*code).is_empty(), "{code} has no message");
+        }
+    }
+
+    #[test]
+    fn test_localized_message_falls_back_to_language() {
+        let bundle: HashMap<ErrorCode, String> =
+            serde_json::from_str(r#"{"TERM_NOT_FOUND": "Período não encontrado"}"#).unwrap();
+        register_bundle("xq", bundle);
+
+        assert_eq!(
+            localized_message(ErrorCode::TermNotFound, "XQ-br").as_deref(),
+            Some("Período não encontrado")
+        );
+        assert_eq!(localized_message(ErrorCode::LevelNotFound, "xq"), None);
+        assert_eq!(localized_message(ErrorCode::TermNotFound, "xz"), None);
+    }
+}
@@ -0,0 +1,270 @@
+//! Optimistic concurrency with `ETag` and `If-Match`.
+//!
+//! Resources that can be updated concurrently send an `ETag` derived from
+//! their `updated_at` column. A client that wants to avoid overwriting
+//! someone else's change sends that ETag back in `If-Match` with its update.
+//! If the row has changed since, the update is not applied and the response
+//! is `412 Precondition Failed` with the current representation and its
+//! ETag, so the client can merge and retry without another GET.
+//!
+//! Updates without `If-Match`, or with `If-Match: *`, apply unconditionally.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_core::conditional::{Conditional, IfMatch, WithEtag};
+//!
+//! async fn get_level(/* ... */) -> Result<WithEtag<LevelWithStats>, AppError> {
+//!     Ok(WithEtag(LevelService::get_level_by_id(/* ... */).await?))
+//! }
+//!
+//! async fn update_level(if_match: IfMatch, /* ... */) -> Result<Conditional<Level>, AppError> {
+//!     LevelService::update_level(/* ... */, &if_match, dto).await
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+
+use axum::{
+    Json,
+    extract::FromRequestParts,
+    http::{HeaderValue, StatusCode, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::{
+    IntoResponses, ToSchema,
+    openapi::{
+        ContentBuilder, Ref, RefOr, ResponseBuilder, header::HeaderBuilder,
+        response::Response as OpenApiResponse,
+    },
+};
+
+/// A resource whose `updated_at` changes on every write.
+pub trait Versioned {
+    /// When the resource was last written.
+    fn updated_at(&self) -> DateTime<Utc>;
+
+    /// Strong ETag of the current version.
+    fn etag(&self) -> String {
+        format!("\"{:x}\"", self.updated_at().timestamp_micros())
+    }
+}
+
+/// The `If-Match` request header.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IfMatch(Option<Vec<String>>);
+
+impl IfMatch {
+    /// Parses a header value: `*` or a comma-separated list of ETags.
+    pub fn parse(value: &str) -> Self {
+        if value.trim() == "*" {
+            return Self(None);
+        }
+        Self(Some(
+            value.split(',').map(|tag| tag.trim().to_string()).collect(),
+        ))
+    }
+
+    /// Whether every version satisfies the precondition, as when the header
+    /// is missing or `*`.
+    pub fn is_any(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Whether the client's precondition holds for `current`. Weak ETags
+    /// never match, as `If-Match` uses strong comparison.
+    pub fn matches(&self, current: &impl Versioned) -> bool {
+        match &self.0 {
+            None => true,
+            Some(tags) => tags.contains(&current.etag()),
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for IfMatch {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(match parts.headers.get(header::IF_MATCH) {
+            // A value that is not text cannot match any ETag
+            Some(value) => value
+                .to_str()
+                .map_or_else(|_| Self(Some(Vec::new())), Self::parse),
+            None => Self::default(),
+        })
+    }
+}
+
+fn json_with_etag<T: Serialize + Versioned>(status: StatusCode, value: T) -> Response {
+    let etag = HeaderValue::from_str(&value.etag()).ok();
+    let mut response = (status, Json(value)).into_response();
+    if let Some(etag) = etag {
+        response.headers_mut().insert(header::ETAG, etag);
+    }
+    response
+}
+
+fn documented<T: ToSchema>(status: &str, description: &str) -> (String, RefOr<OpenApiResponse>) {
+    let content = ContentBuilder::new()
+        .schema(Some(Ref::from_schema_name(T::name())))
+        .build();
+    let response = ResponseBuilder::new()
+        .description(description)
+        .content("application/json", content)
+        .header(
+            "ETag",
+            HeaderBuilder::new()
+                .description(Some("Send in `If-Match` to update this version"))
+                .build(),
+        )
+        .build();
+
+    (status.to_string(), response.into())
+}
+
+/// `200 OK` with `T` as the JSON body and its version as the `ETag`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WithEtag<T>(pub T);
+
+impl<T: Serialize + Versioned> IntoResponse for WithEtag<T> {
+    fn into_response(self) -> Response {
+        json_with_etag(StatusCode::OK, self.0)
+    }
+}
+
+impl<T: ToSchema> IntoResponses for WithEtag<T> {
+    fn responses() -> BTreeMap<String, RefOr<OpenApiResponse>> {
+        BTreeMap::from([documented::<T>("200", "OK")])
+    }
+}
+
+/// Outcome of an update guarded by [`IfMatch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conditional<T> {
+    /// The update was applied; holds the updated resource.
+    Updated(T),
+    /// The resource changed since the client's ETag; holds it unchanged.
+    PreconditionFailed(T),
+}
+
+impl<T> Conditional<T> {
+    /// The updated resource, or `None` if the precondition failed.
+    pub fn updated(self) -> Option<T> {
+        match self {
+            Self::Updated(value) => Some(value),
+            Self::PreconditionFailed(_) => None,
+        }
+    }
+
+    /// Converts the resource either way.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Conditional<U> {
+        match self {
+            Self::Updated(value) => Conditional::Updated(f(value)),
+            Self::PreconditionFailed(value) => Conditional::PreconditionFailed(f(value)),
+        }
+    }
+}
+
+impl<T: Serialize + Versioned> IntoResponse for Conditional<T> {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Updated(value) => json_with_etag(StatusCode::OK, value),
+            Self::PreconditionFailed(value) => {
+                json_with_etag(StatusCode::PRECONDITION_FAILED, value)
+            }
+        }
+    }
+}
+
+impl<T: ToSchema> IntoResponses for Conditional<T> {
+    fn responses() -> BTreeMap<String, RefOr<OpenApiResponse>> {
+        BTreeMap::from([
+            documented::<T>("200", "Updated"),
+            documented::<T>(
+                "412",
+                "Changed since the ETag sent in If-Match; the current version is returned",
+            ),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use chrono::TimeZone;
+
+    #[derive(Serialize, ToSchema)]
+    struct Widget {
+        updated_at: DateTime<Utc>,
+    }
+
+    impl Versioned for Widget {
+        fn updated_at(&self) -> DateTime<Utc> {
+            self.updated_at
+        }
+    }
+
+    fn widget() -> Widget {
+        Widget {
+            updated_at: Utc.timestamp_micros(1_700_000_000_123_456).unwrap(),
+        }
+    }
+
+    async fn if_match(value: Option<&str>) -> IfMatch {
+        let mut builder = Request::builder();
+        if let Some(value) = value {
+            builder = builder.header("If-Match", value);
+        }
+        let (mut parts, _) = builder.body(()).unwrap().into_parts();
+        IfMatch::from_request_parts(&mut parts, &()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_if_match() {
+        let current = widget();
+        let etag = current.etag();
+
+        assert!(if_match(None).await.is_any());
+        assert!(!if_match(Some(&etag)).await.is_any());
+        assert!(if_match(None).await.matches(&current));
+        assert!(if_match(Some("*")).await.matches(&current));
+        assert!(if_match(Some(&etag)).await.matches(&current));
+        assert!(
+            if_match(Some(&format!("\"stale\", {etag}")))
+                .await
+                .matches(&current)
+        );
+        assert!(!if_match(Some("\"stale\"")).await.matches(&current));
+        assert!(!if_match(Some(&format!("W/{etag}"))).await.matches(&current));
+    }
+
+    #[test]
+    fn test_etag_changes_with_updated_at() {
+        let later = Widget {
+            updated_at: widget().updated_at + chrono::Duration::microseconds(1),
+        };
+        assert_ne!(widget().etag(), later.etag());
+    }
+
+    #[test]
+    fn test_conditional_responses() {
+        let response = Conditional::Updated(widget()).into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["etag"], widget().etag().as_str());
+
+        let response = Conditional::PreconditionFailed(widget()).into_response();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+        assert_eq!(response.headers()["etag"], widget().etag().as_str());
+
+        assert_eq!(
+            Conditional::<Widget>::responses()
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["200", "412"]
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Machine-readable error codes.
+//!
+//! Every error response carries a [`ErrorCode`] in its `code` field next to
+//! the human-readable `error` message. Clients branch on the code, which is
+//! stable, rather than on the message, which may be reworded or localized.
+//!
+//! Errors created without a specific code get a generic one derived from
+//! their status, such as `NOT_FOUND`. Specific codes come with a default
+//! English message in the [`catalog`](crate::catalog) and are raised with
+//! [`AppError::from_code`](crate::AppError::from_code).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_core::{AppError, ErrorCode};
+//!
+//! let error = AppError::from_code(ErrorCode::LevelNotFound);
+//! assert_eq!(error.code, ErrorCode::LevelNotFound);
+//! ```
+
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Stable identifier of an error, serialized in `SCREAMING_SNAKE_CASE`.
+///
+/// New variants may be added at any time; clients should treat unknown codes
+/// like the generic code for the response status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    // Generic codes, one per status
+    BadRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Conflict,
+    PayloadTooLarge,
+    UnprocessableEntity,
+    TooManyRequests,
+    InternalError,
+    ServiceUnavailable,
+
+    // Request parsing
+    ValidationFailed,
+    InvalidQuery,
+    InvalidForm,
+    InvalidSort,
+    InvalidFilter,
+
+    // Authentication
+    InvalidCredentials,
+    InvalidToken,
+    InvalidRefreshToken,
+    NoAssociatedSchool,
+    SessionNotFound,
+
+    // Schools
+    SchoolNotFound,
+    SchoolNameConflict,
+
+    // Users and students
+    UserNotFound,
+    EmailTaken,
+    StudentNotFound,
+
+    // Levels and branches
+    LevelNotFound,
+    LevelNameConflict,
+    BranchNotFound,
+    BranchNameConflict,
+
+    // Academic calendar
+    AcademicSessionNotFound,
+    AcademicSessionNameConflict,
+    TermNotFound,
+
+    // Attendance
+    AttendanceCorrectionWindowClosed,
+
+    // Feature flags
+    FeatureFlagNotFound,
+    FeatureFlagKeyConflict,
+    FeatureDisabled,
+
+    // File storage
+    DocumentNotFound,
+    StorageQuotaExceeded,
+}
+
+impl ErrorCode {
+    /// Every code, in declaration order.
+    pub const ALL: &'static [ErrorCode] = &[
+        Self::BadRequest,
+        Self::Unauthorized,
+        Self::Forbidden,
+        Self::NotFound,
+        Self::Conflict,
+        Self::PayloadTooLarge,
+        Self::UnprocessableEntity,
+        Self::TooManyRequests,
+        Self::InternalError,
+        Self::ServiceUnavailable,
+        Self::ValidationFailed,
+        Self::InvalidQuery,
+        Self::InvalidForm,
+        Self::InvalidSort,
+        Self::InvalidFilter,
+        Self::InvalidCredentials,
+        Self::InvalidToken,
+        Self::InvalidRefreshToken,
+        Self::NoAssociatedSchool,
+        Self::SessionNotFound,
+        Self::SchoolNotFound,
+        Self::SchoolNameConflict,
+        Self::UserNotFound,
+        Self::EmailTaken,
+        Self::StudentNotFound,
+        Self::LevelNotFound,
+        Self::LevelNameConflict,
+        Self::BranchNotFound,
+        Self::BranchNameConflict,
+        Self::AcademicSessionNotFound,
+        Self::AcademicSessionNameConflict,
+        Self::TermNotFound,
+        Self::AttendanceCorrectionWindowClosed,
+        Self::FeatureFlagNotFound,
+        Self::FeatureFlagKeyConflict,
+        Self::FeatureDisabled,
+        Self::DocumentNotFound,
+        Self::StorageQuotaExceeded,
+    ];
+
+    /// The generic code for a status, used for errors without a specific code.
+    #[must_use]
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized,
+            StatusCode::FORBIDDEN => Self::Forbidden,
+            StatusCode::NOT_FOUND => Self::NotFound,
+            StatusCode::CONFLICT => Self::Conflict,
+            StatusCode::PAYLOAD_TOO_LARGE => Self::PayloadTooLarge,
+            StatusCode::UNPROCESSABLE_ENTITY => Self::UnprocessableEntity,
+            StatusCode::TOO_MANY_REQUESTS => Self::TooManyRequests,
+            StatusCode::SERVICE_UNAVAILABLE => Self::ServiceUnavailable,
+            s if s.is_server_error() => Self::InternalError,
+            _ => Self::BadRequest,
+        }
+    }
+
+    /// The status an error with this code is returned with.
+    #[must_use]
+    pub fn status(self) -> StatusCode {
+        match self {
+            Self::BadRequest
+            | Self::InvalidQuery
+            | Self::InvalidForm
+            | Self::InvalidSort
+            | Self::InvalidFilter
+            | Self::SchoolNameConflict
+            | Self::LevelNameConflict
+            | Self::BranchNameConflict
+            | Self::AcademicSessionNameConflict
+            | Self::FeatureFlagKeyConflict => StatusCode::BAD_REQUEST,
+            Self::Unauthorized
+            | Self::InvalidCredentials
+            | Self::InvalidToken
+            | Self::InvalidRefreshToken => StatusCode::UNAUTHORIZED,
+            Self::Forbidden
+            | Self::NoAssociatedSchool
+            | Self::AttendanceCorrectionWindowClosed
+            | Self::FeatureDisabled => StatusCode::FORBIDDEN,
+            Self::NotFound
+            | Self::SchoolNotFound
+            | Self::UserNotFound
+            | Self::StudentNotFound
+            | Self::LevelNotFound
+            | Self::BranchNotFound
+            | Self::AcademicSessionNotFound
+            | Self::TermNotFound
+            | Self::FeatureFlagNotFound
+            | Self::DocumentNotFound
+            | Self::SessionNotFound => StatusCode::NOT_FOUND,
+            Self::Conflict => StatusCode::CONFLICT,
+            Self::PayloadTooLarge | Self::StorageQuotaExceeded => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::UnprocessableEntity | Self::ValidationFailed | Self::EmailTaken => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            Self::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
+            Self::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    /// The code as it appears in responses, e.g. `LEVEL_NOT_FOUND`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BadRequest => "BAD_REQUEST",
+            Self::Unauthorized => "UNAUTHORIZED",
+            Self::Forbidden => "FORBIDDEN",
+            Self::NotFound => "NOT_FOUND",
+            Self::Conflict => "CONFLICT",
+            Self::PayloadTooLarge => "PAYLOAD_TOO_LARGE",
+            Self::UnprocessableEntity => "UNPROCESSABLE_ENTITY",
+            Self::TooManyRequests => "TOO_MANY_REQUESTS",
+            Self::InternalError => "INTERNAL_ERROR",
+            Self::ServiceUnavailable => "SERVICE_UNAVAILABLE",
+            Self::ValidationFailed => "VALIDATION_FAILED",
+            Self::InvalidQuery => "INVALID_QUERY",
+            Self::InvalidForm => "INVALID_FORM",
+            Self::InvalidSort => "INVALID_SORT",
+            Self::InvalidFilter => "INVALID_FILTER",
+            Self::InvalidCredentials => "INVALID_CREDENTIALS",
+            Self::InvalidToken => "INVALID_TOKEN",
+            Self::InvalidRefreshToken => "INVALID_REFRESH_TOKEN",
+            Self::NoAssociatedSchool => "NO_ASSOCIATED_SCHOOL",
+            Self::SessionNotFound => "SESSION_NOT_FOUND",
+            Self::SchoolNotFound => "SCHOOL_NOT_FOUND",
+            Self::SchoolNameConflict => "SCHOOL_NAME_CONFLICT",
+            Self::UserNotFound => "USER_NOT_FOUND",
+            Self::EmailTaken => "EMAIL_TAKEN",
+            Self::StudentNotFound => "STUDENT_NOT_FOUND",
+            Self::LevelNotFound => "LEVEL_NOT_FOUND",
+            Self::LevelNameConflict => "LEVEL_NAME_CONFLICT",
+            Self::BranchNotFound => "BRANCH_NOT_FOUND",
+            Self::BranchNameConflict => "BRANCH_NAME_CONFLICT",
+            Self::AcademicSessionNotFound => "ACADEMIC_SESSION_NOT_FOUND",
+            Self::AcademicSessionNameConflict => "ACADEMIC_SESSION_NAME_CONFLICT",
+            Self::TermNotFound => "TERM_NOT_FOUND",
+            Self::AttendanceCorrectionWindowClosed => "ATTENDANCE_CORRECTION_WINDOW_CLOSED",
+            Self::FeatureFlagNotFound => "FEATURE_FLAG_NOT_FOUND",
+            Self::FeatureFlagKeyConflict => "FEATURE_FLAG_KEY_CONFLICT",
+            Self::FeatureDisabled => "FEATURE_DISABLED",
+            Self::DocumentNotFound => "DOCUMENT_NOT_FOUND",
+            Self::StorageQuotaExceeded => "STORAGE_QUOTA_EXCEEDED",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_str_matches_serde() {
+        for code in ErrorCode::ALL {
+            let json = serde_json::to_value(code).unwrap();
+            assert_eq!(json, code.as_str());
+            let parsed: ErrorCode = serde_json::from_value(json).unwrap();
+            assert_eq!(parsed, *code);
+        }
+    }
+
+    #[test]
+    fn test_generic_code_round_trips_status() {
+        for status in [
+            StatusCode::BAD_REQUEST,
+            StatusCode::UNAUTHORIZED,
+            StatusCode::FORBIDDEN,
+            StatusCode::NOT_FOUND,
+            StatusCode::CONFLICT,
+            StatusCode::UNPROCESSABLE_ENTITY,
+            StatusCode::TOO_MANY_REQUESTS,
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ] {
+            assert_eq!(ErrorCode::from_status(status).status(), status);
+        }
+        assert_eq!(
+            ErrorCode::from_status(StatusCode::GONE),
+            ErrorCode::BadRequest
+        );
+        assert_eq!(
+            ErrorCode::from_status(StatusCode::BAD_GATEWAY),
+            ErrorCode::InternalError
+        );
+    }
+}
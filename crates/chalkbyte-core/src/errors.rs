@@ -4,6 +4,22 @@
 //! throughout the application. It wraps various error sources and converts them
 //! into appropriate HTTP responses.
 //!
+//! Responses carry a machine-readable [`ErrorCode`] next to the message:
+//!
+//! ```json
+//! { "error": "Level not found", "code": "LEVEL_NOT_FOUND", "request_id": "..." }
+//! ```
+//!
+//! Validation failures also list each offending field:
+//!
+//! ```json
+//! {
+//!   "error": "Validation error",
+//!   "code": "VALIDATION_FAILED",
+//!   "fields": [{ "field": "name", "code": "length", "message": "Name is required" }]
+//! }
+//! ```
+//!
 //! # Example
 //!
 //! ```ignore
@@ -22,9 +38,70 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use serde::Serialize;
 use serde_json::json;
 use tracing::error;
-use validator::ValidationErrors;
+use utoipa::ToSchema;
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::catalog;
+use crate::error_codes::ErrorCode;
+use crate::request_context::current_request_id;
+
+/// One failed constraint in a validation error response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct FieldError {
+    /// Path of the field, e.g. `email` or `scores[2].score`
+    #[schema(example = "name")]
+    pub field: String,
+    /// The constraint that failed, e.g. `length`, `email`, or `range`
+    #[schema(example = "length")]
+    pub code: String,
+    /// Human-readable description of the failure
+    #[schema(example = "Name is required")]
+    pub message: String,
+}
+
+/// Flattens nested validation errors into one entry per failed constraint,
+/// sorted by field path.
+fn field_errors(errors: &ValidationErrors) -> Vec<FieldError> {
+    fn collect(prefix: &str, errors: &ValidationErrors, out: &mut Vec<FieldError>) {
+        for (field, kind) in errors.errors() {
+            let path = match (prefix.is_empty(), field.as_ref()) {
+                // Struct-level (schema) errors belong to the struct itself
+                (_, "__all__") if !prefix.is_empty() => prefix.to_string(),
+                (true, _) => field.to_string(),
+                (false, _) => format!("{prefix}.{field}"),
+            };
+            match kind {
+                ValidationErrorsKind::Field(errors) => {
+                    out.extend(errors.iter().map(|error| {
+                        FieldError {
+                            field: path.clone(),
+                            code: error.code.to_string(),
+                            message: error
+                                .message
+                                .as_ref()
+                                .map(|message| message.to_string())
+                                .unwrap_or_else(|| format!("{field} is invalid")),
+                        }
+                    }));
+                }
+                ValidationErrorsKind::Struct(errors) => collect(&path, errors, out),
+                ValidationErrorsKind::List(items) => {
+                    for (index, errors) in items {
+                        collect(&format!("{path}[{index}]"), errors, out);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    collect("", errors, &mut out);
+    out.sort_by(|a, b| a.field.cmp(&b.field).then_with(|| a.code.cmp(&b.code)));
+    out
+}
 
 /// Application-wide error type that converts into HTTP responses.
 ///
@@ -37,6 +114,9 @@ use validator::ValidationErrors;
 /// - **Client errors (4xx)**: The error message is returned to the client
 /// - **Server errors (5xx)**: A generic message is returned, and the actual
 ///   error is logged with source location
+/// - **Codes**: Every error has an [`ErrorCode`]; the convenience
+///   constructors use the generic code for their status, and
+///   [`AppError::from_code`] or [`AppError::with_code`] give a specific one
 ///
 /// # Example
 ///
@@ -50,6 +130,9 @@ use validator::ValidationErrors;
 ///
 /// // Using the generic constructor
 /// let custom = AppError::new(StatusCode::IM_A_TEAPOT, anyhow::anyhow!("I'm a teapot"));
+///
+/// // Using a specific code and its catalog message
+/// let level = AppError::from_code(ErrorCode::LevelNotFound);
 /// ```
 #[derive(Debug)]
 pub struct AppError {
@@ -57,6 +140,10 @@ pub struct AppError {
     pub status: StatusCode,
     /// The underlying error
     pub error: Error,
+    /// Machine-readable code sent with the response
+    pub code: ErrorCode,
+    /// Failed field constraints, listed in the response when not empty
+    pub fields: Vec<FieldError>,
     /// Source location where the error was created (for debugging)
     pub location: Option<&'static std::panic::Location<'static>>,
 }
@@ -87,10 +174,38 @@ impl AppError {
         Self {
             status,
             error: err.into(),
+            code: ErrorCode::from_status(status),
+            fields: Vec::new(),
             location: Some(std::panic::Location::caller()),
         }
     }
 
+    /// Creates an error with a specific code, its status, and its default
+    /// English message from the [`catalog`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use chalkbyte_core::{AppError, ErrorCode};
+    ///
+    /// let error = AppError::from_code(ErrorCode::BranchNotFound);
+    /// assert_eq!(error.status, StatusCode::NOT_FOUND);
+    /// ```
+    #[track_caller]
+    pub fn from_code(code: ErrorCode) -> Self {
+        Self::new(code.status(), anyhow!(catalog::default_message(code))).with_code(code)
+    }
+
+    /// Replaces the error's code, keeping its status and message.
+    ///
+    /// Use this for a specific code whose message needs details, such as the
+    /// conflicting value.
+    #[must_use]
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = code;
+        self
+    }
+
     /// Creates an internal server error (500).
     ///
     /// Use this for unexpected errors that indicate a bug or system failure.
@@ -149,16 +264,28 @@ impl AppError {
         Self::new(StatusCode::INTERNAL_SERVER_ERROR, err)
     }
 
-    /// Creates a validation error (400) from [`ValidationErrors`].
+    /// Creates a validation error (422) from [`ValidationErrors`], listing
+    /// each failed constraint in the response's `fields`.
     ///
-    /// Use this when request validation fails.
+    /// Use this when request validation fails, e.g.
+    /// `params.validate().map_err(AppError::validation)?`.
     #[track_caller]
-    #[allow(dead_code)]
     pub fn validation(err: ValidationErrors) -> Self {
-        Self::new(
-            StatusCode::BAD_REQUEST,
-            anyhow!("Validation error: {}", err),
-        )
+        let fields = field_errors(&err);
+        let message = if fields.is_empty() {
+            catalog::default_message(ErrorCode::ValidationFailed).to_string()
+        } else {
+            fields
+                .iter()
+                .map(|f| f.message.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let mut error = Self::from_code(ErrorCode::ValidationFailed);
+        error.error = anyhow!(message);
+        error.fields = fields;
+        error
     }
 
     /// Creates a bad request error (400) from a form rejection.
@@ -171,6 +298,7 @@ impl AppError {
             StatusCode::BAD_REQUEST,
             anyhow!("Form parsing error: {}", err),
         )
+        .with_code(ErrorCode::InvalidForm)
     }
 
     /// Creates a bad request error (400) from a query rejection.
@@ -182,6 +310,7 @@ impl AppError {
             StatusCode::BAD_REQUEST,
             anyhow!("Query parameter error: {}", err),
         )
+        .with_code(ErrorCode::InvalidQuery)
     }
 
     /// Creates an unauthorized error (401).
@@ -233,13 +362,23 @@ impl AppError {
     }
 }
 
+/// The full error message of an [`AppError`] response.
+///
+/// Attached to the response's extensions so request logging can record why a
+/// request failed, including the server error details hidden from the client.
+#[derive(Debug, Clone)]
+pub struct ErrorDetail(pub String);
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let request_id = current_request_id();
+
         let error_message = if self.status.is_server_error() {
             if let Some(location) = self.location {
                 error!(
                     status = %self.status.as_u16(),
                     error = %self.error,
+                    request_id = request_id.as_deref(),
                     file = %location.file(),
                     line = %location.line(),
                     "Internal server error"
@@ -248,20 +387,35 @@ impl IntoResponse for AppError {
                 error!(
                     status = %self.status.as_u16(),
                     error = %self.error,
+                    request_id = request_id.as_deref(),
                     "Internal server error"
                 );
             }
 
-            "Internal server error".to_string()
+            catalog::current_localized_message(ErrorCode::InternalError)
+                .unwrap_or_else(|| "Internal server error".to_string())
         } else {
-            self.error.to_string()
+            catalog::current_localized_message(self.code).unwrap_or_else(|| self.error.to_string())
         };
 
-        let body = Json(json!({
-            "error": error_message
-        }));
+        // Clients quote the request ID to support, who look it up with the
+        // admin trace endpoint
+        let mut body = json!({
+            "error": error_message,
+            "code": self.code,
+        });
+        if let Some(request_id) = &request_id {
+            body["request_id"] = json!(request_id);
+        }
+        if !self.fields.is_empty() {
+            body["fields"] = json!(self.fields);
+        }
 
-        (self.status, body).into_response()
+        let mut response = (self.status, Json(body)).into_response();
+        response
+            .extensions_mut()
+            .insert(ErrorDetail(format!("{:#}", self.error)));
+        response
     }
 }
 
@@ -437,7 +591,62 @@ mod tests {
         use validator::ValidationErrors;
         let errors = ValidationErrors::new();
         let error = AppError::validation(errors);
-        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+        assert_eq!(error.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(error.fields.is_empty());
+    }
+
+    #[test]
+    fn test_app_error_validation_lists_nested_fields() {
+        use validator::{Validate, ValidationError};
+
+        #[derive(Validate)]
+        struct Item {
+            #[validate(range(min = 0, message = "Score must not be negative"))]
+            score: i32,
+        }
+
+        #[derive(Validate)]
+        struct Dto {
+            #[validate(length(min = 1, message = "Name is required"))]
+            name: String,
+            #[validate(email)]
+            email: String,
+            #[validate(nested)]
+            items: Vec<Item>,
+        }
+
+        let dto = Dto {
+            name: String::new(),
+            email: "nope".to_string(),
+            items: vec![Item { score: 1 }, Item { score: -1 }],
+        };
+        let error = AppError::validation(dto.validate().unwrap_err());
+
+        assert_eq!(error.code, ErrorCode::ValidationFailed);
+        assert_eq!(
+            error.fields,
+            vec![
+                FieldError {
+                    field: "email".to_string(),
+                    code: "email".to_string(),
+                    message: "email is invalid".to_string(),
+                },
+                FieldError {
+                    field: "items[1].score".to_string(),
+                    code: "range".to_string(),
+                    message: "Score must not be negative".to_string(),
+                },
+                FieldError {
+                    field: "name".to_string(),
+                    code: "length".to_string(),
+                    message: "Name is required".to_string(),
+                },
+            ]
+        );
+
+        let mut errors = ValidationErrors::new();
+        errors.add("__all__", ValidationError::new("dates"));
+        assert_eq!(AppError::validation(errors).fields[0].field, "__all__");
     }
 
     #[test]
@@ -465,4 +674,72 @@ mod tests {
         assert!(display.contains("404"));
         assert!(display.contains("Resource not found"));
     }
+
+    #[tokio::test]
+    async fn test_error_response_includes_request_id() {
+        let response = crate::request_context::scope("req-abc".to_string(), async {
+            AppError::internal(anyhow!("connection refused")).into_response()
+        })
+        .await;
+
+        let detail = response.extensions().get::<ErrorDetail>().cloned().unwrap();
+        assert_eq!(detail.0, "connection refused");
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"], "Internal server error");
+        assert_eq!(body["code"], "INTERNAL_ERROR");
+        assert_eq!(body["request_id"], "req-abc");
+    }
+
+    #[test]
+    fn test_from_code_uses_catalog_message_and_status() {
+        let error = AppError::from_code(ErrorCode::LevelNotFound);
+        assert_eq!(error.status, StatusCode::NOT_FOUND);
+        assert_eq!(error.code, ErrorCode::LevelNotFound);
+        assert_eq!(error.error.to_string(), "Level not found");
+        assert!(error.location.is_some());
+    }
+
+    #[test]
+    fn test_constructors_use_generic_codes() {
+        assert_eq!(
+            AppError::not_found(anyhow!("gone")).code,
+            ErrorCode::NotFound
+        );
+        assert_eq!(
+            AppError::forbidden("no".to_string()).code,
+            ErrorCode::Forbidden
+        );
+        assert_eq!(
+            AppError::validation(ValidationErrors::new()).code,
+            ErrorCode::ValidationFailed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_response_uses_localized_message() {
+        let bundle: std::collections::HashMap<ErrorCode, String> = [(
+            ErrorCode::BranchNameConflict,
+            "Nom de classe déjà utilisé".to_string(),
+        )]
+        .into();
+        catalog::register_bundle("xf", bundle);
+
+        let error = AppError::bad_request(anyhow!("Branch 'A' already exists"))
+            .with_code(ErrorCode::BranchNameConflict);
+        let response = crate::request_context::scope_locale("xf-CA".to_string(), async {
+            error.into_response()
+        })
+        .await;
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"], "Nom de classe déjà utilisé");
+        assert_eq!(body["code"], "BRANCH_NAME_CONFLICT");
+    }
 }
@@ -0,0 +1,59 @@
+//! Query parameters for list export endpoints.
+//!
+//! Export endpoints take the same filters as the list endpoint they mirror,
+//! plus [`ExportParams`] to pick the file format. Pagination parameters are
+//! ignored: an export always covers every matching row.
+
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+/// File format of an export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+}
+
+impl ExportFormat {
+    /// `Content-Type` of the exported file.
+    #[must_use]
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+        }
+    }
+
+    /// File extension, without the leading dot.
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// Format selection for export endpoints.
+#[derive(Debug, Clone, Copy, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportParams {
+    /// File format, `csv` by default
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_params_default_to_csv() {
+        let params: ExportParams = serde_json::from_str("{}").unwrap();
+        assert_eq!(params.format, ExportFormat::Csv);
+
+        let params: ExportParams = serde_json::from_str(r#"{"format":"csv"}"#).unwrap();
+        assert_eq!(params.format.extension(), "csv");
+
+        assert!(serde_json::from_str::<ExportParams>(r#"{"format":"pdf"}"#).is_err());
+    }
+}
@@ -89,6 +89,9 @@ pub enum StorageError {
 
     /// Invalid storage key format.
     InvalidKey(String),
+
+    /// A remote storage service refused or failed the request.
+    Backend(String),
 }
 
 impl fmt::Display for StorageError {
@@ -108,6 +111,7 @@ impl fmt::Display for StorageError {
             Self::IoError(e) => write!(f, "I/O error: {}", e),
             Self::NotFound => write!(f, "File not found"),
             Self::InvalidKey(msg) => write!(f, "Invalid storage key: {}", msg),
+            Self::Backend(msg) => write!(f, "Storage backend error: {}", msg),
         }
     }
 }
@@ -187,7 +191,7 @@ impl LocalFileStorage {
     }
 
     /// Validate storage key format to prevent path traversal.
-    fn validate_key(key: &str) -> Result<(), StorageError> {
+    pub fn validate_key(key: &str) -> Result<(), StorageError> {
         // Reject empty keys or keys with path traversal attempts
         if key.is_empty() || key.contains("..") || key.starts_with('/') {
             return Err(StorageError::InvalidKey(
@@ -0,0 +1,501 @@
+//! Filter expressions for list endpoints.
+//!
+//! Clients pass comma-separated `field:op:value` clauses in `filter`, all of
+//! which must match:
+//!
+//! ```text
+//! GET /api/users?filter=school_id:eq:<uuid>,created_at:gte:2024-01-01
+//! ```
+//!
+//! | Operator   | Meaning                          | Field types                   |
+//! |------------|----------------------------------|-------------------------------|
+//! | `eq`, `ne` | Equal, not equal                 | All                           |
+//! | `gt`, `gte`, `lt`, `lte` | Ordered comparison | Timestamp, integer            |
+//! | `contains` | Case-insensitive substring       | Text                          |
+//! | `in`       | Any of `\|`-separated values     | Text, UUID, integer           |
+//!
+//! Timestamps are RFC 3339 (`2024-01-01T08:00:00Z`) or a plain date, read as
+//! midnight UTC. Values cannot contain commas.
+//!
+//! As with [`sorting`](crate::sorting), field names are never interpolated
+//! into SQL. Each list declares its filterable fields in a [`Filterable`],
+//! mapping the name clients use to a column and a type, and values are
+//! parsed into that type before they reach the database. Anything else is
+//! rejected with `INVALID_FILTER`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_core::filtering::{FieldType, FilterParams, Filterable};
+//!
+//! const SCHOOL_FILTER: Filterable = Filterable {
+//!     fields: &[
+//!         ("name", "name", FieldType::Text),
+//!         ("created_at", "created_at", FieldType::Timestamp),
+//!     ],
+//! };
+//!
+//! let params = FilterParams { filter: Some("name:contains:high".to_string()) };
+//! let filters = SCHOOL_FILTER.parse(&params)?;
+//! for (condition, value) in filters.sql_conditions(1) {
+//!     // "name ILIKE $1", "%high%"
+//! }
+//! ```
+
+use anyhow::anyhow;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::error_codes::ErrorCode;
+use crate::errors::AppError;
+
+/// Most clauses one `filter` parameter may hold.
+pub const MAX_FILTERS: usize = 20;
+
+/// Query parameter narrowing a list by field conditions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Deserialize, ToSchema)]
+pub struct FilterParams {
+    /// Comma-separated `field:op:value` clauses that must all match, e.g.
+    /// `school_id:eq:<uuid>,created_at:gte:2024-01-01`
+    #[serde(default)]
+    pub filter: Option<String>,
+}
+
+/// Type of a filterable field, deciding how values parse and which
+/// operators apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    Text,
+    Uuid,
+    Timestamp,
+    Integer,
+    Boolean,
+}
+
+impl FieldType {
+    fn allows(self, op: FilterOp) -> bool {
+        use FilterOp::*;
+        match self {
+            Self::Text => matches!(op, Eq | Ne | Contains | In),
+            Self::Uuid => matches!(op, Eq | Ne | In),
+            Self::Timestamp => matches!(op, Eq | Ne | Gt | Gte | Lt | Lte),
+            Self::Integer => !matches!(op, Contains),
+            Self::Boolean => matches!(op, Eq | Ne),
+        }
+    }
+
+    fn sql_type(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Uuid => "uuid",
+            Self::Timestamp => "timestamptz",
+            Self::Integer => "bigint",
+            Self::Boolean => "boolean",
+        }
+    }
+
+    fn parse(self, raw: &str) -> Option<FilterValue> {
+        match self {
+            Self::Text => Some(FilterValue::Text(raw.to_string())),
+            Self::Uuid => Uuid::parse_str(raw).ok().map(FilterValue::Uuid),
+            Self::Timestamp => DateTime::parse_from_rfc3339(raw)
+                .map(|timestamp| timestamp.with_timezone(&Utc))
+                .or_else(|_| {
+                    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                        .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+                })
+                .ok()
+                .map(FilterValue::Timestamp),
+            Self::Integer => raw.parse().ok().map(FilterValue::Integer),
+            Self::Boolean => raw.parse().ok().map(FilterValue::Boolean),
+        }
+    }
+
+    fn expected(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Uuid => "a UUID",
+            Self::Timestamp => "a date or RFC 3339 timestamp",
+            Self::Integer => "an integer",
+            Self::Boolean => "true or false",
+        }
+    }
+}
+
+/// Comparison applied by one clause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Contains,
+    In,
+}
+
+impl FilterOp {
+    const NAMES: &'static str = "eq, ne, gt, gte, lt, lte, contains, in";
+
+    fn parse(raw: &str) -> Option<Self> {
+        Some(match raw {
+            "eq" => Self::Eq,
+            "ne" => Self::Ne,
+            "gt" => Self::Gt,
+            "gte" => Self::Gte,
+            "lt" => Self::Lt,
+            "lte" => Self::Lte,
+            "contains" => Self::Contains,
+            "in" => Self::In,
+            _ => return None,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Eq => "eq",
+            Self::Ne => "ne",
+            Self::Gt => "gt",
+            Self::Gte => "gte",
+            Self::Lt => "lt",
+            Self::Lte => "lte",
+            Self::Contains => "contains",
+            Self::In => "in",
+        }
+    }
+}
+
+/// A parsed filter value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    Text(String),
+    Uuid(Uuid),
+    Timestamp(DateTime<Utc>),
+    Integer(i64),
+    Boolean(bool),
+    /// Values of an `in` clause
+    List(Vec<FilterValue>),
+}
+
+impl FilterValue {
+    /// The value as Postgres reads it when cast from text.
+    fn to_sql_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Uuid(id) => id.to_string(),
+            Self::Timestamp(timestamp) => timestamp.to_rfc3339(),
+            Self::Integer(number) => number.to_string(),
+            Self::Boolean(flag) => flag.to_string(),
+            Self::List(values) => {
+                let elements = values
+                    .iter()
+                    .map(|value| {
+                        let text = value.to_sql_text();
+                        format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!("{{{elements}}}")
+            }
+        }
+    }
+}
+
+/// One clause of a parsed `filter` parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    /// Field name as the client gave it
+    pub field: &'static str,
+    /// SQL column or expression the field maps to
+    pub column: &'static str,
+    pub field_type: FieldType,
+    pub op: FilterOp,
+    pub value: FilterValue,
+}
+
+impl Filter {
+    /// The SQL condition for this clause using parameter `$param`, and the
+    /// text to bind to it.
+    fn sql_condition(&self, param: usize) -> (String, String) {
+        let column = self.column;
+        let cast = self.field_type.sql_type();
+        let value = self.value.to_sql_text();
+        match self.op {
+            FilterOp::Contains => (
+                format!("{column} ILIKE ${param}"),
+                format!("%{}%", escape_like(&value)),
+            ),
+            FilterOp::In => (format!("{column} = ANY(${param}::{cast}[])"), value),
+            op => {
+                let operator = match op {
+                    FilterOp::Eq => "=",
+                    FilterOp::Ne => "IS DISTINCT FROM",
+                    FilterOp::Gt => ">",
+                    FilterOp::Gte => ">=",
+                    FilterOp::Lt => "<",
+                    _ => "<=",
+                };
+                (format!("{column} {operator} ${param}::{cast}"), value)
+            }
+        }
+    }
+}
+
+/// The clauses of a `filter` parameter, checked against a [`Filterable`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FilterSet {
+    pub filters: Vec<Filter>,
+}
+
+impl FilterSet {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    /// SQL conditions for every clause, numbering parameters from
+    /// `first_param`, each paired with the text to bind.
+    ///
+    /// Values are bound as text and cast in SQL, so they can go through the
+    /// same binding loop as a list's other string parameters.
+    #[must_use]
+    pub fn sql_conditions(&self, first_param: usize) -> Vec<(String, String)> {
+        self.filters
+            .iter()
+            .enumerate()
+            .map(|(index, filter)| filter.sql_condition(first_param + index))
+            .collect()
+    }
+}
+
+/// The fields a list can be filtered by.
+#[derive(Debug, Clone, Copy)]
+pub struct Filterable {
+    /// `(field, column, type)` triples: the name clients filter by, the SQL
+    /// column or expression it compares, and the type values parse as
+    pub fields: &'static [(&'static str, &'static str, FieldType)],
+}
+
+impl Filterable {
+    /// Parses the request's `filter` parameter. Empty when no filter was given.
+    ///
+    /// Fails with a 400 `INVALID_FILTER` error if a clause is malformed, names
+    /// an unknown field, uses an operator the field's type does not support,
+    /// or has a value that does not parse as that type.
+    pub fn parse(&self, params: &FilterParams) -> Result<FilterSet, AppError> {
+        let Some(filter) = params.filter.as_deref().map(str::trim) else {
+            return Ok(FilterSet::default());
+        };
+        if filter.is_empty() {
+            return Ok(FilterSet::default());
+        }
+
+        let clauses: Vec<&str> = filter.split(',').map(str::trim).collect();
+        if clauses.len() > MAX_FILTERS {
+            return Err(invalid_filter(format!(
+                "At most {MAX_FILTERS} filter clauses are allowed"
+            )));
+        }
+
+        let filters = clauses
+            .into_iter()
+            .map(|clause| self.parse_clause(clause))
+            .collect::<Result<_, _>>()?;
+        Ok(FilterSet { filters })
+    }
+
+    fn parse_clause(&self, clause: &str) -> Result<Filter, AppError> {
+        let mut parts = clause.splitn(3, ':');
+        let (Some(field), Some(op), Some(raw)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(invalid_filter(format!(
+                "Invalid filter '{clause}'; expected field:op:value"
+            )));
+        };
+
+        let Some(&(field, column, field_type)) =
+            self.fields.iter().find(|(name, _, _)| *name == field)
+        else {
+            return Err(invalid_filter(format!(
+                "Cannot filter by '{field}'; filterable fields are: {}",
+                self.field_names()
+            )));
+        };
+
+        let Some(op) = FilterOp::parse(op) else {
+            return Err(invalid_filter(format!(
+                "Unknown filter operator '{op}'; expected one of: {}",
+                FilterOp::NAMES
+            )));
+        };
+        if !field_type.allows(op) {
+            return Err(invalid_filter(format!(
+                "Operator '{}' is not supported for '{field}'",
+                op.name()
+            )));
+        }
+
+        let parse = |raw: &str| {
+            field_type.parse(raw.trim()).ok_or_else(|| {
+                invalid_filter(format!(
+                    "Invalid value '{raw}' for '{field}'; expected {}",
+                    field_type.expected()
+                ))
+            })
+        };
+        let value = if op == FilterOp::In {
+            let values = raw
+                .split('|')
+                .filter(|value| !value.trim().is_empty())
+                .map(parse)
+                .collect::<Result<Vec<_>, _>>()?;
+            if values.is_empty() {
+                return Err(invalid_filter(format!(
+                    "Filter '{clause}' needs at least one value"
+                )));
+            }
+            FilterValue::List(values)
+        } else {
+            parse(raw)?
+        };
+
+        Ok(Filter {
+            field,
+            column,
+            field_type,
+            op,
+            value,
+        })
+    }
+
+    /// The filterable field names, comma-separated, for error messages and docs.
+    #[must_use]
+    pub fn field_names(&self) -> String {
+        self.fields
+            .iter()
+            .map(|(name, _, _)| *name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Escapes `LIKE` wildcards so `contains` matches them literally.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn invalid_filter(message: String) -> AppError {
+    AppError::bad_request(anyhow!(message)).with_code(ErrorCode::InvalidFilter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    const USERS: Filterable = Filterable {
+        fields: &[
+            ("email", "u.email", FieldType::Text),
+            ("school_id", "u.school_id", FieldType::Uuid),
+            ("created_at", "u.created_at", FieldType::Timestamp),
+            ("mfa_enabled", "u.mfa_enabled", FieldType::Boolean),
+        ],
+    };
+
+    fn filter(value: &str) -> FilterParams {
+        FilterParams {
+            filter: Some(value.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_builds_typed_conditions() {
+        let school_id = Uuid::new_v4();
+        let filters = USERS
+            .parse(&filter(&format!(
+                "school_id:eq:{school_id}, created_at:gte:2024-01-01,email:contains:50%_off,mfa_enabled:ne:true"
+            )))
+            .unwrap();
+
+        assert_eq!(filters.filters[0].value, FilterValue::Uuid(school_id));
+        assert_eq!(
+            filters.sql_conditions(3),
+            vec![
+                ("u.school_id = $3::uuid".to_string(), school_id.to_string()),
+                (
+                    "u.created_at >= $4::timestamptz".to_string(),
+                    "2024-01-01T00:00:00+00:00".to_string()
+                ),
+                ("u.email ILIKE $5".to_string(), "%50\\%\\_off%".to_string()),
+                (
+                    "u.mfa_enabled IS DISTINCT FROM $6::boolean".to_string(),
+                    "true".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_in_and_timestamps() {
+        let filters = USERS
+            .parse(&filter(
+                "email:in:a@x.com|b\"@x.com,created_at:lt:2024-03-01T08:30:00Z",
+            ))
+            .unwrap();
+
+        assert_eq!(
+            filters.sql_conditions(1),
+            vec![
+                (
+                    "u.email = ANY($1::text[])".to_string(),
+                    r#"{"a@x.com","b\"@x.com"}"#.to_string()
+                ),
+                (
+                    "u.created_at < $2::timestamptz".to_string(),
+                    "2024-03-01T08:30:00+00:00".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_filter() {
+        assert!(USERS.parse(&FilterParams::default()).unwrap().is_empty());
+        assert!(USERS.parse(&filter("  ")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_clauses() {
+        for value in [
+            "password:eq:x",
+            "email",
+            "email:eq",
+            "email:like:x",
+            "email:gt:x",
+            "school_id:eq:not-a-uuid",
+            "school_id:contains:abc",
+            "created_at:gte:yesterday",
+            "mfa_enabled:eq:yes",
+            "email:in:|",
+            "email:eq:a,",
+        ] {
+            let error = USERS.parse(&filter(value)).unwrap_err();
+            assert_eq!(error.status, StatusCode::BAD_REQUEST, "{value}");
+            assert_eq!(error.code, ErrorCode::InvalidFilter, "{value}");
+        }
+
+        let error = USERS.parse(&filter("password:eq:x")).unwrap_err();
+        assert_eq!(
+            error.error.to_string(),
+            "Cannot filter by 'password'; filterable fields are: email, school_id, created_at, mfa_enabled"
+        );
+
+        let too_many = vec!["email:eq:a"; MAX_FILTERS + 1].join(",");
+        assert!(USERS.parse(&filter(&too_many)).is_err());
+    }
+}
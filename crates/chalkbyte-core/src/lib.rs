@@ -4,10 +4,20 @@
 //!
 //! This crate provides foundational types used throughout the Chalkbyte application:
 //!
+//! - [`catalog`]: Default English error messages and localized message bundles
+//! - [`conditional`]: `ETag` versions and `If-Match` checks for optimistic concurrency
 //! - [`errors`]: Application error types with HTTP response conversion
+//! - [`error_codes`]: Machine-readable error codes sent in every error response
+//! - [`export`]: Query parameters for list export endpoints
+//! - [`filtering`]: `filter` query parameter parsed against per-list field allowlists
 //! - [`pagination`]: Pagination utilities for API responses
-//! - [`password`]: Secure password hashing and verification
+//! - [`password`]: Secure password hashing, verification, and password policy
+//! - [`request_context`]: ID and locale of the request being handled, for error responses and audit entries
+//! - [`responses`]: `201 Created` and `204 No Content` response types for handlers
 //! - [`serde`]: Custom serde serialization/deserialization helpers
+//! - [`sorting`]: `sort` query parameter and allowlisted `ORDER BY` builder
+//! - [`versioning`]: API versions and per-version response shapes
+//! - [`views`]: Full and lite response views for mobile clients
 //!
 //! # Example
 //!
@@ -27,15 +37,37 @@
 //! let limit = params.limit();
 //! ```
 
+pub mod catalog;
+pub mod conditional;
+pub mod error_codes;
 pub mod errors;
+pub mod export;
 pub mod file_storage;
+pub mod filtering;
 pub mod pagination;
 pub mod password;
 pub mod permissions;
+pub mod request_context;
+pub mod responses;
 pub mod serde;
+pub mod sorting;
+pub mod versioning;
+pub mod views;
+pub mod visibility;
 
 // Re-export commonly used types at crate root
-pub use errors::AppError;
+pub use conditional::{Conditional, IfMatch, Versioned, WithEtag};
+pub use error_codes::ErrorCode;
+pub use errors::{AppError, FieldError};
+pub use export::{ExportFormat, ExportParams};
 pub use file_storage::{FileStorage, LocalFileStorage, StorageError};
-pub use pagination::{PaginationMeta, PaginationParams};
-pub use password::{hash_password, verify_password};
+pub use filtering::{FilterParams, FilterSet, Filterable};
+pub use pagination::{
+    Cursor, CursorMeta, CursorPage, CursorPaginationParams, PaginationMeta, PaginationParams,
+};
+pub use password::{PasswordPolicy, hash_password, verify_password};
+pub use responses::{Created, NoContent};
+pub use sorting::{SortParams, Sortable};
+pub use versioning::{ApiVersion, VersionedView};
+pub use views::{LiteView, ResponseView};
+pub use visibility::{FieldAccess, FieldVisibility, RestrictedField, Visible};
@@ -1,7 +1,8 @@
 //! Pagination utilities for API responses.
 //!
 //! This module provides types and utilities for implementing pagination
-//! in API endpoints. It supports both offset-based and page-based pagination.
+//! in API endpoints. It supports offset-based, page-based, and cursor-based
+//! pagination.
 //!
 //! # Pagination Strategies
 //!
@@ -19,6 +20,18 @@
 //!
 //! When `page` is provided, it takes precedence over `offset`.
 //!
+//! ## Cursor-based (keyset) pagination
+//!
+//! Uses `limit` and `cursor` parameters:
+//! - `limit`: Items per page (1-100, default: 10)
+//! - `cursor`: Opaque cursor from the previous page's `next_cursor`, or empty
+//!   for the first page
+//!
+//! Rows are ordered newest first by `(created_at, id)`, and each page starts
+//! right after the last row of the previous one. Unlike offset pagination
+//! this does not get slower deeper into a large table, but it cannot jump to
+//! an arbitrary page and does not report a total.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -46,8 +59,13 @@
 //! }
 //! ```
 
+use chrono::{DateTime, Utc};
+use data_encoding::BASE64URL_NOPAD;
 use serde::{Deserialize, Deserializer, Serialize};
 use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
 
 /// Deserializes an optional string into an optional i64.
 ///
@@ -185,6 +203,145 @@ impl PaginationParams {
     }
 }
 
+/// Position in a list ordered by `(created_at, id)`.
+///
+/// Encoded for clients as an opaque URL-safe string; they should pass it back
+/// unchanged rather than build one themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    #[must_use]
+    pub fn new(created_at: DateTime<Utc>, id: Uuid) -> Self {
+        Self { created_at, id }
+    }
+
+    /// Encodes the cursor as an opaque string.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        let raw = format!("{}:{}", self.created_at.timestamp_micros(), self.id);
+        BASE64URL_NOPAD.encode(raw.as_bytes())
+    }
+
+    /// Decodes a cursor produced by [`Cursor::encode`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a bad request error if the cursor is malformed.
+    pub fn decode(encoded: &str) -> Result<Self, AppError> {
+        let invalid = || AppError::bad_request(anyhow::anyhow!("Invalid pagination cursor"));
+
+        let raw = BASE64URL_NOPAD
+            .decode(encoded.as_bytes())
+            .map_err(|_| invalid())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+        let (micros, id) = raw.split_once(':').ok_or_else(invalid)?;
+
+        let created_at = micros
+            .parse::<i64>()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or_else(invalid)?;
+        let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+
+        Ok(Self { created_at, id })
+    }
+}
+
+/// Query parameters for cursor-based pagination.
+///
+/// # Example
+///
+/// ```ignore
+/// // GET /api/users?cursor=&limit=50        (first page)
+/// // GET /api/users?cursor=<next_cursor>    (following pages)
+/// let params = CursorPaginationParams {
+///     cursor: Some(String::new()),
+///     limit: Some(50),
+/// };
+///
+/// assert_eq!(params.after()?, None);
+/// ```
+#[derive(Debug, Clone, Default, Hash, Deserialize, ToSchema)]
+pub struct CursorPaginationParams {
+    /// Cursor from the previous page's `next_cursor`; empty for the first page
+    pub cursor: Option<String>,
+    /// Maximum number of items to return (1-100, default: 10)
+    #[serde(default, deserialize_with = "deserialize_optional_i64")]
+    pub limit: Option<i64>,
+}
+
+impl CursorPaginationParams {
+    /// Returns the effective limit, clamped to [1, 100].
+    ///
+    /// Defaults to 10 if not specified.
+    #[must_use]
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(10).clamp(1, 100)
+    }
+
+    /// Returns the position to continue after, or `None` for the first page.
+    ///
+    /// # Errors
+    ///
+    /// Returns a bad request error if the cursor is malformed.
+    pub fn after(&self) -> Result<Option<Cursor>, AppError> {
+        match self.cursor.as_deref() {
+            None | Some("") => Ok(None),
+            Some(cursor) => Cursor::decode(cursor).map(Some),
+        }
+    }
+}
+
+/// Metadata about a cursor-paginated response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CursorMeta {
+    /// Maximum items per page (the limit that was applied)
+    pub limit: i64,
+    /// Cursor for the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Whether there are more items after this page
+    pub has_more: bool,
+}
+
+/// A page of results from cursor-based pagination.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CursorPage<T> {
+    pub data: Vec<T>,
+    pub meta: CursorMeta,
+}
+
+impl<T> CursorPage<T> {
+    /// Builds a page from rows fetched with `LIMIT limit + 1`.
+    ///
+    /// The extra row only signals that another page exists and is dropped;
+    /// `cursor_of` gives the position of a row for the next page's cursor.
+    #[must_use]
+    pub fn from_rows(mut rows: Vec<T>, limit: i64, cursor_of: impl Fn(&T) -> Cursor) -> Self {
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit.max(0) as usize);
+
+        let next_cursor = if has_more {
+            rows.last().map(|row| cursor_of(row).encode())
+        } else {
+            None
+        };
+
+        Self {
+            data: rows,
+            meta: CursorMeta {
+                limit,
+                next_cursor,
+                has_more,
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -509,4 +666,51 @@ mod tests {
         };
         assert_eq!(meta1, meta2);
     }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = Cursor::new(
+            DateTime::from_timestamp_micros(1_760_000_000_123_456).unwrap(),
+            Uuid::new_v4(),
+        );
+        let encoded = cursor.encode();
+        assert!(!encoded.contains(['+', '/', '=']));
+        assert_eq!(Cursor::decode(&encoded).unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_cursor_decode_invalid() {
+        assert!(Cursor::decode("not a cursor").is_err());
+        assert!(Cursor::decode(&BASE64URL_NOPAD.encode(b"123")).is_err());
+        assert!(Cursor::decode(&BASE64URL_NOPAD.encode(b"abc:not-a-uuid")).is_err());
+    }
+
+    #[test]
+    fn test_cursor_params_first_page() {
+        let params = CursorPaginationParams {
+            cursor: Some(String::new()),
+            limit: Some(500),
+        };
+        assert_eq!(params.after().unwrap(), None);
+        assert_eq!(params.limit(), 100);
+    }
+
+    #[test]
+    fn test_cursor_page_from_rows() {
+        let base = DateTime::from_timestamp_micros(1_760_000_000_000_000).unwrap();
+        let rows: Vec<Cursor> = (0..3)
+            .map(|i| Cursor::new(base - chrono::Duration::seconds(i), Uuid::new_v4()))
+            .collect();
+
+        let page = CursorPage::from_rows(rows.clone(), 2, |row| *row);
+        assert_eq!(page.data.len(), 2);
+        assert!(page.meta.has_more);
+        let next = page.meta.next_cursor.unwrap();
+        assert_eq!(Cursor::decode(&next).unwrap(), rows[1]);
+
+        let page = CursorPage::from_rows(rows, 3, |row| *row);
+        assert_eq!(page.data.len(), 3);
+        assert!(!page.meta.has_more);
+        assert_eq!(page.meta.next_cursor, None);
+    }
 }
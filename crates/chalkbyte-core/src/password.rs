@@ -1,8 +1,11 @@
-//! Password hashing and verification utilities.
+//! Password hashing, verification, and policy utilities.
 //!
 //! This module provides secure password hashing using bcrypt. It wraps the
 //! [`bcrypt`] crate to provide a simple API with proper error handling.
 //!
+//! [`PasswordPolicy`] decides which new passwords are accepted. Check a
+//! password against it before hashing it for storage.
+//!
 //! # Security
 //!
 //! - Uses bcrypt with the default cost factor (currently 12)
@@ -27,6 +30,224 @@ use bcrypt::{DEFAULT_COST, hash, verify};
 
 use crate::errors::AppError;
 
+/// Passwords rejected when [`PasswordPolicy::ban_common_passwords`] is set,
+/// compared case-insensitively.
+const COMMON_PASSWORDS: &[&str] = &[
+    "password",
+    "password1",
+    "password12",
+    "password123",
+    "password1234",
+    "passw0rd",
+    "p@ssw0rd",
+    "p@ssword",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "0123456789",
+    "87654321",
+    "11111111",
+    "00000000",
+    "12341234",
+    "11223344",
+    "qwerty12",
+    "qwerty123",
+    "qwertyuiop",
+    "1q2w3e4r",
+    "1qaz2wsx",
+    "zaq12wsx",
+    "asdfghjkl",
+    "asdf1234",
+    "abcd1234",
+    "abc12345",
+    "abcdefgh",
+    "iloveyou",
+    "iloveyou1",
+    "sunshine",
+    "princess",
+    "football",
+    "baseball",
+    "basketball",
+    "superman",
+    "batman123",
+    "trustno1",
+    "whatever",
+    "starwars",
+    "letmein1",
+    "welcome1",
+    "welcome123",
+    "admin123",
+    "administrator",
+    "changeme",
+    "computer",
+    "internet",
+    "master123",
+    "monkey123",
+    "dragon123",
+    "michael1",
+    "jennifer",
+    "qwerty1234",
+    "access14",
+    "shadow12",
+    "school123",
+    "student1",
+    "student123",
+    "teacher1",
+    "teacher123",
+];
+
+/// Rules a new password must meet.
+///
+/// Loaded from the environment with [`PasswordPolicy::from_env`]; the
+/// defaults require 8 characters, reject common passwords and passwords
+/// containing the user's name or email, and prevent reusing any of the
+/// last 5 passwords.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    /// Minimum length in characters
+    pub min_length: usize,
+    /// Require at least one uppercase letter
+    pub require_uppercase: bool,
+    /// Require at least one lowercase letter
+    pub require_lowercase: bool,
+    /// Require at least one digit
+    pub require_digit: bool,
+    /// Require at least one character that is not a letter or digit
+    pub require_symbol: bool,
+    /// Reject well-known common passwords
+    pub ban_common_passwords: bool,
+    /// Reject passwords containing the user's name or the local part of
+    /// their email (parts shorter than 3 characters are ignored)
+    pub disallow_personal_info: bool,
+    /// How many of a user's most recent passwords, including the current
+    /// one, cannot be reused on change or reset; 0 disables the check
+    pub history_size: usize,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            require_uppercase: false,
+            require_lowercase: false,
+            require_digit: false,
+            require_symbol: false,
+            ban_common_passwords: true,
+            disallow_personal_info: true,
+            history_size: 5,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Loads the policy from environment variables, falling back to the
+    /// defaults:
+    ///
+    /// - `PASSWORD_MIN_LENGTH` (default: 8)
+    /// - `PASSWORD_REQUIRE_UPPERCASE`, `PASSWORD_REQUIRE_LOWERCASE`,
+    ///   `PASSWORD_REQUIRE_DIGIT`, `PASSWORD_REQUIRE_SYMBOL` (default: false)
+    /// - `PASSWORD_BAN_COMMON` (default: true)
+    /// - `PASSWORD_DISALLOW_PERSONAL_INFO` (default: true)
+    /// - `PASSWORD_HISTORY_SIZE` (default: 5)
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Loads the policy from the variables `lookup` returns, with the same
+    /// defaults as [`PasswordPolicy::from_env`].
+    #[must_use]
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        fn or<T: std::str::FromStr>(value: Option<String>, default: T) -> T {
+            value.and_then(|v| v.parse().ok()).unwrap_or(default)
+        }
+
+        let defaults = Self::default();
+        Self {
+            min_length: or(lookup("PASSWORD_MIN_LENGTH"), defaults.min_length),
+            require_uppercase: or(
+                lookup("PASSWORD_REQUIRE_UPPERCASE"),
+                defaults.require_uppercase,
+            ),
+            require_lowercase: or(
+                lookup("PASSWORD_REQUIRE_LOWERCASE"),
+                defaults.require_lowercase,
+            ),
+            require_digit: or(lookup("PASSWORD_REQUIRE_DIGIT"), defaults.require_digit),
+            require_symbol: or(lookup("PASSWORD_REQUIRE_SYMBOL"), defaults.require_symbol),
+            ban_common_passwords: or(lookup("PASSWORD_BAN_COMMON"), defaults.ban_common_passwords),
+            disallow_personal_info: or(
+                lookup("PASSWORD_DISALLOW_PERSONAL_INFO"),
+                defaults.disallow_personal_info,
+            ),
+            history_size: or(lookup("PASSWORD_HISTORY_SIZE"), defaults.history_size),
+        }
+    }
+
+    /// Lists every rule `password` breaks, in a fixed order.
+    ///
+    /// `personal_info` holds the user's names and email; see
+    /// [`PasswordPolicy::disallow_personal_info`].
+    #[must_use]
+    pub fn violations(&self, password: &str, personal_info: &[&str]) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        if password.chars().count() < self.min_length {
+            violations.push(format!("must be at least {} characters", self.min_length));
+        }
+        if self.require_uppercase && !password.chars().any(char::is_uppercase) {
+            violations.push("must contain an uppercase letter".to_string());
+        }
+        if self.require_lowercase && !password.chars().any(char::is_lowercase) {
+            violations.push("must contain a lowercase letter".to_string());
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            violations.push("must contain a digit".to_string());
+        }
+        if self.require_symbol && password.chars().all(char::is_alphanumeric) {
+            violations.push("must contain a symbol".to_string());
+        }
+
+        let lowered = password.to_lowercase();
+        if self.ban_common_passwords && COMMON_PASSWORDS.contains(&lowered.as_str()) {
+            violations.push("is too common".to_string());
+        }
+        if self.disallow_personal_info
+            && personal_info
+                .iter()
+                .map(|info| {
+                    info.split('@')
+                        .next()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_lowercase()
+                })
+                .any(|info| info.chars().count() >= 3 && lowered.contains(&info))
+        {
+            violations.push("must not contain your name or email".to_string());
+        }
+
+        violations
+    }
+
+    /// Checks `password` against the policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an unprocessable [`AppError`] listing every broken rule.
+    pub fn validate(&self, password: &str, personal_info: &[&str]) -> Result<(), AppError> {
+        let violations = self.violations(password, personal_info);
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        Err(AppError::unprocessable(anyhow::anyhow!(
+            "Password {}",
+            violations.join("; ")
+        )))
+    }
+}
+
 /// Hashes a password using bcrypt with the default cost factor.
 ///
 /// This function generates a unique salt for each password, ensuring that
@@ -199,6 +420,74 @@ mod tests {
         assert!(result.unwrap());
     }
 
+    #[test]
+    fn test_policy_default_accepts_reasonable_password() {
+        let policy = PasswordPolicy::default();
+        assert!(
+            policy
+                .validate(
+                    "correct horse battery",
+                    &["Ada", "Lovelace", "ada@example.com"]
+                )
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_policy_reports_each_violation() {
+        let policy = PasswordPolicy {
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: true,
+            ..PasswordPolicy::default()
+        };
+
+        assert_eq!(
+            policy.violations("short", &[]),
+            vec![
+                "must be at least 8 characters",
+                "must contain an uppercase letter",
+                "must contain a digit",
+                "must contain a symbol",
+            ]
+        );
+        assert!(policy.violations("Str0ng!pass", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_policy_bans_common_passwords() {
+        let policy = PasswordPolicy::default();
+        assert_eq!(policy.violations("Password123", &[]), vec!["is too common"]);
+
+        let lenient = PasswordPolicy {
+            ban_common_passwords: false,
+            ..PasswordPolicy::default()
+        };
+        assert!(lenient.violations("Password123", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_policy_rejects_personal_info() {
+        let policy = PasswordPolicy::default();
+        let personal = ["Ada", "Lovelace", "ada.l@example.com"];
+
+        assert_eq!(
+            policy.violations("ilovelace99", &personal),
+            vec!["must not contain your name or email"]
+        );
+        assert!(!policy.violations("x-ada.l-2024", &personal).is_empty());
+        // Short parts are ignored
+        assert!(policy.violations("jo-jo-jo-jo", &["Jo"]).is_empty());
+    }
+
+    #[test]
+    fn test_policy_validate_error() {
+        let err = PasswordPolicy::default()
+            .validate("password", &[])
+            .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
     #[test]
     fn test_verify_case_sensitive() {
         let password = "Password123";
@@ -17,6 +17,15 @@
 //!     // Access granted
 //! }
 //! ```
+//!
+//! # Scoped Permissions
+//!
+//! A permission name may end in a scope modifier, `resource:action:scope`,
+//! granting the action on only some records: `students:read:own_branch`
+//! covers the students in branches the user teaches, and `users:update:self`
+//! only the user's own record. The bare permission covers every record the
+//! user's school allows and implies each of its scoped forms. See
+//! [`PermissionScope`].
 
 // =============================================================================
 // Users permissions
@@ -30,6 +39,10 @@ pub const USERS_READ: &str = "users:read";
 pub const USERS_UPDATE: &str = "users:update";
 /// Permission to delete users
 pub const USERS_DELETE: &str = "users:delete";
+/// Permission to read only the user's own record
+pub const USERS_READ_SELF: &str = "users:read:self";
+/// Permission to update only the user's own record
+pub const USERS_UPDATE_SELF: &str = "users:update:self";
 
 // =============================================================================
 // Schools permissions
@@ -56,6 +69,10 @@ pub const STUDENTS_READ: &str = "students:read";
 pub const STUDENTS_UPDATE: &str = "students:update";
 /// Permission to delete students
 pub const STUDENTS_DELETE: &str = "students:delete";
+/// Permission to read students in branches the user currently teaches
+pub const STUDENTS_READ_OWN_BRANCH: &str = "students:read:own_branch";
+/// Permission to update students in branches the user currently teaches
+pub const STUDENTS_UPDATE_OWN_BRANCH: &str = "students:update:own_branch";
 
 // =============================================================================
 // Levels permissions
@@ -71,6 +88,8 @@ pub const LEVELS_UPDATE: &str = "levels:update";
 pub const LEVELS_DELETE: &str = "levels:delete";
 /// Permission to assign students to levels
 pub const LEVELS_ASSIGN_STUDENTS: &str = "levels:assign_students";
+/// Permission to promote students to the next level at the end of a session
+pub const LEVELS_PROMOTE: &str = "levels:promote";
 
 // =============================================================================
 // Branches permissions
@@ -86,6 +105,8 @@ pub const BRANCHES_UPDATE: &str = "branches:update";
 pub const BRANCHES_DELETE: &str = "branches:delete";
 /// Permission to assign students to branches
 pub const BRANCHES_ASSIGN_STUDENTS: &str = "branches:assign_students";
+/// Permission to assign teachers to branches
+pub const BRANCHES_ASSIGN_TEACHERS: &str = "branches:assign_teachers";
 
 // =============================================================================
 // Roles permissions
@@ -119,3 +140,445 @@ pub const REPORTS_EXPORT: &str = "reports:export";
 pub const SETTINGS_READ: &str = "settings:read";
 /// Permission to update settings
 pub const SETTINGS_UPDATE: &str = "settings:update";
+
+// =============================================================================
+// Staff leave permissions
+// =============================================================================
+
+/// Permission to submit and cancel own leave requests
+pub const STAFF_LEAVE_REQUEST: &str = "staff_leave:request";
+/// Permission to read staff leave requests and the leave calendar
+pub const STAFF_LEAVE_READ: &str = "staff_leave:read";
+/// Permission to approve or reject staff leave requests
+pub const STAFF_LEAVE_APPROVE: &str = "staff_leave:approve";
+
+// =============================================================================
+// Asset permissions
+// =============================================================================
+
+/// Permission to register new assets
+pub const ASSETS_CREATE: &str = "assets:create";
+/// Permission to read the asset register
+pub const ASSETS_READ: &str = "assets:read";
+/// Permission to update assets and record condition checks
+pub const ASSETS_UPDATE: &str = "assets:update";
+/// Permission to delete assets
+pub const ASSETS_DELETE: &str = "assets:delete";
+/// Permission to assign assets to rooms or staff
+pub const ASSETS_ASSIGN: &str = "assets:assign";
+
+// =============================================================================
+// Boarding permissions
+// =============================================================================
+
+/// Permission to create hostels, rooms, and boarding fee lines
+pub const BOARDING_CREATE: &str = "boarding:create";
+/// Permission to read hostels, rooms, allocations, and boarding fees
+pub const BOARDING_READ: &str = "boarding:read";
+/// Permission to update hostels and rooms
+pub const BOARDING_UPDATE: &str = "boarding:update";
+/// Permission to delete hostels, rooms, and boarding fee lines
+pub const BOARDING_DELETE: &str = "boarding:delete";
+/// Permission to allocate students to hostel rooms
+pub const BOARDING_ALLOCATE: &str = "boarding:allocate";
+
+// =============================================================================
+// Billing permissions
+// =============================================================================
+
+/// Permission to create fee structures and generate invoices
+pub const BILLING_CREATE: &str = "billing:create";
+/// Permission to read fee structures, invoices, payments, and balances
+pub const BILLING_READ: &str = "billing:read";
+/// Permission to delete fee structures and void invoices
+pub const BILLING_DELETE: &str = "billing:delete";
+/// Permission to record fee payments against invoices
+pub const BILLING_COLLECT: &str = "billing:collect";
+/// Permission to export fee arrears
+pub const BILLING_EXPORT: &str = "billing:export";
+
+// =============================================================================
+// Discipline permissions
+// =============================================================================
+
+/// Permission to record behaviour incidents
+pub const DISCIPLINE_RECORD: &str = "discipline:record";
+/// Permission to read behaviour incidents and trends
+pub const DISCIPLINE_READ: &str = "discipline:read";
+/// Permission to read behaviour incidents of students in branches the user
+/// teaches
+pub const DISCIPLINE_READ_OWN_BRANCH: &str = "discipline:read:own_branch";
+/// Permission to read the user's own behaviour incidents
+pub const DISCIPLINE_READ_SELF: &str = "discipline:read:self";
+/// Permission to update behaviour incidents and notify guardians
+pub const DISCIPLINE_UPDATE: &str = "discipline:update";
+/// Permission to delete behaviour incidents
+pub const DISCIPLINE_DELETE: &str = "discipline:delete";
+
+// =============================================================================
+// Transport permissions
+// =============================================================================
+
+/// Permission to create vehicles, routes, and stops
+pub const TRANSPORT_CREATE: &str = "transport:create";
+/// Permission to read vehicles, routes, assignments, and manifests
+pub const TRANSPORT_READ: &str = "transport:read";
+/// Permission to update vehicles and routes, and generate transport fees
+pub const TRANSPORT_UPDATE: &str = "transport:update";
+/// Permission to delete vehicles, routes, and stops
+pub const TRANSPORT_DELETE: &str = "transport:delete";
+/// Permission to assign students to routes
+pub const TRANSPORT_ASSIGN: &str = "transport:assign";
+/// Permission for drivers to view manifests of their own routes
+pub const TRANSPORT_MANIFEST: &str = "transport:manifest";
+
+// =============================================================================
+// Visitor log permissions
+// =============================================================================
+
+/// Permission to check visitors in
+pub const VISITORS_CREATE: &str = "visitors:create";
+/// Permission to read the visitor log and daily reports
+pub const VISITORS_READ: &str = "visitors:read";
+/// Permission to check visitors out
+pub const VISITORS_UPDATE: &str = "visitors:update";
+/// Permission to delete visitor log entries
+pub const VISITORS_DELETE: &str = "visitors:delete";
+/// Permission to issue and revoke visitor kiosk API keys
+pub const VISITORS_KIOSK: &str = "visitors:kiosk";
+
+// =============================================================================
+// Clinic permissions
+// =============================================================================
+
+/// Permission to read clinic visits and student medical profiles
+pub const CLINIC_READ: &str = "clinic:read";
+/// Permission to record clinic visits, medication, and guardian notifications
+pub const CLINIC_RECORD: &str = "clinic:record";
+/// Permission to update clinic visits and student medical profiles
+pub const CLINIC_UPDATE: &str = "clinic:update";
+/// Permission to delete clinic visits
+pub const CLINIC_DELETE: &str = "clinic:delete";
+
+// =============================================================================
+// Library permissions
+// =============================================================================
+
+/// Permission to add books and copies to the catalog
+pub const LIBRARY_CREATE: &str = "library:create";
+/// Permission to read the catalog, loans, fines, and borrowing history
+pub const LIBRARY_READ: &str = "library:read";
+/// Permission to update books, copies, and the lending policy
+pub const LIBRARY_UPDATE: &str = "library:update";
+/// Permission to remove books and copies from the catalog
+pub const LIBRARY_DELETE: &str = "library:delete";
+/// Permission to check copies out and in
+pub const LIBRARY_LEND: &str = "library:lend";
+
+// =============================================================================
+// Alumni permissions
+// =============================================================================
+
+/// Permission to graduate students into alumni records
+pub const ALUMNI_CREATE: &str = "alumni:create";
+/// Permission to read alumni records
+pub const ALUMNI_READ: &str = "alumni:read";
+/// Permission to update alumni contact details
+pub const ALUMNI_UPDATE: &str = "alumni:update";
+/// Permission to delete alumni records
+pub const ALUMNI_DELETE: &str = "alumni:delete";
+/// Permission to export alumni mailing lists
+pub const ALUMNI_EXPORT: &str = "alumni:export";
+
+// =============================================================================
+// Custom fields permissions
+// =============================================================================
+
+/// Permission to define custom fields
+pub const CUSTOM_FIELDS_CREATE: &str = "custom_fields:create";
+/// Permission to read custom field definitions
+pub const CUSTOM_FIELDS_READ: &str = "custom_fields:read";
+/// Permission to update custom field definitions
+pub const CUSTOM_FIELDS_UPDATE: &str = "custom_fields:update";
+/// Permission to delete custom field definitions
+pub const CUSTOM_FIELDS_DELETE: &str = "custom_fields:delete";
+
+// =============================================================================
+// Recycle bin permissions
+// =============================================================================
+
+/// Permission to view a school's recycle bin
+pub const TRASH_READ: &str = "trash:read";
+/// Permission to restore deleted records
+pub const TRASH_RESTORE: &str = "trash:restore";
+/// Permission to permanently purge deleted records
+pub const TRASH_PURGE: &str = "trash:purge";
+
+// =============================================================================
+// Retention permissions
+// =============================================================================
+
+/// Permission to view retention policies, purge previews, and purge runs
+pub const RETENTION_READ: &str = "retention:read";
+/// Permission to change retention policies and run a purge
+pub const RETENTION_MANAGE: &str = "retention:manage";
+
+// =============================================================================
+// Attendance permissions
+// =============================================================================
+
+/// Permission to mark student attendance
+pub const ATTENDANCE_MARK: &str = "attendance:mark";
+/// Permission to read student attendance
+pub const ATTENDANCE_READ: &str = "attendance:read";
+/// Permission to mark attendance after the school's correction window has closed
+pub const ATTENDANCE_OVERRIDE: &str = "attendance:override";
+/// Permission to register and revoke shared attendance kiosk devices
+pub const KIOSK_DEVICES_MANAGE: &str = "kiosk_devices:manage";
+
+// =============================================================================
+// Assessment and grade permissions
+// =============================================================================
+
+/// Permission to create subjects
+pub const SUBJECTS_CREATE: &str = "subjects:create";
+/// Permission to read subjects
+pub const SUBJECTS_READ: &str = "subjects:read";
+/// Permission to delete subjects
+pub const SUBJECTS_DELETE: &str = "subjects:delete";
+/// Permission to create assessments
+pub const ASSESSMENTS_CREATE: &str = "assessments:create";
+/// Permission to read assessments
+pub const ASSESSMENTS_READ: &str = "assessments:read";
+/// Permission to update assessments
+pub const ASSESSMENTS_UPDATE: &str = "assessments:update";
+/// Permission to delete assessments
+pub const ASSESSMENTS_DELETE: &str = "assessments:delete";
+/// Permission to record student scores
+pub const GRADES_RECORD: &str = "grades:record";
+/// Permission to read student scores and term grades
+pub const GRADES_READ: &str = "grades:read";
+/// Permission to finalize assessment scores
+pub const GRADES_FINALIZE: &str = "grades:finalize";
+/// Permission to configure how assessment components combine into term grades
+pub const GRADING_SCHEMES_MANAGE: &str = "grading_schemes:manage";
+
+// =============================================================================
+// Term result permissions
+// =============================================================================
+
+/// Permission to read term result status and history
+pub const RESULTS_READ: &str = "results:read";
+/// Permission to submit term results for moderation
+pub const RESULTS_SUBMIT: &str = "results:submit";
+/// Permission to moderate term results or return them for correction
+pub const RESULTS_MODERATE: &str = "results:moderate";
+/// Permission to publish moderated term results
+pub const RESULTS_PUBLISH: &str = "results:publish";
+/// Permission to lock published term results
+pub const RESULTS_LOCK: &str = "results:lock";
+/// Permission to unlock locked term results for correction
+pub const RESULTS_UNLOCK: &str = "results:unlock";
+
+// =============================================================================
+// Broadcast permissions
+// =============================================================================
+
+/// Permission to send email and SMS broadcasts
+pub const BROADCASTS_SEND: &str = "broadcasts:send";
+/// Permission to read broadcasts and their delivery status
+pub const BROADCASTS_READ: &str = "broadcasts:read";
+/// Permission to approve broadcasts submitted by others
+pub const BROADCASTS_APPROVE: &str = "broadcasts:approve";
+
+// =============================================================================
+// Announcement permissions
+// =============================================================================
+
+/// Permission to publish announcements
+pub const ANNOUNCEMENTS_CREATE: &str = "announcements:create";
+/// Permission to read announcements and their read counts
+pub const ANNOUNCEMENTS_READ: &str = "announcements:read";
+/// Permission to delete announcements
+pub const ANNOUNCEMENTS_DELETE: &str = "announcements:delete";
+/// Permission to approve announcements submitted by others
+pub const ANNOUNCEMENTS_APPROVE: &str = "announcements:approve";
+
+// =============================================================================
+// Messaging permissions
+// =============================================================================
+
+/// Permission to read the user's own conversations and messages
+pub const MESSAGING_READ: &str = "messaging:read";
+/// Permission to start conversations and send messages
+pub const MESSAGING_SEND: &str = "messaging:send";
+
+// =============================================================================
+// Interoperability permissions
+// =============================================================================
+
+/// Permission to export the school's roster in OneRoster format
+pub const INTEROP_EXPORT: &str = "interop:export";
+/// Permission to import rosters in OneRoster format
+pub const INTEROP_IMPORT: &str = "interop:import";
+
+// =============================================================================
+// LMS sync permissions
+// =============================================================================
+
+/// Permission to view LMS sync connections, runs, and conflicts
+pub const SYNC_READ: &str = "sync:read";
+/// Permission to connect LMS products, run syncs, and resolve conflicts
+pub const SYNC_MANAGE: &str = "sync:manage";
+
+// =============================================================================
+// Change feed permissions
+// =============================================================================
+
+/// Permission to read the data change feed
+pub const CHANGES_READ: &str = "changes:read";
+
+// =============================================================================
+// Webhook permissions
+// =============================================================================
+
+/// Permission to register, update, and delete webhook endpoints
+pub const WEBHOOKS_MANAGE: &str = "webhooks:manage";
+/// Permission to read webhook endpoints and their deliveries
+pub const WEBHOOKS_READ: &str = "webhooks:read";
+
+// =============================================================================
+// Guardian permissions
+// =============================================================================
+
+/// Permission to create guardian accounts from student emergency contacts
+pub const GUARDIANS_MANAGE: &str = "guardians:manage";
+/// Permission to read guardian account runs and their conflicts
+pub const GUARDIANS_READ: &str = "guardians:read";
+
+// =============================================================================
+// Single sign-on permissions
+// =============================================================================
+
+/// Permission to configure OIDC identity providers
+pub const SSO_MANAGE: &str = "sso:manage";
+/// Permission to read OIDC identity providers
+pub const SSO_READ: &str = "sso:read";
+
+// =============================================================================
+// School group permissions
+// =============================================================================
+
+/// Permission to create school groups
+pub const GROUPS_CREATE: &str = "groups:create";
+/// Permission to read school groups and their member schools
+pub const GROUPS_READ: &str = "groups:read";
+/// Permission to update school groups, their member schools, and group admins
+pub const GROUPS_UPDATE: &str = "groups:update";
+/// Permission to delete school groups
+pub const GROUPS_DELETE: &str = "groups:delete";
+/// Permission to view aggregate reports across a group's member schools
+pub const GROUPS_REPORTS: &str = "groups:reports";
+
+// =============================================================================
+// Field visibility permissions
+// =============================================================================
+
+/// Permission to see contact details (emails, phone numbers, emergency
+/// contacts, dates of birth) in API responses; without it the fields are
+/// omitted
+pub const CONTACT_DETAILS_READ: &str = "contact_details:read";
+/// Permission to see medical notes (allergies, conditions, medication,
+/// clinic visit symptoms and treatment) in API responses; without it the
+/// fields are omitted
+pub const MEDICAL_NOTES_READ: &str = "medical_notes:read";
+
+// =============================================================================
+// Permission scopes
+// =============================================================================
+
+/// How many records of a resource a permission covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionScope {
+    /// Every record the user's school allows; the bare permission
+    All,
+    /// Records in branches the user currently teaches (`:own_branch`)
+    OwnBranch,
+    /// Only the user's own record (`:self`)
+    Own,
+}
+
+impl PermissionScope {
+    /// Scopes from widest to narrowest.
+    pub const ALL: [PermissionScope; 3] = [Self::All, Self::OwnBranch, Self::Own];
+
+    /// The modifier appended to a permission name, if any.
+    #[must_use]
+    pub const fn suffix(self) -> Option<&'static str> {
+        match self {
+            Self::All => None,
+            Self::OwnBranch => Some("own_branch"),
+            Self::Own => Some("self"),
+        }
+    }
+
+    /// The permission name granting `permission` with this scope.
+    ///
+    /// ```
+    /// use chalkbyte_core::permissions::PermissionScope;
+    ///
+    /// assert_eq!(PermissionScope::Own.apply("users:update"), "users:update:self");
+    /// ```
+    #[must_use]
+    pub fn apply(self, permission: &str) -> String {
+        match self.suffix() {
+            Some(suffix) => format!("{permission}:{suffix}"),
+            None => permission.to_string(),
+        }
+    }
+
+    /// The widest scope the granted permission names give for `permission`,
+    /// or `None` if they grant it in no scope.
+    #[must_use]
+    pub fn resolve<S: AsRef<str>>(granted: &[S], permission: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|scope| {
+            let name = scope.apply(permission);
+            granted.iter().any(|g| g.as_ref() == name)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_prefers_widest_scope() {
+        let granted = [STUDENTS_READ_OWN_BRANCH, STUDENTS_READ];
+        assert_eq!(
+            PermissionScope::resolve(&granted, STUDENTS_READ),
+            Some(PermissionScope::All)
+        );
+
+        let granted = [STUDENTS_READ_OWN_BRANCH, USERS_UPDATE_SELF];
+        assert_eq!(
+            PermissionScope::resolve(&granted, STUDENTS_READ),
+            Some(PermissionScope::OwnBranch)
+        );
+        assert_eq!(
+            PermissionScope::resolve(&granted, USERS_UPDATE),
+            Some(PermissionScope::Own)
+        );
+        assert_eq!(PermissionScope::resolve(&granted, STUDENTS_UPDATE), None);
+    }
+
+    #[test]
+    fn test_scoped_constants_match_apply() {
+        assert_eq!(
+            PermissionScope::OwnBranch.apply(STUDENTS_READ),
+            STUDENTS_READ_OWN_BRANCH
+        );
+        assert_eq!(PermissionScope::Own.apply(USERS_READ), USERS_READ_SELF);
+        assert_eq!(PermissionScope::All.apply(USERS_READ), USERS_READ);
+    }
+}
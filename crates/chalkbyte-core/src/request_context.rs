@@ -0,0 +1,70 @@
+//! Request ID and locale propagation.
+//!
+//! The HTTP layer runs each request inside [`scope`], which makes the
+//! request's ID available to anything the request calls through
+//! [`current_request_id`]: error responses, outgoing emails, and the database
+//! pool, which tags the request's audit entries with it.
+//!
+//! Requests with an `Accept-Language` header also run inside
+//! [`scope_locale`], so error responses can use the client's language.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_core::request_context;
+//!
+//! let response = request_context::scope(request_id, next.run(request)).await;
+//! ```
+
+use std::future::Future;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+    static LOCALE: String;
+}
+
+/// Runs `future` with `request_id` as the current request ID.
+pub async fn scope<F: Future>(request_id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(request_id, future).await
+}
+
+/// Returns the ID of the request being handled, if any.
+///
+/// Background jobs run outside any request and get `None`.
+#[must_use]
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Runs `future` with `locale`, a language tag such as `fr` or `pt-BR`, as
+/// the current locale.
+pub async fn scope_locale<F: Future>(locale: String, future: F) -> F::Output {
+    LOCALE.scope(locale, future).await
+}
+
+/// Returns the locale of the request being handled, if the client sent one.
+#[must_use]
+pub fn current_locale() -> Option<String> {
+    LOCALE.try_with(Clone::clone).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_current_request_id_in_scope() {
+        assert_eq!(current_request_id(), None);
+
+        let id = scope("req-123".to_string(), async { current_request_id() }).await;
+        assert_eq!(id.as_deref(), Some("req-123"));
+    }
+
+    #[tokio::test]
+    async fn test_current_locale_in_scope() {
+        assert_eq!(current_locale(), None);
+
+        let locale = scope_locale("fr".to_string(), async { current_locale() }).await;
+        assert_eq!(locale.as_deref(), Some("fr"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Typed success responses.
+//!
+//! Handlers that create a resource return [`Created<T>`] and handlers that
+//! have nothing to send back return [`NoContent`], so the status code follows
+//! from the handler's return type rather than from each handler building it.
+//!
+//! Both types also implement [`IntoResponses`], so naming them in a
+//! `#[utoipa::path]` `responses(...)` list documents the same status code the
+//! handler sends.
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[utoipa::path(
+//!     post,
+//!     path = "/api/levels",
+//!     responses(
+//!         Created<Level>,
+//!         (status = 400, description = "Invalid input")
+//!     )
+//! )]
+//! pub async fn create_level(/* ... */) -> Result<Created<Level>, AppError> {
+//!     let level = LevelService::create_level(/* ... */).await?;
+//!     Ok(Created(level))
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use utoipa::{
+    IntoResponses, ToSchema,
+    openapi::{ContentBuilder, Ref, RefOr, ResponseBuilder, response::Response as OpenApiResponse},
+};
+
+/// `201 Created` with `T` as the JSON body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Created<T>(pub T);
+
+impl<T: Serialize> IntoResponse for Created<T> {
+    fn into_response(self) -> Response {
+        (StatusCode::CREATED, Json(self.0)).into_response()
+    }
+}
+
+impl<T: ToSchema> IntoResponses for Created<T> {
+    fn responses() -> BTreeMap<String, RefOr<OpenApiResponse>> {
+        let content = ContentBuilder::new()
+            .schema(Some(Ref::from_schema_name(T::name())))
+            .build();
+        let response = ResponseBuilder::new()
+            .description("Created")
+            .content("application/json", content)
+            .build();
+
+        BTreeMap::from([("201".to_string(), response.into())])
+    }
+}
+
+/// `204 No Content` with an empty body.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoContent;
+
+impl IntoResponse for NoContent {
+    fn into_response(self) -> Response {
+        StatusCode::NO_CONTENT.into_response()
+    }
+}
+
+impl IntoResponses for NoContent {
+    fn responses() -> BTreeMap<String, RefOr<OpenApiResponse>> {
+        let response = ResponseBuilder::new().description("No content").build();
+
+        BTreeMap::from([("204".to_string(), response.into())])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, ToSchema)]
+    struct Widget {
+        name: String,
+    }
+
+    #[test]
+    fn test_created_sets_status_and_body() {
+        let response = Created(Widget {
+            name: "gear".into(),
+        })
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()["content-type"], "application/json");
+    }
+
+    #[test]
+    fn test_no_content_has_empty_body() {
+        let response = NoContent.into_response();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(response.headers().get("content-type").is_none());
+    }
+
+    #[test]
+    fn test_documented_statuses() {
+        let created = Created::<Widget>::responses();
+        let RefOr::T(response) = &created["201"] else {
+            panic!("expected an inline response");
+        };
+        let schema = &response.content["application/json"].schema;
+        assert!(matches!(
+            schema,
+            Some(RefOr::Ref(r)) if r.ref_location == "#/components/schemas/Widget"
+        ));
+
+        assert_eq!(
+            NoContent::responses().keys().collect::<Vec<_>>(),
+            vec!["204"]
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Sorting for list endpoints.
+//!
+//! Clients pass a comma-separated list of fields in `sort`, each optionally
+//! prefixed with `-` for descending order:
+//!
+//! ```text
+//! GET /api/users?sort=last_name,-created_at
+//! ```
+//!
+//! Field names are never interpolated into SQL. Each list declares the
+//! fields it can be sorted by in a [`Sortable`], mapping the name clients use
+//! to the column it orders by, and any other name is rejected with
+//! `INVALID_SORT`. A unique tiebreaker column is appended so pages are
+//! stable when sorted values repeat.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_core::sorting::{SortParams, Sortable};
+//!
+//! const SCHOOL_SORT: Sortable = Sortable {
+//!     columns: &[("name", "name"), ("created_at", "created_at")],
+//!     default: "-created_at",
+//!     tiebreaker: "id",
+//! };
+//!
+//! let params = SortParams { sort: Some("name".to_string()) };
+//! assert_eq!(SCHOOL_SORT.order_by(&params)?, "name ASC, id ASC");
+//! ```
+//!
+//! Sorting applies to offset and page pagination. Cursor pagination always
+//! orders newest first by `(created_at, id)` and ignores `sort`.
+
+use anyhow::anyhow;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::error_codes::ErrorCode;
+use crate::errors::AppError;
+
+/// Query parameter selecting the order of a list.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Deserialize, ToSchema)]
+pub struct SortParams {
+    /// Comma-separated fields to sort by; prefix a field with `-` for
+    /// descending order, e.g. `last_name,-created_at`
+    #[serde(default)]
+    pub sort: Option<String>,
+}
+
+/// One field of a parsed `sort` parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey<'a> {
+    /// Field name as the client gave it
+    pub field: &'a str,
+    pub descending: bool,
+}
+
+impl SortParams {
+    /// Parses the `sort` parameter into its fields, without checking them
+    /// against an allowlist. Empty when no sort was given.
+    pub fn keys(&self) -> Result<Vec<SortKey<'_>>, AppError> {
+        let Some(sort) = self.sort.as_deref().map(str::trim) else {
+            return Ok(Vec::new());
+        };
+        if sort.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        sort.split(',')
+            .map(|part| {
+                let part = part.trim();
+                let (field, descending) = match part.strip_prefix('-') {
+                    Some(field) => (field, true),
+                    None => (part.strip_prefix('+').unwrap_or(part), false),
+                };
+                if field.is_empty() {
+                    return Err(invalid_sort(format!("Invalid sort field '{part}'")));
+                }
+                Ok(SortKey { field, descending })
+            })
+            .collect()
+    }
+}
+
+/// The fields a list can be sorted by.
+#[derive(Debug, Clone, Copy)]
+pub struct Sortable {
+    /// `(field, column)` pairs: the name clients sort by and the SQL column
+    /// or expression it orders by
+    pub columns: &'static [(&'static str, &'static str)],
+    /// Sort used when the request has none, in `sort` syntax
+    pub default: &'static str,
+    /// Unique column appended last, in the direction of the last field
+    pub tiebreaker: &'static str,
+}
+
+impl Sortable {
+    /// Builds the body of an `ORDER BY` clause for the request's sort, or the
+    /// default sort if it has none.
+    ///
+    /// Fails with a 400 `INVALID_SORT` error naming the sortable fields if a
+    /// field is unknown or repeated.
+    pub fn order_by(&self, params: &SortParams) -> Result<String, AppError> {
+        let mut keys = params.keys()?;
+        let default = SortParams {
+            sort: Some(self.default.to_string()),
+        };
+        if keys.is_empty() {
+            keys = default.keys()?;
+        }
+
+        let mut terms = Vec::with_capacity(keys.len() + 1);
+        let mut used = Vec::with_capacity(keys.len());
+        for key in &keys {
+            let Some(&(_, column)) = self.columns.iter().find(|(name, _)| *name == key.field)
+            else {
+                return Err(invalid_sort(format!(
+                    "Cannot sort by '{}'; sortable fields are: {}",
+                    key.field,
+                    self.field_names()
+                )));
+            };
+            if used.contains(&column) {
+                return Err(invalid_sort(format!(
+                    "Sort field '{}' is given more than once",
+                    key.field
+                )));
+            }
+            used.push(column);
+            terms.push(format!("{column} {}", direction(key.descending)));
+        }
+
+        if !used.contains(&self.tiebreaker) {
+            let descending = keys.last().is_some_and(|key| key.descending);
+            terms.push(format!("{} {}", self.tiebreaker, direction(descending)));
+        }
+
+        Ok(terms.join(", "))
+    }
+
+    /// The sortable field names, comma-separated, for error messages and docs.
+    #[must_use]
+    pub fn field_names(&self) -> String {
+        self.columns
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn direction(descending: bool) -> &'static str {
+    if descending { "DESC" } else { "ASC" }
+}
+
+fn invalid_sort(message: String) -> AppError {
+    AppError::bad_request(anyhow!(message)).with_code(ErrorCode::InvalidSort)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    const USERS: Sortable = Sortable {
+        columns: &[
+            ("last_name", "u.last_name"),
+            ("created_at", "u.created_at"),
+            ("id", "u.id"),
+        ],
+        default: "-created_at",
+        tiebreaker: "u.id",
+    };
+
+    fn sort(value: &str) -> SortParams {
+        SortParams {
+            sort: Some(value.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_order_by_maps_fields_and_appends_tiebreaker() {
+        assert_eq!(
+            USERS.order_by(&sort("last_name,-created_at")).unwrap(),
+            "u.last_name ASC, u.created_at DESC, u.id DESC"
+        );
+        assert_eq!(
+            USERS.order_by(&sort(" +last_name ")).unwrap(),
+            "u.last_name ASC, u.id ASC"
+        );
+        assert_eq!(USERS.order_by(&sort("-id")).unwrap(), "u.id DESC");
+    }
+
+    #[test]
+    fn test_order_by_uses_default_without_sort() {
+        let expected = "u.created_at DESC, u.id DESC";
+        assert_eq!(USERS.order_by(&SortParams::default()).unwrap(), expected);
+        assert_eq!(USERS.order_by(&sort("")).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_order_by_rejects_fields_outside_allowlist() {
+        for value in [
+            "password_hash",
+            "u.last_name",
+            "last_name; DROP TABLE users",
+            "last_name,,created_at",
+            "-",
+            "last_name,-last_name",
+        ] {
+            let error = USERS.order_by(&sort(value)).unwrap_err();
+            assert_eq!(error.status, StatusCode::BAD_REQUEST, "{value}");
+            assert_eq!(error.code, ErrorCode::InvalidSort, "{value}");
+        }
+
+        let error = USERS.order_by(&sort("email")).unwrap_err();
+        assert_eq!(
+            error.error.to_string(),
+            "Cannot sort by 'email'; sortable fields are: last_name, created_at, id"
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! API versions and the response shapes each one expects.
+//!
+//! Every route is served under a version prefix, e.g. `/api/v1/students`.
+//! The unversioned `/api` prefix is a deprecated alias: it answers as v1
+//! unless the client asks for another version with the `Api-Version`
+//! header. The router records the version of each request, and handlers
+//! read it with the [`ApiVersion`] extractor.
+//!
+//! All versions share the same routers, handlers, and DTOs. When a version
+//! changes a DTO incompatibly, the type serializes in its newest shape and
+//! implements [`VersionedView::to_version`] to build the older shapes, and
+//! its handlers render through [`ApiVersion::render`]. Types that never
+//! change keep the default implementation.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_core::versioning::{ApiVersion, VersionedView};
+//!
+//! impl VersionedView for Student {}
+//!
+//! async fn get_student(version: ApiVersion, /* ... */) -> Result<Response, AppError> {
+//!     let student = fetch_student().await?;
+//!     Ok(version.render(student))
+//! }
+//! ```
+
+use std::convert::Infallible;
+use std::fmt;
+
+use axum::{
+    Json,
+    extract::FromRequestParts,
+    http::{HeaderMap, HeaderName, HeaderValue, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::errors::AppError;
+
+/// Header a client sends on the `/api` alias to pick a version, and that
+/// every versioned response carries.
+pub const API_VERSION_HEADER: HeaderName = HeaderName::from_static("api-version");
+
+/// A version of the HTTP API.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ApiVersion {
+    /// The first version, also served by the `/api` alias.
+    #[default]
+    V1,
+}
+
+impl ApiVersion {
+    /// Every version the API serves, oldest first.
+    pub const ALL: &'static [Self] = &[Self::V1];
+
+    /// The newest version.
+    pub const LATEST: Self = Self::V1;
+
+    /// Name used in paths and the `Api-Version` header, e.g. `v1`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::V1 => "v1",
+        }
+    }
+
+    /// Path prefix the version's routes are mounted under, e.g. `/api/v1`.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::V1 => "/api/v1",
+        }
+    }
+
+    /// Parses a version name, with or without the leading `v`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let number = value.strip_prefix(['v', 'V']).unwrap_or(value);
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|version| &version.as_str()[1..] == number)
+    }
+
+    /// The version asked for in the `Api-Version` header, if any.
+    ///
+    /// Fails with `400 Bad Request` naming the supported versions when the
+    /// header holds anything else.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Option<Self>, AppError> {
+        let Some(value) = headers.get(&API_VERSION_HEADER) else {
+            return Ok(None);
+        };
+        value
+            .to_str()
+            .ok()
+            .and_then(Self::parse)
+            .map(Some)
+            .ok_or_else(|| {
+                let supported: Vec<&str> = Self::ALL.iter().map(|v| v.as_str()).collect();
+                AppError::bad_request(anyhow::anyhow!(
+                    "Unsupported API version; supported versions are {}",
+                    supported.join(", ")
+                ))
+            })
+    }
+
+    /// The version the router recorded for the request, or v1 for routes
+    /// mounted outside the API.
+    pub fn from_parts(parts: &Parts) -> Self {
+        parts.extensions.get::<Self>().copied().unwrap_or_default()
+    }
+
+    /// `Api-Version` header value for this version.
+    pub fn header_value(self) -> HeaderValue {
+        HeaderValue::from_static(self.as_str())
+    }
+
+    /// Serializes `value` as JSON in the shape this version expects.
+    ///
+    /// Responses vary on `Api-Version`, since the `/api` alias picks the
+    /// version from it.
+    pub fn render<T: VersionedView>(self, value: T) -> Response {
+        let mut response = match value.to_version(self) {
+            Ok(json) => Json(json).into_response(),
+            Err(e) => return AppError::internal(e).into_response(),
+        };
+        response
+            .headers_mut()
+            .append(header::VARY, HeaderValue::from_static("api-version"));
+        response
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A response type whose shape may differ between API versions.
+pub trait VersionedView: Serialize {
+    /// Serializes into the shape `version` expects.
+    ///
+    /// Defaults to the type's own serialization for every version. Override
+    /// it when a version changes the DTO, building the older shapes from
+    /// the newest one.
+    fn to_version(&self, version: ApiVersion) -> serde_json::Result<Value> {
+        let _ = version;
+        serde_json::to_value(self)
+    }
+}
+
+impl<T: VersionedView> VersionedView for Vec<T> {
+    fn to_version(&self, version: ApiVersion) -> serde_json::Result<Value> {
+        self.iter()
+            .map(|item| item.to_version(version))
+            .collect::<serde_json::Result<Vec<_>>>()
+            .map(Value::Array)
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ApiVersion {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_parts(parts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{Request, StatusCode};
+
+    fn headers_with(version: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(API_VERSION_HEADER, HeaderValue::from_str(version).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_parse_version_names() {
+        assert_eq!(ApiVersion::parse("v1"), Some(ApiVersion::V1));
+        assert_eq!(ApiVersion::parse(" V1 "), Some(ApiVersion::V1));
+        assert_eq!(ApiVersion::parse("1"), Some(ApiVersion::V1));
+        assert_eq!(ApiVersion::parse("v0"), None);
+        assert_eq!(ApiVersion::parse("v"), None);
+        assert_eq!(ApiVersion::parse(""), None);
+    }
+
+    #[test]
+    fn test_every_version_has_its_own_prefix() {
+        for version in ApiVersion::ALL {
+            assert_eq!(version.prefix(), format!("/api/{}", version));
+            assert_eq!(ApiVersion::parse(version.as_str()), Some(*version));
+        }
+        assert_eq!(ApiVersion::ALL.last(), Some(&ApiVersion::LATEST));
+    }
+
+    #[test]
+    fn test_version_from_headers() {
+        assert_eq!(ApiVersion::from_headers(&HeaderMap::new()).unwrap(), None);
+        assert_eq!(
+            ApiVersion::from_headers(&headers_with("v1")).unwrap(),
+            Some(ApiVersion::V1)
+        );
+
+        let err = ApiVersion::from_headers(&headers_with("v9")).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_version_from_extensions_defaults_to_v1() {
+        let (mut parts, _) = Request::builder().body(()).unwrap().into_parts();
+        assert_eq!(ApiVersion::from_parts(&parts), ApiVersion::V1);
+
+        parts.extensions.insert(ApiVersion::LATEST);
+        assert_eq!(ApiVersion::from_parts(&parts), ApiVersion::LATEST);
+    }
+
+    #[test]
+    fn test_render_uses_versioned_shape() {
+        #[derive(Serialize)]
+        struct Name {
+            first_name: String,
+        }
+
+        impl VersionedView for Name {
+            fn to_version(&self, version: ApiVersion) -> serde_json::Result<Value> {
+                match version {
+                    ApiVersion::V1 => Ok(serde_json::json!({ "name": self.first_name })),
+                }
+            }
+        }
+
+        let names = vec![Name {
+            first_name: "Ada".to_string(),
+        }];
+        assert_eq!(
+            names.to_version(ApiVersion::V1).unwrap(),
+            serde_json::json!([{ "name": "Ada" }])
+        );
+
+        let response = ApiVersion::V1.render(names);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::VARY], "api-version");
+    }
+}
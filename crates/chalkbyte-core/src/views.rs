@@ -0,0 +1,214 @@
+//! Response views for bandwidth-constrained clients.
+//!
+//! The heaviest read endpoints can return a *lite* view: a pre-defined,
+//! slimmer DTO that drops nested relations, custom fields, and timestamps.
+//! Clients opt in per request with either:
+//!
+//! - the `X-Client: mobile-lite` header, or
+//! - the `view=lite` query parameter.
+//!
+//! Anything else gets the full view, so existing clients are unaffected.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_core::views::{LiteView, ResponseView};
+//!
+//! async fn get_student(view: ResponseView, /* ... */) -> Result<Response, AppError> {
+//!     let student = fetch_student().await?;
+//!     Ok(view.render(student))
+//! }
+//! ```
+
+use std::convert::Infallible;
+
+use axum::{
+    Json,
+    extract::FromRequestParts,
+    http::{HeaderName, HeaderValue, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+use crate::pagination::CursorPage;
+use crate::visibility::{FieldAccess, FieldVisibility, to_visible_json};
+
+/// Header a client sends to request a view.
+pub const CLIENT_HEADER: HeaderName = HeaderName::from_static("x-client");
+
+/// `X-Client` value that selects the lite view.
+pub const MOBILE_LITE_CLIENT: &str = "mobile-lite";
+
+/// Which shape of response the client asked for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ResponseView {
+    /// The complete DTO.
+    #[default]
+    Full,
+    /// The slimmer DTO defined by the type's [`LiteView`] implementation.
+    Lite,
+}
+
+/// A response type with a pre-defined slimmer representation.
+pub trait LiteView {
+    /// The slimmer DTO sent to lite clients.
+    type Lite: Serialize;
+
+    /// Converts into the lite representation.
+    fn into_lite(self) -> Self::Lite;
+}
+
+impl<T: LiteView> LiteView for Vec<T> {
+    type Lite = Vec<T::Lite>;
+
+    fn into_lite(self) -> Self::Lite {
+        self.into_iter().map(LiteView::into_lite).collect()
+    }
+}
+
+impl<T: LiteView> LiteView for CursorPage<T> {
+    type Lite = CursorPage<T::Lite>;
+
+    fn into_lite(self) -> Self::Lite {
+        CursorPage {
+            data: self.data.into_lite(),
+            meta: self.meta,
+        }
+    }
+}
+
+impl ResponseView {
+    /// Picks the view from the `X-Client` header and `view` query parameter.
+    pub fn from_parts(parts: &Parts) -> Self {
+        let lite_client = parts
+            .headers
+            .get(&CLIENT_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|client| client.eq_ignore_ascii_case(MOBILE_LITE_CLIENT));
+
+        let lite_query = parts
+            .uri
+            .query()
+            .is_some_and(|query| query.split('&').any(|pair| pair == "view=lite"));
+
+        if lite_client || lite_query {
+            Self::Lite
+        } else {
+            Self::Full
+        }
+    }
+
+    /// Serializes `value` as JSON in this view.
+    ///
+    /// Responses vary on `X-Client` so shared caches keep the views apart.
+    pub fn render<T>(self, value: T) -> Response
+    where
+        T: Serialize + LiteView,
+    {
+        let mut response = match self {
+            Self::Full => Json(value).into_response(),
+            Self::Lite => Json(value.into_lite()).into_response(),
+        };
+        response
+            .headers_mut()
+            .append(header::VARY, HeaderValue::from_static("x-client"));
+        response
+    }
+
+    /// [`render`](Self::render), with the fields `access` does not allow
+    /// removed from either view. See [`crate::visibility`].
+    pub fn render_visible<T>(self, value: T, access: &FieldAccess) -> Response
+    where
+        T: Serialize + LiteView + FieldVisibility,
+        T::Lite: FieldVisibility,
+    {
+        let json = match self {
+            Self::Full => to_visible_json(&value, access),
+            Self::Lite => to_visible_json(&value.into_lite(), access),
+        };
+        let mut response = match json {
+            Ok(json) => Json(json).into_response(),
+            Err(e) => return e.into_response(),
+        };
+        response
+            .headers_mut()
+            .append(header::VARY, HeaderValue::from_static("x-client"));
+        response
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ResponseView {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_parts(parts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    fn view_for(uri: &str, client: Option<&str>) -> ResponseView {
+        let mut builder = Request::builder().uri(uri);
+        if let Some(client) = client {
+            builder = builder.header("X-Client", client);
+        }
+        let (parts, _) = builder.body(()).unwrap().into_parts();
+        ResponseView::from_parts(&parts)
+    }
+
+    #[test]
+    fn test_defaults_to_full_view() {
+        assert_eq!(view_for("/api/users", None), ResponseView::Full);
+        assert_eq!(view_for("/api/users?view=full", None), ResponseView::Full);
+        assert_eq!(view_for("/api/users", Some("web")), ResponseView::Full);
+    }
+
+    #[test]
+    fn test_lite_view_from_header() {
+        assert_eq!(
+            view_for("/api/users", Some("mobile-lite")),
+            ResponseView::Lite
+        );
+        assert_eq!(
+            view_for("/api/users", Some("Mobile-Lite")),
+            ResponseView::Lite
+        );
+    }
+
+    #[test]
+    fn test_lite_view_from_query() {
+        assert_eq!(
+            view_for("/api/users?limit=5&view=lite", None),
+            ResponseView::Lite
+        );
+        assert_eq!(
+            view_for("/api/users?preview=lite", None),
+            ResponseView::Full
+        );
+    }
+
+    #[test]
+    fn test_render_sets_vary_header() {
+        struct Name(String);
+
+        impl Serialize for Name {
+            fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                s.serialize_str(&self.0)
+            }
+        }
+
+        impl LiteView for Name {
+            type Lite = usize;
+
+            fn into_lite(self) -> usize {
+                self.0.len()
+            }
+        }
+
+        let response = ResponseView::Lite.render(vec![Name("Ada".to_string())]);
+        assert_eq!(response.headers()[header::VARY], "x-client");
+    }
+}
@@ -0,0 +1,249 @@
+//! Field-level response visibility.
+//!
+//! Some response fields, such as contact details and medical notes, are only
+//! for callers holding a permission. A type declares those fields once, in its
+//! [`FieldVisibility`] implementation next to the type, and [`Visible`] (or
+//! [`ResponseView::render_visible`](crate::ResponseView::render_visible))
+//! drops the ones the caller may not see while serializing. One DTO serves
+//! every role, and a handler cannot forget the check for one of them.
+//!
+//! Hidden fields are omitted from the JSON rather than set to `null`, so a
+//! client can tell "not recorded" from "not yours to see".
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_core::visibility::{FieldVisibility, RestrictedField, Visible};
+//!
+//! impl FieldVisibility for Alumnus {
+//!     const RESTRICTED: &'static [RestrictedField] = &[
+//!         RestrictedField::new("email", permissions::CONTACT_DETAILS_READ),
+//!         RestrictedField::new("phone", permissions::CONTACT_DETAILS_READ),
+//!     ];
+//! }
+//!
+//! async fn get_alumnus(auth_user: AuthUser, /* ... */) -> Result<Visible<Alumnus>, AppError> {
+//!     let alumnus = fetch_alumnus().await?;
+//!     Ok(Visible::new(alumnus, auth_user.field_access()))
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    response::{IntoResponse, Response},
+};
+use serde::{Serialize, Serializer, ser::Error as _};
+use serde_json::Value;
+
+use crate::errors::AppError;
+use crate::pagination::CursorPage;
+
+/// A response field only callers with `permission` may see.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestrictedField {
+    /// The field's name in the serialized JSON
+    pub name: &'static str,
+    /// The permission that reveals it
+    pub permission: &'static str,
+}
+
+impl RestrictedField {
+    pub const fn new(name: &'static str, permission: &'static str) -> Self {
+        Self { name, permission }
+    }
+}
+
+/// The permissions a caller's response is filtered by.
+#[derive(Debug, Clone, Default, Hash)]
+pub struct FieldAccess {
+    granted: Arc<[String]>,
+}
+
+impl FieldAccess {
+    pub fn new(granted: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            granted: granted.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Whether fields restricted to `permission` are visible.
+    pub fn allows(&self, permission: &str) -> bool {
+        self.granted.iter().any(|granted| granted == permission)
+    }
+}
+
+/// A response type with fields only some callers may see.
+pub trait FieldVisibility {
+    /// This type's restricted fields.
+    const RESTRICTED: &'static [RestrictedField] = &[];
+
+    /// Removes the fields `access` does not allow from `value`, the
+    /// serialized form of a `Self`.
+    ///
+    /// Types that hold other [`FieldVisibility`] types override this to
+    /// descend into them with [`hide_in`], after calling [`hide_own`].
+    fn hide_fields(value: &mut Value, access: &FieldAccess) {
+        hide_own::<Self>(value, access);
+    }
+}
+
+/// Removes `T`'s own restricted fields that `access` does not allow.
+pub fn hide_own<T: FieldVisibility + ?Sized>(value: &mut Value, access: &FieldAccess) {
+    if let Value::Object(map) = value {
+        for field in T::RESTRICTED {
+            if !access.allows(field.permission) {
+                map.remove(field.name);
+            }
+        }
+    }
+}
+
+/// Applies `T`'s visibility to the `field` of `value`, if present.
+pub fn hide_in<T: FieldVisibility + ?Sized>(value: &mut Value, field: &str, access: &FieldAccess) {
+    if let Some(nested) = value.get_mut(field) {
+        T::hide_fields(nested, access);
+    }
+}
+
+impl<T: FieldVisibility> FieldVisibility for Vec<T> {
+    fn hide_fields(value: &mut Value, access: &FieldAccess) {
+        if let Value::Array(items) = value {
+            for item in items {
+                T::hide_fields(item, access);
+            }
+        }
+    }
+}
+
+impl<T: FieldVisibility> FieldVisibility for Option<T> {
+    fn hide_fields(value: &mut Value, access: &FieldAccess) {
+        T::hide_fields(value, access);
+    }
+}
+
+impl<T: FieldVisibility> FieldVisibility for CursorPage<T> {
+    fn hide_fields(value: &mut Value, access: &FieldAccess) {
+        hide_in::<Vec<T>>(value, "data", access);
+    }
+}
+
+/// Serializes `value` with the fields `access` does not allow removed.
+pub fn to_visible_json<T>(value: &T, access: &FieldAccess) -> Result<Value, AppError>
+where
+    T: Serialize + FieldVisibility,
+{
+    let mut json = serde_json::to_value(value).map_err(AppError::internal)?;
+    T::hide_fields(&mut json, access);
+    Ok(json)
+}
+
+/// A JSON response with the fields the caller may not see removed.
+///
+/// It also serializes filtered, so it can be wrapped, e.g. in
+/// [`Created`](crate::Created).
+pub struct Visible<T> {
+    value: T,
+    access: FieldAccess,
+}
+
+impl<T> Visible<T> {
+    pub fn new(value: T, access: FieldAccess) -> Self {
+        Self { value, access }
+    }
+}
+
+impl<T> Serialize for Visible<T>
+where
+    T: Serialize + FieldVisibility,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut json = serde_json::to_value(&self.value).map_err(S::Error::custom)?;
+        T::hide_fields(&mut json, &self.access);
+        json.serialize(serializer)
+    }
+}
+
+impl<T> IntoResponse for Visible<T>
+where
+    T: Serialize + FieldVisibility,
+{
+    fn into_response(self) -> Response {
+        match to_visible_json(&self.value, &self.access) {
+            Ok(json) => Json(json).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct Profile {
+        name: &'static str,
+        phone: &'static str,
+        notes: &'static str,
+    }
+
+    impl FieldVisibility for Profile {
+        const RESTRICTED: &'static [RestrictedField] = &[
+            RestrictedField::new("phone", "contact_details:read"),
+            RestrictedField::new("notes", "medical_notes:read"),
+        ];
+    }
+
+    #[derive(Serialize)]
+    struct Visit {
+        id: u32,
+        profile: Option<Profile>,
+    }
+
+    impl FieldVisibility for Visit {
+        fn hide_fields(value: &mut Value, access: &FieldAccess) {
+            hide_own::<Self>(value, access);
+            hide_in::<Option<Profile>>(value, "profile", access);
+        }
+    }
+
+    fn profile() -> Profile {
+        Profile {
+            name: "Ada",
+            phone: "555",
+            notes: "Asthma",
+        }
+    }
+
+    #[test]
+    fn test_hides_fields_without_their_permission() {
+        let access = FieldAccess::new(["contact_details:read"]);
+        let json = to_visible_json(&vec![profile()], &access).unwrap();
+        assert_eq!(json, json!([{ "name": "Ada", "phone": "555" }]));
+
+        let json = to_visible_json(&profile(), &FieldAccess::default()).unwrap();
+        assert_eq!(json, json!({ "name": "Ada" }));
+    }
+
+    #[test]
+    fn test_descends_into_nested_types() {
+        let visit = Visit {
+            id: 1,
+            profile: Some(profile()),
+        };
+        let json = to_visible_json(&visit, &FieldAccess::new(["medical_notes:read"])).unwrap();
+        assert_eq!(
+            json,
+            json!({ "id": 1, "profile": { "name": "Ada", "notes": "Asthma" } })
+        );
+
+        let visit = Visit {
+            id: 2,
+            profile: None,
+        };
+        let json = to_visible_json(&visit, &FieldAccess::default()).unwrap();
+        assert_eq!(json, json!({ "id": 2, "profile": null }));
+    }
+}
@@ -0,0 +1,5 @@
+// Rebuild when a migration is added or edited, so the embedded migrations
+// never go stale.
+fn main() {
+    println!("cargo:rerun-if-changed=../../migrations");
+}
@@ -0,0 +1,164 @@
+//! Database connection pool configuration.
+//!
+//! This module provides configuration for the PostgreSQL pool loaded from
+//! environment variables.
+
+use std::env;
+use std::time::Duration;
+
+/// Database pool configuration loaded from environment variables.
+///
+/// # Environment Variables
+///
+/// - `DATABASE_URL`: PostgreSQL connection URL (required)
+/// - `DATABASE_REPLICA_URLS`: Comma-separated read replica URLs (default:
+///   none; reads go to the primary)
+/// - `DB_MAX_CONNECTIONS`: Most connections the pool opens (default: `10`)
+/// - `DB_MIN_CONNECTIONS`: Connections kept open while idle (default: `0`)
+/// - `DB_ACQUIRE_TIMEOUT_SECONDS`: How long a query waits for a free
+///   connection before failing (default: `30`)
+/// - `DB_IDLE_TIMEOUT_SECONDS`: How long an unused connection stays open
+///   (default: `600`; `0` keeps them open)
+/// - `DB_STATEMENT_TIMEOUT_MS`: Server-side limit on each statement
+///   (default: `0`, no limit)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DbConfig {
+    /// PostgreSQL connection URL; empty when `DATABASE_URL` is not set.
+    pub database_url: String,
+
+    /// Read replica URLs; each replica gets a pool with the same settings.
+    pub replica_urls: Vec<String>,
+
+    /// Most connections the pool opens.
+    pub max_connections: u32,
+
+    /// Connections kept open while idle.
+    pub min_connections: u32,
+
+    /// How long a query waits for a free connection.
+    pub acquire_timeout: Duration,
+
+    /// How long an unused connection stays open; `None` keeps them open.
+    pub idle_timeout: Option<Duration>,
+
+    /// Server-side limit on each statement; `None` for no limit.
+    pub statement_timeout: Option<Duration>,
+}
+
+impl DbConfig {
+    /// Load configuration from environment variables.
+    ///
+    /// Unparseable numbers fall back to their defaults; see the type docs.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Load configuration from the variables `lookup` returns.
+    ///
+    /// [`from_env`](Self::from_env) passes `std::env::var`; the layered
+    /// `ChalkbyteConfig` passes its config file with environment overrides.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let number = |name: &str, default: u64| {
+            lookup(name)
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .unwrap_or(default)
+        };
+        let defaults = Self::default();
+
+        Self {
+            database_url: lookup("DATABASE_URL").unwrap_or_default(),
+            replica_urls: lookup("DATABASE_REPLICA_URLS")
+                .map(|urls| {
+                    urls.split(',')
+                        .map(str::trim)
+                        .filter(|url| !url.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            max_connections: u32::try_from(number(
+                "DB_MAX_CONNECTIONS",
+                defaults.max_connections.into(),
+            ))
+            .unwrap_or(defaults.max_connections),
+            min_connections: u32::try_from(number(
+                "DB_MIN_CONNECTIONS",
+                defaults.min_connections.into(),
+            ))
+            .unwrap_or(defaults.min_connections),
+            acquire_timeout: Duration::from_secs(number(
+                "DB_ACQUIRE_TIMEOUT_SECONDS",
+                defaults.acquire_timeout.as_secs(),
+            )),
+            idle_timeout: Some(number("DB_IDLE_TIMEOUT_SECONDS", 600))
+                .filter(|&secs| secs > 0)
+                .map(Duration::from_secs),
+            statement_timeout: Some(number("DB_STATEMENT_TIMEOUT_MS", 0))
+                .filter(|&ms| ms > 0)
+                .map(Duration::from_millis),
+        }
+    }
+}
+
+impl Default for DbConfig {
+    fn default() -> Self {
+        Self {
+            database_url: String::new(),
+            replica_urls: Vec::new(),
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout: Duration::from_secs(30),
+            idle_timeout: Some(Duration::from_secs(600)),
+            statement_timeout: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(vars: &[(&str, &str)]) -> DbConfig {
+        DbConfig::from_lookup(|name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = config(&[]);
+        assert_eq!(config, DbConfig::default());
+        assert!(config.database_url.is_empty());
+    }
+
+    #[test]
+    fn test_from_vars() {
+        let config = config(&[
+            ("DATABASE_URL", "postgres://localhost/chalkbyte"),
+            (
+                "DATABASE_REPLICA_URLS",
+                "postgres://replica-1/chalkbyte, ,postgres://replica-2/chalkbyte",
+            ),
+            ("DB_MAX_CONNECTIONS", "25"),
+            ("DB_MIN_CONNECTIONS", " 2 "),
+            ("DB_ACQUIRE_TIMEOUT_SECONDS", "five"),
+            ("DB_IDLE_TIMEOUT_SECONDS", "0"),
+            ("DB_STATEMENT_TIMEOUT_MS", "15000"),
+        ]);
+
+        assert_eq!(config.database_url, "postgres://localhost/chalkbyte");
+        assert_eq!(
+            config.replica_urls,
+            [
+                "postgres://replica-1/chalkbyte",
+                "postgres://replica-2/chalkbyte"
+            ]
+        );
+        assert_eq!((config.max_connections, config.min_connections), (25, 2));
+        assert_eq!(config.acquire_timeout, Duration::from_secs(30));
+        assert_eq!(config.idle_timeout, None);
+        assert_eq!(config.statement_timeout, Some(Duration::from_secs(15)));
+    }
+}
@@ -3,42 +3,85 @@
 //! Database pool and utilities for the Chalkbyte API.
 //!
 //! This crate provides database connection pool initialization and management
-//! using SQLx with PostgreSQL.
+//! using SQLx with PostgreSQL, configured by [`DbConfig`], read routing to
+//! replicas through [`DbPools`], transactions that compose service calls
+//! through [`UnitOfWork`], school scoping by row-level security through
+//! [`TenantContext`], query latency metrics through [`timed`], a readiness
+//! probe, and the embedded schema [`migrations`].
 //!
 //! # Example
 //!
 //! ```ignore
-//! use chalkbyte_db::init_db_pool;
+//! use chalkbyte_db::{DbConfig, check_health, init_db_pool};
 //!
 //! #[tokio::main]
-//! async fn main() {
-//!     let pool = init_db_pool().await;
-//!     // Use pool for database operations
+//! async fn main() -> Result<(), chalkbyte_db::DbError> {
+//!     let pool = init_db_pool(&DbConfig::from_env()).await?;
+//!     check_health(&pool).await?;
+//!     Ok(())
 //! }
 //! ```
 
-use std::env;
+pub mod config;
+pub mod migrations;
+pub mod pools;
+pub mod tenant;
+pub mod timing;
+pub mod unit_of_work;
+
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use chalkbyte_core::request_context::current_request_id;
+use serde::Serialize;
+use sqlx::PgConnection;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+pub use config::DbConfig;
+pub use migrations::run_migrations;
+pub use pools::DbPools;
+pub use tenant::TenantContext;
+pub use timing::timed;
+pub use unit_of_work::UnitOfWork;
+// Re-export PgPool for convenience
+pub use sqlx::PgPool;
+
+/// How long [`check_health`] waits for the database to answer.
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Errors from setting up or probing the database pool.
+#[derive(Debug, thiserror::Error)]
+pub enum DbError {
+    #[error("DATABASE_URL must be set")]
+    MissingUrl,
+
+    #[error("Invalid database configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Database health check timed out")]
+    Timeout,
+}
 
 /// Initializes a PostgreSQL connection pool.
 ///
-/// This function reads the database URL from the `DATABASE_URL` environment
-/// variable and creates a connection pool. The pool is used throughout the
-/// application for all database operations.
-///
-/// # Returns
+/// Opens one connection before returning, so a wrong URL or an unreachable
+/// server is reported at startup rather than on the first request. The
+/// pool is used throughout the application for all database operations.
 ///
-/// Returns a [`PgPool`] that can be cloned and shared across async tasks.
+/// # Errors
 ///
-/// # Panics
-///
-/// Panics if:
-/// - `DATABASE_URL` environment variable is not set
-/// - Connection to the database fails
+/// - [`DbError::MissingUrl`] if `config.database_url` is empty
+/// - [`DbError::InvalidConfig`] if the URL cannot be parsed or
+///   `min_connections` exceeds `max_connections`
+/// - [`DbError::Database`] if the first connection fails
 ///
 /// # Example
 ///
 /// ```ignore
-/// let pool = init_db_pool().await;
+/// let pool = init_db_pool(&DbConfig::from_env()).await?;
 ///
 /// // Use the pool in queries
 /// let user = sqlx::query_as!(User, "SELECT * FROM users WHERE id = $1", user_id)
@@ -51,13 +94,173 @@ use std::env;
 /// This function should typically be called once during application startup.
 /// The returned pool is cheaply cloneable and should be passed to the
 /// application state for use in request handlers.
-pub async fn init_db_pool() -> sqlx::PgPool {
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+///
+/// Every connection handed out is tagged with the current request ID (see
+/// [`tag_request_id`]), so change events written while handling a request
+/// can be traced back to it.
+pub async fn init_db_pool(config: &DbConfig) -> Result<sqlx::PgPool, DbError> {
+    if config.database_url.is_empty() {
+        return Err(DbError::MissingUrl);
+    }
+    if config.max_connections == 0 || config.min_connections > config.max_connections {
+        return Err(DbError::InvalidConfig(format!(
+            "connections must satisfy 0 <= min ({}) <= max ({}) and max > 0",
+            config.min_connections, config.max_connections
+        )));
+    }
+
+    connect(config, &config.database_url, "DATABASE_URL").await
+}
+
+/// Initializes the primary pool and a pool for each read replica.
+///
+/// Every pool uses the settings in `config`. Like [`init_db_pool`], each
+/// opens one connection before returning, so an unreachable replica is
+/// reported at startup.
+///
+/// # Errors
+///
+/// The errors of [`init_db_pool`], for the primary or any replica.
+pub async fn init_db_pools(config: &DbConfig) -> Result<DbPools, DbError> {
+    let primary = init_db_pool(config).await?;
+
+    let mut replicas = Vec::with_capacity(config.replica_urls.len());
+    for url in &config.replica_urls {
+        replicas.push(connect(config, url, "DATABASE_REPLICA_URLS").await?);
+    }
+
+    Ok(DbPools::with_replicas(primary, replicas))
+}
 
-    sqlx::PgPool::connect(&database_url)
+/// Opens a pool for `url` with the settings in `config`; `var` names the
+/// setting the URL came from in errors.
+async fn connect(config: &DbConfig, url: &str, var: &str) -> Result<sqlx::PgPool, DbError> {
+    let mut options = PgConnectOptions::from_str(url)
+        .map_err(|e| DbError::InvalidConfig(format!("{var}: {e}")))?;
+    if let Some(timeout) = config.statement_timeout {
+        options = options.options([("statement_timeout", format!("{}ms", timeout.as_millis()))]);
+    }
+
+    let pool = PgPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .acquire_timeout(config.acquire_timeout)
+        .idle_timeout(config.idle_timeout)
+        .after_connect(|conn, _meta| Box::pin(timed("tag_request_id", tag_request_id(conn))))
+        .before_acquire(|conn, _meta| {
+            Box::pin(async move {
+                timed("tag_request_id", tag_request_id(conn)).await?;
+                Ok(true)
+            })
+        })
+        .connect_with(options)
+        .await?;
+
+    Ok(pool)
+}
+
+/// Pool state reported by [`check_health`].
+#[derive(Debug, Clone, Serialize)]
+pub struct DbHealth {
+    /// Round trip of the probe query, including waiting for a connection
+    pub latency_ms: u64,
+    /// Connections currently open
+    pub connections: u32,
+    /// Open connections not in use
+    pub idle_connections: usize,
+}
+
+/// Checks that the database answers a query within [`HEALTH_CHECK_TIMEOUT`].
+///
+/// # Errors
+///
+/// - [`DbError::Timeout`] if no answer arrives in time, e.g. because the
+///   pool is exhausted
+/// - [`DbError::Database`] if the query fails
+pub async fn check_health(pool: &sqlx::PgPool) -> Result<DbHealth, DbError> {
+    let started = Instant::now();
+    let probe = timed("health_check", sqlx::query("SELECT 1").execute(pool));
+    tokio::time::timeout(HEALTH_CHECK_TIMEOUT, probe)
         .await
-        .expect("Failed to connect to database")
+        .map_err(|_| DbError::Timeout)??;
+
+    Ok(DbHealth {
+        latency_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        connections: pool.size(),
+        idle_connections: pool.num_idle(),
+    })
 }
 
-// Re-export PgPool for convenience
-pub use sqlx::PgPool;
+/// Sets the `chalkbyte.request_id` session setting to the current request ID.
+///
+/// The change event trigger copies the setting into each event it records.
+/// Outside a request the setting is cleared, so a pooled connection never
+/// carries a previous request's ID into a background job.
+pub async fn tag_request_id(conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+    let request_id = current_request_id().unwrap_or_default();
+
+    sqlx::query("SELECT set_config('chalkbyte.request_id', $1, false)")
+        .bind(request_id)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_init_db_pool_rejects_bad_config() {
+        assert!(matches!(
+            init_db_pool(&DbConfig::default()).await,
+            Err(DbError::MissingUrl)
+        ));
+
+        let config = DbConfig {
+            database_url: "postgres://localhost/chalkbyte".to_string(),
+            min_connections: 5,
+            max_connections: 2,
+            ..DbConfig::default()
+        };
+        assert!(matches!(
+            init_db_pool(&config).await,
+            Err(DbError::InvalidConfig(_))
+        ));
+
+        let config = DbConfig {
+            database_url: "postgres://localhost:port/chalkbyte".to_string(),
+            ..DbConfig::default()
+        };
+        assert!(matches!(
+            init_db_pool(&config).await,
+            Err(DbError::InvalidConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_init_db_pools_rejects_bad_replica_url() {
+        let config = DbConfig {
+            database_url: std::env::var("DATABASE_URL").unwrap(),
+            replica_urls: vec!["postgres://localhost:port/chalkbyte".to_string()],
+            ..DbConfig::default()
+        };
+        assert!(matches!(
+            init_db_pools(&config).await,
+            Err(DbError::InvalidConfig(message)) if message.starts_with("DATABASE_REPLICA_URLS")
+        ));
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn test_check_health(pool: PgPool) {
+        let health = check_health(&pool).await.unwrap();
+        assert!(health.connections >= 1);
+
+        pool.close().await;
+        assert!(matches!(
+            check_health(&pool).await,
+            Err(DbError::Database(_))
+        ));
+    }
+}
@@ -0,0 +1,316 @@
+//! Schema migrations.
+//!
+//! The workspace `migrations/` directory is embedded at compile time, so the
+//! API and CLI binaries can migrate a database without `sqlx-cli` or the SQL
+//! files on disk. Applied migrations are recorded in `_sqlx_migrations`, the
+//! same table `sqlx migrate run` and `#[sqlx::test]` use, so the tools can be
+//! mixed.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_db::migrations::{MigrateOptions, migrate, run_migrations};
+//!
+//! // Apply everything pending
+//! run_migrations(&pool).await?;
+//!
+//! // Preview what migrating up to a version would apply
+//! let planned = migrate(&pool, MigrateOptions { target: Some(20260115120000), dry_run: true }).await?;
+//! ```
+
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
+
+/// Migrations embedded from the workspace `migrations/` directory.
+pub static MIGRATOR: Migrator = sqlx::migrate!("../../migrations");
+
+/// Where a migration stands against the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    /// Recorded as applied with a matching checksum
+    Applied,
+    /// Not applied yet
+    Pending,
+    /// Applied, but the file has changed since
+    Modified,
+    /// Applied, but no longer in the migrations directory
+    Missing,
+}
+
+/// One migration and its state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: i64,
+    /// Empty for a [`MigrationState::Missing`] migration
+    pub description: String,
+    pub state: MigrationState,
+}
+
+/// How far [`migrate`] goes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MigrateOptions {
+    /// Apply migrations up to and including this version; all when `None`
+    pub target: Option<i64>,
+    /// Report what would be applied without changing the database
+    pub dry_run: bool,
+}
+
+/// Applies every pending migration.
+///
+/// # Errors
+///
+/// Fails if a migration fails, or if an applied migration was modified or
+/// removed from the migrations directory.
+pub async fn run_migrations(pool: &PgPool) -> Result<Vec<MigrationStatus>, MigrateError> {
+    migrate(pool, MigrateOptions::default()).await
+}
+
+/// Lists every embedded migration, plus any applied one missing from the
+/// directory, ordered by version.
+///
+/// # Errors
+///
+/// Fails if the migrations table cannot be read or created.
+pub async fn migration_status(pool: &PgPool) -> Result<Vec<MigrationStatus>, MigrateError> {
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table().await?;
+    let applied: HashMap<i64, Vec<u8>> = conn
+        .list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|migration| (migration.version, migration.checksum.into_owned()))
+        .collect();
+
+    let mut statuses: Vec<MigrationStatus> = MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| MigrationStatus {
+            version: migration.version,
+            description: migration.description.to_string(),
+            state: match applied.get(&migration.version) {
+                Some(checksum) if *checksum == *migration.checksum => MigrationState::Applied,
+                Some(_) => MigrationState::Modified,
+                None => MigrationState::Pending,
+            },
+        })
+        .collect();
+
+    statuses.extend(
+        applied
+            .keys()
+            .filter(|version| !MIGRATOR.version_exists(**version))
+            .map(|&version| MigrationStatus {
+                version,
+                description: String::new(),
+                state: MigrationState::Missing,
+            }),
+    );
+    statuses.sort_by_key(|status| status.version);
+
+    Ok(statuses)
+}
+
+/// Applies pending migrations in version order, up to `options.target` if
+/// set, holding the migration lock so concurrent deploys cannot race.
+///
+/// Returns the migrations applied, or with `dry_run` the ones that would be.
+///
+/// # Errors
+///
+/// - [`MigrateError::VersionNotPresent`] if `target` is not an embedded migration
+/// - [`MigrateError::VersionTooOld`] if `target` is older than the latest
+///   applied migration; migrations are never reverted
+/// - [`MigrateError::VersionMismatch`] / [`MigrateError::VersionMissing`] if an
+///   applied migration was modified or removed
+/// - [`MigrateError::Dirty`] if an earlier run failed part way
+/// - [`MigrateError::ExecuteMigration`] if a migration fails
+pub async fn migrate(
+    pool: &PgPool,
+    options: MigrateOptions,
+) -> Result<Vec<MigrationStatus>, MigrateError> {
+    if let Some(target) = options.target
+        && !MIGRATOR.version_exists(target)
+    {
+        return Err(MigrateError::VersionNotPresent(target));
+    }
+
+    let mut conn = pool.acquire().await?;
+    if !options.dry_run {
+        conn.lock().await?;
+    }
+
+    let result = apply_pending(&mut conn, options).await;
+
+    if !options.dry_run {
+        conn.unlock().await?;
+    }
+    result
+}
+
+async fn apply_pending(
+    conn: &mut sqlx::PgConnection,
+    options: MigrateOptions,
+) -> Result<Vec<MigrationStatus>, MigrateError> {
+    conn.ensure_migrations_table().await?;
+    if let Some(version) = conn.dirty_version().await? {
+        return Err(MigrateError::Dirty(version));
+    }
+
+    let applied: HashMap<i64, Vec<u8>> = conn
+        .list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|migration| (migration.version, migration.checksum.into_owned()))
+        .collect();
+    if let Some(&version) = applied.keys().find(|v| !MIGRATOR.version_exists(**v)) {
+        return Err(MigrateError::VersionMissing(version));
+    }
+
+    if let Some(target) = options.target
+        && let Some(&latest) = applied.keys().max()
+        && target < latest
+    {
+        return Err(MigrateError::VersionTooOld(target, latest));
+    }
+
+    let mut done = Vec::new();
+    for migration in MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .filter(|migration| {
+            options
+                .target
+                .is_none_or(|target| migration.version <= target)
+        })
+    {
+        match applied.get(&migration.version) {
+            Some(checksum) if *checksum != *migration.checksum => {
+                return Err(MigrateError::VersionMismatch(migration.version));
+            }
+            Some(_) => {}
+            None => {
+                if !options.dry_run {
+                    conn.apply(migration).await?;
+                }
+                done.push(MigrationStatus {
+                    version: migration.version,
+                    description: migration.description.to_string(),
+                    state: if options.dry_run {
+                        MigrationState::Pending
+                    } else {
+                        MigrationState::Applied
+                    },
+                });
+            }
+        }
+    }
+
+    Ok(done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions() -> Vec<i64> {
+        MIGRATOR.iter().map(|migration| migration.version).collect()
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn test_migrate_to_target_then_rest(pool: PgPool) {
+        let versions = versions();
+        let target = versions[2];
+
+        let planned = migrate(
+            &pool,
+            MigrateOptions {
+                target: Some(target),
+                dry_run: true,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(planned.len(), 3);
+        assert!(
+            planned
+                .iter()
+                .all(|status| status.state == MigrationState::Pending)
+        );
+        assert!(
+            migration_status(&pool)
+                .await
+                .unwrap()
+                .iter()
+                .all(|status| status.state == MigrationState::Pending)
+        );
+
+        let applied = migrate(
+            &pool,
+            MigrateOptions {
+                target: Some(target),
+                dry_run: false,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            applied.iter().map(|s| s.version).collect::<Vec<_>>(),
+            versions[..3]
+        );
+
+        let error = migrate(
+            &pool,
+            MigrateOptions {
+                target: Some(versions[1]),
+                dry_run: false,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(error, MigrateError::VersionTooOld(..)));
+
+        let rest = run_migrations(&pool).await.unwrap();
+        assert_eq!(rest.len(), versions.len() - 3);
+        assert!(run_migrations(&pool).await.unwrap().is_empty());
+        assert!(
+            migration_status(&pool)
+                .await
+                .unwrap()
+                .iter()
+                .all(|status| status.state == MigrationState::Applied)
+        );
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn test_migrate_rejects_unknown_target_and_flags_missing(pool: PgPool) {
+        let error = migrate(
+            &pool,
+            MigrateOptions {
+                target: Some(1),
+                dry_run: true,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(error, MigrateError::VersionNotPresent(1)));
+
+        run_migrations(&pool).await.unwrap();
+        sqlx::query(
+            "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+             VALUES (1, 'gone', true, '\\x00', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let status = migration_status(&pool).await.unwrap();
+        assert_eq!(status[0].version, 1);
+        assert_eq!(status[0].state, MigrationState::Missing);
+        assert!(matches!(
+            run_migrations(&pool).await.unwrap_err(),
+            MigrateError::VersionMissing(1)
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+//! Primary and read replica pools.
+//!
+//! [`DbPools`] holds the primary pool, which takes every write, and any read
+//! replica pools. Heavy read-only queries such as list and search endpoints
+//! run on a replica through [`DbPools::read_only`], which picks replicas in
+//! turn and uses the primary when none are configured.
+//!
+//! Replicas lag behind the primary, so a read that must see a write made in
+//! the same request belongs on the primary.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_db::{DbConfig, init_db_pools};
+//!
+//! let pools = init_db_pools(&DbConfig::from_env()).await?;
+//!
+//! // Writes go to the primary
+//! UserService::create_user(pools.primary(), dto).await?;
+//!
+//! // List endpoints can read from a replica
+//! let users = pools
+//!     .read_only(async |db| UserService::get_users_paginated(db, filters).await)
+//!     .await?;
+//! ```
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use sqlx::PgPool;
+
+/// The primary pool and the read replica pools.
+///
+/// Cheap to clone; clones share the pools and the round-robin position.
+#[derive(Clone, Debug)]
+pub struct DbPools {
+    primary: PgPool,
+    replicas: Arc<[PgPool]>,
+    next_replica: Arc<AtomicUsize>,
+}
+
+impl DbPools {
+    /// Pools with no replicas; reads go to the primary.
+    pub fn new(primary: PgPool) -> Self {
+        Self::with_replicas(primary, Vec::new())
+    }
+
+    /// Pools that spread reads across `replicas`.
+    pub fn with_replicas(primary: PgPool, replicas: Vec<PgPool>) -> Self {
+        Self {
+            primary,
+            replicas: replicas.into(),
+            next_replica: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// The pool for writes and for reads that must see the latest writes.
+    pub fn primary(&self) -> &PgPool {
+        &self.primary
+    }
+
+    /// The read replica pools, in configuration order.
+    pub fn replicas(&self) -> &[PgPool] {
+        &self.replicas
+    }
+
+    /// The pool for the next read: the replicas in turn, or the primary when
+    /// there are none.
+    pub fn read(&self) -> &PgPool {
+        if self.replicas.is_empty() {
+            return &self.primary;
+        }
+        let next = self.next_replica.fetch_add(1, Ordering::Relaxed);
+        &self.replicas[next % self.replicas.len()]
+    }
+
+    /// Runs read-only queries on the pool chosen by [`read`](Self::read).
+    ///
+    /// Nothing stops `f` from writing, but writes on a replica fail, so only
+    /// pass queries that read.
+    pub async fn read_only<T>(&self, f: impl AsyncFnOnce(&PgPool) -> T) -> T {
+        f(self.read()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+
+    fn lazy_pool(database: &str) -> PgPool {
+        PgPoolOptions::new()
+            .connect_lazy(&format!("postgres://localhost/{database}"))
+            .unwrap()
+    }
+
+    fn database(pool: &PgPool) -> String {
+        pool.connect_options().get_database().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_reads_use_primary_without_replicas() {
+        let pools = DbPools::new(lazy_pool("primary"));
+
+        assert_eq!(database(pools.read()), "primary");
+        assert_eq!(pools.read_only(async |db| database(db)).await, "primary");
+    }
+
+    #[tokio::test]
+    async fn test_reads_rotate_through_replicas() {
+        let pools = DbPools::with_replicas(
+            lazy_pool("primary"),
+            vec![lazy_pool("replica_a"), lazy_pool("replica_b")],
+        );
+        let shared = pools.clone();
+
+        let reads: Vec<String> = (0..4)
+            .map(|i| {
+                database(if i % 2 == 0 {
+                    pools.read()
+                } else {
+                    shared.read()
+                })
+            })
+            .collect();
+        assert_eq!(reads, ["replica_a", "replica_b", "replica_a", "replica_b"]);
+        assert_eq!(database(pools.primary()), "primary");
+    }
+}
@@ -0,0 +1,144 @@
+//! School scoping through Postgres row-level security.
+//!
+//! A [`TenantContext`] says which schools' rows a request may touch. It is
+//! built once per request from the caller's token and applied to the
+//! transaction the request's queries run in: for a school-scoped request
+//! the transaction switches to the [`TENANT_ROLE`] and records the schools
+//! in [`TENANT_SCHOOLS_SETTING`], and the row-level security policies on
+//! tenant tables hide every other school's rows. Queries therefore need no
+//! `AND school_id = $n` of their own, and one service method serves system
+//! admins and school admins alike.
+//!
+//! The settings are transaction-local, so they end with the transaction
+//! and never leak to the next user of the pooled connection.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_db::{TenantContext, UnitOfWork};
+//!
+//! let tenant = TenantContext::school(school_id);
+//! let level = UnitOfWork::run_as(&pool, &tenant, async |uow| {
+//!     // Only finds the level if it belongs to the school
+//!     sqlx::query_as::<_, Level>("SELECT * FROM levels WHERE id = $1")
+//!         .bind(level_id)
+//!         .fetch_optional(uow.conn())
+//!         .await
+//! })
+//! .await?;
+//! ```
+
+use std::sync::Arc;
+
+use sqlx::PgConnection;
+use sqlx::types::Uuid;
+
+/// Role school-scoped transactions run as. Row-level security policies on
+/// tenant tables apply to it.
+pub const TENANT_ROLE: &str = "chalkbyte_tenant";
+
+/// Transaction setting holding the comma-separated IDs of the schools a
+/// scoped transaction may see.
+pub const TENANT_SCHOOLS_SETTING: &str = "app.tenant_school_ids";
+
+/// The schools a request's queries are limited to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TenantContext {
+    /// Every school, for system admins. Queries run unscoped.
+    AllSchools,
+    /// Only these schools. An empty list sees no school's rows.
+    Schools(Arc<[Uuid]>),
+}
+
+impl TenantContext {
+    /// Scoped to one school.
+    pub fn school(school_id: impl Into<Uuid>) -> Self {
+        Self::Schools(Arc::new([school_id.into()]))
+    }
+
+    /// Scoped to a set of schools, such as a group's members.
+    pub fn schools(school_ids: impl IntoIterator<Item = Uuid>) -> Self {
+        Self::Schools(school_ids.into_iter().collect())
+    }
+
+    /// The schools this context is limited to, or `None` for every school.
+    pub fn school_ids(&self) -> Option<&[Uuid]> {
+        match self {
+            Self::AllSchools => None,
+            Self::Schools(ids) => Some(ids),
+        }
+    }
+
+    /// The school this context is limited to, when it is exactly one.
+    pub fn single_school(&self) -> Option<Uuid> {
+        match self.school_ids() {
+            Some([school_id]) => Some(*school_id),
+            _ => None,
+        }
+    }
+
+    /// Whether rows of `school_id` are visible in this context.
+    pub fn allows(&self, school_id: impl Into<Uuid>) -> bool {
+        self.school_ids()
+            .is_none_or(|ids| ids.contains(&school_id.into()))
+    }
+
+    /// Scope the current transaction. Does nothing for
+    /// [`AllSchools`](Self::AllSchools).
+    ///
+    /// Must run inside a transaction: outside one the settings would not
+    /// take effect.
+    pub async fn apply(&self, conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+        let Some(ids) = self.school_ids() else {
+            return Ok(());
+        };
+        let ids = ids
+            .iter()
+            .map(Uuid::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        sqlx::query("SELECT set_config($1, $2, true)")
+            .bind(TENANT_SCHOOLS_SETTING)
+            .bind(ids)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query(&format!("SET LOCAL ROLE {TENANT_ROLE}"))
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows() {
+        let school = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        assert!(TenantContext::AllSchools.allows(other));
+        assert!(TenantContext::school(school).allows(school));
+        assert!(!TenantContext::school(school).allows(other));
+        assert!(!TenantContext::schools([]).allows(school));
+        assert_eq!(
+            TenantContext::schools([school, other]).school_ids(),
+            Some(&[school, other][..])
+        );
+    }
+
+    #[test]
+    fn test_single_school() {
+        let school = Uuid::new_v4();
+
+        assert_eq!(TenantContext::school(school).single_school(), Some(school));
+        assert_eq!(TenantContext::AllSchools.single_school(), None);
+        assert_eq!(
+            TenantContext::schools([school, Uuid::new_v4()]).single_school(),
+            None
+        );
+    }
+}
@@ -0,0 +1,38 @@
+//! Query latency metrics.
+//!
+//! [`timed`] wraps a query, or a service call made of a few queries, and
+//! records how long it took under a fixed name with
+//! `chalkbyte_observability::track_db_query`, so slow queries show up in
+//! the `db_query_duration_seconds` histogram on `/metrics`. Queries over
+//! `SLOW_QUERY_MS` are also logged as warnings, see
+//! `chalkbyte_observability::slow`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_db::timed;
+//!
+//! let students = pools
+//!     .read_only(async |db| {
+//!         timed("students.list", StudentService::get_students_by_school(db, /* ... */)).await
+//!     })
+//!     .await?;
+//! ```
+
+use std::time::Instant;
+
+use chalkbyte_observability::slow::observe_query;
+use chalkbyte_observability::track_db_query;
+
+/// Runs `query` and records its latency as `query_name`, whether it
+/// succeeds or fails.
+///
+/// `query_name` labels a metric series, so it must come from a fixed set.
+pub async fn timed<F: Future>(query_name: &'static str, query: F) -> F::Output {
+    let started = Instant::now();
+    let output = query.await;
+    let elapsed = started.elapsed();
+    track_db_query(query_name, elapsed);
+    observe_query(query_name, elapsed);
+    output
+}
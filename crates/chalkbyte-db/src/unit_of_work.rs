@@ -0,0 +1,236 @@
+//! Composing service calls in one transaction.
+//!
+//! Service methods that write take `impl Acquire<'_, Database = Postgres>`
+//! (or `impl PgExecutor<'_>` when they run a single query), so they run on
+//! the pool when called alone and on a transaction when composed. A
+//! [`UnitOfWork`] is that transaction, plus work that must only happen once
+//! it commits, such as invalidating caches.
+//!
+//! Services invalidate their caches as soon as they write, which inside a
+//! unit of work would be before the data is visible to anyone else. Pass
+//! `None` for their cache and register the invalidation with
+//! [`UnitOfWork::after_commit`] instead.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_db::UnitOfWork;
+//!
+//! let (level, branch) = UnitOfWork::run(&pool, async |uow| {
+//!     let level = LevelService::create_level(uow.conn(), None, school_id, level_dto).await?;
+//!     let branch =
+//!         BranchService::create_branch(uow.conn(), None, &tenant, level.id, branch_dto).await?;
+//!
+//!     let cache = cache.clone();
+//!     uow.after_commit(async move {
+//!         invalidate::level(cache.as_ref(), None, Some(school_id.into())).await;
+//!     });
+//!     Ok::<_, AppError>((level, branch))
+//! })
+//! .await?;
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+
+use crate::tenant::TenantContext;
+
+type Hook = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// A transaction that service calls are composed in.
+///
+/// Dropping a unit of work without committing rolls it back and discards
+/// its after-commit work.
+pub struct UnitOfWork {
+    tx: Transaction<'static, Postgres>,
+    after_commit: Vec<Hook>,
+}
+
+impl UnitOfWork {
+    /// Start a unit of work on a connection from `pool`.
+    pub async fn begin(pool: &PgPool) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            tx: pool.begin().await?,
+            after_commit: Vec::new(),
+        })
+    }
+
+    /// Start a unit of work whose queries only see the rows `tenant`
+    /// allows.
+    pub async fn begin_as(pool: &PgPool, tenant: &TenantContext) -> Result<Self, sqlx::Error> {
+        let mut uow = Self::begin(pool).await?;
+        tenant.apply(uow.conn()).await?;
+        Ok(uow)
+    }
+
+    /// Run `f` in a unit of work, committing when it returns `Ok` and
+    /// rolling back when it returns `Err`.
+    pub async fn run<T, E>(
+        pool: &PgPool,
+        f: impl AsyncFnOnce(&mut UnitOfWork) -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        E: From<sqlx::Error>,
+    {
+        Self::finish(Self::begin(pool).await?, f).await
+    }
+
+    /// [`run`](Self::run), with the unit of work scoped to `tenant`.
+    pub async fn run_as<T, E>(
+        pool: &PgPool,
+        tenant: &TenantContext,
+        f: impl AsyncFnOnce(&mut UnitOfWork) -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        E: From<sqlx::Error>,
+    {
+        Self::finish(Self::begin_as(pool, tenant).await?, f).await
+    }
+
+    async fn finish<T, E>(
+        mut uow: UnitOfWork,
+        f: impl AsyncFnOnce(&mut UnitOfWork) -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        E: From<sqlx::Error>,
+    {
+        match f(&mut uow).await {
+            Ok(value) => {
+                uow.commit().await?;
+                Ok(value)
+            }
+            Err(e) => {
+                uow.rollback().await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// The transaction's connection, for passing to service methods.
+    pub fn conn(&mut self) -> &mut PgConnection {
+        &mut self.tx
+    }
+
+    /// Run `hook` after the unit of work commits. Hooks run in the order
+    /// they were added, and not at all if it rolls back.
+    pub fn after_commit(&mut self, hook: impl Future<Output = ()> + Send + 'static) {
+        self.after_commit.push(Box::pin(hook));
+    }
+
+    /// Commit the transaction, then run the after-commit work.
+    pub async fn commit(self) -> Result<(), sqlx::Error> {
+        self.tx.commit().await?;
+        for hook in self.after_commit {
+            hook.await;
+        }
+        Ok(())
+    }
+
+    /// Roll the transaction back and discard the after-commit work.
+    pub async fn rollback(self) -> Result<(), sqlx::Error> {
+        self.tx.rollback().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A pool of one connection, so a temporary table created on it is
+    /// visible to every unit of work.
+    async fn single_connection_pool() -> PgPool {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .connect(&url)
+            .await
+            .unwrap();
+        sqlx::query("CREATE TEMP TABLE unit_of_work_test (value INTEGER NOT NULL)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    async fn insert(conn: &mut PgConnection, value: i32) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT INTO unit_of_work_test (value) VALUES ($1)")
+            .bind(value)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn count(conn: &mut PgConnection) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM unit_of_work_test")
+            .fetch_one(conn)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_run_commits_or_rolls_back_everything() {
+        let pool = single_connection_pool().await;
+        let hooks = Arc::new(AtomicUsize::new(0));
+
+        let failed: Result<(), sqlx::Error> = UnitOfWork::run(&pool, async |uow| {
+            insert(uow.conn(), 1).await?;
+            let hooks = hooks.clone();
+            uow.after_commit(async move {
+                hooks.fetch_add(1, Ordering::SeqCst);
+            });
+            insert(uow.conn(), 2).await?;
+            Err(sqlx::Error::RowNotFound)
+        })
+        .await;
+        assert!(failed.is_err());
+        assert_eq!(hooks.load(Ordering::SeqCst), 0);
+
+        UnitOfWork::run(&pool, async |uow| {
+            insert(uow.conn(), 1).await?;
+            insert(uow.conn(), 2).await?;
+            let hooks = hooks.clone();
+            uow.after_commit(async move {
+                hooks.fetch_add(1, Ordering::SeqCst);
+            });
+            Ok::<_, sqlx::Error>(())
+        })
+        .await
+        .unwrap();
+        assert_eq!(hooks.load(Ordering::SeqCst), 1);
+
+        let mut conn = pool.acquire().await.unwrap();
+        assert_eq!(count(&mut conn).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_as_scopes_only_its_transaction() {
+        let pool = single_connection_pool().await;
+        let school = sqlx::types::Uuid::new_v4();
+        let scoped = async |uow: &mut UnitOfWork| {
+            sqlx::query_as::<_, (String, Option<String>)>(
+                "SELECT current_user::text, current_setting($1, true)",
+            )
+            .bind(crate::tenant::TENANT_SCHOOLS_SETTING)
+            .fetch_one(uow.conn())
+            .await
+        };
+
+        let (role, schools) = UnitOfWork::run_as(&pool, &TenantContext::school(school), scoped)
+            .await
+            .unwrap();
+        assert_eq!(role, crate::tenant::TENANT_ROLE);
+        assert_eq!(schools, Some(school.to_string()));
+
+        // The same connection is back to the application's role
+        let (role, schools) = UnitOfWork::run_as(&pool, &TenantContext::AllSchools, scoped)
+            .await
+            .unwrap();
+        assert_ne!(role, crate::tenant::TENANT_ROLE);
+        assert!(schools.is_none_or(|s| s.is_empty()));
+    }
+}
@@ -0,0 +1,232 @@
+//! Alumni domain models and DTOs.
+//!
+//! This module contains all data structures for alumni relations: alumni
+//! records created when students graduate, their graduation cohort and
+//! contact details, and the mailing list export.
+//!
+//! An alumni record keeps the former student's `user_id` along with their
+//! final level, branch, and graduating academic session, so their academic
+//! history stays reachable after graduation.
+
+use crate::ids::{AcademicSessionId, AlumnusId, BranchId, LevelId, SchoolId, UserId};
+use chalkbyte_core::visibility::{hide_in, hide_own};
+use chalkbyte_core::{
+    FieldAccess, FieldVisibility, PaginationMeta, PaginationParams, RestrictedField, permissions,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// An alumni record.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Alumnus {
+    pub id: AlumnusId,
+    pub school_id: SchoolId,
+    /// The former student's account; `None` if the account has been deleted
+    pub user_id: Option<UserId>,
+    pub first_name: String,
+    pub last_name: String,
+    /// Contact email for alumni relations; omitted without the
+    /// `contact_details:read` permission
+    pub email: Option<String>,
+    /// Omitted without the `contact_details:read` permission
+    pub phone: Option<String>,
+    pub graduation_year: i32,
+    /// Cohort label, e.g. "Class of 2026"
+    pub cohort: Option<String>,
+    /// Academic session the student graduated in
+    pub graduation_session_id: Option<AcademicSessionId>,
+    /// Level the student was in when they graduated
+    pub final_level_id: Option<LevelId>,
+    /// Branch the student was in when they graduated
+    pub final_branch_id: Option<BranchId>,
+    pub occupation: Option<String>,
+    pub employer: Option<String>,
+    pub city: Option<String>,
+    /// Whether the alumnus may be contacted by alumni relations
+    pub contactable: bool,
+    pub notes: Option<String>,
+    pub graduated_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FieldVisibility for Alumnus {
+    const RESTRICTED: &'static [RestrictedField] = &[
+        RestrictedField::new("email", permissions::CONTACT_DETAILS_READ),
+        RestrictedField::new("phone", permissions::CONTACT_DETAILS_READ),
+    ];
+}
+
+/// An alumni record with the names of its academic history links.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AlumnusDetail {
+    #[serde(flatten)]
+    pub alumnus: Alumnus,
+    pub final_level_name: Option<String>,
+    pub final_branch_name: Option<String>,
+    pub graduation_session_name: Option<String>,
+}
+
+impl FieldVisibility for AlumnusDetail {
+    fn hide_fields(value: &mut Value, access: &FieldAccess) {
+        // The alumnus is flattened into the detail object
+        hide_own::<Alumnus>(value, access);
+    }
+}
+
+/// DTO for graduating students into alumni records.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct GraduateStudentsDto {
+    /// Students to graduate (1-500)
+    #[validate(length(min = 1, max = 500))]
+    pub student_ids: Vec<UserId>,
+    /// Year of graduation (1900-2200)
+    #[validate(range(min = 1900, max = 2200))]
+    pub graduation_year: i32,
+    /// Cohort label (max 100 characters)
+    #[validate(length(max = 100))]
+    pub cohort: Option<String>,
+    /// Academic session the students graduated in
+    pub graduation_session_id: Option<AcademicSessionId>,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// Result of graduating students.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GraduateStudentsResponse {
+    /// Alumni records created
+    pub graduated: Vec<Alumnus>,
+    /// Requested IDs that are not current students of the school
+    pub failed_ids: Vec<UserId>,
+}
+
+/// DTO for updating an alumni record.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateAlumnusDto {
+    /// Contact email
+    #[validate(email)]
+    pub email: Option<String>,
+    /// Contact phone (max 30 characters)
+    #[validate(length(max = 30))]
+    pub phone: Option<String>,
+    /// Cohort label (max 100 characters)
+    #[validate(length(max = 100))]
+    pub cohort: Option<String>,
+    /// Current occupation (max 150 characters)
+    #[validate(length(max = 150))]
+    pub occupation: Option<String>,
+    /// Current employer (max 150 characters)
+    #[validate(length(max = 150))]
+    pub employer: Option<String>,
+    /// City of residence (max 100 characters)
+    #[validate(length(max = 100))]
+    pub city: Option<String>,
+    /// Whether the alumnus may be contacted
+    pub contactable: Option<bool>,
+    pub notes: Option<String>,
+}
+
+/// Query parameters for listing alumni.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct AlumniFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by graduation year
+    pub graduation_year: Option<i32>,
+    /// Filter by cohort label
+    pub cohort: Option<String>,
+    /// Case-insensitive match on name or email
+    pub search: Option<String>,
+    /// Filter by whether the alumnus may be contacted
+    pub contactable: Option<bool>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing alumni.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedAlumniResponse {
+    /// List of alumni
+    pub data: Vec<Alumnus>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+impl FieldVisibility for PaginatedAlumniResponse {
+    fn hide_fields(value: &mut Value, access: &FieldAccess) {
+        hide_in::<Vec<Alumnus>>(value, "data", access);
+    }
+}
+
+/// Query parameters for the alumni mailing export.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct AlumniExportParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Only alumni from this graduation year
+    pub graduation_year: Option<i32>,
+    /// Only alumni from this cohort
+    pub cohort: Option<String>,
+}
+
+/// A row of the alumni mailing export.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct AlumniMailingRow {
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub phone: Option<String>,
+    pub graduation_year: i32,
+    pub cohort: Option<String>,
+    pub city: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graduate_students_dto_validation() {
+        let valid_dto = GraduateStudentsDto {
+            student_ids: vec![UserId::new()],
+            graduation_year: 2026,
+            cohort: Some("Class of 2026".to_string()),
+            graduation_session_id: None,
+            school_id: None,
+        };
+        assert!(valid_dto.validate().is_ok());
+
+        let no_students = GraduateStudentsDto {
+            student_ids: vec![],
+            ..valid_dto.clone()
+        };
+        assert!(no_students.validate().is_err());
+
+        let bad_year = GraduateStudentsDto {
+            graduation_year: 26,
+            ..valid_dto
+        };
+        assert!(bad_year.validate().is_err());
+    }
+
+    #[test]
+    fn test_update_alumnus_dto_rejects_invalid_email() {
+        let dto = UpdateAlumnusDto {
+            email: Some("not-an-email".to_string()),
+            phone: None,
+            cohort: None,
+            occupation: None,
+            employer: None,
+            city: None,
+            contactable: None,
+            notes: None,
+        };
+        assert!(dto.validate().is_err());
+    }
+}
@@ -0,0 +1,181 @@
+//! Announcement and notification models.
+//!
+//! An announcement is published by a school admin or teacher to an audience
+//! narrowed by role, level, and branch. Every recipient gets a copy in their
+//! notification feed, which tracks whether they have read it, and optionally
+//! an email.
+//!
+//! Like a broadcast, an announcement may be saved as a draft, wait for a
+//! second reviewer's approval when the school requires it, and wait for a
+//! publish time given in the school's timezone. Nobody receives it until it
+//! is published.
+
+use crate::ids::{AnnouncementId, BranchId, LevelId, NotificationId, RoleId, SchoolId, UserId};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Progress of an announcement from draft to the notification feeds.
+///
+/// Submitting a `Draft` moves it to `PendingApproval` when the school requires
+/// approval, then to `Scheduled` while its publish time is in the future, and
+/// to `Published` once it is due. Rejecting a pending announcement returns it
+/// to `Draft`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum AnnouncementStatus {
+    Draft,
+    PendingApproval,
+    Scheduled,
+    Published,
+}
+
+/// An announcement with its read counts.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Announcement {
+    pub id: AnnouncementId,
+    pub school_id: SchoolId,
+    pub title: String,
+    pub body: String,
+    /// Audience: users with this role
+    pub role_id: Option<RoleId>,
+    /// Audience: users in this level
+    pub level_id: Option<LevelId>,
+    /// Audience: users in this branch
+    pub branch_id: Option<BranchId>,
+    /// Whether recipients are also emailed
+    pub send_email: bool,
+    pub status: AnnouncementStatus,
+    /// When to publish, as a local time in the school's timezone
+    pub publish_at: Option<NaiveDateTime>,
+    pub created_by: Option<UserId>,
+    pub submitted_by: Option<UserId>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    /// User who approved or rejected the announcement
+    pub reviewed_by: Option<UserId>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub review_note: Option<String>,
+    /// When it was delivered to the notification feeds
+    pub published_at: Option<DateTime<Utc>>,
+    /// Users the announcement was delivered to
+    pub recipient_count: i64,
+    /// Recipients who have read it
+    pub read_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO for creating an announcement.
+///
+/// Audience filters are combined; leaving them all out targets the whole
+/// school. The author is never a recipient.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateAnnouncementDto {
+    /// Title (1-200 characters)
+    #[validate(length(min = 1, max = 200))]
+    pub title: String,
+    /// Body (1-10000 characters)
+    #[validate(length(min = 1, max = 10000))]
+    pub body: String,
+    pub role_id: Option<RoleId>,
+    pub level_id: Option<LevelId>,
+    pub branch_id: Option<BranchId>,
+    /// Also email every recipient with an address on file
+    #[serde(default)]
+    pub send_email: bool,
+    /// Local time in the school's timezone to publish at; omit to publish as
+    /// soon as the announcement is submitted (and approved)
+    pub publish_at: Option<NaiveDateTime>,
+    /// Save as a draft instead of submitting
+    #[serde(default)]
+    pub draft: bool,
+    /// School ID (required for system admins, ignored for others)
+    pub school_id: Option<SchoolId>,
+}
+
+/// DTO for editing a draft announcement. Replaces the draft's content,
+/// audience, and publish time.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateAnnouncementDto {
+    /// Title (1-200 characters)
+    #[validate(length(min = 1, max = 200))]
+    pub title: String,
+    /// Body (1-10000 characters)
+    #[validate(length(min = 1, max = 10000))]
+    pub body: String,
+    pub role_id: Option<RoleId>,
+    pub level_id: Option<LevelId>,
+    pub branch_id: Option<BranchId>,
+    #[serde(default)]
+    pub send_email: bool,
+    /// Local time in the school's timezone to publish at
+    pub publish_at: Option<NaiveDateTime>,
+}
+
+/// DTO for approving or rejecting an announcement.
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct ReviewAnnouncementDto {
+    /// Optional note for the submitter (max 1000 characters)
+    #[validate(length(max = 1000))]
+    pub review_note: Option<String>,
+}
+
+/// Query parameters for listing announcements.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct AnnouncementFilterParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    pub status: Option<AnnouncementStatus>,
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedAnnouncementsResponse {
+    pub data: Vec<Announcement>,
+    pub meta: PaginationMeta,
+}
+
+/// An entry in a user's notification feed.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Notification {
+    pub id: NotificationId,
+    /// Announcement the notification was delivered for
+    pub announcement_id: Option<AnnouncementId>,
+    pub title: String,
+    pub body: String,
+    /// When the user read it; `None` while unread
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query parameters for the notification feed.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct NotificationFilterParams {
+    /// Only return unread notifications
+    #[serde(default)]
+    pub unread_only: bool,
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// A page of the notification feed, newest first.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NotificationFeed {
+    pub data: Vec<Notification>,
+    pub meta: PaginationMeta,
+    /// Unread notifications across the whole feed
+    pub unread_count: i64,
+}
+
+/// Response for marking notifications as read.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MarkNotificationsReadResponse {
+    /// Notifications that were unread and are now read
+    pub marked_count: i64,
+    /// Unread notifications left in the feed
+    pub unread_count: i64,
+}
@@ -0,0 +1,421 @@
+//! Assessment and grade domain models and DTOs.
+//!
+//! This module contains the data structures for subjects, assessments
+//! (exams, quizzes, and assignments), the scores students earn on them, and
+//! the term grades aggregated from those scores.
+//!
+//! Each assessment belongs to one term, level, and subject and carries a
+//! weight: its share of the term grade in percent. A student's term grade for
+//! a subject is the weighted average of the assessments they have been scored
+//! on, so a grade can be read mid-term before every assessment is marked.
+//!
+//! A school can instead configure a grading scheme, as a default and per
+//! subject, that weights assessments by kind: for example 30% continuous
+//! assessment from the best two quizzes, 10% assignments, and 60% exam. The
+//! component weights sum to 100. Within a component every counted assessment
+//! has equal weight, and components without a score yet are left out, so
+//! mid-term grades still reflect what has been marked.
+//!
+//! Once an assessment's scores are final it can be finalized, after which
+//! its scores can no longer be changed.
+
+use crate::ids::{
+    AssessmentId, AssessmentScoreId, GradingSchemeId, LevelId, SchoolId, SubjectId, TermId, UserId,
+};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+/// Kind of assessment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum AssessmentKind {
+    Exam,
+    Quiz,
+    Assignment,
+}
+
+/// A subject taught in a school.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Subject {
+    pub id: SubjectId,
+    pub school_id: SchoolId,
+    /// Subject name, unique within the school
+    pub name: String,
+    /// Optional short code, e.g. "MTH"
+    pub code: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for creating a subject.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateSubjectDto {
+    /// Subject name (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Optional short code (max 20 characters)
+    #[validate(length(max = 20))]
+    pub code: Option<String>,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// Query parameters for listing subjects.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct SubjectQueryParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// An assessment set for a term, level, and subject.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Assessment {
+    pub id: AssessmentId,
+    pub school_id: SchoolId,
+    pub term_id: TermId,
+    pub level_id: LevelId,
+    pub subject_id: SubjectId,
+    pub name: String,
+    pub kind: AssessmentKind,
+    /// Highest score a student can get
+    pub max_score: f64,
+    /// Share of the term grade, in percent
+    pub weight: i32,
+    pub due_date: Option<NaiveDate>,
+    pub created_by: Option<UserId>,
+    /// When the scores were finalized; finalized scores cannot be changed
+    pub finalized_at: Option<DateTime<Utc>>,
+    /// Staff member who finalized the scores
+    pub finalized_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for creating an assessment.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateAssessmentDto {
+    pub term_id: TermId,
+    pub level_id: LevelId,
+    pub subject_id: SubjectId,
+    /// Assessment name (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    pub kind: AssessmentKind,
+    /// Highest score a student can get (must be positive)
+    #[validate(range(exclusive_min = 0.0))]
+    pub max_score: f64,
+    /// Share of the term grade, in percent (0-100)
+    #[validate(range(min = 0, max = 100))]
+    pub weight: i32,
+    pub due_date: Option<NaiveDate>,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// DTO for updating an assessment. Omitted fields are left unchanged.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateAssessmentDto {
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    pub kind: Option<AssessmentKind>,
+    #[validate(range(exclusive_min = 0.0))]
+    pub max_score: Option<f64>,
+    #[validate(range(min = 0, max = 100))]
+    pub weight: Option<i32>,
+    pub due_date: Option<NaiveDate>,
+}
+
+/// Query parameters for listing assessments.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct AssessmentFilterParams {
+    pub term_id: Option<TermId>,
+    pub level_id: Option<LevelId>,
+    pub subject_id: Option<SubjectId>,
+    pub kind: Option<AssessmentKind>,
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaginatedAssessmentsResponse {
+    pub data: Vec<Assessment>,
+    pub meta: PaginationMeta,
+}
+
+/// A single student's score in a score submission.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct ScoreEntryDto {
+    pub student_id: UserId,
+    /// Score earned, between 0 and the assessment's max score
+    #[validate(range(min = 0.0))]
+    pub score: f64,
+    #[validate(length(max = 255))]
+    pub remarks: Option<String>,
+}
+
+/// DTO for recording scores on an assessment.
+///
+/// Recording a score for a student who already has one replaces it.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct RecordScoresDto {
+    /// Scores to record (1-200)
+    #[validate(length(min = 1, max = 200), nested)]
+    pub scores: Vec<ScoreEntryDto>,
+}
+
+/// Response for a score submission.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RecordScoresResponse {
+    /// Number of scores written
+    pub recorded_count: usize,
+    /// Students not in the assessment's level, who were skipped
+    pub failed_ids: Vec<UserId>,
+}
+
+/// A recorded score with the student's name.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AssessmentScoreWithStudent {
+    pub id: AssessmentScoreId,
+    pub student_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub score: f64,
+    pub remarks: Option<String>,
+    pub recorded_by: Option<UserId>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaginatedAssessmentScoresResponse {
+    pub data: Vec<AssessmentScoreWithStudent>,
+    pub meta: PaginationMeta,
+}
+
+/// A student's aggregated grade for one subject in a term.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TermGrade {
+    pub student_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub subject_id: SubjectId,
+    pub subject_name: String,
+    /// Weighted average of the scored assessments, as a percentage
+    pub percentage: f64,
+    /// Assessments the student has a score for
+    pub assessments_graded: i64,
+    /// Assessments set for the subject in the term
+    pub assessments_total: i64,
+}
+
+/// Query parameters for term grades of a level.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct TermGradeParams {
+    pub term_id: TermId,
+    pub level_id: LevelId,
+    /// Only this subject
+    pub subject_id: Option<SubjectId>,
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaginatedTermGradesResponse {
+    pub data: Vec<TermGrade>,
+    pub meta: PaginationMeta,
+}
+
+/// One of the caller's own assessment results.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct StudentResult {
+    pub assessment_id: AssessmentId,
+    pub assessment_name: String,
+    pub kind: AssessmentKind,
+    pub term_id: TermId,
+    pub subject_id: SubjectId,
+    pub subject_name: String,
+    pub max_score: f64,
+    pub weight: i32,
+    pub score: f64,
+    pub remarks: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Query parameters for the caller's own results.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct StudentResultParams {
+    /// Only results from this term
+    pub term_id: Option<TermId>,
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaginatedStudentResultsResponse {
+    pub data: Vec<StudentResult>,
+    pub meta: PaginationMeta,
+}
+
+/// Query parameters for the caller's own term grades.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct StudentTermGradeParams {
+    pub term_id: TermId,
+}
+
+/// A component of a grading scheme: how much one kind of assessment counts
+/// towards the term grade.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, FromRow, ToSchema)]
+pub struct GradingComponent {
+    pub kind: AssessmentKind,
+    /// Share of the term grade, in percent (0-100)
+    #[validate(range(min = 0, max = 100))]
+    pub weight: i32,
+    /// Only count the student's best N assessments of this kind; omit to
+    /// count all of them
+    #[validate(range(min = 1))]
+    pub best_of: Option<i32>,
+}
+
+/// How assessment components combine into a term grade for a school or one
+/// of its subjects.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GradingScheme {
+    pub id: GradingSchemeId,
+    pub school_id: SchoolId,
+    /// Subject the scheme applies to; `None` for the school default
+    pub subject_id: Option<SubjectId>,
+    pub components: Vec<GradingComponent>,
+    pub updated_by: Option<UserId>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for setting the grading scheme of a school or subject. Replaces any
+/// existing scheme for the same target.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_grading_components"))]
+pub struct SetGradingSchemeDto {
+    /// Subject to configure; omit to set the school default
+    pub subject_id: Option<SubjectId>,
+    /// One component per assessment kind, with weights summing to 100
+    #[validate(length(min = 1, max = 3), nested)]
+    pub components: Vec<GradingComponent>,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+fn validate_grading_components(dto: &SetGradingSchemeDto) -> Result<(), ValidationError> {
+    let mut kinds = std::collections::HashSet::new();
+    if !dto.components.iter().all(|c| kinds.insert(c.kind)) {
+        return Err(ValidationError::new("duplicate_component_kind"));
+    }
+    if dto.components.iter().map(|c| c.weight).sum::<i32>() != 100 {
+        return Err(ValidationError::new("weights_must_sum_to_100"));
+    }
+    Ok(())
+}
+
+/// Query parameters for listing grading schemes.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct GradingSchemeQueryParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_dto() -> CreateAssessmentDto {
+        CreateAssessmentDto {
+            term_id: TermId::new(),
+            level_id: LevelId::new(),
+            subject_id: SubjectId::new(),
+            name: "Mid-term exam".to_string(),
+            kind: AssessmentKind::Exam,
+            max_score: 100.0,
+            weight: 40,
+            due_date: None,
+            school_id: None,
+        }
+    }
+
+    #[test]
+    fn test_create_assessment_dto_validation() {
+        assert!(create_dto().validate().is_ok());
+
+        let zero_max = CreateAssessmentDto {
+            max_score: 0.0,
+            ..create_dto()
+        };
+        assert!(zero_max.validate().is_err());
+
+        let heavy = CreateAssessmentDto {
+            weight: 101,
+            ..create_dto()
+        };
+        assert!(heavy.validate().is_err());
+    }
+
+    #[test]
+    fn test_record_scores_dto_rejects_negative_score() {
+        let dto = RecordScoresDto {
+            scores: vec![ScoreEntryDto {
+                student_id: UserId::new(),
+                score: -1.0,
+                remarks: None,
+            }],
+        };
+        assert!(dto.validate().is_err());
+    }
+
+    #[test]
+    fn test_grading_scheme_weights_must_sum_to_100() {
+        let component = |kind, weight, best_of| GradingComponent {
+            kind,
+            weight,
+            best_of,
+        };
+        let dto = |components| SetGradingSchemeDto {
+            subject_id: None,
+            components,
+            school_id: None,
+        };
+
+        assert!(
+            dto(vec![
+                component(AssessmentKind::Quiz, 30, Some(2)),
+                component(AssessmentKind::Assignment, 10, None),
+                component(AssessmentKind::Exam, 60, None),
+            ])
+            .validate()
+            .is_ok()
+        );
+        assert!(
+            dto(vec![
+                component(AssessmentKind::Quiz, 30, None),
+                component(AssessmentKind::Exam, 60, None),
+            ])
+            .validate()
+            .is_err()
+        );
+        assert!(
+            dto(vec![
+                component(AssessmentKind::Exam, 50, None),
+                component(AssessmentKind::Exam, 50, None),
+            ])
+            .validate()
+            .is_err()
+        );
+        assert!(
+            dto(vec![component(AssessmentKind::Exam, 100, Some(0))])
+                .validate()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Asset register domain models and DTOs.
+//!
+//! This module contains all data structures related to the school inventory,
+//! including assets (devices, lab equipment, furniture), their assignment to
+//! rooms or staff, condition history, and audit counts.
+//!
+//! Assets are scoped per school and identified by an asset tag that is unique
+//! within the school.
+
+use crate::ids::{AssetId, SchoolId, UserId};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Broad category of an asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum AssetCategory {
+    Device,
+    LabEquipment,
+    Furniture,
+    Sports,
+    Vehicle,
+    Other,
+}
+
+/// Physical condition of an asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum AssetCondition {
+    New,
+    Good,
+    Fair,
+    Poor,
+    Broken,
+}
+
+/// Lifecycle state of an asset.
+///
+/// `Available` and `Assigned` are managed by the assign/unassign endpoints.
+/// The remaining states are set explicitly through an update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum AssetStatus {
+    Available,
+    Assigned,
+    UnderRepair,
+    Lost,
+    Retired,
+}
+
+/// A school asset.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Asset {
+    /// Unique identifier for the asset
+    pub id: AssetId,
+    /// School that owns the asset
+    pub school_id: SchoolId,
+    /// Inventory tag, unique within the school
+    pub asset_tag: String,
+    /// Display name of the asset
+    pub name: String,
+    /// Asset category
+    pub category: AssetCategory,
+    /// Manufacturer serial number
+    pub serial_number: Option<String>,
+    /// Optional description
+    pub description: Option<String>,
+    /// Date the asset was purchased
+    pub purchase_date: Option<NaiveDate>,
+    /// Latest recorded condition
+    pub condition: AssetCondition,
+    /// Current lifecycle state
+    pub status: AssetStatus,
+    /// Room the asset is currently assigned to
+    pub room: Option<String>,
+    /// Staff member the asset is currently assigned to
+    pub assigned_to: Option<UserId>,
+    /// Timestamp when the asset was registered
+    pub created_at: DateTime<Utc>,
+    /// Timestamp when the asset was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for registering a new asset.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateAssetDto {
+    /// Inventory tag (1-50 characters, unique within the school)
+    #[validate(length(min = 1, max = 50))]
+    pub asset_tag: String,
+    /// Display name (1-255 characters)
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    /// Asset category
+    pub category: AssetCategory,
+    /// Manufacturer serial number (max 100 characters)
+    #[validate(length(max = 100))]
+    pub serial_number: Option<String>,
+    /// Optional description
+    pub description: Option<String>,
+    /// Date the asset was purchased
+    pub purchase_date: Option<NaiveDate>,
+    /// Initial condition (defaults to good)
+    pub condition: Option<AssetCondition>,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// DTO for updating an asset.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateAssetDto {
+    /// Updated inventory tag (1-50 characters)
+    #[validate(length(min = 1, max = 50))]
+    pub asset_tag: Option<String>,
+    /// Updated display name (1-255 characters)
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    /// Updated category
+    pub category: Option<AssetCategory>,
+    /// Updated serial number (max 100 characters)
+    #[validate(length(max = 100))]
+    pub serial_number: Option<String>,
+    /// Updated description
+    pub description: Option<String>,
+    /// Updated purchase date
+    pub purchase_date: Option<NaiveDate>,
+    /// Updated lifecycle state (use the assign endpoints for assigned/available)
+    pub status: Option<AssetStatus>,
+}
+
+/// DTO for assigning an asset to a room and/or staff member.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct AssignAssetDto {
+    /// Room to place the asset in (max 100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub room: Option<String>,
+    /// Staff member responsible for the asset
+    pub assigned_to: Option<UserId>,
+    /// Optional notes about the assignment
+    #[validate(length(max = 1000))]
+    pub notes: Option<String>,
+}
+
+/// DTO for recording a condition check.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct RecordConditionDto {
+    /// Observed condition
+    pub condition: AssetCondition,
+    /// Optional notes about the check (max 1000 characters)
+    #[validate(length(max = 1000))]
+    pub notes: Option<String>,
+}
+
+/// A past or current assignment of an asset.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AssetAssignment {
+    pub id: Uuid,
+    pub asset_id: AssetId,
+    pub room: Option<String>,
+    pub assigned_to: Option<UserId>,
+    pub assigned_by: Option<UserId>,
+    pub notes: Option<String>,
+    pub assigned_at: DateTime<Utc>,
+    /// When the asset was returned; `None` for the current assignment
+    pub returned_at: Option<DateTime<Utc>>,
+}
+
+/// A recorded condition check for an asset.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AssetConditionLog {
+    pub id: Uuid,
+    pub asset_id: AssetId,
+    pub condition: AssetCondition,
+    pub notes: Option<String>,
+    pub recorded_by: Option<UserId>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Query parameters for filtering assets.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct AssetFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by category
+    pub category: Option<AssetCategory>,
+    /// Filter by lifecycle state
+    pub status: Option<AssetStatus>,
+    /// Filter by condition
+    pub condition: Option<AssetCondition>,
+    /// Filter by assigned staff member
+    pub assigned_to: Option<UserId>,
+    /// Filter by room (exact match)
+    pub room: Option<String>,
+    /// Search by name, asset tag, or serial number (partial match)
+    pub search: Option<String>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing assets.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedAssetsResponse {
+    /// List of assets
+    pub data: Vec<Asset>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// Query parameters for the asset audit summary.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct AssetSummaryParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// Number of assets sharing a category, status, or condition.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AssetCount {
+    pub key: String,
+    pub count: i64,
+}
+
+/// Audit counts for a school's asset register.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AssetSummary {
+    /// Total number of registered assets
+    pub total: i64,
+    /// Counts grouped by category
+    pub by_category: Vec<AssetCount>,
+    /// Counts grouped by lifecycle state
+    pub by_status: Vec<AssetCount>,
+    /// Counts grouped by condition
+    pub by_condition: Vec<AssetCount>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_asset_dto_validation() {
+        let valid_dto = CreateAssetDto {
+            asset_tag: "LAB-0001".to_string(),
+            name: "Microscope".to_string(),
+            category: AssetCategory::LabEquipment,
+            serial_number: Some("SN-123".to_string()),
+            description: None,
+            purchase_date: NaiveDate::from_ymd_opt(2024, 9, 1),
+            condition: None,
+            school_id: None,
+        };
+        assert!(valid_dto.validate().is_ok());
+
+        let empty_tag = CreateAssetDto {
+            asset_tag: String::new(),
+            ..valid_dto
+        };
+        assert!(empty_tag.validate().is_err());
+    }
+
+    #[test]
+    fn test_asset_enums_serialize_snake_case() {
+        assert_eq!(
+            serde_json::to_string(&AssetCategory::LabEquipment).unwrap(),
+            "\"lab_equipment\""
+        );
+        assert_eq!(
+            serde_json::from_str::<AssetStatus>("\"under_repair\"").unwrap(),
+            AssetStatus::UnderRepair
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Attendance domain models and DTOs.
+//!
+//! Attendance is recorded once per student per day against the branch the
+//! student sits in. Records can be marked by staff through the API or from a
+//! shared kiosk device, in which case the device is recorded alongside the
+//! staff member who unlocked it.
+//!
+//! A school can limit how long after the day attendance can still be marked
+//! or changed. Once its correction window has closed, only staff with the
+//! `attendance:override` permission can change that day's records.
+//!
+//! Analytics report attendance rates over a term. A student counts as having
+//! attended when marked present or late; excused absences are left out of
+//! rates entirely, so they neither help nor hurt a student.
+
+use crate::ids::{AttendanceRecordId, BranchId, KioskDeviceId, LevelId, SchoolId, TermId, UserId};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Attendance status for a student on a given day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum AttendanceStatus {
+    Present,
+    Absent,
+    Late,
+    Excused,
+}
+
+/// A student's attendance for one day.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AttendanceRecord {
+    pub id: AttendanceRecordId,
+    pub school_id: SchoolId,
+    pub branch_id: BranchId,
+    pub student_id: UserId,
+    pub date: NaiveDate,
+    pub status: AttendanceStatus,
+    pub note: Option<String>,
+    /// Staff member who marked the record
+    pub marked_by: Option<UserId>,
+    /// Kiosk device the record was marked on, if any
+    pub kiosk_device_id: Option<KioskDeviceId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Attendance record with the student's name.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AttendanceRecordWithStudent {
+    pub id: AttendanceRecordId,
+    pub student_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub date: NaiveDate,
+    pub status: AttendanceStatus,
+    pub note: Option<String>,
+    pub marked_by: Option<UserId>,
+    pub kiosk_device_id: Option<KioskDeviceId>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A single student's entry in an attendance submission.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct AttendanceEntryDto {
+    pub student_id: UserId,
+    pub status: AttendanceStatus,
+    /// Optional note, e.g. the reason for an excused absence
+    #[validate(length(max = 255))]
+    pub note: Option<String>,
+}
+
+/// DTO for marking attendance for a branch on a given day.
+///
+/// Marking a student who already has a record for the day overwrites it.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct MarkAttendanceDto {
+    pub branch_id: BranchId,
+    pub date: NaiveDate,
+    /// Entries to record (1-200)
+    #[validate(length(min = 1, max = 200), nested)]
+    pub records: Vec<AttendanceEntryDto>,
+}
+
+/// Response for an attendance submission.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MarkAttendanceResponse {
+    /// Number of records written
+    pub recorded_count: usize,
+    /// Students that are not in the branch and were skipped
+    pub failed_ids: Vec<UserId>,
+}
+
+/// A school's attendance settings.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AttendanceSettings {
+    pub school_id: SchoolId,
+    /// Hours after the end of an attendance day, in the school's timezone,
+    /// that its records can still be marked or changed; `null` for no limit
+    pub correction_window_hours: Option<i32>,
+}
+
+/// DTO for replacing a school's attendance settings.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateAttendanceSettingsDto {
+    /// Hours the correction window stays open after each day (1-8760);
+    /// `null` or omitted removes the limit
+    #[validate(range(min = 1, max = 8760))]
+    pub correction_window_hours: Option<i32>,
+}
+
+/// Query parameters for reading a branch's attendance.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct AttendanceQueryParams {
+    pub branch_id: BranchId,
+    pub date: NaiveDate,
+}
+
+/// Query parameters for attendance analytics over a term.
+///
+/// Without a level or branch the whole school is reported.
+#[derive(Debug, Clone, Hash, Deserialize, ToSchema, IntoParams)]
+pub struct AttendanceAnalyticsParams {
+    pub term_id: TermId,
+    /// Only report students in this level
+    pub level_id: Option<LevelId>,
+    /// Only report students in this branch
+    pub branch_id: Option<BranchId>,
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// Query parameters for listing chronic absentees over a term.
+#[derive(Debug, Clone, Hash, Deserialize, Validate, ToSchema, IntoParams)]
+pub struct ChronicAbsenteeParams {
+    pub term_id: TermId,
+    /// Attendance rate, in percent, below which a student is listed (default: 90)
+    #[validate(range(min = 1, max = 100))]
+    pub threshold: Option<u8>,
+    /// Only report students in this level
+    pub level_id: Option<LevelId>,
+    /// Only report students in this branch
+    pub branch_id: Option<BranchId>,
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+}
+
+impl ChronicAbsenteeParams {
+    /// Default threshold below which a student is a chronic absentee.
+    pub const DEFAULT_THRESHOLD: u8 = 90;
+
+    pub fn threshold(&self) -> u8 {
+        self.threshold.unwrap_or(Self::DEFAULT_THRESHOLD)
+    }
+}
+
+/// Attendance rate for one week of a term.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AttendanceTrendPoint {
+    /// Monday of the week
+    pub week_start: NaiveDate,
+    /// Records marked present or late
+    pub attended: i64,
+    /// Records counted towards the rate (excused absences are left out)
+    pub counted: i64,
+    /// Attendance rate for the week, in percent
+    pub rate: f64,
+    /// Average of this and the two previous weekly rates, in percent
+    pub rolling_rate: f64,
+}
+
+/// A student whose attendance rate over a term is below the threshold.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ChronicAbsentee {
+    pub student_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub branch_id: Option<BranchId>,
+    pub attended: i64,
+    pub counted: i64,
+    /// Attendance rate over the term, in percent
+    pub rate: f64,
+}
+
+/// Attendance rate for one weekday across a term.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct DayOfWeekAttendance {
+    /// ISO weekday, 1 (Monday) to 7 (Sunday)
+    pub day_of_week: i32,
+    pub attended: i64,
+    pub counted: i64,
+    /// Attendance rate on this weekday, in percent
+    pub rate: f64,
+    /// Difference from the term's overall rate, in percentage points
+    pub difference_from_overall: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mark_attendance_dto_validation() {
+        let entry = AttendanceEntryDto {
+            student_id: UserId::new(),
+            status: AttendanceStatus::Present,
+            note: None,
+        };
+        let valid = MarkAttendanceDto {
+            branch_id: BranchId::new(),
+            date: NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+            records: vec![entry.clone()],
+        };
+        assert!(valid.validate().is_ok());
+
+        let empty = MarkAttendanceDto {
+            records: vec![],
+            ..valid.clone()
+        };
+        assert!(empty.validate().is_err());
+
+        let long_note = MarkAttendanceDto {
+            records: vec![AttendanceEntryDto {
+                note: Some("x".repeat(256)),
+                ..entry
+            }],
+            ..valid
+        };
+        assert!(long_note.validate().is_err());
+    }
+
+    #[test]
+    fn test_attendance_status_serialization() {
+        let json = serde_json::to_string(&AttendanceStatus::Excused).unwrap();
+        assert_eq!(json, "\"excused\"");
+    }
+
+    #[test]
+    fn test_chronic_absentee_params_threshold() {
+        let params = ChronicAbsenteeParams {
+            term_id: TermId::new(),
+            threshold: None,
+            level_id: None,
+            branch_id: None,
+            school_id: None,
+        };
+        assert_eq!(params.threshold(), 90);
+        assert!(params.validate().is_ok());
+
+        let out_of_range = ChronicAbsenteeParams {
+            threshold: Some(101),
+            ..params
+        };
+        assert!(out_of_range.validate().is_err());
+    }
+}
@@ -14,7 +14,7 @@ use crate::users::{BranchInfo, LevelInfo, SchoolInfo};
 use crate::value_types::Email;
 
 // Re-export JWT claim types from chalkbyte-auth for backward compatibility
-pub use chalkbyte_auth::{Claims, MfaTempClaims, RefreshTokenClaims};
+pub use chalkbyte_auth::{Claims, RefreshTokenClaims};
 
 /// Login request with email and password.
 ///
@@ -27,6 +27,11 @@ pub struct LoginRequest {
     #[validate(length(min = 1))]
     #[schema(example = "password123")]
     pub password: String,
+    /// Stable identifier for the client device; a new login from the same
+    /// device ends its previous session. Each login is its own device if omitted.
+    #[validate(length(min = 1, max = 128))]
+    #[schema(example = "ios-5f2c9a")]
+    pub device_id: Option<String>,
 }
 
 /// User info returned in login response with joined relations
@@ -58,14 +63,31 @@ pub struct LoginResponse {
     pub permissions: Vec<Permission>,
 }
 
+/// A second factor that can complete an MFA login.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MfaMethod {
+    Totp,
+    RecoveryCode,
+    Passkey,
+}
+
 /// Response indicating MFA verification is required.
 ///
-/// Returned when the user has MFA enabled. The `temp_token` must be
-/// submitted along with a TOTP code to complete authentication.
+/// Returned when the user has MFA enabled. The `temp_token` is an opaque
+/// handle to the login challenge held by the server and must be submitted
+/// along with a code from one of the allowed `methods`.
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct MfaRequiredResponse {
     pub mfa_required: bool,
     pub temp_token: String,
+    /// Second factors accepted for this login
+    pub methods: Vec<MfaMethod>,
+    /// Factor the user prefers to be offered first, if they set one
+    pub preferred_method: Option<MfaMethod>,
+    /// Seconds until the challenge expires
+    #[schema(example = 600)]
+    pub expires_in: u64,
 }
 
 /// MFA verification request with TOTP code.
@@ -79,6 +101,11 @@ pub struct MfaVerifyLoginRequest {
     #[validate(length(equal = 6))]
     #[schema(example = "123456")]
     pub code: String,
+    /// Stable identifier for the client device; a new login from the same
+    /// device ends its previous session. Each login is its own device if omitted.
+    #[validate(length(min = 1, max = 128))]
+    #[schema(example = "ios-5f2c9a")]
+    pub device_id: Option<String>,
 }
 
 /// MFA recovery code login request.
@@ -89,9 +116,50 @@ pub struct MfaVerifyLoginRequest {
 pub struct MfaRecoveryLoginRequest {
     #[validate(length(min = 1))]
     pub temp_token: String,
-    #[validate(length(equal = 8))]
+    /// Case-insensitive; spaces and dashes between characters are ignored
+    #[validate(length(min = 8, max = 16))]
     #[schema(example = "ABCD1234")]
     pub recovery_code: String,
+    /// Stable identifier for the client device; a new login from the same
+    /// device ends its previous session. Each login is its own device if omitted.
+    #[validate(length(min = 1, max = 128))]
+    #[schema(example = "ios-5f2c9a")]
+    pub device_id: Option<String>,
+}
+
+/// Request for a passkey challenge to complete an MFA login.
+///
+/// The returned options are passed to `navigator.credentials.get()`.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct MfaPasskeyChallengeRequest {
+    #[validate(length(min = 1))]
+    pub temp_token: String,
+}
+
+/// WebAuthn request options for completing an MFA login with a passkey.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MfaPasskeyChallengeResponse {
+    /// `PublicKeyCredentialRequestOptions` wrapped in a `publicKey` field
+    #[schema(value_type = Object)]
+    pub options: serde_json::Value,
+}
+
+/// MFA passkey login request.
+///
+/// Submit the assertion produced by the authenticator for the challenge
+/// issued for this `temp_token`.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct MfaPasskeyLoginRequest {
+    #[validate(length(min = 1))]
+    pub temp_token: String,
+    /// `PublicKeyCredential` returned by `navigator.credentials.get()`
+    #[schema(value_type = Object)]
+    pub credential: serde_json::Value,
+    /// Stable identifier for the client device; a new login from the same
+    /// device ends its previous session. Each login is its own device if omitted.
+    #[validate(length(min = 1, max = 128))]
+    #[schema(example = "ios-5f2c9a")]
+    pub device_id: Option<String>,
 }
 
 /// Request to refresh an access token.
@@ -104,6 +172,39 @@ pub struct RefreshTokenRequest {
     pub refresh_token: String,
 }
 
+/// Request to log out the device holding a refresh token.
+///
+/// Revokes the token's family so neither it nor any token rotated from it
+/// can be refreshed again.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct LogoutRequest {
+    #[validate(length(min = 1))]
+    pub refresh_token: String,
+}
+
+/// A device signed in to the account.
+///
+/// Each session is one refresh token family; revoking it logs the device
+/// out once its current access token expires.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DeviceSession {
+    pub id: uuid::Uuid,
+    /// The `device_id` sent at login, or the session ID if none was sent
+    #[schema(example = "ios-5f2c9a")]
+    pub device_id: String,
+    /// User agent of the last sign-in or refresh
+    #[schema(example = "Chalkbyte/2.1 (iPhone; iOS 18.0)")]
+    pub user_agent: Option<String>,
+    /// IP address of the last sign-in or refresh
+    #[schema(example = "203.0.113.7")]
+    pub ip_address: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// When the session last signed in or refreshed its token
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
+    /// When the session ends unless refreshed again
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
 /// Forgot password request to initiate password reset.
 ///
 /// Submitting this request sends a password reset email to the user
@@ -146,6 +247,8 @@ mod tests {
             sub: "user-id-123".to_string(),
             email: "test@example.com".to_string(),
             school_id: None,
+            group_id: None,
+            login_id: None,
             role_ids: vec![],
             permissions: vec!["users:read".to_string()],
             exp: 1234567890,
@@ -172,6 +275,8 @@ mod tests {
             sub: "user-id-789".to_string(),
             email: "clone@example.com".to_string(),
             school_id: None,
+            group_id: None,
+            login_id: None,
             role_ids: vec![],
             permissions: vec![],
             exp: 1234567890,
@@ -187,6 +292,7 @@ mod tests {
         let request = LoginRequest {
             email: Email::new("valid@example.com").unwrap(),
             password: "password123".to_string(),
+            device_id: None,
         };
         assert!(request.validate().is_ok());
     }
@@ -202,6 +308,7 @@ mod tests {
         let request = LoginRequest {
             email: Email::new("test@example.com").unwrap(),
             password: "".to_string(),
+            device_id: None,
         };
         assert!(request.validate().is_err());
     }
@@ -211,6 +318,7 @@ mod tests {
         let request = MfaVerifyLoginRequest {
             temp_token: "temp-token-value".to_string(),
             code: "123456".to_string(),
+            device_id: None,
         };
         assert!(request.validate().is_ok());
     }
@@ -220,6 +328,7 @@ mod tests {
         let request = MfaVerifyLoginRequest {
             temp_token: "temp-token-value".to_string(),
             code: "12345".to_string(),
+            device_id: None,
         };
         assert!(request.validate().is_err());
     }
@@ -229,6 +338,7 @@ mod tests {
         let request = MfaVerifyLoginRequest {
             temp_token: "temp-token-value".to_string(),
             code: "1234567".to_string(),
+            device_id: None,
         };
         assert!(request.validate().is_err());
     }
@@ -238,6 +348,7 @@ mod tests {
         let request = MfaRecoveryLoginRequest {
             temp_token: "temp-token-value".to_string(),
             recovery_code: "ABCD1234".to_string(),
+            device_id: None,
         };
         assert!(request.validate().is_ok());
     }
@@ -247,6 +358,7 @@ mod tests {
         let request = MfaRecoveryLoginRequest {
             temp_token: "temp-token-value".to_string(),
             recovery_code: "ABC123".to_string(),
+            device_id: None,
         };
         assert!(request.validate().is_err());
     }
@@ -275,6 +387,8 @@ mod tests {
             exp: 1234567890,
             iat: 1234567800,
             jti: "test-jti-123".to_string(),
+            fam: uuid::Uuid::nil(),
+            login_id: None,
         };
         let serialized = serde_json::to_string(&claims).unwrap();
         assert!(serialized.contains(r#""sub":"user-123""#));
@@ -307,25 +421,12 @@ mod tests {
         assert!(request.validate().is_err());
     }
 
-    #[test]
-    fn test_mfa_temp_claims_serialize() {
-        let claims = MfaTempClaims {
-            sub: "user-mfa-123".to_string(),
-            email: "mfa@test.com".to_string(),
-            mfa_pending: true,
-            exp: 1234567890,
-            iat: 1234567800,
-        };
-        let serialized = serde_json::to_string(&claims).unwrap();
-        assert!(serialized.contains(r#""mfa_pending":true"#));
-        assert!(serialized.contains(r#""email":"mfa@test.com""#));
-    }
-
     #[test]
     fn test_login_request_special_characters_email() {
         let request = LoginRequest {
             email: Email::new("test+tag@example.co.uk").unwrap(),
             password: "password123".to_string(),
+            device_id: None,
         };
         assert!(request.validate().is_ok());
     }
@@ -344,10 +445,25 @@ mod tests {
         let response = MfaRequiredResponse {
             mfa_required: true,
             temp_token: "temporary-token".to_string(),
+            methods: vec![MfaMethod::Totp, MfaMethod::RecoveryCode],
+            preferred_method: None,
+            expires_in: 600,
         };
         let serialized = serde_json::to_string(&response).unwrap();
         assert!(serialized.contains(r#""mfa_required":true"#));
         assert!(serialized.contains(r#""temp_token":"temporary-token""#));
+        assert!(serialized.contains(r#""methods":["totp","recovery_code"]"#));
+        assert!(serialized.contains(r#""preferred_method":null"#));
+    }
+
+    #[test]
+    fn test_mfa_passkey_login_request_empty_temp_token() {
+        let request = MfaPasskeyLoginRequest {
+            temp_token: String::new(),
+            credential: serde_json::json!({}),
+            device_id: None,
+        };
+        assert!(request.validate().is_err());
     }
 
     #[test]
@@ -0,0 +1,373 @@
+//! Billing domain models and DTOs.
+//!
+//! This module contains the data structures for school fees: fee structures
+//! that set what students of a level owe for a term, invoices generated from
+//! them, and manually recorded payments.
+//!
+//! Generating invoices copies a structure's items into one invoice per
+//! student of the level, so later changes never alter what a student was
+//! billed. Payments are recorded against an invoice and can never exceed its
+//! outstanding balance. All amounts are in minor currency units.
+//!
+//! Boarding fees, transport charges, and library fines are kept by their own
+//! modules and billed as extra lines on the student's next invoice. Until
+//! then they count towards balances as uninvoiced charges.
+
+use crate::ids::{FeePaymentId, FeeStructureId, InvoiceId, LevelId, SchoolId, TermId, UserId};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Payment state of an invoice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum InvoiceStatus {
+    Unpaid,
+    PartiallyPaid,
+    Paid,
+    /// Cancelled; a void invoice is not owed
+    Void,
+}
+
+impl InvoiceStatus {
+    /// Status of a live invoice of `amount` with `amount_paid` received.
+    #[must_use]
+    pub fn for_amounts(amount: i64, amount_paid: i64) -> Self {
+        if amount_paid >= amount {
+            Self::Paid
+        } else if amount_paid > 0 {
+            Self::PartiallyPaid
+        } else {
+            Self::Unpaid
+        }
+    }
+}
+
+/// How a payment was made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum PaymentMethod {
+    Cash,
+    BankTransfer,
+    Card,
+    Cheque,
+    MobileMoney,
+    Other,
+}
+
+/// A named amount on a fee structure or invoice.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Validate, ToSchema)]
+pub struct FeeItem {
+    /// Item name (e.g. "Tuition", "Books"), 1-100 characters
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Amount in minor currency units
+    #[validate(range(min = 0))]
+    pub amount: i64,
+}
+
+/// What students of a level owe for a term.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct FeeStructure {
+    pub id: FeeStructureId,
+    pub school_id: SchoolId,
+    pub level_id: LevelId,
+    pub term_id: TermId,
+    /// Structure name, unique within the level and term
+    pub name: String,
+    /// When invoices generated from the structure fall due
+    pub due_date: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A fee structure with its items.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FeeStructureDetail {
+    #[serde(flatten)]
+    pub structure: FeeStructure,
+    /// Items in the order they were given
+    pub items: Vec<FeeItem>,
+    /// Sum of all items in minor currency units
+    pub total: i64,
+}
+
+/// DTO for creating a fee structure.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateFeeStructureDto {
+    /// Level whose students the structure bills
+    pub level_id: LevelId,
+    /// Term the fees are for
+    pub term_id: TermId,
+    /// Structure name (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// When generated invoices fall due
+    pub due_date: Option<NaiveDate>,
+    /// Fee items (1-50, names unique)
+    #[validate(length(min = 1, max = 50), nested)]
+    pub items: Vec<FeeItem>,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// Query parameters for listing fee structures.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct FeeStructureFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by level
+    pub level_id: Option<LevelId>,
+    /// Filter by term
+    pub term_id: Option<TermId>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing fee structures.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedFeeStructuresResponse {
+    /// List of fee structures
+    pub data: Vec<FeeStructure>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// Result of generating invoices from a fee structure.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GenerateInvoicesResponse {
+    pub fee_structure_id: FeeStructureId,
+    /// Number of new invoices issued
+    pub created: i64,
+    /// Number of students of the level who were already invoiced
+    pub already_invoiced: i64,
+}
+
+/// A student's invoice.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Invoice {
+    pub id: InvoiceId,
+    pub school_id: SchoolId,
+    pub student_id: UserId,
+    pub fee_structure_id: FeeStructureId,
+    pub term_id: TermId,
+    /// Amount billed in minor currency units
+    pub amount: i64,
+    /// Amount received so far
+    pub amount_paid: i64,
+    /// Amount still owed; zero for paid and void invoices
+    pub balance: i64,
+    pub status: InvoiceStatus,
+    pub due_date: Option<NaiveDate>,
+    pub issued_at: DateTime<Utc>,
+    pub voided_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A payment recorded against an invoice.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct FeePayment {
+    pub id: FeePaymentId,
+    pub invoice_id: InvoiceId,
+    /// Amount received in minor currency units
+    pub amount: i64,
+    pub method: PaymentMethod,
+    /// Receipt, teller, or transfer reference
+    pub reference: Option<String>,
+    /// Day the money was received
+    pub paid_on: NaiveDate,
+    pub recorded_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An invoice with its lines and payments.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct InvoiceDetail {
+    #[serde(flatten)]
+    pub invoice: Invoice,
+    /// Items copied from the fee structure when the invoice was issued
+    pub lines: Vec<FeeItem>,
+    /// Payments, oldest first
+    pub payments: Vec<FeePayment>,
+}
+
+/// Query parameters for listing invoices.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct InvoiceFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by student
+    pub student_id: Option<UserId>,
+    /// Filter by term
+    pub term_id: Option<TermId>,
+    /// Filter by fee structure
+    pub fee_structure_id: Option<FeeStructureId>,
+    /// Filter by status
+    pub status: Option<InvoiceStatus>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing invoices.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedInvoicesResponse {
+    /// List of invoices, newest first
+    pub data: Vec<Invoice>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// DTO for recording a payment.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct RecordPaymentDto {
+    /// Amount received in minor currency units, at most the invoice balance
+    #[validate(range(min = 1))]
+    pub amount: i64,
+    pub method: PaymentMethod,
+    /// Receipt, teller, or transfer reference (up to 100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub reference: Option<String>,
+    /// Day the money was received (defaults to today in the school's timezone)
+    pub paid_on: Option<NaiveDate>,
+}
+
+/// Query parameters for balance lookups.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct BalanceQueryParams {
+    /// School ID (required for system admins on school balances)
+    pub school_id: Option<SchoolId>,
+    /// Only count invoices for this term
+    pub term_id: Option<TermId>,
+}
+
+/// Module a charge billed alongside school fees comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum ChargeSource {
+    Boarding,
+    Transport,
+    LibraryFine,
+}
+
+/// A boarding fee, transport charge, or library fine not yet on an invoice.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct UninvoicedCharge {
+    pub source: ChargeSource,
+    pub description: String,
+    pub amount: i64,
+    /// Term the charge falls in; `None` for a fine charged between terms
+    pub term_id: Option<TermId>,
+}
+
+/// What a student has been billed, has paid, and still owes.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StudentBalance {
+    pub student_id: UserId,
+    pub term_id: Option<TermId>,
+    /// Sum of all invoices that are not void
+    pub total_invoiced: i64,
+    pub total_paid: i64,
+    /// Sum of charges not yet on an invoice
+    pub uninvoiced: i64,
+    /// Open invoice balances plus uninvoiced charges
+    pub outstanding: i64,
+    /// Invoices with a balance, oldest due first
+    pub open_invoices: Vec<Invoice>,
+    /// Charges that will be billed on the student's next invoice
+    pub uninvoiced_charges: Vec<UninvoicedCharge>,
+}
+
+/// A school's billing totals.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct SchoolBalance {
+    pub school_id: SchoolId,
+    pub term_id: Option<TermId>,
+    /// Sum of all invoices that are not void
+    pub total_invoiced: i64,
+    pub total_paid: i64,
+    /// Sum of charges not yet on an invoice
+    pub uninvoiced: i64,
+    /// Open invoice balances plus uninvoiced charges
+    pub outstanding: i64,
+    /// Invoices with a balance
+    pub open_invoices: i64,
+    /// Students who owe anything
+    pub students_in_arrears: i64,
+}
+
+/// Query parameters for the arrears export.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct ArrearsExportParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Only count invoices for this term
+    pub term_id: Option<TermId>,
+    /// Only students currently in this level
+    pub level_id: Option<LevelId>,
+    /// Only count invoices past their due date, leaving out uninvoiced
+    /// charges
+    #[serde(default)]
+    pub overdue_only: bool,
+}
+
+/// One student's row in the arrears CSV.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ArrearsRow {
+    pub student_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub level: Option<String>,
+    pub open_invoices: i64,
+    pub total_invoiced: i64,
+    pub total_paid: i64,
+    pub uninvoiced: i64,
+    pub outstanding: i64,
+    pub earliest_due_date: Option<NaiveDate>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invoice_status_for_amounts() {
+        assert_eq!(InvoiceStatus::for_amounts(5_000, 0), InvoiceStatus::Unpaid);
+        assert_eq!(
+            InvoiceStatus::for_amounts(5_000, 2_000),
+            InvoiceStatus::PartiallyPaid
+        );
+        assert_eq!(
+            InvoiceStatus::for_amounts(5_000, 5_000),
+            InvoiceStatus::Paid
+        );
+        assert_eq!(InvoiceStatus::for_amounts(0, 0), InvoiceStatus::Paid);
+    }
+
+    #[test]
+    fn test_create_fee_structure_dto_validation() {
+        let dto = |items: Vec<FeeItem>| CreateFeeStructureDto {
+            level_id: LevelId::new(),
+            term_id: TermId::new(),
+            name: "JSS1 First Term".to_string(),
+            due_date: None,
+            items,
+            school_id: None,
+        };
+        let item = |name: &str, amount: i64| FeeItem {
+            name: name.to_string(),
+            amount,
+        };
+
+        assert!(dto(vec![item("Tuition", 12_000_000)]).validate().is_ok());
+        assert!(dto(Vec::new()).validate().is_err());
+        assert!(dto(vec![item("Tuition", -1)]).validate().is_err());
+    }
+}
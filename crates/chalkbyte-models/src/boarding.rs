@@ -0,0 +1,297 @@
+//! Boarding domain models and DTOs.
+//!
+//! This module contains all data structures for boarding schools: hostels,
+//! rooms with a fixed capacity, per-session student room allocations, and
+//! boarding-specific fee lines.
+//!
+//! Allocations are tied to an academic session, so a student can hold at most
+//! one bed per session and rooms are re-allocated each year. Fee lines are
+//! defined per hostel and session, and a student's boarding fees are the lines
+//! of the hostel they are allocated to.
+
+use crate::ids::{
+    AcademicSessionId, BoardingFeeLineId, HostelId, HostelRoomId, RoomAllocationId, SchoolId,
+    UserId,
+};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Which students a hostel houses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum HostelGender {
+    Male,
+    Female,
+    Mixed,
+}
+
+/// A boarding hostel.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Hostel {
+    /// Unique identifier for the hostel
+    pub id: HostelId,
+    /// School that runs the hostel
+    pub school_id: SchoolId,
+    /// Hostel name, unique within the school
+    pub name: String,
+    /// Which students the hostel houses
+    pub gender: HostelGender,
+    /// Staff member in charge of the hostel
+    pub warden_id: Option<UserId>,
+    /// Optional description
+    pub description: Option<String>,
+    /// Timestamp when the hostel was created
+    pub created_at: DateTime<Utc>,
+    /// Timestamp when the hostel was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for creating a hostel.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateHostelDto {
+    /// Hostel name (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Which students the hostel houses (defaults to mixed)
+    pub gender: Option<HostelGender>,
+    /// Staff member in charge of the hostel
+    pub warden_id: Option<UserId>,
+    /// Optional description
+    pub description: Option<String>,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// DTO for updating a hostel.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateHostelDto {
+    /// Updated name (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    /// Updated gender
+    pub gender: Option<HostelGender>,
+    /// Updated warden
+    pub warden_id: Option<UserId>,
+    /// Updated description
+    pub description: Option<String>,
+}
+
+/// Query parameters for listing hostels.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct HostelFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by gender
+    pub gender: Option<HostelGender>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing hostels.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedHostelsResponse {
+    /// List of hostels
+    pub data: Vec<Hostel>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// A room within a hostel.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct HostelRoom {
+    pub id: HostelRoomId,
+    pub hostel_id: HostelId,
+    /// Room name or number, unique within the hostel
+    pub name: String,
+    /// Number of beds in the room
+    pub capacity: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A hostel room with its occupancy for an academic session.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct HostelRoomWithOccupancy {
+    pub id: HostelRoomId,
+    pub hostel_id: HostelId,
+    pub name: String,
+    pub capacity: i32,
+    /// Number of students allocated for the requested session
+    pub occupied: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for creating a hostel room.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateHostelRoomDto {
+    /// Room name or number (1-50 characters)
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+    /// Number of beds (1-100)
+    #[validate(range(min = 1, max = 100))]
+    pub capacity: i32,
+}
+
+/// DTO for updating a hostel room.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateHostelRoomDto {
+    /// Updated name (1-50 characters)
+    #[validate(length(min = 1, max = 50))]
+    pub name: Option<String>,
+    /// Updated capacity (1-100, not below current occupancy)
+    #[validate(range(min = 1, max = 100))]
+    pub capacity: Option<i32>,
+}
+
+/// Query parameters for listing hostel rooms.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct HostelRoomQueryParams {
+    /// Academic session used to compute occupancy (defaults to the active session)
+    pub academic_session_id: Option<AcademicSessionId>,
+}
+
+/// A student's room allocation for an academic session.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct RoomAllocation {
+    pub id: RoomAllocationId,
+    pub room_id: HostelRoomId,
+    pub student_id: UserId,
+    pub academic_session_id: AcademicSessionId,
+    pub allocated_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Room allocation with student, room, and hostel names.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct RoomAllocationWithDetails {
+    pub id: RoomAllocationId,
+    pub room_id: HostelRoomId,
+    pub room_name: String,
+    pub hostel_id: HostelId,
+    pub hostel_name: String,
+    pub student_id: UserId,
+    pub student_first_name: String,
+    pub student_last_name: String,
+    pub academic_session_id: AcademicSessionId,
+    pub allocated_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO for allocating a student to a room.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct AllocateRoomDto {
+    /// Room to allocate
+    pub room_id: HostelRoomId,
+    /// Student being allocated
+    pub student_id: UserId,
+    /// Academic session the allocation applies to
+    pub academic_session_id: AcademicSessionId,
+}
+
+/// Query parameters for listing room allocations.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct RoomAllocationFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by academic session
+    pub academic_session_id: Option<AcademicSessionId>,
+    /// Filter by hostel
+    pub hostel_id: Option<HostelId>,
+    /// Filter by room
+    pub room_id: Option<HostelRoomId>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing room allocations.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedRoomAllocationsResponse {
+    /// List of allocations
+    pub data: Vec<RoomAllocationWithDetails>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// A boarding-specific fee line for a hostel and session.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BoardingFeeLine {
+    pub id: BoardingFeeLineId,
+    pub hostel_id: HostelId,
+    pub academic_session_id: AcademicSessionId,
+    /// Fee name (e.g. "Boarding fee", "Laundry")
+    pub name: String,
+    /// Amount in minor currency units
+    pub amount: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO for adding a fee line to a hostel.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateBoardingFeeLineDto {
+    /// Academic session the fee applies to
+    pub academic_session_id: AcademicSessionId,
+    /// Fee name (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Amount in minor currency units
+    #[validate(range(min = 0))]
+    pub amount: i64,
+}
+
+/// Query parameters for boarding fee lookups.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct BoardingFeeQueryParams {
+    /// Academic session (defaults to the active session)
+    pub academic_session_id: Option<AcademicSessionId>,
+}
+
+/// Boarding fees owed by a student for a session.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StudentBoardingFees {
+    pub student_id: UserId,
+    pub academic_session_id: AcademicSessionId,
+    /// The student's allocation, if they board this session
+    pub allocation: Option<RoomAllocationWithDetails>,
+    /// Fee lines of the allocated hostel
+    pub lines: Vec<BoardingFeeLine>,
+    /// Sum of all fee lines in minor currency units
+    pub total: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_hostel_room_dto_validation() {
+        let valid_dto = CreateHostelRoomDto {
+            name: "Room 1".to_string(),
+            capacity: 4,
+        };
+        assert!(valid_dto.validate().is_ok());
+
+        let zero_capacity = CreateHostelRoomDto {
+            name: "Room 2".to_string(),
+            capacity: 0,
+        };
+        assert!(zero_capacity.validate().is_err());
+    }
+
+    #[test]
+    fn test_create_boarding_fee_line_dto_validation() {
+        let negative = CreateBoardingFeeLineDto {
+            academic_session_id: AcademicSessionId::new(),
+            name: "Boarding fee".to_string(),
+            amount: -1,
+        };
+        assert!(negative.validate().is_err());
+    }
+}
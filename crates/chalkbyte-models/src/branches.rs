@@ -2,13 +2,18 @@
 //!
 //! This module contains all data structures related to school branches,
 //! including branch entities, request/response DTOs, and filtering parameters.
-
-use crate::ids::{BranchId, LevelId, UserId};
-use chalkbyte_core::{PaginationMeta, PaginationParams};
-use chrono::{DateTime, Utc};
+//!
+//! A branch can have several teachers at once, each assigned in a
+//! [`TeacherCapacity`] for a date range. The capacity decides what the
+//! teacher may do for the branch: every capacity can mark attendance, but
+//! assistants cannot finalize grades.
+
+use crate::ids::{BranchId, BranchTeacherId, LevelId, UserId};
+use chalkbyte_core::{PaginationMeta, PaginationParams, SortParams, Versioned};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
@@ -21,6 +26,12 @@ pub struct Branch {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Versioned for Branch {
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct BranchWithStats {
     pub id: BranchId,
@@ -32,17 +43,33 @@ pub struct BranchWithStats {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Versioned for BranchWithStats {
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
+
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct CreateBranchDto {
-    #[validate(length(min = 1, max = 100))]
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
     pub name: String,
+    #[validate(length(max = 2000, message = "Description must not exceed 2000 characters"))]
     pub description: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdateBranchDto {
-    #[validate(length(min = 1, max = 100))]
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
     pub name: Option<String>,
+    #[validate(length(max = 2000, message = "Description must not exceed 2000 characters"))]
     pub description: Option<String>,
 }
 
@@ -51,6 +78,8 @@ pub struct BranchFilterParams {
     pub name: Option<String>,
     #[serde(flatten)]
     pub pagination: PaginationParams,
+    #[serde(flatten)]
+    pub sort: SortParams,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -76,6 +105,67 @@ pub struct BulkAssignResponse {
     pub failed_ids: Vec<UserId>,
 }
 
+/// Capacity a teacher is assigned to a branch in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum TeacherCapacity {
+    /// Teacher in charge of the branch; at most one at a time
+    Lead,
+    /// Supports the lead teacher
+    Assistant,
+    /// Covers for the lead teacher, e.g. during leave
+    Substitute,
+}
+
+impl TeacherCapacity {
+    /// Whether a teacher in this capacity may mark attendance for the branch.
+    pub fn can_mark_attendance(self) -> bool {
+        true
+    }
+
+    /// Whether a teacher in this capacity may finalize grades for the
+    /// branch's level.
+    pub fn can_finalize_grades(self) -> bool {
+        !matches!(self, Self::Assistant)
+    }
+}
+
+/// A teacher's assignment to a branch, with the teacher's name.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BranchTeacher {
+    pub id: BranchTeacherId,
+    pub branch_id: BranchId,
+    pub teacher_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub capacity: TeacherCapacity,
+    /// First day of the assignment (inclusive)
+    pub starts_on: NaiveDate,
+    /// Last day of the assignment (inclusive); open-ended when absent
+    pub ends_on: Option<NaiveDate>,
+    pub assigned_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO for assigning a teacher to a branch.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct AssignBranchTeacherDto {
+    pub teacher_id: UserId,
+    pub capacity: TeacherCapacity,
+    /// First day of the assignment (defaults to today)
+    pub starts_on: Option<NaiveDate>,
+    /// Last day of the assignment (must not be before starts_on)
+    pub ends_on: Option<NaiveDate>,
+}
+
+/// Query parameters for listing a branch's teachers.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct BranchTeacherQueryParams {
+    /// Only return assignments active on this date
+    pub active_on: Option<NaiveDate>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,4 +218,25 @@ mod tests {
         };
         assert!(empty_ids.validate().is_err());
     }
+
+    #[test]
+    fn test_teacher_capacity_permissions() {
+        for capacity in [
+            TeacherCapacity::Lead,
+            TeacherCapacity::Assistant,
+            TeacherCapacity::Substitute,
+        ] {
+            assert!(capacity.can_mark_attendance());
+        }
+
+        assert!(TeacherCapacity::Lead.can_finalize_grades());
+        assert!(TeacherCapacity::Substitute.can_finalize_grades());
+        assert!(!TeacherCapacity::Assistant.can_finalize_grades());
+    }
+
+    #[test]
+    fn test_teacher_capacity_serialization() {
+        let json = serde_json::to_string(&TeacherCapacity::Substitute).unwrap();
+        assert_eq!(json, "\"substitute\"");
+    }
 }
@@ -0,0 +1,383 @@
+//! Broadcast domain models and DTOs.
+//!
+//! A broadcast is a templated email or SMS sent by a school admin to every
+//! user in an audience, narrowed by role, level, branch, and fee status. Recipients are
+//! resolved when the broadcast is queued and delivered one by one by a
+//! background job, so each recipient carries its own delivery status.
+//!
+//! Before it is queued a broadcast may be saved as a draft, wait for a second
+//! admin's approval when the school requires it, and wait for a publish time
+//! given in the school's timezone.
+
+use crate::ids::{BranchId, BroadcastId, BroadcastRecipientId, LevelId, RoleId, SchoolId, UserId};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+/// Placeholders that may appear in a broadcast subject or body.
+pub const TEMPLATE_PLACEHOLDERS: [&str; 4] =
+    ["first_name", "last_name", "full_name", "school_name"];
+
+/// How a broadcast is delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum BroadcastChannel {
+    /// Sent to the user's account email address
+    Email,
+    /// Sent to the emergency contact phone on a student's medical profile
+    Sms,
+}
+
+/// Audience filter on whether students owe fees.
+///
+/// Setting it limits the audience to students.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum FeeStatus {
+    /// Students with an open invoice balance or charges not yet invoiced
+    InArrears,
+    /// Students who owe nothing
+    PaidUp,
+}
+
+/// Progress of a broadcast from draft through the delivery job.
+///
+/// Submitting a `Draft` moves it to `PendingApproval` when the school requires
+/// approval, then to `Scheduled` while its publish time is in the future, and
+/// to `Queued` once it is due. Rejecting a pending broadcast returns it to
+/// `Draft`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum BroadcastStatus {
+    Draft,
+    PendingApproval,
+    Scheduled,
+    Queued,
+    Sending,
+    Completed,
+}
+
+/// Delivery state of a single recipient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Pending,
+    Sent,
+    Failed,
+    /// The recipient has no address on file for the channel
+    Skipped,
+}
+
+/// A broadcast with its delivery counts.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Broadcast {
+    pub id: BroadcastId,
+    pub school_id: SchoolId,
+    pub channel: BroadcastChannel,
+    /// Subject template (email only)
+    pub subject: Option<String>,
+    /// Body template
+    pub body: String,
+    /// Audience: users with this role
+    pub role_id: Option<RoleId>,
+    /// Audience: users in this level
+    pub level_id: Option<LevelId>,
+    /// Audience: users in this branch
+    pub branch_id: Option<BranchId>,
+    /// Audience: students with this fee status
+    pub fee_status: Option<FeeStatus>,
+    pub status: BroadcastStatus,
+    /// When to publish, as a local time in the school's timezone
+    pub publish_at: Option<NaiveDateTime>,
+    pub created_by: Option<UserId>,
+    pub submitted_by: Option<UserId>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    /// Admin who approved or rejected the broadcast
+    pub reviewed_by: Option<UserId>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub review_note: Option<String>,
+    pub total_recipients: i64,
+    pub sent_count: i64,
+    pub failed_count: i64,
+    pub skipped_count: i64,
+    /// When the delivery job picked the broadcast up
+    pub started_at: Option<DateTime<Utc>>,
+    /// When every recipient had been attempted
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A recipient of a broadcast and their delivery status.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BroadcastRecipient {
+    pub id: BroadcastRecipientId,
+    pub broadcast_id: BroadcastId,
+    pub user_id: Option<UserId>,
+    pub first_name: String,
+    pub last_name: String,
+    /// Email address or phone number the message is sent to
+    pub address: Option<String>,
+    pub status: DeliveryStatus,
+    /// Why delivery failed or was skipped
+    pub error: Option<String>,
+    pub attempted_at: Option<DateTime<Utc>>,
+}
+
+/// DTO for queuing a broadcast.
+///
+/// The subject and body may use the placeholders `{{first_name}}`,
+/// `{{last_name}}`, `{{full_name}}`, and `{{school_name}}`. Audience filters
+/// are combined; leaving them all out targets the whole school.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_create_broadcast"))]
+pub struct CreateBroadcastDto {
+    pub channel: BroadcastChannel,
+    /// Subject template (required for email, max 200 characters)
+    #[validate(length(min = 1, max = 200), custom(function = "validate_template"))]
+    pub subject: Option<String>,
+    /// Body template (max 5000 characters)
+    #[validate(length(min = 1, max = 5000), custom(function = "validate_template"))]
+    pub body: String,
+    pub role_id: Option<RoleId>,
+    pub level_id: Option<LevelId>,
+    pub branch_id: Option<BranchId>,
+    pub fee_status: Option<FeeStatus>,
+    /// Local time in the school's timezone to publish at; omit to publish as
+    /// soon as the broadcast is submitted (and approved)
+    pub publish_at: Option<NaiveDateTime>,
+    /// Save as a draft instead of submitting
+    #[serde(default)]
+    pub draft: bool,
+}
+
+/// DTO for editing a draft broadcast. Replaces the draft's content, audience,
+/// and publish time.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_update_broadcast"))]
+pub struct UpdateBroadcastDto {
+    pub channel: BroadcastChannel,
+    /// Subject template (required for email, max 200 characters)
+    #[validate(length(min = 1, max = 200), custom(function = "validate_template"))]
+    pub subject: Option<String>,
+    /// Body template (max 5000 characters)
+    #[validate(length(min = 1, max = 5000), custom(function = "validate_template"))]
+    pub body: String,
+    pub role_id: Option<RoleId>,
+    pub level_id: Option<LevelId>,
+    pub branch_id: Option<BranchId>,
+    pub fee_status: Option<FeeStatus>,
+    /// Local time in the school's timezone to publish at
+    pub publish_at: Option<NaiveDateTime>,
+}
+
+/// DTO for approving or rejecting a broadcast.
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct ReviewBroadcastDto {
+    /// Optional note for the submitter (max 1000 characters)
+    #[validate(length(max = 1000))]
+    pub review_note: Option<String>,
+}
+
+fn validate_create_broadcast(dto: &CreateBroadcastDto) -> Result<(), ValidationError> {
+    validate_subject(dto.channel, dto.subject.as_deref())
+}
+
+fn validate_update_broadcast(dto: &UpdateBroadcastDto) -> Result<(), ValidationError> {
+    validate_subject(dto.channel, dto.subject.as_deref())
+}
+
+fn validate_subject(
+    channel: BroadcastChannel,
+    subject: Option<&str>,
+) -> Result<(), ValidationError> {
+    if channel == BroadcastChannel::Email && subject.is_none() {
+        return Err(
+            ValidationError::new("subject").with_message("Email broadcasts need a subject".into())
+        );
+    }
+    Ok(())
+}
+
+fn validate_template(template: &str) -> Result<(), ValidationError> {
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            return Err(ValidationError::new("template")
+                .with_message("Unclosed placeholder in template".into()));
+        };
+        let name = after[..end].trim();
+        if !TEMPLATE_PLACEHOLDERS.contains(&name) {
+            return Err(ValidationError::new("template")
+                .with_message(format!("Unknown placeholder: {{{{{name}}}}}").into()));
+        }
+        rest = &after[end + 2..];
+    }
+    Ok(())
+}
+
+/// Values substituted into a broadcast template for one recipient.
+#[derive(Debug, Clone)]
+pub struct TemplateContext<'a> {
+    pub first_name: &'a str,
+    pub last_name: &'a str,
+    pub school_name: &'a str,
+}
+
+impl TemplateContext<'_> {
+    /// Substitute placeholders in a template.
+    ///
+    /// Templates are validated when queued, so unknown placeholders are left
+    /// as they are rather than treated as errors.
+    #[must_use]
+    pub fn render(&self, template: &str) -> String {
+        let full_name = format!("{} {}", self.first_name, self.last_name);
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else {
+                out.push_str(&rest[start..]);
+                return out;
+            };
+            match after[..end].trim() {
+                "first_name" => out.push_str(self.first_name),
+                "last_name" => out.push_str(self.last_name),
+                "full_name" => out.push_str(&full_name),
+                "school_name" => out.push_str(self.school_name),
+                _ => out.push_str(&rest[start..start + end + 4]),
+            }
+            rest = &after[end + 2..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// A school's publishing settings.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PublishingSettings {
+    pub school_id: SchoolId,
+    /// IANA timezone name that publish times are given in
+    pub timezone: String,
+    /// Whether a broadcast or announcement must be approved by a second
+    /// reviewer before it goes out
+    pub broadcast_approval_required: bool,
+}
+
+/// DTO for updating a school's publishing settings. Omitted fields are left
+/// unchanged.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdatePublishingSettingsDto {
+    /// IANA timezone name, e.g. `Africa/Lagos`
+    #[validate(length(min = 1, max = 64))]
+    pub timezone: Option<String>,
+    pub broadcast_approval_required: Option<bool>,
+}
+
+/// Query parameters for listing broadcasts.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct BroadcastFilterParams {
+    pub status: Option<BroadcastStatus>,
+    pub channel: Option<BroadcastChannel>,
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Query parameters for listing a broadcast's recipients.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct BroadcastRecipientFilterParams {
+    pub status: Option<DeliveryStatus>,
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedBroadcastsResponse {
+    pub data: Vec<Broadcast>,
+    pub meta: PaginationMeta,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedBroadcastRecipientsResponse {
+    pub data: Vec<BroadcastRecipient>,
+    pub meta: PaginationMeta,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dto(channel: BroadcastChannel, subject: Option<&str>, body: &str) -> CreateBroadcastDto {
+        CreateBroadcastDto {
+            channel,
+            subject: subject.map(str::to_string),
+            body: body.to_string(),
+            role_id: None,
+            level_id: None,
+            branch_id: None,
+            fee_status: None,
+            publish_at: None,
+            draft: false,
+        }
+    }
+
+    #[test]
+    fn test_create_broadcast_validation() {
+        assert!(
+            dto(
+                BroadcastChannel::Email,
+                Some("Hello"),
+                "Dear {{ first_name }},"
+            )
+            .validate()
+            .is_ok()
+        );
+        assert!(
+            dto(BroadcastChannel::Sms, None, "School closes early today")
+                .validate()
+                .is_ok()
+        );
+
+        assert!(
+            dto(BroadcastChannel::Email, None, "No subject")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            dto(BroadcastChannel::Sms, None, "Hi {{nickname}}")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            dto(BroadcastChannel::Sms, None, "Hi {{first_name")
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_template_render() {
+        let context = TemplateContext {
+            first_name: "Ada",
+            last_name: "Obi",
+            school_name: "Greenfield Academy",
+        };
+
+        assert_eq!(
+            context.render("Dear {{full_name}}, welcome to {{ school_name }}."),
+            "Dear Ada Obi, welcome to Greenfield Academy."
+        );
+        assert_eq!(context.render("No placeholders"), "No placeholders");
+        assert_eq!(context.render("Hi {{first_name"), "Hi {{first_name");
+    }
+}
@@ -0,0 +1,87 @@
+//! Data change feed models.
+//!
+//! Every insert, update, and delete of a school, user, level, branch,
+//! academic session, or term is recorded as a change event. Integrations
+//! read the events in order from a cursor to keep an external copy in sync
+//! without re-fetching full lists.
+
+use crate::ids::SchoolId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Kind of record a change event refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum ChangeEntityType {
+    School,
+    User,
+    Level,
+    Branch,
+    AcademicSession,
+    Term,
+}
+
+/// What happened to the record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum ChangeOperation {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// A single change to a record.
+///
+/// Events carry identifiers only; fetch the record from its own endpoint to
+/// get its current state.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ChangeEvent {
+    pub entity_type: ChangeEntityType,
+    pub entity_id: Uuid,
+    pub operation: ChangeOperation,
+    /// Starts at 1 and increases with every change to the same record
+    pub version: i64,
+    /// School the record belongs to, if any
+    pub school_id: Option<SchoolId>,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Query parameters for reading the change feed.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct ChangeFeedParams {
+    /// Cursor from a previous response's `next_cursor`; omit to start from
+    /// the oldest change
+    pub since: Option<String>,
+    /// Maximum number of events to return (1-500, default: 100)
+    pub limit: Option<i64>,
+    /// Only changes in this school (system admins; others always get their own school)
+    pub school_id: Option<SchoolId>,
+    /// Only changes to this kind of record
+    pub entity_type: Option<ChangeEntityType>,
+}
+
+impl ChangeFeedParams {
+    /// Returns the effective limit, clamped to [1, 500].
+    ///
+    /// Defaults to 100 if not specified.
+    #[must_use]
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(100).clamp(1, 500)
+    }
+}
+
+/// A page of the change feed.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChangeFeedResponse {
+    /// Events in the order they were committed
+    pub data: Vec<ChangeEvent>,
+    /// Pass as `since` to continue; returned even when `data` is empty
+    pub next_cursor: String,
+    /// Whether more events are available right away
+    pub has_more: bool,
+}
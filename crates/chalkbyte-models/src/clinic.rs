@@ -0,0 +1,352 @@
+//! Clinic domain models and DTOs.
+//!
+//! This module contains all data structures for the school health room:
+//! student medical profiles (including emergency contacts), clinic visits
+//! with symptoms and treatment, medication administered during a visit, and
+//! the record of how a student's guardian was notified.
+
+use crate::ids::{ClinicMedicationId, ClinicVisitId, SchoolId, UserId};
+use chalkbyte_core::visibility::{hide_in, hide_own};
+use chalkbyte_core::{
+    FieldAccess, FieldVisibility, PaginationMeta, PaginationParams, RestrictedField, permissions,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// What happened to the student after a clinic visit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum ClinicVisitOutcome {
+    ReturnedToClass,
+    Rested,
+    SentHome,
+    Referred,
+}
+
+/// How a guardian was told about a clinic visit.
+///
+/// `Email` sends a message to the emergency contact on the student's medical
+/// profile; the other methods only record a notification made by staff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum GuardianNotificationMethod {
+    Email,
+    Phone,
+    Sms,
+    InPerson,
+}
+
+/// A student's medical profile.
+///
+/// Medical fields are omitted without the `medical_notes:read` permission
+/// and emergency contact fields without `contact_details:read`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct StudentMedicalProfile {
+    pub student_id: UserId,
+    pub school_id: SchoolId,
+    pub blood_group: Option<String>,
+    pub allergies: Option<String>,
+    pub chronic_conditions: Option<String>,
+    /// Medication the student takes regularly
+    pub current_medications: Option<String>,
+    pub dietary_requirements: Option<String>,
+    pub emergency_contact_name: Option<String>,
+    /// Relationship of the emergency contact to the student, e.g. "Mother"
+    pub emergency_contact_relationship: Option<String>,
+    pub emergency_contact_phone: Option<String>,
+    pub emergency_contact_email: Option<String>,
+    pub notes: Option<String>,
+    pub updated_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FieldVisibility for StudentMedicalProfile {
+    const RESTRICTED: &'static [RestrictedField] = &[
+        RestrictedField::new("blood_group", permissions::MEDICAL_NOTES_READ),
+        RestrictedField::new("allergies", permissions::MEDICAL_NOTES_READ),
+        RestrictedField::new("chronic_conditions", permissions::MEDICAL_NOTES_READ),
+        RestrictedField::new("current_medications", permissions::MEDICAL_NOTES_READ),
+        RestrictedField::new("dietary_requirements", permissions::MEDICAL_NOTES_READ),
+        RestrictedField::new("notes", permissions::MEDICAL_NOTES_READ),
+        RestrictedField::new("emergency_contact_name", permissions::CONTACT_DETAILS_READ),
+        RestrictedField::new(
+            "emergency_contact_relationship",
+            permissions::CONTACT_DETAILS_READ,
+        ),
+        RestrictedField::new("emergency_contact_phone", permissions::CONTACT_DETAILS_READ),
+        RestrictedField::new("emergency_contact_email", permissions::CONTACT_DETAILS_READ),
+    ];
+}
+
+/// DTO for creating or replacing a student's medical profile.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpsertMedicalProfileDto {
+    /// Blood group (max 5 characters), e.g. "O+"
+    #[validate(length(max = 5))]
+    pub blood_group: Option<String>,
+    pub allergies: Option<String>,
+    pub chronic_conditions: Option<String>,
+    pub current_medications: Option<String>,
+    pub dietary_requirements: Option<String>,
+    /// Emergency contact name (max 150 characters)
+    #[validate(length(max = 150))]
+    pub emergency_contact_name: Option<String>,
+    /// Emergency contact relationship (max 50 characters)
+    #[validate(length(max = 50))]
+    pub emergency_contact_relationship: Option<String>,
+    /// Emergency contact phone (max 30 characters)
+    #[validate(length(max = 30))]
+    pub emergency_contact_phone: Option<String>,
+    /// Emergency contact email, used for guardian notifications
+    #[validate(email)]
+    pub emergency_contact_email: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// A clinic visit.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ClinicVisit {
+    pub id: ClinicVisitId,
+    pub school_id: SchoolId,
+    pub student_id: UserId,
+    pub visited_at: DateTime<Utc>,
+    pub symptoms: String,
+    pub temperature_celsius: Option<f32>,
+    pub treatment: Option<String>,
+    pub outcome: ClinicVisitOutcome,
+    pub recorded_by: Option<UserId>,
+    /// When the guardian was notified; `None` if not yet notified
+    pub guardian_notified_at: Option<DateTime<Utc>>,
+    pub guardian_notified_by: Option<UserId>,
+    pub guardian_notification_method: Option<GuardianNotificationMethod>,
+    pub guardian_notification_notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FieldVisibility for ClinicVisit {
+    const RESTRICTED: &'static [RestrictedField] = &[
+        RestrictedField::new("symptoms", permissions::MEDICAL_NOTES_READ),
+        RestrictedField::new("temperature_celsius", permissions::MEDICAL_NOTES_READ),
+        RestrictedField::new("treatment", permissions::MEDICAL_NOTES_READ),
+        RestrictedField::new(
+            "guardian_notification_notes",
+            permissions::MEDICAL_NOTES_READ,
+        ),
+    ];
+}
+
+/// Medication administered during a clinic visit.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ClinicMedication {
+    pub id: ClinicMedicationId,
+    pub visit_id: ClinicVisitId,
+    pub medication_name: String,
+    pub dosage: String,
+    pub administered_at: DateTime<Utc>,
+    pub administered_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A clinic visit with its medication and the student's medical profile.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ClinicVisitDetail {
+    #[serde(flatten)]
+    pub visit: ClinicVisit,
+    pub medications: Vec<ClinicMedication>,
+    /// The student's medical profile, if one has been recorded
+    pub medical_profile: Option<StudentMedicalProfile>,
+}
+
+impl FieldVisibility for ClinicVisitDetail {
+    const RESTRICTED: &'static [RestrictedField] = &[RestrictedField::new(
+        "medications",
+        permissions::MEDICAL_NOTES_READ,
+    )];
+
+    fn hide_fields(value: &mut Value, access: &FieldAccess) {
+        hide_own::<Self>(value, access);
+        // The visit is flattened into the same object
+        hide_own::<ClinicVisit>(value, access);
+        hide_in::<Option<StudentMedicalProfile>>(value, "medical_profile", access);
+    }
+}
+
+/// Clinic visit row for listings.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ClinicVisitSummary {
+    pub id: ClinicVisitId,
+    pub student_id: UserId,
+    pub student_first_name: String,
+    pub student_last_name: String,
+    pub visited_at: DateTime<Utc>,
+    pub symptoms: String,
+    pub outcome: ClinicVisitOutcome,
+    pub guardian_notified_at: Option<DateTime<Utc>>,
+}
+
+impl FieldVisibility for ClinicVisitSummary {
+    const RESTRICTED: &'static [RestrictedField] = &[RestrictedField::new(
+        "symptoms",
+        permissions::MEDICAL_NOTES_READ,
+    )];
+}
+
+/// DTO for recording medication given to a student.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct AdministerMedicationDto {
+    /// Medication name (1-150 characters)
+    #[validate(length(min = 1, max = 150))]
+    pub medication_name: String,
+    /// Dosage given (1-100 characters), e.g. "500mg"
+    #[validate(length(min = 1, max = 100))]
+    pub dosage: String,
+    /// When it was given (defaults to now)
+    pub administered_at: Option<DateTime<Utc>>,
+}
+
+/// DTO for recording a clinic visit.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateClinicVisitDto {
+    /// Student who visited the clinic
+    pub student_id: UserId,
+    /// When the visit happened (defaults to now)
+    pub visited_at: Option<DateTime<Utc>>,
+    /// Reported symptoms (1-2000 characters)
+    #[validate(length(min = 1, max = 2000))]
+    pub symptoms: String,
+    /// Body temperature in degrees Celsius (30-45)
+    #[validate(range(min = 30.0, max = 45.0))]
+    pub temperature_celsius: Option<f32>,
+    /// Treatment given (max 2000 characters)
+    #[validate(length(max = 2000))]
+    pub treatment: Option<String>,
+    /// Outcome of the visit (defaults to returned to class)
+    pub outcome: Option<ClinicVisitOutcome>,
+    /// Medication administered during the visit
+    #[validate(nested)]
+    #[serde(default)]
+    pub medications: Vec<AdministerMedicationDto>,
+}
+
+/// DTO for updating a clinic visit.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateClinicVisitDto {
+    /// Updated symptoms (1-2000 characters)
+    #[validate(length(min = 1, max = 2000))]
+    pub symptoms: Option<String>,
+    /// Updated temperature in degrees Celsius (30-45)
+    #[validate(range(min = 30.0, max = 45.0))]
+    pub temperature_celsius: Option<f32>,
+    /// Updated treatment (max 2000 characters)
+    #[validate(length(max = 2000))]
+    pub treatment: Option<String>,
+    /// Updated outcome
+    pub outcome: Option<ClinicVisitOutcome>,
+}
+
+/// DTO for recording a guardian notification.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct NotifyGuardianDto {
+    /// How the guardian was (or should be) notified
+    pub method: GuardianNotificationMethod,
+    /// Notes about the notification (max 500 characters)
+    #[validate(length(max = 500))]
+    pub notes: Option<String>,
+}
+
+/// Query parameters for listing clinic visits.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct ClinicVisitFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by student
+    pub student_id: Option<UserId>,
+    /// Only visits on or after this date
+    pub from: Option<NaiveDate>,
+    /// Only visits on or before this date
+    pub to: Option<NaiveDate>,
+    /// Filter by outcome
+    pub outcome: Option<ClinicVisitOutcome>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing clinic visits.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedClinicVisitsResponse {
+    /// List of visits
+    pub data: Vec<ClinicVisitSummary>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+impl FieldVisibility for PaginatedClinicVisitsResponse {
+    fn hide_fields(value: &mut Value, access: &FieldAccess) {
+        hide_in::<Vec<ClinicVisitSummary>>(value, "data", access);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_clinic_visit_dto_validates_medications() {
+        let valid_dto = CreateClinicVisitDto {
+            student_id: UserId::new(),
+            visited_at: None,
+            symptoms: "Headache".to_string(),
+            temperature_celsius: Some(37.2),
+            treatment: None,
+            outcome: None,
+            medications: vec![AdministerMedicationDto {
+                medication_name: "Paracetamol".to_string(),
+                dosage: "500mg".to_string(),
+                administered_at: None,
+            }],
+        };
+        assert!(valid_dto.validate().is_ok());
+
+        let empty_dosage = CreateClinicVisitDto {
+            medications: vec![AdministerMedicationDto {
+                medication_name: "Paracetamol".to_string(),
+                dosage: String::new(),
+                administered_at: None,
+            }],
+            ..valid_dto.clone()
+        };
+        assert!(empty_dosage.validate().is_err());
+
+        let fever = CreateClinicVisitDto {
+            temperature_celsius: Some(50.0),
+            ..valid_dto
+        };
+        assert!(fever.validate().is_err());
+    }
+
+    #[test]
+    fn test_upsert_medical_profile_dto_rejects_invalid_email() {
+        let dto = UpsertMedicalProfileDto {
+            blood_group: Some("O+".to_string()),
+            allergies: None,
+            chronic_conditions: None,
+            current_medications: None,
+            dietary_requirements: None,
+            emergency_contact_name: Some("Ngozi Obi".to_string()),
+            emergency_contact_relationship: Some("Mother".to_string()),
+            emergency_contact_phone: None,
+            emergency_contact_email: Some("not-an-email".to_string()),
+            notes: None,
+        };
+        assert!(dto.validate().is_err());
+    }
+}
@@ -0,0 +1,314 @@
+//! Custom field domain models and DTOs.
+//!
+//! This module contains the per-school custom field definitions for students
+//! and other users, and the rules used to validate the values stored against
+//! them.
+//!
+//! Values live in the `custom_fields` JSONB column of `users`, keyed by each
+//! definition's `field_key`. Student and user definitions share that column,
+//! so keys are unique per school.
+
+use crate::ids::{CustomFieldId, SchoolId};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Custom field values keyed by field key.
+pub type CustomFieldValues = Map<String, Value>;
+
+/// Maximum length of a `text` custom field value.
+pub const MAX_TEXT_VALUE_LENGTH: usize = 1000;
+
+/// The kind of record a custom field applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum CustomFieldEntity {
+    /// Shown on student records
+    Student,
+    /// Shown on user records managed through the users API
+    User,
+}
+
+/// The type of value a custom field holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum CustomFieldType {
+    /// A string of up to 1000 characters
+    Text,
+    /// A JSON number
+    Number,
+    /// `true` or `false`
+    Boolean,
+    /// A `YYYY-MM-DD` date string
+    Date,
+    /// One of the definition's options
+    Select,
+    /// A list of the definition's options
+    MultiSelect,
+}
+
+impl CustomFieldType {
+    /// The type's name as used in the API.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::Date => "date",
+            Self::Select => "select",
+            Self::MultiSelect => "multi_select",
+        }
+    }
+
+    /// Whether values must be chosen from the definition's options.
+    pub fn has_options(self) -> bool {
+        matches!(self, Self::Select | Self::MultiSelect)
+    }
+}
+
+/// A custom field defined by a school.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CustomFieldDefinition {
+    pub id: CustomFieldId,
+    pub school_id: SchoolId,
+    pub entity_type: CustomFieldEntity,
+    /// Key used in `custom_fields` objects, e.g. "house"
+    pub field_key: String,
+    /// Display label, e.g. "House"
+    pub label: String,
+    pub field_type: CustomFieldType,
+    /// Whether a value must be given when a record is created
+    pub required: bool,
+    /// Allowed values for select and multi-select fields
+    pub options: Vec<String>,
+    /// Display order, lowest first
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CustomFieldDefinition {
+    /// Check a single value against this definition's type and options.
+    pub fn check_value(&self, value: &Value) -> Result<(), String> {
+        let valid = match (self.field_type, value) {
+            (CustomFieldType::Text, Value::String(s)) => s.chars().count() <= MAX_TEXT_VALUE_LENGTH,
+            (CustomFieldType::Number, Value::Number(_)) => true,
+            (CustomFieldType::Boolean, Value::Bool(_)) => true,
+            (CustomFieldType::Date, Value::String(s)) => {
+                NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok()
+            }
+            (CustomFieldType::Select, Value::String(s)) => self.options.contains(s),
+            (CustomFieldType::MultiSelect, Value::Array(items)) => items
+                .iter()
+                .all(|item| matches!(item, Value::String(s) if self.options.contains(s))),
+            _ => false,
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!(
+                "'{}' is not a valid {} value",
+                self.field_key,
+                self.field_type.as_str()
+            ))
+        }
+    }
+}
+
+/// Apply custom field changes on top of a record's current values.
+///
+/// Unknown keys and values that don't match their definition are rejected.
+/// A `null` value clears an optional field. When `creating` is true every
+/// required field must end up with a value; on update, required fields that
+/// are absent are left alone but cannot be cleared.
+pub fn apply_custom_field_values(
+    definitions: &[CustomFieldDefinition],
+    current: &CustomFieldValues,
+    changes: &CustomFieldValues,
+    creating: bool,
+) -> Result<CustomFieldValues, String> {
+    let mut values = current.clone();
+    let mut errors = Vec::new();
+
+    for (key, value) in changes {
+        let Some(definition) = definitions.iter().find(|d| &d.field_key == key) else {
+            errors.push(format!("Unknown custom field '{key}'"));
+            continue;
+        };
+
+        if value.is_null() {
+            if definition.required {
+                errors.push(format!("'{key}' is required"));
+            } else {
+                values.remove(key);
+            }
+            continue;
+        }
+
+        match definition.check_value(value) {
+            Ok(()) => {
+                values.insert(key.clone(), value.clone());
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    if creating {
+        for definition in definitions.iter().filter(|d| d.required) {
+            if !values.contains_key(&definition.field_key)
+                && !changes.contains_key(&definition.field_key)
+            {
+                errors.push(format!("'{}' is required", definition.field_key));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// DTO for defining a custom field.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateCustomFieldDto {
+    pub entity_type: CustomFieldEntity,
+    /// Key (1-50 characters: lowercase letters, digits, and underscores,
+    /// starting with a letter); cannot be changed later
+    #[validate(length(min = 1, max = 50))]
+    pub field_key: String,
+    /// Display label (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub label: String,
+    /// Value type; cannot be changed later
+    pub field_type: CustomFieldType,
+    #[serde(default)]
+    pub required: bool,
+    /// Allowed values, required for select and multi-select fields
+    #[serde(default)]
+    #[validate(length(max = 100))]
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub position: i32,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// DTO for updating a custom field definition.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateCustomFieldDto {
+    /// Display label (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub label: Option<String>,
+    pub required: Option<bool>,
+    /// Replaces the allowed values of a select or multi-select field
+    #[validate(length(max = 100))]
+    pub options: Option<Vec<String>>,
+    pub position: Option<i32>,
+}
+
+/// Query parameters for listing custom field definitions.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct CustomFieldFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Only fields for this kind of record
+    pub entity_type: Option<CustomFieldEntity>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn definition(key: &str, field_type: CustomFieldType, required: bool) -> CustomFieldDefinition {
+        CustomFieldDefinition {
+            id: CustomFieldId::new(),
+            school_id: SchoolId::new(),
+            entity_type: CustomFieldEntity::Student,
+            field_key: key.to_string(),
+            label: key.to_string(),
+            field_type,
+            required,
+            options: vec!["red".to_string(), "blue".to_string()],
+            position: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn values(value: Value) -> CustomFieldValues {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_check_value_by_type() {
+        let date = definition("joined", CustomFieldType::Date, false);
+        assert!(date.check_value(&json!("2026-09-01")).is_ok());
+        assert!(date.check_value(&json!("01/09/2026")).is_err());
+
+        let house = definition("house", CustomFieldType::Select, false);
+        assert!(house.check_value(&json!("red")).is_ok());
+        assert!(house.check_value(&json!("green")).is_err());
+
+        let clubs = definition("clubs", CustomFieldType::MultiSelect, false);
+        assert!(clubs.check_value(&json!(["red", "blue"])).is_ok());
+        assert!(clubs.check_value(&json!(["red", 1])).is_err());
+
+        let number = definition("locker", CustomFieldType::Number, false);
+        assert!(number.check_value(&json!(12)).is_ok());
+        assert!(number.check_value(&json!("12")).is_err());
+    }
+
+    #[test]
+    fn test_apply_custom_field_values() {
+        let definitions = vec![
+            definition("house", CustomFieldType::Select, true),
+            definition("nickname", CustomFieldType::Text, false),
+        ];
+
+        let created = apply_custom_field_values(
+            &definitions,
+            &Map::new(),
+            &values(json!({"house": "red", "nickname": "Ace"})),
+            true,
+        )
+        .unwrap();
+        assert_eq!(created.len(), 2);
+
+        let missing_required =
+            apply_custom_field_values(&definitions, &Map::new(), &values(json!({})), true);
+        assert!(missing_required.is_err());
+
+        let cleared = apply_custom_field_values(
+            &definitions,
+            &created,
+            &values(json!({"nickname": null})),
+            false,
+        )
+        .unwrap();
+        assert!(!cleared.contains_key("nickname"));
+        assert_eq!(cleared.get("house"), Some(&json!("red")));
+
+        assert!(
+            apply_custom_field_values(
+                &definitions,
+                &created,
+                &values(json!({"house": null})),
+                false
+            )
+            .is_err()
+        );
+        assert!(
+            apply_custom_field_values(&definitions, &created, &values(json!({"shoe": 9})), false)
+                .is_err()
+        );
+    }
+}
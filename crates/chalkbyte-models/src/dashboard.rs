@@ -0,0 +1,246 @@
+//! School dashboard models.
+//!
+//! The dashboard assembles several independent widgets in one response.
+//! Each widget is cached with its own TTL, so a widget can be missing or
+//! stale while the others are current; [`WidgetFreshness`] tells the client
+//! which is which. Mobile clients can ask for [`SchoolDashboardLite`], which
+//! keeps each widget's headline figures only.
+
+use crate::assets::AssetSummary;
+use crate::users::SchoolFullInfo;
+use crate::visitor_log::DailyVisitorReport;
+use chalkbyte_core::LiteView;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A widget on the school dashboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DashboardWidget {
+    Overview,
+    AttendanceToday,
+    Assets,
+    VisitorsToday,
+    StaffLeaveToday,
+}
+
+impl DashboardWidget {
+    pub const ALL: [DashboardWidget; 5] = [
+        DashboardWidget::Overview,
+        DashboardWidget::AttendanceToday,
+        DashboardWidget::Assets,
+        DashboardWidget::VisitorsToday,
+        DashboardWidget::StaffLeaveToday,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DashboardWidget::Overview => "overview",
+            DashboardWidget::AttendanceToday => "attendance_today",
+            DashboardWidget::Assets => "assets",
+            DashboardWidget::VisitorsToday => "visitors_today",
+            DashboardWidget::StaffLeaveToday => "staff_leave_today",
+        }
+    }
+}
+
+/// How current a widget's data is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WidgetStatus {
+    /// Within its TTL
+    Fresh,
+    /// Past its TTL; a refresh is running in the background
+    Stale,
+    /// Not computed in time; the widget is left out and will be ready on a
+    /// later request
+    Pending,
+    /// Its source failed; the widget is left out
+    Error,
+}
+
+/// Freshness of one widget in a dashboard response.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WidgetFreshness {
+    pub widget: DashboardWidget,
+    pub status: WidgetStatus,
+    /// When the returned data was computed; `None` when no data was returned
+    pub computed_at: Option<DateTime<Utc>>,
+}
+
+/// Today's attendance marks across the school.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AttendanceTodaySummary {
+    pub date: NaiveDate,
+    pub present: i64,
+    pub absent: i64,
+    pub late: i64,
+    pub excused: i64,
+    /// Share of non-excused marks that are present or late (0-100); `None`
+    /// before any marks are recorded
+    pub attendance_rate: Option<f64>,
+}
+
+/// Staff absence on the current day.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StaffLeaveTodaySummary {
+    pub date: NaiveDate,
+    /// Staff on approved leave today
+    pub on_leave: i64,
+    /// Staff on approved leave today with no substitute assigned
+    pub without_substitute: i64,
+    /// Leave requests awaiting review
+    pub pending_requests: i64,
+}
+
+/// A school's dashboard. Widgets are `None` when their data is pending or
+/// failed; see `widgets` for each one's status.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SchoolDashboard {
+    pub overview: Option<SchoolFullInfo>,
+    pub attendance_today: Option<AttendanceTodaySummary>,
+    pub assets: Option<AssetSummary>,
+    pub visitors_today: Option<DailyVisitorReport>,
+    pub staff_leave_today: Option<StaffLeaveTodaySummary>,
+    /// Freshness of every widget
+    pub widgets: Vec<WidgetFreshness>,
+}
+
+/// Lite view of the school overview widget.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SchoolOverviewLite {
+    pub name: String,
+    pub total_students: i64,
+    pub total_teachers: i64,
+}
+
+/// Lite view of the assets widget.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AssetSummaryLite {
+    /// Total number of registered assets
+    pub total: i64,
+}
+
+/// Lite view of the visitors widget.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct VisitorsTodayLite {
+    pub date: NaiveDate,
+    /// Visits that checked in on the date
+    pub total_visits: i64,
+    /// Visits from the date that have not checked out
+    pub still_on_site: i64,
+}
+
+/// Status of one widget in a lite dashboard response.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WidgetStatusLite {
+    pub widget: DashboardWidget,
+    pub status: WidgetStatus,
+}
+
+/// Lite view of a school's dashboard for mobile clients.
+///
+/// Drops the asset and visitor breakdowns, the list of visitors still on
+/// site, the school address, and when each widget was computed.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SchoolDashboardLite {
+    pub overview: Option<SchoolOverviewLite>,
+    pub attendance_today: Option<AttendanceTodaySummary>,
+    pub assets: Option<AssetSummaryLite>,
+    pub visitors_today: Option<VisitorsTodayLite>,
+    pub staff_leave_today: Option<StaffLeaveTodaySummary>,
+    /// Status of every widget
+    pub widgets: Vec<WidgetStatusLite>,
+}
+
+impl LiteView for SchoolDashboard {
+    type Lite = SchoolDashboardLite;
+
+    fn into_lite(self) -> SchoolDashboardLite {
+        SchoolDashboardLite {
+            overview: self.overview.map(|overview| SchoolOverviewLite {
+                name: overview.name,
+                total_students: overview.total_students,
+                total_teachers: overview.total_teachers,
+            }),
+            attendance_today: self.attendance_today,
+            assets: self.assets.map(|assets| AssetSummaryLite {
+                total: assets.total,
+            }),
+            visitors_today: self.visitors_today.map(|report| VisitorsTodayLite {
+                date: report.date,
+                total_visits: report.total_visits,
+                still_on_site: report.still_on_site,
+            }),
+            staff_leave_today: self.staff_leave_today,
+            widgets: self
+                .widgets
+                .into_iter()
+                .map(|freshness| WidgetStatusLite {
+                    widget: freshness.widget,
+                    status: freshness.status,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Aggregate counts for a school, computed together and cached as one.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SchoolStats {
+    pub total_students: i64,
+    pub total_teachers: i64,
+    pub total_levels: i64,
+    pub total_branches: i64,
+    /// Students per branch among students assigned to one; `None` when the
+    /// school has no branches
+    pub average_branch_size: Option<f64>,
+    pub students_without_branch: i64,
+    /// Students created within the last `recent_enrollment_days` days
+    pub recent_enrollments: i64,
+    pub recent_enrollment_days: i32,
+    pub computed_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lite_dashboard_keeps_headline_figures() {
+        let dashboard = SchoolDashboard {
+            overview: None,
+            attendance_today: None,
+            assets: Some(AssetSummary {
+                total: 12,
+                by_category: Vec::new(),
+                by_status: Vec::new(),
+                by_condition: Vec::new(),
+            }),
+            visitors_today: None,
+            staff_leave_today: None,
+            widgets: vec![WidgetFreshness {
+                widget: DashboardWidget::Assets,
+                status: WidgetStatus::Stale,
+                computed_at: Some(Utc::now()),
+            }],
+        };
+
+        let json = serde_json::to_value(dashboard.into_lite()).unwrap();
+        assert_eq!(json["assets"], serde_json::json!({ "total": 12 }));
+        assert_eq!(
+            json["widgets"],
+            serde_json::json!([{ "widget": "assets", "status": "stale" }])
+        );
+        assert!(json["overview"].is_null());
+    }
+
+    #[test]
+    fn test_widget_names_match_serialization() {
+        for widget in DashboardWidget::ALL {
+            let json = serde_json::to_string(&widget).unwrap();
+            assert_eq!(json, format!("\"{}\"", widget.as_str()));
+        }
+    }
+}
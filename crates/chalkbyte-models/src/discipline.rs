@@ -0,0 +1,239 @@
+//! Discipline domain models and DTOs.
+//!
+//! This module contains the data structures for behaviour incidents: what a
+//! student did, who reported it, how serious it was, and what the school did
+//! about it, along with when the student's guardians were notified. It also
+//! holds the per-student incident history and the school-level trend summary
+//! broken down by category, severity, and month.
+
+use crate::ids::{DisciplineIncidentId, SchoolId, UserId};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Kind of behaviour an incident records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum IncidentCategory {
+    Lateness,
+    Disruption,
+    Disrespect,
+    Bullying,
+    Fighting,
+    Truancy,
+    Dishonesty,
+    Vandalism,
+    SubstanceUse,
+    Other,
+}
+
+/// How serious an incident was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum IncidentSeverity {
+    Minor,
+    Moderate,
+    Serious,
+}
+
+/// A behaviour incident.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct DisciplineIncident {
+    pub id: DisciplineIncidentId,
+    pub school_id: SchoolId,
+    pub student_id: UserId,
+    pub reported_by: Option<UserId>,
+    pub occurred_at: DateTime<Utc>,
+    pub category: IncidentCategory,
+    pub severity: IncidentSeverity,
+    /// Where it happened, e.g. "Playground"
+    pub location: Option<String>,
+    pub description: String,
+    /// What the school did in response
+    pub actions_taken: Option<String>,
+    /// When guardians were last notified; `None` if never
+    pub guardian_notified_at: Option<DateTime<Utc>>,
+    pub guardian_notified_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Incident row for listings.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct DisciplineIncidentSummary {
+    pub id: DisciplineIncidentId,
+    pub student_id: UserId,
+    pub student_first_name: String,
+    pub student_last_name: String,
+    pub occurred_at: DateTime<Utc>,
+    pub category: IncidentCategory,
+    pub severity: IncidentSeverity,
+    pub guardian_notified_at: Option<DateTime<Utc>>,
+}
+
+/// DTO for recording an incident.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateIncidentDto {
+    /// Student the incident is about
+    pub student_id: UserId,
+    /// When it happened (defaults to now)
+    pub occurred_at: Option<DateTime<Utc>>,
+    pub category: IncidentCategory,
+    pub severity: IncidentSeverity,
+    /// Where it happened (max 100 characters)
+    #[validate(length(max = 100))]
+    pub location: Option<String>,
+    /// What happened (1-2000 characters)
+    #[validate(length(min = 1, max = 2000))]
+    pub description: String,
+    /// What the school did in response (max 2000 characters)
+    #[validate(length(max = 2000))]
+    pub actions_taken: Option<String>,
+    /// Notify the student's linked guardians straight away
+    #[serde(default)]
+    pub notify_guardians: bool,
+}
+
+/// DTO for updating an incident.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateIncidentDto {
+    pub category: Option<IncidentCategory>,
+    pub severity: Option<IncidentSeverity>,
+    /// Updated location (max 100 characters)
+    #[validate(length(max = 100))]
+    pub location: Option<String>,
+    /// Updated description (1-2000 characters)
+    #[validate(length(min = 1, max = 2000))]
+    pub description: Option<String>,
+    /// Updated actions taken (max 2000 characters)
+    #[validate(length(max = 2000))]
+    pub actions_taken: Option<String>,
+}
+
+/// Result of notifying a student's guardians about an incident.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NotifyGuardiansResponse {
+    pub incident: DisciplineIncident,
+    /// Number of guardian accounts that received an in-app notification
+    pub guardians_notified: i64,
+}
+
+/// Query parameters for listing incidents.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct IncidentFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by student
+    pub student_id: Option<UserId>,
+    /// Filter by category
+    pub category: Option<IncidentCategory>,
+    /// Filter by severity
+    pub severity: Option<IncidentSeverity>,
+    /// Only incidents on or after this date
+    pub from: Option<NaiveDate>,
+    /// Only incidents on or before this date
+    pub to: Option<NaiveDate>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing incidents.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedIncidentsResponse {
+    /// List of incidents, newest first
+    pub data: Vec<DisciplineIncidentSummary>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// A student's incident history.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StudentIncidentHistory {
+    pub student_id: UserId,
+    /// Incidents by severity, across the whole history
+    pub by_severity: Vec<SeverityCount>,
+    /// Incidents, newest first
+    pub incidents: Vec<DisciplineIncident>,
+}
+
+/// Query parameters for the school trend summary.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct IncidentTrendParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Only incidents on or after this date
+    pub from: Option<NaiveDate>,
+    /// Only incidents on or before this date
+    pub to: Option<NaiveDate>,
+}
+
+/// Number of incidents in a category.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct CategoryCount {
+    pub category: IncidentCategory,
+    pub count: i64,
+}
+
+/// Number of incidents of a severity.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct SeverityCount {
+    pub severity: IncidentSeverity,
+    pub count: i64,
+}
+
+/// Number of incidents in a calendar month.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct MonthlyIncidentCount {
+    /// First day of the month, in the school's timezone
+    pub month: NaiveDate,
+    pub count: i64,
+    pub serious: i64,
+}
+
+/// School-level incident trends.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IncidentTrends {
+    pub school_id: SchoolId,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub total: i64,
+    /// Distinct students with at least one incident
+    pub students_involved: i64,
+    /// Most frequent first
+    pub by_category: Vec<CategoryCount>,
+    /// Minor first
+    pub by_severity: Vec<SeverityCount>,
+    /// Oldest first; months without incidents are omitted
+    pub by_month: Vec<MonthlyIncidentCount>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_incident_dto_validation() {
+        let dto: CreateIncidentDto = serde_json::from_value(serde_json::json!({
+            "student_id": UserId::new(),
+            "category": "substance_use",
+            "severity": "serious",
+            "description": "Found with cigarettes behind the hall",
+        }))
+        .unwrap();
+        assert_eq!(dto.category, IncidentCategory::SubstanceUse);
+        assert!(!dto.notify_guardians);
+        assert!(dto.validate().is_ok());
+
+        let empty = CreateIncidentDto {
+            description: String::new(),
+            ..dto
+        };
+        assert!(empty.validate().is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! Email branding models and DTOs.
+//!
+//! Emails are rendered from templates with the sending school's branding:
+//! its sender name, logo, and primary color. Schools without branding use
+//! the Chalkbyte defaults.
+
+use crate::ids::SchoolId;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+/// A school's email branding. Unset fields fall back to the defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SchoolEmailBranding {
+    pub school_id: SchoolId,
+    /// Name emails are sent from, instead of the configured sender name
+    pub sender_name: Option<String>,
+    /// Logo shown at the top of emails
+    pub logo_url: Option<String>,
+    /// Header and button color as `#RRGGBB`
+    pub primary_color: Option<String>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// DTO for setting a school's email branding. Replaces the current
+/// branding; omitted fields go back to the defaults.
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct UpdateEmailBrandingDto {
+    #[validate(length(min = 1, max = 100))]
+    #[schema(example = "Greenfield Academy")]
+    pub sender_name: Option<String>,
+    #[validate(url, length(max = 2048), custom(function = "validate_logo_scheme"))]
+    #[schema(example = "https://cdn.greenfield.example/logo.png")]
+    pub logo_url: Option<String>,
+    #[validate(custom(function = "validate_color"))]
+    #[schema(example = "#0F766E")]
+    pub primary_color: Option<String>,
+}
+
+fn validate_logo_scheme(url: &str) -> Result<(), ValidationError> {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        Ok(())
+    } else {
+        Err(ValidationError::new("url").with_message("Logo URLs must use http or https".into()))
+    }
+}
+
+fn validate_color(color: &str) -> Result<(), ValidationError> {
+    let valid = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("color").with_message("Colors must be written as #RRGGBB".into()))
+    }
+}
+
+/// An email that can be previewed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailTemplateKind {
+    PasswordReset,
+    PasswordResetConfirmation,
+    Announcement,
+    GuardianInvitation,
+    ClinicVisit,
+    RecoveryCodeUsed,
+}
+
+/// Request to preview an email with sample content.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct EmailPreviewRequest {
+    pub template: EmailTemplateKind,
+    /// Unsaved branding to preview instead of the school's current branding
+    #[validate(nested)]
+    pub branding: Option<UpdateEmailBrandingDto>,
+}
+
+/// An email rendered with sample content.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EmailPreview {
+    pub template: EmailTemplateKind,
+    pub sender_name: String,
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dto(logo_url: Option<&str>, primary_color: Option<&str>) -> UpdateEmailBrandingDto {
+        UpdateEmailBrandingDto {
+            sender_name: None,
+            logo_url: logo_url.map(str::to_string),
+            primary_color: primary_color.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_branding_validation() {
+        assert!(dto(None, None).validate().is_ok());
+        assert!(
+            dto(Some("https://cdn.example.com/logo.png"), Some("#0f766E"))
+                .validate()
+                .is_ok()
+        );
+
+        assert!(dto(Some("javascript:alert(1)"), None).validate().is_err());
+        assert!(
+            dto(Some("ftp://example.com/logo.png"), None)
+                .validate()
+                .is_err()
+        );
+        assert!(dto(None, Some("0F766E")).validate().is_err());
+        assert!(dto(None, Some("#0F766")).validate().is_err());
+        assert!(dto(None, Some("red; x")).validate().is_err());
+    }
+
+    #[test]
+    fn test_template_kind_serialization() {
+        let kind: EmailTemplateKind = serde_json::from_str(r#""recovery_code_used""#).unwrap();
+        assert_eq!(kind, EmailTemplateKind::RecoveryCodeUsed);
+        assert_eq!(
+            serde_json::to_string(&EmailTemplateKind::PasswordReset).unwrap(),
+            r#""password_reset""#
+        );
+    }
+}
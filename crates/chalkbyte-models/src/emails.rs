@@ -0,0 +1,141 @@
+//! Outbound email queue models and DTOs.
+//!
+//! Emails are rendered when they are sent and written to an outbox. A
+//! background job delivers them over SMTP, retrying failures with
+//! exponential backoff. Addresses that bounce permanently are added to a
+//! suppression list and are not emailed again until removed.
+
+use crate::ids::{OutboundEmailId, SchoolId};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Delivery state of a queued email.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum OutboundEmailStatus {
+    /// Waiting for its first attempt or a retry
+    Pending,
+    /// Accepted by the SMTP server
+    Sent,
+    /// Every attempt failed, or the address bounced; no more retries
+    Failed,
+    /// Not sent because the address is on the suppression list
+    Suppressed,
+}
+
+/// Why an address is on the suppression list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum SuppressionReason {
+    /// The SMTP server rejected the address permanently
+    Bounce,
+    /// The recipient reported the email as spam
+    Complaint,
+    /// Added by an admin
+    Manual,
+}
+
+/// An email in the outbound queue. Bodies are not returned, since they can
+/// hold reset and invitation links.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct OutboundEmail {
+    pub id: OutboundEmailId,
+    /// School the email was sent for; `None` for platform emails
+    pub school_id: Option<SchoolId>,
+    pub to_email: String,
+    pub from_name: String,
+    pub subject: String,
+    pub status: OutboundEmailStatus,
+    pub attempts: i32,
+    /// When the next attempt is due (pending emails only)
+    pub next_attempt_at: DateTime<Utc>,
+    /// Why the last attempt failed
+    pub last_error: Option<String>,
+    /// Request that queued the email, for tracing
+    pub request_id: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query parameters for listing queued emails.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct OutboundEmailFilterParams {
+    pub status: Option<OutboundEmailStatus>,
+    /// Recipient address, matched case-insensitively
+    pub to_email: Option<String>,
+    pub school_id: Option<SchoolId>,
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedOutboundEmailsResponse {
+    pub data: Vec<OutboundEmail>,
+    pub meta: PaginationMeta,
+}
+
+/// An address that is not emailed.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct EmailSuppression {
+    /// Lowercased address
+    pub email: String,
+    pub reason: SuppressionReason,
+    /// SMTP response or admin note
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO for suppressing an address by hand.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateEmailSuppressionDto {
+    #[validate(email)]
+    #[schema(example = "parent@example.com")]
+    pub email: String,
+    /// Defaults to `manual`
+    pub reason: Option<SuppressionReason>,
+    #[validate(length(max = 500))]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedEmailSuppressionsResponse {
+    pub data: Vec<EmailSuppression>,
+    pub meta: PaginationMeta,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_serialization() {
+        assert_eq!(
+            serde_json::to_string(&OutboundEmailStatus::Suppressed).unwrap(),
+            r#""suppressed""#
+        );
+        let reason: SuppressionReason = serde_json::from_str(r#""bounce""#).unwrap();
+        assert_eq!(reason, SuppressionReason::Bounce);
+    }
+
+    #[test]
+    fn test_suppression_validation() {
+        let dto = CreateEmailSuppressionDto {
+            email: "not-an-email".to_string(),
+            reason: None,
+            detail: None,
+        };
+        assert!(dto.validate().is_err());
+
+        let dto = CreateEmailSuppressionDto {
+            email: "parent@example.com".to_string(),
+            ..dto
+        };
+        assert!(dto.validate().is_ok());
+    }
+}
@@ -0,0 +1,230 @@
+//! Exam seating plan models and DTOs.
+//!
+//! A seating plan allocates the students sitting an exam (an assessment of
+//! kind `exam`) to numbered seats in the rooms the exam is held in. Rooms are
+//! filled in the order given, seat 1 first. Students with special needs are
+//! placed in the seats reserved for them before anyone else is seated, and
+//! the rest can be ordered so that neighbours come from different branches.
+//!
+//! Generating a plan again replaces the previous one.
+//!
+//! An exam's sitting records when it starts and how long it runs, and the
+//! teachers invigilating it, each optionally watching one room of the
+//! seating plan. A teacher cannot invigilate two sittings that overlap, nor
+//! a sitting on a day they are on approved leave.
+
+use crate::ids::{AssessmentId, BranchId, ExamRoomId, ExamSeatingPlanId, ExamSittingId, UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+fn default_alternate_branches() -> bool {
+    true
+}
+
+/// A room the exam is held in.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_special_needs_seats"))]
+pub struct ExamRoomDto {
+    /// Room name, unique within the plan (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Number of seats, numbered from 1 (1-1000)
+    #[validate(range(min = 1, max = 1000))]
+    pub capacity: i32,
+    /// Seat numbers reserved for students with special needs
+    #[serde(default)]
+    pub special_needs_seats: Vec<i32>,
+}
+
+fn validate_special_needs_seats(room: &ExamRoomDto) -> Result<(), ValidationError> {
+    let mut seats = room.special_needs_seats.clone();
+    seats.sort_unstable();
+    seats.dedup();
+    if seats.len() != room.special_needs_seats.len() {
+        return Err(ValidationError::new("duplicate_special_needs_seat"));
+    }
+    if seats.iter().any(|seat| *seat < 1 || *seat > room.capacity) {
+        return Err(ValidationError::new("special_needs_seat_out_of_range"));
+    }
+    Ok(())
+}
+
+/// DTO for generating an exam seating plan.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct GenerateSeatingPlanDto {
+    /// Rooms in the order they are filled (1-100 rooms)
+    #[validate(length(min = 1, max = 100), nested)]
+    pub rooms: Vec<ExamRoomDto>,
+    /// Seat students so that neighbours come from different branches where
+    /// possible (default true)
+    #[serde(default = "default_alternate_branches")]
+    pub alternate_branches: bool,
+    /// Students who must sit in special-needs seats
+    #[serde(default)]
+    pub special_needs_student_ids: Vec<UserId>,
+    /// Branches of the exam's level to seat; empty seats the whole level
+    #[serde(default)]
+    pub branch_ids: Vec<BranchId>,
+}
+
+/// A student's seat in an exam room.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ExamSeat {
+    #[serde(skip)]
+    pub room_id: ExamRoomId,
+    pub seat_number: i32,
+    pub student_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub branch_id: Option<BranchId>,
+    pub branch_name: Option<String>,
+    /// Whether the student was seated as a special-needs student
+    pub special_needs: bool,
+}
+
+/// A room in a seating plan with its occupied seats, ordered by seat number.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ExamRoom {
+    pub id: ExamRoomId,
+    pub name: String,
+    pub capacity: i32,
+    pub special_needs_seats: Vec<i32>,
+    #[sqlx(skip)]
+    pub seats: Vec<ExamSeat>,
+}
+
+/// An exam's seating plan.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SeatingPlan {
+    pub id: ExamSeatingPlanId,
+    /// The exam this plan is for
+    pub assessment_id: AssessmentId,
+    pub alternate_branches: bool,
+    /// Branches seated; `None` when the whole level is seated
+    pub branch_ids: Option<Vec<BranchId>>,
+    pub generated_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    /// Rooms in the order they were filled
+    #[sqlx(skip)]
+    pub rooms: Vec<ExamRoom>,
+}
+
+/// DTO for scheduling an exam's sitting.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct ScheduleSittingDto {
+    /// When the exam starts
+    pub starts_at: DateTime<Utc>,
+    /// Length of the exam in minutes (1-720)
+    #[validate(range(min = 1, max = 720))]
+    pub duration_minutes: i32,
+}
+
+/// A teacher invigilating an exam sitting.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ExamInvigilator {
+    pub teacher_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    /// Seating plan room they watch; `None` for a roaming invigilator
+    pub room_name: Option<String>,
+    pub assigned_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// When an exam is sat, with its invigilators.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ExamSitting {
+    pub id: ExamSittingId,
+    /// The exam being sat
+    pub assessment_id: AssessmentId,
+    pub starts_at: DateTime<Utc>,
+    pub duration_minutes: i32,
+    /// When the exam ends
+    pub ends_at: DateTime<Utc>,
+    pub scheduled_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Invigilators ordered by name
+    #[sqlx(skip)]
+    pub invigilators: Vec<ExamInvigilator>,
+}
+
+/// DTO for assigning an invigilator to an exam sitting.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct AssignInvigilatorDto {
+    /// Teacher in the exam's school
+    pub teacher_id: UserId,
+    /// Seating plan room to watch; omit for a roaming invigilator
+    #[validate(length(min = 1, max = 100))]
+    pub room_name: Option<String>,
+}
+
+/// File format of a printed room list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SeatingChartFormat {
+    /// A CSV of the room's seats
+    #[default]
+    Csv,
+    /// A printable PDF with the sitting time and invigilators
+    Pdf,
+}
+
+/// Query parameters for printing a room list.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SeatingChartParams {
+    /// `csv` (default) or `pdf`
+    #[serde(default)]
+    pub format: SeatingChartFormat,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(capacity: i32, special_needs_seats: Vec<i32>) -> ExamRoomDto {
+        ExamRoomDto {
+            name: "Hall A".to_string(),
+            capacity,
+            special_needs_seats,
+        }
+    }
+
+    #[test]
+    fn test_special_needs_seats_must_be_unique_and_in_room() {
+        assert!(room(10, vec![1, 10]).validate().is_ok());
+        assert!(room(10, vec![2, 2]).validate().is_err());
+        assert!(room(10, vec![0]).validate().is_err());
+        assert!(room(10, vec![11]).validate().is_err());
+    }
+
+    #[test]
+    fn test_generate_dto_defaults() {
+        let dto: GenerateSeatingPlanDto =
+            serde_json::from_str(r#"{"rooms":[{"name":"Hall A","capacity":30}]}"#).unwrap();
+
+        assert!(dto.alternate_branches);
+        assert!(dto.special_needs_student_ids.is_empty());
+        assert!(dto.branch_ids.is_empty());
+        assert!(dto.rooms[0].special_needs_seats.is_empty());
+        assert!(dto.validate().is_ok());
+    }
+
+    #[test]
+    fn test_schedule_sitting_dto_duration() {
+        let dto: ScheduleSittingDto =
+            serde_json::from_str(r#"{"starts_at":"2026-11-02T09:00:00Z","duration_minutes":120}"#)
+                .unwrap();
+        assert!(dto.validate().is_ok());
+
+        let too_long = ScheduleSittingDto {
+            duration_minutes: 721,
+            ..dto
+        };
+        assert!(too_long.validate().is_err());
+    }
+}
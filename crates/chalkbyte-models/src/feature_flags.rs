@@ -0,0 +1,128 @@
+//! Feature flag models and DTOs.
+//!
+//! A feature flag switches a feature on or off at runtime. Each flag has a
+//! default that applies to every school, and system admins can override it
+//! for individual schools, for example to try a new module with a few
+//! schools before turning it on everywhere.
+
+use std::collections::BTreeMap;
+
+use crate::ids::SchoolId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// A feature flag and its default.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct FeatureFlag {
+    /// Lowercase letters, digits, and underscores
+    #[schema(example = "attendance")]
+    pub key: String,
+    pub description: Option<String>,
+    /// Whether the feature is on for schools without an override
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A school's override of a flag's default.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SchoolFeatureFlag {
+    #[schema(example = "attendance")]
+    pub flag_key: String,
+    pub school_id: SchoolId,
+    pub enabled: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A feature flag with every school override.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FeatureFlagWithOverrides {
+    #[serde(flatten)]
+    pub flag: FeatureFlag,
+    pub overrides: Vec<SchoolFeatureFlag>,
+}
+
+/// DTO for creating a feature flag.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateFeatureFlagDto {
+    /// Lowercase letters, digits, and underscores, starting with a letter
+    /// (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    #[schema(example = "attendance")]
+    pub key: String,
+    /// What the flag controls (max 500 characters)
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    /// Default for schools without an override (default: off)
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// DTO for changing a feature flag's default or description.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateFeatureFlagDto {
+    /// What the flag controls (max 500 characters)
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    /// Default for schools without an override
+    pub enabled: Option<bool>,
+}
+
+/// DTO for overriding a flag for one school.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct SetSchoolFeatureFlagDto {
+    pub enabled: bool,
+}
+
+/// The flags in effect for the caller, after school overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct EffectiveFeatureFlags {
+    /// Every flag key and whether it is on
+    #[schema(example = json!({"attendance": true}))]
+    pub flags: BTreeMap<String, bool>,
+}
+
+impl EffectiveFeatureFlags {
+    /// Whether the flag is on; unknown flags are off.
+    #[must_use]
+    pub fn is_enabled(&self, key: &str) -> bool {
+        self.flags.get(key).copied().unwrap_or(false)
+    }
+}
+
+/// Checks that a flag key uses lowercase letters, digits, and underscores
+/// and starts with a letter, matching the database constraint.
+#[must_use]
+pub fn is_valid_flag_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_key_format() {
+        assert!(is_valid_flag_key("attendance"));
+        assert!(is_valid_flag_key("new_gradebook_v2"));
+        assert!(!is_valid_flag_key(""));
+        assert!(!is_valid_flag_key("2fa"));
+        assert!(!is_valid_flag_key("Attendance"));
+        assert!(!is_valid_flag_key("new-gradebook"));
+    }
+
+    #[test]
+    fn test_unknown_flags_are_off() {
+        let flags = EffectiveFeatureFlags {
+            flags: BTreeMap::from([("attendance".to_string(), true)]),
+        };
+
+        assert!(flags.is_enabled("attendance"));
+        assert!(!flags.is_enabled("library"));
+    }
+}
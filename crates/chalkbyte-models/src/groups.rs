@@ -0,0 +1,268 @@
+//! School group domain models and DTOs.
+//!
+//! A school group (a district, trust, or chain) is an organization layer
+//! above schools. Each school belongs to at most one group. Group admins are
+//! assigned to a single group and act across its member schools: they view
+//! aggregate reports and manage the users of those schools, but have no
+//! school of their own.
+
+use crate::ids::{RoleId, SchoolGroupId, SchoolId, UserId};
+use crate::value_types::Email;
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// A school group.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SchoolGroup {
+    pub id: SchoolGroupId,
+    pub name: String,
+    pub description: Option<String>,
+    /// Number of member schools
+    pub school_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for creating a school group.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateSchoolGroupDto {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+    #[validate(length(max = 2000))]
+    pub description: Option<String>,
+}
+
+/// DTO for updating a school group. Fields left out are unchanged.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateSchoolGroupDto {
+    #[validate(length(min = 1, max = 200))]
+    pub name: Option<String>,
+    #[validate(length(max = 2000))]
+    pub description: Option<String>,
+}
+
+/// DTO for adding a school to a group.
+///
+/// A school that already belongs to another group is moved to this one.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddGroupSchoolDto {
+    pub school_id: SchoolId,
+}
+
+/// DTO for making a user the admin of a group.
+///
+/// The user must not belong to a school. They are given the Group Admin
+/// role, replacing any group they administered before.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AssignGroupAdminDto {
+    pub user_id: UserId,
+}
+
+/// A member school of a group.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct GroupSchool {
+    pub id: SchoolId,
+    pub name: String,
+    pub address: Option<String>,
+    pub student_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An admin of a group.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct GroupAdmin {
+    pub id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: Email,
+}
+
+/// Query parameters for listing users across a group's schools.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct GroupUserFilterParams {
+    /// Only users of this member school
+    pub school_id: Option<SchoolId>,
+    /// Only users with this role
+    pub role_id: Option<RoleId>,
+    /// Partial match on first name, last name, or email
+    pub search: Option<String>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// A user of one of a group's member schools.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct GroupUser {
+    pub id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: Email,
+    pub school_id: SchoolId,
+    pub school_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Paginated response containing group users.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedGroupUsersResponse {
+    /// List of users
+    pub data: Vec<GroupUser>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// Query parameters for a group report.
+#[derive(Debug, Clone, Default, Deserialize, ToSchema, IntoParams)]
+pub struct GroupReportParams {
+    /// First day of the report window (inclusive); defaults to 30 days
+    /// before `to`
+    pub from: Option<NaiveDate>,
+    /// Last day of the report window (inclusive); defaults to today
+    pub to: Option<NaiveDate>,
+}
+
+impl GroupReportParams {
+    /// Default length of the report window, in days
+    pub const DEFAULT_WINDOW_DAYS: i64 = 30;
+
+    /// The report window, filling in defaults relative to `today`.
+    pub fn window(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let to = self.to.unwrap_or(today);
+        let from = self
+            .from
+            .unwrap_or(to - Duration::days(Self::DEFAULT_WINDOW_DAYS - 1));
+        (from, to)
+    }
+}
+
+/// Report figures for one member school.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct GroupSchoolReport {
+    pub school_id: SchoolId,
+    pub school_name: String,
+    pub students: i64,
+    pub teachers: i64,
+    pub attendance_present: i64,
+    pub attendance_absent: i64,
+    pub attendance_late: i64,
+    pub attendance_excused: i64,
+    /// Share of non-excused marks in the window that are present or late
+    /// (0-100); `None` when no marks were recorded
+    #[sqlx(skip)]
+    pub attendance_rate: Option<f64>,
+    /// Approved staff leave requests overlapping the window
+    pub staff_leave_requests: i64,
+}
+
+/// Aggregate report across a group's member schools.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GroupReport {
+    pub group_id: SchoolGroupId,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub school_count: i64,
+    pub students: i64,
+    pub teachers: i64,
+    /// Share of non-excused marks across all schools that are present or
+    /// late (0-100); `None` when no marks were recorded
+    pub attendance_rate: Option<f64>,
+    pub staff_leave_requests: i64,
+    /// Figures for each member school, by name
+    pub schools: Vec<GroupSchoolReport>,
+}
+
+/// Share of non-excused attendance marks that are present or late, as a
+/// percentage, or `None` without any such marks.
+pub fn attendance_rate(present: i64, absent: i64, late: i64) -> Option<f64> {
+    let counted = present + absent + late;
+    (counted > 0).then(|| (present + late) as f64 * 100.0 / counted as f64)
+}
+
+impl GroupReport {
+    /// Total the per-school figures of a group.
+    pub fn new(
+        group_id: SchoolGroupId,
+        from: NaiveDate,
+        to: NaiveDate,
+        mut schools: Vec<GroupSchoolReport>,
+    ) -> Self {
+        for school in &mut schools {
+            school.attendance_rate = attendance_rate(
+                school.attendance_present,
+                school.attendance_absent,
+                school.attendance_late,
+            );
+        }
+
+        let sum = |f: fn(&GroupSchoolReport) -> i64| schools.iter().map(f).sum::<i64>();
+        let attendance_rate = attendance_rate(
+            sum(|s| s.attendance_present),
+            sum(|s| s.attendance_absent),
+            sum(|s| s.attendance_late),
+        );
+
+        Self {
+            group_id,
+            from,
+            to,
+            school_count: schools.len() as i64,
+            students: sum(|s| s.students),
+            teachers: sum(|s| s.teachers),
+            attendance_rate,
+            staff_leave_requests: sum(|s| s.staff_leave_requests),
+            schools,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn school(present: i64, absent: i64, late: i64) -> GroupSchoolReport {
+        GroupSchoolReport {
+            school_id: SchoolId::new(),
+            school_name: "School".to_string(),
+            students: 10,
+            teachers: 2,
+            attendance_present: present,
+            attendance_absent: absent,
+            attendance_late: late,
+            attendance_excused: 1,
+            attendance_rate: None,
+            staff_leave_requests: 1,
+        }
+    }
+
+    #[test]
+    fn test_group_report_totals_schools() {
+        let from = NaiveDate::from_ymd_opt(2026, 9, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 9, 30).unwrap();
+        let report = GroupReport::new(
+            SchoolGroupId::new(),
+            from,
+            to,
+            vec![school(6, 2, 0), school(0, 0, 0), school(1, 0, 1)],
+        );
+
+        assert_eq!(report.school_count, 3);
+        assert_eq!((report.students, report.teachers), (30, 6));
+        assert_eq!(report.staff_leave_requests, 3);
+        assert_eq!(report.attendance_rate, Some(80.0));
+        assert_eq!(report.schools[0].attendance_rate, Some(75.0));
+        assert_eq!(report.schools[1].attendance_rate, None);
+    }
+
+    #[test]
+    fn test_report_window_defaults() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let (from, to) = GroupReportParams::default().window(today);
+        assert_eq!(to, today);
+        assert_eq!(from, NaiveDate::from_ymd_opt(2026, 9, 17).unwrap());
+    }
+}
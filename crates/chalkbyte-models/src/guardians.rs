@@ -0,0 +1,104 @@
+//! Guardian domain models and DTOs.
+//!
+//! Guardians are users with the Guardian role, linked to the students they
+//! look after. Rather than entering them by hand, a school starts a guardian
+//! account run: a background job reads every student's emergency contact,
+//! merges contacts that share an email or phone number, creates one account
+//! per guardian (optionally emailing an invitation to set a password), links
+//! it to the right students, and records contacts it could not handle as
+//! conflicts for manual review.
+
+use crate::ids::{GuardianAccountConflictId, GuardianAccountRunId, SchoolId, UserId};
+use chalkbyte_core::PaginationMeta;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+/// Progress of a guardian account run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum GuardianAccountRunStatus {
+    /// Waiting for the background job
+    Pending,
+    /// Being processed
+    Running,
+    /// Finished; see the counters and conflicts
+    Completed,
+    /// Stopped by an unexpected error
+    Failed,
+}
+
+/// One request to create guardian accounts from a school's emergency contacts.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct GuardianAccountRun {
+    pub id: GuardianAccountRunId,
+    pub school_id: SchoolId,
+    pub status: GuardianAccountRunStatus,
+    /// Whether new guardians are emailed a link to set their password
+    pub send_invitations: bool,
+    pub requested_by: Option<UserId>,
+    /// Students with an emergency phone number or email
+    pub contacts_scanned: i32,
+    /// Guardian accounts created by this run
+    pub accounts_created: i32,
+    /// Existing guardian accounts the contacts matched
+    pub accounts_reused: i32,
+    /// New links between a guardian and a student
+    pub links_created: i32,
+    pub invitations_sent: i32,
+    /// Contacts left for manual review
+    pub conflicts: i32,
+    /// Why the run failed
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// DTO for starting a guardian account run.
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct StartGuardianAccountRunDto {
+    /// Email each new guardian a link to set their password
+    #[serde(default)]
+    pub send_invitations: bool,
+}
+
+/// Why a contact could not be turned into a guardian account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum GuardianConflictKind {
+    /// The contact has a phone number but no email to sign in with
+    MissingEmail,
+    /// Contacts sharing a phone number give different emails
+    ConflictingEmails,
+    /// The email belongs to a user who is not a guardian of this school
+    EmailInUse,
+}
+
+/// A contact left for manual review.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct GuardianAccountConflict {
+    pub id: GuardianAccountConflictId,
+    pub run_id: GuardianAccountRunId,
+    pub kind: GuardianConflictKind,
+    pub contact_name: Option<String>,
+    /// Emails given for the contact, lowercased
+    pub emails: Vec<String>,
+    /// Phone numbers given for the contact, digits only
+    pub phones: Vec<String>,
+    /// Students listing the contact
+    pub student_ids: Vec<UserId>,
+    /// The user already holding the email, for `email_in_use`
+    pub existing_user_id: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Paginated guardian account runs, newest first.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedGuardianAccountRunsResponse {
+    pub data: Vec<GuardianAccountRun>,
+    pub meta: PaginationMeta,
+}
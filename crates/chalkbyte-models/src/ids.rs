@@ -232,6 +232,306 @@ define_id!(
     TermId
 );
 
+define_id!(
+    /// Strongly-typed ID for StaffLeaveRequest entities.
+    StaffLeaveId
+);
+
+define_id!(
+    /// Strongly-typed ID for Asset entities.
+    AssetId
+);
+
+define_id!(
+    /// Strongly-typed ID for Hostel entities.
+    HostelId
+);
+
+define_id!(
+    /// Strongly-typed ID for HostelRoom entities.
+    HostelRoomId
+);
+
+define_id!(
+    /// Strongly-typed ID for RoomAllocation entities.
+    RoomAllocationId
+);
+
+define_id!(
+    /// Strongly-typed ID for BoardingFeeLine entities.
+    BoardingFeeLineId
+);
+
+define_id!(
+    /// Strongly-typed ID for Vehicle entities.
+    VehicleId
+);
+
+define_id!(
+    /// Strongly-typed ID for TransportRoute entities.
+    TransportRouteId
+);
+
+define_id!(
+    /// Strongly-typed ID for RouteStop entities.
+    RouteStopId
+);
+
+define_id!(
+    /// Strongly-typed ID for RouteAssignment entities.
+    RouteAssignmentId
+);
+
+define_id!(
+    /// Strongly-typed ID for TransportFeeCharge entities.
+    TransportFeeChargeId
+);
+
+define_id!(
+    /// Strongly-typed ID for VisitorLog entities.
+    VisitorLogId
+);
+
+define_id!(
+    /// Strongly-typed ID for VisitorKioskKey entities.
+    VisitorKioskKeyId
+);
+
+define_id!(
+    /// Strongly-typed ID for ClinicVisit entities.
+    ClinicVisitId
+);
+
+define_id!(
+    /// Strongly-typed ID for ClinicMedication entities.
+    ClinicMedicationId
+);
+
+define_id!(
+    /// Strongly-typed ID for LibraryBook entities.
+    LibraryBookId
+);
+
+define_id!(
+    /// Strongly-typed ID for LibraryCopy entities.
+    LibraryCopyId
+);
+
+define_id!(
+    /// Strongly-typed ID for LibraryLoan entities.
+    LibraryLoanId
+);
+
+define_id!(
+    /// Strongly-typed ID for LibraryFineCharge entities.
+    LibraryFineChargeId
+);
+
+define_id!(
+    /// Strongly-typed ID for Alumnus entities.
+    AlumnusId
+);
+
+define_id!(
+    /// Strongly-typed ID for CustomFieldDefinition entities.
+    CustomFieldId
+);
+
+define_id!(
+    /// Strongly-typed ID for SavedView entities.
+    SavedViewId
+);
+
+define_id!(
+    /// Strongly-typed ID for TrashItem entities.
+    TrashItemId
+);
+
+define_id!(
+    /// Strongly-typed ID for AttendanceRecord entities.
+    AttendanceRecordId
+);
+
+define_id!(
+    /// Strongly-typed ID for KioskDevice entities.
+    KioskDeviceId
+);
+
+define_id!(
+    /// Strongly-typed ID for Subject entities.
+    SubjectId
+);
+
+define_id!(
+    /// Strongly-typed ID for Assessment entities.
+    AssessmentId
+);
+
+define_id!(
+    /// Strongly-typed ID for AssessmentScore entities.
+    AssessmentScoreId
+);
+
+define_id!(
+    /// Strongly-typed ID for Broadcast entities.
+    BroadcastId
+);
+
+define_id!(
+    /// Strongly-typed ID for BroadcastRecipient entities.
+    BroadcastRecipientId
+);
+
+define_id!(
+    /// Strongly-typed ID for WebhookEndpoint entities.
+    WebhookEndpointId
+);
+
+define_id!(
+    /// Strongly-typed ID for WebhookDelivery entities.
+    WebhookDeliveryId
+);
+
+define_id!(
+    /// Strongly-typed ID for BranchTeacher assignments.
+    BranchTeacherId
+);
+
+define_id!(
+    /// Strongly-typed ID for ExamSeatingPlan entities.
+    ExamSeatingPlanId
+);
+
+define_id!(
+    /// Strongly-typed ID for ExamRoom entities.
+    ExamRoomId
+);
+
+define_id!(
+    /// Strongly-typed ID for ExamSitting entities.
+    ExamSittingId
+);
+
+define_id!(
+    /// Strongly-typed ID for StudentPromotion entities.
+    StudentPromotionId
+);
+
+define_id!(
+    /// Strongly-typed ID for TermResult entities.
+    TermResultId
+);
+
+define_id!(
+    /// Strongly-typed ID for TermResultTransition entities.
+    TermResultTransitionId
+);
+
+define_id!(
+    /// Strongly-typed ID for Announcement entities.
+    AnnouncementId
+);
+
+define_id!(
+    /// Strongly-typed ID for Notification entities.
+    NotificationId
+);
+
+define_id!(
+    /// Strongly-typed ID for GradingScheme entities.
+    GradingSchemeId
+);
+
+define_id!(
+    /// Strongly-typed ID for SchoolGroup entities.
+    SchoolGroupId
+);
+
+define_id!(
+    /// Strongly-typed ID for PurgeRun entities.
+    PurgeRunId
+);
+
+define_id!(
+    /// Strongly-typed ID for WebauthnCredential entities.
+    WebauthnCredentialId
+);
+
+define_id!(
+    /// Strongly-typed ID for OidcProvider entities.
+    OidcProviderId
+);
+
+define_id!(
+    /// Strongly-typed ID for GuardianAccountRun entities.
+    GuardianAccountRunId
+);
+
+define_id!(
+    /// Strongly-typed ID for GuardianAccountConflict entities.
+    GuardianAccountConflictId
+);
+
+define_id!(
+    /// Strongly-typed ID for SessionReport entities.
+    SessionReportId
+);
+
+define_id!(
+    /// Strongly-typed ID for OutboundEmail entities.
+    OutboundEmailId
+);
+
+define_id!(
+    /// Strongly-typed ID for StoredFile entities.
+    StoredFileId
+);
+
+define_id!(
+    /// Strongly-typed ID for FeeStructure entities.
+    FeeStructureId
+);
+
+define_id!(
+    /// Strongly-typed ID for Invoice entities.
+    InvoiceId
+);
+
+define_id!(
+    /// Strongly-typed ID for FeePayment entities.
+    FeePaymentId
+);
+
+define_id!(
+    /// Strongly-typed ID for DisciplineIncident entities.
+    DisciplineIncidentId
+);
+
+define_id!(
+    /// Strongly-typed ID for Conversation entities.
+    ConversationId
+);
+
+define_id!(
+    /// Strongly-typed ID for ConversationMessage entities.
+    MessageId
+);
+
+define_id!(
+    /// Strongly-typed ID for SyncConnection entities.
+    SyncConnectionId
+);
+
+define_id!(
+    /// Strongly-typed ID for SyncRun entities.
+    SyncRunId
+);
+
+define_id!(
+    /// Strongly-typed ID for SyncEntity entities.
+    SyncEntityId
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,297 @@
+//! Import validation and school import models.
+//!
+//! A dataset for a school import is up to five CSV sheets: levels (with
+//! their branches), branches, staff, students, and guardians. Validating a
+//! dataset checks every sheet and the references between them, and against
+//! what the school already has, without writing anything. Importing it
+//! validates the same way and then writes the sheets in dependency order.
+
+use crate::ids::SchoolId;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Machine-readable kind of import problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportIssueCode {
+    /// The row could not be parsed
+    UnreadableRow,
+    /// A required value is empty
+    MissingValue,
+    /// A value has the wrong format or length
+    InvalidValue,
+    /// The value repeats one earlier in the same file
+    Duplicate,
+    /// The value is already registered in the school
+    AlreadyExists,
+    /// The row refers to a level, branch, or student that does not exist
+    /// in the dataset or the school
+    UnknownReference,
+    /// The row is well-formed but breaks a school data policy
+    PolicyViolation,
+}
+
+/// How serious an import problem is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportIssueSeverity {
+    /// The import would be rejected
+    Error,
+    /// The import would go through, but probably not as intended
+    Warning,
+}
+
+/// A file in an import dataset, in the order issues are reported.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportDataset {
+    Levels,
+    Branches,
+    Staff,
+    Students,
+    Guardians,
+}
+
+/// A problem found in an import dataset.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ImportIssue {
+    pub dataset: ImportDataset,
+    pub severity: ImportIssueSeverity,
+    pub code: ImportIssueCode,
+    /// Line number in the file, counting the header as line 1
+    pub line: Option<usize>,
+    /// Column the problem is in, if it is tied to one
+    pub column: Option<String>,
+    pub message: String,
+}
+
+/// Query parameters for validating an import dataset.
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct ImportValidationParams {
+    /// Required for system admins to specify which school the import is for
+    pub school_id: Option<SchoolId>,
+}
+
+/// Multipart form for validating or importing a dataset. At least one file
+/// is required.
+///
+/// - `levels`: `name` and optional `branches` (branch names separated by `;`)
+/// - `branches`: `level` and `name`, for branches not listed with their level
+/// - `staff`: `first_name`, `last_name`, and `email`, with optional `role`
+///   (`teacher`, the default, or `admin`), `password`, and `level` and
+///   `branch` to make a teacher the branch's lead teacher
+/// - `students`: the same columns as the student CSV import
+/// - `guardians`: `student_email` and `name`, with optional `relationship`,
+///   `phone`, and `email`. The first guardian of each student becomes their
+///   emergency contact.
+#[derive(Debug, ToSchema)]
+pub struct ImportValidationForm {
+    #[schema(value_type = Option<String>, format = Binary)]
+    pub levels: Option<Vec<u8>>,
+    #[schema(value_type = Option<String>, format = Binary)]
+    pub branches: Option<Vec<u8>>,
+    #[schema(value_type = Option<String>, format = Binary)]
+    pub staff: Option<Vec<u8>>,
+    #[schema(value_type = Option<String>, format = Binary)]
+    pub students: Option<Vec<u8>>,
+    #[schema(value_type = Option<String>, format = Binary)]
+    pub guardians: Option<Vec<u8>>,
+    /// Password for staff and student rows that leave `password` empty
+    pub default_password: Option<String>,
+}
+
+/// One data row of a levels CSV, before validation.
+#[derive(Deserialize, Debug, Clone)]
+pub struct LevelImportRow {
+    pub name: String,
+    /// Branch names separated by `;`
+    pub branches: Option<String>,
+}
+
+impl LevelImportRow {
+    pub const REQUIRED_COLUMNS: [&'static str; 1] = ["name"];
+
+    pub const MAX_ROWS: usize = 200;
+}
+
+/// One data row of a branches CSV, before validation.
+#[derive(Deserialize, Debug, Clone)]
+pub struct BranchImportRow {
+    /// Name of a level in the levels file or the school
+    pub level: String,
+    pub name: String,
+}
+
+impl BranchImportRow {
+    pub const REQUIRED_COLUMNS: [&'static str; 2] = ["level", "name"];
+
+    pub const MAX_ROWS: usize = 1000;
+}
+
+/// One data row of a staff CSV, before validation.
+#[derive(Deserialize, Debug, Clone)]
+pub struct StaffImportRow {
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    /// `teacher` or `admin`; empty means `teacher`
+    pub role: Option<String>,
+    /// Empty means the import's default password
+    pub password: Option<String>,
+    /// Level of the branch the teacher leads
+    pub level: Option<String>,
+    /// Branch the teacher leads, in `level`
+    pub branch: Option<String>,
+}
+
+impl StaffImportRow {
+    pub const REQUIRED_COLUMNS: [&'static str; 3] = ["first_name", "last_name", "email"];
+
+    pub const MAX_ROWS: usize = 500;
+}
+
+/// One data row of a guardians CSV, before validation.
+#[derive(Deserialize, Debug, Clone)]
+pub struct GuardianImportRow {
+    /// Email of the student, in the students file or already registered
+    pub student_email: String,
+    pub name: String,
+    pub relationship: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+}
+
+impl GuardianImportRow {
+    pub const REQUIRED_COLUMNS: [&'static str; 2] = ["student_email", "name"];
+
+    /// Two guardians for each of the most students a student import accepts
+    pub const MAX_ROWS: usize = 2000;
+}
+
+/// Data rows read from each file of a dataset.
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct ImportRowCounts {
+    pub levels: usize,
+    pub branches: usize,
+    pub staff: usize,
+    pub students: usize,
+    pub guardians: usize,
+}
+
+/// Outcome of validating an import dataset. Nothing is written.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ImportValidationReport {
+    /// Whether the dataset has no errors; warnings do not block an import
+    pub valid: bool,
+    pub rows: ImportRowCounts,
+    pub error_count: usize,
+    pub warning_count: usize,
+    /// Every problem, grouped by file in the order levels, branches, staff,
+    /// students, guardians, then by line
+    pub issues: Vec<ImportIssue>,
+}
+
+impl ImportValidationReport {
+    pub fn new(rows: ImportRowCounts, issues: Vec<ImportIssue>) -> Self {
+        let error_count = issues
+            .iter()
+            .filter(|i| i.severity == ImportIssueSeverity::Error)
+            .count();
+        Self {
+            valid: error_count == 0,
+            rows,
+            error_count,
+            warning_count: issues.len() - error_count,
+            issues,
+        }
+    }
+}
+
+/// Query parameters for importing a school dataset.
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct SchoolImportParams {
+    /// Validate the dataset and report problems without importing anything
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Required for system admins to specify which school to import into
+    pub school_id: Option<SchoolId>,
+}
+
+/// Where a phase of a school import got to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportPhaseStatus {
+    /// The phase's rows were written
+    Committed,
+    /// The phase was rolled back; nothing from it was written
+    Failed,
+    /// The phase did not run, because the dataset was invalid, this was a
+    /// dry run, or an earlier phase failed
+    Skipped,
+}
+
+/// One phase of a school import. Each phase writes one sheet in its own
+/// transaction.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ImportPhaseResult {
+    pub dataset: ImportDataset,
+    pub status: ImportPhaseStatus,
+    /// Records created: levels, branches, staff and students, or emergency
+    /// contacts set from guardian rows
+    pub created: usize,
+    /// Why the phase failed
+    pub error: Option<String>,
+}
+
+/// Outcome of importing a school dataset.
+///
+/// Phases run in the order levels, branches, staff, students, guardians,
+/// and stop at the first that fails. Phases before it stay committed, so
+/// a failed import can be fixed and re-run: levels and branches that now
+/// exist are reported as warnings and reused.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SchoolImportReport {
+    pub dry_run: bool,
+    /// Whether every phase was committed
+    pub imported: bool,
+    pub validation: ImportValidationReport,
+    pub phases: Vec<ImportPhaseResult>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(severity: ImportIssueSeverity) -> ImportIssue {
+        ImportIssue {
+            dataset: ImportDataset::Students,
+            severity,
+            code: ImportIssueCode::InvalidValue,
+            line: Some(2),
+            column: None,
+            message: "bad".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_report_is_valid_with_only_warnings() {
+        let report = ImportValidationReport::new(
+            ImportRowCounts::default(),
+            vec![issue(ImportIssueSeverity::Warning)],
+        );
+        assert!(report.valid);
+        assert_eq!((report.error_count, report.warning_count), (0, 1));
+
+        let report = ImportValidationReport::new(
+            ImportRowCounts::default(),
+            vec![
+                issue(ImportIssueSeverity::Warning),
+                issue(ImportIssueSeverity::Error),
+            ],
+        );
+        assert!(!report.valid);
+        assert_eq!((report.error_count, report.warning_count), (1, 1));
+    }
+}
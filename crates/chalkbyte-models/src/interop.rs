@@ -0,0 +1,224 @@
+//! OneRoster interoperability models.
+//!
+//! OneRoster 1.1 CSV is the format LMS products exchange rosters in. A
+//! school's roster is exported with the school as the org, academic
+//! sessions and their terms as academic sessions, levels as courses,
+//! branches as classes, and students, teachers, admins, and guardians as
+//! users, with enrollments putting students and branch teachers in their
+//! classes.
+//!
+//! Imports go the other way through the school dataset import: courses
+//! become levels, classes become branches of their course's level, and
+//! users become staff, students, and guardians. The importer is tolerant of
+//! how LMS exports differ: headers are matched ignoring case, unknown
+//! columns are ignored, and rows it cannot use are skipped with a warning
+//! instead of failing the file.
+
+use crate::ids::SchoolId;
+use crate::imports::{ImportIssueCode, ImportIssueSeverity, ImportPhaseResult};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// A file of a OneRoster CSV bulk export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum OneRosterFile {
+    Orgs,
+    AcademicSessions,
+    Courses,
+    Classes,
+    Users,
+    Enrollments,
+}
+
+impl OneRosterFile {
+    /// Name of the file in an export, e.g. `academicSessions.csv`.
+    pub const fn file_name(self) -> &'static str {
+        match self {
+            Self::Orgs => "orgs.csv",
+            Self::AcademicSessions => "academicSessions.csv",
+            Self::Courses => "courses.csv",
+            Self::Classes => "classes.csv",
+            Self::Users => "users.csv",
+            Self::Enrollments => "enrollments.csv",
+        }
+    }
+}
+
+/// Query parameters for a OneRoster export.
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct OneRosterExportParams {
+    /// Required for system admins to specify which school to export
+    pub school_id: Option<SchoolId>,
+}
+
+/// Query parameters for a OneRoster import.
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct OneRosterImportParams {
+    /// Map and validate the files and report problems without importing
+    /// anything
+    #[serde(default)]
+    pub dry_run: bool,
+    /// `sourcedId` of the org to import when the files cover several
+    /// schools; classes and users of other orgs are left out
+    pub org: Option<String>,
+    /// Required for system admins to specify which school to import into
+    pub school_id: Option<SchoolId>,
+}
+
+/// Multipart form for a OneRoster import. Fields are named after the
+/// files, with or without `.csv`; `courses` and `classes` are needed for
+/// enrollments to place anyone in a branch. Other files are ignored.
+#[derive(Debug, ToSchema)]
+pub struct OneRosterImportForm {
+    #[schema(value_type = Option<String>, format = Binary)]
+    pub courses: Option<Vec<u8>>,
+    #[schema(value_type = Option<String>, format = Binary)]
+    pub classes: Option<Vec<u8>>,
+    #[schema(value_type = Option<String>, format = Binary)]
+    pub users: Option<Vec<u8>>,
+    #[schema(value_type = Option<String>, format = Binary)]
+    pub enrollments: Option<Vec<u8>>,
+    /// Password for users whose `password` is empty
+    pub default_password: Option<String>,
+}
+
+/// One data row of a OneRoster `courses.csv`. Headers are matched with
+/// case and punctuation removed.
+#[derive(Deserialize, Debug, Clone)]
+pub struct OneRosterCourseRow {
+    #[serde(rename = "sourcedid")]
+    pub sourced_id: String,
+    pub status: Option<String>,
+    pub title: Option<String>,
+    #[serde(rename = "orgsourcedid")]
+    pub org_sourced_id: Option<String>,
+}
+
+impl OneRosterCourseRow {
+    pub const REQUIRED_COLUMNS: [&'static str; 2] = ["sourcedid", "title"];
+}
+
+/// One data row of a OneRoster `classes.csv`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct OneRosterClassRow {
+    #[serde(rename = "sourcedid")]
+    pub sourced_id: String,
+    pub status: Option<String>,
+    pub title: Option<String>,
+    #[serde(rename = "coursesourcedid")]
+    pub course_sourced_id: Option<String>,
+    #[serde(rename = "schoolsourcedid")]
+    pub school_sourced_id: Option<String>,
+}
+
+impl OneRosterClassRow {
+    pub const REQUIRED_COLUMNS: [&'static str; 3] = ["sourcedid", "title", "coursesourcedid"];
+}
+
+/// One data row of a OneRoster `users.csv`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct OneRosterUserRow {
+    #[serde(rename = "sourcedid")]
+    pub sourced_id: String,
+    pub status: Option<String>,
+    #[serde(rename = "enableduser")]
+    pub enabled_user: Option<String>,
+    /// Orgs the user belongs to, separated by `,`
+    #[serde(rename = "orgsourcedids")]
+    pub org_sourced_ids: Option<String>,
+    pub role: Option<String>,
+    pub username: Option<String>,
+    #[serde(rename = "givenname")]
+    pub given_name: Option<String>,
+    #[serde(rename = "familyname")]
+    pub family_name: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub sms: Option<String>,
+    /// Guardians of a student, or students of a guardian, separated by `,`
+    #[serde(rename = "agentsourcedids")]
+    pub agent_sourced_ids: Option<String>,
+    /// Grade codes separated by `,`
+    pub grades: Option<String>,
+    pub password: Option<String>,
+}
+
+impl OneRosterUserRow {
+    pub const REQUIRED_COLUMNS: [&'static str; 4] =
+        ["sourcedid", "role", "givenname", "familyname"];
+}
+
+/// One data row of a OneRoster `enrollments.csv`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct OneRosterEnrollmentRow {
+    #[serde(rename = "sourcedid")]
+    pub sourced_id: Option<String>,
+    pub status: Option<String>,
+    #[serde(rename = "classsourcedid")]
+    pub class_sourced_id: String,
+    #[serde(rename = "schoolsourcedid")]
+    pub school_sourced_id: Option<String>,
+    #[serde(rename = "usersourcedid")]
+    pub user_sourced_id: String,
+    pub role: Option<String>,
+    pub primary: Option<String>,
+}
+
+impl OneRosterEnrollmentRow {
+    pub const REQUIRED_COLUMNS: [&'static str; 3] = ["classsourcedid", "usersourcedid", "role"];
+}
+
+/// A problem found in a OneRoster import, traced back to the row it came
+/// from.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct OneRosterIssue {
+    pub file: OneRosterFile,
+    pub severity: ImportIssueSeverity,
+    pub code: ImportIssueCode,
+    /// Line number in the file, counting the header as line 1
+    pub line: Option<usize>,
+    /// `sourcedId` of the row
+    pub sourced_id: Option<String>,
+    pub message: String,
+}
+
+/// Outcome of a OneRoster import.
+///
+/// The files are mapped to a school dataset (levels, branches, staff,
+/// students, and guardians) and imported the way a school dataset import
+/// is, phase by phase.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OneRosterImportReport {
+    pub dry_run: bool,
+    /// Whether every phase was committed
+    pub imported: bool,
+    /// Whether the mapped dataset has no errors; warnings do not block an
+    /// import
+    pub valid: bool,
+    pub error_count: usize,
+    pub warning_count: usize,
+    /// Problems with the files and the mapped dataset, by file then line
+    pub issues: Vec<OneRosterIssue>,
+    pub phases: Vec<ImportPhaseResult>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_names_match_serialized_names() {
+        for file in [
+            OneRosterFile::Orgs,
+            OneRosterFile::AcademicSessions,
+            OneRosterFile::Courses,
+            OneRosterFile::Classes,
+            OneRosterFile::Users,
+            OneRosterFile::Enrollments,
+        ] {
+            let name = serde_json::to_value(file).unwrap();
+            assert_eq!(format!("{}.csv", name.as_str().unwrap()), file.file_name());
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Background job health models.
+//!
+//! The server runs background jobs for retention purges, broadcast and
+//! webhook delivery, guardian account runs, and session reports. Each job
+//! reports its recent runs so a failing or stalled job can be alerted on
+//! instead of discovered weeks later.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// What a job's recent runs say about it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobHealthStatus {
+    /// Running, and its recent runs succeeded
+    Healthy,
+    /// Started, but has not finished a run yet
+    Pending,
+    /// Several runs in a row have failed
+    Failing,
+    /// Has not started a run for several intervals
+    Stalled,
+}
+
+impl JobHealthStatus {
+    /// Whether the job needs someone to look at it.
+    #[must_use]
+    pub fn is_unhealthy(self) -> bool {
+        matches!(self, Self::Failing | Self::Stalled)
+    }
+}
+
+/// Health of one background job since the server started.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JobHealth {
+    #[schema(example = "webhook_delivery")]
+    pub name: String,
+    pub status: JobHealthStatus,
+    /// Seconds between runs
+    #[schema(example = 5)]
+    pub interval_secs: u64,
+    pub last_started_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    /// Error of the most recent failed run
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+    pub successes: u64,
+    pub failures: u64,
+    /// Work waiting for the job; `None` for jobs without a queue
+    pub queue_depth: Option<u64>,
+}
+
+/// Health of every background job.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JobsHealthResponse {
+    /// `false` if any job is failing or stalled
+    pub healthy: bool,
+    pub jobs: Vec<JobHealth>,
+}
@@ -0,0 +1,160 @@
+//! Shared attendance kiosk models and DTOs.
+//!
+//! A kiosk device is registered by a school admin and bound to one school and
+//! branch. Registration returns a long-lived device token, shown only once.
+//! Staff unlock the device with their email and kiosk PIN to get a
+//! short-lived operation token that can only mark attendance for the
+//! device's branch. Revoking a device invalidates its operation tokens too.
+
+use crate::attendance::{AttendanceEntryDto, AttendanceStatus};
+use crate::ids::{BranchId, KioskDeviceId, SchoolId, UserId};
+use crate::value_types::Email;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+/// A registered kiosk device. The device token is never returned after
+/// registration.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct KioskDevice {
+    pub id: KioskDeviceId,
+    pub school_id: SchoolId,
+    /// Branch whose attendance the device marks
+    pub branch_id: BranchId,
+    /// Label for the device, e.g. "JSS1 A tablet"
+    pub name: String,
+    /// First characters of the device token, for identifying it
+    pub token_prefix: String,
+    pub registered_by: Option<UserId>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+    /// Set once the device has been revoked
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO for registering a kiosk device.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct RegisterKioskDeviceDto {
+    /// Label for the device (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Branch the device is bound to
+    pub branch_id: BranchId,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// Response for a newly registered device, including the plaintext token.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RegisteredKioskDevice {
+    #[serde(flatten)]
+    pub device: KioskDevice,
+    /// Token to configure on the device. It cannot be retrieved again.
+    pub device_token: String,
+}
+
+/// Query parameters for listing kiosk devices.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct KioskDeviceQueryParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+}
+
+fn validate_pin(pin: &str) -> Result<(), ValidationError> {
+    if pin.chars().all(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("pin_not_numeric"))
+    }
+}
+
+/// DTO for setting the caller's kiosk PIN.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct SetKioskPinDto {
+    /// Numeric PIN (4-8 digits)
+    #[validate(length(min = 4, max = 8), custom(function = "validate_pin"))]
+    pub pin: String,
+}
+
+/// Request body for unlocking a kiosk device.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct KioskSessionRequest {
+    pub email: Email,
+    #[validate(length(min = 4, max = 8))]
+    pub pin: String,
+}
+
+/// Operation token issued to a staff member on a kiosk device.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct KioskSessionResponse {
+    pub access_token: String,
+    /// Seconds until the token expires
+    pub expires_in: i64,
+    pub staff_id: UserId,
+    pub staff_name: String,
+    pub branch_id: BranchId,
+}
+
+/// A student in the device's branch with today's status, if marked.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct KioskRosterStudent {
+    pub id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub status: Option<AttendanceStatus>,
+}
+
+/// DTO for marking today's attendance from a kiosk device.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct KioskAttendanceDto {
+    /// Entries to record (1-200)
+    #[validate(length(min = 1, max = 200), nested)]
+    pub records: Vec<AttendanceEntryDto>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_kiosk_pin_dto_validation() {
+        let valid = SetKioskPinDto {
+            pin: "4821".to_string(),
+        };
+        assert!(valid.validate().is_ok());
+
+        let too_short = SetKioskPinDto {
+            pin: "482".to_string(),
+        };
+        assert!(too_short.validate().is_err());
+
+        let not_numeric = SetKioskPinDto {
+            pin: "48a1".to_string(),
+        };
+        assert!(not_numeric.validate().is_err());
+    }
+
+    #[test]
+    fn test_registered_kiosk_device_flattens_device() {
+        let registered = RegisteredKioskDevice {
+            device: KioskDevice {
+                id: KioskDeviceId::new(),
+                school_id: SchoolId::new(),
+                branch_id: BranchId::new(),
+                name: "Front desk".to_string(),
+                token_prefix: "kd_abc1234".to_string(),
+                registered_by: None,
+                last_seen_at: None,
+                revoked_at: None,
+                created_at: Utc::now(),
+            },
+            device_token: "kd_secret".to_string(),
+        };
+
+        let json = serde_json::to_value(&registered).unwrap();
+        assert_eq!(json["name"], "Front desk");
+        assert_eq!(json["device_token"], "kd_secret");
+    }
+}
@@ -1,15 +1,71 @@
 //! Level domain models and DTOs.
 //!
 //! This module contains all data structures related to educational levels,
-//! including level entities, request/response DTOs, and filtering parameters.
+//! including level entities, request/response DTOs, and filtering parameters,
+//! plus the per-school naming templates used to generate levels and branches
+//! and the end-of-session promotion of students to their next level.
 
-use crate::ids::{LevelId, SchoolId, UserId};
-use chalkbyte_core::{PaginationMeta, PaginationParams};
+use crate::branches::Branch;
+use crate::ids::{AcademicSessionId, LevelId, SchoolId, StudentPromotionId, UserId};
+use chalkbyte_core::{PaginationMeta, PaginationParams, Versioned};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use utoipa::ToSchema;
-use validator::Validate;
+use std::collections::HashSet;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+/// Level name template used when a school has not configured one.
+pub const DEFAULT_LEVEL_NAME_TEMPLATE: &str = "Grade {n}";
+
+/// Branch name template used when a school has not configured one.
+pub const DEFAULT_BRANCH_NAME_TEMPLATE: &str = "{letter}";
+
+/// Render a level or branch name template.
+///
+/// `{n}` is replaced with the level number and `{letter}` with the branch
+/// letter, `A` for the first branch (`branch_index` 0). Without a branch
+/// index `{letter}` renders as an empty string.
+///
+/// ```
+/// use chalkbyte_models::levels::render_name_template;
+///
+/// assert_eq!(render_name_template("Grade {n}", 3, None), "Grade 3");
+/// assert_eq!(render_name_template("JSS {n}{letter}", 2, Some(1)), "JSS 2B");
+/// ```
+#[must_use]
+pub fn render_name_template(template: &str, n: u32, branch_index: Option<u32>) -> String {
+    let letter = branch_index
+        .and_then(|i| char::from_u32(u32::from(b'A') + i))
+        .map(String::from)
+        .unwrap_or_default();
+
+    template
+        .replace("{n}", &n.to_string())
+        .replace("{letter}", &letter)
+}
+
+/// Check that a template only uses known placeholders and contains `required`.
+fn check_name_template(template: &str, required: &str) -> Result<(), ValidationError> {
+    let stripped = template.replace("{n}", "").replace("{letter}", "");
+    if stripped.contains('{') || stripped.contains('}') {
+        return Err(ValidationError::new("template")
+            .with_message("Only the {n} and {letter} placeholders are supported".into()));
+    }
+    if !template.contains(required) {
+        return Err(ValidationError::new("template")
+            .with_message(format!("Template must contain {required}").into()));
+    }
+    Ok(())
+}
+
+fn validate_level_name_template(template: &str) -> Result<(), ValidationError> {
+    check_name_template(template, "{n}")
+}
+
+fn validate_branch_name_template(template: &str) -> Result<(), ValidationError> {
+    check_name_template(template, "{letter}")
+}
 
 #[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Level {
@@ -21,6 +77,12 @@ pub struct Level {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Versioned for Level {
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct LevelWithStats {
     pub id: LevelId,
@@ -32,6 +94,12 @@ pub struct LevelWithStats {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Versioned for LevelWithStats {
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
+
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct CreateLevelDto {
     #[validate(length(min = 1, max = 100))]
@@ -48,7 +116,7 @@ pub struct UpdateLevelDto {
     pub description: Option<String>,
 }
 
-#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
 pub struct LevelFilterParams {
     pub name: Option<String>,
     /// School ID - required for system admins to scope the query
@@ -80,6 +148,180 @@ pub struct BulkAssignResponse {
     pub failed_ids: Vec<UserId>,
 }
 
+/// A school's level and branch naming templates.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct NamingTemplates {
+    pub school_id: SchoolId,
+    /// Template for level names, e.g. `Grade {n}`
+    #[schema(example = "Grade {n}")]
+    pub level_name_template: String,
+    /// Template for branch names, e.g. `{letter}` or `JSS {n}{letter}`
+    #[schema(example = "{letter}")]
+    pub branch_name_template: String,
+}
+
+/// DTO for updating a school's naming templates. Omitted fields are left
+/// unchanged.
+///
+/// Templates may use `{n}` for the level number and `{letter}` for the branch
+/// letter. Level templates must contain `{n}` and branch templates `{letter}`
+/// so that generated names are unique.
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct UpdateNamingTemplatesDto {
+    #[validate(
+        length(min = 1, max = 64),
+        custom(function = "validate_level_name_template")
+    )]
+    #[schema(example = "JSS {n}")]
+    pub level_name_template: Option<String>,
+    #[validate(
+        length(min = 1, max = 64),
+        custom(function = "validate_branch_name_template")
+    )]
+    #[schema(example = "JSS {n}{letter}")]
+    pub branch_name_template: Option<String>,
+}
+
+/// DTO for bulk-generating levels and their branches from the school's
+/// naming templates.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct GenerateLevelsDto {
+    /// Number of the first level to generate (defaults to 1)
+    #[validate(range(min = 1, max = 99))]
+    pub start: Option<u32>,
+    /// How many consecutive levels to generate
+    #[validate(range(min = 1, max = 20))]
+    pub count: u32,
+    /// Branches to generate in each level, one per letter (at most 26)
+    #[validate(range(max = 26))]
+    #[serde(default)]
+    pub branches_per_level: u32,
+}
+
+/// A generated level with its generated branches.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GeneratedLevel {
+    #[serde(flatten)]
+    pub level: Level,
+    /// Whether the level was created, rather than already existing
+    pub created: bool,
+    /// Branches named by the template, whether new or already present
+    pub branches: Vec<Branch>,
+}
+
+/// Result of generating levels and branches. Levels and branches whose
+/// generated names already exist are reused, so generation can be repeated.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GenerateLevelsResponse {
+    pub levels: Vec<GeneratedLevel>,
+    pub created_levels: usize,
+    pub created_branches: usize,
+}
+
+/// What happened to a student at the end of a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum PromotionOutcome {
+    /// Moved up to the next level
+    Promoted,
+    /// Left the final level, so no longer in any level
+    Graduated,
+    /// Held back to repeat the same level
+    Repeated,
+}
+
+/// Where the students of one level go at the end of a session.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LevelPromotionMapping {
+    pub from_level_id: LevelId,
+    /// Level to promote into; omit to graduate the level's students
+    pub to_level_id: Option<LevelId>,
+}
+
+/// DTO for promoting a school's students to their next levels.
+///
+/// Every student in a mapped level moves to that mapping's target level,
+/// except the excluded students, who repeat their current level. Levels
+/// without a mapping are left alone.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_promotion_mappings"))]
+pub struct PromoteStudentsDto {
+    /// The session that is ending
+    pub academic_session_id: AcademicSessionId,
+    /// One mapping per source level (1-100)
+    #[validate(length(min = 1, max = 100))]
+    pub mappings: Vec<LevelPromotionMapping>,
+    /// Students who repeat their current level instead of moving on
+    #[serde(default)]
+    pub excluded_student_ids: Vec<UserId>,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+fn validate_promotion_mappings(dto: &PromoteStudentsDto) -> Result<(), ValidationError> {
+    let mut from_levels = HashSet::new();
+    for mapping in &dto.mappings {
+        if !from_levels.insert(mapping.from_level_id) {
+            return Err(ValidationError::new("duplicate_from_level"));
+        }
+        if mapping.to_level_id == Some(mapping.from_level_id) {
+            return Err(ValidationError::new("promotion_to_same_level"));
+        }
+    }
+    Ok(())
+}
+
+/// Promotion counts for one source level.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LevelPromotionSummary {
+    pub from_level_id: LevelId,
+    pub from_level_name: String,
+    pub to_level_id: Option<LevelId>,
+    pub to_level_name: Option<String>,
+    pub promoted_count: usize,
+    pub graduated_count: usize,
+    pub repeating_count: usize,
+}
+
+/// Result of a promotion run, or of its preview when `dry_run` is set.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PromotionReport {
+    /// Whether this is a preview; nothing is changed when true
+    pub dry_run: bool,
+    pub academic_session_id: AcademicSessionId,
+    /// One summary per mapping, in the order given
+    pub levels: Vec<LevelPromotionSummary>,
+    pub promoted_count: usize,
+    pub graduated_count: usize,
+    pub repeating_count: usize,
+}
+
+/// A student's recorded promotion, with names for display.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct StudentPromotion {
+    pub id: StudentPromotionId,
+    pub academic_session_id: AcademicSessionId,
+    pub student_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub from_level_id: Option<LevelId>,
+    pub from_level_name: Option<String>,
+    pub to_level_id: Option<LevelId>,
+    pub to_level_name: Option<String>,
+    pub outcome: PromotionOutcome,
+    pub promoted_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query parameters for a session's promotion history.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct PromotionHistoryParams {
+    pub academic_session_id: AcademicSessionId,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,4 +377,68 @@ mod tests {
         };
         assert!(empty_ids.validate().is_err());
     }
+
+    #[test]
+    fn test_render_name_template() {
+        assert_eq!(
+            render_name_template(DEFAULT_LEVEL_NAME_TEMPLATE, 10, None),
+            "Grade 10"
+        );
+        assert_eq!(
+            render_name_template(DEFAULT_BRANCH_NAME_TEMPLATE, 1, Some(0)),
+            "A"
+        );
+        assert_eq!(
+            render_name_template("JSS {n}{letter}", 3, Some(2)),
+            "JSS 3C"
+        );
+        assert_eq!(render_name_template("Year {n}", 7, Some(4)), "Year 7");
+    }
+
+    #[test]
+    fn test_update_naming_templates_validation() {
+        let dto = |level: &str, branch: &str| UpdateNamingTemplatesDto {
+            level_name_template: Some(level.to_string()),
+            branch_name_template: Some(branch.to_string()),
+        };
+
+        assert!(dto("JSS {n}", "JSS {n}{letter}").validate().is_ok());
+        assert!(UpdateNamingTemplatesDto::default().validate().is_ok());
+        // Level names need the number and branch names the letter to be unique
+        assert!(dto("Grade", "{letter}").validate().is_err());
+        assert!(dto("Grade {n}", "Section").validate().is_err());
+        // Unknown placeholders are rejected
+        assert!(dto("Grade {num}", "{letter}").validate().is_err());
+    }
+
+    #[test]
+    fn test_promote_students_dto_validation() {
+        let (grade_1, grade_2) = (LevelId::new(), LevelId::new());
+        let dto = |mappings: Vec<(LevelId, Option<LevelId>)>| PromoteStudentsDto {
+            academic_session_id: AcademicSessionId::new(),
+            mappings: mappings
+                .into_iter()
+                .map(|(from_level_id, to_level_id)| LevelPromotionMapping {
+                    from_level_id,
+                    to_level_id,
+                })
+                .collect(),
+            excluded_student_ids: vec![],
+            school_id: None,
+        };
+
+        // Chained mappings and graduating the final level are fine
+        assert!(
+            dto(vec![(grade_1, Some(grade_2)), (grade_2, None)])
+                .validate()
+                .is_ok()
+        );
+        assert!(dto(vec![]).validate().is_err());
+        assert!(dto(vec![(grade_1, Some(grade_1))]).validate().is_err());
+        assert!(
+            dto(vec![(grade_1, Some(grade_2)), (grade_1, None)])
+                .validate()
+                .is_err()
+        );
+    }
 }
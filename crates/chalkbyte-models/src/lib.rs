@@ -7,14 +7,55 @@
 //!
 //! # Modules
 //!
+//! - [`alumni`]: Alumni record and mailing export models
+//! - [`announcements`]: Announcement and in-app notification feed models
+//! - [`assessments`]: Subject, assessment, score, and term grade models
+//! - [`assets`]: School inventory and asset register models
+//! - [`attendance`]: Daily student attendance models
 //! - [`auth`]: Authentication models (login, MFA, password reset)
-//! - [`branches`]: School branch models
+//! - [`billing`]: Fee structure, invoice, payment, and balance models
+//! - [`boarding`]: Hostel, room allocation, and boarding fee models
+//! - [`branches`]: School branch models and teacher assignments
+//! - [`broadcasts`]: Templated email/SMS broadcast and delivery status models
+//! - [`changes`]: Data change feed models for incremental sync
+//! - [`clinic`]: Student medical profile and clinic visit models
+//! - [`custom_fields`]: Per-school custom field definitions and value validation
+//! - [`dashboard`]: School dashboard widgets and their freshness
+//! - [`discipline`]: Behaviour incident, history, and trend models
+//! - [`email_branding`]: Per-school email branding and template previews
+//! - [`emails`]: Outbound email queue and suppression list models
+//! - [`feature_flags`]: Runtime feature flags and per-school overrides
+//! - [`groups`]: School group (district) models and aggregate reports
+//! - [`guardians`]: Guardian account runs and their conflicts
 //! - [`ids`]: Strongly-typed ID newtypes for type safety
-//! - [`levels`]: Educational level models
+//! - [`imports`]: Cross-file import dataset validation issues
+//! - [`interop`]: OneRoster CSV import rows and reports
+//! - [`jobs`]: Background job health
+//! - [`kiosk`]: Shared attendance kiosk device and PIN models
+//! - [`levels`]: Educational level models and level/branch naming templates
+//! - [`library`]: Library catalog, loan, and fine models
+//! - [`messaging`]: Conversation, message, and unread count models
 //! - [`mfa`]: Multi-factor authentication models
+//! - [`oidc`]: Single sign-on identity provider models
+//! - [`performance`]: Slow endpoint statistics
+//! - [`profiles`]: Linked profiles and per-profile notification preferences
+//! - [`public_directory`]: Opt-in public school profile models
+//! - [`reports`]: Session summary reports for board reporting
+//! - [`results`]: Term result moderation workflow models
+//! - [`retention`]: Per-school data retention policy and purge run models
 //! - [`roles`]: Role and permission models
+//! - [`saved_views`]: Saved list filter and column selection models
+//! - [`staff_leave`]: Staff leave and absence tracking models
+//! - [`storage`]: Avatar, student document, and storage quota models
+//! - [`student_cards`]: Signed student ID card and QR code models
 //! - [`students`]: Student-specific models
+//! - [`sync`]: LMS connection, sync run, and per-entity sync state models
+//! - [`traces`]: Request log summaries and request traces for support
+//! - [`trash`]: Recycle bin models for deleted students, users, and branches
+//! - [`transport`]: Vehicle, route, stop, and route assignment models
 //! - [`users`]: User models and system roles
+//! - [`visitor_log`]: Visitor and gate log and kiosk key models
+//! - [`webhooks`]: Webhook endpoint, event, and delivery models
 //!
 //! # Example
 //!
@@ -41,21 +82,70 @@
 //! ```
 
 pub mod academic_sessions;
+pub mod alumni;
+pub mod announcements;
+pub mod assessments;
+pub mod assets;
+pub mod attendance;
 pub mod auth;
+pub mod billing;
+pub mod boarding;
 pub mod branches;
+pub mod broadcasts;
+pub mod changes;
+pub mod clinic;
+pub mod custom_fields;
+pub mod dashboard;
+pub mod discipline;
+pub mod email_branding;
+pub mod emails;
+pub mod exams;
+pub mod feature_flags;
+pub mod groups;
+pub mod guardians;
 pub mod ids;
+pub mod imports;
+pub mod interop;
+pub mod jobs;
+pub mod kiosk;
 pub mod levels;
+pub mod library;
+pub mod messaging;
 pub mod mfa;
+pub mod oidc;
+pub mod performance;
+pub mod profiles;
+pub mod public_directory;
+pub mod reports;
+pub mod results;
+pub mod retention;
 pub mod roles;
+pub mod saved_views;
+pub mod staff_leave;
+pub mod storage;
+pub mod student_cards;
 pub mod students;
+pub mod sync;
 pub mod terms;
+pub mod traces;
+pub mod transport;
+pub mod trash;
 pub mod users;
 pub mod value_types;
+pub mod visitor_log;
+pub mod webhooks;
 
 // Re-export ID types at crate root for convenience
 pub use ids::{
-    AcademicSessionId, BranchId, LevelId, PermissionId, RoleId, RolePermissionId, SchoolId, TermId,
-    UserId, UserRoleId,
+    AcademicSessionId, AlumnusId, AssessmentId, AssessmentScoreId, AssetId, AttendanceRecordId,
+    BoardingFeeLineId, BranchId, BroadcastId, BroadcastRecipientId, ClinicMedicationId,
+    ClinicVisitId, ConversationId, CustomFieldId, DisciplineIncidentId, FeePaymentId,
+    FeeStructureId, HostelId, HostelRoomId, InvoiceId, KioskDeviceId, LevelId, LibraryBookId,
+    LibraryCopyId, LibraryFineChargeId, LibraryLoanId, MessageId, PermissionId, RoleId,
+    RolePermissionId, RoomAllocationId, RouteAssignmentId, RouteStopId, SavedViewId, SchoolId,
+    StaffLeaveId, StoredFileId, SubjectId, SyncConnectionId, SyncEntityId, SyncRunId, TermId,
+    TransportFeeChargeId, TransportRouteId, TrashItemId, UserId, UserRoleId, VehicleId,
+    VisitorKioskKeyId, VisitorLogId, WebhookDeliveryId, WebhookEndpointId,
 };
 
 // Re-export value types at crate root for convenience
@@ -63,35 +153,41 @@ pub use value_types::{Email, PhoneNumber, ValueTypeError};
 
 // Re-export commonly used types at crate root for convenience
 pub use auth::{
-    Claims, ForgotPasswordRequest, LoginRequest, LoginResponse, LoginUser, MessageResponse,
-    MfaRecoveryLoginRequest, MfaRequiredResponse, MfaTempClaims, MfaVerifyLoginRequest,
-    RefreshTokenClaims, RefreshTokenRequest, ResetPasswordRequest,
+    Claims, ForgotPasswordRequest, LoginRequest, LoginResponse, LoginUser, LogoutRequest,
+    MessageResponse, MfaMethod, MfaRecoveryLoginRequest, MfaRequiredResponse,
+    MfaVerifyLoginRequest, RefreshTokenClaims, RefreshTokenRequest, ResetPasswordRequest,
 };
 
 pub use roles::{
-    AssignPermissionsDto, AssignRoleToUserDto, CreateRoleDto, PaginatedPermissionsResponse,
-    PaginatedRolesResponse, Permission, PermissionFilterParams, Role, RoleAssignmentResponse,
-    RoleFilterParams, RolePermission, RoleWithPermissions, UpdateRoleDto, UserRole, UserWithRoles,
-    generate_slug,
+    AssignPermissionsDto, AssignRoleToUserDto, BulkAssignRoleDto, BulkRoleAssignmentResponse,
+    CreateRoleDto, FailedRoleAssignment, PaginatedPermissionsResponse, PaginatedRolesResponse,
+    Permission, PermissionFilterParams, Role, RoleAssignmentResponse, RoleFilterParams,
+    RolePermission, RoleWithPermissions, UpdateRoleDto, UserRole, UserWithRoles, generate_slug,
 };
 
 pub use users::{
-    BranchInfo, ChangePasswordDto, CreateSchoolDto, CreateUserDto, LevelInfo,
-    PaginatedBasicUsersResponse, PaginatedSchoolsResponse, PaginatedUsersResponse, RoleInfo,
-    School, SchoolFilterParams, SchoolFullInfo, SchoolInfo, UpdateProfileDto, User,
-    UserFilterParams, UserWithRelations, UserWithSchool, system_roles,
+    BranchInfo, BulkCreateUsersDto, BulkCreateUsersResponse, BulkItemStatus, BulkUserResult,
+    ChangePasswordDto, CreateSchoolDto, CreateUserDto, LevelInfo, PaginatedBasicUsersResponse,
+    PaginatedSchoolsResponse, PaginatedUserLitesResponse, PaginatedUsersResponse, RoleInfo, School,
+    SchoolExportRow, SchoolFilterParams, SchoolFullInfo, SchoolInfo, SchoolSettings,
+    UpdateProfileDto, UpdateSchoolSettingsDto, UpdateUserCustomFieldsDto, User, UserExportRow,
+    UserFilterParams, UserListResponse, UserLite, UserLiteListResponse, UserWithRelations,
+    UserWithSchool, system_roles,
 };
 
 pub use levels::{
-    AssignStudentsToLevelDto, BulkAssignResponse as LevelBulkAssignResponse, CreateLevelDto, Level,
-    LevelFilterParams, LevelWithStats, MoveStudentToLevelDto, PaginatedLevelsResponse,
-    UpdateLevelDto,
+    AssignStudentsToLevelDto, BulkAssignResponse as LevelBulkAssignResponse, CreateLevelDto,
+    GenerateLevelsDto, GenerateLevelsResponse, GeneratedLevel, Level, LevelFilterParams,
+    LevelPromotionMapping, LevelPromotionSummary, LevelWithStats, MoveStudentToLevelDto,
+    NamingTemplates, PaginatedLevelsResponse, PromoteStudentsDto, PromotionHistoryParams,
+    PromotionOutcome, PromotionReport, StudentPromotion, UpdateLevelDto, UpdateNamingTemplatesDto,
 };
 
 pub use branches::{
-    AssignStudentsToBranchDto, Branch, BranchFilterParams, BranchWithStats,
-    BulkAssignResponse as BranchBulkAssignResponse, CreateBranchDto, MoveStudentToBranchDto,
-    PaginatedBranchesResponse, UpdateBranchDto,
+    AssignBranchTeacherDto, AssignStudentsToBranchDto, Branch, BranchFilterParams, BranchTeacher,
+    BranchTeacherQueryParams, BranchWithStats, BulkAssignResponse as BranchBulkAssignResponse,
+    CreateBranchDto, MoveStudentToBranchDto, PaginatedBranchesResponse, TeacherCapacity,
+    UpdateBranchDto,
 };
 
 pub use mfa::{
@@ -99,9 +195,117 @@ pub use mfa::{
     VerifyMfaRequest,
 };
 
+pub use custom_fields::{
+    CreateCustomFieldDto, CustomFieldDefinition, CustomFieldEntity, CustomFieldFilterParams,
+    CustomFieldType, CustomFieldValues, UpdateCustomFieldDto, apply_custom_field_values,
+};
+
+pub use dashboard::{
+    AttendanceTodaySummary, DashboardWidget, SchoolDashboard, SchoolStats, StaffLeaveTodaySummary,
+    WidgetFreshness, WidgetStatus,
+};
+
+pub use saved_views::{
+    CreateSavedViewDto, SavedView, SavedViewFilterParams, SavedViewResource, SortOrder,
+    UpdateSavedViewDto,
+};
+
+pub use exams::{
+    AssignInvigilatorDto, ExamInvigilator, ExamRoom, ExamRoomDto, ExamSeat, ExamSitting,
+    GenerateSeatingPlanDto, ScheduleSittingDto, SeatingChartFormat, SeatingChartParams,
+    SeatingPlan,
+};
+
+pub use assessments::{
+    Assessment, AssessmentFilterParams, AssessmentKind, AssessmentScoreWithStudent,
+    CreateAssessmentDto, CreateSubjectDto, GradingComponent, GradingScheme,
+    GradingSchemeQueryParams, PaginatedAssessmentScoresResponse, PaginatedAssessmentsResponse,
+    PaginatedStudentResultsResponse, PaginatedTermGradesResponse, RecordScoresDto,
+    RecordScoresResponse, ScoreEntryDto, SetGradingSchemeDto, StudentResult, StudentResultParams,
+    StudentTermGradeParams, Subject, SubjectQueryParams, TermGrade, TermGradeParams,
+    UpdateAssessmentDto,
+};
+
+pub use results::{
+    ResultAction, ResultStatus, SkippedResult, TermResult, TermResultParams, TermResultTransition,
+    TransitionResultsDto, TransitionResultsResponse,
+};
+
+pub use attendance::{
+    AttendanceAnalyticsParams, AttendanceEntryDto, AttendanceQueryParams, AttendanceRecord,
+    AttendanceRecordWithStudent, AttendanceStatus, AttendanceTrendPoint, ChronicAbsentee,
+    ChronicAbsenteeParams, DayOfWeekAttendance, MarkAttendanceDto, MarkAttendanceResponse,
+};
+
+pub use kiosk::{
+    KioskAttendanceDto, KioskDevice, KioskDeviceQueryParams, KioskRosterStudent,
+    KioskSessionRequest, KioskSessionResponse, RegisterKioskDeviceDto, RegisteredKioskDevice,
+    SetKioskPinDto,
+};
+
+pub use announcements::{
+    Announcement, AnnouncementFilterParams, AnnouncementStatus, CreateAnnouncementDto,
+    MarkNotificationsReadResponse, Notification, NotificationFeed, NotificationFilterParams,
+    PaginatedAnnouncementsResponse, ReviewAnnouncementDto, UpdateAnnouncementDto,
+};
+
+pub use broadcasts::{
+    Broadcast, BroadcastChannel, BroadcastFilterParams, BroadcastRecipient,
+    BroadcastRecipientFilterParams, BroadcastStatus, CreateBroadcastDto, DeliveryStatus,
+    PaginatedBroadcastRecipientsResponse, PaginatedBroadcastsResponse, PublishingSettings,
+    ReviewBroadcastDto, UpdateBroadcastDto, UpdatePublishingSettingsDto,
+};
+
+pub use changes::{
+    ChangeEntityType, ChangeEvent, ChangeFeedParams, ChangeFeedResponse, ChangeOperation,
+};
+
+pub use public_directory::{PublicProfileSettings, PublicSchoolProfile, UpdatePublicProfileDto};
+
+pub use student_cards::{StudentCard, VerifiedStudentCard, VerifyStudentCardDto};
+
+pub use retention::{
+    PaginatedPurgeRunsResponse, PurgePreview, PurgeRun, PurgeRunFilterParams, RetentionCategory,
+    RetentionPolicy, UpdateRetentionPolicyDto,
+};
+
+pub use traces::{NewRequestLog, RequestLog, RequestTrace};
+
+pub use jobs::{JobHealth, JobHealthStatus, JobsHealthResponse};
+
+pub use performance::{SlowEndpoint, SlowEndpointParams, SlowEndpointsResponse};
+
+pub use trash::{PaginatedTrashResponse, TrashFilterParams, TrashItem, TrashItemType};
+
+pub use groups::{
+    AddGroupSchoolDto, AssignGroupAdminDto, CreateSchoolGroupDto, GroupAdmin, GroupReport,
+    GroupReportParams, GroupSchool, GroupSchoolReport, GroupUser, GroupUserFilterParams,
+    PaginatedGroupUsersResponse, SchoolGroup, UpdateSchoolGroupDto,
+};
+
+pub use imports::{
+    GuardianImportRow, ImportDataset, ImportIssue, ImportIssueCode, ImportIssueSeverity,
+    ImportRowCounts, ImportValidationForm, ImportValidationParams, ImportValidationReport,
+    LevelImportRow,
+};
+
+pub use interop::{
+    OneRosterExportParams, OneRosterFile, OneRosterImportForm, OneRosterImportParams,
+    OneRosterImportReport, OneRosterIssue,
+};
+
+pub use sync::{
+    CreateSyncConnectionDto, GoogleClassroomCredentials, PaginatedSyncEntitiesResponse,
+    PaginatedSyncRunsResponse, SyncConflictKind, SyncConnection, SyncConnectionFilterParams,
+    SyncEntity, SyncEntityFilterParams, SyncEntityStatus, SyncEntityType, SyncProviderKind,
+    SyncRun, SyncRunStatus, SyncRunTrigger, UpdateSyncConnectionDto,
+};
+
 pub use students::{
-    CreateStudentDto, PaginatedStudentsResponse, QueryParams as StudentQueryParams, Student,
-    UpdateStudentDto,
+    CreateStudentDto, PaginatedStudentLitesResponse, PaginatedStudentsResponse,
+    QueryParams as StudentQueryParams, Student, StudentExportRow, StudentImportForm,
+    StudentImportParams, StudentImportReport, StudentImportRow, StudentImportRowError,
+    StudentListResponse, StudentLite, StudentLiteListResponse, UpdateStudentDto,
 };
 
 pub use academic_sessions::{
@@ -113,3 +317,102 @@ pub use terms::{
     CreateTermDto, PaginatedTermsResponse, Term, TermFilterParams, TermWithSessionInfo,
     UpdateTermDto,
 };
+
+pub use staff_leave::{
+    ApproveStaffLeaveDto, CreateStaffLeaveDto, LeaveCalendarEntry, LeaveCalendarParams,
+    LeaveStatus, LeaveType, PaginatedStaffLeaveResponse, RejectStaffLeaveDto,
+    StaffLeaveFilterParams, StaffLeaveRequest, StaffLeaveWithStaff, SubstituteCandidate,
+};
+
+pub use alumni::{
+    AlumniExportParams, AlumniFilterParams, AlumniMailingRow, Alumnus, AlumnusDetail,
+    GraduateStudentsDto, GraduateStudentsResponse, PaginatedAlumniResponse, UpdateAlumnusDto,
+};
+
+pub use assets::{
+    Asset, AssetAssignment, AssetCategory, AssetCondition, AssetConditionLog, AssetCount,
+    AssetFilterParams, AssetStatus, AssetSummary, AssetSummaryParams, AssignAssetDto,
+    CreateAssetDto, PaginatedAssetsResponse, RecordConditionDto, UpdateAssetDto,
+};
+
+pub use billing::{
+    ArrearsExportParams, ArrearsRow, BalanceQueryParams, CreateFeeStructureDto, FeeItem,
+    FeePayment, FeeStructure, FeeStructureDetail, FeeStructureFilterParams,
+    GenerateInvoicesResponse, Invoice, InvoiceDetail, InvoiceFilterParams, InvoiceStatus,
+    PaginatedFeeStructuresResponse, PaginatedInvoicesResponse, PaymentMethod, RecordPaymentDto,
+    SchoolBalance, StudentBalance,
+};
+
+pub use boarding::{
+    AllocateRoomDto, BoardingFeeLine, BoardingFeeQueryParams, CreateBoardingFeeLineDto,
+    CreateHostelDto, CreateHostelRoomDto, Hostel, HostelFilterParams, HostelGender, HostelRoom,
+    HostelRoomQueryParams, HostelRoomWithOccupancy, PaginatedHostelsResponse,
+    PaginatedRoomAllocationsResponse, RoomAllocation, RoomAllocationFilterParams,
+    RoomAllocationWithDetails, StudentBoardingFees, UpdateHostelDto, UpdateHostelRoomDto,
+};
+
+pub use clinic::{
+    AdministerMedicationDto, ClinicMedication, ClinicVisit, ClinicVisitDetail,
+    ClinicVisitFilterParams, ClinicVisitOutcome, ClinicVisitSummary, CreateClinicVisitDto,
+    GuardianNotificationMethod, NotifyGuardianDto, PaginatedClinicVisitsResponse,
+    StudentMedicalProfile, UpdateClinicVisitDto, UpsertMedicalProfileDto,
+};
+
+pub use discipline::{
+    CategoryCount, CreateIncidentDto, DisciplineIncident, DisciplineIncidentSummary,
+    IncidentCategory, IncidentFilterParams, IncidentSeverity, IncidentTrendParams, IncidentTrends,
+    MonthlyIncidentCount, NotifyGuardiansResponse, PaginatedIncidentsResponse, SeverityCount,
+    StudentIncidentHistory, UpdateIncidentDto,
+};
+
+pub use messaging::{
+    AttachmentUploadForm, Conversation, ConversationDetail, ConversationFilterParams,
+    ConversationKind, ConversationMessage, ConversationParticipant, ConversationSummary,
+    MessageAttachment, PaginatedConversationsResponse, PaginatedMessagesResponse, SendMessageDto,
+    StartConversationDto, UnreadMessageCounts,
+};
+
+pub use library::{
+    BookFilterParams, BorrowingHistory, CheckoutDto, CopyStatus, CreateBookDto, CreateCopyDto,
+    LibraryBook, LibraryBookWithAvailability, LibraryCopy, LibraryFineCharge,
+    LibraryFineFilterParams, LibraryLoan, LibraryLoanWithDetails, LibraryPolicy,
+    LibraryPolicyQueryParams, LoanFilterParams, PaginatedBooksResponse, PaginatedLoansResponse,
+    ReturnLoanResponse, UpdateBookDto, UpdateCopyDto, UpdateLibraryPolicyDto,
+};
+
+pub use transport::{
+    AssignRouteDto, CreateRouteStopDto, CreateTransportRouteDto, CreateVehicleDto,
+    GenerateTransportFeesResponse, ManifestStop, ManifestStudent,
+    PaginatedRouteAssignmentsResponse, PaginatedTransportRoutesResponse, RouteAssignment,
+    RouteAssignmentFilterParams, RouteAssignmentWithDetails, RouteManifest, RouteStop,
+    TransportFeeCharge, TransportFeeFilterParams, TransportRoute, TransportRouteFilterParams,
+    TransportTermParams, UpdateTransportRouteDto, UpdateVehicleDto, Vehicle, VehicleQueryParams,
+};
+
+pub use visitor_log::{
+    CheckInVisitorDto, CreateVisitorKioskKeyDto, CreatedVisitorKioskKey, DailyVisitorReport,
+    DailyVisitorReportParams, PaginatedVisitorLogsResponse, VisitPurpose, VisitPurposeCount,
+    VisitorKioskKey, VisitorKioskKeyQueryParams, VisitorLog, VisitorLogFilterParams,
+    VisitorLogWithHost,
+};
+
+pub use guardians::{
+    GuardianAccountConflict, GuardianAccountRun, GuardianAccountRunStatus, GuardianConflictKind,
+    PaginatedGuardianAccountRunsResponse, StartGuardianAccountRunDto,
+};
+
+pub use profiles::{
+    LinkProfileDto, LinkedProfile, MyProfilesResponse, NotificationPreferences, SwitchProfileDto,
+    UpdateNotificationPreferencesDto,
+};
+
+pub use oidc::{
+    CreateOidcProviderDto, OidcCallbackParams, OidcProvider, OidcProviderWithCallback,
+    UpdateOidcProviderDto,
+};
+
+pub use webhooks::{
+    CreateWebhookEndpointDto, PaginatedWebhookDeliveriesResponse, UpdateWebhookEndpointDto,
+    WebhookDelivery, WebhookDeliveryFilterParams, WebhookDeliveryStatus, WebhookEndpoint,
+    WebhookEndpointWithSecret, WebhookEvent,
+};
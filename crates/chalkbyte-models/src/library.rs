@@ -0,0 +1,364 @@
+//! Library domain models and DTOs.
+//!
+//! This module contains all data structures for library lending: the book
+//! catalog and its physical copies, each school's lending policy, loans with
+//! due dates, overdue fine charges, and per-member borrowing history.
+//!
+//! Fine charges are raised when an overdue copy is returned and are stored in
+//! minor currency units, the same as other fee charges.
+
+use crate::ids::{
+    LibraryBookId, LibraryCopyId, LibraryFineChargeId, LibraryLoanId, SchoolId, UserId,
+};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Circulation status of a library copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum CopyStatus {
+    Available,
+    OnLoan,
+    Lost,
+    Withdrawn,
+}
+
+/// A title in the library catalog.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LibraryBook {
+    pub id: LibraryBookId,
+    pub school_id: SchoolId,
+    pub title: String,
+    pub author: Option<String>,
+    /// ISBN, unique within the school
+    pub isbn: Option<String>,
+    pub publisher: Option<String>,
+    pub publication_year: Option<i32>,
+    pub category: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A catalog entry with copy counts.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LibraryBookWithAvailability {
+    pub id: LibraryBookId,
+    pub title: String,
+    pub author: Option<String>,
+    pub isbn: Option<String>,
+    pub category: Option<String>,
+    /// Copies that have not been withdrawn
+    pub total_copies: i64,
+    /// Copies on the shelf and ready to lend
+    pub available_copies: i64,
+}
+
+/// DTO for adding a title to the catalog.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateBookDto {
+    /// Title (1-255 characters)
+    #[validate(length(min = 1, max = 255))]
+    pub title: String,
+    /// Author (max 255 characters)
+    #[validate(length(max = 255))]
+    pub author: Option<String>,
+    /// ISBN (max 20 characters)
+    #[validate(length(max = 20))]
+    pub isbn: Option<String>,
+    /// Publisher (max 255 characters)
+    #[validate(length(max = 255))]
+    pub publisher: Option<String>,
+    /// Year of publication
+    #[validate(range(min = 1000, max = 9999))]
+    pub publication_year: Option<i32>,
+    /// Category or genre (max 100 characters)
+    #[validate(length(max = 100))]
+    pub category: Option<String>,
+    /// School ID (required for system admins, ignored for school staff)
+    pub school_id: Option<SchoolId>,
+}
+
+/// DTO for updating a catalog entry.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateBookDto {
+    /// Updated title (1-255 characters)
+    #[validate(length(min = 1, max = 255))]
+    pub title: Option<String>,
+    /// Updated author (max 255 characters)
+    #[validate(length(max = 255))]
+    pub author: Option<String>,
+    /// Updated ISBN (max 20 characters)
+    #[validate(length(max = 20))]
+    pub isbn: Option<String>,
+    /// Updated publisher (max 255 characters)
+    #[validate(length(max = 255))]
+    pub publisher: Option<String>,
+    /// Updated year of publication
+    #[validate(range(min = 1000, max = 9999))]
+    pub publication_year: Option<i32>,
+    /// Updated category (max 100 characters)
+    #[validate(length(max = 100))]
+    pub category: Option<String>,
+}
+
+/// Query parameters for searching the catalog.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct BookFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Case-insensitive match on title, author, or ISBN
+    pub search: Option<String>,
+    /// Filter by category
+    pub category: Option<String>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing catalog entries.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedBooksResponse {
+    /// List of books
+    pub data: Vec<LibraryBookWithAvailability>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// A physical copy of a book.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LibraryCopy {
+    pub id: LibraryCopyId,
+    pub book_id: LibraryBookId,
+    pub school_id: SchoolId,
+    /// Barcode or accession number, unique within the school
+    pub barcode: String,
+    pub status: CopyStatus,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for adding a copy of a book.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateCopyDto {
+    /// Barcode or accession number (1-50 characters)
+    #[validate(length(min = 1, max = 50))]
+    pub barcode: String,
+    /// Notes about the copy, e.g. its condition
+    pub notes: Option<String>,
+}
+
+/// DTO for updating a copy.
+///
+/// Copies move on and off loan through checkout and return only.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateCopyDto {
+    /// New status; `on_loan` is rejected
+    pub status: Option<CopyStatus>,
+    /// Updated notes
+    pub notes: Option<String>,
+}
+
+/// A school's lending policy.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LibraryPolicy {
+    pub school_id: SchoolId,
+    /// Default loan period in days
+    pub loan_days: i32,
+    /// Maximum open loans for a student
+    pub student_loan_limit: i32,
+    /// Maximum open loans for staff
+    pub staff_loan_limit: i32,
+    /// Fine per overdue day in minor currency units
+    pub daily_fine: i64,
+    /// Cap on the fine for a single loan; `None` for no cap
+    pub max_fine: Option<i64>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for updating a school's lending policy.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateLibraryPolicyDto {
+    /// Default loan period in days (1-365)
+    #[validate(range(min = 1, max = 365))]
+    pub loan_days: Option<i32>,
+    /// Maximum open loans for a student (0-50)
+    #[validate(range(min = 0, max = 50))]
+    pub student_loan_limit: Option<i32>,
+    /// Maximum open loans for staff (0-50)
+    #[validate(range(min = 0, max = 50))]
+    pub staff_loan_limit: Option<i32>,
+    /// Fine per overdue day in minor currency units
+    #[validate(range(min = 0))]
+    pub daily_fine: Option<i64>,
+    /// Cap on the fine for a single loan in minor currency units
+    #[validate(range(min = 0))]
+    pub max_fine: Option<i64>,
+    /// School ID (required for system admins, ignored for school staff)
+    pub school_id: Option<SchoolId>,
+}
+
+/// Query parameters identifying a school's policy.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct LibraryPolicyQueryParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// A library loan.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LibraryLoan {
+    pub id: LibraryLoanId,
+    pub school_id: SchoolId,
+    pub copy_id: LibraryCopyId,
+    pub borrower_id: UserId,
+    pub checked_out_at: DateTime<Utc>,
+    pub due_date: NaiveDate,
+    /// When the copy came back; `None` while on loan
+    pub returned_at: Option<DateTime<Utc>>,
+    pub checked_out_by: Option<UserId>,
+    pub returned_to: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A loan with book, copy, borrower, and fine details.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LibraryLoanWithDetails {
+    pub id: LibraryLoanId,
+    pub copy_id: LibraryCopyId,
+    pub barcode: String,
+    pub book_id: LibraryBookId,
+    pub book_title: String,
+    pub borrower_id: UserId,
+    pub borrower_first_name: String,
+    pub borrower_last_name: String,
+    pub checked_out_at: DateTime<Utc>,
+    pub due_date: NaiveDate,
+    pub returned_at: Option<DateTime<Utc>>,
+    /// Fine charged on return, if the loan was overdue
+    pub fine_amount: Option<i64>,
+}
+
+/// DTO for checking a copy out.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CheckoutDto {
+    /// Copy being lent
+    pub copy_id: LibraryCopyId,
+    /// Student or staff member borrowing the copy
+    pub borrower_id: UserId,
+    /// Due date (defaults to today plus the policy's loan period)
+    pub due_date: Option<NaiveDate>,
+}
+
+/// Result of returning a copy.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReturnLoanResponse {
+    pub loan: LibraryLoan,
+    /// Fine raised because the copy was returned late
+    pub fine: Option<LibraryFineCharge>,
+}
+
+/// Query parameters for listing loans.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct LoanFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by borrower
+    pub borrower_id: Option<UserId>,
+    /// Only loans that have (true) or have not (false) been returned
+    pub returned: Option<bool>,
+    /// Only open loans past their due date
+    pub overdue: Option<bool>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing loans.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedLoansResponse {
+    /// List of loans, newest first
+    pub data: Vec<LibraryLoanWithDetails>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// An overdue fine charged to a borrower.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LibraryFineCharge {
+    pub id: LibraryFineChargeId,
+    pub school_id: SchoolId,
+    pub loan_id: LibraryLoanId,
+    pub borrower_id: UserId,
+    pub days_overdue: i32,
+    /// Amount in minor currency units
+    pub amount: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query parameters for listing fine charges.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct LibraryFineFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by borrower
+    pub borrower_id: Option<UserId>,
+}
+
+/// A member's borrowing history.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BorrowingHistory {
+    pub borrower_id: UserId,
+    /// Loans not yet returned
+    pub open_loans: i64,
+    /// Maximum open loans allowed by the school's policy
+    pub loan_limit: i32,
+    /// Sum of fines charged in minor currency units
+    pub total_fines: i64,
+    /// All loans, newest first
+    pub loans: Vec<LibraryLoanWithDetails>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_book_dto_validation() {
+        let valid_dto = CreateBookDto {
+            title: "Things Fall Apart".to_string(),
+            author: Some("Chinua Achebe".to_string()),
+            isbn: Some("9780385474542".to_string()),
+            publisher: None,
+            publication_year: Some(1958),
+            category: Some("Fiction".to_string()),
+            school_id: None,
+        };
+        assert!(valid_dto.validate().is_ok());
+
+        let bad_year = CreateBookDto {
+            publication_year: Some(58),
+            ..valid_dto.clone()
+        };
+        assert!(bad_year.validate().is_err());
+
+        let empty_title = CreateBookDto {
+            title: String::new(),
+            ..valid_dto
+        };
+        assert!(empty_title.validate().is_err());
+    }
+
+    #[test]
+    fn test_copy_status_serialization() {
+        let json = serde_json::to_string(&CopyStatus::OnLoan).unwrap();
+        assert_eq!(json, "\"on_loan\"");
+        let parsed: CopyStatus = serde_json::from_str("\"withdrawn\"").unwrap();
+        assert_eq!(parsed, CopyStatus::Withdrawn);
+    }
+}
@@ -0,0 +1,192 @@
+//! Messaging domain models and DTOs.
+//!
+//! This module contains the data structures for conversations within a
+//! school: between a teacher and a guardian, optionally about one of the
+//! guardian's children, and between school admins and staff. Each
+//! participant has a read marker, so unread counts come from the messages
+//! sent after it. Messages may carry file attachments kept in the storage
+//! backend.
+
+use crate::ids::{ConversationId, MessageId, SchoolId, StoredFileId, UserId};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Who a conversation is between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum ConversationKind {
+    /// A teacher and a guardian of a student in the school
+    TeacherGuardian,
+    /// A school admin and a teacher or another admin
+    Staff,
+}
+
+/// A conversation.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Conversation {
+    pub id: ConversationId,
+    pub school_id: SchoolId,
+    pub kind: ConversationKind,
+    pub subject: String,
+    /// Student a teacher-guardian conversation is about
+    pub student_id: Option<UserId>,
+    pub created_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    pub last_message_at: DateTime<Utc>,
+}
+
+/// A member of a conversation.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ConversationParticipant {
+    pub user_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    /// When they last read the conversation; `None` if never
+    pub last_read_at: Option<DateTime<Utc>>,
+}
+
+/// A conversation with its participants.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ConversationDetail {
+    #[serde(flatten)]
+    pub conversation: Conversation,
+    pub participants: Vec<ConversationParticipant>,
+    /// Messages from others the caller has not read
+    pub unread_count: i64,
+}
+
+/// Conversation row for the caller's inbox.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ConversationSummary {
+    pub id: ConversationId,
+    pub kind: ConversationKind,
+    pub subject: String,
+    pub student_id: Option<UserId>,
+    /// The other participant
+    pub with_user_id: Option<UserId>,
+    pub with_first_name: Option<String>,
+    pub with_last_name: Option<String>,
+    pub last_message_at: DateTime<Utc>,
+    /// Messages from others the caller has not read
+    pub unread_count: i64,
+}
+
+/// DTO for starting a conversation with its first message.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct StartConversationDto {
+    /// User to talk to, in the caller's school
+    pub recipient_id: UserId,
+    /// Subject (1-200 characters)
+    #[validate(length(min = 1, max = 200))]
+    pub subject: String,
+    /// Student the conversation is about; must be a child of the guardian
+    pub student_id: Option<UserId>,
+    /// First message (1-5000 characters)
+    #[validate(length(min = 1, max = 5000))]
+    pub body: String,
+}
+
+/// DTO for sending a message.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct SendMessageDto {
+    /// Message text (1-5000 characters)
+    #[validate(length(min = 1, max = 5000))]
+    pub body: String,
+}
+
+/// Multipart form for sending a message with an attachment.
+#[derive(Debug, ToSchema)]
+pub struct AttachmentUploadForm {
+    /// Optional message text (max 5000 characters)
+    pub body: Option<String>,
+    /// PDF, PNG, or JPEG file of at most 5 MB
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+/// A file attached to a message.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct MessageAttachment {
+    pub file_id: StoredFileId,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+}
+
+/// A message in a conversation.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ConversationMessage {
+    pub id: MessageId,
+    pub conversation_id: ConversationId,
+    pub sender_id: Option<UserId>,
+    /// Empty for a message that is only an attachment
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    #[sqlx(skip)]
+    pub attachments: Vec<MessageAttachment>,
+}
+
+/// Query parameters for listing the caller's conversations.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct ConversationFilterParams {
+    /// Filter by kind
+    pub kind: Option<ConversationKind>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing the caller's conversations.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedConversationsResponse {
+    /// Conversations, most recently active first
+    pub data: Vec<ConversationSummary>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// Paginated response containing a conversation's messages.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedMessagesResponse {
+    /// Messages, newest first
+    pub data: Vec<ConversationMessage>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// The caller's unread messages across their conversations.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct UnreadMessageCounts {
+    /// Messages from others the caller has not read
+    pub unread_messages: i64,
+    /// Conversations with at least one unread message
+    pub unread_conversations: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_conversation_dto_validation() {
+        let dto: StartConversationDto = serde_json::from_value(serde_json::json!({
+            "recipient_id": UserId::new(),
+            "subject": "Homework",
+            "body": "Could we talk about this week's homework?",
+        }))
+        .unwrap();
+        assert!(dto.student_id.is_none());
+        assert!(dto.validate().is_ok());
+
+        let empty = StartConversationDto {
+            body: String::new(),
+            ..dto
+        };
+        assert!(empty.validate().is_err());
+    }
+}
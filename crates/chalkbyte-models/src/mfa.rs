@@ -1,12 +1,17 @@
 //! MFA (Multi-Factor Authentication) domain models and DTOs.
 //!
 //! This module contains all data structures related to multi-factor authentication,
-//! including MFA setup, verification, and recovery operations.
+//! including MFA setup, verification, and recovery operations, and passkey
+//! (WebAuthn) registration.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
 
+use crate::auth::MfaMethod;
+use crate::ids::WebauthnCredentialId;
+
 /// Response when enabling MFA for a user.
 ///
 /// Contains the TOTP secret and QR code for scanning with an authenticator app.
@@ -46,8 +51,16 @@ pub struct DisableMfaRequest {
 /// Response containing the current MFA status for a user.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct MfaStatusResponse {
-    /// Whether MFA is currently enabled for the user
+    /// Whether any second factor is set up for the user
     pub mfa_enabled: bool,
+    /// Whether an authenticator app (TOTP) is set up
+    pub totp_enabled: bool,
+    /// Number of registered passkeys
+    pub passkeys: i64,
+    /// Number of recovery codes not yet used
+    pub recovery_codes_remaining: i64,
+    /// Factor offered first at login, if the user chose one
+    pub preferred_method: Option<MfaMethod>,
 }
 
 /// Response containing newly generated recovery codes.
@@ -60,6 +73,56 @@ pub struct RegenerateMfaRecoveryCodesResponse {
     pub recovery_codes: Vec<String>,
 }
 
+/// A passkey registered as a second factor.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct PasskeyCredential {
+    pub id: WebauthnCredentialId,
+    /// Label the user gave the passkey, e.g. "Work laptop"
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// WebAuthn creation options for registering a new passkey.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StartPasskeyRegistrationResponse {
+    /// Identifies this registration when finishing it
+    pub registration_id: uuid::Uuid,
+    /// `PublicKeyCredentialCreationOptions` wrapped in a `publicKey` field,
+    /// to pass to `navigator.credentials.create()`
+    #[schema(value_type = Object)]
+    pub options: serde_json::Value,
+}
+
+/// Request to finish registering a passkey.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct FinishPasskeyRegistrationRequest {
+    pub registration_id: uuid::Uuid,
+    #[validate(length(min = 1, max = 100))]
+    #[schema(example = "Work laptop")]
+    pub name: String,
+    /// `PublicKeyCredential` returned by `navigator.credentials.create()`
+    #[schema(value_type = Object)]
+    pub credential: serde_json::Value,
+}
+
+/// Request to remove a passkey.
+///
+/// Requires the user's password for security verification.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RemovePasskeyRequest {
+    #[validate(length(min = 8))]
+    #[schema(example = "password123")]
+    pub password: String,
+}
+
+/// Request to set the second factor offered first at login.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePreferredMfaMethodRequest {
+    /// `totp` or `passkey`; `null` clears the preference
+    pub method: Option<MfaMethod>,
+}
+
 /// Generic success message response for MFA operations.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct MessageResponse {
@@ -127,16 +190,43 @@ mod tests {
 
     #[test]
     fn test_mfa_status_response_serialize() {
-        let enabled = MfaStatusResponse { mfa_enabled: true };
-        let disabled = MfaStatusResponse { mfa_enabled: false };
+        let enabled = MfaStatusResponse {
+            mfa_enabled: true,
+            totp_enabled: false,
+            passkeys: 2,
+            recovery_codes_remaining: 7,
+            preferred_method: Some(MfaMethod::Passkey),
+        };
+        let disabled = MfaStatusResponse {
+            mfa_enabled: false,
+            totp_enabled: false,
+            passkeys: 0,
+            recovery_codes_remaining: 0,
+            preferred_method: None,
+        };
 
         let enabled_json = serde_json::to_string(&enabled).unwrap();
         let disabled_json = serde_json::to_string(&disabled).unwrap();
 
         assert!(enabled_json.contains(r#""mfa_enabled":true"#));
+        assert!(enabled_json.contains(r#""passkeys":2"#));
+        assert!(enabled_json.contains(r#""recovery_codes_remaining":7"#));
+        assert!(enabled_json.contains(r#""preferred_method":"passkey""#));
         assert!(disabled_json.contains(r#""mfa_enabled":false"#));
     }
 
+    #[test]
+    fn test_finish_passkey_registration_request_name_length() {
+        let request = |name: &str| FinishPasskeyRegistrationRequest {
+            registration_id: uuid::Uuid::new_v4(),
+            name: name.to_string(),
+            credential: serde_json::json!({}),
+        };
+        assert!(request("Work laptop").validate().is_ok());
+        assert!(request("").validate().is_err());
+        assert!(request(&"a".repeat(101)).validate().is_err());
+    }
+
     #[test]
     fn test_regenerate_mfa_recovery_codes_response_serialize() {
         let response = RegenerateMfaRecoveryCodesResponse {
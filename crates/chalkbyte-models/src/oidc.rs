@@ -0,0 +1,250 @@
+//! OpenID Connect (SSO) domain models and DTOs.
+//!
+//! A school registers OIDC identity providers, each addressed by a slug in
+//! the login URLs. A successful sign-in is matched to a chalkbyte user by the
+//! provider's subject identifier, which users link to their account while
+//! signed in, and otherwise provisions a new user when the provider allows
+//! it and no account has the email.
+
+use crate::ids::{OidcProviderId, RoleId, SchoolId, UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+/// A school's OIDC identity provider. The client secret is never returned.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct OidcProvider {
+    pub id: OidcProviderId,
+    pub school_id: SchoolId,
+    /// Identifies the provider in `/api/auth/oidc/{provider}/...` URLs
+    pub slug: String,
+    pub name: String,
+    pub issuer: String,
+    pub client_id: String,
+    /// Email domains allowed to sign in; empty allows any
+    pub allowed_domains: Vec<String>,
+    /// Whether unknown users are created on first sign-in
+    pub jit_provisioning: bool,
+    /// Role given to users created on first sign-in
+    pub default_role_id: RoleId,
+    /// Inactive providers cannot be used to sign in
+    pub is_active: bool,
+    pub created_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An OIDC provider together with the callback URL to register with it.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OidcProviderWithCallback {
+    #[serde(flatten)]
+    pub provider: OidcProvider,
+    /// Redirect URI to allow in the identity provider's client settings
+    pub callback_url: String,
+}
+
+/// DTO for registering an OIDC provider.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateOidcProviderDto {
+    /// Lowercase letters, digits, and hyphens; unique across all schools
+    #[validate(length(min = 3, max = 64), custom(function = "validate_slug"))]
+    #[schema(example = "greenfield-google")]
+    pub slug: String,
+    #[validate(length(min = 1, max = 100))]
+    #[schema(example = "Google Workspace")]
+    pub name: String,
+    /// Issuer URL; `{issuer}/.well-known/openid-configuration` must exist
+    #[validate(url, length(max = 2048), custom(function = "validate_issuer"))]
+    #[schema(example = "https://accounts.google.com")]
+    pub issuer: String,
+    #[validate(length(min = 1, max = 255))]
+    pub client_id: String,
+    #[validate(length(min = 1, max = 1024))]
+    pub client_secret: String,
+    /// Email domains allowed to sign in, e.g. `greenfield.edu`
+    #[serde(default)]
+    #[validate(custom(function = "validate_domains"))]
+    pub allowed_domains: Vec<String>,
+    /// Create unknown users on first sign-in
+    #[serde(default)]
+    pub jit_provisioning: bool,
+    /// Role for users created on first sign-in (default: Student)
+    pub default_role_id: Option<RoleId>,
+}
+
+/// DTO for updating an OIDC provider. Omitted fields are left unchanged.
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct UpdateOidcProviderDto {
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    #[validate(url, length(max = 2048), custom(function = "validate_issuer"))]
+    pub issuer: Option<String>,
+    #[validate(length(min = 1, max = 255))]
+    pub client_id: Option<String>,
+    #[validate(length(min = 1, max = 1024))]
+    pub client_secret: Option<String>,
+    #[validate(custom(function = "validate_domains"))]
+    pub allowed_domains: Option<Vec<String>>,
+    pub jit_provisioning: Option<bool>,
+    pub default_role_id: Option<RoleId>,
+    pub is_active: Option<bool>,
+}
+
+/// Where to send the browser to link an SSO identity to the signed-in user.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OidcLinkResponse {
+    /// The identity provider's authorization URL. Signing in there links
+    /// the identity and completes the sign-in as the linking user.
+    pub authorization_url: String,
+}
+
+/// Query parameters the identity provider sends to the callback.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct OidcCallbackParams {
+    /// Authorization code to exchange for tokens
+    pub code: Option<String>,
+    /// The state issued by the authorize endpoint
+    pub state: Option<String>,
+    /// Error code when the user did not authorize
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+fn validate_slug(slug: &str) -> Result<(), ValidationError> {
+    let valid = slug
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !slug.starts_with('-')
+        && !slug.ends_with('-');
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("slug")
+            .with_message("Slugs may only contain lowercase letters, digits, and hyphens".into()))
+    }
+}
+
+/// Issuers must use HTTPS; plain HTTP is accepted for local development only.
+fn validate_issuer(issuer: &str) -> Result<(), ValidationError> {
+    let local = ["http://localhost", "http://127.0.0.1"]
+        .iter()
+        .any(|prefix| issuer.starts_with(prefix));
+    if issuer.starts_with("https://") || local {
+        Ok(())
+    } else {
+        Err(ValidationError::new("issuer").with_message("Issuer URLs must use https".into()))
+    }
+}
+
+fn validate_domains(domains: &[String]) -> Result<(), ValidationError> {
+    let valid = domains.iter().all(|domain| {
+        let domain = normalize_domain(domain);
+        !domain.is_empty()
+            && domain.contains('.')
+            && !domain.contains(['@', '/', ' '])
+            && domain.len() <= 253
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("allowed_domains")
+            .with_message("Allowed domains must look like 'example.edu'".into()))
+    }
+}
+
+/// Lowercases a configured domain and strips a leading `@`.
+#[must_use]
+pub fn normalize_domain(domain: &str) -> String {
+    domain.trim().trim_start_matches('@').to_ascii_lowercase()
+}
+
+/// Whether `email` may sign in through a provider restricted to `allowed`.
+///
+/// An empty list allows any domain. Subdomains are not matched implicitly.
+#[must_use]
+pub fn email_domain_allowed(email: &str, allowed: &[String]) -> bool {
+    if allowed.is_empty() {
+        return true;
+    }
+    let Some((_, domain)) = email.rsplit_once('@') else {
+        return false;
+    };
+    let domain = domain.to_ascii_lowercase();
+    allowed.iter().any(|a| normalize_domain(a) == domain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_dto() -> CreateOidcProviderDto {
+        CreateOidcProviderDto {
+            slug: "greenfield-google".to_string(),
+            name: "Google Workspace".to_string(),
+            issuer: "https://accounts.google.com".to_string(),
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            allowed_domains: vec!["greenfield.edu".to_string()],
+            jit_provisioning: true,
+            default_role_id: None,
+        }
+    }
+
+    #[test]
+    fn test_create_dto_valid() {
+        assert!(create_dto().validate().is_ok());
+    }
+
+    #[test]
+    fn test_create_dto_rejects_bad_slug() {
+        for slug in ["Greenfield", "green field", "-green", "green_field"] {
+            let dto = CreateOidcProviderDto {
+                slug: slug.to_string(),
+                ..create_dto()
+            };
+            assert!(dto.validate().is_err(), "{slug} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_create_dto_rejects_plain_http_issuer() {
+        let dto = CreateOidcProviderDto {
+            issuer: "http://idp.example.com".to_string(),
+            ..create_dto()
+        };
+        assert!(dto.validate().is_err());
+
+        let dto = CreateOidcProviderDto {
+            issuer: "http://localhost:8080/realms/school".to_string(),
+            ..create_dto()
+        };
+        assert!(dto.validate().is_ok());
+    }
+
+    #[test]
+    fn test_create_dto_rejects_bad_domain() {
+        let dto = CreateOidcProviderDto {
+            allowed_domains: vec!["user@greenfield.edu".to_string()],
+            ..create_dto()
+        };
+        assert!(dto.validate().is_err());
+    }
+
+    #[test]
+    fn test_email_domain_allowed() {
+        let allowed = vec![
+            "greenfield.edu".to_string(),
+            "@Staff.Greenfield.edu".to_string(),
+        ];
+
+        assert!(email_domain_allowed("ada@greenfield.edu", &allowed));
+        assert!(email_domain_allowed("ada@GREENFIELD.EDU", &allowed));
+        assert!(email_domain_allowed("bob@staff.greenfield.edu", &allowed));
+        assert!(!email_domain_allowed("eve@evil.com", &allowed));
+        assert!(!email_domain_allowed("eve@mail.greenfield.edu", &allowed));
+        assert!(!email_domain_allowed("not-an-email", &allowed));
+        assert!(email_domain_allowed("anyone@anywhere.org", &[]));
+    }
+}
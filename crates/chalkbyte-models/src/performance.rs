@@ -0,0 +1,56 @@
+//! Slow endpoint models.
+//!
+//! Requests slower than `SLOW_REQUEST_MS` are logged as warnings and counted
+//! per endpoint, so system admins can see which endpoints are slow most often
+//! without searching the logs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Query parameters for the slow endpoint list.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct SlowEndpointParams {
+    /// Maximum number of endpoints to return (1-100, default: 10)
+    pub limit: Option<i64>,
+}
+
+impl SlowEndpointParams {
+    /// Returns the effective limit, clamped to [1, 100].
+    ///
+    /// Defaults to 10 if not specified.
+    #[must_use]
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(10).clamp(1, 100)
+    }
+}
+
+/// Slow requests to one endpoint since the server started.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SlowEndpoint {
+    #[schema(example = "GET")]
+    pub method: String,
+    /// The matched route, with path parameters as placeholders
+    #[schema(example = "/api/students/{id}")]
+    pub route: String,
+    /// Requests that exceeded the threshold
+    #[schema(example = 12)]
+    pub slow_count: u64,
+    /// Slowest of those requests, in milliseconds
+    #[schema(example = 4210)]
+    pub max_ms: u64,
+    /// Average of those requests, in milliseconds
+    #[schema(example = 1830)]
+    pub avg_ms: u64,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+/// The endpoints that were slow most often.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SlowEndpointsResponse {
+    /// Slow request threshold in milliseconds; `None` when detection is off
+    #[schema(example = 1000)]
+    pub threshold_ms: Option<u64>,
+    /// Most frequently slow first
+    pub endpoints: Vec<SlowEndpoint>,
+}
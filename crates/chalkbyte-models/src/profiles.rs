@@ -0,0 +1,71 @@
+//! Linked profile and notification preference models.
+//!
+//! One person can hold several accounts at a school, such as a teacher
+//! account and a guardian account. A school admin links them, and the person
+//! can then switch between them from one login without signing in again.
+//! Each profile keeps its own roles, scope, and notification preferences;
+//! requests made as a linked profile are logged against the profile together
+//! with the account that signed in.
+
+use crate::ids::{SchoolId, UserId};
+use crate::value_types::Email;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// An account a login can act as.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LinkedProfile {
+    pub user_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: Email,
+    pub school_id: Option<SchoolId>,
+    /// Names of the profile's roles
+    pub roles: Vec<String>,
+    /// When the profile was linked; absent for the account that signed in
+    pub linked_at: Option<DateTime<Utc>>,
+}
+
+/// The profiles available to the signed-in login.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MyProfilesResponse {
+    /// Profile the current token acts as
+    pub active_profile_id: UserId,
+    /// The account that signed in, followed by its linked profiles
+    pub profiles: Vec<LinkedProfile>,
+}
+
+/// DTO for linking another account to a user.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct LinkProfileDto {
+    /// Account to link; must belong to the same school
+    pub linked_user_id: UserId,
+}
+
+/// DTO for switching the active profile.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct SwitchProfileDto {
+    /// Profile to act as: a linked profile, or the account that signed in to switch back
+    pub user_id: UserId,
+    /// Stable identifier for the client device; a new switch from the same
+    /// device ends the profile's previous session there
+    #[validate(length(min = 1, max = 128))]
+    pub device_id: Option<String>,
+}
+
+/// A profile's notification preferences.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct NotificationPreferences {
+    /// Receive emailed copies of announcements; they stay in the in-app feed either way
+    pub announcement_emails: bool,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// DTO for updating notification preferences; omitted fields are unchanged.
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct UpdateNotificationPreferencesDto {
+    pub announcement_emails: Option<bool>,
+}
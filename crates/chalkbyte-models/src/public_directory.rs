@@ -0,0 +1,104 @@
+//! Public school directory models and DTOs.
+//!
+//! Schools can opt in to a public profile, served without authentication at
+//! `/api/public/schools/{slug}` for the admissions page. Only the fields in
+//! [`PublicSchoolProfile`] are ever exposed publicly.
+
+use crate::ids::SchoolId;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+/// The public profile of a school, as shown on the admissions page.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PublicSchoolProfile {
+    pub name: String,
+    pub slug: String,
+    pub address: Option<String>,
+    /// Public URL of the school logo, if one is uploaded
+    pub logo_url: Option<String>,
+    /// Whether the school is currently accepting applications
+    pub admissions_open: bool,
+}
+
+/// A school's public profile settings.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PublicProfileSettings {
+    pub school_id: SchoolId,
+    /// URL slug of the public profile
+    pub slug: Option<String>,
+    /// Whether the school is listed in the public directory
+    pub public_profile_enabled: bool,
+    pub admissions_open: bool,
+}
+
+/// DTO for updating a school's public profile settings. Omitted fields are
+/// left unchanged.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdatePublicProfileDto {
+    /// Lowercase letters, digits, and single hyphens (3-100 characters)
+    #[validate(length(min = 3, max = 100), custom(function = "validate_slug"))]
+    pub slug: Option<String>,
+    /// List the school publicly; requires a slug
+    pub public_profile_enabled: Option<bool>,
+    pub admissions_open: Option<bool>,
+}
+
+fn validate_slug(slug: &str) -> Result<(), ValidationError> {
+    let valid = slug.split('-').all(|part| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("slug").with_message(
+            "Slug may only contain lowercase letters, digits, and single hyphens".into(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dto(slug: &str) -> UpdatePublicProfileDto {
+        UpdatePublicProfileDto {
+            slug: Some(slug.to_string()),
+            public_profile_enabled: None,
+            admissions_open: None,
+        }
+    }
+
+    #[test]
+    fn test_slug_validation() {
+        assert!(dto("greenfield-academy").validate().is_ok());
+        assert!(dto("school-42").validate().is_ok());
+
+        assert!(dto("Greenfield").validate().is_err());
+        assert!(dto("green--field").validate().is_err());
+        assert!(dto("-green").validate().is_err());
+        assert!(dto("green field").validate().is_err());
+        assert!(dto("ab").validate().is_err());
+    }
+
+    #[test]
+    fn test_public_profile_serialization() {
+        let profile = PublicSchoolProfile {
+            name: "Greenfield Academy".to_string(),
+            slug: "greenfield-academy".to_string(),
+            address: None,
+            logo_url: None,
+            admissions_open: true,
+        };
+
+        let json = serde_json::to_value(&profile).unwrap();
+        assert_eq!(json["slug"], "greenfield-academy");
+        assert_eq!(json["admissions_open"], true);
+        assert!(json.get("id").is_none());
+    }
+}
@@ -0,0 +1,214 @@
+//! Session report domain models and DTOs.
+//!
+//! A session summary covers a whole academic session of one school:
+//! enrollment movement (admissions, withdrawals, transfers, promotions),
+//! staff changes, and attendance and grade aggregates. Reports are generated
+//! by a background job and kept, so a board pack reads the same figures
+//! every time it is downloaded; asking for a refresh queues a new one.
+
+use crate::groups::attendance_rate;
+use crate::ids::{AcademicSessionId, SchoolId, SessionReportId, TermId, UserId};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Progress of a session report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum SessionReportStatus {
+    /// Waiting for the background job
+    Pending,
+    /// Being generated
+    Running,
+    /// Generated; the summary and PDF are available
+    Completed,
+    /// Stopped by an unexpected error
+    Failed,
+}
+
+/// Output format of a completed session report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionReportFormat {
+    /// The report record with its summary
+    #[default]
+    Json,
+    /// A printable PDF of the summary
+    Pdf,
+}
+
+/// Query parameters for a session summary report.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SessionReportParams {
+    /// Academic session to report on
+    pub session: Uuid,
+    /// `json` (default) or `pdf`
+    #[serde(default)]
+    pub format: SessionReportFormat,
+    /// Queue a new report even if one was already generated
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// Students joining, leaving, and moving during the session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct EnrollmentMovement {
+    /// Student accounts created at the school
+    pub admissions: i64,
+    /// Students deleted from the school
+    pub withdrawals: i64,
+    /// Students moved here from another school
+    pub transfers_in: i64,
+    /// Students moved from here to another school
+    pub transfers_out: i64,
+    /// Students moved between the school's branches within their level
+    pub internal_transfers: i64,
+    pub promoted: i64,
+    pub graduated: i64,
+    pub repeated: i64,
+    /// Students enrolled when the report was generated
+    pub current_enrollment: i64,
+}
+
+/// Staff (admins and teachers) joining and leaving during the session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct StaffChanges {
+    /// Admin or teacher roles assigned to the school's users
+    pub joined: i64,
+    /// Admins and teachers deleted from the school
+    pub left: i64,
+    /// Staff at the school when the report was generated
+    pub current_staff: i64,
+}
+
+/// Attendance marks recorded during the session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SessionAttendance {
+    pub present: i64,
+    pub absent: i64,
+    pub late: i64,
+    pub excused: i64,
+    /// Share of non-excused marks that are present or late (0-100); `None`
+    /// when no marks were recorded
+    #[sqlx(skip)]
+    pub attendance_rate: Option<f64>,
+}
+
+impl SessionAttendance {
+    /// Fill in the attendance rate from the counts.
+    pub fn with_rate(mut self) -> Self {
+        self.attendance_rate = attendance_rate(self.present, self.absent, self.late);
+        self
+    }
+}
+
+/// Assessment results of one term of the session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TermGrades {
+    pub term_id: TermId,
+    pub term_name: String,
+    pub assessments: i64,
+    /// Scores recorded against the term's assessments
+    pub scores: i64,
+    /// Mean score as a percentage of each assessment's maximum; `None`
+    /// without scores
+    pub average_percentage: Option<f64>,
+}
+
+/// Assessment results across the session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SessionGrades {
+    pub assessments: i64,
+    pub scores: i64,
+    /// Mean of every score as a percentage of its assessment's maximum;
+    /// `None` without scores
+    pub average_percentage: Option<f64>,
+    /// Figures for each term, in term order
+    pub terms: Vec<TermGrades>,
+}
+
+impl SessionGrades {
+    /// Total the per-term figures, weighting each term's average by its
+    /// number of scores.
+    pub fn new(terms: Vec<TermGrades>) -> Self {
+        let scores: i64 = terms.iter().map(|t| t.scores).sum();
+        let weighted: f64 = terms
+            .iter()
+            .filter_map(|t| t.average_percentage.map(|avg| avg * t.scores as f64))
+            .sum();
+
+        Self {
+            assessments: terms.iter().map(|t| t.assessments).sum(),
+            scores,
+            average_percentage: (scores > 0).then(|| weighted / scores as f64),
+            terms,
+        }
+    }
+}
+
+/// Summary of a school's academic session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SessionSummary {
+    pub school_id: SchoolId,
+    pub school_name: String,
+    pub academic_session_id: AcademicSessionId,
+    pub session_name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub enrollment: EnrollmentMovement,
+    pub staff: StaffChanges,
+    pub attendance: SessionAttendance,
+    pub grades: SessionGrades,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// One request for a session summary.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SessionReport {
+    pub id: SessionReportId,
+    pub school_id: SchoolId,
+    pub academic_session_id: AcademicSessionId,
+    pub status: SessionReportStatus,
+    pub requested_by: Option<UserId>,
+    /// The report, once completed
+    #[sqlx(json(nullable))]
+    pub summary: Option<SessionSummary>,
+    /// Why generation failed
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(scores: i64, average_percentage: Option<f64>) -> TermGrades {
+        TermGrades {
+            term_id: TermId::new(),
+            term_name: "Term".to_string(),
+            assessments: 2,
+            scores,
+            average_percentage,
+        }
+    }
+
+    #[test]
+    fn test_session_grades_weight_terms_by_scores() {
+        let grades = SessionGrades::new(vec![
+            term(3, Some(80.0)),
+            term(0, None),
+            term(1, Some(40.0)),
+        ]);
+
+        assert_eq!(grades.assessments, 6);
+        assert_eq!(grades.scores, 4);
+        assert_eq!(grades.average_percentage, Some(70.0));
+        assert_eq!(SessionGrades::new(Vec::new()).average_percentage, None);
+    }
+}
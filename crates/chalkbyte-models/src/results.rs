@@ -0,0 +1,215 @@
+//! Term result moderation models.
+//!
+//! The results of a subject in a level and term move through a fixed
+//! workflow: a teacher submits them, a moderator reviews them, and they are
+//! published and finally locked. Scores and assessments can only change while
+//! results are open. Moderators can return results for correction before
+//! they are locked; locked results can only be reopened by unlocking them,
+//! which requires a reason and is recorded like every other transition.
+
+use crate::ids::{
+    LevelId, SchoolId, SubjectId, TermId, TermResultId, TermResultTransitionId, UserId,
+};
+use chalkbyte_core::permissions;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+/// Where the results of a subject are in the moderation workflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum ResultStatus {
+    /// Scores are being entered
+    Open,
+    /// Handed in by the teacher for moderation
+    Submitted,
+    /// Reviewed and ready to publish
+    Moderated,
+    /// Released to students
+    Published,
+    /// Final; can only be reopened by unlocking
+    Locked,
+}
+
+impl ResultStatus {
+    /// Whether scores and assessments may still change.
+    pub fn is_editable(self) -> bool {
+        matches!(self, Self::Open)
+    }
+}
+
+/// A transition in the moderation workflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum ResultAction {
+    /// open → submitted
+    Submit,
+    /// submitted, moderated, or published → open, for correction
+    Return,
+    /// submitted → moderated
+    Moderate,
+    /// moderated → published
+    Publish,
+    /// published → locked
+    Lock,
+    /// locked → open; requires a reason
+    Unlock,
+}
+
+impl ResultAction {
+    /// The status results move to, or `None` if the action does not apply
+    /// to results in `from`.
+    pub fn apply(self, from: ResultStatus) -> Option<ResultStatus> {
+        use ResultStatus::*;
+
+        match (self, from) {
+            (Self::Submit, Open) => Some(Submitted),
+            (Self::Return, Submitted | Moderated | Published) => Some(Open),
+            (Self::Moderate, Submitted) => Some(Moderated),
+            (Self::Publish, Moderated) => Some(Published),
+            (Self::Lock, Published) => Some(Locked),
+            (Self::Unlock, Locked) => Some(Open),
+            _ => None,
+        }
+    }
+
+    /// Permission needed to perform the action.
+    pub fn permission(self) -> &'static str {
+        match self {
+            Self::Submit => permissions::RESULTS_SUBMIT,
+            Self::Return | Self::Moderate => permissions::RESULTS_MODERATE,
+            Self::Publish => permissions::RESULTS_PUBLISH,
+            Self::Lock => permissions::RESULTS_LOCK,
+            Self::Unlock => permissions::RESULTS_UNLOCK,
+        }
+    }
+}
+
+/// The moderation status of a subject's results in a level and term.
+///
+/// Results that have never been transitioned are open and have no `id`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TermResult {
+    pub id: Option<TermResultId>,
+    pub term_id: TermId,
+    pub level_id: LevelId,
+    pub subject_id: SubjectId,
+    pub subject_name: String,
+    pub status: ResultStatus,
+    /// Staff member who made the last transition
+    pub updated_by: Option<UserId>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Query parameters for the result statuses of a level in a term.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct TermResultParams {
+    pub term_id: TermId,
+    pub level_id: LevelId,
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// DTO for moving the results of several subjects through the workflow at
+/// once.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_transition_reason"))]
+pub struct TransitionResultsDto {
+    pub action: ResultAction,
+    pub term_id: TermId,
+    pub level_id: LevelId,
+    /// Subjects to transition (1-100); defaults to every subject with
+    /// assessments in the term and level
+    #[validate(length(min = 1, max = 100))]
+    pub subject_ids: Option<Vec<SubjectId>>,
+    /// Why the action is taken; required to unlock (max 500 characters)
+    #[validate(length(max = 500))]
+    pub reason: Option<String>,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+fn validate_transition_reason(dto: &TransitionResultsDto) -> Result<(), ValidationError> {
+    let has_reason = dto.reason.as_deref().is_some_and(|r| !r.trim().is_empty());
+    if dto.action == ResultAction::Unlock && !has_reason {
+        return Err(ValidationError::new("unlock_requires_reason"));
+    }
+    Ok(())
+}
+
+/// Results the action did not apply to, with their current status.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SkippedResult {
+    pub subject_id: SubjectId,
+    pub status: ResultStatus,
+}
+
+/// Response for a bulk transition.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TransitionResultsResponse {
+    /// Results moved to their new status
+    pub transitioned: Vec<TermResult>,
+    /// Results left alone because the action does not apply to their status
+    pub skipped: Vec<SkippedResult>,
+}
+
+/// A recorded transition of a subject's results.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TermResultTransition {
+    pub id: TermResultTransitionId,
+    pub action: ResultAction,
+    pub from_status: ResultStatus,
+    pub to_status: ResultStatus,
+    pub reason: Option<String>,
+    pub performed_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_actions_follow_the_workflow() {
+        use ResultStatus::*;
+
+        assert_eq!(ResultAction::Submit.apply(Open), Some(Submitted));
+        assert_eq!(ResultAction::Moderate.apply(Submitted), Some(Moderated));
+        assert_eq!(ResultAction::Publish.apply(Moderated), Some(Published));
+        assert_eq!(ResultAction::Lock.apply(Published), Some(Locked));
+        assert_eq!(ResultAction::Unlock.apply(Locked), Some(Open));
+        assert_eq!(ResultAction::Return.apply(Published), Some(Open));
+
+        // No skipping ahead, and locked results only reopen by unlocking
+        assert_eq!(ResultAction::Publish.apply(Submitted), None);
+        assert_eq!(ResultAction::Return.apply(Locked), None);
+        assert_eq!(ResultAction::Submit.apply(Locked), None);
+        assert!(Open.is_editable());
+        assert!(!Submitted.is_editable());
+    }
+
+    #[test]
+    fn test_unlock_requires_reason() {
+        let dto = |action, reason: Option<&str>| TransitionResultsDto {
+            action,
+            term_id: TermId::new(),
+            level_id: LevelId::new(),
+            subject_ids: None,
+            reason: reason.map(str::to_string),
+            school_id: None,
+        };
+
+        assert!(dto(ResultAction::Lock, None).validate().is_ok());
+        assert!(dto(ResultAction::Unlock, None).validate().is_err());
+        assert!(dto(ResultAction::Unlock, Some("  ")).validate().is_err());
+        assert!(
+            dto(ResultAction::Unlock, Some("Wrong exam script marked"))
+                .validate()
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Data retention domain models and DTOs.
+//!
+//! Each school can set how long records in a retention category are kept.
+//! A scheduled job purges whatever is older than that, and every purge that
+//! deletes records is recorded as a purge run. Categories without a policy
+//! use a built-in default period.
+
+use crate::ids::{PurgeRunId, SchoolId, UserId};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// A kind of data subject to retention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum RetentionCategory {
+    /// Entries in the data change feed
+    AuditLogs,
+    /// In-app notifications
+    Notifications,
+    /// Expired or revoked login sessions
+    LoginHistory,
+    /// Items in the recycle bin
+    DeletedRecords,
+}
+
+impl RetentionCategory {
+    /// Every category, in display order.
+    pub const ALL: [RetentionCategory; 4] = [
+        Self::AuditLogs,
+        Self::Notifications,
+        Self::LoginHistory,
+        Self::DeletedRecords,
+    ];
+}
+
+/// How long a school keeps one category of data.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RetentionPolicy {
+    pub category: RetentionCategory,
+    /// Records older than this many days are purged
+    pub retention_days: i32,
+    /// Whether the built-in default applies because the school set no policy
+    pub is_default: bool,
+    pub updated_by: Option<UserId>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// DTO for setting a category's retention period.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateRetentionPolicyDto {
+    /// Days to keep records (1 to 3650)
+    #[validate(range(min = 1, max = 3650))]
+    #[schema(example = 90)]
+    pub retention_days: i32,
+}
+
+/// What the next purge would delete in one category.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PurgePreview {
+    pub category: RetentionCategory,
+    pub retention_days: i32,
+    /// Records older than this are purged
+    pub cutoff: DateTime<Utc>,
+    /// Number of records the purge would delete now
+    pub records: i64,
+}
+
+/// A purge that deleted records in one category of a school.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PurgeRun {
+    pub id: PurgeRunId,
+    pub school_id: SchoolId,
+    pub category: RetentionCategory,
+    /// Retention period in force when the purge ran
+    pub retention_days: i32,
+    pub cutoff: DateTime<Utc>,
+    pub deleted_count: i64,
+    /// User who ran the purge; empty for the scheduled job
+    pub triggered_by: Option<UserId>,
+    pub ran_at: DateTime<Utc>,
+}
+
+/// Query parameters for listing purge runs.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct PurgeRunFilterParams {
+    pub category: Option<RetentionCategory>,
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedPurgeRunsResponse {
+    pub data: Vec<PurgeRun>,
+    pub meta: PaginationMeta,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retention_category_names() {
+        let serialized = serde_json::to_string(&RetentionCategory::LoginHistory).unwrap();
+        assert_eq!(serialized, r#""login_history""#);
+
+        let result: Result<RetentionCategory, _> = serde_json::from_str(r#""grades""#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_update_retention_policy_validation() {
+        let dto = |retention_days| UpdateRetentionPolicyDto { retention_days };
+
+        assert!(dto(90).validate().is_ok());
+        assert!(dto(0).validate().is_err());
+        assert!(dto(3651).validate().is_err());
+    }
+}
@@ -182,6 +182,36 @@ pub struct RoleAssignmentResponse {
     pub role_id: RoleId,
 }
 
+/// DTO for giving one role to many users.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct BulkAssignRoleDto {
+    #[validate(length(
+        min = 1,
+        max = 500,
+        message = "Between 1 and 500 users can be assigned at once"
+    ))]
+    pub user_ids: Vec<UserId>,
+}
+
+/// A user a bulk role assignment did not apply to.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FailedRoleAssignment {
+    pub user_id: UserId,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkRoleAssignmentResponse {
+    pub role_id: RoleId,
+    pub assigned_count: usize,
+    /// Users given the role by this request
+    pub assigned: Vec<UserId>,
+    /// Users who already had the role
+    pub already_assigned: Vec<UserId>,
+    /// Users left unchanged, with the reason
+    pub failed: Vec<FailedRoleAssignment>,
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PermissionCategory {
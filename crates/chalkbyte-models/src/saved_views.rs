@@ -0,0 +1,135 @@
+//! Saved view domain models and DTOs.
+//!
+//! This module contains the data structures for saved views: named
+//! combinations of filters, sort order, and visible columns for a list
+//! endpoint. Views belong to the user who saved them and can be shared with
+//! everyone in the owner's school.
+
+use crate::ids::{SavedViewId, SchoolId, UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// The list endpoint a saved view applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum SavedViewResource {
+    /// `GET /api/users`
+    Users,
+    /// `GET /api/students`
+    Students,
+    /// `GET /api/transport/fees`
+    TransportFees,
+}
+
+/// Sort direction of a saved view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// A saved view.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SavedView {
+    pub id: SavedViewId,
+    pub owner_id: UserId,
+    /// The owner's school; `None` for system admins
+    pub school_id: Option<SchoolId>,
+    pub resource: SavedViewResource,
+    pub name: String,
+    /// Query parameters to apply to the list endpoint
+    #[schema(value_type = Object)]
+    pub filters: Value,
+    pub sort_by: Option<String>,
+    pub sort_order: Option<SortOrder>,
+    /// Columns to show, in order; empty means the client's default
+    pub columns: Vec<String>,
+    /// Whether everyone in the owner's school can use this view
+    pub shared: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for saving a view.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateSavedViewDto {
+    pub resource: SavedViewResource,
+    /// View name, unique per user and resource (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Query parameters to apply to the list endpoint
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub filters: Map<String, Value>,
+    /// Field to sort by (max 50 characters)
+    #[validate(length(max = 50))]
+    pub sort_by: Option<String>,
+    pub sort_order: Option<SortOrder>,
+    /// Columns to show, in order (max 50)
+    #[serde(default)]
+    #[validate(length(max = 50))]
+    pub columns: Vec<String>,
+    /// Share the view with everyone in your school
+    #[serde(default)]
+    pub shared: bool,
+}
+
+/// DTO for updating a saved view.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateSavedViewDto {
+    /// View name (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    /// Replaces the view's filters
+    #[schema(value_type = Option<Object>)]
+    pub filters: Option<Map<String, Value>>,
+    /// Field to sort by (max 50 characters)
+    #[validate(length(max = 50))]
+    pub sort_by: Option<String>,
+    pub sort_order: Option<SortOrder>,
+    /// Replaces the view's columns (max 50)
+    #[validate(length(max = 50))]
+    pub columns: Option<Vec<String>>,
+    pub shared: Option<bool>,
+}
+
+/// Query parameters for listing saved views.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct SavedViewFilterParams {
+    /// Only views for this list endpoint
+    pub resource: Option<SavedViewResource>,
+    /// Include views shared by others in your school (default: true)
+    pub include_shared: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_saved_view_dto_defaults() {
+        let dto: CreateSavedViewDto =
+            serde_json::from_str(r#"{"resource": "transport_fees", "name": "Unpaid"}"#).unwrap();
+        assert_eq!(dto.resource, SavedViewResource::TransportFees);
+        assert!(dto.filters.is_empty());
+        assert!(dto.columns.is_empty());
+        assert!(!dto.shared);
+        assert!(dto.validate().is_ok());
+    }
+
+    #[test]
+    fn test_create_saved_view_dto_validation() {
+        let dto: CreateSavedViewDto =
+            serde_json::from_str(r#"{"resource": "students", "name": "", "sort_order": "desc"}"#)
+                .unwrap();
+        assert_eq!(dto.sort_order, Some(SortOrder::Desc));
+        assert!(dto.validate().is_err());
+    }
+}
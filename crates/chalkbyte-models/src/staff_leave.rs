@@ -0,0 +1,234 @@
+//! Staff leave domain models and DTOs.
+//!
+//! This module contains all data structures related to staff leave and
+//! absence tracking, including leave requests, the approval workflow,
+//! calendar entries, and substitute-teacher hints.
+//!
+//! Leave requests are scoped per school. Staff submit requests for a date
+//! range, and school admins approve or reject them, optionally nominating a
+//! substitute teacher to cover the absence.
+
+use crate::ids::{SchoolId, StaffLeaveId, UserId};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Kind of leave being requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum LeaveType {
+    Annual,
+    Sick,
+    Maternity,
+    Paternity,
+    Study,
+    Compassionate,
+    Unpaid,
+    Other,
+}
+
+/// Approval state of a leave request.
+///
+/// Requests start as `Pending` and move to `Approved` or `Rejected` when an
+/// admin reviews them. Staff may `Cancel` a request that is still pending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum LeaveStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Cancelled,
+}
+
+/// A staff leave request.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct StaffLeaveRequest {
+    /// Unique identifier for the leave request
+    pub id: StaffLeaveId,
+    /// School the staff member belongs to
+    pub school_id: SchoolId,
+    /// Staff member taking leave
+    pub staff_id: UserId,
+    /// Kind of leave
+    pub leave_type: LeaveType,
+    /// First day of leave (inclusive)
+    pub start_date: NaiveDate,
+    /// Last day of leave (inclusive)
+    pub end_date: NaiveDate,
+    /// Optional reason supplied by the staff member
+    pub reason: Option<String>,
+    /// Current approval state
+    pub status: LeaveStatus,
+    /// Teacher nominated to cover the absence
+    pub substitute_id: Option<UserId>,
+    /// Admin who approved or rejected the request
+    pub reviewed_by: Option<UserId>,
+    /// When the request was reviewed
+    pub reviewed_at: Option<DateTime<Utc>>,
+    /// Note left by the reviewer
+    pub review_note: Option<String>,
+    /// Timestamp when the request was created
+    pub created_at: DateTime<Utc>,
+    /// Timestamp when the request was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Leave request joined with the staff member's name.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct StaffLeaveWithStaff {
+    pub id: StaffLeaveId,
+    pub school_id: SchoolId,
+    pub staff_id: UserId,
+    pub staff_first_name: String,
+    pub staff_last_name: String,
+    pub leave_type: LeaveType,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub reason: Option<String>,
+    pub status: LeaveStatus,
+    pub substitute_id: Option<UserId>,
+    pub reviewed_by: Option<UserId>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub review_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for submitting a leave request.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateStaffLeaveDto {
+    /// Kind of leave
+    pub leave_type: LeaveType,
+    /// First day of leave (inclusive)
+    pub start_date: NaiveDate,
+    /// Last day of leave (inclusive, must not be before start_date)
+    pub end_date: NaiveDate,
+    /// Optional reason (max 1000 characters)
+    #[validate(length(max = 1000))]
+    pub reason: Option<String>,
+}
+
+/// DTO for approving a leave request.
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct ApproveStaffLeaveDto {
+    /// Teacher nominated to cover the absence
+    pub substitute_id: Option<UserId>,
+    /// Optional note for the staff member (max 1000 characters)
+    #[validate(length(max = 1000))]
+    pub review_note: Option<String>,
+}
+
+/// DTO for rejecting a leave request.
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct RejectStaffLeaveDto {
+    /// Optional note explaining the rejection (max 1000 characters)
+    #[validate(length(max = 1000))]
+    pub review_note: Option<String>,
+}
+
+/// Query parameters for filtering leave requests.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct StaffLeaveFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by staff member
+    pub staff_id: Option<UserId>,
+    /// Filter by approval state
+    pub status: Option<LeaveStatus>,
+    /// Filter by leave type
+    pub leave_type: Option<LeaveType>,
+    /// Only requests that end on or after this date
+    pub from: Option<NaiveDate>,
+    /// Only requests that start on or before this date
+    pub to: Option<NaiveDate>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing leave requests.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedStaffLeaveResponse {
+    /// List of leave requests
+    pub data: Vec<StaffLeaveWithStaff>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// Query parameters for the leave calendar.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct LeaveCalendarParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// First day of the calendar window (inclusive)
+    pub from: NaiveDate,
+    /// Last day of the calendar window (inclusive)
+    pub to: NaiveDate,
+}
+
+/// An approved absence shown on the school leave calendar.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LeaveCalendarEntry {
+    pub leave_id: StaffLeaveId,
+    pub staff_id: UserId,
+    pub staff_name: String,
+    pub leave_type: LeaveType,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub substitute_id: Option<UserId>,
+    pub substitute_name: Option<String>,
+}
+
+/// A teacher who could cover an absence, used as a timetable hint.
+///
+/// Candidates are teachers in the same school who are not on approved leave
+/// during the requested dates. `cover_count` is the number of other absences
+/// they are already covering in that window, so lower values come first.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SubstituteCandidate {
+    pub user_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub cover_count: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_staff_leave_dto_validation() {
+        let valid_dto = CreateStaffLeaveDto {
+            leave_type: LeaveType::Annual,
+            start_date: NaiveDate::from_ymd_opt(2025, 10, 6).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2025, 10, 10).unwrap(),
+            reason: Some("Family holiday".to_string()),
+        };
+        assert!(valid_dto.validate().is_ok());
+
+        let long_reason = CreateStaffLeaveDto {
+            leave_type: LeaveType::Sick,
+            start_date: NaiveDate::from_ymd_opt(2025, 10, 6).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2025, 10, 6).unwrap(),
+            reason: Some("x".repeat(1001)),
+        };
+        assert!(long_reason.validate().is_err());
+    }
+
+    #[test]
+    fn test_leave_enums_serialize_snake_case() {
+        assert_eq!(
+            serde_json::to_string(&LeaveType::Compassionate).unwrap(),
+            "\"compassionate\""
+        );
+        assert_eq!(
+            serde_json::from_str::<LeaveStatus>("\"cancelled\"").unwrap(),
+            LeaveStatus::Cancelled
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! File storage models and DTOs.
+//!
+//! User avatars and student documents such as birth certificates and
+//! transcripts are uploaded as multipart forms and kept by the configured
+//! storage backend. They are never public: clients get a presigned URL that
+//! downloads the file for a few minutes. Every file of a school counts
+//! towards the school's storage quota.
+
+use crate::ids::{SchoolId, StoredFileId, UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// What a student document is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum DocumentKind {
+    BirthCertificate,
+    Transcript,
+    Other,
+}
+
+impl DocumentKind {
+    /// The name stored in `stored_files.kind`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BirthCertificate => "birth_certificate",
+            Self::Transcript => "transcript",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl std::str::FromStr for DocumentKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "birth_certificate" => Ok(Self::BirthCertificate),
+            "transcript" => Ok(Self::Transcript),
+            "other" => Ok(Self::Other),
+            other => Err(format!(
+                "Unknown document kind '{other}'; expected birth_certificate, transcript, or other"
+            )),
+        }
+    }
+}
+
+/// A document uploaded for a student.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct StudentDocument {
+    pub id: StoredFileId,
+    pub student_id: UserId,
+    pub school_id: Option<SchoolId>,
+    pub kind: DocumentKind,
+    /// Name of the uploaded file, used when it is downloaded
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub uploaded_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A temporary link to download a file.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DownloadUrl {
+    /// Downloads the file without further authentication until `expires_at`
+    pub url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A user's profile picture.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Avatar {
+    pub user_id: UserId,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub uploaded_at: DateTime<Utc>,
+    pub download: DownloadUrl,
+}
+
+/// Multipart form for uploading an avatar.
+#[derive(Debug, ToSchema)]
+pub struct AvatarUploadForm {
+    /// PNG, JPEG, or WebP image of at most 2 MB
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+/// Multipart form for uploading a student document.
+#[derive(Debug, ToSchema)]
+pub struct DocumentUploadForm {
+    pub kind: DocumentKind,
+    /// PDF, PNG, or JPEG file of at most 10 MB
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+/// A school's storage use and quota.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct StorageUsage {
+    pub school_id: SchoolId,
+    /// Bytes taken by the school's avatars and documents
+    pub used_bytes: i64,
+    /// Bytes the school may store
+    pub quota_bytes: i64,
+    /// Whether the quota was set for this school rather than the default
+    pub custom_quota: bool,
+}
+
+/// DTO for setting a school's storage quota.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateStorageQuotaDto {
+    /// Bytes the school may store; omit to use the default quota
+    #[validate(range(min = 0))]
+    #[schema(example = 5368709120_i64)]
+    pub quota_bytes: Option<i64>,
+}
+
+/// Signature of a local storage download link.
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DownloadSignature {
+    /// When the link expires, in seconds since the epoch
+    pub expires: i64,
+    pub signature: String,
+}
@@ -0,0 +1,79 @@
+//! Student ID card models and DTOs.
+//!
+//! A student card carries a signed code that identifies one student in one
+//! school. The code is rendered as a QR code for printing on ID cards and is
+//! scanned back at the attendance kiosk and the library desk. Cards are
+//! generated on demand and never stored; a card stops verifying once the
+//! student leaves the school.
+
+use crate::ids::{BranchId, LevelId, SchoolId, UserId};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// A student's printable ID card.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StudentCard {
+    pub student_id: UserId,
+    pub school_id: SchoolId,
+    pub first_name: String,
+    pub last_name: String,
+    /// Signed card code encoded in the QR code
+    pub code: String,
+    /// Base64-encoded PNG of the QR code
+    pub qr_code_base64: String,
+}
+
+/// DTO for verifying a scanned card code.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct VerifyStudentCardDto {
+    /// Card code read from the QR code
+    #[validate(length(min = 1, max = 200))]
+    pub code: String,
+}
+
+/// The student a verified card belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct VerifiedStudentCard {
+    pub student_id: UserId,
+    pub school_id: SchoolId,
+    pub first_name: String,
+    pub last_name: String,
+    pub level_id: Option<LevelId>,
+    pub branch_id: Option<BranchId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_student_card_dto_validation() {
+        let dto = VerifyStudentCardDto {
+            code: String::new(),
+        };
+        assert!(dto.validate().is_err());
+
+        let dto = VerifyStudentCardDto {
+            code: "a".repeat(201),
+        };
+        assert!(dto.validate().is_err());
+    }
+
+    #[test]
+    fn test_student_card_serializes_qr_code() {
+        let card = StudentCard {
+            student_id: UserId::new(),
+            school_id: SchoolId::new(),
+            first_name: "Ada".to_string(),
+            last_name: "Lovelace".to_string(),
+            code: "CB1.abc".to_string(),
+            qr_code_base64: "iVBORw0KGgo=".to_string(),
+        };
+
+        let json = serde_json::to_value(&card).unwrap();
+        assert_eq!(json["qr_code_base64"], "iVBORw0KGgo=");
+        assert_eq!(json["code"], "CB1.abc");
+    }
+}
@@ -3,9 +3,17 @@
 //! This module contains all data structures related to student management,
 //! including student entities, request/response DTOs, and filtering parameters.
 
+use crate::custom_fields::CustomFieldValues;
 use crate::ids::{SchoolId, UserId};
+use crate::imports::ImportIssueCode;
 use crate::value_types::Email;
+use chalkbyte_core::visibility::hide_in;
+use chalkbyte_core::{
+    CursorPage, CursorPaginationParams, FieldAccess, FieldVisibility, LiteView, RestrictedField,
+    SortParams, permissions,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sqlx::FromRow;
 use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
@@ -26,16 +34,55 @@ pub struct PaginatedStudentsResponse {
     pub meta: PaginationMeta,
 }
 
+/// Students list in the pagination mode the client asked for.
+#[derive(Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum StudentListResponse {
+    Paged(PaginatedStudentsResponse),
+    Cursor(CursorPage<Student>),
+}
+
+impl FieldVisibility for StudentListResponse {
+    fn hide_fields(value: &mut Value, access: &FieldAccess) {
+        hide_in::<Vec<Student>>(value, "data", access);
+    }
+}
+
 /// Query parameters for filtering and paginating students.
 #[derive(Deserialize, Debug, IntoParams)]
 pub struct QueryParams {
     pub page: Option<i64>,
     pub limit: Option<i64>,
+    /// Switches to cursor pagination, newest students first: empty for the
+    /// first page, then the previous page's `next_cursor`. `page` is ignored.
+    pub cursor: Option<String>,
     /// Required for system admins to specify which school's students to fetch
     pub school_id: Option<SchoolId>,
+    /// JSON object of custom field values students must match, e.g. `{"house":"red"}`
+    pub custom_fields: Option<String>,
+    /// Comma-separated fields to sort by, `-` prefix for descending, e.g.
+    /// `last_name,-created_at` (default `last_name,first_name`). Sortable:
+    /// first_name, last_name, email, date_of_birth, grade_level, created_at.
+    /// Ignored with `cursor`.
+    pub sort: Option<String>,
 }
 
 impl QueryParams {
+    /// Cursor pagination parameters, if the client opted into cursor mode.
+    pub fn cursor_pagination(&self) -> Option<CursorPaginationParams> {
+        self.cursor.as_ref().map(|cursor| CursorPaginationParams {
+            cursor: Some(cursor.clone()),
+            limit: self.limit,
+        })
+    }
+
+    /// The requested sort order.
+    pub fn sort_params(&self) -> SortParams {
+        SortParams {
+            sort: self.sort.clone(),
+        }
+    }
+
     /// Get the page number, defaulting to 1 if not specified.
     pub fn page(&self) -> i64 {
         self.page.unwrap_or(1).max(1)
@@ -61,18 +108,88 @@ pub struct Student {
     pub id: UserId,
     pub first_name: String,
     pub last_name: String,
+    /// Omitted without the `contact_details:read` permission
     pub email: Email,
     pub school_id: Option<SchoolId>,
     #[sqlx(default)]
     pub date_of_birth: Option<chrono::NaiveDate>,
     #[sqlx(default)]
     pub grade_level: Option<String>,
+    /// Values for the school's student custom fields, keyed by field key
+    #[schema(value_type = Object)]
+    pub custom_fields: serde_json::Value,
     #[sqlx(default)]
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     #[sqlx(default)]
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+impl FieldVisibility for Student {
+    const RESTRICTED: &'static [RestrictedField] = &[
+        RestrictedField::new("email", permissions::CONTACT_DETAILS_READ),
+        RestrictedField::new("date_of_birth", permissions::CONTACT_DETAILS_READ),
+    ];
+}
+
+/// Lite view of a student for mobile clients.
+///
+/// Drops custom fields, date of birth, and timestamps.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct StudentLite {
+    pub id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub school_id: Option<SchoolId>,
+    pub grade_level: Option<String>,
+}
+
+impl LiteView for Student {
+    type Lite = StudentLite;
+
+    fn into_lite(self) -> StudentLite {
+        StudentLite {
+            id: self.id,
+            first_name: self.first_name,
+            last_name: self.last_name,
+            school_id: self.school_id,
+            grade_level: self.grade_level,
+        }
+    }
+}
+
+/// Paginated response containing lite students.
+#[derive(Serialize, ToSchema)]
+pub struct PaginatedStudentLitesResponse {
+    pub data: Vec<StudentLite>,
+    pub meta: PaginationMeta,
+}
+
+/// Lite students list in the pagination mode the client asked for.
+#[derive(Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum StudentLiteListResponse {
+    Paged(PaginatedStudentLitesResponse),
+    Cursor(CursorPage<StudentLite>),
+}
+
+impl FieldVisibility for StudentLite {}
+
+impl FieldVisibility for StudentLiteListResponse {}
+
+impl LiteView for StudentListResponse {
+    type Lite = StudentLiteListResponse;
+
+    fn into_lite(self) -> StudentLiteListResponse {
+        match self {
+            Self::Paged(page) => StudentLiteListResponse::Paged(PaginatedStudentLitesResponse {
+                data: page.data.into_lite(),
+                meta: page.meta,
+            }),
+            Self::Cursor(page) => StudentLiteListResponse::Cursor(page.into_lite()),
+        }
+    }
+}
+
 /// DTO for creating a new student.
 ///
 /// Used by admins to create students within their school scope.
@@ -88,6 +205,9 @@ pub struct CreateStudentDto {
     pub date_of_birth: Option<chrono::NaiveDate>,
     #[validate(length(max = 10))]
     pub grade_level: Option<String>,
+    /// Values for the school's student custom fields; required fields must be given
+    #[schema(value_type = Option<Object>)]
+    pub custom_fields: Option<CustomFieldValues>,
     /// Required for system admins to specify which school to create the student in
     pub school_id: Option<SchoolId>,
 }
@@ -107,6 +227,98 @@ pub struct UpdateStudentDto {
     pub date_of_birth: Option<chrono::NaiveDate>,
     #[validate(length(max = 10))]
     pub grade_level: Option<String>,
+    /// Custom field values to set; `null` clears an optional field
+    #[schema(value_type = Option<Object>)]
+    pub custom_fields: Option<CustomFieldValues>,
+}
+
+/// Query parameters for a CSV student import.
+#[derive(Deserialize, Debug, IntoParams, ToSchema)]
+pub struct StudentImportParams {
+    /// Validate the file and report problems without importing anything
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Required for system admins to specify which school to import into
+    pub school_id: Option<SchoolId>,
+}
+
+/// Multipart form for a CSV student import.
+///
+/// The CSV needs a header row with at least `first_name`, `last_name`, and
+/// `email`. Optional columns are `date_of_birth` (YYYY-MM-DD),
+/// `grade_level`, `level` and `branch` (matched by name within the school),
+/// and `password`. Unknown columns are ignored.
+#[derive(Debug, ToSchema)]
+pub struct StudentImportForm {
+    /// The CSV file
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+    /// Password for rows that leave `password` empty (min 8 characters)
+    pub default_password: Option<String>,
+}
+
+/// One data row of a student import CSV, before validation.
+#[derive(Deserialize, Debug, Clone)]
+pub struct StudentImportRow {
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub date_of_birth: Option<String>,
+    pub grade_level: Option<String>,
+    pub level: Option<String>,
+    pub branch: Option<String>,
+    pub password: Option<String>,
+}
+
+impl StudentImportRow {
+    /// Columns every import file must have.
+    pub const REQUIRED_COLUMNS: [&'static str; 3] = ["first_name", "last_name", "email"];
+
+    /// Most data rows accepted in one import.
+    pub const MAX_ROWS: usize = 1000;
+}
+
+/// A problem with one row of a student import.
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct StudentImportRowError {
+    /// Line number in the file, counting the header as line 1
+    pub line: usize,
+    /// Column the problem is in, if it is tied to one
+    pub column: Option<String>,
+    pub code: ImportIssueCode,
+    pub message: String,
+}
+
+/// Outcome of a CSV student import.
+///
+/// Imports are all or nothing: when any row has errors, no students are
+/// created and `imported_count` is zero.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct StudentImportReport {
+    pub dry_run: bool,
+    /// Data rows in the file
+    pub total_rows: usize,
+    /// Rows that passed validation
+    pub valid_rows: usize,
+    /// Students created; always zero for dry runs
+    pub imported_count: usize,
+    pub errors: Vec<StudentImportRowError>,
+}
+
+/// One row of a student CSV export.
+#[derive(Debug, Serialize, FromRow)]
+pub struct StudentExportRow {
+    pub id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub date_of_birth: Option<chrono::NaiveDate>,
+    pub grade_level: Option<String>,
+    pub level: Option<String>,
+    pub branch: Option<String>,
+    /// Custom field values as a JSON object
+    pub custom_fields: Option<String>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[cfg(test)]
@@ -118,7 +330,10 @@ mod tests {
         let params = QueryParams {
             page: None,
             limit: None,
+            cursor: None,
             school_id: None,
+            custom_fields: None,
+            sort: None,
         };
         assert_eq!(params.page(), 1);
         assert_eq!(params.limit(), 10);
@@ -130,7 +345,10 @@ mod tests {
         let params = QueryParams {
             page: Some(3),
             limit: Some(25),
+            cursor: None,
             school_id: None,
+            custom_fields: None,
+            sort: None,
         };
         assert_eq!(params.page(), 3);
         assert_eq!(params.limit(), 25);
@@ -142,12 +360,30 @@ mod tests {
         let params = QueryParams {
             page: Some(-5),
             limit: Some(200),
+            cursor: None,
             school_id: None,
+            custom_fields: None,
+            sort: None,
         };
         assert_eq!(params.page(), 1); // Min page is 1
         assert_eq!(params.limit(), 100); // Max limit is 100
     }
 
+    #[test]
+    fn test_query_params_cursor_mode() {
+        let params = QueryParams {
+            page: Some(2),
+            limit: Some(25),
+            cursor: Some(String::new()),
+            school_id: None,
+            custom_fields: None,
+            sort: None,
+        };
+        let cursor = params.cursor_pagination().unwrap();
+        assert_eq!(cursor.limit(), 25);
+        assert_eq!(cursor.after().unwrap(), None);
+    }
+
     #[test]
     fn test_create_student_dto_validation() {
         let valid_dto = CreateStudentDto {
@@ -158,6 +394,7 @@ mod tests {
             date_of_birth: None,
             grade_level: Some("10".to_string()),
             school_id: None,
+            custom_fields: None,
         };
         assert!(valid_dto.validate().is_ok());
     }
@@ -172,6 +409,7 @@ mod tests {
             date_of_birth: None,
             grade_level: None,
             school_id: None,
+            custom_fields: None,
         };
         assert!(invalid_dto.validate().is_err());
     }
@@ -186,6 +424,7 @@ mod tests {
             date_of_birth: None,
             grade_level: None,
             school_id: None,
+            custom_fields: None,
         };
         assert!(invalid_dto.validate().is_err());
     }
@@ -200,6 +439,7 @@ mod tests {
             date_of_birth: None,
             grade_level: None,
             school_id: None,
+            custom_fields: None,
         };
         assert!(invalid_dto.validate().is_err());
     }
@@ -213,6 +453,7 @@ mod tests {
             password: None,
             date_of_birth: None,
             grade_level: None,
+            custom_fields: None,
         };
         assert!(valid_dto.validate().is_ok());
     }
@@ -226,6 +467,7 @@ mod tests {
             password: None,
             date_of_birth: None,
             grade_level: None,
+            custom_fields: None,
         };
         assert!(empty_dto.validate().is_ok());
     }
@@ -239,6 +481,7 @@ mod tests {
             password: Some("short".to_string()),
             date_of_birth: None,
             grade_level: None,
+            custom_fields: None,
         };
         assert!(invalid_dto.validate().is_err());
     }
@@ -252,6 +495,7 @@ mod tests {
             password: None,
             date_of_birth: None,
             grade_level: Some("x".repeat(11)),
+            custom_fields: None,
         };
         assert!(invalid_dto.validate().is_err());
     }
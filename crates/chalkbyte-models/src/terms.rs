@@ -8,7 +8,7 @@
 //! can define their own term structure (semesters, trimesters, quarters, etc.).
 
 use crate::ids::{AcademicSessionId, SchoolId, TermId};
-use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chalkbyte_core::{PaginationMeta, PaginationParams, Versioned};
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
@@ -40,6 +40,12 @@ pub struct Term {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Versioned for Term {
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
+
 /// Term with additional session and school information.
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct TermWithSessionInfo {
@@ -71,13 +77,41 @@ pub struct TermWithSessionInfo {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Versioned for TermWithSessionInfo {
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
+
+impl From<TermWithSessionInfo> for Term {
+    fn from(term: TermWithSessionInfo) -> Self {
+        Self {
+            id: term.id,
+            name: term.name,
+            description: term.description,
+            academic_session_id: term.academic_session_id,
+            start_date: term.start_date,
+            end_date: term.end_date,
+            sequence: term.sequence,
+            is_current: term.is_current,
+            created_at: term.created_at,
+            updated_at: term.updated_at,
+        }
+    }
+}
+
 /// DTO for creating a new term.
 #[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
 pub struct CreateTermDto {
     /// Name of the term (1-100 characters)
-    #[validate(length(min = 1, max = 100))]
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
     pub name: String,
     /// Optional description of the term
+    #[validate(length(max = 2000, message = "Description must not exceed 2000 characters"))]
     pub description: Option<String>,
     /// Academic session ID - can be provided in URL path instead
     pub academic_session_id: Option<AcademicSessionId>,
@@ -86,7 +120,7 @@ pub struct CreateTermDto {
     /// End date of the term (must be after start_date)
     pub end_date: NaiveDate,
     /// Order/sequence of the term within the session (optional, auto-calculated if not provided)
-    #[validate(range(min = 1))]
+    #[validate(range(min = 1, message = "Sequence must be at least 1"))]
     pub sequence: Option<i32>,
 }
 
@@ -94,16 +128,21 @@ pub struct CreateTermDto {
 #[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
 pub struct UpdateTermDto {
     /// Updated name of the term (1-100 characters)
-    #[validate(length(min = 1, max = 100))]
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name must be between 1 and 100 characters"
+    ))]
     pub name: Option<String>,
     /// Updated description of the term
+    #[validate(length(max = 2000, message = "Description must not exceed 2000 characters"))]
     pub description: Option<String>,
     /// Updated start date of the term
     pub start_date: Option<NaiveDate>,
     /// Updated end date of the term
     pub end_date: Option<NaiveDate>,
     /// Updated sequence of the term within the session
-    #[validate(range(min = 1))]
+    #[validate(range(min = 1, message = "Sequence must be at least 1"))]
     pub sequence: Option<i32>,
 }
 
@@ -0,0 +1,59 @@
+//! Request trace models.
+//!
+//! Every write request and every failed request leaves a log summary keyed
+//! by its request ID, and change events record the request that caused
+//! them. A request trace gathers both so support can see what happened to a
+//! request ID quoted from an error response or email.
+
+use crate::changes::ChangeEvent;
+use crate::ids::{SchoolId, UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+/// Log summary of one handled request.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct RequestLog {
+    #[schema(example = "POST")]
+    pub method: String,
+    #[schema(example = "/api/students")]
+    pub path: String,
+    #[schema(example = 500)]
+    pub status: i16,
+    pub latency_ms: i64,
+    /// Caller, if the request carried a valid token; the active profile
+    /// when the caller had switched to a linked profile
+    pub user_id: Option<UserId>,
+    /// Account that signed in, when the request was made as a linked profile
+    pub login_user_id: Option<UserId>,
+    pub school_id: Option<SchoolId>,
+    /// Full error message, including server error details hidden from the client
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A request log summary waiting to be recorded.
+#[derive(Debug, Clone)]
+pub struct NewRequestLog {
+    pub request_id: String,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub latency_ms: i64,
+    pub user_id: Option<UserId>,
+    pub login_user_id: Option<UserId>,
+    pub school_id: Option<SchoolId>,
+    pub error: Option<String>,
+}
+
+/// Everything recorded for a request ID.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RequestTrace {
+    pub request_id: String,
+    /// Log summaries, oldest first; a client retrying with the same ID
+    /// leaves one per attempt
+    pub requests: Vec<RequestLog>,
+    /// Audit entries for the records the request changed, in commit order
+    pub changes: Vec<ChangeEvent>,
+}
@@ -0,0 +1,352 @@
+//! Transport domain models and DTOs.
+//!
+//! This module contains all data structures for school transport: vehicles and
+//! their drivers, bus routes with ordered stops, per-term student route
+//! assignments, driver manifests, and transport fee charges.
+//!
+//! Routes may carry an optional per-term fee. Fee charges are generated on
+//! demand for the students assigned to a route in a term, and generation is
+//! idempotent so it can be re-run after late assignments.
+
+use crate::ids::{
+    RouteAssignmentId, RouteStopId, SchoolId, TermId, TransportFeeChargeId, TransportRouteId,
+    UserId, VehicleId,
+};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// A school vehicle.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Vehicle {
+    /// Unique identifier for the vehicle
+    pub id: VehicleId,
+    /// School that operates the vehicle
+    pub school_id: SchoolId,
+    /// Registration/plate number, unique within the school
+    pub registration_number: String,
+    /// Optional description (make, model, colour)
+    pub description: Option<String>,
+    /// Number of seats
+    pub capacity: i32,
+    /// Staff member who drives the vehicle
+    pub driver_id: Option<UserId>,
+    /// Timestamp when the vehicle was created
+    pub created_at: DateTime<Utc>,
+    /// Timestamp when the vehicle was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for registering a vehicle.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateVehicleDto {
+    /// Registration/plate number (1-20 characters)
+    #[validate(length(min = 1, max = 20))]
+    pub registration_number: String,
+    /// Optional description (max 255 characters)
+    #[validate(length(max = 255))]
+    pub description: Option<String>,
+    /// Number of seats (1-200)
+    #[validate(range(min = 1, max = 200))]
+    pub capacity: i32,
+    /// Staff member who drives the vehicle
+    pub driver_id: Option<UserId>,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// DTO for updating a vehicle.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateVehicleDto {
+    /// Updated registration number (1-20 characters)
+    #[validate(length(min = 1, max = 20))]
+    pub registration_number: Option<String>,
+    /// Updated description (max 255 characters)
+    #[validate(length(max = 255))]
+    pub description: Option<String>,
+    /// Updated capacity (1-200)
+    #[validate(range(min = 1, max = 200))]
+    pub capacity: Option<i32>,
+    /// Updated driver
+    pub driver_id: Option<UserId>,
+}
+
+/// Query parameters for listing vehicles.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct VehicleQueryParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// A bus route.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TransportRoute {
+    pub id: TransportRouteId,
+    pub school_id: SchoolId,
+    /// Route name, unique within the school
+    pub name: String,
+    pub description: Option<String>,
+    /// Vehicle serving the route
+    pub vehicle_id: Option<VehicleId>,
+    /// Per-term fee in minor currency units; `None` if the route is free
+    pub fee_amount: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO for creating a route.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateTransportRouteDto {
+    /// Route name (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Optional description
+    pub description: Option<String>,
+    /// Vehicle serving the route
+    pub vehicle_id: Option<VehicleId>,
+    /// Per-term fee in minor currency units
+    #[validate(range(min = 0))]
+    pub fee_amount: Option<i64>,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// DTO for updating a route.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct UpdateTransportRouteDto {
+    /// Updated name (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    /// Updated description
+    pub description: Option<String>,
+    /// Updated vehicle
+    pub vehicle_id: Option<VehicleId>,
+    /// Updated per-term fee in minor currency units
+    #[validate(range(min = 0))]
+    pub fee_amount: Option<i64>,
+}
+
+/// Query parameters for listing routes.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct TransportRouteFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by vehicle
+    pub vehicle_id: Option<VehicleId>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing routes.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedTransportRoutesResponse {
+    /// List of routes
+    pub data: Vec<TransportRoute>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// A stop on a route.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct RouteStop {
+    pub id: RouteStopId,
+    pub route_id: TransportRouteId,
+    pub name: String,
+    /// Position of the stop along the route, starting at 1
+    pub sequence: i32,
+    #[schema(value_type = Option<String>, format = "time", example = "07:15:00")]
+    pub pickup_time: Option<NaiveTime>,
+    #[schema(value_type = Option<String>, format = "time", example = "15:30:00")]
+    pub dropoff_time: Option<NaiveTime>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO for adding a stop to a route.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateRouteStopDto {
+    /// Stop name (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Position along the route (unique per route, starting at 1)
+    #[validate(range(min = 1))]
+    pub sequence: i32,
+    /// Morning pickup time
+    #[schema(value_type = Option<String>, format = "time", example = "07:15:00")]
+    pub pickup_time: Option<NaiveTime>,
+    /// Afternoon drop-off time
+    #[schema(value_type = Option<String>, format = "time", example = "15:30:00")]
+    pub dropoff_time: Option<NaiveTime>,
+}
+
+/// A student's route assignment for a term.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct RouteAssignment {
+    pub id: RouteAssignmentId,
+    pub route_id: TransportRouteId,
+    pub stop_id: Option<RouteStopId>,
+    pub student_id: UserId,
+    pub term_id: TermId,
+    pub assigned_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Route assignment with student, route, and stop names.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct RouteAssignmentWithDetails {
+    pub id: RouteAssignmentId,
+    pub route_id: TransportRouteId,
+    pub route_name: String,
+    pub stop_id: Option<RouteStopId>,
+    pub stop_name: Option<String>,
+    pub student_id: UserId,
+    pub student_first_name: String,
+    pub student_last_name: String,
+    pub term_id: TermId,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO for assigning a student to a route.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct AssignRouteDto {
+    /// Route the student rides
+    pub route_id: TransportRouteId,
+    /// Stop the student boards at (must be on the route)
+    pub stop_id: Option<RouteStopId>,
+    /// Student being assigned
+    pub student_id: UserId,
+    /// Term the assignment applies to (defaults to the current term)
+    pub term_id: Option<TermId>,
+}
+
+/// Query parameters for listing route assignments.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct RouteAssignmentFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by route
+    pub route_id: Option<TransportRouteId>,
+    /// Filter by term
+    pub term_id: Option<TermId>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing route assignments.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedRouteAssignmentsResponse {
+    /// List of assignments
+    pub data: Vec<RouteAssignmentWithDetails>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// Query parameters for term-scoped transport lookups.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct TransportTermParams {
+    /// Term (defaults to the current term)
+    pub term_id: Option<TermId>,
+}
+
+/// A student on a driver's manifest.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ManifestStudent {
+    pub student_id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+}
+
+/// A stop on a driver's manifest with the students boarding there.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ManifestStop {
+    pub stop: RouteStop,
+    pub students: Vec<ManifestStudent>,
+}
+
+/// Everything a driver needs to run a route for a term.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RouteManifest {
+    pub route: TransportRoute,
+    pub vehicle: Option<Vehicle>,
+    pub term_id: TermId,
+    /// Stops in route order
+    pub stops: Vec<ManifestStop>,
+    /// Assigned students who have not been given a stop
+    pub unassigned_stop: Vec<ManifestStudent>,
+    /// Total number of students on the route
+    pub total_students: i64,
+}
+
+/// A generated transport fee charge.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TransportFeeCharge {
+    pub id: TransportFeeChargeId,
+    pub route_id: TransportRouteId,
+    pub student_id: UserId,
+    pub term_id: TermId,
+    /// Amount in minor currency units
+    pub amount: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Result of generating transport fees for a route.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GenerateTransportFeesResponse {
+    pub route_id: TransportRouteId,
+    pub term_id: TermId,
+    /// Number of new charges created
+    pub created: i64,
+    /// Number of assigned students who were already charged
+    pub already_charged: i64,
+}
+
+/// Query parameters for listing transport fee charges.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct TransportFeeFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Filter by route
+    pub route_id: Option<TransportRouteId>,
+    /// Filter by term
+    pub term_id: Option<TermId>,
+    /// Filter by student
+    pub student_id: Option<UserId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_route_stop_dto_validation() {
+        let valid_dto = CreateRouteStopDto {
+            name: "Market Square".to_string(),
+            sequence: 1,
+            pickup_time: NaiveTime::from_hms_opt(7, 15, 0),
+            dropoff_time: None,
+        };
+        assert!(valid_dto.validate().is_ok());
+
+        let zero_sequence = CreateRouteStopDto {
+            sequence: 0,
+            ..valid_dto
+        };
+        assert!(zero_sequence.validate().is_err());
+    }
+
+    #[test]
+    fn test_create_vehicle_dto_validation() {
+        let dto = CreateVehicleDto {
+            registration_number: String::new(),
+            description: None,
+            capacity: 30,
+            driver_id: None,
+            school_id: None,
+        };
+        assert!(dto.validate().is_err());
+    }
+}
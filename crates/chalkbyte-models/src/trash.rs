@@ -0,0 +1,78 @@
+//! Recycle bin domain models and DTOs.
+//!
+//! Deleted students, users, and branches are moved to their school's recycle
+//! bin as a snapshot of the deleted row. An item stays there until it is
+//! restored, purged by an admin, or purged automatically once the retention
+//! period has passed.
+
+use crate::ids::{SchoolId, TrashItemId, UserId};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// The kind of record held in the recycle bin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum TrashItemType {
+    Students,
+    Users,
+    Branches,
+}
+
+/// A deleted record waiting in the recycle bin.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TrashItem {
+    pub id: TrashItemId,
+    pub school_id: SchoolId,
+    #[serde(rename = "type")]
+    pub item_type: TrashItemType,
+    /// ID of the deleted record; restoring brings it back under the same ID
+    pub entity_id: Uuid,
+    /// Display name of the deleted record
+    pub label: String,
+    pub deleted_by: Option<UserId>,
+    /// Full name of the user who deleted the record
+    pub deleted_by_name: Option<String>,
+    pub deleted_at: DateTime<Utc>,
+    /// When the item will be purged automatically
+    pub purge_at: DateTime<Utc>,
+}
+
+/// Query parameters for listing the recycle bin.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct TrashFilterParams {
+    /// Only items of this type
+    #[serde(rename = "type")]
+    pub item_type: Option<TrashItemType>,
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaginatedTrashResponse {
+    pub data: Vec<TrashItem>,
+    pub meta: PaginationMeta,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trash_filter_params_type() {
+        let params: TrashFilterParams =
+            serde_json::from_str(r#"{"type": "branches", "limit": "5"}"#).unwrap();
+        assert_eq!(params.item_type, Some(TrashItemType::Branches));
+        assert_eq!(params.pagination.limit(), 5);
+    }
+
+    #[test]
+    fn test_trash_item_type_rejects_unknown() {
+        let result: Result<TrashItemType, _> = serde_json::from_str(r#""levels""#);
+        assert!(result.is_err());
+    }
+}
@@ -3,15 +3,21 @@
 //! This module contains all data structures related to user management,
 //! including user entities, request/response DTOs, and system role definitions.
 
+use crate::custom_fields::CustomFieldValues;
 use crate::ids::{BranchId, LevelId, RoleId, SchoolId, UserId};
 use crate::value_types::Email;
 use chalkbyte_core::serde::deserialize_optional_uuid;
-use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chalkbyte_core::{
+    CursorPage, CursorPaginationParams, ErrorCode, FilterParams, LiteView, PaginationMeta,
+    PaginationParams, SortParams, Versioned,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use utoipa::ToSchema;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 /// A user in the system.
 ///
@@ -29,10 +35,19 @@ pub struct User {
     pub branch_id: Option<BranchId>,
     pub date_of_birth: Option<chrono::NaiveDate>,
     pub grade_level: Option<String>,
+    /// Custom field values keyed by field key
+    #[schema(value_type = Object)]
+    pub custom_fields: serde_json::Value,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl Versioned for User {
+    fn updated_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.updated_at
+    }
+}
+
 /// DTO for creating a new user.
 ///
 /// Used by admins to create users within their scope. School admins
@@ -40,17 +55,75 @@ pub struct User {
 /// can create users in any school.
 #[derive(Deserialize, Debug, Clone, Validate, ToSchema)]
 pub struct CreateUserDto {
-    #[validate(length(min = 1))]
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "First name must be between 1 and 100 characters"
+    ))]
     pub first_name: String,
-    #[validate(length(min = 1))]
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Last name must be between 1 and 100 characters"
+    ))]
     pub last_name: String,
     pub email: Email,
-    #[validate(length(min = 8))]
+    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
     pub password: String,
     /// Role IDs to assign to the user. If empty, no roles are assigned.
     #[serde(default)]
     pub role_ids: Vec<RoleId>,
     pub school_id: Option<SchoolId>,
+    /// Values for the school's user custom fields; required fields must be given
+    #[schema(value_type = Option<Object>)]
+    pub custom_fields: Option<CustomFieldValues>,
+}
+
+/// Most users one bulk create request may contain.
+pub const MAX_BULK_USERS: usize = 500;
+
+/// DTO for creating many users in one request.
+///
+/// Holds 1 to [`MAX_BULK_USERS`] users. Items that break a constraint fail
+/// on their own; a body that does not parse is rejected whole.
+#[derive(Deserialize, Debug, Clone, ToSchema)]
+pub struct BulkCreateUsersDto {
+    /// Users to create; each one is checked and created or rejected on its own
+    pub users: Vec<CreateUserDto>,
+}
+
+/// Whether an item of a bulk request was applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkItemStatus {
+    Created,
+    Failed,
+}
+
+/// Outcome of one user in a bulk create request.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BulkUserResult {
+    /// Position of the user in the request
+    pub index: usize,
+    pub email: String,
+    pub status: BulkItemStatus,
+    /// The new user, when created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<User>,
+    /// Why the user was not created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<ErrorCode>,
+}
+
+/// Response for a bulk create request, with one result per requested user
+/// in request order.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BulkCreateUsersResponse {
+    pub created_count: usize,
+    pub failed_count: usize,
+    pub results: Vec<BulkUserResult>,
 }
 
 /// A school entity.
@@ -64,6 +137,10 @@ pub struct School {
     pub address: Option<String>,
     /// Storage key for the school logo (if uploaded)
     pub logo_path: Option<String>,
+    /// IANA timezone name dates are read in, e.g. `Africa/Lagos`
+    pub timezone: String,
+    /// BCP 47 language tag, e.g. `en-NG`
+    pub locale: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
@@ -71,12 +148,84 @@ pub struct School {
 /// DTO for creating a new school.
 ///
 /// Only system admins can create schools.
-#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
 pub struct CreateSchoolDto {
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "Name must be between 1 and 255 characters"
+    ))]
     pub name: String,
+    #[validate(length(max = 1000, message = "Address must not exceed 1000 characters"))]
     pub address: Option<String>,
 }
 
+/// A school's regional settings.
+///
+/// Dates such as "today" for attendance and the current term are read in
+/// the school's timezone rather than UTC.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SchoolSettings {
+    pub school_id: SchoolId,
+    /// IANA timezone name, e.g. `Africa/Lagos`
+    pub timezone: String,
+    /// BCP 47 language tag, e.g. `en-NG`
+    pub locale: String,
+}
+
+impl SchoolSettings {
+    /// The school's timezone; UTC when the stored name is not recognised.
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(Tz::UTC)
+    }
+
+    /// The current date in the school's timezone.
+    pub fn today(&self) -> NaiveDate {
+        self.local_time(Utc::now()).date_naive()
+    }
+
+    /// `instant` as wall-clock time in the school's timezone.
+    pub fn local_time(&self, instant: DateTime<Utc>) -> DateTime<Tz> {
+        instant.with_timezone(&self.tz())
+    }
+}
+
+/// DTO for updating a school's settings. Omitted fields are left unchanged.
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct UpdateSchoolSettingsDto {
+    /// IANA timezone name, e.g. `Africa/Lagos`
+    #[validate(custom(function = "validate_timezone"))]
+    pub timezone: Option<String>,
+    /// BCP 47 language tag, e.g. `en-NG`
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>,
+}
+
+fn validate_timezone(timezone: &str) -> Result<(), ValidationError> {
+    match timezone.parse::<Tz>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(ValidationError::new("timezone")
+            .with_message(format!("Unknown timezone: {timezone}").into())),
+    }
+}
+
+/// Accepts tags shaped like `en`, `en-NG`, or `zh-Hant-TW`: a 2-3 letter
+/// language followed by subtags of 1-8 letters or digits.
+fn validate_locale(locale: &str) -> Result<(), ValidationError> {
+    let mut subtags = locale.split('-');
+    let language_ok = subtags
+        .next()
+        .is_some_and(|l| (2..=3).contains(&l.len()) && l.chars().all(|c| c.is_ascii_alphabetic()));
+    let rest_ok =
+        subtags.all(|t| (1..=8).contains(&t.len()) && t.chars().all(|c| c.is_ascii_alphanumeric()));
+
+    if language_ok && rest_ok && locale.len() <= 35 {
+        Ok(())
+    } else {
+        Err(ValidationError::new("locale").with_message(format!("Invalid locale: {locale}").into()))
+    }
+}
+
 /// User with their associated school information.
 ///
 /// Used in responses where both user and school data are needed.
@@ -86,6 +235,14 @@ pub struct UserWithSchool {
     pub school: Option<School>,
 }
 
+/// Versioned by the user alone; school changes do not conflict with
+/// profile updates.
+impl Versioned for UserWithSchool {
+    fn updated_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.user.updated_at
+    }
+}
+
 /// Summary information about a role.
 ///
 /// Used in user responses to include assigned role details.
@@ -139,6 +296,9 @@ pub struct UserWithRelations {
     pub email: Email,
     pub date_of_birth: Option<chrono::NaiveDate>,
     pub grade_level: Option<String>,
+    /// Custom field values keyed by field key
+    #[schema(value_type = Object)]
+    pub custom_fields: serde_json::Value,
     pub school: Option<SchoolInfo>,
     pub level: Option<LevelInfo>,
     pub branch: Option<BranchInfo>,
@@ -154,6 +314,19 @@ pub struct SchoolFilterParams {
     pub address: Option<String>,
     #[serde(flatten)]
     pub pagination: PaginationParams,
+    #[serde(flatten)]
+    pub sort: SortParams,
+    #[serde(flatten)]
+    pub filter: FilterParams,
+}
+
+/// One row of a school CSV export.
+#[derive(Debug, Serialize, FromRow)]
+pub struct SchoolExportRow {
+    pub id: SchoolId,
+    pub name: String,
+    pub address: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
 /// Paginated response containing schools.
@@ -176,8 +349,34 @@ pub struct UserFilterParams {
     pub role_id: Option<Uuid>,
     #[serde(default, deserialize_with = "deserialize_optional_uuid")]
     pub school_id: Option<Uuid>,
+    /// JSON object of custom field values users must match, e.g. `{"house":"red"}`
+    pub custom_fields: Option<String>,
+    /// Switches to cursor pagination: empty for the first page, then the
+    /// previous page's `next_cursor`. `offset` and `page` are ignored.
+    pub cursor: Option<String>,
     #[serde(flatten)]
     pub pagination: PaginationParams,
+    /// Ignored with `cursor`
+    #[serde(flatten)]
+    pub sort: SortParams,
+    #[serde(flatten)]
+    pub filter: FilterParams,
+    /// Only this user; set from a `users:read:self` permission scope, never
+    /// from the query string
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub only_user_id: Option<Uuid>,
+}
+
+impl UserFilterParams {
+    /// Cursor pagination parameters, if the client opted into cursor mode.
+    #[must_use]
+    pub fn cursor_pagination(&self) -> Option<CursorPaginationParams> {
+        self.cursor.as_ref().map(|cursor| CursorPaginationParams {
+            cursor: Some(cursor.clone()),
+            limit: self.pagination.limit,
+        })
+    }
 }
 
 /// Paginated response containing users with full relations.
@@ -187,6 +386,76 @@ pub struct PaginatedUsersResponse {
     pub meta: PaginationMeta,
 }
 
+/// Users list in the pagination mode the client asked for.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum UserListResponse {
+    Offset(PaginatedUsersResponse),
+    Cursor(CursorPage<UserWithRelations>),
+}
+
+/// Lite view of a user for mobile clients.
+///
+/// Drops custom fields, timestamps, and nested relations; relations are
+/// reduced to their IDs and roles to their names.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UserLite {
+    pub id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: Email,
+    pub school_id: Option<SchoolId>,
+    pub level_id: Option<LevelId>,
+    pub branch_id: Option<BranchId>,
+    pub roles: Vec<String>,
+}
+
+impl LiteView for UserWithRelations {
+    type Lite = UserLite;
+
+    fn into_lite(self) -> UserLite {
+        UserLite {
+            id: self.id,
+            first_name: self.first_name,
+            last_name: self.last_name,
+            email: self.email,
+            school_id: self.school.map(|school| school.id),
+            level_id: self.level.map(|level| level.id),
+            branch_id: self.branch.map(|branch| branch.id),
+            roles: self.roles.into_iter().map(|role| role.name).collect(),
+        }
+    }
+}
+
+/// Paginated response containing lite users.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedUserLitesResponse {
+    pub data: Vec<UserLite>,
+    pub meta: PaginationMeta,
+}
+
+/// Lite users list in the pagination mode the client asked for.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum UserLiteListResponse {
+    Offset(PaginatedUserLitesResponse),
+    Cursor(CursorPage<UserLite>),
+}
+
+impl LiteView for UserListResponse {
+    type Lite = UserLiteListResponse;
+
+    fn into_lite(self) -> UserLiteListResponse {
+        match self {
+            Self::Offset(page) => UserLiteListResponse::Offset(PaginatedUserLitesResponse {
+                data: page.data.into_lite(),
+                meta: page.meta,
+            }),
+            Self::Cursor(page) => UserLiteListResponse::Cursor(page.into_lite()),
+        }
+    }
+}
+
 /// Paginated response containing basic user data.
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct PaginatedBasicUsersResponse {
@@ -195,7 +464,7 @@ pub struct PaginatedBasicUsersResponse {
 }
 
 /// School information with user counts by role.
-#[derive(Debug, Clone, Serialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SchoolFullInfo {
     pub id: SchoolId,
     pub name: String,
@@ -217,6 +486,14 @@ pub struct UpdateProfileDto {
     pub last_name: Option<String>,
 }
 
+/// DTO for setting a user's custom field values.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateUserCustomFieldsDto {
+    /// Custom field values to set; `null` clears an optional field
+    #[schema(value_type = Object)]
+    pub custom_fields: CustomFieldValues,
+}
+
 /// DTO for changing user password.
 ///
 /// Requires the current password for verification before
@@ -246,6 +523,9 @@ pub mod system_roles {
         pub const ADMIN: &str = "admin";
         pub const TEACHER: &str = "teacher";
         pub const STUDENT: &str = "student";
+        pub const GROUP_ADMIN: &str = "group_admin";
+        pub const AUDITOR: &str = "auditor";
+        pub const GUARDIAN: &str = "guardian";
     }
 
     /// System Admin role - full system access
@@ -256,10 +536,24 @@ pub mod system_roles {
     pub const TEACHER: RoleId = RoleId::from_u128(0x00000000_0000_0000_0000_000000000003);
     /// Student role - basic read permissions
     pub const STUDENT: RoleId = RoleId::from_u128(0x00000000_0000_0000_0000_000000000004);
+    /// Group Admin role - manages the schools of a school group
+    pub const GROUP_ADMIN: RoleId = RoleId::from_u128(0x00000000_0000_0000_0000_000000000005);
+    /// Auditor role - read-only school access with sensitive fields masked
+    pub const AUDITOR: RoleId = RoleId::from_u128(0x00000000_0000_0000_0000_000000000006);
+    /// Guardian role - parent or guardian linked to students
+    pub const GUARDIAN: RoleId = RoleId::from_u128(0x00000000_0000_0000_0000_000000000007);
 
     /// Get all system role IDs
     pub fn all() -> Vec<RoleId> {
-        vec![SYSTEM_ADMIN, ADMIN, TEACHER, STUDENT]
+        vec![
+            SYSTEM_ADMIN,
+            ADMIN,
+            TEACHER,
+            STUDENT,
+            GROUP_ADMIN,
+            AUDITOR,
+            GUARDIAN,
+        ]
     }
 
     /// Get all system role slugs
@@ -270,6 +564,9 @@ pub mod system_roles {
             slugs::ADMIN,
             slugs::TEACHER,
             slugs::STUDENT,
+            slugs::GROUP_ADMIN,
+            slugs::AUDITOR,
+            slugs::GUARDIAN,
         ]
     }
 
@@ -291,6 +588,9 @@ pub mod system_roles {
             id if id == ADMIN => Some("Admin"),
             id if id == TEACHER => Some("Teacher"),
             id if id == STUDENT => Some("Student"),
+            id if id == GROUP_ADMIN => Some("Group Admin"),
+            id if id == AUDITOR => Some("Auditor"),
+            id if id == GUARDIAN => Some("Guardian"),
             _ => None,
         }
     }
@@ -303,6 +603,9 @@ pub mod system_roles {
             id if id == ADMIN => Some(slugs::ADMIN),
             id if id == TEACHER => Some(slugs::TEACHER),
             id if id == STUDENT => Some(slugs::STUDENT),
+            id if id == GROUP_ADMIN => Some(slugs::GROUP_ADMIN),
+            id if id == AUDITOR => Some(slugs::AUDITOR),
+            id if id == GUARDIAN => Some(slugs::GUARDIAN),
             _ => None,
         }
     }
@@ -315,15 +618,71 @@ pub mod system_roles {
             slugs::ADMIN => Some(ADMIN),
             slugs::TEACHER => Some(TEACHER),
             slugs::STUDENT => Some(STUDENT),
+            slugs::GROUP_ADMIN => Some(GROUP_ADMIN),
+            slugs::AUDITOR => Some(AUDITOR),
+            slugs::GUARDIAN => Some(GUARDIAN),
             _ => None,
         }
     }
 }
 
+/// One row of a user CSV export.
+#[derive(Debug, Serialize, FromRow)]
+pub struct UserExportRow {
+    pub id: UserId,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub school: Option<String>,
+    pub level: Option<String>,
+    pub branch: Option<String>,
+    /// Role names separated by `;`
+    pub roles: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_school_settings_validation() {
+        let dto = |timezone: &str, locale: &str| UpdateSchoolSettingsDto {
+            timezone: Some(timezone.to_string()),
+            locale: Some(locale.to_string()),
+        };
+
+        assert!(dto("Africa/Lagos", "en-NG").validate().is_ok());
+        assert!(dto("UTC", "zh-Hant-TW").validate().is_ok());
+        assert!(dto("Mars/Olympus_Mons", "en").validate().is_err());
+        assert!(dto("UTC", "english").validate().is_err());
+        assert!(dto("UTC", "en--NG").validate().is_err());
+        assert!(UpdateSchoolSettingsDto::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_school_settings_local_time() {
+        let settings = SchoolSettings {
+            school_id: SchoolId::new(),
+            timezone: "Pacific/Kiritimati".to_string(),
+            locale: "en".to_string(),
+        };
+        // 11:00 UTC is already the next day at UTC+14
+        let instant = DateTime::parse_from_rfc3339("2026-01-01T11:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            settings.local_time(instant).date_naive(),
+            NaiveDate::from_ymd_opt(2026, 1, 2).unwrap()
+        );
+
+        let unknown = SchoolSettings {
+            timezone: "Nowhere/Special".to_string(),
+            ..settings
+        };
+        assert_eq!(unknown.tz(), Tz::UTC);
+    }
+
     #[test]
     fn test_system_roles_ids() {
         assert_eq!(
@@ -366,6 +725,18 @@ mod tests {
             system_roles::get_name(&system_roles::STUDENT),
             Some("Student")
         );
+        assert_eq!(
+            system_roles::get_name(&system_roles::GROUP_ADMIN),
+            Some("Group Admin")
+        );
+        assert_eq!(
+            system_roles::get_name(&system_roles::AUDITOR),
+            Some("Auditor")
+        );
+        assert_eq!(
+            system_roles::get_name(&system_roles::GUARDIAN),
+            Some("Guardian")
+        );
         assert_eq!(system_roles::get_name(&RoleId::new()), None);
     }
 
@@ -0,0 +1,253 @@
+//! Visitor log domain models and DTOs.
+//!
+//! This module contains all data structures for the visitor and gate log:
+//! visitor check-ins with purpose and host staff, check-outs, daily summary
+//! reports, and the API keys used by self-service visitor kiosks.
+//!
+//! Kiosk keys are shown in plaintext only once, when they are issued. Only a
+//! hash is stored, so a lost key must be revoked and replaced.
+
+use crate::ids::{SchoolId, UserId, VisitorKioskKeyId, VisitorLogId};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Reason for a visit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum VisitPurpose {
+    Meeting,
+    ParentVisit,
+    Delivery,
+    Maintenance,
+    Interview,
+    Official,
+    Other,
+}
+
+/// A visitor log entry.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct VisitorLog {
+    /// Unique identifier for the visit
+    pub id: VisitorLogId,
+    /// School being visited
+    pub school_id: SchoolId,
+    /// Visitor's full name
+    pub visitor_name: String,
+    /// Visitor's phone number
+    pub phone: Option<String>,
+    /// Identity document number shown at the gate
+    pub id_document: Option<String>,
+    /// Company or organization the visitor represents
+    pub organization: Option<String>,
+    /// Reason for the visit
+    pub purpose: VisitPurpose,
+    /// Free-text details about the visit
+    pub purpose_details: Option<String>,
+    /// Staff member being visited
+    pub host_id: Option<UserId>,
+    /// Visitor badge handed out at check-in
+    pub badge_number: Option<String>,
+    /// Time the visitor checked in
+    pub check_in_at: DateTime<Utc>,
+    /// Time the visitor checked out; `None` while on site
+    pub check_out_at: Option<DateTime<Utc>>,
+    /// Staff member who checked the visitor in (for desk check-ins)
+    pub checked_in_by: Option<UserId>,
+    /// Kiosk that checked the visitor in (for self-service check-ins)
+    pub kiosk_key_id: Option<VisitorKioskKeyId>,
+    /// Timestamp when the entry was created
+    pub created_at: DateTime<Utc>,
+    /// Timestamp when the entry was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Visitor log entry with the host's name.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct VisitorLogWithHost {
+    pub id: VisitorLogId,
+    pub visitor_name: String,
+    pub phone: Option<String>,
+    pub organization: Option<String>,
+    pub purpose: VisitPurpose,
+    pub purpose_details: Option<String>,
+    pub host_id: Option<UserId>,
+    pub host_first_name: Option<String>,
+    pub host_last_name: Option<String>,
+    pub badge_number: Option<String>,
+    pub check_in_at: DateTime<Utc>,
+    pub check_out_at: Option<DateTime<Utc>>,
+}
+
+/// DTO for checking a visitor in.
+///
+/// Used by both the front desk and kiosk endpoints; kiosks ignore `school_id`.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CheckInVisitorDto {
+    /// Visitor's full name (1-150 characters)
+    #[validate(length(min = 1, max = 150))]
+    pub visitor_name: String,
+    /// Visitor's phone number (max 30 characters)
+    #[validate(length(max = 30))]
+    pub phone: Option<String>,
+    /// Identity document number (max 50 characters)
+    #[validate(length(max = 50))]
+    pub id_document: Option<String>,
+    /// Company or organization (max 150 characters)
+    #[validate(length(max = 150))]
+    pub organization: Option<String>,
+    /// Reason for the visit
+    pub purpose: VisitPurpose,
+    /// Free-text details (max 500 characters)
+    #[validate(length(max = 500))]
+    pub purpose_details: Option<String>,
+    /// Staff member being visited
+    pub host_id: Option<UserId>,
+    /// Visitor badge number (max 20 characters)
+    #[validate(length(max = 20))]
+    pub badge_number: Option<String>,
+    /// School ID (required for system admins, ignored for school staff and kiosks)
+    pub school_id: Option<SchoolId>,
+}
+
+/// Query parameters for listing visitor log entries.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct VisitorLogFilterParams {
+    /// Filter by school ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Only visits that checked in on this date (UTC)
+    pub date: Option<NaiveDate>,
+    /// Filter by host
+    pub host_id: Option<UserId>,
+    /// Filter by purpose
+    pub purpose: Option<VisitPurpose>,
+    /// Only visitors who are still on site
+    pub on_site: Option<bool>,
+    /// Pagination parameters
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+/// Paginated response containing visitor log entries.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedVisitorLogsResponse {
+    /// List of visits
+    pub data: Vec<VisitorLogWithHost>,
+    /// Pagination metadata
+    pub meta: PaginationMeta,
+}
+
+/// Query parameters for the daily visitor report.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct DailyVisitorReportParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+    /// Report date (UTC); defaults to today
+    pub date: Option<NaiveDate>,
+}
+
+/// Number of visits for a purpose.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct VisitPurposeCount {
+    pub purpose: VisitPurpose,
+    pub count: i64,
+}
+
+/// Daily summary of visitor traffic for security compliance.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DailyVisitorReport {
+    pub date: NaiveDate,
+    /// Visits that checked in on the date
+    pub total_visits: i64,
+    /// Visits from the date that have checked out
+    pub checked_out: i64,
+    /// Visits from the date that have not checked out
+    pub still_on_site: i64,
+    /// Mean length of completed visits in minutes
+    pub average_visit_minutes: Option<f64>,
+    /// Visits grouped by purpose
+    pub by_purpose: Vec<VisitPurposeCount>,
+    /// Visitors from the date who have not checked out
+    pub open_visits: Vec<VisitorLogWithHost>,
+}
+
+/// A visitor kiosk API key. The key itself is never returned after creation.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct VisitorKioskKey {
+    pub id: VisitorKioskKeyId,
+    pub school_id: SchoolId,
+    /// Label for the kiosk, e.g. "Main gate"
+    pub name: String,
+    /// First characters of the key, for identifying it
+    pub key_prefix: String,
+    pub created_by: Option<UserId>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// Set once the key has been revoked
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// DTO for issuing a visitor kiosk API key.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateVisitorKioskKeyDto {
+    /// Label for the kiosk (1-100 characters)
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// School ID (required for system admins, ignored for school admins)
+    pub school_id: Option<SchoolId>,
+}
+
+/// Response for a newly issued kiosk key, including the plaintext key.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CreatedVisitorKioskKey {
+    #[serde(flatten)]
+    pub key: VisitorKioskKey,
+    /// The API key to configure on the kiosk. It cannot be retrieved again.
+    pub api_key: String,
+}
+
+/// Query parameters for listing kiosk keys.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct VisitorKioskKeyQueryParams {
+    /// School ID (required for system admins)
+    pub school_id: Option<SchoolId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_in_visitor_dto_validation() {
+        let valid_dto = CheckInVisitorDto {
+            visitor_name: "Ada Obi".to_string(),
+            phone: Some("+2348000000000".to_string()),
+            id_document: None,
+            organization: None,
+            purpose: VisitPurpose::ParentVisit,
+            purpose_details: None,
+            host_id: None,
+            badge_number: Some("V-12".to_string()),
+            school_id: None,
+        };
+        assert!(valid_dto.validate().is_ok());
+
+        let empty_name = CheckInVisitorDto {
+            visitor_name: String::new(),
+            ..valid_dto
+        };
+        assert!(empty_name.validate().is_err());
+    }
+
+    #[test]
+    fn test_visit_purpose_serialization() {
+        let json = serde_json::to_string(&VisitPurpose::ParentVisit).unwrap();
+        assert_eq!(json, "\"parent_visit\"");
+        let parsed: VisitPurpose = serde_json::from_str("\"delivery\"").unwrap();
+        assert_eq!(parsed, VisitPurpose::Delivery);
+    }
+}
@@ -0,0 +1,219 @@
+//! Webhook domain models and DTOs.
+//!
+//! A school registers webhook endpoints, each subscribed to a set of domain
+//! events. When an event happens a delivery is written to an outbox in the
+//! same transaction as the change, and a background job POSTs it to the
+//! endpoint as JSON signed with the endpoint's secret, retrying failures
+//! with exponential backoff.
+
+use crate::ids::{SchoolId, UserId, WebhookDeliveryId, WebhookEndpointId};
+use chalkbyte_core::{PaginationMeta, PaginationParams};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+/// A domain event that can be delivered to webhooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(type_name = "text")]
+pub enum WebhookEvent {
+    /// A user account was created in the school
+    #[serde(rename = "user.created")]
+    #[sqlx(rename = "user.created")]
+    UserCreated,
+    /// A student was moved to another branch, or out of one
+    #[serde(rename = "student.moved_branch")]
+    #[sqlx(rename = "student.moved_branch")]
+    StudentMovedBranch,
+    /// A role was assigned to a user
+    #[serde(rename = "role.assigned")]
+    #[sqlx(rename = "role.assigned")]
+    RoleAssigned,
+    /// A term became the current term of its session
+    #[serde(rename = "term.started")]
+    #[sqlx(rename = "term.started")]
+    TermStarted,
+    /// A term stopped being the current term of its session
+    #[serde(rename = "term.ended")]
+    #[sqlx(rename = "term.ended")]
+    TermEnded,
+    /// A behaviour incident was recorded against a student
+    #[serde(rename = "incident.recorded")]
+    #[sqlx(rename = "incident.recorded")]
+    IncidentRecorded,
+}
+
+impl WebhookEvent {
+    /// The event name as it appears in payloads and subscriptions.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::UserCreated => "user.created",
+            Self::StudentMovedBranch => "student.moved_branch",
+            Self::RoleAssigned => "role.assigned",
+            Self::TermStarted => "term.started",
+            Self::TermEnded => "term.ended",
+            Self::IncidentRecorded => "incident.recorded",
+        }
+    }
+}
+
+/// Delivery state of one event to one endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its first attempt or a retry
+    Pending,
+    /// The endpoint answered with a 2xx status
+    Delivered,
+    /// Every attempt failed; no more retries
+    Failed,
+}
+
+/// A registered webhook endpoint. The signing secret is only returned when
+/// the endpoint is created or its secret is rotated.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct WebhookEndpoint {
+    pub id: WebhookEndpointId,
+    pub school_id: SchoolId,
+    pub url: String,
+    pub description: Option<String>,
+    /// Events delivered to the endpoint
+    pub events: Vec<WebhookEvent>,
+    /// Inactive endpoints receive no new events
+    pub is_active: bool,
+    pub created_by: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A webhook endpoint together with its signing secret.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookEndpointWithSecret {
+    #[serde(flatten)]
+    pub endpoint: WebhookEndpoint,
+    /// Key for verifying the `X-Chalkbyte-Signature` header. Store it
+    /// securely; it is not shown again.
+    pub secret: String,
+}
+
+/// DTO for registering a webhook endpoint.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreateWebhookEndpointDto {
+    /// HTTPS URL the events are POSTed to. Its host must resolve to public
+    /// addresses only.
+    #[validate(url, length(max = 2048), custom(function = "validate_webhook_scheme"))]
+    #[schema(example = "https://example.com/hooks/chalkbyte")]
+    pub url: String,
+    #[validate(length(max = 255))]
+    pub description: Option<String>,
+    /// Events to subscribe to (at least one)
+    #[validate(length(min = 1))]
+    pub events: Vec<WebhookEvent>,
+}
+
+/// DTO for updating a webhook endpoint. Omitted fields are left unchanged.
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct UpdateWebhookEndpointDto {
+    #[validate(url, length(max = 2048), custom(function = "validate_webhook_scheme"))]
+    pub url: Option<String>,
+    #[validate(length(max = 255))]
+    pub description: Option<String>,
+    #[validate(length(min = 1))]
+    pub events: Option<Vec<WebhookEvent>>,
+    pub is_active: Option<bool>,
+}
+
+fn validate_webhook_scheme(url: &str) -> Result<(), ValidationError> {
+    if url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(ValidationError::new("url").with_message("Webhook URLs must use https".into()))
+    }
+}
+
+/// One event queued for, or delivered to, an endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct WebhookDelivery {
+    pub id: WebhookDeliveryId,
+    pub endpoint_id: WebhookEndpointId,
+    pub event: WebhookEvent,
+    /// The JSON body POSTed to the endpoint
+    pub payload: serde_json::Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    /// When the next attempt is due (pending deliveries only)
+    pub next_attempt_at: DateTime<Utc>,
+    /// HTTP status of the last attempt, if the endpoint answered
+    pub last_response_status: Option<i32>,
+    /// Why the last attempt failed
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query parameters for listing an endpoint's deliveries.
+#[derive(Debug, Clone, Deserialize, ToSchema, IntoParams)]
+pub struct WebhookDeliveryFilterParams {
+    pub status: Option<WebhookDeliveryStatus>,
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaginatedWebhookDeliveriesResponse {
+    pub data: Vec<WebhookDelivery>,
+    pub meta: PaginationMeta,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_event_names() {
+        for event in [
+            WebhookEvent::UserCreated,
+            WebhookEvent::StudentMovedBranch,
+            WebhookEvent::RoleAssigned,
+            WebhookEvent::TermStarted,
+            WebhookEvent::IncidentRecorded,
+        ] {
+            let serialized = serde_json::to_string(&event).unwrap();
+            assert_eq!(serialized, format!("\"{}\"", event.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_create_webhook_endpoint_validation() {
+        let dto = |url: &str, events: Vec<WebhookEvent>| CreateWebhookEndpointDto {
+            url: url.to_string(),
+            description: None,
+            events,
+        };
+
+        assert!(
+            dto("https://example.com/hooks", vec![WebhookEvent::UserCreated])
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            dto("ftp://example.com/hooks", vec![WebhookEvent::UserCreated])
+                .validate()
+                .is_err()
+        );
+        assert!(
+            dto("http://example.com/hooks", vec![WebhookEvent::UserCreated])
+                .validate()
+                .is_err()
+        );
+        assert!(
+            dto("not a url", vec![WebhookEvent::UserCreated])
+                .validate()
+                .is_err()
+        );
+        assert!(dto("https://example.com/hooks", vec![]).validate().is_err());
+    }
+}
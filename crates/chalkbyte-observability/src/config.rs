@@ -0,0 +1,292 @@
+//! Runtime configuration for tracing export and sampling.
+//!
+//! [`ObservabilityConfig::from_env`] reads the standard OpenTelemetry
+//! variables plus a Chalkbyte-specific list of per-route sampling overrides:
+//!
+//! | Variable | Default | Meaning |
+//! |----------|---------|---------|
+//! | `OBSERVABILITY_ENABLED` | `true` | Master switch for tracing and metrics |
+//! | `LOG_FORMAT` | `pretty` | Console output: `pretty` for people, `json` for JSON lines with secrets redacted |
+//! | `OTEL_SERVICE_NAME` | crate name | `service.name` resource attribute |
+//! | `ENVIRONMENT` | `development` | `deployment.environment` resource attribute |
+//! | `OTEL_EXPORTER_OTLP_ENDPOINT` | `http://localhost:4317` | OTLP gRPC collector |
+//! | `OTEL_EXPORTER_OTLP_HEADERS` | none | `key=value` pairs, comma separated, sent as gRPC metadata |
+//! | `OTEL_EXPORTER_OTLP_TIMEOUT` | `5000` | Export timeout in milliseconds |
+//! | `OTEL_TRACES_SAMPLER` | `parentbased_always_on` | `always_on`, `always_off`, `traceidratio`, or a `parentbased_` variant of each |
+//! | `OTEL_TRACES_SAMPLER_ARG` | `1.0` | Ratio for the `traceidratio` samplers |
+//! | `OTEL_TRACES_SAMPLER_ROUTES` | `/api/v1/auth=1.0,/api/v1/mfa=1.0,/api/auth=1.0,/api/mfa=1.0` | `prefix=ratio` pairs that override the sampler for matching routes |
+//!
+//! Route overrides are checked before the sampler, so production can run at
+//! `OTEL_TRACES_SAMPLER_ARG=0.01` and still keep every auth request. Set
+//! `OTEL_TRACES_SAMPLER_ROUTES` to an empty string to drop the defaults.
+
+use std::env;
+use std::time::Duration;
+
+const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4317";
+const DEFAULT_OTLP_TIMEOUT_MS: u64 = 5000;
+const DEFAULT_ROUTE_OVERRIDES: &str = "/api/v1/auth=1.0,/api/v1/mfa=1.0,/api/auth=1.0,/api/mfa=1.0";
+
+/// Everything `init_tracing` needs to set up export and sampling.
+#[derive(Clone, Debug)]
+pub struct ObservabilityConfig {
+    pub enabled: bool,
+    pub log_format: LogFormat,
+    pub service_name: String,
+    pub environment: String,
+    pub otlp: OtlpConfig,
+    pub sampling: SamplingConfig,
+}
+
+/// How console logs are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Compact human-readable lines
+    #[default]
+    Pretty,
+    /// One JSON object per line, see [`crate::json_log`]
+    Json,
+}
+
+impl LogFormat {
+    /// Parses `LOG_FORMAT`; anything other than `json` means pretty.
+    pub fn parse(value: &str) -> Self {
+        if value.trim().eq_ignore_ascii_case("json") {
+            Self::Json
+        } else {
+            Self::Pretty
+        }
+    }
+}
+
+/// Where and how spans are exported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OtlpConfig {
+    pub endpoint: String,
+    /// Extra gRPC metadata, usually collector credentials
+    pub headers: Vec<(String, String)>,
+    pub timeout: Duration,
+}
+
+/// Which traces are kept.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SamplingConfig {
+    pub strategy: SamplingStrategy,
+    /// Follow the parent span's decision when there is one
+    pub parent_based: bool,
+    /// Checked in order; the first matching prefix wins
+    pub route_overrides: Vec<RouteSampling>,
+}
+
+/// How root spans without a route override are sampled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SamplingStrategy {
+    AlwaysOn,
+    AlwaysOff,
+    /// Keep this fraction of traces, decided by trace id
+    Ratio(f64),
+}
+
+/// A sampling ratio for every route starting with `prefix`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteSampling {
+    pub prefix: String,
+    pub ratio: f64,
+}
+
+impl ObservabilityConfig {
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Reads the same variables as [`from_env`](Self::from_env) from
+    /// `lookup`, e.g. a config file with environment overrides.
+    ///
+    /// `enabled` is always `false` without the `observability` feature.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            enabled: cfg!(feature = "observability")
+                && lookup("OBSERVABILITY_ENABLED").is_none_or(|v| parse_enabled(&v)),
+            log_format: lookup("LOG_FORMAT")
+                .map(|v| LogFormat::parse(&v))
+                .unwrap_or_default(),
+            service_name: lookup("OTEL_SERVICE_NAME")
+                .unwrap_or_else(|| env!("CARGO_PKG_NAME").to_string()),
+            environment: lookup("ENVIRONMENT").unwrap_or_else(|| "development".to_string()),
+            otlp: OtlpConfig::from_lookup(&lookup),
+            sampling: SamplingConfig::from_lookup(&lookup),
+        }
+    }
+}
+
+/// Whether an `OBSERVABILITY_ENABLED` value leaves observability on.
+pub fn parse_enabled(value: &str) -> bool {
+    !matches!(value.to_lowercase().as_str(), "false" | "0" | "no" | "off")
+}
+
+impl OtlpConfig {
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let timeout_ms = lookup("OTEL_EXPORTER_OTLP_TIMEOUT")
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_OTLP_TIMEOUT_MS);
+
+        Self {
+            endpoint: lookup("OTEL_EXPORTER_OTLP_ENDPOINT")
+                .unwrap_or_else(|| DEFAULT_OTLP_ENDPOINT.to_string()),
+            headers: lookup("OTEL_EXPORTER_OTLP_HEADERS")
+                .map(|v| parse_headers(&v))
+                .unwrap_or_default(),
+            timeout: Duration::from_millis(timeout_ms),
+        }
+    }
+}
+
+impl SamplingConfig {
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let sampler = lookup("OTEL_TRACES_SAMPLER");
+        let arg = lookup("OTEL_TRACES_SAMPLER_ARG");
+        let routes = lookup("OTEL_TRACES_SAMPLER_ROUTES")
+            .unwrap_or_else(|| DEFAULT_ROUTE_OVERRIDES.to_string());
+
+        Self::parse(sampler.as_deref(), arg.as_deref(), &routes)
+    }
+
+    /// Builds a config from the raw variable values.
+    ///
+    /// Unknown sampler names fall back to `parentbased_always_on`, and
+    /// ratios are clamped to `0.0..=1.0`.
+    pub fn parse(sampler: Option<&str>, arg: Option<&str>, routes: &str) -> Self {
+        let name = sampler
+            .map(|s| s.trim().to_lowercase())
+            .unwrap_or_else(|| "parentbased_always_on".to_string());
+        let (parent_based, base) = match name.strip_prefix("parentbased_") {
+            Some(base) => (true, base),
+            None => (false, name.as_str()),
+        };
+
+        let (parent_based, strategy) = match base {
+            "always_off" => (parent_based, SamplingStrategy::AlwaysOff),
+            "traceidratio" | "trace_id_ratio" => (
+                parent_based,
+                SamplingStrategy::Ratio(arg.and_then(parse_ratio).unwrap_or(1.0)),
+            ),
+            "always_on" => (parent_based, SamplingStrategy::AlwaysOn),
+            _ => (true, SamplingStrategy::AlwaysOn),
+        };
+
+        Self {
+            strategy,
+            parent_based,
+            route_overrides: parse_route_overrides(routes),
+        }
+    }
+
+    /// The override ratio for `route`, if one applies.
+    pub fn route_ratio(&self, route: &str) -> Option<f64> {
+        self.route_overrides
+            .iter()
+            .find(|o| route.starts_with(&o.prefix))
+            .map(|o| o.ratio)
+    }
+}
+
+fn parse_ratio(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|r| r.is_finite())
+        .map(|r| r.clamp(0.0, 1.0))
+}
+
+fn parse_headers(value: &str) -> Vec<(String, String)> {
+    value
+        .split(',')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            let key = key.trim();
+            (!key.is_empty()).then(|| (key.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+fn parse_route_overrides(value: &str) -> Vec<RouteSampling> {
+    value
+        .split(',')
+        .filter_map(|pair| {
+            let (prefix, ratio) = pair.split_once('=')?;
+            let prefix = prefix.trim();
+            if prefix.is_empty() {
+                return None;
+            }
+            Some(RouteSampling {
+                prefix: prefix.to_string(),
+                ratio: parse_ratio(ratio)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sampler_names() {
+        let config = SamplingConfig::parse(None, None, "");
+        assert_eq!(config.strategy, SamplingStrategy::AlwaysOn);
+        assert!(config.parent_based);
+
+        let config = SamplingConfig::parse(Some("parentbased_traceidratio"), Some("0.01"), "");
+        assert_eq!(config.strategy, SamplingStrategy::Ratio(0.01));
+        assert!(config.parent_based);
+
+        let config = SamplingConfig::parse(Some("trace_id_ratio"), Some("7"), "");
+        assert_eq!(config.strategy, SamplingStrategy::Ratio(1.0));
+        assert!(!config.parent_based);
+
+        let config = SamplingConfig::parse(Some("always_off"), None, "");
+        assert_eq!(config.strategy, SamplingStrategy::AlwaysOff);
+        assert!(!config.parent_based);
+    }
+
+    #[test]
+    fn test_route_overrides() {
+        let config = SamplingConfig::parse(
+            Some("traceidratio"),
+            Some("0.01"),
+            " /api/auth = 1.0, /health=0, bad, /api/mfa=nope",
+        );
+        assert_eq!(config.route_overrides.len(), 2);
+        assert_eq!(config.route_ratio("/api/auth/login"), Some(1.0));
+        assert_eq!(config.route_ratio("/health"), Some(0.0));
+        assert_eq!(config.route_ratio("/api/mfa/verify"), None);
+        assert_eq!(config.route_ratio("/api/users"), None);
+    }
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!(LogFormat::parse("JSON"), LogFormat::Json);
+        assert_eq!(LogFormat::parse("pretty"), LogFormat::Pretty);
+        assert_eq!(LogFormat::parse("yaml"), LogFormat::Pretty);
+    }
+
+    #[test]
+    fn test_parse_headers() {
+        assert_eq!(
+            parse_headers("x-api-key=secret, x-tenant = chalkbyte,=skipped,novalue"),
+            vec![
+                ("x-api-key".to_string(), "secret".to_string()),
+                ("x-tenant".to_string(), "chalkbyte".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! Error reporting to an external service.
+//!
+//! The HTTP layer builds an [`ErrorReport`] for every server error response
+//! and every panic it catches, and hands it to [`report`], which forwards it
+//! to the [`ErrorReporter`] installed with [`set_reporter`]. Without one,
+//! reports are dropped; the errors are still logged where they happen.
+//!
+//! With the `sentry` feature, [`sentry::init_from_env`] installs a Sentry
+//! reporter when `SENTRY_DSN` is set.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use chalkbyte_observability::error_reporting::{ErrorReport, ErrorReporter, set_reporter};
+//!
+//! struct StderrReporter;
+//!
+//! impl ErrorReporter for StderrReporter {
+//!     fn report(&self, report: &ErrorReport) {
+//!         eprintln!("{} {:?}: {}", report.status, report.route, report.message);
+//!     }
+//! }
+//!
+//! set_reporter(StderrReporter);
+//! ```
+
+use std::sync::OnceLock;
+
+use uuid::Uuid;
+
+/// What went wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A handler returned a 5xx response
+    ServerError,
+    /// A handler panicked
+    Panic,
+}
+
+/// One error with the request it happened in.
+#[derive(Debug, Clone)]
+pub struct ErrorReport {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub status: u16,
+    pub method: String,
+    /// The matched route, or the raw path when no route matched
+    pub route: String,
+    pub request_id: Option<String>,
+    pub user_id: Option<Uuid>,
+    pub school_id: Option<Uuid>,
+}
+
+/// Sends error reports to an external service.
+///
+/// Called on the request's task, so implementations should hand the report
+/// off rather than block on network calls.
+pub trait ErrorReporter: Send + Sync + 'static {
+    fn report(&self, report: &ErrorReport);
+}
+
+static REPORTER: OnceLock<Box<dyn ErrorReporter>> = OnceLock::new();
+
+/// Installs the process-wide reporter. Only the first call takes effect.
+pub fn set_reporter(reporter: impl ErrorReporter) -> bool {
+    REPORTER.set(Box::new(reporter)).is_ok()
+}
+
+/// Forwards `report` to the installed reporter, if any.
+pub fn report(report: &ErrorReport) {
+    if let Some(reporter) = REPORTER.get() {
+        reporter.report(report);
+    }
+}
+
+#[cfg(feature = "sentry")]
+pub mod sentry {
+    //! Sentry reporter.
+    //!
+    //! Configured from `SENTRY_DSN`, `SENTRY_ENVIRONMENT` (falling back to
+    //! `ENVIRONMENT`), and `SENTRY_RELEASE` (falling back to the crate
+    //! version). Panics are reported by the HTTP layer, so Sentry's own panic
+    //! hook is not installed.
+
+    use super::{ErrorKind, ErrorReport, ErrorReporter, set_reporter};
+
+    pub struct SentryReporter;
+
+    impl ErrorReporter for SentryReporter {
+        fn report(&self, report: &ErrorReport) {
+            ::sentry::with_scope(
+                |scope| {
+                    scope.set_tag("http.method", &report.method);
+                    scope.set_tag("http.route", &report.route);
+                    scope.set_tag("http.status_code", report.status);
+                    if let Some(request_id) = &report.request_id {
+                        scope.set_tag("request_id", request_id);
+                    }
+                    if let Some(school_id) = report.school_id {
+                        scope.set_tag("school_id", school_id);
+                    }
+                    scope.set_user(report.user_id.map(|id| ::sentry::User {
+                        id: Some(id.to_string()),
+                        ..Default::default()
+                    }));
+                },
+                || {
+                    let level = match report.kind {
+                        ErrorKind::Panic => ::sentry::Level::Fatal,
+                        ErrorKind::ServerError => ::sentry::Level::Error,
+                    };
+                    ::sentry::capture_message(&report.message, level)
+                },
+            );
+        }
+    }
+
+    /// Starts the Sentry client and installs [`SentryReporter`], if
+    /// `SENTRY_DSN` is set. Keep the guard alive until shutdown so queued
+    /// events are flushed.
+    pub fn init_from_env() -> Option<::sentry::ClientInitGuard> {
+        let dsn = std::env::var("SENTRY_DSN").ok().filter(|d| !d.is_empty())?;
+        let environment = std::env::var("SENTRY_ENVIRONMENT")
+            .or_else(|_| std::env::var("ENVIRONMENT"))
+            .unwrap_or_else(|_| "development".to_string());
+        let release =
+            std::env::var("SENTRY_RELEASE").unwrap_or_else(|_| env!("CARGO_PKG_VERSION").into());
+
+        let guard = ::sentry::init((
+            dsn,
+            ::sentry::ClientOptions {
+                environment: Some(environment.into()),
+                release: Some(release.into()),
+                ..Default::default()
+            },
+        ));
+        set_reporter(SentryReporter);
+        Some(guard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl ErrorReporter for Recorder {
+        fn report(&self, report: &ErrorReport) {
+            self.0.lock().unwrap().push(report.message.clone());
+        }
+    }
+
+    #[test]
+    fn test_report_reaches_installed_reporter() {
+        let recorder = Recorder::default();
+        assert!(set_reporter(recorder.clone()));
+        assert!(!set_reporter(Recorder::default()));
+
+        report(&ErrorReport {
+            kind: ErrorKind::Panic,
+            message: "boom".to_string(),
+            status: 500,
+            method: "GET".to_string(),
+            route: "/api/students".to_string(),
+            request_id: None,
+            user_id: None,
+            school_id: None,
+        });
+        assert_eq!(*recorder.0.lock().unwrap(), vec!["boom".to_string()]);
+    }
+}
@@ -0,0 +1,266 @@
+//! Background job and scheduled task monitoring.
+//!
+//! Each background job registers once with [`register_job`] and wraps every
+//! run in [`JobMonitor::run`]. A run gets a `job.run` span, a success or
+//! failure count, and its duration, and the job's last-run timestamps are
+//! kept in memory so [`job_statuses`] can report jobs that are failing or
+//! have stopped running altogether.
+//!
+//! With the `observability` feature the same data is exported to Prometheus:
+//!
+//! - `job_runs_total{job, status}` - runs by outcome
+//! - `job_run_duration_seconds{job}` - run duration
+//! - `job_queue_depth{job}` - work waiting, for queue-backed jobs
+//! - `job_last_success_timestamp_seconds{job}` - Unix time of the last success
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use chalkbyte_observability::jobs::register_job;
+//!
+//! # async fn purge() -> Result<u64, std::io::Error> { Ok(0) }
+//! # async fn example() {
+//! let job = register_job("retention_purge", Duration::from_secs(3600));
+//! let mut interval = tokio::time::interval(Duration::from_secs(3600));
+//! loop {
+//!     interval.tick().await;
+//!     if let Err(e) = job.run(purge()).await {
+//!         tracing::warn!(error = %e, "Failed to purge");
+//!     }
+//! }
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use tracing::Instrument;
+
+/// Consecutive failed runs after which a job counts as failing.
+pub const FAILING_AFTER_RUNS: u32 = 3;
+
+/// Missed intervals after which a job that has not started a run counts as
+/// stalled.
+pub const STALLED_AFTER_INTERVALS: u32 = 3;
+
+/// Longest error message kept for a failed run.
+const MAX_ERROR_LEN: usize = 500;
+
+/// What a job's recent runs say about it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobHealth {
+    /// Its last run succeeded, or it has failed fewer than
+    /// [`FAILING_AFTER_RUNS`] times in a row
+    Healthy,
+    /// Registered but has not finished a run yet
+    Pending,
+    /// Its last [`FAILING_AFTER_RUNS`] or more runs failed
+    Failing,
+    /// No run has started for [`STALLED_AFTER_INTERVALS`] intervals
+    Stalled,
+}
+
+/// A job's run history since the process started.
+#[derive(Debug, Clone)]
+pub struct JobStatus {
+    pub name: &'static str,
+    /// How often the job is expected to run
+    pub interval: Duration,
+    pub registered_at: DateTime<Utc>,
+    pub last_started_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    /// Error of the most recent failed run
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+    pub successes: u64,
+    pub failures: u64,
+    /// Work waiting for the job, for jobs that report it
+    pub queue_depth: Option<u64>,
+}
+
+impl JobStatus {
+    fn new(name: &'static str, interval: Duration) -> Self {
+        Self {
+            name,
+            interval,
+            registered_at: Utc::now(),
+            last_started_at: None,
+            last_success_at: None,
+            last_failure_at: None,
+            last_error: None,
+            consecutive_failures: 0,
+            successes: 0,
+            failures: 0,
+            queue_depth: None,
+        }
+    }
+
+    /// The job's health as of `now`.
+    pub fn health(&self, now: DateTime<Utc>) -> JobHealth {
+        let stall_after = chrono::Duration::from_std(self.interval * STALLED_AFTER_INTERVALS)
+            .unwrap_or(chrono::Duration::MAX);
+        let last_seen = self.last_started_at.unwrap_or(self.registered_at);
+        if now - last_seen > stall_after {
+            JobHealth::Stalled
+        } else if self.consecutive_failures >= FAILING_AFTER_RUNS {
+            JobHealth::Failing
+        } else if self.successes == 0 && self.failures == 0 {
+            JobHealth::Pending
+        } else {
+            JobHealth::Healthy
+        }
+    }
+}
+
+fn registry() -> &'static Mutex<BTreeMap<&'static str, JobStatus>> {
+    static REGISTRY: OnceLock<Mutex<BTreeMap<&'static str, JobStatus>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+fn update(name: &'static str, f: impl FnOnce(&mut JobStatus)) {
+    let mut jobs = registry().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(status) = jobs.get_mut(name) {
+        f(status);
+    }
+}
+
+/// Registers a job expected to run every `interval` and returns its monitor.
+///
+/// Registering a name again resets its history.
+pub fn register_job(name: &'static str, interval: Duration) -> JobMonitor {
+    registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(name, JobStatus::new(name, interval));
+    JobMonitor { name }
+}
+
+/// Every registered job's status, by name.
+pub fn job_statuses() -> Vec<JobStatus> {
+    registry()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .collect()
+}
+
+/// Records the runs of one registered job.
+#[derive(Debug, Clone, Copy)]
+pub struct JobMonitor {
+    name: &'static str,
+}
+
+impl JobMonitor {
+    /// Runs one pass of the job, recording its outcome.
+    pub async fn run<T, E, F>(&self, run: F) -> Result<T, E>
+    where
+        F: Future<Output = Result<T, E>>,
+        E: Display,
+    {
+        let span = tracing::info_span!(
+            "job.run",
+            job.name = self.name,
+            otel.status_code = tracing::field::Empty,
+            error.message = tracing::field::Empty
+        );
+        update(self.name, |status| {
+            status.last_started_at = Some(Utc::now())
+        });
+
+        let started = Instant::now();
+        let result = run.instrument(span.clone()).await;
+        let elapsed = started.elapsed();
+
+        match &result {
+            Ok(_) => {
+                span.record("otel.status_code", "OK");
+                update(self.name, |status| {
+                    status.last_success_at = Some(Utc::now());
+                    status.consecutive_failures = 0;
+                    status.successes += 1;
+                });
+            }
+            Err(e) => {
+                let message: String = e.to_string().chars().take(MAX_ERROR_LEN).collect();
+                span.record("otel.status_code", "ERROR");
+                span.record("error.message", message.as_str());
+                update(self.name, |status| {
+                    status.last_failure_at = Some(Utc::now());
+                    status.last_error = Some(message);
+                    status.consecutive_failures += 1;
+                    status.failures += 1;
+                });
+            }
+        }
+
+        #[cfg(feature = "observability")]
+        crate::metrics::track_job_run(self.name, result.is_ok(), elapsed);
+        #[cfg(not(feature = "observability"))]
+        let _ = elapsed;
+
+        result
+    }
+
+    /// Records how much work is waiting for the job.
+    pub fn set_queue_depth(&self, depth: u64) {
+        update(self.name, |status| status.queue_depth = Some(depth));
+
+        #[cfg(feature = "observability")]
+        crate::metrics::set_job_queue_depth(self.name, depth);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_at(interval_secs: u64) -> JobStatus {
+        JobStatus::new("test_job", Duration::from_secs(interval_secs))
+    }
+
+    #[test]
+    fn test_health_follows_runs() {
+        let mut status = status_at(60);
+        let now = status.registered_at;
+        assert_eq!(status.health(now), JobHealth::Pending);
+
+        status.last_started_at = Some(now);
+        status.failures = 2;
+        status.consecutive_failures = 2;
+        assert_eq!(status.health(now), JobHealth::Healthy);
+
+        status.failures = 3;
+        status.consecutive_failures = 3;
+        assert_eq!(status.health(now), JobHealth::Failing);
+
+        let later = now + chrono::Duration::seconds(60 * 3 + 1);
+        assert_eq!(status.health(later), JobHealth::Stalled);
+    }
+
+    #[tokio::test]
+    async fn test_run_records_outcome() {
+        let job = register_job("test_run_records_outcome", Duration::from_secs(60));
+
+        let ok: Result<u32, String> = job.run(async { Ok(1) }).await;
+        assert_eq!(ok, Ok(1));
+        let err: Result<u32, String> = job.run(async { Err("boom".to_string()) }).await;
+        assert!(err.is_err());
+        job.set_queue_depth(4);
+
+        let status = job_statuses()
+            .into_iter()
+            .find(|s| s.name == "test_run_records_outcome")
+            .unwrap();
+        assert_eq!(status.successes, 1);
+        assert_eq!(status.failures, 1);
+        assert_eq!(status.consecutive_failures, 1);
+        assert_eq!(status.last_error.as_deref(), Some("boom"));
+        assert_eq!(status.queue_depth, Some(4));
+    }
+}
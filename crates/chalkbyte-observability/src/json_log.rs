@@ -0,0 +1,276 @@
+//! JSON-lines log format with request context and field redaction.
+//!
+//! [`JsonLogFormat`] writes one JSON object per event:
+//!
+//! ```json
+//! {"timestamp":"2026-10-17T09:12:03.512Z","level":"INFO","target":"chalkbyte::modules::auth",
+//!  "span":"http_request","request_id":"…","user_id":"…","school_id":"…",
+//!  "message":"Request completed successfully","status":200}
+//! ```
+//!
+//! `request_id`, `user_id`, and `school_id` are lifted from the enclosing
+//! spans (the `request.id`, `user.id`, and `school.id` fields that
+//! `logging_middleware` records), so every line of a request can be found by
+//! any of them. Event fields are written at the top level after [`redact`]
+//! has replaced anything that looks like a password, token, or
+//! authorization value.
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Replacement for redacted values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Field names are redacted when they contain any of these, case-insensitively.
+const SENSITIVE_KEYS: &[&str] = &[
+    "password",
+    "token",
+    "authorization",
+    "secret",
+    "cookie",
+    "api_key",
+    "x-api-key",
+];
+
+/// Span fields lifted to the top level, with the name they are written as.
+const CONTEXT_FIELDS: &[(&str, &str)] = &[
+    ("request.id", "request_id"),
+    ("request_id", "request_id"),
+    ("user.id", "user_id"),
+    ("user_id", "user_id"),
+    ("school.id", "school_id"),
+    ("school_id", "school_id"),
+];
+
+/// Whether a field with this name must not be logged.
+pub fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    SENSITIVE_KEYS.iter().any(|s| key.contains(s))
+}
+
+/// Redacts sensitive values in place.
+///
+/// Objects have sensitive keys replaced whole. Strings that hold a JSON
+/// document, such as a logged request body or a `HeaderMap` printed with
+/// `?`, are redacted inside and written back, and `Bearer` credentials are
+/// cut from any other string.
+pub fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_sensitive_key(key) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        Value::String(text) => {
+            let trimmed = text.trim_start();
+            if (trimmed.starts_with('{') || trimmed.starts_with('['))
+                && let Ok(mut nested) = serde_json::from_str::<Value>(text)
+            {
+                redact(&mut nested);
+                *text = nested.to_string();
+            } else if text.contains("Bearer ") {
+                *text = redact_bearer(text);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn redact_bearer(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("Bearer ") {
+        let (before, after) = rest.split_at(start + "Bearer ".len());
+        out.push_str(before);
+        out.push_str(REDACTED);
+        let end = after
+            .find(|c: char| c.is_whitespace() || c == '"' || c == ',')
+            .unwrap_or(after.len());
+        rest = &after[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Event formatter for JSON-lines logs. Use with
+/// [`JsonFields`](tracing_subscriber::fmt::format::JsonFields) so span
+/// fields can be read back.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonLogFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonLogFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let meta = event.metadata();
+        let mut line = Map::new();
+        line.insert(
+            "timestamp".into(),
+            chrono::Utc::now()
+                .to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+                .into(),
+        );
+        line.insert("level".into(), meta.level().as_str().into());
+        line.insert("target".into(), meta.target().into());
+
+        if let Some(scope) = ctx.event_scope() {
+            let mut span_name = None;
+            for span in scope.from_root() {
+                span_name = Some(span.name());
+                let extensions = span.extensions();
+                let Some(fields) = extensions.get::<FormattedFields<N>>() else {
+                    continue;
+                };
+                let Ok(Value::Object(fields)) = serde_json::from_str::<Value>(&fields.fields)
+                else {
+                    continue;
+                };
+                for (source, name) in CONTEXT_FIELDS {
+                    if let Some(value) = fields.get(*source) {
+                        line.insert((*name).into(), value.clone());
+                    }
+                }
+            }
+            if let Some(name) = span_name {
+                line.insert("span".into(), name.into());
+            }
+        }
+
+        let mut fields = Value::Object(Map::new());
+        if let Value::Object(map) = &mut fields {
+            event.record(&mut JsonVisitor(map));
+        }
+        redact(&mut fields);
+        if let Value::Object(fields) = fields {
+            for (key, value) in fields {
+                line.entry(key).or_insert(value);
+            }
+        }
+
+        writeln!(writer, "{}", Value::Object(line))
+    }
+}
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().into(), format!("{value:?}").into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tracing_subscriber::fmt::format::JsonFields;
+
+    #[test]
+    fn test_redact_sensitive_keys_and_nested_bodies() {
+        let mut value = json!({
+            "email": "ada@example.com",
+            "password": "hunter2",
+            "refresh_token": "abc",
+            "body": "{\"new_password\":\"x\",\"name\":\"Ada\"}",
+            "headers": "{\"authorization\": \"Bearer abc\", \"host\": \"localhost\"}",
+            "note": "sent Bearer eyJhbGciOi.x.y to upstream",
+        });
+        redact(&mut value);
+
+        assert_eq!(value["email"], "ada@example.com");
+        assert_eq!(value["password"], REDACTED);
+        assert_eq!(value["refresh_token"], REDACTED);
+        let body: Value = serde_json::from_str(value["body"].as_str().unwrap()).unwrap();
+        assert_eq!(body, json!({"new_password": REDACTED, "name": "Ada"}));
+        let headers: Value = serde_json::from_str(value["headers"].as_str().unwrap()).unwrap();
+        assert_eq!(headers["authorization"], REDACTED);
+        assert_eq!(headers["host"], "localhost");
+        assert_eq!(value["note"], "sent Bearer [REDACTED] to upstream");
+    }
+
+    #[test]
+    fn test_format_lifts_request_context() {
+        use std::sync::{Arc, Mutex};
+        use tracing_subscriber::layer::SubscriberExt;
+
+        #[derive(Clone, Default)]
+        struct Buffer(Arc<Mutex<Vec<u8>>>);
+        impl std::io::Write for Buffer {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .event_format(JsonLogFormat)
+                .fmt_fields(JsonFields::new())
+                .with_writer(move || writer.clone()),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!(
+                "http_request",
+                request.id = "req-1",
+                user.id = tracing::field::Empty
+            );
+            span.record("user.id", "user-1");
+            let _guard = span.enter();
+            tracing::info!(password = "hunter2", status = 200, "Request completed");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let line: Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["span"], "http_request");
+        assert_eq!(line["request_id"], "req-1");
+        assert_eq!(line["user_id"], "user-1");
+        assert_eq!(line["message"], "Request completed");
+        assert_eq!(line["status"], 200);
+        assert_eq!(line["password"], REDACTED);
+    }
+}
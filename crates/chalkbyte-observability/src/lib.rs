@@ -1,9 +1,15 @@
 //! Chalkbyte Observability Module
 //!
 //! Provides configurable observability features including:
-//! - Tracing and distributed tracing via OpenTelemetry
+//! - Tracing and distributed tracing via OpenTelemetry, with OTLP export and
+//!   sampling configured by [`ObservabilityConfig`]
 //! - Metrics collection via Prometheus
-//! - HTTP request/response logging
+//! - HTTP request/response logging, as pretty text or redacted JSON lines
+//! - Per-school request and usage counters via [`tenant`]
+//! - Slow request and slow query warnings via [`slow`]
+//! - Background job health via [`jobs`], tracked with or without the feature
+//! - Error reporting hooks via [`error_reporting`], with Sentry behind the
+//!   `sentry` feature
 //!
 //! This module can be enabled or disabled at compile time via the `observability` feature flag.
 //! At runtime, observability can be further controlled via the `OBSERVABILITY_ENABLED` environment variable.
@@ -11,15 +17,16 @@
 //! # Features
 //!
 //! - `observability` (default): Enables all observability features including tracing, logging, and metrics
+//! - `sentry`: Adds the Sentry [`error_reporting::ErrorReporter`]
 //!
 //! # Examples
 //!
 //! ```no_run
-//! use chalkbyte_observability::{init_tracing, shutdown_tracer};
+//! use chalkbyte_observability::{ObservabilityConfig, init_tracing, shutdown_tracer};
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     init_tracing();
+//!     init_tracing(&ObservabilityConfig::from_env());
 //!     // ... application code ...
 //!     shutdown_tracer().await;
 //! }
@@ -27,10 +34,20 @@
 
 #![allow(dead_code)]
 
+pub mod config;
+pub mod error_reporting;
+pub mod jobs;
+#[cfg(feature = "observability")]
+pub mod json_log;
 #[cfg(feature = "observability")]
 pub mod logging;
 #[cfg(feature = "observability")]
 pub mod metrics;
+pub mod request_span;
+#[cfg(feature = "observability")]
+pub mod sampling;
+pub mod slow;
+pub mod tenant;
 #[cfg(feature = "observability")]
 pub mod tracing_utils;
 
@@ -42,11 +59,17 @@ pub mod basic_logging;
 #[cfg(feature = "observability")]
 pub use metrics_exporter_prometheus::PrometheusHandle;
 
+pub use config::{
+    LogFormat, ObservabilityConfig, OtlpConfig, RouteSampling, SamplingConfig, SamplingStrategy,
+};
+pub use request_span::RequestSpan;
+pub use tenant::SchoolLabel;
+
 // Public exports when observability is enabled
 #[cfg(feature = "observability")]
 pub use logging::{is_observability_enabled as is_logging_enabled, logging_middleware, init_tracing, shutdown_tracer};
 #[cfg(feature = "observability")]
-pub use metrics::{is_observability_enabled as is_metrics_enabled, metrics_middleware, init_metrics, track_user_created, track_user_login_success, track_user_login_failure, track_jwt_issued, track_school_created};
+pub use metrics::{is_observability_enabled as is_metrics_enabled, metrics_middleware, init_metrics, track_user_created, track_user_login_success, track_user_login_failure, track_jwt_issued, track_school_created, track_cache_operation, track_db_query, track_school_login, track_students_created};
 
 // Common re-exports when observability is enabled
 #[cfg(feature = "observability")]
@@ -62,9 +85,19 @@ pub mod stubs {
         false
     }
 
-    /// No-op logging middleware when feature disabled
+    /// Logging middleware when feature disabled; only checks for slow requests
     pub async fn logging_middleware(req: Request, next: Next) -> Response {
-        next.run(req).await
+        let start = std::time::Instant::now();
+        let method = req.method().as_str().to_owned();
+        let route = req
+            .extensions()
+            .get::<axum::extract::MatchedPath>()
+            .map(|p| p.as_str().to_owned())
+            .unwrap_or_else(|| req.uri().path().to_owned());
+
+        let response = next.run(req).await;
+        crate::slow::observe_request(&method, &route, response.status().as_u16(), start.elapsed());
+        response
     }
 
     /// No-op metrics middleware when feature disabled
@@ -73,7 +106,7 @@ pub mod stubs {
     }
 
     /// Initialize basic console logging when observability feature disabled
-    pub fn init_tracing() {
+    pub fn init_tracing(_config: &crate::ObservabilityConfig) {
         use crate::basic_logging::init_basic_console_logging;
         init_basic_console_logging();
     }
@@ -92,6 +125,15 @@ pub mod stubs {
     pub fn track_user_login_failure(_reason: &str) {}
     pub fn track_jwt_issued() {}
     pub fn track_school_created() {}
+    pub fn track_school_login(_school_id: Option<uuid::Uuid>) {}
+    pub fn track_students_created(_school_id: uuid::Uuid, _count: u64) {}
+    pub fn track_db_query(_query_name: &'static str, _duration: std::time::Duration) {}
+    pub fn track_cache_operation(
+        _operation: &'static str,
+        _result: &'static str,
+        _duration: std::time::Duration,
+    ) {
+    }
 }
 
 #[cfg(not(feature = "observability"))]
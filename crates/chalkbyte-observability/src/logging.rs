@@ -8,7 +8,7 @@ use axum::{
 /// Defaults to true if not set
 pub fn is_observability_enabled() -> bool {
     std::env::var("OBSERVABILITY_ENABLED")
-        .map(|v| !matches!(v.to_lowercase().as_str(), "false" | "0" | "no" | "off"))
+        .map(|v| crate::config::parse_enabled(&v))
         .unwrap_or(true)
 }
 use opentelemetry::{KeyValue, global, trace::TraceError};
@@ -17,14 +17,20 @@ use opentelemetry_sdk::{
     Resource,
     propagation::TraceContextPropagator,
     runtime,
-    trace::{RandomIdGenerator, Sampler, Tracer},
+    trace::{RandomIdGenerator, Tracer},
 };
 use opentelemetry_semantic_conventions::resource::{SERVICE_NAME, SERVICE_VERSION};
 use std::time::Instant;
+use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
 use tracing::{Instrument, Span, error, field, info, info_span, warn};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 use tracing_subscriber::{EnvFilter, Layer, layer::SubscriberExt, util::SubscriberInitExt};
 
+use crate::config::{LogFormat, ObservabilityConfig};
+use crate::json_log::JsonLogFormat;
+use crate::request_span::RequestSpan;
+use crate::sampling::RouteSampler;
+
 /// Extract trace ID from the current span context for correlation
 fn get_trace_id() -> String {
     use opentelemetry::trace::TraceContextExt;
@@ -39,7 +45,10 @@ fn get_trace_id() -> String {
 }
 
 /// HTTP request/response logging middleware with full observability context
-pub async fn logging_middleware(req: Request, next: Next) -> Response {
+///
+/// The request span is also stored in the request extensions as a
+/// [`RequestSpan`], so the auth extractor can record the caller on it.
+pub async fn logging_middleware(mut req: Request, next: Next) -> Response {
     let start = Instant::now();
     let method = req.method().as_str().to_owned();
     let uri_path = req.uri().path().to_owned();
@@ -97,6 +106,8 @@ pub async fn logging_middleware(req: Request, next: Next) -> Response {
         http.status_code = field::Empty,
         http.response_content_length = field::Empty,
         request.id = %request_id,
+        user.id = field::Empty,
+        school.id = field::Empty,
         trace.id = field::Empty,
         otel.kind = "server",
         otel.status_code = field::Empty,
@@ -104,6 +115,8 @@ pub async fn logging_middleware(req: Request, next: Next) -> Response {
         latency_ms = field::Empty,
     );
 
+    req.extensions_mut().insert(RequestSpan::new(span.clone()));
+
     // Execute the request within the span
     async move {
         info!(
@@ -121,6 +134,8 @@ pub async fn logging_middleware(req: Request, next: Next) -> Response {
         let status = response.status();
         let status_code = status.as_u16();
 
+        crate::slow::observe_request(&method, &matched_path, status_code, latency);
+
         // Get response content length if available
         let content_length = response
             .headers()
@@ -204,19 +219,16 @@ pub async fn logging_middleware(req: Request, next: Next) -> Response {
     .await
 }
 
-fn init_tracer() -> Result<Tracer, TraceError> {
-    // Get OTLP endpoint from environment or use default
-    let otlp_endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
-        .unwrap_or_else(|_| "http://localhost:4317".to_string());
-
-    let service_name =
-        std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| env!("CARGO_PKG_NAME").to_string());
-
-    let environment = std::env::var("ENVIRONMENT").unwrap_or_else(|_| "development".to_string());
-
+fn init_tracer(config: &ObservabilityConfig) -> Result<Tracer, TraceError> {
+    let sampling = &config.sampling;
     info!(
-        "Initializing OpenTelemetry tracer with endpoint: {}, service: {}, environment: {}",
-        otlp_endpoint, service_name, environment
+        endpoint = %config.otlp.endpoint,
+        service = %config.service_name,
+        environment = %config.environment,
+        sampler = ?sampling.strategy,
+        parent_based = sampling.parent_based,
+        route_overrides = sampling.route_overrides.len(),
+        "Initializing OpenTelemetry tracer"
     );
 
     // Set up trace context propagator for distributed tracing
@@ -224,41 +236,40 @@ fn init_tracer() -> Result<Tracer, TraceError> {
 
     // Configure resource with service information following semantic conventions
     let resource = Resource::new(vec![
-        KeyValue::new(SERVICE_NAME, service_name),
+        KeyValue::new(SERVICE_NAME, config.service_name.clone()),
         KeyValue::new(SERVICE_VERSION, env!("CARGO_PKG_VERSION")),
-        KeyValue::new("environment", environment.clone()),
+        KeyValue::new("environment", config.environment.clone()),
         KeyValue::new("service.namespace", "chalkbyte"),
-        KeyValue::new("deployment.environment", environment),
+        KeyValue::new("deployment.environment", config.environment.clone()),
     ]);
 
-    // Build OTLP exporter with timeout
+    // Headers that are not valid gRPC metadata are skipped rather than
+    // failing the whole exporter
+    let mut metadata = MetadataMap::new();
+    for (key, value) in &config.otlp.headers {
+        match (
+            MetadataKey::from_bytes(key.as_bytes()),
+            MetadataValue::try_from(value.as_str()),
+        ) {
+            (Ok(key), Ok(value)) => {
+                metadata.insert(key, value);
+            }
+            _ => warn!(header = %key, "Skipping invalid OTLP header"),
+        }
+    }
+
     let otlp_exporter = opentelemetry_otlp::new_exporter()
         .tonic()
-        .with_endpoint(otlp_endpoint)
-        .with_timeout(std::time::Duration::from_secs(5));
-
-    // Build and install tracer provider with sampling configuration
-    let sampler = match std::env::var("OTEL_TRACES_SAMPLER")
-        .unwrap_or_else(|_| "always_on".to_string())
-        .as_str()
-    {
-        "always_off" => Sampler::AlwaysOff,
-        "trace_id_ratio" => {
-            let ratio: f64 = std::env::var("OTEL_TRACES_SAMPLER_ARG")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(1.0);
-            Sampler::TraceIdRatioBased(ratio)
-        }
-        _ => Sampler::AlwaysOn,
-    };
+        .with_endpoint(config.otlp.endpoint.clone())
+        .with_timeout(config.otlp.timeout)
+        .with_metadata(metadata);
 
     let tracer = opentelemetry_otlp::new_pipeline()
         .tracing()
         .with_exporter(otlp_exporter)
         .with_trace_config(
             opentelemetry_sdk::trace::Config::default()
-                .with_sampler(sampler)
+                .with_sampler(RouteSampler::new(sampling.clone()))
                 .with_id_generator(RandomIdGenerator::default())
                 .with_max_events_per_span(64)
                 .with_max_attributes_per_span(128)
@@ -269,11 +280,10 @@ fn init_tracer() -> Result<Tracer, TraceError> {
     Ok(tracer)
 }
 
-pub fn init_tracing() {
+pub fn init_tracing(config: &ObservabilityConfig) {
     use std::fs::OpenOptions;
     use tracing_subscriber::fmt;
-
-    let observability_enabled = is_observability_enabled();
+    use tracing_subscriber::fmt::format::JsonFields;
 
     // Determine log level from environment
     let log_level = std::env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
@@ -287,18 +297,26 @@ pub fn init_tracing() {
         ))
     });
 
-    let console_layer = fmt::layer()
-        .with_target(true)
-        .with_thread_ids(false)
-        .with_thread_names(false)
-        .with_file(true)
-        .with_line_number(true)
-        .with_level(true)
-        .compact()
-        .with_filter(console_filter);
+    let console_layer = match config.log_format {
+        LogFormat::Pretty => fmt::layer()
+            .with_target(true)
+            .with_thread_ids(false)
+            .with_thread_names(false)
+            .with_file(true)
+            .with_line_number(true)
+            .with_level(true)
+            .compact()
+            .with_filter(console_filter)
+            .boxed(),
+        LogFormat::Json => fmt::layer()
+            .event_format(JsonLogFormat)
+            .fmt_fields(JsonFields::new())
+            .with_filter(console_filter)
+            .boxed(),
+    };
 
     // If observability is disabled, only use console logging
-    if !observability_enabled {
+    if !config.enabled {
         tracing_subscriber::registry().with(console_layer).init();
         eprintln!(
             "ℹ️  Observability disabled - console logging only (OBSERVABILITY_ENABLED=false)"
@@ -350,16 +368,13 @@ pub fn init_tracing() {
     ));
 
     let json_layer = fmt::layer()
-        .json()
+        .event_format(JsonLogFormat)
+        .fmt_fields(JsonFields::new())
         .with_writer(non_blocking_json)
-        .with_current_span(true)
-        .with_span_list(true)
-        .with_target(true)
-        .flatten_event(true)
         .with_filter(json_filter);
 
     // Try to initialize OpenTelemetry tracer
-    match init_tracer() {
+    match init_tracer(config) {
         Ok(tracer) => {
             // OpenTelemetry layer
             let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
@@ -373,7 +388,7 @@ pub fn init_tracing() {
                 .init();
 
             info!(
-                service.name = env!("CARGO_PKG_NAME"),
+                service.name = %config.service_name,
                 service.version = env!("CARGO_PKG_VERSION"),
                 "Tracing initialized with OpenTelemetry and file logging"
             );
@@ -9,6 +9,9 @@ use metrics::{counter, gauge, histogram};
 use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
 use std::sync::OnceLock;
 use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::tenant::{SchoolLabel, school_label};
 
 static OBSERVABILITY_ENABLED: OnceLock<bool> = OnceLock::new();
 
@@ -37,6 +40,27 @@ pub fn init_metrics() -> Option<PrometheusHandle> {
             ],
         )
         .expect("Failed to set buckets")
+        .set_buckets_for_metric(
+            Matcher::Full("db_query_duration_seconds".to_string()),
+            &[
+                0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+            ],
+        )
+        .expect("Failed to set buckets")
+        .set_buckets_for_metric(
+            Matcher::Full("cache_operation_duration_seconds".to_string()),
+            &[
+                0.0001, 0.00025, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.5,
+            ],
+        )
+        .expect("Failed to set buckets")
+        .set_buckets_for_metric(
+            Matcher::Full("job_run_duration_seconds".to_string()),
+            &[
+                0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 300.0, 900.0,
+            ],
+        )
+        .expect("Failed to set buckets")
         .install_recorder()
         .expect("Failed to install Prometheus recorder");
 
@@ -53,7 +77,10 @@ pub fn init_metrics() -> Option<PrometheusHandle> {
 }
 
 /// Metrics middleware to track HTTP requests
-pub async fn metrics_middleware(req: Request, next: Next) -> Response {
+///
+/// `http_requests_total` also carries a `school_id` label, filled in by the
+/// auth extractor through [`SchoolLabel`].
+pub async fn metrics_middleware(mut req: Request, next: Next) -> Response {
     if !is_observability_enabled() {
         return next.run(req).await;
     }
@@ -68,6 +95,9 @@ pub async fn metrics_middleware(req: Request, next: Next) -> Response {
         .map(|p| p.as_str().to_owned())
         .unwrap_or(uri_path);
 
+    let school = SchoolLabel::default();
+    req.extensions_mut().insert(school.clone());
+
     // Increment active requests
     gauge!("http_requests_active").increment(1.0);
 
@@ -78,7 +108,7 @@ pub async fn metrics_middleware(req: Request, next: Next) -> Response {
     let status_str = status.to_string();
 
     // Record metrics
-    counter!("http_requests_total", "method" => method.clone(), "path" => path.clone(), "status" => status_str).increment(1);
+    counter!("http_requests_total", "method" => method.clone(), "path" => path.clone(), "status" => status_str, "school_id" => school.value()).increment(1);
 
     histogram!("http_request_duration_seconds", "method" => method, "path" => path).record(latency);
 
@@ -126,24 +156,46 @@ pub fn track_user_login_failure(reason: &str) {
     counter!("user_logins_total", "role" => "unknown", "status" => "failure", "reason" => reason.to_string()).increment(1);
 }
 
-/// Track database operations
-#[allow(dead_code)]
-pub fn track_db_query(operation: &str, success: bool) {
+/// Track a successful login per school, for tenant billing and alerting
+pub fn track_school_login(school_id: Option<Uuid>) {
     if !is_observability_enabled() {
         return;
     }
-    let status = if success { "success" } else { "error" };
-    counter!("database_queries_total", "operation" => operation.to_string(), "status" => status)
-        .increment(1);
+    counter!("school_logins_total", "school_id" => school_label(school_id)).increment(1);
 }
 
-#[allow(dead_code)]
-pub fn track_db_query_duration(operation: &str, duration_secs: f64) {
+/// Track students created per school
+pub fn track_students_created(school_id: Uuid, count: u64) {
+    if !is_observability_enabled() || count == 0 {
+        return;
+    }
+    counter!("school_students_created_total", "school_id" => school_label(Some(school_id)))
+        .increment(count);
+}
+
+/// Track a database query's latency
+///
+/// `query_name` labels the series, so it must come from a fixed set such as
+/// `"students.list"`, never from user input.
+pub fn track_db_query(query_name: &'static str, duration: Duration) {
+    if !is_observability_enabled() {
+        return;
+    }
+    histogram!("db_query_duration_seconds", "query" => query_name).record(duration.as_secs_f64());
+}
+
+/// Track a cache call's latency and result
+///
+/// `result` is `"hit"` or `"miss"` for lookups and `"ok"` or `"error"`
+/// otherwise, so the hit ratio is
+/// `cache_operations_total{result="hit"}` over hits plus misses.
+pub fn track_cache_operation(operation: &'static str, result: &'static str, duration: Duration) {
     if !is_observability_enabled() {
         return;
     }
-    histogram!("database_query_duration_seconds", "operation" => operation.to_string())
-        .record(duration_secs);
+    counter!("cache_operations_total", "operation" => operation, "result" => result).increment(1);
+    histogram!("cache_operation_duration_seconds", "operation" => operation)
+        .record(duration.as_secs_f64());
 }
 
 /// Track school operations
@@ -213,6 +265,30 @@ pub fn track_api_error(error_type: &str, endpoint: &str) {
     counter!("api_errors_total", "error_type" => error_type.to_string(), "endpoint" => endpoint.to_string()).increment(1);
 }
 
+/// Track a background job run
+pub fn track_job_run(job: &'static str, success: bool, duration: Duration) {
+    if !is_observability_enabled() {
+        return;
+    }
+    let status = if success { "success" } else { "failure" };
+    counter!("job_runs_total", "job" => job, "status" => status).increment(1);
+    histogram!("job_run_duration_seconds", "job" => job).record(duration.as_secs_f64());
+    if success {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        gauge!("job_last_success_timestamp_seconds", "job" => job).set(now.as_secs_f64());
+    }
+}
+
+/// Set the work waiting for a background job
+pub fn set_job_queue_depth(job: &'static str, depth: u64) {
+    if !is_observability_enabled() {
+        return;
+    }
+    gauge!("job_queue_depth", "job" => job).set(depth as f64);
+}
+
 /// Track authorization events
 #[allow(dead_code)]
 pub fn track_authorization_check(allowed: bool, role: &str) {
@@ -0,0 +1,27 @@
+//! The request span as a request extension.
+//!
+//! `logging_middleware` opens the `http_request` span before anyone knows
+//! who is calling. It stores the span in the request extensions as a
+//! [`RequestSpan`], and the auth extractor records the caller on it once
+//! the token is verified, so every later log line of the request carries
+//! `user.id` and `school.id`.
+
+use tracing::Span;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct RequestSpan(Span);
+
+impl RequestSpan {
+    pub fn new(span: Span) -> Self {
+        Self(span)
+    }
+
+    /// Records the authenticated caller on the request span.
+    pub fn record_caller(&self, user_id: Uuid, school_id: Option<Uuid>) {
+        self.0.record("user.id", user_id.to_string());
+        if let Some(school_id) = school_id {
+            self.0.record("school.id", school_id.to_string());
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Trace sampler with per-route overrides.
+//!
+//! [`RouteSampler`] looks at the `http.route` attribute that
+//! `logging_middleware` puts on every request span. Routes with an override
+//! are sampled at the override ratio regardless of the parent; everything
+//! else, including child spans, goes to the configured sampler. With a
+//! parent-based sampler, children of a kept auth request are kept too.
+
+use opentelemetry::{
+    Context, KeyValue,
+    trace::{Link, SamplingResult, SpanKind, TraceId},
+};
+use opentelemetry_sdk::trace::{Sampler, ShouldSample};
+
+use crate::config::{SamplingConfig, SamplingStrategy};
+
+/// Span attribute carrying the matched route of a request span.
+const ROUTE_ATTRIBUTE: &str = "http.route";
+
+#[derive(Clone, Debug)]
+pub struct RouteSampler {
+    config: SamplingConfig,
+    fallback: Sampler,
+}
+
+impl RouteSampler {
+    pub fn new(config: SamplingConfig) -> Self {
+        let base = match config.strategy {
+            SamplingStrategy::AlwaysOn => Sampler::AlwaysOn,
+            SamplingStrategy::AlwaysOff => Sampler::AlwaysOff,
+            SamplingStrategy::Ratio(ratio) => Sampler::TraceIdRatioBased(ratio),
+        };
+        let fallback = if config.parent_based {
+            Sampler::ParentBased(Box::new(base))
+        } else {
+            base
+        };
+
+        Self { config, fallback }
+    }
+}
+
+impl ShouldSample for RouteSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        let route_ratio = attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == ROUTE_ATTRIBUTE)
+            .and_then(|kv| self.config.route_ratio(&kv.value.as_str()));
+
+        match route_ratio {
+            Some(ratio) => Sampler::TraceIdRatioBased(ratio).should_sample(
+                parent_context,
+                trace_id,
+                name,
+                span_kind,
+                attributes,
+                links,
+            ),
+            None => self.fallback.should_sample(
+                parent_context,
+                trace_id,
+                name,
+                span_kind,
+                attributes,
+                links,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::SamplingDecision;
+
+    fn decide(sampler: &RouteSampler, route: Option<&'static str>) -> SamplingDecision {
+        let attributes: Vec<KeyValue> = route
+            .map(|r| vec![KeyValue::new(ROUTE_ATTRIBUTE, r)])
+            .unwrap_or_default();
+        sampler
+            .should_sample(
+                None,
+                TraceId::from_bytes(0x1234_5678_9abc_def0_u128.to_be_bytes()),
+                "http_request",
+                &SpanKind::Server,
+                &attributes,
+                &[],
+            )
+            .decision
+    }
+
+    #[test]
+    fn test_route_overrides_win_over_ratio() {
+        let sampler = RouteSampler::new(SamplingConfig::parse(
+            Some("parentbased_always_off"),
+            None,
+            "/api/auth=1.0,/health=0",
+        ));
+
+        assert_eq!(
+            decide(&sampler, Some("/api/auth/login")),
+            SamplingDecision::RecordAndSample
+        );
+        assert_eq!(decide(&sampler, Some("/api/users")), SamplingDecision::Drop);
+        assert_eq!(decide(&sampler, None), SamplingDecision::Drop);
+
+        let sampler = RouteSampler::new(SamplingConfig::parse(None, None, "/health=0"));
+        assert_eq!(decide(&sampler, Some("/health")), SamplingDecision::Drop);
+        assert_eq!(
+            decide(&sampler, Some("/api/users")),
+            SamplingDecision::RecordAndSample
+        );
+    }
+}
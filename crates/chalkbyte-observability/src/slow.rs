@@ -0,0 +1,164 @@
+//! Slow request and slow query detection.
+//!
+//! Requests slower than `SLOW_REQUEST_MS` (default 1000) and queries run
+//! through `chalkbyte_db::timed` slower than `SLOW_QUERY_MS` (default 250)
+//! are logged as warnings. The warning is emitted inside the current span,
+//! so it carries the request's route, request ID, and caller like any other
+//! log line of the request. A threshold of `0` turns that check off.
+//!
+//! Slow requests are also counted per endpoint in memory, and
+//! [`slowest_endpoints`] lists the endpoints that were slow most often.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tracing::warn;
+
+pub const DEFAULT_SLOW_REQUEST_MS: u64 = 1000;
+pub const DEFAULT_SLOW_QUERY_MS: u64 = 250;
+
+/// Most endpoints tracked; slow requests to further endpoints, such as
+/// unmatched paths, are logged but not counted.
+const MAX_TRACKED_ENDPOINTS: usize = 1000;
+
+/// How often an endpoint has been slow since the process started.
+#[derive(Debug, Clone)]
+pub struct SlowEndpoint {
+    pub method: String,
+    pub route: String,
+    pub count: u64,
+    pub total: Duration,
+    pub max: Duration,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+fn threshold(var: &str, default_ms: u64) -> Option<Duration> {
+    let ms = std::env::var(var)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(default_ms);
+    (ms > 0).then(|| Duration::from_millis(ms))
+}
+
+/// The slow request threshold, or `None` when the check is off.
+pub fn slow_request_threshold() -> Option<Duration> {
+    static THRESHOLD: OnceLock<Option<Duration>> = OnceLock::new();
+    *THRESHOLD.get_or_init(|| threshold("SLOW_REQUEST_MS", DEFAULT_SLOW_REQUEST_MS))
+}
+
+/// The slow query threshold, or `None` when the check is off.
+pub fn slow_query_threshold() -> Option<Duration> {
+    static THRESHOLD: OnceLock<Option<Duration>> = OnceLock::new();
+    *THRESHOLD.get_or_init(|| threshold("SLOW_QUERY_MS", DEFAULT_SLOW_QUERY_MS))
+}
+
+fn registry() -> &'static Mutex<HashMap<(String, String), SlowEndpoint>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<(String, String), SlowEndpoint>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Warns about and counts a request if it exceeded the threshold.
+pub fn observe_request(method: &str, route: &str, status: u16, elapsed: Duration) {
+    let Some(threshold) = slow_request_threshold() else {
+        return;
+    };
+    if elapsed < threshold {
+        return;
+    }
+
+    warn!(
+        http.method = %method,
+        http.route = %route,
+        http.status_code = status,
+        latency_ms = elapsed.as_millis() as u64,
+        threshold_ms = threshold.as_millis() as u64,
+        "Slow request"
+    );
+    record(
+        &mut registry().lock().unwrap_or_else(|e| e.into_inner()),
+        method,
+        route,
+        elapsed,
+    );
+}
+
+/// Warns about a query if it exceeded the threshold.
+pub fn observe_query(query_name: &'static str, elapsed: Duration) {
+    let Some(threshold) = slow_query_threshold() else {
+        return;
+    };
+    if elapsed < threshold {
+        return;
+    }
+
+    warn!(
+        db.query = query_name,
+        latency_ms = elapsed.as_millis() as u64,
+        threshold_ms = threshold.as_millis() as u64,
+        "Slow query"
+    );
+}
+
+fn record(
+    endpoints: &mut HashMap<(String, String), SlowEndpoint>,
+    method: &str,
+    route: &str,
+    elapsed: Duration,
+) {
+    let key = (method.to_string(), route.to_string());
+    if !endpoints.contains_key(&key) && endpoints.len() >= MAX_TRACKED_ENDPOINTS {
+        return;
+    }
+
+    let now = Utc::now();
+    let endpoint = endpoints.entry(key).or_insert_with(|| SlowEndpoint {
+        method: method.to_string(),
+        route: route.to_string(),
+        count: 0,
+        total: Duration::ZERO,
+        max: Duration::ZERO,
+        last_seen_at: now,
+    });
+    endpoint.count += 1;
+    endpoint.total += elapsed;
+    endpoint.max = endpoint.max.max(elapsed);
+    endpoint.last_seen_at = now;
+}
+
+/// Up to `limit` endpoints that were slow most often, slowest first on ties.
+pub fn slowest_endpoints(limit: usize) -> Vec<SlowEndpoint> {
+    let endpoints = registry().lock().unwrap_or_else(|e| e.into_inner());
+    top(endpoints.values(), limit)
+}
+
+fn top<'a>(endpoints: impl Iterator<Item = &'a SlowEndpoint>, limit: usize) -> Vec<SlowEndpoint> {
+    let mut endpoints: Vec<SlowEndpoint> = endpoints.cloned().collect();
+    endpoints.sort_by(|a, b| b.count.cmp(&a.count).then(b.max.cmp(&a.max)));
+    endpoints.truncate(limit);
+    endpoints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_ranks_endpoints() {
+        let mut endpoints = HashMap::new();
+        let ms = Duration::from_millis;
+        record(&mut endpoints, "GET", "/api/students", ms(1200));
+        record(&mut endpoints, "GET", "/api/students", ms(3000));
+        record(&mut endpoints, "POST", "/api/users", ms(5000));
+        record(&mut endpoints, "GET", "/api/levels", ms(1100));
+
+        let ranked = top(endpoints.values(), 2);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].route, "/api/students");
+        assert_eq!(ranked[0].count, 2);
+        assert_eq!(ranked[0].max, ms(3000));
+        assert_eq!(ranked[0].total, ms(4200));
+        assert_eq!(ranked[1].route, "/api/users");
+    }
+}
@@ -0,0 +1,104 @@
+//! Per-school metric labels.
+//!
+//! The metrics middleware runs before authentication, so it cannot read the
+//! school from the token itself. Instead it puts a [`SchoolLabel`] in the
+//! request extensions, the auth extractor fills it in once the token is
+//! verified, and the middleware reads it back when the response is ready.
+//!
+//! Every school is a new label value, so [`school_label`] caps how many
+//! distinct schools get their own series: the first `METRICS_MAX_SCHOOL_LABELS`
+//! schools seen (default 500) keep their ID and the rest share `"other"`.
+//! Requests without a school, such as logins and system admin calls, are
+//! labelled `"none"`.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use uuid::Uuid;
+
+/// Label for requests that carry no school.
+pub const NO_SCHOOL: &str = "none";
+
+/// Label shared by schools past the cardinality limit.
+pub const OVERFLOW_SCHOOL: &str = "other";
+
+const DEFAULT_MAX_SCHOOL_LABELS: usize = 500;
+
+/// Request extension that carries the authenticated user's school to the
+/// metrics middleware.
+#[derive(Debug, Clone, Default)]
+pub struct SchoolLabel(Arc<OnceLock<Uuid>>);
+
+impl SchoolLabel {
+    /// Records the request's school. Later calls are ignored.
+    pub fn set(&self, school_id: Uuid) {
+        let _ = self.0.set(school_id);
+    }
+
+    /// The label value for the request's school.
+    pub fn value(&self) -> String {
+        school_label(self.0.get().copied())
+    }
+}
+
+fn max_school_labels() -> usize {
+    static MAX: OnceLock<usize> = OnceLock::new();
+    *MAX.get_or_init(|| {
+        std::env::var("METRICS_MAX_SCHOOL_LABELS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_MAX_SCHOOL_LABELS)
+    })
+}
+
+fn seen_schools() -> &'static Mutex<HashSet<Uuid>> {
+    static SEEN: OnceLock<Mutex<HashSet<Uuid>>> = OnceLock::new();
+    SEEN.get_or_init(Default::default)
+}
+
+/// The `school_id` label value for `school_id`, within the cardinality limit.
+pub fn school_label(school_id: Option<Uuid>) -> String {
+    match school_id {
+        Some(id) => guarded_label(
+            &mut seen_schools().lock().unwrap_or_else(|e| e.into_inner()),
+            id,
+            max_school_labels(),
+        ),
+        None => NO_SCHOOL.to_string(),
+    }
+}
+
+fn guarded_label(seen: &mut HashSet<Uuid>, school_id: Uuid, limit: usize) -> String {
+    if seen.contains(&school_id) || (seen.len() < limit && seen.insert(school_id)) {
+        school_id.to_string()
+    } else {
+        OVERFLOW_SCHOOL.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guarded_label_caps_distinct_schools() {
+        let mut seen = HashSet::new();
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        assert_eq!(guarded_label(&mut seen, first, 1), first.to_string());
+        assert_eq!(guarded_label(&mut seen, second, 1), OVERFLOW_SCHOOL);
+        assert_eq!(guarded_label(&mut seen, first, 1), first.to_string());
+    }
+
+    #[test]
+    fn test_school_label_set_once() {
+        let label = SchoolLabel::default();
+        assert_eq!(label.value(), NO_SCHOOL);
+
+        let school_id = Uuid::new_v4();
+        label.clone().set(school_id);
+        label.set(Uuid::new_v4());
+        assert_eq!(label.value(), school_id.to_string());
+    }
+}
@@ -0,0 +1,77 @@
+-- Staff Leave Migration
+-- Tracks leave/absence requests for school staff with an admin approval workflow
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('staff_leave:request', 'Submit and cancel own leave requests', 'staff_leave'),
+    ('staff_leave:read', 'View staff leave requests and calendar', 'staff_leave'),
+    ('staff_leave:approve', 'Approve or reject staff leave requests', 'staff_leave');
+
+-- ============================================
+-- Staff Leave Requests Table
+-- ============================================
+CREATE TABLE staff_leave_requests (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    staff_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    leave_type TEXT NOT NULL,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    reason TEXT,
+    status TEXT NOT NULL DEFAULT 'pending',
+    substitute_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at TIMESTAMPTZ,
+    review_note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_leave_dates CHECK (start_date <= end_date),
+    CONSTRAINT valid_leave_type CHECK (
+        leave_type IN ('annual', 'sick', 'maternity', 'paternity', 'study', 'compassionate', 'unpaid', 'other')
+    ),
+    CONSTRAINT valid_leave_status CHECK (
+        status IN ('pending', 'approved', 'rejected', 'cancelled')
+    )
+);
+
+CREATE INDEX idx_staff_leave_requests_school_id ON staff_leave_requests(school_id);
+CREATE INDEX idx_staff_leave_requests_staff_id ON staff_leave_requests(staff_id);
+CREATE INDEX idx_staff_leave_requests_status ON staff_leave_requests(status);
+CREATE INDEX idx_staff_leave_requests_dates ON staff_leave_requests(start_date, end_date);
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_staff_leave_requests_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_staff_leave_requests_updated_at
+    BEFORE UPDATE ON staff_leave_requests
+    FOR EACH ROW
+    EXECUTE FUNCTION update_staff_leave_requests_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'staff_leave:%';
+
+-- School Admin manages leave for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name IN ('staff_leave:request', 'staff_leave:read', 'staff_leave:approve');
+
+-- Teachers can request their own leave
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name IN ('staff_leave:request');
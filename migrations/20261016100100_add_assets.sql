@@ -0,0 +1,111 @@
+-- Assets Migration
+-- School inventory/asset register with room/staff assignment and condition history
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('assets:create', 'Register new assets', 'assets'),
+    ('assets:read', 'View the asset register', 'assets'),
+    ('assets:update', 'Update assets and record condition checks', 'assets'),
+    ('assets:delete', 'Remove assets from the register', 'assets'),
+    ('assets:assign', 'Assign assets to rooms or staff', 'assets');
+
+-- ============================================
+-- Assets Table
+-- ============================================
+CREATE TABLE assets (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    asset_tag VARCHAR(50) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    category TEXT NOT NULL,
+    serial_number VARCHAR(100),
+    description TEXT,
+    purchase_date DATE,
+    condition TEXT NOT NULL DEFAULT 'good',
+    status TEXT NOT NULL DEFAULT 'available',
+    room VARCHAR(100),
+    assigned_to UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_asset_tag_per_school UNIQUE (school_id, asset_tag),
+    CONSTRAINT valid_asset_category CHECK (
+        category IN ('device', 'lab_equipment', 'furniture', 'sports', 'vehicle', 'other')
+    ),
+    CONSTRAINT valid_asset_condition CHECK (
+        condition IN ('new', 'good', 'fair', 'poor', 'broken')
+    ),
+    CONSTRAINT valid_asset_status CHECK (
+        status IN ('available', 'assigned', 'under_repair', 'lost', 'retired')
+    )
+);
+
+CREATE INDEX idx_assets_school_id ON assets(school_id);
+CREATE INDEX idx_assets_category ON assets(category);
+CREATE INDEX idx_assets_status ON assets(status);
+CREATE INDEX idx_assets_assigned_to ON assets(assigned_to);
+
+-- ============================================
+-- Asset Assignment History
+-- ============================================
+CREATE TABLE asset_assignments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    asset_id UUID NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    room VARCHAR(100),
+    assigned_to UUID REFERENCES users(id) ON DELETE SET NULL,
+    assigned_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    notes TEXT,
+    assigned_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    returned_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_asset_assignments_asset_id ON asset_assignments(asset_id);
+CREATE INDEX idx_asset_assignments_assigned_to ON asset_assignments(assigned_to);
+
+-- ============================================
+-- Asset Condition History
+-- ============================================
+CREATE TABLE asset_condition_logs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    asset_id UUID NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    condition TEXT NOT NULL,
+    notes TEXT,
+    recorded_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_asset_log_condition CHECK (
+        condition IN ('new', 'good', 'fair', 'poor', 'broken')
+    )
+);
+
+CREATE INDEX idx_asset_condition_logs_asset_id ON asset_condition_logs(asset_id);
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_assets_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_assets_updated_at
+    BEFORE UPDATE ON assets
+    FOR EACH ROW
+    EXECUTE FUNCTION update_assets_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'assets:%';
+
+-- School Admin manages the register for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'assets:%';
@@ -0,0 +1,123 @@
+-- Boarding Migration
+-- Hostels, rooms with capacity, per-session student room allocation, and boarding fee lines
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('boarding:create', 'Create hostels, rooms, and boarding fee lines', 'boarding'),
+    ('boarding:read', 'View hostels, rooms, allocations, and boarding fees', 'boarding'),
+    ('boarding:update', 'Update hostels and rooms', 'boarding'),
+    ('boarding:delete', 'Delete hostels, rooms, and boarding fee lines', 'boarding'),
+    ('boarding:allocate', 'Allocate students to hostel rooms', 'boarding');
+
+-- ============================================
+-- Hostels Table
+-- ============================================
+CREATE TABLE hostels (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    gender TEXT NOT NULL DEFAULT 'mixed',
+    warden_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_hostel_name_per_school UNIQUE (school_id, name),
+    CONSTRAINT valid_hostel_gender CHECK (gender IN ('male', 'female', 'mixed'))
+);
+
+CREATE INDEX idx_hostels_school_id ON hostels(school_id);
+
+-- ============================================
+-- Hostel Rooms Table
+-- ============================================
+CREATE TABLE hostel_rooms (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    hostel_id UUID NOT NULL REFERENCES hostels(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    capacity INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_room_name_per_hostel UNIQUE (hostel_id, name),
+    CONSTRAINT positive_room_capacity CHECK (capacity > 0)
+);
+
+CREATE INDEX idx_hostel_rooms_hostel_id ON hostel_rooms(hostel_id);
+
+-- ============================================
+-- Room Allocations Table
+-- ============================================
+-- A student holds at most one bed per academic session
+CREATE TABLE room_allocations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    room_id UUID NOT NULL REFERENCES hostel_rooms(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    academic_session_id UUID NOT NULL REFERENCES academic_sessions(id) ON DELETE CASCADE,
+    allocated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_student_allocation_per_session UNIQUE (student_id, academic_session_id)
+);
+
+CREATE INDEX idx_room_allocations_room_id ON room_allocations(room_id);
+CREATE INDEX idx_room_allocations_session ON room_allocations(academic_session_id);
+
+-- ============================================
+-- Boarding Fee Lines Table
+-- ============================================
+-- Amounts are stored in minor currency units (e.g. kobo, cents)
+CREATE TABLE boarding_fee_lines (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    hostel_id UUID NOT NULL REFERENCES hostels(id) ON DELETE CASCADE,
+    academic_session_id UUID NOT NULL REFERENCES academic_sessions(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    amount BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_fee_line_per_hostel_session UNIQUE (hostel_id, academic_session_id, name),
+    CONSTRAINT non_negative_fee_amount CHECK (amount >= 0)
+);
+
+CREATE INDEX idx_boarding_fee_lines_hostel_session ON boarding_fee_lines(hostel_id, academic_session_id);
+
+-- ============================================
+-- Triggers for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_hostels_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_hostels_updated_at
+    BEFORE UPDATE ON hostels
+    FOR EACH ROW
+    EXECUTE FUNCTION update_hostels_updated_at();
+
+CREATE OR REPLACE FUNCTION update_hostel_rooms_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_hostel_rooms_updated_at
+    BEFORE UPDATE ON hostel_rooms
+    FOR EACH ROW
+    EXECUTE FUNCTION update_hostel_rooms_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'boarding:%';
+
+-- School Admin manages boarding for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'boarding:%';
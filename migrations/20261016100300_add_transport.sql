@@ -0,0 +1,153 @@
+-- Transport Migration
+-- Bus routes with stops, vehicles and drivers, per-term student route assignments,
+-- and optional transport fee charges
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('transport:create', 'Create vehicles, routes, and stops', 'transport'),
+    ('transport:read', 'View vehicles, routes, assignments, and manifests', 'transport'),
+    ('transport:update', 'Update vehicles and routes, and generate transport fees', 'transport'),
+    ('transport:delete', 'Delete vehicles, routes, and stops', 'transport'),
+    ('transport:assign', 'Assign students to transport routes', 'transport'),
+    ('transport:manifest', 'View manifests for routes you drive', 'transport');
+
+-- ============================================
+-- Vehicles Table
+-- ============================================
+CREATE TABLE transport_vehicles (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    registration_number VARCHAR(20) NOT NULL,
+    description VARCHAR(255),
+    capacity INTEGER NOT NULL,
+    driver_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_vehicle_registration_per_school UNIQUE (school_id, registration_number),
+    CONSTRAINT positive_vehicle_capacity CHECK (capacity > 0)
+);
+
+CREATE INDEX idx_transport_vehicles_school_id ON transport_vehicles(school_id);
+CREATE INDEX idx_transport_vehicles_driver_id ON transport_vehicles(driver_id);
+
+-- ============================================
+-- Routes Table
+-- ============================================
+-- fee_amount is in minor currency units; NULL means the route is free
+CREATE TABLE transport_routes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    vehicle_id UUID REFERENCES transport_vehicles(id) ON DELETE SET NULL,
+    fee_amount BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_route_name_per_school UNIQUE (school_id, name),
+    CONSTRAINT non_negative_route_fee CHECK (fee_amount IS NULL OR fee_amount >= 0)
+);
+
+CREATE INDEX idx_transport_routes_school_id ON transport_routes(school_id);
+CREATE INDEX idx_transport_routes_vehicle_id ON transport_routes(vehicle_id);
+
+-- ============================================
+-- Route Stops Table
+-- ============================================
+CREATE TABLE route_stops (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    route_id UUID NOT NULL REFERENCES transport_routes(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    sequence INTEGER NOT NULL,
+    pickup_time TIME,
+    dropoff_time TIME,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_stop_sequence_per_route UNIQUE (route_id, sequence),
+    CONSTRAINT positive_stop_sequence CHECK (sequence > 0)
+);
+
+CREATE INDEX idx_route_stops_route_id ON route_stops(route_id);
+
+-- ============================================
+-- Route Assignments Table
+-- ============================================
+-- A student rides at most one route per term
+CREATE TABLE route_assignments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    route_id UUID NOT NULL REFERENCES transport_routes(id) ON DELETE CASCADE,
+    stop_id UUID REFERENCES route_stops(id) ON DELETE SET NULL,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    term_id UUID NOT NULL REFERENCES terms(id) ON DELETE CASCADE,
+    assigned_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_student_route_per_term UNIQUE (student_id, term_id)
+);
+
+CREATE INDEX idx_route_assignments_route_term ON route_assignments(route_id, term_id);
+CREATE INDEX idx_route_assignments_student_id ON route_assignments(student_id);
+
+-- ============================================
+-- Transport Fee Charges Table
+-- ============================================
+CREATE TABLE transport_fee_charges (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    route_id UUID NOT NULL REFERENCES transport_routes(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    term_id UUID NOT NULL REFERENCES terms(id) ON DELETE CASCADE,
+    amount BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_transport_charge UNIQUE (route_id, student_id, term_id),
+    CONSTRAINT non_negative_transport_charge CHECK (amount >= 0)
+);
+
+CREATE INDEX idx_transport_fee_charges_term ON transport_fee_charges(term_id);
+CREATE INDEX idx_transport_fee_charges_student_id ON transport_fee_charges(student_id);
+
+-- ============================================
+-- Triggers for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_transport_vehicles_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_transport_vehicles_updated_at
+    BEFORE UPDATE ON transport_vehicles
+    FOR EACH ROW
+    EXECUTE FUNCTION update_transport_vehicles_updated_at();
+
+CREATE OR REPLACE FUNCTION update_transport_routes_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_transport_routes_updated_at
+    BEFORE UPDATE ON transport_routes
+    FOR EACH ROW
+    EXECUTE FUNCTION update_transport_routes_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'transport:%';
+
+-- School Admin manages transport for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'transport:%';
+
+-- Teachers may be rostered as drivers or escorts
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name IN ('transport:manifest');
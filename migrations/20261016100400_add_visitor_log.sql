@@ -0,0 +1,93 @@
+-- Visitor Log Migration
+-- Visitor and gate log with host staff, check-in/out times, and kiosk API keys
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('visitors:create', 'Check visitors in', 'visitors'),
+    ('visitors:read', 'View the visitor log and daily reports', 'visitors'),
+    ('visitors:update', 'Check visitors out', 'visitors'),
+    ('visitors:delete', 'Delete visitor log entries', 'visitors'),
+    ('visitors:kiosk', 'Issue and revoke visitor kiosk API keys', 'visitors');
+
+-- ============================================
+-- Visitor Kiosk Keys Table
+-- ============================================
+-- Only a SHA-256 hash of each key is stored; the plaintext is shown once on creation
+CREATE TABLE visitor_kiosk_keys (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    key_prefix VARCHAR(12) NOT NULL,
+    key_hash VARCHAR(64) NOT NULL UNIQUE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_visitor_kiosk_keys_school_id ON visitor_kiosk_keys(school_id);
+
+-- ============================================
+-- Visitor Logs Table
+-- ============================================
+CREATE TABLE visitor_logs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    visitor_name VARCHAR(150) NOT NULL,
+    phone VARCHAR(30),
+    id_document VARCHAR(50),
+    organization VARCHAR(150),
+    purpose TEXT NOT NULL,
+    purpose_details VARCHAR(500),
+    host_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    badge_number VARCHAR(20),
+    check_in_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    check_out_at TIMESTAMPTZ,
+    checked_in_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    kiosk_key_id UUID REFERENCES visitor_kiosk_keys(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_visit_purpose CHECK (purpose IN ('meeting', 'parent_visit', 'delivery', 'maintenance', 'interview', 'official', 'other')),
+    CONSTRAINT valid_visit_times CHECK (check_out_at IS NULL OR check_out_at >= check_in_at)
+);
+
+CREATE INDEX idx_visitor_logs_school_check_in ON visitor_logs(school_id, check_in_at);
+CREATE INDEX idx_visitor_logs_host_id ON visitor_logs(host_id);
+CREATE INDEX idx_visitor_logs_on_site ON visitor_logs(school_id) WHERE check_out_at IS NULL;
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_visitor_logs_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_visitor_logs_updated_at
+    BEFORE UPDATE ON visitor_logs
+    FOR EACH ROW
+    EXECUTE FUNCTION update_visitor_logs_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'visitors:%';
+
+-- School Admin manages the visitor log and kiosks for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'visitors:%';
+
+-- Teachers can staff the front desk
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name IN ('visitors:create', 'visitors:read', 'visitors:update');
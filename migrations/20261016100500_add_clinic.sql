@@ -0,0 +1,125 @@
+-- Clinic Migration
+-- Student medical profiles, health-room visits, medication administered,
+-- and guardian notification records
+
+-- ============================================
+-- New Permissions
+-- ============================================
+-- Health records are sensitive, so reading them needs its own permission
+INSERT INTO permissions (name, description, category) VALUES
+    ('clinic:read', 'View clinic visits and student medical profiles', 'clinic'),
+    ('clinic:record', 'Record clinic visits, medication, and guardian notifications', 'clinic'),
+    ('clinic:update', 'Update clinic visits and student medical profiles', 'clinic'),
+    ('clinic:delete', 'Delete clinic visits', 'clinic');
+
+-- ============================================
+-- Student Medical Profiles Table
+-- ============================================
+CREATE TABLE student_medical_profiles (
+    student_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    blood_group VARCHAR(5),
+    allergies TEXT,
+    chronic_conditions TEXT,
+    current_medications TEXT,
+    dietary_requirements TEXT,
+    emergency_contact_name VARCHAR(150),
+    emergency_contact_relationship VARCHAR(50),
+    emergency_contact_phone VARCHAR(30),
+    emergency_contact_email VARCHAR(255),
+    notes TEXT,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_student_medical_profiles_school_id ON student_medical_profiles(school_id);
+
+-- ============================================
+-- Clinic Visits Table
+-- ============================================
+CREATE TABLE clinic_visits (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    visited_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    symptoms TEXT NOT NULL,
+    temperature_celsius REAL,
+    treatment TEXT,
+    outcome TEXT NOT NULL DEFAULT 'returned_to_class',
+    recorded_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    guardian_notified_at TIMESTAMPTZ,
+    guardian_notified_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    guardian_notification_method TEXT,
+    guardian_notification_notes VARCHAR(500),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_clinic_outcome CHECK (outcome IN ('returned_to_class', 'rested', 'sent_home', 'referred')),
+    CONSTRAINT valid_guardian_notification_method CHECK (
+        guardian_notification_method IS NULL
+        OR guardian_notification_method IN ('email', 'phone', 'sms', 'in_person')
+    )
+);
+
+CREATE INDEX idx_clinic_visits_school_visited ON clinic_visits(school_id, visited_at);
+CREATE INDEX idx_clinic_visits_student_id ON clinic_visits(student_id);
+
+-- ============================================
+-- Clinic Medications Table
+-- ============================================
+CREATE TABLE clinic_medications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    visit_id UUID NOT NULL REFERENCES clinic_visits(id) ON DELETE CASCADE,
+    medication_name VARCHAR(150) NOT NULL,
+    dosage VARCHAR(100) NOT NULL,
+    administered_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    administered_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_clinic_medications_visit_id ON clinic_medications(visit_id);
+
+-- ============================================
+-- Triggers for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_student_medical_profiles_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_student_medical_profiles_updated_at
+    BEFORE UPDATE ON student_medical_profiles
+    FOR EACH ROW
+    EXECUTE FUNCTION update_student_medical_profiles_updated_at();
+
+CREATE OR REPLACE FUNCTION update_clinic_visits_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_clinic_visits_updated_at
+    BEFORE UPDATE ON clinic_visits
+    FOR EACH ROW
+    EXECUTE FUNCTION update_clinic_visits_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'clinic:%';
+
+-- School Admin can grant clinic access to a school nurse role
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'clinic:%';
+
+-- Teachers get no clinic access by default
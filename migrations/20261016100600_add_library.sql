@@ -0,0 +1,169 @@
+-- Library Migration
+-- Book catalog with physical copies, per-school lending policy, loans with due
+-- dates, and overdue fine charges
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('library:create', 'Add books and copies to the library catalog', 'library'),
+    ('library:read', 'View the library catalog, loans, fines, and borrowing history', 'library'),
+    ('library:update', 'Update books, copies, and the lending policy', 'library'),
+    ('library:delete', 'Remove books and copies from the library catalog', 'library'),
+    ('library:lend', 'Check library copies out and in', 'library');
+
+-- ============================================
+-- Books Table
+-- ============================================
+CREATE TABLE library_books (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    author VARCHAR(255),
+    isbn VARCHAR(20),
+    publisher VARCHAR(255),
+    publication_year INTEGER,
+    category VARCHAR(100),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_book_isbn_per_school UNIQUE (school_id, isbn)
+);
+
+CREATE INDEX idx_library_books_school_id ON library_books(school_id);
+
+-- ============================================
+-- Copies Table
+-- ============================================
+CREATE TABLE library_copies (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    book_id UUID NOT NULL REFERENCES library_books(id) ON DELETE CASCADE,
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    barcode VARCHAR(50) NOT NULL,
+    status TEXT NOT NULL DEFAULT 'available',
+    notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_copy_barcode_per_school UNIQUE (school_id, barcode),
+    CONSTRAINT valid_copy_status CHECK (status IN ('available', 'on_loan', 'lost', 'withdrawn'))
+);
+
+CREATE INDEX idx_library_copies_book_id ON library_copies(book_id);
+
+-- ============================================
+-- Lending Policy Table
+-- ============================================
+-- One row per school; schools without a row use the column defaults.
+-- daily_fine and max_fine are in minor currency units.
+CREATE TABLE library_policies (
+    school_id UUID PRIMARY KEY REFERENCES schools(id) ON DELETE CASCADE,
+    loan_days INTEGER NOT NULL DEFAULT 14,
+    student_loan_limit INTEGER NOT NULL DEFAULT 3,
+    staff_loan_limit INTEGER NOT NULL DEFAULT 5,
+    daily_fine BIGINT NOT NULL DEFAULT 0,
+    max_fine BIGINT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT positive_loan_days CHECK (loan_days > 0),
+    CONSTRAINT non_negative_loan_limits CHECK (student_loan_limit >= 0 AND staff_loan_limit >= 0),
+    CONSTRAINT non_negative_fines CHECK (daily_fine >= 0 AND (max_fine IS NULL OR max_fine >= 0))
+);
+
+-- ============================================
+-- Loans Table
+-- ============================================
+CREATE TABLE library_loans (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    copy_id UUID NOT NULL REFERENCES library_copies(id) ON DELETE CASCADE,
+    borrower_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    checked_out_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    due_date DATE NOT NULL,
+    returned_at TIMESTAMPTZ,
+    checked_out_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    returned_to UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- A copy can only be on one open loan at a time
+CREATE UNIQUE INDEX idx_library_loans_open_copy ON library_loans(copy_id) WHERE returned_at IS NULL;
+CREATE INDEX idx_library_loans_school_id ON library_loans(school_id);
+CREATE INDEX idx_library_loans_borrower_id ON library_loans(borrower_id);
+
+-- ============================================
+-- Fine Charges Table
+-- ============================================
+-- Raised when an overdue loan is returned
+CREATE TABLE library_fine_charges (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    loan_id UUID NOT NULL UNIQUE REFERENCES library_loans(id) ON DELETE CASCADE,
+    borrower_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    days_overdue INTEGER NOT NULL,
+    amount BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT positive_days_overdue CHECK (days_overdue > 0),
+    CONSTRAINT non_negative_library_fine CHECK (amount >= 0)
+);
+
+CREATE INDEX idx_library_fine_charges_school_id ON library_fine_charges(school_id);
+CREATE INDEX idx_library_fine_charges_borrower_id ON library_fine_charges(borrower_id);
+
+-- ============================================
+-- Triggers for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_library_books_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_library_books_updated_at
+    BEFORE UPDATE ON library_books
+    FOR EACH ROW
+    EXECUTE FUNCTION update_library_books_updated_at();
+
+CREATE OR REPLACE FUNCTION update_library_copies_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_library_copies_updated_at
+    BEFORE UPDATE ON library_copies
+    FOR EACH ROW
+    EXECUTE FUNCTION update_library_copies_updated_at();
+
+CREATE OR REPLACE FUNCTION update_library_policies_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_library_policies_updated_at
+    BEFORE UPDATE ON library_policies
+    FOR EACH ROW
+    EXECUTE FUNCTION update_library_policies_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'library:%';
+
+-- School Admin manages the library for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'library:%';
+
+-- Teachers often run the library desk
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name IN ('library:read', 'library:lend');
@@ -0,0 +1,76 @@
+-- Alumni Migration
+-- Alumni records created when students graduate, with a graduation cohort,
+-- contact details for alumni relations, and links back to the former
+-- student's account and final level, branch, and academic session
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('alumni:create', 'Graduate students into alumni records', 'alumni'),
+    ('alumni:read', 'View alumni records', 'alumni'),
+    ('alumni:update', 'Update alumni contact details', 'alumni'),
+    ('alumni:delete', 'Delete alumni records', 'alumni'),
+    ('alumni:export', 'Export alumni mailing lists', 'alumni');
+
+-- ============================================
+-- Alumni Table
+-- ============================================
+-- user_id keeps the link to the former student's account and its academic
+-- history; names are copied so the record survives the account being removed
+CREATE TABLE alumni (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    user_id UUID UNIQUE REFERENCES users(id) ON DELETE SET NULL,
+    first_name VARCHAR(100) NOT NULL,
+    last_name VARCHAR(100) NOT NULL,
+    email VARCHAR(255),
+    phone VARCHAR(30),
+    graduation_year INTEGER NOT NULL,
+    cohort VARCHAR(100),
+    graduation_session_id UUID REFERENCES academic_sessions(id) ON DELETE SET NULL,
+    final_level_id UUID REFERENCES levels(id) ON DELETE SET NULL,
+    final_branch_id UUID REFERENCES branches(id) ON DELETE SET NULL,
+    occupation VARCHAR(150),
+    employer VARCHAR(150),
+    city VARCHAR(100),
+    contactable BOOLEAN NOT NULL DEFAULT TRUE,
+    notes TEXT,
+    graduated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_graduation_year CHECK (graduation_year BETWEEN 1900 AND 2200)
+);
+
+CREATE INDEX idx_alumni_school_id ON alumni(school_id);
+CREATE INDEX idx_alumni_school_year ON alumni(school_id, graduation_year);
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_alumni_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_alumni_updated_at
+    BEFORE UPDATE ON alumni
+    FOR EACH ROW
+    EXECUTE FUNCTION update_alumni_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'alumni:%';
+
+-- School Admin manages alumni relations for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'alumni:%';
@@ -0,0 +1,82 @@
+-- Custom Fields Migration
+-- Per-school custom field definitions for students and other users, with
+-- the values stored in a JSONB column on users
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('custom_fields:create', 'Define custom fields', 'custom_fields'),
+    ('custom_fields:read', 'View custom field definitions', 'custom_fields'),
+    ('custom_fields:update', 'Update custom field definitions', 'custom_fields'),
+    ('custom_fields:delete', 'Delete custom field definitions', 'custom_fields');
+
+-- ============================================
+-- Custom Field Definitions Table
+-- ============================================
+-- Keys are unique per school rather than per entity type because student and
+-- user values share the same users.custom_fields column
+CREATE TABLE custom_field_definitions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    entity_type TEXT NOT NULL,
+    field_key VARCHAR(50) NOT NULL,
+    label VARCHAR(100) NOT NULL,
+    field_type TEXT NOT NULL,
+    required BOOLEAN NOT NULL DEFAULT FALSE,
+    options TEXT[] NOT NULL DEFAULT '{}',
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_custom_field_entity CHECK (entity_type IN ('student', 'user')),
+    CONSTRAINT valid_custom_field_type CHECK (
+        field_type IN ('text', 'number', 'boolean', 'date', 'select', 'multi_select')
+    ),
+    CONSTRAINT valid_custom_field_key CHECK (field_key ~ '^[a-z][a-z0-9_]*$'),
+    CONSTRAINT unique_custom_field_key UNIQUE (school_id, field_key)
+);
+
+CREATE INDEX idx_custom_field_definitions_school_entity
+    ON custom_field_definitions(school_id, entity_type, position);
+
+-- ============================================
+-- Custom Field Values
+-- ============================================
+ALTER TABLE users ADD COLUMN custom_fields JSONB NOT NULL DEFAULT '{}';
+
+CREATE INDEX idx_users_custom_fields ON users USING GIN (custom_fields jsonb_path_ops);
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_custom_field_definitions_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_custom_field_definitions_updated_at
+    BEFORE UPDATE ON custom_field_definitions
+    FOR EACH ROW
+    EXECUTE FUNCTION update_custom_field_definitions_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'custom_fields:%';
+
+-- School Admin defines the custom fields for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'custom_fields:%';
+
+-- Teachers see custom fields on the students they work with
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name = 'custom_fields:read';
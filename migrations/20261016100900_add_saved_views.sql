@@ -0,0 +1,46 @@
+-- Saved Views Migration
+-- Named filter, sort, and column selections for list endpoints, owned by a
+-- user and optionally shared with the rest of their school
+
+-- ============================================
+-- Saved Views Table
+-- ============================================
+-- school_id is the owner's school when the view was saved; shared views are
+-- visible to every user of that school
+CREATE TABLE saved_views (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    school_id UUID REFERENCES schools(id) ON DELETE CASCADE,
+    resource TEXT NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    filters JSONB NOT NULL DEFAULT '{}',
+    sort_by VARCHAR(50),
+    sort_order TEXT,
+    columns TEXT[] NOT NULL DEFAULT '{}',
+    shared BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_saved_view_resource CHECK (resource IN ('users', 'students', 'transport_fees')),
+    CONSTRAINT valid_saved_view_sort_order CHECK (sort_order IN ('asc', 'desc')),
+    CONSTRAINT shared_saved_view_has_school CHECK (NOT shared OR school_id IS NOT NULL),
+    CONSTRAINT unique_saved_view_name UNIQUE (owner_id, resource, name)
+);
+
+CREATE INDEX idx_saved_views_owner ON saved_views(owner_id);
+CREATE INDEX idx_saved_views_school_shared ON saved_views(school_id) WHERE shared;
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_saved_views_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_saved_views_updated_at
+    BEFORE UPDATE ON saved_views
+    FOR EACH ROW
+    EXECUTE FUNCTION update_saved_views_updated_at();
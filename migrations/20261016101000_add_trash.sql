@@ -0,0 +1,46 @@
+-- Recycle Bin Migration
+-- Deleted students, users, and branches are kept as row snapshots per school
+-- so admins can restore them until they are purged
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('trash:read', 'View the recycle bin', 'trash'),
+    ('trash:restore', 'Restore deleted records', 'trash'),
+    ('trash:purge', 'Permanently purge deleted records', 'trash');
+
+-- ============================================
+-- Trash Items Table
+-- ============================================
+-- data holds the deleted row as JSON plus whatever is needed to re-link it on
+-- restore (role IDs for users, member student IDs for branches)
+CREATE TABLE trash_items (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    item_type TEXT NOT NULL,
+    entity_id UUID NOT NULL,
+    label VARCHAR(255) NOT NULL,
+    data JSONB NOT NULL,
+    deleted_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    deleted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_trash_item_type CHECK (item_type IN ('students', 'users', 'branches')),
+    CONSTRAINT unique_trash_entity UNIQUE (item_type, entity_id)
+);
+
+CREATE INDEX idx_trash_items_school_deleted ON trash_items(school_id, deleted_at DESC);
+CREATE INDEX idx_trash_items_deleted_at ON trash_items(deleted_at);
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'trash:%';
+
+-- School Admin manages their school's recycle bin
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'trash:%';
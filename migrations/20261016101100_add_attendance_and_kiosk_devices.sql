@@ -0,0 +1,82 @@
+-- Attendance and Kiosk Devices Migration
+-- Daily student attendance, plus shared kiosk devices that staff unlock with
+-- a PIN to mark attendance for the device's branch
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('attendance:mark', 'Mark student attendance', 'attendance'),
+    ('attendance:read', 'View student attendance', 'attendance'),
+    ('kiosk_devices:manage', 'Register and revoke attendance kiosk devices', 'attendance');
+
+-- ============================================
+-- Kiosk Devices Table
+-- ============================================
+CREATE TABLE kiosk_devices (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    branch_id UUID NOT NULL REFERENCES branches(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    token_prefix VARCHAR(12) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    registered_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    last_seen_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_kiosk_devices_school_id ON kiosk_devices(school_id);
+
+-- ============================================
+-- Staff Kiosk PINs Table
+-- ============================================
+-- PINs are short, so repeated failures lock the PIN for a while
+CREATE TABLE staff_kiosk_pins (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    pin_hash VARCHAR(255) NOT NULL,
+    failed_attempts INTEGER NOT NULL DEFAULT 0,
+    locked_until TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- ============================================
+-- Attendance Records Table
+-- ============================================
+CREATE TABLE attendance_records (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    branch_id UUID NOT NULL REFERENCES branches(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    date DATE NOT NULL,
+    status TEXT NOT NULL,
+    note VARCHAR(255),
+    marked_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    kiosk_device_id UUID REFERENCES kiosk_devices(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_attendance_status CHECK (status IN ('present', 'absent', 'late', 'excused')),
+    CONSTRAINT unique_attendance_student_date UNIQUE (student_id, date)
+);
+
+CREATE INDEX idx_attendance_records_branch_date ON attendance_records(branch_id, date);
+CREATE INDEX idx_attendance_records_school_date ON attendance_records(school_id, date);
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'attendance:%' OR name = 'kiosk_devices:manage';
+
+-- School Admin marks attendance and manages kiosk devices
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'attendance:%' OR name = 'kiosk_devices:manage';
+
+-- Teacher marks and reads attendance
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name LIKE 'attendance:%';
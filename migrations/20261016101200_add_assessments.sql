@@ -0,0 +1,100 @@
+-- Assessments and Grades Migration
+-- Subjects, assessments (exams, quizzes, assignments) per term, level, and
+-- subject, and the scores students earn on them
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('subjects:create', 'Create subjects', 'assessments'),
+    ('subjects:read', 'View subjects', 'assessments'),
+    ('subjects:delete', 'Delete subjects', 'assessments'),
+    ('assessments:create', 'Create assessments', 'assessments'),
+    ('assessments:read', 'View assessments', 'assessments'),
+    ('assessments:update', 'Update assessments', 'assessments'),
+    ('assessments:delete', 'Delete assessments', 'assessments'),
+    ('grades:record', 'Record student scores', 'assessments'),
+    ('grades:read', 'View student scores and term grades', 'assessments');
+
+-- ============================================
+-- Subjects Table
+-- ============================================
+CREATE TABLE subjects (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    code VARCHAR(20),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_subject_name_per_school UNIQUE (school_id, name)
+);
+
+-- ============================================
+-- Assessments Table
+-- ============================================
+-- weight is the assessment's share of the term grade, in percent
+CREATE TABLE assessments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    term_id UUID NOT NULL REFERENCES terms(id) ON DELETE CASCADE,
+    level_id UUID NOT NULL REFERENCES levels(id) ON DELETE CASCADE,
+    subject_id UUID NOT NULL REFERENCES subjects(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    kind TEXT NOT NULL,
+    max_score DOUBLE PRECISION NOT NULL,
+    weight INTEGER NOT NULL,
+    due_date DATE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_assessment_kind CHECK (kind IN ('exam', 'quiz', 'assignment')),
+    CONSTRAINT positive_assessment_max_score CHECK (max_score > 0),
+    CONSTRAINT valid_assessment_weight CHECK (weight BETWEEN 0 AND 100)
+);
+
+CREATE INDEX idx_assessments_term_level_subject ON assessments(term_id, level_id, subject_id);
+CREATE INDEX idx_assessments_school_id ON assessments(school_id);
+
+-- ============================================
+-- Assessment Scores Table
+-- ============================================
+CREATE TABLE assessment_scores (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    assessment_id UUID NOT NULL REFERENCES assessments(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    score DOUBLE PRECISION NOT NULL,
+    remarks VARCHAR(255),
+    recorded_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT non_negative_assessment_score CHECK (score >= 0),
+    CONSTRAINT unique_assessment_student UNIQUE (assessment_id, student_id)
+);
+
+CREATE INDEX idx_assessment_scores_student_id ON assessment_scores(student_id);
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'subjects:%' OR name LIKE 'assessments:%' OR name LIKE 'grades:%';
+
+-- School Admin manages subjects, assessments, and grades
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'subjects:%' OR name LIKE 'assessments:%' OR name LIKE 'grades:%';
+
+-- Teacher sets assessments and enters marks
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name IN (
+    'subjects:read',
+    'assessments:create',
+    'assessments:read',
+    'assessments:update',
+    'grades:record',
+    'grades:read'
+);
@@ -0,0 +1,13 @@
+-- School Public Profiles Migration
+-- Opt-in public directory listing for the admissions page, addressed by slug
+
+ALTER TABLE schools ADD COLUMN slug VARCHAR(100) UNIQUE;
+ALTER TABLE schools ADD COLUMN public_profile_enabled BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE schools ADD COLUMN admissions_open BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE schools ADD CONSTRAINT valid_school_slug
+    CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$');
+
+-- A school can only be listed once it has a slug to be found by
+ALTER TABLE schools ADD CONSTRAINT public_profile_requires_slug
+    CHECK (NOT public_profile_enabled OR slug IS NOT NULL);
@@ -0,0 +1,91 @@
+-- Broadcasts Migration
+-- Templated email/SMS messages sent by school admins to a filtered audience,
+-- delivered by a background job with per-recipient delivery status
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('broadcasts:send', 'Send email and SMS broadcasts', 'broadcasts'),
+    ('broadcasts:read', 'View broadcasts and their delivery status', 'broadcasts');
+
+-- ============================================
+-- Broadcasts Table
+-- ============================================
+-- The audience filter columns are combined with AND; all NULL means the
+-- whole school
+CREATE TABLE broadcasts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    channel TEXT NOT NULL,
+    subject VARCHAR(200),
+    body TEXT NOT NULL,
+    role_id UUID REFERENCES roles(id) ON DELETE SET NULL,
+    level_id UUID REFERENCES levels(id) ON DELETE SET NULL,
+    branch_id UUID REFERENCES branches(id) ON DELETE SET NULL,
+    status TEXT NOT NULL DEFAULT 'queued',
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_broadcast_channel CHECK (channel IN ('email', 'sms')),
+    CONSTRAINT valid_broadcast_status CHECK (status IN ('queued', 'sending', 'completed')),
+    CONSTRAINT email_broadcast_requires_subject CHECK (channel <> 'email' OR subject IS NOT NULL)
+);
+
+CREATE INDEX idx_broadcasts_school_id ON broadcasts(school_id);
+CREATE INDEX idx_broadcasts_status ON broadcasts(status);
+
+-- ============================================
+-- Broadcast Recipients Table
+-- ============================================
+-- address is the email address or phone number resolved when the broadcast
+-- was queued; NULL when the recipient has none on file
+CREATE TABLE broadcast_recipients (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    broadcast_id UUID NOT NULL REFERENCES broadcasts(id) ON DELETE CASCADE,
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    first_name VARCHAR(100) NOT NULL,
+    last_name VARCHAR(100) NOT NULL,
+    address VARCHAR(255),
+    status TEXT NOT NULL DEFAULT 'pending',
+    error TEXT,
+    attempted_at TIMESTAMPTZ,
+    CONSTRAINT valid_broadcast_recipient_status CHECK (
+        status IN ('pending', 'sent', 'failed', 'skipped')
+    ),
+    CONSTRAINT unique_broadcast_recipient UNIQUE (broadcast_id, user_id)
+);
+
+CREATE INDEX idx_broadcast_recipients_broadcast_status ON broadcast_recipients(broadcast_id, status);
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_broadcasts_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_broadcasts_updated_at
+    BEFORE UPDATE ON broadcasts
+    FOR EACH ROW
+    EXECUTE FUNCTION update_broadcasts_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'broadcasts:%';
+
+-- School Admin sends broadcasts for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name IN ('broadcasts:send', 'broadcasts:read');
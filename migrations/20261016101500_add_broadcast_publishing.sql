@@ -0,0 +1,49 @@
+-- Broadcast Publishing Workflow Migration
+-- Drafts, scheduled publishing in the school's timezone, and optional
+-- four-eyes approval before a broadcast is sent
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('broadcasts:approve', 'Approve broadcasts submitted by others', 'broadcasts');
+
+-- ============================================
+-- School Settings
+-- ============================================
+-- IANA timezone name; scheduled publish times are wall-clock times in it
+ALTER TABLE schools ADD COLUMN timezone VARCHAR(64) NOT NULL DEFAULT 'UTC';
+ALTER TABLE schools ADD COLUMN broadcast_approval_required BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- ============================================
+-- Broadcast Workflow Columns
+-- ============================================
+-- publish_at is a local time in the school's timezone, compared against the
+-- school's current local time so a timezone change moves pending schedules
+ALTER TABLE broadcasts ADD COLUMN publish_at TIMESTAMP;
+ALTER TABLE broadcasts ADD COLUMN submitted_by UUID REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE broadcasts ADD COLUMN submitted_at TIMESTAMPTZ;
+ALTER TABLE broadcasts ADD COLUMN reviewed_by UUID REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE broadcasts ADD COLUMN reviewed_at TIMESTAMPTZ;
+ALTER TABLE broadcasts ADD COLUMN review_note TEXT;
+
+ALTER TABLE broadcasts DROP CONSTRAINT valid_broadcast_status;
+ALTER TABLE broadcasts ADD CONSTRAINT valid_broadcast_status CHECK (
+    status IN ('draft', 'pending_approval', 'scheduled', 'queued', 'sending', 'completed')
+);
+
+CREATE INDEX idx_broadcasts_scheduled ON broadcasts(publish_at) WHERE status = 'scheduled';
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name = 'broadcasts:approve';
+
+-- School Admin approves broadcasts for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name = 'broadcasts:approve';
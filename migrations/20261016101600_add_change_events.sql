@@ -0,0 +1,123 @@
+-- Change Events Migration
+-- Ordered feed of inserts, updates, and deletes on core records so external
+-- systems can sync incrementally
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('changes:read', 'Read the data change feed', 'changes');
+
+-- ============================================
+-- Change Events Table
+-- ============================================
+-- tx_id is the writing transaction's ID. Readers only see events from
+-- transactions older than every transaction still in flight, and page by
+-- (tx_id, id), so an event can never appear behind a cursor already handed out.
+-- school_id has no foreign key so deletions cascading from a school are kept
+CREATE TABLE change_events (
+    id BIGSERIAL PRIMARY KEY,
+    tx_id BIGINT NOT NULL DEFAULT (pg_current_xact_id()::text::bigint),
+    school_id UUID,
+    entity_type TEXT NOT NULL,
+    entity_id UUID NOT NULL,
+    operation TEXT NOT NULL,
+    version BIGINT NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_change_entity_type CHECK (
+        entity_type IN ('school', 'user', 'level', 'branch', 'academic_session', 'term')
+    ),
+    CONSTRAINT valid_change_operation CHECK (operation IN ('created', 'updated', 'deleted'))
+);
+
+CREATE INDEX idx_change_events_position ON change_events(tx_id, id);
+CREATE INDEX idx_change_events_school_position ON change_events(school_id, tx_id, id);
+CREATE INDEX idx_change_events_entity ON change_events(entity_type, entity_id, version);
+
+-- ============================================
+-- Change Capture Trigger
+-- ============================================
+-- TG_ARGV[0] is the entity type recorded for the table
+CREATE OR REPLACE FUNCTION record_change_event()
+RETURNS TRIGGER AS $$
+DECLARE
+    row_data JSONB;
+    changed_id UUID;
+    changed_school_id UUID;
+BEGIN
+    IF TG_OP = 'UPDATE' AND to_jsonb(OLD) = to_jsonb(NEW) THEN
+        RETURN NULL;
+    END IF;
+
+    IF TG_OP = 'DELETE' THEN
+        row_data := to_jsonb(OLD);
+    ELSE
+        row_data := to_jsonb(NEW);
+    END IF;
+
+    changed_id := (row_data->>'id')::uuid;
+    changed_school_id := CASE TG_TABLE_NAME
+        WHEN 'schools' THEN changed_id
+        WHEN 'branches' THEN (
+            SELECT school_id FROM levels WHERE id = (row_data->>'level_id')::uuid
+        )
+        WHEN 'terms' THEN (
+            SELECT school_id FROM academic_sessions
+            WHERE id = (row_data->>'academic_session_id')::uuid
+        )
+        ELSE (row_data->>'school_id')::uuid
+    END;
+
+    INSERT INTO change_events (school_id, entity_type, entity_id, operation, version)
+    VALUES (
+        changed_school_id,
+        TG_ARGV[0],
+        changed_id,
+        CASE TG_OP WHEN 'INSERT' THEN 'created' WHEN 'UPDATE' THEN 'updated' ELSE 'deleted' END,
+        COALESCE((
+            SELECT MAX(version) FROM change_events
+            WHERE entity_type = TG_ARGV[0] AND entity_id = changed_id
+        ), 0) + 1
+    );
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_schools_change_events
+    AFTER INSERT OR UPDATE OR DELETE ON schools
+    FOR EACH ROW EXECUTE FUNCTION record_change_event('school');
+
+CREATE TRIGGER trigger_users_change_events
+    AFTER INSERT OR UPDATE OR DELETE ON users
+    FOR EACH ROW EXECUTE FUNCTION record_change_event('user');
+
+CREATE TRIGGER trigger_levels_change_events
+    AFTER INSERT OR UPDATE OR DELETE ON levels
+    FOR EACH ROW EXECUTE FUNCTION record_change_event('level');
+
+CREATE TRIGGER trigger_branches_change_events
+    AFTER INSERT OR UPDATE OR DELETE ON branches
+    FOR EACH ROW EXECUTE FUNCTION record_change_event('branch');
+
+CREATE TRIGGER trigger_academic_sessions_change_events
+    AFTER INSERT OR UPDATE OR DELETE ON academic_sessions
+    FOR EACH ROW EXECUTE FUNCTION record_change_event('academic_session');
+
+CREATE TRIGGER trigger_terms_change_events
+    AFTER INSERT OR UPDATE OR DELETE ON terms
+    FOR EACH ROW EXECUTE FUNCTION record_change_event('term');
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name = 'changes:read';
+
+-- School Admin reads the feed for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name = 'changes:read';
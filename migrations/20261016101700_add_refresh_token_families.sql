@@ -0,0 +1,24 @@
+-- Refresh Token Families Migration
+-- Replaces the per-token table with one row per device session. Only the
+-- latest token in a family is valid; replaying an older one revokes the family.
+-- Existing refresh tokens carry no family and stop working, so users sign in again.
+
+DROP TABLE refresh_tokens;
+
+-- ============================================
+-- Refresh Token Families Table
+-- ============================================
+CREATE TABLE refresh_token_families (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    device_id VARCHAR(128) NOT NULL,
+    current_jti VARCHAR(64) NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_refresh_token_families_user_device
+    ON refresh_token_families(user_id, device_id) WHERE revoked_at IS NULL;
+CREATE INDEX idx_refresh_token_families_expires_at ON refresh_token_families(expires_at);
@@ -0,0 +1,18 @@
+-- MFA Login Challenges Migration
+-- Holds in-progress MFA logins server-side when Redis is unavailable, so
+-- attempts can be limited and pending logins cancelled.
+
+-- ============================================
+-- MFA Login Challenges Table
+-- ============================================
+CREATE TABLE mfa_login_challenges (
+    handle VARCHAR(64) PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    allow_recovery_code BOOLEAN NOT NULL,
+    attempts_left INTEGER NOT NULL CHECK (attempts_left >= 0),
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_mfa_login_challenges_user_id ON mfa_login_challenges(user_id);
+CREATE INDEX idx_mfa_login_challenges_expires_at ON mfa_login_challenges(expires_at);
@@ -0,0 +1,85 @@
+-- Webhooks Migration
+-- School-registered HTTP endpoints that receive signed domain events, fed by
+-- an outbox written in the same transaction as each change and drained by a
+-- background delivery job with retry and backoff
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('webhooks:manage', 'Register, update, and delete webhook endpoints', 'webhooks'),
+    ('webhooks:read', 'View webhook endpoints and their deliveries', 'webhooks');
+
+-- ============================================
+-- Webhook Endpoints Table
+-- ============================================
+-- events lists the subscribed event names, e.g. 'user.created'
+CREATE TABLE webhook_endpoints (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    url VARCHAR(2048) NOT NULL,
+    description VARCHAR(255),
+    secret VARCHAR(128) NOT NULL,
+    events TEXT[] NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT webhook_endpoint_has_events CHECK (cardinality(events) > 0)
+);
+
+CREATE INDEX idx_webhook_endpoints_school_id ON webhook_endpoints(school_id);
+
+-- ============================================
+-- Webhook Outbox Table
+-- ============================================
+-- One row per event per subscribed endpoint. payload is the exact JSON body
+-- sent. attempts counts attempts started; next_attempt_at is pushed forward
+-- while an attempt is in flight so an interrupted one is retried.
+CREATE TABLE webhook_outbox (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    endpoint_id UUID NOT NULL REFERENCES webhook_endpoints(id) ON DELETE CASCADE,
+    event_type TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_response_status INTEGER,
+    last_error TEXT,
+    delivered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_webhook_delivery_status CHECK (status IN ('pending', 'delivered', 'failed'))
+);
+
+CREATE INDEX idx_webhook_outbox_due ON webhook_outbox(next_attempt_at) WHERE status = 'pending';
+CREATE INDEX idx_webhook_outbox_endpoint ON webhook_outbox(endpoint_id, created_at DESC);
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_webhook_endpoints_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_webhook_endpoints_updated_at
+    BEFORE UPDATE ON webhook_endpoints
+    FOR EACH ROW
+    EXECUTE FUNCTION update_webhook_endpoints_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'webhooks:%';
+
+-- School Admin manages webhooks for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name IN ('webhooks:manage', 'webhooks:read');
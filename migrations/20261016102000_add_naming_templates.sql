@@ -0,0 +1,10 @@
+-- Naming Templates Migration
+-- Per-school templates for level and branch names, used when levels and
+-- branches are generated in bulk. {n} is the level number and {letter} the
+-- branch letter, e.g. 'JSS {n}' and 'JSS {n}{letter}'
+
+-- ============================================
+-- School Settings
+-- ============================================
+ALTER TABLE schools ADD COLUMN level_name_template VARCHAR(64) NOT NULL DEFAULT 'Grade {n}';
+ALTER TABLE schools ADD COLUMN branch_name_template VARCHAR(64) NOT NULL DEFAULT '{letter}';
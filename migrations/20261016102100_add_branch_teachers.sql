@@ -0,0 +1,57 @@
+-- Branch Teachers Migration
+-- Multiple teachers per branch in a lead, assistant, or substitute capacity
+-- for a date range, and finalization of assessment scores
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('branches:assign_teachers', 'Assign teachers to branches', 'branches'),
+    ('grades:finalize', 'Finalize assessment scores', 'assessments');
+
+-- ============================================
+-- Branch Teachers Table
+-- ============================================
+-- ends_on is inclusive; an open-ended assignment has no ends_on
+CREATE TABLE branch_teachers (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    branch_id UUID NOT NULL REFERENCES branches(id) ON DELETE CASCADE,
+    teacher_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    capacity TEXT NOT NULL,
+    starts_on DATE NOT NULL,
+    ends_on DATE,
+    assigned_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_branch_teacher_capacity CHECK (capacity IN ('lead', 'assistant', 'substitute')),
+    CONSTRAINT valid_branch_teacher_dates CHECK (ends_on IS NULL OR ends_on >= starts_on)
+);
+
+CREATE INDEX idx_branch_teachers_branch_id ON branch_teachers(branch_id);
+CREATE INDEX idx_branch_teachers_teacher_id ON branch_teachers(teacher_id);
+
+-- ============================================
+-- Assessment Finalization
+-- ============================================
+ALTER TABLE assessments
+    ADD COLUMN finalized_at TIMESTAMPTZ,
+    ADD COLUMN finalized_by UUID REFERENCES users(id) ON DELETE SET NULL;
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name IN ('branches:assign_teachers', 'grades:finalize');
+
+-- School Admin assigns teachers and finalizes scores
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name IN ('branches:assign_teachers', 'grades:finalize');
+
+-- Teacher finalizes scores, subject to their branch capacity
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name = 'grades:finalize';
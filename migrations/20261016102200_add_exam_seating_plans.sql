@@ -0,0 +1,50 @@
+-- Exam Seating Plans Migration
+-- Allocation of the students sitting an exam to numbered seats in the rooms
+-- the exam is held in, one plan per exam
+
+-- ============================================
+-- Exam Seating Plans Table
+-- ============================================
+CREATE TABLE exam_seating_plans (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    assessment_id UUID NOT NULL UNIQUE REFERENCES assessments(id) ON DELETE CASCADE,
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    alternate_branches BOOLEAN NOT NULL,
+    generated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- ============================================
+-- Exam Rooms Table
+-- ============================================
+-- Seats are numbered 1..capacity; position is the order rooms are filled in
+CREATE TABLE exam_rooms (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    plan_id UUID NOT NULL REFERENCES exam_seating_plans(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    capacity INTEGER NOT NULL,
+    special_needs_seats INTEGER[] NOT NULL DEFAULT '{}',
+    position INTEGER NOT NULL,
+    CONSTRAINT positive_exam_room_capacity CHECK (capacity > 0),
+    CONSTRAINT unique_exam_room_name_per_plan UNIQUE (plan_id, name)
+);
+
+CREATE INDEX idx_exam_rooms_plan_id ON exam_rooms(plan_id);
+
+-- ============================================
+-- Exam Seats Table
+-- ============================================
+CREATE TABLE exam_seats (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    plan_id UUID NOT NULL REFERENCES exam_seating_plans(id) ON DELETE CASCADE,
+    room_id UUID NOT NULL REFERENCES exam_rooms(id) ON DELETE CASCADE,
+    seat_number INTEGER NOT NULL,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    special_needs BOOLEAN NOT NULL DEFAULT FALSE,
+    CONSTRAINT positive_exam_seat_number CHECK (seat_number > 0),
+    CONSTRAINT unique_exam_seat_per_room UNIQUE (room_id, seat_number),
+    CONSTRAINT unique_exam_seat_student_per_plan UNIQUE (plan_id, student_id)
+);
+
+CREATE INDEX idx_exam_seats_student_id ON exam_seats(student_id);
@@ -0,0 +1,44 @@
+-- Student Promotions Migration
+-- End-of-session rollover of students to their next level, with a history of
+-- who was promoted, graduated, or held back to repeat
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('levels:promote', 'Promote students to the next level at the end of a session', 'levels');
+
+-- ============================================
+-- Student Promotions Table
+-- ============================================
+-- One row per student per session; to_level_id is NULL for graduates and
+-- equals from_level_id for students repeating the level
+CREATE TABLE student_promotions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    academic_session_id UUID NOT NULL REFERENCES academic_sessions(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    from_level_id UUID REFERENCES levels(id) ON DELETE SET NULL,
+    to_level_id UUID REFERENCES levels(id) ON DELETE SET NULL,
+    outcome TEXT NOT NULL,
+    promoted_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_promotion_outcome CHECK (outcome IN ('promoted', 'graduated', 'repeated')),
+    CONSTRAINT unique_student_promotion_per_session UNIQUE (student_id, academic_session_id)
+);
+
+CREATE INDEX idx_student_promotions_school_session ON student_promotions(school_id, academic_session_id);
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name = 'levels:promote';
+
+-- School Admin runs the end-of-session rollover
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name = 'levels:promote';
@@ -0,0 +1,80 @@
+-- Term Results Migration
+-- Moderation workflow for the results of a subject in a level and term:
+-- open -> submitted -> moderated -> published -> locked, with every
+-- transition recorded
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('results:read', 'View term result status and history', 'assessments'),
+    ('results:submit', 'Submit term results for moderation', 'assessments'),
+    ('results:moderate', 'Moderate term results or return them for correction', 'assessments'),
+    ('results:publish', 'Publish moderated term results', 'assessments'),
+    ('results:lock', 'Lock published term results', 'assessments'),
+    ('results:unlock', 'Unlock locked term results for correction', 'assessments');
+
+-- ============================================
+-- Term Results Table
+-- ============================================
+-- Results without a row are open
+CREATE TABLE term_results (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    term_id UUID NOT NULL REFERENCES terms(id) ON DELETE CASCADE,
+    level_id UUID NOT NULL REFERENCES levels(id) ON DELETE CASCADE,
+    subject_id UUID NOT NULL REFERENCES subjects(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'open',
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_term_result_status CHECK (status IN ('open', 'submitted', 'moderated', 'published', 'locked')),
+    CONSTRAINT unique_term_result UNIQUE (term_id, level_id, subject_id)
+);
+
+CREATE INDEX idx_term_results_school_id ON term_results(school_id);
+
+-- ============================================
+-- Term Result Transitions Table
+-- ============================================
+-- Audit trail of every status change; unlocking requires a reason
+CREATE TABLE term_result_transitions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    term_result_id UUID NOT NULL REFERENCES term_results(id) ON DELETE CASCADE,
+    action TEXT NOT NULL,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    reason TEXT,
+    performed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_term_result_action CHECK (action IN ('submit', 'return', 'moderate', 'publish', 'lock', 'unlock')),
+    CONSTRAINT unlock_requires_reason CHECK (action <> 'unlock' OR reason IS NOT NULL)
+);
+
+CREATE INDEX idx_term_result_transitions_result ON term_result_transitions(term_result_id, created_at);
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'results:%';
+
+-- School Admin moderates, publishes, and locks; unlocking is left to senior
+-- admins unless granted through a custom role
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name IN (
+    'results:read',
+    'results:submit',
+    'results:moderate',
+    'results:publish',
+    'results:lock'
+);
+
+-- Teacher submits their results for moderation
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name IN ('results:read', 'results:submit');
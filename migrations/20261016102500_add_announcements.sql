@@ -0,0 +1,69 @@
+-- Announcements Migration
+-- In-app announcements to a school audience, fanned out into a per-user
+-- notification feed with read tracking
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('announcements:create', 'Publish announcements to a school audience', 'announcements'),
+    ('announcements:read', 'View published announcements and their read counts', 'announcements'),
+    ('announcements:delete', 'Delete announcements and their notifications', 'announcements');
+
+-- ============================================
+-- Announcements Table
+-- ============================================
+-- Audience filters are combined; leaving them all NULL targets the whole
+-- school
+CREATE TABLE announcements (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    title VARCHAR(200) NOT NULL,
+    body TEXT NOT NULL,
+    role_id UUID REFERENCES roles(id) ON DELETE SET NULL,
+    level_id UUID REFERENCES levels(id) ON DELETE SET NULL,
+    branch_id UUID REFERENCES branches(id) ON DELETE SET NULL,
+    send_email BOOLEAN NOT NULL DEFAULT FALSE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_announcements_school_created ON announcements(school_id, created_at DESC);
+
+-- ============================================
+-- Notifications Table
+-- ============================================
+-- One row per recipient; title and body are copied so the feed does not
+-- depend on the source
+CREATE TABLE notifications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    announcement_id UUID REFERENCES announcements(id) ON DELETE CASCADE,
+    title VARCHAR(200) NOT NULL,
+    body TEXT NOT NULL,
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_announcement_notification UNIQUE (announcement_id, user_id)
+);
+
+CREATE INDEX idx_notifications_user_created ON notifications(user_id, created_at DESC);
+CREATE INDEX idx_notifications_user_unread ON notifications(user_id) WHERE read_at IS NULL;
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'announcements:%';
+
+-- School Admin gets ALL announcement permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'announcements:%';
+
+-- Teacher announces to the levels and branches they teach
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name IN ('announcements:create', 'announcements:read');
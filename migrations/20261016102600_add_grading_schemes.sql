@@ -0,0 +1,56 @@
+-- Grading Schemes Migration
+-- How continuous assessment components and exams combine into a term grade,
+-- configured per school with optional per-subject overrides
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('grading_schemes:manage', 'Configure how assessment components combine into term grades', 'assessments');
+
+-- ============================================
+-- Grading Schemes Table
+-- ============================================
+-- subject_id NULL is the school default; a subject's own scheme overrides it
+CREATE TABLE grading_schemes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    subject_id UUID REFERENCES subjects(id) ON DELETE CASCADE,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_grading_schemes_school_default ON grading_schemes(school_id) WHERE subject_id IS NULL;
+CREATE UNIQUE INDEX idx_grading_schemes_subject ON grading_schemes(subject_id) WHERE subject_id IS NOT NULL;
+
+-- ============================================
+-- Grading Scheme Components Table
+-- ============================================
+-- One weighted component per assessment kind; best_of counts only a
+-- student's highest-scoring assessments of that kind
+CREATE TABLE grading_scheme_components (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    grading_scheme_id UUID NOT NULL REFERENCES grading_schemes(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    weight INTEGER NOT NULL,
+    best_of INTEGER,
+    CONSTRAINT valid_grading_component_kind CHECK (kind IN ('exam', 'quiz', 'assignment')),
+    CONSTRAINT valid_grading_component_weight CHECK (weight BETWEEN 0 AND 100),
+    CONSTRAINT positive_grading_component_best_of CHECK (best_of IS NULL OR best_of > 0),
+    CONSTRAINT unique_grading_component_kind UNIQUE (grading_scheme_id, kind)
+);
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name = 'grading_schemes:manage';
+
+-- School Admin configures their school's grading
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name = 'grading_schemes:manage';
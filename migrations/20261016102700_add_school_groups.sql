@@ -0,0 +1,72 @@
+-- School Groups Migration
+-- An organization layer above schools (a district or group) with a Group
+-- Admin role scoped to the group's member schools
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('groups:create', 'Create school groups', 'groups'),
+    ('groups:read', 'View school groups and their member schools', 'groups'),
+    ('groups:update', 'Update school groups, their member schools, and group admins', 'groups'),
+    ('groups:delete', 'Delete school groups', 'groups'),
+    ('groups:reports', 'View aggregate reports across a group''s member schools', 'groups');
+
+-- ============================================
+-- School Groups Table
+-- ============================================
+CREATE TABLE school_groups (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(200) NOT NULL UNIQUE,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- A school belongs to at most one group
+ALTER TABLE schools ADD COLUMN group_id UUID REFERENCES school_groups(id) ON DELETE SET NULL;
+CREATE INDEX idx_schools_group_id ON schools(group_id);
+
+-- The group a group admin administers; group admins have no school
+ALTER TABLE users ADD COLUMN group_id UUID REFERENCES school_groups(id) ON DELETE SET NULL;
+CREATE INDEX idx_users_group_id ON users(group_id) WHERE group_id IS NOT NULL;
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_school_groups_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_school_groups_updated_at
+    BEFORE UPDATE ON school_groups
+    FOR EACH ROW
+    EXECUTE FUNCTION update_school_groups_updated_at();
+
+-- ============================================
+-- Group Admin System Role
+-- ============================================
+INSERT INTO roles (id, name, description, school_id, is_system_role, slug) VALUES
+    ('00000000-0000-0000-0000-000000000005', 'Group Admin', 'Administers the member schools of a school group', NULL, TRUE, 'group_admin');
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'groups:%';
+
+-- Group Admin views their group and manages users across its schools
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000005', id FROM permissions
+WHERE name IN (
+    'groups:read', 'groups:reports',
+    'users:create', 'users:read', 'users:update', 'users:delete',
+    'schools:read'
+);
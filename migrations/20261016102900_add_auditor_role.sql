@@ -0,0 +1,41 @@
+-- Auditor Role Migration
+-- A read-only system role for external inspectors. Responses to callers
+-- without sensitive_data:read have dates of birth, contact details, and
+-- medical data masked.
+
+-- ============================================
+-- New Permissions
+-- ============================================
+
+INSERT INTO permissions (name, description, category) VALUES
+    ('sensitive_data:read', 'View unmasked dates of birth, contact details, and medical data', 'sensitive_data');
+
+-- ============================================
+-- Auditor System Role
+-- ============================================
+
+INSERT INTO roles (id, name, description, school_id, is_system_role, slug) VALUES
+    ('00000000-0000-0000-0000-000000000006', 'Auditor', 'Read-only access across a school with sensitive fields masked', NULL, TRUE, 'auditor');
+
+-- ============================================
+-- Assign Permissions
+-- ============================================
+
+-- Every existing role keeps seeing unmasked data
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r, permissions p
+WHERE p.name = 'sensitive_data:read'
+  AND r.id <> '00000000-0000-0000-0000-000000000006';
+
+-- Auditor reads school records but never exports or sees secrets
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000006', id FROM permissions
+WHERE name IN (
+    'users:read', 'schools:read', 'students:read',
+    'levels:read', 'branches:read', 'roles:read', 'settings:read',
+    'academic_sessions:read', 'terms:read', 'subjects:read',
+    'attendance:read', 'assessments:read', 'grades:read', 'results:read',
+    'reports:view', 'staff_leave:read', 'assets:read', 'boarding:read',
+    'transport:read', 'library:read', 'alumni:read', 'announcements:read',
+    'custom_fields:read', 'visitors:read', 'clinic:read'
+);
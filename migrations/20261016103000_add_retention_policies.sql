@@ -0,0 +1,69 @@
+-- Retention Policies Migration
+-- Per-school retention periods for audit logs, notifications, login history,
+-- and recycle bin items, enforced by a scheduled purge job that records every
+-- run it deletes anything in
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('retention:read', 'View retention policies, purge previews, and purge runs', 'retention'),
+    ('retention:manage', 'Change retention policies and run purges', 'retention');
+
+-- ============================================
+-- Retention Policies Table
+-- ============================================
+-- A category without a row uses the built-in default retention period
+CREATE TABLE retention_policies (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    category TEXT NOT NULL,
+    retention_days INTEGER NOT NULL,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_retention_category CHECK (
+        category IN ('audit_logs', 'notifications', 'login_history', 'deleted_records')
+    ),
+    CONSTRAINT positive_retention_days CHECK (retention_days > 0),
+    CONSTRAINT unique_school_retention_category UNIQUE (school_id, category)
+);
+
+-- ============================================
+-- Purge Runs Table
+-- ============================================
+-- Audit trail of purges. triggered_by is NULL for the scheduled job.
+CREATE TABLE purge_runs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    category TEXT NOT NULL,
+    retention_days INTEGER NOT NULL,
+    cutoff TIMESTAMPTZ NOT NULL,
+    deleted_count BIGINT NOT NULL,
+    triggered_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    ran_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_purge_run_category CHECK (
+        category IN ('audit_logs', 'notifications', 'login_history', 'deleted_records')
+    )
+);
+
+CREATE INDEX idx_purge_runs_school_ran ON purge_runs(school_id, ran_at DESC);
+CREATE INDEX idx_change_events_school_changed ON change_events(school_id, changed_at);
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'retention:%';
+
+-- School Admin sets their school's retention periods
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'retention:%';
+
+-- Auditors check that retention is configured and enforced
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000006', id FROM permissions
+WHERE name = 'retention:read';
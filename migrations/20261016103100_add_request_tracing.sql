@@ -0,0 +1,82 @@
+-- Request Tracing Migration
+-- Request log summaries and request IDs on change events, so support can
+-- trace a request ID quoted from an error response or email back to what the
+-- request did
+
+-- ============================================
+-- Request Logs Table
+-- ============================================
+-- One row per write request and per failed request. error holds the full
+-- error message, including server error details hidden from the client.
+CREATE TABLE request_logs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    request_id TEXT NOT NULL,
+    method TEXT NOT NULL,
+    path TEXT NOT NULL,
+    status SMALLINT NOT NULL,
+    latency_ms BIGINT NOT NULL,
+    user_id UUID,
+    school_id UUID,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_request_logs_request_id ON request_logs(request_id);
+CREATE INDEX idx_request_logs_created_at ON request_logs(created_at);
+
+-- ============================================
+-- Request IDs on Change Events
+-- ============================================
+-- The API sets chalkbyte.request_id on every pooled connection it uses while
+-- handling a request; changes made outside a request leave it empty
+ALTER TABLE change_events ADD COLUMN request_id TEXT;
+
+CREATE INDEX idx_change_events_request_id ON change_events(request_id)
+    WHERE request_id IS NOT NULL;
+
+CREATE OR REPLACE FUNCTION record_change_event()
+RETURNS TRIGGER AS $$
+DECLARE
+    row_data JSONB;
+    changed_id UUID;
+    changed_school_id UUID;
+BEGIN
+    IF TG_OP = 'UPDATE' AND to_jsonb(OLD) = to_jsonb(NEW) THEN
+        RETURN NULL;
+    END IF;
+
+    IF TG_OP = 'DELETE' THEN
+        row_data := to_jsonb(OLD);
+    ELSE
+        row_data := to_jsonb(NEW);
+    END IF;
+
+    changed_id := (row_data->>'id')::uuid;
+    changed_school_id := CASE TG_TABLE_NAME
+        WHEN 'schools' THEN changed_id
+        WHEN 'branches' THEN (
+            SELECT school_id FROM levels WHERE id = (row_data->>'level_id')::uuid
+        )
+        WHEN 'terms' THEN (
+            SELECT school_id FROM academic_sessions
+            WHERE id = (row_data->>'academic_session_id')::uuid
+        )
+        ELSE (row_data->>'school_id')::uuid
+    END;
+
+    INSERT INTO change_events (school_id, entity_type, entity_id, operation, version, request_id)
+    VALUES (
+        changed_school_id,
+        TG_ARGV[0],
+        changed_id,
+        CASE TG_OP WHEN 'INSERT' THEN 'created' WHEN 'UPDATE' THEN 'updated' ELSE 'deleted' END,
+        COALESCE((
+            SELECT MAX(version) FROM change_events
+            WHERE entity_type = TG_ARGV[0] AND entity_id = changed_id
+        ), 0) + 1,
+        NULLIF(current_setting('chalkbyte.request_id', true), '')
+    );
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
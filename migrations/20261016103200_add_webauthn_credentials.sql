@@ -0,0 +1,47 @@
+-- WebAuthn Credentials Migration
+-- Passkeys as a second factor alongside TOTP, and each user's preferred
+-- factor to offer first at login.
+
+-- ============================================
+-- Preferred Second Factor
+-- ============================================
+ALTER TABLE users
+    ADD COLUMN preferred_mfa_method TEXT
+        CHECK (preferred_mfa_method IN ('totp', 'passkey'));
+
+-- ============================================
+-- WebAuthn Credentials Table
+-- ============================================
+CREATE TABLE webauthn_credentials (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    -- Base64url credential ID, unique across all relying party users
+    credential_id TEXT NOT NULL UNIQUE,
+    -- Serialized passkey: public key, algorithm and signature counter
+    passkey JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_webauthn_credentials_user_id ON webauthn_credentials(user_id);
+
+-- ============================================
+-- WebAuthn Ceremonies Table
+-- ============================================
+-- Server-side state between issuing a WebAuthn challenge and receiving the
+-- authenticator's response. Login ceremonies are keyed by the pending MFA
+-- login's handle.
+CREATE TABLE webauthn_ceremonies (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    purpose TEXT NOT NULL CHECK (purpose IN ('registration', 'authentication')),
+    login_handle VARCHAR(64) UNIQUE,
+    state JSONB NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((purpose = 'authentication') = (login_handle IS NOT NULL))
+);
+
+CREATE INDEX idx_webauthn_ceremonies_user_id ON webauthn_ceremonies(user_id);
+CREATE INDEX idx_webauthn_ceremonies_expires_at ON webauthn_ceremonies(expires_at);
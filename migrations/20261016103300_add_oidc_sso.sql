@@ -0,0 +1,101 @@
+-- OIDC SSO Migration
+-- Schools register OpenID Connect identity providers; users sign in through
+-- them and are matched to existing accounts or provisioned on first login
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('sso:manage', 'Configure single sign-on identity providers', 'sso'),
+    ('sso:read', 'View single sign-on identity providers', 'sso');
+
+-- ============================================
+-- OIDC Providers Table
+-- ============================================
+-- slug names the provider in login URLs and is unique across schools.
+-- allowed_domains restricts sign-in to emails in those domains (empty
+-- allows any). default_role_id is given to users provisioned on first login.
+CREATE TABLE oidc_providers (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    slug VARCHAR(64) NOT NULL UNIQUE,
+    name VARCHAR(100) NOT NULL,
+    issuer VARCHAR(2048) NOT NULL,
+    client_id VARCHAR(255) NOT NULL,
+    client_secret VARCHAR(1024) NOT NULL,
+    allowed_domains TEXT[] NOT NULL DEFAULT '{}',
+    jit_provisioning BOOLEAN NOT NULL DEFAULT FALSE,
+    default_role_id UUID NOT NULL REFERENCES roles(id),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_oidc_providers_school_id ON oidc_providers(school_id);
+
+-- ============================================
+-- OIDC Identities Table
+-- ============================================
+-- Links a provider's subject identifier to a chalkbyte user
+CREATE TABLE oidc_identities (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    provider_id UUID NOT NULL REFERENCES oidc_providers(id) ON DELETE CASCADE,
+    subject VARCHAR(255) NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_login_at TIMESTAMPTZ,
+    UNIQUE (provider_id, subject)
+);
+
+CREATE INDEX idx_oidc_identities_user_id ON oidc_identities(user_id);
+
+-- ============================================
+-- OIDC Login States Table
+-- ============================================
+-- Pending authorization requests, consumed by the callback
+CREATE TABLE oidc_login_states (
+    state VARCHAR(64) PRIMARY KEY,
+    provider_id UUID NOT NULL REFERENCES oidc_providers(id) ON DELETE CASCADE,
+    nonce VARCHAR(64) NOT NULL,
+    code_verifier VARCHAR(128) NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_oidc_login_states_expires_at ON oidc_login_states(expires_at);
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_oidc_providers_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_oidc_providers_updated_at
+    BEFORE UPDATE ON oidc_providers
+    FOR EACH ROW
+    EXECUTE FUNCTION update_oidc_providers_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'sso:%';
+
+-- School Admin configures sign-in for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'sso:%';
+
+-- Auditors review how users can sign in
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000006', id FROM permissions
+WHERE name = 'sso:read';
@@ -0,0 +1,102 @@
+-- Guardians Migration
+-- A Guardian system role, links between guardians and their students, and
+-- runs that create guardian accounts from students' emergency contacts
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('guardians:manage', 'Create guardian accounts from student emergency contacts', 'guardians'),
+    ('guardians:read', 'View guardian account runs and their conflicts', 'guardians');
+
+-- ============================================
+-- Guardian System Role
+-- ============================================
+INSERT INTO roles (id, name, description, school_id, is_system_role, slug) VALUES
+    ('00000000-0000-0000-0000-000000000007', 'Guardian', 'Parent or guardian linked to students of a school', NULL, TRUE, 'guardian');
+
+-- ============================================
+-- Student Guardians Table
+-- ============================================
+CREATE TABLE student_guardians (
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    guardian_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    relationship VARCHAR(50),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (student_id, guardian_id)
+);
+
+CREATE INDEX idx_student_guardians_guardian_id ON student_guardians(guardian_id);
+CREATE INDEX idx_student_guardians_school_id ON student_guardians(school_id);
+
+-- ============================================
+-- Guardian Account Runs Table
+-- ============================================
+-- One request to create guardian accounts for a school, processed by a
+-- background job. The counters are filled in when the run completes.
+CREATE TABLE guardian_account_runs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'pending',
+    send_invitations BOOLEAN NOT NULL DEFAULT FALSE,
+    requested_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    contacts_scanned INTEGER NOT NULL DEFAULT 0,
+    accounts_created INTEGER NOT NULL DEFAULT 0,
+    accounts_reused INTEGER NOT NULL DEFAULT 0,
+    links_created INTEGER NOT NULL DEFAULT 0,
+    invitations_sent INTEGER NOT NULL DEFAULT 0,
+    conflicts INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    CONSTRAINT valid_guardian_account_run_status CHECK (status IN ('pending', 'running', 'completed', 'failed'))
+);
+
+CREATE INDEX idx_guardian_account_runs_school_created ON guardian_account_runs(school_id, created_at DESC);
+CREATE INDEX idx_guardian_account_runs_pending ON guardian_account_runs(created_at) WHERE status = 'pending';
+
+-- A school has at most one run waiting or in progress
+CREATE UNIQUE INDEX idx_guardian_account_runs_one_active
+    ON guardian_account_runs(school_id) WHERE status IN ('pending', 'running');
+
+-- ============================================
+-- Guardian Account Conflicts Table
+-- ============================================
+-- Contacts a run could not turn into an account, for manual review
+CREATE TABLE guardian_account_conflicts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    run_id UUID NOT NULL REFERENCES guardian_account_runs(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    contact_name VARCHAR(150),
+    emails TEXT[] NOT NULL DEFAULT '{}',
+    phones TEXT[] NOT NULL DEFAULT '{}',
+    student_ids UUID[] NOT NULL,
+    existing_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_guardian_account_conflict_kind CHECK (
+        kind IN ('missing_email', 'conflicting_emails', 'email_in_use')
+    )
+);
+
+CREATE INDEX idx_guardian_account_conflicts_run_id ON guardian_account_conflicts(run_id);
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'guardians:%';
+
+-- School Admin creates guardian accounts for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'guardians:%';
+
+-- Auditors check which guardians have accounts
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000006', id FROM permissions
+WHERE name = 'guardians:read';
@@ -0,0 +1,36 @@
+-- Scoped Permissions Migration
+-- Permission names may end in a scope modifier, `resource:action:scope`,
+-- granting the action on only some records. The bare permission still covers
+-- the whole school and implies every scoped form.
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('students:read:own_branch', 'View students in branches you teach', 'students'),
+    ('students:update:own_branch', 'Update students in branches you teach', 'students'),
+    ('users:read:self', 'View your own user record', 'users'),
+    ('users:update:self', 'Update your own user record', 'users');
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name IN (
+    'students:read:own_branch', 'students:update:own_branch',
+    'users:read:self', 'users:update:self'
+);
+
+-- Teacher is limited to students in branches they currently teach
+DELETE FROM role_permissions
+WHERE role_id = '00000000-0000-0000-0000-000000000003'
+  AND permission_id IN (
+      SELECT id FROM permissions WHERE name IN ('students:read', 'students:update')
+  );
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name IN ('students:read:own_branch', 'students:update:own_branch');
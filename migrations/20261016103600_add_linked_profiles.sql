@@ -0,0 +1,36 @@
+-- Linked Profiles Migration
+-- Links between accounts of one person at a school (e.g. a teacher who is
+-- also a parent), per-profile notification preferences, and the signed-in
+-- account behind requests made as a linked profile
+
+-- ============================================
+-- Linked Profiles Table
+-- ============================================
+-- Each pair is stored once, with the smaller user ID first. Links are not
+-- transitive: a login can switch to the accounts linked to it directly.
+CREATE TABLE linked_profiles (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    linked_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    linked_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, linked_user_id),
+    CONSTRAINT linked_profiles_ordered CHECK (user_id < linked_user_id)
+);
+
+CREATE INDEX idx_linked_profiles_linked_user_id ON linked_profiles(linked_user_id);
+
+-- ============================================
+-- Notification Preferences Table
+-- ============================================
+-- One row per user, and so per profile; users without a row get the defaults
+CREATE TABLE notification_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    announcement_emails BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- ============================================
+-- Signed-in Account on Request Logs
+-- ============================================
+-- Set when the request was made as a linked profile; user_id is the profile
+ALTER TABLE request_logs ADD COLUMN login_user_id UUID;
@@ -0,0 +1,75 @@
+-- Session Reports Migration
+-- Student transfers recorded as they happen, and end-of-session summary
+-- reports generated by a background job for board reporting
+
+-- ============================================
+-- Student Transfers Table
+-- ============================================
+-- A student moving between schools, or between branches of a school without
+-- changing level. Branch moves that come with a level change are promotions
+-- and are recorded in student_promotions instead.
+CREATE TABLE student_transfers (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    from_school_id UUID REFERENCES schools(id) ON DELETE SET NULL,
+    to_school_id UUID REFERENCES schools(id) ON DELETE SET NULL,
+    from_branch_id UUID REFERENCES branches(id) ON DELETE SET NULL,
+    to_branch_id UUID REFERENCES branches(id) ON DELETE SET NULL,
+    transferred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_student_transfers_from_school ON student_transfers(from_school_id, transferred_at);
+CREATE INDEX idx_student_transfers_to_school ON student_transfers(to_school_id, transferred_at);
+
+CREATE OR REPLACE FUNCTION record_student_transfer()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF OLD.school_id IS DISTINCT FROM NEW.school_id
+        OR (OLD.branch_id IS NOT NULL
+            AND NEW.branch_id IS NOT NULL
+            AND OLD.branch_id <> NEW.branch_id
+            AND OLD.level_id IS NOT DISTINCT FROM NEW.level_id)
+    THEN
+        IF EXISTS (
+            SELECT 1 FROM user_roles
+            WHERE user_id = NEW.id AND role_id = '00000000-0000-0000-0000-000000000004'
+        ) THEN
+            INSERT INTO student_transfers (student_id, from_school_id, to_school_id, from_branch_id, to_branch_id)
+            VALUES (NEW.id, OLD.school_id, NEW.school_id, OLD.branch_id, NEW.branch_id);
+        END IF;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_record_student_transfer
+    AFTER UPDATE OF school_id, branch_id ON users
+    FOR EACH ROW EXECUTE FUNCTION record_student_transfer();
+
+-- ============================================
+-- Session Reports Table
+-- ============================================
+-- One request for a school's session summary, processed by a background
+-- job. The summary and its PDF rendering are stored when it completes.
+CREATE TABLE session_reports (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    academic_session_id UUID NOT NULL REFERENCES academic_sessions(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'pending',
+    requested_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    summary JSONB,
+    pdf BYTEA,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    CONSTRAINT valid_session_report_status CHECK (status IN ('pending', 'running', 'completed', 'failed'))
+);
+
+CREATE INDEX idx_session_reports_session_created ON session_reports(academic_session_id, created_at DESC);
+CREATE INDEX idx_session_reports_pending ON session_reports(created_at) WHERE status = 'pending';
+
+-- A session has at most one report waiting or in progress
+CREATE UNIQUE INDEX idx_session_reports_one_active
+    ON session_reports(academic_session_id) WHERE status IN ('pending', 'running');
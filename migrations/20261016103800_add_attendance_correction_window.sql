@@ -0,0 +1,31 @@
+-- Attendance Correction Window Migration
+-- A per-school limit on how long after the day attendance can be marked or
+-- changed. Admins with attendance:override can still correct older records.
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('attendance:override', 'Mark or change attendance after the school''s correction window has closed', 'attendance');
+
+-- ============================================
+-- School Settings
+-- ============================================
+-- Hours after the end of the attendance day, in the school's timezone, that
+-- records can still be changed; NULL for no limit
+ALTER TABLE schools ADD COLUMN attendance_correction_window_hours INTEGER
+    CHECK (attendance_correction_window_hours > 0);
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name = 'attendance:override';
+
+-- School Admin corrects attendance for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name = 'attendance:override';
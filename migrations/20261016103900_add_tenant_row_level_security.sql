@@ -0,0 +1,63 @@
+-- Tenant Row-Level Security Migration
+-- School-scoped requests run their queries as the chalkbyte_tenant role,
+-- which row-level security limits to the schools listed in the
+-- app.tenant_school_ids setting of the transaction
+
+-- ============================================
+-- Tenant Role
+-- ============================================
+-- Roles belong to the cluster rather than the database, so another database
+-- (or a concurrent migration of one) may have created it already
+DO $$
+BEGIN
+    CREATE ROLE chalkbyte_tenant NOLOGIN;
+EXCEPTION
+    WHEN duplicate_object OR unique_violation THEN NULL;
+END
+$$;
+
+-- The application's own role switches to the tenant role with SET LOCAL ROLE
+GRANT chalkbyte_tenant TO CURRENT_USER;
+
+GRANT USAGE ON SCHEMA public TO chalkbyte_tenant;
+GRANT SELECT, INSERT, UPDATE, DELETE ON ALL TABLES IN SCHEMA public TO chalkbyte_tenant;
+GRANT USAGE, SELECT ON ALL SEQUENCES IN SCHEMA public TO chalkbyte_tenant;
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+    GRANT SELECT, INSERT, UPDATE, DELETE ON TABLES TO chalkbyte_tenant;
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+    GRANT USAGE, SELECT ON SEQUENCES TO chalkbyte_tenant;
+
+-- ============================================
+-- Tenant Schools
+-- ============================================
+-- NULL when the setting is missing, which no policy matches
+CREATE OR REPLACE FUNCTION tenant_school_ids() RETURNS UUID[]
+    LANGUAGE sql STABLE
+AS $$
+    SELECT string_to_array(NULLIF(current_setting('app.tenant_school_ids', true), ''), ',')::UUID[]
+$$;
+
+-- ============================================
+-- Policies
+-- ============================================
+-- Table owners bypass row-level security, so requests that are not scoped
+-- to schools are unaffected. The policies apply to reads and, as their
+-- WITH CHECK, to the rows an insert or update writes.
+ALTER TABLE levels ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON levels TO chalkbyte_tenant
+    USING (school_id = ANY(tenant_school_ids()));
+
+ALTER TABLE branches ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON branches TO chalkbyte_tenant
+    USING (EXISTS (
+        SELECT 1 FROM levels l
+        WHERE l.id = branches.level_id AND l.school_id = ANY(tenant_school_ids())
+    ));
+
+ALTER TABLE users ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON users TO chalkbyte_tenant
+    USING (school_id = ANY(tenant_school_ids()));
+
+ALTER TABLE academic_sessions ENABLE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON academic_sessions TO chalkbyte_tenant
+    USING (school_id = ANY(tenant_school_ids()));
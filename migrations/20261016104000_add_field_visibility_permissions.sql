@@ -0,0 +1,21 @@
+-- Field Visibility Permissions Migration
+-- Responses omit contact details and medical notes for callers without the
+-- matching permission. Existing roles keep seeing both; school admins grant
+-- medical_notes:read to the custom roles that need it, such as a nurse.
+
+-- ============================================
+-- New Permissions
+-- ============================================
+
+INSERT INTO permissions (name, description, category) VALUES
+    ('contact_details:read', 'View emails, phone numbers, and emergency contacts in responses', 'field_visibility'),
+    ('medical_notes:read', 'View allergies, conditions, medication, and medical notes in responses', 'field_visibility');
+
+-- ============================================
+-- Assign Permissions
+-- ============================================
+
+-- Every existing role keeps the fields it sees today
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r, permissions p
+WHERE p.name IN ('contact_details:read', 'medical_notes:read');
@@ -0,0 +1,56 @@
+-- Feature Flags Migration
+-- Runtime switches for features, on or off for every school with optional
+-- per-school overrides, so system admins can roll a feature out without a
+-- redeploy
+
+-- ============================================
+-- Feature Flags Table
+-- ============================================
+CREATE TABLE feature_flags (
+    key TEXT PRIMARY KEY CHECK (key ~ '^[a-z][a-z0-9_]*$'),
+    description TEXT,
+    -- Whether the feature is on for schools without an override
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- ============================================
+-- School Overrides Table
+-- ============================================
+CREATE TABLE school_feature_flags (
+    flag_key TEXT NOT NULL REFERENCES feature_flags(key) ON DELETE CASCADE,
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    enabled BOOLEAN NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (flag_key, school_id)
+);
+
+CREATE INDEX idx_school_feature_flags_school ON school_feature_flags(school_id);
+
+-- Trigger for updated_at
+CREATE OR REPLACE FUNCTION update_feature_flags_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_feature_flags_updated_at
+    BEFORE UPDATE ON feature_flags
+    FOR EACH ROW
+    EXECUTE FUNCTION update_feature_flags_updated_at();
+
+CREATE TRIGGER trigger_update_school_feature_flags_updated_at
+    BEFORE UPDATE ON school_feature_flags
+    FOR EACH ROW
+    EXECUTE FUNCTION update_feature_flags_updated_at();
+
+-- ============================================
+-- Built-in Flags
+-- ============================================
+-- On by default so existing schools keep the module; turn it off and
+-- override per school to stage a rollout
+INSERT INTO feature_flags (key, description, enabled) VALUES
+    ('attendance', 'Attendance marking, reports, and kiosks', TRUE);
@@ -0,0 +1,13 @@
+-- Session Client Details Migration
+-- Records the client behind each refresh token family so users can list
+-- their signed-in devices and revoke one they do not recognise.
+
+ALTER TABLE refresh_token_families
+    ADD COLUMN user_agent TEXT,
+    ADD COLUMN ip_address TEXT,
+    ADD COLUMN last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+UPDATE refresh_token_families SET last_seen_at = updated_at;
+
+CREATE INDEX idx_refresh_token_families_user_active
+    ON refresh_token_families(user_id, last_seen_at DESC) WHERE revoked_at IS NULL;
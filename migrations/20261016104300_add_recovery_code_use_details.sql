@@ -0,0 +1,11 @@
+-- Recovery Code Use Details Migration
+-- Records where each recovery code was used so a sign-in with one can be
+-- audited later, and indexes the unused codes counted on the MFA status.
+
+ALTER TABLE mfa_recovery_codes
+    ADD COLUMN used_ip_address TEXT,
+    ADD COLUMN used_user_agent TEXT;
+
+DROP INDEX IF EXISTS idx_mfa_recovery_codes_used;
+CREATE INDEX idx_mfa_recovery_codes_unused
+    ON mfa_recovery_codes(user_id) WHERE used = FALSE;
@@ -0,0 +1,12 @@
+-- School Email Branding Migration
+-- Per-school overrides for the sender name, logo, and primary color of
+-- emails sent on a school's behalf. Missing rows and NULL columns use the
+-- defaults.
+
+CREATE TABLE school_email_branding (
+    school_id UUID PRIMARY KEY REFERENCES schools(id) ON DELETE CASCADE,
+    sender_name TEXT,
+    logo_url TEXT,
+    primary_color TEXT CHECK (primary_color ~ '^#[0-9A-Fa-f]{6}$'),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,45 @@
+-- Email Outbox Migration
+-- Outbound emails are written to an outbox and delivered by a background job
+-- with retry and backoff. Addresses that bounce permanently, or that an admin
+-- suppresses, are recorded so they are not emailed again.
+
+-- ============================================
+-- Email Outbox Table
+-- ============================================
+-- Bodies are rendered when the email is queued. attempts counts attempts
+-- started; next_attempt_at is pushed forward while an attempt is in flight so
+-- an interrupted one is retried.
+CREATE TABLE email_outbox (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID REFERENCES schools(id) ON DELETE SET NULL,
+    to_email VARCHAR(255) NOT NULL,
+    from_name VARCHAR(255) NOT NULL,
+    subject TEXT NOT NULL,
+    text_body TEXT NOT NULL,
+    html_body TEXT NOT NULL,
+    request_id TEXT,
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error TEXT,
+    sent_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_email_outbox_status CHECK (status IN ('pending', 'sent', 'failed', 'suppressed'))
+);
+
+CREATE INDEX idx_email_outbox_due ON email_outbox(next_attempt_at) WHERE status = 'pending';
+CREATE INDEX idx_email_outbox_created_at ON email_outbox(created_at DESC);
+CREATE INDEX idx_email_outbox_to_email ON email_outbox(LOWER(to_email), created_at DESC);
+
+-- ============================================
+-- Email Suppressions Table
+-- ============================================
+-- email is stored lowercased
+CREATE TABLE email_suppressions (
+    email VARCHAR(255) PRIMARY KEY,
+    reason TEXT NOT NULL,
+    detail TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_email_suppression_reason CHECK (reason IN ('bounce', 'complaint', 'manual')),
+    CONSTRAINT email_suppression_is_lowercase CHECK (email = LOWER(email))
+);
@@ -0,0 +1,27 @@
+-- File Storage Migration
+-- Private files kept by the storage backend: user avatars and student
+-- documents. Each school's files count towards its storage quota.
+
+ALTER TABLE schools ADD COLUMN storage_quota_bytes BIGINT CHECK (storage_quota_bytes >= 0);
+
+COMMENT ON COLUMN schools.storage_quota_bytes IS
+    'Bytes of files the school may store; NULL uses the configured default';
+
+CREATE TABLE stored_files (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- The user the file is about: the avatar's user or the document's student
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- School whose quota the file counts towards; NULL for users without one
+    school_id UUID REFERENCES schools(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('avatar', 'birth_certificate', 'transcript', 'other')),
+    storage_key TEXT NOT NULL UNIQUE,
+    filename TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes >= 0),
+    uploaded_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_stored_files_one_avatar ON stored_files(owner_id) WHERE kind = 'avatar';
+CREATE INDEX idx_stored_files_owner ON stored_files(owner_id, created_at DESC);
+CREATE INDEX idx_stored_files_school ON stored_files(school_id);
@@ -0,0 +1,11 @@
+-- Term Transitions Migration
+-- A background job moves each school's active session and current term to
+-- match the calendar at midnight in the school's timezone.
+
+-- ============================================
+-- Schools
+-- ============================================
+-- The school-local date transitions were last applied; NULL until the first
+-- run. Each school is transitioned once per local day, so manual changes made
+-- during the day are left alone.
+ALTER TABLE schools ADD COLUMN term_transitions_date DATE;
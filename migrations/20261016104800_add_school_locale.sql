@@ -0,0 +1,10 @@
+-- School Settings Migration
+-- Schools already have a timezone (added for scheduled broadcasts); it now
+-- also sets how dates such as "today" are read for attendance and terms.
+-- Alongside it, a locale for formatting.
+
+-- ============================================
+-- Schools
+-- ============================================
+-- BCP 47 language tag, e.g. 'en-NG'
+ALTER TABLE schools ADD COLUMN locale VARCHAR(35) NOT NULL DEFAULT 'en';
@@ -0,0 +1,147 @@
+-- Billing Migration
+-- Fee structures per level and term, student invoices generated from them,
+-- and manually recorded payments
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('billing:create', 'Create fee structures and generate invoices', 'billing'),
+    ('billing:read', 'View fee structures, invoices, payments, and balances', 'billing'),
+    ('billing:delete', 'Delete fee structures and void invoices', 'billing'),
+    ('billing:collect', 'Record fee payments against invoices', 'billing'),
+    ('billing:export', 'Export fee arrears', 'billing');
+
+-- ============================================
+-- Fee Structures Table
+-- ============================================
+-- Amounts are stored in minor currency units (e.g. kobo, cents)
+CREATE TABLE fee_structures (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    level_id UUID NOT NULL REFERENCES levels(id) ON DELETE CASCADE,
+    term_id UUID NOT NULL REFERENCES terms(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    due_date DATE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_fee_structure_per_level_term UNIQUE (level_id, term_id, name)
+);
+
+CREATE INDEX idx_fee_structures_school_term ON fee_structures(school_id, term_id);
+
+CREATE TABLE fee_structure_items (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    fee_structure_id UUID NOT NULL REFERENCES fee_structures(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    amount BIGINT NOT NULL,
+    position INTEGER NOT NULL,
+    CONSTRAINT unique_fee_item_name UNIQUE (fee_structure_id, name),
+    CONSTRAINT non_negative_fee_item_amount CHECK (amount >= 0)
+);
+
+-- ============================================
+-- Invoices Table
+-- ============================================
+-- An invoice copies its structure's items, so later changes to the structure
+-- never alter what a student was billed. A structure cannot be deleted while
+-- invoices reference it.
+CREATE TABLE invoices (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    fee_structure_id UUID NOT NULL REFERENCES fee_structures(id),
+    term_id UUID NOT NULL REFERENCES terms(id) ON DELETE CASCADE,
+    amount BIGINT NOT NULL,
+    amount_paid BIGINT NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'unpaid',
+    due_date DATE,
+    issued_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    voided_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_invoice_per_structure_student UNIQUE (fee_structure_id, student_id),
+    CONSTRAINT valid_invoice_status CHECK (status IN ('unpaid', 'partially_paid', 'paid', 'void')),
+    CONSTRAINT valid_invoice_amount_paid CHECK (amount_paid >= 0 AND amount_paid <= amount)
+);
+
+CREATE INDEX idx_invoices_school_status ON invoices(school_id, status);
+CREATE INDEX idx_invoices_student_id ON invoices(student_id);
+CREATE INDEX idx_invoices_term_id ON invoices(term_id);
+
+CREATE TABLE invoice_lines (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    invoice_id UUID NOT NULL REFERENCES invoices(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    amount BIGINT NOT NULL,
+    position INTEGER NOT NULL
+);
+
+CREATE INDEX idx_invoice_lines_invoice_id ON invoice_lines(invoice_id);
+
+-- ============================================
+-- Fee Payments Table
+-- ============================================
+CREATE TABLE fee_payments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    invoice_id UUID NOT NULL REFERENCES invoices(id) ON DELETE CASCADE,
+    amount BIGINT NOT NULL,
+    method TEXT NOT NULL,
+    reference VARCHAR(100),
+    paid_on DATE NOT NULL,
+    recorded_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT positive_fee_payment CHECK (amount > 0),
+    CONSTRAINT valid_fee_payment_method CHECK (
+        method IN ('cash', 'bank_transfer', 'card', 'cheque', 'mobile_money', 'other')
+    )
+);
+
+CREATE INDEX idx_fee_payments_invoice_id ON fee_payments(invoice_id);
+
+-- ============================================
+-- Triggers for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_fee_structures_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_fee_structures_updated_at
+    BEFORE UPDATE ON fee_structures
+    FOR EACH ROW
+    EXECUTE FUNCTION update_fee_structures_updated_at();
+
+CREATE OR REPLACE FUNCTION update_invoices_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_invoices_updated_at
+    BEFORE UPDATE ON invoices
+    FOR EACH ROW
+    EXECUTE FUNCTION update_invoices_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'billing:%';
+
+-- School Admin runs billing for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name LIKE 'billing:%';
+
+-- Auditor reads billing records
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000006', id FROM permissions
+WHERE name = 'billing:read';
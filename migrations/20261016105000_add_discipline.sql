@@ -0,0 +1,89 @@
+-- Discipline Migration
+-- Behaviour incidents recorded against students, with a record of when
+-- their guardians were notified
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('discipline:record', 'Record behaviour incidents', 'discipline'),
+    ('discipline:read', 'View behaviour incidents and trends', 'discipline'),
+    ('discipline:read:own_branch', 'View behaviour incidents of students in branches you teach', 'discipline'),
+    ('discipline:read:self', 'View your own behaviour incidents', 'discipline'),
+    ('discipline:update', 'Update behaviour incidents and notify guardians', 'discipline'),
+    ('discipline:delete', 'Delete behaviour incidents', 'discipline');
+
+-- ============================================
+-- Discipline Incidents Table
+-- ============================================
+CREATE TABLE discipline_incidents (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    reported_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    category TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    location VARCHAR(100),
+    description TEXT NOT NULL,
+    actions_taken TEXT,
+    guardian_notified_at TIMESTAMPTZ,
+    guardian_notified_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_discipline_incident_category CHECK (
+        category IN (
+            'lateness', 'disruption', 'disrespect', 'bullying', 'fighting', 'truancy',
+            'dishonesty', 'vandalism', 'substance_use', 'other'
+        )
+    ),
+    CONSTRAINT valid_discipline_incident_severity CHECK (severity IN ('minor', 'moderate', 'serious'))
+);
+
+CREATE INDEX idx_discipline_incidents_school_occurred ON discipline_incidents(school_id, occurred_at DESC);
+CREATE INDEX idx_discipline_incidents_student_occurred ON discipline_incidents(student_id, occurred_at DESC);
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_discipline_incidents_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_discipline_incidents_updated_at
+    BEFORE UPDATE ON discipline_incidents
+    FOR EACH ROW
+    EXECUTE FUNCTION update_discipline_incidents_updated_at();
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'discipline:%';
+
+-- School Admin manages discipline for the whole school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name IN ('discipline:record', 'discipline:read', 'discipline:update', 'discipline:delete');
+
+-- Teacher records incidents and reads those of students in branches they teach
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name IN ('discipline:record', 'discipline:read:own_branch');
+
+-- Student reads only their own incidents
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000004', id FROM permissions
+WHERE name = 'discipline:read:self';
+
+-- Auditor reads discipline records
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000006', id FROM permissions
+WHERE name = 'discipline:read';
@@ -0,0 +1,96 @@
+-- Messaging Migration
+-- School-scoped conversations between teachers and guardians, and between
+-- school admins and staff, with per-participant read markers and file
+-- attachments kept in the storage backend
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('messaging:read', 'View your conversations and messages', 'messaging'),
+    ('messaging:send', 'Start conversations and send messages', 'messaging');
+
+-- ============================================
+-- Conversations Table
+-- ============================================
+CREATE TABLE conversations (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    subject VARCHAR(200) NOT NULL,
+    -- Student a teacher-guardian conversation is about, if any
+    student_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_message_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_conversation_kind CHECK (kind IN ('teacher_guardian', 'staff'))
+);
+
+CREATE INDEX idx_conversations_school ON conversations(school_id, last_message_at DESC);
+
+-- ============================================
+-- Conversation Participants Table
+-- ============================================
+CREATE TABLE conversation_participants (
+    conversation_id UUID NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Messages sent after this are unread; NULL if never read
+    last_read_at TIMESTAMPTZ,
+    joined_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (conversation_id, user_id)
+);
+
+CREATE INDEX idx_conversation_participants_user ON conversation_participants(user_id);
+
+-- ============================================
+-- Messages Table
+-- ============================================
+CREATE TABLE messages (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    conversation_id UUID NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    sender_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    -- Empty for a message that is only an attachment
+    body TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_messages_conversation_created ON messages(conversation_id, created_at DESC);
+
+-- ============================================
+-- Message Attachments Table
+-- ============================================
+ALTER TABLE stored_files DROP CONSTRAINT stored_files_kind_check;
+ALTER TABLE stored_files ADD CONSTRAINT stored_files_kind_check
+    CHECK (kind IN ('avatar', 'birth_certificate', 'transcript', 'other', 'message_attachment'));
+
+CREATE TABLE message_attachments (
+    message_id UUID NOT NULL REFERENCES messages(id) ON DELETE CASCADE,
+    file_id UUID NOT NULL REFERENCES stored_files(id) ON DELETE CASCADE,
+    PRIMARY KEY (message_id, file_id)
+);
+
+CREATE UNIQUE INDEX idx_message_attachments_file ON message_attachments(file_id);
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'messaging:%';
+
+-- School Admin messages teachers and other admins
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name IN ('messaging:read', 'messaging:send');
+
+-- Teacher messages guardians and school admins
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000003', id FROM permissions
+WHERE name IN ('messaging:read', 'messaging:send');
+
+-- Guardian messages their children's teachers
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000007', id FROM permissions
+WHERE name IN ('messaging:read', 'messaging:send');
@@ -0,0 +1,58 @@
+-- Exam Sittings Migration
+-- When each exam is sat and for how long, the branches seated for it, and
+-- the teachers invigilating it
+
+-- ============================================
+-- Seated Branches
+-- ============================================
+-- Branches of the exam's level the plan seats; NULL seats the whole level
+ALTER TABLE exam_seating_plans ADD COLUMN branch_ids UUID[];
+
+-- ============================================
+-- Exam Sittings Table
+-- ============================================
+CREATE TABLE exam_sittings (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    assessment_id UUID NOT NULL UNIQUE REFERENCES assessments(id) ON DELETE CASCADE,
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    starts_at TIMESTAMPTZ NOT NULL,
+    duration_minutes INTEGER NOT NULL,
+    scheduled_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT valid_exam_sitting_duration CHECK (duration_minutes BETWEEN 1 AND 720)
+);
+
+CREATE INDEX idx_exam_sittings_school_starts ON exam_sittings(school_id, starts_at);
+
+-- ============================================
+-- Exam Invigilators Table
+-- ============================================
+-- room_name matches a room of the exam's seating plan, so assignments
+-- survive the plan being generated again; NULL for a roaming invigilator
+CREATE TABLE exam_invigilators (
+    sitting_id UUID NOT NULL REFERENCES exam_sittings(id) ON DELETE CASCADE,
+    teacher_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    room_name VARCHAR(100),
+    assigned_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (sitting_id, teacher_id)
+);
+
+CREATE INDEX idx_exam_invigilators_teacher_id ON exam_invigilators(teacher_id);
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_exam_sittings_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_exam_sittings_updated_at
+    BEFORE UPDATE ON exam_sittings
+    FOR EACH ROW
+    EXECUTE FUNCTION update_exam_sittings_updated_at();
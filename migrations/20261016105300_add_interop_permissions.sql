@@ -0,0 +1,24 @@
+-- Interoperability Permissions Migration
+-- Exporting a school's roster as OneRoster 1.1 CSV and importing rosters
+-- from LMS products in the same format
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('interop:export', 'Export the school roster in OneRoster format', 'interop'),
+    ('interop:import', 'Import rosters in OneRoster format', 'interop');
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'interop:%';
+
+-- School Admin syncs their school's roster with LMS products
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name IN ('interop:export', 'interop:import');
@@ -0,0 +1,124 @@
+-- LMS Sync Migration
+-- Connections that push a school's branches and rosters to an LMS such as
+-- Google Classroom, the runs that do it, and what each run left behind for
+-- every course and roster member
+
+-- ============================================
+-- Sync Connections Table
+-- ============================================
+-- One connection per school and provider. credentials holds the provider's
+-- secrets and is never returned by the API.
+CREATE TABLE sync_connections (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    provider TEXT NOT NULL,
+    credentials JSONB NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    interval_minutes INTEGER NOT NULL DEFAULT 360,
+    next_run_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_sync_connection_per_school UNIQUE (school_id, provider),
+    CONSTRAINT valid_sync_provider CHECK (provider IN ('google_classroom')),
+    CONSTRAINT valid_sync_interval CHECK (interval_minutes BETWEEN 15 AND 10080)
+);
+
+CREATE INDEX idx_sync_connections_due ON sync_connections(next_run_at) WHERE is_active;
+
+-- ============================================
+-- Sync Runs Table
+-- ============================================
+-- A run queued by the schedule or by hand and processed by a background job
+CREATE TABLE sync_runs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    connection_id UUID NOT NULL REFERENCES sync_connections(id) ON DELETE CASCADE,
+    trigger TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    requested_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    pushed INTEGER NOT NULL DEFAULT 0,
+    unchanged INTEGER NOT NULL DEFAULT 0,
+    conflicts INTEGER NOT NULL DEFAULT 0,
+    failures INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    CONSTRAINT valid_sync_run_trigger CHECK (trigger IN ('schedule', 'manual')),
+    CONSTRAINT valid_sync_run_status CHECK (status IN ('pending', 'running', 'completed', 'failed'))
+);
+
+CREATE INDEX idx_sync_runs_connection_created ON sync_runs(connection_id, created_at DESC);
+CREATE INDEX idx_sync_runs_pending ON sync_runs(created_at) WHERE status = 'pending';
+
+-- A connection has at most one run waiting or in progress
+CREATE UNIQUE INDEX idx_sync_runs_one_active
+    ON sync_runs(connection_id) WHERE status IN ('pending', 'running');
+
+-- ============================================
+-- Sync Entities Table
+-- ============================================
+-- Sync state of one branch's course (user_id NULL) or one member of its
+-- roster. Not tied to branches or users by foreign key, so a run can tidy
+-- up the LMS after either is deleted.
+CREATE TABLE sync_entities (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    connection_id UUID NOT NULL REFERENCES sync_connections(id) ON DELETE CASCADE,
+    entity_type TEXT NOT NULL,
+    branch_id UUID NOT NULL,
+    user_id UUID,
+    remote_id TEXT,
+    status TEXT NOT NULL,
+    conflict TEXT,
+    message TEXT,
+    -- What was last pushed, and the LMS's version of it at the time
+    content_hash TEXT,
+    remote_version TEXT,
+    last_run_id UUID REFERENCES sync_runs(id) ON DELETE SET NULL,
+    synced_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_sync_entity UNIQUE NULLS NOT DISTINCT (connection_id, entity_type, branch_id, user_id),
+    CONSTRAINT valid_sync_entity_type CHECK (entity_type IN ('course', 'student', 'teacher')),
+    CONSTRAINT valid_sync_entity_user CHECK ((entity_type = 'course') = (user_id IS NULL)),
+    CONSTRAINT valid_sync_entity_status CHECK (status IN ('pending', 'synced', 'conflict', 'failed')),
+    CONSTRAINT valid_sync_entity_conflict CHECK ((status = 'conflict') = (conflict IS NOT NULL))
+);
+
+CREATE INDEX idx_sync_entities_status ON sync_entities(connection_id, status);
+
+-- ============================================
+-- Trigger for updated_at
+-- ============================================
+CREATE OR REPLACE FUNCTION update_sync_connections_updated_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_update_sync_connections_updated_at
+    BEFORE UPDATE ON sync_connections
+    FOR EACH ROW
+    EXECUTE FUNCTION update_sync_connections_updated_at();
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('sync:read', 'View LMS sync connections, runs, and conflicts', 'sync'),
+    ('sync:manage', 'Connect LMS products, run syncs, and resolve conflicts', 'sync');
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name LIKE 'sync:%';
+
+-- School Admin connects their school's LMS
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name IN ('sync:read', 'sync:manage');
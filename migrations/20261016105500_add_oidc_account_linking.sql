@@ -0,0 +1,11 @@
+-- OIDC Account Linking Migration
+-- SSO identities are no longer matched to existing accounts by email. A
+-- signed-in user links an identity to their own account by starting a
+-- sign-in that records them on the login state.
+
+-- ============================================
+-- OIDC Login States
+-- ============================================
+-- The account the identity is linked to when the sign-in completes
+ALTER TABLE oidc_login_states
+    ADD COLUMN link_user_id UUID REFERENCES users(id) ON DELETE CASCADE;
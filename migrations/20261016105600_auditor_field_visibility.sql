@@ -0,0 +1,31 @@
+-- Auditor Field Visibility Migration
+-- Auditors see restricted fields through the contact_details:read and
+-- medical_notes:read permissions like every other role, instead of through
+-- a separate sensitive_data:read permission: they lose both, and
+-- sensitive_data:read is dropped. Dates of birth now count as contact
+-- details.
+
+-- ============================================
+-- Revoke Field Visibility From Auditor
+-- ============================================
+
+DELETE FROM role_permissions
+WHERE role_id = '00000000-0000-0000-0000-000000000006'
+  AND permission_id IN (
+      SELECT id FROM permissions WHERE name IN ('contact_details:read', 'medical_notes:read')
+  );
+
+-- ============================================
+-- Drop sensitive_data:read
+-- ============================================
+
+-- role_permissions rows go with it (ON DELETE CASCADE)
+DELETE FROM permissions WHERE name = 'sensitive_data:read';
+
+UPDATE permissions
+SET description = 'View emails, phone numbers, emergency contacts, and dates of birth in responses'
+WHERE name = 'contact_details:read';
+
+UPDATE permissions
+SET description = 'View allergies, conditions, medication, symptoms, and treatment in responses'
+WHERE name = 'medical_notes:read';
@@ -0,0 +1,18 @@
+-- Billing Ledger Charges Migration
+-- Boarding fees, transport charges, and library fines are billed on the
+-- student's next invoice. Each charge remembers the invoice it went onto so
+-- it is billed only once; voiding the invoice releases it again.
+
+ALTER TABLE transport_fee_charges
+    ADD COLUMN invoice_id UUID REFERENCES invoices(id) ON DELETE SET NULL;
+
+ALTER TABLE library_fine_charges
+    ADD COLUMN invoice_id UUID REFERENCES invoices(id) ON DELETE SET NULL;
+
+-- Boarding fees are billed once per allocation from its hostel's fee lines
+ALTER TABLE room_allocations
+    ADD COLUMN invoice_id UUID REFERENCES invoices(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_transport_fee_charges_invoice_id ON transport_fee_charges(invoice_id);
+CREATE INDEX idx_library_fine_charges_invoice_id ON library_fine_charges(invoice_id);
+CREATE INDEX idx_room_allocations_invoice_id ON room_allocations(invoice_id);
@@ -0,0 +1,7 @@
+-- Broadcast Fee Status Migration
+-- Lets a broadcast's audience be narrowed to students who owe fees or to
+-- students who are paid up
+
+ALTER TABLE broadcasts
+    ADD COLUMN fee_status TEXT,
+    ADD CONSTRAINT valid_broadcast_fee_status CHECK (fee_status IN ('in_arrears', 'paid_up'));
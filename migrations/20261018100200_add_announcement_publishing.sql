@@ -0,0 +1,46 @@
+-- Announcement Publishing Workflow Migration
+-- Drafts, scheduled publishing in the school's timezone, and four-eyes
+-- approval for announcements, sharing the school's broadcast publishing
+-- settings
+
+-- ============================================
+-- New Permissions
+-- ============================================
+INSERT INTO permissions (name, description, category) VALUES
+    ('announcements:approve', 'Approve announcements submitted by others', 'announcements');
+
+-- ============================================
+-- Announcement Workflow Columns
+-- ============================================
+-- Existing announcements were delivered when created. publish_at is a local
+-- time in the school's timezone, like a broadcast's
+ALTER TABLE announcements ADD COLUMN status TEXT NOT NULL DEFAULT 'published';
+ALTER TABLE announcements ADD COLUMN publish_at TIMESTAMP;
+ALTER TABLE announcements ADD COLUMN submitted_by UUID REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE announcements ADD COLUMN submitted_at TIMESTAMPTZ;
+ALTER TABLE announcements ADD COLUMN reviewed_by UUID REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE announcements ADD COLUMN reviewed_at TIMESTAMPTZ;
+ALTER TABLE announcements ADD COLUMN review_note TEXT;
+ALTER TABLE announcements ADD COLUMN published_at TIMESTAMPTZ;
+
+UPDATE announcements SET published_at = created_at;
+
+ALTER TABLE announcements ADD CONSTRAINT valid_announcement_status CHECK (
+    status IN ('draft', 'pending_approval', 'scheduled', 'published')
+);
+
+CREATE INDEX idx_announcements_scheduled ON announcements(publish_at) WHERE status = 'scheduled';
+
+-- ============================================
+-- Assign Permissions to System Roles
+-- ============================================
+
+-- System Admin gets ALL permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000001', id FROM permissions
+WHERE name = 'announcements:approve';
+
+-- School Admin approves announcements for their school
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT '00000000-0000-0000-0000-000000000002', id FROM permissions
+WHERE name = 'announcements:approve';
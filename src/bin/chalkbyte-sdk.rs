@@ -0,0 +1,116 @@
+//! Generates and verifies the TypeScript and Dart client models and the
+//! canonical OpenAPI document.
+//!
+//! See [`chalkbyte::sdk`] for what is checked.
+
+use std::path::{Path, PathBuf};
+
+use chalkbyte::sdk;
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "chalkbyte-sdk")]
+#[command(about = "Generate client SDK models from the Chalkbyte OpenAPI document", long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Print the canonical OpenAPI document as JSON, or write it to a file
+    Openapi {
+        /// File to write instead of printing, e.g. `openapi.json`
+        #[arg(short = 'o', long)]
+        out: Option<PathBuf>,
+    },
+    /// Write the TypeScript and Dart models
+    Generate {
+        /// Output directory
+        #[arg(short = 'o', long, default_value = "sdk")]
+        out: PathBuf,
+    },
+    /// Fail if the document is incomplete or the models in the output directory are stale
+    Check {
+        /// Directory of previously generated models to compare against
+        #[arg(short = 'o', long)]
+        out: Option<PathBuf>,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let doc = sdk::document();
+
+    match cli.command {
+        Commands::Openapi { out: None } => print!("{}", sdk::openapi_json(&doc)),
+        Commands::Openapi { out: Some(out) } => {
+            verify_document(&doc);
+            write(&out, &sdk::openapi_json(&doc));
+        }
+        Commands::Generate { out } => {
+            verify_document(&doc);
+            for artifact in sdk::artifacts(&doc) {
+                write(&out.join(artifact.path), &artifact.contents);
+            }
+        }
+        Commands::Check { out } => {
+            verify_document(&doc);
+            if let Some(out) = out {
+                verify_artifacts(&doc, &out);
+            }
+            println!("✅ Client models are up to date with the OpenAPI document");
+        }
+    }
+}
+
+fn verify_document(doc: &serde_json::Value) {
+    let problems = sdk::check(doc);
+    if problems.is_empty() {
+        return;
+    }
+    eprintln!("❌ The OpenAPI document is incomplete:");
+    for problem in &problems {
+        eprintln!("   {}", problem);
+    }
+    std::process::exit(1);
+}
+
+fn verify_artifacts(doc: &serde_json::Value, out: &Path) {
+    let stale: Vec<PathBuf> = sdk::artifacts(doc)
+        .into_iter()
+        .map(|artifact| (out.join(artifact.path), artifact.contents))
+        .filter(|(path, contents)| std::fs::read_to_string(path).ok().as_ref() != Some(contents))
+        .map(|(path, _)| path)
+        .collect();
+    if stale.is_empty() {
+        return;
+    }
+    eprintln!("❌ Client models are out of date:");
+    for path in &stale {
+        eprintln!("   {}", path.display());
+    }
+    eprintln!(
+        "   Regenerate with: cargo run --bin chalkbyte-sdk -- generate --out {}",
+        out.display()
+    );
+    std::process::exit(1);
+}
+
+fn write(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+        && let Err(e) = std::fs::create_dir_all(parent)
+    {
+        fail(&format!("Failed to create {}: {}", parent.display(), e));
+    }
+    if let Err(e) = std::fs::write(path, contents) {
+        fail(&format!("Failed to write {}: {}", path.display(), e));
+    }
+    println!("✅ Wrote {}", path.display());
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("❌ {}", message);
+    std::process::exit(1);
+}
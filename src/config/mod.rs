@@ -7,36 +7,53 @@
 //!
 //! # Re-exported from `chalkbyte-config`
 //!
+//! - [`app`]: `ChalkbyteConfig`, every setting loaded from a config file
+//!   with environment overrides
+//! - [`source`]: Config file discovery and validation
 //! - [`cors`]: CORS (Cross-Origin Resource Sharing) configuration
+//! - [`docs`]: API documentation access configuration
 //! - [`email`]: Email/SMTP configuration for sending notifications
 //! - [`jwt`]: JWT authentication configuration
+//! - [`oidc`]: OpenID Connect (SSO) configuration
 //! - [`rate_limit`]: API rate limiting configuration
+//! - [`storage`]: File storage backend and school quota configuration
+//! - [`webauthn`]: WebAuthn (passkey) relying party configuration
 //!
 //! # Re-exported from `chalkbyte-db`
 //!
-//! - [`database`]: PostgreSQL database connection pool initialization
+//! - [`database`]: PostgreSQL connection pool configuration, initialization, and health checks
 //!
 //! # Example
 //!
 //! ```ignore
 //! use crate::config::jwt::JwtConfig;
-//! use crate::config::database::init_db_pool;
+//! use crate::config::database::{DbConfig, init_db_pool};
 //!
 //! // Load JWT config from environment
 //! let jwt_config = JwtConfig::from_env();
 //!
 //! // Initialize database pool
-//! let db = init_db_pool().await;
+//! let db = init_db_pool(&DbConfig::from_env()).await?;
 //! ```
 
 // Re-export from chalkbyte-config
+pub use chalkbyte_config::app;
 pub use chalkbyte_config::cors;
+pub use chalkbyte_config::docs;
 pub use chalkbyte_config::email;
 pub use chalkbyte_config::jwt;
+pub use chalkbyte_config::jwt_keys;
+pub use chalkbyte_config::oidc;
 pub use chalkbyte_config::rate_limit;
+pub use chalkbyte_config::source;
+pub use chalkbyte_config::storage;
+pub use chalkbyte_config::webauthn;
 
 // Re-export database from chalkbyte-db
 pub mod database {
     //! Database pool initialization re-exported from `chalkbyte-db`.
-    pub use chalkbyte_db::init_db_pool;
+    pub use chalkbyte_db::{
+        DbConfig, DbError, DbHealth, DbPools, TenantContext, UnitOfWork, check_health,
+        init_db_pool, init_db_pools, timed,
+    };
 }
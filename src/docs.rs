@@ -1,45 +1,259 @@
-use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use std::collections::HashSet;
+
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{Modify, OpenApi};
 
 use crate::modules::academic_sessions::model::{
     AcademicSession, AcademicSessionFilterParams, AcademicSessionWithStats,
     CreateAcademicSessionDto, PaginatedAcademicSessionsResponse, UpdateAcademicSessionDto,
 };
+use crate::modules::alumni::model::{
+    AlumniExportParams, AlumniFilterParams, Alumnus, AlumnusDetail, GraduateStudentsDto,
+    GraduateStudentsResponse, PaginatedAlumniResponse, UpdateAlumnusDto,
+};
+use crate::modules::announcements::model::{
+    Announcement, AnnouncementStatus, CreateAnnouncementDto, MarkNotificationsReadResponse,
+    Notification, NotificationFeed, PaginatedAnnouncementsResponse, ReviewAnnouncementDto,
+    UpdateAnnouncementDto,
+};
+use crate::modules::assessments::model::{
+    Assessment, AssessmentFilterParams, AssessmentKind, AssessmentScoreWithStudent,
+    CreateAssessmentDto, CreateSubjectDto, GradingComponent, GradingScheme,
+    PaginatedAssessmentScoresResponse, PaginatedAssessmentsResponse,
+    PaginatedStudentResultsResponse, PaginatedTermGradesResponse, RecordScoresDto,
+    RecordScoresResponse, ScoreEntryDto, SetGradingSchemeDto, StudentResult, StudentResultParams,
+    StudentTermGradeParams, Subject, SubjectQueryParams, TermGrade, TermGradeParams,
+    UpdateAssessmentDto,
+};
+use crate::modules::assets::model::{
+    Asset, AssetAssignment, AssetCategory, AssetCondition, AssetConditionLog, AssetCount,
+    AssetFilterParams, AssetStatus, AssetSummary, AssetSummaryParams, AssignAssetDto,
+    CreateAssetDto, PaginatedAssetsResponse, RecordConditionDto, UpdateAssetDto,
+};
+use crate::modules::attendance::model::{
+    AttendanceAnalyticsParams, AttendanceEntryDto, AttendanceQueryParams,
+    AttendanceRecordWithStudent, AttendanceSettings, AttendanceStatus, AttendanceTrendPoint,
+    ChronicAbsentee, ChronicAbsenteeParams, DayOfWeekAttendance, MarkAttendanceDto,
+    MarkAttendanceResponse, UpdateAttendanceSettingsDto,
+};
 use crate::modules::auth::controller::ErrorResponse;
 use crate::modules::auth::model::{
-    ForgotPasswordRequest, LoginRequest, LoginResponse, LoginUser, MessageResponse,
-    MfaRecoveryLoginRequest, MfaRequiredResponse, MfaVerifyLoginRequest, RefreshTokenRequest,
-    ResetPasswordRequest,
+    DeviceSession, ForgotPasswordRequest, LoginRequest, LoginResponse, LoginUser, LogoutRequest,
+    MessageResponse, MfaMethod, MfaPasskeyChallengeRequest, MfaPasskeyChallengeResponse,
+    MfaPasskeyLoginRequest, MfaRecoveryLoginRequest, MfaRequiredResponse, MfaVerifyLoginRequest,
+    RefreshTokenRequest, ResetPasswordRequest,
+};
+use crate::modules::auth::oidc::model::{
+    CreateOidcProviderDto, OidcCallbackParams, OidcLinkResponse, OidcProvider,
+    OidcProviderWithCallback, UpdateOidcProviderDto,
+};
+use crate::modules::billing::model::{
+    ArrearsExportParams, BalanceQueryParams, ChargeSource, CreateFeeStructureDto, FeeItem,
+    FeePayment, FeeStructure, FeeStructureDetail, FeeStructureFilterParams,
+    GenerateInvoicesResponse, Invoice, InvoiceDetail, InvoiceFilterParams, InvoiceStatus,
+    PaginatedFeeStructuresResponse, PaginatedInvoicesResponse, PaymentMethod, RecordPaymentDto,
+    SchoolBalance, StudentBalance, UninvoicedCharge,
+};
+use crate::modules::boarding::model::{
+    AllocateRoomDto, BoardingFeeLine, BoardingFeeQueryParams, CreateBoardingFeeLineDto,
+    CreateHostelDto, CreateHostelRoomDto, Hostel, HostelFilterParams, HostelGender, HostelRoom,
+    HostelRoomQueryParams, HostelRoomWithOccupancy, PaginatedHostelsResponse,
+    PaginatedRoomAllocationsResponse, RoomAllocation, RoomAllocationFilterParams,
+    RoomAllocationWithDetails, StudentBoardingFees, UpdateHostelDto, UpdateHostelRoomDto,
 };
 use crate::modules::branches::model::{
-    AssignStudentsToBranchDto, Branch, BranchFilterParams, BranchWithStats, CreateBranchDto,
-    MoveStudentToBranchDto, PaginatedBranchesResponse, UpdateBranchDto,
+    AssignBranchTeacherDto, AssignStudentsToBranchDto, Branch, BranchFilterParams, BranchTeacher,
+    BranchTeacherQueryParams, BranchWithStats, CreateBranchDto, MoveStudentToBranchDto,
+    PaginatedBranchesResponse, TeacherCapacity, UpdateBranchDto,
+};
+use crate::modules::broadcasts::model::{
+    Broadcast, BroadcastChannel, BroadcastFilterParams, BroadcastRecipient,
+    BroadcastRecipientFilterParams, BroadcastStatus, CreateBroadcastDto, DeliveryStatus, FeeStatus,
+    PaginatedBroadcastRecipientsResponse, PaginatedBroadcastsResponse, PublishingSettings,
+    ReviewBroadcastDto, UpdateBroadcastDto, UpdatePublishingSettingsDto,
+};
+use crate::modules::changes::model::{
+    ChangeEntityType, ChangeEvent, ChangeFeedParams, ChangeFeedResponse, ChangeOperation,
+};
+use crate::modules::clinic::model::{
+    AdministerMedicationDto, ClinicMedication, ClinicVisit, ClinicVisitDetail,
+    ClinicVisitFilterParams, ClinicVisitOutcome, ClinicVisitSummary, CreateClinicVisitDto,
+    GuardianNotificationMethod, NotifyGuardianDto, PaginatedClinicVisitsResponse,
+    StudentMedicalProfile, UpdateClinicVisitDto, UpsertMedicalProfileDto,
+};
+use crate::modules::custom_fields::model::{
+    CreateCustomFieldDto, CustomFieldDefinition, CustomFieldEntity, CustomFieldFilterParams,
+    CustomFieldType, UpdateCustomFieldDto,
+};
+use crate::modules::dashboard::model::{
+    AssetSummaryLite, AttendanceTodaySummary, DashboardWidget, SchoolDashboard,
+    SchoolDashboardLite, SchoolOverviewLite, SchoolStats, StaffLeaveTodaySummary,
+    VisitorsTodayLite, WidgetFreshness, WidgetStatus, WidgetStatusLite,
+};
+use crate::modules::discipline::model::{
+    CategoryCount, CreateIncidentDto, DisciplineIncident, DisciplineIncidentSummary,
+    IncidentCategory, IncidentFilterParams, IncidentSeverity, IncidentTrendParams, IncidentTrends,
+    MonthlyIncidentCount, NotifyGuardiansResponse, PaginatedIncidentsResponse, SeverityCount,
+    StudentIncidentHistory, UpdateIncidentDto,
+};
+use crate::modules::email_branding::model::{
+    EmailPreview, EmailPreviewRequest, EmailTemplateKind, SchoolEmailBranding,
+    UpdateEmailBrandingDto,
+};
+use crate::modules::emails::model::{
+    CreateEmailSuppressionDto, EmailSuppression, OutboundEmail, OutboundEmailStatus,
+    PaginatedEmailSuppressionsResponse, PaginatedOutboundEmailsResponse, SuppressionReason,
+};
+use crate::modules::exams::model::{
+    AssignInvigilatorDto, ExamInvigilator, ExamRoom, ExamRoomDto, ExamSeat, ExamSitting,
+    GenerateSeatingPlanDto, ScheduleSittingDto, SeatingChartFormat, SeatingPlan,
+};
+use crate::modules::feature_flags::model::{
+    CreateFeatureFlagDto, EffectiveFeatureFlags, FeatureFlag, FeatureFlagWithOverrides,
+    SchoolFeatureFlag, SetSchoolFeatureFlagDto, UpdateFeatureFlagDto,
+};
+use crate::modules::groups::model::{
+    AddGroupSchoolDto, AssignGroupAdminDto, CreateSchoolGroupDto, GroupAdmin, GroupReport,
+    GroupReportParams, GroupSchool, GroupSchoolReport, GroupUser, GroupUserFilterParams,
+    PaginatedGroupUsersResponse, SchoolGroup, UpdateSchoolGroupDto,
+};
+use crate::modules::guardians::model::{
+    GuardianAccountConflict, GuardianAccountRun, GuardianAccountRunStatus, GuardianConflictKind,
+    PaginatedGuardianAccountRunsResponse, StartGuardianAccountRunDto,
+};
+use crate::modules::imports::model::{
+    ImportDataset, ImportIssue, ImportIssueCode, ImportIssueSeverity, ImportPhaseResult,
+    ImportPhaseStatus, ImportRowCounts, ImportValidationForm, ImportValidationReport,
+    SchoolImportReport,
+};
+use crate::modules::interop::model::{
+    OneRosterExportParams, OneRosterFile, OneRosterImportForm, OneRosterImportParams,
+    OneRosterImportReport, OneRosterIssue,
+};
+use crate::modules::jobs::model::{JobHealth, JobHealthStatus, JobsHealthResponse};
+use crate::modules::kiosk::model::{
+    KioskAttendanceDto, KioskDevice, KioskDeviceQueryParams, KioskRosterStudent,
+    KioskSessionRequest, KioskSessionResponse, RegisterKioskDeviceDto, RegisteredKioskDevice,
+    SetKioskPinDto,
 };
 use crate::modules::levels::model::{
-    AssignStudentsToLevelDto, BulkAssignResponse, CreateLevelDto, Level, LevelFilterParams,
-    LevelWithStats, MoveStudentToLevelDto, PaginatedLevelsResponse, UpdateLevelDto,
+    AssignStudentsToLevelDto, BulkAssignResponse, CreateLevelDto, GenerateLevelsDto,
+    GenerateLevelsResponse, GeneratedLevel, Level, LevelFilterParams, LevelPromotionMapping,
+    LevelPromotionSummary, LevelWithStats, MoveStudentToLevelDto, NamingTemplates,
+    PaginatedLevelsResponse, PromoteStudentsDto, PromotionOutcome, PromotionReport,
+    StudentPromotion, UpdateLevelDto, UpdateNamingTemplatesDto,
+};
+use crate::modules::library::model::{
+    BookFilterParams, BorrowingHistory, CheckoutDto, CopyStatus, CreateBookDto, CreateCopyDto,
+    LibraryBook, LibraryBookWithAvailability, LibraryCopy, LibraryFineCharge,
+    LibraryFineFilterParams, LibraryLoan, LibraryLoanWithDetails, LibraryPolicy,
+    LibraryPolicyQueryParams, LoanFilterParams, PaginatedBooksResponse, PaginatedLoansResponse,
+    ReturnLoanResponse, UpdateBookDto, UpdateCopyDto, UpdateLibraryPolicyDto,
+};
+use crate::modules::messaging::model::{
+    AttachmentUploadForm, Conversation, ConversationDetail, ConversationFilterParams,
+    ConversationKind, ConversationMessage, ConversationParticipant, ConversationSummary,
+    MessageAttachment, PaginatedConversationsResponse, PaginatedMessagesResponse, SendMessageDto,
+    StartConversationDto, UnreadMessageCounts,
 };
 use crate::modules::mfa::model::{
-    DisableMfaRequest, EnableMfaResponse, MfaStatusResponse, RegenerateMfaRecoveryCodesResponse,
-    VerifyMfaRequest,
+    DisableMfaRequest, EnableMfaResponse, FinishPasskeyRegistrationRequest, MfaStatusResponse,
+    PasskeyCredential, RegenerateMfaRecoveryCodesResponse, RemovePasskeyRequest,
+    StartPasskeyRegistrationResponse, UpdatePreferredMfaMethodRequest, VerifyMfaRequest,
+};
+use crate::modules::performance::model::{SlowEndpoint, SlowEndpointsResponse};
+use crate::modules::profiles::model::{
+    LinkProfileDto, LinkedProfile, MyProfilesResponse, NotificationPreferences, SwitchProfileDto,
+    UpdateNotificationPreferencesDto,
+};
+use crate::modules::public_directory::model::{
+    PublicProfileSettings, PublicSchoolProfile, UpdatePublicProfileDto,
+};
+use crate::modules::reports::model::{
+    EnrollmentMovement, SessionAttendance, SessionGrades, SessionReport, SessionReportFormat,
+    SessionReportStatus, SessionSummary, StaffChanges, TermGrades,
+};
+use crate::modules::results::model::{
+    ResultAction, ResultStatus, SkippedResult, TermResult, TermResultTransition,
+    TransitionResultsDto, TransitionResultsResponse,
+};
+use crate::modules::retention::model::{
+    PaginatedPurgeRunsResponse, PurgePreview, PurgeRun, PurgeRunFilterParams, RetentionCategory,
+    RetentionPolicy, UpdateRetentionPolicyDto,
 };
 use crate::modules::roles::model::{
-    AssignPermissionsDto, AssignRoleToUserDto, CreateRoleDto, PaginatedPermissionsResponse,
-    PaginatedRolesResponse, Permission, PermissionFilterParams, Role, RoleAssignmentResponse,
-    RoleFilterParams, RoleWithPermissions, UpdateRoleDto, UserRole,
+    AssignPermissionsDto, AssignRoleToUserDto, BulkAssignRoleDto, BulkRoleAssignmentResponse,
+    CreateRoleDto, FailedRoleAssignment, PaginatedPermissionsResponse, PaginatedRolesResponse,
+    Permission, PermissionFilterParams, Role, RoleAssignmentResponse, RoleFilterParams,
+    RoleWithPermissions, UpdateRoleDto, UserRole,
+};
+use crate::modules::saved_views::model::{
+    CreateSavedViewDto, SavedView, SavedViewFilterParams, SavedViewResource, SortOrder,
+    UpdateSavedViewDto,
+};
+use crate::modules::staff_leave::model::{
+    ApproveStaffLeaveDto, CreateStaffLeaveDto, LeaveCalendarEntry, LeaveCalendarParams,
+    LeaveStatus, LeaveType, PaginatedStaffLeaveResponse, RejectStaffLeaveDto,
+    StaffLeaveFilterParams, StaffLeaveRequest, StaffLeaveWithStaff, SubstituteCandidate,
+};
+use crate::modules::storage::model::{
+    Avatar, AvatarUploadForm, DocumentKind, DocumentUploadForm, DownloadUrl, StorageUsage,
+    StudentDocument, UpdateStorageQuotaDto,
+};
+use crate::modules::student_cards::model::{
+    StudentCard, VerifiedStudentCard, VerifyStudentCardDto,
+};
+use crate::modules::students::model::{
+    CreateStudentDto, PaginatedStudentLitesResponse, Student, StudentImportForm,
+    StudentImportReport, StudentImportRowError, StudentListResponse, StudentLite,
+    StudentLiteListResponse, UpdateStudentDto,
+};
+use crate::modules::sync::model::{
+    CreateSyncConnectionDto, PaginatedSyncEntitiesResponse, PaginatedSyncRunsResponse,
+    SyncConflictKind, SyncConnection, SyncConnectionFilterParams, SyncEntity,
+    SyncEntityFilterParams, SyncEntityStatus, SyncEntityType, SyncProviderKind, SyncRun,
+    SyncRunStatus, SyncRunTrigger, UpdateSyncConnectionDto,
 };
-use crate::modules::students::model::{CreateStudentDto, Student, UpdateStudentDto};
 use crate::modules::terms::model::{
     CreateTermDto, PaginatedTermsResponse, Term, TermFilterParams, TermWithSessionInfo,
     UpdateTermDto,
 };
+use crate::modules::traces::model::{RequestLog, RequestTrace};
+use crate::modules::transport::model::{
+    AssignRouteDto, CreateRouteStopDto, CreateTransportRouteDto, CreateVehicleDto,
+    GenerateTransportFeesResponse, ManifestStop, ManifestStudent,
+    PaginatedRouteAssignmentsResponse, PaginatedTransportRoutesResponse, RouteAssignment,
+    RouteAssignmentFilterParams, RouteAssignmentWithDetails, RouteManifest, RouteStop,
+    TransportFeeCharge, TransportFeeFilterParams, TransportRoute, TransportRouteFilterParams,
+    TransportTermParams, UpdateTransportRouteDto, UpdateVehicleDto, Vehicle, VehicleQueryParams,
+};
+use crate::modules::trash::model::{
+    PaginatedTrashResponse, TrashFilterParams, TrashItem, TrashItemType,
+};
 use crate::modules::users::controller::ProfileResponse;
 use crate::modules::users::model::{
-    ChangePasswordDto, CreateSchoolDto, CreateUserDto, PaginatedSchoolsResponse,
-    PaginatedUsersResponse, School, SchoolFilterParams, SchoolFullInfo, UpdateProfileDto, User,
-    UserFilterParams,
+    BulkCreateUsersDto, BulkCreateUsersResponse, BulkItemStatus, BulkUserResult, ChangePasswordDto,
+    CreateSchoolDto, CreateUserDto, PaginatedSchoolsResponse, PaginatedUserLitesResponse,
+    PaginatedUsersResponse, School, SchoolFilterParams, SchoolFullInfo, SchoolSettings,
+    UpdateProfileDto, UpdateSchoolSettingsDto, UpdateUserCustomFieldsDto, User, UserFilterParams,
+    UserListResponse, UserLite, UserLiteListResponse,
+};
+use crate::modules::visitor_log::model::{
+    CheckInVisitorDto, CreateVisitorKioskKeyDto, CreatedVisitorKioskKey, DailyVisitorReport,
+    DailyVisitorReportParams, PaginatedVisitorLogsResponse, VisitPurpose, VisitPurposeCount,
+    VisitorKioskKey, VisitorKioskKeyQueryParams, VisitorLog, VisitorLogFilterParams,
+    VisitorLogWithHost,
+};
+use crate::modules::webhooks::model::{
+    CreateWebhookEndpointDto, PaginatedWebhookDeliveriesResponse, UpdateWebhookEndpointDto,
+    WebhookDelivery, WebhookDeliveryFilterParams, WebhookDeliveryStatus, WebhookEndpoint,
+    WebhookEndpointWithSecret, WebhookEvent,
+};
+use chalkbyte_core::versioning::ApiVersion;
+use chalkbyte_core::{
+    CursorMeta, CursorPaginationParams, ErrorCode, ExportFormat, FieldError, PaginationMeta,
+    PaginationParams,
 };
-use chalkbyte_core::{PaginationMeta, PaginationParams};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -47,22 +261,46 @@ use chalkbyte_core::{PaginationMeta, PaginationParams};
         crate::modules::auth::controller::login_user,
         crate::modules::auth::controller::verify_mfa_login,
         crate::modules::auth::controller::verify_mfa_recovery_login,
+        crate::modules::auth::controller::start_passkey_login,
+        crate::modules::auth::controller::verify_passkey_login,
         crate::modules::auth::controller::forgot_password,
         crate::modules::auth::controller::reset_password,
         crate::modules::auth::controller::refresh_token,
         crate::modules::auth::controller::logout,
+        crate::modules::auth::controller::logout_all,
+        crate::modules::auth::controller::get_sessions,
+        crate::modules::auth::controller::revoke_session,
+        crate::modules::auth::controller::get_jwks,
+        // Single sign-on
+        crate::modules::auth::oidc::controller::authorize,
+        crate::modules::auth::oidc::controller::link,
+        crate::modules::auth::oidc::controller::callback,
+        crate::modules::auth::oidc::controller::create_sso_provider,
+        crate::modules::auth::oidc::controller::get_sso_providers,
+        crate::modules::auth::oidc::controller::get_sso_provider,
+        crate::modules::auth::oidc::controller::update_sso_provider,
+        crate::modules::auth::oidc::controller::delete_sso_provider,
         crate::modules::mfa::controller::get_mfa_status,
         crate::modules::mfa::controller::enable_mfa,
         crate::modules::mfa::controller::verify_mfa,
         crate::modules::mfa::controller::disable_mfa,
         crate::modules::mfa::controller::regenerate_recovery_codes,
+        crate::modules::mfa::controller::list_passkeys,
+        crate::modules::mfa::controller::start_passkey_registration,
+        crate::modules::mfa::controller::finish_passkey_registration,
+        crate::modules::mfa::controller::remove_passkey,
+        crate::modules::mfa::controller::update_preferred_method,
         crate::modules::users::controller::create_user,
+        crate::modules::users::controller::create_users_bulk,
         crate::modules::users::controller::get_users,
+        crate::modules::users::controller::export_users,
         crate::modules::users::controller::get_profile,
         crate::modules::users::controller::update_profile,
         crate::modules::users::controller::change_password,
+        crate::modules::users::controller::update_user_custom_fields,
         crate::modules::schools::controller::create_school,
         crate::modules::schools::controller::get_all_schools,
+        crate::modules::schools::controller::export_schools,
         crate::modules::schools::controller::get_school,
         crate::modules::schools::controller::delete_school,
         crate::modules::schools::controller::upload_school_logo,
@@ -70,10 +308,29 @@ use chalkbyte_core::{PaginationMeta, PaginationParams};
         crate::modules::schools::controller::get_school_students,
         crate::modules::schools::controller::get_school_admins,
         crate::modules::schools::controller::get_school_full_info,
+        crate::modules::schools::controller::get_school_settings,
+        crate::modules::schools::controller::update_school_settings,
+        crate::modules::dashboard::controller::get_school_dashboard,
+        crate::modules::dashboard::controller::get_school_stats,
         crate::modules::schools::controller::get_school_levels,
         crate::modules::schools::controller::get_school_level_branches,
         crate::modules::students::controller::create_student,
         crate::modules::students::controller::get_students,
+        crate::modules::students::controller::export_students,
+        crate::modules::students::controller::import_students,
+        crate::modules::imports::controller::validate_import,
+        crate::modules::imports::controller::import_school,
+        crate::modules::interop::controller::export_oneroster,
+        crate::modules::interop::controller::import_oneroster,
+        crate::modules::sync::controller::create_sync_connection,
+        crate::modules::sync::controller::get_sync_connections,
+        crate::modules::sync::controller::get_sync_connection,
+        crate::modules::sync::controller::update_sync_connection,
+        crate::modules::sync::controller::delete_sync_connection,
+        crate::modules::sync::controller::queue_sync_run,
+        crate::modules::sync::controller::get_sync_runs,
+        crate::modules::sync::controller::get_sync_entities,
+        crate::modules::sync::controller::resolve_sync_entity,
         crate::modules::students::controller::get_student,
         crate::modules::students::controller::update_student,
         crate::modules::students::controller::delete_student,
@@ -86,6 +343,12 @@ use chalkbyte_core::{PaginationMeta, PaginationParams};
         crate::modules::levels::controller::get_students_in_level,
         crate::modules::levels::controller::move_student_to_level,
         crate::modules::levels::controller::remove_student_from_level,
+        crate::modules::levels::controller::get_naming_templates,
+        crate::modules::levels::controller::update_naming_templates,
+        crate::modules::levels::controller::generate_levels,
+        crate::modules::levels::controller::preview_promotion,
+        crate::modules::levels::controller::promote_students,
+        crate::modules::levels::controller::get_promotion_history,
         crate::modules::branches::controller::create_branch,
         crate::modules::branches::controller::get_branches,
         crate::modules::branches::controller::get_branch_by_id,
@@ -95,6 +358,9 @@ use chalkbyte_core::{PaginationMeta, PaginationParams};
         crate::modules::branches::controller::get_students_in_branch,
         crate::modules::branches::controller::move_student_to_branch,
         crate::modules::branches::controller::remove_student_from_branch,
+        crate::modules::branches::controller::assign_teacher_to_branch,
+        crate::modules::branches::controller::get_branch_teachers,
+        crate::modules::branches::controller::remove_teacher_from_branch,
         crate::modules::roles::controller::get_permissions,
         crate::modules::roles::controller::get_permission_by_id,
         crate::modules::roles::controller::create_role,
@@ -104,6 +370,7 @@ use chalkbyte_core::{PaginationMeta, PaginationParams};
         crate::modules::roles::controller::delete_role,
         crate::modules::roles::controller::assign_permissions,
         crate::modules::roles::controller::remove_permission,
+        crate::modules::roles::controller::assign_role_to_users_bulk,
         crate::modules::roles::controller::assign_role_to_user,
         crate::modules::roles::controller::remove_role_from_user,
         crate::modules::roles::controller::get_user_roles,
@@ -125,12 +392,329 @@ use chalkbyte_core::{PaginationMeta, PaginationParams};
         crate::modules::terms::controller::update_term,
         crate::modules::terms::controller::delete_term,
         crate::modules::terms::controller::set_current_term,
+        // Staff Leave
+        crate::modules::staff_leave::controller::create_leave_request,
+        crate::modules::staff_leave::controller::get_leave_requests,
+        crate::modules::staff_leave::controller::get_my_leave_requests,
+        crate::modules::staff_leave::controller::get_leave_calendar,
+        crate::modules::staff_leave::controller::get_leave_request_by_id,
+        crate::modules::staff_leave::controller::approve_leave_request,
+        crate::modules::staff_leave::controller::reject_leave_request,
+        crate::modules::staff_leave::controller::cancel_leave_request,
+        crate::modules::staff_leave::controller::get_substitute_candidates,
+        // Assets
+        crate::modules::assets::controller::create_asset,
+        crate::modules::assets::controller::get_assets,
+        crate::modules::assets::controller::get_asset_summary,
+        crate::modules::assets::controller::get_asset_by_id,
+        crate::modules::assets::controller::update_asset,
+        crate::modules::assets::controller::delete_asset,
+        crate::modules::assets::controller::assign_asset,
+        crate::modules::assets::controller::unassign_asset,
+        crate::modules::assets::controller::get_assignment_history,
+        crate::modules::assets::controller::record_condition,
+        crate::modules::assets::controller::get_condition_history,
+        // Billing
+        crate::modules::billing::controller::create_fee_structure,
+        crate::modules::billing::controller::get_fee_structures,
+        crate::modules::billing::controller::get_fee_structure,
+        crate::modules::billing::controller::delete_fee_structure,
+        crate::modules::billing::controller::generate_invoices,
+        crate::modules::billing::controller::get_invoices,
+        crate::modules::billing::controller::get_invoice,
+        crate::modules::billing::controller::void_invoice,
+        crate::modules::billing::controller::record_payment,
+        crate::modules::billing::controller::get_student_balance,
+        crate::modules::billing::controller::get_school_balance,
+        crate::modules::billing::controller::export_arrears,
+        // Boarding
+        crate::modules::boarding::controller::create_hostel,
+        crate::modules::boarding::controller::get_hostels,
+        crate::modules::boarding::controller::get_hostel_by_id,
+        crate::modules::boarding::controller::update_hostel,
+        crate::modules::boarding::controller::delete_hostel,
+        crate::modules::boarding::controller::create_room,
+        crate::modules::boarding::controller::get_rooms,
+        crate::modules::boarding::controller::update_room,
+        crate::modules::boarding::controller::delete_room,
+        crate::modules::boarding::controller::allocate_room,
+        crate::modules::boarding::controller::get_allocations,
+        crate::modules::boarding::controller::delete_allocation,
+        crate::modules::boarding::controller::create_fee_line,
+        crate::modules::boarding::controller::get_fee_lines,
+        crate::modules::boarding::controller::delete_fee_line,
+        crate::modules::boarding::controller::get_student_fees,
+        // Transport
+        crate::modules::transport::controller::create_vehicle,
+        crate::modules::transport::controller::get_vehicles,
+        crate::modules::transport::controller::update_vehicle,
+        crate::modules::transport::controller::delete_vehicle,
+        crate::modules::transport::controller::create_route,
+        crate::modules::transport::controller::get_routes,
+        crate::modules::transport::controller::get_route_by_id,
+        crate::modules::transport::controller::update_route,
+        crate::modules::transport::controller::delete_route,
+        crate::modules::transport::controller::create_stop,
+        crate::modules::transport::controller::get_stops,
+        crate::modules::transport::controller::delete_stop,
+        crate::modules::transport::controller::get_route_manifest,
+        crate::modules::transport::controller::generate_fees,
+        crate::modules::transport::controller::assign_route,
+        crate::modules::transport::controller::get_assignments,
+        crate::modules::transport::controller::delete_assignment,
+        crate::modules::transport::controller::get_fee_charges,
+        crate::modules::transport::controller::get_my_routes,
+        crate::modules::transport::controller::get_my_manifest,
+        // Visitors
+        crate::modules::visitor_log::controller::check_in_visitor,
+        crate::modules::visitor_log::controller::get_visits,
+        crate::modules::visitor_log::controller::get_visit_by_id,
+        crate::modules::visitor_log::controller::check_out_visitor,
+        crate::modules::visitor_log::controller::delete_visit,
+        crate::modules::visitor_log::controller::get_daily_report,
+        crate::modules::visitor_log::controller::create_kiosk_key,
+        crate::modules::visitor_log::controller::get_kiosk_keys,
+        crate::modules::visitor_log::controller::revoke_kiosk_key,
+        crate::modules::visitor_log::controller::kiosk_check_in,
+        crate::modules::visitor_log::controller::kiosk_check_out,
+        // Clinic
+        crate::modules::clinic::controller::create_visit,
+        crate::modules::clinic::controller::get_visits,
+        crate::modules::clinic::controller::get_visit_by_id,
+        crate::modules::clinic::controller::update_visit,
+        crate::modules::clinic::controller::delete_visit,
+        crate::modules::clinic::controller::add_medication,
+        crate::modules::clinic::controller::notify_guardian,
+        crate::modules::clinic::controller::get_medical_profile,
+        crate::modules::clinic::controller::upsert_medical_profile,
+        // Discipline
+        crate::modules::discipline::controller::create_incident,
+        crate::modules::discipline::controller::get_incidents,
+        crate::modules::discipline::controller::get_incident,
+        crate::modules::discipline::controller::update_incident,
+        crate::modules::discipline::controller::delete_incident,
+        crate::modules::discipline::controller::notify_guardians,
+        crate::modules::discipline::controller::get_student_history,
+        crate::modules::discipline::controller::get_trends,
+        // Library
+        crate::modules::library::controller::create_book,
+        crate::modules::library::controller::get_books,
+        crate::modules::library::controller::get_book_by_id,
+        crate::modules::library::controller::update_book,
+        crate::modules::library::controller::delete_book,
+        crate::modules::library::controller::add_copy,
+        crate::modules::library::controller::get_copies,
+        crate::modules::library::controller::update_copy,
+        crate::modules::library::controller::delete_copy,
+        crate::modules::library::controller::get_policy,
+        crate::modules::library::controller::update_policy,
+        crate::modules::library::controller::checkout,
+        crate::modules::library::controller::get_loans,
+        crate::modules::library::controller::return_loan,
+        crate::modules::library::controller::get_fines,
+        crate::modules::library::controller::get_borrowing_history,
+        // Alumni
+        crate::modules::alumni::controller::graduate_students,
+        crate::modules::alumni::controller::get_alumni,
+        crate::modules::alumni::controller::export_mailing_list,
+        crate::modules::alumni::controller::get_alumnus_by_id,
+        crate::modules::alumni::controller::update_alumnus,
+        crate::modules::alumni::controller::delete_alumnus,
+        // Custom Fields
+        crate::modules::custom_fields::controller::create_custom_field,
+        crate::modules::custom_fields::controller::get_custom_fields,
+        crate::modules::custom_fields::controller::get_custom_field_by_id,
+        crate::modules::custom_fields::controller::update_custom_field,
+        crate::modules::custom_fields::controller::delete_custom_field,
+        // Saved Views
+        crate::modules::saved_views::controller::create_saved_view,
+        crate::modules::saved_views::controller::get_saved_views,
+        crate::modules::saved_views::controller::get_saved_view_by_id,
+        crate::modules::saved_views::controller::update_saved_view,
+        crate::modules::saved_views::controller::delete_saved_view,
+        // Profiles
+        crate::modules::profiles::controller::get_linked_profiles,
+        crate::modules::profiles::controller::link_profile,
+        crate::modules::profiles::controller::unlink_profile,
+        crate::modules::profiles::controller::get_my_profiles,
+        crate::modules::profiles::controller::get_notification_preferences,
+        crate::modules::profiles::controller::update_notification_preferences,
+        crate::modules::profiles::controller::switch_profile,
+        // Recycle Bin
+        crate::modules::trash::controller::get_trash,
+        crate::modules::trash::controller::restore_trash_item,
+        crate::modules::trash::controller::purge_trash_item,
+        // Retention
+        crate::modules::retention::controller::get_retention_policies,
+        crate::modules::retention::controller::update_retention_policy,
+        crate::modules::retention::controller::reset_retention_policy,
+        crate::modules::retention::controller::get_purge_preview,
+        crate::modules::retention::controller::get_purge_runs,
+        crate::modules::retention::controller::run_purge,
+        // Traces
+        crate::modules::traces::controller::get_request_trace,
+        // Jobs
+        crate::modules::jobs::controller::get_jobs_health,
+        crate::modules::performance::controller::get_slow_endpoints,
+        // Feature Flags
+        crate::modules::feature_flags::controller::get_my_feature_flags,
+        crate::modules::feature_flags::controller::get_feature_flags,
+        crate::modules::feature_flags::controller::create_feature_flag,
+        crate::modules::feature_flags::controller::get_feature_flag,
+        crate::modules::feature_flags::controller::update_feature_flag,
+        crate::modules::feature_flags::controller::delete_feature_flag,
+        crate::modules::feature_flags::controller::set_school_feature_flag,
+        crate::modules::feature_flags::controller::delete_school_feature_flag,
+        // Attendance
+        crate::modules::attendance::controller::mark_attendance,
+        crate::modules::attendance::controller::get_attendance,
+        crate::modules::attendance::controller::get_attendance_trends,
+        crate::modules::attendance::controller::get_chronic_absentees,
+        crate::modules::attendance::controller::get_day_of_week_patterns,
+        crate::modules::attendance::controller::get_attendance_settings,
+        crate::modules::attendance::controller::update_attendance_settings,
+        // Attendance Kiosk
+        crate::modules::kiosk::controller::register_kiosk_device,
+        crate::modules::kiosk::controller::get_kiosk_devices,
+        crate::modules::kiosk::controller::revoke_kiosk_device,
+        crate::modules::kiosk::controller::set_kiosk_pin,
+        crate::modules::kiosk::controller::start_kiosk_session,
+        crate::modules::kiosk::controller::get_kiosk_roster,
+        crate::modules::kiosk::controller::mark_kiosk_attendance,
+        crate::modules::kiosk::controller::scan_student_card,
+        // Assessments
+        crate::modules::assessments::controller::create_subject,
+        crate::modules::assessments::controller::get_subjects,
+        crate::modules::assessments::controller::delete_subject,
+        crate::modules::assessments::controller::create_assessment,
+        crate::modules::assessments::controller::get_assessments,
+        crate::modules::assessments::controller::get_assessment,
+        crate::modules::assessments::controller::update_assessment,
+        crate::modules::assessments::controller::delete_assessment,
+        crate::modules::assessments::controller::record_scores,
+        crate::modules::assessments::controller::finalize_scores,
+        crate::modules::assessments::controller::get_assessment_scores,
+        crate::modules::assessments::controller::get_term_grades,
+        crate::modules::assessments::controller::get_grading_schemes,
+        crate::modules::assessments::controller::set_grading_scheme,
+        crate::modules::assessments::controller::delete_grading_scheme,
+        crate::modules::assessments::controller::get_my_results,
+        crate::modules::assessments::controller::get_my_term_grades,
+        crate::modules::exams::controller::generate_seating_plan,
+        crate::modules::exams::controller::get_seating_plan,
+        crate::modules::exams::controller::print_room_list,
+        crate::modules::exams::controller::schedule_sitting,
+        crate::modules::exams::controller::get_sitting,
+        crate::modules::exams::controller::delete_sitting,
+        crate::modules::exams::controller::assign_invigilator,
+        crate::modules::exams::controller::remove_invigilator,
+        crate::modules::results::controller::get_results,
+        crate::modules::results::controller::transition_results,
+        crate::modules::results::controller::get_result_history,
+        // Storage
+        crate::modules::storage::controller::upload_my_avatar,
+        crate::modules::storage::controller::get_my_avatar,
+        crate::modules::storage::controller::delete_my_avatar,
+        crate::modules::storage::controller::get_user_avatar,
+        crate::modules::storage::controller::upload_student_document,
+        crate::modules::storage::controller::list_student_documents,
+        crate::modules::storage::controller::get_student_document_download,
+        crate::modules::storage::controller::delete_student_document,
+        crate::modules::storage::controller::get_storage_usage,
+        crate::modules::storage::controller::update_storage_quota,
+        crate::modules::storage::controller::download_file,
+        // Student Cards
+        crate::modules::student_cards::controller::get_student_card,
+        crate::modules::student_cards::controller::verify_student_card,
+        // Public Directory
+        crate::modules::public_directory::controller::get_public_school_profile,
+        crate::modules::public_directory::controller::get_public_profile_settings,
+        crate::modules::public_directory::controller::update_public_profile_settings,
+        // Email Branding
+        crate::modules::email_branding::controller::get_email_branding,
+        crate::modules::email_branding::controller::update_email_branding,
+        crate::modules::email_branding::controller::preview_email,
+        // Emails
+        crate::modules::emails::controller::get_emails,
+        crate::modules::emails::controller::get_email_suppressions,
+        crate::modules::emails::controller::create_email_suppression,
+        crate::modules::emails::controller::delete_email_suppression,
+        // Announcements
+        crate::modules::announcements::controller::create_announcement,
+        crate::modules::announcements::controller::get_announcements,
+        crate::modules::announcements::controller::get_announcement,
+        crate::modules::announcements::controller::update_announcement,
+        crate::modules::announcements::controller::submit_announcement,
+        crate::modules::announcements::controller::approve_announcement,
+        crate::modules::announcements::controller::reject_announcement,
+        crate::modules::announcements::controller::delete_announcement,
+        crate::modules::announcements::controller::get_notifications,
+        crate::modules::announcements::controller::mark_notification_read,
+        crate::modules::announcements::controller::mark_all_notifications_read,
+        // Messaging
+        crate::modules::messaging::controller::start_conversation,
+        crate::modules::messaging::controller::get_conversations,
+        crate::modules::messaging::controller::get_conversation,
+        crate::modules::messaging::controller::get_messages,
+        crate::modules::messaging::controller::send_message,
+        crate::modules::messaging::controller::send_attachment,
+        crate::modules::messaging::controller::mark_read,
+        crate::modules::messaging::controller::get_unread_counts,
+        crate::modules::messaging::controller::get_attachment_download,
+        // Broadcasts
+        crate::modules::broadcasts::controller::create_broadcast,
+        crate::modules::broadcasts::controller::get_broadcasts,
+        crate::modules::broadcasts::controller::get_broadcast,
+        crate::modules::broadcasts::controller::get_broadcast_recipients,
+        crate::modules::broadcasts::controller::update_broadcast,
+        crate::modules::broadcasts::controller::submit_broadcast,
+        crate::modules::broadcasts::controller::approve_broadcast,
+        crate::modules::broadcasts::controller::reject_broadcast,
+        crate::modules::broadcasts::controller::get_publishing_settings,
+        crate::modules::broadcasts::controller::update_publishing_settings,
+        // Changes
+        crate::modules::changes::controller::get_changes,
+        // Webhooks
+        crate::modules::webhooks::controller::create_webhook,
+        crate::modules::webhooks::controller::get_webhooks,
+        crate::modules::webhooks::controller::get_webhook,
+        crate::modules::webhooks::controller::update_webhook,
+        crate::modules::webhooks::controller::rotate_webhook_secret,
+        crate::modules::webhooks::controller::delete_webhook,
+        crate::modules::webhooks::controller::get_webhook_deliveries,
+        // Guardians
+        crate::modules::guardians::controller::start_guardian_account_run,
+        crate::modules::guardians::controller::get_guardian_account_runs,
+        crate::modules::guardians::controller::get_guardian_account_run,
+        crate::modules::guardians::controller::get_guardian_account_run_conflicts,
+        // Reports
+        crate::modules::reports::controller::get_session_summary_report,
+        // School groups
+        crate::modules::groups::controller::create_group,
+        crate::modules::groups::controller::list_groups,
+        crate::modules::groups::controller::get_group,
+        crate::modules::groups::controller::update_group,
+        crate::modules::groups::controller::delete_group,
+        crate::modules::groups::controller::list_group_schools,
+        crate::modules::groups::controller::add_group_school,
+        crate::modules::groups::controller::remove_group_school,
+        crate::modules::groups::controller::list_group_admins,
+        crate::modules::groups::controller::assign_group_admin,
+        crate::modules::groups::controller::remove_group_admin,
+        crate::modules::groups::controller::list_group_users,
+        crate::modules::groups::controller::create_group_user,
+        crate::modules::groups::controller::get_group_report,
     ),
     components(
         schemas(
             User,
             CreateUserDto,
+            BulkCreateUsersDto,
+            BulkItemStatus,
+            BulkUserResult,
+            BulkCreateUsersResponse,
             UpdateProfileDto,
+            UpdateUserCustomFieldsDto,
             ChangePasswordDto,
             School,
             CreateSchoolDto,
@@ -138,29 +722,101 @@ use chalkbyte_core::{PaginationMeta, PaginationParams};
             LoginResponse,
             LoginUser,
             MfaRequiredResponse,
+            MfaMethod,
             MfaVerifyLoginRequest,
             MfaRecoveryLoginRequest,
+            MfaPasskeyChallengeRequest,
+            MfaPasskeyChallengeResponse,
+            MfaPasskeyLoginRequest,
             ForgotPasswordRequest,
             ResetPasswordRequest,
             RefreshTokenRequest,
+            LogoutRequest,
+            DeviceSession,
             MessageResponse,
             MfaStatusResponse,
             EnableMfaResponse,
             VerifyMfaRequest,
             DisableMfaRequest,
             RegenerateMfaRecoveryCodesResponse,
+            PasskeyCredential,
+            StartPasskeyRegistrationResponse,
+            FinishPasskeyRegistrationRequest,
+            RemovePasskeyRequest,
+            UpdatePreferredMfaMethodRequest,
             ProfileResponse,
             ErrorResponse,
+            ErrorCode,
+            FieldError,
             Student,
+            StudentListResponse,
+            StudentImportForm,
+            StudentImportReport,
+            StudentImportRowError,
+            ImportValidationForm,
+            ImportValidationReport,
+            ImportRowCounts,
+            ImportIssue,
+            ImportIssueCode,
+            ImportIssueSeverity,
+            ImportDataset,
+            SchoolImportReport,
+            ImportPhaseResult,
+            ImportPhaseStatus,
+            OneRosterExportParams,
+            OneRosterImportParams,
+            OneRosterImportForm,
+            OneRosterImportReport,
+            OneRosterIssue,
+            OneRosterFile,
+            SyncProviderKind,
+            SyncConnection,
+            CreateSyncConnectionDto,
+            UpdateSyncConnectionDto,
+            SyncConnectionFilterParams,
+            SyncRunTrigger,
+            SyncRunStatus,
+            SyncRun,
+            PaginatedSyncRunsResponse,
+            SyncEntityType,
+            SyncEntityStatus,
+            SyncConflictKind,
+            SyncEntity,
+            SyncEntityFilterParams,
+            PaginatedSyncEntitiesResponse,
+            StudentLite,
+            PaginatedStudentLitesResponse,
+            StudentLiteListResponse,
             CreateStudentDto,
             UpdateStudentDto,
             PaginationMeta,
             PaginationParams,
+            CursorPaginationParams,
             SchoolFilterParams,
             PaginatedSchoolsResponse,
             UserFilterParams,
             PaginatedUsersResponse,
+            UserListResponse,
+            UserLite,
+            PaginatedUserLitesResponse,
+            UserLiteListResponse,
+            CursorMeta,
+            ExportFormat,
             SchoolFullInfo,
+            SchoolSettings,
+            UpdateSchoolSettingsDto,
+            SchoolDashboard,
+            SchoolDashboardLite,
+            SchoolOverviewLite,
+            AssetSummaryLite,
+            VisitorsTodayLite,
+            WidgetStatusLite,
+            SchoolStats,
+            DashboardWidget,
+            WidgetStatus,
+            WidgetFreshness,
+            AttendanceTodaySummary,
+            StaffLeaveTodaySummary,
             Level,
             LevelWithStats,
             CreateLevelDto,
@@ -170,6 +826,17 @@ use chalkbyte_core::{PaginationMeta, PaginationParams};
             BulkAssignResponse,
             LevelFilterParams,
             PaginatedLevelsResponse,
+            NamingTemplates,
+            UpdateNamingTemplatesDto,
+            GenerateLevelsDto,
+            GeneratedLevel,
+            GenerateLevelsResponse,
+            LevelPromotionMapping,
+            PromoteStudentsDto,
+            LevelPromotionSummary,
+            PromotionReport,
+            PromotionOutcome,
+            StudentPromotion,
             Branch,
             BranchWithStats,
             CreateBranchDto,
@@ -178,6 +845,10 @@ use chalkbyte_core::{PaginationMeta, PaginationParams};
             MoveStudentToBranchDto,
             BranchFilterParams,
             PaginatedBranchesResponse,
+            TeacherCapacity,
+            BranchTeacher,
+            AssignBranchTeacherDto,
+            BranchTeacherQueryParams,
             Permission,
             Role,
             RoleWithPermissions,
@@ -191,6 +862,9 @@ use chalkbyte_core::{PaginationMeta, PaginationParams};
             PaginatedRolesResponse,
             PaginatedPermissionsResponse,
             RoleAssignmentResponse,
+            BulkAssignRoleDto,
+            BulkRoleAssignmentResponse,
+            FailedRoleAssignment,
             // Academic Sessions
             AcademicSession,
             AcademicSessionWithStats,
@@ -205,20 +879,480 @@ use chalkbyte_core::{PaginationMeta, PaginationParams};
             UpdateTermDto,
             TermFilterParams,
             PaginatedTermsResponse,
+            // Staff Leave
+            LeaveType,
+            LeaveStatus,
+            StaffLeaveRequest,
+            StaffLeaveWithStaff,
+            CreateStaffLeaveDto,
+            ApproveStaffLeaveDto,
+            RejectStaffLeaveDto,
+            StaffLeaveFilterParams,
+            PaginatedStaffLeaveResponse,
+            LeaveCalendarParams,
+            LeaveCalendarEntry,
+            SubstituteCandidate,
+            // Assets
+            AssetCategory,
+            AssetCondition,
+            AssetStatus,
+            Asset,
+            CreateAssetDto,
+            UpdateAssetDto,
+            AssignAssetDto,
+            RecordConditionDto,
+            AssetAssignment,
+            AssetConditionLog,
+            AssetFilterParams,
+            PaginatedAssetsResponse,
+            AssetSummaryParams,
+            AssetCount,
+            AssetSummary,
+            // Billing
+            InvoiceStatus,
+            PaymentMethod,
+            FeeItem,
+            FeeStructure,
+            FeeStructureDetail,
+            CreateFeeStructureDto,
+            FeeStructureFilterParams,
+            PaginatedFeeStructuresResponse,
+            GenerateInvoicesResponse,
+            Invoice,
+            InvoiceDetail,
+            InvoiceFilterParams,
+            PaginatedInvoicesResponse,
+            FeePayment,
+            RecordPaymentDto,
+            BalanceQueryParams,
+            StudentBalance,
+            UninvoicedCharge,
+            ChargeSource,
+            SchoolBalance,
+            ArrearsExportParams,
+            // Boarding
+            HostelGender,
+            Hostel,
+            CreateHostelDto,
+            UpdateHostelDto,
+            HostelFilterParams,
+            PaginatedHostelsResponse,
+            HostelRoom,
+            HostelRoomWithOccupancy,
+            CreateHostelRoomDto,
+            UpdateHostelRoomDto,
+            HostelRoomQueryParams,
+            RoomAllocation,
+            RoomAllocationWithDetails,
+            AllocateRoomDto,
+            RoomAllocationFilterParams,
+            PaginatedRoomAllocationsResponse,
+            BoardingFeeLine,
+            CreateBoardingFeeLineDto,
+            BoardingFeeQueryParams,
+            StudentBoardingFees,
+            // Transport
+            Vehicle,
+            CreateVehicleDto,
+            UpdateVehicleDto,
+            VehicleQueryParams,
+            TransportRoute,
+            CreateTransportRouteDto,
+            UpdateTransportRouteDto,
+            TransportRouteFilterParams,
+            PaginatedTransportRoutesResponse,
+            RouteStop,
+            CreateRouteStopDto,
+            RouteAssignment,
+            RouteAssignmentWithDetails,
+            AssignRouteDto,
+            RouteAssignmentFilterParams,
+            PaginatedRouteAssignmentsResponse,
+            TransportTermParams,
+            ManifestStudent,
+            ManifestStop,
+            RouteManifest,
+            TransportFeeCharge,
+            GenerateTransportFeesResponse,
+            TransportFeeFilterParams,
+            // Visitors
+            VisitPurpose,
+            VisitorLog,
+            VisitorLogWithHost,
+            CheckInVisitorDto,
+            VisitorLogFilterParams,
+            PaginatedVisitorLogsResponse,
+            DailyVisitorReportParams,
+            VisitPurposeCount,
+            DailyVisitorReport,
+            VisitorKioskKey,
+            CreateVisitorKioskKeyDto,
+            CreatedVisitorKioskKey,
+            VisitorKioskKeyQueryParams,
+            // Clinic
+            ClinicVisitOutcome,
+            GuardianNotificationMethod,
+            StudentMedicalProfile,
+            UpsertMedicalProfileDto,
+            ClinicVisit,
+            ClinicMedication,
+            ClinicVisitDetail,
+            ClinicVisitSummary,
+            AdministerMedicationDto,
+            CreateClinicVisitDto,
+            UpdateClinicVisitDto,
+            NotifyGuardianDto,
+            ClinicVisitFilterParams,
+            PaginatedClinicVisitsResponse,
+            // Discipline
+            IncidentCategory,
+            IncidentSeverity,
+            DisciplineIncident,
+            DisciplineIncidentSummary,
+            CreateIncidentDto,
+            UpdateIncidentDto,
+            NotifyGuardiansResponse,
+            IncidentFilterParams,
+            PaginatedIncidentsResponse,
+            StudentIncidentHistory,
+            IncidentTrendParams,
+            CategoryCount,
+            SeverityCount,
+            MonthlyIncidentCount,
+            IncidentTrends,
+            // Library
+            CopyStatus,
+            LibraryBook,
+            LibraryBookWithAvailability,
+            CreateBookDto,
+            UpdateBookDto,
+            BookFilterParams,
+            PaginatedBooksResponse,
+            LibraryCopy,
+            CreateCopyDto,
+            UpdateCopyDto,
+            LibraryPolicy,
+            UpdateLibraryPolicyDto,
+            LibraryPolicyQueryParams,
+            LibraryLoan,
+            LibraryLoanWithDetails,
+            CheckoutDto,
+            ReturnLoanResponse,
+            LoanFilterParams,
+            PaginatedLoansResponse,
+            LibraryFineCharge,
+            LibraryFineFilterParams,
+            BorrowingHistory,
+            // Alumni
+            Alumnus,
+            AlumnusDetail,
+            GraduateStudentsDto,
+            GraduateStudentsResponse,
+            UpdateAlumnusDto,
+            AlumniFilterParams,
+            PaginatedAlumniResponse,
+            AlumniExportParams,
+            // Custom Fields
+            CustomFieldDefinition,
+            CustomFieldEntity,
+            CustomFieldType,
+            CreateCustomFieldDto,
+            UpdateCustomFieldDto,
+            CustomFieldFilterParams,
+            // Saved Views
+            SavedView,
+            SavedViewResource,
+            SortOrder,
+            CreateSavedViewDto,
+            UpdateSavedViewDto,
+            SavedViewFilterParams,
+            // Profiles
+            LinkedProfile,
+            MyProfilesResponse,
+            LinkProfileDto,
+            SwitchProfileDto,
+            NotificationPreferences,
+            UpdateNotificationPreferencesDto,
+            // Recycle Bin
+            TrashItem,
+            TrashItemType,
+            TrashFilterParams,
+            PaginatedTrashResponse,
+            // Retention
+            RetentionCategory,
+            RetentionPolicy,
+            UpdateRetentionPolicyDto,
+            PurgePreview,
+            PurgeRun,
+            PurgeRunFilterParams,
+            PaginatedPurgeRunsResponse,
+            // Traces
+            RequestLog,
+            RequestTrace,
+            // Jobs
+            JobHealthStatus,
+            JobHealth,
+            JobsHealthResponse,
+            // Performance
+            SlowEndpoint,
+            SlowEndpointsResponse,
+            // Feature Flags
+            FeatureFlag,
+            SchoolFeatureFlag,
+            FeatureFlagWithOverrides,
+            CreateFeatureFlagDto,
+            UpdateFeatureFlagDto,
+            SetSchoolFeatureFlagDto,
+            EffectiveFeatureFlags,
+            // Attendance
+            AttendanceStatus,
+            AttendanceRecordWithStudent,
+            AttendanceEntryDto,
+            MarkAttendanceDto,
+            MarkAttendanceResponse,
+            AttendanceQueryParams,
+            AttendanceAnalyticsParams,
+            ChronicAbsenteeParams,
+            AttendanceTrendPoint,
+            ChronicAbsentee,
+            DayOfWeekAttendance,
+            AttendanceSettings,
+            UpdateAttendanceSettingsDto,
+            // Attendance Kiosk
+            KioskDevice,
+            RegisterKioskDeviceDto,
+            RegisteredKioskDevice,
+            KioskDeviceQueryParams,
+            SetKioskPinDto,
+            KioskSessionRequest,
+            KioskSessionResponse,
+            KioskRosterStudent,
+            KioskAttendanceDto,
+            // Assessments
+            AssessmentKind,
+            Subject,
+            CreateSubjectDto,
+            SubjectQueryParams,
+            Assessment,
+            CreateAssessmentDto,
+            UpdateAssessmentDto,
+            AssessmentFilterParams,
+            PaginatedAssessmentsResponse,
+            ScoreEntryDto,
+            RecordScoresDto,
+            RecordScoresResponse,
+            AssessmentScoreWithStudent,
+            PaginatedAssessmentScoresResponse,
+            TermGrade,
+            TermGradeParams,
+            PaginatedTermGradesResponse,
+            StudentResult,
+            StudentResultParams,
+            PaginatedStudentResultsResponse,
+            StudentTermGradeParams,
+            GradingComponent,
+            GradingScheme,
+            SetGradingSchemeDto,
+            // Exams
+            ExamRoomDto,
+            GenerateSeatingPlanDto,
+            ExamSeat,
+            ExamRoom,
+            SeatingPlan,
+            SeatingChartFormat,
+            ScheduleSittingDto,
+            ExamInvigilator,
+            ExamSitting,
+            AssignInvigilatorDto,
+            // Storage
+            DocumentKind,
+            StudentDocument,
+            DownloadUrl,
+            Avatar,
+            AvatarUploadForm,
+            DocumentUploadForm,
+            StorageUsage,
+            UpdateStorageQuotaDto,
+            // Results
+            ResultStatus,
+            ResultAction,
+            TermResult,
+            TransitionResultsDto,
+            SkippedResult,
+            TransitionResultsResponse,
+            TermResultTransition,
+            // Student Cards
+            StudentCard,
+            VerifyStudentCardDto,
+            VerifiedStudentCard,
+            // Public Directory
+            PublicSchoolProfile,
+            PublicProfileSettings,
+            UpdatePublicProfileDto,
+            // Email Branding
+            SchoolEmailBranding,
+            UpdateEmailBrandingDto,
+            EmailTemplateKind,
+            EmailPreviewRequest,
+            EmailPreview,
+            // Emails
+            OutboundEmail,
+            OutboundEmailStatus,
+            PaginatedOutboundEmailsResponse,
+            EmailSuppression,
+            SuppressionReason,
+            CreateEmailSuppressionDto,
+            PaginatedEmailSuppressionsResponse,
+            // Announcements
+            Announcement,
+            AnnouncementStatus,
+            CreateAnnouncementDto,
+            UpdateAnnouncementDto,
+            ReviewAnnouncementDto,
+            PaginatedAnnouncementsResponse,
+            Notification,
+            NotificationFeed,
+            MarkNotificationsReadResponse,
+            // Messaging
+            ConversationKind,
+            Conversation,
+            ConversationParticipant,
+            ConversationDetail,
+            ConversationSummary,
+            StartConversationDto,
+            SendMessageDto,
+            AttachmentUploadForm,
+            MessageAttachment,
+            ConversationMessage,
+            ConversationFilterParams,
+            PaginatedConversationsResponse,
+            PaginatedMessagesResponse,
+            UnreadMessageCounts,
+            // Broadcasts
+            Broadcast,
+            BroadcastChannel,
+            FeeStatus,
+            BroadcastStatus,
+            BroadcastRecipient,
+            DeliveryStatus,
+            CreateBroadcastDto,
+            UpdateBroadcastDto,
+            ReviewBroadcastDto,
+            PublishingSettings,
+            UpdatePublishingSettingsDto,
+            BroadcastFilterParams,
+            BroadcastRecipientFilterParams,
+            PaginatedBroadcastsResponse,
+            PaginatedBroadcastRecipientsResponse,
+            // Changes
+            ChangeEvent,
+            ChangeEntityType,
+            ChangeOperation,
+            ChangeFeedParams,
+            ChangeFeedResponse,
+            // Webhooks
+            WebhookEndpoint,
+            WebhookEndpointWithSecret,
+            WebhookEvent,
+            CreateWebhookEndpointDto,
+            UpdateWebhookEndpointDto,
+            WebhookDelivery,
+            WebhookDeliveryStatus,
+            WebhookDeliveryFilterParams,
+            PaginatedWebhookDeliveriesResponse,
+            // Guardians
+            GuardianAccountRun,
+            GuardianAccountRunStatus,
+            StartGuardianAccountRunDto,
+            GuardianAccountConflict,
+            GuardianConflictKind,
+            PaginatedGuardianAccountRunsResponse,
+            // Reports
+            SessionReport,
+            SessionReportStatus,
+            SessionReportFormat,
+            SessionSummary,
+            EnrollmentMovement,
+            StaffChanges,
+            SessionAttendance,
+            SessionGrades,
+            TermGrades,
+            // Single sign-on
+            OidcProvider,
+            OidcProviderWithCallback,
+            CreateOidcProviderDto,
+            UpdateOidcProviderDto,
+            OidcCallbackParams,
+            OidcLinkResponse,
+            // School groups
+            SchoolGroup,
+            CreateSchoolGroupDto,
+            UpdateSchoolGroupDto,
+            AddGroupSchoolDto,
+            AssignGroupAdminDto,
+            GroupSchool,
+            GroupAdmin,
+            GroupUser,
+            GroupUserFilterParams,
+            PaginatedGroupUsersResponse,
+            GroupReportParams,
+            GroupSchoolReport,
+            GroupReport,
         )
     ),
     modifiers(&SecurityAddon),
     tags(
         (name = "Authentication", description = "User authentication endpoints"),
         (name = "MFA", description = "Multi-factor authentication management"),
+        (name = "SSO", description = "Sign-in through school OpenID Connect identity providers, and their configuration"),
         (name = "Users", description = "User management endpoints"),
         (name = "Schools", description = "School management endpoints"),
+        (name = "School Groups", description = "School groups (districts), their member schools and group admins, users across member schools, and aggregate reports"),
         (name = "Students", description = "Student management endpoints"),
+        (name = "Imports", description = "Validation and import of a school's levels, branches, staff, students, and guardians"),
+        (name = "Interop", description = "OneRoster 1.1 CSV export and import of a school's roster for LMS products"),
+        (name = "Sync", description = "Scheduled push of branches and rosters to an LMS such as Google Classroom, with per-entity sync state and conflicts"),
         (name = "Levels", description = "Level/Grade management endpoints"),
         (name = "Branches", description = "Branch management endpoints"),
         (name = "Roles", description = "Custom roles and permissions management"),
         (name = "Academic Sessions", description = "Academic session/year management endpoints"),
-        (name = "Terms", description = "Term/semester management endpoints")
+        (name = "Terms", description = "Term/semester management endpoints"),
+        (name = "Staff Leave", description = "Staff leave requests, approvals, and calendar"),
+        (name = "Assets", description = "School inventory and asset register"),
+        (name = "Billing", description = "Fee structures, invoices, payments, and arrears"),
+        (name = "Boarding", description = "Hostels, room allocation, and boarding fees"),
+        (name = "Transport", description = "Vehicles, routes, student assignments, and driver manifests"),
+        (name = "Visitors", description = "Visitor and gate log, kiosk check-in, and daily reports"),
+        (name = "Clinic", description = "Student health-room visits, medication, and medical profiles"),
+        (name = "Discipline", description = "Behaviour incidents, guardian notifications, and incident trends"),
+        (name = "Library", description = "Library catalog, lending, fines, and borrowing history"),
+        (name = "Alumni", description = "Alumni records, graduation, and mailing list export"),
+        (name = "Custom Fields", description = "Per-school custom field definitions for students and users"),
+        (name = "Saved Views", description = "Your saved list filters and views shared within your school"),
+        (name = "Profiles", description = "Linked accounts, profile switching, and per-profile notification preferences"),
+        (name = "Recycle Bin", description = "Deleted students, users, and branches awaiting restore or purge"),
+        (name = "Retention", description = "Per-school retention periods, purge previews, and the purge audit trail"),
+        (name = "Traces", description = "Request ID lookup of log summaries and audit entries for support"),
+        (name = "Jobs", description = "Health of background jobs and scheduled tasks"),
+        (name = "Performance", description = "Slow request statistics"),
+        (name = "Feature Flags", description = "Runtime feature flags and per-school overrides"),
+        (name = "Attendance", description = "Daily student attendance by branch and term analytics"),
+        (name = "Attendance Kiosk", description = "Shared attendance devices, staff kiosk PINs, and device sessions"),
+        (name = "Assessments", description = "Subjects, assessments, marks entry, grading schemes, and term grades"),
+        (name = "Exams", description = "Exam seating plans, printable room lists, sittings, and invigilators"),
+        (name = "Results", description = "Term result moderation workflow: submission, moderation, publishing, and locking"),
+        (name = "Storage", description = "User avatars, student documents with presigned downloads, and school storage quotas"),
+        (name = "Student Cards", description = "Printable student ID cards with signed QR codes"),
+        (name = "Public Directory", description = "Opt-in public school profiles for the admissions page"),
+        (name = "Email Branding", description = "Per-school email sender name, logo, and colors, and email previews"),
+        (name = "Emails", description = "Outbound email delivery status and the bounce suppression list"),
+        (name = "Announcements", description = "Announcements to a school audience and each user's in-app notification feed"),
+        (name = "Messaging", description = "Conversations between teachers and guardians and among school staff, with attachments and unread counts"),
+        (name = "Broadcasts", description = "Templated email and SMS broadcasts with drafts, approval, scheduled publishing, and per-recipient delivery status"),
+        (name = "Changes", description = "Ordered feed of record changes for incremental sync by integrations"),
+        (name = "Webhooks", description = "School webhook endpoints receiving signed domain events, with delivery history"),
+        (name = "Guardians", description = "Guardian accounts created from student emergency contacts, with conflicts for review"),
+        (name = "Reports", description = "End-of-session summary reports for board reporting, as JSON or PDF")
     ),
     info(
         title = "Chalkbyte API",
@@ -248,7 +1382,106 @@ impl Modify for SecurityAddon {
                         .bearer_format("JWT")
                         .build(),
                 ),
-            )
+            );
+            components.add_security_scheme(
+                "kiosk_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Kiosk-Key"))),
+            );
+            components.add_security_scheme(
+                "device_token",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Device-Token"))),
+            );
         }
     }
 }
+
+/// Path prefixes of the routes behind the admin, group admin, and system
+/// admin role gates in [`crate::router`]. Kept in step with the router so the
+/// public spec never lists an endpoint only admins can call.
+pub const ADMIN_PATH_PREFIXES: &[&str] = &[
+    "/api/users",
+    "/api/schools",
+    "/api/groups",
+    "/api/changes",
+    "/api/admin",
+    "/api/imports",
+    "/api/interop",
+    "/api/sync",
+    "/api/levels",
+    "/api/branches",
+    "/api/roles",
+    "/api/academic-sessions",
+    "/api/terms",
+    "/api/assets",
+    "/api/billing",
+    "/api/boarding",
+    "/api/alumni",
+    "/api/kiosk-devices",
+];
+
+/// Whether a documented path sits under one of [`ADMIN_PATH_PREFIXES`].
+#[must_use]
+pub fn is_admin_path(path: &str) -> bool {
+    ADMIN_PATH_PREFIXES.iter().any(|prefix| {
+        path.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    })
+}
+
+/// The canonical spec: [`ApiDoc`] with every path under the latest version's
+/// prefix, e.g. `/api/v1/students`, which is what clients should call.
+///
+/// Handlers are annotated with their paths under the deprecated `/api`
+/// alias, so [`ADMIN_PATH_PREFIXES`] match [`ApiDoc`] as annotated.
+#[must_use]
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    versioned(ApiDoc::openapi())
+}
+
+/// Moves every `/api` path under [`ApiVersion::LATEST`]'s prefix.
+fn versioned(mut openapi: utoipa::openapi::OpenApi) -> utoipa::openapi::OpenApi {
+    let prefix = ApiVersion::LATEST.prefix();
+    openapi.paths.paths = std::mem::take(&mut openapi.paths.paths)
+        .into_iter()
+        .map(|(path, item)| match path.strip_prefix("/api/") {
+            Some(rest) => (format!("{prefix}/{rest}"), item),
+            None => (path, item),
+        })
+        .collect();
+    openapi
+}
+
+/// The spec served without authentication: [`openapi`] minus every
+/// admin-only path, and minus the tags no remaining operation uses.
+#[must_use]
+pub fn public_openapi() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+    openapi.paths.paths.retain(|path, _| !is_admin_path(path));
+    let mut openapi = versioned(openapi);
+
+    let used_tags: HashSet<&str> = openapi
+        .paths
+        .paths
+        .values()
+        .flat_map(|item| {
+            [
+                &item.get,
+                &item.put,
+                &item.post,
+                &item.delete,
+                &item.options,
+                &item.head,
+                &item.patch,
+                &item.trace,
+            ]
+        })
+        .flatten()
+        .flat_map(|operation| operation.tags.iter().flatten())
+        .map(String::as_str)
+        .collect();
+
+    if let Some(tags) = openapi.tags.as_mut() {
+        tags.retain(|tag| used_tags.contains(tag.name.as_str()));
+    }
+    openapi
+}
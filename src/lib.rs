@@ -112,8 +112,10 @@
 //! - [`config`]: Application configuration
 //! - [`docs`]: OpenAPI documentation setup
 //! - [`middleware`]: Authentication and authorization middleware
+//! - `mock`: Mock mode serving demo data without Postgres or Redis (`mock` feature)
 //! - [`modules`]: Feature modules (auth, users, schools, etc.)
 //! - [`router`]: Main application router
+//! - [`sdk`]: TypeScript and Dart client model generation from the OpenAPI document
 //! - [`state`]: Shared application state
 //! - [`utils`]: Shared utilities (errors, JWT, password hashing)
 //! - [`validator`]: Request validation utilities
@@ -131,8 +133,11 @@
 pub mod config;
 pub mod docs;
 pub mod middleware;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod modules;
 pub mod router;
+pub mod sdk;
 pub mod state;
 pub mod utils;
 pub mod validator;
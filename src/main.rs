@@ -1,18 +1,105 @@
 use std::net::SocketAddr;
+use std::path::PathBuf;
 
 use crate::router::init_router;
 use crate::state::init_app_state;
+use chalkbyte_config::{ChalkbyteConfig, ConfigSource};
 use dotenvy::dotenv;
 
 mod config;
 mod docs;
 mod middleware;
+#[cfg(feature = "mock")]
+mod mock;
 mod modules;
 mod router;
 mod state;
 mod utils;
 mod validator;
 
+/// Keeps mock mode's throwaway database alive for as long as it is held.
+#[cfg(feature = "mock")]
+type MockGuard = Option<mock::MockDatabase>;
+#[cfg(not(feature = "mock"))]
+type MockGuard = ();
+
+/// Whether `--mock` was given.
+fn mock_requested() -> bool {
+    std::env::args().skip(1).any(|arg| arg == "--mock")
+}
+
+/// The path given with `--config <path>` or `--config=<path>`, if any.
+fn config_path() -> Option<PathBuf> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.strip_prefix("--config=") {
+            return Some(PathBuf::from(path));
+        }
+    }
+    None
+}
+
+/// Loads the config file and environment, exiting with every problem found
+/// if the configuration is invalid. Mock mode brings its own database, so
+/// `DATABASE_URL` is optional there.
+fn load_config() -> ChalkbyteConfig {
+    let config = ConfigSource::load(config_path().as_deref())
+        .map(|source| {
+            if mock_requested() {
+                source.database_optional()
+            } else {
+                source
+            }
+        })
+        .and_then(|source| ChalkbyteConfig::validated(&source));
+
+    match config {
+        Ok(config) => {
+            if let Some(file) = &config.file {
+                println!("⚙️  Loaded configuration from {}", file.display());
+            }
+            config
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Builds the application state, from a mock database when `--mock` is given.
+async fn load_state(config: &ChalkbyteConfig) -> (state::AppState, MockGuard) {
+    #[cfg(feature = "mock")]
+    {
+        if mock::requested() {
+            let (state, database) = mock::init_mock_state(config).await;
+            return (state, Some(database));
+        }
+        (load_app_state(config).await, None)
+    }
+
+    #[cfg(not(feature = "mock"))]
+    {
+        if mock_requested() {
+            eprintln!("❌ Mock mode is not available in this build.");
+            eprintln!("   To enable, rebuild with: cargo build --features mock");
+            std::process::exit(1);
+        }
+        (load_app_state(config).await, ())
+    }
+}
+
+/// Builds the application state, exiting if the database is unavailable.
+async fn load_app_state(config: &ChalkbyteConfig) -> state::AppState {
+    init_app_state(config).await.unwrap_or_else(|e| {
+        eprintln!("❌ Failed to initialize the database pool: {}", e);
+        std::process::exit(1);
+    })
+}
+
 async fn start_main_server(state: state::AppState, port: u16) {
     // Ensure uploads directory exists
     let uploads_dir = std::path::PathBuf::from("./uploads");
@@ -22,6 +109,22 @@ async fn start_main_server(state: state::AppState, port: u16) {
         eprintln!("⚠️  Warning: Failed to create uploads directory: {}", e);
     }
 
+    modules::retention::service::spawn_purge_job(state.db.clone());
+    modules::broadcasts::service::spawn_delivery_job(
+        state.db.clone(),
+        state.email_config.clone(),
+    );
+    modules::webhooks::service::spawn_delivery_job(state.db.clone());
+    modules::emails::service::spawn_delivery_job(state.db.clone(), state.email_config.clone());
+    modules::guardians::service::spawn_account_job(
+        state.db.clone(),
+        state.email_config.clone(),
+        state.cache.clone(),
+    );
+    modules::reports::service::spawn_report_job(state.db.clone());
+    modules::terms::service::spawn_transition_job(state.db.clone());
+    modules::sync::service::spawn_sync_job(state.db.clone());
+
     let app = init_router(state);
 
     let addr = format!("0.0.0.0:{}", port);
@@ -84,17 +187,21 @@ async fn start_metrics_server(
 async fn main() {
     dotenv().ok();
 
+    let config = load_config();
+
+    // Report server errors and panics to Sentry when SENTRY_DSN is set
+    #[cfg(feature = "sentry")]
+    let _sentry = chalkbyte_observability::error_reporting::sentry::init_from_env();
+
     #[cfg(feature = "observability")]
     {
-        use chalkbyte_observability::{
-            init_metrics, init_tracing, is_observability_enabled, shutdown_tracer,
-        };
+        use chalkbyte_observability::{init_metrics, init_tracing, shutdown_tracer};
 
-        // Check if observability is enabled (default: true)
-        let observability_enabled = is_observability_enabled();
+        // OTLP export and sampling settings; observability defaults to enabled
+        let observability_enabled = config.observability.enabled;
 
         if observability_enabled {
-            init_tracing();
+            init_tracing(&config.observability);
         }
 
         // Initialize metrics only if observability is enabled
@@ -104,19 +211,10 @@ async fn main() {
             None
         };
 
-        let state = init_app_state().await;
+        let (state, _mock_database) = load_state(&config).await;
 
-        // Get the port from the environment variable, default to 3000 if not set
-        let port = std::env::var("PORT")
-            .ok()
-            .and_then(|p| p.parse::<u16>().ok())
-            .unwrap_or(3000);
-
-        // Get metrics port from environment variable, default to 3001
-        let metrics_port = std::env::var("METRICS_PORT")
-            .ok()
-            .and_then(|p| p.parse::<u16>().ok())
-            .unwrap_or(3001);
+        let port = config.server.port;
+        let metrics_port = config.server.metrics_port;
 
         // Start servers based on observability configuration
         if let Some(handle) = metrics_handle {
@@ -145,16 +243,10 @@ async fn main() {
         eprintln!();
 
         // Initialize basic console logging
-        init_tracing();
-
-        let state = init_app_state().await;
+        init_tracing(&config.observability);
 
-        // Get the port from the environment variable, default to 3000 if not set
-        let port = std::env::var("PORT")
-            .ok()
-            .and_then(|p| p.parse::<u16>().ok())
-            .unwrap_or(3000);
+        let (state, _mock_database) = load_state(&config).await;
 
-        start_main_server(state, port).await;
+        start_main_server(state, config.server.port).await;
     }
 }
@@ -0,0 +1,99 @@
+//! API version mounts and the deprecated `/api` alias.
+//!
+//! The router mounts the same module routers once per
+//! [`ApiVersion`] under its prefix (`/api/v1`, ...), each behind
+//! [`set_api_version`], and once more under the unversioned `/api` prefix
+//! behind [`deprecated_api_alias`]. Handlers read the version with the
+//! [`ApiVersion`] extractor; see [`chalkbyte_core::versioning`].
+//!
+//! Alias responses announce the deprecation with the `Deprecation`
+//! (RFC 9745) and `Sunset` (RFC 8594) headers and link the same route under
+//! its versioned prefix as the `successor-version`.
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use chalkbyte_core::versioning::{API_VERSION_HEADER, ApiVersion};
+
+/// Header marking a deprecated resource, with the time it was deprecated.
+pub const DEPRECATION_HEADER: HeaderName = HeaderName::from_static("deprecation");
+
+/// Header giving the time a resource stops being served.
+pub const SUNSET_HEADER: HeaderName = HeaderName::from_static("sunset");
+
+/// When the `/api` alias was deprecated, in seconds since the epoch
+/// (2026-10-18T00:00:00Z).
+pub const ALIAS_DEPRECATED_AT: i64 = 1_792_281_600;
+
+/// When the `/api` alias will be removed, as an HTTP date.
+pub const ALIAS_SUNSET: &str = "Fri, 30 Apr 2027 00:00:00 GMT";
+
+/// Middleware for a versioned mount: records `version` for the handlers and
+/// echoes it in the `Api-Version` response header.
+///
+/// The path decides the version; an `Api-Version` request header is ignored.
+pub async fn set_api_version(
+    State(version): State<ApiVersion>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    req.extensions_mut().insert(version);
+    let mut response = next.run(req).await;
+    response
+        .headers_mut()
+        .insert(API_VERSION_HEADER, version.header_value());
+    response
+}
+
+/// Middleware for the `/api` alias: serves the version asked for in the
+/// `Api-Version` header, or v1 without one, and marks the response
+/// deprecated.
+///
+/// # Errors
+///
+/// Returns `400 Bad Request` for an unsupported `Api-Version`.
+pub async fn deprecated_api_alias(mut req: Request, next: Next) -> Response {
+    let version = match ApiVersion::from_headers(req.headers()) {
+        Ok(version) => version.unwrap_or_default(),
+        Err(e) => return e.into_response(),
+    };
+
+    // Nested under `/api`, so the path is already relative to it
+    let successor = HeaderValue::from_str(&format!(
+        "<{}{}>; rel=\"successor-version\"",
+        version.prefix(),
+        req.uri().path()
+    ));
+
+    req.extensions_mut().insert(version);
+    let mut response = next.run(req).await;
+
+    let headers = response.headers_mut();
+    headers.insert(API_VERSION_HEADER, version.header_value());
+    headers.insert(
+        DEPRECATION_HEADER,
+        HeaderValue::from_str(&format!("@{ALIAS_DEPRECATED_AT}"))
+            .expect("a timestamp is a valid header value"),
+    );
+    headers.insert(SUNSET_HEADER, HeaderValue::from_static(ALIAS_SUNSET));
+    if let Ok(successor) = successor {
+        headers.append(header::LINK, successor);
+    }
+    headers.append(header::VARY, HeaderValue::from_static("api-version"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alias_sunset_follows_deprecation() {
+        let sunset = chrono::DateTime::parse_from_rfc2822(ALIAS_SUNSET).unwrap();
+        assert!(sunset.timestamp() > ALIAS_DEPRECATED_AT);
+    }
+}
@@ -44,8 +44,8 @@
 //! | Extractor | Required Permission |
 //! |-----------|---------------------|
 //! | `RequireUsersCreate` | `users:create` |
-//! | `RequireUsersRead` | `users:read` |
-//! | `RequireUsersUpdate` | `users:update` |
+//! | `RequireUsersRead` | `users:read` or `users:read:self` |
+//! | `RequireUsersUpdate` | `users:update` or `users:update:self` |
 //! | `RequireUsersDelete` | `users:delete` |
 //! | `RequireSchoolsCreate` | `schools:create` |
 //! | `RequireSchoolsRead` | `schools:read` |
@@ -53,6 +53,46 @@
 //! | `RequireSchoolsDelete` | `schools:delete` |
 //! | ... and more |
 //!
+//! ## Scoped Permission Extractors
+//!
+//! Some extractors also accept a scoped form of their permission, such as
+//! `students:read:own_branch` or `users:update:self`, and carry the
+//! [`RecordScope`] the user was granted. Handlers pass it to the service,
+//! which limits list queries with it, and call [`RecordScope::ensure_user`]
+//! before touching a single record:
+//!
+//! ```ignore
+//! async fn get_student(
+//!     State(state): State<AppState>,
+//!     RequireStudentsRead(auth_user, scope): RequireStudentsRead,
+//!     Path(id): Path<Uuid>,
+//! ) -> Result<Json<Student>, AppError> {
+//!     // 403 unless the user holds students:read, or students:read:own_branch
+//!     // and the student is in a branch they teach
+//!     scope.ensure_user(&state.db, id).await?;
+//!     // ...
+//! }
+//! ```
+//!
+//! ## `AuthContext`
+//!
+//! Request-scoped context set by the first extractor that authenticates the
+//! request and reused by every later one. Use it when a check needs the user's
+//! current roles or permissions from the database rather than the JWT; they
+//! are loaded once per request and shared across middleware and handlers.
+//!
+//! ```ignore
+//! async fn handler(
+//!     State(state): State<AppState>,
+//!     context: AuthContext,
+//! ) -> Result<impl IntoResponse, AppError> {
+//!     if !context.has_permission(&state.db, "users:delete").await? {
+//!         return Err(AppError::forbidden("Access denied".to_string()));
+//!     }
+//!     Ok(Json("Success"))
+//! }
+//! ```
+//!
 //! # Custom Permission Checks
 //!
 //! For more complex authorization logic, use the `AuthUser` methods:
@@ -93,17 +133,34 @@
 //!     Ok(Json("Success"))
 //! }
 //! ```
+//!
+//! Group admins have no school of their own. `auth_user.group_id()` is the
+//! school group they administer, and the helpers in `utils::auth_helpers`
+//! limit them to that group's member schools.
+
+use std::collections::HashSet;
+use std::sync::Arc;
 
 use axum::{
     extract::FromRequestParts,
     http::{header, request::Parts},
 };
+use tokio::sync::OnceCell;
 
-use chalkbyte_auth::{Claims, verify_token};
-use chalkbyte_core::AppError;
-use chalkbyte_models::ids::{RoleId, SchoolId, UserId};
+use chalkbyte_auth::{Claims, verify_kiosk_token, verify_token};
+use chalkbyte_core::permissions::PermissionScope;
+use chalkbyte_core::{AppError, FieldAccess};
+use chalkbyte_db::PgPool;
+use chalkbyte_models::ids::{BranchId, RoleId, SchoolGroupId, SchoolId, UserId, VisitorKioskKeyId};
+use chalkbyte_observability::{RequestSpan, SchoolLabel};
 use uuid::Uuid;
 
+use crate::modules::branches::service::BranchService;
+use crate::modules::kiosk::model::KioskDevice;
+use crate::modules::kiosk::service::KioskService;
+use crate::modules::roles::service as roles_service;
+use crate::modules::users::service::UserService;
+use crate::modules::visitor_log::service::VisitorLogService;
 use crate::state::AppState;
 
 /// Extractor that validates JWT and provides the authenticated user's claims.
@@ -210,6 +267,25 @@ impl AuthUser {
         permissions.iter().all(|p| self.has_permission(p))
     }
 
+    /// Gets the widest scope in which the user holds a permission.
+    ///
+    /// # Returns
+    ///
+    /// `PermissionScope::All` if the user holds the bare permission, a narrower
+    /// scope if they only hold a scoped form such as `students:read:own_branch`,
+    /// or `None` if they hold neither.
+    #[must_use]
+    pub fn permission_scope(&self, permission: &str) -> Option<PermissionScope> {
+        PermissionScope::resolve(&self.0.permissions, permission)
+    }
+
+    /// Gets the permissions that decide which restricted response fields
+    /// the user sees. See [`chalkbyte_core::visibility`].
+    #[must_use]
+    pub fn field_access(&self) -> FieldAccess {
+        FieldAccess::new(self.0.permissions.iter().map(String::as_str))
+    }
+
     /// Checks if the user has a specific role by ID.
     ///
     /// # Arguments
@@ -277,6 +353,14 @@ impl AuthUser {
         self.0.school_id.map(SchoolId::from)
     }
 
+    /// Gets the school group the user administers, from the JWT claims.
+    ///
+    /// Only group admins carry a group; everyone else gets `None`.
+    #[must_use]
+    pub fn group_id(&self) -> Option<SchoolGroupId> {
+        self.0.group_id.map(SchoolGroupId::from)
+    }
+
     /// Gets the user ID as a UserId.
     ///
     /// # Returns
@@ -293,6 +377,22 @@ impl AuthUser {
             .map_err(|_| AppError::unauthorized("Invalid user ID in token".to_string()))
     }
 
+    /// Gets the account that signed in.
+    ///
+    /// This is the user ID unless the token acts as one of the login's
+    /// linked profiles, in which case it is the account the profile was
+    /// switched to from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an unauthorized error if the user ID in the token is not a valid UUID.
+    pub fn login_id(&self) -> Result<UserId, AppError> {
+        match self.0.login_id {
+            Some(login_id) => Ok(UserId::from(login_id)),
+            None => self.user_id(),
+        }
+    }
+
     /// Gets the user's email address.
     ///
     /// # Returns
@@ -312,6 +412,137 @@ impl FromRequestParts<AppState> for AuthUser {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
+        let context = AuthContext::from_request_parts(parts, state).await?;
+
+        Ok(AuthUser(context.claims().clone()))
+    }
+}
+
+/// Request-scoped authentication context.
+///
+/// The first extractor to authenticate a request (`AuthUser`, any
+/// `Require*` permission extractor, or the role middleware) verifies the JWT
+/// once and stores an `AuthContext` in the request extensions. Every later
+/// extractor in the same request reuses it instead of verifying the token
+/// again.
+///
+/// Checks that need fresh role or permission data (for example after a role
+/// change that the JWT does not reflect yet) go through
+/// [`AuthContext::has_any_role`] and [`AuthContext::has_permission`], which
+/// load the user's roles and permissions from the database at most once per
+/// request, however many middleware layers and handlers ask.
+#[derive(Debug, Clone)]
+pub struct AuthContext {
+    claims: Arc<Claims>,
+    user_id: UserId,
+    role_ids: Arc<OnceCell<Vec<RoleId>>>,
+    permissions: Arc<OnceCell<HashSet<String>>>,
+}
+
+impl AuthContext {
+    /// Builds a context from verified claims.
+    ///
+    /// # Errors
+    ///
+    /// Returns an unauthorized error if the subject is not a valid UUID.
+    pub fn from_claims(claims: Claims) -> Result<Self, AppError> {
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map(UserId::from)
+            .map_err(|_| AppError::unauthorized("Invalid user ID in token".to_string()))?;
+
+        Ok(Self {
+            claims: Arc::new(claims),
+            user_id,
+            role_ids: Arc::default(),
+            permissions: Arc::default(),
+        })
+    }
+
+    /// Builds a context for a staff member who signed in to a school without
+    /// a bearer token, such as by unlocking a kiosk with their PIN.
+    ///
+    /// There is no token behind it, so its claims carry only the user and
+    /// school; use [`AuthContext::has_any_role`] and
+    /// [`AuthContext::has_permission`] for checks.
+    pub fn for_user(user_id: UserId, school_id: SchoolId) -> Self {
+        Self {
+            claims: Arc::new(Claims {
+                sub: user_id.to_string(),
+                email: String::new(),
+                school_id: Some(school_id.into_inner()),
+                group_id: None,
+                login_id: None,
+                role_ids: Vec::new(),
+                permissions: Vec::new(),
+                exp: 0,
+                iat: 0,
+            }),
+            user_id,
+            role_ids: Arc::default(),
+            permissions: Arc::default(),
+        }
+    }
+
+    /// The verified JWT claims.
+    #[must_use]
+    pub fn claims(&self) -> &Claims {
+        &self.claims
+    }
+
+    /// The authenticated user's ID.
+    #[must_use]
+    pub fn user_id(&self) -> UserId {
+        self.user_id
+    }
+
+    /// The user's school, or `None` for system administrators.
+    #[must_use]
+    pub fn school_id(&self) -> Option<SchoolId> {
+        self.claims.school_id.map(SchoolId::from)
+    }
+
+    /// The user's current role IDs, loaded from the database on first use.
+    pub async fn role_ids(&self, db: &PgPool) -> Result<&[RoleId], AppError> {
+        let role_ids = self
+            .role_ids
+            .get_or_try_init(|| UserService::get_user_role_ids(db, self.user_id))
+            .await?;
+
+        Ok(role_ids)
+    }
+
+    /// Checks whether the user currently holds any of the roles.
+    pub async fn has_any_role(&self, db: &PgPool, role_ids: &[RoleId]) -> Result<bool, AppError> {
+        let current = self.role_ids(db).await?;
+        Ok(role_ids.iter().any(|r| current.contains(r)))
+    }
+
+    /// Checks the user's current permissions, loaded from the database on
+    /// first use.
+    pub async fn has_permission(&self, db: &PgPool, permission: &str) -> Result<bool, AppError> {
+        let permissions = self
+            .permissions
+            .get_or_try_init(|| async {
+                let names = roles_service::get_user_permission_names(db, self.user_id).await?;
+                Ok::<_, AppError>(names.into_iter().collect::<HashSet<_>>())
+            })
+            .await?;
+
+        Ok(permissions.contains(permission))
+    }
+}
+
+impl FromRequestParts<AppState> for AuthContext {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(context) = parts.extensions.get::<AuthContext>() {
+            return Ok(context.clone());
+        }
+
         let auth_header = parts
             .headers
             .get(header::AUTHORIZATION)
@@ -323,8 +554,116 @@ impl FromRequestParts<AppState> for AuthUser {
         })?;
 
         let claims = verify_token(token, &state.jwt_config)?;
+        let context = AuthContext::from_claims(claims)?;
+        parts.extensions.insert(context.clone());
+
+        // Label this request's metrics and logs with the caller
+        if let (Some(label), Some(school_id)) = (
+            parts.extensions.get::<SchoolLabel>(),
+            context.claims.school_id,
+        ) {
+            label.set(school_id);
+        }
+        if let Some(span) = parts.extensions.get::<RequestSpan>() {
+            span.record_caller(context.user_id.into_inner(), context.claims.school_id);
+        }
+
+        Ok(context)
+    }
+}
+
+/// Records a scoped permission lets a request act on.
+///
+/// Built by scoped permission extractors from the widest [`PermissionScope`]
+/// the user holds. `School` leaves the usual school scoping alone; the others
+/// narrow it further.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordScope {
+    /// Every record the user's school scoping allows
+    School,
+    /// Only users in these branches: the ones the user teaches today
+    Branches(Vec<BranchId>),
+    /// Only the user's own record
+    Own(UserId),
+}
+
+impl RecordScope {
+    /// Resolves a permission scope for the authenticated user.
+    pub async fn resolve(
+        db: &PgPool,
+        auth_user: &AuthUser,
+        scope: PermissionScope,
+    ) -> Result<Self, AppError> {
+        match scope {
+            PermissionScope::All => Ok(Self::School),
+            PermissionScope::Own => Ok(Self::Own(auth_user.user_id()?)),
+            PermissionScope::OwnBranch => {
+                let today = chrono::Utc::now().date_naive();
+                let branch_ids =
+                    BranchService::teacher_branch_ids(db, auth_user.user_id()?, today).await?;
+                Ok(Self::Branches(branch_ids))
+            }
+        }
+    }
+
+    /// Branches list queries must be limited to, as a `uuid[]` bind value
+    /// that is `NULL` when there is no branch restriction.
+    #[must_use]
+    pub fn branch_ids(&self) -> Option<Vec<Uuid>> {
+        match self {
+            Self::Branches(ids) => Some(ids.iter().map(|id| id.into_inner()).collect()),
+            _ => None,
+        }
+    }
 
-        Ok(AuthUser(claims))
+    /// The only user list queries may return, as a bind value that is
+    /// `NULL` when there is no such restriction.
+    #[must_use]
+    pub fn user_id(&self) -> Option<Uuid> {
+        match self {
+            Self::Own(id) => Some(id.into_inner()),
+            _ => None,
+        }
+    }
+
+    /// Rejects scopes narrower than the whole school, for operations that
+    /// cannot be limited record by record.
+    pub fn ensure_school(&self) -> Result<(), AppError> {
+        match self {
+            Self::School => Ok(()),
+            _ => Err(AppError::forbidden(
+                "Access denied. This operation needs the unscoped permission".to_string(),
+            )),
+        }
+    }
+
+    /// Checks that a user record is within the scope.
+    ///
+    /// The usual school checks still apply afterwards; this only adds the
+    /// branch or own-record restriction.
+    pub async fn ensure_user(&self, db: &PgPool, user_id: Uuid) -> Result<(), AppError> {
+        let allowed = match self {
+            Self::School => true,
+            Self::Own(own_id) => own_id.into_inner() == user_id,
+            Self::Branches(ids) => {
+                let branch_id = sqlx::query_scalar::<_, Option<BranchId>>(
+                    "SELECT branch_id FROM users WHERE id = $1",
+                )
+                .bind(user_id)
+                .fetch_optional(db)
+                .await?
+                .flatten();
+                branch_id.is_some_and(|branch_id| ids.contains(&branch_id))
+            }
+        };
+
+        if !allowed {
+            return Err(AppError::forbidden(
+                "Access denied. The record is outside your permission scope".to_string(),
+            ));
+        }
+
+        Ok(())
     }
 }
 
@@ -355,8 +694,51 @@ impl FromRequestParts<AppState> for AuthUser {
 ///
 /// The extractor automatically returns `403 Forbidden` if the user lacks
 /// the required permission.
+///
+/// # Scoped Extractors
+///
+/// Adding `scoped` also accepts the permission's scoped forms and carries the
+/// resolved [`RecordScope`] alongside the user:
+///
+/// ```ignore
+/// require_permission!(RequireStudentsRead, "students:read", scoped);
+///
+/// // pub struct RequireStudentsRead(pub AuthUser, pub RecordScope);
+/// ```
 #[macro_export]
 macro_rules! require_permission {
+    ($name:ident, $permission:literal, scoped) => {
+        #[allow(dead_code)]
+        #[derive(Debug, Clone)]
+        pub struct $name(
+            pub $crate::middleware::auth::AuthUser,
+            pub $crate::middleware::auth::RecordScope,
+        );
+
+        impl axum::extract::FromRequestParts<$crate::state::AppState> for $name {
+            type Rejection = $crate::utils::errors::AppError;
+
+            async fn from_request_parts(
+                parts: &mut axum::http::request::Parts,
+                state: &$crate::state::AppState,
+            ) -> Result<Self, Self::Rejection> {
+                let auth_user =
+                    $crate::middleware::auth::AuthUser::from_request_parts(parts, state).await?;
+
+                let Some(scope) = auth_user.permission_scope($permission) else {
+                    return Err($crate::utils::errors::AppError::forbidden(format!(
+                        "Access denied. Missing required permission: {}",
+                        $permission
+                    )));
+                };
+                let scope =
+                    $crate::middleware::auth::RecordScope::resolve(&state.db, &auth_user, scope)
+                        .await?;
+
+                Ok($name(auth_user, scope))
+            }
+        }
+    };
     ($name:ident, $permission:literal) => {
         #[allow(dead_code)]
         #[derive(Debug, Clone)]
@@ -391,8 +773,8 @@ macro_rules! require_permission {
 
 // Users permissions
 require_permission!(RequireUsersCreate, "users:create");
-require_permission!(RequireUsersRead, "users:read");
-require_permission!(RequireUsersUpdate, "users:update");
+require_permission!(RequireUsersRead, "users:read", scoped);
+require_permission!(RequireUsersUpdate, "users:update", scoped);
 require_permission!(RequireUsersDelete, "users:delete");
 
 // Schools permissions
@@ -403,8 +785,8 @@ require_permission!(RequireSchoolsDelete, "schools:delete");
 
 // Students permissions
 require_permission!(RequireStudentsCreate, "students:create");
-require_permission!(RequireStudentsRead, "students:read");
-require_permission!(RequireStudentsUpdate, "students:update");
+require_permission!(RequireStudentsRead, "students:read", scoped);
+require_permission!(RequireStudentsUpdate, "students:update", scoped);
 require_permission!(RequireStudentsDelete, "students:delete");
 
 // Levels permissions
@@ -413,6 +795,7 @@ require_permission!(RequireLevelsRead, "levels:read");
 require_permission!(RequireLevelsUpdate, "levels:update");
 require_permission!(RequireLevelsDelete, "levels:delete");
 require_permission!(RequireLevelsAssignStudents, "levels:assign_students");
+require_permission!(RequireLevelsPromote, "levels:promote");
 
 // Branches permissions
 require_permission!(RequireBranchesCreate, "branches:create");
@@ -420,6 +803,7 @@ require_permission!(RequireBranchesRead, "branches:read");
 require_permission!(RequireBranchesUpdate, "branches:update");
 require_permission!(RequireBranchesDelete, "branches:delete");
 require_permission!(RequireBranchesAssignStudents, "branches:assign_students");
+require_permission!(RequireBranchesAssignTeachers, "branches:assign_teachers");
 
 // Roles permissions
 require_permission!(RequireRolesCreate, "roles:create");
@@ -448,9 +832,286 @@ require_permission!(RequireTermsRead, "terms:read");
 require_permission!(RequireTermsUpdate, "terms:update");
 require_permission!(RequireTermsDelete, "terms:delete");
 
+// Staff leave permissions
+require_permission!(RequireStaffLeaveRequest, "staff_leave:request");
+require_permission!(RequireStaffLeaveRead, "staff_leave:read");
+require_permission!(RequireStaffLeaveApprove, "staff_leave:approve");
+
+// Asset permissions
+require_permission!(RequireAssetsCreate, "assets:create");
+require_permission!(RequireAssetsRead, "assets:read");
+require_permission!(RequireAssetsUpdate, "assets:update");
+require_permission!(RequireAssetsDelete, "assets:delete");
+require_permission!(RequireAssetsAssign, "assets:assign");
+
+// Boarding permissions
+require_permission!(RequireBoardingCreate, "boarding:create");
+require_permission!(RequireBoardingRead, "boarding:read");
+require_permission!(RequireBoardingUpdate, "boarding:update");
+require_permission!(RequireBoardingDelete, "boarding:delete");
+require_permission!(RequireBoardingAllocate, "boarding:allocate");
+
+// Billing permissions
+require_permission!(RequireBillingCreate, "billing:create");
+require_permission!(RequireBillingRead, "billing:read");
+require_permission!(RequireBillingDelete, "billing:delete");
+require_permission!(RequireBillingCollect, "billing:collect");
+require_permission!(RequireBillingExport, "billing:export");
+
+// Transport permissions
+require_permission!(RequireTransportCreate, "transport:create");
+require_permission!(RequireTransportRead, "transport:read");
+require_permission!(RequireTransportUpdate, "transport:update");
+require_permission!(RequireTransportDelete, "transport:delete");
+require_permission!(RequireTransportAssign, "transport:assign");
+require_permission!(RequireTransportManifest, "transport:manifest");
+
+// Visitor log permissions
+require_permission!(RequireVisitorsCreate, "visitors:create");
+require_permission!(RequireVisitorsRead, "visitors:read");
+require_permission!(RequireVisitorsUpdate, "visitors:update");
+require_permission!(RequireVisitorsDelete, "visitors:delete");
+require_permission!(RequireVisitorsKiosk, "visitors:kiosk");
+
+// Clinic permissions
+require_permission!(RequireClinicRead, "clinic:read");
+require_permission!(RequireClinicRecord, "clinic:record");
+require_permission!(RequireClinicUpdate, "clinic:update");
+require_permission!(RequireClinicDelete, "clinic:delete");
+
+// Discipline permissions
+require_permission!(RequireDisciplineRecord, "discipline:record");
+require_permission!(RequireDisciplineRead, "discipline:read", scoped);
+require_permission!(RequireDisciplineUpdate, "discipline:update");
+require_permission!(RequireDisciplineDelete, "discipline:delete");
+
+// Library permissions
+require_permission!(RequireLibraryCreate, "library:create");
+require_permission!(RequireLibraryRead, "library:read");
+require_permission!(RequireLibraryUpdate, "library:update");
+require_permission!(RequireLibraryDelete, "library:delete");
+require_permission!(RequireLibraryLend, "library:lend");
+
+// Alumni permissions
+require_permission!(RequireAlumniCreate, "alumni:create");
+require_permission!(RequireAlumniRead, "alumni:read");
+require_permission!(RequireAlumniUpdate, "alumni:update");
+require_permission!(RequireAlumniDelete, "alumni:delete");
+require_permission!(RequireAlumniExport, "alumni:export");
+
+// Custom fields permissions
+require_permission!(RequireCustomFieldsCreate, "custom_fields:create");
+require_permission!(RequireCustomFieldsRead, "custom_fields:read");
+require_permission!(RequireCustomFieldsUpdate, "custom_fields:update");
+require_permission!(RequireCustomFieldsDelete, "custom_fields:delete");
+
+// Recycle bin permissions
+require_permission!(RequireTrashRead, "trash:read");
+require_permission!(RequireTrashRestore, "trash:restore");
+require_permission!(RequireTrashPurge, "trash:purge");
+
+// Retention permissions
+require_permission!(RequireRetentionRead, "retention:read");
+require_permission!(RequireRetentionManage, "retention:manage");
+
+// Attendance permissions
+require_permission!(RequireAttendanceMark, "attendance:mark");
+require_permission!(RequireAttendanceRead, "attendance:read");
+require_permission!(RequireKioskDevicesManage, "kiosk_devices:manage");
+
+// Assessment and grade permissions
+require_permission!(RequireSubjectsCreate, "subjects:create");
+require_permission!(RequireSubjectsRead, "subjects:read");
+require_permission!(RequireSubjectsDelete, "subjects:delete");
+require_permission!(RequireAssessmentsCreate, "assessments:create");
+require_permission!(RequireAssessmentsRead, "assessments:read");
+require_permission!(RequireAssessmentsUpdate, "assessments:update");
+require_permission!(RequireAssessmentsDelete, "assessments:delete");
+require_permission!(RequireGradesRecord, "grades:record");
+require_permission!(RequireGradesRead, "grades:read");
+require_permission!(RequireGradesFinalize, "grades:finalize");
+require_permission!(RequireGradingSchemesManage, "grading_schemes:manage");
+
+// Term result permissions
+require_permission!(RequireResultsRead, "results:read");
+
+// Broadcast permissions
+require_permission!(RequireBroadcastsSend, "broadcasts:send");
+require_permission!(RequireBroadcastsRead, "broadcasts:read");
+require_permission!(RequireBroadcastsApprove, "broadcasts:approve");
+
+// Announcement permissions
+require_permission!(RequireAnnouncementsCreate, "announcements:create");
+require_permission!(RequireAnnouncementsRead, "announcements:read");
+require_permission!(RequireAnnouncementsDelete, "announcements:delete");
+require_permission!(RequireAnnouncementsApprove, "announcements:approve");
+
+// Messaging permissions
+require_permission!(RequireMessagingRead, "messaging:read");
+require_permission!(RequireMessagingSend, "messaging:send");
+
+// Interoperability permissions
+require_permission!(RequireInteropExport, "interop:export");
+require_permission!(RequireInteropImport, "interop:import");
+
+// LMS sync permissions
+require_permission!(RequireSyncRead, "sync:read");
+require_permission!(RequireSyncManage, "sync:manage");
+
+// Change feed permissions
+require_permission!(RequireChangesRead, "changes:read");
+
+// Webhook permissions
+require_permission!(RequireWebhooksManage, "webhooks:manage");
+require_permission!(RequireWebhooksRead, "webhooks:read");
+
+// Guardian permissions
+require_permission!(RequireGuardiansManage, "guardians:manage");
+require_permission!(RequireGuardiansRead, "guardians:read");
+
+// Single sign-on permissions
+require_permission!(RequireSsoManage, "sso:manage");
+require_permission!(RequireSsoRead, "sso:read");
+
+// School group permissions
+require_permission!(RequireGroupsCreate, "groups:create");
+require_permission!(RequireGroupsRead, "groups:read");
+require_permission!(RequireGroupsUpdate, "groups:update");
+require_permission!(RequireGroupsDelete, "groups:delete");
+require_permission!(RequireGroupsReports, "groups:reports");
+
+/// Header carrying a visitor kiosk API key.
+pub const KIOSK_KEY_HEADER: &str = "x-kiosk-key";
+
+/// Extractor that authenticates a self-service visitor kiosk.
+///
+/// Kiosks do not log in as a user. Instead they send an API key issued by a
+/// school admin in the `X-Kiosk-Key` header, and every request is scoped to
+/// the school the key belongs to.
+///
+/// # Errors
+///
+/// Returns `401 Unauthorized` if the header is missing or the key is unknown
+/// or revoked.
+#[derive(Debug, Clone)]
+pub struct VisitorKiosk {
+    /// The kiosk key used for this request
+    pub key_id: VisitorKioskKeyId,
+    /// School the kiosk belongs to
+    pub school_id: SchoolId,
+}
+
+impl FromRequestParts<AppState> for VisitorKiosk {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let api_key = parts
+            .headers
+            .get(KIOSK_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| AppError::unauthorized("Missing kiosk key header".to_string()))?;
+
+        let key = VisitorLogService::authenticate_kiosk_key(&state.db, api_key).await?;
+
+        Ok(VisitorKiosk {
+            key_id: key.id,
+            school_id: key.school_id,
+        })
+    }
+}
+
+/// Header carrying an attendance kiosk device token.
+pub const DEVICE_TOKEN_HEADER: &str = "x-device-token";
+
+/// Extractor that authenticates a registered attendance kiosk device.
+///
+/// The device sends the token it was given at registration in the
+/// `X-Device-Token` header. On its own this only allows unlocking the device;
+/// marking attendance also needs a staff operation token, see
+/// [`KioskOperator`].
+///
+/// # Errors
+///
+/// Returns `401 Unauthorized` if the header is missing or the device is
+/// unknown or revoked.
+#[derive(Debug, Clone)]
+pub struct AttendanceKiosk(pub KioskDevice);
+
+impl FromRequestParts<AppState> for AttendanceKiosk {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let device_token = parts
+            .headers
+            .get(DEVICE_TOKEN_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| AppError::unauthorized("Missing device token header".to_string()))?;
+
+        let device = KioskService::authenticate_device(&state.db, device_token).await?;
+
+        Ok(AttendanceKiosk(device))
+    }
+}
+
+/// Extractor for a staff member operating an attendance kiosk device.
+///
+/// Requires both the device token and a kiosk operation token issued on that
+/// same device in the `Authorization` header. Because the device is checked on
+/// every request, revoking it cuts off its operation tokens immediately.
+///
+/// # Errors
+///
+/// Returns `401 Unauthorized` if either token is missing, invalid, or expired,
+/// or if the operation token was issued on a different device.
+#[derive(Debug, Clone)]
+pub struct KioskOperator {
+    /// The device the request came from
+    pub device: KioskDevice,
+    /// Staff member who unlocked the device
+    pub staff_id: UserId,
+}
+
+impl FromRequestParts<AppState> for KioskOperator {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| AppError::unauthorized("Missing kiosk operation token".to_string()))?;
+
+        let claims = verify_kiosk_token(token, &state.jwt_config)?;
+        let AttendanceKiosk(device) = AttendanceKiosk::from_request_parts(parts, state).await?;
+
+        if claims.device_id != device.id.into_inner() {
+            return Err(AppError::unauthorized(
+                "Operation token was issued on another device".to_string(),
+            ));
+        }
+
+        let staff_id = Uuid::parse_str(&claims.sub)
+            .map(UserId::from)
+            .map_err(|_| AppError::unauthorized("Invalid kiosk operation token".to_string()))?;
+
+        Ok(KioskOperator { device, staff_id })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::modules::users::model::system_roles;
     use chalkbyte_auth::Claims;
 
     fn create_test_claims(permissions: Vec<String>, role_ids: Vec<Uuid>) -> Claims {
@@ -458,6 +1119,8 @@ mod tests {
             sub: Uuid::new_v4().to_string(),
             email: "test@example.com".to_string(),
             school_id: None,
+            group_id: None,
+            login_id: None,
             role_ids,
             permissions,
             exp: 9999999999,
@@ -465,6 +1128,66 @@ mod tests {
         }
     }
 
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_auth_context_loads_roles_once(pool: PgPool) {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (first_name, last_name, email) VALUES ('Ada', 'Obi', $1) RETURNING id",
+        )
+        .bind(format!("user-{}@example.com", Uuid::new_v4()))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(system_roles::TEACHER)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let mut claims = create_test_claims(vec![], vec![]);
+        claims.sub = user_id.to_string();
+        let context = AuthContext::from_claims(claims).unwrap();
+
+        assert!(
+            context
+                .has_any_role(&pool, &[system_roles::TEACHER])
+                .await
+                .unwrap()
+        );
+        assert!(
+            context
+                .has_permission(&pool, "attendance:mark")
+                .await
+                .unwrap()
+        );
+
+        // Later checks in the same request reuse what was loaded
+        sqlx::query("DELETE FROM user_roles WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(
+            context
+                .clone()
+                .has_any_role(&pool, &[system_roles::TEACHER])
+                .await
+                .unwrap()
+        );
+        assert!(
+            context
+                .has_permission(&pool, "attendance:mark")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !context
+                .has_permission(&pool, "schools:delete")
+                .await
+                .unwrap()
+        );
+    }
+
     #[test]
     fn test_has_permission() {
         let claims = create_test_claims(
@@ -503,6 +1226,120 @@ mod tests {
         assert!(!auth_user.has_all_permissions(&["users:read", "users:delete"]));
     }
 
+    #[test]
+    fn test_permission_scope() {
+        let claims = create_test_claims(
+            vec![
+                "students:read:own_branch".to_string(),
+                "users:update:self".to_string(),
+                "users:read".to_string(),
+                "users:read:self".to_string(),
+            ],
+            vec![],
+        );
+        let auth_user = AuthUser(claims);
+
+        assert_eq!(
+            auth_user.permission_scope("students:read"),
+            Some(PermissionScope::OwnBranch)
+        );
+        assert_eq!(
+            auth_user.permission_scope("users:update"),
+            Some(PermissionScope::Own)
+        );
+        assert_eq!(
+            auth_user.permission_scope("users:read"),
+            Some(PermissionScope::All)
+        );
+        assert_eq!(auth_user.permission_scope("students:update"), None);
+        // A scoped form never satisfies an exact check for the bare permission
+        assert!(!auth_user.has_permission("students:read"));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_record_scope_limits_users_to_taught_branches(pool: PgPool) {
+        let school_id: Uuid =
+            sqlx::query_scalar("INSERT INTO schools (name) VALUES ($1) RETURNING id")
+                .bind(format!("School {}", Uuid::new_v4()))
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let level_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO levels (name, school_id) VALUES ('Grade 1', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let mut branch_ids = vec![];
+        for name in ["A", "B"] {
+            let branch_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO branches (name, level_id) VALUES ($1, $2) RETURNING id",
+            )
+            .bind(name)
+            .bind(level_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            branch_ids.push(branch_id);
+        }
+
+        let mut user_ids = vec![];
+        for branch_id in [None, Some(branch_ids[0]), Some(branch_ids[1])] {
+            let user_id: Uuid = sqlx::query_scalar(
+                "INSERT INTO users (first_name, last_name, email, school_id, branch_id)
+                 VALUES ('Ada', 'Obi', $1, $2, $3) RETURNING id",
+            )
+            .bind(format!("user-{}@example.com", Uuid::new_v4()))
+            .bind(school_id)
+            .bind(branch_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            user_ids.push(user_id);
+        }
+        let (teacher_id, in_branch, other_branch) = (user_ids[0], user_ids[1], user_ids[2]);
+
+        // Teaches branch A now; taught branch B only in the past
+        sqlx::query(
+            "INSERT INTO branch_teachers (branch_id, teacher_id, capacity, starts_on, ends_on)
+             VALUES ($1, $3, 'lead', CURRENT_DATE - 30, NULL),
+                    ($2, $3, 'lead', CURRENT_DATE - 30, CURRENT_DATE - 1)",
+        )
+        .bind(branch_ids[0])
+        .bind(branch_ids[1])
+        .bind(teacher_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut claims = create_test_claims(vec![], vec![]);
+        claims.sub = teacher_id.to_string();
+        let auth_user = AuthUser(claims);
+
+        let scope = RecordScope::resolve(&pool, &auth_user, PermissionScope::OwnBranch)
+            .await
+            .unwrap();
+        assert_eq!(
+            scope,
+            RecordScope::Branches(vec![BranchId::from(branch_ids[0])])
+        );
+        assert_eq!(scope.branch_ids(), Some(vec![branch_ids[0]]));
+        assert!(scope.ensure_school().is_err());
+
+        scope.ensure_user(&pool, in_branch).await.unwrap();
+        assert!(scope.ensure_user(&pool, other_branch).await.is_err());
+        assert!(scope.ensure_user(&pool, Uuid::new_v4()).await.is_err());
+
+        let own = RecordScope::resolve(&pool, &auth_user, PermissionScope::Own)
+            .await
+            .unwrap();
+        assert_eq!(own.user_id(), Some(teacher_id));
+        own.ensure_user(&pool, teacher_id).await.unwrap();
+        assert!(own.ensure_user(&pool, in_branch).await.is_err());
+    }
+
     #[test]
     fn test_has_role() {
         let role_uuid = Uuid::new_v4();
@@ -533,6 +1370,8 @@ mod tests {
             sub: user_id.to_string(),
             email: "test@example.com".to_string(),
             school_id: None,
+            group_id: None,
+            login_id: None,
             role_ids: vec![],
             permissions: vec![],
             exp: 9999999999,
@@ -551,6 +1390,8 @@ mod tests {
             sub: Uuid::new_v4().to_string(),
             email: "test@example.com".to_string(),
             school_id: Some(school_uuid),
+            group_id: None,
+            login_id: None,
             role_ids: vec![],
             permissions: vec![],
             exp: 9999999999,
@@ -567,6 +1408,8 @@ mod tests {
             sub: Uuid::new_v4().to_string(),
             email: "admin@example.com".to_string(),
             school_id: None,
+            group_id: None,
+            login_id: None,
             role_ids: vec![],
             permissions: vec![],
             exp: 9999999999,
@@ -583,6 +1426,8 @@ mod tests {
             sub: Uuid::new_v4().to_string(),
             email: "user@test.com".to_string(),
             school_id: None,
+            group_id: None,
+            login_id: None,
             role_ids: vec![],
             permissions: vec![],
             exp: 9999999999,
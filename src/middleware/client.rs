@@ -0,0 +1,103 @@
+//! Client details recorded against sign-in sessions.
+//!
+//! [`RequestClient`] reads the user agent and client IP of a request so the
+//! session it starts or refreshes shows where it is being used. The IP comes
+//! from `X-Forwarded-For` or `X-Real-IP` when a proxy sets them, matching the
+//! request logs, and otherwise from the connection. It is shown to the user
+//! and never used for access decisions, so a spoofed header only mislabels
+//! the caller's own session.
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::{HeaderMap, header, request::Parts};
+
+use chalkbyte_cache::SessionClient;
+
+/// Longest user agent kept; longer ones are cut to this many characters.
+const MAX_USER_AGENT_LEN: usize = 512;
+
+/// Extractor for the client making the request. Never rejects.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestClient(pub SessionClient);
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for RequestClient {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let user_agent = header_str(&parts.headers, header::USER_AGENT.as_str())
+            .map(|ua| ua.chars().take(MAX_USER_AGENT_LEN).collect());
+
+        let ip_address = header_str(&parts.headers, "x-forwarded-for")
+            .and_then(|v| v.split(',').next())
+            .map(str::trim)
+            .or_else(|| header_str(&parts.headers, "x-real-ip"))
+            .filter(|ip| ip.parse::<std::net::IpAddr>().is_ok())
+            .map(str::to_string)
+            .or_else(|| {
+                parts
+                    .extensions
+                    .get::<ConnectInfo<SocketAddr>>()
+                    .map(|ConnectInfo(addr)| addr.ip().to_string())
+            });
+
+        Ok(Self(SessionClient {
+            user_agent,
+            ip_address,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    async fn extract(request: Request<()>) -> SessionClient {
+        let (mut parts, _) = request.into_parts();
+        RequestClient::from_request_parts(&mut parts, &())
+            .await
+            .unwrap()
+            .0
+    }
+
+    #[tokio::test]
+    async fn test_reads_forwarded_ip_and_user_agent() {
+        let client = extract(
+            Request::builder()
+                .header("user-agent", "Chalkbyte/2.1 (iOS 18)")
+                .header("x-forwarded-for", "203.0.113.7, 10.0.0.1")
+                .body(())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(client.user_agent.as_deref(), Some("Chalkbyte/2.1 (iOS 18)"));
+        assert_eq!(client.ip_address.as_deref(), Some("203.0.113.7"));
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_connection_address() {
+        let mut request = Request::builder()
+            .header("x-forwarded-for", "not-an-ip")
+            .body(())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([198, 51, 100, 4], 50000))));
+
+        let client = extract(request).await;
+
+        assert_eq!(client.user_agent, None);
+        assert_eq!(client.ip_address.as_deref(), Some("198.51.100.4"));
+    }
+}
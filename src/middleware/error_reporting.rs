@@ -0,0 +1,99 @@
+//! Panic capture and error reporting.
+//!
+//! Panics in handlers are caught and turned into the usual 500 error
+//! response instead of dropping the connection. Panics and every other
+//! server error response are passed to
+//! [`chalkbyte_observability::error_reporting::report`] with the route,
+//! request ID, and caller, for Sentry or whichever reporter is installed.
+
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use futures_util::FutureExt;
+use uuid::Uuid;
+
+use chalkbyte_auth::verify_token;
+use chalkbyte_core::AppError;
+use chalkbyte_core::errors::ErrorDetail;
+use chalkbyte_core::request_context::current_request_id;
+use chalkbyte_observability::error_reporting::{self, ErrorKind, ErrorReport};
+
+use crate::state::AppState;
+
+/// The message a panic was raised with.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+/// Middleware that catches panics and reports server errors.
+pub async fn report_errors(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let method = req.method().to_string();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_owned())
+        .unwrap_or_else(|| req.uri().path().to_owned());
+    let authorization = req.headers().get(header::AUTHORIZATION).cloned();
+
+    let (kind, response) = match AssertUnwindSafe(next.run(req)).catch_unwind().await {
+        Ok(response) if response.status().is_server_error() => (ErrorKind::ServerError, response),
+        Ok(response) => return response,
+        Err(payload) => {
+            let message = panic_message(payload.as_ref());
+            let response =
+                AppError::internal(anyhow::anyhow!("Handler panicked: {message}")).into_response();
+            (ErrorKind::Panic, response)
+        }
+    };
+
+    // The token is only decoded for requests that get reported
+    let claims = authorization
+        .as_ref()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .and_then(|token| verify_token(token, &state.jwt_config).ok());
+
+    error_reporting::report(&ErrorReport {
+        kind,
+        message: response
+            .extensions()
+            .get::<ErrorDetail>()
+            .map(|detail| detail.0.clone())
+            .unwrap_or_else(|| response.status().to_string()),
+        status: response.status().as_u16(),
+        method,
+        route,
+        request_id: current_request_id(),
+        user_id: claims.as_ref().and_then(|c| Uuid::parse_str(&c.sub).ok()),
+        school_id: claims.and_then(|c| c.school_id),
+    });
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panic_message() {
+        let payload: Box<dyn Any + Send> = Box::new("boom");
+        assert_eq!(panic_message(payload.as_ref()), "boom");
+
+        let payload: Box<dyn Any + Send> = Box::new(format!("index {}", 3));
+        assert_eq!(panic_message(payload.as_ref()), "index 3");
+
+        let payload: Box<dyn Any + Send> = Box::new(42);
+        assert_eq!(panic_message(payload.as_ref()), "Box<dyn Any>");
+    }
+}
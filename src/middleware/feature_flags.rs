@@ -0,0 +1,125 @@
+//! Feature flag extractor and middleware.
+//!
+//! [`Flags`] loads the flags in effect for the caller's school, so handlers
+//! can branch on a flag:
+//!
+//! ```ignore
+//! use crate::middleware::feature_flags::Flags;
+//!
+//! async fn handler(flags: Flags) -> Result<impl IntoResponse, AppError> {
+//!     if flags.is_enabled("new_gradebook") {
+//!         // ...
+//!     }
+//!     flags.require("attendance")?;
+//!     // ...
+//! }
+//! ```
+//!
+//! A whole router can be gated with [`require_feature`] instead, see
+//! [`require_attendance_enabled`].
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use chalkbyte_core::{AppError, ErrorCode};
+use chalkbyte_models::feature_flags::EffectiveFeatureFlags;
+
+use crate::middleware::auth::AuthContext;
+use crate::modules::feature_flags::service::FeatureFlagService;
+use crate::state::AppState;
+
+/// Flag gating the attendance module.
+pub const ATTENDANCE: &str = "attendance";
+
+/// The feature flags in effect for the caller.
+///
+/// Authenticated callers get their school's flags, with its overrides
+/// applied; system admins and unauthenticated requests get each flag's
+/// default. Flags are loaded at most once per request and are cached in
+/// Redis when it is connected.
+///
+/// # Errors
+///
+/// Returns `401 Unauthorized` if an `Authorization` header is present but
+/// invalid.
+#[derive(Debug, Clone)]
+pub struct Flags(pub EffectiveFeatureFlags);
+
+impl Flags {
+    /// Whether the flag is on; unknown flags are off.
+    #[must_use]
+    pub fn is_enabled(&self, key: &str) -> bool {
+        self.0.is_enabled(key)
+    }
+
+    /// Fails with `403 FEATURE_DISABLED` unless the flag is on.
+    pub fn require(&self, key: &str) -> Result<(), AppError> {
+        if self.is_enabled(key) {
+            Ok(())
+        } else {
+            Err(AppError::from_code(ErrorCode::FeatureDisabled))
+        }
+    }
+}
+
+impl FromRequestParts<AppState> for Flags {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(flags) = parts.extensions.get::<Flags>() {
+            return Ok(flags.clone());
+        }
+
+        let school_id = if parts.headers.contains_key(header::AUTHORIZATION) {
+            AuthContext::from_request_parts(parts, state)
+                .await?
+                .school_id()
+        } else {
+            None
+        };
+
+        let flags = Flags(
+            FeatureFlagService::effective_flags(&state.db, state.cache.as_ref(), school_id).await?,
+        );
+        parts.extensions.insert(flags.clone());
+
+        Ok(flags)
+    }
+}
+
+/// Middleware function that rejects requests unless the flag is on for the
+/// caller's school.
+pub async fn require_feature(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+    key: &str,
+) -> Result<Response, AppError> {
+    let (mut parts, body) = req.into_parts();
+
+    Flags::from_request_parts(&mut parts, &state)
+        .await?
+        .require(key)?;
+
+    Ok(next.run(Request::from_parts(parts, body)).await)
+}
+
+/// Middleware that rejects attendance requests from schools the
+/// `attendance` flag is off for.
+pub async fn require_attendance_enabled(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    match require_feature(State(state), req, next, ATTENDANCE).await {
+        Ok(response) => response,
+        Err(err) => err.into_response(),
+    }
+}
@@ -5,7 +5,13 @@
 //!
 //! # Modules
 //!
+//! - [`api_version`]: Versioned API mounts and the deprecated `/api` alias
 //! - [`auth`]: Authentication extractors and permission-based access control
+//! - [`client`]: User agent and IP of the client, recorded on sessions
+//! - [`error_reporting`]: Panic capture and server error reporting
+//! - [`feature_flags`]: Feature flag extractor and per-school feature gating
+//! - [`rate_limit`]: Per-user, per-school, and per-route rate limits in Redis
+//! - [`request_id`]: Request ID assignment and write/error request logging
 //! - [`role`]: Role checking utilities and system role helpers
 //!
 //! # Authentication Flow
@@ -49,7 +55,13 @@
 //! }
 //! ```
 
+pub mod api_version;
 pub mod auth;
-pub mod role;
+pub mod client;
+pub mod error_reporting;
+pub mod feature_flags;
 #[cfg(not(feature = "observability"))]
 pub mod observability_stubs;
+pub mod rate_limit;
+pub mod request_id;
+pub mod role;
@@ -0,0 +1,168 @@
+//! Per-user, per-school, and per-route rate limits kept in Redis.
+//!
+//! The per-IP governor layers in the router only see the requests reaching
+//! one instance. This middleware counts requests in Redis instead, so limits
+//! hold across instances, and it counts them per caller:
+//!
+//! - every authenticated user has a bucket of their own,
+//! - every school has a bucket shared by all of its users, and
+//! - login and password reset have tighter buckets per client IP, and
+//! - sending messages has a tighter bucket per user, against spam.
+//!
+//! Responses carry `RateLimit-Limit`, `RateLimit-Remaining`, and
+//! `RateLimit-Reset` for the bucket closest to its limit. A request over a
+//! limit gets `429 Too Many Requests` with `Retry-After`. Without Redis, or
+//! while it is degraded, requests are not limited here.
+
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts, Request, State},
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use chalkbyte_cache::{RateLimit, RateLimitBucket, keys};
+use chalkbyte_core::{AppError, ErrorCode};
+
+use crate::config::rate_limit::RateLimitConfig;
+use crate::middleware::auth::AuthContext;
+use crate::state::AppState;
+
+/// The per-client bucket of routes with their own limit, by path below
+/// `/api`.
+fn route_limit(config: &RateLimitConfig, path: &str) -> Option<(&'static str, RateLimit)> {
+    match path {
+        "/auth/login" => Some(("login", config.login_limit())),
+        "/auth/forgot-password" | "/auth/reset-password" => {
+            Some(("password-reset", config.password_reset_limit()))
+        }
+        _ => None,
+    }
+}
+
+/// The per-user bucket of routes with their own limit, by method and path
+/// below `/api`.
+fn user_route_limit(
+    config: &RateLimitConfig,
+    method: &Method,
+    path: &str,
+) -> Option<(&'static str, RateLimit)> {
+    if method != Method::POST {
+        return None;
+    }
+    let sends_message = match path.strip_prefix("/messaging/conversations") {
+        Some(rest) => {
+            rest.is_empty() || rest.ends_with("/messages") || rest.ends_with("/attachments")
+        }
+        None => false,
+    };
+    sends_message.then(|| ("messages", config.message_limit()))
+}
+
+/// Middleware that counts each request against its caller's buckets.
+///
+/// Route buckets are keyed by the connection's peer address, like the
+/// governor layers, so they are skipped when it is unknown.
+pub async fn rate_limit(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let Some(cache) = state.cache.as_ref() else {
+        return next.run(req).await;
+    };
+    let config = &state.rate_limit_config;
+
+    let (mut parts, body) = req.into_parts();
+    let mut buckets = Vec::new();
+
+    if let Some((route, limit)) = route_limit(config, parts.uri.path())
+        && let Some(ConnectInfo(addr)) = parts.extensions.get::<ConnectInfo<SocketAddr>>()
+    {
+        let client = addr.ip().to_string();
+        buckets.push(RateLimitBucket::new(
+            keys::rate_limit::route(route, &client),
+            limit,
+        ));
+    }
+
+    if let Ok(context) = AuthContext::from_request_parts(&mut parts, &state).await {
+        if let Some((route, limit)) = user_route_limit(config, &parts.method, parts.uri.path()) {
+            buckets.push(RateLimitBucket::new(
+                keys::rate_limit::route(route, &context.user_id().to_string()),
+                limit,
+            ));
+        }
+        buckets.push(RateLimitBucket::new(
+            keys::rate_limit::user(context.user_id().into()),
+            config.user_limit(),
+        ));
+        if let Some(school_id) = context.school_id() {
+            buckets.push(RateLimitBucket::new(
+                keys::rate_limit::school(school_id.into()),
+                config.school_limit(),
+            ));
+        }
+    }
+
+    let status = cache.check_rate_limits(&buckets).await;
+
+    let mut response = match status {
+        Some(status) if !status.allowed => {
+            AppError::from_code(ErrorCode::TooManyRequests).into_response()
+        }
+        _ => next.run(Request::from_parts(parts, body)).await,
+    };
+    if let Some(status) = status {
+        status.apply_headers(response.headers_mut());
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_limits() {
+        let config = RateLimitConfig::default();
+
+        assert_eq!(
+            route_limit(&config, "/auth/login"),
+            Some(("login", config.login_limit()))
+        );
+        assert_eq!(
+            route_limit(&config, "/auth/reset-password").map(|(route, _)| route),
+            route_limit(&config, "/auth/forgot-password").map(|(route, _)| route)
+        );
+        assert_eq!(route_limit(&config, "/users"), None);
+    }
+
+    #[test]
+    fn test_user_route_limits() {
+        let config = RateLimitConfig::default();
+        let id = uuid::Uuid::new_v4();
+
+        for path in [
+            "/messaging/conversations".to_string(),
+            format!("/messaging/conversations/{id}/messages"),
+            format!("/messaging/conversations/{id}/attachments"),
+        ] {
+            assert_eq!(
+                user_route_limit(&config, &Method::POST, &path),
+                Some(("messages", config.message_limit()))
+            );
+        }
+        assert_eq!(
+            user_route_limit(&config, &Method::GET, "/messaging/conversations"),
+            None
+        );
+        assert_eq!(
+            user_route_limit(
+                &config,
+                &Method::POST,
+                &format!("/messaging/conversations/{id}/read")
+            ),
+            None
+        );
+    }
+}
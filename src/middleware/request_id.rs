@@ -0,0 +1,177 @@
+//! Request ID assignment and request logging.
+//!
+//! Every request runs with an ID taken from a valid `X-Request-Id` header or
+//! generated as a UUID. The ID is echoed in the `X-Request-Id` response
+//! header and is available to error responses, outgoing emails, and the
+//! database pool through [`chalkbyte_core::request_context`]. The client's
+//! preferred language from `Accept-Language` is made available the same way,
+//! for localized error messages.
+//!
+//! Write requests and failed requests leave a log summary, recorded in the
+//! background so the response is not held up, which system admins look up
+//! with `GET /api/admin/trace/{id}`.
+
+use std::time::Instant;
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue, Method, header},
+    middleware::Next,
+    response::Response,
+};
+use tracing::warn;
+
+use chalkbyte_auth::verify_token;
+use chalkbyte_core::errors::ErrorDetail;
+use chalkbyte_core::request_context;
+use chalkbyte_models::ids::{SchoolId, UserId};
+use uuid::Uuid;
+
+use crate::modules::traces::model::NewRequestLog;
+use crate::modules::traces::service::TraceService;
+use crate::state::AppState;
+
+/// Header carrying the request ID in both directions.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest client-supplied request ID accepted.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Returns the client's request ID if it is safe to echo and store.
+///
+/// IDs longer than [`MAX_REQUEST_ID_LEN`] or containing anything but ASCII
+/// letters, digits, `-`, `_`, `.`, and `:` are replaced with a generated one,
+/// since the ID ends up in headers, logs, and HTML email bodies.
+fn client_request_id(value: &HeaderValue) -> Option<String> {
+    let id = value.to_str().ok()?;
+    let valid = !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"-_.:".contains(&b));
+    valid.then(|| id.to_string())
+}
+
+/// Returns the first language tag of an `Accept-Language` header.
+///
+/// Quality values are not weighed: clients list their preferred language
+/// first. The wildcard `*` means no preference.
+fn preferred_locale(value: &HeaderValue) -> Option<String> {
+    let tag = value
+        .to_str()
+        .ok()?
+        .split(',')
+        .next()?
+        .split(';')
+        .next()?
+        .trim();
+    let valid = !tag.is_empty()
+        && tag != "*"
+        && tag.len() <= 35
+        && tag.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-');
+    valid.then(|| tag.to_string())
+}
+
+/// Middleware that assigns the request ID and logs write and failed requests.
+pub async fn assign_request_id(
+    State(state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let start = Instant::now();
+    let request_id = req
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(client_request_id)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    // Downstream logging middleware reads the ID from the header
+    let header_value = HeaderValue::from_str(&request_id).expect("request ID is plain ASCII");
+    req.headers_mut()
+        .insert(REQUEST_ID_HEADER, header_value.clone());
+
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let authorization = req.headers().get(header::AUTHORIZATION).cloned();
+    let locale = req
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(preferred_locale);
+
+    let mut response = request_context::scope(request_id.clone(), async move {
+        match locale {
+            Some(locale) => request_context::scope_locale(locale, next.run(req)).await,
+            None => next.run(req).await,
+        }
+    })
+    .await;
+    response
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, header_value);
+
+    let status = response.status();
+    let is_write = !matches!(method, Method::GET | Method::HEAD | Method::OPTIONS);
+    if !is_write && !status.is_client_error() && !status.is_server_error() {
+        return response;
+    }
+
+    // The token is only decoded for requests that get logged
+    let claims = authorization
+        .as_ref()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .and_then(|token| verify_token(token, &state.jwt_config).ok());
+
+    let log = NewRequestLog {
+        request_id,
+        method: method.to_string(),
+        path,
+        status: status.as_u16(),
+        latency_ms: i64::try_from(start.elapsed().as_millis()).unwrap_or(i64::MAX),
+        user_id: claims
+            .as_ref()
+            .and_then(|c| Uuid::parse_str(&c.sub).ok())
+            .map(UserId::from),
+        login_user_id: claims.as_ref().and_then(|c| c.login_id).map(UserId::from),
+        school_id: claims.and_then(|c| c.school_id).map(SchoolId::from),
+        error: response
+            .extensions()
+            .get::<ErrorDetail>()
+            .map(|detail| detail.0.clone()),
+    };
+
+    let db = state.db.clone();
+    tokio::spawn(async move {
+        if let Err(e) = TraceService::record_request(&db, log).await {
+            warn!(error = %e.error, "Failed to record request log");
+        }
+    });
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_request_id_validation() {
+        let id = |value: &str| client_request_id(&HeaderValue::from_str(value).unwrap());
+
+        assert_eq!(id("abc-123").as_deref(), Some("abc-123"));
+        assert_eq!(id(""), None);
+        assert_eq!(id("has space"), None);
+        assert_eq!(id("<b>"), None);
+        assert_eq!(id(&"a".repeat(MAX_REQUEST_ID_LEN + 1)), None);
+    }
+
+    #[test]
+    fn test_preferred_locale() {
+        let locale = |value: &str| preferred_locale(&HeaderValue::from_str(value).unwrap());
+
+        assert_eq!(locale("fr-CA,fr;q=0.9,en;q=0.8").as_deref(), Some("fr-CA"));
+        assert_eq!(locale("pt;q=0.7").as_deref(), Some("pt"));
+        assert_eq!(locale("*"), None);
+        assert_eq!(locale("<script>"), None);
+    }
+}
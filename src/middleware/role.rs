@@ -5,16 +5,18 @@
 //! most authorization checks can be done without database queries.
 //!
 //! For operations that require fresh permission data (e.g., after role changes),
-//! use the database-backed functions explicitly.
+//! use the database-backed functions explicitly. The middleware and extractors
+//! here read fresh roles through the request's [`AuthContext`], so they hit the
+//! database at most once per request however many of them run.
 
 use axum::{
     extract::{FromRequestParts, Request, State},
-    http::request::Parts,
+    http::{Method, request::Parts},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 
-use crate::middleware::auth::AuthUser;
+use crate::middleware::auth::{AuthContext, AuthUser};
 use crate::modules::roles::service as roles_service;
 use crate::modules::users::model::system_roles;
 use chalkbyte_core::AppError;
@@ -23,9 +25,6 @@ use chalkbyte_models::ids::{RoleId, SchoolId, UserId};
 use crate::modules::users::service::UserService;
 use crate::state::AppState;
 
-// Re-export auth helpers for convenience
-pub use crate::utils::auth_helpers::get_admin_school_id;
-
 // ============================================================================
 // JWT-Based Permission Checking (No DB queries - uses embedded permissions)
 // ============================================================================
@@ -91,12 +90,11 @@ pub async fn require_roles(
 ) -> Result<Response, AppError> {
     let (mut parts, body) = req.into_parts();
 
-    let auth_user = AuthUser::from_request_parts(&mut parts, &state).await?;
-
-    let user_id = auth_user.user_id()?;
+    let context = AuthContext::from_request_parts(&mut parts, &state).await?;
 
-    // Check if user has any of the allowed roles (from database for fresh data)
-    let has_role = UserService::user_has_any_role(&state.db, user_id, &allowed_role_ids).await?;
+    // Check if user has any of the allowed roles (from database for fresh data,
+    // loaded once per request)
+    let has_role = context.has_any_role(&state.db, &allowed_role_ids).await?;
 
     if !has_role {
         return Err(AppError::forbidden(
@@ -119,13 +117,10 @@ pub async fn require_permission(
 ) -> Result<Response, AppError> {
     let (mut parts, body) = req.into_parts();
 
-    let auth_user = AuthUser::from_request_parts(&mut parts, &state).await?;
-
-    let user_id = auth_user.user_id()?;
+    let context = AuthContext::from_request_parts(&mut parts, &state).await?;
 
-    // Check permission from database for fresh data
-    let has_permission =
-        roles_service::user_has_permission(&state.db, user_id, permission_name).await?;
+    // Check permission from database for fresh data, loaded once per request
+    let has_permission = context.has_permission(&state.db, permission_name).await?;
 
     if !has_permission {
         return Err(AppError::forbidden(format!(
@@ -143,7 +138,6 @@ pub async fn require_permission(
 // ============================================================================
 
 /// Helper function for system admin only routes
-#[allow(dead_code)]
 pub async fn require_system_admin(
     State(state): State<AppState>,
     req: Request,
@@ -155,13 +149,39 @@ pub async fn require_system_admin(
     }
 }
 
-/// Helper function for school admin routes (both SystemAdmin and Admin allowed)
+/// Adds the Auditor role to a route's allowed roles for read-only requests.
+///
+/// Auditors pass the role gate of any school route with `GET` or `HEAD`;
+/// the handler's permission extractor still decides what they may read.
+fn with_auditor_reads(req: &Request, mut allowed_role_ids: Vec<RoleId>) -> Vec<RoleId> {
+    if matches!(*req.method(), Method::GET | Method::HEAD) {
+        allowed_role_ids.push(system_roles::AUDITOR);
+    }
+    allowed_role_ids
+}
+
+/// Helper function for school admin routes (both SystemAdmin and Admin
+/// allowed, and Auditor for reads)
 pub async fn require_admin(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let allowed_role_ids =
+        with_auditor_reads(&req, vec![system_roles::SYSTEM_ADMIN, system_roles::ADMIN]);
+    match require_roles(State(state), req, next, allowed_role_ids).await {
+        Ok(response) => response,
+        Err(err) => err.into_response(),
+    }
+}
+
+/// Helper function for school group routes (SystemAdmin and GroupAdmin allowed)
+pub async fn require_group_admin(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
     match require_roles(
         State(state),
         req,
         next,
-        vec![system_roles::SYSTEM_ADMIN, system_roles::ADMIN],
+        vec![system_roles::SYSTEM_ADMIN, system_roles::GROUP_ADMIN],
     )
     .await
     {
@@ -170,21 +190,18 @@ pub async fn require_admin(State(state): State<AppState>, req: Request, next: Ne
     }
 }
 
-/// Helper function for teacher routes (SystemAdmin, Admin, and Teacher allowed)
-#[allow(dead_code)]
+/// Helper function for teacher routes (SystemAdmin, Admin, and Teacher
+/// allowed, and Auditor for reads)
 pub async fn require_teacher(State(state): State<AppState>, req: Request, next: Next) -> Response {
-    match require_roles(
-        State(state),
-        req,
-        next,
+    let allowed_role_ids = with_auditor_reads(
+        &req,
         vec![
             system_roles::SYSTEM_ADMIN,
             system_roles::ADMIN,
             system_roles::TEACHER,
         ],
-    )
-    .await
-    {
+    );
+    match require_roles(State(state), req, next, allowed_role_ids).await {
         Ok(response) => response,
         Err(err) => err.into_response(),
     }
@@ -215,8 +232,11 @@ impl FromRequestParts<AppState> for RequireSystemAdmin {
             return Ok(RequireSystemAdmin(user_id));
         }
 
-        // Fallback to database check for fresh data
-        let is_sys_admin = UserService::is_system_admin(&state.db, user_id).await?;
+        // Fallback to the request's fresh roles from the database
+        let context = AuthContext::from_request_parts(parts, state).await?;
+        let is_sys_admin = context
+            .has_any_role(&state.db, &[system_roles::SYSTEM_ADMIN])
+            .await?;
 
         if !is_sys_admin {
             return Err(AppError::forbidden(
@@ -249,13 +269,14 @@ impl FromRequestParts<AppState> for RequireAdmin {
             return Ok(RequireAdmin(user_id));
         }
 
-        // Fallback to database check for fresh data
-        let has_role = UserService::user_has_any_role(
-            &state.db,
-            user_id,
-            &[system_roles::SYSTEM_ADMIN, system_roles::ADMIN],
-        )
-        .await?;
+        // Fallback to the request's fresh roles from the database
+        let context = AuthContext::from_request_parts(parts, state).await?;
+        let has_role = context
+            .has_any_role(
+                &state.db,
+                &[system_roles::SYSTEM_ADMIN, system_roles::ADMIN],
+            )
+            .await?;
 
         if !has_role {
             return Err(AppError::forbidden(
@@ -292,17 +313,18 @@ impl FromRequestParts<AppState> for RequireTeacher {
             return Ok(RequireTeacher(user_id));
         }
 
-        // Fallback to database check for fresh data
-        let has_role = UserService::user_has_any_role(
-            &state.db,
-            user_id,
-            &[
-                system_roles::SYSTEM_ADMIN,
-                system_roles::ADMIN,
-                system_roles::TEACHER,
-            ],
-        )
-        .await?;
+        // Fallback to the request's fresh roles from the database
+        let context = AuthContext::from_request_parts(parts, state).await?;
+        let has_role = context
+            .has_any_role(
+                &state.db,
+                &[
+                    system_roles::SYSTEM_ADMIN,
+                    system_roles::ADMIN,
+                    system_roles::TEACHER,
+                ],
+            )
+            .await?;
 
         if !has_role {
             return Err(AppError::forbidden(
@@ -367,6 +389,13 @@ pub fn is_system_admin_jwt(auth_user: &AuthUser) -> bool {
     auth_user.has_role(&system_roles::SYSTEM_ADMIN)
 }
 
+/// Check if user is a group admin using JWT claims (fast, no DB)
+///
+/// Group admins act only on the member schools of their group.
+pub fn is_group_admin_jwt(auth_user: &AuthUser) -> bool {
+    auth_user.has_role(&system_roles::GROUP_ADMIN) && !is_system_admin_jwt(auth_user)
+}
+
 /// Check if user is an admin (school admin or system admin) using database
 #[allow(dead_code)]
 pub async fn is_admin(db: &sqlx::PgPool, user_id: UserId) -> Result<bool, AppError> {
@@ -379,11 +408,17 @@ pub async fn is_admin(db: &sqlx::PgPool, user_id: UserId) -> Result<bool, AppErr
 }
 
 /// Check if user is an admin using JWT claims (fast, no DB)
-#[allow(dead_code)]
 pub fn is_admin_jwt(auth_user: &AuthUser) -> bool {
     auth_user.has_any_role(&[system_roles::SYSTEM_ADMIN, system_roles::ADMIN])
 }
 
+/// Check if user is a teacher without admin rights using JWT claims (fast, no DB)
+///
+/// Such teachers only act on the branches they are assigned to.
+pub fn is_branch_scoped_teacher_jwt(auth_user: &AuthUser) -> bool {
+    auth_user.has_role(&system_roles::TEACHER) && !is_admin_jwt(auth_user)
+}
+
 /// Check if user is at least a teacher (teacher, admin, or system admin) using database
 #[allow(dead_code)]
 pub async fn is_teacher_or_above(db: &sqlx::PgPool, user_id: UserId) -> Result<bool, AppError> {
@@ -427,6 +462,8 @@ mod tests {
             sub: Uuid::new_v4().to_string(),
             email: "test@example.com".to_string(),
             school_id: None,
+            group_id: None,
+            login_id: None,
             role_ids: uuid_role_ids,
             permissions,
             exp: 9999999999,
@@ -470,6 +507,8 @@ mod tests {
             sub: user_id.to_string(),
             email: "test@example.com".to_string(),
             school_id: None,
+            group_id: None,
+            login_id: None,
             role_ids: vec![],
             permissions: vec![],
             exp: 9999999999,
@@ -488,6 +527,8 @@ mod tests {
             sub: "not-a-uuid".to_string(),
             email: "test@example.com".to_string(),
             school_id: None,
+            group_id: None,
+            login_id: None,
             role_ids: vec![],
             permissions: vec![],
             exp: 9999999999,
@@ -520,6 +561,39 @@ mod tests {
         assert!(!is_admin_jwt(&teacher));
     }
 
+    #[test]
+    fn test_with_auditor_reads() {
+        let admin_roles = vec![system_roles::SYSTEM_ADMIN, system_roles::ADMIN];
+        let request = |method| {
+            Request::builder()
+                .method(method)
+                .body(axum::body::Body::empty())
+                .unwrap()
+        };
+
+        let allowed = with_auditor_reads(&request(Method::GET), admin_roles.clone());
+        assert!(allowed.contains(&system_roles::AUDITOR));
+
+        let allowed = with_auditor_reads(&request(Method::POST), admin_roles);
+        assert!(!allowed.contains(&system_roles::AUDITOR));
+    }
+
+    #[test]
+    fn test_is_group_admin_jwt() {
+        let group_admin = create_test_auth_user(vec![system_roles::GROUP_ADMIN], vec![]);
+        assert!(is_group_admin_jwt(&group_admin));
+        assert!(!is_admin_jwt(&group_admin));
+
+        let sys_admin = create_test_auth_user(
+            vec![system_roles::SYSTEM_ADMIN, system_roles::GROUP_ADMIN],
+            vec![],
+        );
+        assert!(!is_group_admin_jwt(&sys_admin));
+
+        let admin = create_test_auth_user(vec![system_roles::ADMIN], vec![]);
+        assert!(!is_group_admin_jwt(&admin));
+    }
+
     #[test]
     fn test_is_teacher_or_above_jwt() {
         let sys_admin = create_test_auth_user(vec![system_roles::SYSTEM_ADMIN], vec![]);
@@ -569,6 +643,8 @@ mod tests {
             sub: Uuid::new_v4().to_string(),
             email: "test@example.com".to_string(),
             school_id: Some(school_uuid),
+            group_id: None,
+            login_id: None,
             role_ids: vec![system_roles::ADMIN.into_inner()],
             permissions: vec![],
             exp: 9999999999,
@@ -585,6 +661,8 @@ mod tests {
             sub: Uuid::new_v4().to_string(),
             email: "sysadmin@example.com".to_string(),
             school_id: None,
+            group_id: None,
+            login_id: None,
             role_ids: vec![system_roles::SYSTEM_ADMIN.into_inner()],
             permissions: vec![],
             exp: 9999999999,
@@ -0,0 +1,157 @@
+//! Mock mode for local frontend development.
+//!
+//! Running `chalkbyte --mock` (built with the `mock` feature) serves the full
+//! API without a Postgres or Redis install:
+//!
+//! - a throwaway PostgreSQL instance is started in a temporary directory,
+//!   downloading the server binaries to `~/.theseus` on first use, or using
+//!   an existing installation named by `MOCK_POSTGRES_DIR` (e.g.
+//!   `/usr/lib/postgresql/16`) when offline
+//! - migrations run and the seeder's demo profile is loaded
+//! - a system admin is created so every role can be signed into
+//! - Redis is skipped, so caching is off and tokens live in the database
+//!
+//! Everything is discarded when the server stops. The API itself runs
+//! unchanged, so responses have the same shapes as in production.
+//!
+//! ```bash
+//! cargo run --features mock -- --mock
+//! ```
+
+use chalkbyte_cli::seeder::{self, SeedConfig};
+use chalkbyte_config::ChalkbyteConfig;
+use chalkbyte_core::hash_password;
+use chalkbyte_db::{DbConfig, DbPools, PgPool};
+use chalkbyte_models::users::system_roles;
+use postgresql_embedded::{PostgreSQL, Settings};
+use sqlx::Row;
+
+use crate::state::{AppState, build_app_state};
+
+/// Command-line flag selecting mock mode.
+pub const MOCK_FLAG: &str = "--mock";
+
+/// Password shared by every mock account, matching the seeder's.
+pub const MOCK_PASSWORD: &str = "Password@123";
+
+/// Email of the mock system admin.
+pub const MOCK_SYSTEM_ADMIN_EMAIL: &str = "sysadmin@example.com";
+
+/// Name of the database created inside the throwaway server.
+const MOCK_DATABASE: &str = "chalkbyte";
+
+/// A running mock database.
+///
+/// The PostgreSQL server stops and its data is deleted when this is dropped,
+/// so it must outlive the HTTP server.
+pub struct MockDatabase {
+    _server: PostgreSQL,
+}
+
+/// Whether mock mode was requested on the command line.
+pub fn requested() -> bool {
+    std::env::args().skip(1).any(|arg| arg == MOCK_FLAG)
+}
+
+/// Starts the mock database and builds the application state around it.
+///
+/// `config.database` is ignored; every other setting applies as usual.
+///
+/// # Panics
+///
+/// Panics if the database cannot be started, migrated, or seeded.
+pub async fn init_mock_state(config: &ChalkbyteConfig) -> (AppState, MockDatabase) {
+    println!("🧪 Starting mock mode (throwaway PostgreSQL, no Redis)...");
+
+    let mut settings = Settings::default();
+    if let Ok(dir) = std::env::var("MOCK_POSTGRES_DIR") {
+        settings.installation_dir = dir.into();
+        settings.trust_installation_dir = true;
+    }
+
+    let mut server = PostgreSQL::new(settings);
+    server
+        .setup()
+        .await
+        .expect("Failed to install embedded PostgreSQL");
+    server
+        .start()
+        .await
+        .expect("Failed to start embedded PostgreSQL");
+    server
+        .create_database(MOCK_DATABASE)
+        .await
+        .expect("Failed to create mock database");
+
+    let db = chalkbyte_db::init_db_pool(&DbConfig {
+        database_url: server.settings().url(MOCK_DATABASE),
+        ..DbConfig::default()
+    })
+    .await
+    .expect("Failed to connect to mock database");
+
+    chalkbyte_db::run_migrations(&db)
+        .await
+        .expect("Failed to run migrations on mock database");
+
+    seeder::seed_all(&db, SeedConfig::demo())
+        .await
+        .unwrap_or_else(|e| panic!("Failed to seed mock database: {}", e));
+
+    create_system_admin(&db)
+        .await
+        .expect("Failed to create mock system admin");
+    print_accounts(&db).await;
+
+    let state = build_app_state(DbPools::new(db), None, config);
+
+    (state, MockDatabase { _server: server })
+}
+
+async fn create_system_admin(db: &PgPool) -> Result<(), sqlx::Error> {
+    let password = hash_password(MOCK_PASSWORD).expect("Failed to hash mock password");
+
+    let mut tx = db.begin().await?;
+
+    let user_id = sqlx::query_scalar::<_, uuid::Uuid>(
+        "INSERT INTO users (first_name, last_name, email, password, school_id)
+         VALUES ('System', 'Admin', $1, $2, NULL)
+         RETURNING id",
+    )
+    .bind(MOCK_SYSTEM_ADMIN_EMAIL)
+    .bind(&password)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+        .bind(user_id)
+        .bind(system_roles::SYSTEM_ADMIN)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await
+}
+
+/// Prints one account per role for signing in.
+async fn print_accounts(db: &PgPool) {
+    let rows = sqlx::query(
+        r#"
+        SELECT DISTINCT ON (r.name) r.name AS role, u.email
+        FROM users u
+        JOIN user_roles ur ON ur.user_id = u.id
+        JOIN roles r ON r.id = ur.role_id
+        WHERE r.is_system_role
+        ORDER BY r.name, u.email
+        "#,
+    )
+    .fetch_all(db)
+    .await
+    .unwrap_or_default();
+
+    println!("🔑 Mock accounts (password: {}):", MOCK_PASSWORD);
+    for row in rows {
+        let role: String = row.get("role");
+        let email: String = row.get("email");
+        println!("   - {:<14} {}", role, email);
+    }
+}
@@ -1,27 +1,25 @@
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
 };
 use tracing::instrument;
 use uuid::Uuid;
-use validator::Validate;
 
-use chalkbyte_core::AppError;
+use chalkbyte_core::{AppError, Created, NoContent};
 use chalkbyte_models::ids::AcademicSessionId;
 
 use crate::middleware::auth::{
     RequireAcademicSessionsCreate, RequireAcademicSessionsDelete, RequireAcademicSessionsRead,
     RequireAcademicSessionsUpdate,
 };
-use crate::middleware::role::is_system_admin_jwt;
 use crate::modules::academic_sessions::model::{
     AcademicSession, AcademicSessionFilterParams, AcademicSessionWithStats,
     CreateAcademicSessionDto, PaginatedAcademicSessionsResponse, UpdateAcademicSessionDto,
 };
 use crate::modules::academic_sessions::service::AcademicSessionService;
 use crate::state::AppState;
-use crate::utils::auth_helpers::{get_admin_school_id, get_school_id_for_scoped_operation};
+use crate::utils::auth_helpers::{get_school_id_for_scoped_operation, tenant_context};
+use crate::validator::ValidatedJson;
 
 /// Create a new academic session
 #[utoipa::path(
@@ -30,7 +28,7 @@ use crate::utils::auth_helpers::{get_admin_school_id, get_school_id_for_scoped_o
     summary = "Create academic session",
     request_body = CreateAcademicSessionDto,
     responses(
-        (status = 201, description = "Academic session created successfully", body = AcademicSession),
+        Created<AcademicSession>,
         (status = 400, description = "Invalid input or missing school_id for system admin"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - requires academic_sessions:create permission")
@@ -42,17 +40,15 @@ use crate::utils::auth_helpers::{get_admin_school_id, get_school_id_for_scoped_o
 pub async fn create_academic_session(
     State(state): State<AppState>,
     RequireAcademicSessionsCreate(auth_user): RequireAcademicSessionsCreate,
-    Json(dto): Json<CreateAcademicSessionDto>,
-) -> Result<(StatusCode, Json<AcademicSession>), AppError> {
+    ValidatedJson(dto): ValidatedJson<CreateAcademicSessionDto>,
+) -> Result<Created<AcademicSession>, AppError> {
     let school_id =
         get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
 
-    dto.validate()?;
-
     let session =
         AcademicSessionService::create_academic_session(&state.db, school_id, dto).await?;
 
-    Ok((StatusCode::CREATED, Json(session)))
+    Ok(Created(session))
 }
 
 /// Get all academic sessions for a school
@@ -142,18 +138,9 @@ pub async fn get_academic_session_by_id(
 ) -> Result<Json<AcademicSessionWithStats>, AppError> {
     let session_id = AcademicSessionId::from(id);
 
-    if is_system_admin_jwt(&auth_user) {
-        let session = AcademicSessionService::get_academic_session_by_id_no_school_filter(
-            &state.db, session_id,
-        )
-        .await?;
-        return Ok(Json(session));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
     let session =
-        AcademicSessionService::get_academic_session_by_id(&state.db, session_id, school_id)
-            .await?;
+        AcademicSessionService::get_academic_session_by_id(&state.db, &tenant, session_id).await?;
 
     Ok(Json(session))
 }
@@ -182,22 +169,13 @@ pub async fn update_academic_session(
     State(state): State<AppState>,
     RequireAcademicSessionsUpdate(auth_user): RequireAcademicSessionsUpdate,
     Path(id): Path<Uuid>,
-    Json(dto): Json<UpdateAcademicSessionDto>,
+    ValidatedJson(dto): ValidatedJson<UpdateAcademicSessionDto>,
 ) -> Result<Json<AcademicSession>, AppError> {
-    dto.validate()?;
     let session_id = AcademicSessionId::from(id);
 
-    if is_system_admin_jwt(&auth_user) {
-        let session = AcademicSessionService::update_academic_session_no_school_filter(
-            &state.db, session_id, dto,
-        )
-        .await?;
-        return Ok(Json(session));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
     let session =
-        AcademicSessionService::update_academic_session(&state.db, session_id, school_id, dto)
+        AcademicSessionService::update_academic_session(&state.db, &tenant, session_id, dto)
             .await?;
 
     Ok(Json(session))
@@ -212,7 +190,7 @@ pub async fn update_academic_session(
         ("id" = Uuid, Path, description = "Academic session ID")
     ),
     responses(
-        (status = 204, description = "Academic session deleted successfully"),
+        NoContent,
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - requires academic_sessions:delete permission"),
         (status = 404, description = "Academic session not found")
@@ -225,19 +203,13 @@ pub async fn delete_academic_session(
     State(state): State<AppState>,
     RequireAcademicSessionsDelete(auth_user): RequireAcademicSessionsDelete,
     Path(id): Path<Uuid>,
-) -> Result<StatusCode, AppError> {
+) -> Result<NoContent, AppError> {
     let session_id = AcademicSessionId::from(id);
 
-    if is_system_admin_jwt(&auth_user) {
-        AcademicSessionService::delete_academic_session_no_school_filter(&state.db, session_id)
-            .await?;
-        return Ok(StatusCode::NO_CONTENT);
-    }
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    AcademicSessionService::delete_academic_session(&state.db, &tenant, session_id).await?;
 
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    AcademicSessionService::delete_academic_session(&state.db, session_id, school_id).await?;
-
-    Ok(StatusCode::NO_CONTENT)
+    Ok(NoContent)
 }
 
 /// Activate an academic session
@@ -265,17 +237,9 @@ pub async fn activate_academic_session(
 ) -> Result<Json<AcademicSession>, AppError> {
     let session_id = AcademicSessionId::from(id);
 
-    if is_system_admin_jwt(&auth_user) {
-        let session = AcademicSessionService::activate_academic_session_no_school_filter(
-            &state.db, session_id,
-        )
-        .await?;
-        return Ok(Json(session));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
     let session =
-        AcademicSessionService::activate_academic_session(&state.db, session_id, school_id).await?;
+        AcademicSessionService::activate_academic_session(&state.db, &tenant, session_id).await?;
 
     Ok(Json(session))
 }
@@ -305,18 +269,9 @@ pub async fn deactivate_academic_session(
 ) -> Result<Json<AcademicSession>, AppError> {
     let session_id = AcademicSessionId::from(id);
 
-    if is_system_admin_jwt(&auth_user) {
-        let session = AcademicSessionService::deactivate_academic_session_no_school_filter(
-            &state.db, session_id,
-        )
-        .await?;
-        return Ok(Json(session));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
     let session =
-        AcademicSessionService::deactivate_academic_session(&state.db, session_id, school_id)
-            .await?;
+        AcademicSessionService::deactivate_academic_session(&state.db, &tenant, session_id).await?;
 
     Ok(Json(session))
 }
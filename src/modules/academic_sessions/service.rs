@@ -1,9 +1,10 @@
 use sqlx::PgPool;
 use tracing::instrument;
 
-use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_core::{AppError, ErrorCode, PaginationMeta};
 use chalkbyte_models::ids::{AcademicSessionId, SchoolId};
 
+use crate::config::database::{TenantContext, UnitOfWork};
 use crate::modules::academic_sessions::model::{
     AcademicSession, AcademicSessionFilterParams, AcademicSessionWithStats,
     CreateAcademicSessionDto, PaginatedAcademicSessionsResponse, UpdateAcademicSessionDto,
@@ -44,9 +45,7 @@ impl AcademicSessionService {
         .await
         .map_err(|e| {
             if let sqlx::Error::Database(db_err) = &e && db_err.is_unique_violation() {
-                return AppError::bad_request(anyhow::anyhow!(
-                    "An academic session with this name already exists in this school"
-                ));
+                return AppError::from_code(ErrorCode::AcademicSessionNameConflict);
             }
             AppError::from(e)
         })?;
@@ -121,68 +120,37 @@ impl AcademicSessionService {
         })
     }
 
-    /// Get an academic session by ID with school filtering.
+    /// Get an academic session by ID.
     #[instrument(skip(db))]
     pub async fn get_academic_session_by_id(
         db: &PgPool,
+        tenant: &TenantContext,
         session_id: AcademicSessionId,
-        school_id: SchoolId,
     ) -> Result<AcademicSessionWithStats, AppError> {
-        let session = sqlx::query_as::<_, AcademicSessionWithStats>(
-            r#"SELECT
-                s.id,
-                s.name,
-                s.description,
-                s.school_id,
-                s.start_date,
-                s.end_date,
-                s.is_active,
-                s.created_at,
-                s.updated_at,
-                COUNT(t.id) as term_count
-               FROM academic_sessions s
-               LEFT JOIN terms t ON t.academic_session_id = s.id
-               WHERE s.id = $1 AND s.school_id = $2
-               GROUP BY s.id, s.name, s.description, s.school_id, s.start_date, s.end_date, s.is_active, s.created_at, s.updated_at"#,
-        )
-        .bind(session_id)
-        .bind(school_id)
-        .fetch_optional(db)
-        .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Academic session not found")))?;
-
-        Ok(session)
-    }
-
-    /// Get an academic session by ID without school filtering (for system admins).
-    #[instrument(skip(db))]
-    pub async fn get_academic_session_by_id_no_school_filter(
-        db: &PgPool,
-        session_id: AcademicSessionId,
-    ) -> Result<AcademicSessionWithStats, AppError> {
-        let session = sqlx::query_as::<_, AcademicSessionWithStats>(
-            r#"SELECT
-                s.id,
-                s.name,
-                s.description,
-                s.school_id,
-                s.start_date,
-                s.end_date,
-                s.is_active,
-                s.created_at,
-                s.updated_at,
-                COUNT(t.id) as term_count
-               FROM academic_sessions s
-               LEFT JOIN terms t ON t.academic_session_id = s.id
-               WHERE s.id = $1
-               GROUP BY s.id, s.name, s.description, s.school_id, s.start_date, s.end_date, s.is_active, s.created_at, s.updated_at"#,
-        )
-        .bind(session_id)
-        .fetch_optional(db)
-        .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Academic session not found")))?;
-
-        Ok(session)
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            sqlx::query_as::<_, AcademicSessionWithStats>(
+                r#"SELECT
+                    s.id,
+                    s.name,
+                    s.description,
+                    s.school_id,
+                    s.start_date,
+                    s.end_date,
+                    s.is_active,
+                    s.created_at,
+                    s.updated_at,
+                    COUNT(t.id) as term_count
+                   FROM academic_sessions s
+                   LEFT JOIN terms t ON t.academic_session_id = s.id
+                   WHERE s.id = $1
+                   GROUP BY s.id, s.name, s.description, s.school_id, s.start_date, s.end_date, s.is_active, s.created_at, s.updated_at"#,
+            )
+            .bind(session_id)
+            .fetch_optional(uow.conn())
+            .await?
+            .ok_or_else(|| AppError::from_code(ErrorCode::AcademicSessionNotFound))
+        })
+        .await
     }
 
     /// Get the active academic session for a school.
@@ -223,201 +191,100 @@ impl AcademicSessionService {
     #[instrument(skip(db))]
     pub async fn update_academic_session(
         db: &PgPool,
+        tenant: &TenantContext,
         session_id: AcademicSessionId,
-        school_id: SchoolId,
         dto: UpdateAcademicSessionDto,
     ) -> Result<AcademicSession, AppError> {
-        let existing = sqlx::query_as::<_, AcademicSession>(
-            r#"SELECT id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at
-               FROM academic_sessions WHERE id = $1 AND school_id = $2"#,
-        )
-        .bind(session_id)
-        .bind(school_id)
-        .fetch_optional(db)
-        .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Academic session not found")))?;
-
-        let name = dto.name.unwrap_or(existing.name);
-        let description = if dto.description.is_some() {
-            dto.description
-        } else {
-            existing.description
-        };
-        let start_date = dto.start_date.unwrap_or(existing.start_date);
-        let end_date = dto.end_date.unwrap_or(existing.end_date);
-
-        // Validate dates
-        if start_date >= end_date {
-            return Err(AppError::bad_request(anyhow::anyhow!(
-                "Start date must be before end date"
-            )));
-        }
-
-        // Check if any terms would fall outside the new date range
-        let invalid_terms = sqlx::query_scalar::<_, i64>(
-            r#"SELECT COUNT(*) FROM terms
-               WHERE academic_session_id = $1
-               AND (start_date < $2 OR end_date > $3)"#,
-        )
-        .bind(session_id)
-        .bind(start_date)
-        .bind(end_date)
-        .fetch_one(db)
-        .await?;
-
-        if invalid_terms > 0 {
-            return Err(AppError::bad_request(anyhow::anyhow!(
-                "Cannot update dates: {} term(s) would fall outside the new date range",
-                invalid_terms
-            )));
-        }
-
-        let session = sqlx::query_as::<_, AcademicSession>(
-            r#"UPDATE academic_sessions
-               SET name = $1, description = $2, start_date = $3, end_date = $4, updated_at = NOW()
-               WHERE id = $5 AND school_id = $6
-               RETURNING id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at"#,
-        )
-        .bind(&name)
-        .bind(&description)
-        .bind(start_date)
-        .bind(end_date)
-        .bind(session_id)
-        .bind(school_id)
-        .fetch_one(db)
-        .await
-        .map_err(|e| {
-            if let sqlx::Error::Database(db_err) = &e
-                && db_err.is_unique_violation()
-            {
-                return AppError::bad_request(anyhow::anyhow!(
-                    "An academic session with this name already exists in this school"
-                ));
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            let existing = sqlx::query_as::<_, AcademicSession>(
+                r#"SELECT id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at
+                   FROM academic_sessions WHERE id = $1"#,
+            )
+            .bind(session_id)
+            .fetch_optional(uow.conn())
+            .await?
+            .ok_or_else(|| AppError::from_code(ErrorCode::AcademicSessionNotFound))?;
+
+            let name = dto.name.unwrap_or(existing.name);
+            let description = if dto.description.is_some() {
+                dto.description
+            } else {
+                existing.description
+            };
+            let start_date = dto.start_date.unwrap_or(existing.start_date);
+            let end_date = dto.end_date.unwrap_or(existing.end_date);
+
+            // Validate dates
+            if start_date >= end_date {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "Start date must be before end date"
+                )));
             }
-            AppError::from(e)
-        })?;
-
-        Ok(session)
-    }
-
-    /// Update an academic session without school filtering (for system admins).
-    #[instrument(skip(db))]
-    pub async fn update_academic_session_no_school_filter(
-        db: &PgPool,
-        session_id: AcademicSessionId,
-        dto: UpdateAcademicSessionDto,
-    ) -> Result<AcademicSession, AppError> {
-        let existing = sqlx::query_as::<_, AcademicSession>(
-            r#"SELECT id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at
-               FROM academic_sessions WHERE id = $1"#,
-        )
-        .bind(session_id)
-        .fetch_optional(db)
-        .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Academic session not found")))?;
-
-        let name = dto.name.unwrap_or(existing.name);
-        let description = if dto.description.is_some() {
-            dto.description
-        } else {
-            existing.description
-        };
-        let start_date = dto.start_date.unwrap_or(existing.start_date);
-        let end_date = dto.end_date.unwrap_or(existing.end_date);
-
-        // Validate dates
-        if start_date >= end_date {
-            return Err(AppError::bad_request(anyhow::anyhow!(
-                "Start date must be before end date"
-            )));
-        }
-
-        // Check if any terms would fall outside the new date range
-        let invalid_terms = sqlx::query_scalar::<_, i64>(
-            r#"SELECT COUNT(*) FROM terms
-               WHERE academic_session_id = $1
-               AND (start_date < $2 OR end_date > $3)"#,
-        )
-        .bind(session_id)
-        .bind(start_date)
-        .bind(end_date)
-        .fetch_one(db)
-        .await?;
 
-        if invalid_terms > 0 {
-            return Err(AppError::bad_request(anyhow::anyhow!(
-                "Cannot update dates: {} term(s) would fall outside the new date range",
-                invalid_terms
-            )));
-        }
+            // Check if any terms would fall outside the new date range
+            let invalid_terms = sqlx::query_scalar::<_, i64>(
+                r#"SELECT COUNT(*) FROM terms
+                   WHERE academic_session_id = $1
+                   AND (start_date < $2 OR end_date > $3)"#,
+            )
+            .bind(session_id)
+            .bind(start_date)
+            .bind(end_date)
+            .fetch_one(uow.conn())
+            .await?;
 
-        let session = sqlx::query_as::<_, AcademicSession>(
-            r#"UPDATE academic_sessions
-               SET name = $1, description = $2, start_date = $3, end_date = $4, updated_at = NOW()
-               WHERE id = $5
-               RETURNING id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at"#,
-        )
-        .bind(&name)
-        .bind(&description)
-        .bind(start_date)
-        .bind(end_date)
-        .bind(session_id)
-        .fetch_one(db)
-        .await
-        .map_err(|e| {
-            if let sqlx::Error::Database(db_err) = &e
-                && db_err.is_unique_violation()
-            {
-                return AppError::bad_request(anyhow::anyhow!(
-                    "An academic session with this name already exists in this school"
-                ));
+            if invalid_terms > 0 {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "Cannot update dates: {} term(s) would fall outside the new date range",
+                    invalid_terms
+                )));
             }
-            AppError::from(e)
-        })?;
 
-        Ok(session)
+            sqlx::query_as::<_, AcademicSession>(
+                r#"UPDATE academic_sessions
+                   SET name = $1, description = $2, start_date = $3, end_date = $4, updated_at = NOW()
+                   WHERE id = $5
+                   RETURNING id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at"#,
+            )
+            .bind(&name)
+            .bind(&description)
+            .bind(start_date)
+            .bind(end_date)
+            .bind(session_id)
+            .fetch_one(uow.conn())
+            .await
+            .map_err(|e| {
+                if let sqlx::Error::Database(db_err) = &e
+                    && db_err.is_unique_violation()
+                {
+                    return AppError::from_code(ErrorCode::AcademicSessionNameConflict);
+                }
+                AppError::from(e)
+            })
+        })
+        .await
     }
 
     /// Delete an academic session.
     #[instrument(skip(db))]
     pub async fn delete_academic_session(
         db: &PgPool,
-        session_id: AcademicSessionId,
-        school_id: SchoolId,
-    ) -> Result<(), AppError> {
-        let result = sqlx::query("DELETE FROM academic_sessions WHERE id = $1 AND school_id = $2")
-            .bind(session_id)
-            .bind(school_id)
-            .execute(db)
-            .await?;
-
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!(
-                "Academic session not found"
-            )));
-        }
-
-        Ok(())
-    }
-
-    /// Delete an academic session without school filtering (for system admins).
-    #[instrument(skip(db))]
-    pub async fn delete_academic_session_no_school_filter(
-        db: &PgPool,
+        tenant: &TenantContext,
         session_id: AcademicSessionId,
     ) -> Result<(), AppError> {
-        let result = sqlx::query("DELETE FROM academic_sessions WHERE id = $1")
-            .bind(session_id)
-            .execute(db)
-            .await?;
-
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!(
-                "Academic session not found"
-            )));
-        }
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            let result = sqlx::query("DELETE FROM academic_sessions WHERE id = $1")
+                .bind(session_id)
+                .execute(uow.conn())
+                .await?;
+
+            if result.rows_affected() == 0 {
+                return Err(AppError::from_code(ErrorCode::AcademicSessionNotFound));
+            }
 
-        Ok(())
+            Ok(())
+        })
+        .await
     }
 
     /// Activate an academic session.
@@ -427,133 +294,69 @@ impl AcademicSessionService {
     #[instrument(skip(db))]
     pub async fn activate_academic_session(
         db: &PgPool,
+        tenant: &TenantContext,
         session_id: AcademicSessionId,
-        school_id: SchoolId,
     ) -> Result<AcademicSession, AppError> {
-        // Verify the session exists and belongs to the school
-        let exists = sqlx::query_scalar::<_, bool>(
-            "SELECT EXISTS(SELECT 1 FROM academic_sessions WHERE id = $1 AND school_id = $2)",
-        )
-        .bind(session_id)
-        .bind(school_id)
-        .fetch_one(db)
-        .await?;
-
-        if !exists {
-            return Err(AppError::not_found(anyhow::anyhow!(
-                "Academic session not found"
-            )));
-        }
-
-        // Deactivate all sessions for this school first
-        sqlx::query("UPDATE academic_sessions SET is_active = FALSE, updated_at = NOW() WHERE school_id = $1")
-            .bind(school_id)
-            .execute(db)
-            .await?;
-
-        // Activate the specified session
-        let session = sqlx::query_as::<_, AcademicSession>(
-            r#"UPDATE academic_sessions
-               SET is_active = TRUE, updated_at = NOW()
-               WHERE id = $1 AND school_id = $2
-               RETURNING id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at"#,
-        )
-        .bind(session_id)
-        .bind(school_id)
-        .fetch_one(db)
-        .await?;
-
-        Ok(session)
-    }
-
-    /// Activate an academic session without school filtering (for system admins).
-    #[instrument(skip(db))]
-    pub async fn activate_academic_session_no_school_filter(
-        db: &PgPool,
-        session_id: AcademicSessionId,
-    ) -> Result<AcademicSession, AppError> {
-        // Get the session to find its school_id
-        let existing = sqlx::query_as::<_, AcademicSession>(
-            r#"SELECT id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at
-               FROM academic_sessions WHERE id = $1"#,
-        )
-        .bind(session_id)
-        .fetch_optional(db)
-        .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Academic session not found")))?;
-
-        // Deactivate all sessions for this school first
-        sqlx::query("UPDATE academic_sessions SET is_active = FALSE, updated_at = NOW() WHERE school_id = $1")
-            .bind(existing.school_id)
-            .execute(db)
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            // Get the session to find its school_id
+            let school_id = sqlx::query_scalar::<_, SchoolId>(
+                "SELECT school_id FROM academic_sessions WHERE id = $1",
+            )
+            .bind(session_id)
+            .fetch_optional(uow.conn())
+            .await?
+            .ok_or_else(|| AppError::from_code(ErrorCode::AcademicSessionNotFound))?;
+
+            // Deactivate all sessions for this school first
+            sqlx::query("UPDATE academic_sessions SET is_active = FALSE, updated_at = NOW() WHERE school_id = $1")
+                .bind(school_id)
+                .execute(uow.conn())
+                .await?;
+
+            // Activate the specified session
+            let session = sqlx::query_as::<_, AcademicSession>(
+                r#"UPDATE academic_sessions
+                   SET is_active = TRUE, updated_at = NOW()
+                   WHERE id = $1
+                   RETURNING id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at"#,
+            )
+            .bind(session_id)
+            .fetch_one(uow.conn())
             .await?;
 
-        // Activate the specified session
-        let session = sqlx::query_as::<_, AcademicSession>(
-            r#"UPDATE academic_sessions
-               SET is_active = TRUE, updated_at = NOW()
-               WHERE id = $1
-               RETURNING id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at"#,
-        )
-        .bind(session_id)
-        .fetch_one(db)
-        .await?;
-
-        Ok(session)
+            Ok(session)
+        })
+        .await
     }
 
     /// Deactivate an academic session.
     #[instrument(skip(db))]
     pub async fn deactivate_academic_session(
         db: &PgPool,
+        tenant: &TenantContext,
         session_id: AcademicSessionId,
-        school_id: SchoolId,
     ) -> Result<AcademicSession, AppError> {
-        let session = sqlx::query_as::<_, AcademicSession>(
-            r#"UPDATE academic_sessions
-               SET is_active = FALSE, updated_at = NOW()
-               WHERE id = $1 AND school_id = $2
-               RETURNING id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at"#,
-        )
-        .bind(session_id)
-        .bind(school_id)
-        .fetch_optional(db)
-        .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Academic session not found")))?;
-
-        // Also unset any current term in this session
-        sqlx::query("UPDATE terms SET is_current = FALSE, updated_at = NOW() WHERE academic_session_id = $1")
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            let session = sqlx::query_as::<_, AcademicSession>(
+                r#"UPDATE academic_sessions
+                   SET is_active = FALSE, updated_at = NOW()
+                   WHERE id = $1
+                   RETURNING id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at"#,
+            )
             .bind(session_id)
-            .execute(db)
-            .await?;
+            .fetch_optional(uow.conn())
+            .await?
+            .ok_or_else(|| AppError::from_code(ErrorCode::AcademicSessionNotFound))?;
 
-        Ok(session)
-    }
+            // Also unset any current term in this session
+            sqlx::query("UPDATE terms SET is_current = FALSE, updated_at = NOW() WHERE academic_session_id = $1")
+                .bind(session_id)
+                .execute(uow.conn())
+                .await?;
 
-    /// Deactivate an academic session without school filtering (for system admins).
-    #[instrument(skip(db))]
-    pub async fn deactivate_academic_session_no_school_filter(
-        db: &PgPool,
-        session_id: AcademicSessionId,
-    ) -> Result<AcademicSession, AppError> {
-        let session = sqlx::query_as::<_, AcademicSession>(
-            r#"UPDATE academic_sessions
-               SET is_active = FALSE, updated_at = NOW()
-               WHERE id = $1
-               RETURNING id, name, description, school_id, start_date, end_date, is_active, created_at, updated_at"#,
-        )
-        .bind(session_id)
-        .fetch_optional(db)
-        .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Academic session not found")))?;
-
-        // Also unset any current term in this session
-        sqlx::query("UPDATE terms SET is_current = FALSE, updated_at = NOW() WHERE academic_session_id = $1")
-            .bind(session_id)
-            .execute(db)
-            .await?;
-
-        Ok(session)
+            Ok(session)
+        })
+        .await
     }
 }
 
@@ -633,10 +436,13 @@ mod tests {
             .unwrap();
         assert!(!session.is_active);
 
-        let activated =
-            AcademicSessionService::activate_academic_session(&pool, session.id, school_id)
-                .await
-                .unwrap();
+        let activated = AcademicSessionService::activate_academic_session(
+            &pool,
+            &TenantContext::school(school_id),
+            session.id,
+        )
+        .await
+        .unwrap();
         assert!(activated.is_active);
     }
 
@@ -673,27 +479,81 @@ mod tests {
         .unwrap();
 
         // Activate first session
-        AcademicSessionService::activate_academic_session(&pool, session1.id, school_id)
-            .await
-            .unwrap();
+        AcademicSessionService::activate_academic_session(
+            &pool,
+            &TenantContext::school(school_id),
+            session1.id,
+        )
+        .await
+        .unwrap();
 
         // Activate second session - should deactivate first
-        AcademicSessionService::activate_academic_session(&pool, session2.id, school_id)
-            .await
-            .unwrap();
+        AcademicSessionService::activate_academic_session(
+            &pool,
+            &TenantContext::school(school_id),
+            session2.id,
+        )
+        .await
+        .unwrap();
 
         // Check that only second session is active
-        let s1 = AcademicSessionService::get_academic_session_by_id(&pool, session1.id, school_id)
-            .await
-            .unwrap();
-        let s2 = AcademicSessionService::get_academic_session_by_id(&pool, session2.id, school_id)
-            .await
-            .unwrap();
+        let s1 = AcademicSessionService::get_academic_session_by_id(
+            &pool,
+            &TenantContext::school(school_id),
+            session1.id,
+        )
+        .await
+        .unwrap();
+        let s2 = AcademicSessionService::get_academic_session_by_id(
+            &pool,
+            &TenantContext::school(school_id),
+            session2.id,
+        )
+        .await
+        .unwrap();
 
         assert!(!s1.is_active);
         assert!(s2.is_active);
     }
 
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_sessions_of_other_schools_are_hidden(pool: PgPool) {
+        let school_id = create_test_school(&pool, "Scoped School").await;
+        let other_school_id = create_test_school(&pool, "Other School").await;
+
+        let session = AcademicSessionService::create_academic_session(
+            &pool,
+            school_id,
+            CreateAcademicSessionDto {
+                name: "2024-2025".to_string(),
+                description: None,
+                school_id: None,
+                start_date: NaiveDate::from_ymd_opt(2024, 9, 1).unwrap(),
+                end_date: NaiveDate::from_ymd_opt(2025, 6, 30).unwrap(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let other = TenantContext::school(other_school_id);
+        let err = AcademicSessionService::get_academic_session_by_id(&pool, &other, session.id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+        let err = AcademicSessionService::activate_academic_session(&pool, &other, session.id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        AcademicSessionService::get_academic_session_by_id(
+            &pool,
+            &TenantContext::AllSchools,
+            session.id,
+        )
+        .await
+        .unwrap();
+    }
+
     #[sqlx::test(migrations = "./migrations")]
     async fn test_get_active_academic_session(pool: PgPool) {
         let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
@@ -719,9 +579,13 @@ mod tests {
         .await
         .unwrap();
 
-        AcademicSessionService::activate_academic_session(&pool, session.id, school_id)
-            .await
-            .unwrap();
+        AcademicSessionService::activate_academic_session(
+            &pool,
+            &TenantContext::school(school_id),
+            session.id,
+        )
+        .await
+        .unwrap();
 
         let active = AcademicSessionService::get_active_academic_session(&pool, school_id)
             .await
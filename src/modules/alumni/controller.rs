@@ -0,0 +1,215 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::IntoResponse,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent, Visible};
+use chalkbyte_models::ids::AlumnusId;
+
+use crate::middleware::auth::{
+    RequireAlumniCreate, RequireAlumniDelete, RequireAlumniExport, RequireAlumniRead,
+    RequireAlumniUpdate,
+};
+use crate::modules::alumni::model::{
+    AlumniExportParams, AlumniFilterParams, Alumnus, AlumnusDetail, GraduateStudentsDto,
+    GraduateStudentsResponse, PaginatedAlumniResponse, UpdateAlumnusDto,
+};
+use crate::modules::alumni::service::AlumniService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Graduate students into alumni records
+///
+/// Creates an alumni record for each student and removes their student role.
+/// Their account, level, and branch links are kept.
+#[utoipa::path(
+    post,
+    path = "/api/alumni/graduate",
+    summary = "Graduate students",
+    request_body = GraduateStudentsDto,
+    responses(
+        Created<GraduateStudentsResponse>,
+        (status = 400, description = "Invalid input, session from another school, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires alumni:create permission")
+    ),
+    tag = "Alumni",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn graduate_students(
+    State(state): State<AppState>,
+    RequireAlumniCreate(auth_user): RequireAlumniCreate,
+    ValidatedJson(dto): ValidatedJson<GraduateStudentsDto>,
+) -> Result<Created<GraduateStudentsResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let graduated_by = auth_user.user_id()?;
+    let result = AlumniService::graduate_students(&state.db, school_id, graduated_by, dto).await?;
+
+    Ok(Created(result))
+}
+
+/// List alumni for a school
+#[utoipa::path(
+    get,
+    path = "/api/alumni",
+    summary = "List alumni",
+    params(AlumniFilterParams),
+    responses(
+        (status = 200, description = "Alumni, most recent graduates first", body = PaginatedAlumniResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires alumni:read permission")
+    ),
+    tag = "Alumni",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_alumni(
+    State(state): State<AppState>,
+    RequireAlumniRead(auth_user): RequireAlumniRead,
+    Query(filters): Query<AlumniFilterParams>,
+) -> Result<Visible<PaginatedAlumniResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let alumni = AlumniService::get_alumni(&state.db, school_id, filters).await?;
+
+    Ok(Visible::new(alumni, auth_user.field_access()))
+}
+
+/// Export a mailing list of contactable alumni as CSV
+#[utoipa::path(
+    get,
+    path = "/api/alumni/export",
+    summary = "Export alumni mailing list",
+    params(AlumniExportParams),
+    responses(
+        (status = 200, description = "CSV of contactable alumni with an email address", content_type = "text/csv", body = String),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires alumni:export permission")
+    ),
+    tag = "Alumni",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn export_mailing_list(
+    State(state): State<AppState>,
+    RequireAlumniExport(auth_user): RequireAlumniExport,
+    Query(params): Query<AlumniExportParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let csv = AlumniService::export_mailing_list(&state.db, school_id, params).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"alumni-mailing-list.csv\"",
+            ),
+        ],
+        csv,
+    ))
+}
+
+/// Get an alumni record by ID
+#[utoipa::path(
+    get,
+    path = "/api/alumni/{id}",
+    summary = "Get alumnus",
+    params(
+        ("id" = Uuid, Path, description = "Alumnus ID")
+    ),
+    responses(
+        (status = 200, description = "Alumni record with academic history links", body = AlumnusDetail),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires alumni:read permission"),
+        (status = 404, description = "Alumnus not found")
+    ),
+    tag = "Alumni",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_alumnus_by_id(
+    State(state): State<AppState>,
+    RequireAlumniRead(auth_user): RequireAlumniRead,
+    Path(id): Path<Uuid>,
+) -> Result<Visible<AlumnusDetail>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let alumnus =
+        AlumniService::get_alumnus_by_id(&state.db, AlumnusId::from(id), school_id).await?;
+
+    Ok(Visible::new(alumnus, auth_user.field_access()))
+}
+
+/// Update an alumni record
+#[utoipa::path(
+    put,
+    path = "/api/alumni/{id}",
+    summary = "Update alumnus",
+    params(
+        ("id" = Uuid, Path, description = "Alumnus ID")
+    ),
+    request_body = UpdateAlumnusDto,
+    responses(
+        (status = 200, description = "Alumni record updated", body = Alumnus),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires alumni:update permission"),
+        (status = 404, description = "Alumnus not found")
+    ),
+    tag = "Alumni",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_alumnus(
+    State(state): State<AppState>,
+    RequireAlumniUpdate(auth_user): RequireAlumniUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateAlumnusDto>,
+) -> Result<Visible<Alumnus>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let alumnus =
+        AlumniService::update_alumnus(&state.db, AlumnusId::from(id), school_id, dto).await?;
+
+    Ok(Visible::new(alumnus, auth_user.field_access()))
+}
+
+/// Delete an alumni record
+#[utoipa::path(
+    delete,
+    path = "/api/alumni/{id}",
+    summary = "Delete alumnus",
+    params(
+        ("id" = Uuid, Path, description = "Alumnus ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires alumni:delete permission"),
+        (status = 404, description = "Alumnus not found")
+    ),
+    tag = "Alumni",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_alumnus(
+    State(state): State<AppState>,
+    RequireAlumniDelete(auth_user): RequireAlumniDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    AlumniService::delete_alumnus(&state.db, AlumnusId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
@@ -0,0 +1,12 @@
+//! Alumni module.
+//!
+//! This module turns graduating students into alumni records with a
+//! graduation year and cohort, keeps their contact details for alumni
+//! relations, and exports CSV mailing lists of alumni who agreed to be
+//! contacted. Graduation removes the student role but keeps the account, so
+//! an alumnus still links back to their academic history.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Alumni data models and DTOs.
+//!
+//! This module re-exports alumni models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all alumni models from the shared crate
+pub use chalkbyte_models::alumni::*;
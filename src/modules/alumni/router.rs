@@ -0,0 +1,26 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    delete_alumnus, export_mailing_list, get_alumni, get_alumnus_by_id, graduate_students,
+    update_alumnus,
+};
+
+/// Initialize the alumni router
+/// Routes: POST /graduate, GET /, GET /export, GET /{id}, PUT /{id}, DELETE /{id}
+pub fn init_alumni_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_alumni))
+        .route("/graduate", post(graduate_students))
+        .route("/export", get(export_mailing_list))
+        .route(
+            "/{id}",
+            get(get_alumnus_by_id)
+                .put(update_alumnus)
+                .delete(delete_alumnus),
+        )
+}
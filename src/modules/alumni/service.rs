@@ -0,0 +1,505 @@
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_models::ids::{AlumnusId, SchoolId, UserId};
+
+use crate::modules::alumni::model::{
+    AlumniExportParams, AlumniFilterParams, AlumniMailingRow, Alumnus, AlumnusDetail,
+    GraduateStudentsDto, GraduateStudentsResponse, PaginatedAlumniResponse, UpdateAlumnusDto,
+};
+use crate::modules::users::model::system_roles;
+
+const ALUMNUS_COLUMNS: &str = "id, school_id, user_id, first_name, last_name, email, phone, \
+     graduation_year, cohort, graduation_session_id, final_level_id, final_branch_id, \
+     occupation, employer, city, contactable, notes, graduated_by, created_at, updated_at";
+
+pub struct AlumniService;
+
+impl AlumniService {
+    async fn find_alumnus(
+        db: &PgPool,
+        alumnus_id: AlumnusId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Alumnus, AppError> {
+        sqlx::query_as::<_, Alumnus>(&format!(
+            "SELECT {ALUMNUS_COLUMNS} FROM alumni
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(alumnus_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Alumnus not found")))
+    }
+
+    /// Convert graduating students of a school into alumni records.
+    ///
+    /// Each record copies the student's name, email, level, and branch and
+    /// keeps a link to their account. The student role is removed so former
+    /// students drop out of rosters while their account and history remain.
+    /// IDs that are not current students of the school are returned in
+    /// `failed_ids`.
+    #[instrument(skip(db, dto))]
+    pub async fn graduate_students(
+        db: &PgPool,
+        school_id: SchoolId,
+        graduated_by: UserId,
+        dto: GraduateStudentsDto,
+    ) -> Result<GraduateStudentsResponse, AppError> {
+        if let Some(session_id) = dto.graduation_session_id {
+            let session_in_school = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM academic_sessions WHERE id = $1 AND school_id = $2)",
+            )
+            .bind(session_id)
+            .bind(school_id)
+            .fetch_one(db)
+            .await?;
+
+            if !session_in_school {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "Academic session does not belong to this school"
+                )));
+            }
+        }
+
+        let mut tx = db.begin().await?;
+        let mut graduated = Vec::new();
+        let mut failed_ids = Vec::new();
+
+        for student_id in dto.student_ids {
+            let alumnus = sqlx::query_as::<_, Alumnus>(&format!(
+                "INSERT INTO alumni (school_id, user_id, first_name, last_name, email,
+                     graduation_year, cohort, graduation_session_id, final_level_id,
+                     final_branch_id, graduated_by)
+                 SELECT u.school_id, u.id, u.first_name, u.last_name, u.email, $3, $4, $5,
+                        u.level_id, u.branch_id, $6
+                 FROM users u
+                 JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $7
+                 WHERE u.id = $1 AND u.school_id = $2
+                 ON CONFLICT (user_id) DO NOTHING
+                 RETURNING {ALUMNUS_COLUMNS}"
+            ))
+            .bind(student_id)
+            .bind(school_id)
+            .bind(dto.graduation_year)
+            .bind(&dto.cohort)
+            .bind(dto.graduation_session_id)
+            .bind(graduated_by)
+            .bind(system_roles::STUDENT)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            match alumnus {
+                Some(alumnus) => {
+                    sqlx::query("DELETE FROM user_roles WHERE user_id = $1 AND role_id = $2")
+                        .bind(student_id)
+                        .bind(system_roles::STUDENT)
+                        .execute(&mut *tx)
+                        .await?;
+                    graduated.push(alumnus);
+                }
+                None => failed_ids.push(student_id),
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(GraduateStudentsResponse {
+            graduated,
+            failed_ids,
+        })
+    }
+
+    /// Get paginated alumni for a school, most recent graduates first.
+    #[instrument(skip(db))]
+    pub async fn get_alumni(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: AlumniFilterParams,
+    ) -> Result<PaginatedAlumniResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+        let search = filters.search.as_ref().map(|s| format!("%{}%", s));
+
+        let where_clause = r#"WHERE school_id = $1
+              AND ($2::int IS NULL OR graduation_year = $2)
+              AND ($3::text IS NULL OR cohort = $3)
+              AND ($4::text IS NULL OR first_name ILIKE $4 OR last_name ILIKE $4 OR email ILIKE $4)
+              AND ($5::bool IS NULL OR contactable = $5)"#;
+
+        let total =
+            sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM alumni {where_clause}"))
+                .bind(school_id)
+                .bind(filters.graduation_year)
+                .bind(&filters.cohort)
+                .bind(&search)
+                .bind(filters.contactable)
+                .fetch_one(db)
+                .await?;
+
+        let data = sqlx::query_as::<_, Alumnus>(&format!(
+            "SELECT {ALUMNUS_COLUMNS} FROM alumni
+             {where_clause}
+             ORDER BY graduation_year DESC, last_name, first_name
+             LIMIT $6 OFFSET $7"
+        ))
+        .bind(school_id)
+        .bind(filters.graduation_year)
+        .bind(&filters.cohort)
+        .bind(&search)
+        .bind(filters.contactable)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedAlumniResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Get an alumni record with the names of its final level, branch, and session.
+    #[instrument(skip(db))]
+    pub async fn get_alumnus_by_id(
+        db: &PgPool,
+        alumnus_id: AlumnusId,
+        school_id: Option<SchoolId>,
+    ) -> Result<AlumnusDetail, AppError> {
+        let alumnus = Self::find_alumnus(db, alumnus_id, school_id).await?;
+
+        let (final_level_name, final_branch_name, graduation_session_name) =
+            sqlx::query_as::<_, (Option<String>, Option<String>, Option<String>)>(
+                r#"SELECT
+                       (SELECT name FROM levels WHERE id = $1),
+                       (SELECT name FROM branches WHERE id = $2),
+                       (SELECT name FROM academic_sessions WHERE id = $3)"#,
+            )
+            .bind(alumnus.final_level_id)
+            .bind(alumnus.final_branch_id)
+            .bind(alumnus.graduation_session_id)
+            .fetch_one(db)
+            .await?;
+
+        Ok(AlumnusDetail {
+            alumnus,
+            final_level_name,
+            final_branch_name,
+            graduation_session_name,
+        })
+    }
+
+    /// Update an alumni record's contact details.
+    #[instrument(skip(db, dto))]
+    pub async fn update_alumnus(
+        db: &PgPool,
+        alumnus_id: AlumnusId,
+        school_id: Option<SchoolId>,
+        dto: UpdateAlumnusDto,
+    ) -> Result<Alumnus, AppError> {
+        Self::find_alumnus(db, alumnus_id, school_id).await?;
+
+        let alumnus = sqlx::query_as::<_, Alumnus>(&format!(
+            "UPDATE alumni
+             SET email = COALESCE($2, email),
+                 phone = COALESCE($3, phone),
+                 cohort = COALESCE($4, cohort),
+                 occupation = COALESCE($5, occupation),
+                 employer = COALESCE($6, employer),
+                 city = COALESCE($7, city),
+                 contactable = COALESCE($8, contactable),
+                 notes = COALESCE($9, notes)
+             WHERE id = $1
+             RETURNING {ALUMNUS_COLUMNS}"
+        ))
+        .bind(alumnus_id)
+        .bind(&dto.email)
+        .bind(&dto.phone)
+        .bind(&dto.cohort)
+        .bind(&dto.occupation)
+        .bind(&dto.employer)
+        .bind(&dto.city)
+        .bind(dto.contactable)
+        .bind(&dto.notes)
+        .fetch_one(db)
+        .await?;
+
+        Ok(alumnus)
+    }
+
+    /// Delete an alumni record. The former student's account is untouched.
+    #[instrument(skip(db))]
+    pub async fn delete_alumnus(
+        db: &PgPool,
+        alumnus_id: AlumnusId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "DELETE FROM alumni WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(alumnus_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!("Alumnus not found")));
+        }
+
+        Ok(())
+    }
+
+    /// Build a CSV mailing list of contactable alumni with an email address.
+    #[instrument(skip(db))]
+    pub async fn export_mailing_list(
+        db: &PgPool,
+        school_id: SchoolId,
+        params: AlumniExportParams,
+    ) -> Result<String, AppError> {
+        let rows = sqlx::query_as::<_, AlumniMailingRow>(
+            r#"SELECT first_name, last_name, email, phone, graduation_year, cohort, city
+               FROM alumni
+               WHERE school_id = $1
+                 AND contactable
+                 AND email IS NOT NULL
+                 AND ($2::int IS NULL OR graduation_year = $2)
+                 AND ($3::text IS NULL OR cohort = $3)
+               ORDER BY graduation_year DESC, last_name, first_name"#,
+        )
+        .bind(school_id)
+        .bind(params.graduation_year)
+        .bind(&params.cohort)
+        .fetch_all(db)
+        .await?;
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for row in &rows {
+            writer.serialize(row)?;
+        }
+        let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+
+        Ok(String::from_utf8(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use chalkbyte_core::PaginationParams;
+    use chalkbyte_models::ids::RoleId;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId, role_id: RoleId) -> UserId {
+        let user_id: UserId = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Test', 'User', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id.into_inner(),
+            role_id.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id
+    }
+
+    fn graduate_dto(student_ids: Vec<UserId>) -> GraduateStudentsDto {
+        GraduateStudentsDto {
+            student_ids,
+            graduation_year: 2026,
+            cohort: Some("Class of 2026".to_string()),
+            graduation_session_id: None,
+            school_id: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_graduate_students_creates_alumni_and_drops_student_role(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let teacher = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+
+        let level_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO levels (name, school_id) VALUES ('SS3', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        sqlx::query("UPDATE users SET level_id = $1 WHERE id = $2")
+            .bind(level_id)
+            .bind(student)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let result = AlumniService::graduate_students(
+            &pool,
+            school_id,
+            admin,
+            graduate_dto(vec![student, teacher]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.graduated.len(), 1);
+        assert_eq!(result.failed_ids, vec![teacher]);
+
+        let alumnus = &result.graduated[0];
+        assert_eq!(alumnus.user_id, Some(student));
+        assert_eq!(alumnus.graduation_year, 2026);
+
+        let detail = AlumniService::get_alumnus_by_id(&pool, alumnus.id, Some(school_id))
+            .await
+            .unwrap();
+        assert_eq!(detail.final_level_name.as_deref(), Some("SS3"));
+
+        let still_student = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
+        )
+        .bind(student)
+        .bind(system_roles::STUDENT)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert!(!still_student);
+
+        // Graduating again fails: they are no longer a student
+        let again =
+            AlumniService::graduate_students(&pool, school_id, admin, graduate_dto(vec![student]))
+                .await
+                .unwrap();
+        assert!(again.graduated.is_empty());
+        assert_eq!(again.failed_ids, vec![student]);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_graduate_rejects_session_from_other_school(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let other_school = create_test_school(&pool).await;
+        let admin = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+
+        let session_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO academic_sessions (name, school_id, start_date, end_date)
+             VALUES ('2025/2026', $1, '2025-09-01', '2026-07-31') RETURNING id",
+        )
+        .bind(other_school)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let err = AlumniService::graduate_students(
+            &pool,
+            school_id,
+            admin,
+            GraduateStudentsDto {
+                graduation_session_id: Some(session_id.into()),
+                ..graduate_dto(vec![student])
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_export_mailing_list_skips_uncontactable(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+        let first = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let second = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+
+        let result = AlumniService::graduate_students(
+            &pool,
+            school_id,
+            admin,
+            graduate_dto(vec![first, second]),
+        )
+        .await
+        .unwrap();
+
+        AlumniService::update_alumnus(
+            &pool,
+            result.graduated[1].id,
+            Some(school_id),
+            UpdateAlumnusDto {
+                email: None,
+                phone: None,
+                cohort: None,
+                occupation: None,
+                employer: None,
+                city: None,
+                contactable: Some(false),
+                notes: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let csv = AlumniService::export_mailing_list(
+            &pool,
+            school_id,
+            AlumniExportParams {
+                school_id: None,
+                graduation_year: Some(2026),
+                cohort: None,
+            },
+        )
+        .await
+        .unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "first_name,last_name,email,phone,graduation_year,cohort,city"
+        );
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].contains(result.graduated[0].email.as_deref().unwrap()));
+
+        let contactable = AlumniService::get_alumni(
+            &pool,
+            school_id,
+            AlumniFilterParams {
+                school_id: None,
+                graduation_year: None,
+                cohort: None,
+                search: None,
+                contactable: Some(true),
+                pagination: PaginationParams::default(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(contactable.meta.total, 1);
+    }
+}
@@ -0,0 +1,454 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use chrono::Utc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::{AnnouncementId, BranchId, LevelId, NotificationId};
+
+use crate::middleware::auth::{
+    AuthUser, RequireAnnouncementsApprove, RequireAnnouncementsCreate, RequireAnnouncementsDelete,
+    RequireAnnouncementsRead,
+};
+use crate::middleware::role::is_branch_scoped_teacher_jwt;
+use crate::modules::announcements::model::{
+    Announcement, AnnouncementFilterParams, AnnouncementStatus, CreateAnnouncementDto,
+    MarkNotificationsReadResponse, NotificationFeed, NotificationFilterParams,
+    PaginatedAnnouncementsResponse, ReviewAnnouncementDto, UpdateAnnouncementDto,
+};
+use crate::modules::announcements::service::{
+    AnnouncementService, NotificationService, spawn_email_fan_out,
+};
+use crate::modules::branches::service::BranchService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Create an announcement
+///
+/// Saves a draft when `draft` is set. Otherwise the announcement is
+/// submitted: it waits for approval if the school requires it, then for its
+/// publish time, and is then delivered to the notification feed of every
+/// user in the audience except the author. Audience filters are combined;
+/// leaving them all out targets the whole school. With `send_email` set,
+/// recipients are also emailed in the background once it is published.
+/// Teachers must target a level or branch they are assigned to.
+#[utoipa::path(
+    post,
+    path = "/api/announcements",
+    summary = "Create announcement",
+    request_body = CreateAnnouncementDto,
+    responses(
+        Created<Announcement>,
+        (status = 400, description = "Invalid input, unknown audience filter, no matching users, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires announcements:create permission and, for teachers, an assignment in the targeted level or branch")
+    ),
+    tag = "Announcements",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn create_announcement(
+    State(state): State<AppState>,
+    RequireAnnouncementsCreate(auth_user): RequireAnnouncementsCreate,
+    ValidatedJson(dto): ValidatedJson<CreateAnnouncementDto>,
+) -> Result<Created<Announcement>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let created_by = auth_user.user_id()?;
+    verify_teacher_audience(&state, &auth_user, dto.level_id, dto.branch_id).await?;
+
+    let announcement =
+        AnnouncementService::create_announcement(&state.db, school_id, created_by, dto).await?;
+    email_if_published(&state, &announcement);
+
+    Ok(Created(announcement))
+}
+
+/// Edit a draft announcement
+#[utoipa::path(
+    put,
+    path = "/api/announcements/{id}",
+    summary = "Update draft announcement",
+    params(
+        ("id" = Uuid, Path, description = "Announcement ID")
+    ),
+    request_body = UpdateAnnouncementDto,
+    responses(
+        (status = 200, description = "Draft updated", body = Announcement),
+        (status = 400, description = "Announcement is not a draft, invalid input, or unknown audience filter"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires announcements:create permission and, for teachers, an assignment in the targeted level or branch"),
+        (status = 404, description = "Announcement not found")
+    ),
+    tag = "Announcements",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn update_announcement(
+    State(state): State<AppState>,
+    RequireAnnouncementsCreate(auth_user): RequireAnnouncementsCreate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateAnnouncementDto>,
+) -> Result<Json<Announcement>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    verify_teacher_audience(&state, &auth_user, dto.level_id, dto.branch_id).await?;
+
+    let announcement = AnnouncementService::update_announcement(
+        &state.db,
+        AnnouncementId::from(id),
+        school_id,
+        dto,
+    )
+    .await?;
+
+    Ok(Json(announcement))
+}
+
+/// Submit a draft announcement
+///
+/// The announcement waits for approval if the school requires it, then for
+/// its publish time, and is then published.
+#[utoipa::path(
+    post,
+    path = "/api/announcements/{id}/submit",
+    summary = "Submit draft announcement",
+    params(
+        ("id" = Uuid, Path, description = "Announcement ID")
+    ),
+    responses(
+        (status = 200, description = "Announcement submitted", body = Announcement),
+        (status = 400, description = "Announcement is not a draft, or no matching users"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires announcements:create permission"),
+        (status = 404, description = "Announcement not found")
+    ),
+    tag = "Announcements",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn submit_announcement(
+    State(state): State<AppState>,
+    RequireAnnouncementsCreate(auth_user): RequireAnnouncementsCreate,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Announcement>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let announcement = AnnouncementService::submit_announcement(
+        &state.db,
+        AnnouncementId::from(id),
+        school_id,
+        auth_user.user_id()?,
+    )
+    .await?;
+    email_if_published(&state, &announcement);
+
+    Ok(Json(announcement))
+}
+
+/// Approve an announcement
+///
+/// Releases an announcement awaiting approval. It must be approved by
+/// someone other than the user who submitted it.
+#[utoipa::path(
+    post,
+    path = "/api/announcements/{id}/approve",
+    summary = "Approve announcement",
+    params(
+        ("id" = Uuid, Path, description = "Announcement ID")
+    ),
+    request_body = ReviewAnnouncementDto,
+    responses(
+        (status = 200, description = "Announcement approved and scheduled or published", body = Announcement),
+        (status = 400, description = "Announcement is not awaiting approval, or no matching users"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires announcements:approve permission and not being the submitter"),
+        (status = 404, description = "Announcement not found")
+    ),
+    tag = "Announcements",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn approve_announcement(
+    State(state): State<AppState>,
+    RequireAnnouncementsApprove(auth_user): RequireAnnouncementsApprove,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<ReviewAnnouncementDto>,
+) -> Result<Json<Announcement>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let announcement = AnnouncementService::approve_announcement(
+        &state.db,
+        AnnouncementId::from(id),
+        school_id,
+        auth_user.user_id()?,
+        dto,
+    )
+    .await?;
+    email_if_published(&state, &announcement);
+
+    Ok(Json(announcement))
+}
+
+/// Reject an announcement
+///
+/// Returns an announcement awaiting approval to draft so it can be edited
+/// and submitted again.
+#[utoipa::path(
+    post,
+    path = "/api/announcements/{id}/reject",
+    summary = "Reject announcement",
+    params(
+        ("id" = Uuid, Path, description = "Announcement ID")
+    ),
+    request_body = ReviewAnnouncementDto,
+    responses(
+        (status = 200, description = "Announcement returned to draft", body = Announcement),
+        (status = 400, description = "Announcement is not awaiting approval"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires announcements:approve permission and not being the submitter"),
+        (status = 404, description = "Announcement not found")
+    ),
+    tag = "Announcements",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn reject_announcement(
+    State(state): State<AppState>,
+    RequireAnnouncementsApprove(auth_user): RequireAnnouncementsApprove,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<ReviewAnnouncementDto>,
+) -> Result<Json<Announcement>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let announcement = AnnouncementService::reject_announcement(
+        &state.db,
+        AnnouncementId::from(id),
+        school_id,
+        auth_user.user_id()?,
+        dto,
+    )
+    .await?;
+
+    Ok(Json(announcement))
+}
+
+/// Teachers can only announce to a level or branch they are assigned to.
+async fn verify_teacher_audience(
+    state: &AppState,
+    auth_user: &AuthUser,
+    level_id: Option<LevelId>,
+    branch_id: Option<BranchId>,
+) -> Result<(), AppError> {
+    if !is_branch_scoped_teacher_jwt(auth_user) {
+        return Ok(());
+    }
+
+    let teacher_id = auth_user.user_id()?;
+    let today = Utc::now().date_naive();
+    let capacities = match (branch_id, level_id) {
+        (Some(branch_id), _) => {
+            BranchService::teacher_capacities_on_branch(&state.db, branch_id, teacher_id, today)
+                .await?
+        }
+        (None, Some(level_id)) => {
+            BranchService::teacher_capacities_in_level(&state.db, level_id, teacher_id, today)
+                .await?
+        }
+        (None, None) => Vec::new(),
+    };
+    if capacities.is_empty() {
+        return Err(AppError::forbidden(
+            "Teachers can only announce to a level or branch they are assigned to".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Email the recipients in the background once the announcement is out.
+/// Scheduled announcements are emailed by the publishing job instead.
+fn email_if_published(state: &AppState, announcement: &Announcement) {
+    if announcement.status == AnnouncementStatus::Published && announcement.send_email {
+        spawn_email_fan_out(
+            state.db.clone(),
+            state.email_config.clone(),
+            announcement.clone(),
+        );
+    }
+}
+
+/// List a school's announcements
+///
+/// Most recent first, with how many recipients have read each one.
+#[utoipa::path(
+    get,
+    path = "/api/announcements",
+    summary = "List announcements",
+    params(AnnouncementFilterParams),
+    responses(
+        (status = 200, description = "Announcements with read counts", body = PaginatedAnnouncementsResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires announcements:read permission")
+    ),
+    tag = "Announcements",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_announcements(
+    State(state): State<AppState>,
+    RequireAnnouncementsRead(auth_user): RequireAnnouncementsRead,
+    Query(params): Query<AnnouncementFilterParams>,
+) -> Result<Json<PaginatedAnnouncementsResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let announcements =
+        AnnouncementService::get_announcements(&state.db, school_id, params).await?;
+
+    Ok(Json(announcements))
+}
+
+/// Get an announcement
+#[utoipa::path(
+    get,
+    path = "/api/announcements/{id}",
+    summary = "Get announcement",
+    params(
+        ("id" = Uuid, Path, description = "Announcement ID")
+    ),
+    responses(
+        (status = 200, description = "Announcement with read counts", body = Announcement),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires announcements:read permission"),
+        (status = 404, description = "Announcement not found")
+    ),
+    tag = "Announcements",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_announcement(
+    State(state): State<AppState>,
+    RequireAnnouncementsRead(auth_user): RequireAnnouncementsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Announcement>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let announcement =
+        AnnouncementService::get_announcement(&state.db, AnnouncementId::from(id), school_id)
+            .await?;
+
+    Ok(Json(announcement))
+}
+
+/// Delete an announcement
+///
+/// Also removes it from every recipient's notification feed.
+#[utoipa::path(
+    delete,
+    path = "/api/announcements/{id}",
+    summary = "Delete announcement",
+    params(
+        ("id" = Uuid, Path, description = "Announcement ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires announcements:delete permission"),
+        (status = 404, description = "Announcement not found")
+    ),
+    tag = "Announcements",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_announcement(
+    State(state): State<AppState>,
+    RequireAnnouncementsDelete(auth_user): RequireAnnouncementsDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    AnnouncementService::delete_announcement(&state.db, AnnouncementId::from(id), school_id)
+        .await?;
+
+    Ok(NoContent)
+}
+
+/// Get your notification feed
+///
+/// Newest first, with the number of unread notifications across the whole
+/// feed.
+#[utoipa::path(
+    get,
+    path = "/api/notifications",
+    summary = "Get notifications",
+    params(NotificationFilterParams),
+    responses(
+        (status = 200, description = "Notifications and unread count", body = NotificationFeed),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Announcements",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_notifications(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Query(params): Query<NotificationFilterParams>,
+) -> Result<Json<NotificationFeed>, AppError> {
+    let feed = NotificationService::get_feed(&state.db, auth_user.user_id()?, params).await?;
+
+    Ok(Json(feed))
+}
+
+/// Mark a notification as read
+#[utoipa::path(
+    post,
+    path = "/api/notifications/{id}/read",
+    summary = "Mark notification read",
+    params(
+        ("id" = Uuid, Path, description = "Notification ID")
+    ),
+    responses(
+        (status = 200, description = "Notification marked as read", body = MarkNotificationsReadResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Notification not found")
+    ),
+    tag = "Announcements",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn mark_notification_read(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<MarkNotificationsReadResponse>, AppError> {
+    let response =
+        NotificationService::mark_read(&state.db, auth_user.user_id()?, NotificationId::from(id))
+            .await?;
+
+    Ok(Json(response))
+}
+
+/// Mark all your notifications as read
+#[utoipa::path(
+    post,
+    path = "/api/notifications/read-all",
+    summary = "Mark all notifications read",
+    responses(
+        (status = 200, description = "Notifications marked as read", body = MarkNotificationsReadResponse),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Announcements",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn mark_all_notifications_read(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<Json<MarkNotificationsReadResponse>, AppError> {
+    let response = NotificationService::mark_all_read(&state.db, auth_user.user_id()?).await?;
+
+    Ok(Json(response))
+}
@@ -0,0 +1,20 @@
+//! Announcements and notifications module.
+//!
+//! School admins and teachers publish announcements to an audience narrowed
+//! by role, level, and branch. Publishing copies the announcement into the
+//! notification feed of every user in the audience, where each user tracks
+//! what they have read. Announcements can also be emailed; the emails go out
+//! in the background at the broadcast send rate so publishing is not held up.
+//!
+//! Announcements share the broadcast publishing workflow and the school's
+//! publishing settings: they can be saved as drafts, need a second reviewer's
+//! approval when the school requires it for broadcasts, and can be scheduled
+//! for a local time in the school's timezone. The broadcast delivery job
+//! publishes scheduled announcements when they fall due.
+//!
+//! Teachers can only announce to a level or branch they are assigned to.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Announcement data models and DTOs.
+//!
+//! This module re-exports announcement models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all announcement models from the shared crate
+pub use chalkbyte_models::announcements::*;
@@ -0,0 +1,38 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    approve_announcement, create_announcement, delete_announcement, get_announcement,
+    get_announcements, get_notifications, mark_all_notifications_read, mark_notification_read,
+    reject_announcement, submit_announcement, update_announcement,
+};
+
+/// Initialize the announcements router
+/// Routes: GET/POST /, GET/PUT/DELETE /{id}, POST /{id}/submit,
+/// POST /{id}/approve, POST /{id}/reject
+pub fn init_announcements_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_announcements).post(create_announcement))
+        .route(
+            "/{id}",
+            get(get_announcement)
+                .put(update_announcement)
+                .delete(delete_announcement),
+        )
+        .route("/{id}/submit", post(submit_announcement))
+        .route("/{id}/approve", post(approve_announcement))
+        .route("/{id}/reject", post(reject_announcement))
+}
+
+/// Initialize the notification feed router for the authenticated user
+/// Routes: GET /, POST /read-all, POST /{id}/read
+pub fn init_notifications_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_notifications))
+        .route("/read-all", post(mark_all_notifications_read))
+        .route("/{id}/read", post(mark_notification_read))
+}
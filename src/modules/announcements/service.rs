@@ -0,0 +1,953 @@
+use std::time::Duration;
+
+use sqlx::{PgPool, Postgres, Transaction};
+use tracing::{info, instrument, warn};
+
+use chalkbyte_config::EmailConfig;
+use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_models::ids::{AnnouncementId, NotificationId, SchoolId, UserId};
+
+use crate::modules::announcements::model::{
+    Announcement, AnnouncementFilterParams, AnnouncementStatus, CreateAnnouncementDto,
+    MarkNotificationsReadResponse, Notification, NotificationFeed, NotificationFilterParams,
+    PaginatedAnnouncementsResponse, ReviewAnnouncementDto, UpdateAnnouncementDto,
+};
+use crate::modules::broadcasts::service::{BroadcastService, send_per_second};
+use crate::modules::email_branding::service::EmailBrandingService;
+use crate::utils::email::EmailService;
+
+const ANNOUNCEMENT_COLUMNS: &str = r#"a.id, a.school_id, a.title, a.body,
+    a.role_id, a.level_id, a.branch_id, a.send_email, a.status, a.publish_at, a.created_by,
+    a.submitted_by, a.submitted_at, a.reviewed_by, a.reviewed_at, a.review_note, a.published_at,
+    COUNT(n.id) AS recipient_count, COUNT(n.read_at) AS read_count, a.created_at"#;
+
+/// Email every recipient of an announcement in the background.
+///
+/// Sends are throttled to `BROADCAST_SEND_PER_SECOND`, the same rate as
+/// broadcasts, and failures are logged without stopping the rest.
+pub fn spawn_email_fan_out(db: PgPool, email_config: EmailConfig, announcement: Announcement) {
+    tokio::spawn(async move {
+        let email = EmailService::new(db.clone(), email_config);
+        match AnnouncementService::email_recipients(&db, &email, &announcement, send_per_second())
+            .await
+        {
+            Ok(sent) => info!(announcement_id = %announcement.id, sent, "Emailed announcement"),
+            Err(e) => {
+                warn!(announcement_id = %announcement.id, error = %e, "Failed to email announcement")
+            }
+        }
+    });
+}
+
+pub struct AnnouncementService;
+
+impl AnnouncementService {
+    /// Create an announcement, either as a draft or submitted straight away.
+    ///
+    /// See [`Self::submit_announcement`] for what happens on submission.
+    #[instrument(skip(db, dto))]
+    pub async fn create_announcement(
+        db: &PgPool,
+        school_id: SchoolId,
+        created_by: UserId,
+        dto: CreateAnnouncementDto,
+    ) -> Result<Announcement, AppError> {
+        BroadcastService::verify_audience(db, school_id, dto.role_id, dto.level_id, dto.branch_id)
+            .await?;
+
+        let mut tx = db.begin().await?;
+
+        let announcement_id = sqlx::query_scalar::<_, AnnouncementId>(
+            r#"INSERT INTO announcements
+                   (school_id, title, body, role_id, level_id, branch_id, send_email,
+                    publish_at, status, published_at, created_by)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 'draft', NULL, $9)
+               RETURNING id"#,
+        )
+        .bind(school_id)
+        .bind(dto.title.trim())
+        .bind(dto.body.trim())
+        .bind(dto.role_id)
+        .bind(dto.level_id)
+        .bind(dto.branch_id)
+        .bind(dto.send_email)
+        .bind(dto.publish_at)
+        .bind(created_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if !dto.draft {
+            Self::submit(&mut tx, announcement_id, created_by).await?;
+        }
+
+        tx.commit().await?;
+
+        Self::get_announcement(db, announcement_id, Some(school_id)).await
+    }
+
+    /// Replace the content, audience, and publish time of a draft.
+    #[instrument(skip(db, dto))]
+    pub async fn update_announcement(
+        db: &PgPool,
+        id: AnnouncementId,
+        school_id: Option<SchoolId>,
+        dto: UpdateAnnouncementDto,
+    ) -> Result<Announcement, AppError> {
+        let announcement = Self::get_announcement(db, id, school_id).await?;
+        if announcement.status != AnnouncementStatus::Draft {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only draft announcements can be edited"
+            )));
+        }
+
+        BroadcastService::verify_audience(
+            db,
+            announcement.school_id,
+            dto.role_id,
+            dto.level_id,
+            dto.branch_id,
+        )
+        .await?;
+
+        sqlx::query(
+            r#"UPDATE announcements
+               SET title = $2, body = $3, role_id = $4, level_id = $5, branch_id = $6,
+                   send_email = $7, publish_at = $8
+               WHERE id = $1"#,
+        )
+        .bind(id)
+        .bind(dto.title.trim())
+        .bind(dto.body.trim())
+        .bind(dto.role_id)
+        .bind(dto.level_id)
+        .bind(dto.branch_id)
+        .bind(dto.send_email)
+        .bind(dto.publish_at)
+        .execute(db)
+        .await?;
+
+        Self::get_announcement(db, id, school_id).await
+    }
+
+    /// Submit a draft.
+    ///
+    /// When the school requires approval the announcement waits for a second
+    /// reviewer; otherwise it is released straight away, see [`Self::release`].
+    #[instrument(skip(db))]
+    pub async fn submit_announcement(
+        db: &PgPool,
+        id: AnnouncementId,
+        school_id: Option<SchoolId>,
+        submitted_by: UserId,
+    ) -> Result<Announcement, AppError> {
+        let announcement = Self::get_announcement(db, id, school_id).await?;
+        if announcement.status != AnnouncementStatus::Draft {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only draft announcements can be submitted"
+            )));
+        }
+
+        let mut tx = db.begin().await?;
+        Self::submit(&mut tx, id, submitted_by).await?;
+        tx.commit().await?;
+
+        Self::get_announcement(db, id, school_id).await
+    }
+
+    /// Approve an announcement awaiting approval and release it.
+    ///
+    /// The approver must be someone other than the user who submitted it.
+    #[instrument(skip(db, dto))]
+    pub async fn approve_announcement(
+        db: &PgPool,
+        id: AnnouncementId,
+        school_id: Option<SchoolId>,
+        reviewer_id: UserId,
+        dto: ReviewAnnouncementDto,
+    ) -> Result<Announcement, AppError> {
+        Self::find_pending_approval(db, id, school_id, reviewer_id).await?;
+
+        let mut tx = db.begin().await?;
+
+        sqlx::query(
+            "UPDATE announcements SET reviewed_by = $2, reviewed_at = NOW(), review_note = $3
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(reviewer_id)
+        .bind(&dto.review_note)
+        .execute(&mut *tx)
+        .await?;
+
+        Self::release(&mut tx, id).await?;
+
+        tx.commit().await?;
+
+        Self::get_announcement(db, id, school_id).await
+    }
+
+    /// Reject an announcement awaiting approval, returning it to draft.
+    #[instrument(skip(db, dto))]
+    pub async fn reject_announcement(
+        db: &PgPool,
+        id: AnnouncementId,
+        school_id: Option<SchoolId>,
+        reviewer_id: UserId,
+        dto: ReviewAnnouncementDto,
+    ) -> Result<Announcement, AppError> {
+        Self::find_pending_approval(db, id, school_id, reviewer_id).await?;
+
+        sqlx::query(
+            "UPDATE announcements
+             SET status = 'draft', reviewed_by = $2, reviewed_at = NOW(), review_note = $3
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(reviewer_id)
+        .bind(&dto.review_note)
+        .execute(db)
+        .await?;
+
+        Self::get_announcement(db, id, school_id).await
+    }
+
+    async fn find_pending_approval(
+        db: &PgPool,
+        id: AnnouncementId,
+        school_id: Option<SchoolId>,
+        reviewer_id: UserId,
+    ) -> Result<Announcement, AppError> {
+        let announcement = Self::get_announcement(db, id, school_id).await?;
+
+        if announcement.status != AnnouncementStatus::PendingApproval {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only announcements awaiting approval can be reviewed"
+            )));
+        }
+        if announcement.submitted_by == Some(reviewer_id) {
+            return Err(AppError::forbidden(
+                "An announcement must be reviewed by someone other than its submitter".to_string(),
+            ));
+        }
+
+        Ok(announcement)
+    }
+
+    /// Uses the school's broadcast approval setting, so both go through the
+    /// same review.
+    async fn submit(
+        tx: &mut Transaction<'_, Postgres>,
+        id: AnnouncementId,
+        submitted_by: UserId,
+    ) -> Result<(), AppError> {
+        let approval_required = sqlx::query_scalar::<_, bool>(
+            "UPDATE announcements a
+             SET status = CASE WHEN s.broadcast_approval_required THEN 'pending_approval' ELSE a.status END,
+                 submitted_by = $2, submitted_at = NOW(),
+                 reviewed_by = NULL, reviewed_at = NULL, review_note = NULL
+             FROM schools s
+             WHERE s.id = a.school_id AND a.id = $1
+             RETURNING s.broadcast_approval_required",
+        )
+        .bind(id)
+        .bind(submitted_by)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        if !approval_required {
+            Self::release(tx, id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Schedule the announcement if its publish time is still ahead in the
+    /// school's timezone, otherwise publish it now.
+    async fn release(
+        tx: &mut Transaction<'_, Postgres>,
+        id: AnnouncementId,
+    ) -> Result<(), AppError> {
+        let status = sqlx::query_scalar::<_, AnnouncementStatus>(
+            "UPDATE announcements a
+             SET status = CASE WHEN a.publish_at > NOW() AT TIME ZONE s.timezone
+                               THEN 'scheduled' ELSE a.status END
+             FROM schools s
+             WHERE s.id = a.school_id AND a.id = $1
+             RETURNING a.status",
+        )
+        .bind(id)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        if status == AnnouncementStatus::Scheduled {
+            info!(announcement_id = %id, "Announcement scheduled");
+            return Ok(());
+        }
+
+        let recipients = Self::publish(tx, id).await?;
+        if recipients == 0 {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "No users match this audience"
+            )));
+        }
+        info!(announcement_id = %id, recipients, "Announcement published");
+
+        Ok(())
+    }
+
+    /// Mark the announcement published and deliver it to the notification
+    /// feed of every user in its audience except the author.
+    async fn publish(
+        tx: &mut Transaction<'_, Postgres>,
+        id: AnnouncementId,
+    ) -> Result<u64, AppError> {
+        sqlx::query(
+            "UPDATE announcements SET status = 'published', published_at = NOW() WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+
+        let recipients = sqlx::query(
+            r#"INSERT INTO notifications (user_id, announcement_id, title, body)
+               SELECT u.id, a.id, a.title, a.body
+               FROM announcements a
+               JOIN users u ON u.school_id = a.school_id
+               WHERE a.id = $1
+                 AND u.id IS DISTINCT FROM a.created_by
+                 AND (a.role_id IS NULL OR EXISTS (
+                     SELECT 1 FROM user_roles ur WHERE ur.user_id = u.id AND ur.role_id = a.role_id
+                 ))
+                 AND (a.level_id IS NULL OR u.level_id = a.level_id)
+                 AND (a.branch_id IS NULL OR u.branch_id = a.branch_id)"#,
+        )
+        .bind(id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+        Ok(recipients)
+    }
+
+    /// Publish every scheduled announcement whose publish time has passed in
+    /// its school's timezone, returning them so their emails can be sent.
+    ///
+    /// An announcement whose audience has emptied out since it was scheduled
+    /// is published without recipients.
+    pub async fn publish_due(db: &PgPool) -> Result<Vec<Announcement>, AppError> {
+        let mut published = Vec::new();
+
+        loop {
+            let mut tx = db.begin().await?;
+
+            let Some(id) = sqlx::query_scalar::<_, AnnouncementId>(
+                "SELECT a.id FROM announcements a
+                 JOIN schools s ON s.id = a.school_id
+                 WHERE a.status = 'scheduled'
+                   AND a.publish_at <= NOW() AT TIME ZONE s.timezone
+                 ORDER BY a.publish_at
+                 LIMIT 1
+                 FOR UPDATE OF a SKIP LOCKED",
+            )
+            .fetch_optional(&mut *tx)
+            .await?
+            else {
+                return Ok(published);
+            };
+
+            if Self::publish(&mut tx, id).await? == 0 {
+                warn!(announcement_id = %id, "Scheduled announcement has no recipients");
+            }
+
+            tx.commit().await?;
+            published.push(Self::get_announcement(db, id, None).await?);
+        }
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_announcements(
+        db: &PgPool,
+        school_id: SchoolId,
+        params: AnnouncementFilterParams,
+    ) -> Result<PaginatedAnnouncementsResponse, AppError> {
+        let limit = params.pagination.limit();
+        let offset = params.pagination.offset();
+
+        let data = sqlx::query_as::<_, Announcement>(&format!(
+            "SELECT {ANNOUNCEMENT_COLUMNS}
+             FROM announcements a
+             LEFT JOIN notifications n ON n.announcement_id = a.id
+             WHERE a.school_id = $1 AND ($2::text IS NULL OR a.status = $2)
+             GROUP BY a.id
+             ORDER BY a.created_at DESC
+             LIMIT $3 OFFSET $4"
+        ))
+        .bind(school_id)
+        .bind(params.status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM announcements
+             WHERE school_id = $1 AND ($2::text IS NULL OR status = $2)",
+        )
+        .bind(school_id)
+        .bind(params.status)
+        .fetch_one(db)
+        .await?;
+
+        Ok(PaginatedAnnouncementsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: params.pagination.page(),
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_announcement(
+        db: &PgPool,
+        id: AnnouncementId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Announcement, AppError> {
+        sqlx::query_as::<_, Announcement>(&format!(
+            "SELECT {ANNOUNCEMENT_COLUMNS}
+             FROM announcements a
+             LEFT JOIN notifications n ON n.announcement_id = a.id
+             WHERE a.id = $1 AND ($2::uuid IS NULL OR a.school_id = $2)
+             GROUP BY a.id"
+        ))
+        .bind(id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Announcement not found")))
+    }
+
+    /// Delete an announcement, removing it from every recipient's feed.
+    #[instrument(skip(db))]
+    pub async fn delete_announcement(
+        db: &PgPool,
+        id: AnnouncementId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "DELETE FROM announcements WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Announcement not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Email an announcement to each of its recipients who have not turned
+    /// off announcement emails, sending at most `per_second` messages a
+    /// second. Returns how many were sent.
+    #[instrument(skip(db, email, announcement))]
+    pub async fn email_recipients(
+        db: &PgPool,
+        email: &EmailService,
+        announcement: &Announcement,
+        per_second: u32,
+    ) -> Result<u64, AppError> {
+        let email = email
+            .with_branding(EmailBrandingService::email_branding(db, announcement.school_id).await?);
+
+        let addresses = sqlx::query_scalar::<_, String>(
+            r#"SELECT u.email FROM notifications n
+               JOIN users u ON u.id = n.user_id
+               LEFT JOIN notification_preferences np ON np.user_id = u.id
+               WHERE n.announcement_id = $1
+                 AND COALESCE(np.announcement_emails, TRUE)
+               ORDER BY n.id"#,
+        )
+        .bind(announcement.id)
+        .fetch_all(db)
+        .await?;
+
+        let pause = Duration::from_secs(1) / per_second.max(1);
+        let mut sent = 0;
+
+        for address in addresses {
+            match email
+                .send_broadcast_email(&address, &announcement.title, &announcement.body)
+                .await
+            {
+                Ok(()) => sent += 1,
+                Err(e) => {
+                    warn!(announcement_id = %announcement.id, error = %e.error, "Failed to email announcement recipient")
+                }
+            }
+            tokio::time::sleep(pause).await;
+        }
+
+        Ok(sent)
+    }
+}
+
+pub struct NotificationService;
+
+impl NotificationService {
+    /// A page of the user's notification feed, newest first, with their
+    /// unread count.
+    #[instrument(skip(db))]
+    pub async fn get_feed(
+        db: &PgPool,
+        user_id: UserId,
+        params: NotificationFilterParams,
+    ) -> Result<NotificationFeed, AppError> {
+        let limit = params.pagination.limit();
+        let offset = params.pagination.offset();
+
+        let data = sqlx::query_as::<_, Notification>(
+            r#"SELECT id, announcement_id, title, body, read_at, created_at
+               FROM notifications
+               WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+               ORDER BY created_at DESC, id
+               LIMIT $3 OFFSET $4"#,
+        )
+        .bind(user_id)
+        .bind(params.unread_only)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let (total, unread_count) = sqlx::query_as::<_, (i64, i64)>(
+            r#"SELECT COUNT(*) FILTER (WHERE NOT $2 OR read_at IS NULL),
+                      COUNT(*) FILTER (WHERE read_at IS NULL)
+               FROM notifications
+               WHERE user_id = $1"#,
+        )
+        .bind(user_id)
+        .bind(params.unread_only)
+        .fetch_one(db)
+        .await?;
+
+        Ok(NotificationFeed {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: params.pagination.page(),
+                has_more: offset + limit < total,
+            },
+            unread_count,
+        })
+    }
+
+    /// Mark one of the user's notifications as read. Marking a read
+    /// notification again changes nothing.
+    #[instrument(skip(db))]
+    pub async fn mark_read(
+        db: &PgPool,
+        user_id: UserId,
+        id: NotificationId,
+    ) -> Result<MarkNotificationsReadResponse, AppError> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM notifications WHERE id = $1 AND user_id = $2)",
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_one(db)
+        .await?;
+
+        if !exists {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Notification not found"
+            )));
+        }
+
+        let marked = sqlx::query(
+            "UPDATE notifications SET read_at = NOW() WHERE id = $1 AND read_at IS NULL",
+        )
+        .bind(id)
+        .execute(db)
+        .await?
+        .rows_affected();
+
+        Self::read_response(db, user_id, marked).await
+    }
+
+    /// Mark every unread notification of the user as read.
+    #[instrument(skip(db))]
+    pub async fn mark_all_read(
+        db: &PgPool,
+        user_id: UserId,
+    ) -> Result<MarkNotificationsReadResponse, AppError> {
+        let marked = sqlx::query(
+            "UPDATE notifications SET read_at = NOW() WHERE user_id = $1 AND read_at IS NULL",
+        )
+        .bind(user_id)
+        .execute(db)
+        .await?
+        .rows_affected();
+
+        Self::read_response(db, user_id, marked).await
+    }
+
+    async fn read_response(
+        db: &PgPool,
+        user_id: UserId,
+        marked: u64,
+    ) -> Result<MarkNotificationsReadResponse, AppError> {
+        let unread_count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND read_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(MarkNotificationsReadResponse {
+            marked_count: marked as i64,
+            unread_count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::broadcasts::model::UpdatePublishingSettingsDto;
+    use axum::http::StatusCode;
+    use chalkbyte_core::PaginationParams;
+    use chalkbyte_models::ids::LevelId;
+    use uuid::Uuid;
+
+    async fn create_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar::<_, SchoolId>("INSERT INTO schools (name) VALUES ($1) RETURNING id")
+            .bind(format!("School {}", Uuid::new_v4()))
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn create_user(pool: &PgPool, school_id: SchoolId, level_id: Option<LevelId>) -> UserId {
+        sqlx::query_scalar::<_, UserId>(
+            r#"INSERT INTO users (first_name, last_name, email, school_id, level_id)
+               VALUES ('Ada', 'Obi', $1, $2, $3) RETURNING id"#,
+        )
+        .bind(format!("user-{}@example.com", Uuid::new_v4()))
+        .bind(school_id)
+        .bind(level_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    fn announcement_dto(level_id: Option<LevelId>) -> CreateAnnouncementDto {
+        CreateAnnouncementDto {
+            title: "Sports day".to_string(),
+            body: "Sports day is on Friday.".to_string(),
+            role_id: None,
+            level_id,
+            branch_id: None,
+            send_email: true,
+            publish_at: None,
+            draft: false,
+            school_id: None,
+        }
+    }
+
+    fn feed_params(unread_only: bool) -> NotificationFilterParams {
+        NotificationFilterParams {
+            unread_only,
+            pagination: PaginationParams::default(),
+        }
+    }
+
+    fn disabled_email(pool: &PgPool) -> EmailService {
+        EmailService::new(
+            pool.clone(),
+            EmailConfig {
+                enabled: false,
+                smtp_host: "localhost".to_string(),
+                smtp_port: 1025,
+                smtp_username: String::new(),
+                smtp_password: String::new(),
+                from_email: "noreply@example.com".to_string(),
+                from_name: "Chalkbyte".to_string(),
+                frontend_url: "http://localhost:3000".to_string(),
+            },
+        )
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_announcement_reaches_level_audience_feeds(pool: PgPool) {
+        let school_id = create_school(&pool).await;
+        let level_id = sqlx::query_scalar::<_, LevelId>(
+            "INSERT INTO levels (name, school_id) VALUES ('JSS 1', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let author = create_user(&pool, school_id, None).await;
+        let student = create_user(&pool, school_id, Some(level_id)).await;
+        let outsider = create_user(&pool, school_id, None).await;
+
+        let announcement = AnnouncementService::create_announcement(
+            &pool,
+            school_id,
+            author,
+            announcement_dto(Some(level_id)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(announcement.recipient_count, 1);
+        assert_eq!(announcement.read_count, 0);
+
+        let feed = NotificationService::get_feed(&pool, student, feed_params(false))
+            .await
+            .unwrap();
+        assert_eq!(feed.unread_count, 1);
+        assert_eq!(feed.data[0].announcement_id, Some(announcement.id));
+        assert_eq!(feed.data[0].title, "Sports day");
+        let notification_id = feed.data[0].id;
+
+        let feed = NotificationService::get_feed(&pool, outsider, feed_params(false))
+            .await
+            .unwrap();
+        assert!(feed.data.is_empty());
+
+        // Another user's notification is not found
+        let err = NotificationService::mark_read(&pool, outsider, notification_id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let sent = AnnouncementService::email_recipients(
+            &pool,
+            &disabled_email(&pool),
+            &announcement,
+            1000,
+        )
+        .await
+        .unwrap();
+        assert_eq!(sent, 1);
+
+        let marked = NotificationService::mark_read(&pool, student, notification_id)
+            .await
+            .unwrap();
+        assert_eq!(marked.marked_count, 1);
+        assert_eq!(marked.unread_count, 0);
+
+        let again = NotificationService::mark_read(&pool, student, notification_id)
+            .await
+            .unwrap();
+        assert_eq!(again.marked_count, 0);
+
+        let announcement =
+            AnnouncementService::get_announcement(&pool, announcement.id, Some(school_id))
+                .await
+                .unwrap();
+        assert_eq!(announcement.read_count, 1);
+
+        AnnouncementService::delete_announcement(&pool, announcement.id, Some(school_id))
+            .await
+            .unwrap();
+        let feed = NotificationService::get_feed(&pool, student, feed_params(false))
+            .await
+            .unwrap();
+        assert!(feed.data.is_empty());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_announcement_rejects_empty_audience_and_mark_all_read(pool: PgPool) {
+        let school_id = create_school(&pool).await;
+        let author = create_user(&pool, school_id, None).await;
+
+        let err = AnnouncementService::create_announcement(
+            &pool,
+            school_id,
+            author,
+            announcement_dto(None),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let reader = create_user(&pool, school_id, None).await;
+        for _ in 0..2 {
+            AnnouncementService::create_announcement(
+                &pool,
+                school_id,
+                author,
+                announcement_dto(None),
+            )
+            .await
+            .unwrap();
+        }
+
+        let marked = NotificationService::mark_all_read(&pool, reader)
+            .await
+            .unwrap();
+        assert_eq!(marked.marked_count, 2);
+        assert_eq!(marked.unread_count, 0);
+
+        let unread = NotificationService::get_feed(&pool, reader, feed_params(true))
+            .await
+            .unwrap();
+        assert!(unread.data.is_empty());
+        assert_eq!(unread.meta.total, 0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_announcement_requires_approval_by_another_reviewer(pool: PgPool) {
+        let school_id = create_school(&pool).await;
+        let author = create_user(&pool, school_id, None).await;
+        let approver = create_user(&pool, school_id, None).await;
+        let reader = create_user(&pool, school_id, None).await;
+
+        BroadcastService::update_publishing_settings(
+            &pool,
+            None,
+            school_id,
+            UpdatePublishingSettingsDto {
+                timezone: None,
+                broadcast_approval_required: Some(true),
+            },
+        )
+        .await
+        .unwrap();
+
+        let mut dto = announcement_dto(None);
+        dto.draft = true;
+        let announcement = AnnouncementService::create_announcement(&pool, school_id, author, dto)
+            .await
+            .unwrap();
+        assert_eq!(announcement.status, AnnouncementStatus::Draft);
+        assert_eq!(announcement.recipient_count, 0);
+
+        let announcement = AnnouncementService::submit_announcement(
+            &pool,
+            announcement.id,
+            Some(school_id),
+            author,
+        )
+        .await
+        .unwrap();
+        assert_eq!(announcement.status, AnnouncementStatus::PendingApproval);
+        let feed = NotificationService::get_feed(&pool, reader, feed_params(false))
+            .await
+            .unwrap();
+        assert!(feed.data.is_empty());
+
+        let err = AnnouncementService::approve_announcement(
+            &pool,
+            announcement.id,
+            Some(school_id),
+            author,
+            ReviewAnnouncementDto::default(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        let announcement = AnnouncementService::reject_announcement(
+            &pool,
+            announcement.id,
+            Some(school_id),
+            approver,
+            ReviewAnnouncementDto {
+                review_note: Some("Add the venue".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(announcement.status, AnnouncementStatus::Draft);
+        assert_eq!(announcement.review_note.as_deref(), Some("Add the venue"));
+
+        let announcement = AnnouncementService::submit_announcement(
+            &pool,
+            announcement.id,
+            Some(school_id),
+            author,
+        )
+        .await
+        .unwrap();
+        let announcement = AnnouncementService::approve_announcement(
+            &pool,
+            announcement.id,
+            Some(school_id),
+            approver,
+            ReviewAnnouncementDto::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(announcement.status, AnnouncementStatus::Published);
+        assert_eq!(announcement.reviewed_by, Some(approver));
+        assert!(announcement.published_at.is_some());
+        assert_eq!(announcement.recipient_count, 2);
+
+        let feed = NotificationService::get_feed(&pool, reader, feed_params(false))
+            .await
+            .unwrap();
+        assert_eq!(feed.data[0].announcement_id, Some(announcement.id));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_scheduled_announcement_publishes_in_school_timezone(pool: PgPool) {
+        let school_id = create_school(&pool).await;
+        let author = create_user(&pool, school_id, None).await;
+        let reader = create_user(&pool, school_id, None).await;
+
+        // Two hours ahead in UTC, but already past in a UTC+14 timezone
+        let publish_at = (chrono::Utc::now() + chrono::Duration::hours(2)).naive_utc();
+
+        let mut dto = announcement_dto(None);
+        dto.publish_at = Some(publish_at);
+        let scheduled = AnnouncementService::create_announcement(&pool, school_id, author, dto)
+            .await
+            .unwrap();
+        assert_eq!(scheduled.status, AnnouncementStatus::Scheduled);
+        assert!(
+            AnnouncementService::publish_due(&pool)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let feed = NotificationService::get_feed(&pool, reader, feed_params(false))
+            .await
+            .unwrap();
+        assert!(feed.data.is_empty());
+
+        BroadcastService::update_publishing_settings(
+            &pool,
+            None,
+            school_id,
+            UpdatePublishingSettingsDto {
+                timezone: Some("Pacific/Kiritimati".to_string()),
+                broadcast_approval_required: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let published = AnnouncementService::publish_due(&pool).await.unwrap();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].id, scheduled.id);
+        assert_eq!(published[0].status, AnnouncementStatus::Published);
+        assert_eq!(published[0].recipient_count, 1);
+
+        let feed = NotificationService::get_feed(&pool, reader, feed_params(false))
+            .await
+            .unwrap();
+        assert_eq!(feed.unread_count, 1);
+    }
+}
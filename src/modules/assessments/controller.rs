@@ -0,0 +1,587 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use chrono::Utc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent, PaginationParams};
+use chalkbyte_models::ids::{AssessmentId, GradingSchemeId, SubjectId};
+
+use crate::middleware::auth::{
+    AuthUser, RequireAssessmentsCreate, RequireAssessmentsDelete, RequireAssessmentsRead,
+    RequireAssessmentsUpdate, RequireGradesFinalize, RequireGradesRead, RequireGradesRecord,
+    RequireGradingSchemesManage, RequireSubjectsCreate, RequireSubjectsDelete, RequireSubjectsRead,
+};
+use crate::middleware::role::is_branch_scoped_teacher_jwt;
+use crate::modules::assessments::model::{
+    Assessment, AssessmentFilterParams, CreateAssessmentDto, CreateSubjectDto, GradingScheme,
+    GradingSchemeQueryParams, PaginatedAssessmentScoresResponse, PaginatedAssessmentsResponse,
+    PaginatedStudentResultsResponse, PaginatedTermGradesResponse, RecordScoresDto,
+    RecordScoresResponse, SetGradingSchemeDto, StudentResultParams, StudentTermGradeParams,
+    Subject, SubjectQueryParams, TermGrade, TermGradeParams, UpdateAssessmentDto,
+};
+use crate::modules::assessments::service::{AssessmentService, GradeService};
+use crate::modules::branches::service::BranchService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Create a subject
+#[utoipa::path(
+    post,
+    path = "/api/assessments/subjects",
+    summary = "Create subject",
+    request_body = CreateSubjectDto,
+    responses(
+        Created<Subject>,
+        (status = 400, description = "Invalid input, duplicate name, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires subjects:create permission")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_subject(
+    State(state): State<AppState>,
+    RequireSubjectsCreate(auth_user): RequireSubjectsCreate,
+    ValidatedJson(dto): ValidatedJson<CreateSubjectDto>,
+) -> Result<Created<Subject>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let subject = AssessmentService::create_subject(&state.db, school_id, dto).await?;
+
+    Ok(Created(subject))
+}
+
+/// List subjects
+#[utoipa::path(
+    get,
+    path = "/api/assessments/subjects",
+    summary = "List subjects",
+    params(SubjectQueryParams),
+    responses(
+        (status = 200, description = "Subjects ordered by name", body = Vec<Subject>),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires subjects:read permission")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_subjects(
+    State(state): State<AppState>,
+    RequireSubjectsRead(auth_user): RequireSubjectsRead,
+    Query(params): Query<SubjectQueryParams>,
+) -> Result<Json<Vec<Subject>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let subjects = AssessmentService::get_subjects(&state.db, school_id).await?;
+
+    Ok(Json(subjects))
+}
+
+/// Delete a subject
+///
+/// Also deletes the subject's assessments and every score recorded on them.
+#[utoipa::path(
+    delete,
+    path = "/api/assessments/subjects/{id}",
+    summary = "Delete subject",
+    params(
+        ("id" = Uuid, Path, description = "Subject ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires subjects:delete permission"),
+        (status = 404, description = "Subject not found")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_subject(
+    State(state): State<AppState>,
+    RequireSubjectsDelete(auth_user): RequireSubjectsDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    AssessmentService::delete_subject(&state.db, SubjectId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Create an assessment
+///
+/// The term, level, and subject must all belong to the school.
+#[utoipa::path(
+    post,
+    path = "/api/assessments",
+    summary = "Create assessment",
+    request_body = CreateAssessmentDto,
+    responses(
+        Created<Assessment>,
+        (status = 400, description = "Invalid input, term, level, or subject not in the school, or term results not open"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:create permission")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_assessment(
+    State(state): State<AppState>,
+    RequireAssessmentsCreate(auth_user): RequireAssessmentsCreate,
+    ValidatedJson(dto): ValidatedJson<CreateAssessmentDto>,
+) -> Result<Created<Assessment>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let created_by = auth_user.user_id()?;
+    let assessment =
+        AssessmentService::create_assessment(&state.db, school_id, created_by, dto).await?;
+
+    Ok(Created(assessment))
+}
+
+/// List assessments
+#[utoipa::path(
+    get,
+    path = "/api/assessments",
+    summary = "List assessments",
+    params(AssessmentFilterParams),
+    responses(
+        (status = 200, description = "Assessments ordered by due date", body = PaginatedAssessmentsResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:read permission")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_assessments(
+    State(state): State<AppState>,
+    RequireAssessmentsRead(auth_user): RequireAssessmentsRead,
+    Query(params): Query<AssessmentFilterParams>,
+) -> Result<Json<PaginatedAssessmentsResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let assessments = AssessmentService::get_assessments(&state.db, school_id, params).await?;
+
+    Ok(Json(assessments))
+}
+
+/// Get an assessment
+#[utoipa::path(
+    get,
+    path = "/api/assessments/{id}",
+    summary = "Get assessment",
+    params(
+        ("id" = Uuid, Path, description = "Assessment ID")
+    ),
+    responses(
+        (status = 200, description = "Assessment", body = Assessment),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:read permission"),
+        (status = 404, description = "Assessment not found")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_assessment(
+    State(state): State<AppState>,
+    RequireAssessmentsRead(auth_user): RequireAssessmentsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Assessment>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::get_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+
+    Ok(Json(assessment))
+}
+
+/// Update an assessment
+///
+/// The max score cannot be lowered below a score that is already recorded.
+/// Assessments cannot change once the subject's term results are submitted.
+#[utoipa::path(
+    put,
+    path = "/api/assessments/{id}",
+    summary = "Update assessment",
+    params(
+        ("id" = Uuid, Path, description = "Assessment ID")
+    ),
+    request_body = UpdateAssessmentDto,
+    responses(
+        (status = 200, description = "Assessment updated", body = Assessment),
+        (status = 400, description = "Invalid input, max score below a recorded score, or term results not open"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:update permission"),
+        (status = 404, description = "Assessment not found")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_assessment(
+    State(state): State<AppState>,
+    RequireAssessmentsUpdate(auth_user): RequireAssessmentsUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateAssessmentDto>,
+) -> Result<Json<Assessment>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::update_assessment(&state.db, AssessmentId::from(id), school_id, dto)
+            .await?;
+
+    Ok(Json(assessment))
+}
+
+/// Delete an assessment
+///
+/// Also deletes every score recorded on it.
+#[utoipa::path(
+    delete,
+    path = "/api/assessments/{id}",
+    summary = "Delete assessment",
+    params(
+        ("id" = Uuid, Path, description = "Assessment ID")
+    ),
+    responses(
+        NoContent,
+        (status = 400, description = "Term results for the subject are not open"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:delete permission"),
+        (status = 404, description = "Assessment not found")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_assessment(
+    State(state): State<AppState>,
+    RequireAssessmentsDelete(auth_user): RequireAssessmentsDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    AssessmentService::delete_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Record scores on an assessment
+///
+/// Scores for students who already have one are replaced. Students who are
+/// not in the assessment's level are skipped and returned in `failed_ids`.
+#[utoipa::path(
+    put,
+    path = "/api/assessments/{id}/scores",
+    summary = "Record scores",
+    params(
+        ("id" = Uuid, Path, description = "Assessment ID")
+    ),
+    request_body = RecordScoresDto,
+    responses(
+        (status = 200, description = "Scores recorded", body = RecordScoresResponse),
+        (status = 400, description = "Invalid input, a score above the max score, finalized scores, or term results not open"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires grades:record permission"),
+        (status = 404, description = "Assessment not found")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn record_scores(
+    State(state): State<AppState>,
+    RequireGradesRecord(auth_user): RequireGradesRecord,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<RecordScoresDto>,
+) -> Result<Json<RecordScoresResponse>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::get_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+
+    let recorded_by = auth_user.user_id()?;
+    let response = GradeService::record_scores(&state.db, &assessment, recorded_by, dto).await?;
+
+    Ok(Json(response))
+}
+
+/// Finalize the scores on an assessment
+///
+/// Finalized scores can no longer be changed. Teachers can only finalize
+/// assessments for a level where they lead or substitute on a branch;
+/// assistants cannot finalize grades.
+#[utoipa::path(
+    post,
+    path = "/api/assessments/{id}/finalize",
+    summary = "Finalize scores",
+    params(
+        ("id" = Uuid, Path, description = "Assessment ID")
+    ),
+    responses(
+        (status = 200, description = "Scores finalized", body = Assessment),
+        (status = 400, description = "Scores already finalized"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires grades:finalize permission and, for teachers, a lead or substitute assignment in the level"),
+        (status = 404, description = "Assessment not found")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn finalize_scores(
+    State(state): State<AppState>,
+    RequireGradesFinalize(auth_user): RequireGradesFinalize,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Assessment>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::get_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+
+    let finalized_by = auth_user.user_id()?;
+    if is_branch_scoped_teacher_jwt(&auth_user) {
+        let capacities = BranchService::teacher_capacities_in_level(
+            &state.db,
+            assessment.level_id,
+            finalized_by,
+            Utc::now().date_naive(),
+        )
+        .await?;
+        if !capacities.iter().any(|c| c.can_finalize_grades()) {
+            let message = if capacities.is_empty() {
+                "You are not assigned to a branch in this level"
+            } else {
+                "Assistant teachers cannot finalize grades"
+            };
+            return Err(AppError::forbidden(message.to_string()));
+        }
+    }
+
+    let assessment = GradeService::finalize_scores(&state.db, assessment.id, finalized_by).await?;
+
+    Ok(Json(assessment))
+}
+
+/// List the scores recorded on an assessment
+#[utoipa::path(
+    get,
+    path = "/api/assessments/{id}/scores",
+    summary = "Get assessment scores",
+    params(
+        ("id" = Uuid, Path, description = "Assessment ID"),
+        ("limit" = Option<i64>, Query, description = "Number of items per page (1-100, default: 10)"),
+        ("offset" = Option<i64>, Query, description = "Number of items to skip (default: 0)")
+    ),
+    responses(
+        (status = 200, description = "Scores ordered by student name", body = PaginatedAssessmentScoresResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires grades:read permission"),
+        (status = 404, description = "Assessment not found")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_assessment_scores(
+    State(state): State<AppState>,
+    RequireGradesRead(auth_user): RequireGradesRead,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<Json<PaginatedAssessmentScoresResponse>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::get_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+
+    let scores = GradeService::get_scores(&state.db, assessment.id, pagination).await?;
+
+    Ok(Json(scores))
+}
+
+/// Get term grades for a level
+///
+/// One row per student and subject. Subjects with a grading scheme, their
+/// own or the school default, combine assessment components by the scheme's
+/// weights; others take the weighted average of the assessments the student
+/// has been scored on so far.
+#[utoipa::path(
+    get,
+    path = "/api/assessments/term-grades",
+    summary = "Get term grades",
+    params(TermGradeParams),
+    responses(
+        (status = 200, description = "Term grades ordered by student and subject", body = PaginatedTermGradesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires grades:read permission")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_term_grades(
+    State(state): State<AppState>,
+    RequireGradesRead(auth_user): RequireGradesRead,
+    Query(params): Query<TermGradeParams>,
+) -> Result<Json<PaginatedTermGradesResponse>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let grades = GradeService::get_term_grades(&state.db, school_id, params).await?;
+
+    Ok(Json(grades))
+}
+
+/// List grading schemes
+///
+/// The school default first, then subject overrides by subject name.
+#[utoipa::path(
+    get,
+    path = "/api/assessments/grading-schemes",
+    summary = "List grading schemes",
+    params(GradingSchemeQueryParams),
+    responses(
+        (status = 200, description = "Grading schemes with their components", body = Vec<GradingScheme>),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires grades:read permission")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_grading_schemes(
+    State(state): State<AppState>,
+    RequireGradesRead(auth_user): RequireGradesRead,
+    Query(params): Query<GradingSchemeQueryParams>,
+) -> Result<Json<Vec<GradingScheme>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let schemes = GradeService::get_grading_schemes(&state.db, school_id).await?;
+
+    Ok(Json(schemes))
+}
+
+/// Set a grading scheme
+///
+/// Sets how assessment kinds combine into the term grade for the school, or
+/// for one subject when `subject_id` is given. Component weights must sum to
+/// 100. Replaces any existing scheme for the same school or subject and
+/// applies to term grades of every term.
+#[utoipa::path(
+    put,
+    path = "/api/assessments/grading-schemes",
+    summary = "Set grading scheme",
+    request_body = SetGradingSchemeDto,
+    responses(
+        (status = 200, description = "Grading scheme saved", body = GradingScheme),
+        (status = 400, description = "Weights do not sum to 100, duplicate component kind, subject not in the school, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires grading_schemes:manage permission")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn set_grading_scheme(
+    State(state): State<AppState>,
+    RequireGradingSchemesManage(auth_user): RequireGradingSchemesManage,
+    ValidatedJson(dto): ValidatedJson<SetGradingSchemeDto>,
+) -> Result<Json<GradingScheme>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+    let scheme =
+        GradeService::set_grading_scheme(&state.db, school_id, auth_user.user_id()?, dto).await?;
+
+    Ok(Json(scheme))
+}
+
+/// Delete a grading scheme
+///
+/// Subjects it applied to fall back to the school default, or to assessment
+/// weights when there is none.
+#[utoipa::path(
+    delete,
+    path = "/api/assessments/grading-schemes/{id}",
+    summary = "Delete grading scheme",
+    params(
+        ("id" = Uuid, Path, description = "Grading scheme ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires grading_schemes:manage permission"),
+        (status = 404, description = "Grading scheme not found")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_grading_scheme(
+    State(state): State<AppState>,
+    RequireGradingSchemesManage(auth_user): RequireGradingSchemesManage,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    GradeService::delete_grading_scheme(&state.db, GradingSchemeId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Get your assessment results
+#[utoipa::path(
+    get,
+    path = "/api/me/results",
+    summary = "Get my results",
+    params(StudentResultParams),
+    responses(
+        (status = 200, description = "Your scored assessments, most recent first", body = PaginatedStudentResultsResponse),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_my_results(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Query(params): Query<StudentResultParams>,
+) -> Result<Json<PaginatedStudentResultsResponse>, AppError> {
+    let results =
+        GradeService::get_student_results(&state.db, auth_user.user_id()?, params).await?;
+
+    Ok(Json(results))
+}
+
+/// Get your term grades
+#[utoipa::path(
+    get,
+    path = "/api/me/results/term-grades",
+    summary = "Get my term grades",
+    params(StudentTermGradeParams),
+    responses(
+        (status = 200, description = "Your term grade per subject", body = Vec<TermGrade>),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Assessments",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_my_term_grades(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Query(params): Query<StudentTermGradeParams>,
+) -> Result<Json<Vec<TermGrade>>, AppError> {
+    let grades =
+        GradeService::get_student_term_grades(&state.db, auth_user.user_id()?, params.term_id)
+            .await?;
+
+    Ok(Json(grades))
+}
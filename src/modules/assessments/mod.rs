@@ -0,0 +1,14 @@
+//! Assessments and grades module.
+//!
+//! Teachers set assessments (exams, quizzes, and assignments) for a term,
+//! level, and subject, each weighted as a share of the term grade, and enter
+//! marks for the students in that level. Term grades are the weighted average
+//! of a student's scored assessments per subject, unless the school has set a
+//! grading scheme that weights assessment kinds instead (with optional
+//! best-of-N rules), for the whole school or a single subject. Students read
+//! their own results and term grades from `/api/me/results`.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
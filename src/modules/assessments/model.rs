@@ -0,0 +1,7 @@
+//! Assessment and grade data models and DTOs.
+//!
+//! This module re-exports assessment models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all assessment models from the shared crate
+pub use chalkbyte_models::assessments::*;
@@ -0,0 +1,50 @@
+use axum::{
+    Router,
+    routing::{delete, get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    create_assessment, create_subject, delete_assessment, delete_grading_scheme, delete_subject,
+    finalize_scores, get_assessment, get_assessment_scores, get_assessments, get_grading_schemes,
+    get_my_results, get_my_term_grades, get_subjects, get_term_grades, record_scores,
+    set_grading_scheme, update_assessment,
+};
+
+/// Initialize the assessments router
+/// Routes: POST /, GET /, GET /{id}, PUT /{id}, DELETE /{id},
+/// PUT /{id}/scores, GET /{id}/scores, POST /{id}/finalize, GET /term-grades,
+/// POST /subjects, GET /subjects, DELETE /subjects/{id},
+/// GET/PUT /grading-schemes, DELETE /grading-schemes/{id}
+pub fn init_assessments_router() -> Router<AppState> {
+    Router::new()
+        .route("/", post(create_assessment).get(get_assessments))
+        .route("/subjects", post(create_subject).get(get_subjects))
+        .route("/subjects/{id}", delete(delete_subject))
+        .route("/term-grades", get(get_term_grades))
+        .route(
+            "/grading-schemes",
+            get(get_grading_schemes).put(set_grading_scheme),
+        )
+        .route("/grading-schemes/{id}", delete(delete_grading_scheme))
+        .route(
+            "/{id}",
+            get(get_assessment)
+                .put(update_assessment)
+                .delete(delete_assessment),
+        )
+        .route(
+            "/{id}/scores",
+            get(get_assessment_scores).put(record_scores),
+        )
+        .route("/{id}/finalize", post(finalize_scores))
+}
+
+/// Initialize the student results router, nested under /me
+/// Routes: GET /, GET /term-grades
+pub fn init_my_results_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_my_results))
+        .route("/term-grades", get(get_my_term_grades))
+}
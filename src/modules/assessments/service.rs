@@ -0,0 +1,1196 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, PaginationMeta, PaginationParams};
+use chalkbyte_models::ids::{
+    AssessmentId, GradingSchemeId, LevelId, SchoolId, SubjectId, TermId, UserId,
+};
+
+use crate::modules::assessments::model::{
+    Assessment, AssessmentFilterParams, AssessmentScoreWithStudent, CreateAssessmentDto,
+    CreateSubjectDto, GradingComponent, GradingScheme, PaginatedAssessmentScoresResponse,
+    PaginatedAssessmentsResponse, PaginatedStudentResultsResponse, PaginatedTermGradesResponse,
+    RecordScoresDto, RecordScoresResponse, SetGradingSchemeDto, StudentResult, StudentResultParams,
+    Subject, TermGrade, TermGradeParams, UpdateAssessmentDto,
+};
+use crate::modules::results::service::ResultService;
+use crate::modules::users::model::system_roles;
+
+const SUBJECT_COLUMNS: &str = "id, school_id, name, code, created_at, updated_at";
+
+const ASSESSMENT_COLUMNS: &str = "id, school_id, term_id, level_id, subject_id, name, kind, \
+     max_score, weight, due_date, created_by, finalized_at, finalized_by, created_at, updated_at";
+
+/// Term grades per student and subject from `rows`, a query with one row
+/// per student and assessment and the columns `student_id`, `first_name`,
+/// `last_name`, `subject_id`, `subject_name`, `assessment_id`, `kind`,
+/// `weight`, and `ratio` (score over max score, NULL while unscored).
+///
+/// Subjects with a grading scheme, their own or the school default, are
+/// graded by component: the average of each component's counted assessments,
+/// weighted by component among those with a score. Other subjects use the
+/// weighted average of the scored assessments. Percentages are rounded to
+/// two places.
+fn term_grade_query(rows: &str) -> String {
+    format!(
+        "WITH grade_rows AS ({rows}),
+         ranked AS (
+             SELECT r.*, ROW_NUMBER() OVER (
+                 PARTITION BY r.student_id, r.subject_id, r.kind
+                 ORDER BY r.ratio DESC NULLS LAST, r.assessment_id
+             ) AS score_rank
+             FROM grade_rows r
+         ),
+         subject_schemes AS (
+             SELECT DISTINCT ON (s.id) s.id AS subject_id, gs.id AS grading_scheme_id
+             FROM subjects s
+             INNER JOIN grading_schemes gs
+                 ON gs.school_id = s.school_id AND (gs.subject_id = s.id OR gs.subject_id IS NULL)
+             WHERE s.id IN (SELECT subject_id FROM grade_rows)
+             ORDER BY s.id, gs.subject_id NULLS LAST
+         ),
+         component_ratios AS (
+             SELECT r.student_id, r.subject_id, c.weight AS component_weight,
+                    AVG(r.ratio) FILTER (WHERE c.best_of IS NULL OR r.score_rank <= c.best_of) AS ratio
+             FROM ranked r
+             INNER JOIN subject_schemes ss ON ss.subject_id = r.subject_id
+             INNER JOIN grading_scheme_components c
+                 ON c.grading_scheme_id = ss.grading_scheme_id AND c.kind = r.kind
+             GROUP BY r.student_id, r.subject_id, c.id
+         ),
+         scheme_ratios AS (
+             SELECT student_id, subject_id,
+                    SUM(component_weight * ratio)
+                    / NULLIF(SUM(component_weight) FILTER (WHERE ratio IS NOT NULL), 0) AS ratio
+             FROM component_ratios
+             GROUP BY student_id, subject_id
+         )
+         SELECT r.student_id, r.first_name, r.last_name, r.subject_id, r.subject_name,
+                COALESCE(ROUND((CASE
+                    WHEN ss.grading_scheme_id IS NULL THEN
+                        SUM(r.weight * r.ratio) / NULLIF(SUM(r.weight) FILTER (WHERE r.ratio IS NOT NULL), 0)
+                    ELSE sr.ratio
+                END * 100)::numeric, 2)::float8, 0) AS percentage,
+                COUNT(r.ratio) AS assessments_graded,
+                COUNT(*) AS assessments_total
+         FROM grade_rows r
+         LEFT JOIN subject_schemes ss ON ss.subject_id = r.subject_id
+         LEFT JOIN scheme_ratios sr ON sr.student_id = r.student_id AND sr.subject_id = r.subject_id
+         GROUP BY r.student_id, r.first_name, r.last_name, r.subject_id, r.subject_name,
+                  ss.grading_scheme_id, sr.ratio"
+    )
+}
+
+fn unique_violation_as(e: sqlx::Error, message: &'static str) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e
+        && db_err.is_unique_violation()
+    {
+        return AppError::bad_request(anyhow::anyhow!(message));
+    }
+    AppError::from(e)
+}
+
+fn page_meta(pagination: &PaginationParams, total: i64) -> PaginationMeta {
+    let limit = pagination.limit();
+    let offset = pagination.offset();
+    PaginationMeta {
+        total,
+        limit,
+        offset: Some(offset),
+        page: pagination.page(),
+        has_more: offset + limit < total,
+    }
+}
+
+pub struct AssessmentService;
+
+impl AssessmentService {
+    #[instrument(skip(db))]
+    pub async fn create_subject(
+        db: &PgPool,
+        school_id: SchoolId,
+        dto: CreateSubjectDto,
+    ) -> Result<Subject, AppError> {
+        let subject = sqlx::query_as::<_, Subject>(&format!(
+            "INSERT INTO subjects (school_id, name, code)
+             VALUES ($1, $2, $3)
+             RETURNING {SUBJECT_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.name)
+        .bind(&dto.code)
+        .fetch_one(db)
+        .await
+        .map_err(|e| unique_violation_as(e, "A subject with this name already exists"))?;
+
+        Ok(subject)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_subjects(db: &PgPool, school_id: SchoolId) -> Result<Vec<Subject>, AppError> {
+        let subjects = sqlx::query_as::<_, Subject>(&format!(
+            "SELECT {SUBJECT_COLUMNS} FROM subjects WHERE school_id = $1 ORDER BY name"
+        ))
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(subjects)
+    }
+
+    /// Delete a subject along with its assessments and their scores.
+    #[instrument(skip(db))]
+    pub async fn delete_subject(
+        db: &PgPool,
+        subject_id: SubjectId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "DELETE FROM subjects WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(subject_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!("Subject not found")));
+        }
+
+        Ok(())
+    }
+
+    /// Ensure the term, level, and subject all belong to the school.
+    async fn validate_scope(
+        db: &PgPool,
+        school_id: SchoolId,
+        term_id: TermId,
+        level_id: LevelId,
+        subject_id: SubjectId,
+    ) -> Result<(), AppError> {
+        let (term_ok, level_ok, subject_ok) = sqlx::query_as::<_, (bool, bool, bool)>(
+            r#"SELECT
+                   EXISTS(SELECT 1 FROM terms t
+                          INNER JOIN academic_sessions s ON s.id = t.academic_session_id
+                          WHERE t.id = $2 AND s.school_id = $1),
+                   EXISTS(SELECT 1 FROM levels WHERE id = $3 AND school_id = $1),
+                   EXISTS(SELECT 1 FROM subjects WHERE id = $4 AND school_id = $1)"#,
+        )
+        .bind(school_id)
+        .bind(term_id)
+        .bind(level_id)
+        .bind(subject_id)
+        .fetch_one(db)
+        .await?;
+
+        if !term_ok {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Term not found in this school"
+            )));
+        }
+        if !level_ok {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Level not found in this school"
+            )));
+        }
+        if !subject_ok {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Subject not found in this school"
+            )));
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip(db))]
+    pub async fn create_assessment(
+        db: &PgPool,
+        school_id: SchoolId,
+        created_by: UserId,
+        dto: CreateAssessmentDto,
+    ) -> Result<Assessment, AppError> {
+        Self::validate_scope(db, school_id, dto.term_id, dto.level_id, dto.subject_id).await?;
+        ResultService::ensure_editable(db, dto.term_id, dto.level_id, dto.subject_id).await?;
+
+        let assessment = sqlx::query_as::<_, Assessment>(&format!(
+            "INSERT INTO assessments
+                 (school_id, term_id, level_id, subject_id, name, kind, max_score, weight, due_date, created_by)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             RETURNING {ASSESSMENT_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(dto.term_id)
+        .bind(dto.level_id)
+        .bind(dto.subject_id)
+        .bind(&dto.name)
+        .bind(dto.kind)
+        .bind(dto.max_score)
+        .bind(dto.weight)
+        .bind(dto.due_date)
+        .bind(created_by)
+        .fetch_one(db)
+        .await?;
+
+        Ok(assessment)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_assessments(
+        db: &PgPool,
+        school_id: SchoolId,
+        params: AssessmentFilterParams,
+    ) -> Result<PaginatedAssessmentsResponse, AppError> {
+        let filter = "school_id = $1
+             AND ($2::uuid IS NULL OR term_id = $2)
+             AND ($3::uuid IS NULL OR level_id = $3)
+             AND ($4::uuid IS NULL OR subject_id = $4)
+             AND ($5::text IS NULL OR kind = $5)";
+
+        let assessments = sqlx::query_as::<_, Assessment>(&format!(
+            "SELECT {ASSESSMENT_COLUMNS} FROM assessments
+             WHERE {filter}
+             ORDER BY due_date NULLS LAST, created_at
+             LIMIT $6 OFFSET $7"
+        ))
+        .bind(school_id)
+        .bind(params.term_id)
+        .bind(params.level_id)
+        .bind(params.subject_id)
+        .bind(params.kind)
+        .bind(params.pagination.limit())
+        .bind(params.pagination.offset())
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM assessments WHERE {filter}"
+        ))
+        .bind(school_id)
+        .bind(params.term_id)
+        .bind(params.level_id)
+        .bind(params.subject_id)
+        .bind(params.kind)
+        .fetch_one(db)
+        .await?;
+
+        Ok(PaginatedAssessmentsResponse {
+            data: assessments,
+            meta: page_meta(&params.pagination, total),
+        })
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_assessment(
+        db: &PgPool,
+        assessment_id: AssessmentId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Assessment, AppError> {
+        sqlx::query_as::<_, Assessment>(&format!(
+            "SELECT {ASSESSMENT_COLUMNS} FROM assessments
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(assessment_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Assessment not found")))
+    }
+
+    /// Update an assessment. Lowering the max score below a score already
+    /// recorded is rejected, as is any change once the subject's term
+    /// results have been submitted.
+    #[instrument(skip(db))]
+    pub async fn update_assessment(
+        db: &PgPool,
+        assessment_id: AssessmentId,
+        school_id: Option<SchoolId>,
+        dto: UpdateAssessmentDto,
+    ) -> Result<Assessment, AppError> {
+        let assessment = Self::get_assessment(db, assessment_id, school_id).await?;
+        ResultService::ensure_editable(
+            db,
+            assessment.term_id,
+            assessment.level_id,
+            assessment.subject_id,
+        )
+        .await?;
+
+        if let Some(max_score) = dto.max_score {
+            let exceeded = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM assessment_scores WHERE assessment_id = $1 AND score > $2)",
+            )
+            .bind(assessment_id)
+            .bind(max_score)
+            .fetch_one(db)
+            .await?;
+
+            if exceeded {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "Some recorded scores are higher than the new max score"
+                )));
+            }
+        }
+
+        let assessment = sqlx::query_as::<_, Assessment>(&format!(
+            "UPDATE assessments
+             SET name = COALESCE($2, name),
+                 kind = COALESCE($3, kind),
+                 max_score = COALESCE($4, max_score),
+                 weight = COALESCE($5, weight),
+                 due_date = COALESCE($6, due_date),
+                 updated_at = NOW()
+             WHERE id = $1
+             RETURNING {ASSESSMENT_COLUMNS}"
+        ))
+        .bind(assessment_id)
+        .bind(&dto.name)
+        .bind(dto.kind)
+        .bind(dto.max_score)
+        .bind(dto.weight)
+        .bind(dto.due_date)
+        .fetch_one(db)
+        .await?;
+
+        Ok(assessment)
+    }
+
+    /// Delete an assessment and every score recorded on it.
+    #[instrument(skip(db))]
+    pub async fn delete_assessment(
+        db: &PgPool,
+        assessment_id: AssessmentId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let assessment = Self::get_assessment(db, assessment_id, school_id).await?;
+        ResultService::ensure_editable(
+            db,
+            assessment.term_id,
+            assessment.level_id,
+            assessment.subject_id,
+        )
+        .await?;
+
+        sqlx::query("DELETE FROM assessments WHERE id = $1")
+            .bind(assessment_id)
+            .execute(db)
+            .await?;
+
+        Ok(())
+    }
+}
+
+pub struct GradeService;
+
+impl GradeService {
+    /// Record scores on an assessment.
+    ///
+    /// Only students currently in the assessment's level can be scored; the
+    /// rest are returned in `failed_ids`. A score above the assessment's max
+    /// score rejects the whole submission, as does a finalized assessment or
+    /// one whose term results are no longer open.
+    #[instrument(skip(db, dto))]
+    pub async fn record_scores(
+        db: &PgPool,
+        assessment: &Assessment,
+        recorded_by: UserId,
+        dto: RecordScoresDto,
+    ) -> Result<RecordScoresResponse, AppError> {
+        if assessment.finalized_at.is_some() {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Assessment scores are finalized and cannot be changed"
+            )));
+        }
+        ResultService::ensure_editable(
+            db,
+            assessment.term_id,
+            assessment.level_id,
+            assessment.subject_id,
+        )
+        .await?;
+
+        if let Some(entry) = dto.scores.iter().find(|s| s.score > assessment.max_score) {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Score {} for student {} is above the max score of {}",
+                entry.score,
+                entry.student_id,
+                assessment.max_score
+            )));
+        }
+
+        let student_ids: Vec<UserId> = dto.scores.iter().map(|s| s.student_id).collect();
+
+        let in_level: HashSet<UserId> = sqlx::query_scalar::<_, UserId>(
+            r#"SELECT u.id
+               FROM users u
+               INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $3
+               WHERE u.id = ANY($1) AND u.level_id = $2"#,
+        )
+        .bind(&student_ids)
+        .bind(assessment.level_id)
+        .bind(system_roles::STUDENT)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+        let mut tx = db.begin().await?;
+        let mut recorded = HashSet::new();
+        let mut failed_ids = Vec::new();
+
+        for entry in dto.scores {
+            if !in_level.contains(&entry.student_id) {
+                failed_ids.push(entry.student_id);
+                continue;
+            }
+
+            sqlx::query(
+                r#"INSERT INTO assessment_scores (assessment_id, student_id, score, remarks, recorded_by)
+                   VALUES ($1, $2, $3, $4, $5)
+                   ON CONFLICT (assessment_id, student_id) DO UPDATE SET
+                       score = EXCLUDED.score,
+                       remarks = EXCLUDED.remarks,
+                       recorded_by = EXCLUDED.recorded_by,
+                       updated_at = NOW()"#,
+            )
+            .bind(assessment.id)
+            .bind(entry.student_id)
+            .bind(entry.score)
+            .bind(&entry.remarks)
+            .bind(recorded_by)
+            .execute(&mut *tx)
+            .await?;
+
+            recorded.insert(entry.student_id);
+        }
+
+        tx.commit().await?;
+
+        Ok(RecordScoresResponse {
+            recorded_count: recorded.len(),
+            failed_ids,
+        })
+    }
+
+    /// Finalize an assessment's scores so they can no longer be changed.
+    #[instrument(skip(db))]
+    pub async fn finalize_scores(
+        db: &PgPool,
+        assessment_id: AssessmentId,
+        finalized_by: UserId,
+    ) -> Result<Assessment, AppError> {
+        sqlx::query_as::<_, Assessment>(&format!(
+            "UPDATE assessments SET finalized_at = NOW(), finalized_by = $2, updated_at = NOW()
+             WHERE id = $1 AND finalized_at IS NULL
+             RETURNING {ASSESSMENT_COLUMNS}"
+        ))
+        .bind(assessment_id)
+        .bind(finalized_by)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| {
+            AppError::bad_request(anyhow::anyhow!("Assessment scores are already finalized"))
+        })
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_scores(
+        db: &PgPool,
+        assessment_id: AssessmentId,
+        pagination: PaginationParams,
+    ) -> Result<PaginatedAssessmentScoresResponse, AppError> {
+        let scores = sqlx::query_as::<_, AssessmentScoreWithStudent>(
+            r#"SELECT sc.id, sc.student_id, u.first_name, u.last_name, sc.score, sc.remarks,
+                      sc.recorded_by, sc.updated_at
+               FROM assessment_scores sc
+               INNER JOIN users u ON u.id = sc.student_id
+               WHERE sc.assessment_id = $1
+               ORDER BY u.last_name, u.first_name
+               LIMIT $2 OFFSET $3"#,
+        )
+        .bind(assessment_id)
+        .bind(pagination.limit())
+        .bind(pagination.offset())
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM assessment_scores WHERE assessment_id = $1",
+        )
+        .bind(assessment_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(PaginatedAssessmentScoresResponse {
+            data: scores,
+            meta: page_meta(&pagination, total),
+        })
+    }
+
+    /// Term grades for every student in a level, one row per student and
+    /// subject.
+    #[instrument(skip(db))]
+    pub async fn get_term_grades(
+        db: &PgPool,
+        school_id: Option<SchoolId>,
+        params: TermGradeParams,
+    ) -> Result<PaginatedTermGradesResponse, AppError> {
+        let grouped = term_grade_query(
+            "SELECT u.id AS student_id, u.first_name, u.last_name,
+                    s.id AS subject_id, s.name AS subject_name,
+                    a.id AS assessment_id, a.kind, a.weight, sc.score / a.max_score AS ratio
+             FROM assessments a
+             INNER JOIN subjects s ON s.id = a.subject_id
+             INNER JOIN users u ON u.level_id = a.level_id
+             INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $5
+             LEFT JOIN assessment_scores sc ON sc.assessment_id = a.id AND sc.student_id = u.id
+             WHERE ($1::uuid IS NULL OR a.school_id = $1) AND a.term_id = $2 AND a.level_id = $3
+               AND ($4::uuid IS NULL OR a.subject_id = $4)",
+        );
+
+        let grades = sqlx::query_as::<_, TermGrade>(&format!(
+            "{grouped}
+             ORDER BY r.last_name, r.first_name, r.subject_name
+             LIMIT $6 OFFSET $7"
+        ))
+        .bind(school_id)
+        .bind(params.term_id)
+        .bind(params.level_id)
+        .bind(params.subject_id)
+        .bind(system_roles::STUDENT)
+        .bind(params.pagination.limit())
+        .bind(params.pagination.offset())
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM ({grouped}) g"))
+            .bind(school_id)
+            .bind(params.term_id)
+            .bind(params.level_id)
+            .bind(params.subject_id)
+            .bind(system_roles::STUDENT)
+            .fetch_one(db)
+            .await?;
+
+        Ok(PaginatedTermGradesResponse {
+            data: grades,
+            meta: page_meta(&params.pagination, total),
+        })
+    }
+
+    /// A student's own scored assessments, most recent first.
+    #[instrument(skip(db))]
+    pub async fn get_student_results(
+        db: &PgPool,
+        student_id: UserId,
+        params: StudentResultParams,
+    ) -> Result<PaginatedStudentResultsResponse, AppError> {
+        let results = sqlx::query_as::<_, StudentResult>(
+            r#"SELECT a.id AS assessment_id, a.name AS assessment_name, a.kind, a.term_id,
+                      a.subject_id, s.name AS subject_name, a.max_score, a.weight,
+                      sc.score, sc.remarks, sc.updated_at AS recorded_at
+               FROM assessment_scores sc
+               INNER JOIN assessments a ON a.id = sc.assessment_id
+               INNER JOIN subjects s ON s.id = a.subject_id
+               WHERE sc.student_id = $1 AND ($2::uuid IS NULL OR a.term_id = $2)
+               ORDER BY sc.updated_at DESC
+               LIMIT $3 OFFSET $4"#,
+        )
+        .bind(student_id)
+        .bind(params.term_id)
+        .bind(params.pagination.limit())
+        .bind(params.pagination.offset())
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(
+            r#"SELECT COUNT(*)
+               FROM assessment_scores sc
+               INNER JOIN assessments a ON a.id = sc.assessment_id
+               WHERE sc.student_id = $1 AND ($2::uuid IS NULL OR a.term_id = $2)"#,
+        )
+        .bind(student_id)
+        .bind(params.term_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(PaginatedStudentResultsResponse {
+            data: results,
+            meta: page_meta(&params.pagination, total),
+        })
+    }
+
+    /// A student's own term grades, one per subject. Covers the assessments
+    /// of their current level plus any others they were scored on.
+    #[instrument(skip(db))]
+    pub async fn get_student_term_grades(
+        db: &PgPool,
+        student_id: UserId,
+        term_id: TermId,
+    ) -> Result<Vec<TermGrade>, AppError> {
+        let grades = sqlx::query_as::<_, TermGrade>(&format!(
+            "{}
+             ORDER BY r.subject_name",
+            term_grade_query(
+                "SELECT u.id AS student_id, u.first_name, u.last_name,
+                        s.id AS subject_id, s.name AS subject_name,
+                        a.id AS assessment_id, a.kind, a.weight, sc.score / a.max_score AS ratio
+                 FROM users u
+                 INNER JOIN assessments a ON a.term_id = $2
+                 INNER JOIN subjects s ON s.id = a.subject_id
+                 LEFT JOIN assessment_scores sc ON sc.assessment_id = a.id AND sc.student_id = u.id
+                 WHERE u.id = $1 AND (a.level_id = u.level_id OR sc.id IS NOT NULL)"
+            )
+        ))
+        .bind(student_id)
+        .bind(term_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(grades)
+    }
+
+    /// A school's grading schemes: the school default first, then subject
+    /// overrides by subject name.
+    #[instrument(skip(db))]
+    pub async fn get_grading_schemes(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<Vec<GradingScheme>, AppError> {
+        Self::fetch_grading_schemes(db, Some(school_id), None).await
+    }
+
+    /// Set the grading scheme of a school or one of its subjects, replacing
+    /// any existing scheme for it.
+    #[instrument(skip(db, dto))]
+    pub async fn set_grading_scheme(
+        db: &PgPool,
+        school_id: SchoolId,
+        updated_by: UserId,
+        dto: SetGradingSchemeDto,
+    ) -> Result<GradingScheme, AppError> {
+        if let Some(subject_id) = dto.subject_id {
+            let exists = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM subjects WHERE id = $1 AND school_id = $2)",
+            )
+            .bind(subject_id)
+            .bind(school_id)
+            .fetch_one(db)
+            .await?;
+
+            if !exists {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "Subject not found in this school"
+                )));
+            }
+        }
+
+        let conflict_target = if dto.subject_id.is_some() {
+            "(subject_id) WHERE subject_id IS NOT NULL"
+        } else {
+            "(school_id) WHERE subject_id IS NULL"
+        };
+
+        let mut tx = db.begin().await?;
+
+        let scheme_id = sqlx::query_scalar::<_, GradingSchemeId>(&format!(
+            "INSERT INTO grading_schemes (school_id, subject_id, updated_by)
+             VALUES ($1, $2, $3)
+             ON CONFLICT {conflict_target}
+             DO UPDATE SET updated_by = EXCLUDED.updated_by, updated_at = NOW()
+             RETURNING id"
+        ))
+        .bind(school_id)
+        .bind(dto.subject_id)
+        .bind(updated_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM grading_scheme_components WHERE grading_scheme_id = $1")
+            .bind(scheme_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"INSERT INTO grading_scheme_components (grading_scheme_id, kind, weight, best_of)
+               SELECT $1, c.kind, c.weight, c.best_of
+               FROM UNNEST($2::text[], $3::int[], $4::int[]) AS c(kind, weight, best_of)"#,
+        )
+        .bind(scheme_id)
+        .bind(dto.components.iter().map(|c| c.kind).collect::<Vec<_>>())
+        .bind(dto.components.iter().map(|c| c.weight).collect::<Vec<_>>())
+        .bind(dto.components.iter().map(|c| c.best_of).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Self::fetch_grading_schemes(db, None, Some(scheme_id))
+            .await?
+            .pop()
+            .ok_or_else(|| {
+                AppError::internal_error("Grading scheme not found after saving".to_string())
+            })
+    }
+
+    /// Delete a grading scheme. A subject without its own scheme falls back
+    /// to the school default, and without one to assessment weights.
+    #[instrument(skip(db))]
+    pub async fn delete_grading_scheme(
+        db: &PgPool,
+        id: GradingSchemeId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "DELETE FROM grading_schemes WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Grading scheme not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_grading_schemes(
+        db: &PgPool,
+        school_id: Option<SchoolId>,
+        scheme_id: Option<GradingSchemeId>,
+    ) -> Result<Vec<GradingScheme>, AppError> {
+        let schemes = sqlx::query_as::<_, GradingSchemeRow>(
+            r#"SELECT gs.id, gs.school_id, gs.subject_id, gs.updated_by, gs.updated_at
+               FROM grading_schemes gs
+               LEFT JOIN subjects s ON s.id = gs.subject_id
+               WHERE ($1::uuid IS NULL OR gs.school_id = $1) AND ($2::uuid IS NULL OR gs.id = $2)
+               ORDER BY gs.subject_id IS NOT NULL, s.name"#,
+        )
+        .bind(school_id)
+        .bind(scheme_id)
+        .fetch_all(db)
+        .await?;
+
+        let ids: Vec<GradingSchemeId> = schemes.iter().map(|s| s.id).collect();
+        let components = sqlx::query_as::<_, GradingComponentRow>(
+            r#"SELECT grading_scheme_id, kind, weight, best_of
+               FROM grading_scheme_components
+               WHERE grading_scheme_id = ANY($1)
+               ORDER BY weight DESC, kind"#,
+        )
+        .bind(&ids)
+        .fetch_all(db)
+        .await?;
+
+        Ok(schemes
+            .into_iter()
+            .map(|scheme| GradingScheme {
+                id: scheme.id,
+                school_id: scheme.school_id,
+                subject_id: scheme.subject_id,
+                components: components
+                    .iter()
+                    .filter(|c| c.grading_scheme_id == scheme.id)
+                    .map(|c| c.component.clone())
+                    .collect(),
+                updated_by: scheme.updated_by,
+                updated_at: scheme.updated_at,
+            })
+            .collect())
+    }
+}
+
+#[derive(FromRow)]
+struct GradingSchemeRow {
+    id: GradingSchemeId,
+    school_id: SchoolId,
+    subject_id: Option<SubjectId>,
+    updated_by: Option<UserId>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(FromRow)]
+struct GradingComponentRow {
+    grading_scheme_id: GradingSchemeId,
+    #[sqlx(flatten)]
+    component: GradingComponent,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::assessments::model::{AssessmentKind, ScoreEntryDto};
+    use axum::http::StatusCode;
+    use chalkbyte_models::ids::RoleId;
+    use uuid::Uuid;
+
+    struct Fixture {
+        school_id: SchoolId,
+        term_id: TermId,
+        level_id: LevelId,
+        subject_id: SubjectId,
+    }
+
+    async fn create_fixture(pool: &PgPool) -> Fixture {
+        let school_id = sqlx::query_scalar!(
+            r#"INSERT INTO schools (name) VALUES ($1) RETURNING id"#,
+            format!("School {}", Uuid::new_v4())
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let session_id = sqlx::query_scalar!(
+            r#"INSERT INTO academic_sessions (name, school_id, start_date, end_date)
+               VALUES ('2026/2027', $1, '2026-09-01', '2027-07-31') RETURNING id"#,
+            school_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let term_id = sqlx::query_scalar!(
+            r#"INSERT INTO terms (name, academic_session_id, start_date, end_date)
+               VALUES ('First Term', $1, '2026-09-01', '2026-12-15') RETURNING id"#,
+            session_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let level_id = sqlx::query_scalar!(
+            "INSERT INTO levels (name, school_id) VALUES ('JSS 1', $1) RETURNING id",
+            school_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let subject = AssessmentService::create_subject(
+            pool,
+            school_id.into(),
+            CreateSubjectDto {
+                name: "Mathematics".to_string(),
+                code: Some("MTH".to_string()),
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        Fixture {
+            school_id: school_id.into(),
+            term_id: term_id.into(),
+            level_id: level_id.into(),
+            subject_id: subject.id,
+        }
+    }
+
+    async fn create_test_user(
+        pool: &PgPool,
+        school_id: SchoolId,
+        level_id: Option<LevelId>,
+        role_id: RoleId,
+    ) -> UserId {
+        let user_id = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id, level_id)
+               VALUES ('Ada', 'Tester', $1, $2, $3) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner(),
+            level_id.map(|id| id.into_inner())
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id,
+            role_id.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id.into()
+    }
+
+    async fn create_test_assessment(
+        pool: &PgPool,
+        fixture: &Fixture,
+        max_score: f64,
+        weight: i32,
+    ) -> Assessment {
+        create_test_assessment_of_kind(pool, fixture, AssessmentKind::Quiz, max_score, weight).await
+    }
+
+    async fn create_test_assessment_of_kind(
+        pool: &PgPool,
+        fixture: &Fixture,
+        kind: AssessmentKind,
+        max_score: f64,
+        weight: i32,
+    ) -> Assessment {
+        AssessmentService::create_assessment(
+            pool,
+            fixture.school_id,
+            create_test_user(pool, fixture.school_id, None, system_roles::TEACHER).await,
+            CreateAssessmentDto {
+                term_id: fixture.term_id,
+                level_id: fixture.level_id,
+                subject_id: fixture.subject_id,
+                name: format!("Assessment {}", Uuid::new_v4()),
+                kind,
+                max_score,
+                weight,
+                due_date: None,
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    fn scores(entries: &[(UserId, f64)]) -> RecordScoresDto {
+        RecordScoresDto {
+            scores: entries
+                .iter()
+                .map(|&(student_id, score)| ScoreEntryDto {
+                    student_id,
+                    score,
+                    remarks: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_record_scores_skips_students_outside_level(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let assessment = create_test_assessment(&pool, &fixture, 20.0, 50).await;
+
+        let student = create_test_user(
+            &pool,
+            fixture.school_id,
+            Some(fixture.level_id),
+            system_roles::STUDENT,
+        )
+        .await;
+        let outsider =
+            create_test_user(&pool, fixture.school_id, None, system_roles::STUDENT).await;
+
+        let teacher = assessment.created_by.unwrap();
+        let response = GradeService::record_scores(
+            &pool,
+            &assessment,
+            teacher,
+            scores(&[(student, 15.0), (outsider, 12.0)]),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.recorded_count, 1);
+        assert_eq!(response.failed_ids, vec![outsider]);
+
+        let over_max =
+            GradeService::record_scores(&pool, &assessment, teacher, scores(&[(student, 21.0)]))
+                .await;
+        assert!(over_max.is_err());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_term_grade_is_weighted_average_of_scored_assessments(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let quiz = create_test_assessment(&pool, &fixture, 20.0, 20).await;
+        let exam = create_test_assessment(&pool, &fixture, 100.0, 60).await;
+        // Not yet marked, so it must not drag the grade down
+        create_test_assessment(&pool, &fixture, 10.0, 20).await;
+
+        let student = create_test_user(
+            &pool,
+            fixture.school_id,
+            Some(fixture.level_id),
+            system_roles::STUDENT,
+        )
+        .await;
+
+        let teacher = quiz.created_by.unwrap();
+        GradeService::record_scores(&pool, &quiz, teacher, scores(&[(student, 10.0)]))
+            .await
+            .unwrap();
+        GradeService::record_scores(&pool, &exam, teacher, scores(&[(student, 80.0)]))
+            .await
+            .unwrap();
+
+        let grades = GradeService::get_term_grades(
+            &pool,
+            Some(fixture.school_id),
+            TermGradeParams {
+                term_id: fixture.term_id,
+                level_id: fixture.level_id,
+                subject_id: None,
+                pagination: PaginationParams::default(),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(grades.meta.total, 1);
+        let grade = &grades.data[0];
+        // (20 * 0.5 + 60 * 0.8) / 80 = 72.5%
+        assert_eq!(grade.percentage, 72.5);
+        assert_eq!(grade.assessments_graded, 2);
+        assert_eq!(grade.assessments_total, 3);
+
+        let own = GradeService::get_student_term_grades(&pool, student, fixture.term_id)
+            .await
+            .unwrap();
+        assert_eq!(own.len(), 1);
+        assert_eq!(own[0].percentage, 72.5);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_term_grade_follows_grading_scheme(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let mut quizzes = Vec::new();
+        for _ in 0..3 {
+            quizzes.push(
+                create_test_assessment_of_kind(&pool, &fixture, AssessmentKind::Quiz, 20.0, 10)
+                    .await,
+            );
+        }
+        let exam =
+            create_test_assessment_of_kind(&pool, &fixture, AssessmentKind::Exam, 100.0, 70).await;
+        // Not part of either scheme, so it does not count
+        let assignment =
+            create_test_assessment_of_kind(&pool, &fixture, AssessmentKind::Assignment, 10.0, 0)
+                .await;
+
+        let student = create_test_user(
+            &pool,
+            fixture.school_id,
+            Some(fixture.level_id),
+            system_roles::STUDENT,
+        )
+        .await;
+        let teacher = exam.created_by.unwrap();
+        for (quiz, score) in quizzes.iter().zip([10.0, 18.0, 20.0]) {
+            GradeService::record_scores(&pool, quiz, teacher, scores(&[(student, score)]))
+                .await
+                .unwrap();
+        }
+        GradeService::record_scores(&pool, &assignment, teacher, scores(&[(student, 0.0)]))
+            .await
+            .unwrap();
+
+        let component = |kind, weight, best_of| GradingComponent {
+            kind,
+            weight,
+            best_of,
+        };
+        GradeService::set_grading_scheme(
+            &pool,
+            fixture.school_id,
+            teacher,
+            SetGradingSchemeDto {
+                subject_id: None,
+                components: vec![component(AssessmentKind::Exam, 100, None)],
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap();
+        let subject_scheme = GradeService::set_grading_scheme(
+            &pool,
+            fixture.school_id,
+            teacher,
+            SetGradingSchemeDto {
+                subject_id: Some(fixture.subject_id),
+                components: vec![
+                    component(AssessmentKind::Quiz, 40, Some(2)),
+                    component(AssessmentKind::Exam, 60, None),
+                ],
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let percentage = || async {
+            GradeService::get_student_term_grades(&pool, student, fixture.term_id)
+                .await
+                .unwrap()[0]
+                .percentage
+        };
+
+        // Only the quiz component has scores: best two of 50%, 90%, 100%
+        assert_eq!(percentage().await, 95.0);
+
+        GradeService::record_scores(&pool, &exam, teacher, scores(&[(student, 70.0)]))
+            .await
+            .unwrap();
+        // 40 * 0.95 + 60 * 0.7
+        assert_eq!(percentage().await, 80.0);
+
+        let grades = GradeService::get_term_grades(
+            &pool,
+            Some(fixture.school_id),
+            TermGradeParams {
+                term_id: fixture.term_id,
+                level_id: fixture.level_id,
+                subject_id: None,
+                pagination: PaginationParams::default(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(grades.data[0].percentage, 80.0);
+        assert_eq!(grades.data[0].assessments_graded, 5);
+
+        let schemes = GradeService::get_grading_schemes(&pool, fixture.school_id)
+            .await
+            .unwrap();
+        assert_eq!(schemes.len(), 2);
+        assert!(schemes[0].subject_id.is_none());
+
+        // Without its own scheme the subject falls back to the school default
+        GradeService::delete_grading_scheme(&pool, subject_scheme.id, Some(fixture.school_id))
+            .await
+            .unwrap();
+        assert_eq!(percentage().await, 70.0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_finalized_scores_cannot_change(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let assessment = create_test_assessment(&pool, &fixture, 20.0, 50).await;
+        let student = create_test_user(
+            &pool,
+            fixture.school_id,
+            Some(fixture.level_id),
+            system_roles::STUDENT,
+        )
+        .await;
+        let teacher = assessment.created_by.unwrap();
+
+        let finalized = GradeService::finalize_scores(&pool, assessment.id, teacher)
+            .await
+            .unwrap();
+        assert!(finalized.finalized_at.is_some());
+        assert_eq!(finalized.finalized_by, Some(teacher));
+
+        let again = GradeService::finalize_scores(&pool, assessment.id, teacher).await;
+        assert_eq!(again.unwrap_err().status, StatusCode::BAD_REQUEST);
+
+        let record =
+            GradeService::record_scores(&pool, &finalized, teacher, scores(&[(student, 15.0)]))
+                .await;
+        assert_eq!(record.unwrap_err().status, StatusCode::BAD_REQUEST);
+    }
+}
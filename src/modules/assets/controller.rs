@@ -0,0 +1,360 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::AssetId;
+
+use crate::middleware::auth::{
+    RequireAssetsAssign, RequireAssetsCreate, RequireAssetsDelete, RequireAssetsRead,
+    RequireAssetsUpdate,
+};
+use crate::modules::assets::model::{
+    Asset, AssetAssignment, AssetConditionLog, AssetFilterParams, AssetSummary, AssetSummaryParams,
+    AssignAssetDto, CreateAssetDto, PaginatedAssetsResponse, RecordConditionDto, UpdateAssetDto,
+};
+use crate::modules::assets::service::AssetService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Register a new asset
+#[utoipa::path(
+    post,
+    path = "/api/assets",
+    summary = "Register asset",
+    request_body = CreateAssetDto,
+    responses(
+        Created<Asset>,
+        (status = 400, description = "Invalid input, duplicate asset tag, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assets:create permission")
+    ),
+    tag = "Assets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_asset(
+    State(state): State<AppState>,
+    RequireAssetsCreate(auth_user): RequireAssetsCreate,
+    ValidatedJson(dto): ValidatedJson<CreateAssetDto>,
+) -> Result<Created<Asset>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let recorded_by = auth_user.user_id()?;
+    let asset = AssetService::create_asset(&state.db, school_id, recorded_by, dto).await?;
+
+    Ok(Created(asset))
+}
+
+/// List assets for a school
+#[utoipa::path(
+    get,
+    path = "/api/assets",
+    summary = "List assets",
+    params(AssetFilterParams),
+    responses(
+        (status = 200, description = "List of assets", body = PaginatedAssetsResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assets:read permission")
+    ),
+    tag = "Assets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_assets(
+    State(state): State<AppState>,
+    RequireAssetsRead(auth_user): RequireAssetsRead,
+    Query(filters): Query<AssetFilterParams>,
+) -> Result<Json<PaginatedAssetsResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let assets = AssetService::get_assets(&state.db, school_id, filters).await?;
+
+    Ok(Json(assets))
+}
+
+/// Get audit counts for the asset register
+#[utoipa::path(
+    get,
+    path = "/api/assets/summary",
+    summary = "Asset audit summary",
+    params(AssetSummaryParams),
+    responses(
+        (status = 200, description = "Asset counts by category, status, and condition", body = AssetSummary),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assets:read permission")
+    ),
+    tag = "Assets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_asset_summary(
+    State(state): State<AppState>,
+    RequireAssetsRead(auth_user): RequireAssetsRead,
+    Query(params): Query<AssetSummaryParams>,
+) -> Result<Json<AssetSummary>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let summary = AssetService::get_asset_summary(&state.db, school_id).await?;
+
+    Ok(Json(summary))
+}
+
+/// Get an asset by ID
+#[utoipa::path(
+    get,
+    path = "/api/assets/{id}",
+    summary = "Get asset",
+    params(
+        ("id" = Uuid, Path, description = "Asset ID")
+    ),
+    responses(
+        (status = 200, description = "Asset details", body = Asset),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assets:read permission"),
+        (status = 404, description = "Asset not found")
+    ),
+    tag = "Assets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_asset_by_id(
+    State(state): State<AppState>,
+    RequireAssetsRead(auth_user): RequireAssetsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Asset>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let asset = AssetService::get_asset_by_id(&state.db, AssetId::from(id), school_id).await?;
+
+    Ok(Json(asset))
+}
+
+/// Update an asset
+#[utoipa::path(
+    put,
+    path = "/api/assets/{id}",
+    summary = "Update asset",
+    params(
+        ("id" = Uuid, Path, description = "Asset ID")
+    ),
+    request_body = UpdateAssetDto,
+    responses(
+        (status = 200, description = "Asset updated", body = Asset),
+        (status = 400, description = "Invalid input or duplicate asset tag"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assets:update permission"),
+        (status = 404, description = "Asset not found")
+    ),
+    tag = "Assets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_asset(
+    State(state): State<AppState>,
+    RequireAssetsUpdate(auth_user): RequireAssetsUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateAssetDto>,
+) -> Result<Json<Asset>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let asset = AssetService::update_asset(&state.db, AssetId::from(id), school_id, dto).await?;
+
+    Ok(Json(asset))
+}
+
+/// Delete an asset
+#[utoipa::path(
+    delete,
+    path = "/api/assets/{id}",
+    summary = "Delete asset",
+    params(
+        ("id" = Uuid, Path, description = "Asset ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assets:delete permission"),
+        (status = 404, description = "Asset not found")
+    ),
+    tag = "Assets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_asset(
+    State(state): State<AppState>,
+    RequireAssetsDelete(auth_user): RequireAssetsDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    AssetService::delete_asset(&state.db, AssetId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Assign an asset to a room and/or staff member
+#[utoipa::path(
+    post,
+    path = "/api/assets/{id}/assign",
+    summary = "Assign asset",
+    params(
+        ("id" = Uuid, Path, description = "Asset ID")
+    ),
+    request_body = AssignAssetDto,
+    responses(
+        (status = 200, description = "Asset assigned", body = Asset),
+        (status = 400, description = "No target given, asset lost/retired, or assignee outside the school"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assets:assign permission"),
+        (status = 404, description = "Asset not found")
+    ),
+    tag = "Assets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn assign_asset(
+    State(state): State<AppState>,
+    RequireAssetsAssign(auth_user): RequireAssetsAssign,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<AssignAssetDto>,
+) -> Result<Json<Asset>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assigned_by = auth_user.user_id()?;
+    let asset =
+        AssetService::assign_asset(&state.db, AssetId::from(id), school_id, assigned_by, dto)
+            .await?;
+
+    Ok(Json(asset))
+}
+
+/// Return an assigned asset to storage
+#[utoipa::path(
+    post,
+    path = "/api/assets/{id}/unassign",
+    summary = "Unassign asset",
+    params(
+        ("id" = Uuid, Path, description = "Asset ID")
+    ),
+    responses(
+        (status = 200, description = "Asset returned", body = Asset),
+        (status = 400, description = "Asset is not currently assigned"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assets:assign permission"),
+        (status = 404, description = "Asset not found")
+    ),
+    tag = "Assets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn unassign_asset(
+    State(state): State<AppState>,
+    RequireAssetsAssign(auth_user): RequireAssetsAssign,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Asset>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let asset = AssetService::unassign_asset(&state.db, AssetId::from(id), school_id).await?;
+
+    Ok(Json(asset))
+}
+
+/// Get the assignment history of an asset
+#[utoipa::path(
+    get,
+    path = "/api/assets/{id}/assignments",
+    summary = "Asset assignment history",
+    params(
+        ("id" = Uuid, Path, description = "Asset ID")
+    ),
+    responses(
+        (status = 200, description = "Assignments, most recent first", body = Vec<AssetAssignment>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assets:read permission"),
+        (status = 404, description = "Asset not found")
+    ),
+    tag = "Assets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_assignment_history(
+    State(state): State<AppState>,
+    RequireAssetsRead(auth_user): RequireAssetsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<AssetAssignment>>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let history =
+        AssetService::get_assignment_history(&state.db, AssetId::from(id), school_id).await?;
+
+    Ok(Json(history))
+}
+
+/// Record a condition check for an asset
+#[utoipa::path(
+    post,
+    path = "/api/assets/{id}/condition",
+    summary = "Record asset condition",
+    params(
+        ("id" = Uuid, Path, description = "Asset ID")
+    ),
+    request_body = RecordConditionDto,
+    responses(
+        Created<AssetConditionLog>,
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assets:update permission"),
+        (status = 404, description = "Asset not found")
+    ),
+    tag = "Assets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn record_condition(
+    State(state): State<AppState>,
+    RequireAssetsUpdate(auth_user): RequireAssetsUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<RecordConditionDto>,
+) -> Result<Created<AssetConditionLog>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let recorded_by = auth_user.user_id()?;
+    let log =
+        AssetService::record_condition(&state.db, AssetId::from(id), school_id, recorded_by, dto)
+            .await?;
+
+    Ok(Created(log))
+}
+
+/// Get the condition history of an asset
+#[utoipa::path(
+    get,
+    path = "/api/assets/{id}/condition",
+    summary = "Asset condition history",
+    params(
+        ("id" = Uuid, Path, description = "Asset ID")
+    ),
+    responses(
+        (status = 200, description = "Condition checks, most recent first", body = Vec<AssetConditionLog>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assets:read permission"),
+        (status = 404, description = "Asset not found")
+    ),
+    tag = "Assets",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_condition_history(
+    State(state): State<AppState>,
+    RequireAssetsRead(auth_user): RequireAssetsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<AssetConditionLog>>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let history =
+        AssetService::get_condition_history(&state.db, AssetId::from(id), school_id).await?;
+
+    Ok(Json(history))
+}
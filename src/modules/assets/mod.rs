@@ -0,0 +1,10 @@
+//! Assets module.
+//!
+//! This module maintains the school asset register: devices, lab equipment,
+//! and other property, with room/staff assignment, condition history, and
+//! audit counts.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Asset data models and DTOs.
+//!
+//! This module re-exports asset models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all asset models from the shared crate
+pub use chalkbyte_models::assets::*;
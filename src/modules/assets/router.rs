@@ -0,0 +1,32 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    assign_asset, create_asset, delete_asset, get_asset_by_id, get_asset_summary, get_assets,
+    get_assignment_history, get_condition_history, record_condition, unassign_asset, update_asset,
+};
+
+/// Initialize the assets router
+/// Routes: POST /, GET /, GET /summary, GET /{id}, PUT /{id}, DELETE /{id},
+/// POST /{id}/assign, POST /{id}/unassign, GET /{id}/assignments,
+/// POST /{id}/condition, GET /{id}/condition
+pub fn init_assets_router() -> Router<AppState> {
+    Router::new()
+        .route("/", post(create_asset).get(get_assets))
+        .route("/summary", get(get_asset_summary))
+        .route(
+            "/{id}",
+            get(get_asset_by_id).put(update_asset).delete(delete_asset),
+        )
+        .route("/{id}/assign", post(assign_asset))
+        .route("/{id}/unassign", post(unassign_asset))
+        .route("/{id}/assignments", get(get_assignment_history))
+        .route(
+            "/{id}/condition",
+            post(record_condition).get(get_condition_history),
+        )
+}
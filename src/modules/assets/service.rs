@@ -0,0 +1,795 @@
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_models::ids::{AssetId, SchoolId, UserId};
+
+use crate::modules::assets::model::{
+    Asset, AssetAssignment, AssetCondition, AssetConditionLog, AssetCount, AssetFilterParams,
+    AssetStatus, AssetSummary, AssignAssetDto, CreateAssetDto, PaginatedAssetsResponse,
+    RecordConditionDto, UpdateAssetDto,
+};
+
+const ASSET_COLUMNS: &str = r#"id, school_id, asset_tag, name, category, serial_number, description,
+    purchase_date, condition, status, room, assigned_to, created_at, updated_at"#;
+
+fn map_asset_tag_conflict(e: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e
+        && db_err.is_unique_violation()
+    {
+        return AppError::bad_request(anyhow::anyhow!(
+            "An asset with this tag already exists in this school"
+        ));
+    }
+    AppError::from(e)
+}
+
+pub struct AssetService;
+
+impl AssetService {
+    /// Fetch an asset, optionally restricted to a school.
+    async fn find_asset(
+        db: &PgPool,
+        asset_id: AssetId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Asset, AppError> {
+        sqlx::query_as::<_, Asset>(&format!(
+            "SELECT {ASSET_COLUMNS} FROM assets
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(asset_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Asset not found")))
+    }
+
+    /// Register a new asset for a school.
+    ///
+    /// The initial condition is also written to the condition history so the
+    /// log always starts from the registered state.
+    #[instrument(skip(db))]
+    pub async fn create_asset(
+        db: &PgPool,
+        school_id: SchoolId,
+        recorded_by: UserId,
+        dto: CreateAssetDto,
+    ) -> Result<Asset, AppError> {
+        let condition = dto.condition.unwrap_or(AssetCondition::Good);
+        let mut tx = db.begin().await?;
+
+        let asset = sqlx::query_as::<_, Asset>(&format!(
+            "INSERT INTO assets
+                (school_id, asset_tag, name, category, serial_number, description, purchase_date, condition)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING {ASSET_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.asset_tag)
+        .bind(&dto.name)
+        .bind(dto.category)
+        .bind(&dto.serial_number)
+        .bind(&dto.description)
+        .bind(dto.purchase_date)
+        .bind(condition)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_asset_tag_conflict)?;
+
+        sqlx::query(
+            r#"INSERT INTO asset_condition_logs (asset_id, condition, notes, recorded_by)
+               VALUES ($1, $2, 'Registered', $3)"#,
+        )
+        .bind(asset.id)
+        .bind(condition)
+        .bind(recorded_by)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(asset)
+    }
+
+    /// Get paginated assets for a school.
+    #[instrument(skip(db))]
+    pub async fn get_assets(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: AssetFilterParams,
+    ) -> Result<PaginatedAssetsResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+        let search = filters.search.as_ref().map(|s| format!("%{}%", s));
+
+        let where_clause = r#"WHERE school_id = $1
+              AND ($2::text IS NULL OR category = $2)
+              AND ($3::text IS NULL OR status = $3)
+              AND ($4::text IS NULL OR condition = $4)
+              AND ($5::uuid IS NULL OR assigned_to = $5)
+              AND ($6::text IS NULL OR room = $6)
+              AND ($7::text IS NULL OR name ILIKE $7 OR asset_tag ILIKE $7 OR serial_number ILIKE $7)"#;
+
+        let total =
+            sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM assets {where_clause}"))
+                .bind(school_id)
+                .bind(filters.category)
+                .bind(filters.status)
+                .bind(filters.condition)
+                .bind(filters.assigned_to)
+                .bind(&filters.room)
+                .bind(&search)
+                .fetch_one(db)
+                .await?;
+
+        let data = sqlx::query_as::<_, Asset>(&format!(
+            "SELECT {ASSET_COLUMNS} FROM assets {where_clause}
+             ORDER BY asset_tag ASC
+             LIMIT $8 OFFSET $9"
+        ))
+        .bind(school_id)
+        .bind(filters.category)
+        .bind(filters.status)
+        .bind(filters.condition)
+        .bind(filters.assigned_to)
+        .bind(&filters.room)
+        .bind(&search)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedAssetsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Get an asset by ID.
+    ///
+    /// When `school_id` is provided, the asset must belong to that school.
+    #[instrument(skip(db))]
+    pub async fn get_asset_by_id(
+        db: &PgPool,
+        asset_id: AssetId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Asset, AppError> {
+        Self::find_asset(db, asset_id, school_id).await
+    }
+
+    /// Update an asset's details or lifecycle state.
+    #[instrument(skip(db))]
+    pub async fn update_asset(
+        db: &PgPool,
+        asset_id: AssetId,
+        school_id: Option<SchoolId>,
+        dto: UpdateAssetDto,
+    ) -> Result<Asset, AppError> {
+        let existing = Self::find_asset(db, asset_id, school_id).await?;
+
+        if let Some(status) = dto.status
+            && status != existing.status
+            && matches!(status, AssetStatus::Available | AssetStatus::Assigned)
+        {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Use the assign and unassign endpoints to change assignment status"
+            )));
+        }
+
+        let asset = sqlx::query_as::<_, Asset>(&format!(
+            "UPDATE assets
+             SET asset_tag = COALESCE($2, asset_tag),
+                 name = COALESCE($3, name),
+                 category = COALESCE($4, category),
+                 serial_number = COALESCE($5, serial_number),
+                 description = COALESCE($6, description),
+                 purchase_date = COALESCE($7, purchase_date),
+                 status = COALESCE($8, status)
+             WHERE id = $1
+             RETURNING {ASSET_COLUMNS}"
+        ))
+        .bind(asset_id)
+        .bind(&dto.asset_tag)
+        .bind(&dto.name)
+        .bind(dto.category)
+        .bind(&dto.serial_number)
+        .bind(&dto.description)
+        .bind(dto.purchase_date)
+        .bind(dto.status)
+        .fetch_one(db)
+        .await
+        .map_err(map_asset_tag_conflict)?;
+
+        Ok(asset)
+    }
+
+    /// Delete an asset and its history.
+    #[instrument(skip(db))]
+    pub async fn delete_asset(
+        db: &PgPool,
+        asset_id: AssetId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "DELETE FROM assets WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(asset_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!("Asset not found")));
+        }
+
+        Ok(())
+    }
+
+    /// Assign an asset to a room and/or staff member.
+    ///
+    /// Any open assignment is closed first, so reassigning an asset keeps a
+    /// complete custody trail.
+    #[instrument(skip(db))]
+    pub async fn assign_asset(
+        db: &PgPool,
+        asset_id: AssetId,
+        school_id: Option<SchoolId>,
+        assigned_by: UserId,
+        dto: AssignAssetDto,
+    ) -> Result<Asset, AppError> {
+        if dto.room.is_none() && dto.assigned_to.is_none() {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Either room or assigned_to must be provided"
+            )));
+        }
+
+        let asset = Self::find_asset(db, asset_id, school_id).await?;
+
+        if matches!(asset.status, AssetStatus::Lost | AssetStatus::Retired) {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Lost or retired assets cannot be assigned"
+            )));
+        }
+
+        if let Some(user_id) = dto.assigned_to {
+            let in_school = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND school_id = $2)",
+            )
+            .bind(user_id)
+            .bind(asset.school_id)
+            .fetch_one(db)
+            .await?;
+
+            if !in_school {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "Assignee must be a staff member of the asset's school"
+                )));
+            }
+        }
+
+        let mut tx = db.begin().await?;
+
+        sqlx::query(
+            "UPDATE asset_assignments SET returned_at = NOW() WHERE asset_id = $1 AND returned_at IS NULL",
+        )
+        .bind(asset_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"INSERT INTO asset_assignments (asset_id, room, assigned_to, assigned_by, notes)
+               VALUES ($1, $2, $3, $4, $5)"#,
+        )
+        .bind(asset_id)
+        .bind(&dto.room)
+        .bind(dto.assigned_to)
+        .bind(assigned_by)
+        .bind(&dto.notes)
+        .execute(&mut *tx)
+        .await?;
+
+        let asset = sqlx::query_as::<_, Asset>(&format!(
+            "UPDATE assets
+             SET room = $2, assigned_to = $3, status = 'assigned'
+             WHERE id = $1
+             RETURNING {ASSET_COLUMNS}"
+        ))
+        .bind(asset_id)
+        .bind(&dto.room)
+        .bind(dto.assigned_to)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(asset)
+    }
+
+    /// Return an assigned asset to storage.
+    #[instrument(skip(db))]
+    pub async fn unassign_asset(
+        db: &PgPool,
+        asset_id: AssetId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Asset, AppError> {
+        let asset = Self::find_asset(db, asset_id, school_id).await?;
+
+        if asset.status != AssetStatus::Assigned {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Asset is not currently assigned"
+            )));
+        }
+
+        let mut tx = db.begin().await?;
+
+        sqlx::query(
+            "UPDATE asset_assignments SET returned_at = NOW() WHERE asset_id = $1 AND returned_at IS NULL",
+        )
+        .bind(asset_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let asset = sqlx::query_as::<_, Asset>(&format!(
+            "UPDATE assets
+             SET room = NULL, assigned_to = NULL, status = 'available'
+             WHERE id = $1
+             RETURNING {ASSET_COLUMNS}"
+        ))
+        .bind(asset_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(asset)
+    }
+
+    /// Get the assignment history of an asset, most recent first.
+    #[instrument(skip(db))]
+    pub async fn get_assignment_history(
+        db: &PgPool,
+        asset_id: AssetId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Vec<AssetAssignment>, AppError> {
+        Self::find_asset(db, asset_id, school_id).await?;
+
+        let history = sqlx::query_as::<_, AssetAssignment>(
+            r#"SELECT id, asset_id, room, assigned_to, assigned_by, notes, assigned_at, returned_at
+               FROM asset_assignments
+               WHERE asset_id = $1
+               ORDER BY assigned_at DESC"#,
+        )
+        .bind(asset_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(history)
+    }
+
+    /// Record a condition check and update the asset's current condition.
+    #[instrument(skip(db))]
+    pub async fn record_condition(
+        db: &PgPool,
+        asset_id: AssetId,
+        school_id: Option<SchoolId>,
+        recorded_by: UserId,
+        dto: RecordConditionDto,
+    ) -> Result<AssetConditionLog, AppError> {
+        Self::find_asset(db, asset_id, school_id).await?;
+
+        let mut tx = db.begin().await?;
+
+        let log = sqlx::query_as::<_, AssetConditionLog>(
+            r#"INSERT INTO asset_condition_logs (asset_id, condition, notes, recorded_by)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id, asset_id, condition, notes, recorded_by, recorded_at"#,
+        )
+        .bind(asset_id)
+        .bind(dto.condition)
+        .bind(&dto.notes)
+        .bind(recorded_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE assets SET condition = $2 WHERE id = $1")
+            .bind(asset_id)
+            .bind(dto.condition)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(log)
+    }
+
+    /// Get the condition history of an asset, most recent first.
+    #[instrument(skip(db))]
+    pub async fn get_condition_history(
+        db: &PgPool,
+        asset_id: AssetId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Vec<AssetConditionLog>, AppError> {
+        Self::find_asset(db, asset_id, school_id).await?;
+
+        let history = sqlx::query_as::<_, AssetConditionLog>(
+            r#"SELECT id, asset_id, condition, notes, recorded_by, recorded_at
+               FROM asset_condition_logs
+               WHERE asset_id = $1
+               ORDER BY recorded_at DESC"#,
+        )
+        .bind(asset_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(history)
+    }
+
+    /// Get audit counts for a school's asset register.
+    #[instrument(skip(db))]
+    pub async fn get_asset_summary(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<AssetSummary, AppError> {
+        let total =
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM assets WHERE school_id = $1")
+                .bind(school_id)
+                .fetch_one(db)
+                .await?;
+
+        let by_category = Self::count_by(db, school_id, "category").await?;
+        let by_status = Self::count_by(db, school_id, "status").await?;
+        let by_condition = Self::count_by(db, school_id, "condition").await?;
+
+        Ok(AssetSummary {
+            total,
+            by_category,
+            by_status,
+            by_condition,
+        })
+    }
+
+    /// Group a school's assets by one of the fixed enum columns.
+    async fn count_by(
+        db: &PgPool,
+        school_id: SchoolId,
+        column: &'static str,
+    ) -> Result<Vec<AssetCount>, AppError> {
+        let counts = sqlx::query_as::<_, AssetCount>(&format!(
+            "SELECT {column} AS key, COUNT(*) AS count
+             FROM assets
+             WHERE school_id = $1
+             GROUP BY {column}
+             ORDER BY {column}"
+        ))
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::assets::model::AssetCategory;
+    use axum::http::StatusCode;
+    use chalkbyte_core::PaginationParams;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId) -> UserId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Test', 'Staff', $1, $2) RETURNING id"#,
+            format!("staff-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    fn asset_dto(tag: &str, category: AssetCategory) -> CreateAssetDto {
+        CreateAssetDto {
+            asset_tag: tag.to_string(),
+            name: format!("Asset {}", tag),
+            category,
+            serial_number: None,
+            description: None,
+            purchase_date: None,
+            condition: None,
+            school_id: None,
+        }
+    }
+
+    fn empty_filters() -> AssetFilterParams {
+        AssetFilterParams {
+            school_id: None,
+            category: None,
+            status: None,
+            condition: None,
+            assigned_to: None,
+            room: None,
+            search: None,
+            pagination: PaginationParams::default(),
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_create_asset_logs_initial_condition(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id).await;
+
+        let asset = AssetService::create_asset(
+            &pool,
+            school_id,
+            admin_id,
+            asset_dto("DEV-001", AssetCategory::Device),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(asset.status, AssetStatus::Available);
+        assert_eq!(asset.condition, AssetCondition::Good);
+
+        let history = AssetService::get_condition_history(&pool, asset.id, Some(school_id))
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].condition, AssetCondition::Good);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_create_asset_duplicate_tag(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id).await;
+
+        AssetService::create_asset(
+            &pool,
+            school_id,
+            admin_id,
+            asset_dto("DEV-001", AssetCategory::Device),
+        )
+        .await
+        .unwrap();
+
+        let err = AssetService::create_asset(
+            &pool,
+            school_id,
+            admin_id,
+            asset_dto("DEV-001", AssetCategory::Furniture),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        // Same tag in another school is fine
+        let other_school = create_test_school(&pool).await;
+        assert!(
+            AssetService::create_asset(
+                &pool,
+                other_school,
+                admin_id,
+                asset_dto("DEV-001", AssetCategory::Device),
+            )
+            .await
+            .is_ok()
+        );
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_assign_and_unassign_asset(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id).await;
+        let teacher_id = create_test_user(&pool, school_id).await;
+
+        let asset = AssetService::create_asset(
+            &pool,
+            school_id,
+            admin_id,
+            asset_dto("DEV-001", AssetCategory::Device),
+        )
+        .await
+        .unwrap();
+
+        let assigned = AssetService::assign_asset(
+            &pool,
+            asset.id,
+            Some(school_id),
+            admin_id,
+            AssignAssetDto {
+                room: Some("Lab 1".to_string()),
+                assigned_to: None,
+                notes: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(assigned.status, AssetStatus::Assigned);
+        assert_eq!(assigned.room.as_deref(), Some("Lab 1"));
+
+        let reassigned = AssetService::assign_asset(
+            &pool,
+            asset.id,
+            Some(school_id),
+            admin_id,
+            AssignAssetDto {
+                room: None,
+                assigned_to: Some(teacher_id),
+                notes: Some("Laptop for marking".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(reassigned.assigned_to, Some(teacher_id));
+        assert!(reassigned.room.is_none());
+
+        let history = AssetService::get_assignment_history(&pool, asset.id, Some(school_id))
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(history[0].returned_at.is_none());
+        assert!(history[1].returned_at.is_some());
+
+        let returned = AssetService::unassign_asset(&pool, asset.id, Some(school_id))
+            .await
+            .unwrap();
+        assert_eq!(returned.status, AssetStatus::Available);
+        assert!(returned.assigned_to.is_none());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_assign_asset_rejects_staff_from_other_school(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let other_school = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id).await;
+        let outsider = create_test_user(&pool, other_school).await;
+
+        let asset = AssetService::create_asset(
+            &pool,
+            school_id,
+            admin_id,
+            asset_dto("DEV-001", AssetCategory::Device),
+        )
+        .await
+        .unwrap();
+
+        let err = AssetService::assign_asset(
+            &pool,
+            asset.id,
+            Some(school_id),
+            admin_id,
+            AssignAssetDto {
+                room: None,
+                assigned_to: Some(outsider),
+                notes: None,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_record_condition_and_summary(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id).await;
+
+        let microscope = AssetService::create_asset(
+            &pool,
+            school_id,
+            admin_id,
+            asset_dto("LAB-001", AssetCategory::LabEquipment),
+        )
+        .await
+        .unwrap();
+        AssetService::create_asset(
+            &pool,
+            school_id,
+            admin_id,
+            asset_dto("LAB-002", AssetCategory::LabEquipment),
+        )
+        .await
+        .unwrap();
+        AssetService::create_asset(
+            &pool,
+            school_id,
+            admin_id,
+            asset_dto("DEV-001", AssetCategory::Device),
+        )
+        .await
+        .unwrap();
+
+        AssetService::record_condition(
+            &pool,
+            microscope.id,
+            Some(school_id),
+            admin_id,
+            RecordConditionDto {
+                condition: AssetCondition::Broken,
+                notes: Some("Cracked lens".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        let updated = AssetService::get_asset_by_id(&pool, microscope.id, Some(school_id))
+            .await
+            .unwrap();
+        assert_eq!(updated.condition, AssetCondition::Broken);
+
+        let summary = AssetService::get_asset_summary(&pool, school_id)
+            .await
+            .unwrap();
+        assert_eq!(summary.total, 3);
+        let lab = summary
+            .by_category
+            .iter()
+            .find(|c| c.key == "lab_equipment")
+            .unwrap();
+        assert_eq!(lab.count, 2);
+        let broken = summary
+            .by_condition
+            .iter()
+            .find(|c| c.key == "broken")
+            .unwrap();
+        assert_eq!(broken.count, 1);
+
+        let filtered = AssetService::get_assets(
+            &pool,
+            school_id,
+            AssetFilterParams {
+                search: Some("lab-".to_string()),
+                ..empty_filters()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(filtered.meta.total, 2);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_asset_scoped_to_school(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let other_school = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id).await;
+
+        let asset = AssetService::create_asset(
+            &pool,
+            school_id,
+            admin_id,
+            asset_dto("DEV-001", AssetCategory::Device),
+        )
+        .await
+        .unwrap();
+
+        let err = AssetService::get_asset_by_id(&pool, asset.id, Some(other_school))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let err = AssetService::delete_asset(&pool, asset.id, Some(other_school))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,298 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
+
+use chalkbyte_core::{AppError, permissions};
+use chalkbyte_models::ids::SchoolId;
+
+use crate::middleware::auth::{
+    RequireAttendanceMark, RequireAttendanceRead, RequireSchoolsRead, RequireSchoolsUpdate,
+};
+use crate::middleware::role::is_branch_scoped_teacher_jwt;
+use crate::modules::attendance::model::{
+    AttendanceAnalyticsParams, AttendanceQueryParams, AttendanceRecordWithStudent,
+    AttendanceSettings, AttendanceTrendPoint, ChronicAbsentee, ChronicAbsenteeParams,
+    DayOfWeekAttendance, MarkAttendanceDto, MarkAttendanceResponse, UpdateAttendanceSettingsDto,
+};
+use crate::modules::attendance::service::AttendanceService;
+use crate::modules::branches::service::BranchService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+    verify_school_access,
+};
+use crate::validator::ValidatedJson;
+
+/// Mark attendance for a branch
+///
+/// Students who already have a record for the day are overwritten. Students
+/// who are not in the branch are skipped and returned in `failed_ids`.
+/// Teachers can only mark branches they are assigned to on that day, in any
+/// capacity. Once the school's correction window for the day has closed, only
+/// callers with `attendance:override` can mark it.
+#[utoipa::path(
+    post,
+    path = "/api/attendance",
+    summary = "Mark attendance",
+    request_body = MarkAttendanceDto,
+    responses(
+        (status = 200, description = "Attendance recorded", body = MarkAttendanceResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires attendance:mark permission and, for teachers, an assignment to the branch; ATTENDANCE_CORRECTION_WINDOW_CLOSED when the day can no longer be changed"),
+        (status = 404, description = "Branch not found")
+    ),
+    tag = "Attendance",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn mark_attendance(
+    State(state): State<AppState>,
+    RequireAttendanceMark(auth_user): RequireAttendanceMark,
+    ValidatedJson(dto): ValidatedJson<MarkAttendanceDto>,
+) -> Result<Json<MarkAttendanceResponse>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let school_id =
+        AttendanceService::get_branch_school(&state.db, dto.branch_id, school_id).await?;
+
+    let marked_by = auth_user.user_id()?;
+    if is_branch_scoped_teacher_jwt(&auth_user) {
+        let capacities = BranchService::teacher_capacities_on_branch(
+            &state.db,
+            dto.branch_id,
+            marked_by,
+            dto.date,
+        )
+        .await?;
+        if !capacities.iter().any(|c| c.can_mark_attendance()) {
+            return Err(AppError::forbidden(
+                "You are not assigned to this branch".to_string(),
+            ));
+        }
+    }
+
+    let override_window = auth_user.has_permission(permissions::ATTENDANCE_OVERRIDE);
+    let response = AttendanceService::mark_attendance(
+        &state.db,
+        school_id,
+        marked_by,
+        None,
+        override_window,
+        dto,
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+/// Get a branch's attendance for a day
+#[utoipa::path(
+    get,
+    path = "/api/attendance",
+    summary = "Get branch attendance",
+    params(AttendanceQueryParams),
+    responses(
+        (status = 200, description = "Attendance records ordered by student name", body = Vec<AttendanceRecordWithStudent>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires attendance:read permission"),
+        (status = 404, description = "Branch not found")
+    ),
+    tag = "Attendance",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_attendance(
+    State(state): State<AppState>,
+    RequireAttendanceRead(auth_user): RequireAttendanceRead,
+    Query(params): Query<AttendanceQueryParams>,
+) -> Result<Json<Vec<AttendanceRecordWithStudent>>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    AttendanceService::get_branch_school(&state.db, params.branch_id, school_id).await?;
+
+    let records = AttendanceService::get_branch_attendance(&state.db, params).await?;
+
+    Ok(Json(records))
+}
+
+/// Weekly attendance rate trend over a term
+///
+/// Reports the whole school, or one level or branch when filtered. Present
+/// and late count as attended; excused absences are left out of rates.
+#[utoipa::path(
+    get,
+    path = "/api/attendance/analytics/trends",
+    summary = "Get attendance trends",
+    params(AttendanceAnalyticsParams),
+    responses(
+        (status = 200, description = "Weekly rates with a three-week rolling average", body = Vec<AttendanceTrendPoint>),
+        (status = 400, description = "System admin did not specify school_id"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires attendance:read permission"),
+        (status = 404, description = "Term not found")
+    ),
+    tag = "Attendance",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_attendance_trends(
+    State(state): State<AppState>,
+    RequireAttendanceRead(auth_user): RequireAttendanceRead,
+    Query(params): Query<AttendanceAnalyticsParams>,
+) -> Result<Json<Vec<AttendanceTrendPoint>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let trends = AttendanceService::get_attendance_trends(
+        &state.db,
+        state.cache.as_ref(),
+        school_id,
+        params,
+    )
+    .await?;
+
+    Ok(Json(trends))
+}
+
+/// Students below an attendance rate threshold over a term
+#[utoipa::path(
+    get,
+    path = "/api/attendance/analytics/chronic-absentees",
+    summary = "Get chronic absentees",
+    params(ChronicAbsenteeParams),
+    responses(
+        (status = 200, description = "Students below the threshold, lowest rate first", body = Vec<ChronicAbsentee>),
+        (status = 400, description = "Invalid threshold or system admin did not specify school_id"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires attendance:read permission"),
+        (status = 404, description = "Term not found")
+    ),
+    tag = "Attendance",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_chronic_absentees(
+    State(state): State<AppState>,
+    RequireAttendanceRead(auth_user): RequireAttendanceRead,
+    Query(params): Query<ChronicAbsenteeParams>,
+) -> Result<Json<Vec<ChronicAbsentee>>, AppError> {
+    params.validate().map_err(AppError::validation)?;
+
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let absentees = AttendanceService::get_chronic_absentees(
+        &state.db,
+        state.cache.as_ref(),
+        school_id,
+        params,
+    )
+    .await?;
+
+    Ok(Json(absentees))
+}
+
+/// Attendance rate by weekday over a term
+#[utoipa::path(
+    get,
+    path = "/api/attendance/analytics/day-of-week",
+    summary = "Get day-of-week attendance patterns",
+    params(AttendanceAnalyticsParams),
+    responses(
+        (status = 200, description = "Rates per ISO weekday, compared to the overall rate", body = Vec<DayOfWeekAttendance>),
+        (status = 400, description = "System admin did not specify school_id"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires attendance:read permission"),
+        (status = 404, description = "Term not found")
+    ),
+    tag = "Attendance",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_day_of_week_patterns(
+    State(state): State<AppState>,
+    RequireAttendanceRead(auth_user): RequireAttendanceRead,
+    Query(params): Query<AttendanceAnalyticsParams>,
+) -> Result<Json<Vec<DayOfWeekAttendance>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let days = AttendanceService::get_day_of_week_patterns(
+        &state.db,
+        state.cache.as_ref(),
+        school_id,
+        params,
+    )
+    .await?;
+
+    Ok(Json(days))
+}
+
+/// Get a school's attendance settings
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/attendance-settings",
+    summary = "Get attendance settings",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    responses(
+        (status = 200, description = "Attendance settings", body = AttendanceSettings),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:read permission and access to the school"),
+        (status = 404, description = "School not found")
+    ),
+    tag = "Attendance",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_attendance_settings(
+    State(state): State<AppState>,
+    RequireSchoolsRead(auth_user): RequireSchoolsRead,
+    Path(school_id): Path<Uuid>,
+) -> Result<Json<AttendanceSettings>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let settings = AttendanceService::get_settings(&state.db, school_id).await?;
+
+    Ok(Json(settings))
+}
+
+/// Update a school's attendance settings
+///
+/// Sets how many hours after the end of each day, in the school's timezone,
+/// its attendance can still be marked or changed. Staff with
+/// `attendance:override` can change attendance after the window has closed.
+#[utoipa::path(
+    put,
+    path = "/api/schools/{id}/attendance-settings",
+    summary = "Update attendance settings",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = UpdateAttendanceSettingsDto,
+    responses(
+        (status = 200, description = "Attendance settings updated", body = AttendanceSettings),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:update permission and access to the school"),
+        (status = 404, description = "School not found")
+    ),
+    tag = "Attendance",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn update_attendance_settings(
+    State(state): State<AppState>,
+    RequireSchoolsUpdate(auth_user): RequireSchoolsUpdate,
+    Path(school_id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateAttendanceSettingsDto>,
+) -> Result<Json<AttendanceSettings>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let settings = AttendanceService::update_settings(&state.db, school_id, dto).await?;
+
+    Ok(Json(settings))
+}
@@ -0,0 +1,15 @@
+//! Attendance module.
+//!
+//! Staff mark daily attendance for the students in a branch and read it back
+//! per branch and day. Marking the same student twice on one day overwrites
+//! the earlier record. Shared kiosk devices mark attendance through the same
+//! service, see [`crate::modules::kiosk`].
+//!
+//! Analytics endpoints report weekly rate trends, chronic absentees, and
+//! weekday patterns over a term for the admin dashboard. Reports are cached
+//! for a few minutes, so newly marked attendance can take that long to show.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
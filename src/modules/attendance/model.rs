@@ -0,0 +1,7 @@
+//! Attendance data models and DTOs.
+//!
+//! This module re-exports attendance models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all attendance models from the shared crate
+pub use chalkbyte_models::attendance::*;
@@ -0,0 +1,31 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    get_attendance, get_attendance_settings, get_attendance_trends, get_chronic_absentees,
+    get_day_of_week_patterns, mark_attendance, update_attendance_settings,
+};
+
+/// Initialize the attendance router
+/// Routes: POST /, GET /, GET /analytics/trends, GET /analytics/chronic-absentees,
+/// GET /analytics/day-of-week
+pub fn init_attendance_router() -> Router<AppState> {
+    Router::new()
+        .route("/", post(mark_attendance).get(get_attendance))
+        .route("/analytics/trends", get(get_attendance_trends))
+        .route("/analytics/chronic-absentees", get(get_chronic_absentees))
+        .route("/analytics/day-of-week", get(get_day_of_week_patterns))
+}
+
+/// Initialize the school attendance settings router, merged into the schools router
+/// Routes: GET/PUT /{id}/attendance-settings
+pub fn init_school_attendance_router() -> Router<AppState> {
+    Router::new().route(
+        "/{id}/attendance-settings",
+        get(get_attendance_settings).put(update_attendance_settings),
+    )
+}
@@ -0,0 +1,739 @@
+use std::collections::HashSet;
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Serialize, de::DeserializeOwned};
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_cache::{RedisCache, hash_filters, keys};
+use chalkbyte_core::{AppError, ErrorCode};
+use chalkbyte_models::ids::{BranchId, KioskDeviceId, SchoolId, TermId, UserId};
+
+use crate::modules::attendance::model::{
+    AttendanceAnalyticsParams, AttendanceQueryParams, AttendanceRecordWithStudent,
+    AttendanceSettings, AttendanceTrendPoint, ChronicAbsentee, ChronicAbsenteeParams,
+    DayOfWeekAttendance, MarkAttendanceDto, MarkAttendanceResponse, UpdateAttendanceSettingsDto,
+};
+use crate::modules::schools::service::SchoolService;
+use crate::modules::users::model::system_roles;
+
+/// How long analytics reports are served before a single caller recomputes them.
+const ANALYTICS_CACHE_TTL: Duration = Duration::from_secs(300);
+
+const ATTENDANCE_SETTINGS_COLUMNS: &str =
+    "id AS school_id, attendance_correction_window_hours AS correction_window_hours";
+
+/// Attendance records that count towards rates, with whether the student
+/// attended. Binds the school ($1), the date range ($2, $3), and the optional
+/// level ($4) and branch ($5).
+const COUNTED_ATTENDANCE_CTE: &str = r#"counted AS (
+    SELECT a.student_id, a.date, a.status IN ('present', 'late') AS attended
+    FROM attendance_records a
+    INNER JOIN branches b ON b.id = a.branch_id
+    WHERE a.school_id = $1
+      AND a.date BETWEEN $2 AND $3
+      AND a.status <> 'excused'
+      AND ($4::uuid IS NULL OR b.level_id = $4)
+      AND ($5::uuid IS NULL OR a.branch_id = $5)
+)"#;
+
+/// Serve a report from the cache when one is connected.
+async fn cached_report<T, F, Fut>(
+    cache: Option<&RedisCache>,
+    cache_key: String,
+    compute: F,
+) -> Result<T, AppError>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, AppError>>,
+{
+    match cache {
+        Some(cache) => {
+            cache
+                .get_or_compute(&cache_key, ANALYTICS_CACHE_TTL, compute)
+                .await
+        }
+        None => compute().await,
+    }
+}
+
+pub struct AttendanceService;
+
+impl AttendanceService {
+    /// Resolve the school a branch belongs to, optionally requiring a school.
+    #[instrument(skip(db))]
+    pub async fn get_branch_school(
+        db: &PgPool,
+        branch_id: BranchId,
+        school_id: Option<SchoolId>,
+    ) -> Result<SchoolId, AppError> {
+        sqlx::query_scalar::<_, SchoolId>(
+            r#"SELECT l.school_id
+               FROM branches b
+               INNER JOIN levels l ON l.id = b.level_id
+               WHERE b.id = $1 AND ($2::uuid IS NULL OR l.school_id = $2)"#,
+        )
+        .bind(branch_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::BranchNotFound))
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_settings(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<AttendanceSettings, AppError> {
+        sqlx::query_as::<_, AttendanceSettings>(&format!(
+            "SELECT {ATTENDANCE_SETTINGS_COLUMNS} FROM schools WHERE id = $1"
+        ))
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))
+    }
+
+    #[instrument(skip(db))]
+    pub async fn update_settings(
+        db: &PgPool,
+        school_id: SchoolId,
+        dto: UpdateAttendanceSettingsDto,
+    ) -> Result<AttendanceSettings, AppError> {
+        sqlx::query_as::<_, AttendanceSettings>(&format!(
+            "UPDATE schools
+             SET attendance_correction_window_hours = $2, updated_at = NOW()
+             WHERE id = $1
+             RETURNING {ATTENDANCE_SETTINGS_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(dto.correction_window_hours)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))
+    }
+
+    /// Refuse changes to a day's attendance once the school's correction
+    /// window for that day has closed.
+    ///
+    /// The window runs from the end of the day in the school's timezone.
+    #[instrument(skip(db))]
+    pub async fn check_correction_window(
+        db: &PgPool,
+        school_id: SchoolId,
+        date: NaiveDate,
+    ) -> Result<(), AppError> {
+        let window = sqlx::query_as::<_, (i32, DateTime<Utc>, bool)>(
+            r#"SELECT hours, closes_at, closes_at <= NOW()
+               FROM (
+                   SELECT attendance_correction_window_hours AS hours,
+                          (($2::date + 1)::timestamp AT TIME ZONE timezone)
+                              + make_interval(hours => attendance_correction_window_hours) AS closes_at
+                   FROM schools
+                   WHERE id = $1 AND attendance_correction_window_hours IS NOT NULL
+               ) w"#,
+        )
+        .bind(school_id)
+        .bind(date)
+        .fetch_optional(db)
+        .await?;
+
+        match window {
+            Some((hours, closes_at, true)) => {
+                let settings = SchoolService::get_settings(db, None, school_id).await?;
+                Err(AppError::forbidden(format!(
+                    "Attendance for {date} can no longer be changed: the school's {hours}-hour \
+                     correction window closed at {} ({}). Ask an administrator to correct it.",
+                    settings.local_time(closes_at).format("%Y-%m-%d %H:%M"),
+                    settings.timezone
+                ))
+                .with_code(ErrorCode::AttendanceCorrectionWindowClosed))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Record attendance for students in a branch on one day.
+    ///
+    /// Students who are not in the branch are skipped and reported back in
+    /// `failed_ids`. The caller must have checked the branch belongs to
+    /// `school_id`. Days whose correction window has closed are refused
+    /// unless `override_window` is set.
+    #[instrument(skip(db, dto))]
+    pub async fn mark_attendance(
+        db: &PgPool,
+        school_id: SchoolId,
+        marked_by: UserId,
+        kiosk_device_id: Option<KioskDeviceId>,
+        override_window: bool,
+        dto: MarkAttendanceDto,
+    ) -> Result<MarkAttendanceResponse, AppError> {
+        if !override_window {
+            Self::check_correction_window(db, school_id, dto.date).await?;
+        }
+
+        let student_ids: Vec<UserId> = dto.records.iter().map(|r| r.student_id).collect();
+
+        let in_branch: HashSet<UserId> = sqlx::query_scalar::<_, UserId>(
+            r#"SELECT u.id
+               FROM users u
+               INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $3
+               WHERE u.id = ANY($1) AND u.branch_id = $2"#,
+        )
+        .bind(&student_ids)
+        .bind(dto.branch_id)
+        .bind(system_roles::STUDENT)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+        let mut tx = db.begin().await?;
+        let mut recorded = HashSet::new();
+        let mut failed_ids = Vec::new();
+
+        for entry in dto.records {
+            if !in_branch.contains(&entry.student_id) {
+                failed_ids.push(entry.student_id);
+                continue;
+            }
+
+            sqlx::query(
+                r#"INSERT INTO attendance_records
+                       (school_id, branch_id, student_id, date, status, note, marked_by, kiosk_device_id)
+                   VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                   ON CONFLICT (student_id, date) DO UPDATE SET
+                       branch_id = EXCLUDED.branch_id,
+                       status = EXCLUDED.status,
+                       note = EXCLUDED.note,
+                       marked_by = EXCLUDED.marked_by,
+                       kiosk_device_id = EXCLUDED.kiosk_device_id,
+                       updated_at = NOW()"#,
+            )
+            .bind(school_id)
+            .bind(dto.branch_id)
+            .bind(entry.student_id)
+            .bind(dto.date)
+            .bind(entry.status)
+            .bind(&entry.note)
+            .bind(marked_by)
+            .bind(kiosk_device_id)
+            .execute(&mut *tx)
+            .await?;
+
+            recorded.insert(entry.student_id);
+        }
+
+        tx.commit().await?;
+
+        Ok(MarkAttendanceResponse {
+            recorded_count: recorded.len(),
+            failed_ids,
+        })
+    }
+
+    /// Attendance marked for a branch on one day, by student name.
+    #[instrument(skip(db))]
+    pub async fn get_branch_attendance(
+        db: &PgPool,
+        params: AttendanceQueryParams,
+    ) -> Result<Vec<AttendanceRecordWithStudent>, AppError> {
+        let records = sqlx::query_as::<_, AttendanceRecordWithStudent>(
+            r#"SELECT a.id, a.student_id, u.first_name, u.last_name, a.date, a.status, a.note,
+                      a.marked_by, a.kiosk_device_id, a.updated_at
+               FROM attendance_records a
+               INNER JOIN users u ON u.id = a.student_id
+               WHERE a.branch_id = $1 AND a.date = $2
+               ORDER BY u.last_name, u.first_name"#,
+        )
+        .bind(params.branch_id)
+        .bind(params.date)
+        .fetch_all(db)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// First and last day of a term in the school.
+    async fn get_term_range(
+        db: &PgPool,
+        school_id: SchoolId,
+        term_id: TermId,
+    ) -> Result<(NaiveDate, NaiveDate), AppError> {
+        sqlx::query_as::<_, (NaiveDate, NaiveDate)>(
+            r#"SELECT t.start_date, t.end_date
+               FROM terms t
+               INNER JOIN academic_sessions s ON s.id = t.academic_session_id
+               WHERE t.id = $1 AND s.school_id = $2"#,
+        )
+        .bind(term_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::TermNotFound))
+    }
+
+    /// Weekly attendance rates over a term, with a three-week rolling average.
+    #[instrument(skip(db, cache))]
+    pub async fn get_attendance_trends(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        params: AttendanceAnalyticsParams,
+    ) -> Result<Vec<AttendanceTrendPoint>, AppError> {
+        let (start, end) = Self::get_term_range(db, school_id, params.term_id).await?;
+        let cache_key =
+            keys::attendance::analytics(school_id.into(), "trends", &hash_filters(&params));
+
+        cached_report(cache, cache_key, || async move {
+            let points = sqlx::query_as::<_, AttendanceTrendPoint>(&format!(
+                r#"WITH {COUNTED_ATTENDANCE_CTE},
+                   weekly AS (
+                       SELECT date_trunc('week', date)::date AS week_start,
+                              COUNT(*) FILTER (WHERE attended) AS attended,
+                              COUNT(*) AS counted
+                       FROM counted
+                       GROUP BY 1
+                   )
+                   SELECT week_start, attended, counted,
+                          ROUND(attended * 100.0 / counted, 2)::float8 AS rate,
+                          ROUND(AVG(attended * 100.0 / counted) OVER (
+                              ORDER BY week_start ROWS BETWEEN 2 PRECEDING AND CURRENT ROW
+                          ), 2)::float8 AS rolling_rate
+                   FROM weekly
+                   ORDER BY week_start"#
+            ))
+            .bind(school_id)
+            .bind(start)
+            .bind(end)
+            .bind(params.level_id)
+            .bind(params.branch_id)
+            .fetch_all(db)
+            .await?;
+
+            Ok(points)
+        })
+        .await
+    }
+
+    /// Students whose attendance rate over a term is below the threshold,
+    /// lowest rate first.
+    #[instrument(skip(db, cache))]
+    pub async fn get_chronic_absentees(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        params: ChronicAbsenteeParams,
+    ) -> Result<Vec<ChronicAbsentee>, AppError> {
+        let (start, end) = Self::get_term_range(db, school_id, params.term_id).await?;
+        let cache_key = keys::attendance::analytics(
+            school_id.into(),
+            "chronic_absentees",
+            &hash_filters(&params),
+        );
+
+        cached_report(cache, cache_key, || async move {
+            let absentees = sqlx::query_as::<_, ChronicAbsentee>(&format!(
+                r#"WITH {COUNTED_ATTENDANCE_CTE},
+                   per_student AS (
+                       SELECT student_id,
+                              COUNT(*) FILTER (WHERE attended) AS attended,
+                              COUNT(*) AS counted
+                       FROM counted
+                       GROUP BY student_id
+                   )
+                   SELECT p.student_id, u.first_name, u.last_name, u.branch_id,
+                          p.attended, p.counted,
+                          ROUND(p.attended * 100.0 / p.counted, 2)::float8 AS rate
+                   FROM per_student p
+                   INNER JOIN users u ON u.id = p.student_id
+                   WHERE p.attended * 100.0 / p.counted < $6
+                   ORDER BY rate, u.last_name, u.first_name"#
+            ))
+            .bind(school_id)
+            .bind(start)
+            .bind(end)
+            .bind(params.level_id)
+            .bind(params.branch_id)
+            .bind(i32::from(params.threshold()))
+            .fetch_all(db)
+            .await?;
+
+            Ok(absentees)
+        })
+        .await
+    }
+
+    /// Attendance rate per weekday over a term, compared to the overall rate.
+    #[instrument(skip(db, cache))]
+    pub async fn get_day_of_week_patterns(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        params: AttendanceAnalyticsParams,
+    ) -> Result<Vec<DayOfWeekAttendance>, AppError> {
+        let (start, end) = Self::get_term_range(db, school_id, params.term_id).await?;
+        let cache_key =
+            keys::attendance::analytics(school_id.into(), "day_of_week", &hash_filters(&params));
+
+        cached_report(cache, cache_key, || async move {
+            let days = sqlx::query_as::<_, DayOfWeekAttendance>(&format!(
+                r#"WITH {COUNTED_ATTENDANCE_CTE},
+                   per_day AS (
+                       SELECT EXTRACT(ISODOW FROM date)::int AS day_of_week,
+                              COUNT(*) FILTER (WHERE attended) AS attended,
+                              COUNT(*) AS counted
+                       FROM counted
+                       GROUP BY 1
+                   )
+                   SELECT day_of_week, attended, counted,
+                          ROUND(attended * 100.0 / counted, 2)::float8 AS rate,
+                          ROUND(attended * 100.0 / counted
+                                - SUM(attended) OVER () * 100.0 / SUM(counted) OVER (), 2
+                          )::float8 AS difference_from_overall
+                   FROM per_day
+                   ORDER BY day_of_week"#
+            ))
+            .bind(school_id)
+            .bind(start)
+            .bind(end)
+            .bind(params.level_id)
+            .bind(params.branch_id)
+            .fetch_all(db)
+            .await?;
+
+            Ok(days)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::attendance::model::{AttendanceEntryDto, AttendanceStatus};
+    use uuid::Uuid;
+
+    struct Fixture {
+        school_id: SchoolId,
+        term_id: TermId,
+        branch_id: BranchId,
+    }
+
+    async fn create_fixture(pool: &PgPool) -> Fixture {
+        let school_id = sqlx::query_scalar!(
+            r#"INSERT INTO schools (name) VALUES ($1) RETURNING id"#,
+            format!("School {}", Uuid::new_v4())
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let session_id = sqlx::query_scalar!(
+            r#"INSERT INTO academic_sessions (name, school_id, start_date, end_date)
+               VALUES ('2026/2027', $1, '2026-09-01', '2027-07-31') RETURNING id"#,
+            school_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let term_id = sqlx::query_scalar!(
+            r#"INSERT INTO terms (name, academic_session_id, start_date, end_date)
+               VALUES ('First Term', $1, '2026-09-01', '2026-12-15') RETURNING id"#,
+            session_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let level_id = sqlx::query_scalar!(
+            "INSERT INTO levels (name, school_id) VALUES ('JSS 1', $1) RETURNING id",
+            school_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let branch_id = sqlx::query_scalar!(
+            "INSERT INTO branches (name, level_id) VALUES ('A', $1) RETURNING id",
+            level_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        Fixture {
+            school_id: school_id.into(),
+            term_id: term_id.into(),
+            branch_id: branch_id.into(),
+        }
+    }
+
+    async fn create_student(pool: &PgPool, fixture: &Fixture, last_name: &str) -> UserId {
+        let user_id = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id, branch_id)
+               VALUES ('Ada', $1, $2, $3, $4) RETURNING id"#,
+            last_name,
+            format!("student-{}@example.com", Uuid::new_v4()),
+            fixture.school_id.into_inner(),
+            fixture.branch_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id,
+            system_roles::STUDENT.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id.into()
+    }
+
+    async fn mark(
+        pool: &PgPool,
+        fixture: &Fixture,
+        date: &str,
+        records: &[(UserId, AttendanceStatus)],
+    ) {
+        let marked_by = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Grace', 'Teacher', $1, $2) RETURNING id"#,
+            format!("teacher-{}@example.com", Uuid::new_v4()),
+            fixture.school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        AttendanceService::mark_attendance(
+            pool,
+            fixture.school_id,
+            marked_by.into(),
+            None,
+            false,
+            MarkAttendanceDto {
+                branch_id: fixture.branch_id,
+                date: date.parse().unwrap(),
+                records: records
+                    .iter()
+                    .map(|&(student_id, status)| AttendanceEntryDto {
+                        student_id,
+                        status,
+                        note: None,
+                    })
+                    .collect(),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_attendance_analytics(pool: PgPool) {
+        use AttendanceStatus::*;
+
+        let fixture = create_fixture(&pool).await;
+        let regular = create_student(&pool, &fixture, "Regular").await;
+        let absentee = create_student(&pool, &fixture, "Absentee").await;
+
+        // Two Monday/Tuesday pairs; the excused absence is left out of rates
+        mark(
+            &pool,
+            &fixture,
+            "2026-09-07",
+            &[(regular, Present), (absentee, Present)],
+        )
+        .await;
+        mark(
+            &pool,
+            &fixture,
+            "2026-09-08",
+            &[(regular, Late), (absentee, Absent)],
+        )
+        .await;
+        mark(
+            &pool,
+            &fixture,
+            "2026-09-14",
+            &[(regular, Present), (absentee, Absent)],
+        )
+        .await;
+        mark(
+            &pool,
+            &fixture,
+            "2026-09-15",
+            &[(regular, Present), (absentee, Excused)],
+        )
+        .await;
+
+        let params = AttendanceAnalyticsParams {
+            term_id: fixture.term_id,
+            level_id: None,
+            branch_id: Some(fixture.branch_id),
+            school_id: None,
+        };
+
+        let trends = AttendanceService::get_attendance_trends(
+            &pool,
+            None,
+            fixture.school_id,
+            params.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(trends.len(), 2);
+        assert_eq!((trends[0].attended, trends[0].counted), (3, 4));
+        assert_eq!(trends[0].rate, 75.0);
+        assert_eq!(trends[1].rate, 66.67);
+        assert_eq!(trends[1].rolling_rate, 70.83);
+
+        let absentees = AttendanceService::get_chronic_absentees(
+            &pool,
+            None,
+            fixture.school_id,
+            ChronicAbsenteeParams {
+                term_id: fixture.term_id,
+                threshold: None,
+                level_id: None,
+                branch_id: None,
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(absentees.len(), 1);
+        assert_eq!(absentees[0].student_id, absentee);
+        assert_eq!((absentees[0].attended, absentees[0].counted), (1, 3));
+        assert_eq!(absentees[0].rate, 33.33);
+
+        let days =
+            AttendanceService::get_day_of_week_patterns(&pool, None, fixture.school_id, params)
+                .await
+                .unwrap();
+        let summary: Vec<_> = days
+            .iter()
+            .map(|d| (d.day_of_week, d.rate, d.difference_from_overall))
+            .collect();
+        assert_eq!(summary, [(1, 75.0, 3.57), (2, 66.67, -4.76)]);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_attendance_analytics_rejects_other_school_term(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let other = create_fixture(&pool).await;
+
+        let result = AttendanceService::get_attendance_trends(
+            &pool,
+            None,
+            fixture.school_id,
+            AttendanceAnalyticsParams {
+                term_id: other.term_id,
+                level_id: None,
+                branch_id: None,
+                school_id: None,
+            },
+        )
+        .await;
+        assert_eq!(
+            result.unwrap_err().status,
+            axum::http::StatusCode::NOT_FOUND
+        );
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_correction_window_refuses_closed_days(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let student = create_student(&pool, &fixture, "Late").await;
+        let today = Utc::now().date_naive();
+        let last_week = today - chrono::Days::new(7);
+        let dto = |date| MarkAttendanceDto {
+            branch_id: fixture.branch_id,
+            date,
+            records: vec![AttendanceEntryDto {
+                student_id: student,
+                status: AttendanceStatus::Present,
+                note: None,
+            }],
+        };
+
+        // No limit until the school sets one
+        AttendanceService::check_correction_window(&pool, fixture.school_id, last_week)
+            .await
+            .unwrap();
+
+        let settings = AttendanceService::update_settings(
+            &pool,
+            fixture.school_id,
+            UpdateAttendanceSettingsDto {
+                correction_window_hours: Some(48),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(settings.correction_window_hours, Some(48));
+
+        let marked = AttendanceService::mark_attendance(
+            &pool,
+            fixture.school_id,
+            student,
+            None,
+            false,
+            dto(today),
+        )
+        .await
+        .unwrap();
+        assert_eq!(marked.recorded_count, 1);
+
+        let error = AttendanceService::mark_attendance(
+            &pool,
+            fixture.school_id,
+            student,
+            None,
+            false,
+            dto(last_week),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.code, ErrorCode::AttendanceCorrectionWindowClosed);
+        assert_eq!(error.status, axum::http::StatusCode::FORBIDDEN);
+        assert!(
+            error
+                .error
+                .to_string()
+                .contains("48-hour correction window")
+        );
+
+        // Overriding skips the window
+        let marked = AttendanceService::mark_attendance(
+            &pool,
+            fixture.school_id,
+            student,
+            None,
+            true,
+            dto(last_week),
+        )
+        .await
+        .unwrap();
+        assert_eq!(marked.recorded_count, 1);
+
+        let settings = AttendanceService::update_settings(
+            &pool,
+            fixture.school_id,
+            UpdateAttendanceSettingsDto {
+                correction_window_hours: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(settings.correction_window_hours, None);
+    }
+}
@@ -1,26 +1,44 @@
-use chalkbyte_core::AppError;
+use chalkbyte_core::{AppError, ErrorCode, FieldError, NoContent};
 
 use crate::state::AppState;
 use crate::validator::ValidatedJson;
 use axum::Json;
-use axum::extract::State;
+use axum::extract::{Path, State};
 
 use axum::response::IntoResponse;
+use jsonwebtoken::jwk::JwkSet;
 use tracing::instrument;
 use utoipa::ToSchema;
 
 use super::model::{
-    ForgotPasswordRequest, LoginRequest, LoginResponse, MessageResponse, MfaRecoveryLoginRequest,
-    MfaRequiredResponse, MfaVerifyLoginRequest, RefreshTokenRequest, ResetPasswordRequest,
+    DeviceSession, ForgotPasswordRequest, LoginRequest, LoginResponse, LogoutRequest,
+    MessageResponse, MfaPasskeyChallengeRequest, MfaPasskeyChallengeResponse,
+    MfaPasskeyLoginRequest, MfaRecoveryLoginRequest, MfaRequiredResponse, MfaVerifyLoginRequest,
+    RefreshTokenRequest, ResetPasswordRequest,
 };
 use super::service::AuthService;
 use crate::middleware::auth::AuthUser;
+use crate::middleware::client::RequestClient;
+use crate::utils::email::EmailService;
 use uuid::Uuid;
 
+// Only referenced from the OpenAPI docs
+#[cfg_attr(not(feature = "scalar"), allow(dead_code))]
 #[derive(ToSchema)]
 pub struct ErrorResponse {
+    /// Human-readable message, localized when the client's `Accept-Language`
+    /// has a message bundle
     #[allow(dead_code)]
     pub error: String,
+    /// Machine-readable error code
+    #[allow(dead_code)]
+    pub code: ErrorCode,
+    /// ID of the request, for support to look up
+    #[allow(dead_code)]
+    pub request_id: Option<String>,
+    /// Failed field constraints; only present on `VALIDATION_FAILED` errors
+    #[allow(dead_code)]
+    pub fields: Option<Vec<FieldError>>,
 }
 
 /// Login and receive JWT token or MFA challenge
@@ -41,9 +59,19 @@ pub struct ErrorResponse {
 #[instrument]
 pub async fn login_user(
     State(state): State<AppState>,
+    RequestClient(client): RequestClient,
     ValidatedJson(dto): ValidatedJson<LoginRequest>,
 ) -> Result<axum::response::Response, AppError> {
-    match AuthService::login_user(&state.db, dto, &state.jwt_config).await? {
+    match AuthService::login_user(
+        &state.db,
+        state.token_store.as_ref(),
+        state.mfa_challenges.as_ref(),
+        dto,
+        client,
+        &state.jwt_config,
+    )
+    .await?
+    {
         Ok(login_response) => Ok(Json(login_response).into_response()),
         Err(mfa_required) => Ok(Json(mfa_required).into_response()),
     }
@@ -57,7 +85,7 @@ pub async fn login_user(
     request_body = MfaVerifyLoginRequest,
     responses(
         (status = 200, description = "MFA verification successful", body = LoginResponse),
-        (status = 401, description = "Invalid MFA code or temp token", body = ErrorResponse),
+        (status = 401, description = "Invalid MFA code, or the login expired or ran out of attempts", body = ErrorResponse),
         (status = 400, description = "Bad request - validation error", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
@@ -66,9 +94,18 @@ pub async fn login_user(
 #[instrument]
 pub async fn verify_mfa_login(
     State(state): State<AppState>,
+    RequestClient(client): RequestClient,
     ValidatedJson(dto): ValidatedJson<MfaVerifyLoginRequest>,
 ) -> Result<Json<LoginResponse>, AppError> {
-    let response = AuthService::verify_mfa_login(&state.db, dto, &state.jwt_config).await?;
+    let response = AuthService::verify_mfa_login(
+        &state.db,
+        state.token_store.as_ref(),
+        state.mfa_challenges.as_ref(),
+        dto,
+        client,
+        &state.jwt_config,
+    )
+    .await?;
     Ok(Json(response))
 }
 
@@ -80,7 +117,7 @@ pub async fn verify_mfa_login(
     request_body = MfaRecoveryLoginRequest,
     responses(
         (status = 200, description = "Recovery code verification successful", body = LoginResponse),
-        (status = 401, description = "Invalid recovery code or temp token", body = ErrorResponse),
+        (status = 401, description = "Invalid recovery code, or the login expired or ran out of attempts", body = ErrorResponse),
         (status = 400, description = "Bad request - validation error", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
@@ -89,10 +126,81 @@ pub async fn verify_mfa_login(
 #[instrument]
 pub async fn verify_mfa_recovery_login(
     State(state): State<AppState>,
+    RequestClient(client): RequestClient,
     ValidatedJson(dto): ValidatedJson<MfaRecoveryLoginRequest>,
 ) -> Result<Json<LoginResponse>, AppError> {
-    let response =
-        AuthService::verify_mfa_recovery_login(&state.db, dto, &state.jwt_config).await?;
+    let response = AuthService::verify_mfa_recovery_login(
+        &state.db,
+        state.token_store.as_ref(),
+        state.mfa_challenges.as_ref(),
+        dto,
+        client,
+        &EmailService::new(state.db.clone(), state.email_config.clone()),
+        &state.jwt_config,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+/// Get a passkey challenge to complete login
+#[utoipa::path(
+    post,
+    path = "/api/auth/mfa/passkey/challenge",
+    summary = "Start passkey verification",
+    request_body = MfaPasskeyChallengeRequest,
+    responses(
+        (status = 200, description = "Options for navigator.credentials.get()", body = MfaPasskeyChallengeResponse),
+        (status = 401, description = "Login expired or passkeys not allowed for it", body = ErrorResponse),
+        (status = 400, description = "No passkeys registered, or validation error", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Authentication"
+)]
+#[instrument]
+pub async fn start_passkey_login(
+    State(state): State<AppState>,
+    ValidatedJson(dto): ValidatedJson<MfaPasskeyChallengeRequest>,
+) -> Result<Json<MfaPasskeyChallengeResponse>, AppError> {
+    let response = AuthService::start_passkey_login(
+        &state.db,
+        state.mfa_challenges.as_ref(),
+        dto,
+        &state.webauthn_config,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+/// Use a passkey to complete login
+#[utoipa::path(
+    post,
+    path = "/api/auth/mfa/passkey/verify",
+    summary = "Verify passkey",
+    request_body = MfaPasskeyLoginRequest,
+    responses(
+        (status = 200, description = "Passkey verification successful", body = LoginResponse),
+        (status = 401, description = "Invalid passkey, or the login expired or ran out of attempts", body = ErrorResponse),
+        (status = 400, description = "No challenge issued, or validation error", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Authentication"
+)]
+#[instrument]
+pub async fn verify_passkey_login(
+    State(state): State<AppState>,
+    RequestClient(client): RequestClient,
+    ValidatedJson(dto): ValidatedJson<MfaPasskeyLoginRequest>,
+) -> Result<Json<LoginResponse>, AppError> {
+    let response = AuthService::verify_passkey_login(
+        &state.db,
+        state.token_store.as_ref(),
+        state.mfa_challenges.as_ref(),
+        dto,
+        client,
+        &state.jwt_config,
+        &state.webauthn_config,
+    )
+    .await?;
     Ok(Json(response))
 }
 
@@ -139,7 +247,14 @@ pub async fn reset_password(
     State(state): State<AppState>,
     ValidatedJson(dto): ValidatedJson<ResetPasswordRequest>,
 ) -> Result<Json<MessageResponse>, AppError> {
-    AuthService::reset_password(&state.db, dto).await?;
+    AuthService::reset_password(
+        &state.db,
+        state.token_store.as_ref(),
+        state.mfa_challenges.as_ref(),
+        dto,
+        &state.password_policy,
+    )
+    .await?;
     Ok(Json(MessageResponse {
         message: "Password has been reset successfully. You can now log in with your new password."
             .to_string(),
@@ -163,20 +278,30 @@ pub async fn reset_password(
 #[instrument]
 pub async fn refresh_token(
     State(state): State<AppState>,
+    RequestClient(client): RequestClient,
     ValidatedJson(dto): ValidatedJson<RefreshTokenRequest>,
 ) -> Result<Json<LoginResponse>, AppError> {
-    let response = AuthService::refresh_access_token(&state.db, dto, &state.jwt_config).await?;
+    let response = AuthService::refresh_access_token(
+        &state.db,
+        state.token_store.as_ref(),
+        dto,
+        client,
+        &state.jwt_config,
+    )
+    .await?;
     Ok(Json(response))
 }
 
-/// Logout and revoke all refresh tokens
+/// Logout the current device by revoking its refresh token family
 #[utoipa::path(
     post,
     path = "/api/auth/logout",
-    summary = "Logout user",
+    summary = "Logout device",
+    request_body = LogoutRequest,
     responses(
         (status = 200, description = "Logged out successfully", body = MessageResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 401, description = "Unauthorized or invalid refresh token", body = ErrorResponse),
+        (status = 403, description = "Refresh token belongs to another user", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Authentication",
@@ -186,12 +311,122 @@ pub async fn refresh_token(
 pub async fn logout(
     State(state): State<AppState>,
     auth_user: AuthUser,
+    ValidatedJson(dto): ValidatedJson<LogoutRequest>,
 ) -> Result<Json<MessageResponse>, AppError> {
     let user_id = Uuid::parse_str(&auth_user.0.sub)
         .map_err(|_| AppError::unauthorized("Invalid token".to_string()))?;
 
-    AuthService::revoke_all_refresh_tokens(&state.db, user_id).await?;
+    AuthService::logout(state.token_store.as_ref(), user_id, dto, &state.jwt_config).await?;
+    Ok(Json(MessageResponse {
+        message: "Logged out successfully.".to_string(),
+    }))
+}
+
+/// Logout every device by revoking all refresh tokens and pending MFA logins
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout-all",
+    summary = "Logout all devices",
+    responses(
+        (status = 200, description = "Logged out of all devices", body = MessageResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Authentication",
+    security(("bearer_auth" = []))
+)]
+#[instrument]
+pub async fn logout_all(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<Json<MessageResponse>, AppError> {
+    let user_id = Uuid::parse_str(&auth_user.0.sub)
+        .map_err(|_| AppError::unauthorized("Invalid token".to_string()))?;
+
+    AuthService::revoke_all_refresh_tokens(state.token_store.as_ref(), user_id).await?;
+    AuthService::cancel_mfa_logins(state.mfa_challenges.as_ref(), user_id).await?;
     Ok(Json(MessageResponse {
         message: "Logged out successfully. All refresh tokens have been revoked.".to_string(),
     }))
 }
+
+/// List the devices signed in to your account
+///
+/// One entry per session, with the user agent and IP address that last used
+/// it, most recently active first.
+#[utoipa::path(
+    get,
+    path = "/api/auth/sessions",
+    summary = "List sessions",
+    responses(
+        (status = 200, description = "Active sessions", body = Vec<DeviceSession>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Authentication",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_sessions(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<Json<Vec<DeviceSession>>, AppError> {
+    let user_id = Uuid::parse_str(&auth_user.0.sub)
+        .map_err(|_| AppError::unauthorized("Invalid token".to_string()))?;
+
+    let sessions = AuthService::list_sessions(state.token_store.as_ref(), user_id).await?;
+    Ok(Json(sessions))
+}
+
+/// Sign out one of your devices
+///
+/// Its refresh token stops working immediately; the access token it already
+/// holds expires on its own.
+#[utoipa::path(
+    delete,
+    path = "/api/auth/sessions/{id}",
+    summary = "Revoke session",
+    params(
+        ("id" = Uuid, Path, description = "Session ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "Session not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Authentication",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn revoke_session(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(session_id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let user_id = Uuid::parse_str(&auth_user.0.sub)
+        .map_err(|_| AppError::unauthorized("Invalid token".to_string()))?;
+
+    AuthService::revoke_session(state.token_store.as_ref(), user_id, session_id).await?;
+    Ok(NoContent)
+}
+
+/// Public keys for verifying tokens
+///
+/// The JSON Web Key Set other services can verify tokens issued by this API
+/// with, matching a token's `kid` header to a key. Previous keys stay listed
+/// until they are removed from the configuration. Empty when tokens are
+/// signed with an HS256 secret.
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    summary = "Get token verification keys",
+    responses(
+        (status = 200, description = "JSON Web Key Set (RFC 7517)", body = Object)
+    ),
+    tag = "Authentication"
+)]
+#[instrument(skip(state))]
+pub async fn get_jwks(State(state): State<AppState>) -> Json<JwkSet> {
+    Json(state.jwt_config.keys.jwks())
+}
@@ -1,4 +1,5 @@
 pub mod controller;
 pub mod model;
+pub mod oidc;
 pub mod router;
 pub mod service;
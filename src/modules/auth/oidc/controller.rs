@@ -0,0 +1,333 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Redirect, Response},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::{OidcProviderId, SchoolId};
+
+use crate::middleware::auth::{AuthUser, RequireSsoManage, RequireSsoRead};
+use crate::middleware::client::RequestClient;
+use crate::modules::auth::model::{LoginResponse, MfaRequiredResponse};
+use crate::modules::auth::oidc::model::{
+    CreateOidcProviderDto, OidcCallbackParams, OidcLinkResponse, OidcProviderWithCallback,
+    UpdateOidcProviderDto,
+};
+use crate::modules::auth::oidc::service::OidcService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::verify_school_access;
+use crate::validator::ValidatedJson;
+
+/// Start signing in with an SSO provider
+///
+/// Redirects the browser to the school's identity provider. After the user
+/// signs in there, the provider redirects back to the callback endpoint.
+#[utoipa::path(
+    get,
+    path = "/api/auth/oidc/{provider}/authorize",
+    summary = "Start SSO sign-in",
+    params(
+        ("provider" = String, Path, description = "SSO provider slug")
+    ),
+    responses(
+        (status = 303, description = "Redirect to the identity provider"),
+        (status = 404, description = "Unknown or inactive SSO provider"),
+        (status = 502, description = "The identity provider could not be reached")
+    ),
+    tag = "SSO"
+)]
+#[instrument(skip(state))]
+pub async fn authorize(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+) -> Result<Redirect, AppError> {
+    let url = OidcService::authorize_url(&state.db, &state.oidc_config, &provider).await?;
+
+    Ok(Redirect::to(&url))
+}
+
+/// Link an SSO identity to your account
+///
+/// Returns the identity provider's authorization URL. Signing in there links
+/// the identity to the signed-in user, who must belong to the provider's
+/// school, and completes the sign-in as them. Existing accounts can only
+/// sign in with SSO once linked this way.
+#[utoipa::path(
+    post,
+    path = "/api/auth/oidc/{provider}/link",
+    summary = "Link SSO identity",
+    params(
+        ("provider" = String, Path, description = "SSO provider slug")
+    ),
+    responses(
+        (status = 200, description = "Authorization URL to send the browser to", body = OidcLinkResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The provider belongs to another school"),
+        (status = 404, description = "Unknown or inactive SSO provider"),
+        (status = 502, description = "The identity provider could not be reached")
+    ),
+    tag = "SSO",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn link(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(provider): Path<String>,
+) -> Result<Json<OidcLinkResponse>, AppError> {
+    let authorization_url = OidcService::link_url(
+        &state.db,
+        &state.oidc_config,
+        &provider,
+        auth_user.user_id()?,
+    )
+    .await?;
+
+    Ok(Json(OidcLinkResponse { authorization_url }))
+}
+
+/// Complete signing in with an SSO provider
+///
+/// Called by the identity provider's redirect. Verifies the sign-in, matches
+/// it to a user of the provider's school by a linked identity (creating a
+/// user if the provider provisions users on first sign-in), and returns the
+/// usual access and refresh tokens, or an MFA challenge when the user has a
+/// second factor.
+#[utoipa::path(
+    get,
+    path = "/api/auth/oidc/{provider}/callback",
+    summary = "Complete SSO sign-in",
+    params(
+        ("provider" = String, Path, description = "SSO provider slug"),
+        OidcCallbackParams
+    ),
+    responses(
+        (status = 200, description = "Signed in", body = LoginResponse),
+        (status = 200, description = "MFA required", body = MfaRequiredResponse),
+        (status = 400, description = "Missing code or state"),
+        (status = 401, description = "Sign-in was cancelled, expired, or the ID token is invalid"),
+        (status = 403, description = "The identity is not allowed to sign in to the school"),
+        (status = 404, description = "Unknown or inactive SSO provider"),
+        (status = 409, description = "The identity is already linked to another account"),
+        (status = 502, description = "The identity provider could not be reached")
+    ),
+    tag = "SSO"
+)]
+#[instrument(skip(state, params))]
+pub async fn callback(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+    Query(params): Query<OidcCallbackParams>,
+    RequestClient(client): RequestClient,
+) -> Result<Response, AppError> {
+    match OidcService::complete_login(
+        &state.db,
+        state.token_store.as_ref(),
+        state.mfa_challenges.as_ref(),
+        state.cache.as_ref(),
+        &state.oidc_config,
+        &state.jwt_config,
+        &provider,
+        params,
+        client,
+    )
+    .await?
+    {
+        Ok(login_response) => Ok(Json(login_response).into_response()),
+        Err(mfa_required) => Ok(Json(mfa_required).into_response()),
+    }
+}
+
+/// Register an SSO provider
+///
+/// Register the returned `callback_url` as a redirect URI in the identity
+/// provider's client settings. The client secret is never returned.
+#[utoipa::path(
+    post,
+    path = "/api/schools/{id}/sso-providers",
+    summary = "Create SSO provider",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = CreateOidcProviderDto,
+    responses(
+        Created<OidcProviderWithCallback>,
+        (status = 400, description = "Invalid settings, slug taken, or default role not allowed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sso:manage permission and access to the school")
+    ),
+    tag = "SSO",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn create_sso_provider(
+    State(state): State<AppState>,
+    RequireSsoManage(auth_user): RequireSsoManage,
+    Path(school_id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<CreateOidcProviderDto>,
+) -> Result<Created<OidcProviderWithCallback>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let provider = OidcService::create_provider(
+        &state.db,
+        &state.oidc_config,
+        school_id,
+        auth_user.user_id()?,
+        dto,
+    )
+    .await?;
+
+    Ok(Created(provider))
+}
+
+/// List a school's SSO providers
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/sso-providers",
+    summary = "List SSO providers",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    responses(
+        (status = 200, description = "SSO providers", body = Vec<OidcProviderWithCallback>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sso:read permission and access to the school")
+    ),
+    tag = "SSO",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_sso_providers(
+    State(state): State<AppState>,
+    RequireSsoRead(auth_user): RequireSsoRead,
+    Path(school_id): Path<Uuid>,
+) -> Result<Json<Vec<OidcProviderWithCallback>>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let providers = OidcService::get_providers(&state.db, &state.oidc_config, school_id).await?;
+
+    Ok(Json(providers))
+}
+
+/// Get an SSO provider
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/sso-providers/{provider_id}",
+    summary = "Get SSO provider",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("provider_id" = Uuid, Path, description = "SSO provider ID")
+    ),
+    responses(
+        (status = 200, description = "SSO provider", body = OidcProviderWithCallback),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sso:read permission and access to the school"),
+        (status = 404, description = "SSO provider not found")
+    ),
+    tag = "SSO",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_sso_provider(
+    State(state): State<AppState>,
+    RequireSsoRead(auth_user): RequireSsoRead,
+    Path((school_id, provider_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<OidcProviderWithCallback>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let provider = OidcService::get_provider(
+        &state.db,
+        &state.oidc_config,
+        school_id,
+        OidcProviderId::from(provider_id),
+    )
+    .await?;
+
+    Ok(Json(provider))
+}
+
+/// Update an SSO provider
+///
+/// Deactivating a provider stops sign-ins through it; users it linked or
+/// created keep their accounts.
+#[utoipa::path(
+    put,
+    path = "/api/schools/{id}/sso-providers/{provider_id}",
+    summary = "Update SSO provider",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("provider_id" = Uuid, Path, description = "SSO provider ID")
+    ),
+    request_body = UpdateOidcProviderDto,
+    responses(
+        (status = 200, description = "SSO provider updated", body = OidcProviderWithCallback),
+        (status = 400, description = "Invalid settings or default role not allowed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sso:manage permission and access to the school"),
+        (status = 404, description = "SSO provider not found")
+    ),
+    tag = "SSO",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn update_sso_provider(
+    State(state): State<AppState>,
+    RequireSsoManage(auth_user): RequireSsoManage,
+    Path((school_id, provider_id)): Path<(Uuid, Uuid)>,
+    ValidatedJson(dto): ValidatedJson<UpdateOidcProviderDto>,
+) -> Result<Json<OidcProviderWithCallback>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let provider = OidcService::update_provider(
+        &state.db,
+        &state.oidc_config,
+        school_id,
+        OidcProviderId::from(provider_id),
+        dto,
+    )
+    .await?;
+
+    Ok(Json(provider))
+}
+
+/// Delete an SSO provider
+///
+/// Users it linked or created keep their accounts but can no longer sign in
+/// through it.
+#[utoipa::path(
+    delete,
+    path = "/api/schools/{id}/sso-providers/{provider_id}",
+    summary = "Delete SSO provider",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("provider_id" = Uuid, Path, description = "SSO provider ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sso:manage permission and access to the school"),
+        (status = 404, description = "SSO provider not found")
+    ),
+    tag = "SSO",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_sso_provider(
+    State(state): State<AppState>,
+    RequireSsoManage(auth_user): RequireSsoManage,
+    Path((school_id, provider_id)): Path<(Uuid, Uuid)>,
+) -> Result<NoContent, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    OidcService::delete_provider(&state.db, school_id, OidcProviderId::from(provider_id)).await?;
+
+    Ok(NoContent)
+}
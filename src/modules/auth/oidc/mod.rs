@@ -0,0 +1,27 @@
+//! OpenID Connect single sign-on.
+//!
+//! School admins register identity providers (issuer, client credentials,
+//! allowed email domains) under `/api/schools/{id}/sso-providers`. Users then
+//! sign in with the authorization code flow:
+//!
+//! 1. `GET /api/auth/oidc/{provider}/authorize` redirects to the provider
+//!    with a one-time `state`, a `nonce`, and a PKCE challenge
+//! 2. the provider redirects back to `/api/auth/oidc/{provider}/callback`,
+//!    which exchanges the code, verifies the ID token against the provider's
+//!    published keys, and issues the usual access and refresh tokens
+//!
+//! The external identity is matched to a user by its subject. Existing users
+//! link an identity while signed in, through
+//! `POST /api/auth/oidc/{provider}/link`, since the school's admins control
+//! the provider and email matching would let them sign in as anyone in the
+//! school. Unknown identities are provisioned on first sign-in when the
+//! provider allows it and no account has the email. Users with a second
+//! factor still answer the usual MFA challenge after signing in.
+//!
+//! Requests to the issuer, its token endpoint, and its keys only reach
+//! public https URLs unless `OIDC_ALLOW_LOCAL_ISSUERS` is set.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
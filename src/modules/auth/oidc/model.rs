@@ -0,0 +1,7 @@
+//! OIDC data models and DTOs.
+//!
+//! This module re-exports OIDC models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all OIDC models from the shared crate
+pub use chalkbyte_models::oidc::*;
@@ -0,0 +1,38 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    authorize, callback, create_sso_provider, delete_sso_provider, get_sso_provider,
+    get_sso_providers, link, update_sso_provider,
+};
+
+/// Initialize the OIDC login router, merged into the auth router
+/// Routes: GET /oidc/{provider}/authorize, POST /oidc/{provider}/link,
+/// GET /oidc/{provider}/callback
+pub fn init_oidc_router() -> Router<AppState> {
+    Router::new()
+        .route("/oidc/{provider}/authorize", get(authorize))
+        .route("/oidc/{provider}/link", post(link))
+        .route("/oidc/{provider}/callback", get(callback))
+}
+
+/// Initialize the SSO provider router, merged into the schools router
+/// Routes: GET/POST /{id}/sso-providers,
+/// GET/PUT/DELETE /{id}/sso-providers/{provider_id}
+pub fn init_sso_providers_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/{id}/sso-providers",
+            get(get_sso_providers).post(create_sso_provider),
+        )
+        .route(
+            "/{id}/sso-providers/{provider_id}",
+            get(get_sso_provider)
+                .put(update_sso_provider)
+                .delete(delete_sso_provider),
+        )
+}
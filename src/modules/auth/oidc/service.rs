@@ -0,0 +1,1102 @@
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use chrono::Utc;
+use data_encoding::BASE64URL_NOPAD;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
+use rand::RngCore;
+use serde::{Deserialize, Deserializer};
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, PgPool};
+use tracing::{info, instrument, warn};
+
+use chalkbyte_cache::{MfaChallengeStore, RedisCache, SessionClient, TokenStore, invalidate};
+use chalkbyte_config::{JwtConfig, OidcConfig};
+use chalkbyte_core::{AppError, ErrorCode, hash_password};
+use chalkbyte_models::ids::{OidcProviderId, RoleId, SchoolId, UserId};
+#[cfg(feature = "observability")]
+use chalkbyte_observability::metrics;
+
+use crate::modules::auth::model::{LoginResponse, MfaRequiredResponse};
+use crate::modules::auth::oidc::model::{
+    CreateOidcProviderDto, OidcCallbackParams, OidcProvider, OidcProviderWithCallback,
+    UpdateOidcProviderDto, email_domain_allowed, normalize_domain,
+};
+use crate::modules::auth::service::{issue_login_tokens, start_mfa_challenge};
+use crate::modules::users::model::system_roles;
+use crate::modules::webhooks::model::WebhookEvent;
+use crate::modules::webhooks::service::WebhookService;
+use crate::utils::outbound::OutboundClient;
+
+/// How long the identity provider has to answer discovery, token, and key
+/// requests.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a user has to finish signing in at the identity provider.
+const LOGIN_STATE_TTL_MINUTES: i64 = 10;
+
+/// Scopes requested from the identity provider.
+const SCOPES: &str = "openid email profile";
+
+const PROVIDER_COLUMNS: &str = "id, school_id, slug, name, issuer, client_id, allowed_domains,
+    jit_provisioning, default_role_id, is_active, created_by, created_at, updated_at";
+
+/// The parts of the provider's discovery document used for sign-in.
+#[derive(Deserialize)]
+struct DiscoveryDocument {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+/// A provider together with its client secret, for talking to it.
+#[derive(FromRow)]
+struct ProviderWithSecret {
+    #[sqlx(flatten)]
+    provider: OidcProvider,
+    client_secret: String,
+}
+
+/// Claims read from a verified ID token.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct IdTokenClaims {
+    pub sub: String,
+    pub email: Option<String>,
+    /// Some providers send this as a string
+    #[serde(default, deserialize_with = "bool_or_string")]
+    pub email_verified: Option<bool>,
+    pub nonce: Option<String>,
+    pub given_name: Option<String>,
+    pub family_name: Option<String>,
+    pub name: Option<String>,
+}
+
+fn bool_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<bool>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrString {
+        Bool(bool),
+        String(String),
+    }
+
+    Ok(match Option::<BoolOrString>::deserialize(deserializer)? {
+        Some(BoolOrString::Bool(value)) => Some(value),
+        Some(BoolOrString::String(value)) => Some(value.eq_ignore_ascii_case("true")),
+        None => None,
+    })
+}
+
+fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    BASE64URL_NOPAD.encode(&bytes)
+}
+
+/// PKCE `S256` challenge for a code verifier.
+fn pkce_challenge(verifier: &str) -> String {
+    BASE64URL_NOPAD.encode(&Sha256::digest(verifier.as_bytes()))
+}
+
+/// Client for the identity provider. School admins choose the issuer, so
+/// it may only reach public https URLs unless local issuers are allowed.
+fn http_client(config: &OidcConfig) -> Result<OutboundClient, AppError> {
+    let client = if config.allow_local_issuers {
+        OutboundClient::unrestricted(HTTP_TIMEOUT)
+    } else {
+        OutboundClient::new(HTTP_TIMEOUT)
+    };
+    client.map_err(|e| AppError::internal_error(format!("Failed to build HTTP client: {e}")))
+}
+
+fn provider_error(message: impl std::fmt::Display) -> AppError {
+    AppError::new(
+        StatusCode::BAD_GATEWAY,
+        anyhow::anyhow!("Identity provider request failed: {message}"),
+    )
+}
+
+async fn fetch_json<T: serde::de::DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+) -> Result<T, AppError> {
+    request
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(provider_error)?
+        .json()
+        .await
+        .map_err(provider_error)
+}
+
+/// Starts a GET to a URL the provider chose, once the client allows it.
+async fn provider_get(
+    http: &OutboundClient,
+    url: &str,
+) -> Result<reqwest::RequestBuilder, AppError> {
+    http.get(url).await.map_err(provider_error)
+}
+
+/// Fetches the provider's discovery document, checking it describes `issuer`.
+async fn discover(http: &OutboundClient, issuer: &str) -> Result<DiscoveryDocument, AppError> {
+    let issuer = issuer.trim_end_matches('/');
+    let document: DiscoveryDocument = fetch_json(
+        provider_get(http, &format!("{issuer}/.well-known/openid-configuration")).await?,
+    )
+    .await?;
+
+    if document.issuer.trim_end_matches('/') != issuer {
+        return Err(provider_error(format!(
+            "discovery document is for issuer '{}'",
+            document.issuer
+        )));
+    }
+
+    Ok(document)
+}
+
+/// Splits the names in the claims into first and last name, falling back to
+/// the email's local part.
+fn names_from_claims(claims: &IdTokenClaims, email: &str) -> (String, String) {
+    let first = claims.given_name.as_deref().map(str::trim).unwrap_or("");
+    let last = claims.family_name.as_deref().map(str::trim).unwrap_or("");
+    let (first, last) = if !first.is_empty() || !last.is_empty() {
+        (first.to_string(), last.to_string())
+    } else if let Some(name) = claims
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+    {
+        match name.rsplit_once(' ') {
+            Some((first, last)) => (first.trim().to_string(), last.to_string()),
+            None => (name.to_string(), String::new()),
+        }
+    } else {
+        let local = email.split('@').next().unwrap_or(email);
+        (local.to_string(), String::new())
+    };
+
+    let truncate = |s: String| s.chars().take(100).collect::<String>();
+    (truncate(first), truncate(last))
+}
+
+pub struct OidcService;
+
+impl OidcService {
+    #[instrument(skip(db, config, dto), fields(oidc.slug = %dto.slug))]
+    pub async fn create_provider(
+        db: &PgPool,
+        config: &OidcConfig,
+        school_id: SchoolId,
+        created_by: UserId,
+        dto: CreateOidcProviderDto,
+    ) -> Result<OidcProviderWithCallback, AppError> {
+        let default_role_id = dto.default_role_id.unwrap_or(system_roles::STUDENT);
+        Self::validate_default_role(db, school_id, default_role_id).await?;
+
+        let provider = sqlx::query_as::<_, OidcProvider>(&format!(
+            "INSERT INTO oidc_providers
+                 (school_id, slug, name, issuer, client_id, client_secret, allowed_domains,
+                  jit_provisioning, default_role_id, created_by)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             RETURNING {PROVIDER_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.slug)
+        .bind(&dto.name)
+        .bind(dto.issuer.trim_end_matches('/'))
+        .bind(&dto.client_id)
+        .bind(&dto.client_secret)
+        .bind(normalize_domains(dto.allowed_domains))
+        .bind(dto.jit_provisioning)
+        .bind(default_role_id)
+        .bind(created_by)
+        .fetch_one(db)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(db_err) = &e
+                && db_err.is_unique_violation()
+            {
+                return AppError::bad_request(anyhow::anyhow!(
+                    "An SSO provider with this slug already exists"
+                ));
+            }
+            AppError::from(e)
+        })?;
+
+        info!(oidc.provider_id = %provider.id, "SSO provider registered");
+
+        Ok(with_callback(config, provider))
+    }
+
+    #[instrument(skip(db, config))]
+    pub async fn get_providers(
+        db: &PgPool,
+        config: &OidcConfig,
+        school_id: SchoolId,
+    ) -> Result<Vec<OidcProviderWithCallback>, AppError> {
+        let providers = sqlx::query_as::<_, OidcProvider>(&format!(
+            "SELECT {PROVIDER_COLUMNS} FROM oidc_providers
+             WHERE school_id = $1
+             ORDER BY created_at"
+        ))
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(providers
+            .into_iter()
+            .map(|provider| with_callback(config, provider))
+            .collect())
+    }
+
+    #[instrument(skip(db, config))]
+    pub async fn get_provider(
+        db: &PgPool,
+        config: &OidcConfig,
+        school_id: SchoolId,
+        provider_id: OidcProviderId,
+    ) -> Result<OidcProviderWithCallback, AppError> {
+        sqlx::query_as::<_, OidcProvider>(&format!(
+            "SELECT {PROVIDER_COLUMNS} FROM oidc_providers WHERE id = $1 AND school_id = $2"
+        ))
+        .bind(provider_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .map(|provider| with_callback(config, provider))
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("SSO provider not found")))
+    }
+
+    #[instrument(skip(db, config, dto))]
+    pub async fn update_provider(
+        db: &PgPool,
+        config: &OidcConfig,
+        school_id: SchoolId,
+        provider_id: OidcProviderId,
+        dto: UpdateOidcProviderDto,
+    ) -> Result<OidcProviderWithCallback, AppError> {
+        if let Some(role_id) = dto.default_role_id {
+            Self::validate_default_role(db, school_id, role_id).await?;
+        }
+
+        sqlx::query_as::<_, OidcProvider>(&format!(
+            "UPDATE oidc_providers
+             SET name = COALESCE($3, name),
+                 issuer = COALESCE($4, issuer),
+                 client_id = COALESCE($5, client_id),
+                 client_secret = COALESCE($6, client_secret),
+                 allowed_domains = COALESCE($7, allowed_domains),
+                 jit_provisioning = COALESCE($8, jit_provisioning),
+                 default_role_id = COALESCE($9, default_role_id),
+                 is_active = COALESCE($10, is_active)
+             WHERE id = $1 AND school_id = $2
+             RETURNING {PROVIDER_COLUMNS}"
+        ))
+        .bind(provider_id)
+        .bind(school_id)
+        .bind(dto.name)
+        .bind(
+            dto.issuer
+                .map(|issuer| issuer.trim_end_matches('/').to_string()),
+        )
+        .bind(dto.client_id)
+        .bind(dto.client_secret)
+        .bind(dto.allowed_domains.map(normalize_domains))
+        .bind(dto.jit_provisioning)
+        .bind(dto.default_role_id)
+        .bind(dto.is_active)
+        .fetch_optional(db)
+        .await?
+        .map(|provider| with_callback(config, provider))
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("SSO provider not found")))
+    }
+
+    /// Delete a provider. Users it linked or provisioned keep their accounts.
+    #[instrument(skip(db))]
+    pub async fn delete_provider(
+        db: &PgPool,
+        school_id: SchoolId,
+        provider_id: OidcProviderId,
+    ) -> Result<(), AppError> {
+        let deleted = sqlx::query("DELETE FROM oidc_providers WHERE id = $1 AND school_id = $2")
+            .bind(provider_id)
+            .bind(school_id)
+            .execute(db)
+            .await?
+            .rows_affected();
+
+        if deleted == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "SSO provider not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Users provisioned on first sign-in may only get the Teacher or Student
+    /// role, or one of the school's own roles.
+    async fn validate_default_role(
+        db: &PgPool,
+        school_id: SchoolId,
+        role_id: RoleId,
+    ) -> Result<(), AppError> {
+        if role_id == system_roles::TEACHER || role_id == system_roles::STUDENT {
+            return Ok(());
+        }
+
+        let school_role = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM roles WHERE id = $1 AND school_id = $2)",
+        )
+        .bind(role_id)
+        .bind(school_id)
+        .fetch_one(db)
+        .await?;
+
+        if !school_role {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "The default role must be Teacher, Student, or one of the school's roles"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// An active provider by slug, with its client secret.
+    async fn active_provider(db: &PgPool, slug: &str) -> Result<ProviderWithSecret, AppError> {
+        sqlx::query_as::<_, ProviderWithSecret>(&format!(
+            "SELECT {PROVIDER_COLUMNS}, client_secret FROM oidc_providers
+             WHERE slug = $1 AND is_active"
+        ))
+        .bind(slug)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("SSO provider not found")))
+    }
+
+    /// Start signing in: returns the provider's authorization URL to
+    /// redirect the browser to.
+    #[instrument(skip(db, config))]
+    pub async fn authorize_url(
+        db: &PgPool,
+        config: &OidcConfig,
+        slug: &str,
+    ) -> Result<String, AppError> {
+        let ProviderWithSecret { provider, .. } = Self::active_provider(db, slug).await?;
+        Self::start_sign_in(db, config, &provider, None).await
+    }
+
+    /// Start linking an identity at the provider to the signed-in user, who
+    /// must belong to the provider's school. Returns the provider's
+    /// authorization URL; the callback links the identity it signs in as.
+    #[instrument(skip(db, config))]
+    pub async fn link_url(
+        db: &PgPool,
+        config: &OidcConfig,
+        slug: &str,
+        user_id: UserId,
+    ) -> Result<String, AppError> {
+        let ProviderWithSecret { provider, .. } = Self::active_provider(db, slug).await?;
+
+        let school_id =
+            sqlx::query_scalar::<_, Option<SchoolId>>("SELECT school_id FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(db)
+                .await?
+                .ok_or_else(|| AppError::from_code(ErrorCode::UserNotFound))?;
+        if school_id != Some(provider.school_id) {
+            return Err(AppError::forbidden(
+                "This SSO provider belongs to another school".into(),
+            ));
+        }
+
+        Self::start_sign_in(db, config, &provider, Some(user_id)).await
+    }
+
+    async fn start_sign_in(
+        db: &PgPool,
+        config: &OidcConfig,
+        provider: &OidcProvider,
+        link_user_id: Option<UserId>,
+    ) -> Result<String, AppError> {
+        let discovery = discover(&http_client(config)?, &provider.issuer).await?;
+
+        let state = random_token();
+        let nonce = random_token();
+        let code_verifier = random_token();
+
+        sqlx::query("DELETE FROM oidc_login_states WHERE expires_at < NOW()")
+            .execute(db)
+            .await?;
+
+        sqlx::query(
+            "INSERT INTO oidc_login_states
+                 (state, provider_id, nonce, code_verifier, link_user_id, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(&state)
+        .bind(provider.id)
+        .bind(&nonce)
+        .bind(&code_verifier)
+        .bind(link_user_id)
+        .bind(Utc::now() + chrono::Duration::minutes(LOGIN_STATE_TTL_MINUTES))
+        .execute(db)
+        .await?;
+
+        let url = reqwest::Url::parse_with_params(
+            &discovery.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", provider.client_id.as_str()),
+                ("redirect_uri", config.callback_url(&provider.slug).as_str()),
+                ("scope", SCOPES),
+                ("state", state.as_str()),
+                ("nonce", nonce.as_str()),
+                ("code_challenge", pkce_challenge(&code_verifier).as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(provider_error)?;
+
+        Ok(url.into())
+    }
+
+    /// Finish signing in: exchanges the authorization code, verifies the ID
+    /// token, and maps it to a user, linking it first when the sign-in was
+    /// started by [`link_url`](Self::link_url). Users with a second factor
+    /// get the usual MFA challenge instead of tokens.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(db, tokens, challenges, cache, config, jwt_config, params, client))]
+    pub async fn complete_login(
+        db: &PgPool,
+        tokens: &dyn TokenStore,
+        challenges: &dyn MfaChallengeStore,
+        cache: Option<&RedisCache>,
+        config: &OidcConfig,
+        jwt_config: &JwtConfig,
+        slug: &str,
+        params: OidcCallbackParams,
+        client: SessionClient,
+    ) -> Result<Result<LoginResponse, MfaRequiredResponse>, AppError> {
+        if let Some(error) = params.error {
+            let description = params.error_description.unwrap_or_default();
+            return Err(AppError::unauthorized(format!(
+                "Sign-in was not completed: {error} {description}"
+            )));
+        }
+        let (Some(code), Some(state)) = (params.code, params.state) else {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Missing authorization code or state"
+            )));
+        };
+
+        let ProviderWithSecret {
+            provider,
+            client_secret,
+        } = Self::active_provider(db, slug).await?;
+
+        let (nonce, code_verifier, link_user_id) = sqlx::query_as::<
+            _,
+            (String, String, Option<UserId>),
+        >(
+            "DELETE FROM oidc_login_states
+                 WHERE state = $1 AND provider_id = $2 AND expires_at > NOW()
+                 RETURNING nonce, code_verifier, link_user_id",
+        )
+        .bind(&state)
+        .bind(provider.id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| {
+            AppError::unauthorized("Sign-in expired or was already used, start again".to_string())
+        })?;
+
+        let http = http_client(config)?;
+        let discovery = discover(&http, &provider.issuer).await?;
+
+        let callback_url = config.callback_url(&provider.slug);
+        let token_request = http
+            .post(&discovery.token_endpoint)
+            .await
+            .map_err(provider_error)?;
+        let token: TokenResponse = fetch_json(token_request.form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", callback_url.as_str()),
+            ("client_id", provider.client_id.as_str()),
+            ("client_secret", client_secret.as_str()),
+            ("code_verifier", code_verifier.as_str()),
+        ]))
+        .await?;
+
+        let claims = verify_id_token(&http, &discovery, &provider, &token.id_token, &nonce).await?;
+        let user_id = match link_user_id {
+            Some(user_id) => Self::link_identity(db, &provider, &claims, user_id).await?,
+            None => Self::resolve_user(db, cache, &provider, &claims).await?,
+        };
+
+        if let Some(mfa_required) =
+            start_mfa_challenge(db, challenges, user_id.into_inner()).await?
+        {
+            info!(user.id = %user_id, oidc.provider_id = %provider.id, "SSO login awaiting MFA");
+            return Ok(Err(mfa_required));
+        }
+
+        let response =
+            issue_login_tokens(db, tokens, user_id.into_inner(), None, client, jwt_config).await?;
+
+        #[cfg(feature = "observability")]
+        {
+            metrics::track_jwt_issued();
+            let primary_role = response
+                .roles
+                .first()
+                .map(|r| r.role.name.as_str())
+                .unwrap_or("none");
+            metrics::track_user_login_success(primary_role);
+            metrics::track_school_login(Some(provider.school_id.into_inner()));
+        }
+
+        info!(user.id = %user_id, oidc.provider_id = %provider.id, "SSO login succeeded");
+
+        Ok(Ok(response))
+    }
+
+    /// The verified email in the claims, if the provider may sign it in.
+    fn allowed_email<'c>(
+        provider: &OidcProvider,
+        claims: &'c IdTokenClaims,
+    ) -> Result<&'c str, AppError> {
+        let email = claims
+            .email
+            .as_deref()
+            .map(str::trim)
+            .filter(|email| !email.is_empty())
+            .ok_or_else(|| {
+                AppError::forbidden("The identity provider did not share an email address".into())
+            })?;
+        if claims.email_verified == Some(false) {
+            return Err(AppError::forbidden(
+                "The email address is not verified with the identity provider".into(),
+            ));
+        }
+        if !email_domain_allowed(email, &provider.allowed_domains) {
+            return Err(AppError::forbidden(
+                "This email domain may not sign in with this provider".into(),
+            ));
+        }
+        Ok(email)
+    }
+
+    /// Map a verified external identity to a user of the provider's school.
+    ///
+    /// Tries an identity already linked to the subject, then a new user when
+    /// the provider provisions on first sign-in. Existing accounts are never
+    /// matched by email, since the school's admins control the provider and
+    /// could otherwise sign in as anyone in the school; their owners link
+    /// the identity with [`link_url`](Self::link_url) instead.
+    #[instrument(skip(db, cache, provider, claims), fields(oidc.provider_id = %provider.id))]
+    pub async fn resolve_user(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        provider: &OidcProvider,
+        claims: &IdTokenClaims,
+    ) -> Result<UserId, AppError> {
+        let email = Self::allowed_email(provider, claims)?;
+
+        if let Some(user_id) = sqlx::query_scalar::<_, UserId>(
+            "UPDATE oidc_identities SET last_login_at = NOW()
+             WHERE provider_id = $1 AND subject = $2
+             RETURNING user_id",
+        )
+        .bind(provider.id)
+        .bind(&claims.sub)
+        .fetch_optional(db)
+        .await?
+        {
+            return Ok(user_id);
+        }
+
+        let email_taken = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE LOWER(email) = LOWER($1))",
+        )
+        .bind(email)
+        .fetch_one(db)
+        .await?;
+
+        if email_taken {
+            Err(AppError::forbidden(
+                "An account with this email already exists; sign in with its password and link this provider first".into(),
+            ))
+        } else if provider.jit_provisioning {
+            Self::provision_user(db, cache, provider, claims, email).await
+        } else {
+            Err(AppError::forbidden(
+                "No account exists for this email, ask a school admin to create one".into(),
+            ))
+        }
+    }
+
+    /// Link a verified external identity to the user who started linking.
+    ///
+    /// Fails if the identity is already linked to another account.
+    #[instrument(skip(db, provider, claims), fields(oidc.provider_id = %provider.id))]
+    pub async fn link_identity(
+        db: &PgPool,
+        provider: &OidcProvider,
+        claims: &IdTokenClaims,
+        user_id: UserId,
+    ) -> Result<UserId, AppError> {
+        Self::allowed_email(provider, claims)?;
+
+        let linked = sqlx::query_scalar::<_, UserId>(
+            "INSERT INTO oidc_identities (provider_id, subject, user_id, last_login_at)
+             VALUES ($1, $2, $3, NOW())
+             ON CONFLICT (provider_id, subject) DO UPDATE SET last_login_at = NOW()
+             WHERE oidc_identities.user_id = EXCLUDED.user_id
+             RETURNING user_id",
+        )
+        .bind(provider.id)
+        .bind(&claims.sub)
+        .bind(user_id)
+        .fetch_optional(db)
+        .await?;
+
+        if linked.is_none() {
+            return Err(AppError::new(
+                StatusCode::CONFLICT,
+                anyhow::anyhow!("This identity is already linked to another account"),
+            ));
+        }
+
+        info!(user.id = %user_id, "Linked SSO identity to account");
+        Ok(user_id)
+    }
+
+    /// Create a user for a first-time SSO sign-in, linked to the identity.
+    ///
+    /// The password is random, so the account can only sign in through SSO
+    /// until a password is set with the reset flow.
+    async fn provision_user(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        provider: &OidcProvider,
+        claims: &IdTokenClaims,
+        email: &str,
+    ) -> Result<UserId, AppError> {
+        let (first_name, last_name) = names_from_claims(claims, email);
+        let password_hash = hash_password(&random_token())?;
+
+        let mut tx = db.begin().await?;
+
+        let user_id = sqlx::query_scalar::<_, UserId>(
+            "INSERT INTO users (first_name, last_name, email, password, school_id)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id",
+        )
+        .bind(&first_name)
+        .bind(&last_name)
+        .bind(email)
+        .bind(&password_hash)
+        .bind(provider.school_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(db_err) = &e
+                && db_err.is_unique_violation()
+            {
+                warn!("SSO provisioning raced with another account creation");
+                return AppError::from_code(ErrorCode::EmailTaken);
+            }
+            AppError::from(e)
+        })?;
+
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(provider.default_role_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            "INSERT INTO oidc_identities (provider_id, subject, user_id, last_login_at)
+             VALUES ($1, $2, $3, NOW())",
+        )
+        .bind(provider.id)
+        .bind(&claims.sub)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        WebhookService::enqueue(
+            &mut *tx,
+            provider.school_id,
+            WebhookEvent::UserCreated,
+            &serde_json::json!({
+                "user_id": user_id,
+                "email": email,
+                "first_name": first_name,
+                "last_name": last_name,
+                "role_ids": [provider.default_role_id],
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        invalidate::user(
+            cache,
+            Some(user_id.into_inner()),
+            Some(provider.school_id.into_inner()),
+        )
+        .await;
+
+        info!(user.id = %user_id, "Provisioned user from SSO sign-in");
+
+        Ok(user_id)
+    }
+}
+
+/// Verifies an ID token's signature against the provider's published keys,
+/// and its issuer, audience, expiry, and nonce.
+async fn verify_id_token(
+    http: &OutboundClient,
+    discovery: &DiscoveryDocument,
+    provider: &OidcProvider,
+    id_token: &str,
+    nonce: &str,
+) -> Result<IdTokenClaims, AppError> {
+    let invalid = |reason: &str| AppError::unauthorized(format!("Invalid ID token: {reason}"));
+
+    let header = decode_header(id_token).map_err(|_| invalid("malformed"))?;
+    // Shared-secret algorithms would let anyone holding the client secret
+    // mint tokens; providers sign with their published keys
+    if matches!(
+        header.alg,
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+    ) {
+        return Err(invalid("unsupported signing algorithm"));
+    }
+
+    let keys: JwkSet = fetch_json(provider_get(http, &discovery.jwks_uri).await?).await?;
+    let jwk = match header.kid.as_deref() {
+        Some(kid) => keys.find(kid),
+        None => keys.keys.first(),
+    }
+    .ok_or_else(|| invalid("unknown signing key"))?;
+    let key = DecodingKey::from_jwk(jwk).map_err(|_| invalid("unusable signing key"))?;
+
+    let mut validation = Validation::new(header.alg);
+    validation.set_audience(&[&provider.client_id]);
+    validation.set_issuer(&[&discovery.issuer]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+    let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+        .map_err(|e| invalid(&e.to_string()))?
+        .claims;
+
+    if claims.nonce.as_deref() != Some(nonce) {
+        return Err(invalid("nonce mismatch"));
+    }
+
+    Ok(claims)
+}
+
+fn with_callback(config: &OidcConfig, provider: OidcProvider) -> OidcProviderWithCallback {
+    OidcProviderWithCallback {
+        callback_url: config.callback_url(&provider.slug),
+        provider,
+    }
+}
+
+fn normalize_domains(domains: Vec<String>) -> Vec<String> {
+    let mut domains: Vec<String> = domains.iter().map(|d| normalize_domain(d)).collect();
+    domains.sort();
+    domains.dedup();
+    domains
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar::<_, SchoolId>(
+            "INSERT INTO schools (name, address) VALUES ($1, 'Test Address') RETURNING id",
+        )
+        .bind(format!("School {}", Uuid::new_v4()))
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId, email: &str) -> UserId {
+        sqlx::query_scalar::<_, UserId>(
+            "INSERT INTO users (first_name, last_name, email, school_id)
+             VALUES ('Test', 'User', $1, $2) RETURNING id",
+        )
+        .bind(email)
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn create_test_provider(
+        pool: &PgPool,
+        school_id: SchoolId,
+        jit_provisioning: bool,
+    ) -> OidcProvider {
+        OidcService::create_provider(
+            pool,
+            &OidcConfig {
+                redirect_base_url: "http://localhost:3000".to_string(),
+                allow_local_issuers: false,
+            },
+            school_id,
+            create_test_user(
+                pool,
+                school_id,
+                &format!("admin-{}@greenfield.edu", Uuid::new_v4()),
+            )
+            .await,
+            CreateOidcProviderDto {
+                slug: format!("sso-{}", &Uuid::new_v4().simple().to_string()[..8]),
+                name: "Google Workspace".to_string(),
+                issuer: "https://accounts.google.com".to_string(),
+                client_id: "client".to_string(),
+                client_secret: "secret".to_string(),
+                allowed_domains: vec!["Greenfield.edu".to_string()],
+                jit_provisioning,
+                default_role_id: None,
+            },
+        )
+        .await
+        .unwrap()
+        .provider
+    }
+
+    fn claims(sub: &str, email: &str) -> IdTokenClaims {
+        IdTokenClaims {
+            sub: sub.to_string(),
+            email: Some(email.to_string()),
+            email_verified: Some(true),
+            given_name: Some("Ada".to_string()),
+            family_name: Some("Lovelace".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pkce_challenge() {
+        // Unpadded base64url of the verifier's SHA-256 digest
+        let challenge = pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r7wW1gFWFOEjXk");
+        assert_eq!(challenge, "bwWFMyPfdG9qreDhH2lmftFx_dFeLDalzcT1gb_j68g");
+        assert_eq!(challenge.len(), 43);
+    }
+
+    #[test]
+    fn test_email_verified_accepts_strings() {
+        let claims: IdTokenClaims =
+            serde_json::from_str(r#"{"sub": "1", "email_verified": "false"}"#).unwrap();
+        assert_eq!(claims.email_verified, Some(false));
+
+        let claims: IdTokenClaims = serde_json::from_str(r#"{"sub": "1"}"#).unwrap();
+        assert_eq!(claims.email_verified, None);
+    }
+
+    #[test]
+    fn test_names_from_claims_fallbacks() {
+        let full = IdTokenClaims {
+            name: Some("Grace Brewster Hopper".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            names_from_claims(&full, "grace@navy.mil"),
+            ("Grace Brewster".to_string(), "Hopper".to_string())
+        );
+
+        assert_eq!(
+            names_from_claims(&IdTokenClaims::default(), "grace@navy.mil"),
+            ("grace".to_string(), String::new())
+        );
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_existing_users_sign_in_only_after_linking(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let provider = create_test_provider(&pool, school_id, true).await;
+        let user_id = create_test_user(&pool, school_id, "ada@greenfield.edu").await;
+
+        // A matching email is not enough, even with provisioning on
+        let err = OidcService::resolve_user(
+            &pool,
+            None,
+            &provider,
+            &claims("sub-1", "Ada@greenfield.edu"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        let linked = OidcService::link_identity(
+            &pool,
+            &provider,
+            &claims("sub-1", "ada@greenfield.edu"),
+            user_id,
+        )
+        .await
+        .unwrap();
+        assert_eq!(linked, user_id);
+
+        // Later sign-ins match the linked subject even if the email changed
+        let resolved = OidcService::resolve_user(
+            &pool,
+            None,
+            &provider,
+            &claims("sub-1", "ada.lovelace@greenfield.edu"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resolved, user_id);
+
+        // Linking again is harmless, but the identity cannot move accounts
+        OidcService::link_identity(
+            &pool,
+            &provider,
+            &claims("sub-1", "a@greenfield.edu"),
+            user_id,
+        )
+        .await
+        .unwrap();
+        let other_id = create_test_user(&pool, school_id, "grace@greenfield.edu").await;
+        let err = OidcService::link_identity(
+            &pool,
+            &provider,
+            &claims("sub-1", "grace@greenfield.edu"),
+            other_id,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_link_url_requires_provider_school(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let other_school_id = create_test_school(&pool).await;
+        let provider = create_test_provider(&pool, school_id, false).await;
+        let outsider = create_test_user(&pool, other_school_id, "eve@greenfield.edu").await;
+        let config = OidcConfig {
+            redirect_base_url: "http://localhost:3000".to_string(),
+            allow_local_issuers: false,
+        };
+
+        let err = OidcService::link_url(&pool, &config, &provider.slug, outsider)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_discover_refuses_private_issuers() {
+        let http = http_client(&OidcConfig {
+            redirect_base_url: "http://localhost:3000".to_string(),
+            allow_local_issuers: false,
+        })
+        .unwrap();
+
+        for issuer in ["https://169.254.169.254", "http://127.0.0.1:8080"] {
+            let err = discover(&http, issuer).await.err().unwrap();
+            assert_eq!(err.status, StatusCode::BAD_GATEWAY, "{issuer}");
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_resolve_user_provisions_when_enabled(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let provider = create_test_provider(&pool, school_id, true).await;
+
+        let user_id = OidcService::resolve_user(
+            &pool,
+            None,
+            &provider,
+            &claims("sub-2", "new@greenfield.edu"),
+        )
+        .await
+        .unwrap();
+
+        let (first_name, user_school) = sqlx::query_as::<_, (String, Option<SchoolId>)>(
+            "SELECT first_name, school_id FROM users WHERE id = $1",
+        )
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(first_name, "Ada");
+        assert_eq!(user_school, Some(school_id));
+
+        let role_id =
+            sqlx::query_scalar::<_, RoleId>("SELECT role_id FROM user_roles WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(role_id, system_roles::STUDENT);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_resolve_user_rejections(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let other_school_id = create_test_school(&pool).await;
+        let provider = create_test_provider(&pool, school_id, false).await;
+        create_test_user(&pool, other_school_id, "elsewhere@greenfield.edu").await;
+
+        let unknown = OidcService::resolve_user(
+            &pool,
+            None,
+            &provider,
+            &claims("a", "nobody@greenfield.edu"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(unknown.status, StatusCode::FORBIDDEN);
+
+        let wrong_domain =
+            OidcService::resolve_user(&pool, None, &provider, &claims("b", "eve@evil.com"))
+                .await
+                .unwrap_err();
+        assert_eq!(wrong_domain.status, StatusCode::FORBIDDEN);
+
+        let other_school = OidcService::resolve_user(
+            &pool,
+            None,
+            &provider,
+            &claims("c", "elsewhere@greenfield.edu"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(other_school.status, StatusCode::FORBIDDEN);
+
+        let unverified = OidcService::resolve_user(
+            &pool,
+            None,
+            &provider,
+            &IdTokenClaims {
+                email_verified: Some(false),
+                ..claims("d", "nobody@greenfield.edu")
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(unverified.status, StatusCode::FORBIDDEN);
+    }
+}
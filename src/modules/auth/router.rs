@@ -1,9 +1,16 @@
 use crate::state::AppState;
-use axum::{Router, routing::post};
+use axum::{
+    Router,
+    routing::{delete, get, post},
+};
+
+use super::oidc::router::init_oidc_router;
+use crate::modules::profiles::router::init_switch_profile_router;
 
 use super::controller::{
-    forgot_password, login_user, logout, refresh_token, reset_password, verify_mfa_login,
-    verify_mfa_recovery_login,
+    forgot_password, get_sessions, login_user, logout, logout_all, refresh_token, reset_password,
+    revoke_session, start_passkey_login, verify_mfa_login, verify_mfa_recovery_login,
+    verify_passkey_login,
 };
 
 pub fn init_auth_router() -> Router<AppState> {
@@ -11,8 +18,15 @@ pub fn init_auth_router() -> Router<AppState> {
         .route("/login", post(login_user))
         .route("/mfa/verify", post(verify_mfa_login))
         .route("/mfa/recovery", post(verify_mfa_recovery_login))
+        .route("/mfa/passkey/challenge", post(start_passkey_login))
+        .route("/mfa/passkey/verify", post(verify_passkey_login))
         .route("/forgot-password", post(forgot_password))
         .route("/reset-password", post(reset_password))
         .route("/refresh", post(refresh_token))
         .route("/logout", post(logout))
+        .route("/logout-all", post(logout_all))
+        .route("/sessions", get(get_sessions))
+        .route("/sessions/{id}", delete(revoke_session))
+        .merge(init_oidc_router())
+        .merge(init_switch_profile_router())
 }
@@ -1,4 +1,6 @@
 use chrono::{Duration, Utc};
+use data_encoding::BASE64URL_NOPAD;
+use rand::RngCore;
 use sqlx::{PgPool, Row};
 use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
@@ -6,40 +8,157 @@ use uuid::Uuid;
 use chalkbyte_models::Email;
 
 use chalkbyte_auth::{
-    create_access_token, create_mfa_temp_token, create_refresh_token, verify_mfa_temp_token,
+    access_claims, create_access_token, encode_access_token, encode_refresh_token, refresh_claims,
     verify_refresh_token,
 };
-use chalkbyte_config::JwtConfig;
-use chalkbyte_core::{AppError, hash_password, verify_password};
+use chalkbyte_cache::{
+    MfaChallenge, MfaChallengeStore, Rotation, SessionClient, TokenFamily, TokenStore,
+    TokenStoreError,
+};
+use chalkbyte_config::{JwtConfig, WebauthnConfig};
+use chalkbyte_core::{AppError, ErrorCode, PasswordPolicy, verify_password};
 
 #[cfg(feature = "observability")]
 use chalkbyte_observability::metrics;
 use crate::modules::auth::model::{
-    ForgotPasswordRequest, LoginRequest, LoginResponse, LoginUser, MessageResponse,
-    MfaRecoveryLoginRequest, MfaRequiredResponse, MfaVerifyLoginRequest, RefreshTokenRequest,
-    ResetPasswordRequest,
+    DeviceSession, ForgotPasswordRequest, LoginRequest, LoginResponse, LoginUser, LogoutRequest,
+    MessageResponse, MfaMethod, MfaPasskeyChallengeRequest, MfaPasskeyChallengeResponse,
+    MfaPasskeyLoginRequest, MfaRecoveryLoginRequest, MfaRequiredResponse, MfaVerifyLoginRequest,
+    RefreshTokenRequest, ResetPasswordRequest,
 };
+use crate::modules::mfa::service::{MfaService, mfa_method_from_db};
+use crate::modules::profiles::service::ProfileService;
 use crate::modules::roles::service as roles_service;
 use crate::modules::users::model::{BranchInfo, LevelInfo, SchoolInfo};
+use crate::modules::users::service::UserService;
+use crate::utils::email::EmailService;
 use chalkbyte_models::ids::{BranchId, LevelId, SchoolId, UserId};
 
 pub struct AuthService;
 
-/// Helper struct for user data with school_id needed for JWT
+/// Wrong codes accepted before a pending MFA login must start over
+const MFA_MAX_ATTEMPTS: u32 = 5;
+
+/// How long a pending MFA login stays valid
+const MFA_CHALLENGE_TTL: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// Helper struct for user data with school_id and group_id needed for JWT
 struct UserForLogin {
     id: Uuid,
     email: String,
     school_id: Option<Uuid>,
+    group_id: Option<Uuid>,
     login_user: LoginUser,
 }
 
+fn token_store_error(e: TokenStoreError) -> AppError {
+    AppError::internal_error(e.to_string())
+}
+
+fn refresh_ttl(jwt_config: &JwtConfig) -> std::time::Duration {
+    std::time::Duration::from_secs(jwt_config.refresh_token_expiry.max(0) as u64)
+}
+
+/// Start a new token family for the device and return its first refresh token.
+///
+/// `login_id` is the account that signed in when the session is for one of
+/// its linked profiles.
+async fn start_session(
+    tokens: &dyn TokenStore,
+    user_id: Uuid,
+    email: &str,
+    login_id: Option<Uuid>,
+    device_id: Option<String>,
+    client: SessionClient,
+    jwt_config: &JwtConfig,
+) -> Result<String, AppError> {
+    let family_id = Uuid::new_v4();
+    let jti = Uuid::new_v4();
+    let family = TokenFamily {
+        family_id,
+        user_id,
+        device_id: device_id.unwrap_or_else(|| family_id.to_string()),
+        client,
+    };
+
+    let mut claims = refresh_claims(user_id, email, family_id, jti, jwt_config);
+    claims.login_id = login_id;
+    let refresh_token = encode_refresh_token(&claims, jwt_config)?;
+    tokens
+        .start_family(&family, &jti.to_string(), refresh_ttl(jwt_config))
+        .await
+        .map_err(token_store_error)?;
+
+    Ok(refresh_token)
+}
+
+/// Random, unguessable handle identifying a pending MFA login
+fn new_challenge_handle() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    BASE64URL_NOPAD.encode(&bytes)
+}
+
+/// Look up a pending MFA login that accepts the given method
+async fn pending_challenge(
+    challenges: &dyn MfaChallengeStore,
+    handle: &str,
+    method: MfaMethod,
+) -> Result<MfaChallenge, AppError> {
+    let challenge = challenges
+        .get(handle)
+        .await
+        .map_err(token_store_error)?
+        .ok_or_else(|| AppError::unauthorized("Invalid or expired temp token".to_string()))?;
+
+    if method == MfaMethod::RecoveryCode && !challenge.allow_recovery_code {
+        return Err(AppError::unauthorized(
+            "Recovery codes are not available for this login".to_string(),
+        ));
+    }
+
+    Ok(challenge)
+}
+
+/// Settle a pending MFA login after checking its code.
+///
+/// A wrong code uses up an attempt and the login is cancelled once none are
+/// left. A right code consumes the challenge so the handle works only once.
+async fn settle_challenge(
+    challenges: &dyn MfaChallengeStore,
+    handle: &str,
+    is_valid: bool,
+    invalid_message: &str,
+) -> Result<(), AppError> {
+    if !is_valid {
+        let attempts_left = challenges
+            .record_failure(handle)
+            .await
+            .map_err(token_store_error)?;
+
+        return Err(match attempts_left {
+            Some(left) if left > 0 => AppError::unauthorized(invalid_message.to_string()),
+            _ => AppError::unauthorized("Too many invalid codes, please sign in again".to_string()),
+        });
+    }
+
+    // A concurrent request may have completed or cancelled the login meanwhile
+    challenges
+        .complete(handle)
+        .await
+        .map_err(token_store_error)?
+        .ok_or_else(|| AppError::unauthorized("Invalid or expired temp token".to_string()))?;
+
+    Ok(())
+}
+
 /// Fetch user with joined school/level/branch relations
 async fn fetch_user_with_relations(db: &PgPool, user_id: Uuid) -> Result<UserForLogin, AppError> {
     let row = sqlx::query(
         r#"SELECT
             u.id, u.first_name, u.last_name, u.email,
             u.date_of_birth, u.grade_level, u.created_at, u.updated_at,
-            u.school_id, u.level_id, u.branch_id,
+            u.school_id, u.group_id, u.level_id, u.branch_id,
             s.id as school_id_joined, s.name as school_name, s.address as school_address,
             l.id as level_id_joined, l.name as level_name, l.description as level_description,
             b.id as branch_id_joined, b.name as branch_name, b.description as branch_description
@@ -56,6 +175,7 @@ async fn fetch_user_with_relations(db: &PgPool, user_id: Uuid) -> Result<UserFor
     let id = row.get("id");
     let email: String = row.get("email");
     let school_id = row.get("school_id");
+    let group_id = row.get("group_id");
 
     let school = row
         .try_get::<Option<Uuid>, _>("school_id_joined")
@@ -91,6 +211,7 @@ async fn fetch_user_with_relations(db: &PgPool, user_id: Uuid) -> Result<UserFor
         id,
         email: email.clone(),
         school_id,
+        group_id,
         login_user: LoginUser {
             id: UserId::from(id),
             first_name: row.get("first_name"),
@@ -107,11 +228,143 @@ async fn fetch_user_with_relations(db: &PgPool, user_id: Uuid) -> Result<UserFor
     })
 }
 
+/// Hold a pending login server-side when the user has a second factor.
+///
+/// Returns the challenge to answer before tokens are issued, or `None` when
+/// the user has no second factor.
+pub(super) async fn start_mfa_challenge(
+    db: &PgPool,
+    challenges: &dyn MfaChallengeStore,
+    user_id: Uuid,
+) -> Result<Option<MfaRequiredResponse>, AppError> {
+    let (mfa_enabled, preferred_mfa_method) = sqlx::query_as::<_, (bool, Option<String>)>(
+        "SELECT mfa_enabled, preferred_mfa_method FROM users WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_one(db)
+    .await?;
+
+    let has_passkeys = MfaService::has_passkeys(db, user_id).await?;
+    if !mfa_enabled && !has_passkeys {
+        return Ok(None);
+    }
+
+    let allow_recovery_code = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM mfa_recovery_codes WHERE user_id = $1 AND used = FALSE)",
+    )
+    .bind(user_id)
+    .fetch_one(db)
+    .await?;
+
+    let handle = new_challenge_handle();
+    let challenge = MfaChallenge {
+        user_id,
+        allow_recovery_code,
+        attempts_left: MFA_MAX_ATTEMPTS,
+    };
+    challenges
+        .create(&handle, &challenge, MFA_CHALLENGE_TTL)
+        .await
+        .map_err(token_store_error)?;
+
+    let mut methods = Vec::new();
+    if mfa_enabled {
+        methods.push(MfaMethod::Totp);
+    }
+    if has_passkeys {
+        methods.push(MfaMethod::Passkey);
+    }
+    if allow_recovery_code {
+        methods.push(MfaMethod::RecoveryCode);
+    }
+    let preferred_method = preferred_mfa_method
+        .as_deref()
+        .and_then(mfa_method_from_db)
+        .filter(|method| methods.contains(method));
+
+    Ok(Some(MfaRequiredResponse {
+        mfa_required: true,
+        temp_token: handle,
+        methods,
+        preferred_method,
+        expires_in: MFA_CHALLENGE_TTL.as_secs(),
+    }))
+}
+
+/// Issue tokens for a user who has signed in, including any second factor
+pub(super) async fn issue_login_tokens(
+    db: &PgPool,
+    tokens: &dyn TokenStore,
+    user_id: Uuid,
+    device_id: Option<String>,
+    client: SessionClient,
+    jwt_config: &JwtConfig,
+) -> Result<LoginResponse, AppError> {
+    issue_tokens(db, tokens, user_id, None, device_id, client, jwt_config).await
+}
+
+/// Issue tokens for a user, acting as a linked profile of `login_id` if given
+async fn issue_tokens(
+    db: &PgPool,
+    tokens: &dyn TokenStore,
+    user_id: Uuid,
+    login_id: Option<Uuid>,
+    device_id: Option<String>,
+    client: SessionClient,
+    jwt_config: &JwtConfig,
+) -> Result<LoginResponse, AppError> {
+    // Get user details with relations
+    let user_data = fetch_user_with_relations(db, user_id).await?;
+
+    // Fetch roles and permissions for JWT
+    let roles = roles_service::get_user_roles_internal(db, UserId::from(user_data.id)).await?;
+    let permissions = roles_service::get_user_permissions(db, UserId::from(user_data.id)).await?;
+
+    // Extract role IDs and permission names for JWT
+    let role_ids: Vec<Uuid> = roles.iter().map(|r| r.role.id.into_inner()).collect();
+    let permission_names: Vec<String> = permissions.iter().map(|p| p.name.clone()).collect();
+
+    // Generate final access token with roles and permissions
+    let mut claims = access_claims(
+        user_id,
+        &user_data.email,
+        user_data.school_id,
+        user_data.group_id,
+        role_ids,
+        permission_names,
+        jwt_config,
+    );
+    claims.login_id = login_id;
+    let access_token = encode_access_token(&claims, jwt_config)?;
+
+    let refresh_token = start_session(
+        tokens,
+        user_id,
+        &user_data.email,
+        login_id,
+        device_id,
+        client,
+        jwt_config,
+    )
+    .await?;
+
+    Ok(LoginResponse {
+        access_token,
+        refresh_token,
+        user: user_data.login_user,
+        roles,
+        permissions,
+    })
+}
+
 impl AuthService {
-    #[instrument(skip(db, dto, jwt_config), fields(auth.email = %dto.email, auth.event = "login_attempt"))]
+    #[instrument(skip(db, tokens, challenges, dto, client, jwt_config), fields(auth.email = %dto.email, auth.event = "login_attempt"))]
     pub async fn login_user(
         db: &PgPool,
+        tokens: &dyn TokenStore,
+        challenges: &dyn MfaChallengeStore,
         dto: LoginRequest,
+        client: SessionClient,
         jwt_config: &JwtConfig,
     ) -> Result<Result<LoginResponse, MfaRequiredResponse>, AppError> {
         debug!(email = %dto.email, "Processing login request");
@@ -119,8 +372,8 @@ impl AuthService {
         let row = sqlx::query(
             r#"SELECT
                 u.id, u.first_name, u.last_name, u.email, u.password,
-                u.date_of_birth, u.grade_level, u.created_at, u.updated_at, u.mfa_enabled,
-                u.school_id, u.level_id, u.branch_id,
+                u.date_of_birth, u.grade_level, u.created_at, u.updated_at,
+                u.school_id, u.group_id, u.level_id, u.branch_id,
                 s.id as school_id_joined, s.name as school_name, s.address as school_address,
                 l.id as level_id_joined, l.name as level_name, l.description as level_description,
                 b.id as branch_id_joined, b.name as branch_name, b.description as branch_description
@@ -136,7 +389,7 @@ impl AuthService {
         .ok_or_else(|| {
             #[cfg(feature = "observability")]
             metrics::track_user_login_failure("invalid_email");
-            AppError::unauthorized("Invalid email or password".to_string())
+            AppError::from_code(ErrorCode::InvalidCredentials)
         })?;
 
         let user_id = row.get("id");
@@ -145,11 +398,11 @@ impl AuthService {
         let email: String = row.get("email");
         let password: String = row.get("password");
         let school_id = row.get("school_id");
+        let group_id = row.get("group_id");
         let date_of_birth = row.get("date_of_birth");
         let grade_level = row.get("grade_level");
         let created_at = row.get("created_at");
         let updated_at = row.get("updated_at");
-        let mfa_enabled = row.get("mfa_enabled");
 
         let school = row
             .try_get::<Option<Uuid>, _>("school_id_joined")
@@ -183,25 +436,14 @@ impl AuthService {
 
         let is_valid = verify_password(&dto.password, &password)?;
 
-        if !is_valid {
+         if !is_valid {
             #[cfg(feature = "observability")]
             metrics::track_user_login_failure("invalid_password");
-            return Err(AppError::unauthorized(
-                "Invalid email or password".to_string(),
-            ));
+            return Err(AppError::from_code(ErrorCode::InvalidCredentials));
         }
 
-        // Check if MFA is enabled
-        if mfa_enabled {
-            // Generate temporary token for MFA verification
-            let temp_token = create_mfa_temp_token(user_id, &email, jwt_config)?;
-
-            #[cfg(feature = "observability")]
-            metrics::track_jwt_issued();
-            return Ok(Err(MfaRequiredResponse {
-                mfa_required: true,
-                temp_token,
-            }));
+        if let Some(mfa_required) = start_mfa_challenge(db, challenges, user_id).await? {
+            return Ok(Err(mfa_required));
         }
 
         // No MFA, proceed with normal login
@@ -217,12 +459,22 @@ impl AuthService {
             user_id,
             &email,
             school_id,
+            group_id,
             role_ids,
             permission_names,
             jwt_config,
         )?;
 
-        let refresh_token = create_refresh_token(user_id, &email, jwt_config)?;
+        let refresh_token = start_session(
+            tokens,
+            user_id,
+            &email,
+            None,
+            dto.device_id,
+            client,
+            jwt_config,
+        )
+        .await?;
 
         // Track metrics
         #[cfg(feature = "observability")]
@@ -235,17 +487,9 @@ impl AuthService {
                 .map(|r| r.role.name.as_str())
                 .unwrap_or("none");
             metrics::track_user_login_success(primary_role);
+            metrics::track_school_login(school_id);
         }
 
-        // Store refresh token in database
-        let expires_at = Utc::now() + Duration::seconds(jwt_config.refresh_token_expiry);
-        sqlx::query("INSERT INTO refresh_tokens (user_id, token, expires_at) VALUES ($1, $2, $3)")
-            .bind(user_id)
-            .bind(&refresh_token)
-            .bind(expires_at)
-            .execute(db)
-            .await?;
-
         let user = LoginUser {
             id: UserId::from(user_id),
             first_name,
@@ -269,137 +513,153 @@ impl AuthService {
         }))
     }
 
-    #[instrument(skip(db, dto, jwt_config), fields(auth.event = "mfa_verification"))]
+    #[instrument(skip(db, tokens, challenges, dto, client, jwt_config), fields(auth.event = "mfa_verification"))]
     pub async fn verify_mfa_login(
         db: &PgPool,
+        tokens: &dyn TokenStore,
+        challenges: &dyn MfaChallengeStore,
         dto: MfaVerifyLoginRequest,
+        client: SessionClient,
         jwt_config: &JwtConfig,
     ) -> Result<LoginResponse, AppError> {
         debug!("Processing MFA verification request");
-        use crate::modules::mfa::service::MfaService;
-
-        // Verify temp token
-        let temp_claims = verify_mfa_temp_token(&dto.temp_token, jwt_config)?;
 
-        let user_id = Uuid::parse_str(&temp_claims.sub)
-            .map_err(|_| AppError::unauthorized("Invalid token".to_string()))?;
+        let challenge = pending_challenge(challenges, &dto.temp_token, MfaMethod::Totp).await?;
+        let user_id = challenge.user_id;
 
         // Verify TOTP code
         let is_valid = MfaService::verify_totp_login(db, user_id, &dto.code).await?;
 
-         if !is_valid {
-            #[cfg(feature = "observability")]
+        #[cfg(feature = "observability")]
+        if !is_valid {
             metrics::track_user_login_failure("invalid_mfa_code");
-            return Err(AppError::unauthorized("Invalid MFA code".to_string()));
         }
+        settle_challenge(challenges, &dto.temp_token, is_valid, "Invalid MFA code").await?;
 
-        // Get user details with relations
-        let user_data = fetch_user_with_relations(db, user_id).await?;
-
-        // Fetch roles and permissions for JWT
-        let roles = roles_service::get_user_roles_internal(db, UserId::from(user_data.id)).await?;
-        let permissions =
-            roles_service::get_user_permissions(db, UserId::from(user_data.id)).await?;
-
-        // Extract role IDs and permission names for JWT
-        let role_ids: Vec<Uuid> = roles.iter().map(|r| r.role.id.into_inner()).collect();
-        let permission_names: Vec<String> = permissions.iter().map(|p| p.name.clone()).collect();
-
-        // Generate final access token with roles and permissions
-        let access_token = create_access_token(
-            user_id,
-            &user_data.email,
-            user_data.school_id,
-            role_ids,
-            permission_names,
-            jwt_config,
-        )?;
-
-        let refresh_token = create_refresh_token(user_id, &user_data.email, jwt_config)?;
-
-        // Store refresh token in database
-        let expires_at = Utc::now() + Duration::seconds(jwt_config.refresh_token_expiry);
-        sqlx::query("INSERT INTO refresh_tokens (user_id, token, expires_at) VALUES ($1, $2, $3)")
-            .bind(user_id)
-            .bind(&refresh_token)
-            .bind(expires_at)
-            .execute(db)
-            .await?;
-
-        Ok(LoginResponse {
-            access_token,
-            refresh_token,
-            user: user_data.login_user,
-            roles,
-            permissions,
-        })
+        issue_login_tokens(db, tokens, user_id, dto.device_id, client, jwt_config).await
     }
 
-    #[instrument(skip(db, dto, jwt_config), fields(auth.event = "mfa_recovery_verification"))]
+    /// Complete a login with a recovery code
+    ///
+    /// Each accepted code is logged as an audit event and the user is
+    /// emailed, since a recovery code bypasses their usual second factor.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(db, tokens, challenges, dto, client, email, jwt_config), fields(auth.event = "mfa_recovery_verification"))]
     pub async fn verify_mfa_recovery_login(
         db: &PgPool,
+        tokens: &dyn TokenStore,
+        challenges: &dyn MfaChallengeStore,
         dto: MfaRecoveryLoginRequest,
+        client: SessionClient,
+        email: &EmailService,
         jwt_config: &JwtConfig,
     ) -> Result<LoginResponse, AppError> {
         debug!("Processing MFA recovery code verification");
-        use crate::modules::mfa::service::MfaService;
-
-        // Verify temp token
-        let temp_claims = verify_mfa_temp_token(&dto.temp_token, jwt_config)?;
 
-        let user_id = Uuid::parse_str(&temp_claims.sub)
-            .map_err(|_| AppError::unauthorized("Invalid token".to_string()))?;
+        let challenge =
+            pending_challenge(challenges, &dto.temp_token, MfaMethod::RecoveryCode).await?;
+        let user_id = challenge.user_id;
 
-        // Verify recovery code
-        let is_valid =
-            MfaService::verify_recovery_code_login(db, user_id, &dto.recovery_code).await?;
+        // Verify and consume recovery code
+        let remaining =
+            MfaService::verify_recovery_code_login(db, user_id, &dto.recovery_code, &client)
+                .await?;
 
-        if !is_valid {
-            return Err(AppError::unauthorized(
-                "Invalid or already used recovery code".to_string(),
-            ));
+        settle_challenge(
+            challenges,
+            &dto.temp_token,
+            remaining.is_some(),
+            "Invalid or already used recovery code",
+        )
+        .await?;
+        let remaining = remaining.unwrap_or_default();
+
+        info!(
+            audit.action = "mfa_recovery_code_used",
+            audit.resource = "user",
+            %user_id,
+            remaining,
+            ip_address = client.ip_address.as_deref(),
+            user_agent = client.user_agent.as_deref(),
+            "Recovery code used to sign in"
+        );
+
+        let ip_address = client.ip_address.clone();
+        let user_agent = client.user_agent.clone();
+        let response =
+            issue_login_tokens(db, tokens, user_id, dto.device_id, client, jwt_config).await?;
+
+        // The login already succeeded; a mail failure is logged, not returned
+        if let Err(e) = email
+            .send_recovery_code_used_notification(
+                response.user.email.as_str(),
+                &response.user.first_name,
+                remaining,
+                ip_address.as_deref(),
+                user_agent.as_deref(),
+            )
+            .await
+        {
+            warn!(error = %e, %user_id, "Failed to send recovery code notification");
         }
 
-        // Get user details with relations
-        let user_data = fetch_user_with_relations(db, user_id).await?;
+        Ok(response)
+    }
 
-        // Fetch roles and permissions for JWT
-        let roles = roles_service::get_user_roles_internal(db, UserId::from(user_data.id)).await?;
-        let permissions =
-            roles_service::get_user_permissions(db, UserId::from(user_data.id)).await?;
+    #[instrument(skip(db, challenges, dto, webauthn_config), fields(auth.event = "mfa_passkey_challenge"))]
+    pub async fn start_passkey_login(
+        db: &PgPool,
+        challenges: &dyn MfaChallengeStore,
+        dto: MfaPasskeyChallengeRequest,
+        webauthn_config: &WebauthnConfig,
+    ) -> Result<MfaPasskeyChallengeResponse, AppError> {
+        debug!("Issuing MFA passkey challenge");
+
+        let challenge = pending_challenge(challenges, &dto.temp_token, MfaMethod::Passkey).await?;
+
+        let options = MfaService::start_passkey_login(
+            db,
+            webauthn_config,
+            challenge.user_id,
+            &dto.temp_token,
+            MFA_CHALLENGE_TTL,
+        )
+        .await?;
 
-        // Extract role IDs and permission names for JWT
-        let role_ids: Vec<Uuid> = roles.iter().map(|r| r.role.id.into_inner()).collect();
-        let permission_names: Vec<String> = permissions.iter().map(|p| p.name.clone()).collect();
+        Ok(MfaPasskeyChallengeResponse { options })
+    }
 
-        // Generate final access token with roles and permissions
-        let access_token = create_access_token(
-            user_id,
-            &user_data.email,
-            user_data.school_id,
-            role_ids,
-            permission_names,
-            jwt_config,
-        )?;
+    #[instrument(skip(db, tokens, challenges, dto, client, jwt_config, webauthn_config), fields(auth.event = "mfa_passkey_verification"))]
+    pub async fn verify_passkey_login(
+        db: &PgPool,
+        tokens: &dyn TokenStore,
+        challenges: &dyn MfaChallengeStore,
+        dto: MfaPasskeyLoginRequest,
+        client: SessionClient,
+        jwt_config: &JwtConfig,
+        webauthn_config: &WebauthnConfig,
+    ) -> Result<LoginResponse, AppError> {
+        debug!("Processing MFA passkey verification");
 
-        let refresh_token = create_refresh_token(user_id, &user_data.email, jwt_config)?;
+        let challenge = pending_challenge(challenges, &dto.temp_token, MfaMethod::Passkey).await?;
+        let user_id = challenge.user_id;
 
-        // Store refresh token in database
-        let expires_at = Utc::now() + Duration::seconds(jwt_config.refresh_token_expiry);
-        sqlx::query("INSERT INTO refresh_tokens (user_id, token, expires_at) VALUES ($1, $2, $3)")
-            .bind(user_id)
-            .bind(&refresh_token)
-            .bind(expires_at)
-            .execute(db)
-            .await?;
+        let is_valid = MfaService::verify_passkey_login(
+            db,
+            webauthn_config,
+            user_id,
+            &dto.temp_token,
+            dto.credential,
+        )
+        .await?;
 
-        Ok(LoginResponse {
-            access_token,
-            refresh_token,
-            user: user_data.login_user,
-            roles,
-            permissions,
-        })
+        #[cfg(feature = "observability")]
+        if !is_valid {
+            metrics::track_user_login_failure("invalid_passkey");
+        }
+        settle_challenge(challenges, &dto.temp_token, is_valid, "Invalid passkey").await?;
+
+        issue_login_tokens(db, tokens, user_id, dto.device_id, client, jwt_config).await
     }
 
     #[instrument(skip(db, dto), fields(auth.email = %dto.email, auth.event = "forgot_password"))]
@@ -447,10 +707,16 @@ impl AuthService {
         Ok(())
     }
 
-    #[instrument(skip(db, dto), fields(auth.event = "reset_password"))]
+    #[instrument(
+        skip(db, tokens, challenges, dto, password_policy),
+        fields(auth.event = "reset_password")
+    )]
     pub async fn reset_password(
         db: &PgPool,
+        tokens: &dyn TokenStore,
+        challenges: &dyn MfaChallengeStore,
         dto: ResetPasswordRequest,
+        password_policy: &PasswordPolicy,
     ) -> Result<MessageResponse, AppError> {
         debug!("Processing password reset request");
 
@@ -483,15 +749,13 @@ impl AuthService {
             )));
         }
 
-        // Hash new password
-        let password_hash = hash_password(&dto.new_password)?;
-
-        // Update password
-        sqlx::query("UPDATE users SET password = $1, updated_at = NOW() WHERE id = $2")
-            .bind(&password_hash)
-            .bind(token_record.user_id)
-            .execute(db)
-            .await?;
+        UserService::set_password(
+            db,
+            token_record.user_id.into(),
+            &dto.new_password,
+            password_policy,
+        )
+        .await?;
 
         // Mark token as used
         sqlx::query("UPDATE password_reset_tokens SET used = TRUE WHERE id = $1")
@@ -499,8 +763,9 @@ impl AuthService {
             .execute(db)
             .await?;
 
-        // Revoke all refresh tokens for this user
-        Self::revoke_all_refresh_tokens(db, token_record.user_id).await?;
+        // Revoke all refresh tokens and pending MFA logins for this user
+        Self::revoke_all_refresh_tokens(tokens, token_record.user_id).await?;
+        Self::cancel_mfa_logins(challenges, token_record.user_id).await?;
 
         info!(user.id = %token_record.user_id, "Password reset successfully");
 
@@ -509,10 +774,12 @@ impl AuthService {
         })
     }
 
-    #[instrument(skip(db, dto, jwt_config), fields(auth.event = "token_refresh"))]
+    #[instrument(skip(db, tokens, dto, client, jwt_config), fields(auth.event = "token_refresh"))]
     pub async fn refresh_access_token(
         db: &PgPool,
+        tokens: &dyn TokenStore,
         dto: RefreshTokenRequest,
+        client: SessionClient,
         jwt_config: &JwtConfig,
     ) -> Result<LoginResponse, AppError> {
         debug!("Processing token refresh request");
@@ -522,46 +789,54 @@ impl AuthService {
         let user_id = Uuid::parse_str(&claims.sub)
             .map_err(|_| AppError::unauthorized("Invalid token".to_string()))?;
 
-        // Check if refresh token exists in database and is not revoked
-        #[derive(sqlx::FromRow)]
-        struct RefreshTokenRecord {
-            revoked: bool,
-            expires_at: chrono::DateTime<Utc>,
-        }
-
-        let token_record = sqlx::query_as::<_, RefreshTokenRecord>(
-            "SELECT revoked, expires_at FROM refresh_tokens WHERE token = $1 AND user_id = $2",
-        )
-        .bind(&dto.refresh_token)
-        .bind(user_id)
-        .fetch_optional(db)
-        .await?
-        .ok_or_else(|| AppError::unauthorized("Invalid refresh token".to_string()))?;
-
-        if token_record.revoked {
+        // A linked profile's session ends once the profile is unlinked
+        if let Some(login_id) = claims.login_id
+            && !ProfileService::is_linked(db, UserId::from(login_id), UserId::from(user_id)).await?
+        {
             return Err(AppError::unauthorized(
-                "Refresh token has been revoked".to_string(),
+                "This profile is no longer linked to your account".to_string(),
             ));
         }
 
-        // Check if token is expired
-        if token_record.expires_at < Utc::now() {
-            warn!(
-                user.id = %user_id,
-                auth.event = "token_refresh_failed",
-                reason = "token_expired",
-                "Refresh token expired"
-            );
-            return Err(AppError::unauthorized(
-                "Refresh token has expired".to_string(),
-            ));
+        // Get user details with relations
+        let user_data = fetch_user_with_relations(db, user_id).await?;
+
+        // Rotate the family to a new refresh token; only its current token may do this
+        let next_jti = Uuid::new_v4();
+        let rotation = tokens
+            .rotate(
+                user_id,
+                claims.fam,
+                &claims.jti,
+                &next_jti.to_string(),
+                &client,
+                refresh_ttl(jwt_config),
+            )
+            .await
+            .map_err(token_store_error)?;
+
+        match rotation {
+            Rotation::Rotated => {}
+            Rotation::Reused => {
+                warn!(
+                    user.id = %user_id,
+                    auth.event = "token_refresh_failed",
+                    reason = "token_reused",
+                    "Superseded refresh token presented, session revoked"
+                );
+                return Err(AppError::unauthorized(
+                    "Refresh token has already been used".to_string(),
+                ));
+            }
+            Rotation::Revoked => {
+                return Err(AppError::unauthorized(
+                    "Refresh token has been revoked".to_string(),
+                ));
+            }
         }
 
         debug!(user.id = %user_id, "Refresh token valid, generating new tokens");
 
-        // Get user details with relations
-        let user_data = fetch_user_with_relations(db, user_id).await?;
-
         // Fetch roles and permissions for new access token
         let roles = roles_service::get_user_roles_internal(db, UserId::from(user_data.id)).await?;
         let permissions =
@@ -572,64 +847,198 @@ impl AuthService {
         let permission_names: Vec<String> = permissions.iter().map(|p| p.name.clone()).collect();
 
         // Generate new access token with roles and permissions
-        let access_token = create_access_token(
+        let mut access = access_claims(
             user_id,
             &user_data.email,
             user_data.school_id,
+            user_data.group_id,
             role_ids,
             permission_names,
             jwt_config,
-        )?;
-
-        // Generate new refresh token (refresh token rotation)
-        let new_refresh_token = create_refresh_token(user_id, &user_data.email, jwt_config)?;
-
-        // Revoke old refresh token
-        sqlx::query(
-            "UPDATE refresh_tokens SET revoked = TRUE, updated_at = NOW() WHERE token = $1",
-        )
-        .bind(&dto.refresh_token)
-        .execute(db)
-        .await?;
+        );
+        access.login_id = claims.login_id;
+        let access_token = encode_access_token(&access, jwt_config)?;
 
-        // Store new refresh token
-        let expires_at = Utc::now() + Duration::seconds(jwt_config.refresh_token_expiry);
-        sqlx::query("INSERT INTO refresh_tokens (user_id, token, expires_at) VALUES ($1, $2, $3)")
-            .bind(user_id)
-            .bind(&new_refresh_token)
-            .bind(expires_at)
-            .execute(db)
-            .await?;
+        let mut refresh =
+            refresh_claims(user_id, &user_data.email, claims.fam, next_jti, jwt_config);
+        refresh.login_id = claims.login_id;
+        let refresh_token = encode_refresh_token(&refresh, jwt_config)?;
 
         Ok(LoginResponse {
             access_token,
-            refresh_token: new_refresh_token,
+            refresh_token,
             user: user_data.login_user,
             roles,
             permissions,
         })
     }
 
-    #[instrument(skip(db), fields(user.id = %user_id, auth.event = "revoke_all_tokens"))]
-    pub async fn revoke_all_refresh_tokens(db: &PgPool, user_id: Uuid) -> Result<(), AppError> {
-        debug!(user.id = %user_id, "Revoking all refresh tokens");
+    /// Issue tokens acting as one of the login's linked profiles.
+    ///
+    /// `login_id` is the account that signed in. Switching to it ends acting
+    /// as a profile; any other target must be linked to it. The new session
+    /// is separate from the login's, so each can be refreshed and logged out
+    /// on its own.
+    #[instrument(skip(db, tokens, client, jwt_config), fields(user.id = %login_id, auth.event = "switch_profile"))]
+    pub async fn switch_profile(
+        db: &PgPool,
+        tokens: &dyn TokenStore,
+        login_id: UserId,
+        profile_id: UserId,
+        device_id: Option<String>,
+        client: SessionClient,
+        jwt_config: &JwtConfig,
+    ) -> Result<LoginResponse, AppError> {
+        let acting_for = if profile_id == login_id {
+            None
+        } else if ProfileService::is_linked(db, login_id, profile_id).await? {
+            Some(login_id.into_inner())
+        } else {
+            return Err(AppError::forbidden(
+                "You can only switch to profiles linked to your account".to_string(),
+            ));
+        };
 
-        sqlx::query(
-            "UPDATE refresh_tokens SET revoked = TRUE, updated_at = NOW() WHERE user_id = $1 AND revoked = FALSE",
+        let response = issue_tokens(
+            db,
+            tokens,
+            profile_id.into_inner(),
+            acting_for,
+            device_id,
+            client,
+            jwt_config,
         )
-        .bind(user_id)
-        .execute(db)
         .await?;
 
+        info!(auth.profile_id = %profile_id, "Switched profile");
+
+        Ok(response)
+    }
+
+    /// Revoke the family of the presented refresh token, logging out its device.
+    ///
+    /// Only the authenticated user's own tokens can be revoked.
+    #[instrument(skip(tokens, dto, jwt_config), fields(user.id = %user_id, auth.event = "logout"))]
+    pub async fn logout(
+        tokens: &dyn TokenStore,
+        user_id: Uuid,
+        dto: LogoutRequest,
+        jwt_config: &JwtConfig,
+    ) -> Result<(), AppError> {
+        let claims = verify_refresh_token(&dto.refresh_token, jwt_config)?;
+
+        if claims.sub != user_id.to_string() {
+            return Err(AppError::forbidden(
+                "Refresh token belongs to another user".to_string(),
+            ));
+        }
+
+        tokens
+            .revoke_family(user_id, claims.fam)
+            .await
+            .map_err(token_store_error)?;
+
+        info!(user.id = %user_id, auth.family_id = %claims.fam, "Device logged out");
+
+        Ok(())
+    }
+
+    /// List the devices signed in to the account, most recently active first.
+    #[instrument(skip(tokens), fields(user.id = %user_id))]
+    pub async fn list_sessions(
+        tokens: &dyn TokenStore,
+        user_id: Uuid,
+    ) -> Result<Vec<DeviceSession>, AppError> {
+        let families = tokens
+            .list_families(user_id)
+            .await
+            .map_err(token_store_error)?;
+
+        Ok(families
+            .into_iter()
+            .map(|family| DeviceSession {
+                id: family.family_id,
+                device_id: family.device_id,
+                user_agent: family.client.user_agent,
+                ip_address: family.client.ip_address,
+                created_at: family.created_at,
+                last_seen_at: family.last_seen_at,
+                expires_at: family.expires_at,
+            })
+            .collect())
+    }
+
+    /// Revoke one of the user's sessions, logging out that device.
+    ///
+    /// Its refresh token stops working at once; an access token already
+    /// issued to it stays valid until it expires.
+    #[instrument(skip(tokens), fields(user.id = %user_id, auth.event = "revoke_session"))]
+    pub async fn revoke_session(
+        tokens: &dyn TokenStore,
+        user_id: Uuid,
+        session_id: Uuid,
+    ) -> Result<(), AppError> {
+        let families = tokens
+            .list_families(user_id)
+            .await
+            .map_err(token_store_error)?;
+        if !families.iter().any(|f| f.family_id == session_id) {
+            return Err(AppError::from_code(ErrorCode::SessionNotFound));
+        }
+
+        tokens
+            .revoke_family(user_id, session_id)
+            .await
+            .map_err(token_store_error)?;
+
+        info!(user.id = %user_id, auth.family_id = %session_id, "Session revoked");
+
+        Ok(())
+    }
+
+    #[instrument(skip(tokens), fields(user.id = %user_id, auth.event = "revoke_all_tokens"))]
+    pub async fn revoke_all_refresh_tokens(
+        tokens: &dyn TokenStore,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        debug!(user.id = %user_id, "Revoking all refresh tokens");
+
+        tokens
+            .revoke_all(user_id)
+            .await
+            .map_err(token_store_error)?;
+
         info!(user.id = %user_id, "All refresh tokens revoked");
 
         Ok(())
     }
+
+    /// Cancel every pending MFA login for the user, so a password that
+    /// has already been entered can no longer be completed with a code.
+    #[instrument(skip(challenges), fields(user.id = %user_id, auth.event = "cancel_mfa_logins"))]
+    pub async fn cancel_mfa_logins(
+        challenges: &dyn MfaChallengeStore,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        challenges
+            .cancel_all(user_id)
+            .await
+            .map_err(token_store_error)?;
+
+        debug!(user.id = %user_id, "Pending MFA logins cancelled");
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::mfa_challenge::PgMfaChallengeStore;
+    use crate::utils::token_store::PgTokenStore;
+    use axum::http::StatusCode;
+    use chalkbyte_config::JwtKeySet;
+    use chalkbyte_core::hash_password;
     use chalkbyte_models::Email;
     use sqlx::PgPool;
 
@@ -654,7 +1063,7 @@ mod tests {
 
     // Helper to cleanup test user
     async fn cleanup_test_user(db: &PgPool, user_id: Uuid) {
-        sqlx::query("DELETE FROM refresh_tokens WHERE user_id = $1")
+        sqlx::query("DELETE FROM refresh_token_families WHERE user_id = $1")
             .bind(user_id)
             .execute(db)
             .await
@@ -674,16 +1083,29 @@ mod tests {
 
         let jwt_config = crate::config::jwt::JwtConfig {
             secret: "test_secret_key_for_testing".to_string(),
+            card_secret: "test_secret_key_for_testing".to_string(),
+            keys: JwtKeySet::hmac("test_secret_key_for_testing"),
             access_token_expiry: 3600,
             refresh_token_expiry: 604800,
         };
+        let tokens = PgTokenStore::new(db.clone());
+        let challenges = PgMfaChallengeStore::new(db.clone());
 
         let dto = LoginRequest {
             email: Email::new(&email).unwrap(),
             password: "testpassword123".to_string(),
+            device_id: None,
         };
 
-        let result = AuthService::login_user(&db, dto, &jwt_config).await;
+        let result = AuthService::login_user(
+            &db,
+            &tokens,
+            &challenges,
+            dto,
+            SessionClient::default(),
+            &jwt_config,
+        )
+        .await;
         assert!(result.is_ok());
 
         let login_result = result.unwrap();
@@ -727,27 +1149,46 @@ mod tests {
 
         let jwt_config = crate::config::jwt::JwtConfig {
             secret: "test_secret_key_for_testing".to_string(),
+            card_secret: "test_secret_key_for_testing".to_string(),
+            keys: JwtKeySet::hmac("test_secret_key_for_testing"),
             access_token_expiry: 3600,
             refresh_token_expiry: 604800,
         };
+        let tokens = PgTokenStore::new(db.clone());
+        let challenges = PgMfaChallengeStore::new(db.clone());
 
         // First login to get refresh token
         let login_dto = LoginRequest {
             email: Email::new(&email).unwrap(),
             password: "testpassword123".to_string(),
+            device_id: None,
         };
 
-        let login_result = AuthService::login_user(&db, login_dto, &jwt_config)
-            .await
-            .unwrap()
-            .unwrap();
+        let login_result = AuthService::login_user(
+            &db,
+            &tokens,
+            &challenges,
+            login_dto,
+            SessionClient::default(),
+            &jwt_config,
+        )
+        .await
+        .unwrap()
+        .unwrap();
 
         // Now refresh
         let refresh_dto = RefreshTokenRequest {
             refresh_token: login_result.refresh_token,
         };
 
-        let result = AuthService::refresh_access_token(&db, refresh_dto, &jwt_config).await;
+        let result = AuthService::refresh_access_token(
+            &db,
+            &tokens,
+            refresh_dto,
+            SessionClient::default(),
+            &jwt_config,
+        )
+        .await;
         assert!(result.is_ok(), "Refresh failed: {:?}", result.err());
 
         let response = result.unwrap();
@@ -757,6 +1198,188 @@ mod tests {
         cleanup_test_user(&db, user_id).await;
     }
 
+    async fn login_on_device(
+        db: &PgPool,
+        tokens: &PgTokenStore,
+        email: &str,
+        device_id: Option<&str>,
+        jwt_config: &JwtConfig,
+    ) -> String {
+        let dto = LoginRequest {
+            email: Email::new(email).unwrap(),
+            password: "testpassword123".to_string(),
+            device_id: device_id.map(str::to_string),
+        };
+        let challenges = PgMfaChallengeStore::new(db.clone());
+        let client = SessionClient {
+            user_agent: device_id.map(|device| format!("{device} browser")),
+            ip_address: Some("203.0.113.7".to_string()),
+        };
+        AuthService::login_user(db, tokens, &challenges, dto, client, jwt_config)
+            .await
+            .unwrap()
+            .unwrap()
+            .refresh_token
+    }
+
+    async fn refresh(
+        db: &PgPool,
+        tokens: &PgTokenStore,
+        refresh_token: &str,
+        jwt_config: &JwtConfig,
+    ) -> Result<LoginResponse, AppError> {
+        let dto = RefreshTokenRequest {
+            refresh_token: refresh_token.to_string(),
+        };
+        let client = SessionClient {
+            user_agent: Some("refreshing browser".to_string()),
+            ip_address: Some("198.51.100.4".to_string()),
+        };
+        AuthService::refresh_access_token(db, tokens, dto, client, jwt_config).await
+    }
+
+    fn test_jwt_config() -> JwtConfig {
+        JwtConfig {
+            secret: "test_secret_key_for_testing".to_string(),
+            card_secret: "test_secret_key_for_testing".to_string(),
+            keys: JwtKeySet::hmac("test_secret_key_for_testing"),
+            access_token_expiry: 3600,
+            refresh_token_expiry: 604800,
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_refresh_token_reuse_revokes_family(db: PgPool) {
+        let email = format!("test_reuse_{}@example.com", Uuid::new_v4());
+        create_test_user(&db, &email).await;
+        let jwt_config = test_jwt_config();
+        let tokens = PgTokenStore::new(db.clone());
+
+        let first = login_on_device(&db, &tokens, &email, Some("phone"), &jwt_config).await;
+        let second = refresh(&db, &tokens, &first, &jwt_config)
+            .await
+            .unwrap()
+            .refresh_token;
+
+        // Replaying the superseded token is rejected and ends the session
+        let replay = refresh(&db, &tokens, &first, &jwt_config).await;
+        assert_eq!(replay.unwrap_err().status, StatusCode::UNAUTHORIZED);
+
+        let after = refresh(&db, &tokens, &second, &jwt_config).await;
+        assert_eq!(after.unwrap_err().status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[sqlx::test]
+    async fn test_login_replaces_session_on_same_device_only(db: PgPool) {
+        let email = format!("test_device_{}@example.com", Uuid::new_v4());
+        create_test_user(&db, &email).await;
+        let jwt_config = test_jwt_config();
+        let tokens = PgTokenStore::new(db.clone());
+
+        let old_phone = login_on_device(&db, &tokens, &email, Some("phone"), &jwt_config).await;
+        let laptop = login_on_device(&db, &tokens, &email, Some("laptop"), &jwt_config).await;
+        let phone = login_on_device(&db, &tokens, &email, Some("phone"), &jwt_config).await;
+
+        assert!(
+            refresh(&db, &tokens, &old_phone, &jwt_config)
+                .await
+                .is_err()
+        );
+        assert!(refresh(&db, &tokens, &laptop, &jwt_config).await.is_ok());
+        assert!(refresh(&db, &tokens, &phone, &jwt_config).await.is_ok());
+    }
+
+    #[sqlx::test]
+    async fn test_logout_and_logout_all(db: PgPool) {
+        let email = format!("test_logout_{}@example.com", Uuid::new_v4());
+        let user_id = create_test_user(&db, &email).await;
+        let jwt_config = test_jwt_config();
+        let tokens = PgTokenStore::new(db.clone());
+
+        let phone = login_on_device(&db, &tokens, &email, Some("phone"), &jwt_config).await;
+        let laptop = login_on_device(&db, &tokens, &email, Some("laptop"), &jwt_config).await;
+        let tablet = login_on_device(&db, &tokens, &email, None, &jwt_config).await;
+
+        let dto = LogoutRequest {
+            refresh_token: phone.clone(),
+        };
+        AuthService::logout(&tokens, user_id, dto, &jwt_config)
+            .await
+            .unwrap();
+
+        assert!(refresh(&db, &tokens, &phone, &jwt_config).await.is_err());
+        let laptop = refresh(&db, &tokens, &laptop, &jwt_config)
+            .await
+            .unwrap()
+            .refresh_token;
+
+        AuthService::revoke_all_refresh_tokens(&tokens, user_id)
+            .await
+            .unwrap();
+
+        assert!(refresh(&db, &tokens, &laptop, &jwt_config).await.is_err());
+        assert!(refresh(&db, &tokens, &tablet, &jwt_config).await.is_err());
+    }
+
+    #[sqlx::test]
+    async fn test_list_and_revoke_sessions(db: PgPool) {
+        let email = format!("test_sessions_{}@example.com", Uuid::new_v4());
+        let user_id = create_test_user(&db, &email).await;
+        let jwt_config = test_jwt_config();
+        let tokens = PgTokenStore::new(db.clone());
+
+        let phone = login_on_device(&db, &tokens, &email, Some("phone"), &jwt_config).await;
+        login_on_device(&db, &tokens, &email, Some("laptop"), &jwt_config).await;
+        let phone = refresh(&db, &tokens, &phone, &jwt_config)
+            .await
+            .unwrap()
+            .refresh_token;
+
+        let sessions = AuthService::list_sessions(&tokens, user_id).await.unwrap();
+        assert_eq!(sessions.len(), 2);
+        // The refresh makes the phone the most recently seen
+        assert_eq!(sessions[0].device_id, "phone");
+        assert_eq!(
+            sessions[0].user_agent.as_deref(),
+            Some("refreshing browser")
+        );
+        assert_eq!(sessions[0].ip_address.as_deref(), Some("198.51.100.4"));
+        assert_eq!(sessions[1].device_id, "laptop");
+        assert_eq!(sessions[1].user_agent.as_deref(), Some("laptop browser"));
+
+        // Another user cannot revoke the session
+        let err = AuthService::revoke_session(&tokens, Uuid::new_v4(), sessions[0].id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        AuthService::revoke_session(&tokens, user_id, sessions[0].id)
+            .await
+            .unwrap();
+        assert!(refresh(&db, &tokens, &phone, &jwt_config).await.is_err());
+
+        let sessions = AuthService::list_sessions(&tokens, user_id).await.unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].device_id, "laptop");
+    }
+
+    #[sqlx::test]
+    async fn test_logout_rejects_another_users_token(db: PgPool) {
+        let email = format!("test_logout_other_{}@example.com", Uuid::new_v4());
+        create_test_user(&db, &email).await;
+        let jwt_config = test_jwt_config();
+        let tokens = PgTokenStore::new(db.clone());
+
+        let token = login_on_device(&db, &tokens, &email, None, &jwt_config).await;
+        let dto = LogoutRequest {
+            refresh_token: token.clone(),
+        };
+        let result = AuthService::logout(&tokens, Uuid::new_v4(), dto, &jwt_config).await;
+
+        assert_eq!(result.unwrap_err().status, StatusCode::FORBIDDEN);
+        assert!(refresh(&db, &tokens, &token, &jwt_config).await.is_ok());
+    }
+
     #[sqlx::test]
     async fn test_user_query_includes_all_required_fields(db: PgPool) {
         let email = format!("test_fields_{}@example.com", Uuid::new_v4());
@@ -0,0 +1,405 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::header,
+    response::IntoResponse,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::{FeeStructureId, InvoiceId, UserId};
+
+use crate::middleware::auth::{
+    RequireBillingCollect, RequireBillingCreate, RequireBillingDelete, RequireBillingExport,
+    RequireBillingRead,
+};
+use crate::modules::billing::model::{
+    ArrearsExportParams, BalanceQueryParams, CreateFeeStructureDto, FeePayment, FeeStructureDetail,
+    FeeStructureFilterParams, GenerateInvoicesResponse, Invoice, InvoiceDetail,
+    InvoiceFilterParams, PaginatedFeeStructuresResponse, PaginatedInvoicesResponse,
+    RecordPaymentDto, SchoolBalance, StudentBalance,
+};
+use crate::modules::billing::service::BillingService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Create a fee structure for a level and term
+#[utoipa::path(
+    post,
+    path = "/api/billing/fee-structures",
+    summary = "Create fee structure",
+    request_body = CreateFeeStructureDto,
+    responses(
+        Created<FeeStructureDetail>,
+        (status = 400, description = "Invalid input, duplicate name, unknown level or term, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires billing:create permission")
+    ),
+    tag = "Billing",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_fee_structure(
+    State(state): State<AppState>,
+    RequireBillingCreate(auth_user): RequireBillingCreate,
+    ValidatedJson(dto): ValidatedJson<CreateFeeStructureDto>,
+) -> Result<Created<FeeStructureDetail>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+    let structure = BillingService::create_fee_structure(&state.db, school_id, dto).await?;
+
+    Ok(Created(structure))
+}
+
+/// List fee structures for a school
+#[utoipa::path(
+    get,
+    path = "/api/billing/fee-structures",
+    summary = "List fee structures",
+    params(FeeStructureFilterParams),
+    responses(
+        (status = 200, description = "List of fee structures", body = PaginatedFeeStructuresResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires billing:read permission")
+    ),
+    tag = "Billing",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_fee_structures(
+    State(state): State<AppState>,
+    RequireBillingRead(auth_user): RequireBillingRead,
+    Query(filters): Query<FeeStructureFilterParams>,
+) -> Result<Json<PaginatedFeeStructuresResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let structures = BillingService::get_fee_structures(&state.db, school_id, filters).await?;
+
+    Ok(Json(structures))
+}
+
+/// Get a fee structure with its items
+#[utoipa::path(
+    get,
+    path = "/api/billing/fee-structures/{id}",
+    summary = "Get fee structure",
+    params(
+        ("id" = Uuid, Path, description = "Fee structure ID")
+    ),
+    responses(
+        (status = 200, description = "Fee structure with items", body = FeeStructureDetail),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires billing:read permission"),
+        (status = 404, description = "Fee structure not found")
+    ),
+    tag = "Billing",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_fee_structure(
+    State(state): State<AppState>,
+    RequireBillingRead(auth_user): RequireBillingRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<FeeStructureDetail>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let structure =
+        BillingService::get_fee_structure(&state.db, FeeStructureId::from(id), school_id).await?;
+
+    Ok(Json(structure))
+}
+
+/// Delete a fee structure that has not been invoiced
+#[utoipa::path(
+    delete,
+    path = "/api/billing/fee-structures/{id}",
+    summary = "Delete fee structure",
+    params(
+        ("id" = Uuid, Path, description = "Fee structure ID")
+    ),
+    responses(
+        NoContent,
+        (status = 400, description = "Fee structure has invoices"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires billing:delete permission"),
+        (status = 404, description = "Fee structure not found")
+    ),
+    tag = "Billing",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_fee_structure(
+    State(state): State<AppState>,
+    RequireBillingDelete(auth_user): RequireBillingDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    BillingService::delete_fee_structure(&state.db, FeeStructureId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Generate invoices from a fee structure for every student of its level
+#[utoipa::path(
+    post,
+    path = "/api/billing/fee-structures/{id}/invoices",
+    summary = "Generate invoices",
+    params(
+        ("id" = Uuid, Path, description = "Fee structure ID")
+    ),
+    responses(
+        (status = 200, description = "Invoices issued to students not yet invoiced", body = GenerateInvoicesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires billing:create permission"),
+        (status = 404, description = "Fee structure not found")
+    ),
+    tag = "Billing",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn generate_invoices(
+    State(state): State<AppState>,
+    RequireBillingCreate(auth_user): RequireBillingCreate,
+    Path(id): Path<Uuid>,
+) -> Result<Json<GenerateInvoicesResponse>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let result =
+        BillingService::generate_invoices(&state.db, FeeStructureId::from(id), school_id).await?;
+
+    Ok(Json(result))
+}
+
+/// List invoices for a school
+#[utoipa::path(
+    get,
+    path = "/api/billing/invoices",
+    summary = "List invoices",
+    params(InvoiceFilterParams),
+    responses(
+        (status = 200, description = "List of invoices, newest first", body = PaginatedInvoicesResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires billing:read permission")
+    ),
+    tag = "Billing",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_invoices(
+    State(state): State<AppState>,
+    RequireBillingRead(auth_user): RequireBillingRead,
+    Query(filters): Query<InvoiceFilterParams>,
+) -> Result<Json<PaginatedInvoicesResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let invoices = BillingService::get_invoices(&state.db, school_id, filters).await?;
+
+    Ok(Json(invoices))
+}
+
+/// Get an invoice with its lines and payments
+#[utoipa::path(
+    get,
+    path = "/api/billing/invoices/{id}",
+    summary = "Get invoice",
+    params(
+        ("id" = Uuid, Path, description = "Invoice ID")
+    ),
+    responses(
+        (status = 200, description = "Invoice with lines and payments", body = InvoiceDetail),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires billing:read permission"),
+        (status = 404, description = "Invoice not found")
+    ),
+    tag = "Billing",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_invoice(
+    State(state): State<AppState>,
+    RequireBillingRead(auth_user): RequireBillingRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<InvoiceDetail>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let invoice = BillingService::get_invoice(&state.db, InvoiceId::from(id), school_id).await?;
+
+    Ok(Json(invoice))
+}
+
+/// Void an invoice with no payments
+#[utoipa::path(
+    post,
+    path = "/api/billing/invoices/{id}/void",
+    summary = "Void invoice",
+    params(
+        ("id" = Uuid, Path, description = "Invoice ID")
+    ),
+    responses(
+        (status = 200, description = "Invoice voided", body = Invoice),
+        (status = 400, description = "Invoice already void or has payments"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires billing:delete permission"),
+        (status = 404, description = "Invoice not found")
+    ),
+    tag = "Billing",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn void_invoice(
+    State(state): State<AppState>,
+    RequireBillingDelete(auth_user): RequireBillingDelete,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Invoice>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let invoice = BillingService::void_invoice(&state.db, InvoiceId::from(id), school_id).await?;
+
+    Ok(Json(invoice))
+}
+
+/// Record a payment against an invoice
+#[utoipa::path(
+    post,
+    path = "/api/billing/invoices/{id}/payments",
+    summary = "Record payment",
+    params(
+        ("id" = Uuid, Path, description = "Invoice ID")
+    ),
+    request_body = RecordPaymentDto,
+    responses(
+        Created<FeePayment>,
+        (status = 400, description = "Invoice void, payment exceeds the balance, or dated in the future"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires billing:collect permission"),
+        (status = 404, description = "Invoice not found")
+    ),
+    tag = "Billing",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn record_payment(
+    State(state): State<AppState>,
+    RequireBillingCollect(auth_user): RequireBillingCollect,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<RecordPaymentDto>,
+) -> Result<Created<FeePayment>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let recorded_by = auth_user.user_id()?;
+    let payment = BillingService::record_payment(
+        &state.db,
+        state.cache.as_ref(),
+        InvoiceId::from(id),
+        school_id,
+        recorded_by,
+        dto,
+    )
+    .await?;
+
+    Ok(Created(payment))
+}
+
+/// Get a student's outstanding balance
+#[utoipa::path(
+    get,
+    path = "/api/billing/students/{student_id}/balance",
+    summary = "Student balance",
+    params(
+        ("student_id" = Uuid, Path, description = "Student ID"),
+        BalanceQueryParams
+    ),
+    responses(
+        (status = 200, description = "Totals and open invoices", body = StudentBalance),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires billing:read permission"),
+        (status = 404, description = "Student not found")
+    ),
+    tag = "Billing",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_student_balance(
+    State(state): State<AppState>,
+    RequireBillingRead(auth_user): RequireBillingRead,
+    Path(student_id): Path<Uuid>,
+    Query(params): Query<BalanceQueryParams>,
+) -> Result<Json<StudentBalance>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let balance = BillingService::get_student_balance(
+        &state.db,
+        UserId::from(student_id),
+        school_id,
+        params.term_id,
+    )
+    .await?;
+
+    Ok(Json(balance))
+}
+
+/// Get a school's billing totals
+#[utoipa::path(
+    get,
+    path = "/api/billing/balance",
+    summary = "School balance",
+    params(BalanceQueryParams),
+    responses(
+        (status = 200, description = "Invoiced, paid, and outstanding totals", body = SchoolBalance),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires billing:read permission")
+    ),
+    tag = "Billing",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_school_balance(
+    State(state): State<AppState>,
+    RequireBillingRead(auth_user): RequireBillingRead,
+    Query(params): Query<BalanceQueryParams>,
+) -> Result<Json<SchoolBalance>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let balance = BillingService::get_school_balance(&state.db, school_id, params.term_id).await?;
+
+    Ok(Json(balance))
+}
+
+/// Export students in arrears as CSV
+#[utoipa::path(
+    get,
+    path = "/api/billing/arrears/export",
+    summary = "Export arrears",
+    params(ArrearsExportParams),
+    responses(
+        (status = 200, description = "CSV of students who owe fees, largest balance first", content_type = "text/csv", body = String),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires billing:export permission")
+    ),
+    tag = "Billing",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn export_arrears(
+    State(state): State<AppState>,
+    RequireBillingExport(auth_user): RequireBillingExport,
+    Query(params): Query<ArrearsExportParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let csv =
+        BillingService::export_arrears(&state.db, state.cache.as_ref(), school_id, params).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"fee-arrears.csv\"",
+            ),
+        ],
+        csv,
+    ))
+}
@@ -0,0 +1,11 @@
+//! Billing module.
+//!
+//! This module manages school fees: fee structures per level and term,
+//! invoice generation for the students of a level, manually recorded
+//! payments, outstanding balances per student and per school, and a CSV
+//! export of arrears.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
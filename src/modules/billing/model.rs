@@ -0,0 +1,7 @@
+//! Billing data models and DTOs.
+//!
+//! This module re-exports billing models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all billing models from the shared crate
+pub use chalkbyte_models::billing::*;
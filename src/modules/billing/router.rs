@@ -0,0 +1,38 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    create_fee_structure, delete_fee_structure, export_arrears, generate_invoices,
+    get_fee_structure, get_fee_structures, get_invoice, get_invoices, get_school_balance,
+    get_student_balance, record_payment, void_invoice,
+};
+
+/// Initialize the billing router
+/// Routes: POST /fee-structures, GET /fee-structures, GET /fee-structures/{id},
+/// DELETE /fee-structures/{id}, POST /fee-structures/{id}/invoices,
+/// GET /invoices, GET /invoices/{id}, POST /invoices/{id}/void,
+/// POST /invoices/{id}/payments, GET /students/{student_id}/balance,
+/// GET /balance, GET /arrears/export
+pub fn init_billing_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/fee-structures",
+            post(create_fee_structure).get(get_fee_structures),
+        )
+        .route(
+            "/fee-structures/{id}",
+            get(get_fee_structure).delete(delete_fee_structure),
+        )
+        .route("/fee-structures/{id}/invoices", post(generate_invoices))
+        .route("/invoices", get(get_invoices))
+        .route("/invoices/{id}", get(get_invoice))
+        .route("/invoices/{id}/void", post(void_invoice))
+        .route("/invoices/{id}/payments", post(record_payment))
+        .route("/students/{student_id}/balance", get(get_student_balance))
+        .route("/balance", get(get_school_balance))
+        .route("/arrears/export", get(export_arrears))
+}
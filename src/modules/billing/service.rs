@@ -0,0 +1,1262 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+use sqlx::{PgPool, Postgres, Transaction};
+use tracing::instrument;
+
+use chalkbyte_cache::RedisCache;
+use chalkbyte_core::{AppError, ErrorCode, PaginationMeta};
+use chalkbyte_models::ids::{FeeStructureId, InvoiceId, LevelId, SchoolId, TermId, UserId};
+
+use crate::modules::billing::model::{
+    ArrearsExportParams, ArrearsRow, CreateFeeStructureDto, FeeItem, FeePayment, FeeStructure,
+    FeeStructureDetail, FeeStructureFilterParams, GenerateInvoicesResponse, Invoice, InvoiceDetail,
+    InvoiceFilterParams, InvoiceStatus, PaginatedFeeStructuresResponse, PaginatedInvoicesResponse,
+    RecordPaymentDto, SchoolBalance, StudentBalance, UninvoicedCharge,
+};
+use crate::modules::schools::service::SchoolService;
+use crate::modules::users::model::system_roles;
+
+const FEE_STRUCTURE_COLUMNS: &str =
+    "id, school_id, level_id, term_id, name, due_date, created_at, updated_at";
+
+const INVOICE_COLUMNS: &str = "id, school_id, student_id, fee_structure_id, term_id, amount, \
+     amount_paid, CASE WHEN status = 'void' THEN 0 ELSE amount - amount_paid END AS balance, \
+     status, due_date, issued_at, voided_at, updated_at";
+
+const PAYMENT_COLUMNS: &str =
+    "id, invoice_id, amount, method, reference, paid_on, recorded_by, created_at";
+
+/// Boarding fees, transport charges, and students' library fines not yet on
+/// an invoice, one row per charge with the term it falls in.
+///
+/// Boarding fees fall in the first term of the allocation's session and
+/// library fines in the term containing the day they were charged.
+fn uninvoiced_charges() -> String {
+    format!(
+        r#"SELECT r.school_id, c.student_id, c.term_id, 'transport' AS source,
+                  LEFT('Transport: ' || r.name, 100) AS description, c.amount
+           FROM transport_fee_charges c
+           JOIN transport_routes r ON r.id = c.route_id
+           WHERE c.invoice_id IS NULL
+           UNION ALL
+           SELECT h.school_id, a.student_id, t.id, 'boarding',
+                  LEFT('Boarding: ' || f.name, 100), f.amount
+           FROM room_allocations a
+           JOIN hostel_rooms hr ON hr.id = a.room_id
+           JOIN hostels h ON h.id = hr.hostel_id
+           JOIN boarding_fee_lines f
+               ON f.hostel_id = h.id AND f.academic_session_id = a.academic_session_id
+           LEFT JOIN LATERAL (
+               SELECT id FROM terms
+               WHERE academic_session_id = a.academic_session_id
+               ORDER BY sequence
+               LIMIT 1
+           ) t ON true
+           WHERE a.invoice_id IS NULL
+           UNION ALL
+           SELECT f.school_id, f.borrower_id, t.id, 'library_fine',
+                  LEFT('Library fine: ' || b.title, 100), f.amount
+           FROM library_fine_charges f
+           JOIN library_loans l ON l.id = f.loan_id
+           JOIN library_copies cp ON cp.id = l.copy_id
+           JOIN library_books b ON b.id = cp.book_id
+           LEFT JOIN LATERAL (
+               SELECT t.id FROM terms t
+               JOIN academic_sessions s ON s.id = t.academic_session_id
+               WHERE s.school_id = f.school_id
+                 AND f.created_at::date BETWEEN t.start_date AND t.end_date
+               ORDER BY t.start_date
+               LIMIT 1
+           ) t ON true
+           WHERE f.invoice_id IS NULL AND f.amount > 0
+             AND EXISTS (
+                 SELECT 1 FROM user_roles ur
+                 WHERE ur.user_id = f.borrower_id AND ur.role_id = '{student}'
+             )"#,
+        student = system_roles::STUDENT
+    )
+}
+
+/// IDs of students with an open invoice balance or an uninvoiced charge.
+pub(crate) fn students_owing() -> String {
+    format!(
+        "SELECT student_id FROM invoices WHERE status IN ('unpaid', 'partially_paid')
+         UNION
+         SELECT c.student_id FROM ({}) c",
+        uninvoiced_charges()
+    )
+}
+
+fn unique_violation_as(e: sqlx::Error, message: &'static str) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e
+        && db_err.is_unique_violation()
+    {
+        return AppError::bad_request(anyhow::anyhow!(message));
+    }
+    AppError::from(e)
+}
+
+pub struct BillingService;
+
+impl BillingService {
+    /// Fetch a fee structure, optionally restricted to a school.
+    async fn find_structure(
+        db: &PgPool,
+        structure_id: FeeStructureId,
+        school_id: Option<SchoolId>,
+    ) -> Result<FeeStructure, AppError> {
+        sqlx::query_as::<_, FeeStructure>(&format!(
+            "SELECT {FEE_STRUCTURE_COLUMNS} FROM fee_structures
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(structure_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Fee structure not found")))
+    }
+
+    /// Fetch an invoice, optionally restricted to a school.
+    async fn find_invoice(
+        db: &PgPool,
+        invoice_id: InvoiceId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Invoice, AppError> {
+        sqlx::query_as::<_, Invoice>(&format!(
+            "SELECT {INVOICE_COLUMNS} FROM invoices
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(invoice_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Invoice not found")))
+    }
+
+    /// Ensure a level and term both belong to a school.
+    async fn validate_level_and_term(
+        db: &PgPool,
+        school_id: SchoolId,
+        level_id: LevelId,
+        term_id: TermId,
+    ) -> Result<(), AppError> {
+        let (level_found, term_found) = sqlx::query_as::<_, (bool, bool)>(
+            r#"SELECT
+                   EXISTS(SELECT 1 FROM levels WHERE id = $1 AND school_id = $3),
+                   EXISTS(
+                       SELECT 1 FROM terms t
+                       JOIN academic_sessions s ON s.id = t.academic_session_id
+                       WHERE t.id = $2 AND s.school_id = $3
+                   )"#,
+        )
+        .bind(level_id)
+        .bind(term_id)
+        .bind(school_id)
+        .fetch_one(db)
+        .await?;
+
+        if !level_found {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Level not found in this school"
+            )));
+        }
+        if !term_found {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Term not found in this school"
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn structure_items(
+        db: &PgPool,
+        structure_id: FeeStructureId,
+    ) -> Result<Vec<FeeItem>, AppError> {
+        let items = sqlx::query_as::<_, FeeItem>(
+            "SELECT name, amount FROM fee_structure_items
+             WHERE fee_structure_id = $1
+             ORDER BY position",
+        )
+        .bind(structure_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(items)
+    }
+
+    /// Create a fee structure with its items.
+    #[instrument(skip(db))]
+    pub async fn create_fee_structure(
+        db: &PgPool,
+        school_id: SchoolId,
+        dto: CreateFeeStructureDto,
+    ) -> Result<FeeStructureDetail, AppError> {
+        let mut names = HashSet::new();
+        if !dto
+            .items
+            .iter()
+            .all(|item| names.insert(item.name.as_str()))
+        {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Fee item names must be unique within a structure"
+            )));
+        }
+        Self::validate_level_and_term(db, school_id, dto.level_id, dto.term_id).await?;
+
+        let mut tx = db.begin().await?;
+
+        let structure = sqlx::query_as::<_, FeeStructure>(&format!(
+            "INSERT INTO fee_structures (school_id, level_id, term_id, name, due_date)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {FEE_STRUCTURE_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(dto.level_id)
+        .bind(dto.term_id)
+        .bind(&dto.name)
+        .bind(dto.due_date)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            unique_violation_as(
+                e,
+                "A fee structure with this name already exists for this level and term",
+            )
+        })?;
+
+        let names: Vec<&str> = dto.items.iter().map(|item| item.name.as_str()).collect();
+        let amounts: Vec<i64> = dto.items.iter().map(|item| item.amount).collect();
+        sqlx::query(
+            r#"INSERT INTO fee_structure_items (fee_structure_id, name, amount, position)
+               SELECT $1, item.name, item.amount, item.position
+               FROM UNNEST($2::text[], $3::bigint[]) WITH ORDINALITY AS item(name, amount, position)"#,
+        )
+        .bind(structure.id)
+        .bind(&names)
+        .bind(&amounts)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        let total = dto.items.iter().map(|item| item.amount).sum();
+
+        Ok(FeeStructureDetail {
+            structure,
+            items: dto.items,
+            total,
+        })
+    }
+
+    /// Get paginated fee structures for a school.
+    #[instrument(skip(db))]
+    pub async fn get_fee_structures(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: FeeStructureFilterParams,
+    ) -> Result<PaginatedFeeStructuresResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+
+        let where_clause = r#"WHERE school_id = $1
+              AND ($2::uuid IS NULL OR level_id = $2)
+              AND ($3::uuid IS NULL OR term_id = $3)"#;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM fee_structures {where_clause}"
+        ))
+        .bind(school_id)
+        .bind(filters.level_id)
+        .bind(filters.term_id)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, FeeStructure>(&format!(
+            "SELECT {FEE_STRUCTURE_COLUMNS} FROM fee_structures {where_clause}
+             ORDER BY created_at DESC
+             LIMIT $4 OFFSET $5"
+        ))
+        .bind(school_id)
+        .bind(filters.level_id)
+        .bind(filters.term_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedFeeStructuresResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Get a fee structure with its items.
+    ///
+    /// When `school_id` is provided, the structure must belong to that school.
+    #[instrument(skip(db))]
+    pub async fn get_fee_structure(
+        db: &PgPool,
+        structure_id: FeeStructureId,
+        school_id: Option<SchoolId>,
+    ) -> Result<FeeStructureDetail, AppError> {
+        let structure = Self::find_structure(db, structure_id, school_id).await?;
+        let items = Self::structure_items(db, structure_id).await?;
+        let total = items.iter().map(|item| item.amount).sum();
+
+        Ok(FeeStructureDetail {
+            structure,
+            items,
+            total,
+        })
+    }
+
+    /// Delete a fee structure that has not been invoiced.
+    #[instrument(skip(db))]
+    pub async fn delete_fee_structure(
+        db: &PgPool,
+        structure_id: FeeStructureId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        Self::find_structure(db, structure_id, school_id).await?;
+
+        let result = sqlx::query(
+            r#"DELETE FROM fee_structures f
+               WHERE f.id = $1
+                 AND NOT EXISTS (SELECT 1 FROM invoices i WHERE i.fee_structure_id = f.id)"#,
+        )
+        .bind(structure_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Fee structure has been invoiced and cannot be deleted"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Issue an invoice from a fee structure to every student of its level.
+    ///
+    /// Each invoice also bills the student's uninvoiced boarding fees,
+    /// transport charges, and library fines from this term or earlier as
+    /// extra lines. Students who already have an invoice from the structure
+    /// are skipped, so this can safely be re-run after late enrollments.
+    #[instrument(skip(db))]
+    pub async fn generate_invoices(
+        db: &PgPool,
+        structure_id: FeeStructureId,
+        school_id: Option<SchoolId>,
+    ) -> Result<GenerateInvoicesResponse, AppError> {
+        let structure = Self::find_structure(db, structure_id, school_id).await?;
+        let items = Self::structure_items(db, structure_id).await?;
+        let total: i64 = items.iter().map(|item| item.amount).sum();
+
+        let mut tx = db.begin().await?;
+
+        let students = sqlx::query_scalar::<_, i64>(
+            r#"SELECT COUNT(DISTINCT u.id) FROM users u
+               JOIN user_roles ur ON ur.user_id = u.id
+               WHERE u.school_id = $1 AND u.level_id = $2 AND ur.role_id = $3"#,
+        )
+        .bind(structure.school_id)
+        .bind(structure.level_id)
+        .bind(system_roles::STUDENT)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let invoice_ids = sqlx::query_scalar::<_, InvoiceId>(
+            r#"INSERT INTO invoices
+                   (school_id, student_id, fee_structure_id, term_id, amount, status, due_date)
+               SELECT $1, u.id, $3, $4, $5, $6, $7
+               FROM users u
+               WHERE u.school_id = $1 AND u.level_id = $2
+                 AND EXISTS (
+                     SELECT 1 FROM user_roles ur
+                     WHERE ur.user_id = u.id AND ur.role_id = $8
+                 )
+               ON CONFLICT (fee_structure_id, student_id) DO NOTHING
+               RETURNING id"#,
+        )
+        .bind(structure.school_id)
+        .bind(structure.level_id)
+        .bind(structure.id)
+        .bind(structure.term_id)
+        .bind(total)
+        .bind(InvoiceStatus::for_amounts(total, 0))
+        .bind(structure.due_date)
+        .bind(system_roles::STUDENT)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"INSERT INTO invoice_lines (invoice_id, name, amount, position)
+               SELECT i.id, item.name, item.amount, item.position
+               FROM UNNEST($1::uuid[]) AS i(id)
+               CROSS JOIN fee_structure_items item
+               WHERE item.fee_structure_id = $2"#,
+        )
+        .bind(&invoice_ids)
+        .bind(structure.id)
+        .execute(&mut *tx)
+        .await?;
+
+        Self::bill_ledger_charges(&mut tx, &invoice_ids).await?;
+
+        tx.commit().await?;
+
+        let created = invoice_ids.len() as i64;
+
+        Ok(GenerateInvoicesResponse {
+            fee_structure_id: structure.id,
+            created,
+            already_invoiced: students - created,
+        })
+    }
+
+    /// Add each new invoice's student's uninvoiced charges to it as lines
+    /// and raise its amount to match.
+    ///
+    /// Each charge is claimed by setting its `invoice_id` before its line is
+    /// written, so a charge is billed on one invoice only even when invoices
+    /// are generated concurrently.
+    async fn bill_ledger_charges(
+        tx: &mut Transaction<'_, Postgres>,
+        invoice_ids: &[InvoiceId],
+    ) -> Result<(), AppError> {
+        for claim in [
+            r#"WITH billed AS (
+                   UPDATE transport_fee_charges c SET invoice_id = i.id
+                   FROM invoices i, terms it, terms ct, transport_routes r
+                   WHERE i.id = ANY($1) AND it.id = i.term_id
+                     AND c.student_id = i.student_id AND c.invoice_id IS NULL
+                     AND ct.id = c.term_id AND ct.start_date <= it.start_date
+                     AND r.id = c.route_id
+                   RETURNING c.invoice_id, LEFT('Transport: ' || r.name, 100) AS name, c.amount
+               )"#,
+            r#"WITH claimed AS (
+                   UPDATE room_allocations a SET invoice_id = i.id
+                   FROM invoices i, terms it, hostel_rooms hr
+                   WHERE i.id = ANY($1) AND it.id = i.term_id
+                     AND a.student_id = i.student_id AND a.invoice_id IS NULL
+                     AND hr.id = a.room_id
+                     AND EXISTS (
+                         SELECT 1 FROM terms st
+                         WHERE st.academic_session_id = a.academic_session_id
+                           AND st.start_date <= it.start_date
+                     )
+                     AND EXISTS (
+                         SELECT 1 FROM boarding_fee_lines f
+                         WHERE f.hostel_id = hr.hostel_id
+                           AND f.academic_session_id = a.academic_session_id
+                     )
+                   RETURNING a.invoice_id, hr.hostel_id, a.academic_session_id
+               ),
+               billed AS (
+                   SELECT c.invoice_id, LEFT('Boarding: ' || f.name, 100) AS name, f.amount
+                   FROM claimed c
+                   JOIN boarding_fee_lines f
+                       ON f.hostel_id = c.hostel_id
+                      AND f.academic_session_id = c.academic_session_id
+               )"#,
+            r#"WITH billed AS (
+                   UPDATE library_fine_charges f SET invoice_id = i.id
+                   FROM invoices i, library_loans l, library_copies cp, library_books b
+                   WHERE i.id = ANY($1)
+                     AND f.borrower_id = i.student_id AND f.invoice_id IS NULL
+                     AND f.amount > 0
+                     AND l.id = f.loan_id AND cp.id = l.copy_id AND b.id = cp.book_id
+                   RETURNING f.invoice_id, LEFT('Library fine: ' || b.title, 100) AS name,
+                             f.amount
+               )"#,
+        ] {
+            sqlx::query(&format!(
+                r#"{claim}
+                   INSERT INTO invoice_lines (invoice_id, name, amount, position)
+                   SELECT b.invoice_id, b.name, b.amount,
+                          (SELECT COALESCE(MAX(position), -1) FROM invoice_lines
+                           WHERE invoice_id = b.invoice_id)
+                          + ROW_NUMBER() OVER (PARTITION BY b.invoice_id ORDER BY b.name)
+                   FROM billed b"#
+            ))
+            .bind(invoice_ids)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        sqlx::query(
+            r#"UPDATE invoices i
+               SET amount = l.total,
+                   status = CASE WHEN l.total > 0 THEN 'unpaid' ELSE 'paid' END
+               FROM (
+                   SELECT invoice_id, SUM(amount)::bigint AS total FROM invoice_lines
+                   WHERE invoice_id = ANY($1)
+                   GROUP BY invoice_id
+               ) l
+               WHERE i.id = l.invoice_id AND i.amount <> l.total"#,
+        )
+        .bind(invoice_ids)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get paginated invoices for a school, newest first.
+    #[instrument(skip(db))]
+    pub async fn get_invoices(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: InvoiceFilterParams,
+    ) -> Result<PaginatedInvoicesResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+
+        let where_clause = r#"WHERE school_id = $1
+              AND ($2::uuid IS NULL OR student_id = $2)
+              AND ($3::uuid IS NULL OR term_id = $3)
+              AND ($4::uuid IS NULL OR fee_structure_id = $4)
+              AND ($5::text IS NULL OR status = $5)"#;
+
+        let total =
+            sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM invoices {where_clause}"))
+                .bind(school_id)
+                .bind(filters.student_id)
+                .bind(filters.term_id)
+                .bind(filters.fee_structure_id)
+                .bind(filters.status)
+                .fetch_one(db)
+                .await?;
+
+        let data = sqlx::query_as::<_, Invoice>(&format!(
+            "SELECT {INVOICE_COLUMNS} FROM invoices {where_clause}
+             ORDER BY issued_at DESC, id
+             LIMIT $6 OFFSET $7"
+        ))
+        .bind(school_id)
+        .bind(filters.student_id)
+        .bind(filters.term_id)
+        .bind(filters.fee_structure_id)
+        .bind(filters.status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedInvoicesResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Get an invoice with its lines and payments.
+    ///
+    /// When `school_id` is provided, the invoice must belong to that school.
+    #[instrument(skip(db))]
+    pub async fn get_invoice(
+        db: &PgPool,
+        invoice_id: InvoiceId,
+        school_id: Option<SchoolId>,
+    ) -> Result<InvoiceDetail, AppError> {
+        let invoice = Self::find_invoice(db, invoice_id, school_id).await?;
+
+        let lines = sqlx::query_as::<_, FeeItem>(
+            "SELECT name, amount FROM invoice_lines WHERE invoice_id = $1 ORDER BY position",
+        )
+        .bind(invoice_id)
+        .fetch_all(db)
+        .await?;
+
+        let payments = sqlx::query_as::<_, FeePayment>(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM fee_payments
+             WHERE invoice_id = $1
+             ORDER BY paid_on, created_at"
+        ))
+        .bind(invoice_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(InvoiceDetail {
+            invoice,
+            lines,
+            payments,
+        })
+    }
+
+    /// Void an invoice that has no payments, so it is no longer owed.
+    ///
+    /// Boarding fees, transport charges, and library fines it billed become
+    /// uninvoiced again and go onto the student's next invoice.
+    #[instrument(skip(db))]
+    pub async fn void_invoice(
+        db: &PgPool,
+        invoice_id: InvoiceId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Invoice, AppError> {
+        let invoice = Self::find_invoice(db, invoice_id, school_id).await?;
+
+        let mut tx = db.begin().await?;
+
+        let voided = sqlx::query_as::<_, Invoice>(&format!(
+            "UPDATE invoices
+             SET status = 'void', voided_at = NOW()
+             WHERE id = $1 AND status <> 'void' AND amount_paid = 0
+             RETURNING {INVOICE_COLUMNS}"
+        ))
+        .bind(invoice.id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            if invoice.status == InvoiceStatus::Void {
+                AppError::bad_request(anyhow::anyhow!("Invoice is already void"))
+            } else {
+                AppError::bad_request(anyhow::anyhow!(
+                    "Invoices with payments recorded cannot be voided"
+                ))
+            }
+        })?;
+
+        for ledger in [
+            "transport_fee_charges",
+            "room_allocations",
+            "library_fine_charges",
+        ] {
+            sqlx::query(&format!(
+                "UPDATE {ledger} SET invoice_id = NULL WHERE invoice_id = $1"
+            ))
+            .bind(invoice.id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(voided)
+    }
+
+    /// Record a payment against an invoice.
+    ///
+    /// The invoice row is locked while the payment is checked against its
+    /// balance, so concurrent payments cannot overpay it. Payments default to
+    /// today in the school's timezone and cannot be dated in the future.
+    #[instrument(skip(db, cache))]
+    pub async fn record_payment(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        invoice_id: InvoiceId,
+        school_id: Option<SchoolId>,
+        recorded_by: UserId,
+        dto: RecordPaymentDto,
+    ) -> Result<FeePayment, AppError> {
+        let invoice = Self::find_invoice(db, invoice_id, school_id).await?;
+        let today = SchoolService::get_settings(db, cache, invoice.school_id)
+            .await?
+            .today();
+        let paid_on = dto.paid_on.unwrap_or(today);
+        if paid_on > today {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Payments cannot be dated in the future"
+            )));
+        }
+
+        let mut tx = db.begin().await?;
+
+        let (amount, amount_paid, status) = sqlx::query_as::<_, (i64, i64, InvoiceStatus)>(
+            "SELECT amount, amount_paid, status FROM invoices WHERE id = $1 FOR UPDATE",
+        )
+        .bind(invoice.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if status == InvoiceStatus::Void {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Payments cannot be recorded against a void invoice"
+            )));
+        }
+        let balance = amount - amount_paid;
+        if dto.amount > balance {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Payment of {} exceeds the invoice balance of {}",
+                dto.amount,
+                balance
+            )));
+        }
+
+        let payment = sqlx::query_as::<_, FeePayment>(&format!(
+            "INSERT INTO fee_payments (invoice_id, amount, method, reference, paid_on, recorded_by)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             RETURNING {PAYMENT_COLUMNS}"
+        ))
+        .bind(invoice.id)
+        .bind(dto.amount)
+        .bind(dto.method)
+        .bind(&dto.reference)
+        .bind(paid_on)
+        .bind(recorded_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let amount_paid = amount_paid + dto.amount;
+        sqlx::query("UPDATE invoices SET amount_paid = $2, status = $3 WHERE id = $1")
+            .bind(invoice.id)
+            .bind(amount_paid)
+            .bind(InvoiceStatus::for_amounts(amount, amount_paid))
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(payment)
+    }
+
+    /// Get what a student has been billed, has paid, and still owes.
+    #[instrument(skip(db))]
+    pub async fn get_student_balance(
+        db: &PgPool,
+        student_id: UserId,
+        school_id: Option<SchoolId>,
+        term_id: Option<TermId>,
+    ) -> Result<StudentBalance, AppError> {
+        let found = sqlx::query_scalar::<_, bool>(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM users
+                WHERE id = $1 AND school_id IS NOT NULL AND ($2::uuid IS NULL OR school_id = $2)
+            )"#,
+        )
+        .bind(student_id)
+        .bind(school_id)
+        .fetch_one(db)
+        .await?;
+
+        if !found {
+            return Err(AppError::from_code(ErrorCode::StudentNotFound));
+        }
+
+        let invoices = sqlx::query_as::<_, Invoice>(&format!(
+            "SELECT {INVOICE_COLUMNS} FROM invoices
+             WHERE student_id = $1 AND status <> 'void'
+               AND ($2::uuid IS NULL OR term_id = $2)
+             ORDER BY due_date NULLS LAST, issued_at"
+        ))
+        .bind(student_id)
+        .bind(term_id)
+        .fetch_all(db)
+        .await?;
+
+        let uninvoiced_charges = sqlx::query_as::<_, UninvoicedCharge>(&format!(
+            "SELECT c.source, c.description, c.amount, c.term_id
+             FROM ({}) c
+             WHERE c.student_id = $1 AND ($2::uuid IS NULL OR c.term_id = $2)
+             ORDER BY c.source, c.description",
+            uninvoiced_charges()
+        ))
+        .bind(student_id)
+        .bind(term_id)
+        .fetch_all(db)
+        .await?;
+
+        let total_invoiced = invoices.iter().map(|invoice| invoice.amount).sum();
+        let total_paid = invoices.iter().map(|invoice| invoice.amount_paid).sum();
+        let uninvoiced: i64 = uninvoiced_charges.iter().map(|charge| charge.amount).sum();
+        let open_invoices: Vec<Invoice> = invoices
+            .into_iter()
+            .filter(|invoice| invoice.balance > 0)
+            .collect();
+        let outstanding = open_invoices
+            .iter()
+            .map(|invoice| invoice.balance)
+            .sum::<i64>()
+            + uninvoiced;
+
+        Ok(StudentBalance {
+            student_id,
+            term_id,
+            total_invoiced,
+            total_paid,
+            uninvoiced,
+            outstanding,
+            open_invoices,
+            uninvoiced_charges,
+        })
+    }
+
+    /// Get a school's billing totals.
+    #[instrument(skip(db))]
+    pub async fn get_school_balance(
+        db: &PgPool,
+        school_id: SchoolId,
+        term_id: Option<TermId>,
+    ) -> Result<SchoolBalance, AppError> {
+        let balance = sqlx::query_as::<_, SchoolBalance>(&format!(
+            r#"WITH live AS (
+                   SELECT student_id, amount, amount_paid FROM invoices
+                   WHERE school_id = $1 AND status <> 'void'
+                     AND ($2::uuid IS NULL OR term_id = $2)
+               ),
+               pending AS (
+                   SELECT c.student_id, c.amount FROM ({}) c
+                   WHERE c.school_id = $1 AND ($2::uuid IS NULL OR c.term_id = $2)
+               )
+               SELECT $1::uuid AS school_id,
+                      $2::uuid AS term_id,
+                      (SELECT COALESCE(SUM(amount), 0) FROM live)::bigint AS total_invoiced,
+                      (SELECT COALESCE(SUM(amount_paid), 0) FROM live)::bigint AS total_paid,
+                      (SELECT COALESCE(SUM(amount), 0) FROM pending)::bigint AS uninvoiced,
+                      ((SELECT COALESCE(SUM(amount - amount_paid), 0) FROM live)
+                       + (SELECT COALESCE(SUM(amount), 0) FROM pending))::bigint AS outstanding,
+                      (SELECT COUNT(*) FROM live WHERE amount > amount_paid) AS open_invoices,
+                      (SELECT COUNT(*) FROM (
+                           SELECT student_id FROM live WHERE amount > amount_paid
+                           UNION
+                           SELECT student_id FROM pending
+                       ) owing) AS students_in_arrears"#,
+            uninvoiced_charges()
+        ))
+        .bind(school_id)
+        .bind(term_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(balance)
+    }
+
+    /// Build a CSV of students who owe fees, largest balance first.
+    ///
+    /// Overdue invoices are those due before today in the school's timezone.
+    /// Uninvoiced charges are not due yet, so they are left out of an
+    /// overdue-only export.
+    #[instrument(skip(db, cache))]
+    pub async fn export_arrears(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        params: ArrearsExportParams,
+    ) -> Result<String, AppError> {
+        let overdue_before: Option<NaiveDate> = if params.overdue_only {
+            Some(
+                SchoolService::get_settings(db, cache, school_id)
+                    .await?
+                    .today(),
+            )
+        } else {
+            None
+        };
+
+        let rows = sqlx::query_as::<_, ArrearsRow>(&format!(
+            r#"WITH owed AS (
+                   SELECT i.student_id, 1 AS invoices, i.amount AS invoiced,
+                          i.amount_paid AS paid, 0::bigint AS uninvoiced, i.due_date
+                   FROM invoices i
+                   WHERE i.school_id = $1
+                     AND i.status IN ('unpaid', 'partially_paid')
+                     AND ($2::uuid IS NULL OR i.term_id = $2)
+                     AND ($4::date IS NULL OR i.due_date < $4)
+                   UNION ALL
+                   SELECT c.student_id, 0, 0::bigint, 0::bigint, c.amount, NULL::date
+                   FROM ({}) c
+                   WHERE c.school_id = $1
+                     AND ($2::uuid IS NULL OR c.term_id = $2)
+                     AND $4::date IS NULL
+               )
+               SELECT u.id AS student_id, u.first_name, u.last_name, l.name AS level,
+                      SUM(o.invoices)::bigint AS open_invoices,
+                      SUM(o.invoiced)::bigint AS total_invoiced,
+                      SUM(o.paid)::bigint AS total_paid,
+                      SUM(o.uninvoiced)::bigint AS uninvoiced,
+                      SUM(o.invoiced - o.paid + o.uninvoiced)::bigint AS outstanding,
+                      MIN(o.due_date) AS earliest_due_date
+               FROM owed o
+               JOIN users u ON u.id = o.student_id
+               LEFT JOIN levels l ON l.id = u.level_id
+               WHERE ($3::uuid IS NULL OR u.level_id = $3)
+               GROUP BY u.id, u.first_name, u.last_name, l.name
+               ORDER BY outstanding DESC, u.last_name, u.first_name"#,
+            uninvoiced_charges()
+        ))
+        .bind(school_id)
+        .bind(params.term_id)
+        .bind(params.level_id)
+        .bind(overdue_before)
+        .fetch_all(db)
+        .await?;
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for row in &rows {
+            writer.serialize(row)?;
+        }
+        let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+
+        Ok(String::from_utf8(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use chalkbyte_core::PaginationParams;
+    use chalkbyte_models::billing::{ChargeSource, PaymentMethod};
+    use uuid::Uuid;
+
+    struct Fixture {
+        school_id: SchoolId,
+        level_id: LevelId,
+        term_id: TermId,
+    }
+
+    async fn create_fixture(pool: &PgPool) -> Fixture {
+        let school_id: SchoolId = sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into();
+
+        let level_id = sqlx::query_scalar::<_, LevelId>(
+            "INSERT INTO levels (name, school_id) VALUES ('JSS 1', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let term_id = sqlx::query_scalar::<_, TermId>(
+            r#"WITH session AS (
+                   INSERT INTO academic_sessions (name, school_id, start_date, end_date, is_active)
+                   VALUES ('2025/2026', $1, $2, $3, true) RETURNING id
+               )
+               INSERT INTO terms (name, academic_session_id, start_date, end_date, sequence)
+               SELECT 'First Term', id, $2, $4, 1 FROM session
+               RETURNING id"#,
+        )
+        .bind(school_id)
+        .bind(NaiveDate::from_ymd_opt(2025, 9, 1).unwrap())
+        .bind(NaiveDate::from_ymd_opt(2026, 7, 31).unwrap())
+        .bind(NaiveDate::from_ymd_opt(2025, 12, 15).unwrap())
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        Fixture {
+            school_id,
+            level_id,
+            term_id,
+        }
+    }
+
+    async fn create_test_student(pool: &PgPool, fixture: &Fixture, last_name: &str) -> UserId {
+        let user_id = sqlx::query_scalar::<_, UserId>(
+            r#"INSERT INTO users (first_name, last_name, email, school_id, level_id)
+               VALUES ('Test', $1, $2, $3, $4) RETURNING id"#,
+        )
+        .bind(last_name)
+        .bind(format!("student-{}@example.com", Uuid::new_v4()))
+        .bind(fixture.school_id)
+        .bind(fixture.level_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(system_roles::STUDENT)
+            .execute(pool)
+            .await
+            .unwrap();
+
+        user_id
+    }
+
+    async fn create_structure(pool: &PgPool, fixture: &Fixture) -> FeeStructureDetail {
+        BillingService::create_fee_structure(
+            pool,
+            fixture.school_id,
+            CreateFeeStructureDto {
+                level_id: fixture.level_id,
+                term_id: fixture.term_id,
+                name: "First Term Fees".to_string(),
+                due_date: NaiveDate::from_ymd_opt(2025, 9, 30),
+                items: vec![
+                    FeeItem {
+                        name: "Tuition".to_string(),
+                        amount: 10_000_000,
+                    },
+                    FeeItem {
+                        name: "Books".to_string(),
+                        amount: 2_000_000,
+                    },
+                ],
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    fn payment(amount: i64) -> RecordPaymentDto {
+        RecordPaymentDto {
+            amount,
+            method: PaymentMethod::BankTransfer,
+            reference: Some("TRF-001".to_string()),
+            paid_on: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_generate_invoices_once_per_student(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let student = create_test_student(&pool, &fixture, "Adeyemi").await;
+        create_test_student(&pool, &fixture, "Bello").await;
+        let structure = create_structure(&pool, &fixture).await;
+        assert_eq!(structure.total, 12_000_000);
+
+        let generated = BillingService::generate_invoices(&pool, structure.structure.id, None)
+            .await
+            .unwrap();
+        assert_eq!(generated.created, 2);
+        assert_eq!(generated.already_invoiced, 0);
+
+        create_test_student(&pool, &fixture, "Chukwu").await;
+        let generated = BillingService::generate_invoices(&pool, structure.structure.id, None)
+            .await
+            .unwrap();
+        assert_eq!(generated.created, 1);
+        assert_eq!(generated.already_invoiced, 2);
+
+        let invoices = BillingService::get_invoices(
+            &pool,
+            fixture.school_id,
+            InvoiceFilterParams {
+                school_id: None,
+                student_id: Some(student),
+                term_id: None,
+                fee_structure_id: None,
+                status: None,
+                pagination: PaginationParams::default(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(invoices.meta.total, 1);
+
+        let detail = BillingService::get_invoice(&pool, invoices.data[0].id, None)
+            .await
+            .unwrap();
+        let lines: Vec<&str> = detail.lines.iter().map(|line| line.name.as_str()).collect();
+        assert_eq!(lines, ["Tuition", "Books"]);
+        assert_eq!(detail.invoice.balance, 12_000_000);
+
+        // Invoiced structures are kept for their invoices
+        let err = BillingService::delete_fee_structure(&pool, structure.structure.id, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_payments_settle_invoices_and_balances(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let payer = create_test_student(&pool, &fixture, "Adeyemi").await;
+        let debtor = create_test_student(&pool, &fixture, "Bello").await;
+        let structure = create_structure(&pool, &fixture).await;
+        BillingService::generate_invoices(&pool, structure.structure.id, None)
+            .await
+            .unwrap();
+
+        let balance = BillingService::get_student_balance(&pool, payer, None, None)
+            .await
+            .unwrap();
+        let invoice_id = balance.open_invoices[0].id;
+
+        BillingService::record_payment(&pool, None, invoice_id, None, payer, payment(5_000_000))
+            .await
+            .unwrap();
+        let detail = BillingService::get_invoice(&pool, invoice_id, None)
+            .await
+            .unwrap();
+        assert_eq!(detail.invoice.status, InvoiceStatus::PartiallyPaid);
+        assert_eq!(detail.invoice.balance, 7_000_000);
+        assert_eq!(detail.payments.len(), 1);
+
+        // Overpaying is refused and records nothing
+        let err = BillingService::record_payment(
+            &pool,
+            None,
+            invoice_id,
+            None,
+            payer,
+            payment(7_000_001),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        BillingService::record_payment(&pool, None, invoice_id, None, payer, payment(7_000_000))
+            .await
+            .unwrap();
+        let balance = BillingService::get_student_balance(&pool, payer, None, None)
+            .await
+            .unwrap();
+        assert_eq!(balance.total_paid, 12_000_000);
+        assert_eq!(balance.outstanding, 0);
+        assert!(balance.open_invoices.is_empty());
+
+        // Paid invoices cannot be voided; unpaid ones stop counting once void
+        let err = BillingService::void_invoice(&pool, invoice_id, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let school = BillingService::get_school_balance(&pool, fixture.school_id, None)
+            .await
+            .unwrap();
+        assert_eq!(school.total_invoiced, 24_000_000);
+        assert_eq!(school.outstanding, 12_000_000);
+        assert_eq!(school.students_in_arrears, 1);
+
+        let csv = BillingService::export_arrears(
+            &pool,
+            None,
+            fixture.school_id,
+            ArrearsExportParams {
+                school_id: None,
+                term_id: Some(fixture.term_id),
+                level_id: None,
+                overdue_only: true,
+            },
+        )
+        .await
+        .unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with(&format!(
+            "{debtor},Test,Bello,JSS 1,1,12000000,0,0,12000000"
+        )));
+
+        let debtor_invoice = BillingService::get_student_balance(&pool, debtor, None, None)
+            .await
+            .unwrap()
+            .open_invoices[0]
+            .id;
+        BillingService::void_invoice(&pool, debtor_invoice, None)
+            .await
+            .unwrap();
+        let school = BillingService::get_school_balance(&pool, fixture.school_id, None)
+            .await
+            .unwrap();
+        assert_eq!(school.total_invoiced, 12_000_000);
+        assert_eq!(school.outstanding, 0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_library_fine_counts_until_billed_on_next_invoice(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let student = create_test_student(&pool, &fixture, "Adeyemi").await;
+        let structure = create_structure(&pool, &fixture).await;
+
+        sqlx::query(
+            r#"WITH book AS (
+                   INSERT INTO library_books (school_id, title)
+                   VALUES ($1, 'Things Fall Apart') RETURNING id
+               ),
+               copy AS (
+                   INSERT INTO library_copies (book_id, school_id, barcode)
+                   SELECT id, $1, 'LIB-0001' FROM book RETURNING id
+               ),
+               loan AS (
+                   INSERT INTO library_loans (school_id, copy_id, borrower_id, due_date)
+                   SELECT $1, id, $2, '2025-10-01' FROM copy RETURNING id
+               )
+               INSERT INTO library_fine_charges
+                   (school_id, loan_id, borrower_id, days_overdue, amount, created_at)
+               SELECT $1, id, $2, 5, 500, '2025-10-06' FROM loan"#,
+        )
+        .bind(fixture.school_id)
+        .bind(student)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let balance = BillingService::get_student_balance(&pool, student, None, None)
+            .await
+            .unwrap();
+        assert_eq!(balance.total_invoiced, 0);
+        assert_eq!(balance.uninvoiced, 500);
+        assert_eq!(balance.outstanding, 500);
+        assert_eq!(
+            balance.uninvoiced_charges[0].source,
+            ChargeSource::LibraryFine
+        );
+        assert_eq!(balance.uninvoiced_charges[0].term_id, Some(fixture.term_id));
+
+        let school = BillingService::get_school_balance(&pool, fixture.school_id, None)
+            .await
+            .unwrap();
+        assert_eq!(school.outstanding, 500);
+        assert_eq!(school.students_in_arrears, 1);
+
+        BillingService::generate_invoices(&pool, structure.structure.id, None)
+            .await
+            .unwrap();
+
+        let balance = BillingService::get_student_balance(&pool, student, None, None)
+            .await
+            .unwrap();
+        assert_eq!(balance.total_invoiced, 12_000_500);
+        assert_eq!(balance.uninvoiced, 0);
+        assert_eq!(balance.outstanding, 12_000_500);
+
+        let invoice_id = balance.open_invoices[0].id;
+        let detail = BillingService::get_invoice(&pool, invoice_id, None)
+            .await
+            .unwrap();
+        let lines: Vec<&str> = detail.lines.iter().map(|line| line.name.as_str()).collect();
+        assert_eq!(
+            lines,
+            ["Tuition", "Books", "Library fine: Things Fall Apart"]
+        );
+
+        let arrears = BillingService::export_arrears(
+            &pool,
+            None,
+            fixture.school_id,
+            ArrearsExportParams {
+                school_id: None,
+                term_id: None,
+                level_id: None,
+                overdue_only: false,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(arrears.contains(",12000500,0,0,12000500,"));
+
+        // Voiding the invoice puts the fine back to be billed again
+        BillingService::void_invoice(&pool, invoice_id, None)
+            .await
+            .unwrap();
+        let balance = BillingService::get_student_balance(&pool, student, None, None)
+            .await
+            .unwrap();
+        assert_eq!(balance.uninvoiced, 500);
+        assert_eq!(balance.outstanding, 500);
+    }
+}
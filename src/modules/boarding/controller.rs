@@ -0,0 +1,533 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::{BoardingFeeLineId, HostelId, HostelRoomId, RoomAllocationId, UserId};
+
+use crate::middleware::auth::{
+    RequireBoardingAllocate, RequireBoardingCreate, RequireBoardingDelete, RequireBoardingRead,
+    RequireBoardingUpdate,
+};
+use crate::modules::boarding::model::{
+    AllocateRoomDto, BoardingFeeLine, BoardingFeeQueryParams, CreateBoardingFeeLineDto,
+    CreateHostelDto, CreateHostelRoomDto, Hostel, HostelFilterParams, HostelRoom,
+    HostelRoomQueryParams, HostelRoomWithOccupancy, PaginatedHostelsResponse,
+    PaginatedRoomAllocationsResponse, RoomAllocation, RoomAllocationFilterParams,
+    StudentBoardingFees, UpdateHostelDto, UpdateHostelRoomDto,
+};
+use crate::modules::boarding::service::BoardingService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Create a hostel
+#[utoipa::path(
+    post,
+    path = "/api/boarding/hostels",
+    summary = "Create hostel",
+    request_body = CreateHostelDto,
+    responses(
+        Created<Hostel>,
+        (status = 400, description = "Invalid input, duplicate name, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:create permission")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_hostel(
+    State(state): State<AppState>,
+    RequireBoardingCreate(auth_user): RequireBoardingCreate,
+    ValidatedJson(dto): ValidatedJson<CreateHostelDto>,
+) -> Result<Created<Hostel>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let hostel = BoardingService::create_hostel(&state.db, school_id, dto).await?;
+
+    Ok(Created(hostel))
+}
+
+/// List hostels for a school
+#[utoipa::path(
+    get,
+    path = "/api/boarding/hostels",
+    summary = "List hostels",
+    params(HostelFilterParams),
+    responses(
+        (status = 200, description = "List of hostels", body = PaginatedHostelsResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:read permission")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_hostels(
+    State(state): State<AppState>,
+    RequireBoardingRead(auth_user): RequireBoardingRead,
+    Query(filters): Query<HostelFilterParams>,
+) -> Result<Json<PaginatedHostelsResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let hostels = BoardingService::get_hostels(&state.db, school_id, filters).await?;
+
+    Ok(Json(hostels))
+}
+
+/// Get a hostel by ID
+#[utoipa::path(
+    get,
+    path = "/api/boarding/hostels/{id}",
+    summary = "Get hostel",
+    params(
+        ("id" = Uuid, Path, description = "Hostel ID")
+    ),
+    responses(
+        (status = 200, description = "Hostel details", body = Hostel),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:read permission"),
+        (status = 404, description = "Hostel not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_hostel_by_id(
+    State(state): State<AppState>,
+    RequireBoardingRead(auth_user): RequireBoardingRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Hostel>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let hostel =
+        BoardingService::get_hostel_by_id(&state.db, HostelId::from(id), school_id).await?;
+
+    Ok(Json(hostel))
+}
+
+/// Update a hostel
+#[utoipa::path(
+    put,
+    path = "/api/boarding/hostels/{id}",
+    summary = "Update hostel",
+    params(
+        ("id" = Uuid, Path, description = "Hostel ID")
+    ),
+    request_body = UpdateHostelDto,
+    responses(
+        (status = 200, description = "Hostel updated", body = Hostel),
+        (status = 400, description = "Invalid input or duplicate name"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:update permission"),
+        (status = 404, description = "Hostel not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_hostel(
+    State(state): State<AppState>,
+    RequireBoardingUpdate(auth_user): RequireBoardingUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateHostelDto>,
+) -> Result<Json<Hostel>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let hostel =
+        BoardingService::update_hostel(&state.db, HostelId::from(id), school_id, dto).await?;
+
+    Ok(Json(hostel))
+}
+
+/// Delete a hostel
+#[utoipa::path(
+    delete,
+    path = "/api/boarding/hostels/{id}",
+    summary = "Delete hostel",
+    params(
+        ("id" = Uuid, Path, description = "Hostel ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:delete permission"),
+        (status = 404, description = "Hostel not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_hostel(
+    State(state): State<AppState>,
+    RequireBoardingDelete(auth_user): RequireBoardingDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    BoardingService::delete_hostel(&state.db, HostelId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Add a room to a hostel
+#[utoipa::path(
+    post,
+    path = "/api/boarding/hostels/{id}/rooms",
+    summary = "Create hostel room",
+    params(
+        ("id" = Uuid, Path, description = "Hostel ID")
+    ),
+    request_body = CreateHostelRoomDto,
+    responses(
+        Created<HostelRoom>,
+        (status = 400, description = "Invalid input or duplicate room name"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:create permission"),
+        (status = 404, description = "Hostel not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_room(
+    State(state): State<AppState>,
+    RequireBoardingCreate(auth_user): RequireBoardingCreate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<CreateHostelRoomDto>,
+) -> Result<Created<HostelRoom>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let room = BoardingService::create_room(&state.db, HostelId::from(id), school_id, dto).await?;
+
+    Ok(Created(room))
+}
+
+/// List a hostel's rooms with occupancy
+#[utoipa::path(
+    get,
+    path = "/api/boarding/hostels/{id}/rooms",
+    summary = "List hostel rooms",
+    params(
+        ("id" = Uuid, Path, description = "Hostel ID"),
+        HostelRoomQueryParams
+    ),
+    responses(
+        (status = 200, description = "Rooms with occupancy for the session", body = Vec<HostelRoomWithOccupancy>),
+        (status = 400, description = "No active academic session"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:read permission"),
+        (status = 404, description = "Hostel not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_rooms(
+    State(state): State<AppState>,
+    RequireBoardingRead(auth_user): RequireBoardingRead,
+    Path(id): Path<Uuid>,
+    Query(params): Query<HostelRoomQueryParams>,
+) -> Result<Json<Vec<HostelRoomWithOccupancy>>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let rooms = BoardingService::get_rooms(
+        &state.db,
+        HostelId::from(id),
+        school_id,
+        params.academic_session_id,
+    )
+    .await?;
+
+    Ok(Json(rooms))
+}
+
+/// Update a hostel room
+#[utoipa::path(
+    put,
+    path = "/api/boarding/rooms/{id}",
+    summary = "Update hostel room",
+    params(
+        ("id" = Uuid, Path, description = "Room ID")
+    ),
+    request_body = UpdateHostelRoomDto,
+    responses(
+        (status = 200, description = "Room updated", body = HostelRoom),
+        (status = 400, description = "Invalid input or capacity below occupancy"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:update permission"),
+        (status = 404, description = "Room not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_room(
+    State(state): State<AppState>,
+    RequireBoardingUpdate(auth_user): RequireBoardingUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateHostelRoomDto>,
+) -> Result<Json<HostelRoom>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let room =
+        BoardingService::update_room(&state.db, HostelRoomId::from(id), school_id, dto).await?;
+
+    Ok(Json(room))
+}
+
+/// Delete a hostel room
+#[utoipa::path(
+    delete,
+    path = "/api/boarding/rooms/{id}",
+    summary = "Delete hostel room",
+    params(
+        ("id" = Uuid, Path, description = "Room ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:delete permission"),
+        (status = 404, description = "Room not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_room(
+    State(state): State<AppState>,
+    RequireBoardingDelete(auth_user): RequireBoardingDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    BoardingService::delete_room(&state.db, HostelRoomId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Allocate a student to a hostel room
+#[utoipa::path(
+    post,
+    path = "/api/boarding/allocations",
+    summary = "Allocate room",
+    request_body = AllocateRoomDto,
+    responses(
+        Created<RoomAllocation>,
+        (status = 400, description = "Room full, not a student, or already allocated this session"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:allocate permission"),
+        (status = 404, description = "Room not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn allocate_room(
+    State(state): State<AppState>,
+    RequireBoardingAllocate(auth_user): RequireBoardingAllocate,
+    ValidatedJson(dto): ValidatedJson<AllocateRoomDto>,
+) -> Result<Created<RoomAllocation>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let allocated_by = auth_user.user_id()?;
+    let allocation =
+        BoardingService::allocate_room(&state.db, school_id, allocated_by, dto).await?;
+
+    Ok(Created(allocation))
+}
+
+/// List room allocations for a school
+#[utoipa::path(
+    get,
+    path = "/api/boarding/allocations",
+    summary = "List room allocations",
+    params(RoomAllocationFilterParams),
+    responses(
+        (status = 200, description = "List of allocations", body = PaginatedRoomAllocationsResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:read permission")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_allocations(
+    State(state): State<AppState>,
+    RequireBoardingRead(auth_user): RequireBoardingRead,
+    Query(filters): Query<RoomAllocationFilterParams>,
+) -> Result<Json<PaginatedRoomAllocationsResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let allocations = BoardingService::get_allocations(&state.db, school_id, filters).await?;
+
+    Ok(Json(allocations))
+}
+
+/// Remove a room allocation
+#[utoipa::path(
+    delete,
+    path = "/api/boarding/allocations/{id}",
+    summary = "Remove room allocation",
+    params(
+        ("id" = Uuid, Path, description = "Allocation ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:allocate permission"),
+        (status = 404, description = "Allocation not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_allocation(
+    State(state): State<AppState>,
+    RequireBoardingAllocate(auth_user): RequireBoardingAllocate,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    BoardingService::delete_allocation(&state.db, RoomAllocationId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Add a boarding fee line to a hostel
+#[utoipa::path(
+    post,
+    path = "/api/boarding/hostels/{id}/fees",
+    summary = "Create boarding fee line",
+    params(
+        ("id" = Uuid, Path, description = "Hostel ID")
+    ),
+    request_body = CreateBoardingFeeLineDto,
+    responses(
+        Created<BoardingFeeLine>,
+        (status = 400, description = "Invalid input or duplicate fee line"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:create permission"),
+        (status = 404, description = "Hostel not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_fee_line(
+    State(state): State<AppState>,
+    RequireBoardingCreate(auth_user): RequireBoardingCreate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<CreateBoardingFeeLineDto>,
+) -> Result<Created<BoardingFeeLine>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let line =
+        BoardingService::create_fee_line(&state.db, HostelId::from(id), school_id, dto).await?;
+
+    Ok(Created(line))
+}
+
+/// List a hostel's boarding fee lines
+#[utoipa::path(
+    get,
+    path = "/api/boarding/hostels/{id}/fees",
+    summary = "List boarding fee lines",
+    params(
+        ("id" = Uuid, Path, description = "Hostel ID"),
+        BoardingFeeQueryParams
+    ),
+    responses(
+        (status = 200, description = "Fee lines for the session", body = Vec<BoardingFeeLine>),
+        (status = 400, description = "No active academic session"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:read permission"),
+        (status = 404, description = "Hostel not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_fee_lines(
+    State(state): State<AppState>,
+    RequireBoardingRead(auth_user): RequireBoardingRead,
+    Path(id): Path<Uuid>,
+    Query(params): Query<BoardingFeeQueryParams>,
+) -> Result<Json<Vec<BoardingFeeLine>>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let lines = BoardingService::get_fee_lines(
+        &state.db,
+        HostelId::from(id),
+        school_id,
+        params.academic_session_id,
+    )
+    .await?;
+
+    Ok(Json(lines))
+}
+
+/// Delete a boarding fee line
+#[utoipa::path(
+    delete,
+    path = "/api/boarding/fees/{id}",
+    summary = "Delete boarding fee line",
+    params(
+        ("id" = Uuid, Path, description = "Fee line ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:delete permission"),
+        (status = 404, description = "Fee line not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_fee_line(
+    State(state): State<AppState>,
+    RequireBoardingDelete(auth_user): RequireBoardingDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    BoardingService::delete_fee_line(&state.db, BoardingFeeLineId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Get a student's boarding fees for a session
+#[utoipa::path(
+    get,
+    path = "/api/boarding/students/{student_id}/fees",
+    summary = "Student boarding fees",
+    params(
+        ("student_id" = Uuid, Path, description = "Student ID"),
+        BoardingFeeQueryParams
+    ),
+    responses(
+        (status = 200, description = "Allocation and fee lines owed", body = StudentBoardingFees),
+        (status = 400, description = "No active academic session"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires boarding:read permission"),
+        (status = 404, description = "Student not found")
+    ),
+    tag = "Boarding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_student_fees(
+    State(state): State<AppState>,
+    RequireBoardingRead(auth_user): RequireBoardingRead,
+    Path(student_id): Path<Uuid>,
+    Query(params): Query<BoardingFeeQueryParams>,
+) -> Result<Json<StudentBoardingFees>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let fees = BoardingService::get_student_fees(
+        &state.db,
+        UserId::from(student_id),
+        school_id,
+        params.academic_session_id,
+    )
+    .await?;
+
+    Ok(Json(fees))
+}
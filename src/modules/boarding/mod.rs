@@ -0,0 +1,9 @@
+//! Boarding module.
+//!
+//! This module manages boarding for residential schools: hostels, rooms with
+//! capacity, per-session student room allocations, and boarding fee lines.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Boarding data models and DTOs.
+//!
+//! This module re-exports boarding models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all boarding models from the shared crate
+pub use chalkbyte_models::boarding::*;
@@ -0,0 +1,39 @@
+use axum::{
+    Router,
+    routing::{delete, get, post, put},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    allocate_room, create_fee_line, create_hostel, create_room, delete_allocation, delete_fee_line,
+    delete_hostel, delete_room, get_allocations, get_fee_lines, get_hostel_by_id, get_hostels,
+    get_rooms, get_student_fees, update_hostel, update_room,
+};
+
+/// Initialize the boarding router
+/// Routes: POST /hostels, GET /hostels, GET /hostels/{id}, PUT /hostels/{id},
+/// DELETE /hostels/{id}, POST /hostels/{id}/rooms, GET /hostels/{id}/rooms,
+/// PUT /rooms/{id}, DELETE /rooms/{id}, POST /hostels/{id}/fees,
+/// GET /hostels/{id}/fees, DELETE /fees/{id}, POST /allocations,
+/// GET /allocations, DELETE /allocations/{id}, GET /students/{student_id}/fees
+pub fn init_boarding_router() -> Router<AppState> {
+    Router::new()
+        .route("/hostels", post(create_hostel).get(get_hostels))
+        .route(
+            "/hostels/{id}",
+            get(get_hostel_by_id)
+                .put(update_hostel)
+                .delete(delete_hostel),
+        )
+        .route("/hostels/{id}/rooms", post(create_room).get(get_rooms))
+        .route("/rooms/{id}", put(update_room).delete(delete_room))
+        .route(
+            "/hostels/{id}/fees",
+            post(create_fee_line).get(get_fee_lines),
+        )
+        .route("/fees/{id}", delete(delete_fee_line))
+        .route("/allocations", post(allocate_room).get(get_allocations))
+        .route("/allocations/{id}", delete(delete_allocation))
+        .route("/students/{student_id}/fees", get(get_student_fees))
+}
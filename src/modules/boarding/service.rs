@@ -0,0 +1,979 @@
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, ErrorCode, PaginationMeta};
+use chalkbyte_models::ids::{
+    AcademicSessionId, BoardingFeeLineId, HostelId, HostelRoomId, RoomAllocationId, SchoolId,
+    UserId,
+};
+
+use crate::modules::boarding::model::{
+    AllocateRoomDto, BoardingFeeLine, CreateBoardingFeeLineDto, CreateHostelDto,
+    CreateHostelRoomDto, Hostel, HostelFilterParams, HostelGender, HostelRoom,
+    HostelRoomWithOccupancy, PaginatedHostelsResponse, PaginatedRoomAllocationsResponse,
+    RoomAllocation, RoomAllocationFilterParams, RoomAllocationWithDetails, StudentBoardingFees,
+    UpdateHostelDto, UpdateHostelRoomDto,
+};
+use crate::modules::users::model::system_roles;
+
+const HOSTEL_COLUMNS: &str =
+    "id, school_id, name, gender, warden_id, description, created_at, updated_at";
+
+const ALLOCATION_DETAILS_SELECT: &str = r#"SELECT a.id, a.room_id, r.name AS room_name,
+           h.id AS hostel_id, h.name AS hostel_name,
+           a.student_id, u.first_name AS student_first_name, u.last_name AS student_last_name,
+           a.academic_session_id, a.allocated_by, a.created_at
+    FROM room_allocations a
+    JOIN hostel_rooms r ON r.id = a.room_id
+    JOIN hostels h ON h.id = r.hostel_id
+    JOIN users u ON u.id = a.student_id"#;
+
+fn unique_violation_as(e: sqlx::Error, message: &'static str) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e
+        && db_err.is_unique_violation()
+    {
+        return AppError::bad_request(anyhow::anyhow!(message));
+    }
+    AppError::from(e)
+}
+
+pub struct BoardingService;
+
+impl BoardingService {
+    /// Fetch a hostel, optionally restricted to a school.
+    async fn find_hostel(
+        db: &PgPool,
+        hostel_id: HostelId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Hostel, AppError> {
+        sqlx::query_as::<_, Hostel>(&format!(
+            "SELECT {HOSTEL_COLUMNS} FROM hostels
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(hostel_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Hostel not found")))
+    }
+
+    /// Fetch a room, optionally restricted to the school that owns its hostel.
+    async fn find_room(
+        db: &PgPool,
+        room_id: HostelRoomId,
+        school_id: Option<SchoolId>,
+    ) -> Result<HostelRoom, AppError> {
+        sqlx::query_as::<_, HostelRoom>(
+            r#"SELECT r.id, r.hostel_id, r.name, r.capacity, r.created_at, r.updated_at
+               FROM hostel_rooms r
+               JOIN hostels h ON h.id = r.hostel_id
+               WHERE r.id = $1 AND ($2::uuid IS NULL OR h.school_id = $2)"#,
+        )
+        .bind(room_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Room not found")))
+    }
+
+    /// Resolve the academic session to use, falling back to the school's active session.
+    async fn resolve_session(
+        db: &PgPool,
+        school_id: SchoolId,
+        session_id: Option<AcademicSessionId>,
+    ) -> Result<AcademicSessionId, AppError> {
+        match session_id {
+            Some(session_id) => {
+                let belongs = sqlx::query_scalar::<_, bool>(
+                    "SELECT EXISTS(SELECT 1 FROM academic_sessions WHERE id = $1 AND school_id = $2)",
+                )
+                .bind(session_id)
+                .bind(school_id)
+                .fetch_one(db)
+                .await?;
+
+                if !belongs {
+                    return Err(AppError::bad_request(anyhow::anyhow!(
+                        "Academic session not found in this school"
+                    )));
+                }
+                Ok(session_id)
+            }
+            None => sqlx::query_scalar::<_, AcademicSessionId>(
+                "SELECT id FROM academic_sessions WHERE school_id = $1 AND is_active = true",
+            )
+            .bind(school_id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| {
+                AppError::bad_request(anyhow::anyhow!(
+                    "No active academic session; specify academic_session_id"
+                ))
+            }),
+        }
+    }
+
+    /// Ensure a warden is a member of the hostel's school.
+    async fn validate_warden(
+        db: &PgPool,
+        school_id: SchoolId,
+        warden_id: UserId,
+    ) -> Result<(), AppError> {
+        let in_school = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND school_id = $2)",
+        )
+        .bind(warden_id)
+        .bind(school_id)
+        .fetch_one(db)
+        .await?;
+
+        if !in_school {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Warden must be a staff member of the hostel's school"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Create a hostel for a school.
+    #[instrument(skip(db))]
+    pub async fn create_hostel(
+        db: &PgPool,
+        school_id: SchoolId,
+        dto: CreateHostelDto,
+    ) -> Result<Hostel, AppError> {
+        if let Some(warden_id) = dto.warden_id {
+            Self::validate_warden(db, school_id, warden_id).await?;
+        }
+
+        let hostel = sqlx::query_as::<_, Hostel>(&format!(
+            "INSERT INTO hostels (school_id, name, gender, warden_id, description)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {HOSTEL_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.name)
+        .bind(dto.gender.unwrap_or(HostelGender::Mixed))
+        .bind(dto.warden_id)
+        .bind(&dto.description)
+        .fetch_one(db)
+        .await
+        .map_err(|e| unique_violation_as(e, "A hostel with this name already exists"))?;
+
+        Ok(hostel)
+    }
+
+    /// Get paginated hostels for a school.
+    #[instrument(skip(db))]
+    pub async fn get_hostels(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: HostelFilterParams,
+    ) -> Result<PaginatedHostelsResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM hostels WHERE school_id = $1 AND ($2::text IS NULL OR gender = $2)",
+        )
+        .bind(school_id)
+        .bind(filters.gender)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, Hostel>(&format!(
+            "SELECT {HOSTEL_COLUMNS} FROM hostels
+             WHERE school_id = $1 AND ($2::text IS NULL OR gender = $2)
+             ORDER BY name ASC
+             LIMIT $3 OFFSET $4"
+        ))
+        .bind(school_id)
+        .bind(filters.gender)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedHostelsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Get a hostel by ID.
+    ///
+    /// When `school_id` is provided, the hostel must belong to that school.
+    #[instrument(skip(db))]
+    pub async fn get_hostel_by_id(
+        db: &PgPool,
+        hostel_id: HostelId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Hostel, AppError> {
+        Self::find_hostel(db, hostel_id, school_id).await
+    }
+
+    /// Update a hostel.
+    #[instrument(skip(db))]
+    pub async fn update_hostel(
+        db: &PgPool,
+        hostel_id: HostelId,
+        school_id: Option<SchoolId>,
+        dto: UpdateHostelDto,
+    ) -> Result<Hostel, AppError> {
+        let existing = Self::find_hostel(db, hostel_id, school_id).await?;
+
+        if let Some(warden_id) = dto.warden_id {
+            Self::validate_warden(db, existing.school_id, warden_id).await?;
+        }
+
+        let hostel = sqlx::query_as::<_, Hostel>(&format!(
+            "UPDATE hostels
+             SET name = COALESCE($2, name),
+                 gender = COALESCE($3, gender),
+                 warden_id = COALESCE($4, warden_id),
+                 description = COALESCE($5, description)
+             WHERE id = $1
+             RETURNING {HOSTEL_COLUMNS}"
+        ))
+        .bind(hostel_id)
+        .bind(&dto.name)
+        .bind(dto.gender)
+        .bind(dto.warden_id)
+        .bind(&dto.description)
+        .fetch_one(db)
+        .await
+        .map_err(|e| unique_violation_as(e, "A hostel with this name already exists"))?;
+
+        Ok(hostel)
+    }
+
+    /// Delete a hostel with its rooms, allocations, and fee lines.
+    #[instrument(skip(db))]
+    pub async fn delete_hostel(
+        db: &PgPool,
+        hostel_id: HostelId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "DELETE FROM hostels WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(hostel_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!("Hostel not found")));
+        }
+
+        Ok(())
+    }
+
+    /// Add a room to a hostel.
+    #[instrument(skip(db))]
+    pub async fn create_room(
+        db: &PgPool,
+        hostel_id: HostelId,
+        school_id: Option<SchoolId>,
+        dto: CreateHostelRoomDto,
+    ) -> Result<HostelRoom, AppError> {
+        Self::find_hostel(db, hostel_id, school_id).await?;
+
+        let room = sqlx::query_as::<_, HostelRoom>(
+            r#"INSERT INTO hostel_rooms (hostel_id, name, capacity)
+               VALUES ($1, $2, $3)
+               RETURNING id, hostel_id, name, capacity, created_at, updated_at"#,
+        )
+        .bind(hostel_id)
+        .bind(&dto.name)
+        .bind(dto.capacity)
+        .fetch_one(db)
+        .await
+        .map_err(|e| {
+            unique_violation_as(e, "A room with this name already exists in this hostel")
+        })?;
+
+        Ok(room)
+    }
+
+    /// List a hostel's rooms with their occupancy for a session.
+    #[instrument(skip(db))]
+    pub async fn get_rooms(
+        db: &PgPool,
+        hostel_id: HostelId,
+        school_id: Option<SchoolId>,
+        session_id: Option<AcademicSessionId>,
+    ) -> Result<Vec<HostelRoomWithOccupancy>, AppError> {
+        let hostel = Self::find_hostel(db, hostel_id, school_id).await?;
+        let session_id = Self::resolve_session(db, hostel.school_id, session_id).await?;
+
+        let rooms = sqlx::query_as::<_, HostelRoomWithOccupancy>(
+            r#"SELECT r.id, r.hostel_id, r.name, r.capacity,
+                      COUNT(a.id) AS occupied,
+                      r.created_at, r.updated_at
+               FROM hostel_rooms r
+               LEFT JOIN room_allocations a
+                 ON a.room_id = r.id AND a.academic_session_id = $2
+               WHERE r.hostel_id = $1
+               GROUP BY r.id
+               ORDER BY r.name ASC"#,
+        )
+        .bind(hostel_id)
+        .bind(session_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(rooms)
+    }
+
+    /// Update a hostel room.
+    ///
+    /// Capacity cannot be reduced below the room's occupancy in any session.
+    #[instrument(skip(db))]
+    pub async fn update_room(
+        db: &PgPool,
+        room_id: HostelRoomId,
+        school_id: Option<SchoolId>,
+        dto: UpdateHostelRoomDto,
+    ) -> Result<HostelRoom, AppError> {
+        Self::find_room(db, room_id, school_id).await?;
+
+        if let Some(capacity) = dto.capacity {
+            let max_occupied = sqlx::query_scalar::<_, i64>(
+                r#"SELECT COALESCE(MAX(cnt), 0) FROM (
+                       SELECT COUNT(*) AS cnt FROM room_allocations
+                       WHERE room_id = $1
+                       GROUP BY academic_session_id
+                   ) per_session"#,
+            )
+            .bind(room_id)
+            .fetch_one(db)
+            .await?;
+
+            if i64::from(capacity) < max_occupied {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "Capacity cannot be lower than the {} students already allocated",
+                    max_occupied
+                )));
+            }
+        }
+
+        let room = sqlx::query_as::<_, HostelRoom>(
+            r#"UPDATE hostel_rooms
+               SET name = COALESCE($2, name), capacity = COALESCE($3, capacity)
+               WHERE id = $1
+               RETURNING id, hostel_id, name, capacity, created_at, updated_at"#,
+        )
+        .bind(room_id)
+        .bind(&dto.name)
+        .bind(dto.capacity)
+        .fetch_one(db)
+        .await
+        .map_err(|e| {
+            unique_violation_as(e, "A room with this name already exists in this hostel")
+        })?;
+
+        Ok(room)
+    }
+
+    /// Delete a hostel room and its allocations.
+    #[instrument(skip(db))]
+    pub async fn delete_room(
+        db: &PgPool,
+        room_id: HostelRoomId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        Self::find_room(db, room_id, school_id).await?;
+
+        sqlx::query("DELETE FROM hostel_rooms WHERE id = $1")
+            .bind(room_id)
+            .execute(db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Allocate a student to a room for an academic session.
+    ///
+    /// The room row is locked while occupancy is checked so concurrent
+    /// allocations cannot overfill it.
+    #[instrument(skip(db))]
+    pub async fn allocate_room(
+        db: &PgPool,
+        school_id: Option<SchoolId>,
+        allocated_by: UserId,
+        dto: AllocateRoomDto,
+    ) -> Result<RoomAllocation, AppError> {
+        let room = Self::find_room(db, dto.room_id, school_id).await?;
+        let room_school_id = Self::find_hostel(db, room.hostel_id, None).await?.school_id;
+        Self::resolve_session(db, room_school_id, Some(dto.academic_session_id)).await?;
+
+        let is_student = sqlx::query_scalar::<_, bool>(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM users u
+                JOIN user_roles ur ON ur.user_id = u.id
+                WHERE u.id = $1 AND u.school_id = $2 AND ur.role_id = $3
+            )"#,
+        )
+        .bind(dto.student_id)
+        .bind(room_school_id)
+        .bind(system_roles::STUDENT)
+        .fetch_one(db)
+        .await?;
+
+        if !is_student {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only students of the hostel's school can be allocated a room"
+            )));
+        }
+
+        let mut tx = db.begin().await?;
+
+        sqlx::query("SELECT id FROM hostel_rooms WHERE id = $1 FOR UPDATE")
+            .bind(room.id)
+            .execute(&mut *tx)
+            .await?;
+
+        let occupied = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM room_allocations WHERE room_id = $1 AND academic_session_id = $2",
+        )
+        .bind(room.id)
+        .bind(dto.academic_session_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if occupied >= i64::from(room.capacity) {
+            return Err(AppError::bad_request(anyhow::anyhow!("Room is full")));
+        }
+
+        let allocation = sqlx::query_as::<_, RoomAllocation>(
+            r#"INSERT INTO room_allocations (room_id, student_id, academic_session_id, allocated_by)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id, room_id, student_id, academic_session_id, allocated_by, created_at"#,
+        )
+        .bind(room.id)
+        .bind(dto.student_id)
+        .bind(dto.academic_session_id)
+        .bind(allocated_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            unique_violation_as(e, "Student already has a room allocation for this session")
+        })?;
+
+        tx.commit().await?;
+
+        Ok(allocation)
+    }
+
+    /// Get paginated room allocations for a school.
+    #[instrument(skip(db))]
+    pub async fn get_allocations(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: RoomAllocationFilterParams,
+    ) -> Result<PaginatedRoomAllocationsResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+
+        let where_clause = r#"WHERE h.school_id = $1
+              AND ($2::uuid IS NULL OR a.academic_session_id = $2)
+              AND ($3::uuid IS NULL OR h.id = $3)
+              AND ($4::uuid IS NULL OR r.id = $4)"#;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            r#"SELECT COUNT(*) FROM room_allocations a
+               JOIN hostel_rooms r ON r.id = a.room_id
+               JOIN hostels h ON h.id = r.hostel_id
+               {where_clause}"#
+        ))
+        .bind(school_id)
+        .bind(filters.academic_session_id)
+        .bind(filters.hostel_id)
+        .bind(filters.room_id)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, RoomAllocationWithDetails>(&format!(
+            "{ALLOCATION_DETAILS_SELECT}
+             {where_clause}
+             ORDER BY h.name, r.name, u.last_name, u.first_name
+             LIMIT $5 OFFSET $6"
+        ))
+        .bind(school_id)
+        .bind(filters.academic_session_id)
+        .bind(filters.hostel_id)
+        .bind(filters.room_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedRoomAllocationsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Remove a room allocation.
+    #[instrument(skip(db))]
+    pub async fn delete_allocation(
+        db: &PgPool,
+        allocation_id: RoomAllocationId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"DELETE FROM room_allocations a
+               USING hostel_rooms r, hostels h
+               WHERE a.room_id = r.id AND r.hostel_id = h.id
+                 AND a.id = $1 AND ($2::uuid IS NULL OR h.school_id = $2)"#,
+        )
+        .bind(allocation_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Room allocation not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Add a boarding fee line to a hostel for a session.
+    #[instrument(skip(db))]
+    pub async fn create_fee_line(
+        db: &PgPool,
+        hostel_id: HostelId,
+        school_id: Option<SchoolId>,
+        dto: CreateBoardingFeeLineDto,
+    ) -> Result<BoardingFeeLine, AppError> {
+        let hostel = Self::find_hostel(db, hostel_id, school_id).await?;
+        Self::resolve_session(db, hostel.school_id, Some(dto.academic_session_id)).await?;
+
+        let line = sqlx::query_as::<_, BoardingFeeLine>(
+            r#"INSERT INTO boarding_fee_lines (hostel_id, academic_session_id, name, amount)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id, hostel_id, academic_session_id, name, amount, created_at"#,
+        )
+        .bind(hostel_id)
+        .bind(dto.academic_session_id)
+        .bind(&dto.name)
+        .bind(dto.amount)
+        .fetch_one(db)
+        .await
+        .map_err(|e| {
+            unique_violation_as(
+                e,
+                "A fee line with this name already exists for this session",
+            )
+        })?;
+
+        Ok(line)
+    }
+
+    /// List a hostel's fee lines for a session.
+    #[instrument(skip(db))]
+    pub async fn get_fee_lines(
+        db: &PgPool,
+        hostel_id: HostelId,
+        school_id: Option<SchoolId>,
+        session_id: Option<AcademicSessionId>,
+    ) -> Result<Vec<BoardingFeeLine>, AppError> {
+        let hostel = Self::find_hostel(db, hostel_id, school_id).await?;
+        let session_id = Self::resolve_session(db, hostel.school_id, session_id).await?;
+
+        Self::fee_lines_for(db, hostel_id, session_id).await
+    }
+
+    async fn fee_lines_for(
+        db: &PgPool,
+        hostel_id: HostelId,
+        session_id: AcademicSessionId,
+    ) -> Result<Vec<BoardingFeeLine>, AppError> {
+        let lines = sqlx::query_as::<_, BoardingFeeLine>(
+            r#"SELECT id, hostel_id, academic_session_id, name, amount, created_at
+               FROM boarding_fee_lines
+               WHERE hostel_id = $1 AND academic_session_id = $2
+               ORDER BY name ASC"#,
+        )
+        .bind(hostel_id)
+        .bind(session_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(lines)
+    }
+
+    /// Delete a boarding fee line.
+    #[instrument(skip(db))]
+    pub async fn delete_fee_line(
+        db: &PgPool,
+        fee_line_id: BoardingFeeLineId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"DELETE FROM boarding_fee_lines f
+               USING hostels h
+               WHERE f.hostel_id = h.id
+                 AND f.id = $1 AND ($2::uuid IS NULL OR h.school_id = $2)"#,
+        )
+        .bind(fee_line_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!("Fee line not found")));
+        }
+
+        Ok(())
+    }
+
+    /// Get the boarding fees a student owes for a session.
+    ///
+    /// Students without an allocation for the session owe nothing.
+    #[instrument(skip(db))]
+    pub async fn get_student_fees(
+        db: &PgPool,
+        student_id: UserId,
+        school_id: Option<SchoolId>,
+        session_id: Option<AcademicSessionId>,
+    ) -> Result<StudentBoardingFees, AppError> {
+        let student_school_id = sqlx::query_scalar::<_, Option<SchoolId>>(
+            "SELECT school_id FROM users WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(student_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .flatten()
+        .ok_or_else(|| AppError::from_code(ErrorCode::StudentNotFound))?;
+
+        let session_id = Self::resolve_session(db, student_school_id, session_id).await?;
+
+        let allocation = sqlx::query_as::<_, RoomAllocationWithDetails>(&format!(
+            "{ALLOCATION_DETAILS_SELECT}
+             WHERE a.student_id = $1 AND a.academic_session_id = $2"
+        ))
+        .bind(student_id)
+        .bind(session_id)
+        .fetch_optional(db)
+        .await?;
+
+        let lines = match &allocation {
+            Some(allocation) => Self::fee_lines_for(db, allocation.hostel_id, session_id).await?,
+            None => Vec::new(),
+        };
+        let total = lines.iter().map(|line| line.amount).sum();
+
+        Ok(StudentBoardingFees {
+            student_id,
+            academic_session_id: session_id,
+            allocation,
+            lines,
+            total,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_session(pool: &PgPool, school_id: SchoolId) -> AcademicSessionId {
+        sqlx::query_scalar::<_, AcademicSessionId>(
+            r#"INSERT INTO academic_sessions (name, school_id, start_date, end_date, is_active)
+               VALUES ('2025/2026', $1, $2, $3, true) RETURNING id"#,
+        )
+        .bind(school_id)
+        .bind(NaiveDate::from_ymd_opt(2025, 9, 1).unwrap())
+        .bind(NaiveDate::from_ymd_opt(2026, 7, 31).unwrap())
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn create_test_student(pool: &PgPool, school_id: SchoolId) -> UserId {
+        let user_id: UserId = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Test', 'Student', $1, $2) RETURNING id"#,
+            format!("student-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id.into_inner(),
+            system_roles::STUDENT.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id
+    }
+
+    async fn create_test_hostel(pool: &PgPool, school_id: SchoolId) -> Hostel {
+        BoardingService::create_hostel(
+            pool,
+            school_id,
+            CreateHostelDto {
+                name: "Blue House".to_string(),
+                gender: Some(HostelGender::Female),
+                warden_id: None,
+                description: None,
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    fn room_dto(name: &str, capacity: i32) -> CreateHostelRoomDto {
+        CreateHostelRoomDto {
+            name: name.to_string(),
+            capacity,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_allocate_room_respects_capacity(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let session_id = create_test_session(&pool, school_id).await;
+        let hostel = create_test_hostel(&pool, school_id).await;
+        let room =
+            BoardingService::create_room(&pool, hostel.id, Some(school_id), room_dto("A1", 1))
+                .await
+                .unwrap();
+        let first = create_test_student(&pool, school_id).await;
+        let second = create_test_student(&pool, school_id).await;
+
+        BoardingService::allocate_room(
+            &pool,
+            Some(school_id),
+            first,
+            AllocateRoomDto {
+                room_id: room.id,
+                student_id: first,
+                academic_session_id: session_id,
+            },
+        )
+        .await
+        .unwrap();
+
+        let err = BoardingService::allocate_room(
+            &pool,
+            Some(school_id),
+            first,
+            AllocateRoomDto {
+                room_id: room.id,
+                student_id: second,
+                academic_session_id: session_id,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let rooms = BoardingService::get_rooms(&pool, hostel.id, Some(school_id), None)
+            .await
+            .unwrap();
+        assert_eq!(rooms.len(), 1);
+        assert_eq!(rooms[0].occupied, 1);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_student_single_allocation_per_session(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let session_id = create_test_session(&pool, school_id).await;
+        let hostel = create_test_hostel(&pool, school_id).await;
+        let room_a =
+            BoardingService::create_room(&pool, hostel.id, Some(school_id), room_dto("A1", 4))
+                .await
+                .unwrap();
+        let room_b =
+            BoardingService::create_room(&pool, hostel.id, Some(school_id), room_dto("B1", 4))
+                .await
+                .unwrap();
+        let student = create_test_student(&pool, school_id).await;
+
+        for (room_id, should_succeed) in [(room_a.id, true), (room_b.id, false)] {
+            let result = BoardingService::allocate_room(
+                &pool,
+                Some(school_id),
+                student,
+                AllocateRoomDto {
+                    room_id,
+                    student_id: student,
+                    academic_session_id: session_id,
+                },
+            )
+            .await;
+            assert_eq!(result.is_ok(), should_succeed);
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_allocate_rejects_non_student(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let session_id = create_test_session(&pool, school_id).await;
+        let hostel = create_test_hostel(&pool, school_id).await;
+        let room =
+            BoardingService::create_room(&pool, hostel.id, Some(school_id), room_dto("A1", 2))
+                .await
+                .unwrap();
+        let staff: UserId = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Staff', 'Member', $1, $2) RETURNING id"#,
+            format!("staff-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .into();
+
+        let err = BoardingService::allocate_room(
+            &pool,
+            Some(school_id),
+            staff,
+            AllocateRoomDto {
+                room_id: room.id,
+                student_id: staff,
+                academic_session_id: session_id,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_student_boarding_fees(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let session_id = create_test_session(&pool, school_id).await;
+        let hostel = create_test_hostel(&pool, school_id).await;
+        let room =
+            BoardingService::create_room(&pool, hostel.id, Some(school_id), room_dto("A1", 4))
+                .await
+                .unwrap();
+        let boarder = create_test_student(&pool, school_id).await;
+        let day_student = create_test_student(&pool, school_id).await;
+
+        for (name, amount) in [("Boarding fee", 15_000_000), ("Laundry", 1_000_000)] {
+            BoardingService::create_fee_line(
+                &pool,
+                hostel.id,
+                Some(school_id),
+                CreateBoardingFeeLineDto {
+                    academic_session_id: session_id,
+                    name: name.to_string(),
+                    amount,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        BoardingService::allocate_room(
+            &pool,
+            Some(school_id),
+            boarder,
+            AllocateRoomDto {
+                room_id: room.id,
+                student_id: boarder,
+                academic_session_id: session_id,
+            },
+        )
+        .await
+        .unwrap();
+
+        let fees = BoardingService::get_student_fees(&pool, boarder, Some(school_id), None)
+            .await
+            .unwrap();
+        assert_eq!(fees.lines.len(), 2);
+        assert_eq!(fees.total, 16_000_000);
+        assert_eq!(fees.allocation.unwrap().hostel_id, hostel.id);
+
+        let fees = BoardingService::get_student_fees(&pool, day_student, Some(school_id), None)
+            .await
+            .unwrap();
+        assert!(fees.allocation.is_none());
+        assert_eq!(fees.total, 0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_room_capacity_cannot_drop_below_occupancy(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let session_id = create_test_session(&pool, school_id).await;
+        let hostel = create_test_hostel(&pool, school_id).await;
+        let room =
+            BoardingService::create_room(&pool, hostel.id, Some(school_id), room_dto("A1", 3))
+                .await
+                .unwrap();
+
+        for _ in 0..2 {
+            let student = create_test_student(&pool, school_id).await;
+            BoardingService::allocate_room(
+                &pool,
+                Some(school_id),
+                student,
+                AllocateRoomDto {
+                    room_id: room.id,
+                    student_id: student,
+                    academic_session_id: session_id,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let err = BoardingService::update_room(
+            &pool,
+            room.id,
+            Some(school_id),
+            UpdateHostelRoomDto {
+                name: None,
+                capacity: Some(1),
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+}
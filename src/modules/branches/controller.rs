@@ -1,27 +1,32 @@
 use axum::{
     Json,
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{OriginalUri, Path, Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
 };
 use tracing::instrument;
 use uuid::Uuid;
-use validator::Validate;
 
-use chalkbyte_core::AppError;
-use chalkbyte_models::ids::{BranchId, LevelId, UserId};
+use chalkbyte_cache::{ListEtag, lists};
+use chalkbyte_core::{AppError, Conditional, Created, IfMatch, NoContent, WithEtag};
+use chalkbyte_models::ids::{BranchId, BranchTeacherId, LevelId, UserId};
 
+use crate::config::database::timed;
 use crate::middleware::auth::{
-    RequireBranchesAssignStudents, RequireBranchesCreate, RequireBranchesDelete,
-    RequireBranchesRead, RequireBranchesUpdate,
+    RequireBranchesAssignStudents, RequireBranchesAssignTeachers, RequireBranchesCreate,
+    RequireBranchesDelete, RequireBranchesRead, RequireBranchesUpdate,
 };
-use crate::middleware::role::{get_admin_school_id, is_system_admin_jwt};
+use crate::modules::auth::controller::ErrorResponse;
 use crate::modules::branches::model::{
-    AssignStudentsToBranchDto, Branch, BranchFilterParams, BranchWithStats, BulkAssignResponse,
-    CreateBranchDto, MoveStudentToBranchDto, PaginatedBranchesResponse, UpdateBranchDto,
+    AssignBranchTeacherDto, AssignStudentsToBranchDto, Branch, BranchFilterParams, BranchTeacher,
+    BranchTeacherQueryParams, BranchWithStats, BulkAssignResponse, CreateBranchDto,
+    MoveStudentToBranchDto, PaginatedBranchesResponse, UpdateBranchDto,
 };
 use crate::modules::branches::service::BranchService;
 use crate::modules::users::model::User;
 use crate::state::AppState;
+use crate::utils::auth_helpers::{get_optional_school_id_for_resource_operation, tenant_context};
+use crate::validator::ValidatedJson;
 
 #[utoipa::path(
     post,
@@ -32,10 +37,12 @@ use crate::state::AppState;
     ),
     request_body = CreateBranchDto,
     responses(
-        (status = 201, description = "Branch created successfully", body = Branch),
-        (status = 400, description = "Invalid input"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires branches:create permission")
+        Created<Branch>,
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires branches:create permission", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Branches",
     security(("bearer_auth" = []))
@@ -45,30 +52,16 @@ pub async fn create_branch(
     State(state): State<AppState>,
     RequireBranchesCreate(auth_user): RequireBranchesCreate,
     Path(level_id): Path<Uuid>,
-    Json(dto): Json<CreateBranchDto>,
-) -> Result<(StatusCode, Json<Branch>), AppError> {
-    dto.validate()?;
-
+    ValidatedJson(dto): ValidatedJson<CreateBranchDto>,
+) -> Result<Created<Branch>, AppError> {
     let level_id = LevelId::from(level_id);
 
-    // System admins can create branches for any level
-    if is_system_admin_jwt(&auth_user) {
-        let branch = BranchService::create_branch_no_school_filter(
-            &state.db,
-            state.cache.as_ref(),
-            level_id,
-            dto,
-        )
-        .await?;
-        return Ok((StatusCode::CREATED, Json(branch)));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
     let branch =
-        BranchService::create_branch(&state.db, state.cache.as_ref(), level_id, school_id, dto)
+        BranchService::create_branch(&state.db, state.cache.as_ref(), &tenant, level_id, dto)
             .await?;
 
-    Ok((StatusCode::CREATED, Json(branch)))
+    Ok(Created(branch))
 }
 
 #[utoipa::path(
@@ -77,12 +70,16 @@ pub async fn create_branch(
     summary = "List branches",
     params(
         ("level_id" = Uuid, Path, description = "Level ID"),
-        BranchFilterParams
+        BranchFilterParams,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a previously fetched list")
     ),
     responses(
         (status = 200, description = "List of branches", body = PaginatedBranchesResponse),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires branches:read permission")
+        (status = 304, description = "Not modified - the list still matches If-None-Match"),
+        (status = 400, description = "Invalid sort field", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires branches:read permission", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Branches",
     security(("bearer_auth" = []))
@@ -92,23 +89,36 @@ pub async fn get_branches(
     State(state): State<AppState>,
     RequireBranchesRead(auth_user): RequireBranchesRead,
     Path(level_id): Path<Uuid>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
     Query(filters): Query<BranchFilterParams>,
-) -> Result<Json<PaginatedBranchesResponse>, AppError> {
+) -> Result<Response, AppError> {
     let level_id = LevelId::from(level_id);
 
-    // System admins can view branches for any level
-    if is_system_admin_jwt(&auth_user) {
-        let branches =
-            BranchService::get_branches_by_level_no_school_filter(&state.db, level_id, filters)
-                .await?;
-        return Ok(Json(branches));
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    let etag = ListEtag::lookup(
+        state.cache.as_ref(),
+        lists::BRANCHES,
+        tenant.single_school(),
+        &(uri.to_string(), tenant.school_ids()),
+    )
+    .await;
+    if let Some(not_modified) = etag.not_modified(&headers) {
+        return Ok(not_modified);
     }
 
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    let branches =
-        BranchService::get_branches_by_level(&state.db, level_id, school_id, filters).await?;
+    let branches = state
+        .pools
+        .read_only(async |db| {
+            timed(
+                "branches.list",
+                BranchService::get_branches_by_level(db, &tenant, level_id, filters),
+            )
+            .await
+        })
+        .await?;
 
-    Ok(Json(branches))
+    Ok(etag.store(Json(branches).into_response()).await)
 }
 
 #[utoipa::path(
@@ -119,10 +129,11 @@ pub async fn get_branches(
         ("id" = Uuid, Path, description = "Branch ID")
     ),
     responses(
-        (status = 200, description = "Branch details", body = BranchWithStats),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires branches:read permission"),
-        (status = 404, description = "Branch not found")
+        WithEtag<BranchWithStats>,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires branches:read permission", body = ErrorResponse),
+        (status = 404, description = "Branch not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Branches",
     security(("bearer_auth" = []))
@@ -132,19 +143,14 @@ pub async fn get_branch_by_id(
     State(state): State<AppState>,
     RequireBranchesRead(auth_user): RequireBranchesRead,
     Path(id): Path<Uuid>,
-) -> Result<Json<BranchWithStats>, AppError> {
+) -> Result<WithEtag<BranchWithStats>, AppError> {
     let id = BranchId::from(id);
 
-    // System admins can view any branch
-    if is_system_admin_jwt(&auth_user) {
-        let branch = BranchService::get_branch_by_id_no_school_filter(&state.db, id).await?;
-        return Ok(Json(branch));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    let branch = BranchService::get_branch_by_id(&state.db, id, school_id).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    let branch =
+        BranchService::get_branch_by_id(&state.db, state.cache.as_ref(), &tenant, id).await?;
 
-    Ok(Json(branch))
+    Ok(WithEtag(branch))
 }
 
 #[utoipa::path(
@@ -152,15 +158,18 @@ pub async fn get_branch_by_id(
     path = "/api/branches/{id}",
     summary = "Update branch",
     params(
-        ("id" = Uuid, Path, description = "Branch ID")
+        ("id" = Uuid, Path, description = "Branch ID"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the branch as last read; the update is refused if it has changed since")
     ),
     request_body = UpdateBranchDto,
     responses(
-        (status = 200, description = "Branch updated successfully", body = Branch),
-        (status = 400, description = "Invalid input"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires branches:update permission"),
-        (status = 404, description = "Branch not found")
+        Conditional<Branch>,
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires branches:update permission", body = ErrorResponse),
+        (status = 404, description = "Branch not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Branches",
     security(("bearer_auth" = []))
@@ -170,25 +179,13 @@ pub async fn update_branch(
     State(state): State<AppState>,
     RequireBranchesUpdate(auth_user): RequireBranchesUpdate,
     Path(id): Path<Uuid>,
-    Json(dto): Json<UpdateBranchDto>,
-) -> Result<Json<Branch>, AppError> {
-    dto.validate()?;
-
+    if_match: IfMatch,
+    ValidatedJson(dto): ValidatedJson<UpdateBranchDto>,
+) -> Result<Conditional<Branch>, AppError> {
     let id = BranchId::from(id);
 
-    // System admins can update any branch
-    if is_system_admin_jwt(&auth_user) {
-        let branch =
-            BranchService::update_branch_no_school_filter(&state.db, state.cache.as_ref(), id, dto)
-                .await?;
-        return Ok(Json(branch));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    let branch =
-        BranchService::update_branch(&state.db, state.cache.as_ref(), id, school_id, dto).await?;
-
-    Ok(Json(branch))
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    BranchService::update_branch(&state.db, state.cache.as_ref(), &tenant, id, &if_match, dto).await
 }
 
 #[utoipa::path(
@@ -199,10 +196,11 @@ pub async fn update_branch(
         ("id" = Uuid, Path, description = "Branch ID")
     ),
     responses(
-        (status = 204, description = "Branch deleted successfully"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires branches:delete permission"),
-        (status = 404, description = "Branch not found")
+        NoContent,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires branches:delete permission", body = ErrorResponse),
+        (status = 404, description = "Branch not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Branches",
     security(("bearer_auth" = []))
@@ -212,20 +210,21 @@ pub async fn delete_branch(
     State(state): State<AppState>,
     RequireBranchesDelete(auth_user): RequireBranchesDelete,
     Path(id): Path<Uuid>,
-) -> Result<StatusCode, AppError> {
+) -> Result<NoContent, AppError> {
     let id = BranchId::from(id);
 
-    // System admins can delete any branch
-    if is_system_admin_jwt(&auth_user) {
-        BranchService::delete_branch_no_school_filter(&state.db, state.cache.as_ref(), id, None)
-            .await?;
-        return Ok(StatusCode::NO_CONTENT);
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    BranchService::delete_branch(&state.db, state.cache.as_ref(), id, school_id, None).await?;
-
-    Ok(StatusCode::NO_CONTENT)
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    BranchService::delete_branch(
+        &state.db,
+        state.cache.as_ref(),
+        &tenant,
+        id,
+        None,
+        auth_user.user_id()?,
+    )
+    .await?;
+
+    Ok(NoContent)
 }
 
 #[utoipa::path(
@@ -238,10 +237,12 @@ pub async fn delete_branch(
     request_body = AssignStudentsToBranchDto,
     responses(
         (status = 200, description = "Students assigned to branch", body = BulkAssignResponse),
-        (status = 400, description = "Invalid input"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires branches:assign_students permission"),
-        (status = 404, description = "Branch not found")
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires branches:assign_students permission", body = ErrorResponse),
+        (status = 404, description = "Branch not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Branches",
     security(("bearer_auth" = []))
@@ -251,21 +252,14 @@ pub async fn assign_students_to_branch(
     State(state): State<AppState>,
     RequireBranchesAssignStudents(auth_user): RequireBranchesAssignStudents,
     Path(id): Path<Uuid>,
-    Json(dto): Json<AssignStudentsToBranchDto>,
+    ValidatedJson(dto): ValidatedJson<AssignStudentsToBranchDto>,
 ) -> Result<Json<BulkAssignResponse>, AppError> {
-    dto.validate()?;
-
     let id = BranchId::from(id);
 
-    // System admins can assign students to any branch
-    if is_system_admin_jwt(&auth_user) {
-        let response =
-            BranchService::assign_students_to_branch_no_school_filter(&state.db, id, dto).await?;
-        return Ok(Json(response));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    let response = BranchService::assign_students_to_branch(&state.db, id, school_id, dto).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    let response =
+        BranchService::assign_students_to_branch(&state.db, state.cache.as_ref(), &tenant, id, dto)
+            .await?;
 
     Ok(Json(response))
 }
@@ -279,9 +273,10 @@ pub async fn assign_students_to_branch(
     ),
     responses(
         (status = 200, description = "List of students in branch", body = Vec<User>),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires branches:read permission"),
-        (status = 404, description = "Branch not found")
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires branches:read permission", body = ErrorResponse),
+        (status = 404, description = "Branch not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Branches",
     security(("bearer_auth" = []))
@@ -294,15 +289,8 @@ pub async fn get_students_in_branch(
 ) -> Result<Json<Vec<User>>, AppError> {
     let id = BranchId::from(id);
 
-    // System admins can view students in any branch
-    if is_system_admin_jwt(&auth_user) {
-        let students =
-            BranchService::get_students_in_branch_no_school_filter(&state.db, id).await?;
-        return Ok(Json(students));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    let students = BranchService::get_students_in_branch(&state.db, id, school_id).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    let students = BranchService::get_students_in_branch(&state.db, &tenant, id).await?;
 
     Ok(Json(students))
 }
@@ -316,11 +304,13 @@ pub async fn get_students_in_branch(
     ),
     request_body = MoveStudentToBranchDto,
     responses(
-        (status = 204, description = "Student moved successfully"),
-        (status = 400, description = "Invalid input"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires branches:assign_students permission"),
-        (status = 404, description = "Student or branch not found")
+        NoContent,
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires branches:assign_students permission", body = ErrorResponse),
+        (status = 404, description = "Student or branch not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Branches",
     security(("bearer_auth" = []))
@@ -330,22 +320,21 @@ pub async fn move_student_to_branch(
     State(state): State<AppState>,
     RequireBranchesAssignStudents(auth_user): RequireBranchesAssignStudents,
     Path(student_id): Path<Uuid>,
-    Json(dto): Json<MoveStudentToBranchDto>,
-) -> Result<StatusCode, AppError> {
-    dto.validate()?;
-
+    ValidatedJson(dto): ValidatedJson<MoveStudentToBranchDto>,
+) -> Result<NoContent, AppError> {
     let student_id = UserId::from(student_id);
 
-    // System admins can move any student
-    if is_system_admin_jwt(&auth_user) {
-        BranchService::move_student_to_branch_no_school_filter(&state.db, student_id, dto).await?;
-        return Ok(StatusCode::NO_CONTENT);
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    BranchService::move_student_to_branch(&state.db, student_id, school_id, dto).await?;
-
-    Ok(StatusCode::NO_CONTENT)
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    BranchService::move_student_to_branch(
+        &state.db,
+        state.cache.as_ref(),
+        &tenant,
+        student_id,
+        dto,
+    )
+    .await?;
+
+    Ok(NoContent)
 }
 
 #[utoipa::path(
@@ -356,10 +345,11 @@ pub async fn move_student_to_branch(
         ("student_id" = Uuid, Path, description = "Student ID")
     ),
     responses(
-        (status = 204, description = "Student removed from branch"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires branches:assign_students permission"),
-        (status = 404, description = "Student not found")
+        NoContent,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires branches:assign_students permission", body = ErrorResponse),
+        (status = 404, description = "Student not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Branches",
     security(("bearer_auth" = []))
@@ -369,17 +359,123 @@ pub async fn remove_student_from_branch(
     State(state): State<AppState>,
     RequireBranchesAssignStudents(auth_user): RequireBranchesAssignStudents,
     Path(student_id): Path<Uuid>,
-) -> Result<StatusCode, AppError> {
+) -> Result<NoContent, AppError> {
     let student_id = UserId::from(student_id);
 
-    // System admins can remove any student from branch
-    if is_system_admin_jwt(&auth_user) {
-        BranchService::remove_student_from_branch_no_school_filter(&state.db, student_id).await?;
-        return Ok(StatusCode::NO_CONTENT);
-    }
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    BranchService::remove_student_from_branch(&state.db, state.cache.as_ref(), &tenant, student_id)
+        .await?;
+
+    Ok(NoContent)
+}
+
+/// Assign a teacher to a branch
+///
+/// A branch can have several teachers at once. The capacity decides what the
+/// teacher may do: assistants can mark attendance but cannot finalize grades.
+#[utoipa::path(
+    post,
+    path = "/api/branches/{id}/teachers",
+    summary = "Assign teacher to branch",
+    params(
+        ("id" = Uuid, Path, description = "Branch ID")
+    ),
+    request_body = AssignBranchTeacherDto,
+    responses(
+        Created<BranchTeacher>,
+        (status = 400, description = "Invalid input, not a teacher, or overlapping assignment", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires branches:assign_teachers permission", body = ErrorResponse),
+        (status = 404, description = "Branch not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Branches",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn assign_teacher_to_branch(
+    State(state): State<AppState>,
+    RequireBranchesAssignTeachers(auth_user): RequireBranchesAssignTeachers,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<AssignBranchTeacherDto>,
+) -> Result<Created<BranchTeacher>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assigned_by = auth_user.user_id()?;
+    let teacher =
+        BranchService::assign_teacher(&state.db, BranchId::from(id), school_id, assigned_by, dto)
+            .await?;
+
+    Ok(Created(teacher))
+}
+
+/// List the teachers assigned to a branch
+#[utoipa::path(
+    get,
+    path = "/api/branches/{id}/teachers",
+    summary = "Get branch teachers",
+    params(
+        ("id" = Uuid, Path, description = "Branch ID"),
+        BranchTeacherQueryParams
+    ),
+    responses(
+        (status = 200, description = "Teacher assignments, leads first", body = Vec<BranchTeacher>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires branches:read permission", body = ErrorResponse),
+        (status = 404, description = "Branch not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Branches",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_branch_teachers(
+    State(state): State<AppState>,
+    RequireBranchesRead(auth_user): RequireBranchesRead,
+    Path(id): Path<Uuid>,
+    Query(params): Query<BranchTeacherQueryParams>,
+) -> Result<Json<Vec<BranchTeacher>>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let teachers =
+        BranchService::get_branch_teachers(&state.db, BranchId::from(id), school_id, params)
+            .await?;
 
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    BranchService::remove_student_from_branch(&state.db, student_id, school_id).await?;
+    Ok(Json(teachers))
+}
 
-    Ok(StatusCode::NO_CONTENT)
+/// Remove a teacher assignment from a branch
+#[utoipa::path(
+    delete,
+    path = "/api/branches/{id}/teachers/{assignment_id}",
+    summary = "Remove teacher from branch",
+    params(
+        ("id" = Uuid, Path, description = "Branch ID"),
+        ("assignment_id" = Uuid, Path, description = "Teacher assignment ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires branches:assign_teachers permission", body = ErrorResponse),
+        (status = 404, description = "Branch or assignment not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Branches",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn remove_teacher_from_branch(
+    State(state): State<AppState>,
+    RequireBranchesAssignTeachers(auth_user): RequireBranchesAssignTeachers,
+    Path((id, assignment_id)): Path<(Uuid, Uuid)>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    BranchService::remove_teacher(
+        &state.db,
+        BranchId::from(id),
+        BranchTeacherId::from(assignment_id),
+        school_id,
+    )
+    .await?;
+
+    Ok(NoContent)
 }
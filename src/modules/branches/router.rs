@@ -6,8 +6,9 @@ use axum::{
 use crate::state::AppState;
 
 use super::controller::{
-    assign_students_to_branch, create_branch, delete_branch, get_branch_by_id, get_branches,
-    get_students_in_branch, move_student_to_branch, remove_student_from_branch, update_branch,
+    assign_students_to_branch, assign_teacher_to_branch, create_branch, delete_branch,
+    get_branch_by_id, get_branch_teachers, get_branches, get_students_in_branch,
+    move_student_to_branch, remove_student_from_branch, remove_teacher_from_branch, update_branch,
 };
 
 pub fn init_branches_router() -> Router<AppState> {
@@ -18,6 +19,14 @@ pub fn init_branches_router() -> Router<AppState> {
             "/{id}/students",
             post(assign_students_to_branch).get(get_students_in_branch),
         )
+        .route(
+            "/{id}/teachers",
+            post(assign_teacher_to_branch).get(get_branch_teachers),
+        )
+        .route(
+            "/{id}/teachers/{assignment_id}",
+            delete(remove_teacher_from_branch),
+        )
         .route(
             "/{id}",
             get(get_branch_by_id)
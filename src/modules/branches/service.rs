@@ -1,43 +1,66 @@
-use sqlx::PgPool;
+use std::time::Duration;
+
+use chrono::{NaiveDate, Utc};
+use sqlx::{Acquire, PgConnection, PgExecutor, PgPool, Postgres};
 use tracing::instrument;
 
-use chalkbyte_cache::{RedisCache, invalidate};
-use chalkbyte_core::{AppError, PaginationMeta};
-use chalkbyte_models::ids::{BranchId, LevelId, SchoolId, UserId};
+use chalkbyte_cache::{RedisCache, invalidate, keys};
+use chalkbyte_core::{AppError, Conditional, ErrorCode, IfMatch, PaginationMeta, Sortable};
+use chalkbyte_models::ids::{BranchId, BranchTeacherId, LevelId, SchoolId, UserId};
 
+use crate::config::database::{TenantContext, UnitOfWork};
+use crate::modules::students::service::{AssignmentTarget, StudentService};
+use crate::modules::trash::service::TrashService;
 use crate::modules::users::model::system_roles;
+use crate::modules::webhooks::model::WebhookEvent;
+use crate::modules::webhooks::service::WebhookService;
 
 use super::model::{
-    AssignStudentsToBranchDto, Branch, BranchFilterParams, BranchWithStats, BulkAssignResponse,
-    CreateBranchDto, MoveStudentToBranchDto, PaginatedBranchesResponse, UpdateBranchDto,
+    AssignBranchTeacherDto, AssignStudentsToBranchDto, Branch, BranchFilterParams, BranchTeacher,
+    BranchTeacherQueryParams, BranchWithStats, BulkAssignResponse, CreateBranchDto,
+    MoveStudentToBranchDto, PaginatedBranchesResponse, TeacherCapacity, UpdateBranchDto,
+};
+
+/// How long branch stats are served before a single caller recomputes them.
+const STATS_CACHE_TTL: Duration = Duration::from_secs(30);
+
+const BRANCH_TEACHER_COLUMNS: &str = "bt.id, bt.branch_id, bt.teacher_id, u.first_name, u.last_name, \
+     bt.capacity, bt.starts_on, bt.ends_on, bt.assigned_by, bt.created_at";
+
+/// Fields the branch list can be sorted by
+const BRANCH_SORT: Sortable = Sortable {
+    columns: &[
+        ("name", "b.name"),
+        ("student_count", "student_count"),
+        ("created_at", "b.created_at"),
+        ("updated_at", "b.updated_at"),
+    ],
+    default: "-created_at",
+    tiebreaker: "b.id",
 };
 
 pub struct BranchService;
 
 impl BranchService {
+    /// Create a branch in a level the tenant can see. Runs on the pool or on
+    /// a transaction it is composed in.
     #[instrument(skip(db, cache))]
     pub async fn create_branch(
-        db: &PgPool,
+        db: impl Acquire<'_, Database = Postgres>,
         cache: Option<&RedisCache>,
+        tenant: &TenantContext,
         level_id: LevelId,
-        school_id: SchoolId,
         dto: CreateBranchDto,
     ) -> Result<Branch, AppError> {
-        let level = sqlx::query!(
-            r#"SELECT id, school_id FROM levels WHERE id = $1"#,
-            level_id.into_inner()
+        let mut conn = db.acquire().await?;
+        Self::ensure_level_access(
+            &mut *conn,
+            tenant,
+            level_id,
+            "Cannot create branch for level in another school",
         )
-        .fetch_optional(db)
         .await?;
 
-        let level = level.ok_or_else(|| AppError::not_found(anyhow::anyhow!("Level not found")))?;
-
-        if level.school_id != school_id.into_inner() {
-            return Err(AppError::forbidden(
-                "Cannot create branch for level in another school".to_string(),
-            ));
-        }
-
         let branch = sqlx::query_as!(
             Branch,
             r#"
@@ -49,122 +72,149 @@ impl BranchService {
             dto.description,
             level_id.into_inner()
         )
-        .fetch_one(db)
+        .fetch_one(&mut *conn)
         .await
         .map_err(|e| {
             if let sqlx::Error::Database(db_err) = &e
                 && db_err.is_unique_violation()
             {
-                return AppError::bad_request(anyhow::anyhow!(
-                    "Branch with this name already exists for this level"
-                ));
+                return AppError::from_code(ErrorCode::BranchNameConflict);
             }
             AppError::from(e)
         })?;
 
-        invalidate::branch(cache, Some(branch.id.into()), Some(level_id.into_inner())).await;
+        invalidate::branch(
+            cache,
+            Some(branch.id.into()),
+            Some(level_id.into_inner()),
+            tenant.single_school(),
+        )
+        .await;
 
         Ok(branch)
     }
 
+    /// Check that a level exists and belongs to one of the tenant's schools.
+    ///
+    /// Looked up unscoped, so that another school's level is reported as
+    /// forbidden rather than missing.
+    async fn ensure_level_access(
+        db: impl PgExecutor<'_>,
+        tenant: &TenantContext,
+        level_id: LevelId,
+        denied: &str,
+    ) -> Result<(), AppError> {
+        let school_id =
+            sqlx::query_scalar::<_, SchoolId>("SELECT school_id FROM levels WHERE id = $1")
+                .bind(level_id)
+                .fetch_optional(db)
+                .await?
+                .ok_or_else(|| AppError::from_code(ErrorCode::LevelNotFound))?;
+
+        if !tenant.allows(school_id) {
+            return Err(AppError::forbidden(denied.to_string()));
+        }
+
+        Ok(())
+    }
+
     #[instrument(skip(db))]
     pub async fn get_branches_by_level(
         db: &PgPool,
+        tenant: &TenantContext,
         level_id: LevelId,
-        school_id: SchoolId,
         filters: BranchFilterParams,
     ) -> Result<PaginatedBranchesResponse, AppError> {
-        let level = sqlx::query!(
-            r#"SELECT id, school_id FROM levels WHERE id = $1"#,
-            level_id.into_inner()
+        Self::ensure_level_access(
+            db,
+            tenant,
+            level_id,
+            "Cannot access branches for level in another school",
         )
-        .fetch_optional(db)
         .await?;
 
-        let level = level.ok_or_else(|| AppError::not_found(anyhow::anyhow!("Level not found")))?;
-
-        if level.school_id != school_id.into_inner() {
-            return Err(AppError::forbidden(
-                "Cannot access branches for level in another school".to_string(),
-            ));
-        }
-
         let page = filters.pagination.page();
         let limit = filters.pagination.limit();
         let offset = filters.pagination.offset();
+        let order_by = BRANCH_SORT.order_by(&filters.sort)?;
 
         let student_role_id = system_roles::STUDENT;
-        let branches = if let Some(name) = &filters.name {
-            sqlx::query_as::<_, BranchWithStats>(
-                r#"
-                SELECT
-                    b.id,
-                    b.name,
-                    b.description,
-                    b.level_id,
-                    b.created_at,
-                    b.updated_at,
-                    COUNT(DISTINCT CASE WHEN ur.role_id IS NOT NULL THEN u.id END)::bigint as student_count
-                FROM branches b
-                LEFT JOIN users u ON u.branch_id = b.id
-                LEFT JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $5
-                WHERE b.level_id = $1 AND b.name ILIKE $2
-                GROUP BY b.id
-                ORDER BY b.created_at DESC
-                LIMIT $3 OFFSET $4
-                "#,
-            )
-            .bind(level_id.into_inner())
-            .bind(format!("%{}%", name))
-            .bind(limit)
-            .bind(offset)
-            .bind(student_role_id)
-            .fetch_all(db)
-            .await?
-        } else {
-            sqlx::query_as::<_, BranchWithStats>(
-                r#"
-                SELECT
-                    b.id,
-                    b.name,
-                    b.description,
-                    b.level_id,
-                    b.created_at,
-                    b.updated_at,
-                    COUNT(DISTINCT CASE WHEN ur.role_id IS NOT NULL THEN u.id END)::bigint as student_count
-                FROM branches b
-                LEFT JOIN users u ON u.branch_id = b.id
-                LEFT JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $4
-                WHERE b.level_id = $1
-                GROUP BY b.id
-                ORDER BY b.created_at DESC
-                LIMIT $2 OFFSET $3
-                "#,
-            )
-            .bind(level_id.into_inner())
-            .bind(limit)
-            .bind(offset)
-            .bind(student_role_id)
-            .fetch_all(db)
-            .await?
-        };
+        let (branches, total_query) = UnitOfWork::run_as(db, tenant, async |uow| {
+            let branches = if let Some(name) = &filters.name {
+                sqlx::query_as::<_, BranchWithStats>(&format!(
+                    r#"
+                    SELECT
+                        b.id,
+                        b.name,
+                        b.description,
+                        b.level_id,
+                        b.created_at,
+                        b.updated_at,
+                        COUNT(DISTINCT CASE WHEN ur.role_id IS NOT NULL THEN u.id END)::bigint as student_count
+                    FROM branches b
+                    LEFT JOIN users u ON u.branch_id = b.id
+                    LEFT JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $5
+                    WHERE b.level_id = $1 AND b.name ILIKE $2
+                    GROUP BY b.id
+                    ORDER BY {order_by}
+                    LIMIT $3 OFFSET $4
+                    "#
+                ))
+                .bind(level_id.into_inner())
+                .bind(format!("%{}%", name))
+                .bind(limit)
+                .bind(offset)
+                .bind(student_role_id)
+                .fetch_all(uow.conn())
+                .await?
+            } else {
+                sqlx::query_as::<_, BranchWithStats>(&format!(
+                    r#"
+                    SELECT
+                        b.id,
+                        b.name,
+                        b.description,
+                        b.level_id,
+                        b.created_at,
+                        b.updated_at,
+                        COUNT(DISTINCT CASE WHEN ur.role_id IS NOT NULL THEN u.id END)::bigint as student_count
+                    FROM branches b
+                    LEFT JOIN users u ON u.branch_id = b.id
+                    LEFT JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $4
+                    WHERE b.level_id = $1
+                    GROUP BY b.id
+                    ORDER BY {order_by}
+                    LIMIT $2 OFFSET $3
+                    "#
+                ))
+                .bind(level_id.into_inner())
+                .bind(limit)
+                .bind(offset)
+                .bind(student_role_id)
+                .fetch_all(uow.conn())
+                .await?
+            };
 
-        let total_query = if let Some(name) = &filters.name {
-            sqlx::query_scalar!(
-                "SELECT COUNT(*) FROM branches WHERE level_id = $1 AND name ILIKE $2",
-                level_id.into_inner(),
-                format!("%{}%", name)
-            )
-            .fetch_one(db)
-            .await?
-        } else {
-            sqlx::query_scalar!(
-                "SELECT COUNT(*) FROM branches WHERE level_id = $1",
-                level_id.into_inner()
-            )
-            .fetch_one(db)
-            .await?
-        };
+            let total_query = if let Some(name) = &filters.name {
+                sqlx::query_scalar!(
+                    "SELECT COUNT(*) FROM branches WHERE level_id = $1 AND name ILIKE $2",
+                    level_id.into_inner(),
+                    format!("%{}%", name)
+                )
+                .fetch_one(uow.conn())
+                .await?
+            } else {
+                sqlx::query_scalar!(
+                    "SELECT COUNT(*) FROM branches WHERE level_id = $1",
+                    level_id.into_inner()
+                )
+                .fetch_one(uow.conn())
+                .await?
+            };
+
+            Ok::<_, AppError>((branches, total_query))
+        })
+        .await?;
 
         let total = total_query.unwrap_or(0);
 
@@ -180,11 +230,33 @@ impl BranchService {
         })
     }
 
-    #[instrument(skip(db))]
+    /// Get a branch with its student count, cached with single-flight
+    /// recompute when the caller is scoped to one school
+    #[instrument(skip(db, cache))]
     pub async fn get_branch_by_id(
         db: &PgPool,
+        cache: Option<&RedisCache>,
+        tenant: &TenantContext,
+        id: BranchId,
+    ) -> Result<BranchWithStats, AppError> {
+        let fetch = || {
+            UnitOfWork::run_as(db, tenant, async |uow| {
+                Self::fetch_branch_with_stats(uow.conn(), id).await
+            })
+        };
+        let (Some(cache), Some(&[school_id])) = (cache, tenant.school_ids()) else {
+            return fetch().await;
+        };
+
+        let cache_key = keys::branches::stats(id.into(), school_id);
+        cache
+            .get_or_compute(&cache_key, STATS_CACHE_TTL, fetch)
+            .await
+    }
+
+    async fn fetch_branch_with_stats(
+        db: impl PgExecutor<'_>,
         id: BranchId,
-        school_id: SchoolId,
     ) -> Result<BranchWithStats, AppError> {
         let student_role_id = system_roles::STUDENT;
         let branch = sqlx::query_as::<_, BranchWithStats>(
@@ -198,86 +270,113 @@ impl BranchService {
                 b.updated_at,
                 COUNT(DISTINCT CASE WHEN ur.role_id IS NOT NULL THEN u.id END)::bigint as student_count
             FROM branches b
-            INNER JOIN levels l ON l.id = b.level_id
             LEFT JOIN users u ON u.branch_id = b.id
-            LEFT JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $3
-            WHERE b.id = $1 AND l.school_id = $2
+            LEFT JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $2
+            WHERE b.id = $1
             GROUP BY b.id
             "#,
         )
         .bind(id.into_inner())
-        .bind(school_id.into_inner())
         .bind(student_role_id)
         .fetch_optional(db)
         .await?;
 
-        branch.ok_or_else(|| AppError::not_found(anyhow::anyhow!("Branch not found")))
+        branch.ok_or_else(|| AppError::from_code(ErrorCode::BranchNotFound))
+    }
+
+    async fn ensure_branch_exists(
+        db: impl PgExecutor<'_>,
+        branch_id: BranchId,
+    ) -> Result<(), AppError> {
+        let exists =
+            sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM branches WHERE id = $1)")
+                .bind(branch_id)
+                .fetch_one(db)
+                .await?;
+
+        if !exists {
+            return Err(AppError::from_code(ErrorCode::BranchNotFound));
+        }
+
+        Ok(())
     }
 
+    /// Update a branch. Leaves it unchanged when it no longer matches
+    /// `if_match`.
     #[instrument(skip(db))]
     pub async fn update_branch(
         db: &PgPool,
         cache: Option<&RedisCache>,
+        tenant: &TenantContext,
         id: BranchId,
-        school_id: SchoolId,
+        if_match: &IfMatch,
         dto: UpdateBranchDto,
-    ) -> Result<Branch, AppError> {
-        let existing = sqlx::query!(
-            r#"
-            SELECT b.id
-            FROM branches b
-            INNER JOIN levels l ON l.id = b.level_id
-            WHERE b.id = $1 AND l.school_id = $2
-            "#,
-            id.into_inner(),
-            school_id.into_inner()
-        )
-        .fetch_optional(db)
-        .await?;
-
-        if existing.is_none() {
-            return Err(AppError::not_found(anyhow::anyhow!("Branch not found")));
-        }
+    ) -> Result<Conditional<Branch>, AppError> {
+        let branch = UnitOfWork::run_as(db, tenant, async |uow| {
+            let existing = sqlx::query_as::<_, Branch>(
+                "SELECT id, name, description, level_id, created_at, updated_at
+                 FROM branches WHERE id = $1 FOR UPDATE",
+            )
+            .bind(id)
+            .fetch_optional(uow.conn())
+            .await?
+            .ok_or_else(|| AppError::from_code(ErrorCode::BranchNotFound))?;
 
-        let mut query = String::from("UPDATE branches SET updated_at = NOW()");
-        let mut param_count = 1;
+            if !if_match.matches(&existing) {
+                return Ok(Conditional::PreconditionFailed(existing));
+            }
 
-        if dto.name.is_some() {
-            param_count += 1;
-            query.push_str(&format!(", name = ${}", param_count));
-        }
+            let mut query = String::from("UPDATE branches SET updated_at = NOW()");
+            let mut param_count = 1;
 
-        if dto.description.is_some() {
-            param_count += 1;
-            query.push_str(&format!(", description = ${}", param_count));
-        }
+            if dto.name.is_some() {
+                param_count += 1;
+                query.push_str(&format!(", name = ${}", param_count));
+            }
 
-        query.push_str(
-            " WHERE id = $1 RETURNING id, name, description, level_id, created_at, updated_at",
-        );
+            if dto.description.is_some() {
+                param_count += 1;
+                query.push_str(&format!(", description = ${}", param_count));
+            }
 
-        let mut query_builder = sqlx::query_as::<_, Branch>(&query).bind(id.into_inner());
+            query.push_str(
+                " WHERE id = $1 RETURNING id, name, description, level_id, created_at, updated_at",
+            );
 
-        if let Some(name) = dto.name {
-            query_builder = query_builder.bind(name);
-        }
+            let mut query_builder = sqlx::query_as::<_, Branch>(&query).bind(id.into_inner());
 
-        if let Some(description) = dto.description {
-            query_builder = query_builder.bind(description);
-        }
+            if let Some(name) = dto.name {
+                query_builder = query_builder.bind(name);
+            }
 
-        let branch = query_builder.fetch_one(db).await.map_err(|e| {
-            if let sqlx::Error::Database(db_err) = &e
-                && db_err.is_unique_violation()
-            {
-                return AppError::bad_request(anyhow::anyhow!(
-                    "Branch with this name already exists for this level"
-                ));
+            if let Some(description) = dto.description {
+                query_builder = query_builder.bind(description);
             }
-            AppError::from(e)
-        })?;
 
-        invalidate::branch(cache, Some(id.into_inner()), Some(branch.level_id.into())).await;
+            query_builder
+                .fetch_one(uow.conn())
+                .await
+                .map(Conditional::Updated)
+                .map_err(|e| {
+                    if let sqlx::Error::Database(db_err) = &e
+                        && db_err.is_unique_violation()
+                    {
+                        return AppError::from_code(ErrorCode::BranchNameConflict);
+                    }
+                    AppError::from(e)
+                })
+        })
+        .await?;
+
+        if let Conditional::Updated(branch) = &branch {
+            invalidate::branch(
+                cache,
+                Some(id.into_inner()),
+                Some(branch.level_id.into()),
+                tenant.single_school(),
+            )
+            .await;
+        }
 
         Ok(branch)
     }
@@ -286,99 +385,62 @@ impl BranchService {
     pub async fn delete_branch(
         db: &PgPool,
         cache: Option<&RedisCache>,
+        tenant: &TenantContext,
         id: BranchId,
-        school_id: SchoolId,
         level_id: Option<LevelId>,
+        deleted_by: UserId,
     ) -> Result<(), AppError> {
-        let result = sqlx::query!(
-            r#"
-            DELETE FROM branches
-            WHERE id = $1 AND level_id IN (
-                SELECT id FROM levels WHERE school_id = $2
-            )
-            "#,
-            id.into_inner(),
-            school_id.into_inner()
-        )
-        .execute(db)
-        .await?;
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            TrashService::trash_branch(uow.conn(), id, deleted_by).await?;
 
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!("Branch not found")));
-        }
+            let result = sqlx::query!(r#"DELETE FROM branches WHERE id = $1"#, id.into_inner())
+                .execute(uow.conn())
+                .await?;
+
+            if result.rows_affected() == 0 {
+                return Err(AppError::from_code(ErrorCode::BranchNotFound));
+            }
+
+            Ok(())
+        })
+        .await?;
 
         invalidate::branch(
             cache,
             Some(id.into_inner()),
             level_id.map(|l| l.into_inner()),
+            tenant.single_school(),
         )
         .await;
 
         Ok(())
     }
 
-    #[instrument(skip(db))]
+    /// Move students into a branch with one role check and one batched update.
+    ///
+    /// IDs that are not students, or that belong to another school than the
+    /// branch, are reported in `failed_ids`.
+    #[instrument(skip(db, cache))]
     pub async fn assign_students_to_branch(
         db: &PgPool,
+        cache: Option<&RedisCache>,
+        tenant: &TenantContext,
         branch_id: BranchId,
-        school_id: SchoolId,
         dto: AssignStudentsToBranchDto,
     ) -> Result<BulkAssignResponse, AppError> {
-        let branch = sqlx::query!(
-            r#"
-            SELECT b.id
-            FROM branches b
-            INNER JOIN levels l ON l.id = b.level_id
-            WHERE b.id = $1 AND l.school_id = $2
-            "#,
-            branch_id.into_inner(),
-            school_id.into_inner()
-        )
-        .fetch_optional(db)
-        .await?;
-
-        if branch.is_none() {
-            return Err(AppError::not_found(anyhow::anyhow!("Branch not found")));
-        }
+        let (assigned_count, failed_ids) = UnitOfWork::run_as(db, tenant, async |uow| {
+            Self::ensure_branch_exists(uow.conn(), branch_id).await?;
 
-        let mut assigned_count = 0;
-        let mut failed_ids = Vec::new();
-
-        let student_role_id = system_roles::STUDENT;
-        for student_id in dto.student_ids {
-            // Check if user has student role
-            let is_student = sqlx::query_scalar::<_, bool>(
-                "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
+            StudentService::batch_assign_students(
+                uow.conn(),
+                &dto.student_ids,
+                AssignmentTarget::Branch(branch_id),
             )
-            .bind(student_id.into_inner())
-            .bind(student_role_id)
-            .fetch_one(db)
             .await
-            .unwrap_or(false);
-
-            if !is_student {
-                failed_ids.push(student_id);
-                continue;
-            }
-
-            let result = sqlx::query(
-                r#"
-                UPDATE users
-                SET branch_id = $1, updated_at = NOW()
-                WHERE id = $2 AND school_id = $3
-                "#,
-            )
-            .bind(branch_id.into_inner())
-            .bind(student_id.into_inner())
-            .bind(school_id.into_inner())
-            .execute(db)
-            .await;
+        })
+        .await?;
 
-            match result {
-                Ok(res) if res.rows_affected() > 0 => assigned_count += 1,
-                _ => failed_ids.push(student_id),
-            }
-        }
+        invalidate::user(cache, None, tenant.single_school()).await;
 
         Ok(BulkAssignResponse {
             assigned_count,
@@ -386,62 +448,26 @@ impl BranchService {
         })
     }
 
-    #[instrument(skip(db))]
+    /// Move a student into a branch of their school, or out of their branch
+    /// when `branch_id` is empty.
+    #[instrument(skip(db, cache))]
     pub async fn move_student_to_branch(
         db: &PgPool,
+        cache: Option<&RedisCache>,
+        tenant: &TenantContext,
         student_id: UserId,
-        school_id: SchoolId,
         dto: MoveStudentToBranchDto,
     ) -> Result<(), AppError> {
-        if let Some(branch_id) = dto.branch_id {
-            let branch = sqlx::query!(
-                r#"
-                SELECT b.id
-                FROM branches b
-                INNER JOIN levels l ON l.id = b.level_id
-                WHERE b.id = $1 AND l.school_id = $2
-                "#,
-                branch_id.into_inner(),
-                school_id.into_inner()
-            )
-            .fetch_optional(db)
-            .await?;
-
-            if branch.is_none() {
-                return Err(AppError::not_found(anyhow::anyhow!("Branch not found")));
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            if let Some(branch_id) = dto.branch_id {
+                Self::ensure_branch_exists(uow.conn(), branch_id).await?;
             }
-        }
-
-        // Check if user has student role
-        let student_role_id = system_roles::STUDENT;
-        let is_student = sqlx::query_scalar::<_, bool>(
-            "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
-        )
-        .bind(student_id.into_inner())
-        .bind(student_role_id)
-        .fetch_one(db)
-        .await?;
-
-        if !is_student {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
-        }
 
-        let result = sqlx::query(
-            r#"
-            UPDATE users
-            SET branch_id = $1, updated_at = NOW()
-            WHERE id = $2 AND school_id = $3
-            "#,
-        )
-        .bind(dto.branch_id.map(|b| b.into_inner()))
-        .bind(student_id.into_inner())
-        .bind(school_id.into_inner())
-        .execute(db)
+            Self::set_student_branch(uow.conn(), student_id, dto.branch_id).await
+        })
         .await?;
 
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
-        }
+        invalidate::user(cache, Some(student_id.into()), tenant.single_school()).await;
 
         Ok(())
     }
@@ -449,554 +475,349 @@ impl BranchService {
     #[instrument(skip(db))]
     pub async fn get_students_in_branch(
         db: &PgPool,
+        tenant: &TenantContext,
         branch_id: BranchId,
-        school_id: SchoolId,
     ) -> Result<Vec<crate::modules::users::model::User>, AppError> {
-        let branch = sqlx::query!(
-            r#"
-            SELECT b.id
-            FROM branches b
-            INNER JOIN levels l ON l.id = b.level_id
-            WHERE b.id = $1 AND l.school_id = $2
-            "#,
-            branch_id.into_inner(),
-            school_id.into_inner()
-        )
-        .fetch_optional(db)
-        .await?;
-
-        if branch.is_none() {
-            return Err(AppError::not_found(anyhow::anyhow!("Branch not found")));
-        }
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            Self::ensure_branch_exists(uow.conn(), branch_id).await?;
 
-        let student_role_id = system_roles::STUDENT;
-        let students = sqlx::query_as::<_, crate::modules::users::model::User>(
-            r#"
-            SELECT
-                u.id,
-                u.first_name,
-                u.last_name,
-                u.email,
-                u.school_id,
-                u.level_id,
-                u.branch_id,
-                u.date_of_birth,
-                u.grade_level,
-                u.created_at,
-                u.updated_at
-            FROM users u
-            INNER JOIN user_roles ur ON ur.user_id = u.id
-            WHERE u.branch_id = $1 AND ur.role_id = $2
-            ORDER BY u.last_name, u.first_name
-            "#,
-        )
-        .bind(branch_id.into_inner())
-        .bind(student_role_id)
-        .fetch_all(db)
-        .await?;
+            let student_role_id = system_roles::STUDENT;
+            let students = sqlx::query_as::<_, crate::modules::users::model::User>(
+                r#"
+                SELECT
+                    u.id,
+                    u.first_name,
+                    u.last_name,
+                    u.email,
+                    u.school_id,
+                    u.level_id,
+                    u.branch_id,
+                    u.date_of_birth,
+                    u.grade_level,
+                    u.custom_fields,
+                    u.created_at,
+                    u.updated_at
+                FROM users u
+                INNER JOIN user_roles ur ON ur.user_id = u.id
+                WHERE u.branch_id = $1 AND ur.role_id = $2
+                ORDER BY u.last_name, u.first_name
+                "#,
+            )
+            .bind(branch_id.into_inner())
+            .bind(student_role_id)
+            .fetch_all(uow.conn())
+            .await?;
 
-        Ok(students)
+            Ok(students)
+        })
+        .await
     }
 
-    #[instrument(skip(db))]
+    #[instrument(skip(db, cache))]
     pub async fn remove_student_from_branch(
         db: &PgPool,
+        cache: Option<&RedisCache>,
+        tenant: &TenantContext,
         student_id: UserId,
-        school_id: SchoolId,
     ) -> Result<(), AppError> {
-        let student_role_id = system_roles::STUDENT;
-        let result = sqlx::query(
-            r#"
-            UPDATE users
-            SET branch_id = NULL, updated_at = NOW()
-            WHERE id = $1 AND school_id = $2
-            AND EXISTS (
-                SELECT 1 FROM user_roles ur
-                WHERE ur.user_id = $1 AND ur.role_id = $3
-            )
-            "#,
-        )
-        .bind(student_id.into_inner())
-        .bind(school_id.into_inner())
-        .bind(student_role_id)
-        .execute(db)
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            Self::set_student_branch(uow.conn(), student_id, None).await
+        })
         .await?;
 
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
-        }
+        invalidate::user(cache, Some(student_id.into()), tenant.single_school()).await;
 
         Ok(())
     }
 
-    // =====================================================
-    // No school filter variants for system admin operations
-    // =====================================================
-
-    #[instrument(skip(db))]
-    pub async fn create_branch_no_school_filter(
-        db: &PgPool,
-        cache: Option<&RedisCache>,
-        level_id: LevelId,
-        dto: CreateBranchDto,
-    ) -> Result<Branch, AppError> {
-        // Verify level exists (no school check)
-        let level = sqlx::query!(
-            r#"SELECT id FROM levels WHERE id = $1"#,
-            level_id.into_inner()
-        )
-        .fetch_optional(db)
-        .await?;
-
-        if level.is_none() {
-            return Err(AppError::not_found(anyhow::anyhow!("Level not found")));
-        }
-
-        let branch = sqlx::query_as!(
-            Branch,
+    /// Set a student's branch, keeping them within the branch's school, and
+    /// queue a `student.moved_branch` webhook event when the branch changes.
+    async fn set_student_branch(
+        conn: &mut PgConnection,
+        student_id: UserId,
+        branch_id: Option<BranchId>,
+    ) -> Result<(), AppError> {
+        let moved = sqlx::query_as::<_, (Option<BranchId>, Option<SchoolId>)>(
             r#"
-            INSERT INTO branches (name, description, level_id)
-            VALUES ($1, $2, $3)
-            RETURNING id, name, description, level_id, created_at, updated_at
+            WITH previous AS (
+                SELECT id, branch_id FROM users
+                WHERE id = $2
+                AND ($1::uuid IS NULL OR school_id = (
+                    SELECT l.school_id FROM branches b
+                    INNER JOIN levels l ON l.id = b.level_id
+                    WHERE b.id = $1
+                ))
+                AND EXISTS (
+                    SELECT 1 FROM user_roles ur
+                    WHERE ur.user_id = $2 AND ur.role_id = $3
+                )
+                FOR UPDATE
+            )
+            UPDATE users u
+            SET branch_id = $1, updated_at = NOW()
+            FROM previous p
+            WHERE u.id = p.id
+            RETURNING p.branch_id, u.school_id
             "#,
-            dto.name,
-            dto.description,
-            level_id.into_inner()
-        )
-        .fetch_one(db)
-        .await
-        .map_err(|e| {
-            if let sqlx::Error::Database(db_err) = &e
-                && db_err.is_unique_violation()
-            {
-                return AppError::bad_request(anyhow::anyhow!(
-                    "Branch with this name already exists for this level"
-                ));
-            }
-            AppError::from(e)
-        })?;
-
-        invalidate::branch(
-            cache,
-            Some(branch.id.into_inner()),
-            Some(level_id.into_inner()),
-        )
-        .await;
-
-        Ok(branch)
-    }
-
-    #[instrument(skip(db))]
-    pub async fn get_branches_by_level_no_school_filter(
-        db: &PgPool,
-        level_id: LevelId,
-        filters: BranchFilterParams,
-    ) -> Result<PaginatedBranchesResponse, AppError> {
-        // Verify level exists (no school check)
-        let level = sqlx::query!(
-            r#"SELECT id FROM levels WHERE id = $1"#,
-            level_id.into_inner()
         )
-        .fetch_optional(db)
+        .bind(branch_id)
+        .bind(student_id)
+        .bind(system_roles::STUDENT)
+        .fetch_optional(&mut *conn)
         .await?;
 
-        if level.is_none() {
-            return Err(AppError::not_found(anyhow::anyhow!("Level not found")));
-        }
-
-        let page = filters.pagination.page();
-        let limit = filters.pagination.limit();
-        let offset = filters.pagination.offset();
-
-        let student_role_id = system_roles::STUDENT;
-        let branches = if let Some(name) = &filters.name {
-            sqlx::query_as::<_, BranchWithStats>(
-                r#"
-                SELECT
-                    b.id,
-                    b.name,
-                    b.description,
-                    b.level_id,
-                    b.created_at,
-                    b.updated_at,
-                    COUNT(u.id)::bigint as student_count
-                FROM branches b
-                LEFT JOIN users u ON u.branch_id = b.id
-                LEFT JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $5
-                WHERE b.level_id = $1 AND b.name ILIKE $2 AND (u.id IS NULL OR ur.role_id IS NOT NULL)
-                GROUP BY b.id
-                ORDER BY b.created_at DESC
-                LIMIT $3 OFFSET $4
-                "#,
-            )
-            .bind(level_id.into_inner())
-            .bind(format!("%{}%", name))
-            .bind(limit)
-            .bind(offset)
-            .bind(student_role_id)
-            .fetch_all(db)
-            .await?
-        } else {
-            sqlx::query_as::<_, BranchWithStats>(
-                r#"
-                SELECT
-                    b.id,
-                    b.name,
-                    b.description,
-                    b.level_id,
-                    b.created_at,
-                    b.updated_at,
-                    COUNT(DISTINCT CASE WHEN ur.role_id IS NOT NULL THEN u.id END)::bigint as student_count
-                FROM branches b
-                LEFT JOIN users u ON u.branch_id = b.id
-                LEFT JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $4
-                WHERE b.level_id = $1
-                GROUP BY b.id
-                ORDER BY b.created_at DESC
-                LIMIT $2 OFFSET $3
-                "#,
-            )
-            .bind(level_id.into_inner())
-            .bind(limit)
-            .bind(offset)
-            .bind(student_role_id)
-            .fetch_all(db)
-            .await?
+        let Some((from_branch_id, student_school_id)) = moved else {
+            return Err(AppError::from_code(ErrorCode::StudentNotFound));
         };
 
-        let total_query = if let Some(name) = &filters.name {
-            sqlx::query_scalar!(
-                "SELECT COUNT(*) FROM branches WHERE level_id = $1 AND name ILIKE $2",
-                level_id.into_inner(),
-                format!("%{}%", name)
-            )
-            .fetch_one(db)
-            .await?
-        } else {
-            sqlx::query_scalar!(
-                "SELECT COUNT(*) FROM branches WHERE level_id = $1",
-                level_id.into_inner()
+        if let Some(student_school_id) = student_school_id
+            && from_branch_id != branch_id
+        {
+            WebhookService::enqueue(
+                &mut *conn,
+                student_school_id,
+                WebhookEvent::StudentMovedBranch,
+                &serde_json::json!({
+                    "student_id": student_id,
+                    "from_branch_id": from_branch_id,
+                    "to_branch_id": branch_id,
+                }),
             )
-            .fetch_one(db)
-            .await?
-        };
-
-        let total = total_query.unwrap_or(0);
-
-        Ok(PaginatedBranchesResponse {
-            data: branches,
-            meta: PaginationMeta {
-                total,
-                limit,
-                offset: Some(offset),
-                page,
-                has_more: offset + limit < total,
-            },
-        })
-    }
-
-    #[instrument(skip(db))]
-    pub async fn get_branch_by_id_no_school_filter(
-        db: &PgPool,
-        id: BranchId,
-    ) -> Result<BranchWithStats, AppError> {
-        let student_role_id = system_roles::STUDENT;
-        let branch = sqlx::query_as::<_, BranchWithStats>(
-            r#"
-            SELECT
-                b.id,
-                b.name,
-                b.description,
-                b.level_id,
-                b.created_at,
-                b.updated_at,
-                COUNT(DISTINCT CASE WHEN ur.role_id IS NOT NULL THEN u.id END)::bigint as student_count
-            FROM branches b
-            LEFT JOIN users u ON u.branch_id = b.id
-            LEFT JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $2
-            WHERE b.id = $1
-            GROUP BY b.id
-            "#,
-        )
-        .bind(id.into_inner())
-        .bind(student_role_id)
-        .fetch_optional(db)
-        .await?;
-
-        branch.ok_or_else(|| AppError::not_found(anyhow::anyhow!("Branch not found")))
-    }
-
-    #[instrument(skip(db))]
-    pub async fn update_branch_no_school_filter(
-        db: &PgPool,
-        cache: Option<&RedisCache>,
-        id: BranchId,
-        dto: UpdateBranchDto,
-    ) -> Result<Branch, AppError> {
-        let existing = sqlx::query!(r#"SELECT id FROM branches WHERE id = $1"#, id.into_inner())
-            .fetch_optional(db)
-            .await?;
-
-        if existing.is_none() {
-            return Err(AppError::not_found(anyhow::anyhow!("Branch not found")));
-        }
-
-        let mut query = String::from("UPDATE branches SET updated_at = NOW()");
-        let mut param_count = 1;
-
-        if dto.name.is_some() {
-            param_count += 1;
-            query.push_str(&format!(", name = ${}", param_count));
-        }
-
-        if dto.description.is_some() {
-            param_count += 1;
-            query.push_str(&format!(", description = ${}", param_count));
-        }
-
-        query.push_str(
-            " WHERE id = $1 RETURNING id, name, description, level_id, created_at, updated_at",
-        );
-
-        let mut query_builder = sqlx::query_as::<_, Branch>(&query).bind(id.into_inner());
-
-        if let Some(name) = dto.name {
-            query_builder = query_builder.bind(name);
-        }
-
-        if let Some(description) = dto.description {
-            query_builder = query_builder.bind(description);
-        }
-
-        let branch = query_builder.fetch_one(db).await.map_err(|e| {
-            if let sqlx::Error::Database(db_err) = &e
-                && db_err.is_unique_violation()
-            {
-                return AppError::bad_request(anyhow::anyhow!(
-                    "Branch with this name already exists for this level"
-                ));
-            }
-            AppError::from(e)
-        })?;
-
-        invalidate::branch(
-            cache,
-            Some(id.into_inner()),
-            Some(branch.level_id.into()),
-        )
-        .await;
-
-        Ok(branch)
-    }
-
-    #[instrument(skip(db, cache))]
-    pub async fn delete_branch_no_school_filter(
-        db: &PgPool,
-        cache: Option<&RedisCache>,
-        id: BranchId,
-        level_id: Option<LevelId>,
-    ) -> Result<(), AppError> {
-        let result = sqlx::query!(r#"DELETE FROM branches WHERE id = $1"#, id.into_inner())
-            .execute(db)
             .await?;
-
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!("Branch not found")));
         }
 
-        invalidate::branch(
-            cache,
-            Some(id.into_inner()),
-            level_id.map(|l| l.into_inner()),
-        )
-        .await;
-
         Ok(())
     }
 
-    #[instrument(skip(db))]
-    pub async fn assign_students_to_branch_no_school_filter(
+    /// Resolve the school a branch belongs to, optionally requiring a school.
+    async fn get_branch_school(
         db: &PgPool,
         branch_id: BranchId,
-        dto: AssignStudentsToBranchDto,
-    ) -> Result<BulkAssignResponse, AppError> {
-        let branch = sqlx::query!(
-            r#"SELECT id FROM branches WHERE id = $1"#,
-            branch_id.into_inner()
+        school_id: Option<SchoolId>,
+    ) -> Result<SchoolId, AppError> {
+        sqlx::query_scalar::<_, SchoolId>(
+            r#"SELECT l.school_id
+               FROM branches b
+               INNER JOIN levels l ON l.id = b.level_id
+               WHERE b.id = $1 AND ($2::uuid IS NULL OR l.school_id = $2)"#,
         )
+        .bind(branch_id)
+        .bind(school_id)
         .fetch_optional(db)
-        .await?;
-
-        if branch.is_none() {
-            return Err(AppError::not_found(anyhow::anyhow!("Branch not found")));
-        }
-
-        let mut assigned_count = 0;
-        let mut failed_ids = Vec::new();
-
-        let student_role_id = system_roles::STUDENT;
-        for student_id in dto.student_ids {
-            let is_student = sqlx::query_scalar::<_, bool>(
-                "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
-            )
-            .bind(student_id.into_inner())
-            .bind(student_role_id)
-            .fetch_one(db)
-            .await
-            .unwrap_or(false);
-
-            if !is_student {
-                failed_ids.push(student_id);
-                continue;
-            }
-
-            let result = sqlx::query(
-                r#"
-                UPDATE users
-                SET branch_id = $1, updated_at = NOW()
-                WHERE id = $2
-                "#,
-            )
-            .bind(branch_id.into_inner())
-            .bind(student_id.into_inner())
-            .execute(db)
-            .await;
-
-            match result {
-                Ok(res) if res.rows_affected() > 0 => assigned_count += 1,
-                _ => failed_ids.push(student_id),
-            }
-        }
-
-        Ok(BulkAssignResponse {
-            assigned_count,
-            failed_ids,
-        })
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::BranchNotFound))
     }
 
+    /// Assign a teacher to a branch in a capacity for a date range.
+    ///
+    /// The teacher must be a teacher in the branch's school. A teacher cannot
+    /// hold overlapping assignments on the same branch, and a branch has at
+    /// most one lead teacher on any day.
     #[instrument(skip(db))]
-    pub async fn get_students_in_branch_no_school_filter(
+    pub async fn assign_teacher(
         db: &PgPool,
         branch_id: BranchId,
-    ) -> Result<Vec<crate::modules::users::model::User>, AppError> {
-        let branch = sqlx::query!(
-            r#"SELECT id FROM branches WHERE id = $1"#,
-            branch_id.into_inner()
+        school_id: Option<SchoolId>,
+        assigned_by: UserId,
+        dto: AssignBranchTeacherDto,
+    ) -> Result<BranchTeacher, AppError> {
+        let school_id = Self::get_branch_school(db, branch_id, school_id).await?;
+
+        let starts_on = dto.starts_on.unwrap_or_else(|| Utc::now().date_naive());
+        if let Some(ends_on) = dto.ends_on
+            && ends_on < starts_on
+        {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "ends_on must not be before starts_on"
+            )));
+        }
+
+        let is_teacher = sqlx::query_scalar::<_, bool>(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM users u
+                   INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $3
+                   WHERE u.id = $1 AND u.school_id = $2
+               )"#,
+        )
+        .bind(dto.teacher_id)
+        .bind(school_id)
+        .bind(system_roles::TEACHER)
+        .fetch_one(db)
+        .await?;
+
+        if !is_teacher {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Teacher must be a teacher in the same school"
+            )));
+        }
+
+        // One row per overlapping assignment, true when it is this teacher's own
+        let overlapping = sqlx::query_scalar::<_, bool>(
+            r#"SELECT teacher_id = $2 FROM branch_teachers
+               WHERE branch_id = $1
+                 AND (teacher_id = $2 OR ($5 = 'lead' AND capacity = 'lead'))
+                 AND starts_on <= COALESCE($4, 'infinity'::date)
+                 AND COALESCE(ends_on, 'infinity'::date) >= $3"#,
         )
-        .fetch_optional(db)
+        .bind(branch_id)
+        .bind(dto.teacher_id)
+        .bind(starts_on)
+        .bind(dto.ends_on)
+        .bind(dto.capacity)
+        .fetch_all(db)
         .await?;
 
-        if branch.is_none() {
-            return Err(AppError::not_found(anyhow::anyhow!("Branch not found")));
+        if overlapping.contains(&true) {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Teacher is already assigned to this branch for these dates"
+            )));
+        }
+        if !overlapping.is_empty() {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Branch already has a lead teacher for these dates"
+            )));
         }
 
-        let student_role_id = system_roles::STUDENT;
-        let students = sqlx::query_as::<_, crate::modules::users::model::User>(
-            r#"
-            SELECT
-                u.id,
-                u.first_name,
-                u.last_name,
-                u.email,
-                u.school_id,
-                u.level_id,
-                u.branch_id,
-                u.date_of_birth,
-                u.grade_level,
-                u.created_at,
-                u.updated_at
-            FROM users u
-            INNER JOIN user_roles ur ON ur.user_id = u.id
-            WHERE u.branch_id = $1 AND ur.role_id = $2
-            ORDER BY u.last_name, u.first_name
-            "#,
+        let id = sqlx::query_scalar::<_, BranchTeacherId>(
+            r#"INSERT INTO branch_teachers (branch_id, teacher_id, capacity, starts_on, ends_on, assigned_by)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id"#,
         )
-        .bind(branch_id.into_inner())
-        .bind(student_role_id)
+        .bind(branch_id)
+        .bind(dto.teacher_id)
+        .bind(dto.capacity)
+        .bind(starts_on)
+        .bind(dto.ends_on)
+        .bind(assigned_by)
+        .fetch_one(db)
+        .await?;
+
+        sqlx::query_as::<_, BranchTeacher>(&format!(
+            "SELECT {BRANCH_TEACHER_COLUMNS} FROM branch_teachers bt
+             INNER JOIN users u ON u.id = bt.teacher_id
+             WHERE bt.id = $1"
+        ))
+        .bind(id)
+        .fetch_one(db)
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// Teachers assigned to a branch, leads first.
+    #[instrument(skip(db))]
+    pub async fn get_branch_teachers(
+        db: &PgPool,
+        branch_id: BranchId,
+        school_id: Option<SchoolId>,
+        params: BranchTeacherQueryParams,
+    ) -> Result<Vec<BranchTeacher>, AppError> {
+        Self::get_branch_school(db, branch_id, school_id).await?;
+
+        let teachers = sqlx::query_as::<_, BranchTeacher>(&format!(
+            "SELECT {BRANCH_TEACHER_COLUMNS} FROM branch_teachers bt
+             INNER JOIN users u ON u.id = bt.teacher_id
+             WHERE bt.branch_id = $1
+               AND ($2::date IS NULL OR (bt.starts_on <= $2
+                    AND COALESCE(bt.ends_on, 'infinity'::date) >= $2))
+             ORDER BY CASE bt.capacity WHEN 'lead' THEN 0 WHEN 'substitute' THEN 1 ELSE 2 END,
+                      bt.starts_on, u.last_name, u.first_name"
+        ))
+        .bind(branch_id)
+        .bind(params.active_on)
         .fetch_all(db)
         .await?;
 
-        Ok(students)
+        Ok(teachers)
     }
 
+    /// Remove a teacher assignment from a branch.
     #[instrument(skip(db))]
-    pub async fn move_student_to_branch_no_school_filter(
+    pub async fn remove_teacher(
         db: &PgPool,
-        student_id: UserId,
-        dto: MoveStudentToBranchDto,
+        branch_id: BranchId,
+        assignment_id: BranchTeacherId,
+        school_id: Option<SchoolId>,
     ) -> Result<(), AppError> {
-        if let Some(branch_id) = dto.branch_id {
-            let branch = sqlx::query!(
-                r#"SELECT id FROM branches WHERE id = $1"#,
-                branch_id.into_inner()
-            )
-            .fetch_optional(db)
+        Self::get_branch_school(db, branch_id, school_id).await?;
+
+        let result = sqlx::query("DELETE FROM branch_teachers WHERE id = $1 AND branch_id = $2")
+            .bind(assignment_id)
+            .bind(branch_id)
+            .execute(db)
             .await?;
 
-            if branch.is_none() {
-                return Err(AppError::not_found(anyhow::anyhow!("Branch not found")));
-            }
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Teacher assignment not found"
+            )));
         }
 
-        let student_role_id = system_roles::STUDENT;
-        let is_student = sqlx::query_scalar::<_, bool>(
-            "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
+        Ok(())
+    }
+
+    /// Capacities a teacher holds on a branch on a given day.
+    #[instrument(skip(db))]
+    pub async fn teacher_capacities_on_branch(
+        db: &PgPool,
+        branch_id: BranchId,
+        teacher_id: UserId,
+        on: NaiveDate,
+    ) -> Result<Vec<TeacherCapacity>, AppError> {
+        let capacities = sqlx::query_scalar::<_, TeacherCapacity>(
+            r#"SELECT capacity FROM branch_teachers
+               WHERE branch_id = $1 AND teacher_id = $2
+                 AND starts_on <= $3 AND COALESCE(ends_on, 'infinity'::date) >= $3"#,
         )
-        .bind(student_id.into_inner())
-        .bind(student_role_id)
-        .fetch_one(db)
+        .bind(branch_id)
+        .bind(teacher_id)
+        .bind(on)
+        .fetch_all(db)
         .await?;
 
-        if !is_student {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
-        }
+        Ok(capacities)
+    }
 
-        let result = sqlx::query(
-            r#"
-            UPDATE users
-            SET branch_id = $1, updated_at = NOW()
-            WHERE id = $2
-            "#,
+    /// Capacities a teacher holds on any branch of a level on a given day.
+    #[instrument(skip(db))]
+    pub async fn teacher_capacities_in_level(
+        db: &PgPool,
+        level_id: LevelId,
+        teacher_id: UserId,
+        on: NaiveDate,
+    ) -> Result<Vec<TeacherCapacity>, AppError> {
+        let capacities = sqlx::query_scalar::<_, TeacherCapacity>(
+            r#"SELECT bt.capacity FROM branch_teachers bt
+               INNER JOIN branches b ON b.id = bt.branch_id
+               WHERE b.level_id = $1 AND bt.teacher_id = $2
+                 AND bt.starts_on <= $3 AND COALESCE(bt.ends_on, 'infinity'::date) >= $3"#,
         )
-        .bind(dto.branch_id.map(|b| b.into_inner()))
-        .bind(student_id.into_inner())
-        .execute(db)
+        .bind(level_id)
+        .bind(teacher_id)
+        .bind(on)
+        .fetch_all(db)
         .await?;
 
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
-        }
-
-        Ok(())
+        Ok(capacities)
     }
 
+    /// Branches a teacher is assigned to in any capacity on a given day.
     #[instrument(skip(db))]
-    pub async fn remove_student_from_branch_no_school_filter(
+    pub async fn teacher_branch_ids(
         db: &PgPool,
-        student_id: UserId,
-    ) -> Result<(), AppError> {
-        let student_role_id = system_roles::STUDENT;
-        let result = sqlx::query(
-            r#"
-            UPDATE users
-            SET branch_id = NULL, updated_at = NOW()
-            WHERE id = $1
-            AND EXISTS (
-                SELECT 1 FROM user_roles ur
-                WHERE ur.user_id = $1 AND ur.role_id = $2
-            )
-            "#,
+        teacher_id: UserId,
+        on: NaiveDate,
+    ) -> Result<Vec<BranchId>, AppError> {
+        let branch_ids = sqlx::query_scalar::<_, BranchId>(
+            r#"SELECT DISTINCT branch_id FROM branch_teachers
+               WHERE teacher_id = $1
+                 AND starts_on <= $2 AND COALESCE(ends_on, 'infinity'::date) >= $2"#,
         )
-        .bind(student_id.into_inner())
-        .bind(student_role_id)
-        .execute(db)
+        .bind(teacher_id)
+        .bind(on)
+        .fetch_all(db)
         .await?;
 
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
-        }
-
-        Ok(())
+        Ok(branch_ids)
     }
 }
 
@@ -1004,10 +825,11 @@ impl BranchService {
 mod tests {
     use super::*;
     use crate::modules::branches::model::{
-        AssignStudentsToBranchDto, BranchFilterParams, CreateBranchDto, MoveStudentToBranchDto,
+        AssignBranchTeacherDto, AssignStudentsToBranchDto, BranchFilterParams,
+        BranchTeacherQueryParams, CreateBranchDto, MoveStudentToBranchDto, TeacherCapacity,
         UpdateBranchDto,
     };
-    use chalkbyte_core::PaginationParams;
+    use chalkbyte_core::{PaginationParams, SortParams};
     use sqlx::PgPool;
     use uuid::Uuid;
 
@@ -1067,6 +889,21 @@ mod tests {
         UserId::from(user_id)
     }
 
+    async fn create_teacher(pool: &PgPool, school_id: SchoolId) -> UserId {
+        let user_id = create_student(pool, school_id).await;
+
+        sqlx::query!(
+            "UPDATE user_roles SET role_id = $2 WHERE user_id = $1",
+            user_id.into_inner(),
+            crate::modules::users::model::system_roles::TEACHER.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id
+    }
+
     #[sqlx::test(migrations = "./migrations")]
     async fn test_create_branch_success(pool: PgPool) {
         let (school_id, level_id, _) = setup_test_data(&pool).await;
@@ -1076,7 +913,14 @@ mod tests {
             description: Some("Test Description".to_string()),
         };
 
-        let result = BranchService::create_branch(&pool, None, level_id, school_id, dto).await;
+        let result = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await;
 
         assert!(result.is_ok());
         let branch = result.unwrap();
@@ -1095,8 +939,14 @@ mod tests {
             description: None,
         };
 
-        let result =
-            BranchService::create_branch(&pool, None, non_existent_level_id, school_id, dto).await;
+        let result = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            non_existent_level_id,
+            dto,
+        )
+        .await;
 
         assert!(result.is_err());
     }
@@ -1111,8 +961,14 @@ mod tests {
             description: None,
         };
 
-        let result =
-            BranchService::create_branch(&pool, None, level_id, wrong_school_id, dto).await;
+        let result = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(wrong_school_id),
+            level_id,
+            dto,
+        )
+        .await;
 
         assert!(result.is_err());
     }
@@ -1126,16 +982,29 @@ mod tests {
             description: None,
         };
 
-        BranchService::create_branch(&pool, None, level_id, school_id, dto1)
-            .await
-            .unwrap();
+        BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto1,
+        )
+        .await
+        .unwrap();
 
         let dto2 = CreateBranchDto {
             name: "Duplicate Branch".to_string(),
             description: None,
         };
 
-        let result = BranchService::create_branch(&pool, None, level_id, school_id, dto2).await;
+        let result = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto2,
+        )
+        .await;
 
         assert!(result.is_err());
     }
@@ -1149,9 +1018,15 @@ mod tests {
                 name: format!("Branch {}", i),
                 description: None,
             };
-            BranchService::create_branch(&pool, None, level_id, school_id, dto)
-                .await
-                .unwrap();
+            BranchService::create_branch(
+                &pool,
+                None,
+                &TenantContext::school(school_id),
+                level_id,
+                dto,
+            )
+            .await
+            .unwrap();
         }
 
         let filters = BranchFilterParams {
@@ -1161,10 +1036,16 @@ mod tests {
                 limit: Some(10),
                 offset: None,
             },
+            sort: SortParams::default(),
         };
 
-        let result =
-            BranchService::get_branches_by_level(&pool, level_id, school_id, filters).await;
+        let result = BranchService::get_branches_by_level(
+            &pool,
+            &TenantContext::school(school_id),
+            level_id,
+            filters,
+        )
+        .await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1180,17 +1061,29 @@ mod tests {
             name: "Science Branch".to_string(),
             description: None,
         };
-        BranchService::create_branch(&pool, None, level_id, school_id, dto1)
-            .await
-            .unwrap();
+        BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto1,
+        )
+        .await
+        .unwrap();
 
         let dto2 = CreateBranchDto {
             name: "Arts Branch".to_string(),
             description: None,
         };
-        BranchService::create_branch(&pool, None, level_id, school_id, dto2)
-            .await
-            .unwrap();
+        BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto2,
+        )
+        .await
+        .unwrap();
 
         let filters = BranchFilterParams {
             name: Some("Science".to_string()),
@@ -1199,10 +1092,16 @@ mod tests {
                 limit: Some(10),
                 offset: None,
             },
+            sort: SortParams::default(),
         };
 
-        let result =
-            BranchService::get_branches_by_level(&pool, level_id, school_id, filters).await;
+        let result = BranchService::get_branches_by_level(
+            &pool,
+            &TenantContext::school(school_id),
+            level_id,
+            filters,
+        )
+        .await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1218,11 +1117,23 @@ mod tests {
             name: "Test Branch".to_string(),
             description: None,
         };
-        let branch = BranchService::create_branch(&pool, None, level_id, school_id, dto)
-            .await
-            .unwrap();
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await
+        .unwrap();
 
-        let result = BranchService::get_branch_by_id(&pool, branch.id, school_id).await;
+        let result = BranchService::get_branch_by_id(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            branch.id,
+        )
+        .await;
 
         assert!(result.is_ok());
         let fetched = result.unwrap();
@@ -1236,7 +1147,13 @@ mod tests {
         let (school_id, _, _) = setup_test_data(&pool).await;
         let non_existent_id = BranchId::new();
 
-        let result = BranchService::get_branch_by_id(&pool, non_existent_id, school_id).await;
+        let result = BranchService::get_branch_by_id(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            non_existent_id,
+        )
+        .await;
 
         assert!(result.is_err());
     }
@@ -1249,20 +1166,33 @@ mod tests {
             name: "Original Name".to_string(),
             description: Some("Original Description".to_string()),
         };
-        let branch = BranchService::create_branch(&pool, None, level_id, school_id, dto)
-            .await
-            .unwrap();
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await
+        .unwrap();
 
         let update_dto = UpdateBranchDto {
             name: Some("Updated Name".to_string()),
             description: Some("Updated Description".to_string()),
         };
 
-        let result =
-            BranchService::update_branch(&pool, None, branch.id, school_id, update_dto).await;
+        let result = BranchService::update_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            branch.id,
+            &IfMatch::default(),
+            update_dto,
+        )
+        .await;
 
         assert!(result.is_ok());
-        let updated = result.unwrap();
+        let updated = result.unwrap().updated().unwrap();
         assert_eq!(updated.name, "Updated Name");
         assert_eq!(updated.description, Some("Updated Description".to_string()));
     }
@@ -1275,20 +1205,33 @@ mod tests {
             name: "Original Name".to_string(),
             description: Some("Original Description".to_string()),
         };
-        let branch = BranchService::create_branch(&pool, None, level_id, school_id, dto)
-            .await
-            .unwrap();
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await
+        .unwrap();
 
         let update_dto = UpdateBranchDto {
             name: Some("Updated Name".to_string()),
             description: None,
         };
 
-        let result =
-            BranchService::update_branch(&pool, None, branch.id, school_id, update_dto).await;
+        let result = BranchService::update_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            branch.id,
+            &IfMatch::default(),
+            update_dto,
+        )
+        .await;
 
         assert!(result.is_ok());
-        let updated = result.unwrap();
+        let updated = result.unwrap().updated().unwrap();
         assert_eq!(updated.name, "Updated Name");
         assert_eq!(
             updated.description,
@@ -1304,15 +1247,36 @@ mod tests {
             name: "To Be Deleted".to_string(),
             description: None,
         };
-        let branch = BranchService::create_branch(&pool, None, level_id, school_id, dto)
-            .await
-            .unwrap();
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await
+        .unwrap();
 
-        let result = BranchService::delete_branch(&pool, None, branch.id, school_id, None).await;
+        let deleted_by = create_student(&pool, school_id).await;
+        let result = BranchService::delete_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            branch.id,
+            None,
+            deleted_by,
+        )
+        .await;
 
         assert!(result.is_ok());
 
-        let fetch_result = BranchService::get_branch_by_id(&pool, branch.id, school_id).await;
+        let fetch_result = BranchService::get_branch_by_id(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            branch.id,
+        )
+        .await;
         assert!(fetch_result.is_err());
     }
 
@@ -1321,8 +1285,15 @@ mod tests {
         let (school_id, _, _) = setup_test_data(&pool).await;
         let non_existent_id = BranchId::new();
 
-        let result =
-            BranchService::delete_branch(&pool, None, non_existent_id, school_id, None).await;
+        let result = BranchService::delete_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            non_existent_id,
+            None,
+            UserId::new(),
+        )
+        .await;
 
         assert!(result.is_err());
     }
@@ -1335,9 +1306,15 @@ mod tests {
             name: "Test Branch".to_string(),
             description: None,
         };
-        let branch = BranchService::create_branch(&pool, None, level_id, school_id, dto)
-            .await
-            .unwrap();
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await
+        .unwrap();
 
         let student1_id = create_student(&pool, school_id).await;
         let student2_id = create_student(&pool, school_id).await;
@@ -1346,8 +1323,14 @@ mod tests {
             student_ids: vec![student1_id, student2_id],
         };
 
-        let result =
-            BranchService::assign_students_to_branch(&pool, branch.id, school_id, assign_dto).await;
+        let result = BranchService::assign_students_to_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            branch.id,
+            assign_dto,
+        )
+        .await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1363,9 +1346,15 @@ mod tests {
             name: "Test Branch".to_string(),
             description: None,
         };
-        let branch = BranchService::create_branch(&pool, None, level_id, school_id, dto)
-            .await
-            .unwrap();
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await
+        .unwrap();
 
         let valid_student_id = create_student(&pool, school_id).await;
         let invalid_student_id = UserId::new();
@@ -1374,8 +1363,14 @@ mod tests {
             student_ids: vec![valid_student_id, invalid_student_id],
         };
 
-        let result =
-            BranchService::assign_students_to_branch(&pool, branch.id, school_id, assign_dto).await;
+        let result = BranchService::assign_students_to_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            branch.id,
+            assign_dto,
+        )
+        .await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1384,6 +1379,54 @@ mod tests {
         assert_eq!(response.failed_ids[0], invalid_student_id);
     }
 
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_assign_students_skips_other_schools(pool: PgPool) {
+        let (school_id, level_id, _) = setup_test_data(&pool).await;
+        let (other_school_id, _, _) = setup_test_data(&pool).await;
+
+        let dto = CreateBranchDto {
+            name: "Test Branch".to_string(),
+            description: None,
+        };
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await
+        .unwrap();
+
+        let own_student_id = create_student(&pool, school_id).await;
+        let other_student_id = create_student(&pool, other_school_id).await;
+
+        let assign_dto = AssignStudentsToBranchDto {
+            student_ids: vec![other_student_id, own_student_id],
+        };
+        let response = BranchService::assign_students_to_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            branch.id,
+            assign_dto,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.assigned_count, 1);
+        assert_eq!(response.failed_ids, vec![other_student_id]);
+
+        let other_branch_id = sqlx::query_scalar!(
+            "SELECT branch_id FROM users WHERE id = $1",
+            other_student_id.into_inner()
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert!(other_branch_id.is_none());
+    }
+
     #[sqlx::test(migrations = "./migrations")]
     async fn test_move_student_to_branch(pool: PgPool) {
         let (school_id, level_id, _) = setup_test_data(&pool).await;
@@ -1392,9 +1435,15 @@ mod tests {
             name: "Target Branch".to_string(),
             description: None,
         };
-        let branch = BranchService::create_branch(&pool, None, level_id, school_id, dto)
-            .await
-            .unwrap();
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await
+        .unwrap();
 
         let student_id = create_student(&pool, school_id).await;
 
@@ -1402,8 +1451,14 @@ mod tests {
             branch_id: Some(branch.id),
         };
 
-        let result =
-            BranchService::move_student_to_branch(&pool, student_id, school_id, move_dto).await;
+        let result = BranchService::move_student_to_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            student_id,
+            move_dto,
+        )
+        .await;
 
         assert!(result.is_ok());
 
@@ -1426,9 +1481,15 @@ mod tests {
             name: "Initial Branch".to_string(),
             description: None,
         };
-        let branch = BranchService::create_branch(&pool, None, level_id, school_id, dto)
-            .await
-            .unwrap();
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await
+        .unwrap();
 
         let student_id = create_student(&pool, school_id).await;
 
@@ -1443,8 +1504,14 @@ mod tests {
 
         let move_dto = MoveStudentToBranchDto { branch_id: None };
 
-        let result =
-            BranchService::move_student_to_branch(&pool, student_id, school_id, move_dto).await;
+        let result = BranchService::move_student_to_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            student_id,
+            move_dto,
+        )
+        .await;
 
         assert!(result.is_ok());
 
@@ -1467,9 +1534,15 @@ mod tests {
             name: "Test Branch".to_string(),
             description: None,
         };
-        let branch = BranchService::create_branch(&pool, None, level_id, school_id, dto)
-            .await
-            .unwrap();
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await
+        .unwrap();
 
         let student_id = create_student(&pool, school_id).await;
 
@@ -1482,7 +1555,13 @@ mod tests {
         .await
         .unwrap();
 
-        let result = BranchService::remove_student_from_branch(&pool, student_id, school_id).await;
+        let result = BranchService::remove_student_from_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            student_id,
+        )
+        .await;
 
         assert!(result.is_ok());
 
@@ -1505,9 +1584,15 @@ mod tests {
             name: "Test Branch".to_string(),
             description: None,
         };
-        let branch = BranchService::create_branch(&pool, None, level_id, school_id, dto)
-            .await
-            .unwrap();
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await
+        .unwrap();
 
         let student1_id = create_student(&pool, school_id).await;
         let student2_id = create_student(&pool, school_id).await;
@@ -1521,7 +1606,12 @@ mod tests {
         .await
         .unwrap();
 
-        let result = BranchService::get_students_in_branch(&pool, branch.id, school_id).await;
+        let result = BranchService::get_students_in_branch(
+            &pool,
+            &TenantContext::school(school_id),
+            branch.id,
+        )
+        .await;
 
         assert!(result.is_ok());
         let students = result.unwrap();
@@ -1536,9 +1626,15 @@ mod tests {
             name: "Test Branch".to_string(),
             description: None,
         };
-        let branch = BranchService::create_branch(&pool, None, level_id, school_id, dto)
-            .await
-            .unwrap();
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            dto,
+        )
+        .await
+        .unwrap();
 
         let student1_id = create_student(&pool, school_id).await;
         let student2_id = create_student(&pool, school_id).await;
@@ -1557,10 +1653,133 @@ mod tests {
         .await
         .unwrap();
 
-        let result = BranchService::get_branch_by_id(&pool, branch.id, school_id).await;
+        let result = BranchService::get_branch_by_id(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            branch.id,
+        )
+        .await;
 
         assert!(result.is_ok());
         let branch_with_stats = result.unwrap();
         assert_eq!(branch_with_stats.student_count, 3);
     }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_assign_teachers_in_capacities(pool: PgPool) {
+        let (school_id, level_id, _) = setup_test_data(&pool).await;
+        let branch = BranchService::create_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level_id,
+            CreateBranchDto {
+                name: "A".to_string(),
+                description: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let lead = create_teacher(&pool, school_id).await;
+        let assistant = create_teacher(&pool, school_id).await;
+        let substitute = create_teacher(&pool, school_id).await;
+        let student = create_student(&pool, school_id).await;
+        let date = |d| NaiveDate::from_ymd_opt(2026, 9, d).unwrap();
+        let assign = |teacher_id, capacity, starts_on, ends_on| AssignBranchTeacherDto {
+            teacher_id,
+            capacity,
+            starts_on: Some(starts_on),
+            ends_on,
+        };
+
+        for dto in [
+            assign(lead, TeacherCapacity::Lead, date(1), None),
+            assign(assistant, TeacherCapacity::Assistant, date(1), None),
+            assign(
+                substitute,
+                TeacherCapacity::Substitute,
+                date(10),
+                Some(date(20)),
+            ),
+        ] {
+            BranchService::assign_teacher(&pool, branch.id, Some(school_id), lead, dto)
+                .await
+                .unwrap();
+        }
+
+        // A second lead, a repeat assignment, bad dates, and non-teachers are rejected
+        for dto in [
+            assign(substitute, TeacherCapacity::Lead, date(25), None),
+            assign(
+                assistant,
+                TeacherCapacity::Assistant,
+                date(15),
+                Some(date(16)),
+            ),
+            assign(
+                substitute,
+                TeacherCapacity::Assistant,
+                date(5),
+                Some(date(4)),
+            ),
+            assign(student, TeacherCapacity::Assistant, date(1), None),
+        ] {
+            let err = BranchService::assign_teacher(&pool, branch.id, Some(school_id), lead, dto)
+                .await
+                .unwrap_err();
+            assert_eq!(err.status, axum::http::StatusCode::BAD_REQUEST);
+        }
+
+        let teachers = BranchService::get_branch_teachers(
+            &pool,
+            branch.id,
+            Some(school_id),
+            BranchTeacherQueryParams { active_on: None },
+        )
+        .await
+        .unwrap();
+        let capacities: Vec<_> = teachers.iter().map(|t| t.capacity).collect();
+        assert_eq!(
+            capacities,
+            [
+                TeacherCapacity::Lead,
+                TeacherCapacity::Substitute,
+                TeacherCapacity::Assistant
+            ]
+        );
+
+        let active_early = BranchService::get_branch_teachers(
+            &pool,
+            branch.id,
+            Some(school_id),
+            BranchTeacherQueryParams {
+                active_on: Some(date(5)),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(active_early.len(), 2);
+
+        let on_branch =
+            BranchService::teacher_capacities_on_branch(&pool, branch.id, assistant, date(5))
+                .await
+                .unwrap();
+        assert_eq!(on_branch, [TeacherCapacity::Assistant]);
+
+        let in_level =
+            BranchService::teacher_capacities_in_level(&pool, level_id, substitute, date(5))
+                .await
+                .unwrap();
+        assert!(in_level.is_empty());
+
+        BranchService::remove_teacher(&pool, branch.id, teachers[0].id, Some(school_id))
+            .await
+            .unwrap();
+        let in_level = BranchService::teacher_capacities_in_level(&pool, level_id, lead, date(5))
+            .await
+            .unwrap();
+        assert!(in_level.is_empty());
+    }
 }
@@ -0,0 +1,429 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::{BroadcastId, SchoolId};
+
+use crate::middleware::auth::{
+    RequireBroadcastsApprove, RequireBroadcastsRead, RequireBroadcastsSend, RequireSchoolsRead,
+    RequireSchoolsUpdate,
+};
+use crate::modules::broadcasts::model::{
+    Broadcast, BroadcastFilterParams, BroadcastRecipientFilterParams, BroadcastStatus,
+    CreateBroadcastDto, PaginatedBroadcastRecipientsResponse, PaginatedBroadcastsResponse,
+    PublishingSettings, ReviewBroadcastDto, UpdateBroadcastDto, UpdatePublishingSettingsDto,
+};
+use crate::modules::broadcasts::service::BroadcastService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::verify_school_access;
+use crate::validator::ValidatedJson;
+
+/// Create a broadcast
+///
+/// Saves a draft when `draft` is set. Otherwise the broadcast is submitted:
+/// it waits for approval if the school requires it, then for its publish
+/// time, and is then queued. Queued broadcasts have their recipients resolved
+/// immediately and are delivered in the background at a throttled rate. Poll
+/// the broadcast to follow its progress.
+#[utoipa::path(
+    post,
+    path = "/api/schools/{id}/broadcasts",
+    summary = "Create broadcast",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = CreateBroadcastDto,
+    responses(
+        (status = 201, description = "Broadcast saved as a draft, awaiting approval, or scheduled", body = Broadcast),
+        (status = 202, description = "Broadcast queued for delivery", body = Broadcast),
+        (status = 400, description = "Invalid template, unknown audience filter, or no matching users"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires broadcasts:send permission and access to the school")
+    ),
+    tag = "Broadcasts",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn create_broadcast(
+    State(state): State<AppState>,
+    RequireBroadcastsSend(auth_user): RequireBroadcastsSend,
+    Path(school_id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<CreateBroadcastDto>,
+) -> Result<(StatusCode, Json<Broadcast>), AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let broadcast =
+        BroadcastService::create_broadcast(&state.db, school_id, auth_user.user_id()?, dto).await?;
+
+    let status = if broadcast.status == BroadcastStatus::Queued {
+        StatusCode::ACCEPTED
+    } else {
+        StatusCode::CREATED
+    };
+
+    Ok((status, Json(broadcast)))
+}
+
+/// Edit a draft broadcast
+#[utoipa::path(
+    put,
+    path = "/api/schools/{id}/broadcasts/{broadcast_id}",
+    summary = "Update draft broadcast",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("broadcast_id" = Uuid, Path, description = "Broadcast ID")
+    ),
+    request_body = UpdateBroadcastDto,
+    responses(
+        (status = 200, description = "Draft updated", body = Broadcast),
+        (status = 400, description = "Broadcast is not a draft, invalid template, or unknown audience filter"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires broadcasts:send permission and access to the school"),
+        (status = 404, description = "Broadcast not found")
+    ),
+    tag = "Broadcasts",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn update_broadcast(
+    State(state): State<AppState>,
+    RequireBroadcastsSend(auth_user): RequireBroadcastsSend,
+    Path((school_id, broadcast_id)): Path<(Uuid, Uuid)>,
+    ValidatedJson(dto): ValidatedJson<UpdateBroadcastDto>,
+) -> Result<Json<Broadcast>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let broadcast = BroadcastService::update_broadcast(
+        &state.db,
+        school_id,
+        BroadcastId::from(broadcast_id),
+        dto,
+    )
+    .await?;
+
+    Ok(Json(broadcast))
+}
+
+/// Submit a draft broadcast
+///
+/// The broadcast waits for approval if the school requires it, then for its
+/// publish time, and is then queued for delivery.
+#[utoipa::path(
+    post,
+    path = "/api/schools/{id}/broadcasts/{broadcast_id}/submit",
+    summary = "Submit draft broadcast",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("broadcast_id" = Uuid, Path, description = "Broadcast ID")
+    ),
+    responses(
+        (status = 200, description = "Broadcast submitted", body = Broadcast),
+        (status = 400, description = "Broadcast is not a draft, or no matching users"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires broadcasts:send permission and access to the school"),
+        (status = 404, description = "Broadcast not found")
+    ),
+    tag = "Broadcasts",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn submit_broadcast(
+    State(state): State<AppState>,
+    RequireBroadcastsSend(auth_user): RequireBroadcastsSend,
+    Path((school_id, broadcast_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Broadcast>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let broadcast = BroadcastService::submit_broadcast(
+        &state.db,
+        school_id,
+        BroadcastId::from(broadcast_id),
+        auth_user.user_id()?,
+    )
+    .await?;
+
+    Ok(Json(broadcast))
+}
+
+/// Approve a broadcast
+///
+/// Releases a broadcast awaiting approval. It must be approved by someone
+/// other than the admin who submitted it.
+#[utoipa::path(
+    post,
+    path = "/api/schools/{id}/broadcasts/{broadcast_id}/approve",
+    summary = "Approve broadcast",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("broadcast_id" = Uuid, Path, description = "Broadcast ID")
+    ),
+    request_body = ReviewBroadcastDto,
+    responses(
+        (status = 200, description = "Broadcast approved and scheduled or queued", body = Broadcast),
+        (status = 400, description = "Broadcast is not awaiting approval, or no matching users"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires broadcasts:approve permission, access to the school, and not being the submitter"),
+        (status = 404, description = "Broadcast not found")
+    ),
+    tag = "Broadcasts",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn approve_broadcast(
+    State(state): State<AppState>,
+    RequireBroadcastsApprove(auth_user): RequireBroadcastsApprove,
+    Path((school_id, broadcast_id)): Path<(Uuid, Uuid)>,
+    ValidatedJson(dto): ValidatedJson<ReviewBroadcastDto>,
+) -> Result<Json<Broadcast>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let broadcast = BroadcastService::approve_broadcast(
+        &state.db,
+        school_id,
+        BroadcastId::from(broadcast_id),
+        auth_user.user_id()?,
+        dto,
+    )
+    .await?;
+
+    Ok(Json(broadcast))
+}
+
+/// Reject a broadcast
+///
+/// Returns a broadcast awaiting approval to draft so it can be edited and
+/// submitted again.
+#[utoipa::path(
+    post,
+    path = "/api/schools/{id}/broadcasts/{broadcast_id}/reject",
+    summary = "Reject broadcast",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("broadcast_id" = Uuid, Path, description = "Broadcast ID")
+    ),
+    request_body = ReviewBroadcastDto,
+    responses(
+        (status = 200, description = "Broadcast returned to draft", body = Broadcast),
+        (status = 400, description = "Broadcast is not awaiting approval"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires broadcasts:approve permission, access to the school, and not being the submitter"),
+        (status = 404, description = "Broadcast not found")
+    ),
+    tag = "Broadcasts",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn reject_broadcast(
+    State(state): State<AppState>,
+    RequireBroadcastsApprove(auth_user): RequireBroadcastsApprove,
+    Path((school_id, broadcast_id)): Path<(Uuid, Uuid)>,
+    ValidatedJson(dto): ValidatedJson<ReviewBroadcastDto>,
+) -> Result<Json<Broadcast>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let broadcast = BroadcastService::reject_broadcast(
+        &state.db,
+        school_id,
+        BroadcastId::from(broadcast_id),
+        auth_user.user_id()?,
+        dto,
+    )
+    .await?;
+
+    Ok(Json(broadcast))
+}
+
+/// List a school's broadcasts
+///
+/// Most recent first, with delivery counts.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/broadcasts",
+    summary = "List broadcasts",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        BroadcastFilterParams
+    ),
+    responses(
+        (status = 200, description = "Broadcasts with delivery counts", body = PaginatedBroadcastsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires broadcasts:read permission and access to the school")
+    ),
+    tag = "Broadcasts",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_broadcasts(
+    State(state): State<AppState>,
+    RequireBroadcastsRead(auth_user): RequireBroadcastsRead,
+    Path(school_id): Path<Uuid>,
+    Query(params): Query<BroadcastFilterParams>,
+) -> Result<Json<PaginatedBroadcastsResponse>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let broadcasts = BroadcastService::get_broadcasts(&state.db, school_id, params).await?;
+
+    Ok(Json(broadcasts))
+}
+
+/// Get a broadcast
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/broadcasts/{broadcast_id}",
+    summary = "Get broadcast",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("broadcast_id" = Uuid, Path, description = "Broadcast ID")
+    ),
+    responses(
+        (status = 200, description = "Broadcast with delivery counts", body = Broadcast),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires broadcasts:read permission and access to the school"),
+        (status = 404, description = "Broadcast not found")
+    ),
+    tag = "Broadcasts",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_broadcast(
+    State(state): State<AppState>,
+    RequireBroadcastsRead(auth_user): RequireBroadcastsRead,
+    Path((school_id, broadcast_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Broadcast>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let broadcast =
+        BroadcastService::get_broadcast(&state.db, school_id, BroadcastId::from(broadcast_id))
+            .await?;
+
+    Ok(Json(broadcast))
+}
+
+/// List a broadcast's recipients
+///
+/// Shows the delivery status of each recipient, and why delivery failed or
+/// was skipped.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/broadcasts/{broadcast_id}/recipients",
+    summary = "List broadcast recipients",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("broadcast_id" = Uuid, Path, description = "Broadcast ID"),
+        BroadcastRecipientFilterParams
+    ),
+    responses(
+        (status = 200, description = "Recipients with delivery status", body = PaginatedBroadcastRecipientsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires broadcasts:read permission and access to the school"),
+        (status = 404, description = "Broadcast not found")
+    ),
+    tag = "Broadcasts",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_broadcast_recipients(
+    State(state): State<AppState>,
+    RequireBroadcastsRead(auth_user): RequireBroadcastsRead,
+    Path((school_id, broadcast_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<BroadcastRecipientFilterParams>,
+) -> Result<Json<PaginatedBroadcastRecipientsResponse>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let recipients = BroadcastService::get_recipients(
+        &state.db,
+        school_id,
+        BroadcastId::from(broadcast_id),
+        params,
+    )
+    .await?;
+
+    Ok(Json(recipients))
+}
+
+/// Get a school's publishing settings
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/publishing-settings",
+    summary = "Get publishing settings",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    responses(
+        (status = 200, description = "Publishing settings", body = PublishingSettings),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:read permission and access to the school"),
+        (status = 404, description = "School not found")
+    ),
+    tag = "Broadcasts",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_publishing_settings(
+    State(state): State<AppState>,
+    RequireSchoolsRead(auth_user): RequireSchoolsRead,
+    Path(school_id): Path<Uuid>,
+) -> Result<Json<PublishingSettings>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let settings = BroadcastService::get_publishing_settings(&state.db, school_id).await?;
+
+    Ok(Json(settings))
+}
+
+/// Update a school's publishing settings
+///
+/// Sets the timezone that broadcast publish times are given in, and whether
+/// broadcasts need a second admin's approval before they are sent.
+#[utoipa::path(
+    put,
+    path = "/api/schools/{id}/publishing-settings",
+    summary = "Update publishing settings",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = UpdatePublishingSettingsDto,
+    responses(
+        (status = 200, description = "Publishing settings updated", body = PublishingSettings),
+        (status = 400, description = "Unknown timezone"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:update permission and access to the school"),
+        (status = 404, description = "School not found")
+    ),
+    tag = "Broadcasts",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn update_publishing_settings(
+    State(state): State<AppState>,
+    RequireSchoolsUpdate(auth_user): RequireSchoolsUpdate,
+    Path(school_id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdatePublishingSettingsDto>,
+) -> Result<Json<PublishingSettings>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let settings = BroadcastService::update_publishing_settings(
+        &state.db,
+        state.cache.as_ref(),
+        school_id,
+        dto,
+    )
+    .await?;
+
+    Ok(Json(settings))
+}
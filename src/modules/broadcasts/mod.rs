@@ -0,0 +1,22 @@
+//! Broadcasts module.
+//!
+//! School admins send a templated email or SMS to an audience narrowed by
+//! role, level, and branch. Queuing a broadcast resolves its recipients up
+//! front; a background job started with the server then delivers them at
+//! `BROADCAST_SEND_PER_SECOND` messages a second (default 5) to stay within
+//! provider limits, recording a delivery status per recipient.
+//!
+//! A broadcast can first be saved as a draft. Submitting it sends it for
+//! approval when the school requires a second admin to sign off (four-eyes),
+//! and holds it until its publish time, a wall-clock time in the school's
+//! timezone. The same background job publishes scheduled broadcasts once
+//! the school's local time reaches them.
+//!
+//! SMS goes to the emergency contact phone on a student's medical profile.
+//! No SMS gateway is integrated yet, so SMS recipients with a phone number
+//! are recorded as failed with the reason, never as sent.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Broadcast data models and DTOs.
+//!
+//! This module re-exports broadcast models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all broadcast models from the shared crate
+pub use chalkbyte_models::broadcasts::*;
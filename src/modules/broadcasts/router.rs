@@ -0,0 +1,48 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    approve_broadcast, create_broadcast, get_broadcast, get_broadcast_recipients, get_broadcasts,
+    get_publishing_settings, reject_broadcast, submit_broadcast, update_broadcast,
+    update_publishing_settings,
+};
+
+/// Initialize the broadcasts router, merged into the schools router
+/// Routes: GET/POST /{id}/broadcasts, GET/PUT /{id}/broadcasts/{broadcast_id},
+/// POST /{id}/broadcasts/{broadcast_id}/submit, POST .../approve, POST .../reject,
+/// GET /{id}/broadcasts/{broadcast_id}/recipients, GET/PUT /{id}/publishing-settings
+pub fn init_broadcasts_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/{id}/broadcasts",
+            get(get_broadcasts).post(create_broadcast),
+        )
+        .route(
+            "/{id}/broadcasts/{broadcast_id}",
+            get(get_broadcast).put(update_broadcast),
+        )
+        .route(
+            "/{id}/broadcasts/{broadcast_id}/submit",
+            post(submit_broadcast),
+        )
+        .route(
+            "/{id}/broadcasts/{broadcast_id}/approve",
+            post(approve_broadcast),
+        )
+        .route(
+            "/{id}/broadcasts/{broadcast_id}/reject",
+            post(reject_broadcast),
+        )
+        .route(
+            "/{id}/broadcasts/{broadcast_id}/recipients",
+            get(get_broadcast_recipients),
+        )
+        .route(
+            "/{id}/publishing-settings",
+            get(get_publishing_settings).put(update_publishing_settings),
+        )
+}
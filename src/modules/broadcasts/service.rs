@@ -0,0 +1,1193 @@
+use std::time::Duration;
+
+use sqlx::{FromRow, PgPool, Postgres, Transaction};
+use tracing::{info, instrument, warn};
+
+use chalkbyte_cache::{RedisCache, invalidate};
+use chalkbyte_config::EmailConfig;
+use chalkbyte_core::{AppError, ErrorCode, PaginationMeta};
+use chalkbyte_models::ids::{
+    BranchId, BroadcastId, BroadcastRecipientId, LevelId, RoleId, SchoolId, UserId,
+};
+use chalkbyte_observability::jobs::register_job;
+
+use crate::modules::announcements::service::{AnnouncementService, spawn_email_fan_out};
+use crate::modules::billing::service::students_owing;
+use crate::modules::broadcasts::model::{
+    Broadcast, BroadcastChannel, BroadcastFilterParams, BroadcastRecipient,
+    BroadcastRecipientFilterParams, BroadcastStatus, CreateBroadcastDto, DeliveryStatus,
+    PaginatedBroadcastRecipientsResponse, PaginatedBroadcastsResponse, PublishingSettings,
+    ReviewBroadcastDto, TemplateContext, UpdateBroadcastDto, UpdatePublishingSettingsDto,
+};
+use crate::modules::email_branding::service::EmailBrandingService;
+use crate::modules::users::model::system_roles;
+use crate::utils::email::EmailService;
+
+/// How often the delivery job looks for due and queued broadcasts.
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Default delivery rate, kept low enough for typical SMTP and SMS provider limits.
+const DEFAULT_SEND_PER_SECOND: u32 = 5;
+
+/// Recipients fetched per round trip while delivering.
+const DELIVERY_BATCH_SIZE: i64 = 50;
+
+/// Messages sent per second by the delivery job, from `BROADCAST_SEND_PER_SECOND`.
+pub fn send_per_second() -> u32 {
+    std::env::var("BROADCAST_SEND_PER_SECOND")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|rate| *rate > 0)
+        .unwrap_or(DEFAULT_SEND_PER_SECOND)
+}
+
+/// Start the background job that publishes scheduled broadcasts and
+/// announcements when they fall due and delivers queued broadcasts.
+///
+/// Broadcasts left half-sent by a previous run are picked up again; their
+/// recipients that were already attempted are not sent to twice.
+pub fn spawn_delivery_job(db: PgPool, email_config: EmailConfig) {
+    tokio::spawn(async move {
+        let email = EmailService::new(db.clone(), email_config.clone());
+
+        if let Err(e) = BroadcastService::requeue_interrupted(&db).await {
+            warn!(error = %e, "Failed to requeue interrupted broadcasts");
+        }
+
+        let publish_job = register_job("broadcast_publishing", POLL_INTERVAL);
+        let announcement_job = register_job("announcement_publishing", POLL_INTERVAL);
+        let delivery_job = register_job("broadcast_delivery", POLL_INTERVAL);
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match publish_job.run(BroadcastService::publish_due(&db)).await {
+                Ok(0) => {}
+                Ok(published) => info!(published, "Published scheduled broadcasts"),
+                Err(e) => warn!(error = %e, "Failed to publish scheduled broadcasts"),
+            }
+            match announcement_job
+                .run(AnnouncementService::publish_due(&db))
+                .await
+            {
+                Ok(published) => {
+                    for announcement in published {
+                        info!(announcement_id = %announcement.id, "Published scheduled announcement");
+                        if announcement.send_email {
+                            spawn_email_fan_out(db.clone(), email_config.clone(), announcement);
+                        }
+                    }
+                }
+                Err(e) => warn!(error = %e, "Failed to publish scheduled announcements"),
+            }
+            let run = delivery_job
+                .run(async {
+                    while let Some(broadcast_id) =
+                        BroadcastService::deliver_next(&db, &email, send_per_second()).await?
+                    {
+                        info!(%broadcast_id, "Delivered broadcast");
+                    }
+                    Ok::<_, AppError>(())
+                })
+                .await;
+            if let Err(e) = run {
+                warn!(error = %e, "Failed to deliver broadcast");
+            }
+            match BroadcastService::queue_depth(&db).await {
+                Ok(depth) => delivery_job.set_queue_depth(depth),
+                Err(e) => warn!(error = %e, "Failed to count queued broadcasts"),
+            }
+        }
+    });
+}
+
+const BROADCAST_COLUMNS: &str = r#"b.id, b.school_id, b.channel, b.subject, b.body,
+    b.role_id, b.level_id, b.branch_id, b.fee_status, b.status, b.publish_at, b.created_by,
+    b.submitted_by, b.submitted_at, b.reviewed_by, b.reviewed_at, b.review_note,
+    COUNT(r.id) AS total_recipients,
+    COUNT(r.id) FILTER (WHERE r.status = 'sent') AS sent_count,
+    COUNT(r.id) FILTER (WHERE r.status = 'failed') AS failed_count,
+    COUNT(r.id) FILTER (WHERE r.status = 'skipped') AS skipped_count,
+    b.started_at, b.completed_at, b.created_at, b.updated_at"#;
+
+const PUBLISHING_SETTINGS_COLUMNS: &str = "id AS school_id, timezone, broadcast_approval_required";
+
+const RECIPIENT_COLUMNS: &str =
+    "id, broadcast_id, user_id, first_name, last_name, address, status, error, attempted_at";
+
+/// A broadcast claimed by the delivery job.
+#[derive(FromRow)]
+struct ClaimedBroadcast {
+    id: BroadcastId,
+    school_id: SchoolId,
+    channel: BroadcastChannel,
+    subject: Option<String>,
+    body: String,
+    school_name: String,
+}
+
+#[derive(FromRow)]
+struct PendingRecipient {
+    id: BroadcastRecipientId,
+    first_name: String,
+    last_name: String,
+    address: String,
+}
+
+pub struct BroadcastService;
+
+impl BroadcastService {
+    /// Create a broadcast, either as a draft or submitted straight away.
+    ///
+    /// See [`Self::submit_broadcast`] for what happens on submission.
+    #[instrument(skip(db, dto))]
+    pub async fn create_broadcast(
+        db: &PgPool,
+        school_id: SchoolId,
+        created_by: UserId,
+        dto: CreateBroadcastDto,
+    ) -> Result<Broadcast, AppError> {
+        Self::verify_audience(db, school_id, dto.role_id, dto.level_id, dto.branch_id).await?;
+
+        let mut tx = db.begin().await?;
+
+        let broadcast_id = sqlx::query_scalar::<_, BroadcastId>(
+            "INSERT INTO broadcasts
+                 (school_id, channel, subject, body, role_id, level_id, branch_id, fee_status,
+                  publish_at, status, created_by)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, 'draft', $10)
+             RETURNING id",
+        )
+        .bind(school_id)
+        .bind(dto.channel)
+        .bind(&dto.subject)
+        .bind(&dto.body)
+        .bind(dto.role_id)
+        .bind(dto.level_id)
+        .bind(dto.branch_id)
+        .bind(dto.fee_status)
+        .bind(dto.publish_at)
+        .bind(created_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if !dto.draft {
+            Self::submit(&mut tx, broadcast_id, created_by).await?;
+        }
+
+        tx.commit().await?;
+
+        Self::get_broadcast(db, school_id, broadcast_id).await
+    }
+
+    /// Replace the content, audience, and publish time of a draft.
+    #[instrument(skip(db, dto))]
+    pub async fn update_broadcast(
+        db: &PgPool,
+        school_id: SchoolId,
+        broadcast_id: BroadcastId,
+        dto: UpdateBroadcastDto,
+    ) -> Result<Broadcast, AppError> {
+        let broadcast = Self::get_broadcast(db, school_id, broadcast_id).await?;
+        if broadcast.status != BroadcastStatus::Draft {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only draft broadcasts can be edited"
+            )));
+        }
+
+        Self::verify_audience(db, school_id, dto.role_id, dto.level_id, dto.branch_id).await?;
+
+        sqlx::query(
+            "UPDATE broadcasts
+             SET channel = $2, subject = $3, body = $4, role_id = $5, level_id = $6,
+                 branch_id = $7, fee_status = $8, publish_at = $9
+             WHERE id = $1",
+        )
+        .bind(broadcast_id)
+        .bind(dto.channel)
+        .bind(&dto.subject)
+        .bind(&dto.body)
+        .bind(dto.role_id)
+        .bind(dto.level_id)
+        .bind(dto.branch_id)
+        .bind(dto.fee_status)
+        .bind(dto.publish_at)
+        .execute(db)
+        .await?;
+
+        Self::get_broadcast(db, school_id, broadcast_id).await
+    }
+
+    /// Submit a draft.
+    ///
+    /// When the school requires approval the broadcast waits for a second
+    /// admin; otherwise it is released straight away, see [`Self::release`].
+    #[instrument(skip(db))]
+    pub async fn submit_broadcast(
+        db: &PgPool,
+        school_id: SchoolId,
+        broadcast_id: BroadcastId,
+        submitted_by: UserId,
+    ) -> Result<Broadcast, AppError> {
+        let broadcast = Self::get_broadcast(db, school_id, broadcast_id).await?;
+        if broadcast.status != BroadcastStatus::Draft {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only draft broadcasts can be submitted"
+            )));
+        }
+
+        let mut tx = db.begin().await?;
+        Self::submit(&mut tx, broadcast_id, submitted_by).await?;
+        tx.commit().await?;
+
+        Self::get_broadcast(db, school_id, broadcast_id).await
+    }
+
+    /// Approve a broadcast awaiting approval and release it.
+    ///
+    /// The approver must be someone other than the admin who submitted it.
+    #[instrument(skip(db, dto))]
+    pub async fn approve_broadcast(
+        db: &PgPool,
+        school_id: SchoolId,
+        broadcast_id: BroadcastId,
+        reviewer_id: UserId,
+        dto: ReviewBroadcastDto,
+    ) -> Result<Broadcast, AppError> {
+        let broadcast =
+            Self::find_pending_approval(db, school_id, broadcast_id, reviewer_id).await?;
+
+        let mut tx = db.begin().await?;
+
+        sqlx::query(
+            "UPDATE broadcasts SET reviewed_by = $2, reviewed_at = NOW(), review_note = $3
+             WHERE id = $1",
+        )
+        .bind(broadcast.id)
+        .bind(reviewer_id)
+        .bind(&dto.review_note)
+        .execute(&mut *tx)
+        .await?;
+
+        Self::release(&mut tx, broadcast.id).await?;
+
+        tx.commit().await?;
+
+        Self::get_broadcast(db, school_id, broadcast_id).await
+    }
+
+    /// Reject a broadcast awaiting approval, returning it to draft.
+    #[instrument(skip(db, dto))]
+    pub async fn reject_broadcast(
+        db: &PgPool,
+        school_id: SchoolId,
+        broadcast_id: BroadcastId,
+        reviewer_id: UserId,
+        dto: ReviewBroadcastDto,
+    ) -> Result<Broadcast, AppError> {
+        let broadcast =
+            Self::find_pending_approval(db, school_id, broadcast_id, reviewer_id).await?;
+
+        sqlx::query(
+            "UPDATE broadcasts
+             SET status = 'draft', reviewed_by = $2, reviewed_at = NOW(), review_note = $3
+             WHERE id = $1",
+        )
+        .bind(broadcast.id)
+        .bind(reviewer_id)
+        .bind(&dto.review_note)
+        .execute(db)
+        .await?;
+
+        Self::get_broadcast(db, school_id, broadcast_id).await
+    }
+
+    async fn find_pending_approval(
+        db: &PgPool,
+        school_id: SchoolId,
+        broadcast_id: BroadcastId,
+        reviewer_id: UserId,
+    ) -> Result<Broadcast, AppError> {
+        let broadcast = Self::get_broadcast(db, school_id, broadcast_id).await?;
+
+        if broadcast.status != BroadcastStatus::PendingApproval {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only broadcasts awaiting approval can be reviewed"
+            )));
+        }
+        if broadcast.submitted_by == Some(reviewer_id) {
+            return Err(AppError::forbidden(
+                "A broadcast must be reviewed by someone other than its submitter".to_string(),
+            ));
+        }
+
+        Ok(broadcast)
+    }
+
+    async fn submit(
+        tx: &mut Transaction<'_, Postgres>,
+        broadcast_id: BroadcastId,
+        submitted_by: UserId,
+    ) -> Result<(), AppError> {
+        let approval_required = sqlx::query_scalar::<_, bool>(
+            "UPDATE broadcasts b
+             SET status = CASE WHEN s.broadcast_approval_required THEN 'pending_approval' ELSE b.status END,
+                 submitted_by = $2, submitted_at = NOW(),
+                 reviewed_by = NULL, reviewed_at = NULL, review_note = NULL
+             FROM schools s
+             WHERE s.id = b.school_id AND b.id = $1
+             RETURNING s.broadcast_approval_required",
+        )
+        .bind(broadcast_id)
+        .bind(submitted_by)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        if !approval_required {
+            Self::release(tx, broadcast_id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Schedule the broadcast if its publish time is still ahead in the
+    /// school's timezone, otherwise queue it for delivery now.
+    async fn release(
+        tx: &mut Transaction<'_, Postgres>,
+        broadcast_id: BroadcastId,
+    ) -> Result<(), AppError> {
+        let status = sqlx::query_scalar::<_, BroadcastStatus>(
+            "UPDATE broadcasts b
+             SET status = CASE WHEN b.publish_at > NOW() AT TIME ZONE s.timezone
+                               THEN 'scheduled' ELSE 'queued' END
+             FROM schools s
+             WHERE s.id = b.school_id AND b.id = $1
+             RETURNING b.status",
+        )
+        .bind(broadcast_id)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        if status == BroadcastStatus::Queued {
+            let recipients = Self::resolve_recipients(tx, broadcast_id).await?;
+            if recipients == 0 {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "No users match this audience"
+                )));
+            }
+            info!(%broadcast_id, recipients, "Broadcast queued");
+        } else {
+            info!(%broadcast_id, "Broadcast scheduled");
+        }
+
+        Ok(())
+    }
+
+    /// Record the broadcast's audience as its recipients.
+    ///
+    /// Recipients without an address for the channel are recorded as skipped
+    /// so the sender can see who was missed.
+    async fn resolve_recipients(
+        tx: &mut Transaction<'_, Postgres>,
+        broadcast_id: BroadcastId,
+    ) -> Result<u64, AppError> {
+        let recipients = sqlx::query(&format!(
+            r#"INSERT INTO broadcast_recipients
+                   (broadcast_id, user_id, first_name, last_name, address, status, error)
+               SELECT a.broadcast_id, a.id, a.first_name, a.last_name, a.address,
+                      CASE WHEN a.address IS NULL THEN 'skipped' ELSE 'pending' END,
+                      CASE WHEN a.address IS NULL THEN 'No address on file for this channel' END
+               FROM (
+                   SELECT b.id AS broadcast_id, u.id, u.first_name, u.last_name,
+                          CASE WHEN b.channel = 'sms' THEN mp.emergency_contact_phone ELSE u.email END AS address
+                   FROM broadcasts b
+                   JOIN users u ON u.school_id = b.school_id
+                   LEFT JOIN student_medical_profiles mp ON mp.student_id = u.id
+                   WHERE b.id = $1
+                     AND (b.role_id IS NULL OR EXISTS (
+                         SELECT 1 FROM user_roles ur WHERE ur.user_id = u.id AND ur.role_id = b.role_id
+                     ))
+                     AND (b.level_id IS NULL OR u.level_id = b.level_id)
+                     AND (b.branch_id IS NULL OR u.branch_id = b.branch_id)
+                     AND (b.fee_status IS NULL OR (
+                         EXISTS (
+                             SELECT 1 FROM user_roles ur
+                             WHERE ur.user_id = u.id AND ur.role_id = '{student}'
+                         )
+                         AND (u.id IN ({owing})) = (b.fee_status = 'in_arrears')
+                     ))
+               ) a
+               ON CONFLICT (broadcast_id, user_id) DO NOTHING"#,
+            student = system_roles::STUDENT,
+            owing = students_owing()
+        ))
+        .bind(broadcast_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+
+        Ok(recipients)
+    }
+
+    /// Queue every scheduled broadcast whose publish time has passed in its
+    /// school's timezone. Returns how many were published.
+    ///
+    /// A broadcast whose audience has emptied out since it was scheduled is
+    /// completed without recipients.
+    pub async fn publish_due(db: &PgPool) -> Result<u64, AppError> {
+        let mut published = 0;
+
+        loop {
+            let mut tx = db.begin().await?;
+
+            let Some(broadcast_id) = sqlx::query_scalar::<_, BroadcastId>(
+                "UPDATE broadcasts SET status = 'queued'
+                 WHERE id = (
+                     SELECT b.id FROM broadcasts b
+                     JOIN schools s ON s.id = b.school_id
+                     WHERE b.status = 'scheduled'
+                       AND b.publish_at <= NOW() AT TIME ZONE s.timezone
+                     ORDER BY b.publish_at
+                     LIMIT 1
+                     FOR UPDATE OF b SKIP LOCKED
+                 )
+                 RETURNING id",
+            )
+            .fetch_optional(&mut *tx)
+            .await?
+            else {
+                return Ok(published);
+            };
+
+            if Self::resolve_recipients(&mut tx, broadcast_id).await? == 0 {
+                warn!(%broadcast_id, "Scheduled broadcast has no recipients");
+                sqlx::query(
+                    "UPDATE broadcasts SET status = 'completed', completed_at = NOW() WHERE id = $1",
+                )
+                .bind(broadcast_id)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            tx.commit().await?;
+            published += 1;
+        }
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_publishing_settings(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<PublishingSettings, AppError> {
+        sqlx::query_as::<_, PublishingSettings>(&format!(
+            "SELECT {PUBLISHING_SETTINGS_COLUMNS} FROM schools WHERE id = $1"
+        ))
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))
+    }
+
+    /// Update a school's publishing settings. The timezone is shared with
+    /// the school's settings, whose cached copy is dropped.
+    #[instrument(skip(db, cache))]
+    pub async fn update_publishing_settings(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        dto: UpdatePublishingSettingsDto,
+    ) -> Result<PublishingSettings, AppError> {
+        if let Some(timezone) = &dto.timezone {
+            let known = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM pg_timezone_names WHERE name = $1)",
+            )
+            .bind(timezone)
+            .fetch_one(db)
+            .await?;
+            if !known {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "Unknown timezone: {timezone}"
+                )));
+            }
+        }
+
+        let settings = sqlx::query_as::<_, PublishingSettings>(&format!(
+            "UPDATE schools
+             SET timezone = COALESCE($2, timezone),
+                 broadcast_approval_required = COALESCE($3, broadcast_approval_required),
+                 updated_at = NOW()
+             WHERE id = $1
+             RETURNING {PUBLISHING_SETTINGS_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.timezone)
+        .bind(dto.broadcast_approval_required)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))?;
+
+        invalidate::school(cache, Some(school_id.into_inner())).await;
+
+        Ok(settings)
+    }
+
+    /// Check that the audience filters refer to this school's roles, levels, and branches.
+    pub(crate) async fn verify_audience(
+        db: &PgPool,
+        school_id: SchoolId,
+        role_id: Option<RoleId>,
+        level_id: Option<LevelId>,
+        branch_id: Option<BranchId>,
+    ) -> Result<(), AppError> {
+        if let Some(role_id) = role_id {
+            let exists = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM roles WHERE id = $1 AND (school_id IS NULL OR school_id = $2))",
+            )
+            .bind(role_id)
+            .bind(school_id)
+            .fetch_one(db)
+            .await?;
+            if !exists {
+                return Err(AppError::bad_request(anyhow::anyhow!("Role not found")));
+            }
+        }
+
+        if let Some(level_id) = level_id {
+            let exists = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM levels WHERE id = $1 AND school_id = $2)",
+            )
+            .bind(level_id)
+            .bind(school_id)
+            .fetch_one(db)
+            .await?;
+            if !exists {
+                return Err(AppError::bad_request(anyhow::anyhow!("Level not found")));
+            }
+        }
+
+        if let Some(branch_id) = branch_id {
+            let exists = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(
+                    SELECT 1 FROM branches b JOIN levels l ON l.id = b.level_id
+                    WHERE b.id = $1 AND l.school_id = $2
+                )",
+            )
+            .bind(branch_id)
+            .bind(school_id)
+            .fetch_one(db)
+            .await?;
+            if !exists {
+                return Err(AppError::bad_request(anyhow::anyhow!("Branch not found")));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_broadcast(
+        db: &PgPool,
+        school_id: SchoolId,
+        broadcast_id: BroadcastId,
+    ) -> Result<Broadcast, AppError> {
+        sqlx::query_as::<_, Broadcast>(&format!(
+            "SELECT {BROADCAST_COLUMNS}
+             FROM broadcasts b
+             LEFT JOIN broadcast_recipients r ON r.broadcast_id = b.id
+             WHERE b.id = $1 AND b.school_id = $2
+             GROUP BY b.id"
+        ))
+        .bind(broadcast_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Broadcast not found")))
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_broadcasts(
+        db: &PgPool,
+        school_id: SchoolId,
+        params: BroadcastFilterParams,
+    ) -> Result<PaginatedBroadcastsResponse, AppError> {
+        let limit = params.pagination.limit();
+        let offset = params.pagination.offset();
+
+        let data = sqlx::query_as::<_, Broadcast>(&format!(
+            "SELECT {BROADCAST_COLUMNS}
+             FROM broadcasts b
+             LEFT JOIN broadcast_recipients r ON r.broadcast_id = b.id
+             WHERE b.school_id = $1
+               AND ($2::text IS NULL OR b.status = $2)
+               AND ($3::text IS NULL OR b.channel = $3)
+             GROUP BY b.id
+             ORDER BY b.created_at DESC
+             LIMIT $4 OFFSET $5"
+        ))
+        .bind(school_id)
+        .bind(params.status)
+        .bind(params.channel)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM broadcasts
+             WHERE school_id = $1
+               AND ($2::text IS NULL OR status = $2)
+               AND ($3::text IS NULL OR channel = $3)",
+        )
+        .bind(school_id)
+        .bind(params.status)
+        .bind(params.channel)
+        .fetch_one(db)
+        .await?;
+
+        Ok(PaginatedBroadcastsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: params.pagination.page(),
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_recipients(
+        db: &PgPool,
+        school_id: SchoolId,
+        broadcast_id: BroadcastId,
+        params: BroadcastRecipientFilterParams,
+    ) -> Result<PaginatedBroadcastRecipientsResponse, AppError> {
+        // Confirms the broadcast belongs to the school
+        Self::get_broadcast(db, school_id, broadcast_id).await?;
+
+        let limit = params.pagination.limit();
+        let offset = params.pagination.offset();
+
+        let data = sqlx::query_as::<_, BroadcastRecipient>(&format!(
+            "SELECT {RECIPIENT_COLUMNS} FROM broadcast_recipients
+             WHERE broadcast_id = $1 AND ($2::text IS NULL OR status = $2)
+             ORDER BY last_name, first_name
+             LIMIT $3 OFFSET $4"
+        ))
+        .bind(broadcast_id)
+        .bind(params.status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM broadcast_recipients
+             WHERE broadcast_id = $1 AND ($2::text IS NULL OR status = $2)",
+        )
+        .bind(broadcast_id)
+        .bind(params.status)
+        .fetch_one(db)
+        .await?;
+
+        Ok(PaginatedBroadcastRecipientsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: params.pagination.page(),
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Number of broadcasts waiting to be sent.
+    pub async fn queue_depth(db: &PgPool) -> Result<u64, AppError> {
+        let queued =
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM broadcasts WHERE status = 'queued'")
+                .fetch_one(db)
+                .await?;
+
+        Ok(queued.try_into().unwrap_or_default())
+    }
+
+    /// Put broadcasts that were being sent when the server stopped back in the queue.
+    pub async fn requeue_interrupted(db: &PgPool) -> Result<u64, AppError> {
+        let requeued =
+            sqlx::query("UPDATE broadcasts SET status = 'queued' WHERE status = 'sending'")
+                .execute(db)
+                .await?
+                .rows_affected();
+
+        Ok(requeued)
+    }
+
+    /// Deliver the oldest queued broadcast, sending at most `per_second`
+    /// messages a second.
+    ///
+    /// Returns the broadcast delivered, or `None` when the queue is empty.
+    /// A failed send is recorded on the recipient and does not stop the rest.
+    #[instrument(skip(db, email))]
+    pub async fn deliver_next(
+        db: &PgPool,
+        email: &EmailService,
+        per_second: u32,
+    ) -> Result<Option<BroadcastId>, AppError> {
+        let Some(broadcast) = sqlx::query_as::<_, ClaimedBroadcast>(
+            r#"UPDATE broadcasts b
+               SET status = 'sending', started_at = COALESCE(b.started_at, NOW())
+               FROM schools s
+               WHERE s.id = b.school_id
+                 AND b.id = (
+                     SELECT id FROM broadcasts
+                     WHERE status = 'queued'
+                     ORDER BY created_at
+                     LIMIT 1
+                     FOR UPDATE SKIP LOCKED
+                 )
+               RETURNING b.id, b.school_id, b.channel, b.subject, b.body, s.name AS school_name"#,
+        )
+        .fetch_optional(db)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let email = email
+            .with_branding(EmailBrandingService::email_branding(db, broadcast.school_id).await?);
+        let pause = Duration::from_secs(1) / per_second.max(1);
+
+        loop {
+            let batch = sqlx::query_as::<_, PendingRecipient>(
+                "SELECT id, first_name, last_name, address FROM broadcast_recipients
+                 WHERE broadcast_id = $1 AND status = 'pending'
+                 ORDER BY id
+                 LIMIT $2",
+            )
+            .bind(broadcast.id)
+            .bind(DELIVERY_BATCH_SIZE)
+            .fetch_all(db)
+            .await?;
+
+            if batch.is_empty() {
+                break;
+            }
+
+            for recipient in batch {
+                let context = TemplateContext {
+                    first_name: &recipient.first_name,
+                    last_name: &recipient.last_name,
+                    school_name: &broadcast.school_name,
+                };
+
+                let (status, error) =
+                    match Self::deliver(&email, &broadcast, &context, &recipient.address).await {
+                        Ok(()) => (DeliveryStatus::Sent, None),
+                        Err(e) => (DeliveryStatus::Failed, Some(e.error.to_string())),
+                    };
+
+                sqlx::query(
+                    "UPDATE broadcast_recipients
+                     SET status = $2, error = $3, attempted_at = NOW()
+                     WHERE id = $1",
+                )
+                .bind(recipient.id)
+                .bind(status)
+                .bind(error)
+                .execute(db)
+                .await?;
+
+                tokio::time::sleep(pause).await;
+            }
+        }
+
+        sqlx::query(
+            "UPDATE broadcasts SET status = 'completed', completed_at = NOW() WHERE id = $1",
+        )
+        .bind(broadcast.id)
+        .execute(db)
+        .await?;
+
+        Ok(Some(broadcast.id))
+    }
+
+    async fn deliver(
+        email: &EmailService,
+        broadcast: &ClaimedBroadcast,
+        context: &TemplateContext<'_>,
+        address: &str,
+    ) -> Result<(), AppError> {
+        let body = context.render(&broadcast.body);
+
+        match broadcast.channel {
+            BroadcastChannel::Email => {
+                let subject = context.render(broadcast.subject.as_deref().unwrap_or_default());
+                email.send_broadcast_email(address, &subject, &body).await
+            }
+            // Recorded as failed rather than sent until there is a gateway
+            BroadcastChannel::Sms => Err(AppError::internal_error(
+                "No SMS gateway is configured".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::broadcasts::model::{BroadcastStatus, FeeStatus};
+    use axum::http::StatusCode;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name) VALUES ($1) RETURNING id"#,
+            format!("School {}", Uuid::new_v4())
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId, role_id: RoleId) -> UserId {
+        let user_id: UserId = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Ada', 'Obi', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id.into_inner(),
+            role_id.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id
+    }
+
+    fn disabled_email(pool: &PgPool) -> EmailService {
+        EmailService::new(
+            pool.clone(),
+            EmailConfig {
+                enabled: false,
+                smtp_host: "localhost".to_string(),
+                smtp_port: 1025,
+                smtp_username: String::new(),
+                smtp_password: String::new(),
+                from_email: "noreply@example.com".to_string(),
+                from_name: "Chalkbyte".to_string(),
+                frontend_url: "http://localhost:3000".to_string(),
+            },
+        )
+    }
+
+    fn broadcast_dto(channel: BroadcastChannel, role_id: Option<RoleId>) -> CreateBroadcastDto {
+        CreateBroadcastDto {
+            channel,
+            subject: Some("Term starts Monday".to_string()),
+            body: "Dear {{first_name}}, term starts on Monday.".to_string(),
+            role_id,
+            level_id: None,
+            branch_id: None,
+            fee_status: None,
+            publish_at: None,
+            draft: false,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_broadcast_delivers_to_filtered_audience(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+        create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        create_test_user(&pool, school_id, system_roles::TEACHER).await;
+
+        let broadcast = BroadcastService::create_broadcast(
+            &pool,
+            school_id,
+            admin_id,
+            broadcast_dto(BroadcastChannel::Email, Some(system_roles::TEACHER)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(broadcast.total_recipients, 2);
+        assert_eq!(broadcast.sent_count, 0);
+
+        let delivered = BroadcastService::deliver_next(&pool, &disabled_email(&pool), 1000)
+            .await
+            .unwrap();
+        assert_eq!(delivered, Some(broadcast.id));
+
+        let broadcast = BroadcastService::get_broadcast(&pool, school_id, broadcast.id)
+            .await
+            .unwrap();
+        assert_eq!(broadcast.status, BroadcastStatus::Completed);
+        assert_eq!(broadcast.sent_count, 2);
+        assert!(broadcast.completed_at.is_some());
+
+        let next = BroadcastService::deliver_next(&pool, &disabled_email(&pool), 1000)
+            .await
+            .unwrap();
+        assert_eq!(next, None);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_sms_broadcast_skips_recipients_without_phone(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+        let student_id = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+
+        sqlx::query!(
+            "INSERT INTO student_medical_profiles (student_id, school_id, emergency_contact_phone)
+             VALUES ($1, $2, '+2348000000000')",
+            student_id.into_inner(),
+            school_id.into_inner()
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let broadcast = BroadcastService::create_broadcast(
+            &pool,
+            school_id,
+            admin_id,
+            broadcast_dto(BroadcastChannel::Sms, None),
+        )
+        .await
+        .unwrap();
+        assert_eq!(broadcast.total_recipients, 2);
+        assert_eq!(broadcast.skipped_count, 1);
+
+        BroadcastService::deliver_next(&pool, &disabled_email(&pool), 1000)
+            .await
+            .unwrap();
+
+        let skipped = BroadcastService::get_recipients(
+            &pool,
+            school_id,
+            broadcast.id,
+            BroadcastRecipientFilterParams {
+                status: Some(DeliveryStatus::Skipped),
+                pagination: Default::default(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(skipped.meta.total, 1);
+        assert_eq!(skipped.data[0].user_id, Some(admin_id));
+
+        let failed = BroadcastService::get_recipients(
+            &pool,
+            school_id,
+            broadcast.id,
+            BroadcastRecipientFilterParams {
+                status: Some(DeliveryStatus::Failed),
+                pagination: Default::default(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(failed.meta.total, 1);
+        assert_eq!(failed.data[0].user_id, Some(student_id));
+        assert_eq!(
+            failed.data[0].error.as_deref(),
+            Some("No SMS gateway is configured")
+        );
+
+        let broadcast = BroadcastService::get_broadcast(&pool, school_id, broadcast.id)
+            .await
+            .unwrap();
+        assert_eq!(broadcast.sent_count, 0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_broadcast_filters_students_by_fee_status(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+        let debtor_id = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let paid_up_id = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+
+        sqlx::query(
+            r#"WITH book AS (
+                   INSERT INTO library_books (school_id, title)
+                   VALUES ($1, 'Things Fall Apart') RETURNING id
+               ),
+               copy AS (
+                   INSERT INTO library_copies (book_id, school_id, barcode)
+                   SELECT id, $1, 'LIB-0001' FROM book RETURNING id
+               ),
+               loan AS (
+                   INSERT INTO library_loans (school_id, copy_id, borrower_id, due_date)
+                   SELECT $1, id, $2, '2025-10-01' FROM copy RETURNING id
+               )
+               INSERT INTO library_fine_charges
+                   (school_id, loan_id, borrower_id, days_overdue, amount)
+               SELECT $1, id, $2, 5, 500 FROM loan"#,
+        )
+        .bind(school_id.into_inner())
+        .bind(debtor_id.into_inner())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for (fee_status, expected) in [
+            (FeeStatus::InArrears, debtor_id),
+            (FeeStatus::PaidUp, paid_up_id),
+        ] {
+            let mut dto = broadcast_dto(BroadcastChannel::Email, None);
+            dto.fee_status = Some(fee_status);
+            let broadcast = BroadcastService::create_broadcast(&pool, school_id, admin_id, dto)
+                .await
+                .unwrap();
+            assert_eq!(broadcast.total_recipients, 1);
+
+            let recipients = BroadcastService::get_recipients(
+                &pool,
+                school_id,
+                broadcast.id,
+                BroadcastRecipientFilterParams {
+                    status: None,
+                    pagination: Default::default(),
+                },
+            )
+            .await
+            .unwrap();
+            assert_eq!(recipients.data[0].user_id, Some(expected));
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_broadcast_rejects_foreign_audience(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let other_school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+
+        let level_id: Uuid = sqlx::query_scalar!(
+            "INSERT INTO levels (name, school_id) VALUES ('Grade 1', $1) RETURNING id",
+            other_school_id.into_inner()
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let mut dto = broadcast_dto(BroadcastChannel::Email, None);
+        dto.level_id = Some(level_id.into());
+
+        let result = BroadcastService::create_broadcast(&pool, school_id, admin_id, dto).await;
+        assert_eq!(result.unwrap_err().status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_broadcast_requires_approval_by_another_admin(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let author_id = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+        let approver_id = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+
+        BroadcastService::update_publishing_settings(
+            &pool,
+            None,
+            school_id,
+            UpdatePublishingSettingsDto {
+                timezone: None,
+                broadcast_approval_required: Some(true),
+            },
+        )
+        .await
+        .unwrap();
+
+        let mut dto = broadcast_dto(BroadcastChannel::Email, None);
+        dto.draft = true;
+        let broadcast = BroadcastService::create_broadcast(&pool, school_id, author_id, dto)
+            .await
+            .unwrap();
+        assert_eq!(broadcast.status, BroadcastStatus::Draft);
+        assert_eq!(broadcast.total_recipients, 0);
+
+        let broadcast =
+            BroadcastService::submit_broadcast(&pool, school_id, broadcast.id, author_id)
+                .await
+                .unwrap();
+        assert_eq!(broadcast.status, BroadcastStatus::PendingApproval);
+
+        let result = BroadcastService::approve_broadcast(
+            &pool,
+            school_id,
+            broadcast.id,
+            author_id,
+            ReviewBroadcastDto::default(),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().status, StatusCode::FORBIDDEN);
+
+        let broadcast = BroadcastService::approve_broadcast(
+            &pool,
+            school_id,
+            broadcast.id,
+            approver_id,
+            ReviewBroadcastDto::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(broadcast.status, BroadcastStatus::Queued);
+        assert_eq!(broadcast.reviewed_by, Some(approver_id));
+        assert_eq!(broadcast.total_recipients, 2);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_scheduled_broadcast_publishes_in_school_timezone(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+
+        // Two hours ahead in UTC, but already past in a UTC+14 timezone
+        let publish_at = (chrono::Utc::now() + chrono::Duration::hours(2)).naive_utc();
+
+        let mut dto = broadcast_dto(BroadcastChannel::Email, None);
+        dto.publish_at = Some(publish_at);
+        let scheduled = BroadcastService::create_broadcast(&pool, school_id, admin_id, dto)
+            .await
+            .unwrap();
+        assert_eq!(scheduled.status, BroadcastStatus::Scheduled);
+        assert_eq!(BroadcastService::publish_due(&pool).await.unwrap(), 0);
+
+        BroadcastService::update_publishing_settings(
+            &pool,
+            None,
+            school_id,
+            UpdatePublishingSettingsDto {
+                timezone: Some("Pacific/Kiritimati".to_string()),
+                broadcast_approval_required: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(BroadcastService::publish_due(&pool).await.unwrap(), 1);
+
+        let broadcast = BroadcastService::get_broadcast(&pool, school_id, scheduled.id)
+            .await
+            .unwrap();
+        assert_eq!(broadcast.status, BroadcastStatus::Queued);
+        assert_eq!(broadcast.total_recipients, 1);
+
+        let result = BroadcastService::update_publishing_settings(
+            &pool,
+            None,
+            school_id,
+            UpdatePublishingSettingsDto {
+                timezone: Some("Mars/Olympus_Mons".to_string()),
+                broadcast_approval_required: None,
+            },
+        )
+        .await;
+        assert_eq!(result.unwrap_err().status, StatusCode::BAD_REQUEST);
+    }
+}
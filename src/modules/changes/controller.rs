@@ -0,0 +1,48 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use tracing::instrument;
+
+use chalkbyte_core::AppError;
+
+use crate::middleware::auth::RequireChangesRead;
+use crate::modules::changes::model::{ChangeFeedParams, ChangeFeedResponse};
+use crate::modules::changes::service::ChangeService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::get_optional_school_id_for_resource_operation;
+
+/// Read the data change feed
+///
+/// Returns changes to schools, users, levels, branches, academic sessions,
+/// and terms in commit order. Start without `since`, then pass each
+/// response's `next_cursor` to receive only what changed afterwards. School
+/// admins only see changes in their own school.
+#[utoipa::path(
+    get,
+    path = "/api/changes",
+    summary = "Read change feed",
+    params(ChangeFeedParams),
+    responses(
+        (status = 200, description = "Changes after the cursor", body = ChangeFeedResponse),
+        (status = 400, description = "Invalid cursor"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires changes:read permission")
+    ),
+    tag = "Changes",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_changes(
+    State(state): State<AppState>,
+    RequireChangesRead(auth_user): RequireChangesRead,
+    Query(params): Query<ChangeFeedParams>,
+) -> Result<Json<ChangeFeedResponse>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user)
+        .await?
+        .or(params.school_id);
+
+    let changes = ChangeService::get_changes(&state.db, school_id, params).await?;
+
+    Ok(Json(changes))
+}
@@ -0,0 +1,15 @@
+//! Data change feed module.
+//!
+//! Database triggers record every insert, update, and delete of schools,
+//! users, levels, branches, academic sessions, and terms in `change_events`.
+//! `GET /api/changes` returns them in commit order from an opaque cursor so
+//! third-party systems can sync incrementally instead of polling full lists.
+//!
+//! The feed only shows events from transactions older than any transaction
+//! still running, so a long-running transaction holds the feed back until
+//! it finishes rather than letting a consumer's cursor skip past its events.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
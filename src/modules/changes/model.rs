@@ -0,0 +1,7 @@
+//! Change feed data models.
+//!
+//! This module re-exports change feed models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all change feed models from the shared crate
+pub use chalkbyte_models::changes::*;
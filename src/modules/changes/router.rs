@@ -0,0 +1,11 @@
+use axum::{Router, routing::get};
+
+use crate::state::AppState;
+
+use super::controller::get_changes;
+
+/// Initialize the change feed router
+/// Routes: GET /
+pub fn init_changes_router() -> Router<AppState> {
+    Router::new().route("/", get(get_changes))
+}
@@ -0,0 +1,200 @@
+use data_encoding::BASE64URL_NOPAD;
+use sqlx::{FromRow, PgPool};
+use tracing::instrument;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::SchoolId;
+
+use crate::modules::changes::model::{ChangeEvent, ChangeFeedParams, ChangeFeedResponse};
+
+/// Position in the change feed: the writing transaction, then the event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct FeedPosition {
+    tx_id: i64,
+    id: i64,
+}
+
+impl FeedPosition {
+    fn encode(self) -> String {
+        BASE64URL_NOPAD.encode(format!("{}:{}", self.tx_id, self.id).as_bytes())
+    }
+
+    fn decode(cursor: &str) -> Result<Self, AppError> {
+        let invalid = || AppError::bad_request(anyhow::anyhow!("Invalid change feed cursor"));
+
+        let raw = BASE64URL_NOPAD
+            .decode(cursor.as_bytes())
+            .map_err(|_| invalid())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+        let (tx_id, id) = raw.split_once(':').ok_or_else(invalid)?;
+
+        Ok(Self {
+            tx_id: tx_id.parse().map_err(|_| invalid())?,
+            id: id.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+#[derive(FromRow)]
+struct ChangeRow {
+    id: i64,
+    tx_id: i64,
+    #[sqlx(flatten)]
+    event: ChangeEvent,
+}
+
+pub struct ChangeService;
+
+impl ChangeService {
+    /// Read changes after the cursor in `params`, optionally limited to one school.
+    #[instrument(skip(db))]
+    pub async fn get_changes(
+        db: &PgPool,
+        school_id: Option<SchoolId>,
+        params: ChangeFeedParams,
+    ) -> Result<ChangeFeedResponse, AppError> {
+        let since = match params.since.as_deref() {
+            None | Some("") => FeedPosition::default(),
+            Some(cursor) => FeedPosition::decode(cursor)?,
+        };
+        let limit = params.limit();
+
+        // Events from transactions that are still running, or that started
+        // before the oldest one still running finished, could yet commit
+        // behind the cursor, so they are held back until it passes them
+        let mut rows = sqlx::query_as::<_, ChangeRow>(
+            r#"SELECT id, tx_id, entity_type, entity_id, operation, version, school_id, changed_at
+               FROM change_events
+               WHERE tx_id < pg_snapshot_xmin(pg_current_snapshot())::text::bigint
+                 AND (tx_id, id) > ($1, $2)
+                 AND ($3::uuid IS NULL OR school_id = $3)
+                 AND ($4::text IS NULL OR entity_type = $4)
+               ORDER BY tx_id, id
+               LIMIT $5"#,
+        )
+        .bind(since.tx_id)
+        .bind(since.id)
+        .bind(school_id)
+        .bind(params.entity_type)
+        .bind(limit + 1)
+        .fetch_all(db)
+        .await?;
+
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
+
+        let next = rows.last().map_or(since, |row| FeedPosition {
+            tx_id: row.tx_id,
+            id: row.id,
+        });
+
+        Ok(ChangeFeedResponse {
+            data: rows.into_iter().map(|row| row.event).collect(),
+            next_cursor: next.encode(),
+            has_more,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::changes::model::{ChangeEntityType, ChangeOperation};
+    use axum::http::StatusCode;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn params(since: Option<String>) -> ChangeFeedParams {
+        ChangeFeedParams {
+            since,
+            limit: None,
+            school_id: None,
+            entity_type: None,
+        }
+    }
+
+    /// Reads the feed, waiting out transactions in other test databases that
+    /// briefly hold it back.
+    async fn read_feed(
+        pool: &PgPool,
+        school_id: Option<SchoolId>,
+        params: ChangeFeedParams,
+        expected: usize,
+    ) -> ChangeFeedResponse {
+        for _ in 0..100 {
+            let feed = ChangeService::get_changes(pool, school_id, params.clone())
+                .await
+                .unwrap();
+            if feed.data.len() >= expected {
+                return feed;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("change feed did not catch up");
+    }
+
+    #[test]
+    fn test_feed_position_roundtrip() {
+        let position = FeedPosition { tx_id: 812, id: 45 };
+        assert_eq!(FeedPosition::decode(&position.encode()).unwrap(), position);
+        assert_eq!(
+            FeedPosition::decode("garbage").unwrap_err().status,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_change_feed_records_versions_in_order(pool: PgPool) {
+        let school_id: SchoolId = sqlx::query_scalar!(
+            "INSERT INTO schools (name) VALUES ($1) RETURNING id",
+            format!("School {}", Uuid::new_v4())
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .into();
+        sqlx::query!(
+            "UPDATE schools SET address = 'New address' WHERE id = $1",
+            school_id.into_inner()
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let feed = read_feed(&pool, Some(school_id), params(None), 2).await;
+        assert_eq!(feed.data.len(), 2);
+        assert!(!feed.has_more);
+        assert_eq!(feed.data[0].entity_type, ChangeEntityType::School);
+        assert_eq!(feed.data[0].operation, ChangeOperation::Created);
+        assert_eq!(feed.data[1].operation, ChangeOperation::Updated);
+        assert_eq!(feed.data[1].version, 2);
+
+        let cursor = feed.next_cursor;
+        let level_id: Uuid = sqlx::query_scalar!(
+            "INSERT INTO levels (name, school_id) VALUES ('Grade 1', $1) RETURNING id",
+            school_id.into_inner()
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        sqlx::query!(
+            "INSERT INTO branches (name, level_id) VALUES ('A', $1)",
+            level_id
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let feed = read_feed(&pool, Some(school_id), params(Some(cursor.clone())), 2).await;
+        assert_eq!(feed.data.len(), 2);
+        assert_eq!(feed.data[0].entity_id, level_id);
+        assert_eq!(feed.data[1].entity_type, ChangeEntityType::Branch);
+        assert_eq!(feed.data[1].school_id, Some(school_id));
+
+        let other_school = Some(SchoolId::from(Uuid::new_v4()));
+        let feed = ChangeService::get_changes(&pool, other_school, params(Some(cursor)))
+            .await
+            .unwrap();
+        assert!(feed.data.is_empty());
+    }
+}
@@ -0,0 +1,327 @@
+use axum::extract::{Path, Query, State};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent, Visible};
+use chalkbyte_models::ids::{ClinicVisitId, UserId};
+
+use crate::middleware::auth::{
+    RequireClinicDelete, RequireClinicRead, RequireClinicRecord, RequireClinicUpdate,
+};
+use crate::modules::clinic::model::{
+    AdministerMedicationDto, ClinicMedication, ClinicVisit, ClinicVisitDetail,
+    ClinicVisitFilterParams, CreateClinicVisitDto, NotifyGuardianDto,
+    PaginatedClinicVisitsResponse, StudentMedicalProfile, UpdateClinicVisitDto,
+    UpsertMedicalProfileDto,
+};
+use crate::modules::clinic::service::ClinicService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::utils::email::EmailService;
+use crate::validator::ValidatedJson;
+
+/// Record a student's visit to the clinic
+#[utoipa::path(
+    post,
+    path = "/api/clinic/visits",
+    summary = "Record clinic visit",
+    request_body = CreateClinicVisitDto,
+    responses(
+        Created<ClinicVisitDetail>,
+        (status = 400, description = "Invalid input or user is not a student"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires clinic:record permission")
+    ),
+    tag = "Clinic",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_visit(
+    State(state): State<AppState>,
+    RequireClinicRecord(auth_user): RequireClinicRecord,
+    ValidatedJson(dto): ValidatedJson<CreateClinicVisitDto>,
+) -> Result<Created<Visible<ClinicVisitDetail>>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let recorded_by = auth_user.user_id()?;
+    let visit = ClinicService::create_visit(&state.db, school_id, recorded_by, dto).await?;
+
+    Ok(Created(Visible::new(visit, auth_user.field_access())))
+}
+
+/// List clinic visits for a school
+#[utoipa::path(
+    get,
+    path = "/api/clinic/visits",
+    summary = "List clinic visits",
+    params(ClinicVisitFilterParams),
+    responses(
+        (status = 200, description = "Clinic visits, newest first", body = PaginatedClinicVisitsResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires clinic:read permission")
+    ),
+    tag = "Clinic",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_visits(
+    State(state): State<AppState>,
+    RequireClinicRead(auth_user): RequireClinicRead,
+    Query(filters): Query<ClinicVisitFilterParams>,
+) -> Result<Visible<PaginatedClinicVisitsResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let visits = ClinicService::get_visits(&state.db, school_id, filters).await?;
+
+    Ok(Visible::new(visits, auth_user.field_access()))
+}
+
+/// Get a clinic visit with medication and the student's medical profile
+#[utoipa::path(
+    get,
+    path = "/api/clinic/visits/{id}",
+    summary = "Get clinic visit",
+    params(
+        ("id" = Uuid, Path, description = "Clinic visit ID")
+    ),
+    responses(
+        (status = 200, description = "Clinic visit", body = ClinicVisitDetail),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires clinic:read permission"),
+        (status = 404, description = "Clinic visit not found")
+    ),
+    tag = "Clinic",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_visit_by_id(
+    State(state): State<AppState>,
+    RequireClinicRead(auth_user): RequireClinicRead,
+    Path(id): Path<Uuid>,
+) -> Result<Visible<ClinicVisitDetail>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let visit =
+        ClinicService::get_visit_detail(&state.db, ClinicVisitId::from(id), school_id).await?;
+
+    Ok(Visible::new(visit, auth_user.field_access()))
+}
+
+/// Update a clinic visit
+#[utoipa::path(
+    put,
+    path = "/api/clinic/visits/{id}",
+    summary = "Update clinic visit",
+    params(
+        ("id" = Uuid, Path, description = "Clinic visit ID")
+    ),
+    request_body = UpdateClinicVisitDto,
+    responses(
+        (status = 200, description = "Clinic visit updated", body = ClinicVisit),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires clinic:update permission"),
+        (status = 404, description = "Clinic visit not found")
+    ),
+    tag = "Clinic",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_visit(
+    State(state): State<AppState>,
+    RequireClinicUpdate(auth_user): RequireClinicUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateClinicVisitDto>,
+) -> Result<Visible<ClinicVisit>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let visit =
+        ClinicService::update_visit(&state.db, ClinicVisitId::from(id), school_id, dto).await?;
+
+    Ok(Visible::new(visit, auth_user.field_access()))
+}
+
+/// Delete a clinic visit
+#[utoipa::path(
+    delete,
+    path = "/api/clinic/visits/{id}",
+    summary = "Delete clinic visit",
+    params(
+        ("id" = Uuid, Path, description = "Clinic visit ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires clinic:delete permission"),
+        (status = 404, description = "Clinic visit not found")
+    ),
+    tag = "Clinic",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_visit(
+    State(state): State<AppState>,
+    RequireClinicDelete(auth_user): RequireClinicDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    ClinicService::delete_visit(&state.db, ClinicVisitId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Record medication given during a clinic visit
+#[utoipa::path(
+    post,
+    path = "/api/clinic/visits/{id}/medications",
+    summary = "Record medication",
+    params(
+        ("id" = Uuid, Path, description = "Clinic visit ID")
+    ),
+    request_body = AdministerMedicationDto,
+    responses(
+        Created<ClinicMedication>,
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires clinic:record permission"),
+        (status = 404, description = "Clinic visit not found")
+    ),
+    tag = "Clinic",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn add_medication(
+    State(state): State<AppState>,
+    RequireClinicRecord(auth_user): RequireClinicRecord,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<AdministerMedicationDto>,
+) -> Result<Created<ClinicMedication>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let administered_by = auth_user.user_id()?;
+    let medication = ClinicService::add_medication(
+        &state.db,
+        ClinicVisitId::from(id),
+        school_id,
+        administered_by,
+        dto,
+    )
+    .await?;
+
+    Ok(Created(medication))
+}
+
+/// Notify the student's guardian about a clinic visit
+///
+/// The `email` method sends a message to the emergency contact email on the
+/// student's medical profile. Other methods record a notification that staff
+/// made themselves.
+#[utoipa::path(
+    post,
+    path = "/api/clinic/visits/{id}/notify-guardian",
+    summary = "Notify guardian",
+    params(
+        ("id" = Uuid, Path, description = "Clinic visit ID")
+    ),
+    request_body = NotifyGuardianDto,
+    responses(
+        (status = 200, description = "Guardian notification recorded", body = ClinicVisit),
+        (status = 400, description = "Invalid input or no emergency contact email on file"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires clinic:update permission"),
+        (status = 404, description = "Clinic visit not found")
+    ),
+    tag = "Clinic",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn notify_guardian(
+    State(state): State<AppState>,
+    RequireClinicUpdate(auth_user): RequireClinicUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<NotifyGuardianDto>,
+) -> Result<Visible<ClinicVisit>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let notified_by = auth_user.user_id()?;
+    let email = EmailService::new(state.db.clone(), state.email_config.clone());
+    let visit = ClinicService::notify_guardian(
+        &state.db,
+        &email,
+        ClinicVisitId::from(id),
+        school_id,
+        notified_by,
+        dto,
+    )
+    .await?;
+
+    Ok(Visible::new(visit, auth_user.field_access()))
+}
+
+/// Get a student's medical profile
+#[utoipa::path(
+    get,
+    path = "/api/clinic/students/{student_id}/profile",
+    summary = "Get medical profile",
+    params(
+        ("student_id" = Uuid, Path, description = "Student ID")
+    ),
+    responses(
+        (status = 200, description = "Medical profile", body = StudentMedicalProfile),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires clinic:read permission"),
+        (status = 404, description = "Student or medical profile not found")
+    ),
+    tag = "Clinic",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_medical_profile(
+    State(state): State<AppState>,
+    RequireClinicRead(auth_user): RequireClinicRead,
+    Path(student_id): Path<Uuid>,
+) -> Result<Visible<StudentMedicalProfile>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let profile =
+        ClinicService::get_medical_profile(&state.db, UserId::from(student_id), school_id).await?;
+
+    Ok(Visible::new(profile, auth_user.field_access()))
+}
+
+/// Create or replace a student's medical profile
+#[utoipa::path(
+    put,
+    path = "/api/clinic/students/{student_id}/profile",
+    summary = "Update medical profile",
+    params(
+        ("student_id" = Uuid, Path, description = "Student ID")
+    ),
+    request_body = UpsertMedicalProfileDto,
+    responses(
+        (status = 200, description = "Medical profile saved", body = StudentMedicalProfile),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires clinic:update permission"),
+        (status = 404, description = "Student not found")
+    ),
+    tag = "Clinic",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn upsert_medical_profile(
+    State(state): State<AppState>,
+    RequireClinicUpdate(auth_user): RequireClinicUpdate,
+    Path(student_id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpsertMedicalProfileDto>,
+) -> Result<Visible<StudentMedicalProfile>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let updated_by = auth_user.user_id()?;
+    let profile = ClinicService::upsert_medical_profile(
+        &state.db,
+        UserId::from(student_id),
+        school_id,
+        updated_by,
+        dto,
+    )
+    .await?;
+
+    Ok(Visible::new(profile, auth_user.field_access()))
+}
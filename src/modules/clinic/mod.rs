@@ -0,0 +1,13 @@
+//! Clinic module.
+//!
+//! This module records student visits to the school health room: symptoms,
+//! treatment, medication administered, and whether the student's guardian
+//! was notified. Each student can have a medical profile holding allergies,
+//! conditions, and emergency contact details; the emergency contact email is
+//! used when a guardian is notified by email. All clinic data is readable
+//! only with the dedicated `clinic:read` permission.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Clinic data models and DTOs.
+//!
+//! This module re-exports clinic models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all clinic models from the shared crate
+pub use chalkbyte_models::clinic::*;
@@ -0,0 +1,31 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    add_medication, create_visit, delete_visit, get_medical_profile, get_visit_by_id, get_visits,
+    notify_guardian, update_visit, upsert_medical_profile,
+};
+
+/// Initialize the clinic router
+/// Routes: POST /visits, GET /visits, GET /visits/{id}, PUT /visits/{id},
+/// DELETE /visits/{id}, POST /visits/{id}/medications,
+/// POST /visits/{id}/notify-guardian, GET /students/{student_id}/profile,
+/// PUT /students/{student_id}/profile
+pub fn init_clinic_router() -> Router<AppState> {
+    Router::new()
+        .route("/visits", post(create_visit).get(get_visits))
+        .route(
+            "/visits/{id}",
+            get(get_visit_by_id).put(update_visit).delete(delete_visit),
+        )
+        .route("/visits/{id}/medications", post(add_medication))
+        .route("/visits/{id}/notify-guardian", post(notify_guardian))
+        .route(
+            "/students/{student_id}/profile",
+            get(get_medical_profile).put(upsert_medical_profile),
+        )
+}
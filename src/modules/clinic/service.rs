@@ -0,0 +1,711 @@
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, ErrorCode, PaginationMeta};
+use chalkbyte_models::ids::{ClinicVisitId, SchoolId, UserId};
+
+use crate::modules::clinic::model::{
+    AdministerMedicationDto, ClinicMedication, ClinicVisit, ClinicVisitDetail,
+    ClinicVisitFilterParams, ClinicVisitOutcome, ClinicVisitSummary, CreateClinicVisitDto,
+    GuardianNotificationMethod, NotifyGuardianDto, PaginatedClinicVisitsResponse,
+    StudentMedicalProfile, UpdateClinicVisitDto, UpsertMedicalProfileDto,
+};
+use crate::modules::email_branding::service::EmailBrandingService;
+use crate::modules::users::model::system_roles;
+use crate::utils::email::EmailService;
+
+const PROFILE_COLUMNS: &str = "student_id, school_id, blood_group, allergies, chronic_conditions, \
+     current_medications, dietary_requirements, emergency_contact_name, \
+     emergency_contact_relationship, emergency_contact_phone, emergency_contact_email, notes, \
+     updated_by, created_at, updated_at";
+
+const VISIT_COLUMNS: &str = "id, school_id, student_id, visited_at, symptoms, temperature_celsius, \
+     treatment, outcome, recorded_by, guardian_notified_at, guardian_notified_by, \
+     guardian_notification_method, guardian_notification_notes, created_at, updated_at";
+
+const MEDICATION_COLUMNS: &str =
+    "id, visit_id, medication_name, dosage, administered_at, administered_by, created_at";
+
+pub struct ClinicService;
+
+impl ClinicService {
+    /// Look up the school of a student, optionally restricted to a school.
+    async fn find_student_school(
+        db: &PgPool,
+        student_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Option<SchoolId>, AppError> {
+        let school = sqlx::query_scalar::<_, SchoolId>(
+            r#"SELECT u.school_id FROM users u
+               JOIN user_roles ur ON ur.user_id = u.id
+               WHERE u.id = $1 AND ur.role_id = $2 AND u.school_id IS NOT NULL
+                 AND ($3::uuid IS NULL OR u.school_id = $3)"#,
+        )
+        .bind(student_id)
+        .bind(system_roles::STUDENT)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?;
+
+        Ok(school)
+    }
+
+    async fn find_profile(
+        db: &PgPool,
+        student_id: UserId,
+    ) -> Result<Option<StudentMedicalProfile>, AppError> {
+        let profile = sqlx::query_as::<_, StudentMedicalProfile>(&format!(
+            "SELECT {PROFILE_COLUMNS} FROM student_medical_profiles WHERE student_id = $1"
+        ))
+        .bind(student_id)
+        .fetch_optional(db)
+        .await?;
+
+        Ok(profile)
+    }
+
+    async fn find_visit(
+        db: &PgPool,
+        visit_id: ClinicVisitId,
+        school_id: Option<SchoolId>,
+    ) -> Result<ClinicVisit, AppError> {
+        sqlx::query_as::<_, ClinicVisit>(&format!(
+            "SELECT {VISIT_COLUMNS} FROM clinic_visits
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(visit_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Clinic visit not found")))
+    }
+
+    /// Get a student's medical profile.
+    #[instrument(skip(db))]
+    pub async fn get_medical_profile(
+        db: &PgPool,
+        student_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<StudentMedicalProfile, AppError> {
+        Self::find_student_school(db, student_id, school_id)
+            .await?
+            .ok_or_else(|| AppError::from_code(ErrorCode::StudentNotFound))?;
+
+        Self::find_profile(db, student_id)
+            .await?
+            .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Medical profile not found")))
+    }
+
+    /// Create or replace a student's medical profile.
+    #[instrument(skip(db, dto))]
+    pub async fn upsert_medical_profile(
+        db: &PgPool,
+        student_id: UserId,
+        school_id: Option<SchoolId>,
+        updated_by: UserId,
+        dto: UpsertMedicalProfileDto,
+    ) -> Result<StudentMedicalProfile, AppError> {
+        let student_school = Self::find_student_school(db, student_id, school_id)
+            .await?
+            .ok_or_else(|| AppError::from_code(ErrorCode::StudentNotFound))?;
+
+        let profile = sqlx::query_as::<_, StudentMedicalProfile>(&format!(
+            "INSERT INTO student_medical_profiles (student_id, school_id, blood_group, allergies,
+                 chronic_conditions, current_medications, dietary_requirements,
+                 emergency_contact_name, emergency_contact_relationship, emergency_contact_phone,
+                 emergency_contact_email, notes, updated_by)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+             ON CONFLICT (student_id) DO UPDATE SET
+                 blood_group = EXCLUDED.blood_group,
+                 allergies = EXCLUDED.allergies,
+                 chronic_conditions = EXCLUDED.chronic_conditions,
+                 current_medications = EXCLUDED.current_medications,
+                 dietary_requirements = EXCLUDED.dietary_requirements,
+                 emergency_contact_name = EXCLUDED.emergency_contact_name,
+                 emergency_contact_relationship = EXCLUDED.emergency_contact_relationship,
+                 emergency_contact_phone = EXCLUDED.emergency_contact_phone,
+                 emergency_contact_email = EXCLUDED.emergency_contact_email,
+                 notes = EXCLUDED.notes,
+                 updated_by = EXCLUDED.updated_by
+             RETURNING {PROFILE_COLUMNS}"
+        ))
+        .bind(student_id)
+        .bind(student_school)
+        .bind(&dto.blood_group)
+        .bind(&dto.allergies)
+        .bind(&dto.chronic_conditions)
+        .bind(&dto.current_medications)
+        .bind(&dto.dietary_requirements)
+        .bind(&dto.emergency_contact_name)
+        .bind(&dto.emergency_contact_relationship)
+        .bind(&dto.emergency_contact_phone)
+        .bind(&dto.emergency_contact_email)
+        .bind(&dto.notes)
+        .bind(updated_by)
+        .fetch_one(db)
+        .await?;
+
+        Ok(profile)
+    }
+
+    /// Record a clinic visit along with any medication given.
+    #[instrument(skip(db, dto))]
+    pub async fn create_visit(
+        db: &PgPool,
+        school_id: Option<SchoolId>,
+        recorded_by: UserId,
+        dto: CreateClinicVisitDto,
+    ) -> Result<ClinicVisitDetail, AppError> {
+        let student_school = Self::find_student_school(db, dto.student_id, school_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::bad_request(anyhow::anyhow!(
+                    "Clinic visits can only be recorded for students"
+                ))
+            })?;
+
+        let mut tx = db.begin().await?;
+
+        let visit = sqlx::query_as::<_, ClinicVisit>(&format!(
+            "INSERT INTO clinic_visits (school_id, student_id, visited_at, symptoms,
+                 temperature_celsius, treatment, outcome, recorded_by)
+             VALUES ($1, $2, COALESCE($3, NOW()), $4, $5, $6, $7, $8)
+             RETURNING {VISIT_COLUMNS}"
+        ))
+        .bind(student_school)
+        .bind(dto.student_id)
+        .bind(dto.visited_at)
+        .bind(&dto.symptoms)
+        .bind(dto.temperature_celsius)
+        .bind(&dto.treatment)
+        .bind(dto.outcome.unwrap_or(ClinicVisitOutcome::ReturnedToClass))
+        .bind(recorded_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut medications = Vec::with_capacity(dto.medications.len());
+        for medication in &dto.medications {
+            medications.push(
+                sqlx::query_as::<_, ClinicMedication>(&format!(
+                    "INSERT INTO clinic_medications (visit_id, medication_name, dosage,
+                         administered_at, administered_by)
+                     VALUES ($1, $2, $3, COALESCE($4, NOW()), $5)
+                     RETURNING {MEDICATION_COLUMNS}"
+                ))
+                .bind(visit.id)
+                .bind(&medication.medication_name)
+                .bind(&medication.dosage)
+                .bind(medication.administered_at)
+                .bind(recorded_by)
+                .fetch_one(&mut *tx)
+                .await?,
+            );
+        }
+
+        tx.commit().await?;
+
+        let medical_profile = Self::find_profile(db, visit.student_id).await?;
+
+        Ok(ClinicVisitDetail {
+            visit,
+            medications,
+            medical_profile,
+        })
+    }
+
+    /// Get paginated clinic visits for a school, newest first.
+    #[instrument(skip(db))]
+    pub async fn get_visits(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: ClinicVisitFilterParams,
+    ) -> Result<PaginatedClinicVisitsResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+
+        let where_clause = r#"WHERE v.school_id = $1
+              AND ($2::uuid IS NULL OR v.student_id = $2)
+              AND ($3::date IS NULL OR v.visited_at >= $3::date)
+              AND ($4::date IS NULL OR v.visited_at < $4::date + 1)
+              AND ($5::text IS NULL OR v.outcome = $5)"#;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM clinic_visits v {where_clause}"
+        ))
+        .bind(school_id)
+        .bind(filters.student_id)
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(filters.outcome)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, ClinicVisitSummary>(&format!(
+            r#"SELECT v.id, v.student_id, u.first_name AS student_first_name,
+                      u.last_name AS student_last_name, v.visited_at, v.symptoms, v.outcome,
+                      v.guardian_notified_at
+               FROM clinic_visits v
+               JOIN users u ON u.id = v.student_id
+               {where_clause}
+               ORDER BY v.visited_at DESC
+               LIMIT $6 OFFSET $7"#
+        ))
+        .bind(school_id)
+        .bind(filters.student_id)
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(filters.outcome)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedClinicVisitsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Get a clinic visit with its medication and the student's medical profile.
+    #[instrument(skip(db))]
+    pub async fn get_visit_detail(
+        db: &PgPool,
+        visit_id: ClinicVisitId,
+        school_id: Option<SchoolId>,
+    ) -> Result<ClinicVisitDetail, AppError> {
+        let visit = Self::find_visit(db, visit_id, school_id).await?;
+
+        let medications = sqlx::query_as::<_, ClinicMedication>(&format!(
+            "SELECT {MEDICATION_COLUMNS} FROM clinic_medications
+             WHERE visit_id = $1
+             ORDER BY administered_at"
+        ))
+        .bind(visit_id)
+        .fetch_all(db)
+        .await?;
+
+        let medical_profile = Self::find_profile(db, visit.student_id).await?;
+
+        Ok(ClinicVisitDetail {
+            visit,
+            medications,
+            medical_profile,
+        })
+    }
+
+    /// Update a clinic visit.
+    #[instrument(skip(db, dto))]
+    pub async fn update_visit(
+        db: &PgPool,
+        visit_id: ClinicVisitId,
+        school_id: Option<SchoolId>,
+        dto: UpdateClinicVisitDto,
+    ) -> Result<ClinicVisit, AppError> {
+        Self::find_visit(db, visit_id, school_id).await?;
+
+        let visit = sqlx::query_as::<_, ClinicVisit>(&format!(
+            "UPDATE clinic_visits
+             SET symptoms = COALESCE($2, symptoms),
+                 temperature_celsius = COALESCE($3, temperature_celsius),
+                 treatment = COALESCE($4, treatment),
+                 outcome = COALESCE($5, outcome)
+             WHERE id = $1
+             RETURNING {VISIT_COLUMNS}"
+        ))
+        .bind(visit_id)
+        .bind(&dto.symptoms)
+        .bind(dto.temperature_celsius)
+        .bind(&dto.treatment)
+        .bind(dto.outcome)
+        .fetch_one(db)
+        .await?;
+
+        Ok(visit)
+    }
+
+    /// Delete a clinic visit and its medication records.
+    #[instrument(skip(db))]
+    pub async fn delete_visit(
+        db: &PgPool,
+        visit_id: ClinicVisitId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "DELETE FROM clinic_visits WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(visit_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Clinic visit not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Record medication given during an existing visit.
+    #[instrument(skip(db))]
+    pub async fn add_medication(
+        db: &PgPool,
+        visit_id: ClinicVisitId,
+        school_id: Option<SchoolId>,
+        administered_by: UserId,
+        dto: AdministerMedicationDto,
+    ) -> Result<ClinicMedication, AppError> {
+        Self::find_visit(db, visit_id, school_id).await?;
+
+        let medication = sqlx::query_as::<_, ClinicMedication>(&format!(
+            "INSERT INTO clinic_medications (visit_id, medication_name, dosage, administered_at,
+                 administered_by)
+             VALUES ($1, $2, $3, COALESCE($4, NOW()), $5)
+             RETURNING {MEDICATION_COLUMNS}"
+        ))
+        .bind(visit_id)
+        .bind(&dto.medication_name)
+        .bind(&dto.dosage)
+        .bind(dto.administered_at)
+        .bind(administered_by)
+        .fetch_one(db)
+        .await?;
+
+        Ok(medication)
+    }
+
+    /// Notify a student's guardian about a clinic visit and record it on the visit.
+    ///
+    /// Email notifications go to the emergency contact on the medical profile.
+    /// Other methods record a notification already made by staff.
+    #[instrument(skip(db, email))]
+    pub async fn notify_guardian(
+        db: &PgPool,
+        email: &EmailService,
+        visit_id: ClinicVisitId,
+        school_id: Option<SchoolId>,
+        notified_by: UserId,
+        dto: NotifyGuardianDto,
+    ) -> Result<ClinicVisit, AppError> {
+        let visit = Self::find_visit(db, visit_id, school_id).await?;
+
+        if dto.method == GuardianNotificationMethod::Email {
+            let contact = Self::find_profile(db, visit.student_id)
+                .await?
+                .and_then(|p| {
+                    p.emergency_contact_email
+                        .map(|email| (email, p.emergency_contact_name))
+                })
+                .ok_or_else(|| {
+                    AppError::bad_request(anyhow::anyhow!(
+                        "Student has no emergency contact email on their medical profile"
+                    ))
+                })?;
+
+            let (first_name, last_name) = sqlx::query_as::<_, (String, String)>(
+                "SELECT first_name, last_name FROM users WHERE id = $1",
+            )
+            .bind(visit.student_id)
+            .fetch_one(db)
+            .await?;
+
+            let (to_email, to_name) = contact;
+            email
+                .with_branding(EmailBrandingService::email_branding(db, visit.school_id).await?)
+                .send_clinic_visit_notification(
+                    &to_email,
+                    to_name.as_deref().unwrap_or("Parent/Guardian"),
+                    &format!("{first_name} {last_name}"),
+                    &Self::visit_summary(&visit),
+                )
+                .await?;
+        }
+
+        let visit = sqlx::query_as::<_, ClinicVisit>(&format!(
+            "UPDATE clinic_visits
+             SET guardian_notified_at = NOW(),
+                 guardian_notified_by = $2,
+                 guardian_notification_method = $3,
+                 guardian_notification_notes = $4
+             WHERE id = $1
+             RETURNING {VISIT_COLUMNS}"
+        ))
+        .bind(visit_id)
+        .bind(notified_by)
+        .bind(dto.method)
+        .bind(&dto.notes)
+        .fetch_one(db)
+        .await?;
+
+        Ok(visit)
+    }
+
+    /// Plain-language summary of a visit for guardians.
+    fn visit_summary(visit: &ClinicVisit) -> String {
+        let outcome = match visit.outcome {
+            ClinicVisitOutcome::ReturnedToClass => "was treated and returned to class",
+            ClinicVisitOutcome::Rested => "rested in the clinic",
+            ClinicVisitOutcome::SentHome => "has been sent home",
+            ClinicVisitOutcome::Referred => "has been referred for further care",
+        };
+
+        match &visit.treatment {
+            Some(treatment) => format!(
+                "Symptoms: {}. Treatment: {}. Your child {}.",
+                visit.symptoms, treatment, outcome
+            ),
+            None => format!("Symptoms: {}. Your child {}.", visit.symptoms, outcome),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use chalkbyte_config::EmailConfig;
+    use chalkbyte_models::ids::RoleId;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId, role_id: RoleId) -> UserId {
+        let user_id: UserId = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Test', 'User', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id.into_inner(),
+            role_id.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id
+    }
+
+    fn disabled_email(pool: &PgPool) -> EmailService {
+        EmailService::new(
+            pool.clone(),
+            EmailConfig {
+                enabled: false,
+                smtp_host: "localhost".to_string(),
+                smtp_port: 1025,
+                smtp_username: String::new(),
+                smtp_password: String::new(),
+                from_email: "noreply@example.com".to_string(),
+                from_name: "Chalkbyte".to_string(),
+                frontend_url: "http://localhost:3000".to_string(),
+            },
+        )
+    }
+
+    fn visit_dto(student_id: UserId) -> CreateClinicVisitDto {
+        CreateClinicVisitDto {
+            student_id,
+            visited_at: None,
+            symptoms: "Headache".to_string(),
+            temperature_celsius: Some(37.8),
+            treatment: Some("Rest and water".to_string()),
+            outcome: Some(ClinicVisitOutcome::Rested),
+            medications: vec![AdministerMedicationDto {
+                medication_name: "Paracetamol".to_string(),
+                dosage: "500mg".to_string(),
+                administered_at: None,
+            }],
+        }
+    }
+
+    fn profile_dto(email: Option<&str>) -> UpsertMedicalProfileDto {
+        UpsertMedicalProfileDto {
+            blood_group: Some("O+".to_string()),
+            allergies: Some("Peanuts".to_string()),
+            chronic_conditions: None,
+            current_medications: None,
+            dietary_requirements: None,
+            emergency_contact_name: Some("Ngozi Obi".to_string()),
+            emergency_contact_relationship: Some("Mother".to_string()),
+            emergency_contact_phone: Some("+2348000000000".to_string()),
+            emergency_contact_email: email.map(str::to_string),
+            notes: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_create_visit_links_medication_and_profile(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let nurse = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+
+        ClinicService::upsert_medical_profile(
+            &pool,
+            student,
+            Some(school_id),
+            nurse,
+            profile_dto(None),
+        )
+        .await
+        .unwrap();
+
+        let detail = ClinicService::create_visit(&pool, Some(school_id), nurse, visit_dto(student))
+            .await
+            .unwrap();
+        assert_eq!(detail.visit.school_id, school_id);
+        assert_eq!(detail.medications.len(), 1);
+        assert_eq!(
+            detail.medical_profile.unwrap().allergies.as_deref(),
+            Some("Peanuts")
+        );
+
+        let err = ClinicService::create_visit(&pool, Some(school_id), nurse, visit_dto(nurse))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_upsert_medical_profile_replaces_existing(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let nurse = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+
+        let err = ClinicService::get_medical_profile(&pool, student, Some(school_id))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        ClinicService::upsert_medical_profile(
+            &pool,
+            student,
+            Some(school_id),
+            nurse,
+            profile_dto(None),
+        )
+        .await
+        .unwrap();
+        let updated = ClinicService::upsert_medical_profile(
+            &pool,
+            student,
+            Some(school_id),
+            nurse,
+            UpsertMedicalProfileDto {
+                allergies: None,
+                ..profile_dto(Some("ngozi@example.com"))
+            },
+        )
+        .await
+        .unwrap();
+        assert!(updated.allergies.is_none());
+        assert_eq!(
+            updated.emergency_contact_email.as_deref(),
+            Some("ngozi@example.com")
+        );
+
+        let other_school = create_test_school(&pool).await;
+        let err = ClinicService::get_medical_profile(&pool, student, Some(other_school))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_notify_guardian(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let nurse = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let email = disabled_email(&pool);
+
+        let detail = ClinicService::create_visit(&pool, Some(school_id), nurse, visit_dto(student))
+            .await
+            .unwrap();
+        assert!(detail.visit.guardian_notified_at.is_none());
+
+        let err = ClinicService::notify_guardian(
+            &pool,
+            &email,
+            detail.visit.id,
+            Some(school_id),
+            nurse,
+            NotifyGuardianDto {
+                method: GuardianNotificationMethod::Email,
+                notes: None,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let visit = ClinicService::notify_guardian(
+            &pool,
+            &email,
+            detail.visit.id,
+            Some(school_id),
+            nurse,
+            NotifyGuardianDto {
+                method: GuardianNotificationMethod::Phone,
+                notes: Some("Spoke to mother".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(visit.guardian_notified_at.is_some());
+        assert_eq!(visit.guardian_notified_by, Some(nurse));
+        assert_eq!(
+            visit.guardian_notification_method,
+            Some(GuardianNotificationMethod::Phone)
+        );
+
+        ClinicService::upsert_medical_profile(
+            &pool,
+            student,
+            Some(school_id),
+            nurse,
+            profile_dto(Some("ngozi@example.com")),
+        )
+        .await
+        .unwrap();
+        let visit = ClinicService::notify_guardian(
+            &pool,
+            &email,
+            detail.visit.id,
+            Some(school_id),
+            nurse,
+            NotifyGuardianDto {
+                method: GuardianNotificationMethod::Email,
+                notes: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            visit.guardian_notification_method,
+            Some(GuardianNotificationMethod::Email)
+        );
+    }
+}
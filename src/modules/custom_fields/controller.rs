@@ -0,0 +1,185 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::CustomFieldId;
+
+use crate::middleware::auth::{
+    RequireCustomFieldsCreate, RequireCustomFieldsDelete, RequireCustomFieldsRead,
+    RequireCustomFieldsUpdate,
+};
+use crate::modules::custom_fields::model::{
+    CreateCustomFieldDto, CustomFieldDefinition, CustomFieldFilterParams, UpdateCustomFieldDto,
+};
+use crate::modules::custom_fields::service::CustomFieldService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Define a custom field for students or users
+#[utoipa::path(
+    post,
+    path = "/api/custom-fields",
+    summary = "Create custom field",
+    request_body = CreateCustomFieldDto,
+    responses(
+        Created<CustomFieldDefinition>,
+        (status = 400, description = "Invalid key or options, duplicate key, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires custom_fields:create permission")
+    ),
+    tag = "Custom Fields",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_custom_field(
+    State(state): State<AppState>,
+    RequireCustomFieldsCreate(auth_user): RequireCustomFieldsCreate,
+    ValidatedJson(dto): ValidatedJson<CreateCustomFieldDto>,
+) -> Result<Created<CustomFieldDefinition>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let definition = CustomFieldService::create_definition(&state.db, school_id, dto).await?;
+
+    Ok(Created(definition))
+}
+
+/// List a school's custom field definitions
+#[utoipa::path(
+    get,
+    path = "/api/custom-fields",
+    summary = "List custom fields",
+    params(CustomFieldFilterParams),
+    responses(
+        (status = 200, description = "Custom field definitions in display order", body = Vec<CustomFieldDefinition>),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires custom_fields:read permission")
+    ),
+    tag = "Custom Fields",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_custom_fields(
+    State(state): State<AppState>,
+    RequireCustomFieldsRead(auth_user): RequireCustomFieldsRead,
+    Query(filters): Query<CustomFieldFilterParams>,
+) -> Result<Json<Vec<CustomFieldDefinition>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let definitions =
+        CustomFieldService::get_definitions(&state.db, school_id, filters.entity_type).await?;
+
+    Ok(Json(definitions))
+}
+
+/// Get a custom field definition by ID
+#[utoipa::path(
+    get,
+    path = "/api/custom-fields/{id}",
+    summary = "Get custom field",
+    params(
+        ("id" = Uuid, Path, description = "Custom field ID")
+    ),
+    responses(
+        (status = 200, description = "Custom field definition", body = CustomFieldDefinition),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires custom_fields:read permission"),
+        (status = 404, description = "Custom field not found")
+    ),
+    tag = "Custom Fields",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_custom_field_by_id(
+    State(state): State<AppState>,
+    RequireCustomFieldsRead(auth_user): RequireCustomFieldsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CustomFieldDefinition>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let definition =
+        CustomFieldService::get_definition_by_id(&state.db, CustomFieldId::from(id), school_id)
+            .await?;
+
+    Ok(Json(definition))
+}
+
+/// Update a custom field definition
+///
+/// The key and type of a field cannot be changed.
+#[utoipa::path(
+    put,
+    path = "/api/custom-fields/{id}",
+    summary = "Update custom field",
+    params(
+        ("id" = Uuid, Path, description = "Custom field ID")
+    ),
+    request_body = UpdateCustomFieldDto,
+    responses(
+        (status = 200, description = "Custom field updated", body = CustomFieldDefinition),
+        (status = 400, description = "Invalid input or options"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires custom_fields:update permission"),
+        (status = 404, description = "Custom field not found")
+    ),
+    tag = "Custom Fields",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_custom_field(
+    State(state): State<AppState>,
+    RequireCustomFieldsUpdate(auth_user): RequireCustomFieldsUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateCustomFieldDto>,
+) -> Result<Json<CustomFieldDefinition>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let definition =
+        CustomFieldService::update_definition(&state.db, CustomFieldId::from(id), school_id, dto)
+            .await?;
+
+    Ok(Json(definition))
+}
+
+/// Delete a custom field definition
+///
+/// Stored values for the field are removed from every user in the school.
+#[utoipa::path(
+    delete,
+    path = "/api/custom-fields/{id}",
+    summary = "Delete custom field",
+    params(
+        ("id" = Uuid, Path, description = "Custom field ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires custom_fields:delete permission"),
+        (status = 404, description = "Custom field not found")
+    ),
+    tag = "Custom Fields",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_custom_field(
+    State(state): State<AppState>,
+    RequireCustomFieldsDelete(auth_user): RequireCustomFieldsDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    CustomFieldService::delete_definition(
+        &state.db,
+        CustomFieldId::from(id),
+        school_id,
+        state.cache.as_ref(),
+    )
+    .await?;
+
+    Ok(NoContent)
+}
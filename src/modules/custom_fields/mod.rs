@@ -0,0 +1,15 @@
+//! Custom fields module.
+//!
+//! This module lets each school define its own extra attributes for students
+//! and other users, such as a house or a transport code. Definitions set a
+//! key, label, type, whether the field is required, and the allowed options
+//! for select fields.
+//!
+//! Values are stored in the `custom_fields` JSONB column of `users` and are
+//! validated against the school's definitions by the students and users
+//! services whenever they are written.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
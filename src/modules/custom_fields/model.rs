@@ -0,0 +1,7 @@
+//! Custom field data models and DTOs.
+//!
+//! This module re-exports custom field models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all custom field models from the shared crate
+pub use chalkbyte_models::custom_fields::*;
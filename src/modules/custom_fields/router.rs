@@ -0,0 +1,21 @@
+use axum::{Router, routing::get};
+
+use crate::state::AppState;
+
+use super::controller::{
+    create_custom_field, delete_custom_field, get_custom_field_by_id, get_custom_fields,
+    update_custom_field,
+};
+
+/// Initialize the custom fields router
+/// Routes: POST /, GET /, GET /{id}, PUT /{id}, DELETE /{id}
+pub fn init_custom_fields_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_custom_fields).post(create_custom_field))
+        .route(
+            "/{id}",
+            get(get_custom_field_by_id)
+                .put(update_custom_field)
+                .delete(delete_custom_field),
+        )
+}
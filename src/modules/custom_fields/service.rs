@@ -0,0 +1,444 @@
+use chalkbyte_cache::{RedisCache, invalidate};
+use serde_json::Value;
+use sqlx::{PgExecutor, PgPool};
+use tracing::instrument;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::{CustomFieldId, SchoolId};
+
+use crate::modules::custom_fields::model::{
+    CreateCustomFieldDto, CustomFieldDefinition, CustomFieldEntity, CustomFieldValues,
+    UpdateCustomFieldDto, apply_custom_field_values,
+};
+
+const DEFINITION_COLUMNS: &str = "id, school_id, entity_type, field_key, label, field_type, \
+     required, options, position, created_at, updated_at";
+
+fn validate_field_key(key: &str) -> Result<(), AppError> {
+    let mut chars = key.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::bad_request(anyhow::anyhow!(
+            "Field key must start with a lowercase letter and contain only lowercase letters, digits, and underscores"
+        )))
+    }
+}
+
+fn validate_options(field_type_has_options: bool, options: &[String]) -> Result<(), AppError> {
+    if !field_type_has_options {
+        if !options.is_empty() {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only select and multi-select fields have options"
+            )));
+        }
+        return Ok(());
+    }
+
+    if options.is_empty() {
+        return Err(AppError::bad_request(anyhow::anyhow!(
+            "Select and multi-select fields need at least one option"
+        )));
+    }
+    if options.iter().any(|o| o.trim().is_empty()) {
+        return Err(AppError::bad_request(anyhow::anyhow!(
+            "Options cannot be blank"
+        )));
+    }
+    for (i, option) in options.iter().enumerate() {
+        if options[..i].contains(option) {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Duplicate option '{}'",
+                option
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+pub struct CustomFieldService;
+
+impl CustomFieldService {
+    async fn find_definition(
+        db: &PgPool,
+        field_id: CustomFieldId,
+        school_id: Option<SchoolId>,
+    ) -> Result<CustomFieldDefinition, AppError> {
+        sqlx::query_as::<_, CustomFieldDefinition>(&format!(
+            "SELECT {DEFINITION_COLUMNS} FROM custom_field_definitions
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(field_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Custom field not found")))
+    }
+
+    #[instrument(skip(db, dto))]
+    pub async fn create_definition(
+        db: &PgPool,
+        school_id: SchoolId,
+        dto: CreateCustomFieldDto,
+    ) -> Result<CustomFieldDefinition, AppError> {
+        validate_field_key(&dto.field_key)?;
+        validate_options(dto.field_type.has_options(), &dto.options)?;
+
+        let definition = sqlx::query_as::<_, CustomFieldDefinition>(&format!(
+            "INSERT INTO custom_field_definitions
+                 (school_id, entity_type, field_key, label, field_type, required, options, position)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING {DEFINITION_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(dto.entity_type)
+        .bind(&dto.field_key)
+        .bind(&dto.label)
+        .bind(dto.field_type)
+        .bind(dto.required)
+        .bind(&dto.options)
+        .bind(dto.position)
+        .fetch_one(db)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(db_err) = &e
+                && db_err.is_unique_violation()
+            {
+                return AppError::bad_request(anyhow::anyhow!(
+                    "A custom field with key '{}' already exists",
+                    dto.field_key
+                ));
+            }
+            AppError::from(e)
+        })?;
+
+        Ok(definition)
+    }
+
+    /// Get a school's custom field definitions in display order.
+    #[instrument(skip(db))]
+    pub async fn get_definitions(
+        db: impl PgExecutor<'_>,
+        school_id: SchoolId,
+        entity_type: Option<CustomFieldEntity>,
+    ) -> Result<Vec<CustomFieldDefinition>, AppError> {
+        let definitions = sqlx::query_as::<_, CustomFieldDefinition>(&format!(
+            "SELECT {DEFINITION_COLUMNS} FROM custom_field_definitions
+             WHERE school_id = $1 AND ($2::text IS NULL OR entity_type = $2)
+             ORDER BY entity_type, position, label"
+        ))
+        .bind(school_id)
+        .bind(entity_type)
+        .fetch_all(db)
+        .await?;
+
+        Ok(definitions)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_definition_by_id(
+        db: &PgPool,
+        field_id: CustomFieldId,
+        school_id: Option<SchoolId>,
+    ) -> Result<CustomFieldDefinition, AppError> {
+        Self::find_definition(db, field_id, school_id).await
+    }
+
+    /// Update a definition's label, requirement, options, or position.
+    ///
+    /// The key and type are fixed once a field exists, since stored values
+    /// depend on them.
+    #[instrument(skip(db, dto))]
+    pub async fn update_definition(
+        db: &PgPool,
+        field_id: CustomFieldId,
+        school_id: Option<SchoolId>,
+        dto: UpdateCustomFieldDto,
+    ) -> Result<CustomFieldDefinition, AppError> {
+        let existing = Self::find_definition(db, field_id, school_id).await?;
+
+        if let Some(options) = &dto.options {
+            validate_options(existing.field_type.has_options(), options)?;
+        }
+
+        let definition = sqlx::query_as::<_, CustomFieldDefinition>(&format!(
+            "UPDATE custom_field_definitions
+             SET label = COALESCE($2, label),
+                 required = COALESCE($3, required),
+                 options = COALESCE($4, options),
+                 position = COALESCE($5, position)
+             WHERE id = $1
+             RETURNING {DEFINITION_COLUMNS}"
+        ))
+        .bind(existing.id)
+        .bind(&dto.label)
+        .bind(dto.required)
+        .bind(&dto.options)
+        .bind(dto.position)
+        .fetch_one(db)
+        .await?;
+
+        Ok(definition)
+    }
+
+    /// Delete a definition and remove its values from the school's users.
+    #[instrument(skip(db, cache))]
+    pub async fn delete_definition(
+        db: &PgPool,
+        field_id: CustomFieldId,
+        school_id: Option<SchoolId>,
+        cache: Option<&RedisCache>,
+    ) -> Result<(), AppError> {
+        let definition = Self::find_definition(db, field_id, school_id).await?;
+
+        let mut tx = db.begin().await?;
+
+        sqlx::query("DELETE FROM custom_field_definitions WHERE id = $1")
+            .bind(definition.id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            "UPDATE users SET custom_fields = custom_fields - $2
+             WHERE school_id = $1 AND custom_fields ? $2",
+        )
+        .bind(definition.school_id)
+        .bind(&definition.field_key)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        invalidate::user(cache, None, Some(definition.school_id.into())).await;
+
+        Ok(())
+    }
+
+    /// Validate custom field changes for a record and return its new values.
+    ///
+    /// `current` is the record's stored `custom_fields` object. Records with
+    /// no school have no definitions, so any change to them is rejected.
+    #[instrument(skip(db, current, changes))]
+    pub async fn resolve_values(
+        db: impl PgExecutor<'_>,
+        school_id: Option<SchoolId>,
+        entity_type: CustomFieldEntity,
+        current: &Value,
+        changes: &CustomFieldValues,
+        creating: bool,
+    ) -> Result<Value, AppError> {
+        let definitions = match school_id {
+            Some(school_id) => Self::get_definitions(db, school_id, Some(entity_type)).await?,
+            None => Vec::new(),
+        };
+
+        let current = current.as_object().cloned().unwrap_or_default();
+        let values = apply_custom_field_values(&definitions, &current, changes, creating)
+            .map_err(|e| AppError::bad_request(anyhow::anyhow!(e)))?;
+
+        Ok(Value::Object(values))
+    }
+
+    /// Parse a `custom_fields` list filter into a JSON object for `@>` matching.
+    pub fn parse_filter(filter: Option<&str>) -> Result<Option<Value>, AppError> {
+        let Some(filter) = filter else {
+            return Ok(None);
+        };
+
+        match serde_json::from_str::<Value>(filter) {
+            Ok(value @ Value::Object(_)) => Ok(Some(value)),
+            _ => Err(AppError::bad_request(anyhow::anyhow!(
+                "custom_fields filter must be a JSON object"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::custom_fields::model::CustomFieldType;
+    use crate::modules::students::model::CreateStudentDto;
+    use crate::modules::students::service::StudentService;
+    use axum::http::StatusCode;
+    use chalkbyte_core::{PasswordPolicy, SortParams};
+    use chalkbyte_models::Email;
+    use serde_json::json;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        let school_id = sqlx::query_scalar!(
+            "INSERT INTO schools (name) VALUES ($1) RETURNING id",
+            format!("Custom Fields School {}", uuid::Uuid::new_v4())
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        SchoolId::from(school_id)
+    }
+
+    fn house_field(school_id: SchoolId) -> CreateCustomFieldDto {
+        CreateCustomFieldDto {
+            entity_type: CustomFieldEntity::Student,
+            field_key: "house".to_string(),
+            label: "House".to_string(),
+            field_type: CustomFieldType::Select,
+            required: true,
+            options: vec!["red".to_string(), "blue".to_string()],
+            position: 0,
+            school_id: Some(school_id),
+        }
+    }
+
+    fn student_dto(email: &str, custom_fields: Option<Value>) -> CreateStudentDto {
+        CreateStudentDto {
+            first_name: "Ada".to_string(),
+            last_name: "Obi".to_string(),
+            email: Email::new(email).unwrap(),
+            password: "testpass123".to_string(),
+            date_of_birth: None,
+            grade_level: None,
+            custom_fields: custom_fields.and_then(|v| v.as_object().cloned()),
+            school_id: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_create_definition_rejects_bad_key_and_duplicates(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+
+        let bad_key = CreateCustomFieldDto {
+            field_key: "House Colour".to_string(),
+            ..house_field(school_id)
+        };
+        let err = CustomFieldService::create_definition(&pool, school_id, bad_key)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let no_options = CreateCustomFieldDto {
+            options: vec![],
+            ..house_field(school_id)
+        };
+        assert!(
+            CustomFieldService::create_definition(&pool, school_id, no_options)
+                .await
+                .is_err()
+        );
+
+        CustomFieldService::create_definition(&pool, school_id, house_field(school_id))
+            .await
+            .unwrap();
+        let duplicate = CreateCustomFieldDto {
+            entity_type: CustomFieldEntity::User,
+            ..house_field(school_id)
+        };
+        let err = CustomFieldService::create_definition(&pool, school_id, duplicate)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_student_values_are_validated_and_filterable(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        CustomFieldService::create_definition(&pool, school_id, house_field(school_id))
+            .await
+            .unwrap();
+
+        let missing = StudentService::create_student(
+            &pool,
+            student_dto("missing@example.com", None),
+            school_id.into_inner(),
+            None,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(missing.status, StatusCode::BAD_REQUEST);
+
+        let wrong_option = StudentService::create_student(
+            &pool,
+            student_dto("green@example.com", Some(json!({"house": "green"}))),
+            school_id.into_inner(),
+            None,
+            &PasswordPolicy::default(),
+        )
+        .await;
+        assert!(wrong_option.is_err());
+
+        let student = StudentService::create_student(
+            &pool,
+            student_dto("red@example.com", Some(json!({"house": "red"}))),
+            school_id.into_inner(),
+            None,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(student.custom_fields, json!({"house": "red"}));
+
+        let filter = CustomFieldService::parse_filter(Some(r#"{"house":"red"}"#)).unwrap();
+        let (students, total) = StudentService::get_students_by_school(
+            &pool,
+            school_id.into_inner(),
+            filter.as_ref(),
+            None,
+            &SortParams::default(),
+            10,
+            0,
+        )
+        .await
+        .unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(students[0].id, student.id);
+
+        let filter = CustomFieldService::parse_filter(Some(r#"{"house":"blue"}"#)).unwrap();
+        let (_, total) = StudentService::get_students_by_school(
+            &pool,
+            school_id.into_inner(),
+            filter.as_ref(),
+            None,
+            &SortParams::default(),
+            10,
+            0,
+        )
+        .await
+        .unwrap();
+        assert_eq!(total, 0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_delete_definition_strips_stored_values(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let definition =
+            CustomFieldService::create_definition(&pool, school_id, house_field(school_id))
+                .await
+                .unwrap();
+
+        let student = StudentService::create_student(
+            &pool,
+            student_dto("blue@example.com", Some(json!({"house": "blue"}))),
+            school_id.into_inner(),
+            None,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        CustomFieldService::delete_definition(&pool, definition.id, Some(school_id), None)
+            .await
+            .unwrap();
+
+        let stored =
+            sqlx::query_scalar::<_, Value>("SELECT custom_fields FROM users WHERE id = $1")
+                .bind(student.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(stored, json!({}));
+    }
+}
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::Response,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, ResponseView};
+use chalkbyte_models::ids::SchoolId;
+
+use crate::middleware::auth::RequireSchoolsRead;
+use crate::modules::dashboard::model::{SchoolDashboard, SchoolDashboardLite, SchoolStats};
+use crate::modules::dashboard::service::DashboardService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::verify_school_access;
+
+/// Get a school's dashboard
+///
+/// Returns every dashboard widget in one response. Widgets are cached
+/// independently, so `widgets` reports each one's status: `fresh`, `stale`
+/// (served while it refreshes in the background), `pending` (its source was
+/// too slow; retry shortly), or `error`. Pending and failed widgets are
+/// `null`. School admins can only view their own school. Mobile clients can
+/// ask for the lite view, which keeps each widget's headline figures.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/dashboard",
+    summary = "Get school dashboard",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("view" = Option<String>, Query, description = "Set to `lite` for the slimmer mobile view"),
+        ("X-Client" = Option<String>, Header, description = "Set to `mobile-lite` for the slimmer mobile view")
+    ),
+    responses(
+        (status = 200, description = "Dashboard widgets with their freshness", body = SchoolDashboard),
+        (status = 200, description = "Lite view of the dashboard", body = SchoolDashboardLite),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:read permission and access to the school")
+    ),
+    tag = "Schools",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_school_dashboard(
+    State(state): State<AppState>,
+    RequireSchoolsRead(auth_user): RequireSchoolsRead,
+    view: ResponseView,
+    Path(id): Path<Uuid>,
+) -> Result<Response, AppError> {
+    let school_id = SchoolId::from(id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let dashboard =
+        DashboardService::get_dashboard(&state.db, state.cache.as_ref(), school_id).await;
+
+    Ok(view.render(dashboard))
+}
+
+/// Get a school's statistics
+///
+/// Returns student, teacher, level, and branch counts, the average branch
+/// size, students without a branch, and students enrolled in the last 30
+/// days. The figures are cached for `SCHOOL_STATS_TTL_SECONDS`, so they can
+/// trail recent changes by that long; `computed_at` says when they were
+/// taken. School admins can only view their own school.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/stats",
+    summary = "Get school statistics",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    responses(
+        (status = 200, description = "Aggregate counts for the school", body = SchoolStats),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:read permission and access to the school"),
+        (status = 404, description = "School not found")
+    ),
+    tag = "Schools",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_school_stats(
+    State(state): State<AppState>,
+    RequireSchoolsRead(auth_user): RequireSchoolsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SchoolStats>, AppError> {
+    let school_id = SchoolId::from(id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let stats = DashboardService::get_school_stats(
+        &state.db,
+        state.cache.as_ref(),
+        school_id,
+        Duration::from_secs(state.cache_config.school_stats_ttl_seconds),
+    )
+    .await?;
+
+    Ok(Json(stats))
+}
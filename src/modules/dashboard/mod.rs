@@ -0,0 +1,16 @@
+//! School dashboard module.
+//!
+//! One endpoint assembles every dashboard widget server-side instead of the
+//! client calling each stats endpoint. Each widget is cached under its own
+//! key and TTL, so they do not all expire together. A stale widget is served
+//! as-is while a single background task refreshes it; a missing widget is
+//! computed with a short deadline and reported as pending when its source is
+//! too slow, leaving the rest of the dashboard unaffected.
+//!
+//! The stats endpoint returns a school's aggregate counts from a couple of
+//! grouped queries, cached as one entry for `SCHOOL_STATS_TTL_SECONDS`.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
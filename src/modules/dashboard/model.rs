@@ -0,0 +1,7 @@
+//! Dashboard data models.
+//!
+//! This module re-exports dashboard models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all dashboard models from the shared crate
+pub use chalkbyte_models::dashboard::*;
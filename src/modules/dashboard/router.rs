@@ -0,0 +1,13 @@
+use axum::{Router, routing::get};
+
+use crate::state::AppState;
+
+use super::controller::{get_school_dashboard, get_school_stats};
+
+/// Initialize the dashboard router, merged into the schools router
+/// Routes: GET /{id}/dashboard, GET /{id}/stats
+pub fn init_dashboard_router() -> Router<AppState> {
+    Router::new()
+        .route("/{id}/dashboard", get(get_school_dashboard))
+        .route("/{id}/stats", get(get_school_stats))
+}
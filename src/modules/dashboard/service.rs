@@ -0,0 +1,512 @@
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, de::DeserializeOwned};
+use sqlx::PgPool;
+use tracing::{instrument, warn};
+
+use chalkbyte_cache::{RedisCache, keys};
+use chalkbyte_core::{AppError, ErrorCode};
+use chalkbyte_models::ids::SchoolId;
+
+use crate::modules::assets::service::AssetService;
+use crate::modules::dashboard::model::{
+    AttendanceTodaySummary, DashboardWidget, SchoolDashboard, SchoolStats, StaffLeaveTodaySummary,
+    WidgetFreshness, WidgetStatus,
+};
+use crate::modules::schools::service::SchoolService;
+use crate::modules::users::model::system_roles;
+use crate::modules::visitor_log::service::VisitorLogService;
+
+/// How far back a student's creation counts as a recent enrollment.
+const RECENT_ENROLLMENT_DAYS: i32 = 30;
+
+/// How long a request waits for a widget that is not cached before
+/// reporting it as pending.
+const WIDGET_DEADLINE: Duration = Duration::from_secs(2);
+
+/// How long each widget is served before it is refreshed. Same-day activity
+/// changes quickly; the asset register rarely does.
+fn widget_ttl(widget: DashboardWidget) -> Duration {
+    match widget {
+        DashboardWidget::Overview => Duration::from_secs(300),
+        DashboardWidget::AttendanceToday => Duration::from_secs(60),
+        DashboardWidget::Assets => Duration::from_secs(900),
+        DashboardWidget::VisitorsToday => Duration::from_secs(60),
+        DashboardWidget::StaffLeaveToday => Duration::from_secs(300),
+    }
+}
+
+/// A widget's data, if any, and its freshness.
+type LoadedWidget<T> = (Option<T>, WidgetFreshness);
+
+fn loaded<T>(
+    widget: DashboardWidget,
+    data: Option<T>,
+    status: WidgetStatus,
+    computed_at: Option<DateTime<Utc>>,
+) -> LoadedWidget<T> {
+    (
+        data,
+        WidgetFreshness {
+            widget,
+            status,
+            computed_at,
+        },
+    )
+}
+
+/// Load one widget, from the cache when one is connected.
+///
+/// A cached widget is returned even when stale, with a background refresh
+/// started for it. An uncached widget is computed in a spawned task so that,
+/// when it misses the deadline, it still finishes and is cached for the next
+/// request.
+async fn load_widget<T, F, Fut>(
+    db: &PgPool,
+    cache: Option<&RedisCache>,
+    school_id: SchoolId,
+    widget: DashboardWidget,
+    compute: F,
+) -> LoadedWidget<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+    F: FnOnce(PgPool, SchoolId) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, AppError>> + Send + 'static,
+{
+    let Some(cache) = cache else {
+        return match tokio::time::timeout(WIDGET_DEADLINE, compute(db.clone(), school_id)).await {
+            Ok(Ok(value)) => loaded(widget, Some(value), WidgetStatus::Fresh, Some(Utc::now())),
+            Ok(Err(e)) => {
+                warn!(widget = widget.as_str(), error = %e, "Dashboard widget failed");
+                loaded(widget, None, WidgetStatus::Error, None)
+            }
+            Err(_) => loaded(widget, None, WidgetStatus::Pending, None),
+        };
+    };
+
+    let key = keys::dashboard::widget(school_id.into_inner(), widget.as_str());
+    let ttl = widget_ttl(widget);
+    let cache = cache.clone();
+    let db = db.clone();
+
+    if let Some(entry) = cache.peek::<T>(&key).await {
+        let computed_at = DateTime::from_timestamp_millis(
+            entry.fresh_until_ms.saturating_sub(ttl.as_millis() as u64) as i64,
+        );
+        if entry.is_fresh() {
+            return loaded(widget, Some(entry.value), WidgetStatus::Fresh, computed_at);
+        }
+
+        tokio::spawn(async move {
+            if let Some(Err(e)) = cache.refresh(&key, ttl, || compute(db, school_id)).await {
+                warn!(widget = widget.as_str(), error = %e, "Dashboard widget refresh failed");
+            }
+        });
+        return loaded(widget, Some(entry.value), WidgetStatus::Stale, computed_at);
+    }
+
+    let task =
+        tokio::spawn(async move { cache.refresh(&key, ttl, || compute(db, school_id)).await });
+
+    match tokio::time::timeout(WIDGET_DEADLINE, task).await {
+        Ok(Ok(Some(Ok(value)))) => {
+            loaded(widget, Some(value), WidgetStatus::Fresh, Some(Utc::now()))
+        }
+        Ok(Ok(Some(Err(e)))) => {
+            warn!(widget = widget.as_str(), error = %e, "Dashboard widget failed");
+            loaded(widget, None, WidgetStatus::Error, None)
+        }
+        Ok(Err(e)) => {
+            warn!(widget = widget.as_str(), error = %e, "Dashboard widget task panicked");
+            loaded(widget, None, WidgetStatus::Error, None)
+        }
+        // Another request is computing it, or it missed the deadline
+        Ok(Ok(None)) | Err(_) => loaded(widget, None, WidgetStatus::Pending, None),
+    }
+}
+
+pub struct DashboardService;
+
+impl DashboardService {
+    /// Assemble a school's dashboard, loading every widget concurrently.
+    #[instrument(skip(db, cache))]
+    pub async fn get_dashboard(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+    ) -> SchoolDashboard {
+        let (overview, attendance_today, assets, visitors_today, staff_leave_today) = tokio::join!(
+            load_widget(
+                db,
+                cache,
+                school_id,
+                DashboardWidget::Overview,
+                |db, school_id| async move {
+                    SchoolService::get_school_full_info(&db, school_id.into_inner()).await
+                },
+            ),
+            load_widget(
+                db,
+                cache,
+                school_id,
+                DashboardWidget::AttendanceToday,
+                |db, school_id| async move { Self::attendance_today(&db, school_id).await },
+            ),
+            load_widget(
+                db,
+                cache,
+                school_id,
+                DashboardWidget::Assets,
+                |db, school_id| async move { AssetService::get_asset_summary(&db, school_id).await },
+            ),
+            load_widget(
+                db,
+                cache,
+                school_id,
+                DashboardWidget::VisitorsToday,
+                |db, school_id| async move {
+                    VisitorLogService::get_daily_report(&db, school_id, None).await
+                },
+            ),
+            load_widget(
+                db,
+                cache,
+                school_id,
+                DashboardWidget::StaffLeaveToday,
+                |db, school_id| async move { Self::staff_leave_today(&db, school_id).await },
+            ),
+        );
+
+        SchoolDashboard {
+            widgets: vec![
+                overview.1,
+                attendance_today.1,
+                assets.1,
+                visitors_today.1,
+                staff_leave_today.1,
+            ],
+            overview: overview.0,
+            attendance_today: attendance_today.0,
+            assets: assets.0,
+            visitors_today: visitors_today.0,
+            staff_leave_today: staff_leave_today.0,
+        }
+    }
+
+    /// Get a school's aggregate statistics, cached for `ttl` when a cache
+    /// is connected.
+    #[instrument(skip(db, cache))]
+    pub async fn get_school_stats(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        ttl: Duration,
+    ) -> Result<SchoolStats, AppError> {
+        let Some(cache) = cache else {
+            return Self::school_stats(db, school_id).await;
+        };
+
+        let key = keys::dashboard::stats(school_id.into_inner());
+        cache
+            .get_or_compute(&key, ttl, || Self::school_stats(db, school_id))
+            .await
+    }
+
+    /// Compute a school's aggregate statistics: one query over its students
+    /// and teachers, one over its levels and branches.
+    #[instrument(skip(db))]
+    pub async fn school_stats(db: &PgPool, school_id: SchoolId) -> Result<SchoolStats, AppError> {
+        let (total_levels, total_branches) = sqlx::query_as::<_, (i64, i64)>(
+            r#"SELECT
+                   (SELECT COUNT(*) FROM levels WHERE school_id = s.id),
+                   (SELECT COUNT(*) FROM branches b
+                    INNER JOIN levels l ON l.id = b.level_id
+                    WHERE l.school_id = s.id)
+               FROM schools s
+               WHERE s.id = $1"#,
+        )
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))?;
+
+        let (total_students, total_teachers, students_without_branch, recent_enrollments) =
+            sqlx::query_as::<_, (i64, i64, i64, i64)>(
+                r#"SELECT
+                       COUNT(*) FILTER (WHERE ur.role_id = $2),
+                       COUNT(*) FILTER (WHERE ur.role_id = $3),
+                       COUNT(*) FILTER (WHERE ur.role_id = $2 AND u.branch_id IS NULL),
+                       COUNT(*) FILTER (
+                           WHERE ur.role_id = $2
+                             AND u.created_at >= NOW() - make_interval(days => $4)
+                       )
+                   FROM users u
+                   INNER JOIN user_roles ur ON ur.user_id = u.id
+                   WHERE u.school_id = $1 AND ur.role_id IN ($2, $3)"#,
+            )
+            .bind(school_id)
+            .bind(system_roles::STUDENT)
+            .bind(system_roles::TEACHER)
+            .bind(RECENT_ENROLLMENT_DAYS)
+            .fetch_one(db)
+            .await?;
+
+        let average_branch_size = (total_branches > 0)
+            .then(|| (total_students - students_without_branch) as f64 / total_branches as f64);
+
+        Ok(SchoolStats {
+            total_students,
+            total_teachers,
+            total_levels,
+            total_branches,
+            average_branch_size,
+            students_without_branch,
+            recent_enrollments,
+            recent_enrollment_days: RECENT_ENROLLMENT_DAYS,
+            computed_at: Utc::now(),
+        })
+    }
+
+    /// Count today's attendance marks across the school, today being the
+    /// date in the school's timezone.
+    #[instrument(skip(db))]
+    pub async fn attendance_today(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<AttendanceTodaySummary, AppError> {
+        let date = SchoolService::get_settings(db, None, school_id)
+            .await?
+            .today();
+
+        let (present, absent, late, excused) = sqlx::query_as::<_, (i64, i64, i64, i64)>(
+            r#"SELECT
+                   COUNT(*) FILTER (WHERE status = 'present'),
+                   COUNT(*) FILTER (WHERE status = 'absent'),
+                   COUNT(*) FILTER (WHERE status = 'late'),
+                   COUNT(*) FILTER (WHERE status = 'excused')
+               FROM attendance_records
+               WHERE school_id = $1 AND date = $2"#,
+        )
+        .bind(school_id)
+        .bind(date)
+        .fetch_one(db)
+        .await?;
+
+        let counted = present + absent + late;
+        let attendance_rate =
+            (counted > 0).then(|| (present + late) as f64 * 100.0 / counted as f64);
+
+        Ok(AttendanceTodaySummary {
+            date,
+            present,
+            absent,
+            late,
+            excused,
+            attendance_rate,
+        })
+    }
+
+    /// Count staff away today and leave requests awaiting review.
+    #[instrument(skip(db))]
+    pub async fn staff_leave_today(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<StaffLeaveTodaySummary, AppError> {
+        let date = SchoolService::get_settings(db, None, school_id)
+            .await?
+            .today();
+
+        let (on_leave, without_substitute, pending_requests) =
+            sqlx::query_as::<_, (i64, i64, i64)>(
+                r#"SELECT
+                       COUNT(DISTINCT staff_id) FILTER (
+                           WHERE status = 'approved' AND $2 BETWEEN start_date AND end_date
+                       ),
+                       COUNT(DISTINCT staff_id) FILTER (
+                           WHERE status = 'approved' AND $2 BETWEEN start_date AND end_date
+                             AND substitute_id IS NULL
+                       ),
+                       COUNT(*) FILTER (WHERE status = 'pending')
+                   FROM staff_leave_requests
+                   WHERE school_id = $1"#,
+            )
+            .bind(school_id)
+            .bind(date)
+            .fetch_one(db)
+            .await?;
+
+        Ok(StaffLeaveTodaySummary {
+            date,
+            on_leave,
+            without_substitute,
+            pending_requests,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chalkbyte_models::ids::{BranchId, LevelId, UserId};
+    use uuid::Uuid;
+
+    async fn create_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar::<_, SchoolId>("INSERT INTO schools (name) VALUES ($1) RETURNING id")
+            .bind(format!("School {}", Uuid::new_v4()))
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn create_user(pool: &PgPool, school_id: SchoolId) -> UserId {
+        sqlx::query_scalar::<_, UserId>(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Ada', 'Obi', $1, $2) RETURNING id"#,
+        )
+        .bind(format!("user-{}@example.com", Uuid::new_v4()))
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn mark(pool: &PgPool, school_id: SchoolId, branch_id: BranchId, status: &str) {
+        let student_id = create_user(pool, school_id).await;
+        sqlx::query(
+            r#"INSERT INTO attendance_records (school_id, branch_id, student_id, date, status)
+               VALUES ($1, $2, $3, CURRENT_DATE, $4)"#,
+        )
+        .bind(school_id)
+        .bind(branch_id)
+        .bind(student_id)
+        .bind(status)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_dashboard_without_cache_computes_every_widget(pool: PgPool) {
+        let school_id = create_school(&pool).await;
+        let level_id = sqlx::query_scalar::<_, LevelId>(
+            "INSERT INTO levels (name, school_id) VALUES ('JSS 1', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let branch_id = sqlx::query_scalar::<_, BranchId>(
+            "INSERT INTO branches (name, level_id) VALUES ('A', $1) RETURNING id",
+        )
+        .bind(level_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        mark(&pool, school_id, branch_id, "present").await;
+        mark(&pool, school_id, branch_id, "late").await;
+        mark(&pool, school_id, branch_id, "absent").await;
+        mark(&pool, school_id, branch_id, "excused").await;
+
+        let staff_id = create_user(&pool, school_id).await;
+        sqlx::query(
+            r#"INSERT INTO staff_leave_requests
+                   (school_id, staff_id, leave_type, start_date, end_date, status)
+               VALUES ($1, $2, 'sick', CURRENT_DATE, CURRENT_DATE, 'approved'),
+                      ($1, $2, 'annual', CURRENT_DATE + 7, CURRENT_DATE + 8, 'pending')"#,
+        )
+        .bind(school_id)
+        .bind(staff_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let dashboard = DashboardService::get_dashboard(&pool, None, school_id).await;
+
+        assert_eq!(dashboard.widgets.len(), DashboardWidget::ALL.len());
+        for freshness in &dashboard.widgets {
+            assert_eq!(freshness.status, WidgetStatus::Fresh);
+            assert!(freshness.computed_at.is_some());
+        }
+
+        let attendance = dashboard.attendance_today.unwrap();
+        assert_eq!(
+            (
+                attendance.present,
+                attendance.absent,
+                attendance.late,
+                attendance.excused
+            ),
+            (1, 1, 1, 1)
+        );
+        let rate = attendance.attendance_rate.unwrap();
+        assert!((rate - 200.0 / 3.0).abs() < 1e-9);
+
+        let leave = dashboard.staff_leave_today.unwrap();
+        assert_eq!(leave.on_leave, 1);
+        assert_eq!(leave.without_substitute, 1);
+        assert_eq!(leave.pending_requests, 1);
+
+        assert_eq!(dashboard.overview.unwrap().id, school_id);
+        assert_eq!(dashboard.assets.unwrap().total, 0);
+        assert_eq!(dashboard.visitors_today.unwrap().total_visits, 0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_school_stats_counts_students_teachers_and_branches(pool: PgPool) {
+        let school_id = create_school(&pool).await;
+        let level_id = sqlx::query_scalar::<_, LevelId>(
+            "INSERT INTO levels (name, school_id) VALUES ('JSS 1', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let branch_ids = sqlx::query_scalar::<_, BranchId>(
+            "INSERT INTO branches (name, level_id) VALUES ('A', $1), ('B', $1) RETURNING id",
+        )
+        .bind(level_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        for (role_id, branch_id) in [
+            (system_roles::STUDENT, Some(branch_ids[0])),
+            (system_roles::STUDENT, Some(branch_ids[0])),
+            (system_roles::STUDENT, Some(branch_ids[1])),
+            (system_roles::STUDENT, None),
+            (system_roles::TEACHER, None),
+        ] {
+            let user_id = create_user(&pool, school_id).await;
+            sqlx::query("UPDATE users SET branch_id = $2 WHERE id = $1")
+                .bind(user_id)
+                .bind(branch_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+            sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+                .bind(user_id)
+                .bind(role_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let stats = DashboardService::get_school_stats(&pool, None, school_id, Duration::ZERO)
+            .await
+            .unwrap();
+
+        assert_eq!(stats.total_students, 4);
+        assert_eq!(stats.total_teachers, 1);
+        assert_eq!(stats.total_levels, 1);
+        assert_eq!(stats.total_branches, 2);
+        assert_eq!(stats.students_without_branch, 1);
+        assert_eq!(stats.recent_enrollments, 4);
+        assert_eq!(stats.average_branch_size, Some(1.5));
+
+        let missing = DashboardService::school_stats(&pool, SchoolId::new()).await;
+        assert!(missing.is_err());
+    }
+}
@@ -0,0 +1,325 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent, permissions};
+use chalkbyte_models::ids::{DisciplineIncidentId, UserId};
+
+use crate::middleware::auth::{
+    AuthUser, RecordScope, RequireDisciplineDelete, RequireDisciplineRead, RequireDisciplineRecord,
+    RequireDisciplineUpdate,
+};
+use crate::modules::discipline::model::{
+    CreateIncidentDto, DisciplineIncident, IncidentFilterParams, IncidentTrendParams,
+    IncidentTrends, NotifyGuardiansResponse, PaginatedIncidentsResponse, StudentIncidentHistory,
+    UpdateIncidentDto,
+};
+use crate::modules::discipline::service::DisciplineService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Record a behaviour incident
+///
+/// Queues an `incident.recorded` webhook. With `notify_guardians` set, the
+/// student's linked guardians also get an in-app notification.
+#[utoipa::path(
+    post,
+    path = "/api/discipline/incidents",
+    summary = "Record incident",
+    request_body = CreateIncidentDto,
+    responses(
+        Created<DisciplineIncident>,
+        (status = 400, description = "Invalid input or user is not a student"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires discipline:record permission")
+    ),
+    tag = "Discipline",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_incident(
+    State(state): State<AppState>,
+    RequireDisciplineRecord(auth_user): RequireDisciplineRecord,
+    ValidatedJson(dto): ValidatedJson<CreateIncidentDto>,
+) -> Result<Created<DisciplineIncident>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let reported_by = auth_user.user_id()?;
+    let incident =
+        DisciplineService::create_incident(&state.db, school_id, reported_by, dto).await?;
+
+    Ok(Created(incident))
+}
+
+/// List behaviour incidents for a school
+///
+/// Teachers with `discipline:read:own_branch` only see students in branches
+/// they teach; students with `discipline:read:self` only see their own.
+#[utoipa::path(
+    get,
+    path = "/api/discipline/incidents",
+    summary = "List incidents",
+    params(IncidentFilterParams),
+    responses(
+        (status = 200, description = "Incidents, newest first", body = PaginatedIncidentsResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires discipline:read permission")
+    ),
+    tag = "Discipline",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_incidents(
+    State(state): State<AppState>,
+    RequireDisciplineRead(auth_user, scope): RequireDisciplineRead,
+    Query(filters): Query<IncidentFilterParams>,
+) -> Result<Json<PaginatedIncidentsResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let incidents = DisciplineService::get_incidents(
+        &state.db,
+        school_id,
+        scope.branch_ids().as_deref(),
+        scope.user_id(),
+        filters,
+    )
+    .await?;
+
+    Ok(Json(incidents))
+}
+
+/// Get a behaviour incident
+#[utoipa::path(
+    get,
+    path = "/api/discipline/incidents/{id}",
+    summary = "Get incident",
+    params(
+        ("id" = Uuid, Path, description = "Incident ID")
+    ),
+    responses(
+        (status = 200, description = "Incident", body = DisciplineIncident),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires discipline:read permission covering the student"),
+        (status = 404, description = "Incident not found")
+    ),
+    tag = "Discipline",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_incident(
+    State(state): State<AppState>,
+    RequireDisciplineRead(auth_user, scope): RequireDisciplineRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<DisciplineIncident>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let incident =
+        DisciplineService::get_incident(&state.db, DisciplineIncidentId::from(id), school_id)
+            .await?;
+    scope
+        .ensure_user(&state.db, incident.student_id.into_inner())
+        .await?;
+
+    Ok(Json(incident))
+}
+
+/// Update a behaviour incident
+#[utoipa::path(
+    put,
+    path = "/api/discipline/incidents/{id}",
+    summary = "Update incident",
+    params(
+        ("id" = Uuid, Path, description = "Incident ID")
+    ),
+    request_body = UpdateIncidentDto,
+    responses(
+        (status = 200, description = "Incident updated", body = DisciplineIncident),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires discipline:update permission"),
+        (status = 404, description = "Incident not found")
+    ),
+    tag = "Discipline",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_incident(
+    State(state): State<AppState>,
+    RequireDisciplineUpdate(auth_user): RequireDisciplineUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateIncidentDto>,
+) -> Result<Json<DisciplineIncident>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let incident = DisciplineService::update_incident(
+        &state.db,
+        DisciplineIncidentId::from(id),
+        school_id,
+        dto,
+    )
+    .await?;
+
+    Ok(Json(incident))
+}
+
+/// Delete a behaviour incident
+#[utoipa::path(
+    delete,
+    path = "/api/discipline/incidents/{id}",
+    summary = "Delete incident",
+    params(
+        ("id" = Uuid, Path, description = "Incident ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires discipline:delete permission"),
+        (status = 404, description = "Incident not found")
+    ),
+    tag = "Discipline",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_incident(
+    State(state): State<AppState>,
+    RequireDisciplineDelete(auth_user): RequireDisciplineDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    DisciplineService::delete_incident(&state.db, DisciplineIncidentId::from(id), school_id)
+        .await?;
+
+    Ok(NoContent)
+}
+
+/// Notify the student's guardians about an incident
+///
+/// Sends each guardian account linked to the student an in-app
+/// notification with the incident's category, severity, and date, and marks
+/// the incident as notified.
+#[utoipa::path(
+    post,
+    path = "/api/discipline/incidents/{id}/notify-guardians",
+    summary = "Notify guardians",
+    params(
+        ("id" = Uuid, Path, description = "Incident ID")
+    ),
+    responses(
+        (status = 200, description = "Guardians notified", body = NotifyGuardiansResponse),
+        (status = 400, description = "Student has no linked guardian accounts"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires discipline:update permission"),
+        (status = 404, description = "Incident not found")
+    ),
+    tag = "Discipline",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn notify_guardians(
+    State(state): State<AppState>,
+    RequireDisciplineUpdate(auth_user): RequireDisciplineUpdate,
+    Path(id): Path<Uuid>,
+) -> Result<Json<NotifyGuardiansResponse>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let notified_by = auth_user.user_id()?;
+    let response = DisciplineService::notify_guardians(
+        &state.db,
+        DisciplineIncidentId::from(id),
+        school_id,
+        notified_by,
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+/// Get a student's incident history
+///
+/// Open to holders of `discipline:read` in a scope covering the student
+/// (the student themself with `discipline:read:self`) and to the student's
+/// linked guardians.
+#[utoipa::path(
+    get,
+    path = "/api/discipline/students/{student_id}/history",
+    summary = "Get student incident history",
+    params(
+        ("student_id" = Uuid, Path, description = "Student ID")
+    ),
+    responses(
+        (status = 200, description = "Incident history", body = StudentIncidentHistory),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires discipline:read covering the student, or being their guardian"),
+        (status = 404, description = "Student not found")
+    ),
+    tag = "Discipline",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_student_history(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(student_id): Path<Uuid>,
+) -> Result<Json<StudentIncidentHistory>, AppError> {
+    let student_id = UserId::from(student_id);
+
+    let school_id = match auth_user.permission_scope(permissions::DISCIPLINE_READ) {
+        Some(scope) => {
+            RecordScope::resolve(&state.db, &auth_user, scope)
+                .await?
+                .ensure_user(&state.db, student_id.into_inner())
+                .await?;
+            get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?
+        }
+        // Guardians hold no discipline permission; the link is their access
+        None => {
+            let guardian_id = auth_user.user_id()?;
+            if !DisciplineService::is_guardian_of(&state.db, guardian_id, student_id).await? {
+                return Err(AppError::forbidden(format!(
+                    "Access denied. Missing required permission: {}",
+                    permissions::DISCIPLINE_READ
+                )));
+            }
+            None
+        }
+    };
+
+    let history = DisciplineService::get_student_history(&state.db, student_id, school_id).await?;
+
+    Ok(Json(history))
+}
+
+/// Get a school's incident trends
+///
+/// Counts incidents by category, severity, and month in the school's
+/// timezone. Needs the unscoped `discipline:read` permission.
+#[utoipa::path(
+    get,
+    path = "/api/discipline/trends",
+    summary = "Get incident trends",
+    params(IncidentTrendParams),
+    responses(
+        (status = 200, description = "Incident trends", body = IncidentTrends),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires discipline:read permission")
+    ),
+    tag = "Discipline",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_trends(
+    State(state): State<AppState>,
+    RequireDisciplineRead(auth_user, scope): RequireDisciplineRead,
+    Query(params): Query<IncidentTrendParams>,
+) -> Result<Json<IncidentTrends>, AppError> {
+    scope.ensure_school()?;
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let trends = DisciplineService::get_trends(&state.db, school_id, params).await?;
+
+    Ok(Json(trends))
+}
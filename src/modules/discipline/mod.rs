@@ -0,0 +1,19 @@
+//! Discipline module.
+//!
+//! This module records behaviour incidents against students: the category
+//! and severity of what happened, who reported it, and the actions the school
+//! took. Recording an incident queues an `incident.recorded` webhook, and the
+//! student's linked guardians can be sent an in-app notification when the
+//! incident is recorded or later on. Schools see incident trends by
+//! category, severity, and month.
+//!
+//! Reads go through scoped permissions: `discipline:read` covers the whole
+//! school, `discipline:read:own_branch` lets teachers see students in the
+//! branches they teach, and `discipline:read:self` lets students see only
+//! their own incidents. A linked guardian can read their student's history
+//! without any discipline permission.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Discipline data models and DTOs.
+//!
+//! This module re-exports discipline models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all discipline models from the shared crate
+pub use chalkbyte_models::discipline::*;
@@ -0,0 +1,30 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    create_incident, delete_incident, get_incident, get_incidents, get_student_history, get_trends,
+    notify_guardians, update_incident,
+};
+
+/// Initialize the discipline router
+/// Routes: POST /incidents, GET /incidents, GET /incidents/{id},
+/// PUT /incidents/{id}, DELETE /incidents/{id},
+/// POST /incidents/{id}/notify-guardians, GET /students/{student_id}/history,
+/// GET /trends
+pub fn init_discipline_router() -> Router<AppState> {
+    Router::new()
+        .route("/incidents", post(create_incident).get(get_incidents))
+        .route(
+            "/incidents/{id}",
+            get(get_incident)
+                .put(update_incident)
+                .delete(delete_incident),
+        )
+        .route("/incidents/{id}/notify-guardians", post(notify_guardians))
+        .route("/students/{student_id}/history", get(get_student_history))
+        .route("/trends", get(get_trends))
+}
@@ -0,0 +1,722 @@
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, ErrorCode, PaginationMeta};
+use chalkbyte_models::ids::{DisciplineIncidentId, SchoolId, UserId};
+use chalkbyte_models::webhooks::WebhookEvent;
+
+use crate::modules::discipline::model::{
+    CategoryCount, CreateIncidentDto, DisciplineIncident, DisciplineIncidentSummary,
+    IncidentCategory, IncidentFilterParams, IncidentSeverity, IncidentTrendParams, IncidentTrends,
+    MonthlyIncidentCount, NotifyGuardiansResponse, PaginatedIncidentsResponse, SeverityCount,
+    StudentIncidentHistory, UpdateIncidentDto,
+};
+use crate::modules::users::model::system_roles;
+use crate::modules::webhooks::service::WebhookService;
+
+const INCIDENT_COLUMNS: &str = "id, school_id, student_id, reported_by, occurred_at, category, \
+     severity, location, description, actions_taken, guardian_notified_at, guardian_notified_by, \
+     created_at, updated_at";
+
+pub struct DisciplineService;
+
+impl DisciplineService {
+    /// Look up the school of a student, optionally restricted to a school.
+    async fn find_student_school(
+        db: &PgPool,
+        student_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Option<SchoolId>, AppError> {
+        let school = sqlx::query_scalar::<_, SchoolId>(
+            r#"SELECT u.school_id FROM users u
+               JOIN user_roles ur ON ur.user_id = u.id
+               WHERE u.id = $1 AND ur.role_id = $2 AND u.school_id IS NOT NULL
+                 AND ($3::uuid IS NULL OR u.school_id = $3)"#,
+        )
+        .bind(student_id)
+        .bind(system_roles::STUDENT)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?;
+
+        Ok(school)
+    }
+
+    /// Get an incident, optionally restricted to a school.
+    #[instrument(skip(db))]
+    pub async fn get_incident(
+        db: &PgPool,
+        incident_id: DisciplineIncidentId,
+        school_id: Option<SchoolId>,
+    ) -> Result<DisciplineIncident, AppError> {
+        sqlx::query_as::<_, DisciplineIncident>(&format!(
+            "SELECT {INCIDENT_COLUMNS} FROM discipline_incidents
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(incident_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Incident not found")))
+    }
+
+    /// Whether a user is a linked guardian of a student.
+    #[instrument(skip(db))]
+    pub async fn is_guardian_of(
+        db: &PgPool,
+        guardian_id: UserId,
+        student_id: UserId,
+    ) -> Result<bool, AppError> {
+        let linked = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(
+                 SELECT 1 FROM student_guardians WHERE guardian_id = $1 AND student_id = $2
+             )",
+        )
+        .bind(guardian_id)
+        .bind(student_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(linked)
+    }
+
+    /// Record an incident and queue an `incident.recorded` webhook.
+    ///
+    /// With `notify_guardians` set, the student's linked guardians are
+    /// notified in the same transaction.
+    #[instrument(skip(db, dto))]
+    pub async fn create_incident(
+        db: &PgPool,
+        school_id: Option<SchoolId>,
+        reported_by: UserId,
+        dto: CreateIncidentDto,
+    ) -> Result<DisciplineIncident, AppError> {
+        let student_school = Self::find_student_school(db, dto.student_id, school_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::bad_request(anyhow::anyhow!(
+                    "Incidents can only be recorded for students"
+                ))
+            })?;
+
+        let mut tx = db.begin().await?;
+
+        let mut incident = sqlx::query_as::<_, DisciplineIncident>(&format!(
+            "INSERT INTO discipline_incidents (school_id, student_id, reported_by, occurred_at,
+                 category, severity, location, description, actions_taken)
+             VALUES ($1, $2, $3, COALESCE($4, NOW()), $5, $6, $7, $8, $9)
+             RETURNING {INCIDENT_COLUMNS}"
+        ))
+        .bind(student_school)
+        .bind(dto.student_id)
+        .bind(reported_by)
+        .bind(dto.occurred_at)
+        .bind(dto.category)
+        .bind(dto.severity)
+        .bind(&dto.location)
+        .bind(&dto.description)
+        .bind(&dto.actions_taken)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // Integrations (e.g. SMS gateways) get the facts, not the narrative
+        WebhookService::enqueue(
+            &mut *tx,
+            student_school,
+            WebhookEvent::IncidentRecorded,
+            &serde_json::json!({
+                "incident_id": incident.id,
+                "student_id": incident.student_id,
+                "category": incident.category,
+                "severity": incident.severity,
+                "occurred_at": incident.occurred_at,
+            }),
+        )
+        .await?;
+
+        if dto.notify_guardians {
+            let (updated, _) =
+                Self::notify_linked_guardians(&mut tx, &incident, reported_by).await?;
+            incident = updated;
+        }
+
+        tx.commit().await?;
+
+        Ok(incident)
+    }
+
+    /// Send each linked guardian an in-app notification about an incident.
+    ///
+    /// The incident is only marked as notified when at least one guardian
+    /// was reached.
+    async fn notify_linked_guardians(
+        conn: &mut PgConnection,
+        incident: &DisciplineIncident,
+        notified_by: UserId,
+    ) -> Result<(DisciplineIncident, i64), AppError> {
+        let (first_name, last_name, local_date) =
+            sqlx::query_as::<_, (String, String, chrono::NaiveDate)>(
+                r#"SELECT u.first_name, u.last_name, ($2::timestamptz AT TIME ZONE s.timezone)::date
+                   FROM users u
+                   JOIN schools s ON s.id = $3
+                   WHERE u.id = $1"#,
+            )
+            .bind(incident.student_id)
+            .bind(incident.occurred_at)
+            .bind(incident.school_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        let notified = sqlx::query(
+            r#"INSERT INTO notifications (user_id, title, body)
+               SELECT guardian_id, $2, $3 FROM student_guardians WHERE student_id = $1"#,
+        )
+        .bind(incident.student_id)
+        .bind("Behaviour incident recorded")
+        .bind(format!(
+            "{first_name} {last_name} was involved in a {} incident ({}) on {local_date}. \
+             Please contact the school for details.",
+            Self::severity_label(incident.severity),
+            Self::category_label(incident.category),
+        ))
+        .execute(&mut *conn)
+        .await?
+        .rows_affected() as i64;
+
+        if notified == 0 {
+            return Ok((incident.clone(), 0));
+        }
+
+        let incident = sqlx::query_as::<_, DisciplineIncident>(&format!(
+            "UPDATE discipline_incidents
+             SET guardian_notified_at = NOW(), guardian_notified_by = $2
+             WHERE id = $1
+             RETURNING {INCIDENT_COLUMNS}"
+        ))
+        .bind(incident.id)
+        .bind(notified_by)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok((incident, notified))
+    }
+
+    /// Notify a student's linked guardians about an existing incident.
+    #[instrument(skip(db))]
+    pub async fn notify_guardians(
+        db: &PgPool,
+        incident_id: DisciplineIncidentId,
+        school_id: Option<SchoolId>,
+        notified_by: UserId,
+    ) -> Result<NotifyGuardiansResponse, AppError> {
+        let incident = Self::get_incident(db, incident_id, school_id).await?;
+
+        let mut tx = db.begin().await?;
+        let (incident, guardians_notified) =
+            Self::notify_linked_guardians(&mut tx, &incident, notified_by).await?;
+        if guardians_notified == 0 {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Student has no linked guardian accounts"
+            )));
+        }
+        tx.commit().await?;
+
+        Ok(NotifyGuardiansResponse {
+            incident,
+            guardians_notified,
+        })
+    }
+
+    /// Get paginated incidents for a school, newest first.
+    ///
+    /// `branch_ids` limits the list to students in those branches and
+    /// `only_student_id` to a single student, for scoped readers.
+    #[instrument(skip(db))]
+    pub async fn get_incidents(
+        db: &PgPool,
+        school_id: SchoolId,
+        branch_ids: Option<&[Uuid]>,
+        only_student_id: Option<Uuid>,
+        filters: IncidentFilterParams,
+    ) -> Result<PaginatedIncidentsResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+
+        let where_clause = r#"WHERE i.school_id = $1
+              AND ($2::uuid IS NULL OR i.student_id = $2)
+              AND ($3::text IS NULL OR i.category = $3)
+              AND ($4::text IS NULL OR i.severity = $4)
+              AND ($5::date IS NULL OR (i.occurred_at AT TIME ZONE s.timezone)::date >= $5)
+              AND ($6::date IS NULL OR (i.occurred_at AT TIME ZONE s.timezone)::date <= $6)
+              AND ($7::uuid[] IS NULL OR u.branch_id = ANY($7))
+              AND ($8::uuid IS NULL OR i.student_id = $8)"#;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM discipline_incidents i
+             JOIN users u ON u.id = i.student_id
+             JOIN schools s ON s.id = i.school_id
+             {where_clause}"
+        ))
+        .bind(school_id)
+        .bind(filters.student_id)
+        .bind(filters.category)
+        .bind(filters.severity)
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(branch_ids)
+        .bind(only_student_id)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, DisciplineIncidentSummary>(&format!(
+            r#"SELECT i.id, i.student_id, u.first_name AS student_first_name,
+                      u.last_name AS student_last_name, i.occurred_at, i.category, i.severity,
+                      i.guardian_notified_at
+               FROM discipline_incidents i
+               JOIN users u ON u.id = i.student_id
+               JOIN schools s ON s.id = i.school_id
+               {where_clause}
+               ORDER BY i.occurred_at DESC
+               LIMIT $9 OFFSET $10"#
+        ))
+        .bind(school_id)
+        .bind(filters.student_id)
+        .bind(filters.category)
+        .bind(filters.severity)
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(branch_ids)
+        .bind(only_student_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedIncidentsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Get a student's full incident history, newest first.
+    #[instrument(skip(db))]
+    pub async fn get_student_history(
+        db: &PgPool,
+        student_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<StudentIncidentHistory, AppError> {
+        Self::find_student_school(db, student_id, school_id)
+            .await?
+            .ok_or_else(|| AppError::from_code(ErrorCode::StudentNotFound))?;
+
+        let incidents = sqlx::query_as::<_, DisciplineIncident>(&format!(
+            "SELECT {INCIDENT_COLUMNS} FROM discipline_incidents
+             WHERE student_id = $1
+             ORDER BY occurred_at DESC"
+        ))
+        .bind(student_id)
+        .fetch_all(db)
+        .await?;
+
+        let by_severity = [
+            IncidentSeverity::Minor,
+            IncidentSeverity::Moderate,
+            IncidentSeverity::Serious,
+        ]
+        .into_iter()
+        .map(|severity| SeverityCount {
+            severity,
+            count: incidents.iter().filter(|i| i.severity == severity).count() as i64,
+        })
+        .collect();
+
+        Ok(StudentIncidentHistory {
+            student_id,
+            by_severity,
+            incidents,
+        })
+    }
+
+    /// Update an incident.
+    #[instrument(skip(db, dto))]
+    pub async fn update_incident(
+        db: &PgPool,
+        incident_id: DisciplineIncidentId,
+        school_id: Option<SchoolId>,
+        dto: UpdateIncidentDto,
+    ) -> Result<DisciplineIncident, AppError> {
+        Self::get_incident(db, incident_id, school_id).await?;
+
+        let incident = sqlx::query_as::<_, DisciplineIncident>(&format!(
+            "UPDATE discipline_incidents
+             SET category = COALESCE($2, category),
+                 severity = COALESCE($3, severity),
+                 location = COALESCE($4, location),
+                 description = COALESCE($5, description),
+                 actions_taken = COALESCE($6, actions_taken)
+             WHERE id = $1
+             RETURNING {INCIDENT_COLUMNS}"
+        ))
+        .bind(incident_id)
+        .bind(dto.category)
+        .bind(dto.severity)
+        .bind(&dto.location)
+        .bind(&dto.description)
+        .bind(&dto.actions_taken)
+        .fetch_one(db)
+        .await?;
+
+        Ok(incident)
+    }
+
+    /// Delete an incident.
+    #[instrument(skip(db))]
+    pub async fn delete_incident(
+        db: &PgPool,
+        incident_id: DisciplineIncidentId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "DELETE FROM discipline_incidents WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(incident_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!("Incident not found")));
+        }
+
+        Ok(())
+    }
+
+    /// Summarize a school's incidents by category, severity, and month.
+    ///
+    /// Dates and months are taken in the school's timezone.
+    #[instrument(skip(db))]
+    pub async fn get_trends(
+        db: &PgPool,
+        school_id: SchoolId,
+        params: IncidentTrendParams,
+    ) -> Result<IncidentTrends, AppError> {
+        let incidents = r#"SELECT i.student_id, i.category, i.severity,
+                      (i.occurred_at AT TIME ZONE s.timezone)::date AS local_date
+               FROM discipline_incidents i
+               JOIN schools s ON s.id = i.school_id
+               WHERE i.school_id = $1"#;
+        let in_range = "($2::date IS NULL OR local_date >= $2)
+                    AND ($3::date IS NULL OR local_date <= $3)";
+
+        let (total, students_involved) = sqlx::query_as::<_, (i64, i64)>(&format!(
+            "SELECT COUNT(*), COUNT(DISTINCT student_id)
+             FROM ({incidents}) i WHERE {in_range}"
+        ))
+        .bind(school_id)
+        .bind(params.from)
+        .bind(params.to)
+        .fetch_one(db)
+        .await?;
+
+        let by_category = sqlx::query_as::<_, CategoryCount>(&format!(
+            "SELECT category, COUNT(*) AS count
+             FROM ({incidents}) i WHERE {in_range}
+             GROUP BY category
+             ORDER BY count DESC, category"
+        ))
+        .bind(school_id)
+        .bind(params.from)
+        .bind(params.to)
+        .fetch_all(db)
+        .await?;
+
+        let by_severity = sqlx::query_as::<_, SeverityCount>(&format!(
+            "SELECT severity, COUNT(*) AS count
+             FROM ({incidents}) i WHERE {in_range}
+             GROUP BY severity
+             ORDER BY array_position(ARRAY['minor', 'moderate', 'serious'], severity)"
+        ))
+        .bind(school_id)
+        .bind(params.from)
+        .bind(params.to)
+        .fetch_all(db)
+        .await?;
+
+        let by_month = sqlx::query_as::<_, MonthlyIncidentCount>(&format!(
+            "SELECT date_trunc('month', local_date)::date AS month, COUNT(*) AS count,
+                    COUNT(*) FILTER (WHERE severity = 'serious') AS serious
+             FROM ({incidents}) i WHERE {in_range}
+             GROUP BY month
+             ORDER BY month"
+        ))
+        .bind(school_id)
+        .bind(params.from)
+        .bind(params.to)
+        .fetch_all(db)
+        .await?;
+
+        Ok(IncidentTrends {
+            school_id,
+            from: params.from,
+            to: params.to,
+            total,
+            students_involved,
+            by_category,
+            by_severity,
+            by_month,
+        })
+    }
+
+    fn severity_label(severity: IncidentSeverity) -> &'static str {
+        match severity {
+            IncidentSeverity::Minor => "minor",
+            IncidentSeverity::Moderate => "moderate",
+            IncidentSeverity::Serious => "serious",
+        }
+    }
+
+    fn category_label(category: IncidentCategory) -> &'static str {
+        match category {
+            IncidentCategory::Lateness => "lateness",
+            IncidentCategory::Disruption => "disruption",
+            IncidentCategory::Disrespect => "disrespect",
+            IncidentCategory::Bullying => "bullying",
+            IncidentCategory::Fighting => "fighting",
+            IncidentCategory::Truancy => "truancy",
+            IncidentCategory::Dishonesty => "dishonesty",
+            IncidentCategory::Vandalism => "vandalism",
+            IncidentCategory::SubstanceUse => "substance use",
+            IncidentCategory::Other => "other",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use chalkbyte_core::PaginationParams;
+    use chalkbyte_models::ids::RoleId;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId, role_id: RoleId) -> UserId {
+        let user_id: UserId = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Test', 'User', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id.into_inner(),
+            role_id.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id
+    }
+
+    fn incident_dto(student_id: UserId, severity: IncidentSeverity) -> CreateIncidentDto {
+        CreateIncidentDto {
+            student_id,
+            occurred_at: None,
+            category: IncidentCategory::Fighting,
+            severity,
+            location: Some("Playground".to_string()),
+            description: "Pushed another student during break".to_string(),
+            actions_taken: Some("Detention".to_string()),
+            notify_guardians: false,
+        }
+    }
+
+    fn filters(student_id: Option<UserId>) -> IncidentFilterParams {
+        IncidentFilterParams {
+            school_id: None,
+            student_id,
+            category: None,
+            severity: None,
+            from: None,
+            to: None,
+            pagination: PaginationParams::default(),
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_create_incident_and_notify_guardians(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let teacher = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let guardian = create_test_user(&pool, school_id, system_roles::GUARDIAN).await;
+
+        let err = DisciplineService::create_incident(
+            &pool,
+            Some(school_id),
+            teacher,
+            incident_dto(teacher, IncidentSeverity::Minor),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        // Nobody to notify yet, so the incident stays unnotified
+        let incident = DisciplineService::create_incident(
+            &pool,
+            Some(school_id),
+            teacher,
+            CreateIncidentDto {
+                notify_guardians: true,
+                ..incident_dto(student, IncidentSeverity::Serious)
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(incident.reported_by, Some(teacher));
+        assert!(incident.guardian_notified_at.is_none());
+
+        let err = DisciplineService::notify_guardians(&pool, incident.id, Some(school_id), teacher)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        sqlx::query(
+            "INSERT INTO student_guardians (student_id, guardian_id, school_id) VALUES ($1, $2, $3)",
+        )
+        .bind(student)
+        .bind(guardian)
+        .bind(school_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert!(
+            DisciplineService::is_guardian_of(&pool, guardian, student)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !DisciplineService::is_guardian_of(&pool, teacher, student)
+                .await
+                .unwrap()
+        );
+
+        let response =
+            DisciplineService::notify_guardians(&pool, incident.id, Some(school_id), teacher)
+                .await
+                .unwrap();
+        assert_eq!(response.guardians_notified, 1);
+        assert_eq!(response.incident.guardian_notified_by, Some(teacher));
+
+        let body =
+            sqlx::query_scalar::<_, String>("SELECT body FROM notifications WHERE user_id = $1")
+                .bind(guardian)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert!(body.contains("serious incident (fighting)"));
+        assert!(!body.contains("Pushed another student"));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_incident_listing_history_and_trends(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let teacher = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let other = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+
+        for (student_id, severity) in [
+            (student, IncidentSeverity::Minor),
+            (student, IncidentSeverity::Serious),
+            (other, IncidentSeverity::Minor),
+        ] {
+            DisciplineService::create_incident(
+                &pool,
+                Some(school_id),
+                teacher,
+                incident_dto(student_id, severity),
+            )
+            .await
+            .unwrap();
+        }
+
+        let all = DisciplineService::get_incidents(&pool, school_id, None, None, filters(None))
+            .await
+            .unwrap();
+        assert_eq!(all.meta.total, 3);
+
+        // A reader limited to their own record never sees anyone else's,
+        // even when asking for them by ID
+        let own = DisciplineService::get_incidents(
+            &pool,
+            school_id,
+            None,
+            Some(student.into_inner()),
+            filters(Some(other)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(own.meta.total, 0);
+
+        let no_branches =
+            DisciplineService::get_incidents(&pool, school_id, Some(&[]), None, filters(None))
+                .await
+                .unwrap();
+        assert_eq!(no_branches.meta.total, 0);
+
+        let history = DisciplineService::get_student_history(&pool, student, Some(school_id))
+            .await
+            .unwrap();
+        assert_eq!(history.incidents.len(), 2);
+        assert_eq!(history.by_severity[0].count, 1);
+        assert_eq!(history.by_severity[2].count, 1);
+
+        let other_school = create_test_school(&pool).await;
+        let err = DisciplineService::get_student_history(&pool, student, Some(other_school))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let trends = DisciplineService::get_trends(
+            &pool,
+            school_id,
+            IncidentTrendParams {
+                school_id: None,
+                from: None,
+                to: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(trends.total, 3);
+        assert_eq!(trends.students_involved, 2);
+        assert_eq!(trends.by_category.len(), 1);
+        assert_eq!(trends.by_category[0].count, 3);
+        assert_eq!(trends.by_severity[0].severity, IncidentSeverity::Minor);
+        assert_eq!(trends.by_severity[0].count, 2);
+        assert_eq!(trends.by_month.len(), 1);
+        assert_eq!(trends.by_month[0].serious, 1);
+    }
+}
@@ -0,0 +1,127 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::SchoolId;
+
+use crate::middleware::auth::{RequireSchoolsRead, RequireSchoolsUpdate};
+use crate::modules::email_branding::model::{
+    EmailPreview, EmailPreviewRequest, SchoolEmailBranding, UpdateEmailBrandingDto,
+};
+use crate::modules::email_branding::service::EmailBrandingService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::verify_school_access;
+use crate::utils::email::EmailService;
+use crate::validator::ValidatedJson;
+
+/// Get a school's email branding
+///
+/// Unset fields use the default sender name, no logo, and the default color.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/email-branding",
+    summary = "Get email branding",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    responses(
+        (status = 200, description = "Email branding", body = SchoolEmailBranding),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:read permission for this school"),
+        (status = 404, description = "School not found")
+    ),
+    tag = "Email Branding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_email_branding(
+    State(state): State<AppState>,
+    RequireSchoolsRead(auth_user): RequireSchoolsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SchoolEmailBranding>, AppError> {
+    let school_id = SchoolId::from(id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let branding = EmailBrandingService::get_branding(&state.db, school_id).await?;
+
+    Ok(Json(branding))
+}
+
+/// Set a school's email branding
+///
+/// Replaces the current branding; omitted fields go back to the defaults.
+/// Applies to emails sent on the school's behalf from then on.
+#[utoipa::path(
+    put,
+    path = "/api/schools/{id}/email-branding",
+    summary = "Update email branding",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = UpdateEmailBrandingDto,
+    responses(
+        (status = 200, description = "Email branding updated", body = SchoolEmailBranding),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:update permission for this school"),
+        (status = 404, description = "School not found"),
+        (status = 422, description = "Invalid logo URL or color")
+    ),
+    tag = "Email Branding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_email_branding(
+    State(state): State<AppState>,
+    RequireSchoolsUpdate(auth_user): RequireSchoolsUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateEmailBrandingDto>,
+) -> Result<Json<SchoolEmailBranding>, AppError> {
+    let school_id = SchoolId::from(id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let branding = EmailBrandingService::update_branding(&state.db, school_id, dto).await?;
+
+    Ok(Json(branding))
+}
+
+/// Preview an email with the school's branding
+///
+/// Renders the chosen email with sample content. Pass `branding` to preview
+/// changes before saving them; nothing is sent or saved.
+#[utoipa::path(
+    post,
+    path = "/api/schools/{id}/email-branding/preview",
+    summary = "Preview email",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = EmailPreviewRequest,
+    responses(
+        (status = 200, description = "Rendered email", body = EmailPreview),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:read permission for this school"),
+        (status = 404, description = "School not found"),
+        (status = 422, description = "Unknown template, or invalid logo URL or color")
+    ),
+    tag = "Email Branding",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn preview_email(
+    State(state): State<AppState>,
+    RequireSchoolsRead(auth_user): RequireSchoolsRead,
+    Path(id): Path<Uuid>,
+    ValidatedJson(request): ValidatedJson<EmailPreviewRequest>,
+) -> Result<Json<EmailPreview>, AppError> {
+    let school_id = SchoolId::from(id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let email = EmailService::new(state.db.clone(), state.email_config.clone());
+    let preview = EmailBrandingService::preview(&state.db, &email, school_id, request).await?;
+
+    Ok(Json(preview))
+}
@@ -0,0 +1,12 @@
+//! Email branding module.
+//!
+//! Lets school admins set the sender name, logo, and primary color of the
+//! emails sent on their school's behalf, such as announcements, guardian
+//! invitations, and clinic visit notices, and preview any email with
+//! sample content before saving. Platform emails like password resets use
+//! the default branding.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Email branding data models and DTOs.
+//!
+//! This module re-exports email branding models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all email branding models from the shared crate
+pub use chalkbyte_models::email_branding::*;
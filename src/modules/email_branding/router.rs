@@ -0,0 +1,20 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{get_email_branding, preview_email, update_email_branding};
+
+/// Initialize the email branding router, merged into the schools router
+/// Routes: GET /{id}/email-branding, PUT /{id}/email-branding,
+/// POST /{id}/email-branding/preview
+pub fn init_email_branding_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/{id}/email-branding",
+            get(get_email_branding).put(update_email_branding),
+        )
+        .route("/{id}/email-branding/preview", post(preview_email))
+}
@@ -0,0 +1,238 @@
+use sqlx::{FromRow, PgPool};
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, ErrorCode};
+use chalkbyte_models::ids::SchoolId;
+
+use crate::modules::email_branding::model::{
+    EmailPreview, EmailPreviewRequest, SchoolEmailBranding, UpdateEmailBrandingDto,
+};
+use crate::utils::email::EmailService;
+use crate::utils::email::templates::{EmailBranding, render_sample};
+
+#[derive(FromRow)]
+struct BrandingRow {
+    school_name: String,
+    #[sqlx(flatten)]
+    branding: SchoolEmailBranding,
+}
+
+pub struct EmailBrandingService;
+
+impl EmailBrandingService {
+    async fn find(db: &PgPool, school_id: SchoolId) -> Result<BrandingRow, AppError> {
+        sqlx::query_as::<_, BrandingRow>(
+            r#"SELECT s.name AS school_name, s.id AS school_id,
+                      b.sender_name, b.logo_url, b.primary_color, b.updated_at
+               FROM schools s
+               LEFT JOIN school_email_branding b ON b.school_id = s.id
+               WHERE s.id = $1"#,
+        )
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))
+    }
+
+    /// Get a school's stored branding; unset fields use the defaults.
+    #[instrument(skip(db))]
+    pub async fn get_branding(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<SchoolEmailBranding, AppError> {
+        Ok(Self::find(db, school_id).await?.branding)
+    }
+
+    /// Replace a school's branding.
+    #[instrument(skip(db))]
+    pub async fn update_branding(
+        db: &PgPool,
+        school_id: SchoolId,
+        dto: UpdateEmailBrandingDto,
+    ) -> Result<SchoolEmailBranding, AppError> {
+        Self::find(db, school_id).await?;
+
+        let branding = sqlx::query_as::<_, SchoolEmailBranding>(
+            r#"INSERT INTO school_email_branding (school_id, sender_name, logo_url, primary_color)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (school_id) DO UPDATE
+               SET sender_name = EXCLUDED.sender_name,
+                   logo_url = EXCLUDED.logo_url,
+                   primary_color = EXCLUDED.primary_color,
+                   updated_at = NOW()
+               RETURNING school_id, sender_name, logo_url, primary_color, updated_at"#,
+        )
+        .bind(school_id)
+        .bind(&dto.sender_name)
+        .bind(&dto.logo_url)
+        .bind(&dto.primary_color)
+        .fetch_one(db)
+        .await?;
+
+        Ok(branding)
+    }
+
+    /// Branding for emails sent on a school's behalf.
+    #[instrument(skip(db))]
+    pub async fn email_branding(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<EmailBranding, AppError> {
+        let row = Self::find(db, school_id).await?;
+
+        Ok(EmailBranding::for_school(row.school_name, &row.branding))
+    }
+
+    /// Render an email with sample content and the school's branding, or
+    /// the unsaved branding in the request.
+    #[instrument(skip(db, email))]
+    pub async fn preview(
+        db: &PgPool,
+        email: &EmailService,
+        school_id: SchoolId,
+        request: EmailPreviewRequest,
+    ) -> Result<EmailPreview, AppError> {
+        let mut row = Self::find(db, school_id).await?;
+        if let Some(draft) = request.branding {
+            row.branding.sender_name = draft.sender_name;
+            row.branding.logo_url = draft.logo_url;
+            row.branding.primary_color = draft.primary_color;
+        }
+
+        let email = email.with_branding(EmailBranding::for_school(row.school_name, &row.branding));
+        let rendered = render_sample(request.template, email.branding(), email.frontend_url())?;
+
+        Ok(EmailPreview {
+            template: request.template,
+            sender_name: email.sender_name().to_string(),
+            subject: rendered.subject,
+            html: rendered.html,
+            text: rendered.text,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::email_branding::model::EmailTemplateKind;
+    use chalkbyte_config::EmailConfig;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar::<_, Uuid>("INSERT INTO schools (name) VALUES ($1) RETURNING id")
+            .bind(format!("School {}", Uuid::new_v4()))
+            .fetch_one(pool)
+            .await
+            .unwrap()
+            .into()
+    }
+
+    fn disabled_email(pool: &PgPool) -> EmailService {
+        EmailService::new(
+            pool.clone(),
+            EmailConfig {
+                smtp_host: "localhost".to_string(),
+                smtp_port: 1025,
+                smtp_username: String::new(),
+                smtp_password: String::new(),
+                from_email: "noreply@example.com".to_string(),
+                from_name: "Chalkbyte".to_string(),
+                frontend_url: "https://app.example.com".to_string(),
+                enabled: false,
+            },
+        )
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_branding_defaults_until_set(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+
+        let stored = EmailBrandingService::get_branding(&pool, school_id)
+            .await
+            .unwrap();
+        assert!(stored.sender_name.is_none() && stored.updated_at.is_none());
+
+        let branding = EmailBrandingService::email_branding(&pool, school_id)
+            .await
+            .unwrap();
+        assert!(branding.school_name.is_some());
+        assert_eq!(branding.primary_color, "#4F46E5");
+
+        EmailBrandingService::update_branding(
+            &pool,
+            school_id,
+            UpdateEmailBrandingDto {
+                sender_name: Some("Greenfield Office".to_string()),
+                logo_url: None,
+                primary_color: Some("#0F766E".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        let branding = EmailBrandingService::email_branding(&pool, school_id)
+            .await
+            .unwrap();
+        assert_eq!(branding.sender_name.as_deref(), Some("Greenfield Office"));
+        assert_eq!(branding.primary_color, "#0F766E");
+
+        // Replacing the branding clears fields that are left out
+        let stored = EmailBrandingService::update_branding(
+            &pool,
+            school_id,
+            UpdateEmailBrandingDto::default(),
+        )
+        .await
+        .unwrap();
+        assert!(stored.sender_name.is_none() && stored.primary_color.is_none());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_preview_uses_draft_branding(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+
+        let preview = EmailBrandingService::preview(
+            &pool,
+            &disabled_email(&pool),
+            school_id,
+            EmailPreviewRequest {
+                template: EmailTemplateKind::GuardianInvitation,
+                branding: Some(UpdateEmailBrandingDto {
+                    sender_name: Some("Greenfield Office".to_string()),
+                    logo_url: Some("https://cdn.example.com/logo.png".to_string()),
+                    primary_color: Some("#0F766E".to_string()),
+                }),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(preview.sender_name, "Greenfield Office");
+        assert!(preview.subject.ends_with("guardian account"));
+        assert!(preview.html.contains("#0F766E"));
+        assert!(
+            preview
+                .text
+                .contains("https://app.example.com/reset-password")
+        );
+
+        // Nothing was saved
+        let stored = EmailBrandingService::get_branding(&pool, school_id)
+            .await
+            .unwrap();
+        assert!(stored.updated_at.is_none());
+
+        let missing = EmailBrandingService::preview(
+            &pool,
+            &disabled_email(&pool),
+            SchoolId::from(Uuid::new_v4()),
+            EmailPreviewRequest {
+                template: EmailTemplateKind::PasswordReset,
+                branding: None,
+            },
+        )
+        .await;
+        assert!(missing.is_err());
+    }
+}
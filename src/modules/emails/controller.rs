@@ -0,0 +1,130 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, Created, NoContent, PaginationParams};
+
+use crate::modules::emails::model::{
+    CreateEmailSuppressionDto, EmailSuppression, OutboundEmailFilterParams,
+    PaginatedEmailSuppressionsResponse, PaginatedOutboundEmailsResponse,
+};
+use crate::modules::emails::service::EmailQueueService;
+use crate::state::AppState;
+use crate::validator::ValidatedJson;
+
+/// List outbound emails
+///
+/// Shows each queued or sent email with its delivery status, attempts, last
+/// error, and when the next retry is due. Bodies are not returned. System
+/// admins only.
+#[utoipa::path(
+    get,
+    path = "/api/admin/emails",
+    summary = "List outbound emails",
+    params(OutboundEmailFilterParams),
+    responses(
+        (status = 200, description = "Emails, newest first", body = PaginatedOutboundEmailsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only")
+    ),
+    tag = "Emails",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_emails(
+    State(state): State<AppState>,
+    Query(params): Query<OutboundEmailFilterParams>,
+) -> Result<Json<PaginatedOutboundEmailsResponse>, AppError> {
+    let emails = EmailQueueService::get_emails(&state.db, params).await?;
+
+    Ok(Json(emails))
+}
+
+/// List suppressed addresses
+///
+/// Addresses that bounced permanently or were suppressed by an admin. They
+/// are not emailed until removed. System admins only.
+#[utoipa::path(
+    get,
+    path = "/api/admin/emails/suppressions",
+    summary = "List suppressed addresses",
+    params(
+        ("limit" = Option<i64>, Query, description = "Number of items per page (1-100, default: 10)"),
+        ("offset" = Option<i64>, Query, description = "Number of items to skip (default: 0)")
+    ),
+    responses(
+        (status = 200, description = "Suppressed addresses, newest first", body = PaginatedEmailSuppressionsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only")
+    ),
+    tag = "Emails",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_email_suppressions(
+    State(state): State<AppState>,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<Json<PaginatedEmailSuppressionsResponse>, AppError> {
+    let suppressions = EmailQueueService::get_suppressions(&state.db, pagination).await?;
+
+    Ok(Json(suppressions))
+}
+
+/// Suppress an address
+///
+/// Stops emails to the address, for example after a spam complaint. Queued
+/// emails to it are dropped. Suppressing an address again updates the
+/// reason. System admins only.
+#[utoipa::path(
+    post,
+    path = "/api/admin/emails/suppressions",
+    summary = "Suppress address",
+    request_body = CreateEmailSuppressionDto,
+    responses(
+        Created<EmailSuppression>,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only"),
+        (status = 422, description = "Invalid email address")
+    ),
+    tag = "Emails",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_email_suppression(
+    State(state): State<AppState>,
+    ValidatedJson(dto): ValidatedJson<CreateEmailSuppressionDto>,
+) -> Result<Created<EmailSuppression>, AppError> {
+    let suppression = EmailQueueService::suppress(&state.db, dto).await?;
+
+    Ok(Created(suppression))
+}
+
+/// Remove a suppressed address
+///
+/// The address is emailed again from then on. Emails dropped while it was
+/// suppressed are not resent. System admins only.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/emails/suppressions/{email}",
+    summary = "Remove suppressed address",
+    params(("email" = String, Path, description = "Email address")),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only"),
+        (status = 404, description = "Address is not suppressed")
+    ),
+    tag = "Emails",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_email_suppression(
+    State(state): State<AppState>,
+    Path(email): Path<String>,
+) -> Result<NoContent, AppError> {
+    EmailQueueService::unsuppress(&state.db, &email).await?;
+
+    Ok(NoContent)
+}
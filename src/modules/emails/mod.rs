@@ -0,0 +1,18 @@
+//! Outbound email queue module.
+//!
+//! [`crate::utils::email::EmailService`] writes every email to an outbox
+//! instead of sending it inside the request. A background job delivers due
+//! emails over SMTP, retrying failures with exponential backoff up to
+//! [`service::MAX_ATTEMPTS`] times. A permanent mailbox rejection (`550`,
+//! `551`, or `553`) fails the email at once and adds the address to the
+//! suppression list; suppressed addresses are not emailed again until they
+//! are removed from it.
+//!
+//! `GET /api/admin/emails` lets system admins inspect delivery status, and
+//! `/api/admin/emails/suppressions` manages the suppression list. Finished
+//! emails are kept for [`service::EMAIL_RETENTION_DAYS`] days.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
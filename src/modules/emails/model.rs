@@ -0,0 +1,7 @@
+//! Outbound email data models and DTOs.
+//!
+//! This module re-exports outbound email models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all outbound email models from the shared crate
+pub use chalkbyte_models::emails::*;
@@ -0,0 +1,22 @@
+use axum::{
+    Router,
+    routing::{delete, get},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    create_email_suppression, delete_email_suppression, get_email_suppressions, get_emails,
+};
+
+/// Initialize the outbound email router
+/// Routes: GET /, GET/POST /suppressions, DELETE /suppressions/{email}
+pub fn init_emails_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_emails))
+        .route(
+            "/suppressions",
+            get(get_email_suppressions).post(create_email_suppression),
+        )
+        .route("/suppressions/{email}", delete(delete_email_suppression))
+}
@@ -0,0 +1,714 @@
+use std::time::Duration;
+
+use lettre::{SmtpTransport, Transport};
+use sqlx::{FromRow, PgPool};
+use tracing::{info, instrument, warn};
+
+use chalkbyte_config::EmailConfig;
+use chalkbyte_core::{AppError, PaginationMeta, PaginationParams};
+use chalkbyte_models::ids::{OutboundEmailId, SchoolId};
+use chalkbyte_observability::jobs::register_job;
+
+use crate::modules::emails::model::{
+    CreateEmailSuppressionDto, EmailSuppression, OutboundEmail, OutboundEmailFilterParams,
+    OutboundEmailStatus, PaginatedEmailSuppressionsResponse, PaginatedOutboundEmailsResponse,
+    SuppressionReason,
+};
+use crate::utils::email::{RenderedEmail, build_message, smtp_transport};
+
+/// How often the delivery job looks for due emails.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Emails claimed per round trip.
+const DELIVERY_BATCH_SIZE: i64 = 20;
+
+/// How long a claimed email waits before another attempt may pick it up.
+const CLAIM_TIMEOUT_SECS: f64 = 300.0;
+
+/// Attempts made before an email is marked failed.
+pub const MAX_ATTEMPTS: i32 = 6;
+
+/// Delay before the first retry; doubled after every further failure.
+const BASE_RETRY_DELAY_SECS: i64 = 60;
+
+/// Longest delay between two attempts.
+const MAX_RETRY_DELAY_SECS: i64 = 6 * 60 * 60;
+
+/// Days sent, failed, and suppressed emails are kept.
+pub const EMAIL_RETENTION_DAYS: i32 = 30;
+
+const EMAIL_COLUMNS: &str = "id, school_id, to_email, from_name, subject, status, attempts,
+    next_attempt_at, last_error, request_id, sent_at, created_at";
+
+const SUPPRESSION_COLUMNS: &str = "email, reason, detail, created_at";
+
+/// Start the background job that delivers queued emails over SMTP.
+pub fn spawn_delivery_job(db: PgPool, email_config: EmailConfig) {
+    tokio::spawn(async move {
+        if !email_config.enabled {
+            info!("SMTP disabled, email delivery job not started");
+            return;
+        }
+
+        let mailer = match smtp_transport(&email_config) {
+            Ok(mailer) => mailer,
+            Err(e) => {
+                warn!(error = %e.error, "Failed to build SMTP transport, email delivery disabled");
+                return;
+            }
+        };
+
+        let job = register_job("email_delivery", POLL_INTERVAL);
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let run = job
+                .run(async {
+                    loop {
+                        match EmailQueueService::deliver_due(&db, &email_config, &mailer).await? {
+                            0 => return Ok::<_, AppError>(()),
+                            attempted => info!(attempted, "Attempted email deliveries"),
+                        }
+                    }
+                })
+                .await;
+            if let Err(e) = run {
+                warn!(error = %e, "Failed to deliver emails");
+            }
+            match EmailQueueService::queue_depth(&db).await {
+                Ok(depth) => job.set_queue_depth(depth),
+                Err(e) => warn!(error = %e, "Failed to count pending emails"),
+            }
+        }
+    });
+}
+
+/// Delay before retrying an email that has failed `attempts` times.
+fn retry_delay_secs(attempts: i32) -> i64 {
+    let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+    BASE_RETRY_DELAY_SECS
+        .saturating_mul(1 << exponent)
+        .min(MAX_RETRY_DELAY_SECS)
+}
+
+/// Whether an SMTP reply rejects the recipient's mailbox for good: it does
+/// not exist, is not local, or its name is not allowed.
+fn is_bounce(code: u16) -> bool {
+    matches!(code, 550 | 551 | 553)
+}
+
+/// An email claimed by the delivery job.
+#[derive(FromRow)]
+struct ClaimedEmail {
+    id: OutboundEmailId,
+    to_email: String,
+    from_name: String,
+    subject: String,
+    text_body: String,
+    html_body: String,
+    request_id: Option<String>,
+    attempts: i32,
+}
+
+pub struct EmailQueueService;
+
+impl EmailQueueService {
+    /// Queue an email for delivery. Emails to suppressed addresses are
+    /// recorded as suppressed and never sent.
+    #[instrument(skip(db, email), fields(subject = %email.subject))]
+    pub async fn enqueue(
+        db: &PgPool,
+        school_id: Option<SchoolId>,
+        to_email: &str,
+        from_name: &str,
+        email: &RenderedEmail,
+        request_id: Option<&str>,
+    ) -> Result<OutboundEmailStatus, AppError> {
+        let status = sqlx::query_scalar::<_, OutboundEmailStatus>(
+            r#"INSERT INTO email_outbox
+                   (school_id, to_email, from_name, subject, text_body, html_body, request_id, status)
+               SELECT $1, $2, $3, $4, $5, $6, $7,
+                      CASE WHEN EXISTS (SELECT 1 FROM email_suppressions WHERE email = LOWER($2))
+                           THEN 'suppressed' ELSE 'pending' END
+               RETURNING status"#,
+        )
+        .bind(school_id)
+        .bind(to_email)
+        .bind(from_name)
+        .bind(&email.subject)
+        .bind(&email.text)
+        .bind(&email.html)
+        .bind(request_id)
+        .fetch_one(db)
+        .await?;
+
+        if status == OutboundEmailStatus::Suppressed {
+            info!(email = %to_email, "Address is suppressed - email not sent");
+        }
+
+        Ok(status)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_emails(
+        db: &PgPool,
+        params: OutboundEmailFilterParams,
+    ) -> Result<PaginatedOutboundEmailsResponse, AppError> {
+        let limit = params.pagination.limit();
+        let offset = params.pagination.offset();
+
+        let filter = "($1::text IS NULL OR status = $1)
+             AND ($2::text IS NULL OR LOWER(to_email) = LOWER($2))
+             AND ($3::uuid IS NULL OR school_id = $3)";
+
+        let data = sqlx::query_as::<_, OutboundEmail>(&format!(
+            "SELECT {EMAIL_COLUMNS} FROM email_outbox
+             WHERE {filter}
+             ORDER BY created_at DESC
+             LIMIT $4 OFFSET $5"
+        ))
+        .bind(params.status)
+        .bind(&params.to_email)
+        .bind(params.school_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM email_outbox WHERE {filter}"
+        ))
+        .bind(params.status)
+        .bind(&params.to_email)
+        .bind(params.school_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(PaginatedOutboundEmailsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: params.pagination.page(),
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_suppressions(
+        db: &PgPool,
+        pagination: PaginationParams,
+    ) -> Result<PaginatedEmailSuppressionsResponse, AppError> {
+        let limit = pagination.limit();
+        let offset = pagination.offset();
+
+        let data = sqlx::query_as::<_, EmailSuppression>(&format!(
+            "SELECT {SUPPRESSION_COLUMNS} FROM email_suppressions
+             ORDER BY created_at DESC
+             LIMIT $1 OFFSET $2"
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM email_suppressions")
+            .fetch_one(db)
+            .await?;
+
+        Ok(PaginatedEmailSuppressionsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: pagination.page(),
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Suppress an address, or update the reason it is suppressed.
+    #[instrument(skip(db))]
+    pub async fn suppress(
+        db: &PgPool,
+        dto: CreateEmailSuppressionDto,
+    ) -> Result<EmailSuppression, AppError> {
+        let suppression = sqlx::query_as::<_, EmailSuppression>(&format!(
+            "INSERT INTO email_suppressions (email, reason, detail)
+             VALUES (LOWER($1), $2, $3)
+             ON CONFLICT (email) DO UPDATE
+             SET reason = EXCLUDED.reason, detail = EXCLUDED.detail
+             RETURNING {SUPPRESSION_COLUMNS}"
+        ))
+        .bind(dto.email.trim())
+        .bind(dto.reason.unwrap_or(SuppressionReason::Manual))
+        .bind(&dto.detail)
+        .fetch_one(db)
+        .await?;
+
+        Ok(suppression)
+    }
+
+    /// Remove an address from the suppression list so it is emailed again.
+    #[instrument(skip(db))]
+    pub async fn unsuppress(db: &PgPool, email: &str) -> Result<(), AppError> {
+        let deleted = sqlx::query("DELETE FROM email_suppressions WHERE email = LOWER($1)")
+            .bind(email.trim())
+            .execute(db)
+            .await?
+            .rows_affected();
+
+        if deleted == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Email address is not suppressed"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Number of emails waiting for an attempt.
+    pub async fn queue_depth(db: &PgPool) -> Result<u64, AppError> {
+        let pending = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM email_outbox WHERE status = 'pending'",
+        )
+        .fetch_one(db)
+        .await?;
+
+        Ok(pending.try_into().unwrap_or_default())
+    }
+
+    /// Deletes finished emails older than [`EMAIL_RETENTION_DAYS`].
+    #[instrument(skip(db))]
+    pub async fn purge_finished(db: &PgPool) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "DELETE FROM email_outbox
+             WHERE status <> 'pending' AND created_at < NOW() - make_interval(days => $1)",
+        )
+        .bind(EMAIL_RETENTION_DAYS)
+        .execute(db)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Attempt a batch of due emails and return how many were attempted.
+    ///
+    /// Pending emails to addresses suppressed since they were queued are
+    /// dropped first. Claiming an email pushes its next attempt out, so an
+    /// interrupted attempt is retried later rather than lost, and several
+    /// servers can run the job without sending the same email twice.
+    pub async fn deliver_due(
+        db: &PgPool,
+        config: &EmailConfig,
+        mailer: &SmtpTransport,
+    ) -> Result<usize, AppError> {
+        sqlx::query(
+            "UPDATE email_outbox o SET status = 'suppressed'
+             FROM email_suppressions s
+             WHERE o.status = 'pending' AND LOWER(o.to_email) = s.email",
+        )
+        .execute(db)
+        .await?;
+
+        let claimed = sqlx::query_as::<_, ClaimedEmail>(
+            r#"UPDATE email_outbox
+               SET attempts = attempts + 1,
+                   next_attempt_at = NOW() + make_interval(secs => $2)
+               WHERE id IN (
+                   SELECT id FROM email_outbox
+                   WHERE status = 'pending' AND next_attempt_at <= NOW()
+                   ORDER BY next_attempt_at
+                   LIMIT $1
+                   FOR UPDATE SKIP LOCKED
+               )
+               RETURNING id, to_email, from_name, subject, text_body, html_body,
+                         request_id, attempts"#,
+        )
+        .bind(DELIVERY_BATCH_SIZE)
+        .bind(CLAIM_TIMEOUT_SECS)
+        .fetch_all(db)
+        .await?;
+
+        let attempted = claimed.len();
+        for email in claimed {
+            Self::attempt(db, config, mailer, email).await?;
+        }
+
+        Ok(attempted)
+    }
+
+    async fn attempt(
+        db: &PgPool,
+        config: &EmailConfig,
+        mailer: &SmtpTransport,
+        email: ClaimedEmail,
+    ) -> Result<(), AppError> {
+        let message = build_message(
+            config,
+            &email.from_name,
+            &email.to_email,
+            RenderedEmail {
+                subject: email.subject,
+                text: email.text_body,
+                html: email.html_body,
+            },
+            email.request_id.as_deref(),
+        );
+        let message = match message {
+            Ok(message) => message,
+            Err(e) => {
+                // A malformed address never becomes deliverable
+                return Self::mark_failed(db, email.id, &e.error.to_string()).await;
+            }
+        };
+
+        let mailer = mailer.clone();
+        let outcome = tokio::task::spawn_blocking(move || mailer.send(&message))
+            .await
+            .map_err(|e| AppError::internal_error(format!("Task join error: {}", e)))?;
+
+        let error = match outcome {
+            Ok(_) => {
+                sqlx::query(
+                    "UPDATE email_outbox
+                     SET status = 'sent', sent_at = NOW(), last_error = NULL
+                     WHERE id = $1",
+                )
+                .bind(email.id)
+                .execute(db)
+                .await?;
+                return Ok(());
+            }
+            Err(e) => e,
+        };
+
+        let message = error.to_string();
+        if error.is_permanent() && error.status().is_some_and(|code| is_bounce(code.into())) {
+            sqlx::query(
+                "INSERT INTO email_suppressions (email, reason, detail)
+                 VALUES (LOWER($1), 'bounce', $2)
+                 ON CONFLICT (email) DO NOTHING",
+            )
+            .bind(&email.to_email)
+            .bind(&message)
+            .execute(db)
+            .await?;
+
+            warn!(email.id = %email.id, error = %message, "Email bounced, address suppressed");
+            return Self::mark_failed(db, email.id, &message).await;
+        }
+
+        let exhausted = email.attempts >= MAX_ATTEMPTS;
+        sqlx::query(
+            "UPDATE email_outbox
+             SET status = CASE WHEN $2 THEN 'failed' ELSE 'pending' END,
+                 next_attempt_at = NOW() + make_interval(secs => $3),
+                 last_error = $4
+             WHERE id = $1",
+        )
+        .bind(email.id)
+        .bind(exhausted)
+        .bind(retry_delay_secs(email.attempts) as f64)
+        .bind(&message)
+        .execute(db)
+        .await?;
+
+        warn!(
+            email.id = %email.id,
+            attempts = email.attempts,
+            exhausted,
+            error = %message,
+            "Email delivery failed"
+        );
+
+        Ok(())
+    }
+
+    async fn mark_failed(db: &PgPool, id: OutboundEmailId, error: &str) -> Result<(), AppError> {
+        sqlx::query("UPDATE email_outbox SET status = 'failed', last_error = $2 WHERE id = $1")
+            .bind(id)
+            .bind(error)
+            .execute(db)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    fn rendered() -> RenderedEmail {
+        RenderedEmail {
+            subject: "Hello".to_string(),
+            text: "Hello there".to_string(),
+            html: "<p>Hello there</p>".to_string(),
+        }
+    }
+
+    fn smtp_config(port: u16) -> EmailConfig {
+        EmailConfig {
+            smtp_host: "127.0.0.1".to_string(),
+            smtp_port: port,
+            smtp_username: String::new(),
+            smtp_password: String::new(),
+            from_email: "noreply@example.com".to_string(),
+            from_name: "Chalkbyte".to_string(),
+            frontend_url: "https://app.example.com".to_string(),
+            enabled: true,
+        }
+    }
+
+    /// A minimal SMTP server that accepts every message, or rejects every
+    /// recipient with `550`.
+    async fn spawn_smtp_server(reject_recipients: bool) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let (reader, mut writer) = stream.into_split();
+                    let mut lines = BufReader::new(reader).lines();
+                    writer.write_all(b"220 test ESMTP\r\n").await.unwrap();
+
+                    let mut in_data = false;
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let reply: &[u8] = if in_data {
+                            if line != "." {
+                                continue;
+                            }
+                            in_data = false;
+                            b"250 Queued\r\n"
+                        } else {
+                            match line.get(..4).map(str::to_ascii_uppercase).as_deref() {
+                                Some("EHLO") | Some("HELO") => b"250 test\r\n",
+                                Some("RCPT") if reject_recipients => b"550 5.1.1 No such user\r\n",
+                                Some("DATA") => {
+                                    in_data = true;
+                                    b"354 Go ahead\r\n"
+                                }
+                                Some("QUIT") => {
+                                    let _ = writer.write_all(b"221 Bye\r\n").await;
+                                    break;
+                                }
+                                _ => b"250 OK\r\n",
+                            }
+                        };
+                        if writer.write_all(reply).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        port
+    }
+
+    async fn status_of(pool: &PgPool, to_email: &str) -> (OutboundEmailStatus, i32) {
+        sqlx::query_as::<_, (OutboundEmailStatus, i32)>(
+            "SELECT status, attempts FROM email_outbox WHERE to_email = $1",
+        )
+        .bind(to_email)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off() {
+        assert_eq!(retry_delay_secs(1), 60);
+        assert_eq!(retry_delay_secs(2), 120);
+        assert_eq!(retry_delay_secs(3), 240);
+        assert_eq!(retry_delay_secs(30), MAX_RETRY_DELAY_SECS);
+        assert!(is_bounce(550) && !is_bounce(421) && !is_bounce(535));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_delivers_queued_email(pool: PgPool) {
+        let config = smtp_config(spawn_smtp_server(false).await);
+        let mailer = smtp_transport(&config).unwrap();
+
+        EmailQueueService::enqueue(
+            &pool,
+            None,
+            "ada@example.com",
+            "Chalkbyte",
+            &rendered(),
+            Some("req-1"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(EmailQueueService::queue_depth(&pool).await.unwrap(), 1);
+
+        let attempted = EmailQueueService::deliver_due(&pool, &config, &mailer)
+            .await
+            .unwrap();
+        assert_eq!(attempted, 1);
+        assert_eq!(
+            status_of(&pool, "ada@example.com").await,
+            (OutboundEmailStatus::Sent, 1)
+        );
+        assert_eq!(EmailQueueService::queue_depth(&pool).await.unwrap(), 0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_unreachable_server_is_retried(pool: PgPool) {
+        // Nothing listens on the port once the listener is dropped
+        let port = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let config = smtp_config(port);
+        let mailer = smtp_transport(&config).unwrap();
+
+        EmailQueueService::enqueue(
+            &pool,
+            None,
+            "ada@example.com",
+            "Chalkbyte",
+            &rendered(),
+            None,
+        )
+        .await
+        .unwrap();
+        EmailQueueService::deliver_due(&pool, &config, &mailer)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            status_of(&pool, "ada@example.com").await,
+            (OutboundEmailStatus::Pending, 1)
+        );
+        // Backed off, so not due again yet
+        let attempted = EmailQueueService::deliver_due(&pool, &config, &mailer)
+            .await
+            .unwrap();
+        assert_eq!(attempted, 0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_bounce_suppresses_address(pool: PgPool) {
+        let config = smtp_config(spawn_smtp_server(true).await);
+        let mailer = smtp_transport(&config).unwrap();
+
+        EmailQueueService::enqueue(
+            &pool,
+            None,
+            "Gone@Example.com",
+            "Chalkbyte",
+            &rendered(),
+            None,
+        )
+        .await
+        .unwrap();
+        EmailQueueService::deliver_due(&pool, &config, &mailer)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            status_of(&pool, "Gone@Example.com").await.0,
+            OutboundEmailStatus::Failed
+        );
+        let suppressions = EmailQueueService::get_suppressions(&pool, PaginationParams::default())
+            .await
+            .unwrap();
+        assert_eq!(suppressions.data.len(), 1);
+        assert_eq!(suppressions.data[0].email, "gone@example.com");
+        assert_eq!(suppressions.data[0].reason, SuppressionReason::Bounce);
+
+        // Later emails to the address are not sent
+        let status = EmailQueueService::enqueue(
+            &pool,
+            None,
+            "gone@example.com",
+            "Chalkbyte",
+            &rendered(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, OutboundEmailStatus::Suppressed);
+
+        // Until the address is removed from the list
+        EmailQueueService::unsuppress(&pool, "GONE@example.com")
+            .await
+            .unwrap();
+        assert!(
+            EmailQueueService::unsuppress(&pool, "gone@example.com")
+                .await
+                .is_err()
+        );
+        let status = EmailQueueService::enqueue(
+            &pool,
+            None,
+            "gone@example.com",
+            "Chalkbyte",
+            &rendered(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, OutboundEmailStatus::Pending);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_suppressing_drops_pending_emails(pool: PgPool) {
+        let config = smtp_config(spawn_smtp_server(false).await);
+        let mailer = smtp_transport(&config).unwrap();
+
+        EmailQueueService::enqueue(
+            &pool,
+            None,
+            "ada@example.com",
+            "Chalkbyte",
+            &rendered(),
+            None,
+        )
+        .await
+        .unwrap();
+        EmailQueueService::suppress(
+            &pool,
+            CreateEmailSuppressionDto {
+                email: "ADA@example.com".to_string(),
+                reason: Some(SuppressionReason::Complaint),
+                detail: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let attempted = EmailQueueService::deliver_due(&pool, &config, &mailer)
+            .await
+            .unwrap();
+        assert_eq!(attempted, 0);
+        assert_eq!(
+            status_of(&pool, "ada@example.com").await,
+            (OutboundEmailStatus::Suppressed, 0)
+        );
+
+        let listed = EmailQueueService::get_emails(
+            &pool,
+            OutboundEmailFilterParams {
+                status: Some(OutboundEmailStatus::Suppressed),
+                to_email: Some("Ada@Example.com".to_string()),
+                school_id: None,
+                pagination: PaginationParams::default(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(listed.meta.total, 1);
+        assert_eq!(listed.data[0].subject, "Hello");
+    }
+}
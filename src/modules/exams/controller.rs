@@ -0,0 +1,351 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::{AssessmentId, ExamRoomId, UserId};
+
+use crate::middleware::auth::{RequireAssessmentsRead, RequireAssessmentsUpdate};
+use crate::modules::assessments::service::AssessmentService;
+use crate::modules::exams::model::{
+    AssignInvigilatorDto, ExamSitting, GenerateSeatingPlanDto, ScheduleSittingDto,
+    SeatingChartFormat, SeatingChartParams, SeatingPlan,
+};
+use crate::modules::exams::service::ExamService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::get_optional_school_id_for_resource_operation;
+use crate::validator::ValidatedJson;
+
+/// Generate the seating plan for an exam
+///
+/// Seats every student in the exam's level, or in the branches given, across
+/// the rooms given, filling rooms in order from seat 1. Special-needs
+/// students get the reserved seats first. Generating again replaces the
+/// previous plan; invigilators of rooms no longer in it become roaming.
+#[utoipa::path(
+    post,
+    path = "/api/exams/{id}/seating-plan",
+    summary = "Generate seating plan",
+    params(
+        ("id" = Uuid, Path, description = "Exam (assessment) ID")
+    ),
+    request_body = GenerateSeatingPlanDto,
+    responses(
+        Created<SeatingPlan>,
+        (status = 400, description = "Not an exam, duplicate room names, a branch outside the exam's level, or not enough seats"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:update permission"),
+        (status = 404, description = "Exam not found"),
+        (status = 422, description = "Invalid rooms or special-needs seats")
+    ),
+    tag = "Exams",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn generate_seating_plan(
+    State(state): State<AppState>,
+    RequireAssessmentsUpdate(auth_user): RequireAssessmentsUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<GenerateSeatingPlanDto>,
+) -> Result<Created<SeatingPlan>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::get_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+
+    let plan =
+        ExamService::generate_seating_plan(&state.db, &assessment, auth_user.user_id()?, dto)
+            .await?;
+
+    Ok(Created(plan))
+}
+
+/// Get the seating plan for an exam
+#[utoipa::path(
+    get,
+    path = "/api/exams/{id}/seating-plan",
+    summary = "Get seating plan",
+    params(
+        ("id" = Uuid, Path, description = "Exam (assessment) ID")
+    ),
+    responses(
+        (status = 200, description = "Seating plan with every room's seats", body = SeatingPlan),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:read permission"),
+        (status = 404, description = "Exam not found or has no seating plan")
+    ),
+    tag = "Exams",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_seating_plan(
+    State(state): State<AppState>,
+    RequireAssessmentsRead(auth_user): RequireAssessmentsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SeatingPlan>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::get_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+
+    let plan = ExamService::get_seating_plan(&state.db, assessment.id).await?;
+
+    Ok(Json(plan))
+}
+
+/// Download one room's seating list for printing
+///
+/// The PDF also shows when the exam is sat, in the school's timezone, and
+/// who invigilates the room.
+#[utoipa::path(
+    get,
+    path = "/api/exams/{id}/seating-plan/rooms/{room_id}/print",
+    summary = "Print room list",
+    params(
+        ("id" = Uuid, Path, description = "Exam (assessment) ID"),
+        ("room_id" = Uuid, Path, description = "Exam room ID"),
+        SeatingChartParams
+    ),
+    responses(
+        (status = 200, description = "The room's seats in seat order, as CSV or with format=pdf a PDF",
+            content(
+                (String = "text/csv"),
+                (Vec<u8> = "application/pdf")
+            )
+        ),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:read permission"),
+        (status = 404, description = "Exam, seating plan, or room not found")
+    ),
+    tag = "Exams",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn print_room_list(
+    State(state): State<AppState>,
+    RequireAssessmentsRead(auth_user): RequireAssessmentsRead,
+    Path((id, room_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<SeatingChartParams>,
+) -> Result<Response, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::get_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+    let room_id = ExamRoomId::from(room_id);
+
+    let (room_name, content_type, extension, body) = match params.format {
+        SeatingChartFormat::Csv => {
+            let (name, csv) = ExamService::room_list_csv(&state.db, assessment.id, room_id).await?;
+            (name, "text/csv; charset=utf-8", "csv", csv.into_bytes())
+        }
+        SeatingChartFormat::Pdf => {
+            let (name, pdf) = ExamService::room_list_pdf(&state.db, &assessment, room_id).await?;
+            (name, "application/pdf", "pdf", pdf)
+        }
+    };
+
+    let file_name: String = room_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"seating-{}.{}\"",
+                    file_name, extension
+                ),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Schedule an exam's sitting
+///
+/// Sets when the exam starts and how long it runs, replacing any earlier
+/// time. Moving a sitting fails if one of its invigilators would then clash
+/// with another exam they invigilate or their approved leave.
+#[utoipa::path(
+    put,
+    path = "/api/exams/{id}/sitting",
+    summary = "Schedule sitting",
+    params(
+        ("id" = Uuid, Path, description = "Exam (assessment) ID")
+    ),
+    request_body = ScheduleSittingDto,
+    responses(
+        (status = 200, description = "Sitting with its invigilators", body = ExamSitting),
+        (status = 400, description = "Not an exam"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:update permission"),
+        (status = 404, description = "Exam not found"),
+        (status = 409, description = "An invigilator would clash with another duty or leave"),
+        (status = 422, description = "Invalid duration")
+    ),
+    tag = "Exams",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn schedule_sitting(
+    State(state): State<AppState>,
+    RequireAssessmentsUpdate(auth_user): RequireAssessmentsUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<ScheduleSittingDto>,
+) -> Result<Json<ExamSitting>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::get_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+
+    let sitting =
+        ExamService::schedule_sitting(&state.db, &assessment, auth_user.user_id()?, dto).await?;
+
+    Ok(Json(sitting))
+}
+
+/// Get an exam's sitting
+#[utoipa::path(
+    get,
+    path = "/api/exams/{id}/sitting",
+    summary = "Get sitting",
+    params(
+        ("id" = Uuid, Path, description = "Exam (assessment) ID")
+    ),
+    responses(
+        (status = 200, description = "Sitting with its invigilators", body = ExamSitting),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:read permission"),
+        (status = 404, description = "Exam not found or not scheduled")
+    ),
+    tag = "Exams",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_sitting(
+    State(state): State<AppState>,
+    RequireAssessmentsRead(auth_user): RequireAssessmentsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ExamSitting>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::get_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+
+    let sitting = ExamService::get_sitting(&state.db, assessment.id).await?;
+
+    Ok(Json(sitting))
+}
+
+/// Cancel an exam's sitting
+///
+/// Its invigilators are released.
+#[utoipa::path(
+    delete,
+    path = "/api/exams/{id}/sitting",
+    summary = "Cancel sitting",
+    params(
+        ("id" = Uuid, Path, description = "Exam (assessment) ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:update permission"),
+        (status = 404, description = "Exam not found or not scheduled")
+    ),
+    tag = "Exams",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_sitting(
+    State(state): State<AppState>,
+    RequireAssessmentsUpdate(auth_user): RequireAssessmentsUpdate,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::get_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+
+    ExamService::delete_sitting(&state.db, assessment.id).await?;
+
+    Ok(NoContent)
+}
+
+/// Assign an invigilator to an exam's sitting
+///
+/// Assigning a teacher again moves them to the room given. Fails if the
+/// teacher invigilates another exam at an overlapping time or is on
+/// approved leave that day.
+#[utoipa::path(
+    post,
+    path = "/api/exams/{id}/sitting/invigilators",
+    summary = "Assign invigilator",
+    params(
+        ("id" = Uuid, Path, description = "Exam (assessment) ID")
+    ),
+    request_body = AssignInvigilatorDto,
+    responses(
+        (status = 200, description = "Sitting with its invigilators", body = ExamSitting),
+        (status = 400, description = "Not a teacher at the school, or the room is not in the seating plan"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:update permission"),
+        (status = 404, description = "Exam not found or not scheduled"),
+        (status = 409, description = "The teacher is invigilating another exam at the same time or is on leave"),
+        (status = 422, description = "Invalid room name")
+    ),
+    tag = "Exams",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn assign_invigilator(
+    State(state): State<AppState>,
+    RequireAssessmentsUpdate(auth_user): RequireAssessmentsUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<AssignInvigilatorDto>,
+) -> Result<Json<ExamSitting>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::get_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+
+    let sitting =
+        ExamService::assign_invigilator(&state.db, &assessment, auth_user.user_id()?, dto).await?;
+
+    Ok(Json(sitting))
+}
+
+/// Remove an invigilator from an exam's sitting
+#[utoipa::path(
+    delete,
+    path = "/api/exams/{id}/sitting/invigilators/{teacher_id}",
+    summary = "Remove invigilator",
+    params(
+        ("id" = Uuid, Path, description = "Exam (assessment) ID"),
+        ("teacher_id" = Uuid, Path, description = "Teacher user ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires assessments:update permission"),
+        (status = 404, description = "Exam not found, or the teacher is not invigilating it")
+    ),
+    tag = "Exams",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn remove_invigilator(
+    State(state): State<AppState>,
+    RequireAssessmentsUpdate(auth_user): RequireAssessmentsUpdate,
+    Path((id, teacher_id)): Path<(Uuid, Uuid)>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assessment =
+        AssessmentService::get_assessment(&state.db, AssessmentId::from(id), school_id).await?;
+
+    ExamService::remove_invigilator(&state.db, assessment.id, UserId::from(teacher_id)).await?;
+
+    Ok(NoContent)
+}
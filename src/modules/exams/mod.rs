@@ -0,0 +1,17 @@
+//! Exam seating module.
+//!
+//! Generates seating plans for exams (assessments of kind `exam`): the
+//! students in the exam's level are allocated to numbered seats across the
+//! rooms given, with special-needs students in their reserved seats and,
+//! optionally, neighbours from different branches. Plans are stored per exam
+//! and each room's list can be downloaded as CSV or PDF for printing.
+//!
+//! Each exam also has a sitting: when it starts, how long it runs, and the
+//! teachers invigilating it. The school has no class timetable, so an
+//! invigilator clashes when they already invigilate an overlapping sitting
+//! or are on approved leave that day.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Exam seating data models and DTOs.
+//!
+//! This module re-exports exam models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all exam models from the shared crate
+pub use chalkbyte_models::exams::*;
@@ -0,0 +1,40 @@
+use axum::{
+    Router,
+    routing::{delete, get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    assign_invigilator, delete_sitting, generate_seating_plan, get_seating_plan, get_sitting,
+    print_room_list, remove_invigilator, schedule_sitting,
+};
+
+/// Initialize the exams router
+/// Routes: POST /{id}/seating-plan, GET /{id}/seating-plan,
+/// GET /{id}/seating-plan/rooms/{room_id}/print,
+/// PUT /{id}/sitting, GET /{id}/sitting, DELETE /{id}/sitting,
+/// POST /{id}/sitting/invigilators,
+/// DELETE /{id}/sitting/invigilators/{teacher_id}
+pub fn init_exams_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/{id}/seating-plan",
+            post(generate_seating_plan).get(get_seating_plan),
+        )
+        .route(
+            "/{id}/seating-plan/rooms/{room_id}/print",
+            get(print_room_list),
+        )
+        .route(
+            "/{id}/sitting",
+            get(get_sitting)
+                .put(schedule_sitting)
+                .delete(delete_sitting),
+        )
+        .route("/{id}/sitting/invigilators", post(assign_invigilator))
+        .route(
+            "/{id}/sitting/invigilators/{teacher_id}",
+            delete(remove_invigilator),
+        )
+}
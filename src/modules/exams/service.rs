@@ -0,0 +1,1405 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use axum::http::StatusCode;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::Serialize;
+use sqlx::{PgConnection, PgExecutor, PgPool};
+use tracing::instrument;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::{
+    AssessmentId, BranchId, ExamRoomId, ExamSeatingPlanId, ExamSittingId, LevelId, SchoolId, UserId,
+};
+
+use crate::modules::assessments::model::{Assessment, AssessmentKind};
+use crate::modules::exams::model::{
+    AssignInvigilatorDto, ExamInvigilator, ExamRoom, ExamRoomDto, ExamSeat, ExamSitting,
+    GenerateSeatingPlanDto, ScheduleSittingDto, SeatingPlan,
+};
+use crate::modules::users::model::system_roles;
+use crate::utils::pdf::TextPdf;
+
+const SITTING_COLUMNS: &str = r#"id, assessment_id, starts_at, duration_minutes,
+    starts_at + make_interval(mins => duration_minutes) AS ends_at,
+    scheduled_by, created_at, updated_at"#;
+
+/// A student to be seated, with the branch used to alternate neighbours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SeatCandidate {
+    student_id: UserId,
+    branch_id: Option<BranchId>,
+}
+
+/// A student placed in a seat; `room` indexes the rooms in the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SeatAllocation {
+    room: usize,
+    seat_number: i32,
+    student_id: UserId,
+    special_needs: bool,
+}
+
+/// One line of a printable room list.
+#[derive(Serialize)]
+struct PrintableSeat<'a> {
+    seat: i32,
+    last_name: &'a str,
+    first_name: &'a str,
+    branch: Option<&'a str>,
+    special_needs: &'static str,
+}
+
+/// Reorder students so that consecutive students come from different
+/// branches wherever the mix allows.
+///
+/// Each step takes the next student from the branch with the most students
+/// left that differs from the previous student's branch, which only seats
+/// two of a branch together once no other branch is left. Students keep
+/// their relative order within a branch.
+fn alternate_branches(students: Vec<SeatCandidate>) -> Vec<SeatCandidate> {
+    let total = students.len();
+    let mut groups: Vec<(Option<BranchId>, VecDeque<SeatCandidate>)> = Vec::new();
+    for student in students {
+        match groups
+            .iter_mut()
+            .find(|(branch, _)| *branch == student.branch_id)
+        {
+            Some((_, group)) => group.push_back(student),
+            None => groups.push((student.branch_id, VecDeque::from([student]))),
+        }
+    }
+
+    let mut ordered = Vec::with_capacity(total);
+    let mut previous = None;
+    while let Some(i) = (0..groups.len())
+        .filter(|&i| !groups[i].1.is_empty())
+        // A different branch first, then the largest group, then the earliest
+        .min_by_key(|&i| (Some(groups[i].0) == previous, Reverse(groups[i].1.len()), i))
+    {
+        if let Some(student) = groups[i].1.pop_front() {
+            previous = Some(groups[i].0);
+            ordered.push(student);
+        }
+    }
+    ordered
+}
+
+/// Seat special-needs students in the reserved seats, then everyone else in
+/// the remaining seats, filling rooms in order from seat 1.
+///
+/// Callers check beforehand that there are enough seats of each kind.
+fn allocate_seats(
+    rooms: &[ExamRoomDto],
+    special_needs: &[SeatCandidate],
+    others: &[SeatCandidate],
+) -> Vec<SeatAllocation> {
+    let mut allocations = Vec::with_capacity(special_needs.len() + others.len());
+
+    let reserved = rooms.iter().enumerate().flat_map(|(room, dto)| {
+        let mut seats = dto.special_needs_seats.clone();
+        seats.sort_unstable();
+        seats.into_iter().map(move |seat| (room, seat))
+    });
+    for ((room, seat_number), student) in reserved.zip(special_needs) {
+        allocations.push(SeatAllocation {
+            room,
+            seat_number,
+            student_id: student.student_id,
+            special_needs: true,
+        });
+    }
+
+    let taken: HashSet<(usize, i32)> = allocations
+        .iter()
+        .map(|a| (a.room, a.seat_number))
+        .collect();
+    let free = rooms
+        .iter()
+        .enumerate()
+        .flat_map(|(room, dto)| (1..=dto.capacity).map(move |seat| (room, seat)))
+        .filter(|seat| !taken.contains(seat));
+    for ((room, seat_number), student) in free.zip(others) {
+        allocations.push(SeatAllocation {
+            room,
+            seat_number,
+            student_id: student.student_id,
+            special_needs: false,
+        });
+    }
+
+    allocations
+}
+
+pub struct ExamService;
+
+impl ExamService {
+    /// Generate and store the seating plan for an exam, replacing any
+    /// previous plan.
+    ///
+    /// Everyone currently in the exam's level is seated. The request is
+    /// rejected when the rooms are too small, when there are fewer
+    /// special-needs seats than special-needs students, or when a
+    /// special-needs student is not sitting the exam.
+    #[instrument(skip(db, dto))]
+    pub async fn generate_seating_plan(
+        db: &PgPool,
+        assessment: &Assessment,
+        generated_by: UserId,
+        dto: GenerateSeatingPlanDto,
+    ) -> Result<SeatingPlan, AppError> {
+        if assessment.kind != AssessmentKind::Exam {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Seating plans can only be generated for exams"
+            )));
+        }
+
+        let mut names = HashSet::new();
+        if let Some(room) = dto
+            .rooms
+            .iter()
+            .find(|room| !names.insert(room.name.to_lowercase()))
+        {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Room '{}' is listed more than once",
+                room.name
+            )));
+        }
+
+        let mut branch_ids = dto.branch_ids.clone();
+        branch_ids.sort_unstable_by_key(|id| id.into_inner());
+        branch_ids.dedup();
+        let level_branches: Vec<BranchId> =
+            sqlx::query_scalar("SELECT id FROM branches WHERE id = ANY($1) AND level_id = $2")
+                .bind(&branch_ids)
+                .bind(assessment.level_id)
+                .fetch_all(db)
+                .await?;
+        if let Some(id) = branch_ids.iter().find(|id| !level_branches.contains(id)) {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Branch {} is not in this exam's level",
+                id
+            )));
+        }
+
+        let students =
+            Self::get_candidates(db, assessment.level_id, assessment.school_id, &branch_ids)
+                .await?;
+
+        let special_ids: HashSet<UserId> = dto.special_needs_student_ids.iter().copied().collect();
+        if let Some(id) = special_ids
+            .iter()
+            .find(|id| !students.iter().any(|s| s.student_id == **id))
+        {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Student {} is not sitting this exam",
+                id
+            )));
+        }
+
+        let seats: i64 = dto.rooms.iter().map(|r| i64::from(r.capacity)).sum();
+        if students.len() as i64 > seats {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "The rooms seat {} students but {} are sitting this exam",
+                seats,
+                students.len()
+            )));
+        }
+
+        let reserved: usize = dto.rooms.iter().map(|r| r.special_needs_seats.len()).sum();
+        if special_ids.len() > reserved {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "{} students need special-needs seats but only {} are reserved",
+                special_ids.len(),
+                reserved
+            )));
+        }
+
+        let (special_needs, others): (Vec<_>, Vec<_>) = students
+            .into_iter()
+            .partition(|s| special_ids.contains(&s.student_id));
+        let others = if dto.alternate_branches {
+            alternate_branches(others)
+        } else {
+            others
+        };
+        let allocations = allocate_seats(&dto.rooms, &special_needs, &others);
+
+        let mut tx = db.begin().await?;
+
+        sqlx::query("DELETE FROM exam_seating_plans WHERE assessment_id = $1")
+            .bind(assessment.id)
+            .execute(&mut *tx)
+            .await?;
+
+        let plan_id = sqlx::query_scalar::<_, ExamSeatingPlanId>(
+            r#"INSERT INTO exam_seating_plans (assessment_id, school_id, alternate_branches, branch_ids, generated_by)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id"#,
+        )
+        .bind(assessment.id)
+        .bind(assessment.school_id)
+        .bind(dto.alternate_branches)
+        .bind((!branch_ids.is_empty()).then_some(&branch_ids))
+        .bind(generated_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut room_ids = Vec::with_capacity(dto.rooms.len());
+        for (position, room) in dto.rooms.iter().enumerate() {
+            let mut special_needs_seats = room.special_needs_seats.clone();
+            special_needs_seats.sort_unstable();
+
+            let room_id = sqlx::query_scalar::<_, ExamRoomId>(
+                r#"INSERT INTO exam_rooms (plan_id, name, capacity, special_needs_seats, position)
+                   VALUES ($1, $2, $3, $4, $5)
+                   RETURNING id"#,
+            )
+            .bind(plan_id)
+            .bind(&room.name)
+            .bind(room.capacity)
+            .bind(&special_needs_seats)
+            .bind(position as i32)
+            .fetch_one(&mut *tx)
+            .await?;
+            room_ids.push(room_id);
+        }
+
+        sqlx::query(
+            r#"INSERT INTO exam_seats (plan_id, room_id, seat_number, student_id, special_needs)
+               SELECT $1, t.room_id, t.seat_number, t.student_id, t.special_needs
+               FROM UNNEST($2::uuid[], $3::int[], $4::uuid[], $5::bool[])
+                    AS t(room_id, seat_number, student_id, special_needs)"#,
+        )
+        .bind(plan_id)
+        .bind(
+            allocations
+                .iter()
+                .map(|a| room_ids[a.room])
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            allocations
+                .iter()
+                .map(|a| a.seat_number)
+                .collect::<Vec<_>>(),
+        )
+        .bind(allocations.iter().map(|a| a.student_id).collect::<Vec<_>>())
+        .bind(
+            allocations
+                .iter()
+                .map(|a| a.special_needs)
+                .collect::<Vec<_>>(),
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Invigilators of rooms that are gone become roaming
+        sqlx::query(
+            r#"UPDATE exam_invigilators i
+               SET room_name = NULL
+               FROM exam_sittings s
+               WHERE s.id = i.sitting_id AND s.assessment_id = $1
+                 AND i.room_name IS NOT NULL
+                 AND NOT EXISTS (
+                     SELECT 1 FROM exam_rooms r
+                     WHERE r.plan_id = $2 AND LOWER(r.name) = LOWER(i.room_name)
+                 )"#,
+        )
+        .bind(assessment.id)
+        .bind(plan_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Self::get_seating_plan(db, assessment.id).await
+    }
+
+    /// Students sitting an exam for a level, ordered by name. When branches
+    /// are given, only students in them are included.
+    async fn get_candidates(
+        db: &PgPool,
+        level_id: LevelId,
+        school_id: SchoolId,
+        branch_ids: &[BranchId],
+    ) -> Result<Vec<SeatCandidate>, AppError> {
+        let rows = sqlx::query_as::<_, (UserId, Option<BranchId>)>(
+            r#"SELECT u.id, u.branch_id
+               FROM users u
+               INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $3
+               WHERE u.level_id = $1 AND u.school_id = $2
+                 AND (cardinality($4::uuid[]) = 0 OR u.branch_id = ANY($4))
+               ORDER BY u.last_name, u.first_name, u.id"#,
+        )
+        .bind(level_id)
+        .bind(school_id)
+        .bind(system_roles::STUDENT)
+        .bind(branch_ids)
+        .fetch_all(db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(student_id, branch_id)| SeatCandidate {
+                student_id,
+                branch_id,
+            })
+            .collect())
+    }
+
+    /// Get an exam's seating plan with every room's seats.
+    #[instrument(skip(db))]
+    pub async fn get_seating_plan(
+        db: &PgPool,
+        assessment_id: AssessmentId,
+    ) -> Result<SeatingPlan, AppError> {
+        let mut plan = sqlx::query_as::<_, SeatingPlan>(
+            r#"SELECT id, assessment_id, alternate_branches, branch_ids, generated_by, created_at
+               FROM exam_seating_plans
+               WHERE assessment_id = $1"#,
+        )
+        .bind(assessment_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("This exam has no seating plan")))?;
+
+        let mut rooms = sqlx::query_as::<_, ExamRoom>(
+            r#"SELECT id, name, capacity, special_needs_seats
+               FROM exam_rooms
+               WHERE plan_id = $1
+               ORDER BY position"#,
+        )
+        .bind(plan.id)
+        .fetch_all(db)
+        .await?;
+
+        let seats = sqlx::query_as::<_, ExamSeat>(
+            r#"SELECT s.room_id, s.seat_number, s.student_id, u.first_name, u.last_name,
+                      u.branch_id, b.name AS branch_name, s.special_needs
+               FROM exam_seats s
+               INNER JOIN users u ON u.id = s.student_id
+               LEFT JOIN branches b ON b.id = u.branch_id
+               WHERE s.plan_id = $1
+               ORDER BY s.seat_number"#,
+        )
+        .bind(plan.id)
+        .fetch_all(db)
+        .await?;
+
+        let mut seats_by_room: HashMap<ExamRoomId, Vec<ExamSeat>> = HashMap::new();
+        for seat in seats {
+            seats_by_room.entry(seat.room_id).or_default().push(seat);
+        }
+        for room in &mut rooms {
+            room.seats = seats_by_room.remove(&room.id).unwrap_or_default();
+        }
+        plan.rooms = rooms;
+
+        Ok(plan)
+    }
+
+    /// Render one room of an exam's seating plan as a printable CSV list in
+    /// seat order. Returns the room name with the CSV.
+    #[instrument(skip(db))]
+    pub async fn room_list_csv(
+        db: &PgPool,
+        assessment_id: AssessmentId,
+        room_id: ExamRoomId,
+    ) -> Result<(String, String), AppError> {
+        let room = Self::get_room(db, assessment_id, room_id).await?;
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for seat in &room.seats {
+            writer.serialize(PrintableSeat {
+                seat: seat.seat_number,
+                last_name: &seat.last_name,
+                first_name: &seat.first_name,
+                branch: seat.branch_name.as_deref(),
+                special_needs: if seat.special_needs { "yes" } else { "" },
+            })?;
+        }
+        let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+
+        Ok((room.name, String::from_utf8(bytes)?))
+    }
+
+    /// Render one room of an exam's seating plan as a printable PDF with the
+    /// sitting time, in the school's timezone, and the room's invigilators.
+    /// Returns the room name with the PDF.
+    #[instrument(skip(db, assessment), fields(assessment_id = %assessment.id))]
+    pub async fn room_list_pdf(
+        db: &PgPool,
+        assessment: &Assessment,
+        room_id: ExamRoomId,
+    ) -> Result<(String, Vec<u8>), AppError> {
+        let room = Self::get_room(db, assessment.id, room_id).await?;
+        let sitting = Self::find_sitting(db, assessment.id).await?;
+
+        let mut pdf = TextPdf::new(format!("{} - {}", assessment.name, room.name));
+        pdf.heading(format!("{} - {}", assessment.name, room.name));
+        pdf.line(format!(
+            "{} of {} seats taken",
+            room.seats.len(),
+            room.capacity
+        ));
+
+        match &sitting {
+            Some(sitting) => {
+                let (starts_at, timezone) = sqlx::query_as::<_, (NaiveDateTime, String)>(
+                    r#"SELECT ($1::timestamptz AT TIME ZONE timezone), timezone
+                       FROM schools WHERE id = $2"#,
+                )
+                .bind(sitting.starts_at)
+                .bind(assessment.school_id)
+                .fetch_one(db)
+                .await?;
+                pdf.line(format!(
+                    "Starts {} ({}), {} minutes",
+                    starts_at.format("%A %-d %B %Y, %H:%M"),
+                    timezone,
+                    sitting.duration_minutes
+                ));
+
+                let invigilators: Vec<String> = sitting
+                    .invigilators
+                    .iter()
+                    .filter_map(|i| match &i.room_name {
+                        Some(name) if name.eq_ignore_ascii_case(&room.name) => {
+                            Some(format!("{} {}", i.first_name, i.last_name))
+                        }
+                        Some(_) => None,
+                        None => Some(format!("{} {} (roaming)", i.first_name, i.last_name)),
+                    })
+                    .collect();
+                if invigilators.is_empty() {
+                    pdf.line("Invigilators: none assigned");
+                } else {
+                    pdf.line(format!("Invigilators: {}", invigilators.join(", ")));
+                }
+            }
+            None => {
+                pdf.line("Not yet scheduled");
+            }
+        }
+
+        pdf.blank().heading("Seats");
+        for seat in &room.seats {
+            let mut line = format!(
+                "{:>4}  {}, {}",
+                seat.seat_number, seat.last_name, seat.first_name
+            );
+            if let Some(branch) = &seat.branch_name {
+                line.push_str(&format!(" - {}", branch));
+            }
+            if seat.special_needs {
+                line.push_str(" (special needs)");
+            }
+            pdf.line(line);
+        }
+
+        Ok((room.name, pdf.finish()))
+    }
+
+    /// One room of an exam's seating plan with its seats.
+    async fn get_room(
+        db: &PgPool,
+        assessment_id: AssessmentId,
+        room_id: ExamRoomId,
+    ) -> Result<ExamRoom, AppError> {
+        let plan = Self::get_seating_plan(db, assessment_id).await?;
+        plan.rooms
+            .into_iter()
+            .find(|room| room.id == room_id)
+            .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Exam room not found")))
+    }
+
+    /// Schedule an exam's sitting, or move it.
+    ///
+    /// Moving a sitting is rejected with a conflict when one of its
+    /// invigilators would then clash with another duty or their leave.
+    #[instrument(skip(db, assessment, dto), fields(assessment_id = %assessment.id))]
+    pub async fn schedule_sitting(
+        db: &PgPool,
+        assessment: &Assessment,
+        scheduled_by: UserId,
+        dto: ScheduleSittingDto,
+    ) -> Result<ExamSitting, AppError> {
+        if assessment.kind != AssessmentKind::Exam {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Sittings can only be scheduled for exams"
+            )));
+        }
+
+        let mut tx = db.begin().await?;
+
+        let sitting = sqlx::query_as::<_, ExamSitting>(&format!(
+            r#"INSERT INTO exam_sittings (assessment_id, school_id, starts_at, duration_minutes, scheduled_by)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (assessment_id) DO UPDATE SET
+                   starts_at = EXCLUDED.starts_at,
+                   duration_minutes = EXCLUDED.duration_minutes,
+                   scheduled_by = EXCLUDED.scheduled_by
+               RETURNING {SITTING_COLUMNS}"#
+        ))
+        .bind(assessment.id)
+        .bind(assessment.school_id)
+        .bind(dto.starts_at)
+        .bind(dto.duration_minutes)
+        .bind(scheduled_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let invigilators = Self::get_invigilators(&mut *tx, sitting.id).await?;
+        Self::lock_teachers(&mut tx, invigilators.iter().map(|i| i.teacher_id)).await?;
+        for invigilator in &invigilators {
+            if let Some(clash) = Self::find_clash(
+                &mut tx,
+                invigilator.teacher_id,
+                sitting.id,
+                sitting.starts_at,
+                sitting.ends_at,
+            )
+            .await?
+            {
+                return Err(AppError::new(
+                    StatusCode::CONFLICT,
+                    anyhow::anyhow!(
+                        "{} {} {}",
+                        invigilator.first_name,
+                        invigilator.last_name,
+                        clash
+                    ),
+                ));
+            }
+        }
+
+        tx.commit().await?;
+
+        Self::get_sitting(db, assessment.id).await
+    }
+
+    /// Get an exam's sitting with its invigilators.
+    #[instrument(skip(db))]
+    pub async fn get_sitting(
+        db: &PgPool,
+        assessment_id: AssessmentId,
+    ) -> Result<ExamSitting, AppError> {
+        Self::find_sitting(db, assessment_id)
+            .await?
+            .ok_or_else(|| AppError::not_found(anyhow::anyhow!("This exam has no sitting")))
+    }
+
+    async fn find_sitting(
+        db: &PgPool,
+        assessment_id: AssessmentId,
+    ) -> Result<Option<ExamSitting>, AppError> {
+        let sitting = sqlx::query_as::<_, ExamSitting>(&format!(
+            "SELECT {SITTING_COLUMNS} FROM exam_sittings WHERE assessment_id = $1"
+        ))
+        .bind(assessment_id)
+        .fetch_optional(db)
+        .await?;
+
+        let Some(mut sitting) = sitting else {
+            return Ok(None);
+        };
+        sitting.invigilators = Self::get_invigilators(db, sitting.id).await?;
+
+        Ok(Some(sitting))
+    }
+
+    /// Cancel an exam's sitting, dropping its invigilators.
+    #[instrument(skip(db))]
+    pub async fn delete_sitting(db: &PgPool, assessment_id: AssessmentId) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM exam_sittings WHERE assessment_id = $1")
+            .bind(assessment_id)
+            .execute(db)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "This exam has no sitting"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Assign a teacher to invigilate an exam's sitting, or move them to
+    /// another room.
+    ///
+    /// The school has no class timetable, so a clash is another sitting the
+    /// teacher invigilates that overlaps this one, or approved leave on the
+    /// day of the sitting; either is rejected with a conflict.
+    #[instrument(skip(db, assessment, dto), fields(assessment_id = %assessment.id))]
+    pub async fn assign_invigilator(
+        db: &PgPool,
+        assessment: &Assessment,
+        assigned_by: UserId,
+        dto: AssignInvigilatorDto,
+    ) -> Result<ExamSitting, AppError> {
+        let sitting = Self::get_sitting(db, assessment.id).await?;
+
+        let (first_name, last_name) = sqlx::query_as::<_, (String, String)>(
+            r#"SELECT u.first_name, u.last_name
+               FROM users u
+               INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $3
+               WHERE u.id = $1 AND u.school_id = $2"#,
+        )
+        .bind(dto.teacher_id)
+        .bind(assessment.school_id)
+        .bind(system_roles::TEACHER)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| {
+            AppError::bad_request(anyhow::anyhow!(
+                "User {} is not a teacher at this school",
+                dto.teacher_id
+            ))
+        })?;
+
+        let room_name = match &dto.room_name {
+            Some(name) => Some(
+                sqlx::query_scalar::<_, String>(
+                    r#"SELECT r.name
+                       FROM exam_rooms r
+                       INNER JOIN exam_seating_plans p ON p.id = r.plan_id
+                       WHERE p.assessment_id = $1 AND LOWER(r.name) = LOWER($2)"#,
+                )
+                .bind(assessment.id)
+                .bind(name)
+                .fetch_optional(db)
+                .await?
+                .ok_or_else(|| {
+                    AppError::bad_request(anyhow::anyhow!(
+                        "Room '{}' is not in this exam's seating plan",
+                        name
+                    ))
+                })?,
+            ),
+            None => None,
+        };
+
+        let mut tx = db.begin().await?;
+
+        Self::lock_teachers(&mut tx, [dto.teacher_id]).await?;
+        if let Some(clash) = Self::find_clash(
+            &mut tx,
+            dto.teacher_id,
+            sitting.id,
+            sitting.starts_at,
+            sitting.ends_at,
+        )
+        .await?
+        {
+            return Err(AppError::new(
+                StatusCode::CONFLICT,
+                anyhow::anyhow!("{} {} {}", first_name, last_name, clash),
+            ));
+        }
+
+        sqlx::query(
+            r#"INSERT INTO exam_invigilators (sitting_id, teacher_id, room_name, assigned_by)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (sitting_id, teacher_id) DO UPDATE SET
+                   room_name = EXCLUDED.room_name,
+                   assigned_by = EXCLUDED.assigned_by"#,
+        )
+        .bind(sitting.id)
+        .bind(dto.teacher_id)
+        .bind(room_name)
+        .bind(assigned_by)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Self::get_sitting(db, assessment.id).await
+    }
+
+    /// Remove a teacher from an exam's invigilators.
+    #[instrument(skip(db))]
+    pub async fn remove_invigilator(
+        db: &PgPool,
+        assessment_id: AssessmentId,
+        teacher_id: UserId,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"DELETE FROM exam_invigilators i
+               USING exam_sittings s
+               WHERE s.id = i.sitting_id AND s.assessment_id = $1 AND i.teacher_id = $2"#,
+        )
+        .bind(assessment_id)
+        .bind(teacher_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Invigilator not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn get_invigilators(
+        db: impl PgExecutor<'_>,
+        sitting_id: ExamSittingId,
+    ) -> Result<Vec<ExamInvigilator>, AppError> {
+        let invigilators = sqlx::query_as::<_, ExamInvigilator>(
+            r#"SELECT i.teacher_id, u.first_name, u.last_name, i.room_name, i.assigned_by,
+                      i.created_at
+               FROM exam_invigilators i
+               INNER JOIN users u ON u.id = i.teacher_id
+               WHERE i.sitting_id = $1
+               ORDER BY u.last_name, u.first_name, u.id"#,
+        )
+        .bind(sitting_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(invigilators)
+    }
+
+    /// Lock teachers' rows so concurrent assignments of the same teacher are
+    /// checked for clashes one at a time.
+    async fn lock_teachers(
+        conn: &mut PgConnection,
+        teacher_ids: impl IntoIterator<Item = UserId>,
+    ) -> Result<(), AppError> {
+        let teacher_ids: Vec<UserId> = teacher_ids.into_iter().collect();
+        sqlx::query("SELECT id FROM users WHERE id = ANY($1) ORDER BY id FOR UPDATE")
+            .bind(&teacher_ids)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Describe what stops a teacher invigilating between `starts_at` and
+    /// `ends_at`: another sitting they invigilate that overlaps, or approved
+    /// leave covering a day of it in the school's timezone.
+    async fn find_clash(
+        conn: &mut PgConnection,
+        teacher_id: UserId,
+        sitting_id: ExamSittingId,
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+    ) -> Result<Option<String>, AppError> {
+        let duty = sqlx::query_scalar::<_, String>(
+            r#"SELECT a.name
+               FROM exam_invigilators i
+               INNER JOIN exam_sittings s ON s.id = i.sitting_id
+               INNER JOIN assessments a ON a.id = s.assessment_id
+               WHERE i.teacher_id = $1 AND s.id <> $2
+                 AND s.starts_at < $4
+                 AND s.starts_at + make_interval(mins => s.duration_minutes) > $3
+               ORDER BY s.starts_at
+               LIMIT 1"#,
+        )
+        .bind(teacher_id)
+        .bind(sitting_id)
+        .bind(starts_at)
+        .bind(ends_at)
+        .fetch_optional(&mut *conn)
+        .await?;
+        if let Some(exam) = duty {
+            return Ok(Some(format!("is invigilating {} at the same time", exam)));
+        }
+
+        let leave = sqlx::query_as::<_, (NaiveDate, NaiveDate)>(
+            r#"SELECT l.start_date, l.end_date
+               FROM staff_leave_requests l
+               INNER JOIN schools sc ON sc.id = l.school_id
+               WHERE l.staff_id = $1 AND l.status = 'approved'
+                 AND l.start_date <= ($3::timestamptz AT TIME ZONE sc.timezone)::date
+                 AND l.end_date >= ($2::timestamptz AT TIME ZONE sc.timezone)::date
+               ORDER BY l.start_date
+               LIMIT 1"#,
+        )
+        .bind(teacher_id)
+        .bind(starts_at)
+        .bind(ends_at)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(leave.map(|(start, end)| format!("is on approved leave from {} to {}", start, end)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chalkbyte_models::ids::{SubjectId, TermId};
+
+    fn candidate(branch: Option<BranchId>) -> SeatCandidate {
+        SeatCandidate {
+            student_id: UserId::new(),
+            branch_id: branch,
+        }
+    }
+
+    fn room(name: &str, capacity: i32, special_needs_seats: Vec<i32>) -> ExamRoomDto {
+        ExamRoomDto {
+            name: name.to_string(),
+            capacity,
+            special_needs_seats,
+        }
+    }
+
+    #[test]
+    fn test_alternate_branches_separates_neighbours() {
+        let (a, b, c) = (BranchId::new(), BranchId::new(), BranchId::new());
+        let students: Vec<_> = [a, a, a, a, b, b, c]
+            .into_iter()
+            .map(|branch| candidate(Some(branch)))
+            .collect();
+
+        let ordered = alternate_branches(students.clone());
+
+        assert_eq!(ordered.len(), students.len());
+        assert!(
+            ordered
+                .windows(2)
+                .all(|pair| pair[0].branch_id != pair[1].branch_id)
+        );
+        // Order within a branch is kept
+        let branch_a: Vec<_> = ordered.iter().filter(|s| s.branch_id == Some(a)).collect();
+        let original_a: Vec<_> = students.iter().filter(|s| s.branch_id == Some(a)).collect();
+        assert_eq!(branch_a, original_a);
+    }
+
+    #[test]
+    fn test_alternate_branches_with_one_branch_keeps_everyone() {
+        let branch = Some(BranchId::new());
+        let students: Vec<_> = (0..3).map(|_| candidate(branch)).collect();
+
+        assert_eq!(alternate_branches(students.clone()), students);
+    }
+
+    #[test]
+    fn test_allocate_seats_reserves_special_needs_seats() {
+        let rooms = [room("Hall A", 3, vec![3]), room("Hall B", 2, vec![])];
+        let special = [candidate(None)];
+        let others: Vec<_> = (0..4).map(|_| candidate(None)).collect();
+
+        let allocations = allocate_seats(&rooms, &special, &others);
+
+        let seats: Vec<_> = allocations
+            .iter()
+            .map(|a| (a.room, a.seat_number, a.special_needs))
+            .collect();
+        assert_eq!(
+            seats,
+            [
+                (0, 3, true),
+                (0, 1, false),
+                (0, 2, false),
+                (1, 1, false),
+                (1, 2, false),
+            ]
+        );
+        assert_eq!(allocations[0].student_id, special[0].student_id);
+    }
+
+    struct Fixture {
+        school_id: SchoolId,
+        level_id: LevelId,
+        term_id: TermId,
+        subject_id: SubjectId,
+        admin_id: UserId,
+    }
+
+    async fn create_fixture(pool: &PgPool) -> Fixture {
+        let school_id: SchoolId =
+            sqlx::query_scalar("INSERT INTO schools (name) VALUES ('Exam School') RETURNING id")
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        let level_id: LevelId = sqlx::query_scalar(
+            "INSERT INTO levels (name, school_id) VALUES ('Grade 9', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let session_id: uuid::Uuid = sqlx::query_scalar(
+            r#"INSERT INTO academic_sessions (school_id, name, start_date, end_date)
+               VALUES ($1, '2026/2027', '2026-09-01', '2027-07-31') RETURNING id"#,
+        )
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let term_id: TermId = sqlx::query_scalar(
+            r#"INSERT INTO terms (academic_session_id, name, start_date, end_date)
+               VALUES ($1, 'First Term', '2026-09-01', '2026-12-15') RETURNING id"#,
+        )
+        .bind(session_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let subject_id: SubjectId = sqlx::query_scalar(
+            "INSERT INTO subjects (school_id, name) VALUES ($1, 'Mathematics') RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let admin_id: UserId = sqlx::query_scalar(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Exam', 'Officer', 'officer@example.com', $1) RETURNING id"#,
+        )
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        Fixture {
+            school_id,
+            level_id,
+            term_id,
+            subject_id,
+            admin_id,
+        }
+    }
+
+    async fn create_exam(pool: &PgPool, fixture: &Fixture, kind: &str) -> Assessment {
+        sqlx::query_as::<_, Assessment>(
+            r#"INSERT INTO assessments (school_id, term_id, level_id, subject_id, name, kind, max_score, weight)
+               VALUES ($1, $2, $3, $4, 'Final', $5, 100, 60)
+               RETURNING id, school_id, term_id, level_id, subject_id, name, kind, max_score,
+                         weight, due_date, created_by, finalized_at, finalized_by, created_at, updated_at"#,
+        )
+        .bind(fixture.school_id)
+        .bind(fixture.term_id)
+        .bind(fixture.level_id)
+        .bind(fixture.subject_id)
+        .bind(kind)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn create_student(
+        pool: &PgPool,
+        fixture: &Fixture,
+        name: &str,
+        branch_id: Option<BranchId>,
+    ) -> UserId {
+        let student_id: UserId = sqlx::query_scalar(
+            r#"INSERT INTO users (first_name, last_name, email, school_id, level_id, branch_id)
+               VALUES ($1, 'Student', $2, $3, $4, $5) RETURNING id"#,
+        )
+        .bind(name)
+        .bind(format!("{}@example.com", name.to_lowercase()))
+        .bind(fixture.school_id)
+        .bind(fixture.level_id)
+        .bind(branch_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(student_id)
+            .bind(system_roles::STUDENT)
+            .execute(pool)
+            .await
+            .unwrap();
+        student_id
+    }
+
+    async fn create_branch(pool: &PgPool, fixture: &Fixture, name: &str) -> BranchId {
+        sqlx::query_scalar("INSERT INTO branches (name, level_id) VALUES ($1, $2) RETURNING id")
+            .bind(name)
+            .bind(fixture.level_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    fn plan_dto(rooms: Vec<ExamRoomDto>, special: Vec<UserId>) -> GenerateSeatingPlanDto {
+        GenerateSeatingPlanDto {
+            rooms,
+            alternate_branches: true,
+            special_needs_student_ids: special,
+            branch_ids: Vec::new(),
+        }
+    }
+
+    async fn create_teacher(pool: &PgPool, fixture: &Fixture, name: &str) -> UserId {
+        let teacher_id: UserId = sqlx::query_scalar(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ($1, 'Teacher', $2, $3) RETURNING id"#,
+        )
+        .bind(name)
+        .bind(format!("{}@example.com", name.to_lowercase()))
+        .bind(fixture.school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(teacher_id)
+            .bind(system_roles::TEACHER)
+            .execute(pool)
+            .await
+            .unwrap();
+        teacher_id
+    }
+
+    fn sitting_dto(starts_at: &str, duration_minutes: i32) -> ScheduleSittingDto {
+        ScheduleSittingDto {
+            starts_at: starts_at.parse().unwrap(),
+            duration_minutes,
+        }
+    }
+
+    fn invigilator_dto(teacher_id: UserId, room_name: Option<&str>) -> AssignInvigilatorDto {
+        AssignInvigilatorDto {
+            teacher_id,
+            room_name: room_name.map(str::to_string),
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_generate_seating_plan_persists_and_replaces(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let exam = create_exam(&pool, &fixture, "exam").await;
+        let (a, b) = (
+            create_branch(&pool, &fixture, "A").await,
+            create_branch(&pool, &fixture, "B").await,
+        );
+        let mut students = Vec::new();
+        for (name, branch) in [("Ada", a), ("Bola", a), ("Chidi", b), ("Dayo", b)] {
+            students.push(create_student(&pool, &fixture, name, Some(branch)).await);
+        }
+
+        let plan = ExamService::generate_seating_plan(
+            &pool,
+            &exam,
+            fixture.admin_id,
+            plan_dto(
+                vec![room("Hall A", 3, vec![1]), room("Hall B", 3, vec![])],
+                vec![students[3]],
+            ),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(plan.rooms.len(), 2);
+        let hall_a = &plan.rooms[0];
+        assert_eq!(hall_a.seats.len(), 3);
+        assert_eq!(hall_a.seats[0].student_id, students[3]);
+        assert!(hall_a.seats[0].special_needs);
+        assert_ne!(hall_a.seats[1].branch_id, hall_a.seats[2].branch_id);
+        assert_eq!(plan.rooms[1].seats.len(), 1);
+
+        let (name, csv) = ExamService::room_list_csv(&pool, exam.id, hall_a.id)
+            .await
+            .unwrap();
+        assert_eq!(name, "Hall A");
+        assert!(csv.starts_with("seat,last_name,first_name,branch,special_needs\n"));
+        assert!(csv.contains("1,Student,Dayo,B,yes"));
+
+        let replaced = ExamService::generate_seating_plan(
+            &pool,
+            &exam,
+            fixture.admin_id,
+            plan_dto(vec![room("Library", 10, vec![])], vec![]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(replaced.rooms.len(), 1);
+        assert_eq!(replaced.rooms[0].seats.len(), 4);
+
+        let rooms: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM exam_rooms")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(rooms, 1);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_generate_seating_plan_rejects_impossible_plans(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let exam = create_exam(&pool, &fixture, "exam").await;
+        let quiz = create_exam(&pool, &fixture, "quiz").await;
+        let first = create_student(&pool, &fixture, "Ada", None).await;
+        let second = create_student(&pool, &fixture, "Bola", None).await;
+
+        let cases = [
+            (&quiz, plan_dto(vec![room("Hall A", 10, vec![])], vec![])),
+            (
+                &exam,
+                plan_dto(
+                    vec![room("Hall A", 5, vec![]), room("hall a", 5, vec![])],
+                    vec![],
+                ),
+            ),
+            (&exam, plan_dto(vec![room("Hall A", 1, vec![])], vec![])),
+            (
+                &exam,
+                plan_dto(vec![room("Hall A", 10, vec![1])], vec![first, second]),
+            ),
+            (
+                &exam,
+                plan_dto(vec![room("Hall A", 10, vec![1])], vec![UserId::new()]),
+            ),
+        ];
+
+        for (assessment, dto) in cases {
+            let err = ExamService::generate_seating_plan(&pool, assessment, fixture.admin_id, dto)
+                .await
+                .unwrap_err();
+            assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        }
+
+        let err = ExamService::get_seating_plan(&pool, exam.id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_generate_seating_plan_for_selected_branches(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let exam = create_exam(&pool, &fixture, "exam").await;
+        let (a, b) = (
+            create_branch(&pool, &fixture, "A").await,
+            create_branch(&pool, &fixture, "B").await,
+        );
+        let ada = create_student(&pool, &fixture, "Ada", Some(a)).await;
+        create_student(&pool, &fixture, "Bola", Some(b)).await;
+
+        let plan = ExamService::generate_seating_plan(
+            &pool,
+            &exam,
+            fixture.admin_id,
+            GenerateSeatingPlanDto {
+                branch_ids: vec![a],
+                ..plan_dto(vec![room("Hall A", 1, vec![])], vec![])
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(plan.branch_ids, Some(vec![a]));
+        assert_eq!(plan.rooms[0].seats.len(), 1);
+        assert_eq!(plan.rooms[0].seats[0].student_id, ada);
+
+        let err = ExamService::generate_seating_plan(
+            &pool,
+            &exam,
+            fixture.admin_id,
+            GenerateSeatingPlanDto {
+                branch_ids: vec![BranchId::new()],
+                ..plan_dto(vec![room("Hall A", 10, vec![])], vec![])
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_invigilators_cannot_clash(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let maths = create_exam(&pool, &fixture, "exam").await;
+        let english = create_exam(&pool, &fixture, "exam").await;
+        create_student(&pool, &fixture, "Ada", None).await;
+        let teacher = create_teacher(&pool, &fixture, "Tolu").await;
+
+        ExamService::generate_seating_plan(
+            &pool,
+            &maths,
+            fixture.admin_id,
+            plan_dto(vec![room("Hall A", 10, vec![])], vec![]),
+        )
+        .await
+        .unwrap();
+
+        let err = ExamService::assign_invigilator(
+            &pool,
+            &maths,
+            fixture.admin_id,
+            invigilator_dto(teacher, None),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let sitting = ExamService::schedule_sitting(
+            &pool,
+            &maths,
+            fixture.admin_id,
+            sitting_dto("2026-11-02T09:00:00Z", 120),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            sitting.ends_at,
+            "2026-11-02T11:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        ExamService::schedule_sitting(
+            &pool,
+            &english,
+            fixture.admin_id,
+            sitting_dto("2026-11-02T10:30:00Z", 60),
+        )
+        .await
+        .unwrap();
+
+        for dto in [
+            invigilator_dto(fixture.admin_id, None),
+            invigilator_dto(teacher, Some("Library")),
+        ] {
+            let err = ExamService::assign_invigilator(&pool, &maths, fixture.admin_id, dto)
+                .await
+                .unwrap_err();
+            assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        }
+
+        let sitting = ExamService::assign_invigilator(
+            &pool,
+            &maths,
+            fixture.admin_id,
+            invigilator_dto(teacher, Some("hall a")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(sitting.invigilators.len(), 1);
+        assert_eq!(sitting.invigilators[0].room_name.as_deref(), Some("Hall A"));
+
+        // Overlaps the maths sitting
+        let err = ExamService::assign_invigilator(
+            &pool,
+            &english,
+            fixture.admin_id,
+            invigilator_dto(teacher, None),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        ExamService::schedule_sitting(
+            &pool,
+            &english,
+            fixture.admin_id,
+            sitting_dto("2026-11-02T11:00:00Z", 60),
+        )
+        .await
+        .unwrap();
+        ExamService::assign_invigilator(
+            &pool,
+            &english,
+            fixture.admin_id,
+            invigilator_dto(teacher, None),
+        )
+        .await
+        .unwrap();
+
+        // Moving the maths sitting onto the english one clashes
+        let err = ExamService::schedule_sitting(
+            &pool,
+            &maths,
+            fixture.admin_id,
+            sitting_dto("2026-11-02T10:00:00Z", 90),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+        let unchanged = ExamService::get_sitting(&pool, maths.id).await.unwrap();
+        assert_eq!(unchanged.starts_at, sitting.starts_at);
+
+        // Approved leave covering another day of the week
+        sqlx::query(
+            r#"INSERT INTO staff_leave_requests (school_id, staff_id, leave_type, start_date, end_date, status)
+               VALUES ($1, $2, 'sick', '2026-11-04', '2026-11-05', 'approved')"#,
+        )
+        .bind(fixture.school_id)
+        .bind(teacher)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let err = ExamService::schedule_sitting(
+            &pool,
+            &maths,
+            fixture.admin_id,
+            sitting_dto("2026-11-05T09:00:00Z", 120),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        ExamService::remove_invigilator(&pool, maths.id, teacher)
+            .await
+            .unwrap();
+        let err = ExamService::remove_invigilator(&pool, maths.id, teacher)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_room_list_pdf_and_regenerated_rooms(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let exam = create_exam(&pool, &fixture, "exam").await;
+        create_student(&pool, &fixture, "Ada", None).await;
+        let teacher = create_teacher(&pool, &fixture, "Tolu").await;
+
+        let plan = ExamService::generate_seating_plan(
+            &pool,
+            &exam,
+            fixture.admin_id,
+            plan_dto(vec![room("Hall A", 10, vec![])], vec![]),
+        )
+        .await
+        .unwrap();
+
+        let (name, pdf) = ExamService::room_list_pdf(&pool, &exam, plan.rooms[0].id)
+            .await
+            .unwrap();
+        assert_eq!(name, "Hall A");
+        assert!(pdf.starts_with(b"%PDF"));
+        assert!(String::from_utf8_lossy(&pdf).contains("Not yet scheduled"));
+
+        ExamService::schedule_sitting(
+            &pool,
+            &exam,
+            fixture.admin_id,
+            sitting_dto("2026-11-02T09:00:00Z", 120),
+        )
+        .await
+        .unwrap();
+        ExamService::assign_invigilator(
+            &pool,
+            &exam,
+            fixture.admin_id,
+            invigilator_dto(teacher, Some("Hall A")),
+        )
+        .await
+        .unwrap();
+
+        let (_, pdf) = ExamService::room_list_pdf(&pool, &exam, plan.rooms[0].id)
+            .await
+            .unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains(r"Starts Monday 2 November 2026, 09:00 \(UTC\), 120 minutes"));
+        assert!(text.contains("Invigilators: Tolu Teacher"));
+
+        ExamService::generate_seating_plan(
+            &pool,
+            &exam,
+            fixture.admin_id,
+            plan_dto(vec![room("Library", 10, vec![])], vec![]),
+        )
+        .await
+        .unwrap();
+        let sitting = ExamService::get_sitting(&pool, exam.id).await.unwrap();
+        assert_eq!(sitting.invigilators[0].room_name, None);
+
+        ExamService::delete_sitting(&pool, exam.id).await.unwrap();
+        let err = ExamService::get_sitting(&pool, exam.id).await.unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,244 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::SchoolId;
+
+use crate::middleware::feature_flags::Flags;
+use crate::modules::feature_flags::model::{
+    CreateFeatureFlagDto, EffectiveFeatureFlags, FeatureFlag, FeatureFlagWithOverrides,
+    SchoolFeatureFlag, SetSchoolFeatureFlagDto, UpdateFeatureFlagDto,
+};
+use crate::modules::feature_flags::service::FeatureFlagService;
+use crate::state::AppState;
+use crate::validator::ValidatedJson;
+
+/// Your feature flags
+///
+/// Every flag and whether it is on for your school, so clients can hide
+/// disabled features.
+#[utoipa::path(
+    get,
+    path = "/api/feature-flags",
+    summary = "Get your feature flags",
+    responses(
+        (status = 200, description = "Flags in effect for your school", body = EffectiveFeatureFlags),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Feature Flags",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(flags))]
+pub async fn get_my_feature_flags(flags: Flags) -> Json<EffectiveFeatureFlags> {
+    Json(flags.0)
+}
+
+/// List feature flags
+///
+/// Every flag with its default and school overrides. System admins only.
+#[utoipa::path(
+    get,
+    path = "/api/admin/feature-flags",
+    summary = "List feature flags",
+    responses(
+        (status = 200, description = "Flags ordered by key", body = Vec<FeatureFlagWithOverrides>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only")
+    ),
+    tag = "Feature Flags",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_feature_flags(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<FeatureFlagWithOverrides>>, AppError> {
+    let flags = FeatureFlagService::list_flags(&state.db).await?;
+
+    Ok(Json(flags))
+}
+
+/// Create a feature flag
+#[utoipa::path(
+    post,
+    path = "/api/admin/feature-flags",
+    summary = "Create feature flag",
+    request_body = CreateFeatureFlagDto,
+    responses(
+        Created<FeatureFlag>,
+        (status = 400, description = "Invalid key or a flag with this key already exists"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only")
+    ),
+    tag = "Feature Flags",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_feature_flag(
+    State(state): State<AppState>,
+    ValidatedJson(dto): ValidatedJson<CreateFeatureFlagDto>,
+) -> Result<Created<FeatureFlag>, AppError> {
+    let flag = FeatureFlagService::create_flag(&state.db, state.cache.as_ref(), dto).await?;
+
+    Ok(Created(flag))
+}
+
+/// Get a feature flag with its school overrides
+#[utoipa::path(
+    get,
+    path = "/api/admin/feature-flags/{key}",
+    summary = "Get feature flag",
+    params(
+        ("key" = String, Path, description = "Flag key")
+    ),
+    responses(
+        (status = 200, description = "Feature flag", body = FeatureFlagWithOverrides),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only"),
+        (status = 404, description = "Feature flag not found")
+    ),
+    tag = "Feature Flags",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_feature_flag(
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+) -> Result<Json<FeatureFlagWithOverrides>, AppError> {
+    let flag = FeatureFlagService::get_flag(&state.db, &key).await?;
+
+    Ok(Json(flag))
+}
+
+/// Change a feature flag's default or description
+///
+/// Takes effect for every school without an override on its next request.
+#[utoipa::path(
+    put,
+    path = "/api/admin/feature-flags/{key}",
+    summary = "Update feature flag",
+    params(
+        ("key" = String, Path, description = "Flag key")
+    ),
+    request_body = UpdateFeatureFlagDto,
+    responses(
+        (status = 200, description = "Feature flag updated", body = FeatureFlag),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only"),
+        (status = 404, description = "Feature flag not found")
+    ),
+    tag = "Feature Flags",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_feature_flag(
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+    ValidatedJson(dto): ValidatedJson<UpdateFeatureFlagDto>,
+) -> Result<Json<FeatureFlag>, AppError> {
+    let flag = FeatureFlagService::update_flag(&state.db, state.cache.as_ref(), &key, dto).await?;
+
+    Ok(Json(flag))
+}
+
+/// Delete a feature flag and its school overrides
+#[utoipa::path(
+    delete,
+    path = "/api/admin/feature-flags/{key}",
+    summary = "Delete feature flag",
+    params(
+        ("key" = String, Path, description = "Flag key")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only"),
+        (status = 404, description = "Feature flag not found")
+    ),
+    tag = "Feature Flags",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_feature_flag(
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+) -> Result<NoContent, AppError> {
+    FeatureFlagService::delete_flag(&state.db, state.cache.as_ref(), &key).await?;
+
+    Ok(NoContent)
+}
+
+/// Turn a feature flag on or off for one school
+#[utoipa::path(
+    put,
+    path = "/api/admin/feature-flags/{key}/schools/{school_id}",
+    summary = "Set school override",
+    params(
+        ("key" = String, Path, description = "Flag key"),
+        ("school_id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = SetSchoolFeatureFlagDto,
+    responses(
+        (status = 200, description = "School override set", body = SchoolFeatureFlag),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only"),
+        (status = 404, description = "Feature flag or school not found")
+    ),
+    tag = "Feature Flags",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn set_school_feature_flag(
+    State(state): State<AppState>,
+    Path((key, school_id)): Path<(String, Uuid)>,
+    Json(dto): Json<SetSchoolFeatureFlagDto>,
+) -> Result<Json<SchoolFeatureFlag>, AppError> {
+    let school_override = FeatureFlagService::set_school_override(
+        &state.db,
+        state.cache.as_ref(),
+        &key,
+        SchoolId::from(school_id),
+        dto.enabled,
+    )
+    .await?;
+
+    Ok(Json(school_override))
+}
+
+/// Remove a school's override so the flag's default applies
+#[utoipa::path(
+    delete,
+    path = "/api/admin/feature-flags/{key}/schools/{school_id}",
+    summary = "Remove school override",
+    params(
+        ("key" = String, Path, description = "Flag key"),
+        ("school_id" = Uuid, Path, description = "School ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only"),
+        (status = 404, description = "The school has no override for this flag")
+    ),
+    tag = "Feature Flags",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_school_feature_flag(
+    State(state): State<AppState>,
+    Path((key, school_id)): Path<(String, Uuid)>,
+) -> Result<NoContent, AppError> {
+    FeatureFlagService::remove_school_override(
+        &state.db,
+        state.cache.as_ref(),
+        &key,
+        SchoolId::from(school_id),
+    )
+    .await?;
+
+    Ok(NoContent)
+}
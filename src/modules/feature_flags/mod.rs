@@ -0,0 +1,15 @@
+//! Feature flags module.
+//!
+//! Flags switch features on or off at runtime. Each flag has a default for
+//! every school, which system admins can override per school under
+//! `/api/admin/feature-flags`, for example to turn the attendance module on
+//! for a few schools first. Changes take effect on the next request.
+//!
+//! Handlers read flags through the [`Flags`](crate::middleware::feature_flags::Flags)
+//! extractor, and `GET /api/feature-flags` returns the caller's flags so
+//! clients can hide disabled features.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
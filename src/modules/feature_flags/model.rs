@@ -0,0 +1,7 @@
+//! Feature flag data models.
+//!
+//! This module re-exports feature flag models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all feature flag models from the shared crate
+pub use chalkbyte_models::feature_flags::*;
@@ -0,0 +1,35 @@
+use axum::{
+    Router,
+    routing::{get, put},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    create_feature_flag, delete_feature_flag, delete_school_feature_flag, get_feature_flag,
+    get_feature_flags, get_my_feature_flags, set_school_feature_flag, update_feature_flag,
+};
+
+/// Initialize the feature flag admin router
+/// Routes: GET /, POST /, GET /{key}, PUT /{key}, DELETE /{key},
+/// PUT/DELETE /{key}/schools/{school_id}
+pub fn init_feature_flags_admin_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_feature_flags).post(create_feature_flag))
+        .route(
+            "/{key}",
+            get(get_feature_flag)
+                .put(update_feature_flag)
+                .delete(delete_feature_flag),
+        )
+        .route(
+            "/{key}/schools/{school_id}",
+            put(set_school_feature_flag).delete(delete_school_feature_flag),
+        )
+}
+
+/// Initialize the router for the caller's own flags
+/// Routes: GET /
+pub fn init_feature_flags_router() -> Router<AppState> {
+    Router::new().route("/", get(get_my_feature_flags))
+}
@@ -0,0 +1,386 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tracing::{instrument, warn};
+
+use chalkbyte_cache::{RedisCache, keys};
+use chalkbyte_core::{AppError, ErrorCode};
+use chalkbyte_models::ids::SchoolId;
+
+use crate::modules::feature_flags::model::{
+    CreateFeatureFlagDto, EffectiveFeatureFlags, FeatureFlag, FeatureFlagWithOverrides,
+    SchoolFeatureFlag, UpdateFeatureFlagDto, is_valid_flag_key,
+};
+
+/// How long a school's flags are cached. Changes invalidate the cache, so
+/// this only bounds how long another instance's stale write could linger.
+const FLAGS_CACHE_TTL: Duration = Duration::from_secs(300);
+
+const FLAG_COLUMNS: &str = "key, description, enabled, created_at, updated_at";
+
+const OVERRIDE_COLUMNS: &str = "flag_key, school_id, enabled, updated_at";
+
+pub struct FeatureFlagService;
+
+impl FeatureFlagService {
+    async fn find_flag(db: &PgPool, key: &str) -> Result<FeatureFlag, AppError> {
+        sqlx::query_as::<_, FeatureFlag>(&format!(
+            "SELECT {FLAG_COLUMNS} FROM feature_flags WHERE key = $1"
+        ))
+        .bind(key)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::FeatureFlagNotFound))
+    }
+
+    async fn with_overrides(
+        db: &PgPool,
+        flag: FeatureFlag,
+    ) -> Result<FeatureFlagWithOverrides, AppError> {
+        let overrides = sqlx::query_as::<_, SchoolFeatureFlag>(&format!(
+            "SELECT {OVERRIDE_COLUMNS} FROM school_feature_flags
+             WHERE flag_key = $1
+             ORDER BY school_id"
+        ))
+        .bind(&flag.key)
+        .fetch_all(db)
+        .await?;
+
+        Ok(FeatureFlagWithOverrides { flag, overrides })
+    }
+
+    /// Get every flag with its school overrides, ordered by key.
+    #[instrument(skip(db))]
+    pub async fn list_flags(db: &PgPool) -> Result<Vec<FeatureFlagWithOverrides>, AppError> {
+        let flags = sqlx::query_as::<_, FeatureFlag>(&format!(
+            "SELECT {FLAG_COLUMNS} FROM feature_flags ORDER BY key"
+        ))
+        .fetch_all(db)
+        .await?;
+
+        let mut overrides = sqlx::query_as::<_, SchoolFeatureFlag>(&format!(
+            "SELECT {OVERRIDE_COLUMNS} FROM school_feature_flags ORDER BY flag_key, school_id"
+        ))
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .peekable();
+
+        Ok(flags
+            .into_iter()
+            .map(|flag| {
+                let mut flag_overrides = Vec::new();
+                while let Some(o) = overrides.next_if(|o| o.flag_key == flag.key) {
+                    flag_overrides.push(o);
+                }
+                FeatureFlagWithOverrides {
+                    flag,
+                    overrides: flag_overrides,
+                }
+            })
+            .collect())
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_flag(db: &PgPool, key: &str) -> Result<FeatureFlagWithOverrides, AppError> {
+        let flag = Self::find_flag(db, key).await?;
+        Self::with_overrides(db, flag).await
+    }
+
+    #[instrument(skip(db, cache, dto))]
+    pub async fn create_flag(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        dto: CreateFeatureFlagDto,
+    ) -> Result<FeatureFlag, AppError> {
+        if !is_valid_flag_key(&dto.key) {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Flag keys use lowercase letters, digits, and underscores and start with a letter"
+            )));
+        }
+
+        let flag = sqlx::query_as::<_, FeatureFlag>(&format!(
+            "INSERT INTO feature_flags (key, description, enabled)
+             VALUES ($1, $2, $3)
+             RETURNING {FLAG_COLUMNS}"
+        ))
+        .bind(&dto.key)
+        .bind(&dto.description)
+        .bind(dto.enabled)
+        .fetch_one(db)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                AppError::from_code(ErrorCode::FeatureFlagKeyConflict)
+            }
+            _ => AppError::from(e),
+        })?;
+
+        keys::invalidate::feature_flags(cache).await;
+
+        Ok(flag)
+    }
+
+    #[instrument(skip(db, cache, dto))]
+    pub async fn update_flag(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        key: &str,
+        dto: UpdateFeatureFlagDto,
+    ) -> Result<FeatureFlag, AppError> {
+        let flag = sqlx::query_as::<_, FeatureFlag>(&format!(
+            "UPDATE feature_flags
+             SET description = COALESCE($2, description),
+                 enabled = COALESCE($3, enabled)
+             WHERE key = $1
+             RETURNING {FLAG_COLUMNS}"
+        ))
+        .bind(key)
+        .bind(&dto.description)
+        .bind(dto.enabled)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::FeatureFlagNotFound))?;
+
+        keys::invalidate::feature_flags(cache).await;
+
+        Ok(flag)
+    }
+
+    /// Delete a flag and its overrides. Code that still checks the flag
+    /// sees it as off.
+    #[instrument(skip(db, cache))]
+    pub async fn delete_flag(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        key: &str,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM feature_flags WHERE key = $1")
+            .bind(key)
+            .execute(db)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::from_code(ErrorCode::FeatureFlagNotFound));
+        }
+
+        keys::invalidate::feature_flags(cache).await;
+
+        Ok(())
+    }
+
+    /// Turn a flag on or off for one school, whatever its default.
+    #[instrument(skip(db, cache))]
+    pub async fn set_school_override(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        key: &str,
+        school_id: SchoolId,
+        enabled: bool,
+    ) -> Result<SchoolFeatureFlag, AppError> {
+        let flag = Self::find_flag(db, key).await?;
+
+        let school_override = sqlx::query_as::<_, SchoolFeatureFlag>(&format!(
+            "INSERT INTO school_feature_flags (flag_key, school_id, enabled)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (flag_key, school_id) DO UPDATE SET enabled = EXCLUDED.enabled
+             RETURNING {OVERRIDE_COLUMNS}"
+        ))
+        .bind(&flag.key)
+        .bind(school_id)
+        .bind(enabled)
+        .fetch_one(db)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                AppError::from_code(ErrorCode::SchoolNotFound)
+            }
+            _ => AppError::from(e),
+        })?;
+
+        keys::invalidate::feature_flags(cache).await;
+
+        Ok(school_override)
+    }
+
+    /// Remove a school's override so the flag's default applies again.
+    #[instrument(skip(db, cache))]
+    pub async fn remove_school_override(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        key: &str,
+        school_id: SchoolId,
+    ) -> Result<(), AppError> {
+        let result =
+            sqlx::query("DELETE FROM school_feature_flags WHERE flag_key = $1 AND school_id = $2")
+                .bind(key)
+                .bind(school_id)
+                .execute(db)
+                .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "This school has no override for the flag"
+            )));
+        }
+
+        keys::invalidate::feature_flags(cache).await;
+
+        Ok(())
+    }
+
+    /// The flags in effect for a school, or every flag's default when
+    /// `school_id` is `None`. Cached when a cache is connected.
+    #[instrument(skip(db, cache))]
+    pub async fn effective_flags(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: Option<SchoolId>,
+    ) -> Result<EffectiveFeatureFlags, AppError> {
+        let cache_key = keys::feature_flags::effective(school_id.map(SchoolId::into_inner));
+
+        if let Some(cache) = cache
+            && let Some(flags) = cache.get::<EffectiveFeatureFlags>(&cache_key).await
+        {
+            return Ok(flags);
+        }
+
+        let rows: Vec<(String, bool)> = sqlx::query_as(
+            "SELECT f.key, COALESCE(s.enabled, f.enabled)
+             FROM feature_flags f
+             LEFT JOIN school_feature_flags s ON s.flag_key = f.key AND s.school_id = $1",
+        )
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        let flags = EffectiveFeatureFlags {
+            flags: rows.into_iter().collect(),
+        };
+
+        if let Some(cache) = cache
+            && let Err(e) = cache
+                .set_with_ttl(&cache_key, &flags, FLAGS_CACHE_TTL)
+                .await
+        {
+            warn!(error = %e, "Failed to cache feature flags");
+        }
+
+        Ok(flags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    fn flag_dto(key: &str, enabled: bool) -> CreateFeatureFlagDto {
+        CreateFeatureFlagDto {
+            key: key.to_string(),
+            description: None,
+            enabled,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_school_override_wins_over_default(pool: PgPool) {
+        let pilot = create_test_school(&pool).await;
+        let other = create_test_school(&pool).await;
+        FeatureFlagService::create_flag(&pool, None, flag_dto("new_gradebook", false))
+            .await
+            .unwrap();
+
+        FeatureFlagService::set_school_override(&pool, None, "new_gradebook", pilot, true)
+            .await
+            .unwrap();
+
+        let pilot_flags = FeatureFlagService::effective_flags(&pool, None, Some(pilot))
+            .await
+            .unwrap();
+        let other_flags = FeatureFlagService::effective_flags(&pool, None, Some(other))
+            .await
+            .unwrap();
+        let defaults = FeatureFlagService::effective_flags(&pool, None, None)
+            .await
+            .unwrap();
+
+        assert!(pilot_flags.is_enabled("new_gradebook"));
+        assert!(!other_flags.is_enabled("new_gradebook"));
+        assert!(!defaults.is_enabled("new_gradebook"));
+        assert!(defaults.is_enabled("attendance"));
+
+        FeatureFlagService::remove_school_override(&pool, None, "new_gradebook", pilot)
+            .await
+            .unwrap();
+        let pilot_flags = FeatureFlagService::effective_flags(&pool, None, Some(pilot))
+            .await
+            .unwrap();
+        assert!(!pilot_flags.is_enabled("new_gradebook"));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_list_groups_overrides_by_flag(pool: PgPool) {
+        let school = create_test_school(&pool).await;
+        FeatureFlagService::create_flag(&pool, None, flag_dto("library", true))
+            .await
+            .unwrap();
+        FeatureFlagService::set_school_override(&pool, None, "library", school, false)
+            .await
+            .unwrap();
+
+        let flags = FeatureFlagService::list_flags(&pool).await.unwrap();
+        let keys: Vec<&str> = flags.iter().map(|f| f.flag.key.as_str()).collect();
+        assert_eq!(keys, ["attendance", "library"]);
+        assert!(flags[0].overrides.is_empty());
+        assert_eq!(flags[1].overrides.len(), 1);
+        assert!(!flags[1].overrides[0].enabled);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_invalid_and_duplicate_keys_are_rejected(pool: PgPool) {
+        let err = FeatureFlagService::create_flag(&pool, None, flag_dto("New-Gradebook", true))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let err = FeatureFlagService::create_flag(&pool, None, flag_dto("attendance", true))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::FeatureFlagKeyConflict);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_override_for_unknown_school_or_flag(pool: PgPool) {
+        let school = create_test_school(&pool).await;
+
+        let err = FeatureFlagService::set_school_override(&pool, None, "missing", school, true)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::FeatureFlagNotFound);
+
+        let err = FeatureFlagService::set_school_override(
+            &pool,
+            None,
+            "attendance",
+            SchoolId::new(),
+            true,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code, ErrorCode::SchoolNotFound);
+    }
+}
@@ -0,0 +1,500 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use chrono::Utc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::{SchoolGroupId, SchoolId, UserId};
+
+use crate::middleware::auth::{
+    RequireGroupsCreate, RequireGroupsDelete, RequireGroupsRead, RequireGroupsReports,
+    RequireGroupsUpdate, RequireUsersCreate, RequireUsersRead,
+};
+use crate::middleware::role::is_system_admin_jwt;
+use crate::modules::groups::model::{
+    AddGroupSchoolDto, AssignGroupAdminDto, CreateSchoolGroupDto, GroupAdmin, GroupReport,
+    GroupReportParams, GroupSchool, GroupUserFilterParams, PaginatedGroupUsersResponse,
+    SchoolGroup, UpdateSchoolGroupDto,
+};
+use crate::modules::groups::service::GroupService;
+use crate::modules::users::model::{CreateUserDto, User};
+use crate::modules::users::service::UserService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::verify_group_access;
+use crate::validator::ValidatedJson;
+
+/// Create a school group
+#[utoipa::path(
+    post,
+    path = "/api/groups",
+    summary = "Create school group",
+    request_body = CreateSchoolGroupDto,
+    responses(
+        Created<SchoolGroup>,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires groups:create permission"),
+        (status = 422, description = "Invalid input or name already in use")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_group(
+    State(state): State<AppState>,
+    RequireGroupsCreate(_auth_user): RequireGroupsCreate,
+    ValidatedJson(dto): ValidatedJson<CreateSchoolGroupDto>,
+) -> Result<Created<SchoolGroup>, AppError> {
+    let group = GroupService::create_group(&state.db, dto).await?;
+
+    Ok(Created(group))
+}
+
+/// List school groups
+///
+/// System admins see every group; group admins see their own.
+#[utoipa::path(
+    get,
+    path = "/api/groups",
+    summary = "List school groups",
+    responses(
+        (status = 200, description = "School groups by name", body = Vec<SchoolGroup>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires groups:read permission")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn list_groups(
+    State(state): State<AppState>,
+    RequireGroupsRead(auth_user): RequireGroupsRead,
+) -> Result<Json<Vec<SchoolGroup>>, AppError> {
+    let groups = if is_system_admin_jwt(&auth_user) {
+        GroupService::list_groups(&state.db, None).await?
+    } else {
+        let group_id = auth_user.group_id().ok_or_else(|| {
+            AppError::forbidden("User must be assigned to a school group".to_string())
+        })?;
+        GroupService::list_groups(&state.db, Some(group_id)).await?
+    };
+
+    Ok(Json(groups))
+}
+
+/// Get a school group by ID
+#[utoipa::path(
+    get,
+    path = "/api/groups/{id}",
+    summary = "Get school group",
+    params(
+        ("id" = Uuid, Path, description = "School group ID")
+    ),
+    responses(
+        (status = 200, description = "School group details", body = SchoolGroup),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires groups:read permission and access to the group"),
+        (status = 404, description = "School group not found")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_group(
+    State(state): State<AppState>,
+    RequireGroupsRead(auth_user): RequireGroupsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SchoolGroup>, AppError> {
+    let group_id = SchoolGroupId::from(id);
+    verify_group_access(&auth_user, group_id)?;
+
+    let group = GroupService::get_group(&state.db, group_id).await?;
+
+    Ok(Json(group))
+}
+
+/// Update a school group
+#[utoipa::path(
+    put,
+    path = "/api/groups/{id}",
+    summary = "Update school group",
+    params(
+        ("id" = Uuid, Path, description = "School group ID")
+    ),
+    request_body = UpdateSchoolGroupDto,
+    responses(
+        (status = 200, description = "School group updated", body = SchoolGroup),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires groups:update permission"),
+        (status = 404, description = "School group not found"),
+        (status = 422, description = "Invalid input or name already in use")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_group(
+    State(state): State<AppState>,
+    RequireGroupsUpdate(auth_user): RequireGroupsUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateSchoolGroupDto>,
+) -> Result<Json<SchoolGroup>, AppError> {
+    let group_id = SchoolGroupId::from(id);
+    verify_group_access(&auth_user, group_id)?;
+
+    let group = GroupService::update_group(&state.db, group_id, dto).await?;
+
+    Ok(Json(group))
+}
+
+/// Delete a school group
+///
+/// Member schools are kept without a group, and the group's admins lose the
+/// Group Admin role.
+#[utoipa::path(
+    delete,
+    path = "/api/groups/{id}",
+    summary = "Delete school group",
+    params(
+        ("id" = Uuid, Path, description = "School group ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires groups:delete permission"),
+        (status = 404, description = "School group not found")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_group(
+    State(state): State<AppState>,
+    RequireGroupsDelete(auth_user): RequireGroupsDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let group_id = SchoolGroupId::from(id);
+    verify_group_access(&auth_user, group_id)?;
+
+    GroupService::delete_group(&state.db, group_id).await?;
+
+    Ok(NoContent)
+}
+
+/// List a group's member schools
+#[utoipa::path(
+    get,
+    path = "/api/groups/{id}/schools",
+    summary = "List group schools",
+    params(
+        ("id" = Uuid, Path, description = "School group ID")
+    ),
+    responses(
+        (status = 200, description = "Member schools by name", body = Vec<GroupSchool>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires groups:read permission and access to the group"),
+        (status = 404, description = "School group not found")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn list_group_schools(
+    State(state): State<AppState>,
+    RequireGroupsRead(auth_user): RequireGroupsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<GroupSchool>>, AppError> {
+    let group_id = SchoolGroupId::from(id);
+    verify_group_access(&auth_user, group_id)?;
+
+    let schools = GroupService::list_schools(&state.db, group_id).await?;
+
+    Ok(Json(schools))
+}
+
+/// Add a school to a group
+///
+/// A school that belongs to another group is moved to this one.
+#[utoipa::path(
+    post,
+    path = "/api/groups/{id}/schools",
+    summary = "Add group school",
+    params(
+        ("id" = Uuid, Path, description = "School group ID")
+    ),
+    request_body = AddGroupSchoolDto,
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires groups:update permission"),
+        (status = 404, description = "School group or school not found")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn add_group_school(
+    State(state): State<AppState>,
+    RequireGroupsUpdate(auth_user): RequireGroupsUpdate,
+    Path(id): Path<Uuid>,
+    Json(dto): Json<AddGroupSchoolDto>,
+) -> Result<NoContent, AppError> {
+    let group_id = SchoolGroupId::from(id);
+    verify_group_access(&auth_user, group_id)?;
+
+    GroupService::add_school(&state.db, group_id, dto.school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Remove a school from a group
+#[utoipa::path(
+    delete,
+    path = "/api/groups/{id}/schools/{school_id}",
+    summary = "Remove group school",
+    params(
+        ("id" = Uuid, Path, description = "School group ID"),
+        ("school_id" = Uuid, Path, description = "School ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires groups:update permission"),
+        (status = 404, description = "School is not a member of the group")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn remove_group_school(
+    State(state): State<AppState>,
+    RequireGroupsUpdate(auth_user): RequireGroupsUpdate,
+    Path((id, school_id)): Path<(Uuid, Uuid)>,
+) -> Result<NoContent, AppError> {
+    let group_id = SchoolGroupId::from(id);
+    verify_group_access(&auth_user, group_id)?;
+
+    GroupService::remove_school(&state.db, group_id, SchoolId::from(school_id)).await?;
+
+    Ok(NoContent)
+}
+
+/// List a group's admins
+#[utoipa::path(
+    get,
+    path = "/api/groups/{id}/admins",
+    summary = "List group admins",
+    params(
+        ("id" = Uuid, Path, description = "School group ID")
+    ),
+    responses(
+        (status = 200, description = "Group admins by name", body = Vec<GroupAdmin>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires groups:read permission and access to the group"),
+        (status = 404, description = "School group not found")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn list_group_admins(
+    State(state): State<AppState>,
+    RequireGroupsRead(auth_user): RequireGroupsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<GroupAdmin>>, AppError> {
+    let group_id = SchoolGroupId::from(id);
+    verify_group_access(&auth_user, group_id)?;
+
+    let admins = GroupService::list_admins(&state.db, group_id).await?;
+
+    Ok(Json(admins))
+}
+
+/// Make a user an admin of a group
+///
+/// The user must not belong to a school. The group is added to their token
+/// at their next login.
+#[utoipa::path(
+    post,
+    path = "/api/groups/{id}/admins",
+    summary = "Assign group admin",
+    params(
+        ("id" = Uuid, Path, description = "School group ID")
+    ),
+    request_body = AssignGroupAdminDto,
+    responses(
+        (status = 200, description = "User is now a group admin", body = GroupAdmin),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires groups:update permission"),
+        (status = 404, description = "School group or user not found"),
+        (status = 422, description = "User belongs to a school")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn assign_group_admin(
+    State(state): State<AppState>,
+    RequireGroupsUpdate(auth_user): RequireGroupsUpdate,
+    Path(id): Path<Uuid>,
+    Json(dto): Json<AssignGroupAdminDto>,
+) -> Result<Json<GroupAdmin>, AppError> {
+    let group_id = SchoolGroupId::from(id);
+    verify_group_access(&auth_user, group_id)?;
+
+    let admin = GroupService::assign_admin(&state.db, group_id, dto.user_id).await?;
+
+    Ok(Json(admin))
+}
+
+/// Remove a user as admin of a group
+#[utoipa::path(
+    delete,
+    path = "/api/groups/{id}/admins/{user_id}",
+    summary = "Remove group admin",
+    params(
+        ("id" = Uuid, Path, description = "School group ID"),
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires groups:update permission"),
+        (status = 404, description = "User is not an admin of the group")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn remove_group_admin(
+    State(state): State<AppState>,
+    RequireGroupsUpdate(auth_user): RequireGroupsUpdate,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<NoContent, AppError> {
+    let group_id = SchoolGroupId::from(id);
+    verify_group_access(&auth_user, group_id)?;
+
+    GroupService::remove_admin(&state.db, group_id, UserId::from(user_id)).await?;
+
+    Ok(NoContent)
+}
+
+/// List users across a group's member schools
+#[utoipa::path(
+    get,
+    path = "/api/groups/{id}/users",
+    summary = "List group users",
+    params(
+        ("id" = Uuid, Path, description = "School group ID"),
+        GroupUserFilterParams
+    ),
+    responses(
+        (status = 200, description = "Users of the member schools", body = PaginatedGroupUsersResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires users:read permission and access to the group")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn list_group_users(
+    State(state): State<AppState>,
+    RequireUsersRead(auth_user, scope): RequireUsersRead,
+    Path(id): Path<Uuid>,
+    Query(filters): Query<GroupUserFilterParams>,
+) -> Result<Json<PaginatedGroupUsersResponse>, AppError> {
+    scope.ensure_school()?;
+
+    let group_id = SchoolGroupId::from(id);
+    verify_group_access(&auth_user, group_id)?;
+
+    let users = GroupService::list_users(&state.db, group_id, filters).await?;
+
+    Ok(Json(users))
+}
+
+/// Create a user in one of a group's member schools
+///
+/// `school_id` is required and must be a member school. System and group
+/// admin roles cannot be given; custom roles must belong to that school.
+#[utoipa::path(
+    post,
+    path = "/api/groups/{id}/users",
+    summary = "Create group user",
+    params(
+        ("id" = Uuid, Path, description = "School group ID")
+    ),
+    request_body = CreateUserDto,
+    responses(
+        Created<User>,
+        (status = 400, description = "Missing school_id"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires users:create permission and access to the group, or an admin role was requested"),
+        (status = 422, description = "Invalid input, school not in the group, or role not allowed")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn create_group_user(
+    State(state): State<AppState>,
+    RequireUsersCreate(auth_user): RequireUsersCreate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<CreateUserDto>,
+) -> Result<Created<User>, AppError> {
+    let group_id = SchoolGroupId::from(id);
+    verify_group_access(&auth_user, group_id)?;
+
+    let school_id = dto.school_id.ok_or_else(|| {
+        AppError::bad_request(anyhow::anyhow!("school_id is required for group users"))
+    })?;
+    if !GroupService::is_member_school(&state.db, group_id, school_id).await? {
+        return Err(AppError::unprocessable(anyhow::anyhow!(
+            "School is not a member of this group"
+        )));
+    }
+    GroupService::validate_member_user_roles(&state.db, school_id, &dto.role_ids).await?;
+
+    let user =
+        UserService::create_user(&state.db, dto, state.cache.as_ref(), &state.password_policy)
+            .await?;
+
+    Ok(Created(user))
+}
+
+/// Get the aggregate report of a group's member schools
+#[utoipa::path(
+    get,
+    path = "/api/groups/{id}/reports/summary",
+    summary = "Group summary report",
+    params(
+        ("id" = Uuid, Path, description = "School group ID"),
+        GroupReportParams
+    ),
+    responses(
+        (status = 200, description = "Totals and per-school figures for the window", body = GroupReport),
+        (status = 400, description = "from is after to"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires groups:reports permission and access to the group"),
+        (status = 404, description = "School group not found")
+    ),
+    tag = "School Groups",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_group_report(
+    State(state): State<AppState>,
+    RequireGroupsReports(auth_user): RequireGroupsReports,
+    Path(id): Path<Uuid>,
+    Query(params): Query<GroupReportParams>,
+) -> Result<Json<GroupReport>, AppError> {
+    let group_id = SchoolGroupId::from(id);
+    verify_group_access(&auth_user, group_id)?;
+
+    let (from, to) = params.window(Utc::now().date_naive());
+    let report = GroupService::get_report(&state.db, group_id, from, to).await?;
+
+    Ok(Json(report))
+}
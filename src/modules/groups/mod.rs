@@ -0,0 +1,11 @@
+//! School groups module.
+//!
+//! An organization layer above schools, such as a district or a chain.
+//! System admins create groups, add schools to them, and assign group
+//! admins. A group admin has no school of their own: they see their group,
+//! its aggregate reports, and manage users across its member schools.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
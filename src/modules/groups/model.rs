@@ -0,0 +1,7 @@
+//! School group data models and DTOs.
+//!
+//! This module re-exports school group models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all school group models from the shared crate
+pub use chalkbyte_models::groups::*;
@@ -0,0 +1,33 @@
+use axum::{
+    Router,
+    routing::{delete, get},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    add_group_school, assign_group_admin, create_group, create_group_user, delete_group, get_group,
+    get_group_report, list_group_admins, list_group_schools, list_group_users, list_groups,
+    remove_group_admin, remove_group_school, update_group,
+};
+
+pub fn init_groups_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_groups).post(create_group))
+        .route(
+            "/{id}",
+            get(get_group).put(update_group).delete(delete_group),
+        )
+        .route(
+            "/{id}/schools",
+            get(list_group_schools).post(add_group_school),
+        )
+        .route("/{id}/schools/{school_id}", delete(remove_group_school))
+        .route(
+            "/{id}/admins",
+            get(list_group_admins).post(assign_group_admin),
+        )
+        .route("/{id}/admins/{user_id}", delete(remove_group_admin))
+        .route("/{id}/users", get(list_group_users).post(create_group_user))
+        .route("/{id}/reports/summary", get(get_group_report))
+}
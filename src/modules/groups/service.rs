@@ -0,0 +1,691 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, ErrorCode, PaginationMeta};
+use chalkbyte_models::ids::{RoleId, SchoolGroupId, SchoolId, UserId};
+
+use crate::modules::groups::model::{
+    CreateSchoolGroupDto, GroupAdmin, GroupReport, GroupSchool, GroupSchoolReport, GroupUser,
+    GroupUserFilterParams, PaginatedGroupUsersResponse, SchoolGroup, UpdateSchoolGroupDto,
+};
+use crate::modules::users::model::system_roles;
+
+const GROUP_COLUMNS: &str = r#"g.id, g.name, g.description,
+    (SELECT COUNT(*) FROM schools s WHERE s.group_id = g.id) AS school_count,
+    g.created_at, g.updated_at"#;
+
+/// Map a unique violation on the group name to a validation error.
+fn map_name_conflict(e: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e
+        && db_err.is_unique_violation()
+    {
+        return AppError::unprocessable(anyhow::anyhow!(
+            "A school group with this name already exists"
+        ));
+    }
+    e.into()
+}
+
+pub struct GroupService;
+
+impl GroupService {
+    #[instrument(skip(db, dto), fields(group.name = %dto.name))]
+    pub async fn create_group(
+        db: &PgPool,
+        dto: CreateSchoolGroupDto,
+    ) -> Result<SchoolGroup, AppError> {
+        let id = sqlx::query_scalar::<_, SchoolGroupId>(
+            "INSERT INTO school_groups (name, description) VALUES ($1, $2) RETURNING id",
+        )
+        .bind(dto.name.trim())
+        .bind(&dto.description)
+        .fetch_one(db)
+        .await
+        .map_err(map_name_conflict)?;
+
+        Self::get_group(db, id).await
+    }
+
+    /// List all groups, or only `group_id` when given.
+    #[instrument(skip(db))]
+    pub async fn list_groups(
+        db: &PgPool,
+        group_id: Option<SchoolGroupId>,
+    ) -> Result<Vec<SchoolGroup>, AppError> {
+        let groups = sqlx::query_as::<_, SchoolGroup>(&format!(
+            "SELECT {GROUP_COLUMNS} FROM school_groups g
+             WHERE ($1::uuid IS NULL OR g.id = $1)
+             ORDER BY g.name"
+        ))
+        .bind(group_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(groups)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_group(db: &PgPool, group_id: SchoolGroupId) -> Result<SchoolGroup, AppError> {
+        sqlx::query_as::<_, SchoolGroup>(&format!(
+            "SELECT {GROUP_COLUMNS} FROM school_groups g WHERE g.id = $1"
+        ))
+        .bind(group_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("School group not found")))
+    }
+
+    #[instrument(skip(db, dto))]
+    pub async fn update_group(
+        db: &PgPool,
+        group_id: SchoolGroupId,
+        dto: UpdateSchoolGroupDto,
+    ) -> Result<SchoolGroup, AppError> {
+        let updated = sqlx::query(
+            "UPDATE school_groups
+             SET name = COALESCE($2, name), description = COALESCE($3, description)
+             WHERE id = $1",
+        )
+        .bind(group_id)
+        .bind(dto.name.as_deref().map(str::trim))
+        .bind(&dto.description)
+        .execute(db)
+        .await
+        .map_err(map_name_conflict)?;
+
+        if updated.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "School group not found"
+            )));
+        }
+
+        Self::get_group(db, group_id).await
+    }
+
+    /// Delete a group. Its schools stay, without a group, and its admins
+    /// lose the Group Admin role.
+    #[instrument(skip(db))]
+    pub async fn delete_group(db: &PgPool, group_id: SchoolGroupId) -> Result<(), AppError> {
+        let mut tx = db.begin().await?;
+
+        sqlx::query(
+            "DELETE FROM user_roles
+             WHERE role_id = $2 AND user_id IN (SELECT id FROM users WHERE group_id = $1)",
+        )
+        .bind(group_id)
+        .bind(system_roles::GROUP_ADMIN)
+        .execute(&mut *tx)
+        .await?;
+
+        let deleted = sqlx::query("DELETE FROM school_groups WHERE id = $1")
+            .bind(group_id)
+            .execute(&mut *tx)
+            .await?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "School group not found"
+            )));
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[instrument(skip(db))]
+    pub async fn list_schools(
+        db: &PgPool,
+        group_id: SchoolGroupId,
+    ) -> Result<Vec<GroupSchool>, AppError> {
+        Self::get_group(db, group_id).await?;
+
+        let schools = sqlx::query_as::<_, GroupSchool>(
+            r#"SELECT s.id, s.name, s.address,
+                      (SELECT COUNT(*) FROM users u
+                       JOIN user_roles ur ON ur.user_id = u.id
+                       WHERE u.school_id = s.id AND ur.role_id = $2) AS student_count,
+                      s.created_at
+               FROM schools s
+               WHERE s.group_id = $1
+               ORDER BY s.name"#,
+        )
+        .bind(group_id)
+        .bind(system_roles::STUDENT)
+        .fetch_all(db)
+        .await?;
+
+        Ok(schools)
+    }
+
+    /// Add a school to a group, moving it out of any other group.
+    #[instrument(skip(db))]
+    pub async fn add_school(
+        db: &PgPool,
+        group_id: SchoolGroupId,
+        school_id: SchoolId,
+    ) -> Result<(), AppError> {
+        Self::get_group(db, group_id).await?;
+
+        let updated = sqlx::query("UPDATE schools SET group_id = $1 WHERE id = $2")
+            .bind(group_id)
+            .bind(school_id)
+            .execute(db)
+            .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(AppError::from_code(ErrorCode::SchoolNotFound));
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip(db))]
+    pub async fn remove_school(
+        db: &PgPool,
+        group_id: SchoolGroupId,
+        school_id: SchoolId,
+    ) -> Result<(), AppError> {
+        let updated =
+            sqlx::query("UPDATE schools SET group_id = NULL WHERE id = $1 AND group_id = $2")
+                .bind(school_id)
+                .bind(group_id)
+                .execute(db)
+                .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "School is not a member of this group"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Whether a school is a member of a group.
+    pub async fn is_member_school(
+        db: &PgPool,
+        group_id: SchoolGroupId,
+        school_id: SchoolId,
+    ) -> Result<bool, AppError> {
+        let is_member = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM schools WHERE id = $1 AND group_id = $2)",
+        )
+        .bind(school_id)
+        .bind(group_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(is_member)
+    }
+
+    /// IDs of a group's member schools.
+    pub async fn member_school_ids(
+        db: &PgPool,
+        group_id: SchoolGroupId,
+    ) -> Result<Vec<SchoolId>, AppError> {
+        let school_ids =
+            sqlx::query_scalar::<_, SchoolId>("SELECT id FROM schools WHERE group_id = $1")
+                .bind(group_id)
+                .fetch_all(db)
+                .await?;
+
+        Ok(school_ids)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn list_admins(
+        db: &PgPool,
+        group_id: SchoolGroupId,
+    ) -> Result<Vec<GroupAdmin>, AppError> {
+        Self::get_group(db, group_id).await?;
+
+        let admins = sqlx::query_as::<_, GroupAdmin>(
+            r#"SELECT u.id, u.first_name, u.last_name, u.email
+               FROM users u
+               JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $2
+               WHERE u.group_id = $1
+               ORDER BY u.last_name, u.first_name"#,
+        )
+        .bind(group_id)
+        .bind(system_roles::GROUP_ADMIN)
+        .fetch_all(db)
+        .await?;
+
+        Ok(admins)
+    }
+
+    /// Make a user an admin of a group. The user must not belong to a
+    /// school; a user who administered another group moves to this one.
+    #[instrument(skip(db))]
+    pub async fn assign_admin(
+        db: &PgPool,
+        group_id: SchoolGroupId,
+        user_id: UserId,
+    ) -> Result<GroupAdmin, AppError> {
+        Self::get_group(db, group_id).await?;
+
+        let school_id =
+            sqlx::query_scalar::<_, Option<SchoolId>>("SELECT school_id FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(db)
+                .await?
+                .ok_or_else(|| AppError::from_code(ErrorCode::UserNotFound))?;
+
+        if school_id.is_some() {
+            return Err(AppError::unprocessable(anyhow::anyhow!(
+                "Users who belong to a school cannot be group admins"
+            )));
+        }
+
+        let mut tx = db.begin().await?;
+
+        sqlx::query("UPDATE users SET group_id = $1 WHERE id = $2")
+            .bind(group_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        )
+        .bind(user_id)
+        .bind(system_roles::GROUP_ADMIN)
+        .execute(&mut *tx)
+        .await?;
+
+        let admin = sqlx::query_as::<_, GroupAdmin>(
+            "SELECT id, first_name, last_name, email FROM users WHERE id = $1",
+        )
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(admin)
+    }
+
+    /// Remove a user as admin of a group, taking away the Group Admin role.
+    #[instrument(skip(db))]
+    pub async fn remove_admin(
+        db: &PgPool,
+        group_id: SchoolGroupId,
+        user_id: UserId,
+    ) -> Result<(), AppError> {
+        let mut tx = db.begin().await?;
+
+        let updated =
+            sqlx::query("UPDATE users SET group_id = NULL WHERE id = $1 AND group_id = $2")
+                .bind(user_id)
+                .bind(group_id)
+                .execute(&mut *tx)
+                .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "User is not an admin of this group"
+            )));
+        }
+
+        sqlx::query("DELETE FROM user_roles WHERE user_id = $1 AND role_id = $2")
+            .bind(user_id)
+            .bind(system_roles::GROUP_ADMIN)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Get paginated users across a group's member schools.
+    #[instrument(skip(db))]
+    pub async fn list_users(
+        db: &PgPool,
+        group_id: SchoolGroupId,
+        filters: GroupUserFilterParams,
+    ) -> Result<PaginatedGroupUsersResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+        let search = filters
+            .search
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| format!("%{s}%"));
+
+        let where_clause = r#"WHERE s.group_id = $1
+              AND ($2::uuid IS NULL OR u.school_id = $2)
+              AND ($3::uuid IS NULL OR EXISTS (
+                  SELECT 1 FROM user_roles ur WHERE ur.user_id = u.id AND ur.role_id = $3
+              ))
+              AND ($4::text IS NULL OR u.first_name ILIKE $4 OR u.last_name ILIKE $4
+                   OR u.email ILIKE $4)"#;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM users u JOIN schools s ON s.id = u.school_id {where_clause}"
+        ))
+        .bind(group_id)
+        .bind(filters.school_id)
+        .bind(filters.role_id)
+        .bind(&search)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, GroupUser>(&format!(
+            r#"SELECT u.id, u.first_name, u.last_name, u.email, u.school_id,
+                      s.name AS school_name, u.created_at
+               FROM users u
+               JOIN schools s ON s.id = u.school_id
+               {where_clause}
+               ORDER BY s.name, u.last_name, u.first_name
+               LIMIT $5 OFFSET $6"#
+        ))
+        .bind(group_id)
+        .bind(filters.school_id)
+        .bind(filters.role_id)
+        .bind(&search)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedGroupUsersResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Check that roles may be given to a new user of `school_id` by a group
+    /// admin: system and group admin roles never, custom roles only when
+    /// they belong to that school.
+    pub async fn validate_member_user_roles(
+        db: &PgPool,
+        school_id: SchoolId,
+        role_ids: &[RoleId],
+    ) -> Result<(), AppError> {
+        if role_ids
+            .iter()
+            .any(|r| *r == system_roles::SYSTEM_ADMIN || *r == system_roles::GROUP_ADMIN)
+        {
+            return Err(AppError::forbidden(
+                "Group admins cannot create system or group admins".to_string(),
+            ));
+        }
+
+        let allowed = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM roles
+             WHERE id = ANY($1) AND (is_system_role OR school_id = $2)",
+        )
+        .bind(role_ids)
+        .bind(school_id)
+        .fetch_one(db)
+        .await?;
+
+        if allowed as usize != role_ids.len() {
+            return Err(AppError::unprocessable(anyhow::anyhow!(
+                "Roles must be built-in roles or belong to the user's school"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Build the aggregate report of a group's member schools over a window.
+    #[instrument(skip(db))]
+    pub async fn get_report(
+        db: &PgPool,
+        group_id: SchoolGroupId,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<GroupReport, AppError> {
+        if from > to {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "from must be on or before to"
+            )));
+        }
+
+        Self::get_group(db, group_id).await?;
+
+        let schools = sqlx::query_as::<_, GroupSchoolReport>(
+            r#"SELECT s.id AS school_id, s.name AS school_name,
+                      (SELECT COUNT(*) FROM users u JOIN user_roles ur ON ur.user_id = u.id
+                       WHERE u.school_id = s.id AND ur.role_id = $2) AS students,
+                      (SELECT COUNT(*) FROM users u JOIN user_roles ur ON ur.user_id = u.id
+                       WHERE u.school_id = s.id AND ur.role_id = $3) AS teachers,
+                      COALESCE(a.present, 0) AS attendance_present,
+                      COALESCE(a.absent, 0) AS attendance_absent,
+                      COALESCE(a.late, 0) AS attendance_late,
+                      COALESCE(a.excused, 0) AS attendance_excused,
+                      (SELECT COUNT(*) FROM staff_leave_requests l
+                       WHERE l.school_id = s.id AND l.status = 'approved'
+                         AND l.start_date <= $5 AND l.end_date >= $4) AS staff_leave_requests
+               FROM schools s
+               LEFT JOIN (
+                   SELECT school_id,
+                          COUNT(*) FILTER (WHERE status = 'present') AS present,
+                          COUNT(*) FILTER (WHERE status = 'absent') AS absent,
+                          COUNT(*) FILTER (WHERE status = 'late') AS late,
+                          COUNT(*) FILTER (WHERE status = 'excused') AS excused
+                   FROM attendance_records
+                   WHERE date BETWEEN $4 AND $5
+                   GROUP BY school_id
+               ) a ON a.school_id = s.id
+               WHERE s.group_id = $1
+               ORDER BY s.name"#,
+        )
+        .bind(group_id)
+        .bind(system_roles::STUDENT)
+        .bind(system_roles::TEACHER)
+        .bind(from)
+        .bind(to)
+        .fetch_all(db)
+        .await?;
+
+        Ok(GroupReport::new(group_id, from, to, schools))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar::<_, SchoolId>("INSERT INTO schools (name) VALUES ($1) RETURNING id")
+            .bind(format!("School {}", Uuid::new_v4()))
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn create_test_user(
+        pool: &PgPool,
+        school_id: Option<SchoolId>,
+        role_id: RoleId,
+    ) -> UserId {
+        let user_id = sqlx::query_scalar::<_, UserId>(
+            "INSERT INTO users (first_name, last_name, email, school_id)
+             VALUES ('Test', 'User', $1, $2) RETURNING id",
+        )
+        .bind(format!("user-{}@example.com", Uuid::new_v4()))
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(role_id)
+            .execute(pool)
+            .await
+            .unwrap();
+
+        user_id
+    }
+
+    async fn create_test_group(pool: &PgPool, name: &str) -> SchoolGroup {
+        GroupService::create_group(
+            pool,
+            CreateSchoolGroupDto {
+                name: name.to_string(),
+                description: None,
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_group_name_is_unique(pool: PgPool) {
+        create_test_group(&pool, "North District").await;
+
+        let err = GroupService::create_group(
+            &pool,
+            CreateSchoolGroupDto {
+                name: "North District".to_string(),
+                description: None,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_member_schools_and_users(pool: PgPool) {
+        let group = create_test_group(&pool, "North District").await;
+        let other = create_test_group(&pool, "South District").await;
+        let member = create_test_school(&pool).await;
+        let outsider = create_test_school(&pool).await;
+        create_test_user(&pool, Some(member), system_roles::STUDENT).await;
+        create_test_user(&pool, Some(member), system_roles::TEACHER).await;
+        create_test_user(&pool, Some(outsider), system_roles::STUDENT).await;
+
+        GroupService::add_school(&pool, group.id, member)
+            .await
+            .unwrap();
+        GroupService::add_school(&pool, other.id, outsider)
+            .await
+            .unwrap();
+
+        let schools = GroupService::list_schools(&pool, group.id).await.unwrap();
+        assert_eq!(schools.len(), 1);
+        assert_eq!(schools[0].student_count, 1);
+        assert!(
+            GroupService::is_member_school(&pool, group.id, member)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !GroupService::is_member_school(&pool, group.id, outsider)
+                .await
+                .unwrap()
+        );
+
+        let users = GroupService::list_users(
+            &pool,
+            group.id,
+            GroupUserFilterParams {
+                school_id: None,
+                role_id: Some(system_roles::STUDENT),
+                search: None,
+                pagination: Default::default(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(users.meta.total, 1);
+        assert_eq!(users.data[0].school_id, member);
+
+        // Moving the school to another group takes its users with it
+        GroupService::add_school(&pool, other.id, member)
+            .await
+            .unwrap();
+        let report = GroupService::get_report(
+            &pool,
+            other.id,
+            NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 9, 30).unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.school_count, 2);
+        assert_eq!((report.students, report.teachers), (2, 1));
+        assert_eq!(report.attendance_rate, None);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_assign_and_remove_admin(pool: PgPool) {
+        let group = create_test_group(&pool, "North District").await;
+        let school_id = create_test_school(&pool).await;
+        let school_user = create_test_user(&pool, Some(school_id), system_roles::ADMIN).await;
+        let user_id = sqlx::query_scalar::<_, UserId>(
+            "INSERT INTO users (first_name, last_name, email)
+             VALUES ('Group', 'Admin', 'group-admin@example.com') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let err = GroupService::assign_admin(&pool, group.id, school_user)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        GroupService::assign_admin(&pool, group.id, user_id)
+            .await
+            .unwrap();
+        let admins = GroupService::list_admins(&pool, group.id).await.unwrap();
+        assert_eq!(admins.len(), 1);
+        assert_eq!(admins[0].id, user_id);
+
+        GroupService::remove_admin(&pool, group.id, user_id)
+            .await
+            .unwrap();
+        assert!(
+            GroupService::list_admins(&pool, group.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let has_role = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
+        )
+        .bind(user_id)
+        .bind(system_roles::GROUP_ADMIN)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert!(!has_role);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_group_admins_cannot_create_admins(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+
+        GroupService::validate_member_user_roles(&pool, school_id, &[system_roles::TEACHER])
+            .await
+            .unwrap();
+
+        let err = GroupService::validate_member_user_roles(
+            &pool,
+            school_id,
+            &[system_roles::GROUP_ADMIN],
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        let err = GroupService::validate_member_user_roles(&pool, school_id, &[RoleId::new()])
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
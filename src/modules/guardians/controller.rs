@@ -0,0 +1,159 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, PaginationParams};
+use chalkbyte_models::ids::{GuardianAccountRunId, SchoolId};
+
+use crate::middleware::auth::{RequireGuardiansManage, RequireGuardiansRead};
+use crate::modules::guardians::model::{
+    GuardianAccountConflict, GuardianAccountRun, PaginatedGuardianAccountRunsResponse,
+    StartGuardianAccountRunDto,
+};
+use crate::modules::guardians::service::GuardianService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::verify_school_access;
+
+/// Start a guardian account run
+///
+/// Queues a background run that creates guardian accounts from the school's
+/// student emergency contacts and links them to their students. Poll the run
+/// for its counters, then review its conflicts. Running again is safe:
+/// existing guardians and links are reused.
+#[utoipa::path(
+    post,
+    path = "/api/schools/{id}/guardian-account-runs",
+    summary = "Start guardian account run",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = StartGuardianAccountRunDto,
+    responses(
+        (status = 202, description = "Run queued", body = GuardianAccountRun),
+        (status = 400, description = "A run is already in progress for the school"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires guardians:manage permission and access to the school")
+    ),
+    tag = "Guardians",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn start_guardian_account_run(
+    State(state): State<AppState>,
+    RequireGuardiansManage(auth_user): RequireGuardiansManage,
+    Path(school_id): Path<Uuid>,
+    Json(dto): Json<StartGuardianAccountRunDto>,
+) -> Result<(StatusCode, Json<GuardianAccountRun>), AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let run = GuardianService::start_run(&state.db, school_id, auth_user.user_id()?, dto).await?;
+
+    Ok((StatusCode::ACCEPTED, Json(run)))
+}
+
+/// List a school's guardian account runs
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/guardian-account-runs",
+    summary = "List guardian account runs",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("limit" = Option<i64>, Query, description = "Number of items per page (1-100, default: 10)"),
+        ("offset" = Option<i64>, Query, description = "Number of items to skip (default: 0)")
+    ),
+    responses(
+        (status = 200, description = "Runs, newest first", body = PaginatedGuardianAccountRunsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires guardians:read permission and access to the school")
+    ),
+    tag = "Guardians",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_guardian_account_runs(
+    State(state): State<AppState>,
+    RequireGuardiansRead(auth_user): RequireGuardiansRead,
+    Path(school_id): Path<Uuid>,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<Json<PaginatedGuardianAccountRunsResponse>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let runs = GuardianService::get_runs(&state.db, school_id, pagination).await?;
+
+    Ok(Json(runs))
+}
+
+/// Get a guardian account run
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/guardian-account-runs/{run_id}",
+    summary = "Get guardian account run",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("run_id" = Uuid, Path, description = "Guardian account run ID")
+    ),
+    responses(
+        (status = 200, description = "Guardian account run", body = GuardianAccountRun),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires guardians:read permission and access to the school"),
+        (status = 404, description = "Guardian account run not found")
+    ),
+    tag = "Guardians",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_guardian_account_run(
+    State(state): State<AppState>,
+    RequireGuardiansRead(auth_user): RequireGuardiansRead,
+    Path((school_id, run_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<GuardianAccountRun>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let run =
+        GuardianService::get_run(&state.db, school_id, GuardianAccountRunId::from(run_id)).await?;
+
+    Ok(Json(run))
+}
+
+/// List a guardian account run's conflicts
+///
+/// Contacts the run could not turn into an account, for manual review.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/guardian-account-runs/{run_id}/conflicts",
+    summary = "List guardian account run conflicts",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("run_id" = Uuid, Path, description = "Guardian account run ID")
+    ),
+    responses(
+        (status = 200, description = "Conflicts", body = Vec<GuardianAccountConflict>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires guardians:read permission and access to the school"),
+        (status = 404, description = "Guardian account run not found")
+    ),
+    tag = "Guardians",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_guardian_account_run_conflicts(
+    State(state): State<AppState>,
+    RequireGuardiansRead(auth_user): RequireGuardiansRead,
+    Path((school_id, run_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Vec<GuardianAccountConflict>>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let conflicts =
+        GuardianService::get_conflicts(&state.db, school_id, GuardianAccountRunId::from(run_id))
+            .await?;
+
+    Ok(Json(conflicts))
+}
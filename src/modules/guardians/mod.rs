@@ -0,0 +1,15 @@
+//! Guardians module.
+//!
+//! Creates guardian accounts from students' emergency contacts. A school
+//! admin starts a guardian account run; a background job started with the
+//! server merges contacts that share an email or phone number, creates one
+//! Guardian-role account per email (or reuses the school's existing one),
+//! links it to each student listing the contact, and optionally emails new
+//! guardians an invitation to set their password. Contacts without a usable
+//! email, with disagreeing emails, or whose email belongs to another user are
+//! recorded as conflicts for manual review.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
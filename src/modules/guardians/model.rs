@@ -0,0 +1,7 @@
+//! Guardian data models and DTOs.
+//!
+//! This module re-exports guardian models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all guardian models from the shared crate
+pub use chalkbyte_models::guardians::*;
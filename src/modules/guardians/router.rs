@@ -0,0 +1,28 @@
+use axum::{Router, routing::get};
+
+use crate::state::AppState;
+
+use super::controller::{
+    get_guardian_account_run, get_guardian_account_run_conflicts, get_guardian_account_runs,
+    start_guardian_account_run,
+};
+
+/// Initialize the guardian account runs router, merged into the schools router
+/// Routes: GET/POST /{id}/guardian-account-runs,
+/// GET /{id}/guardian-account-runs/{run_id},
+/// GET /{id}/guardian-account-runs/{run_id}/conflicts
+pub fn init_guardian_account_runs_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/{id}/guardian-account-runs",
+            get(get_guardian_account_runs).post(start_guardian_account_run),
+        )
+        .route(
+            "/{id}/guardian-account-runs/{run_id}",
+            get(get_guardian_account_run),
+        )
+        .route(
+            "/{id}/guardian-account-runs/{run_id}/conflicts",
+            get(get_guardian_account_run_conflicts),
+        )
+}
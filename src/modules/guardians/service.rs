@@ -0,0 +1,937 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::Utc;
+use rand::Rng;
+use sqlx::{FromRow, PgPool};
+use tracing::{info, instrument, warn};
+
+use chalkbyte_cache::{RedisCache, invalidate};
+use chalkbyte_config::EmailConfig;
+use chalkbyte_core::{AppError, PaginationMeta, PaginationParams, hash_password};
+use chalkbyte_models::ids::{GuardianAccountRunId, SchoolId, UserId};
+use chalkbyte_observability::jobs::register_job;
+
+use crate::modules::email_branding::service::EmailBrandingService;
+use crate::modules::guardians::model::{
+    GuardianAccountConflict, GuardianAccountRun, GuardianConflictKind,
+    PaginatedGuardianAccountRunsResponse, StartGuardianAccountRunDto,
+};
+use crate::modules::users::model::system_roles;
+use crate::modules::webhooks::model::WebhookEvent;
+use crate::modules::webhooks::service::WebhookService;
+use crate::utils::email::EmailService;
+
+/// How often the job looks for pending runs.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long an invitation link stays valid.
+pub const INVITATION_TTL_DAYS: i64 = 7;
+
+/// Phone numbers with fewer digits are too short to identify a guardian.
+const MIN_PHONE_DIGITS: usize = 7;
+
+const RUN_COLUMNS: &str = "id, school_id, status, send_invitations, requested_by,
+    contacts_scanned, accounts_created, accounts_reused, links_created, invitations_sent,
+    conflicts, error, created_at, started_at, completed_at";
+
+const CONFLICT_COLUMNS: &str =
+    "id, run_id, kind, contact_name, emails, phones, student_ids, existing_user_id, created_at";
+
+/// Start the background job that processes guardian account runs.
+///
+/// Runs interrupted by a restart are processed again from the start; a run
+/// reuses the accounts and links it already made, so nothing is duplicated.
+pub fn spawn_account_job(db: PgPool, email_config: EmailConfig, cache: Option<RedisCache>) {
+    tokio::spawn(async move {
+        let email = EmailService::new(db.clone(), email_config);
+
+        if let Err(e) = GuardianService::requeue_interrupted(&db).await {
+            warn!(error = %e, "Failed to requeue interrupted guardian account runs");
+        }
+
+        let job = register_job("guardian_accounts", POLL_INTERVAL);
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let run = job
+                .run(async {
+                    while let Some(run_id) =
+                        GuardianService::process_next(&db, &email, cache.as_ref()).await?
+                    {
+                        info!(%run_id, "Processed guardian account run");
+                    }
+                    Ok::<_, AppError>(())
+                })
+                .await;
+            if let Err(e) = run {
+                warn!(error = %e, "Failed to process guardian account run");
+            }
+            match GuardianService::queue_depth(&db).await {
+                Ok(depth) => job.set_queue_depth(depth),
+                Err(e) => warn!(error = %e, "Failed to count pending guardian account runs"),
+            }
+        }
+    });
+}
+
+/// A student's emergency contact.
+#[derive(Debug, Clone, FromRow)]
+struct StudentContact {
+    student_id: UserId,
+    name: Option<String>,
+    relationship: Option<String>,
+    phone: Option<String>,
+    email: Option<String>,
+}
+
+/// Contacts taken to be the same guardian, because they share an email or
+/// a phone number directly or through other contacts.
+#[derive(Debug, Default, PartialEq)]
+struct ContactGroup {
+    /// The first name given for the contact
+    name: Option<String>,
+    /// Distinct normalized emails, sorted
+    emails: Vec<String>,
+    /// Distinct normalized phone numbers, sorted
+    phones: Vec<String>,
+    /// Each student listing the contact, with the relationship they gave
+    students: Vec<(UserId, Option<String>)>,
+}
+
+/// A run claimed by the job.
+#[derive(FromRow)]
+struct ClaimedRun {
+    id: GuardianAccountRunId,
+    school_id: SchoolId,
+    send_invitations: bool,
+}
+
+/// Counters written to a run when it completes.
+#[derive(Debug, Default)]
+struct RunSummary {
+    contacts_scanned: i32,
+    accounts_created: i32,
+    accounts_reused: i32,
+    links_created: i32,
+    invitations_sent: i32,
+    conflicts: i32,
+}
+
+fn normalize_email(email: Option<&str>) -> Option<String> {
+    let email = email?.trim().to_lowercase();
+    match email.split_once('@') {
+        Some((local, domain)) if !local.is_empty() && domain.contains('.') => Some(email),
+        _ => None,
+    }
+}
+
+fn normalize_phone(phone: Option<&str>) -> Option<String> {
+    let digits: String = phone?.chars().filter(char::is_ascii_digit).collect();
+    (digits.len() >= MIN_PHONE_DIGITS).then_some(digits)
+}
+
+fn non_empty(text: Option<&str>) -> Option<String> {
+    text.map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+/// Merges contacts sharing an email or phone number into one group per
+/// guardian, in the order the contacts were given.
+fn group_contacts(contacts: &[StudentContact]) -> Vec<ContactGroup> {
+    fn find(parents: &mut [usize], mut i: usize) -> usize {
+        while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i = parents[i];
+        }
+        i
+    }
+
+    let mut parents: Vec<usize> = (0..contacts.len()).collect();
+    let mut first_by_key: HashMap<String, usize> = HashMap::new();
+
+    for (i, contact) in contacts.iter().enumerate() {
+        let keys = [
+            normalize_email(contact.email.as_deref()).map(|e| format!("email:{e}")),
+            normalize_phone(contact.phone.as_deref()).map(|p| format!("phone:{p}")),
+        ];
+        for key in keys.into_iter().flatten() {
+            let first = *first_by_key.entry(key).or_insert(i);
+            let (a, b) = (find(&mut parents, first), find(&mut parents, i));
+            if a != b {
+                parents[b.max(a)] = a.min(b);
+            }
+        }
+    }
+
+    let mut group_by_root: HashMap<usize, usize> = HashMap::new();
+    let mut groups: Vec<ContactGroup> = Vec::new();
+    for (i, contact) in contacts.iter().enumerate() {
+        let email = normalize_email(contact.email.as_deref());
+        let phone = normalize_phone(contact.phone.as_deref());
+        if email.is_none() && phone.is_none() {
+            continue;
+        }
+
+        let root = find(&mut parents, i);
+        let index = *group_by_root.entry(root).or_insert_with(|| {
+            groups.push(ContactGroup::default());
+            groups.len() - 1
+        });
+        let group = &mut groups[index];
+
+        if group.name.is_none() {
+            group.name = non_empty(contact.name.as_deref());
+        }
+        group.emails.extend(email);
+        group.phones.extend(phone);
+        if !group
+            .students
+            .iter()
+            .any(|(id, _)| *id == contact.student_id)
+        {
+            group.students.push((
+                contact.student_id,
+                non_empty(contact.relationship.as_deref()),
+            ));
+        }
+    }
+
+    for group in &mut groups {
+        group.emails.sort();
+        group.emails.dedup();
+        group.phones.sort();
+        group.phones.dedup();
+    }
+
+    groups
+}
+
+/// Splits a contact's name into first and last name, falling back to the
+/// email's local part.
+fn split_name(name: Option<&str>, email: &str) -> (String, String) {
+    let (first, last) = match name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => match name.rsplit_once(' ') {
+            Some((first, last)) => (first.trim().to_string(), last.to_string()),
+            None => (name.to_string(), String::new()),
+        },
+        None => (
+            email.split('@').next().unwrap_or(email).to_string(),
+            String::new(),
+        ),
+    };
+
+    let truncate = |s: String| s.chars().take(100).collect::<String>();
+    (truncate(first), truncate(last))
+}
+
+fn invitation_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+pub struct GuardianService;
+
+impl GuardianService {
+    /// Queue a run for the school. Only one run per school may be waiting or
+    /// in progress at a time.
+    #[instrument(skip(db, dto))]
+    pub async fn start_run(
+        db: &PgPool,
+        school_id: SchoolId,
+        requested_by: UserId,
+        dto: StartGuardianAccountRunDto,
+    ) -> Result<GuardianAccountRun, AppError> {
+        let run = sqlx::query_as::<_, GuardianAccountRun>(&format!(
+            "INSERT INTO guardian_account_runs (school_id, send_invitations, requested_by)
+             VALUES ($1, $2, $3)
+             RETURNING {RUN_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(dto.send_invitations)
+        .bind(requested_by)
+        .fetch_one(db)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(db_err) = &e
+                && db_err.is_unique_violation()
+            {
+                return AppError::bad_request(anyhow::anyhow!(
+                    "A guardian account run is already in progress for this school"
+                ));
+            }
+            AppError::from(e)
+        })?;
+
+        info!(run.id = %run.id, "Guardian account run queued");
+
+        Ok(run)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_runs(
+        db: &PgPool,
+        school_id: SchoolId,
+        pagination: PaginationParams,
+    ) -> Result<PaginatedGuardianAccountRunsResponse, AppError> {
+        let limit = pagination.limit();
+        let offset = pagination.offset();
+
+        let data = sqlx::query_as::<_, GuardianAccountRun>(&format!(
+            "SELECT {RUN_COLUMNS} FROM guardian_account_runs
+             WHERE school_id = $1
+             ORDER BY created_at DESC
+             LIMIT $2 OFFSET $3"
+        ))
+        .bind(school_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM guardian_account_runs WHERE school_id = $1",
+        )
+        .bind(school_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(PaginatedGuardianAccountRunsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: pagination.page(),
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_run(
+        db: &PgPool,
+        school_id: SchoolId,
+        run_id: GuardianAccountRunId,
+    ) -> Result<GuardianAccountRun, AppError> {
+        sqlx::query_as::<_, GuardianAccountRun>(&format!(
+            "SELECT {RUN_COLUMNS} FROM guardian_account_runs WHERE id = $1 AND school_id = $2"
+        ))
+        .bind(run_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Guardian account run not found")))
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_conflicts(
+        db: &PgPool,
+        school_id: SchoolId,
+        run_id: GuardianAccountRunId,
+    ) -> Result<Vec<GuardianAccountConflict>, AppError> {
+        // Confirms the run belongs to the school
+        Self::get_run(db, school_id, run_id).await?;
+
+        let conflicts = sqlx::query_as::<_, GuardianAccountConflict>(&format!(
+            "SELECT {CONFLICT_COLUMNS} FROM guardian_account_conflicts
+             WHERE run_id = $1
+             ORDER BY created_at, id"
+        ))
+        .bind(run_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(conflicts)
+    }
+
+    /// Number of runs waiting to be processed.
+    pub async fn queue_depth(db: &PgPool) -> Result<u64, AppError> {
+        let pending = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM guardian_account_runs WHERE status = 'pending'",
+        )
+        .fetch_one(db)
+        .await?;
+
+        Ok(pending.try_into().unwrap_or_default())
+    }
+
+    /// Put runs left in progress by a previous process back in the queue.
+    pub async fn requeue_interrupted(db: &PgPool) -> Result<u64, AppError> {
+        let requeued = sqlx::query(
+            "UPDATE guardian_account_runs SET status = 'pending' WHERE status = 'running'",
+        )
+        .execute(db)
+        .await?
+        .rows_affected();
+
+        Ok(requeued)
+    }
+
+    /// Process the oldest pending run.
+    ///
+    /// Returns the run processed, or `None` when none is pending. A run that
+    /// hits an unexpected error is marked failed with the error.
+    #[instrument(skip(db, email, cache))]
+    pub async fn process_next(
+        db: &PgPool,
+        email: &EmailService,
+        cache: Option<&RedisCache>,
+    ) -> Result<Option<GuardianAccountRunId>, AppError> {
+        let Some(run) = sqlx::query_as::<_, ClaimedRun>(
+            "UPDATE guardian_account_runs SET status = 'running', started_at = NOW()
+             WHERE id = (
+                 SELECT id FROM guardian_account_runs
+                 WHERE status = 'pending'
+                 ORDER BY created_at
+                 LIMIT 1
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING id, school_id, send_invitations",
+        )
+        .fetch_optional(db)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        match Self::process_run(db, email, &run).await {
+            Ok(summary) => {
+                sqlx::query(
+                    "UPDATE guardian_account_runs
+                     SET status = 'completed', completed_at = NOW(),
+                         contacts_scanned = $2, accounts_created = $3, accounts_reused = $4,
+                         links_created = $5, invitations_sent = $6, conflicts = $7
+                     WHERE id = $1",
+                )
+                .bind(run.id)
+                .bind(summary.contacts_scanned)
+                .bind(summary.accounts_created)
+                .bind(summary.accounts_reused)
+                .bind(summary.links_created)
+                .bind(summary.invitations_sent)
+                .bind(summary.conflicts)
+                .execute(db)
+                .await?;
+            }
+            Err(e) => {
+                warn!(run.id = %run.id, error = %e, "Guardian account run failed");
+                sqlx::query(
+                    "UPDATE guardian_account_runs
+                     SET status = 'failed', completed_at = NOW(), error = $2
+                     WHERE id = $1",
+                )
+                .bind(run.id)
+                .bind(e.to_string())
+                .execute(db)
+                .await?;
+            }
+        }
+
+        invalidate::user(cache, None, Some(run.school_id.into_inner())).await;
+
+        Ok(Some(run.id))
+    }
+
+    async fn process_run(
+        db: &PgPool,
+        email: &EmailService,
+        run: &ClaimedRun,
+    ) -> Result<RunSummary, AppError> {
+        let email =
+            email.with_branding(EmailBrandingService::email_branding(db, run.school_id).await?);
+        let school_name = email.branding().name().to_string();
+
+        let contacts = sqlx::query_as::<_, StudentContact>(
+            "SELECT p.student_id,
+                    p.emergency_contact_name AS name,
+                    p.emergency_contact_relationship AS relationship,
+                    p.emergency_contact_phone AS phone,
+                    p.emergency_contact_email AS email
+             FROM student_medical_profiles p
+             JOIN users u ON u.id = p.student_id AND u.school_id = p.school_id
+             WHERE p.school_id = $1
+               AND (NULLIF(TRIM(p.emergency_contact_phone), '') IS NOT NULL
+                    OR NULLIF(TRIM(p.emergency_contact_email), '') IS NOT NULL)
+             ORDER BY u.last_name, u.first_name, p.student_id",
+        )
+        .bind(run.school_id)
+        .fetch_all(db)
+        .await?;
+
+        // Conflicts from an interrupted earlier attempt are found again
+        sqlx::query("DELETE FROM guardian_account_conflicts WHERE run_id = $1")
+            .bind(run.id)
+            .execute(db)
+            .await?;
+
+        let mut summary = RunSummary {
+            contacts_scanned: contacts.len() as i32,
+            ..Default::default()
+        };
+
+        for group in group_contacts(&contacts) {
+            let guardian_email = match group.emails.as_slice() {
+                [email] => email.clone(),
+                [] => {
+                    Self::record_conflict(
+                        db,
+                        run.id,
+                        GuardianConflictKind::MissingEmail,
+                        &group,
+                        None,
+                    )
+                    .await?;
+                    summary.conflicts += 1;
+                    continue;
+                }
+                _ => {
+                    Self::record_conflict(
+                        db,
+                        run.id,
+                        GuardianConflictKind::ConflictingEmails,
+                        &group,
+                        None,
+                    )
+                    .await?;
+                    summary.conflicts += 1;
+                    continue;
+                }
+            };
+
+            let existing = sqlx::query_as::<_, (UserId, Option<SchoolId>, bool)>(
+                "SELECT u.id, u.school_id,
+                        EXISTS(SELECT 1 FROM user_roles ur WHERE ur.user_id = u.id AND ur.role_id = $2)
+                 FROM users u
+                 WHERE LOWER(u.email) = $1",
+            )
+            .bind(&guardian_email)
+            .bind(system_roles::GUARDIAN)
+            .fetch_optional(db)
+            .await?;
+
+            let mut tx = db.begin().await?;
+
+            let (guardian_id, created) = match existing {
+                Some((user_id, Some(school_id), true)) if school_id == run.school_id => {
+                    (user_id, false)
+                }
+                Some((user_id, ..)) => {
+                    Self::record_conflict(
+                        db,
+                        run.id,
+                        GuardianConflictKind::EmailInUse,
+                        &group,
+                        Some(user_id),
+                    )
+                    .await?;
+                    summary.conflicts += 1;
+                    continue;
+                }
+                None => {
+                    let (first_name, last_name) =
+                        split_name(group.name.as_deref(), &guardian_email);
+                    let password_hash = hash_password(&invitation_token())?;
+
+                    let user_id = sqlx::query_scalar::<_, UserId>(
+                        "INSERT INTO users (first_name, last_name, email, password, school_id)
+                         VALUES ($1, $2, $3, $4, $5)
+                         RETURNING id",
+                    )
+                    .bind(&first_name)
+                    .bind(&last_name)
+                    .bind(&guardian_email)
+                    .bind(&password_hash)
+                    .bind(run.school_id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+
+                    sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+                        .bind(user_id)
+                        .bind(system_roles::GUARDIAN)
+                        .execute(&mut *tx)
+                        .await?;
+
+                    WebhookService::enqueue(
+                        &mut *tx,
+                        run.school_id,
+                        WebhookEvent::UserCreated,
+                        &serde_json::json!({
+                            "user_id": user_id,
+                            "email": guardian_email,
+                            "first_name": first_name,
+                            "last_name": last_name,
+                            "role_ids": [system_roles::GUARDIAN],
+                        }),
+                    )
+                    .await?;
+
+                    (user_id, true)
+                }
+            };
+
+            for (student_id, relationship) in &group.students {
+                summary.links_created += sqlx::query(
+                    "INSERT INTO student_guardians (student_id, guardian_id, school_id, relationship)
+                     VALUES ($1, $2, $3, $4)
+                     ON CONFLICT (student_id, guardian_id) DO NOTHING",
+                )
+                .bind(student_id)
+                .bind(guardian_id)
+                .bind(run.school_id)
+                .bind(relationship)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected() as i32;
+            }
+
+            // New guardians set their password through the reset flow
+            let invitation = if created && run.send_invitations {
+                let token = invitation_token();
+                sqlx::query(
+                    "INSERT INTO password_reset_tokens (user_id, token, expires_at)
+                     VALUES ($1, $2, $3)",
+                )
+                .bind(guardian_id)
+                .bind(&token)
+                .bind(Utc::now() + chrono::Duration::days(INVITATION_TTL_DAYS))
+                .execute(&mut *tx)
+                .await?;
+                Some(token)
+            } else {
+                None
+            };
+
+            tx.commit().await?;
+
+            if created {
+                summary.accounts_created += 1;
+            } else {
+                summary.accounts_reused += 1;
+            }
+
+            if let Some(token) = invitation {
+                let (first_name, _) = split_name(group.name.as_deref(), &guardian_email);
+                match email
+                    .send_guardian_invitation(
+                        &guardian_email,
+                        &first_name,
+                        &school_name,
+                        &token,
+                        INVITATION_TTL_DAYS,
+                    )
+                    .await
+                {
+                    Ok(()) => summary.invitations_sent += 1,
+                    Err(e) => {
+                        warn!(user.id = %guardian_id, error = %e, "Failed to send guardian invitation")
+                    }
+                }
+            }
+        }
+
+        info!(
+            run.id = %run.id,
+            created = summary.accounts_created,
+            reused = summary.accounts_reused,
+            conflicts = summary.conflicts,
+            "Guardian account run completed"
+        );
+
+        Ok(summary)
+    }
+
+    async fn record_conflict(
+        db: &PgPool,
+        run_id: GuardianAccountRunId,
+        kind: GuardianConflictKind,
+        group: &ContactGroup,
+        existing_user_id: Option<UserId>,
+    ) -> Result<(), AppError> {
+        let student_ids: Vec<UserId> = group.students.iter().map(|(id, _)| *id).collect();
+
+        sqlx::query(
+            "INSERT INTO guardian_account_conflicts
+                 (run_id, kind, contact_name, emails, phones, student_ids, existing_user_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(run_id)
+        .bind(kind)
+        .bind(&group.name)
+        .bind(&group.emails)
+        .bind(&group.phones)
+        .bind(&student_ids)
+        .bind(existing_user_id)
+        .execute(db)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::guardians::model::GuardianAccountRunStatus;
+    use uuid::Uuid;
+
+    fn contact(
+        student_id: UserId,
+        name: &str,
+        phone: Option<&str>,
+        email: Option<&str>,
+    ) -> StudentContact {
+        StudentContact {
+            student_id,
+            name: Some(name.to_string()),
+            relationship: Some("Mother".to_string()),
+            phone: phone.map(str::to_string),
+            email: email.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_group_contacts_merges_by_email_and_phone() {
+        let (a, b, c, d) = (UserId::new(), UserId::new(), UserId::new(), UserId::new());
+        let contacts = vec![
+            contact(a, "Ada Obi", Some("+234 801 234 5678"), None),
+            contact(b, "Ada Obi", None, Some(" Ada@Example.com ")),
+            // Shares the phone with the first and the email with the second
+            contact(c, "Mrs Obi", Some("0801-234-5678"), Some("ada@example.com")),
+            contact(
+                d,
+                "Bayo Ade",
+                Some("0802 000 0000"),
+                Some("bayo@example.com"),
+            ),
+        ];
+
+        let groups = group_contacts(&contacts);
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].students.len(), 1);
+        assert_eq!(groups[0].phones, vec!["2348012345678"]);
+        // The first two only meet through the third; phone formats differ
+        assert_eq!(groups[1].emails, vec!["ada@example.com"]);
+        assert_eq!(groups[1].students.len(), 2);
+        assert_eq!(groups[2].name.as_deref(), Some("Bayo Ade"));
+    }
+
+    #[test]
+    fn test_group_contacts_links_shared_phone_to_email() {
+        let (a, b) = (UserId::new(), UserId::new());
+        let contacts = vec![
+            contact(a, "Ada Obi", Some("08012345678"), None),
+            contact(b, "", Some("0801 234 5678"), Some("ada@example.com")),
+            contact(UserId::new(), "Nobody", Some("123"), Some("not-an-email")),
+        ];
+
+        let groups = group_contacts(&contacts);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name.as_deref(), Some("Ada Obi"));
+        assert_eq!(groups[0].emails, vec!["ada@example.com"]);
+        assert_eq!(
+            groups[0]
+                .students
+                .iter()
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>(),
+            vec![a, b]
+        );
+    }
+
+    #[test]
+    fn test_split_name() {
+        assert_eq!(
+            split_name(Some("Ada Nneka Obi"), "ada@example.com"),
+            ("Ada Nneka".to_string(), "Obi".to_string())
+        );
+        assert_eq!(
+            split_name(None, "ada@example.com"),
+            ("ada".to_string(), String::new())
+        );
+    }
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar::<_, SchoolId>(
+            "INSERT INTO schools (name, address) VALUES ($1, 'Test Address') RETURNING id",
+        )
+        .bind(format!("School {}", Uuid::new_v4()))
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn create_student_with_contact(
+        pool: &PgPool,
+        school_id: SchoolId,
+        phone: Option<&str>,
+        email: Option<&str>,
+    ) -> UserId {
+        let student_id = sqlx::query_scalar::<_, UserId>(
+            "INSERT INTO users (first_name, last_name, email, school_id)
+             VALUES ('Test', 'Student', $1, $2) RETURNING id",
+        )
+        .bind(format!("student-{}@example.com", Uuid::new_v4()))
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            "INSERT INTO student_medical_profiles
+                 (student_id, school_id, emergency_contact_name, emergency_contact_relationship,
+                  emergency_contact_phone, emergency_contact_email)
+             VALUES ($1, $2, 'Ada Obi', 'Mother', $3, $4)",
+        )
+        .bind(student_id)
+        .bind(school_id)
+        .bind(phone)
+        .bind(email)
+        .execute(pool)
+        .await
+        .unwrap();
+
+        student_id
+    }
+
+    async fn create_admin(pool: &PgPool, school_id: SchoolId) -> UserId {
+        sqlx::query_scalar::<_, UserId>(
+            "INSERT INTO users (first_name, last_name, email, school_id)
+             VALUES ('Admin', 'User', $1, $2) RETURNING id",
+        )
+        .bind(format!("admin-{}@example.com", Uuid::new_v4()))
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn run_once(pool: &PgPool, school_id: SchoolId) -> GuardianAccountRun {
+        let admin_id = create_admin(pool, school_id).await;
+        let run = GuardianService::start_run(
+            pool,
+            school_id,
+            admin_id,
+            StartGuardianAccountRunDto {
+                send_invitations: true,
+            },
+        )
+        .await
+        .unwrap();
+
+        let email = EmailService::new(
+            pool.clone(),
+            EmailConfig {
+                enabled: false,
+                smtp_host: "localhost".to_string(),
+                smtp_port: 1025,
+                smtp_username: String::new(),
+                smtp_password: String::new(),
+                from_email: "noreply@example.com".to_string(),
+                from_name: "Chalkbyte".to_string(),
+                frontend_url: "http://localhost:3000".to_string(),
+            },
+        );
+        let processed = GuardianService::process_next(pool, &email, None)
+            .await
+            .unwrap();
+        assert_eq!(processed, Some(run.id));
+
+        GuardianService::get_run(pool, school_id, run.id)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_run_creates_links_and_reports_conflicts(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let first = create_student_with_contact(
+            &pool,
+            school_id,
+            Some("0801 234 5678"),
+            Some("ada@example.com"),
+        )
+        .await;
+        let sibling =
+            create_student_with_contact(&pool, school_id, Some("08012345678"), None).await;
+        create_student_with_contact(&pool, school_id, Some("0809 999 9999"), None).await;
+
+        let run = run_once(&pool, school_id).await;
+
+        assert_eq!(run.status, GuardianAccountRunStatus::Completed);
+        assert_eq!(run.contacts_scanned, 3);
+        assert_eq!(run.accounts_created, 1);
+        assert_eq!(run.links_created, 2);
+        assert_eq!(run.conflicts, 1);
+
+        let linked: Vec<UserId> = sqlx::query_scalar(
+            "SELECT sg.student_id FROM student_guardians sg
+             JOIN users g ON g.id = sg.guardian_id
+             WHERE g.email = 'ada@example.com'
+             ORDER BY sg.student_id",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let mut expected = vec![first, sibling];
+        expected.sort_by_key(|id| id.into_inner());
+        assert_eq!(linked, expected);
+
+        let conflicts = GuardianService::get_conflicts(&pool, school_id, run.id)
+            .await
+            .unwrap();
+        assert_eq!(conflicts[0].kind, GuardianConflictKind::MissingEmail);
+        assert_eq!(conflicts[0].phones, vec!["08099999999"]);
+
+        // A second run reuses the guardian instead of duplicating it
+        let rerun = run_once(&pool, school_id).await;
+        assert_eq!(rerun.accounts_created, 0);
+        assert_eq!(rerun.accounts_reused, 1);
+        assert_eq!(rerun.links_created, 0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_run_reports_email_used_by_staff(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let teacher_id = sqlx::query_scalar::<_, UserId>(
+            "INSERT INTO users (first_name, last_name, email, school_id)
+             VALUES ('Ada', 'Obi', 'ada@example.com', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        create_student_with_contact(&pool, school_id, None, Some("ADA@example.com")).await;
+
+        let run = run_once(&pool, school_id).await;
+        assert_eq!(run.accounts_created, 0);
+        assert_eq!(run.conflicts, 1);
+
+        let conflicts = GuardianService::get_conflicts(&pool, school_id, run.id)
+            .await
+            .unwrap();
+        assert_eq!(conflicts[0].kind, GuardianConflictKind::EmailInUse);
+        assert_eq!(conflicts[0].existing_user_id, Some(teacher_id));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_only_one_active_run_per_school(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin_id = create_admin(&pool, school_id).await;
+
+        GuardianService::start_run(&pool, school_id, admin_id, Default::default())
+            .await
+            .unwrap();
+        let err = GuardianService::start_run(&pool, school_id, admin_id, Default::default())
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.status, axum::http::StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,159 @@
+use axum::{
+    Json,
+    extract::{Multipart, Query, State},
+};
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, permissions};
+
+use crate::middleware::auth::{AuthUser, RequireStudentsCreate};
+use crate::modules::auth::controller::ErrorResponse;
+use crate::modules::imports::model::{
+    ImportValidationForm, ImportValidationParams, ImportValidationReport, SchoolImportParams,
+    SchoolImportReport,
+};
+use crate::modules::imports::service::{
+    ImportDatasetFiles, ImportValidationService, SchoolImportService,
+};
+use crate::state::AppState;
+use crate::utils::auth_helpers::get_school_id_for_scoped_operation;
+
+/// Read the sheets and default password of an import dataset.
+async fn read_dataset(mut multipart: Multipart) -> Result<ImportDatasetFiles, AppError> {
+    let mut files = ImportDatasetFiles::default();
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::bad_request(anyhow::anyhow!("Invalid multipart body: {}", e)))?
+    {
+        let Some(name) = field.name().map(str::to_string) else {
+            continue;
+        };
+        let slot = match name.as_str() {
+            "levels" => &mut files.levels,
+            "branches" => &mut files.branches,
+            "staff" => &mut files.staff,
+            "students" => &mut files.students,
+            "guardians" => &mut files.guardians,
+            "default_password" => {
+                let text = field.text().await.map_err(|e| {
+                    AppError::bad_request(anyhow::anyhow!("Could not read default_password: {}", e))
+                })?;
+                if !text.is_empty() {
+                    files.default_password = Some(text);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        let bytes = field.bytes().await.map_err(|e| {
+            AppError::bad_request(anyhow::anyhow!("Could not read {}: {}", name, e))
+        })?;
+        *slot = Some(bytes.to_vec());
+    }
+    Ok(files)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/imports/validate",
+    summary = "Validate import dataset",
+    description = "Upload any of a `levels`, `branches`, `staff`, `students`, and `guardians` \
+        CSV file to check them against each other and the school without importing anything. \
+        Levels have `name` and optional `branches` (separated by `;`) columns; branches have \
+        `level` and `name`; staff have `first_name`, `last_name`, and `email`, with optional \
+        `role` (`teacher` or `admin`), `password`, `level`, and `branch`; students use the \
+        student import columns; guardians have `student_email` and `name`, with optional \
+        `relationship`, `phone`, and `email`. Each issue has a machine-readable `code`; the \
+        dataset is `valid` when no issue is an error.",
+    params(ImportValidationParams),
+    request_body(content = ImportValidationForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Issues found in the dataset", body = ImportValidationReport),
+        (status = 400, description = "No files, or a file is unreadable or missing required columns", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires students:create permission", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Imports"
+)]
+#[instrument(skip(state, multipart))]
+pub async fn validate_import(
+    State(state): State<AppState>,
+    RequireStudentsCreate(auth_user): RequireStudentsCreate,
+    Query(params): Query<ImportValidationParams>,
+    multipart: Multipart,
+) -> Result<Json<ImportValidationReport>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+
+    let files = read_dataset(multipart).await?;
+    let report =
+        ImportValidationService::validate(&state.db, school_id, files, &state.password_policy)
+            .await?;
+    Ok(Json(report))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/imports/school",
+    summary = "Import school dataset",
+    description = "Upload the sheets of a school's dataset as CSV files, with the same fields and \
+        columns as import validation, to create everything in one request. The whole dataset is \
+        validated first and nothing is written if any issue is an error; use `dry_run=true` to \
+        only get the validation report. A valid dataset is written in the order levels, \
+        branches, staff, students, guardians, each sheet in its own transaction, so later sheets \
+        can refer to levels and branches by name. If a phase fails it is rolled back, later \
+        phases are skipped, and earlier ones stay committed; fixing the problem and uploading \
+        again reuses the levels and branches already created. The first guardian of each \
+        student becomes their emergency contact.",
+    params(SchoolImportParams),
+    request_body(content = ImportValidationForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Validation report and the outcome of each phase", body = SchoolImportReport),
+        (status = 400, description = "No files, a file is unreadable or missing required columns, or the school has required custom fields", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires the create permission for each uploaded sheet: levels:create, branches:create (also for branches listed in the levels sheet), users:create, or students:create", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Imports"
+)]
+#[instrument(skip(state, auth_user, multipart))]
+pub async fn import_school(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Query(params): Query<SchoolImportParams>,
+    multipart: Multipart,
+) -> Result<Json<SchoolImportReport>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+
+    let files = read_dataset(multipart).await?;
+    for permission in files.required_permissions() {
+        if !auth_user.has_permission(permission) {
+            return Err(AppError::forbidden(format!(
+                "Access denied. Missing required permission: {}",
+                permission
+            )));
+        }
+    }
+
+    let report = SchoolImportService::import(
+        &state.db,
+        state.cache.as_ref(),
+        school_id,
+        auth_user.user_id()?,
+        auth_user.has_permission(permissions::BRANCHES_CREATE),
+        files,
+        params.dry_run,
+        &state.password_policy,
+    )
+    .await?;
+    Ok(Json(report))
+}
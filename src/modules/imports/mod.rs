@@ -0,0 +1,19 @@
+//! School dataset import module.
+//!
+//! Checks a dataset for a school import (levels with their branches,
+//! branches, staff, students, and guardians as CSV files) before anything is
+//! written. Each file is validated the way its import would validate it, and
+//! the files are cross-checked: staff and students may use levels and
+//! branches from the dataset, guardians must belong to a student in the
+//! students file or the school, and policy problems such as students without
+//! a guardian are flagged. The result is a list of issues with
+//! machine-readable codes for the UI to render.
+//!
+//! A valid dataset can then be imported in one request, which writes the
+//! sheets in dependency order with one transaction per sheet, so onboarding
+//! a school does not take separate imports run in the right order.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
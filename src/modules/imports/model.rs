@@ -0,0 +1,7 @@
+//! Import validation data models.
+//!
+//! This module re-exports import models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all import models from the shared crate
+pub use chalkbyte_models::imports::*;
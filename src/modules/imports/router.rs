@@ -0,0 +1,13 @@
+use axum::{Router, routing::post};
+
+use crate::state::AppState;
+
+use super::controller::{import_school, validate_import};
+
+/// Initialize the imports router
+/// Routes: POST /validate, POST /school
+pub fn init_imports_router() -> Router<AppState> {
+    Router::new()
+        .route("/validate", post(validate_import))
+        .route("/school", post(import_school))
+}
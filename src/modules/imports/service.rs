@@ -0,0 +1,1590 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+use sqlx::{PgConnection, PgPool};
+use tracing::{error, instrument};
+
+use chalkbyte_cache::{RedisCache, keys::invalidate};
+use chalkbyte_core::{AppError, PasswordPolicy, permissions};
+use chalkbyte_models::ids::{BranchId, LevelId, RoleId, SchoolId, UserId};
+use chalkbyte_models::{Email, PhoneNumber};
+
+use crate::modules::custom_fields::model::CustomFieldEntity;
+use crate::modules::imports::model::{
+    BranchImportRow, GuardianImportRow, ImportDataset, ImportIssue, ImportIssueCode,
+    ImportIssueSeverity, ImportPhaseResult, ImportPhaseStatus, ImportRowCounts,
+    ImportValidationReport, LevelImportRow, SchoolImportReport, StaffImportRow,
+};
+use crate::modules::students::service::{
+    ImportLookups, StudentService, ValidImportRow, read_import_csv, validate_import_row,
+};
+use crate::modules::users::model::system_roles;
+use crate::utils::csv_import::{CsvRow, read_csv_rows};
+use crate::utils::password::hash_password;
+
+/// Files of an import dataset, as uploaded.
+#[derive(Debug, Default)]
+pub struct ImportDatasetFiles {
+    pub levels: Option<Vec<u8>>,
+    pub branches: Option<Vec<u8>>,
+    pub staff: Option<Vec<u8>>,
+    pub students: Option<Vec<u8>>,
+    pub guardians: Option<Vec<u8>>,
+    pub default_password: Option<String>,
+}
+
+impl ImportDatasetFiles {
+    /// Permissions needed to import the uploaded sheets: the create
+    /// permission for each.
+    pub fn required_permissions(&self) -> impl Iterator<Item = &'static str> {
+        [
+            (self.levels.is_some(), permissions::LEVELS_CREATE),
+            (self.branches.is_some(), permissions::BRANCHES_CREATE),
+            (self.staff.is_some(), permissions::USERS_CREATE),
+            (
+                self.students.is_some() || self.guardians.is_some(),
+                permissions::STUDENTS_CREATE,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(uploaded, permission)| uploaded.then_some(permission))
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.levels.is_none()
+            && self.branches.is_none()
+            && self.staff.is_none()
+            && self.students.is_none()
+            && self.guardians.is_none()
+    }
+}
+
+/// A staff import row that passed validation.
+struct ValidStaffRow {
+    first_name: String,
+    last_name: String,
+    email: String,
+    /// `None` when the row uses the import's default password
+    password: Option<String>,
+    role_id: RoleId,
+    /// Branch the teacher leads
+    branch_id: Option<BranchId>,
+}
+
+/// What a dataset writes, phase by phase. Levels and branches get their IDs
+/// during validation so later sheets can refer to them.
+#[derive(Default)]
+struct ImportPlan {
+    levels: Vec<(LevelId, String)>,
+    branches: Vec<(BranchId, LevelId, String)>,
+    staff: Vec<ValidStaffRow>,
+    students: Vec<ValidImportRow>,
+    /// The emergency contact for each lowercased student email
+    guardians: Vec<(String, GuardianImportRow)>,
+}
+
+/// Collects issues for one file of the dataset.
+struct IssueLog<'a> {
+    dataset: ImportDataset,
+    issues: &'a mut Vec<ImportIssue>,
+}
+
+impl IssueLog<'_> {
+    fn push(
+        &mut self,
+        severity: ImportIssueSeverity,
+        code: ImportIssueCode,
+        line: Option<usize>,
+        column: Option<&str>,
+        message: String,
+    ) {
+        self.issues.push(ImportIssue {
+            dataset: self.dataset,
+            severity,
+            code,
+            line,
+            column: column.map(str::to_string),
+            message,
+        });
+    }
+
+    fn error(&mut self, code: ImportIssueCode, line: usize, column: &str, message: String) {
+        self.push(
+            ImportIssueSeverity::Error,
+            code,
+            Some(line),
+            Some(column),
+            message,
+        );
+    }
+
+    fn warning(&mut self, code: ImportIssueCode, line: usize, column: &str, message: String) {
+        self.push(
+            ImportIssueSeverity::Warning,
+            code,
+            Some(line),
+            Some(column),
+            message,
+        );
+    }
+
+    fn unreadable(&mut self, line: usize, message: String) {
+        self.push(
+            ImportIssueSeverity::Error,
+            ImportIssueCode::UnreadableRow,
+            Some(line),
+            None,
+            message,
+        );
+    }
+}
+
+/// A registered student a guardian row can refer to.
+struct ExistingStudent {
+    has_emergency_contact: bool,
+}
+
+fn read_file<T: serde::de::DeserializeOwned>(
+    name: &str,
+    bytes: Option<&[u8]>,
+    required_columns: &[&str],
+    max_rows: usize,
+) -> Result<Vec<CsvRow<T>>, AppError> {
+    let Some(bytes) = bytes else {
+        return Ok(Vec::new());
+    };
+    read_csv_rows(bytes, required_columns, max_rows)
+        .map_err(|e| AppError::bad_request(anyhow::anyhow!("{} file: {}", name, e.error)))
+}
+
+/// Check the levels file, adding new levels and branches to `lookups` so
+/// later sheets can refer to them.
+fn validate_levels(
+    rows: Vec<CsvRow<LevelImportRow>>,
+    lookups: &mut ImportLookups,
+    plan: &mut ImportPlan,
+    issues: &mut Vec<ImportIssue>,
+) {
+    let mut log = IssueLog {
+        dataset: ImportDataset::Levels,
+        issues,
+    };
+    let mut seen_levels: HashMap<String, usize> = HashMap::new();
+
+    for (line, row) in rows {
+        let row = match row {
+            Ok(row) => row,
+            Err(message) => {
+                log.unreadable(line, message);
+                continue;
+            }
+        };
+
+        if row.name.is_empty() || row.name.chars().count() > 100 {
+            log.error(
+                ImportIssueCode::InvalidValue,
+                line,
+                "name",
+                "must be 1-100 characters".to_string(),
+            );
+            continue;
+        }
+
+        let key = row.name.to_lowercase();
+        if let Some(first_line) = seen_levels.get(&key) {
+            log.error(
+                ImportIssueCode::Duplicate,
+                line,
+                "name",
+                format!("duplicates the level on line {}", first_line),
+            );
+            continue;
+        }
+        seen_levels.insert(key.clone(), line);
+
+        let level_id = match lookups.levels.get(&key) {
+            Some(level_id) => {
+                log.warning(
+                    ImportIssueCode::AlreadyExists,
+                    line,
+                    "name",
+                    format!(
+                        "level '{}' already exists; its branches would be added to it",
+                        row.name
+                    ),
+                );
+                *level_id
+            }
+            None => {
+                let level_id = LevelId::new();
+                lookups.levels.insert(key, level_id);
+                plan.levels.push((level_id, row.name.clone()));
+                level_id
+            }
+        };
+
+        let mut seen_branches = HashSet::new();
+        let branches = row.branches.as_deref().unwrap_or_default();
+        for name in branches.split(';').map(str::trim).filter(|n| !n.is_empty()) {
+            let branch_key = name.to_lowercase();
+            if name.chars().count() > 100 {
+                log.error(
+                    ImportIssueCode::InvalidValue,
+                    line,
+                    "branches",
+                    format!("branch '{}' must be at most 100 characters", name),
+                );
+            } else if !seen_branches.insert(branch_key.clone()) {
+                log.error(
+                    ImportIssueCode::Duplicate,
+                    line,
+                    "branches",
+                    format!("branch '{}' is listed twice", name),
+                );
+            } else if lookups
+                .branches
+                .contains_key(&(level_id, branch_key.clone()))
+            {
+                log.warning(
+                    ImportIssueCode::AlreadyExists,
+                    line,
+                    "branches",
+                    format!("branch '{}' already exists in level '{}'", name, row.name),
+                );
+            } else {
+                let branch_id = BranchId::new();
+                lookups.branches.insert((level_id, branch_key), branch_id);
+                plan.branches.push((branch_id, level_id, name.to_string()));
+            }
+        }
+    }
+}
+
+/// Check the branches file against the levels file and the school, adding
+/// new branches to `lookups`.
+fn validate_branches(
+    rows: Vec<CsvRow<BranchImportRow>>,
+    lookups: &mut ImportLookups,
+    plan: &mut ImportPlan,
+    issues: &mut Vec<ImportIssue>,
+) {
+    let mut log = IssueLog {
+        dataset: ImportDataset::Branches,
+        issues,
+    };
+    let mut new_branches: HashSet<BranchId> = plan.branches.iter().map(|b| b.0).collect();
+
+    for (line, row) in rows {
+        let row = match row {
+            Ok(row) => row,
+            Err(message) => {
+                log.unreadable(line, message);
+                continue;
+            }
+        };
+
+        let level_id = lookups.levels.get(&row.level.to_lowercase()).copied();
+        if row.level.is_empty() {
+            log.error(
+                ImportIssueCode::MissingValue,
+                line,
+                "level",
+                "is required".to_string(),
+            );
+        } else if level_id.is_none() {
+            log.error(
+                ImportIssueCode::UnknownReference,
+                line,
+                "level",
+                format!(
+                    "no level named '{}' in the levels file or this school",
+                    row.level
+                ),
+            );
+        }
+        if row.name.is_empty() || row.name.chars().count() > 100 {
+            log.error(
+                ImportIssueCode::InvalidValue,
+                line,
+                "name",
+                "must be 1-100 characters".to_string(),
+            );
+            continue;
+        }
+        let Some(level_id) = level_id else {
+            continue;
+        };
+
+        let key = (level_id, row.name.to_lowercase());
+        match lookups.branches.get(&key) {
+            Some(branch_id) if new_branches.contains(branch_id) => {
+                log.error(
+                    ImportIssueCode::Duplicate,
+                    line,
+                    "name",
+                    format!(
+                        "branch '{}' of level '{}' is already in the dataset",
+                        row.name, row.level
+                    ),
+                );
+            }
+            Some(_) => {
+                log.warning(
+                    ImportIssueCode::AlreadyExists,
+                    line,
+                    "name",
+                    format!(
+                        "branch '{}' already exists in level '{}'",
+                        row.name, row.level
+                    ),
+                );
+            }
+            None => {
+                let branch_id = BranchId::new();
+                lookups.branches.insert(key, branch_id);
+                new_branches.insert(branch_id);
+                plan.branches.push((branch_id, level_id, row.name));
+            }
+        }
+    }
+}
+
+/// Check the staff file, returning the line each email first appears on.
+fn validate_staff(
+    rows: Vec<CsvRow<StaffImportRow>>,
+    lookups: &ImportLookups,
+    has_default_password: bool,
+    password_policy: &PasswordPolicy,
+    plan: &mut ImportPlan,
+    issues: &mut Vec<ImportIssue>,
+) -> HashMap<String, usize> {
+    let mut log = IssueLog {
+        dataset: ImportDataset::Staff,
+        issues,
+    };
+    let mut staff_lines = HashMap::new();
+
+    for (line, row) in rows {
+        let row = match row {
+            Ok(row) => row,
+            Err(message) => {
+                log.unreadable(line, message);
+                continue;
+            }
+        };
+        let errors_before = log.issues.len();
+
+        for (column, value) in [
+            ("first_name", &row.first_name),
+            ("last_name", &row.last_name),
+        ] {
+            if value.is_empty() || value.chars().count() > 100 {
+                log.error(
+                    ImportIssueCode::InvalidValue,
+                    line,
+                    column,
+                    "must be 1-100 characters".to_string(),
+                );
+            }
+        }
+
+        let email_key = row.email.to_lowercase();
+        if let Err(e) = Email::new(row.email.as_str()) {
+            log.error(ImportIssueCode::InvalidValue, line, "email", e.to_string());
+        } else if let Some(first_line) = staff_lines.get(&email_key) {
+            log.error(
+                ImportIssueCode::Duplicate,
+                line,
+                "email",
+                format!("duplicates the email on line {}", first_line),
+            );
+        } else if lookups.existing_emails.contains(&email_key) {
+            log.error(
+                ImportIssueCode::AlreadyExists,
+                line,
+                "email",
+                "is already registered".to_string(),
+            );
+        }
+        staff_lines.entry(email_key).or_insert(line);
+
+        let role_id = match row.role.as_deref().map(str::to_lowercase).as_deref() {
+            None | Some("teacher") => Some(system_roles::TEACHER),
+            Some("admin") => Some(system_roles::ADMIN),
+            Some(_) => {
+                log.error(
+                    ImportIssueCode::InvalidValue,
+                    line,
+                    "role",
+                    "must be 'teacher' or 'admin'".to_string(),
+                );
+                None
+            }
+        };
+
+        let branch_id = match (row.level.as_deref(), row.branch.as_deref()) {
+            (_, None) => {
+                if row.level.is_some() {
+                    log.warning(
+                        ImportIssueCode::MissingValue,
+                        line,
+                        "branch",
+                        "level is ignored without a branch".to_string(),
+                    );
+                }
+                None
+            }
+            (None, Some(_)) => {
+                log.error(
+                    ImportIssueCode::MissingValue,
+                    line,
+                    "level",
+                    "is required with a branch".to_string(),
+                );
+                None
+            }
+            (Some(level), Some(branch)) => {
+                let branch_id = lookups
+                    .levels
+                    .get(&level.to_lowercase())
+                    .and_then(|level_id| lookups.branches.get(&(*level_id, branch.to_lowercase())))
+                    .copied();
+                if branch_id.is_none() {
+                    log.error(
+                        ImportIssueCode::UnknownReference,
+                        line,
+                        "branch",
+                        format!(
+                            "no branch named '{}' in level '{}' in the dataset or this school",
+                            branch, level
+                        ),
+                    );
+                } else if role_id != Some(system_roles::TEACHER) {
+                    log.error(
+                        ImportIssueCode::PolicyViolation,
+                        line,
+                        "branch",
+                        "only teachers can lead a branch".to_string(),
+                    );
+                }
+                branch_id
+            }
+        };
+
+        match row.password.as_deref() {
+            Some(password) => {
+                let violations = password_policy
+                    .violations(password, &[&row.first_name, &row.last_name, &row.email]);
+                if !violations.is_empty() {
+                    log.error(
+                        ImportIssueCode::PolicyViolation,
+                        line,
+                        "password",
+                        violations.join("; "),
+                    );
+                }
+            }
+            None if !has_default_password => {
+                log.error(
+                    ImportIssueCode::MissingValue,
+                    line,
+                    "password",
+                    "is required when no default_password is given".to_string(),
+                );
+            }
+            None => {}
+        }
+
+        let has_errors = log.issues[errors_before..]
+            .iter()
+            .any(|i| i.severity == ImportIssueSeverity::Error);
+        if let (false, Some(role_id)) = (has_errors, role_id) {
+            plan.staff.push(ValidStaffRow {
+                first_name: row.first_name,
+                last_name: row.last_name,
+                email: row.email,
+                password: row.password,
+                role_id,
+                branch_id,
+            });
+        }
+    }
+
+    staff_lines
+}
+
+pub struct ImportValidationService;
+
+impl ImportValidationService {
+    /// Validate an import dataset without writing anything.
+    ///
+    /// Each file is checked on its own and against the others: branch,
+    /// staff, and student rows may use levels and branches from the levels
+    /// and branches files, and guardian rows must name a student from the
+    /// students file or one already registered in the school. Problems that
+    /// block an import are errors; ones that only change what an import
+    /// would do are warnings.
+    #[instrument(skip(db, files, password_policy))]
+    pub async fn validate(
+        db: &PgPool,
+        school_id: SchoolId,
+        files: ImportDatasetFiles,
+        password_policy: &PasswordPolicy,
+    ) -> Result<ImportValidationReport, AppError> {
+        let (report, _) = Self::check(db, school_id, &files, password_policy).await?;
+        Ok(report)
+    }
+
+    /// Validate a dataset and plan what importing it would write. The plan
+    /// is only complete when the report is valid.
+    async fn check(
+        db: &PgPool,
+        school_id: SchoolId,
+        files: &ImportDatasetFiles,
+        password_policy: &PasswordPolicy,
+    ) -> Result<(ImportValidationReport, ImportPlan), AppError> {
+        if files.is_empty() {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Upload at least one of the 'levels', 'branches', 'staff', 'students', or 'guardians' files"
+            )));
+        }
+
+        let level_rows = read_file::<LevelImportRow>(
+            "levels",
+            files.levels.as_deref(),
+            &LevelImportRow::REQUIRED_COLUMNS,
+            LevelImportRow::MAX_ROWS,
+        )?;
+        let branch_rows = read_file::<BranchImportRow>(
+            "branches",
+            files.branches.as_deref(),
+            &BranchImportRow::REQUIRED_COLUMNS,
+            BranchImportRow::MAX_ROWS,
+        )?;
+        let staff_rows = read_file::<StaffImportRow>(
+            "staff",
+            files.staff.as_deref(),
+            &StaffImportRow::REQUIRED_COLUMNS,
+            StaffImportRow::MAX_ROWS,
+        )?;
+        let student_rows = match files.students.as_deref() {
+            Some(bytes) => read_import_csv(bytes).map_err(|e| {
+                AppError::bad_request(anyhow::anyhow!("students file: {}", e.error))
+            })?,
+            None => Vec::new(),
+        };
+        let guardian_rows = read_file::<GuardianImportRow>(
+            "guardians",
+            files.guardians.as_deref(),
+            &GuardianImportRow::REQUIRED_COLUMNS,
+            GuardianImportRow::MAX_ROWS,
+        )?;
+
+        let rows = ImportRowCounts {
+            levels: level_rows.len(),
+            branches: branch_rows.len(),
+            staff: staff_rows.len(),
+            students: student_rows.len(),
+            guardians: guardian_rows.len(),
+        };
+
+        let emails: Vec<String> = student_rows
+            .iter()
+            .filter_map(|(_, row)| row.as_ref().ok())
+            .map(|row| row.email.to_lowercase())
+            .chain(
+                staff_rows
+                    .iter()
+                    .filter_map(|(_, row)| row.as_ref().ok())
+                    .map(|row| row.email.to_lowercase()),
+            )
+            .collect();
+        let mut lookups = StudentService::load_import_lookups(db, school_id, &emails).await?;
+
+        let mut issues = Vec::new();
+        let mut plan = ImportPlan::default();
+        validate_levels(level_rows, &mut lookups, &mut plan, &mut issues);
+        validate_branches(branch_rows, &mut lookups, &mut plan, &mut issues);
+
+        // The default password is reported with the first sheet that uses it
+        if let Some(password) = &files.default_password
+            && (!staff_rows.is_empty() || !student_rows.is_empty())
+        {
+            let violations = password_policy.violations(password, &[]);
+            if !violations.is_empty() {
+                IssueLog {
+                    dataset: if staff_rows.is_empty() {
+                        ImportDataset::Students
+                    } else {
+                        ImportDataset::Staff
+                    },
+                    issues: &mut issues,
+                }
+                .push(
+                    ImportIssueSeverity::Error,
+                    ImportIssueCode::PolicyViolation,
+                    None,
+                    Some("default_password"),
+                    violations.join("; "),
+                );
+            }
+        }
+
+        let staff_lines = validate_staff(
+            staff_rows,
+            &lookups,
+            files.default_password.is_some(),
+            password_policy,
+            &mut plan,
+            &mut issues,
+        );
+
+        // Student rows, with the line each email first appears on
+        let mut student_lines = HashMap::new();
+        if !student_rows.is_empty() {
+            let mut log = IssueLog {
+                dataset: ImportDataset::Students,
+                issues: &mut issues,
+            };
+
+            let mut errors = Vec::new();
+            for (line, row) in student_rows {
+                match row {
+                    Ok(row) => {
+                        if let Some(row) = validate_import_row(
+                            line,
+                            row,
+                            &lookups,
+                            files.default_password.is_some(),
+                            password_policy,
+                            &mut student_lines,
+                            &mut errors,
+                        ) {
+                            plan.students.push(row);
+                        }
+                    }
+                    Err(message) => log.unreadable(line, message),
+                }
+            }
+            for (email, line) in &student_lines {
+                if let Some(staff_line) = staff_lines.get(email) {
+                    log.error(
+                        ImportIssueCode::Duplicate,
+                        *line,
+                        "email",
+                        format!("is also used by the staff row on line {}", staff_line),
+                    );
+                }
+            }
+            for error in errors {
+                log.push(
+                    ImportIssueSeverity::Error,
+                    error.code,
+                    Some(error.line),
+                    error.column.as_deref(),
+                    error.message,
+                );
+            }
+        }
+
+        let guardian_student_emails: Vec<String> = guardian_rows
+            .iter()
+            .filter_map(|(_, row)| row.as_ref().ok())
+            .map(|row| row.student_email.to_lowercase())
+            .filter(|email| !student_lines.contains_key(email))
+            .collect();
+        let existing_students =
+            Self::load_existing_students(db, school_id, &guardian_student_emails).await?;
+
+        let mut log = IssueLog {
+            dataset: ImportDataset::Guardians,
+            issues: &mut issues,
+        };
+        // First valid guardian line for each student, and each student's
+        // guardians by name
+        let mut primary_guardians: HashMap<String, usize> = HashMap::new();
+        let mut seen_guardians: HashMap<(String, String), usize> = HashMap::new();
+        for (line, row) in guardian_rows {
+            let row = match row {
+                Ok(row) => row,
+                Err(message) => {
+                    log.unreadable(line, message);
+                    continue;
+                }
+            };
+            let errors_before = log.issues.len();
+
+            let student_key = row.student_email.to_lowercase();
+            let existing = existing_students.get(&student_key);
+            if row.student_email.is_empty() {
+                log.error(
+                    ImportIssueCode::MissingValue,
+                    line,
+                    "student_email",
+                    "is required".to_string(),
+                );
+            } else if !student_lines.contains_key(&student_key) && existing.is_none() {
+                log.error(
+                    ImportIssueCode::UnknownReference,
+                    line,
+                    "student_email",
+                    format!(
+                        "no student with email '{}' in the students file or this school",
+                        row.student_email
+                    ),
+                );
+            }
+
+            if row.name.is_empty() || row.name.chars().count() > 150 {
+                log.error(
+                    ImportIssueCode::InvalidValue,
+                    line,
+                    "name",
+                    "must be 1-150 characters".to_string(),
+                );
+            } else if let Some(first_line) =
+                seen_guardians.get(&(student_key.clone(), row.name.to_lowercase()))
+            {
+                log.error(
+                    ImportIssueCode::Duplicate,
+                    line,
+                    "name",
+                    format!("duplicates the guardian on line {}", first_line),
+                );
+            }
+            seen_guardians
+                .entry((student_key.clone(), row.name.to_lowercase()))
+                .or_insert(line);
+
+            if let Some(relationship) = &row.relationship
+                && relationship.chars().count() > 50
+            {
+                log.error(
+                    ImportIssueCode::InvalidValue,
+                    line,
+                    "relationship",
+                    "must be at most 50 characters".to_string(),
+                );
+            }
+            if let Some(phone) = &row.phone
+                && let Err(e) = PhoneNumber::new(phone.as_str())
+            {
+                log.error(ImportIssueCode::InvalidValue, line, "phone", e.to_string());
+            }
+            if let Some(email) = &row.email {
+                if let Err(e) = Email::new(email.as_str()) {
+                    log.error(ImportIssueCode::InvalidValue, line, "email", e.to_string());
+                } else if email.to_lowercase() == student_key {
+                    log.error(
+                        ImportIssueCode::PolicyViolation,
+                        line,
+                        "email",
+                        "must not be the student's own email".to_string(),
+                    );
+                }
+            }
+            if row.phone.is_none() && row.email.is_none() {
+                log.error(
+                    ImportIssueCode::PolicyViolation,
+                    line,
+                    "phone",
+                    "a guardian needs a phone or an email".to_string(),
+                );
+            }
+
+            if log.issues.len() > errors_before || row.student_email.is_empty() {
+                continue;
+            }
+
+            if let Some(first_line) = primary_guardians.get(&student_key) {
+                log.warning(
+                    ImportIssueCode::PolicyViolation,
+                    line,
+                    "student_email",
+                    format!(
+                        "the student already has a guardian on line {}, which becomes their emergency contact",
+                        first_line
+                    ),
+                );
+                continue;
+            }
+            primary_guardians.insert(student_key.clone(), line);
+
+            if existing.is_some_and(|s| s.has_emergency_contact) {
+                log.warning(
+                    ImportIssueCode::AlreadyExists,
+                    line,
+                    "student_email",
+                    "would replace the student's existing emergency contact".to_string(),
+                );
+            }
+            plan.guardians.push((student_key, row));
+        }
+
+        // Students without a guardian, when guardians are part of the dataset
+        if files.guardians.is_some() {
+            let mut log = IssueLog {
+                dataset: ImportDataset::Students,
+                issues: &mut issues,
+            };
+            let guardian_students: HashSet<&String> =
+                seen_guardians.keys().map(|(student, _)| student).collect();
+            for (email, line) in &student_lines {
+                if !guardian_students.contains(email) {
+                    log.warning(
+                        ImportIssueCode::PolicyViolation,
+                        *line,
+                        "email",
+                        "student has no guardian in the guardians file".to_string(),
+                    );
+                }
+            }
+        }
+
+        issues.sort_by_key(|issue| (issue.dataset, issue.line));
+
+        Ok((ImportValidationReport::new(rows, issues), plan))
+    }
+
+    /// Load the students of a school with the given lowercased emails.
+    async fn load_existing_students(
+        db: &PgPool,
+        school_id: SchoolId,
+        emails: &[String],
+    ) -> Result<HashMap<String, ExistingStudent>, AppError> {
+        let students = sqlx::query_as::<_, (String, bool)>(
+            r#"SELECT LOWER(u.email),
+                      COALESCE(p.emergency_contact_name IS NOT NULL, FALSE)
+               FROM users u
+               INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $3
+               LEFT JOIN student_medical_profiles p ON p.student_id = u.id
+               WHERE u.school_id = $1 AND LOWER(u.email) = ANY($2)"#,
+        )
+        .bind(school_id)
+        .bind(emails)
+        .bind(system_roles::STUDENT)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|(email, has_emergency_contact)| {
+            (
+                email,
+                ExistingStudent {
+                    has_emergency_contact,
+                },
+            )
+        })
+        .collect();
+
+        Ok(students)
+    }
+}
+
+/// The sheets of a dataset in the order they are written; each depends
+/// only on the ones before it.
+const IMPORT_PHASES: [ImportDataset; 5] = [
+    ImportDataset::Levels,
+    ImportDataset::Branches,
+    ImportDataset::Staff,
+    ImportDataset::Students,
+    ImportDataset::Guardians,
+];
+
+pub struct SchoolImportService;
+
+impl SchoolImportService {
+    /// Import a school dataset.
+    ///
+    /// The whole dataset is validated first, exactly as
+    /// [`ImportValidationService::validate`] does, and nothing is written
+    /// if it has errors or this is a dry run. A valid dataset is written
+    /// one sheet at a time, each in its own transaction, in the order of
+    /// [`IMPORT_PHASES`]. The first phase that fails is rolled back and the
+    /// rest are skipped; earlier phases stay committed.
+    ///
+    /// `can_create_branches` is whether the importer may create branches,
+    /// which the levels sheet can list alongside its levels.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(db, cache, files, password_policy))]
+    pub async fn import(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        imported_by: UserId,
+        can_create_branches: bool,
+        files: ImportDatasetFiles,
+        dry_run: bool,
+        password_policy: &PasswordPolicy,
+    ) -> Result<SchoolImportReport, AppError> {
+        let staff_fields = match files.staff {
+            Some(_) => Some(
+                StudentService::import_custom_fields(db, school_id, CustomFieldEntity::User)
+                    .await?,
+            ),
+            None => None,
+        };
+        let student_fields = match files.students {
+            Some(_) => Some(
+                StudentService::import_custom_fields(db, school_id, CustomFieldEntity::Student)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        let (validation, plan) =
+            ImportValidationService::check(db, school_id, &files, password_policy).await?;
+        if !plan.branches.is_empty() && !can_create_branches {
+            return Err(AppError::forbidden(format!(
+                "Access denied. Missing required permission: {}",
+                permissions::BRANCHES_CREATE
+            )));
+        }
+        let default_password = files.default_password.as_deref();
+
+        let mut phases = Vec::with_capacity(IMPORT_PHASES.len());
+        let mut run = validation.valid && !dry_run;
+        for dataset in IMPORT_PHASES {
+            if !run {
+                phases.push(ImportPhaseResult {
+                    dataset,
+                    status: ImportPhaseStatus::Skipped,
+                    created: 0,
+                    error: None,
+                });
+                continue;
+            }
+
+            let mut tx = db.begin().await?;
+            let written = match dataset {
+                ImportDataset::Levels => {
+                    Self::insert_levels(&mut tx, school_id, &plan.levels).await
+                }
+                ImportDataset::Branches => Self::insert_branches(&mut tx, &plan.branches).await,
+                ImportDataset::Staff => {
+                    Self::insert_staff(
+                        &mut tx,
+                        school_id,
+                        imported_by,
+                        &plan.staff,
+                        default_password,
+                        staff_fields.as_ref().unwrap_or(&Value::Null),
+                    )
+                    .await
+                }
+                ImportDataset::Students => StudentService::insert_import_rows(
+                    &mut tx,
+                    school_id,
+                    &plan.students,
+                    default_password,
+                    student_fields.as_ref().unwrap_or(&Value::Null),
+                )
+                .await
+                .map(|ids| ids.len()),
+                ImportDataset::Guardians => {
+                    Self::set_emergency_contacts(&mut tx, school_id, imported_by, &plan.guardians)
+                        .await
+                }
+            };
+            let written = match written {
+                Ok(created) => tx.commit().await.map(|_| created).map_err(AppError::from),
+                Err(e) => Err(e),
+            };
+
+            match written {
+                Ok(created) => {
+                    Self::invalidate(cache, school_id, dataset, &plan).await;
+                    phases.push(ImportPhaseResult {
+                        dataset,
+                        status: ImportPhaseStatus::Committed,
+                        created,
+                        error: None,
+                    });
+                }
+                Err(e) => {
+                    let message = if e.status.is_server_error() {
+                        error!(error = %e, ?dataset, "School import phase failed");
+                        "Could not write this sheet; nothing from it was imported".to_string()
+                    } else {
+                        e.error.to_string()
+                    };
+                    phases.push(ImportPhaseResult {
+                        dataset,
+                        status: ImportPhaseStatus::Failed,
+                        created: 0,
+                        error: Some(message),
+                    });
+                    run = false;
+                }
+            }
+        }
+
+        Ok(SchoolImportReport {
+            dry_run,
+            imported: phases
+                .iter()
+                .all(|p| p.status == ImportPhaseStatus::Committed),
+            validation,
+            phases,
+        })
+    }
+
+    async fn insert_levels(
+        conn: &mut PgConnection,
+        school_id: SchoolId,
+        levels: &[(LevelId, String)],
+    ) -> Result<usize, AppError> {
+        let result = sqlx::query(
+            r#"INSERT INTO levels (id, name, school_id)
+               SELECT t.id, t.name, $3
+               FROM UNNEST($1::uuid[], $2::text[]) AS t(id, name)"#,
+        )
+        .bind(levels.iter().map(|l| l.0).collect::<Vec<_>>())
+        .bind(levels.iter().map(|l| l.1.as_str()).collect::<Vec<_>>())
+        .bind(school_id)
+        .execute(conn)
+        .await
+        .map_err(|e| {
+            Self::conflict_error(e, "A level in the dataset was created during the import")
+        })?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn insert_branches(
+        conn: &mut PgConnection,
+        branches: &[(BranchId, LevelId, String)],
+    ) -> Result<usize, AppError> {
+        let result = sqlx::query(
+            r#"INSERT INTO branches (id, level_id, name)
+               SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::text[])"#,
+        )
+        .bind(branches.iter().map(|b| b.0).collect::<Vec<_>>())
+        .bind(branches.iter().map(|b| b.1).collect::<Vec<_>>())
+        .bind(branches.iter().map(|b| b.2.as_str()).collect::<Vec<_>>())
+        .execute(conn)
+        .await
+        .map_err(|e| {
+            Self::conflict_error(e, "A branch in the dataset was created during the import")
+        })?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    /// Create staff accounts with their roles, and make teachers with a
+    /// branch its lead teacher from today.
+    async fn insert_staff(
+        conn: &mut PgConnection,
+        school_id: SchoolId,
+        imported_by: UserId,
+        staff: &[ValidStaffRow],
+        default_password: Option<&str>,
+        custom_fields: &Value,
+    ) -> Result<usize, AppError> {
+        let mut hashes: HashMap<&str, String> = HashMap::new();
+        let mut password_hashes = Vec::with_capacity(staff.len());
+        for row in staff {
+            let password = row
+                .password
+                .as_deref()
+                .or(default_password)
+                .unwrap_or_default();
+            if !hashes.contains_key(password) {
+                hashes.insert(password, hash_password(password)?);
+            }
+            password_hashes.push(hashes[password].clone());
+        }
+        let user_ids: Vec<UserId> = staff.iter().map(|_| UserId::new()).collect();
+
+        sqlx::query(
+            r#"INSERT INTO users (id, first_name, last_name, email, password, school_id, custom_fields)
+               SELECT t.id, t.first_name, t.last_name, t.email, t.password, $6, $7
+               FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::text[], $5::text[])
+                    AS t(id, first_name, last_name, email, password)"#,
+        )
+        .bind(&user_ids)
+        .bind(staff.iter().map(|r| r.first_name.as_str()).collect::<Vec<_>>())
+        .bind(staff.iter().map(|r| r.last_name.as_str()).collect::<Vec<_>>())
+        .bind(staff.iter().map(|r| r.email.as_str()).collect::<Vec<_>>())
+        .bind(password_hashes)
+        .bind(school_id)
+        .bind(custom_fields)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            Self::conflict_error(e, "An email in the staff file was registered during the import")
+        })?;
+
+        sqlx::query(
+            "INSERT INTO user_roles (user_id, role_id) SELECT * FROM UNNEST($1::uuid[], $2::uuid[])",
+        )
+        .bind(&user_ids)
+        .bind(staff.iter().map(|r| r.role_id).collect::<Vec<_>>())
+        .execute(&mut *conn)
+        .await?;
+
+        let (leads, branches): (Vec<UserId>, Vec<BranchId>) = user_ids
+            .iter()
+            .zip(staff)
+            .filter_map(|(id, row)| row.branch_id.map(|branch_id| (*id, branch_id)))
+            .unzip();
+        sqlx::query(
+            r#"INSERT INTO branch_teachers (branch_id, teacher_id, capacity, starts_on, assigned_by)
+               SELECT t.branch_id, t.teacher_id, 'lead', CURRENT_DATE, $3
+               FROM UNNEST($1::uuid[], $2::uuid[]) AS t(branch_id, teacher_id)"#,
+        )
+        .bind(branches)
+        .bind(leads)
+        .bind(imported_by)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(user_ids.len())
+    }
+
+    /// Make each guardian their student's emergency contact. Students that
+    /// were removed since validation are skipped.
+    async fn set_emergency_contacts(
+        conn: &mut PgConnection,
+        school_id: SchoolId,
+        imported_by: UserId,
+        guardians: &[(String, GuardianImportRow)],
+    ) -> Result<usize, AppError> {
+        let emails: Vec<&str> = guardians.iter().map(|(email, _)| email.as_str()).collect();
+        let student_ids: HashMap<String, UserId> = sqlx::query_as::<_, (UserId, String)>(
+            r#"SELECT u.id, LOWER(u.email)
+               FROM users u
+               INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $3
+               WHERE u.school_id = $1 AND LOWER(u.email) = ANY($2)"#,
+        )
+        .bind(school_id)
+        .bind(&emails)
+        .bind(system_roles::STUDENT)
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .map(|(id, email)| (email, id))
+        .collect();
+
+        let contacts: Vec<(UserId, &GuardianImportRow)> = guardians
+            .iter()
+            .filter_map(|(email, row)| student_ids.get(email).map(|id| (*id, row)))
+            .collect();
+        let result = sqlx::query(
+            r#"INSERT INTO student_medical_profiles
+                   (student_id, school_id, emergency_contact_name, emergency_contact_relationship,
+                    emergency_contact_phone, emergency_contact_email, updated_by)
+               SELECT t.student_id, $6, t.name, t.relationship, t.phone, t.email, $7
+               FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::text[], $5::text[])
+                    AS t(student_id, name, relationship, phone, email)
+               ON CONFLICT (student_id) DO UPDATE SET
+                   emergency_contact_name = EXCLUDED.emergency_contact_name,
+                   emergency_contact_relationship = EXCLUDED.emergency_contact_relationship,
+                   emergency_contact_phone = EXCLUDED.emergency_contact_phone,
+                   emergency_contact_email = EXCLUDED.emergency_contact_email,
+                   updated_by = EXCLUDED.updated_by,
+                   updated_at = NOW()"#,
+        )
+        .bind(contacts.iter().map(|c| c.0).collect::<Vec<_>>())
+        .bind(
+            contacts
+                .iter()
+                .map(|c| c.1.name.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            contacts
+                .iter()
+                .map(|c| c.1.relationship.as_deref())
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            contacts
+                .iter()
+                .map(|c| c.1.phone.as_deref())
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            contacts
+                .iter()
+                .map(|c| c.1.email.as_deref())
+                .collect::<Vec<_>>(),
+        )
+        .bind(school_id)
+        .bind(imported_by)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    fn conflict_error(e: sqlx::Error, message: &'static str) -> AppError {
+        if let sqlx::Error::Database(db_err) = &e
+            && db_err.is_unique_violation()
+        {
+            return AppError::bad_request(anyhow::anyhow!(message));
+        }
+        AppError::from(e)
+    }
+
+    async fn invalidate(
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        dataset: ImportDataset,
+        plan: &ImportPlan,
+    ) {
+        match dataset {
+            ImportDataset::Levels => {
+                invalidate::level(cache, None, Some(school_id.into())).await;
+            }
+            ImportDataset::Branches => {
+                let level_ids: HashSet<LevelId> = plan.branches.iter().map(|b| b.1).collect();
+                for level_id in level_ids {
+                    invalidate::branch(cache, None, Some(level_id.into()), Some(school_id.into()))
+                        .await;
+                }
+            }
+            ImportDataset::Staff | ImportDataset::Students => {
+                invalidate::user(cache, None, Some(school_id.into())).await;
+            }
+            ImportDataset::Guardians => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use chalkbyte_models::ids::UserId;
+
+    async fn create_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar("INSERT INTO schools (name) VALUES ('Import School') RETURNING id")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_validate_requires_a_file(pool: PgPool) {
+        let school_id = create_school(&pool).await;
+
+        let err = ImportValidationService::validate(
+            &pool,
+            school_id,
+            Default::default(),
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_validate_cross_checks_files(pool: PgPool) {
+        let school_id = create_school(&pool).await;
+        let level_id: LevelId = sqlx::query_scalar(
+            "INSERT INTO levels (name, school_id) VALUES ('JSS 1', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO branches (name, level_id) VALUES ('A', $1)")
+            .bind(level_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let student_id: UserId = sqlx::query_scalar(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Bisi', 'Ade', 'bisi@example.com', $1) RETURNING id"#,
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(student_id)
+            .bind(system_roles::STUDENT)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            r#"INSERT INTO student_medical_profiles (student_id, school_id, emergency_contact_name)
+               VALUES ($1, $2, 'Funke Ade')"#,
+        )
+        .bind(student_id)
+        .bind(school_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let levels = "name,branches\nJSS 2,A;B;a\njss 1,A;C\nJSS 2,\n";
+        let students = "first_name,last_name,email,level,branch\n\
+            Ada,Obi,ada@example.com,JSS 2,B\n\
+            Tunde,Bello,tunde@example.com,JSS 1,C\n\
+            Kemi,Eze,kemi@example.com,JSS 3,\n";
+        let guardians = "student_email,name,relationship,phone,email\n\
+            ada@example.com,Ngozi Obi,Mother,,ngozi@example.com\n\
+            ada@example.com,Emeka Obi,Father,+2348012345678,\n\
+            ghost@example.com,Someone,,,someone@example.com\n\
+            bisi@example.com,Bola Ade,,,\n\
+            bisi@example.com,Dayo Ade,,,dayo@example.com\n";
+
+        let report = ImportValidationService::validate(
+            &pool,
+            school_id,
+            ImportDatasetFiles {
+                levels: Some(levels.as_bytes().to_vec()),
+                students: Some(students.as_bytes().to_vec()),
+                guardians: Some(guardians.as_bytes().to_vec()),
+                default_password: Some("testpass123".to_string()),
+                ..Default::default()
+            },
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        use ImportDataset::*;
+        use ImportIssueCode::*;
+        use ImportIssueSeverity::*;
+        let found: Vec<_> = report
+            .issues
+            .iter()
+            .map(|i| (i.dataset, i.line.unwrap(), i.code, i.severity))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (Levels, 2, Duplicate, Error),
+                (Levels, 3, AlreadyExists, Warning),
+                (Levels, 3, AlreadyExists, Warning),
+                (Levels, 4, Duplicate, Error),
+                (Students, 3, PolicyViolation, Warning),
+                (Students, 4, UnknownReference, Error),
+                (Students, 4, PolicyViolation, Warning),
+                (Guardians, 3, PolicyViolation, Warning),
+                (Guardians, 4, UnknownReference, Error),
+                (Guardians, 5, PolicyViolation, Error),
+                (Guardians, 6, AlreadyExists, Warning),
+            ]
+        );
+        assert!(!report.valid);
+        assert_eq!((report.error_count, report.warning_count), (5, 6));
+        assert_eq!(
+            (
+                report.rows.levels,
+                report.rows.students,
+                report.rows.guardians
+            ),
+            (3, 3, 5)
+        );
+
+        let users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE school_id = $1")
+            .bind(school_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(users, 1);
+    }
+
+    fn school_dataset(students: &str) -> ImportDatasetFiles {
+        ImportDatasetFiles {
+            levels: Some(b"name,branches\nJSS 1,A\n".to_vec()),
+            branches: Some(b"level,name\nJSS 1,B\n".to_vec()),
+            staff: Some(
+                b"first_name,last_name,email,role,level,branch\n\
+                  Tayo,Ajayi,tayo@example.com,teacher,JSS 1,B\n\
+                  Amaka,Nwosu,amaka@example.com,admin,,\n"
+                    .to_vec(),
+            ),
+            students: Some(students.as_bytes().to_vec()),
+            guardians: Some(
+                b"student_email,name,relationship,phone,email\n\
+                  ada@example.com,Ngozi Obi,Mother,+2348012345678,\n"
+                    .to_vec(),
+            ),
+            default_password: Some("testpass123".to_string()),
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_import_writes_sheets_in_dependency_order(pool: PgPool) {
+        let school_id = create_school(&pool).await;
+        let importer: UserId = sqlx::query_scalar(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Head', 'Admin', 'head@example.com', $1) RETURNING id"#,
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let report = SchoolImportService::import(
+            &pool,
+            None,
+            school_id,
+            importer,
+            true,
+            school_dataset(
+                "first_name,last_name,email,level,branch\nAda,Obi,ada@example.com,JSS 1,B\n",
+            ),
+            false,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(report.validation.valid, "{:?}", report.validation.issues);
+        assert!(report.imported);
+        let phases: Vec<_> = report
+            .phases
+            .iter()
+            .map(|p| (p.dataset, p.status, p.created))
+            .collect();
+        use ImportDataset::*;
+        use ImportPhaseStatus::*;
+        assert_eq!(
+            phases,
+            [
+                (Levels, Committed, 1),
+                (Branches, Committed, 2),
+                (Staff, Committed, 2),
+                (Students, Committed, 1),
+                (Guardians, Committed, 1),
+            ]
+        );
+
+        let (branch, contact): (String, Option<String>) = sqlx::query_as(
+            r#"SELECT b.name, p.emergency_contact_name
+               FROM users u
+               INNER JOIN branches b ON b.id = u.branch_id
+               LEFT JOIN student_medical_profiles p ON p.student_id = u.id
+               WHERE u.email = 'ada@example.com'"#,
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            (branch.as_str(), contact.as_deref()),
+            ("B", Some("Ngozi Obi"))
+        );
+
+        let lead: String = sqlx::query_scalar(
+            r#"SELECT u.email FROM branch_teachers bt
+               INNER JOIN users u ON u.id = bt.teacher_id
+               INNER JOIN branches b ON b.id = bt.branch_id
+               WHERE b.name = 'B' AND bt.capacity = 'lead'"#,
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(lead, "tayo@example.com");
+
+        let admin_roles: i64 = sqlx::query_scalar(
+            r#"SELECT COUNT(*) FROM user_roles ur INNER JOIN users u ON u.id = ur.user_id
+               WHERE u.email = 'amaka@example.com' AND ur.role_id = $1"#,
+        )
+        .bind(system_roles::ADMIN)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(admin_roles, 1);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_import_writes_nothing_for_invalid_datasets(pool: PgPool) {
+        let school_id = create_school(&pool).await;
+        let importer: UserId = sqlx::query_scalar(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Head', 'Admin', 'head@example.com', $1) RETURNING id"#,
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        // A student reusing a staff email and naming an unknown branch
+        let invalid = school_dataset(
+            "first_name,last_name,email,level,branch\n\
+             Ada,Obi,ada@example.com,JSS 1,C\n\
+             Tayo,Ajayi,tayo@example.com,,\n",
+        );
+        let report = SchoolImportService::import(
+            &pool,
+            None,
+            school_id,
+            importer,
+            true,
+            invalid,
+            false,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        let found: Vec<_> = report
+            .validation
+            .issues
+            .iter()
+            .map(|i| (i.dataset, i.line.unwrap(), i.code))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    ImportDataset::Students,
+                    2,
+                    ImportIssueCode::UnknownReference
+                ),
+                (ImportDataset::Students, 3, ImportIssueCode::Duplicate),
+                (ImportDataset::Students, 3, ImportIssueCode::PolicyViolation),
+            ]
+        );
+        assert!(!report.imported);
+        assert!(
+            report
+                .phases
+                .iter()
+                .all(|p| p.status == ImportPhaseStatus::Skipped)
+        );
+
+        let dry_run = SchoolImportService::import(
+            &pool,
+            None,
+            school_id,
+            importer,
+            true,
+            school_dataset(
+                "first_name,last_name,email,level,branch\nAda,Obi,ada@example.com,JSS 1,B\n",
+            ),
+            true,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap();
+        assert!(dry_run.validation.valid && !dry_run.imported);
+
+        let levels: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM levels WHERE school_id = $1")
+            .bind(school_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(levels, 0);
+
+        let err = SchoolImportService::import(
+            &pool,
+            None,
+            school_id,
+            importer,
+            false,
+            school_dataset("first_name,last_name,email\n"),
+            false,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,167 @@
+use axum::{
+    Json,
+    extract::{Multipart, Query, State},
+    http::header,
+    response::IntoResponse,
+};
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, permissions};
+
+use crate::middleware::auth::{RequireInteropExport, RequireInteropImport};
+use crate::modules::auth::controller::ErrorResponse;
+use crate::modules::interop::model::{
+    OneRosterExportParams, OneRosterImportForm, OneRosterImportParams, OneRosterImportReport,
+};
+use crate::modules::interop::service::{OneRosterFiles, OneRosterService};
+use crate::state::AppState;
+use crate::utils::auth_helpers::get_school_id_for_scoped_operation;
+
+/// Read the files and default password of a OneRoster import. Fields are
+/// matched by file name, with or without `.csv` and ignoring case.
+async fn read_files(mut multipart: Multipart) -> Result<OneRosterFiles, AppError> {
+    let mut files = OneRosterFiles::default();
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::bad_request(anyhow::anyhow!("Invalid multipart body: {}", e)))?
+    {
+        let Some(name) = field.name().map(str::to_ascii_lowercase) else {
+            continue;
+        };
+        let slot = match name.strip_suffix(".csv").unwrap_or(&name) {
+            "courses" => &mut files.courses,
+            "classes" => &mut files.classes,
+            "users" => &mut files.users,
+            "enrollments" => &mut files.enrollments,
+            "default_password" => {
+                let text = field.text().await.map_err(|e| {
+                    AppError::bad_request(anyhow::anyhow!("Could not read default_password: {}", e))
+                })?;
+                if !text.is_empty() {
+                    files.default_password = Some(text);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        let bytes = field.bytes().await.map_err(|e| {
+            AppError::bad_request(anyhow::anyhow!("Could not read {}: {}", name, e))
+        })?;
+        *slot = Some(bytes.to_vec());
+    }
+    Ok(files)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/interop/oneroster/export",
+    summary = "Export OneRoster roster",
+    description = "Download the school's roster as a OneRoster 1.1 CSV bulk export: a ZIP of \
+        `manifest.csv`, `orgs.csv`, `academicSessions.csv`, `courses.csv`, `classes.csv`, \
+        `users.csv`, and `enrollments.csv`. The school is the org; academic sessions are school \
+        years and their terms are terms; levels are courses of the active session; branches \
+        are homeroom classes in the active session's terms; admins, teachers, students, and \
+        guardians are users, with guardians and students linked through `agentSourcedIds`. \
+        Students are enrolled in their branch, and current and upcoming branch teachers in \
+        theirs, with lead teachers as primary.",
+    params(OneRosterExportParams),
+    responses(
+        (status = 200, description = "OneRoster CSV bulk export", content_type = "application/zip", body = Vec<u8>),
+        (status = 400, description = "Missing school_id for system admin", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires interop:export permission", body = ErrorResponse),
+        (status = 404, description = "School not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Interop"
+)]
+#[instrument(skip(state))]
+pub async fn export_oneroster(
+    State(state): State<AppState>,
+    RequireInteropExport(auth_user): RequireInteropExport,
+    Query(params): Query<OneRosterExportParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let zip = OneRosterService::export(&state.db, school_id).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"oneroster.zip\"",
+            ),
+        ],
+        zip,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/interop/oneroster/import",
+    summary = "Import OneRoster roster",
+    description = "Upload any of a OneRoster 1.1 `courses`, `classes`, `users`, and \
+        `enrollments` CSV file to import them as a school dataset. Courses become levels and \
+        classes become branches of their course's level; teachers and administrators become \
+        staff, students become students, and parents, guardians, and relatives become the \
+        guardians of the students their `agentSourcedIds` link them to. Students are placed in \
+        the class of their first enrollment, and teachers lead the class of their first \
+        primary enrollment. Headers are matched ignoring case and punctuation, unknown columns \
+        are ignored, rows marked `tobedeleted` or with `enabledUser` false are left out, and \
+        rows that cannot be used, such as users whose email is already registered, are skipped \
+        with a warning. Use `org` to import one school's rows from a multi-school export. The \
+        mapped dataset is validated and written as a school dataset import is, and `dry_run=true` \
+        only reports the issues, each traced back to its file, line, and `sourcedId`.",
+    params(OneRosterImportParams),
+    request_body(content = OneRosterImportForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Issues found and the outcome of each phase", body = OneRosterImportReport),
+        (status = 400, description = "No files, a file is unreadable or missing required columns, or nothing in the files can be imported", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires interop:import and the create permission for each mapped sheet: levels:create, branches:create, users:create, or students:create", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Interop"
+)]
+#[instrument(skip(state, auth_user, multipart))]
+pub async fn import_oneroster(
+    State(state): State<AppState>,
+    RequireInteropImport(auth_user): RequireInteropImport,
+    Query(params): Query<OneRosterImportParams>,
+    multipart: Multipart,
+) -> Result<Json<OneRosterImportReport>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+
+    let files = read_files(multipart).await?;
+    let dataset = OneRosterService::map(&state.db, &files, params.org.as_deref()).await?;
+    for permission in dataset.files.required_permissions() {
+        if !auth_user.has_permission(permission) {
+            return Err(AppError::forbidden(format!(
+                "Access denied. Missing required permission: {}",
+                permission
+            )));
+        }
+    }
+
+    let report = OneRosterService::import(
+        &state.db,
+        state.cache.as_ref(),
+        school_id,
+        auth_user.user_id()?,
+        auth_user.has_permission(permissions::BRANCHES_CREATE),
+        dataset,
+        params.dry_run,
+        &state.password_policy,
+    )
+    .await?;
+    Ok(Json(report))
+}
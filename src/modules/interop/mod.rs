@@ -0,0 +1,18 @@
+//! Roster interoperability module.
+//!
+//! Exports a school's roster as a OneRoster 1.1 CSV bulk export, a ZIP of
+//! the orgs, academic sessions, courses, classes, users, and enrollments
+//! files that LMS products read, with levels as courses and branches as
+//! classes.
+//!
+//! Imports OneRoster files from other systems through the school dataset
+//! import: courses, classes, users, and enrollments are mapped to levels,
+//! branches, staff, students, and guardians, then validated and written
+//! the way a school dataset is. The importer is tolerant of how LMS exports
+//! differ, skipping rows it cannot use with a warning, and reports every
+//! problem against the OneRoster file, line, and `sourcedId` it came from.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Interoperability data models.
+//!
+//! This module re-exports interop models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all interop models from the shared crate
+pub use chalkbyte_models::interop::*;
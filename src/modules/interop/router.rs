@@ -0,0 +1,16 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{export_oneroster, import_oneroster};
+
+/// Initialize the interop router
+/// Routes: GET /oneroster/export, POST /oneroster/import
+pub fn init_interop_router() -> Router<AppState> {
+    Router::new()
+        .route("/oneroster/export", get(export_oneroster))
+        .route("/oneroster/import", post(import_oneroster))
+}
@@ -0,0 +1,1658 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_cache::RedisCache;
+use chalkbyte_core::{AppError, PasswordPolicy};
+use chalkbyte_models::ids::{BranchId, LevelId, SchoolId, UserId};
+
+use crate::modules::imports::model::{ImportDataset, ImportIssueCode, ImportIssueSeverity};
+use crate::modules::imports::service::{ImportDatasetFiles, SchoolImportService};
+use crate::modules::interop::model::{
+    OneRosterClassRow, OneRosterCourseRow, OneRosterEnrollmentRow, OneRosterFile,
+    OneRosterImportReport, OneRosterIssue, OneRosterUserRow,
+};
+use crate::modules::users::model::system_roles;
+use crate::utils::zip::StoredZip;
+
+/// Most data rows read from each file of an import.
+const MAX_COURSES: usize = 200;
+const MAX_CLASSES: usize = 1000;
+const MAX_USERS: usize = 3500;
+const MAX_ENROLLMENTS: usize = 10_000;
+
+/// Files of a OneRoster import, as uploaded.
+#[derive(Debug, Default)]
+pub struct OneRosterFiles {
+    pub courses: Option<Vec<u8>>,
+    pub classes: Option<Vec<u8>>,
+    pub users: Option<Vec<u8>>,
+    pub enrollments: Option<Vec<u8>>,
+    pub default_password: Option<String>,
+}
+
+/// The row of a OneRoster file a mapped dataset row came from.
+#[derive(Debug, Clone, PartialEq)]
+struct Origin {
+    file: OneRosterFile,
+    line: usize,
+    sourced_id: Option<String>,
+}
+
+/// OneRoster files mapped to a school import dataset, with the problems
+/// found while mapping and where each dataset row came from.
+#[derive(Debug, Default)]
+pub struct OneRosterDataset {
+    pub files: ImportDatasetFiles,
+    issues: Vec<OneRosterIssue>,
+    /// Origins of each sheet's data rows, in order
+    origins: HashMap<ImportDataset, Vec<Origin>>,
+}
+
+/// Rows of a OneRoster file with their line numbers.
+type Rows<T> = Vec<(usize, T)>;
+
+/// Read a OneRoster file tolerantly: headers are compared with case,
+/// spaces, and punctuation removed, so `sourcedId`, `SourcedID`, and
+/// `sourced_id` all match, a byte order mark is ignored, and rows that
+/// cannot be read are skipped with a warning.
+fn read_file<T: DeserializeOwned>(
+    file: OneRosterFile,
+    bytes: &[u8],
+    required_columns: &[&str],
+    max_rows: usize,
+    issues: &mut Vec<OneRosterIssue>,
+) -> Result<Rows<T>, AppError> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(bytes);
+
+    let headers: csv::StringRecord = reader
+        .headers()
+        .map_err(|e| {
+            AppError::bad_request(anyhow::anyhow!(
+                "Could not read the header of {}: {}",
+                file.file_name(),
+                e
+            ))
+        })?
+        .iter()
+        .map(|h| {
+            h.chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+                .to_ascii_lowercase()
+        })
+        .collect();
+
+    let missing: Vec<&str> = required_columns
+        .iter()
+        .copied()
+        .filter(|column| !headers.iter().any(|h| h == *column))
+        .collect();
+    if !missing.is_empty() {
+        return Err(AppError::bad_request(anyhow::anyhow!(
+            "{} is missing required columns: {}",
+            file.file_name(),
+            missing.join(", ")
+        )));
+    }
+
+    let mut rows = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        if index == max_rows {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "{} has more than {} rows",
+                file.file_name(),
+                max_rows
+            )));
+        }
+
+        let line = index + 2;
+        let row = record.map_err(|e| e.to_string()).and_then(|record| {
+            let line = record.position().map_or(line, |p| p.line() as usize);
+            record
+                .deserialize::<T>(Some(&headers))
+                .map(|row| (line, row))
+                .map_err(|e| e.to_string())
+        });
+        match row {
+            Ok(row) => rows.push(row),
+            Err(message) => issues.push(OneRosterIssue {
+                file,
+                severity: ImportIssueSeverity::Warning,
+                code: ImportIssueCode::UnreadableRow,
+                line: Some(line),
+                sourced_id: None,
+                message: format!("Skipped: {}", message),
+            }),
+        }
+    }
+
+    Ok(rows)
+}
+
+/// Whether a row is current: bulk files leave `status` empty and delta
+/// files mark removed rows `tobedeleted`.
+fn is_active(status: Option<&str>) -> bool {
+    !status.is_some_and(|s| s.eq_ignore_ascii_case("tobedeleted"))
+}
+
+/// Values of a list column, which OneRoster separates with `,`.
+fn split_list(value: Option<&str>) -> impl Iterator<Item = &str> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+/// Whether a row belongs to the org being imported: rows that do not say
+/// belong to every org.
+fn in_org(org: Option<&str>, value: Option<&str>) -> bool {
+    match (org, value) {
+        (Some(org), Some(value)) if !value.is_empty() => split_list(Some(value)).any(|v| v == org),
+        _ => true,
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Which kind of school account a OneRoster user role becomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UserKind {
+    Student,
+    Teacher,
+    Admin,
+    Guardian,
+}
+
+impl UserKind {
+    fn from_role(role: &str) -> Option<Self> {
+        match role.to_ascii_lowercase().as_str() {
+            "student" => Some(Self::Student),
+            "teacher" => Some(Self::Teacher),
+            "administrator" => Some(Self::Admin),
+            "parent" | "guardian" | "relative" => Some(Self::Guardian),
+            _ => None,
+        }
+    }
+}
+
+/// A user row being mapped.
+struct MappedUser {
+    origin: Origin,
+    kind: UserKind,
+    role: String,
+    row: OneRosterUserRow,
+    email: Option<String>,
+    /// Whether the account is written; registered users are only referred to
+    write: bool,
+    /// Branch as (level name, branch name): where a student sits or the
+    /// branch a teacher leads
+    branch: Option<(String, String)>,
+}
+
+/// Collects mapping problems.
+struct IssueLog<'a>(&'a mut Vec<OneRosterIssue>);
+
+impl IssueLog<'_> {
+    fn warn(&mut self, origin: &Origin, code: ImportIssueCode, message: String) {
+        self.0.push(OneRosterIssue {
+            file: origin.file,
+            severity: ImportIssueSeverity::Warning,
+            code,
+            line: Some(origin.line),
+            sourced_id: origin.sourced_id.clone(),
+            message,
+        });
+    }
+}
+
+#[derive(Serialize)]
+struct LevelSheetRow<'a> {
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+struct BranchSheetRow<'a> {
+    level: &'a str,
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+struct StaffSheetRow<'a> {
+    first_name: &'a str,
+    last_name: &'a str,
+    email: &'a str,
+    role: &'static str,
+    password: Option<&'a str>,
+    level: Option<&'a str>,
+    branch: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct StudentSheetRow<'a> {
+    first_name: &'a str,
+    last_name: &'a str,
+    email: &'a str,
+    grade_level: Option<&'a str>,
+    level: Option<&'a str>,
+    branch: Option<&'a str>,
+    password: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct GuardianSheetRow<'a> {
+    student_email: &'a str,
+    name: String,
+    relationship: &'a str,
+    phone: Option<&'a str>,
+    email: Option<&'a str>,
+}
+
+/// Write sheet rows as CSV, or `None` when there are none.
+fn write_sheet<T: Serialize>(rows: &[T]) -> Result<Option<Vec<u8>>, AppError> {
+    if rows.is_empty() {
+        return Ok(None);
+    }
+    write_csv(rows).map(Some)
+}
+
+/// Map OneRoster files to a school import dataset.
+///
+/// Courses become levels and classes become branches of their course's
+/// level, with repeated titles merged. Students are placed in the class
+/// of their first student enrollment, and teachers lead the class of their
+/// first primary teacher enrollment. Guardians, from `parent`, `guardian`,
+/// and `relative` users, are linked to students through `agentSourcedIds`
+/// on either side. Users whose email is in `registered` already have an
+/// account and are left unchanged.
+///
+/// Nothing here fails the import: rows that cannot be used are skipped
+/// with a warning saying why.
+fn map_files(
+    files: &OneRosterFiles,
+    org: Option<&str>,
+    registered: &HashSet<String>,
+) -> Result<OneRosterDataset, AppError> {
+    let mut issues = Vec::new();
+    let courses: Rows<OneRosterCourseRow> = match &files.courses {
+        Some(bytes) => read_file(
+            OneRosterFile::Courses,
+            bytes,
+            &OneRosterCourseRow::REQUIRED_COLUMNS,
+            MAX_COURSES,
+            &mut issues,
+        )?,
+        None => Vec::new(),
+    };
+    let classes: Rows<OneRosterClassRow> = match &files.classes {
+        Some(bytes) => read_file(
+            OneRosterFile::Classes,
+            bytes,
+            &OneRosterClassRow::REQUIRED_COLUMNS,
+            MAX_CLASSES,
+            &mut issues,
+        )?,
+        None => Vec::new(),
+    };
+    let users: Rows<OneRosterUserRow> = match &files.users {
+        Some(bytes) => read_file(
+            OneRosterFile::Users,
+            bytes,
+            &OneRosterUserRow::REQUIRED_COLUMNS,
+            MAX_USERS,
+            &mut issues,
+        )?,
+        None => Vec::new(),
+    };
+    let enrollments: Rows<OneRosterEnrollmentRow> = match &files.enrollments {
+        Some(bytes) => read_file(
+            OneRosterFile::Enrollments,
+            bytes,
+            &OneRosterEnrollmentRow::REQUIRED_COLUMNS,
+            MAX_ENROLLMENTS,
+            &mut issues,
+        )?,
+        None => Vec::new(),
+    };
+    let mut log = IssueLog(&mut issues);
+
+    // Courses to levels, merging repeated titles
+    let mut levels: Vec<(String, Origin)> = Vec::new();
+    let mut level_index: HashMap<String, usize> = HashMap::new();
+    let mut course_levels: HashMap<String, String> = HashMap::new();
+    for (line, course) in courses {
+        let origin = Origin {
+            file: OneRosterFile::Courses,
+            line,
+            sourced_id: Some(course.sourced_id.clone()),
+        };
+        if !is_active(course.status.as_deref()) || !in_org(org, course.org_sourced_id.as_deref()) {
+            continue;
+        }
+        let Some(title) = non_empty(course.title.as_deref()) else {
+            log.warn(
+                &origin,
+                ImportIssueCode::MissingValue,
+                "Skipped: no title".into(),
+            );
+            continue;
+        };
+        if course_levels.contains_key(&course.sourced_id) {
+            log.warn(
+                &origin,
+                ImportIssueCode::Duplicate,
+                "Skipped: sourcedId appears earlier in the file".into(),
+            );
+            continue;
+        }
+        let index = *level_index.entry(title.to_lowercase()).or_insert_with(|| {
+            levels.push((title.to_string(), origin));
+            levels.len() - 1
+        });
+        course_levels.insert(course.sourced_id, levels[index].0.clone());
+    }
+
+    // Classes to branches of their course's level
+    let mut branches: Vec<(String, String, Origin)> = Vec::new();
+    let mut branch_index: HashMap<(String, String), usize> = HashMap::new();
+    let mut class_branches: HashMap<String, (String, String)> = HashMap::new();
+    for (line, class) in classes {
+        let origin = Origin {
+            file: OneRosterFile::Classes,
+            line,
+            sourced_id: Some(class.sourced_id.clone()),
+        };
+        if !is_active(class.status.as_deref()) || !in_org(org, class.school_sourced_id.as_deref()) {
+            continue;
+        }
+        let Some(title) = non_empty(class.title.as_deref()) else {
+            log.warn(
+                &origin,
+                ImportIssueCode::MissingValue,
+                "Skipped: no title".into(),
+            );
+            continue;
+        };
+        let Some(level) = non_empty(class.course_sourced_id.as_deref())
+            .and_then(|course| course_levels.get(course))
+        else {
+            log.warn(
+                &origin,
+                ImportIssueCode::UnknownReference,
+                format!(
+                    "Skipped: course '{}' is not in courses.csv",
+                    class.course_sourced_id.as_deref().unwrap_or_default()
+                ),
+            );
+            continue;
+        };
+        if class_branches.contains_key(&class.sourced_id) {
+            log.warn(
+                &origin,
+                ImportIssueCode::Duplicate,
+                "Skipped: sourcedId appears earlier in the file".into(),
+            );
+            continue;
+        }
+        let index = *branch_index
+            .entry((level.to_lowercase(), title.to_lowercase()))
+            .or_insert_with(|| {
+                branches.push((level.clone(), title.to_string(), origin));
+                branches.len() - 1
+            });
+        let (level, name, _) = &branches[index];
+        class_branches.insert(class.sourced_id, (level.clone(), name.clone()));
+    }
+
+    // Users by kind
+    let mut mapped: Vec<MappedUser> = Vec::new();
+    let mut user_index: HashMap<String, usize> = HashMap::new();
+    let mut emails = HashSet::new();
+    for (line, row) in users {
+        let origin = Origin {
+            file: OneRosterFile::Users,
+            line,
+            sourced_id: Some(row.sourced_id.clone()),
+        };
+        let enabled = !row
+            .enabled_user
+            .as_deref()
+            .is_some_and(|e| e.eq_ignore_ascii_case("false"));
+        if !is_active(row.status.as_deref())
+            || !enabled
+            || !in_org(org, row.org_sourced_ids.as_deref())
+        {
+            continue;
+        }
+        let role = row.role.as_deref().unwrap_or_default().to_string();
+        let Some(kind) = UserKind::from_role(&role) else {
+            log.warn(
+                &origin,
+                ImportIssueCode::InvalidValue,
+                format!("Skipped: role '{}' is not imported", role),
+            );
+            continue;
+        };
+        if user_index.contains_key(&row.sourced_id) {
+            log.warn(
+                &origin,
+                ImportIssueCode::Duplicate,
+                "Skipped: sourcedId appears earlier in the file".into(),
+            );
+            continue;
+        }
+        // Many LMS products put the login email in username only
+        let email = non_empty(row.email.as_deref())
+            .or_else(|| non_empty(row.username.as_deref()).filter(|u| u.contains('@')))
+            .map(str::to_string);
+        let mut write = true;
+        match (&email, kind) {
+            (None, UserKind::Guardian) => {}
+            (None, _) => {
+                log.warn(
+                    &origin,
+                    ImportIssueCode::MissingValue,
+                    "Skipped: no email, which accounts need".into(),
+                );
+                continue;
+            }
+            (Some(email), _) => {
+                let key = email.to_lowercase();
+                if registered.contains(&key) {
+                    write = false;
+                    if kind != UserKind::Guardian {
+                        log.warn(
+                            &origin,
+                            ImportIssueCode::AlreadyExists,
+                            format!("{} is already registered; left unchanged", email),
+                        );
+                    }
+                } else if kind != UserKind::Guardian && !emails.insert(key) {
+                    log.warn(
+                        &origin,
+                        ImportIssueCode::Duplicate,
+                        format!("Skipped: {} appears earlier in the file", email),
+                    );
+                    continue;
+                }
+            }
+        }
+        user_index.insert(row.sourced_id.clone(), mapped.len());
+        mapped.push(MappedUser {
+            origin,
+            kind,
+            role,
+            row,
+            email,
+            write,
+            branch: None,
+        });
+    }
+
+    // Enrollments place students and lead teachers
+    for (line, enrollment) in enrollments {
+        let origin = Origin {
+            file: OneRosterFile::Enrollments,
+            line,
+            sourced_id: enrollment.sourced_id.clone(),
+        };
+        if !is_active(enrollment.status.as_deref())
+            || !in_org(org, enrollment.school_sourced_id.as_deref())
+        {
+            continue;
+        }
+        let role = enrollment.role.as_deref().unwrap_or_default();
+        let primary = enrollment
+            .primary
+            .as_deref()
+            .is_some_and(|p| p.eq_ignore_ascii_case("true"));
+        let wanted = match role.to_ascii_lowercase().as_str() {
+            "student" => UserKind::Student,
+            "teacher" if primary => UserKind::Teacher,
+            _ => continue,
+        };
+        let Some(branch) = class_branches.get(&enrollment.class_sourced_id) else {
+            log.warn(
+                &origin,
+                ImportIssueCode::UnknownReference,
+                format!(
+                    "Skipped: class '{}' is not in classes.csv",
+                    enrollment.class_sourced_id
+                ),
+            );
+            continue;
+        };
+        let Some(user) = user_index
+            .get(&enrollment.user_sourced_id)
+            .map(|&i| &mut mapped[i])
+            .filter(|user| user.kind == wanted)
+        else {
+            log.warn(
+                &origin,
+                ImportIssueCode::UnknownReference,
+                format!(
+                    "Skipped: {} '{}' is not in users.csv",
+                    role, enrollment.user_sourced_id
+                ),
+            );
+            continue;
+        };
+        match &user.branch {
+            None => user.branch = Some(branch.clone()),
+            Some((level, name)) if (level, name) != (&branch.0, &branch.1) => {
+                log.warn(
+                    &origin,
+                    ImportIssueCode::PolicyViolation,
+                    format!("Skipped: already placed in {} {}", level, name),
+                );
+            }
+            Some(_) => {}
+        }
+    }
+
+    // Guardian links from either side's agentSourcedIds
+    let mut links: Vec<(usize, usize)> = Vec::new();
+    let mut linked = HashSet::new();
+    for (i, user) in mapped.iter().enumerate() {
+        for agent in split_list(user.row.agent_sourced_ids.as_deref()) {
+            let Some(&j) = user_index.get(agent) else {
+                continue;
+            };
+            let link = match (user.kind, mapped[j].kind) {
+                (UserKind::Student, UserKind::Guardian) => (j, i),
+                (UserKind::Guardian, UserKind::Student) => (i, j),
+                _ => continue,
+            };
+            if linked.insert(link) {
+                links.push(link);
+            }
+        }
+    }
+    links.sort_by_key(|&(guardian, _)| guardian);
+    for (i, user) in mapped.iter().enumerate() {
+        if user.kind == UserKind::Guardian && !links.iter().any(|&(g, _)| g == i) {
+            log.warn(
+                &user.origin,
+                ImportIssueCode::UnknownReference,
+                "Skipped: not linked to any student in users.csv".into(),
+            );
+        }
+    }
+
+    // Sheets, recording where every row came from
+    let mut origins: HashMap<ImportDataset, Vec<Origin>> = HashMap::new();
+
+    let level_rows: Vec<_> = levels
+        .iter()
+        .map(|(name, _)| LevelSheetRow { name })
+        .collect();
+    origins.insert(
+        ImportDataset::Levels,
+        levels.iter().map(|(_, o)| o.clone()).collect(),
+    );
+
+    let branch_rows: Vec<_> = branches
+        .iter()
+        .map(|(level, name, _)| BranchSheetRow { level, name })
+        .collect();
+    origins.insert(
+        ImportDataset::Branches,
+        branches.iter().map(|(_, _, o)| o.clone()).collect(),
+    );
+
+    let mut staff_rows = Vec::new();
+    let mut student_rows = Vec::new();
+    for user in mapped.iter().filter(|u| u.write) {
+        let (Some(email), Some(first_name), Some(last_name)) = (
+            user.email.as_deref(),
+            user.row.given_name.as_deref(),
+            user.row.family_name.as_deref(),
+        ) else {
+            continue;
+        };
+        let (level, branch) = match &user.branch {
+            Some((level, branch)) => (Some(level.as_str()), Some(branch.as_str())),
+            None => (None, None),
+        };
+        let password = non_empty(user.row.password.as_deref());
+        match user.kind {
+            UserKind::Student => {
+                student_rows.push(StudentSheetRow {
+                    first_name,
+                    last_name,
+                    email,
+                    grade_level: split_list(user.row.grades.as_deref()).next(),
+                    level,
+                    branch,
+                    password,
+                });
+                origins
+                    .entry(ImportDataset::Students)
+                    .or_default()
+                    .push(user.origin.clone());
+            }
+            UserKind::Teacher | UserKind::Admin => {
+                staff_rows.push(StaffSheetRow {
+                    first_name,
+                    last_name,
+                    email,
+                    role: if user.kind == UserKind::Admin {
+                        "admin"
+                    } else {
+                        "teacher"
+                    },
+                    password,
+                    level,
+                    branch,
+                });
+                origins
+                    .entry(ImportDataset::Staff)
+                    .or_default()
+                    .push(user.origin.clone());
+            }
+            UserKind::Guardian => {}
+        }
+    }
+
+    let mut guardian_rows = Vec::new();
+    for &(guardian, student) in &links {
+        let (guardian, student) = (&mapped[guardian], &mapped[student]);
+        let Some(student_email) = student.email.as_deref() else {
+            continue;
+        };
+        let name = [
+            guardian.row.given_name.as_deref(),
+            guardian.row.family_name.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+        guardian_rows.push(GuardianSheetRow {
+            student_email,
+            name,
+            relationship: &guardian.role,
+            phone: non_empty(guardian.row.phone.as_deref())
+                .or_else(|| non_empty(guardian.row.sms.as_deref())),
+            email: guardian.email.as_deref(),
+        });
+        origins
+            .entry(ImportDataset::Guardians)
+            .or_default()
+            .push(guardian.origin.clone());
+    }
+
+    Ok(OneRosterDataset {
+        files: ImportDatasetFiles {
+            levels: write_sheet(&level_rows)?,
+            branches: write_sheet(&branch_rows)?,
+            staff: write_sheet(&staff_rows)?,
+            students: write_sheet(&student_rows)?,
+            guardians: write_sheet(&guardian_rows)?,
+            default_password: files.default_password.clone(),
+        },
+        issues,
+        origins,
+    })
+}
+
+// OneRoster 1.1 CSV rows, with columns in the order the specification
+// lists them. Bulk exports leave `status` and `dateLastModified` empty.
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OrgCsvRow<'a> {
+    sourced_id: SchoolId,
+    status: &'static str,
+    date_last_modified: &'static str,
+    name: &'a str,
+    #[serde(rename = "type")]
+    org_type: &'static str,
+    identifier: &'static str,
+    parent_sourced_id: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AcademicSessionCsvRow<'a> {
+    sourced_id: uuid::Uuid,
+    status: &'static str,
+    date_last_modified: &'static str,
+    title: &'a str,
+    #[serde(rename = "type")]
+    session_type: &'static str,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    parent_sourced_id: Option<uuid::Uuid>,
+    school_year: i32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CourseCsvRow<'a> {
+    sourced_id: LevelId,
+    status: &'static str,
+    date_last_modified: &'static str,
+    school_year_sourced_id: Option<uuid::Uuid>,
+    title: &'a str,
+    course_code: &'static str,
+    grades: &'static str,
+    org_sourced_id: SchoolId,
+    subjects: &'static str,
+    subject_codes: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ClassCsvRow<'a> {
+    sourced_id: BranchId,
+    status: &'static str,
+    date_last_modified: &'static str,
+    title: &'a str,
+    grades: &'static str,
+    course_sourced_id: LevelId,
+    class_code: &'static str,
+    class_type: &'static str,
+    location: &'static str,
+    school_sourced_id: SchoolId,
+    term_sourced_ids: &'a str,
+    subjects: &'static str,
+    subject_codes: &'static str,
+    periods: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UserCsvRow<'a> {
+    sourced_id: UserId,
+    status: &'static str,
+    date_last_modified: &'static str,
+    enabled_user: &'static str,
+    org_sourced_ids: SchoolId,
+    role: &'a str,
+    username: &'a str,
+    user_ids: &'static str,
+    given_name: &'a str,
+    family_name: &'a str,
+    middle_name: &'static str,
+    identifier: &'static str,
+    email: &'a str,
+    sms: &'static str,
+    phone: &'static str,
+    agent_sourced_ids: String,
+    grades: Option<&'a str>,
+    password: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EnrollmentCsvRow {
+    sourced_id: String,
+    status: &'static str,
+    date_last_modified: &'static str,
+    class_sourced_id: BranchId,
+    school_sourced_id: SchoolId,
+    user_sourced_id: UserId,
+    role: &'static str,
+    primary: &'static str,
+    begin_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+}
+
+#[derive(sqlx::FromRow)]
+struct SessionRecord {
+    id: uuid::Uuid,
+    name: String,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    is_active: bool,
+}
+
+#[derive(sqlx::FromRow)]
+struct TermRecord {
+    id: uuid::Uuid,
+    academic_session_id: uuid::Uuid,
+    name: String,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+}
+
+#[derive(sqlx::FromRow)]
+struct UserRecord {
+    id: UserId,
+    first_name: String,
+    last_name: String,
+    email: String,
+    grade_level: Option<String>,
+    role: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct TeacherEnrollmentRecord {
+    id: uuid::Uuid,
+    branch_id: BranchId,
+    teacher_id: UserId,
+    capacity: String,
+    starts_on: NaiveDate,
+    ends_on: Option<NaiveDate>,
+}
+
+fn write_csv<T: Serialize>(rows: impl IntoIterator<Item = T>) -> Result<Vec<u8>, AppError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    Ok(writer.into_inner().map_err(|e| e.into_error())?)
+}
+
+/// The manifest of a bulk export with every file this export writes.
+const MANIFEST: &str = "propertyName,value
+manifest.version,1.0
+oneroster.version,1.1
+file.academicSessions,bulk
+file.categories,absent
+file.classes,bulk
+file.classResources,absent
+file.courses,bulk
+file.courseResources,absent
+file.demographics,absent
+file.enrollments,bulk
+file.lineItems,absent
+file.orgs,bulk
+file.resources,absent
+file.results,absent
+file.users,bulk
+source.systemName,Chalkbyte
+";
+
+pub struct OneRosterService;
+
+impl OneRosterService {
+    /// Export a school's roster as a OneRoster 1.1 CSV bulk export: a ZIP
+    /// of `manifest.csv` and the orgs, academic sessions, courses, classes,
+    /// users, and enrollments files.
+    ///
+    /// Classes list the terms of the active academic session, and courses
+    /// belong to that session's school year. Teacher enrollments come from
+    /// current and upcoming branch teacher assignments, with lead teachers
+    /// as the primary teacher.
+    #[instrument(skip(db))]
+    pub async fn export(db: &PgPool, school_id: SchoolId) -> Result<Vec<u8>, AppError> {
+        let school_name = sqlx::query_scalar::<_, String>("SELECT name FROM schools WHERE id = $1")
+            .bind(school_id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| AppError::not_found(anyhow::anyhow!("School not found")))?;
+
+        let sessions = sqlx::query_as::<_, SessionRecord>(
+            r#"SELECT id, name, start_date, end_date, is_active
+               FROM academic_sessions
+               WHERE school_id = $1
+               ORDER BY start_date, name"#,
+        )
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        let terms = sqlx::query_as::<_, TermRecord>(
+            r#"SELECT t.id, t.academic_session_id, t.name, t.start_date, t.end_date
+               FROM terms t
+               INNER JOIN academic_sessions s ON s.id = t.academic_session_id
+               WHERE s.school_id = $1
+               ORDER BY s.start_date, t.sequence"#,
+        )
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        let levels = sqlx::query_as::<_, (LevelId, String)>(
+            "SELECT id, name FROM levels WHERE school_id = $1 ORDER BY name, id",
+        )
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        let branches = sqlx::query_as::<_, (BranchId, LevelId, String)>(
+            r#"SELECT b.id, b.level_id, b.name
+               FROM branches b
+               INNER JOIN levels l ON l.id = b.level_id
+               WHERE l.school_id = $1
+               ORDER BY l.name, b.name, b.id"#,
+        )
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        // One role per user, the most senior they hold
+        let roles = [
+            system_roles::ADMIN,
+            system_roles::TEACHER,
+            system_roles::STUDENT,
+            system_roles::GUARDIAN,
+        ];
+        let users = sqlx::query_as::<_, UserRecord>(
+            r#"SELECT id, first_name, last_name, email, grade_level, role
+               FROM (
+                   SELECT DISTINCT ON (u.id) u.id, u.first_name, u.last_name, u.email,
+                          u.grade_level,
+                          (ARRAY['administrator', 'teacher', 'student', 'guardian'])
+                              [array_position($2::uuid[], ur.role_id)] AS role
+                   FROM users u
+                   INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = ANY($2)
+                   WHERE u.school_id = $1
+                   ORDER BY u.id, array_position($2::uuid[], ur.role_id)
+               ) ranked
+               ORDER BY array_position(ARRAY['administrator', 'teacher', 'student', 'guardian'], role),
+                        last_name, first_name, id"#,
+        )
+        .bind(school_id)
+        .bind(&roles[..])
+        .fetch_all(db)
+        .await?;
+
+        let student_branches = sqlx::query_as::<_, (UserId, BranchId)>(
+            r#"SELECT u.id, u.branch_id
+               FROM users u
+               INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $2
+               WHERE u.school_id = $1 AND u.branch_id IS NOT NULL
+               ORDER BY u.last_name, u.first_name, u.id"#,
+        )
+        .bind(school_id)
+        .bind(system_roles::STUDENT)
+        .fetch_all(db)
+        .await?;
+
+        let teacher_enrollments = sqlx::query_as::<_, TeacherEnrollmentRecord>(
+            r#"SELECT bt.id, bt.branch_id, bt.teacher_id, bt.capacity, bt.starts_on, bt.ends_on
+               FROM branch_teachers bt
+               INNER JOIN branches b ON b.id = bt.branch_id
+               INNER JOIN levels l ON l.id = b.level_id
+               INNER JOIN schools s ON s.id = l.school_id
+               WHERE l.school_id = $1
+                 AND (bt.ends_on IS NULL OR bt.ends_on >= (NOW() AT TIME ZONE s.timezone)::date)
+               ORDER BY bt.starts_on, bt.id"#,
+        )
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        let guardian_links = sqlx::query_as::<_, (UserId, UserId)>(
+            "SELECT student_id, guardian_id FROM student_guardians WHERE school_id = $1",
+        )
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        let exported: HashSet<UserId> = users.iter().map(|u| u.id).collect();
+        let mut agents: HashMap<UserId, Vec<UserId>> = HashMap::new();
+        for (student, guardian) in guardian_links {
+            if exported.contains(&student) && exported.contains(&guardian) {
+                agents.entry(student).or_default().push(guardian);
+                agents.entry(guardian).or_default().push(student);
+            }
+        }
+        for ids in agents.values_mut() {
+            ids.sort_unstable_by_key(|id| id.into_inner());
+        }
+
+        let active_session = sessions.iter().find(|s| s.is_active);
+        let school_year = |session_id: uuid::Uuid| {
+            sessions
+                .iter()
+                .find(|s| s.id == session_id)
+                .map_or(0, |s| s.end_date.year())
+        };
+        let current_terms = active_session
+            .map(|session| {
+                terms
+                    .iter()
+                    .filter(|t| t.academic_session_id == session.id)
+                    .map(|t| t.id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .unwrap_or_default();
+
+        let orgs = write_csv([OrgCsvRow {
+            sourced_id: school_id,
+            status: "",
+            date_last_modified: "",
+            name: &school_name,
+            org_type: "school",
+            identifier: "",
+            parent_sourced_id: "",
+        }])?;
+
+        let academic_sessions = write_csv(
+            sessions
+                .iter()
+                .map(|s| AcademicSessionCsvRow {
+                    sourced_id: s.id,
+                    status: "",
+                    date_last_modified: "",
+                    title: &s.name,
+                    session_type: "schoolYear",
+                    start_date: s.start_date,
+                    end_date: s.end_date,
+                    parent_sourced_id: None,
+                    school_year: s.end_date.year(),
+                })
+                .chain(terms.iter().map(|t| AcademicSessionCsvRow {
+                    sourced_id: t.id,
+                    status: "",
+                    date_last_modified: "",
+                    title: &t.name,
+                    session_type: "term",
+                    start_date: t.start_date,
+                    end_date: t.end_date,
+                    parent_sourced_id: Some(t.academic_session_id),
+                    school_year: school_year(t.academic_session_id),
+                })),
+        )?;
+
+        let courses = write_csv(levels.iter().map(|(id, name)| CourseCsvRow {
+            sourced_id: *id,
+            status: "",
+            date_last_modified: "",
+            school_year_sourced_id: active_session.map(|s| s.id),
+            title: name,
+            course_code: "",
+            grades: "",
+            org_sourced_id: school_id,
+            subjects: "",
+            subject_codes: "",
+        }))?;
+
+        let classes = write_csv(branches.iter().map(|(id, level_id, name)| ClassCsvRow {
+            sourced_id: *id,
+            status: "",
+            date_last_modified: "",
+            title: name,
+            grades: "",
+            course_sourced_id: *level_id,
+            class_code: "",
+            class_type: "homeroom",
+            location: "",
+            school_sourced_id: school_id,
+            term_sourced_ids: &current_terms,
+            subjects: "",
+            subject_codes: "",
+            periods: "",
+        }))?;
+
+        let users_csv = write_csv(users.iter().map(|u| {
+            UserCsvRow {
+                sourced_id: u.id,
+                status: "",
+                date_last_modified: "",
+                enabled_user: "true",
+                org_sourced_ids: school_id,
+                role: &u.role,
+                username: &u.email,
+                user_ids: "",
+                given_name: &u.first_name,
+                family_name: &u.last_name,
+                middle_name: "",
+                identifier: "",
+                email: &u.email,
+                sms: "",
+                phone: "",
+                agent_sourced_ids: agents
+                    .get(&u.id)
+                    .map(|ids| {
+                        ids.iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    })
+                    .unwrap_or_default(),
+                grades: u.grade_level.as_deref().filter(|_| u.role == "student"),
+                password: "",
+            }
+        }))?;
+
+        let enrollments = write_csv(
+            student_branches
+                .iter()
+                .map(|(student_id, branch_id)| EnrollmentCsvRow {
+                    sourced_id: format!("{}-{}", branch_id, student_id),
+                    status: "",
+                    date_last_modified: "",
+                    class_sourced_id: *branch_id,
+                    school_sourced_id: school_id,
+                    user_sourced_id: *student_id,
+                    role: "student",
+                    primary: "false",
+                    begin_date: None,
+                    end_date: None,
+                })
+                .chain(
+                    teacher_enrollments
+                        .iter()
+                        .filter(|t| exported.contains(&t.teacher_id))
+                        .map(|t| EnrollmentCsvRow {
+                            sourced_id: t.id.to_string(),
+                            status: "",
+                            date_last_modified: "",
+                            class_sourced_id: t.branch_id,
+                            school_sourced_id: school_id,
+                            user_sourced_id: t.teacher_id,
+                            role: "teacher",
+                            primary: if t.capacity == "lead" {
+                                "true"
+                            } else {
+                                "false"
+                            },
+                            begin_date: Some(t.starts_on),
+                            end_date: t.ends_on,
+                        }),
+                ),
+        )?;
+
+        let mut zip = StoredZip::new(Utc::now().naive_utc());
+        zip.add("manifest.csv", MANIFEST.as_bytes())
+            .add(OneRosterFile::Orgs.file_name(), &orgs)
+            .add(
+                OneRosterFile::AcademicSessions.file_name(),
+                &academic_sessions,
+            )
+            .add(OneRosterFile::Courses.file_name(), &courses)
+            .add(OneRosterFile::Classes.file_name(), &classes)
+            .add(OneRosterFile::Users.file_name(), &users_csv)
+            .add(OneRosterFile::Enrollments.file_name(), &enrollments);
+
+        Ok(zip.finish())
+    }
+
+    /// Map OneRoster files to a school import dataset; see
+    /// [`OneRosterService::import`]. Users whose email is already
+    /// registered are left unchanged.
+    #[instrument(skip(db, files))]
+    pub async fn map(
+        db: &PgPool,
+        files: &OneRosterFiles,
+        org: Option<&str>,
+    ) -> Result<OneRosterDataset, AppError> {
+        if files.courses.is_none()
+            && files.classes.is_none()
+            && files.users.is_none()
+            && files.enrollments.is_none()
+        {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Upload at least one of courses, classes, users, or enrollments"
+            )));
+        }
+
+        let registered: HashSet<String> =
+            sqlx::query_scalar::<_, String>("SELECT LOWER(email) FROM users")
+                .fetch_all(db)
+                .await?
+                .into_iter()
+                .collect();
+
+        let dataset = map_files(files, org, &registered)?;
+        if dataset.files.is_empty() {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Nothing in the files can be imported"
+            )));
+        }
+
+        Ok(dataset)
+    }
+
+    /// Import a mapped OneRoster dataset through the school dataset import.
+    ///
+    /// The dataset is validated and written exactly as
+    /// [`SchoolImportService::import`] does. Its issues are traced back to
+    /// the OneRoster rows they came from and reported with the warnings
+    /// from mapping.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(db, cache, dataset, password_policy))]
+    pub async fn import(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        imported_by: UserId,
+        can_create_branches: bool,
+        dataset: OneRosterDataset,
+        dry_run: bool,
+        password_policy: &PasswordPolicy,
+    ) -> Result<OneRosterImportReport, AppError> {
+        let OneRosterDataset {
+            files,
+            mut issues,
+            origins,
+        } = dataset;
+
+        let report = SchoolImportService::import(
+            db,
+            cache,
+            school_id,
+            imported_by,
+            can_create_branches,
+            files,
+            dry_run,
+            password_policy,
+        )
+        .await?;
+
+        for issue in report.validation.issues {
+            let origin = issue.line.and_then(|line| {
+                origins
+                    .get(&issue.dataset)
+                    .and_then(|rows| rows.get(line.checked_sub(2)?))
+            });
+            let file = origin.map_or(
+                match issue.dataset {
+                    ImportDataset::Levels => OneRosterFile::Courses,
+                    ImportDataset::Branches => OneRosterFile::Classes,
+                    _ => OneRosterFile::Users,
+                },
+                |o| o.file,
+            );
+            issues.push(OneRosterIssue {
+                file,
+                severity: issue.severity,
+                code: issue.code,
+                line: origin.map(|o| o.line),
+                sourced_id: origin.and_then(|o| o.sourced_id.clone()),
+                message: issue.message,
+            });
+        }
+        issues.sort_by_key(|i| (i.file, i.line));
+
+        let error_count = issues
+            .iter()
+            .filter(|i| i.severity == ImportIssueSeverity::Error)
+            .count();
+
+        Ok(OneRosterImportReport {
+            dry_run,
+            imported: report.imported,
+            valid: report.validation.valid,
+            error_count,
+            warning_count: issues.len() - error_count,
+            issues,
+            phases: report.phases,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use chalkbyte_models::ids::RoleId;
+
+    async fn create_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar("INSERT INTO schools (name) VALUES ('Roster School') RETURNING id")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn create_user(
+        pool: &PgPool,
+        school_id: SchoolId,
+        name: &str,
+        role_id: RoleId,
+        branch_id: Option<BranchId>,
+    ) -> UserId {
+        let user_id = sqlx::query_scalar(
+            r#"INSERT INTO users (first_name, last_name, email, school_id, branch_id)
+               VALUES ($1, 'Test', $2, $3, $4) RETURNING id"#,
+        )
+        .bind(name)
+        .bind(format!("{}@example.com", name.to_lowercase()))
+        .bind(school_id)
+        .bind(branch_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(role_id)
+            .execute(pool)
+            .await
+            .unwrap();
+        user_id
+    }
+
+    /// Content of a file in a stored ZIP, found by walking the local headers.
+    fn zip_entry(zip: &[u8], name: &str) -> String {
+        let u16_at = |at: usize| u16::from_le_bytes([zip[at], zip[at + 1]]) as usize;
+        let mut at = 0;
+        while zip[at..at + 4] == 0x0403_4b50u32.to_le_bytes() {
+            let size = u32::from_le_bytes(zip[at + 18..at + 22].try_into().unwrap()) as usize;
+            let name_len = u16_at(at + 26);
+            let start = at + 30 + name_len + u16_at(at + 28);
+            if &zip[at + 30..at + 30 + name_len] == name.as_bytes() {
+                return String::from_utf8(zip[start..start + size].to_vec()).unwrap();
+            }
+            at = start + size;
+        }
+        panic!("{} is not in the archive", name);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_export_maps_roster_to_oneroster(pool: PgPool) {
+        let school_id = create_school(&pool).await;
+        let session_id: uuid::Uuid = sqlx::query_scalar(
+            r#"INSERT INTO academic_sessions (name, school_id, start_date, end_date, is_active)
+               VALUES ('2025/2026', $1, '2025-09-01', '2026-07-31', true) RETURNING id"#,
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let term_id: uuid::Uuid = sqlx::query_scalar(
+            r#"INSERT INTO terms (name, academic_session_id, start_date, end_date, sequence)
+               VALUES ('First Term', $1, '2025-09-01', '2025-12-15', 1) RETURNING id"#,
+        )
+        .bind(session_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let level_id: LevelId = sqlx::query_scalar(
+            "INSERT INTO levels (name, school_id) VALUES ('JSS 1', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let branch_id: BranchId = sqlx::query_scalar(
+            "INSERT INTO branches (name, level_id) VALUES ('A', $1) RETURNING id",
+        )
+        .bind(level_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let student = create_user(
+            &pool,
+            school_id,
+            "Ada",
+            system_roles::STUDENT,
+            Some(branch_id),
+        )
+        .await;
+        let teacher = create_user(&pool, school_id, "Tunde", system_roles::TEACHER, None).await;
+        let guardian = create_user(&pool, school_id, "Ngozi", system_roles::GUARDIAN, None).await;
+        // Admins who also teach are exported once, as administrators
+        let admin = create_user(&pool, school_id, "Kemi", system_roles::ADMIN, None).await;
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(admin)
+            .bind(system_roles::TEACHER)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO student_guardians (student_id, guardian_id, school_id) VALUES ($1, $2, $3)",
+        )
+        .bind(student)
+        .bind(guardian)
+        .bind(school_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let assignment: uuid::Uuid = sqlx::query_scalar(
+            r#"INSERT INTO branch_teachers (branch_id, teacher_id, capacity, starts_on)
+               VALUES ($1, $2, 'lead', '2025-09-01') RETURNING id"#,
+        )
+        .bind(branch_id)
+        .bind(teacher)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        // Past assignments are not enrollments
+        sqlx::query(
+            r#"INSERT INTO branch_teachers (branch_id, teacher_id, capacity, starts_on, ends_on)
+               VALUES ($1, $2, 'assistant', '2024-09-01', '2024-12-01')"#,
+        )
+        .bind(branch_id)
+        .bind(admin)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let zip = OneRosterService::export(&pool, school_id).await.unwrap();
+
+        assert!(zip_entry(&zip, "manifest.csv").contains("oneroster.version,1.1\n"));
+        assert_eq!(
+            zip_entry(&zip, "orgs.csv"),
+            format!(
+                "sourcedId,status,dateLastModified,name,type,identifier,parentSourcedId\n\
+                 {},,,Roster School,school,,\n",
+                school_id
+            )
+        );
+        assert_eq!(
+            zip_entry(&zip, "academicSessions.csv"),
+            format!(
+                "sourcedId,status,dateLastModified,title,type,startDate,endDate,parentSourcedId,schoolYear\n\
+                 {session_id},,,2025/2026,schoolYear,2025-09-01,2026-07-31,,2026\n\
+                 {term_id},,,First Term,term,2025-09-01,2025-12-15,{session_id},2026\n"
+            )
+        );
+        assert!(zip_entry(&zip, "courses.csv").contains(&format!(
+            "{level_id},,,{session_id},JSS 1,,,{school_id},,\n"
+        )));
+        assert!(zip_entry(&zip, "classes.csv").contains(&format!(
+            "{branch_id},,,A,,{level_id},,homeroom,,{school_id},{term_id},,,\n"
+        )));
+
+        let users = zip_entry(&zip, "users.csv");
+        let roles: Vec<(&str, &str)> = users
+            .lines()
+            .skip(1)
+            .map(|line| {
+                let fields: Vec<&str> = line.split(',').collect();
+                (fields[8], fields[5])
+            })
+            .collect();
+        assert_eq!(
+            roles,
+            [
+                ("Kemi", "administrator"),
+                ("Tunde", "teacher"),
+                ("Ada", "student"),
+                ("Ngozi", "guardian"),
+            ]
+        );
+        assert!(users.contains(&format!(",{},", guardian)));
+
+        assert_eq!(
+            zip_entry(&zip, "enrollments.csv"),
+            format!(
+                "sourcedId,status,dateLastModified,classSourcedId,schoolSourcedId,userSourcedId,role,primary,beginDate,endDate\n\
+                 {branch_id}-{student},,,{branch_id},{school_id},{student},student,false,,\n\
+                 {assignment},,,{branch_id},{school_id},{teacher},teacher,true,2025-09-01,\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_map_files_tolerates_lms_exports() {
+        let courses = "\u{feff}SourcedID,Status,Title,Org Sourced Id\n\
+            c1,,JSS 1,org1\n\
+            c2,tobedeleted,JSS 2,org1\n\
+            c3,,jss 1,org1\n";
+        let classes = "sourcedId,title,courseSourcedId,schoolSourcedId,periods\n\
+            k1,A,c1,org1,1\n\
+            k2,B,c3,org1,2\n\
+            k3,C,c9,org1,\n\
+            k4,D,c1,org2,\n";
+        let users = "sourcedId,enabledUser,orgSourcedIds,role,username,givenName,familyName,email,agentSourcedIds,grades\n\
+            u1,true,org1,student,ada@example.com,Ada,Obi,,p1,07\n\
+            u2,true,org1,teacher,tunde,Tunde,Bello,tunde@example.com,,\n\
+            u3,false,org1,student,,Off,User,off@example.com,,\n\
+            u4,true,org1,aide,,Sam,Aide,sam@example.com,,\n\
+            p1,true,org1,parent,,Ngozi,Obi,,,\n\
+            u5,true,org1,student,,Bisi,Ade,bisi@example.com,,\n\
+            u6,true,org2,student,,Other,School,other@example.com,,\n";
+        let enrollments = "classSourcedId,userSourcedId,role,primary\n\
+            k1,u1,student,false\n\
+            k2,u1,student,false\n\
+            k2,u2,teacher,true\n\
+            k3,u5,student,false\n";
+        let registered = HashSet::from(["bisi@example.com".to_string()]);
+
+        let dataset = map_files(
+            &OneRosterFiles {
+                courses: Some(courses.as_bytes().to_vec()),
+                classes: Some(classes.as_bytes().to_vec()),
+                users: Some(users.as_bytes().to_vec()),
+                enrollments: Some(enrollments.as_bytes().to_vec()),
+                default_password: None,
+            },
+            Some("org1"),
+            &registered,
+        )
+        .unwrap();
+
+        let sheet = |bytes: &Option<Vec<u8>>| String::from_utf8(bytes.clone().unwrap()).unwrap();
+        assert_eq!(sheet(&dataset.files.levels), "name\nJSS 1\n");
+        assert_eq!(
+            sheet(&dataset.files.branches),
+            "level,name\nJSS 1,A\nJSS 1,B\n"
+        );
+        assert_eq!(
+            sheet(&dataset.files.staff),
+            "first_name,last_name,email,role,password,level,branch\n\
+             Tunde,Bello,tunde@example.com,teacher,,JSS 1,B\n"
+        );
+        assert_eq!(
+            sheet(&dataset.files.students),
+            "first_name,last_name,email,grade_level,level,branch,password\n\
+             Ada,Obi,ada@example.com,07,JSS 1,A,\n"
+        );
+        assert_eq!(
+            sheet(&dataset.files.guardians),
+            "student_email,name,relationship,phone,email\n\
+             ada@example.com,Ngozi Obi,parent,,\n"
+        );
+
+        let issues: Vec<_> = dataset
+            .issues
+            .iter()
+            .map(|i| (i.file, i.code, i.sourced_id.as_deref().unwrap_or_default()))
+            .collect();
+        use ImportIssueCode::*;
+        use OneRosterFile::*;
+        assert_eq!(
+            issues,
+            [
+                (Classes, UnknownReference, "k3"),
+                (Users, InvalidValue, "u4"),
+                (Users, AlreadyExists, "u5"),
+                (Enrollments, PolicyViolation, ""),
+                (Enrollments, UnknownReference, ""),
+            ]
+        );
+        assert_eq!(dataset.origins[&ImportDataset::Staff][0].line, 3);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_import_traces_issues_to_oneroster_rows(pool: PgPool) {
+        let school_id = create_school(&pool).await;
+        let importer = create_user(&pool, school_id, "Head", system_roles::ADMIN, None).await;
+
+        let files = OneRosterFiles {
+            courses: Some(b"sourcedId,title\nc1,JSS 1\n".to_vec()),
+            classes: Some(b"sourcedId,title,courseSourcedId\nk1,A,c1\n".to_vec()),
+            users: Some(
+                b"sourcedId,role,givenName,familyName,email,password\n\
+                  u1,student,Ada,Obi,ada@example.com,\n\
+                  u2,student,Tayo,Ajayi,not-an-email,\n"
+                    .to_vec(),
+            ),
+            enrollments: Some(b"classSourcedId,userSourcedId,role\nk1,u1,student\n".to_vec()),
+            default_password: Some("testpass123".to_string()),
+        };
+
+        let dataset = OneRosterService::map(&pool, &files, None).await.unwrap();
+        let report = OneRosterService::import(
+            &pool,
+            None,
+            school_id,
+            importer,
+            true,
+            dataset,
+            true,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(!report.valid);
+        assert!(!report.imported);
+        let error = report
+            .issues
+            .iter()
+            .find(|i| i.severity == ImportIssueSeverity::Error)
+            .unwrap();
+        assert_eq!(error.file, OneRosterFile::Users);
+        assert_eq!(error.line, Some(3));
+        assert_eq!(error.sourced_id.as_deref(), Some("u2"));
+
+        // Without the bad row the roster imports
+        let files = OneRosterFiles {
+            users: Some(
+                b"sourcedId,role,givenName,familyName,email\nu1,student,Ada,Obi,ada@example.com\n"
+                    .to_vec(),
+            ),
+            ..files
+        };
+        let dataset = OneRosterService::map(&pool, &files, None).await.unwrap();
+        let report = OneRosterService::import(
+            &pool,
+            None,
+            school_id,
+            importer,
+            true,
+            dataset,
+            false,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(report.imported, "{:?}", report.issues);
+        let (level, branch): (String, String) = sqlx::query_as(
+            r#"SELECT l.name, b.name
+               FROM users u
+               INNER JOIN branches b ON b.id = u.branch_id
+               INNER JOIN levels l ON l.id = b.level_id
+               WHERE u.email = 'ada@example.com'"#,
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!((level.as_str(), branch.as_str()), ("JSS 1", "A"));
+
+        // Importing again finds nothing new
+        let err = OneRosterService::map(
+            &pool,
+            &OneRosterFiles {
+                users: files.users.clone(),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+}
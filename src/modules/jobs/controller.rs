@@ -0,0 +1,39 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use tracing::instrument;
+
+use crate::modules::jobs::model::JobsHealthResponse;
+use crate::modules::jobs::service::JobService;
+
+/// Background job health
+///
+/// Reports each background job's last runs, failure counts, and queue
+/// depth. Answers 503 while any job is failing or stalled, so uptime checks
+/// can page on it. System admins only.
+#[utoipa::path(
+    get,
+    path = "/api/admin/jobs/health",
+    summary = "Background job health",
+    responses(
+        (status = 200, description = "Every job is healthy or pending", body = JobsHealthResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only"),
+        (status = 503, description = "A job is failing or stalled", body = JobsHealthResponse)
+    ),
+    tag = "Jobs",
+    security(("bearer_auth" = []))
+)]
+#[instrument]
+pub async fn get_jobs_health() -> Response {
+    let health = JobService::health();
+    let status = if health.healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(health)).into_response()
+}
@@ -0,0 +1,14 @@
+//! Background job health module.
+//!
+//! Background jobs record every run through
+//! [`chalkbyte_observability::jobs`]: a span per run, success and failure
+//! counters, queue depth gauges, and last-run timestamps.
+//!
+//! `GET /api/admin/jobs/health` lets system admins, and the alerting that
+//! polls it, see which jobs are failing or have stopped running. It answers
+//! `503 Service Unavailable` while any job is unhealthy.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
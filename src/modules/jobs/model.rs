@@ -0,0 +1,7 @@
+//! Background job health data models.
+//!
+//! This module re-exports job health models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all job health models from the shared crate
+pub use chalkbyte_models::jobs::*;
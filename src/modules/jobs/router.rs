@@ -0,0 +1,11 @@
+use axum::{Router, routing::get};
+
+use crate::state::AppState;
+
+use super::controller::get_jobs_health;
+
+/// Initialize the background job health router
+/// Routes: GET /health
+pub fn init_jobs_router() -> Router<AppState> {
+    Router::new().route("/health", get(get_jobs_health))
+}
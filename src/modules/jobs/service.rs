@@ -0,0 +1,85 @@
+use chrono::Utc;
+
+use chalkbyte_observability::jobs::{self, JobHealth as RunHealth, JobStatus};
+
+use crate::modules::jobs::model::{JobHealth, JobHealthStatus, JobsHealthResponse};
+
+pub struct JobService;
+
+impl JobService {
+    /// Health of every background job registered in this process.
+    pub fn health() -> JobsHealthResponse {
+        let now = Utc::now();
+        let jobs: Vec<JobHealth> = jobs::job_statuses()
+            .into_iter()
+            .map(|status| Self::job_health(status, now))
+            .collect();
+
+        JobsHealthResponse {
+            healthy: !jobs.iter().any(|job| job.status.is_unhealthy()),
+            jobs,
+        }
+    }
+
+    fn job_health(status: JobStatus, now: chrono::DateTime<Utc>) -> JobHealth {
+        let health = match status.health(now) {
+            RunHealth::Healthy => JobHealthStatus::Healthy,
+            RunHealth::Pending => JobHealthStatus::Pending,
+            RunHealth::Failing => JobHealthStatus::Failing,
+            RunHealth::Stalled => JobHealthStatus::Stalled,
+        };
+
+        JobHealth {
+            name: status.name.to_string(),
+            status: health,
+            interval_secs: status.interval.as_secs(),
+            last_started_at: status.last_started_at,
+            last_success_at: status.last_success_at,
+            last_failure_at: status.last_failure_at,
+            last_error: status.last_error,
+            consecutive_failures: status.consecutive_failures,
+            successes: status.successes,
+            failures: status.failures,
+            queue_depth: status.queue_depth,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chalkbyte_observability::jobs::{FAILING_AFTER_RUNS, register_job};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_failing_job_makes_health_unhealthy() {
+        let job = register_job("test_failing_job", Duration::from_secs(60));
+        for _ in 0..FAILING_AFTER_RUNS {
+            let _ = job
+                .run(async { Err::<(), _>("database unavailable") })
+                .await;
+        }
+
+        let health = JobService::health();
+        assert!(!health.healthy);
+        let failing = health
+            .jobs
+            .iter()
+            .find(|job| job.name == "test_failing_job")
+            .unwrap();
+        assert_eq!(failing.status, JobHealthStatus::Failing);
+        assert_eq!(failing.last_error.as_deref(), Some("database unavailable"));
+
+        // The job recovers on its next success
+        job.run(async { Ok::<_, &str>(()) }).await.unwrap();
+        let health = JobService::health();
+        let job = health
+            .jobs
+            .iter()
+            .find(|job| job.name == "test_failing_job")
+            .unwrap();
+        assert_eq!(job.status, JobHealthStatus::Healthy);
+    }
+}
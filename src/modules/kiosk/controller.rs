@@ -0,0 +1,278 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::KioskDeviceId;
+
+use crate::middleware::auth::{
+    AttendanceKiosk, KioskOperator, RequireAttendanceMark, RequireKioskDevicesManage,
+};
+use crate::modules::attendance::model::MarkAttendanceResponse;
+use crate::modules::kiosk::model::{
+    KioskAttendanceDto, KioskDevice, KioskDeviceQueryParams, KioskRosterStudent,
+    KioskSessionRequest, KioskSessionResponse, RegisterKioskDeviceDto, RegisteredKioskDevice,
+    SetKioskPinDto,
+};
+use crate::modules::kiosk::service::KioskService;
+use crate::modules::student_cards::model::{VerifiedStudentCard, VerifyStudentCardDto};
+use crate::modules::student_cards::service::StudentCardService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Register an attendance kiosk device
+///
+/// The device is bound to one branch. The returned device token is shown
+/// only once and must be configured on the device.
+#[utoipa::path(
+    post,
+    path = "/api/kiosk-devices",
+    summary = "Register kiosk device",
+    request_body = RegisterKioskDeviceDto,
+    responses(
+        Created<RegisteredKioskDevice>,
+        (status = 400, description = "Invalid input or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires kiosk_devices:manage permission"),
+        (status = 404, description = "Branch not found")
+    ),
+    tag = "Attendance Kiosk",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn register_kiosk_device(
+    State(state): State<AppState>,
+    RequireKioskDevicesManage(auth_user): RequireKioskDevicesManage,
+    ValidatedJson(dto): ValidatedJson<RegisterKioskDeviceDto>,
+) -> Result<Created<RegisteredKioskDevice>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let registered_by = auth_user.user_id()?;
+    let device = KioskService::register_device(&state.db, school_id, registered_by, dto).await?;
+
+    Ok(Created(device))
+}
+
+/// List attendance kiosk devices
+#[utoipa::path(
+    get,
+    path = "/api/kiosk-devices",
+    summary = "List kiosk devices",
+    params(KioskDeviceQueryParams),
+    responses(
+        (status = 200, description = "Devices, active first", body = Vec<KioskDevice>),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires kiosk_devices:manage permission")
+    ),
+    tag = "Attendance Kiosk",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_kiosk_devices(
+    State(state): State<AppState>,
+    RequireKioskDevicesManage(auth_user): RequireKioskDevicesManage,
+    Query(params): Query<KioskDeviceQueryParams>,
+) -> Result<Json<Vec<KioskDevice>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let devices = KioskService::get_devices(&state.db, school_id).await?;
+
+    Ok(Json(devices))
+}
+
+/// Revoke an attendance kiosk device
+///
+/// Use this for lost or retired devices. Operation tokens issued on the
+/// device stop working immediately.
+#[utoipa::path(
+    delete,
+    path = "/api/kiosk-devices/{id}",
+    summary = "Revoke kiosk device",
+    params(
+        ("id" = Uuid, Path, description = "Kiosk device ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires kiosk_devices:manage permission"),
+        (status = 404, description = "Kiosk device not found")
+    ),
+    tag = "Attendance Kiosk",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn revoke_kiosk_device(
+    State(state): State<AppState>,
+    RequireKioskDevicesManage(auth_user): RequireKioskDevicesManage,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    KioskService::revoke_device(&state.db, KioskDeviceId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Set your kiosk PIN
+///
+/// The PIN unlocks shared attendance kiosk devices in your school. Setting a
+/// new PIN clears any lockout from wrong entries.
+#[utoipa::path(
+    put,
+    path = "/api/me/kiosk-pin",
+    summary = "Set kiosk PIN",
+    request_body = SetKioskPinDto,
+    responses(
+        NoContent,
+        (status = 400, description = "PIN must be 4-8 digits"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires attendance:mark permission")
+    ),
+    tag = "Attendance Kiosk",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn set_kiosk_pin(
+    State(state): State<AppState>,
+    RequireAttendanceMark(auth_user): RequireAttendanceMark,
+    ValidatedJson(dto): ValidatedJson<SetKioskPinDto>,
+) -> Result<NoContent, AppError> {
+    let user_id = auth_user.user_id()?;
+    KioskService::set_pin(&state.db, user_id, &dto.pin).await?;
+
+    Ok(NoContent)
+}
+
+/// Unlock a kiosk device with a staff PIN
+///
+/// Returns a short-lived operation token for the device's branch. Send it as
+/// a bearer token together with the device token on the other kiosk
+/// endpoints. Too many wrong PINs lock the PIN for 15 minutes.
+#[utoipa::path(
+    post,
+    path = "/api/kiosk/session",
+    summary = "Start kiosk session",
+    request_body = KioskSessionRequest,
+    responses(
+        (status = 200, description = "Operation token issued", body = KioskSessionResponse),
+        (status = 401, description = "Invalid device token, email, or PIN"),
+        (status = 403, description = "Staff member cannot mark attendance"),
+        (status = 429, description = "PIN locked after too many wrong entries")
+    ),
+    tag = "Attendance Kiosk",
+    security(("device_token" = []))
+)]
+#[instrument(skip(state, request))]
+pub async fn start_kiosk_session(
+    State(state): State<AppState>,
+    AttendanceKiosk(device): AttendanceKiosk,
+    ValidatedJson(request): ValidatedJson<KioskSessionRequest>,
+) -> Result<Json<KioskSessionResponse>, AppError> {
+    let session =
+        KioskService::start_session(&state.db, &state.jwt_config, &device, request).await?;
+
+    Ok(Json(session))
+}
+
+/// Get the kiosk branch roster
+///
+/// Students in the device's branch with their attendance status for today,
+/// if already marked.
+#[utoipa::path(
+    get,
+    path = "/api/kiosk/roster",
+    summary = "Get kiosk roster",
+    responses(
+        (status = 200, description = "Students ordered by name", body = Vec<KioskRosterStudent>),
+        (status = 401, description = "Invalid device or operation token")
+    ),
+    tag = "Attendance Kiosk",
+    security(("device_token" = [], "bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_kiosk_roster(
+    State(state): State<AppState>,
+    operator: KioskOperator,
+) -> Result<Json<Vec<KioskRosterStudent>>, AppError> {
+    let roster =
+        KioskService::get_roster(&state.db, state.cache.as_ref(), &operator.device).await?;
+
+    Ok(Json(roster))
+}
+
+/// Mark today's attendance from a kiosk
+///
+/// Students who are not in the device's branch are skipped and returned in
+/// `failed_ids`.
+#[utoipa::path(
+    post,
+    path = "/api/kiosk/attendance",
+    summary = "Mark kiosk attendance",
+    request_body = KioskAttendanceDto,
+    responses(
+        (status = 200, description = "Attendance recorded", body = MarkAttendanceResponse),
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Invalid device or operation token")
+    ),
+    tag = "Attendance Kiosk",
+    security(("device_token" = [], "bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn mark_kiosk_attendance(
+    State(state): State<AppState>,
+    operator: KioskOperator,
+    ValidatedJson(dto): ValidatedJson<KioskAttendanceDto>,
+) -> Result<Json<MarkAttendanceResponse>, AppError> {
+    let response = KioskService::mark_attendance(
+        &state.db,
+        state.cache.as_ref(),
+        &operator.device,
+        operator.staff_id,
+        dto,
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+/// Scan a student card at a kiosk
+///
+/// Resolves a scanned student ID card to its student. Cards from other
+/// schools are reported as not found.
+#[utoipa::path(
+    post,
+    path = "/api/kiosk/scan",
+    summary = "Scan student card",
+    request_body = VerifyStudentCardDto,
+    responses(
+        (status = 200, description = "Card is valid", body = VerifiedStudentCard),
+        (status = 400, description = "Malformed or tampered card code"),
+        (status = 401, description = "Invalid device or operation token"),
+        (status = 404, description = "Student not found in the device's school")
+    ),
+    tag = "Attendance Kiosk",
+    security(("device_token" = [], "bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn scan_student_card(
+    State(state): State<AppState>,
+    operator: KioskOperator,
+    ValidatedJson(dto): ValidatedJson<VerifyStudentCardDto>,
+) -> Result<Json<VerifiedStudentCard>, AppError> {
+    let student = StudentCardService::verify_code(
+        &state.db,
+        &dto.code,
+        Some(operator.device.school_id),
+        &state.jwt_config.card_secret,
+    )
+    .await?;
+
+    Ok(Json(student))
+}
@@ -0,0 +1,13 @@
+//! Attendance kiosk module.
+//!
+//! Shared devices such as a classroom tablet can mark attendance without a
+//! full staff login. A school admin registers the device against a branch and
+//! configures the one-time device token on it. Staff then unlock the device
+//! with their email and a kiosk PIN, which yields a 15-minute operation token
+//! that only works on that device and only for attendance and student card
+//! scans. Lost devices are revoked from the device management endpoints.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Kiosk device data models and DTOs.
+//!
+//! This module re-exports kiosk models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all kiosk models from the shared crate
+pub use chalkbyte_models::kiosk::*;
@@ -0,0 +1,37 @@
+use axum::{
+    Router,
+    routing::{delete, get, post, put},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    get_kiosk_devices, get_kiosk_roster, mark_kiosk_attendance, register_kiosk_device,
+    revoke_kiosk_device, scan_student_card, set_kiosk_pin, start_kiosk_session,
+};
+
+/// Initialize the kiosk device management router
+/// Routes: POST /, GET /, DELETE /{id}
+pub fn init_kiosk_devices_router() -> Router<AppState> {
+    Router::new()
+        .route("/", post(register_kiosk_device).get(get_kiosk_devices))
+        .route("/{id}", delete(revoke_kiosk_device))
+}
+
+/// Initialize the kiosk PIN router, nested under /me
+/// Routes: PUT /
+pub fn init_kiosk_pin_router() -> Router<AppState> {
+    Router::new().route("/", put(set_kiosk_pin))
+}
+
+/// Initialize the attendance kiosk router
+/// Authenticated with an `X-Device-Token` header, plus a kiosk operation
+/// token for everything except unlocking.
+/// Routes: POST /session, GET /roster, POST /attendance, POST /scan
+pub fn init_kiosk_router() -> Router<AppState> {
+    Router::new()
+        .route("/session", post(start_kiosk_session))
+        .route("/roster", get(get_kiosk_roster))
+        .route("/attendance", post(mark_kiosk_attendance))
+        .route("/scan", post(scan_student_card))
+}
@@ -0,0 +1,545 @@
+use axum::http::StatusCode;
+use chalkbyte_auth::{KIOSK_TOKEN_EXPIRY, create_kiosk_token};
+use chalkbyte_cache::RedisCache;
+use chalkbyte_config::JwtConfig;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, hash_password, permissions, verify_password};
+use chalkbyte_models::ids::{KioskDeviceId, SchoolId, UserId};
+
+use crate::middleware::auth::AuthContext;
+use crate::modules::attendance::model::{MarkAttendanceDto, MarkAttendanceResponse};
+use crate::modules::attendance::service::AttendanceService;
+use crate::modules::kiosk::model::{
+    KioskAttendanceDto, KioskDevice, KioskRosterStudent, KioskSessionRequest, KioskSessionResponse,
+    RegisterKioskDeviceDto, RegisteredKioskDevice,
+};
+use crate::modules::schools::service::SchoolService;
+use crate::modules::users::model::system_roles;
+
+const DEVICE_COLUMNS: &str = "id, school_id, branch_id, name, token_prefix, registered_by, \
+     last_seen_at, revoked_at, created_at";
+
+/// Prefix that marks a string as a kiosk device token.
+const DEVICE_TOKEN_PREFIX: &str = "kd_";
+/// Number of random characters following the prefix.
+const DEVICE_TOKEN_LENGTH: usize = 40;
+/// Number of leading characters stored in the clear to identify a device.
+const DEVICE_TOKEN_DISPLAY_LENGTH: usize = 10;
+/// Wrong PIN entries allowed before the PIN is locked.
+const MAX_PIN_ATTEMPTS: i32 = 5;
+/// How long a PIN stays locked after too many wrong entries.
+const PIN_LOCKOUT_MINUTES: i32 = 15;
+
+#[derive(sqlx::FromRow)]
+struct KioskStaff {
+    id: UserId,
+    first_name: String,
+    last_name: String,
+    pin_hash: Option<String>,
+    locked: bool,
+}
+
+pub struct KioskService;
+
+impl KioskService {
+    fn generate_device_token() -> String {
+        let random: String = rand::thread_rng()
+            .sample_iter(&rand::distributions::Alphanumeric)
+            .take(DEVICE_TOKEN_LENGTH)
+            .map(char::from)
+            .collect();
+        format!("{DEVICE_TOKEN_PREFIX}{random}")
+    }
+
+    fn hash_device_token(token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+
+    /// Register a device for a branch. The plaintext token is only returned here.
+    #[instrument(skip(db))]
+    pub async fn register_device(
+        db: &PgPool,
+        school_id: SchoolId,
+        registered_by: UserId,
+        dto: RegisterKioskDeviceDto,
+    ) -> Result<RegisteredKioskDevice, AppError> {
+        AttendanceService::get_branch_school(db, dto.branch_id, Some(school_id)).await?;
+
+        let device_token = Self::generate_device_token();
+
+        let device = sqlx::query_as::<_, KioskDevice>(&format!(
+            "INSERT INTO kiosk_devices (school_id, branch_id, name, token_prefix, token_hash, registered_by)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             RETURNING {DEVICE_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(dto.branch_id)
+        .bind(&dto.name)
+        .bind(&device_token[..DEVICE_TOKEN_DISPLAY_LENGTH])
+        .bind(Self::hash_device_token(&device_token))
+        .bind(registered_by)
+        .fetch_one(db)
+        .await?;
+
+        Ok(RegisteredKioskDevice {
+            device,
+            device_token,
+        })
+    }
+
+    /// List a school's devices, including revoked ones.
+    #[instrument(skip(db))]
+    pub async fn get_devices(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<Vec<KioskDevice>, AppError> {
+        let devices = sqlx::query_as::<_, KioskDevice>(&format!(
+            "SELECT {DEVICE_COLUMNS} FROM kiosk_devices
+             WHERE school_id = $1
+             ORDER BY revoked_at NULLS FIRST, created_at DESC"
+        ))
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(devices)
+    }
+
+    /// Revoke a lost or retired device. Operation tokens issued on it stop
+    /// working immediately. Revoking an already revoked device is a no-op.
+    #[instrument(skip(db))]
+    pub async fn revoke_device(
+        db: &PgPool,
+        device_id: KioskDeviceId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"UPDATE kiosk_devices
+               SET revoked_at = COALESCE(revoked_at, NOW())
+               WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"#,
+        )
+        .bind(device_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Kiosk device not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Resolve an active device token and record that the device was seen.
+    #[instrument(skip(db, device_token))]
+    pub async fn authenticate_device(
+        db: &PgPool,
+        device_token: &str,
+    ) -> Result<KioskDevice, AppError> {
+        sqlx::query_as::<_, KioskDevice>(&format!(
+            "UPDATE kiosk_devices SET last_seen_at = NOW()
+             WHERE token_hash = $1 AND revoked_at IS NULL
+             RETURNING {DEVICE_COLUMNS}"
+        ))
+        .bind(Self::hash_device_token(device_token))
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::unauthorized("Invalid or revoked device token".to_string()))
+    }
+
+    /// Set or replace a staff member's kiosk PIN, clearing any lockout.
+    #[instrument(skip(db, pin))]
+    pub async fn set_pin(db: &PgPool, user_id: UserId, pin: &str) -> Result<(), AppError> {
+        let pin_hash = hash_password(pin)?;
+
+        sqlx::query(
+            r#"INSERT INTO staff_kiosk_pins (user_id, pin_hash)
+               VALUES ($1, $2)
+               ON CONFLICT (user_id) DO UPDATE SET
+                   pin_hash = EXCLUDED.pin_hash,
+                   failed_attempts = 0,
+                   locked_until = NULL,
+                   updated_at = NOW()"#,
+        )
+        .bind(user_id)
+        .bind(pin_hash)
+        .execute(db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Unlock a device for a staff member of its school and issue an
+    /// operation token.
+    ///
+    /// The staff member must have set a kiosk PIN and hold `attendance:mark`.
+    /// After too many wrong PINs the PIN is locked for a while.
+    #[instrument(skip(db, jwt_config, request))]
+    pub async fn start_session(
+        db: &PgPool,
+        jwt_config: &JwtConfig,
+        device: &KioskDevice,
+        request: KioskSessionRequest,
+    ) -> Result<KioskSessionResponse, AppError> {
+        let invalid = || AppError::unauthorized("Invalid email or PIN".to_string());
+
+        let staff = sqlx::query_as::<_, KioskStaff>(
+            r#"SELECT u.id, u.first_name, u.last_name, p.pin_hash,
+                      COALESCE(p.locked_until > NOW(), false) AS locked
+               FROM users u
+               LEFT JOIN staff_kiosk_pins p ON p.user_id = u.id
+               WHERE u.email = $1 AND u.school_id = $2"#,
+        )
+        .bind(request.email.as_str())
+        .bind(device.school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(invalid)?;
+
+        let pin_hash = staff.pin_hash.as_deref().ok_or_else(invalid)?;
+
+        if staff.locked {
+            return Err(AppError::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                anyhow::anyhow!("Too many wrong PIN entries, try again later"),
+            ));
+        }
+
+        if !verify_password(&request.pin, pin_hash)? {
+            sqlx::query(
+                r#"UPDATE staff_kiosk_pins SET
+                       failed_attempts = CASE WHEN failed_attempts + 1 >= $2 THEN 0
+                                              ELSE failed_attempts + 1 END,
+                       locked_until = CASE WHEN failed_attempts + 1 >= $2
+                                           THEN NOW() + make_interval(mins => $3)
+                                           ELSE locked_until END
+                   WHERE user_id = $1"#,
+            )
+            .bind(staff.id)
+            .bind(MAX_PIN_ATTEMPTS)
+            .bind(PIN_LOCKOUT_MINUTES)
+            .execute(db)
+            .await?;
+
+            return Err(invalid());
+        }
+
+        sqlx::query(
+            "UPDATE staff_kiosk_pins SET failed_attempts = 0, locked_until = NULL WHERE user_id = $1",
+        )
+        .bind(staff.id)
+        .execute(db)
+        .await?;
+
+        if !AuthContext::for_user(staff.id, device.school_id)
+            .has_permission(db, permissions::ATTENDANCE_MARK)
+            .await?
+        {
+            return Err(AppError::forbidden(
+                "Not allowed to mark attendance".to_string(),
+            ));
+        }
+
+        let access_token = create_kiosk_token(
+            staff.id.into_inner(),
+            device.school_id.into_inner(),
+            device.branch_id.into_inner(),
+            device.id.into_inner(),
+            jwt_config,
+        )?;
+
+        Ok(KioskSessionResponse {
+            access_token,
+            expires_in: KIOSK_TOKEN_EXPIRY,
+            staff_id: staff.id,
+            staff_name: format!("{} {}", staff.first_name, staff.last_name),
+            branch_id: device.branch_id,
+        })
+    }
+
+    /// Students in the device's branch with today's attendance status,
+    /// today being the date in the school's timezone.
+    #[instrument(skip(db, cache))]
+    pub async fn get_roster(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        device: &KioskDevice,
+    ) -> Result<Vec<KioskRosterStudent>, AppError> {
+        let today = SchoolService::get_settings(db, cache, device.school_id)
+            .await?
+            .today();
+        let roster = sqlx::query_as::<_, KioskRosterStudent>(
+            r#"SELECT u.id, u.first_name, u.last_name, a.status
+               FROM users u
+               INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $2
+               LEFT JOIN attendance_records a ON a.student_id = u.id AND a.date = $3
+               WHERE u.branch_id = $1
+               ORDER BY u.last_name, u.first_name"#,
+        )
+        .bind(device.branch_id)
+        .bind(system_roles::STUDENT)
+        .bind(today)
+        .fetch_all(db)
+        .await?;
+
+        Ok(roster)
+    }
+
+    /// Mark today's attendance for the device's branch, today being the
+    /// date in the school's timezone.
+    #[instrument(skip(db, cache, dto))]
+    pub async fn mark_attendance(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        device: &KioskDevice,
+        staff_id: UserId,
+        dto: KioskAttendanceDto,
+    ) -> Result<MarkAttendanceResponse, AppError> {
+        let settings = SchoolService::get_settings(db, cache, device.school_id).await?;
+        let dto = MarkAttendanceDto {
+            branch_id: device.branch_id,
+            date: settings.today(),
+            records: dto.records,
+        };
+
+        AttendanceService::mark_attendance(
+            db,
+            device.school_id,
+            staff_id,
+            Some(device.id),
+            false,
+            dto,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::attendance::model::{AttendanceEntryDto, AttendanceStatus};
+    use chalkbyte_config::JwtKeySet;
+    use chalkbyte_models::Email;
+    use chalkbyte_models::ids::{BranchId, RoleId};
+    use uuid::Uuid;
+
+    fn jwt_config() -> JwtConfig {
+        JwtConfig {
+            secret: "test-secret-key-at-least-32-characters-long".to_string(),
+            card_secret: "test-secret-key-at-least-32-characters-long".to_string(),
+            keys: JwtKeySet::hmac("test-secret-key-at-least-32-characters-long"),
+            access_token_expiry: 3600,
+            refresh_token_expiry: 604800,
+        }
+    }
+
+    async fn create_test_branch(pool: &PgPool) -> (SchoolId, BranchId) {
+        let school_id = sqlx::query_scalar!(
+            r#"INSERT INTO schools (name) VALUES ($1) RETURNING id"#,
+            format!("School {}", Uuid::new_v4())
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let level_id = sqlx::query_scalar!(
+            "INSERT INTO levels (name, school_id) VALUES ('JSS 1', $1) RETURNING id",
+            school_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let branch_id = sqlx::query_scalar!(
+            "INSERT INTO branches (name, level_id) VALUES ('A', $1) RETURNING id",
+            level_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        (school_id.into(), branch_id.into())
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId, role_id: RoleId) -> UserId {
+        let user_id = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Ada', 'Tester', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id,
+            role_id.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id.into()
+    }
+
+    async fn register_test_device(
+        pool: &PgPool,
+        school_id: SchoolId,
+        branch_id: BranchId,
+        registered_by: UserId,
+    ) -> RegisteredKioskDevice {
+        KioskService::register_device(
+            pool,
+            school_id,
+            registered_by,
+            RegisterKioskDeviceDto {
+                name: "JSS 1A tablet".to_string(),
+                branch_id,
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_revoked_device_cannot_authenticate(pool: PgPool) {
+        let (school_id, branch_id) = create_test_branch(&pool).await;
+        let admin_id = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+        let registered = register_test_device(&pool, school_id, branch_id, admin_id).await;
+
+        let device = KioskService::authenticate_device(&pool, &registered.device_token)
+            .await
+            .unwrap();
+        assert_eq!(device.branch_id, branch_id);
+
+        KioskService::revoke_device(&pool, device.id, Some(school_id))
+            .await
+            .unwrap();
+
+        let result = KioskService::authenticate_device(&pool, &registered.device_token).await;
+        assert_eq!(result.unwrap_err().status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_session_locks_pin_after_failed_attempts(pool: PgPool) {
+        let (school_id, branch_id) = create_test_branch(&pool).await;
+        let teacher_id = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let registered = register_test_device(&pool, school_id, branch_id, teacher_id).await;
+        KioskService::set_pin(&pool, teacher_id, "4821")
+            .await
+            .unwrap();
+
+        let email = sqlx::query_scalar!(
+            "SELECT email FROM users WHERE id = $1",
+            teacher_id.into_inner()
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let config = jwt_config();
+        let request = |pin: &str| KioskSessionRequest {
+            email: Email::new(email.clone()).unwrap(),
+            pin: pin.to_string(),
+        };
+
+        let session =
+            KioskService::start_session(&pool, &config, &registered.device, request("4821"))
+                .await
+                .unwrap();
+        assert_eq!(session.staff_id, teacher_id);
+
+        for _ in 0..MAX_PIN_ATTEMPTS {
+            let err =
+                KioskService::start_session(&pool, &config, &registered.device, request("0000"))
+                    .await
+                    .unwrap_err();
+            assert_eq!(err.status, StatusCode::UNAUTHORIZED);
+        }
+
+        let err = KioskService::start_session(&pool, &config, &registered.device, request("4821"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_session_requires_attendance_mark(pool: PgPool) {
+        let (school_id, branch_id) = create_test_branch(&pool).await;
+        let admin_id = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+        let student_id = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let registered = register_test_device(&pool, school_id, branch_id, admin_id).await;
+        KioskService::set_pin(&pool, student_id, "4821")
+            .await
+            .unwrap();
+
+        let email = sqlx::query_scalar!(
+            "SELECT email FROM users WHERE id = $1",
+            student_id.into_inner()
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let request = KioskSessionRequest {
+            email: Email::new(email).unwrap(),
+            pin: "4821".to_string(),
+        };
+
+        let err = KioskService::start_session(&pool, &jwt_config(), &registered.device, request)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_kiosk_marks_only_branch_students(pool: PgPool) {
+        let (school_id, branch_id) = create_test_branch(&pool).await;
+        let teacher_id = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let student_id = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let outsider_id = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let registered = register_test_device(&pool, school_id, branch_id, teacher_id).await;
+
+        sqlx::query!(
+            "UPDATE users SET branch_id = $1 WHERE id = $2",
+            branch_id.into_inner(),
+            student_id.into_inner()
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let entry = |student_id| AttendanceEntryDto {
+            student_id,
+            status: AttendanceStatus::Present,
+            note: None,
+        };
+        let response = KioskService::mark_attendance(
+            &pool,
+            None,
+            &registered.device,
+            teacher_id,
+            KioskAttendanceDto {
+                records: vec![entry(student_id), entry(outsider_id)],
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.recorded_count, 1);
+        assert_eq!(response.failed_ids, vec![outsider_id]);
+
+        let roster = KioskService::get_roster(&pool, None, &registered.device)
+            .await
+            .unwrap();
+        assert_eq!(roster.len(), 1);
+        assert_eq!(roster[0].status, Some(AttendanceStatus::Present));
+    }
+}
@@ -1,28 +1,35 @@
 use axum::{
     Json,
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{OriginalUri, Path, Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
 };
 use tracing::instrument;
 use uuid::Uuid;
-use validator::Validate;
 
-use chalkbyte_core::AppError;
-use chalkbyte_models::ids::{LevelId, UserId};
+use chalkbyte_cache::{ListEtag, lists};
+use chalkbyte_core::permissions;
+use chalkbyte_core::{AppError, Conditional, Created, IfMatch, NoContent, WithEtag};
+use chalkbyte_models::ids::{LevelId, SchoolId, UserId};
 
 use crate::middleware::auth::{
-    RequireLevelsAssignStudents, RequireLevelsCreate, RequireLevelsDelete, RequireLevelsRead,
-    RequireLevelsUpdate,
+    RequireLevelsAssignStudents, RequireLevelsCreate, RequireLevelsDelete, RequireLevelsPromote,
+    RequireLevelsRead, RequireLevelsUpdate, RequireSchoolsRead, RequireSchoolsUpdate,
 };
-use crate::middleware::role::is_system_admin_jwt;
+use crate::modules::auth::controller::ErrorResponse;
 use crate::modules::levels::model::{
-    AssignStudentsToLevelDto, BulkAssignResponse, CreateLevelDto, Level, LevelFilterParams,
-    LevelWithStats, MoveStudentToLevelDto, PaginatedLevelsResponse, UpdateLevelDto,
+    AssignStudentsToLevelDto, BulkAssignResponse, CreateLevelDto, GenerateLevelsDto,
+    GenerateLevelsResponse, Level, LevelFilterParams, LevelWithStats, MoveStudentToLevelDto,
+    NamingTemplates, PaginatedLevelsResponse, PromoteStudentsDto, PromotionHistoryParams,
+    PromotionReport, StudentPromotion, UpdateLevelDto, UpdateNamingTemplatesDto,
 };
-use crate::modules::levels::service::LevelService;
+use crate::modules::levels::service::{LevelService, PromotionService};
 use crate::modules::users::model::User;
 use crate::state::AppState;
-use crate::utils::auth_helpers::{get_admin_school_id, get_school_id_for_scoped_operation};
+use crate::utils::auth_helpers::{
+    get_school_id_for_scoped_operation, tenant_context, verify_school_access,
+};
+use crate::validator::ValidatedJson;
 
 #[utoipa::path(
     post,
@@ -30,10 +37,12 @@ use crate::utils::auth_helpers::{get_admin_school_id, get_school_id_for_scoped_o
     summary = "Create level",
     request_body = CreateLevelDto,
     responses(
-        (status = 201, description = "Level created successfully", body = Level),
-        (status = 400, description = "Invalid input or missing school_id for system admin"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires levels:create permission")
+        Created<Level>,
+        (status = 400, description = "Invalid input or missing school_id for system admin", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:create permission", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Levels",
     security(("bearer_auth" = []))
@@ -42,29 +51,32 @@ use crate::utils::auth_helpers::{get_admin_school_id, get_school_id_for_scoped_o
 pub async fn create_level(
     State(state): State<AppState>,
     RequireLevelsCreate(auth_user): RequireLevelsCreate,
-    Json(dto): Json<CreateLevelDto>,
-) -> Result<(StatusCode, Json<Level>), AppError> {
+    ValidatedJson(dto): ValidatedJson<CreateLevelDto>,
+) -> Result<Created<Level>, AppError> {
     // Creating requires school_id - system admins must specify it in the DTO
     let school_id =
         get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
 
-    dto.validate()?;
-
     let level = LevelService::create_level(&state.db, state.cache.as_ref(), school_id, dto).await?;
 
-    Ok((StatusCode::CREATED, Json(level)))
+    Ok(Created(level))
 }
 
 #[utoipa::path(
     get,
     path = "/api/levels",
     summary = "List levels",
-    params(LevelFilterParams),
+    params(
+        LevelFilterParams,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a previously fetched list")
+    ),
     responses(
         (status = 200, description = "List of levels", body = PaginatedLevelsResponse),
-        (status = 400, description = "Missing school_id for system admin"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires levels:read permission")
+        (status = 304, description = "Not modified - the list still matches If-None-Match"),
+        (status = 400, description = "Missing school_id for system admin", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:read permission", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Levels",
     security(("bearer_auth" = []))
@@ -73,15 +85,28 @@ pub async fn create_level(
 pub async fn get_levels(
     State(state): State<AppState>,
     RequireLevelsRead(auth_user): RequireLevelsRead,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
     Query(filters): Query<LevelFilterParams>,
-) -> Result<Json<PaginatedLevelsResponse>, AppError> {
+) -> Result<Response, AppError> {
     // Listing requires school_id - system admins must specify it in query params
     let school_id =
         get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
 
+    let etag = ListEtag::lookup(
+        state.cache.as_ref(),
+        lists::LEVELS,
+        Some(school_id.into()),
+        &uri.to_string(),
+    )
+    .await;
+    if let Some(not_modified) = etag.not_modified(&headers) {
+        return Ok(not_modified);
+    }
+
     let levels = LevelService::get_levels_by_school(&state.db, school_id, filters).await?;
 
-    Ok(Json(levels))
+    Ok(etag.store(Json(levels).into_response()).await)
 }
 
 #[utoipa::path(
@@ -92,10 +117,11 @@ pub async fn get_levels(
         ("id" = Uuid, Path, description = "Level ID")
     ),
     responses(
-        (status = 200, description = "Level details", body = LevelWithStats),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires levels:read permission"),
-        (status = 404, description = "Level not found")
+        WithEtag<LevelWithStats>,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:read permission", body = ErrorResponse),
+        (status = 404, description = "Level not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Levels",
     security(("bearer_auth" = []))
@@ -105,19 +131,14 @@ pub async fn get_level_by_id(
     State(state): State<AppState>,
     RequireLevelsRead(auth_user): RequireLevelsRead,
     Path(id): Path<Uuid>,
-) -> Result<Json<LevelWithStats>, AppError> {
+) -> Result<WithEtag<LevelWithStats>, AppError> {
     let level_id = LevelId::from(id);
 
-    // For resource operations, system admins don't need school_id
-    if is_system_admin_jwt(&auth_user) {
-        let level = LevelService::get_level_by_id_no_school_filter(&state.db, level_id).await?;
-        return Ok(Json(level));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    let level = LevelService::get_level_by_id(&state.db, level_id, school_id).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    let level =
+        LevelService::get_level_by_id(&state.db, state.cache.as_ref(), &tenant, level_id).await?;
 
-    Ok(Json(level))
+    Ok(WithEtag(level))
 }
 
 #[utoipa::path(
@@ -125,15 +146,18 @@ pub async fn get_level_by_id(
     path = "/api/levels/{id}",
     summary = "Update level",
     params(
-        ("id" = Uuid, Path, description = "Level ID")
+        ("id" = Uuid, Path, description = "Level ID"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the level as last read; the update is refused if it has changed since")
     ),
     request_body = UpdateLevelDto,
     responses(
-        (status = 200, description = "Level updated successfully", body = Level),
-        (status = 400, description = "Invalid input"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires levels:update permission"),
-        (status = 404, description = "Level not found")
+        Conditional<Level>,
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:update permission", body = ErrorResponse),
+        (status = 404, description = "Level not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Levels",
     security(("bearer_auth" = []))
@@ -143,29 +167,21 @@ pub async fn update_level(
     State(state): State<AppState>,
     RequireLevelsUpdate(auth_user): RequireLevelsUpdate,
     Path(id): Path<Uuid>,
-    Json(dto): Json<UpdateLevelDto>,
-) -> Result<Json<Level>, AppError> {
-    dto.validate()?;
+    if_match: IfMatch,
+    ValidatedJson(dto): ValidatedJson<UpdateLevelDto>,
+) -> Result<Conditional<Level>, AppError> {
     let level_id = LevelId::from(id);
 
-    // For resource operations, system admins don't need school_id
-    if is_system_admin_jwt(&auth_user) {
-        let level = LevelService::update_level_no_school_filter(
-            &state.db,
-            state.cache.as_ref(),
-            level_id,
-            dto,
-        )
-        .await?;
-        return Ok(Json(level));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    let level =
-        LevelService::update_level(&state.db, state.cache.as_ref(), level_id, school_id, dto)
-            .await?;
-
-    Ok(Json(level))
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    LevelService::update_level(
+        &state.db,
+        state.cache.as_ref(),
+        &tenant,
+        level_id,
+        &if_match,
+        dto,
+    )
+    .await
 }
 
 #[utoipa::path(
@@ -176,10 +192,11 @@ pub async fn update_level(
         ("id" = Uuid, Path, description = "Level ID")
     ),
     responses(
-        (status = 204, description = "Level deleted successfully"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires levels:delete permission"),
-        (status = 404, description = "Level not found")
+        NoContent,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:delete permission", body = ErrorResponse),
+        (status = 404, description = "Level not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Levels",
     security(("bearer_auth" = []))
@@ -189,20 +206,13 @@ pub async fn delete_level(
     State(state): State<AppState>,
     RequireLevelsDelete(auth_user): RequireLevelsDelete,
     Path(id): Path<Uuid>,
-) -> Result<StatusCode, AppError> {
+) -> Result<NoContent, AppError> {
     let level_id = LevelId::from(id);
 
-    // For resource operations, system admins don't need school_id
-    if is_system_admin_jwt(&auth_user) {
-        LevelService::delete_level_no_school_filter(&state.db, state.cache.as_ref(), level_id)
-            .await?;
-        return Ok(StatusCode::NO_CONTENT);
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    LevelService::delete_level(&state.db, state.cache.as_ref(), level_id, school_id).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    LevelService::delete_level(&state.db, state.cache.as_ref(), &tenant, level_id).await?;
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok(NoContent)
 }
 
 #[utoipa::path(
@@ -215,10 +225,12 @@ pub async fn delete_level(
     request_body = AssignStudentsToLevelDto,
     responses(
         (status = 200, description = "Students assigned to level", body = BulkAssignResponse),
-        (status = 400, description = "Invalid input"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires levels:assign_students permission"),
-        (status = 404, description = "Level not found")
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:assign_students permission", body = ErrorResponse),
+        (status = 404, description = "Level not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Levels",
     security(("bearer_auth" = []))
@@ -228,22 +240,19 @@ pub async fn assign_students_to_level(
     State(state): State<AppState>,
     RequireLevelsAssignStudents(auth_user): RequireLevelsAssignStudents,
     Path(id): Path<Uuid>,
-    Json(dto): Json<AssignStudentsToLevelDto>,
+    ValidatedJson(dto): ValidatedJson<AssignStudentsToLevelDto>,
 ) -> Result<Json<BulkAssignResponse>, AppError> {
-    dto.validate()?;
     let level_id = LevelId::from(id);
 
-    // For resource operations, system admins don't need school_id
-    if is_system_admin_jwt(&auth_user) {
-        let response =
-            LevelService::assign_students_to_level_no_school_filter(&state.db, level_id, dto)
-                .await?;
-        return Ok(Json(response));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    let response =
-        LevelService::assign_students_to_level(&state.db, level_id, school_id, dto).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    let response = LevelService::assign_students_to_level(
+        &state.db,
+        state.cache.as_ref(),
+        &tenant,
+        level_id,
+        dto,
+    )
+    .await?;
 
     Ok(Json(response))
 }
@@ -257,9 +266,10 @@ pub async fn assign_students_to_level(
     ),
     responses(
         (status = 200, description = "List of students in level", body = Vec<User>),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires levels:read permission"),
-        (status = 404, description = "Level not found")
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:read permission", body = ErrorResponse),
+        (status = 404, description = "Level not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Levels",
     security(("bearer_auth" = []))
@@ -272,15 +282,8 @@ pub async fn get_students_in_level(
 ) -> Result<Json<Vec<User>>, AppError> {
     let level_id = LevelId::from(id);
 
-    // For resource operations, system admins don't need school_id
-    if is_system_admin_jwt(&auth_user) {
-        let students =
-            LevelService::get_students_in_level_no_school_filter(&state.db, level_id).await?;
-        return Ok(Json(students));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    let students = LevelService::get_students_in_level(&state.db, level_id, school_id).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    let students = LevelService::get_students_in_level(&state.db, &tenant, level_id).await?;
 
     Ok(Json(students))
 }
@@ -294,11 +297,13 @@ pub async fn get_students_in_level(
     ),
     request_body = MoveStudentToLevelDto,
     responses(
-        (status = 204, description = "Student moved successfully"),
-        (status = 400, description = "Invalid input"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires levels:assign_students permission"),
-        (status = 404, description = "Student or level not found")
+        NoContent,
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:assign_students permission", body = ErrorResponse),
+        (status = 404, description = "Student or level not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Levels",
     security(("bearer_auth" = []))
@@ -308,21 +313,15 @@ pub async fn move_student_to_level(
     State(state): State<AppState>,
     RequireLevelsAssignStudents(auth_user): RequireLevelsAssignStudents,
     Path(student_id): Path<Uuid>,
-    Json(dto): Json<MoveStudentToLevelDto>,
-) -> Result<StatusCode, AppError> {
-    dto.validate()?;
+    ValidatedJson(dto): ValidatedJson<MoveStudentToLevelDto>,
+) -> Result<NoContent, AppError> {
     let student_id = UserId::from(student_id);
 
-    // For resource operations, system admins don't need school_id
-    if is_system_admin_jwt(&auth_user) {
-        LevelService::move_student_to_level_no_school_filter(&state.db, student_id, dto).await?;
-        return Ok(StatusCode::NO_CONTENT);
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    LevelService::move_student_to_level(&state.db, student_id, school_id, dto).await?;
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    LevelService::move_student_to_level(&state.db, state.cache.as_ref(), &tenant, student_id, dto)
+        .await?;
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok(NoContent)
 }
 
 #[utoipa::path(
@@ -333,10 +332,11 @@ pub async fn move_student_to_level(
         ("student_id" = Uuid, Path, description = "Student ID")
     ),
     responses(
-        (status = 204, description = "Student removed from level"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires levels:assign_students permission"),
-        (status = 404, description = "Student not found")
+        NoContent,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:assign_students permission", body = ErrorResponse),
+        (status = 404, description = "Student not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Levels",
     security(("bearer_auth" = []))
@@ -346,17 +346,251 @@ pub async fn remove_student_from_level(
     State(state): State<AppState>,
     RequireLevelsAssignStudents(auth_user): RequireLevelsAssignStudents,
     Path(student_id): Path<Uuid>,
-) -> Result<StatusCode, AppError> {
+) -> Result<NoContent, AppError> {
     let student_id = UserId::from(student_id);
 
-    // For resource operations, system admins don't need school_id
-    if is_system_admin_jwt(&auth_user) {
-        LevelService::remove_student_from_level_no_school_filter(&state.db, student_id).await?;
-        return Ok(StatusCode::NO_CONTENT);
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    LevelService::remove_student_from_level(&state.db, state.cache.as_ref(), &tenant, student_id)
+        .await?;
+
+    Ok(NoContent)
+}
+
+/// Get a school's level and branch naming templates
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/naming-templates",
+    summary = "Get naming templates",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    responses(
+        (status = 200, description = "Naming templates", body = NamingTemplates),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires schools:read permission and access to the school", body = ErrorResponse),
+        (status = 404, description = "School not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Levels",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_naming_templates(
+    State(state): State<AppState>,
+    RequireSchoolsRead(auth_user): RequireSchoolsRead,
+    Path(school_id): Path<Uuid>,
+) -> Result<Json<NamingTemplates>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let templates = LevelService::get_naming_templates(&state.db, school_id).await?;
+
+    Ok(Json(templates))
+}
+
+/// Update a school's level and branch naming templates
+///
+/// Templates may use `{n}` for the level number and `{letter}` for the branch
+/// letter, e.g. `JSS {n}` for levels and `JSS {n}{letter}` for branches.
+#[utoipa::path(
+    put,
+    path = "/api/schools/{id}/naming-templates",
+    summary = "Update naming templates",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = UpdateNamingTemplatesDto,
+    responses(
+        (status = 200, description = "Naming templates updated", body = NamingTemplates),
+        (status = 400, description = "Invalid template", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires schools:update permission and access to the school", body = ErrorResponse),
+        (status = 404, description = "School not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Levels",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn update_naming_templates(
+    State(state): State<AppState>,
+    RequireSchoolsUpdate(auth_user): RequireSchoolsUpdate,
+    Path(school_id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateNamingTemplatesDto>,
+) -> Result<Json<NamingTemplates>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let templates = LevelService::update_naming_templates(&state.db, school_id, dto).await?;
+
+    Ok(Json(templates))
+}
+
+/// Generate levels and branches from the school's naming templates
+///
+/// Creates `count` consecutive levels starting at `start`, each with
+/// `branches_per_level` branches. Names that already exist are reused, so
+/// the request can be repeated safely.
+#[utoipa::path(
+    post,
+    path = "/api/schools/{id}/levels/generate",
+    summary = "Generate levels and branches",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = GenerateLevelsDto,
+    responses(
+        Created<GenerateLevelsResponse>,
+        (status = 400, description = "Invalid input", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:create (and branches:create when generating branches) and access to the school", body = ErrorResponse),
+        (status = 404, description = "School not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Levels",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn generate_levels(
+    State(state): State<AppState>,
+    RequireLevelsCreate(auth_user): RequireLevelsCreate,
+    Path(school_id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<GenerateLevelsDto>,
+) -> Result<Created<GenerateLevelsResponse>, AppError> {
+    if dto.branches_per_level > 0 && !auth_user.has_permission(permissions::BRANCHES_CREATE) {
+        return Err(AppError::forbidden(format!(
+            "Access denied. Missing required permission: {}",
+            permissions::BRANCHES_CREATE
+        )));
     }
 
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    LevelService::remove_student_from_level(&state.db, student_id, school_id).await?;
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let generated =
+        LevelService::generate_levels(&state.db, state.cache.as_ref(), school_id, dto).await?;
+
+    Ok(Created(generated))
+}
+
+/// Preview an end-of-session promotion
+///
+/// Runs the same checks and planning as the promotion itself and reports
+/// how many students each level would promote, graduate, or hold back,
+/// without changing anything.
+#[utoipa::path(
+    post,
+    path = "/api/levels/promotions/preview",
+    summary = "Preview student promotion",
+    request_body = PromoteStudentsDto,
+    responses(
+        (status = 200, description = "Promotion preview", body = PromotionReport),
+        (status = 400, description = "Invalid input, excluded students outside the promoted levels, or session already promoted", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:promote permission", body = ErrorResponse),
+        (status = 404, description = "Academic session or level not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Levels",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn preview_promotion(
+    State(state): State<AppState>,
+    RequireLevelsPromote(auth_user): RequireLevelsPromote,
+    ValidatedJson(dto): ValidatedJson<PromoteStudentsDto>,
+) -> Result<Json<PromotionReport>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let report = PromotionService::promote_students(
+        &state.db,
+        state.cache.as_ref(),
+        school_id,
+        auth_user.user_id()?,
+        dto,
+        true,
+    )
+    .await?;
+
+    Ok(Json(report))
+}
+
+/// Promote students at the end of a session
+///
+/// Moves every student in each mapped level to its target level, or out of
+/// all levels when the mapping has no target, clearing their branches.
+/// Excluded students repeat their level. Each session can be promoted once,
+/// and every outcome is recorded in the promotion history.
+#[utoipa::path(
+    post,
+    path = "/api/levels/promotions",
+    summary = "Promote students",
+    request_body = PromoteStudentsDto,
+    responses(
+        (status = 200, description = "Students promoted", body = PromotionReport),
+        (status = 400, description = "Invalid input, excluded students outside the promoted levels, or session already promoted", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:promote permission", body = ErrorResponse),
+        (status = 404, description = "Academic session or level not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Levels",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn promote_students(
+    State(state): State<AppState>,
+    RequireLevelsPromote(auth_user): RequireLevelsPromote,
+    ValidatedJson(dto): ValidatedJson<PromoteStudentsDto>,
+) -> Result<Json<PromotionReport>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let report = PromotionService::promote_students(
+        &state.db,
+        state.cache.as_ref(),
+        school_id,
+        auth_user.user_id()?,
+        dto,
+        false,
+    )
+    .await?;
+
+    Ok(Json(report))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/levels/promotions",
+    summary = "Get promotion history",
+    params(PromotionHistoryParams),
+    responses(
+        (status = 200, description = "Recorded promotions for the session", body = Vec<StudentPromotion>),
+        (status = 400, description = "Missing school_id for system admin", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires levels:read permission", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Levels",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_promotion_history(
+    State(state): State<AppState>,
+    RequireLevelsRead(auth_user): RequireLevelsRead,
+    Query(params): Query<PromotionHistoryParams>,
+) -> Result<Json<Vec<StudentPromotion>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+
+    let promotions =
+        PromotionService::get_promotion_history(&state.db, school_id, params.academic_session_id)
+            .await?;
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok(Json(promotions))
 }
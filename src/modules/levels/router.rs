@@ -6,13 +6,20 @@ use axum::{
 use crate::state::AppState;
 
 use super::controller::{
-    assign_students_to_level, create_level, delete_level, get_level_by_id, get_levels,
-    get_students_in_level, move_student_to_level, remove_student_from_level, update_level,
+    assign_students_to_level, create_level, delete_level, generate_levels, get_level_by_id,
+    get_levels, get_naming_templates, get_promotion_history, get_students_in_level,
+    move_student_to_level, preview_promotion, promote_students, remove_student_from_level,
+    update_level, update_naming_templates,
 };
 
 pub fn init_levels_router() -> Router<AppState> {
     Router::new()
         .route("/", post(create_level).get(get_levels))
+        .route(
+            "/promotions",
+            post(promote_students).get(get_promotion_history),
+        )
+        .route("/promotions/preview", post(preview_promotion))
         .route(
             "/{id}",
             get(get_level_by_id).put(update_level).delete(delete_level),
@@ -24,3 +31,14 @@ pub fn init_levels_router() -> Router<AppState> {
         .route("/students/{student_id}/move", patch(move_student_to_level))
         .route("/students/{student_id}", delete(remove_student_from_level))
 }
+
+/// Initialize the naming templates router, merged into the schools router
+/// Routes: GET/PUT /{id}/naming-templates, POST /{id}/levels/generate
+pub fn init_naming_templates_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/{id}/naming-templates",
+            get(get_naming_templates).put(update_naming_templates),
+        )
+        .route("/{id}/levels/generate", post(generate_levels))
+}
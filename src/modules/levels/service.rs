@@ -1,22 +1,38 @@
-use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use sqlx::{PgExecutor, PgPool};
 use tracing::instrument;
 
-use chalkbyte_cache::RedisCache;
-use chalkbyte_core::{AppError, PaginationMeta};
-use chalkbyte_models::ids::{LevelId, SchoolId, UserId};
+use chalkbyte_cache::{RedisCache, keys};
+use chalkbyte_core::{AppError, Conditional, ErrorCode, IfMatch, PaginationMeta};
+use chalkbyte_models::ids::{AcademicSessionId, LevelId, SchoolId, UserId};
 
+use crate::config::database::{TenantContext, UnitOfWork};
+use crate::modules::branches::model::Branch;
 use crate::modules::levels::model::{
-    AssignStudentsToLevelDto, BulkAssignResponse, CreateLevelDto, Level, LevelFilterParams,
-    LevelWithStats, MoveStudentToLevelDto, PaginatedLevelsResponse, UpdateLevelDto,
+    AssignStudentsToLevelDto, BulkAssignResponse, CreateLevelDto, GenerateLevelsDto,
+    GenerateLevelsResponse, GeneratedLevel, Level, LevelFilterParams, LevelPromotionMapping,
+    LevelPromotionSummary, LevelWithStats, MoveStudentToLevelDto, NamingTemplates,
+    PaginatedLevelsResponse, PromoteStudentsDto, PromotionOutcome, PromotionReport,
+    StudentPromotion, UpdateLevelDto, UpdateNamingTemplatesDto, render_name_template,
 };
+use crate::modules::students::service::{AssignmentTarget, StudentService};
 use crate::modules::users::model::system_roles;
 
+/// How long level stats are served before a single caller recomputes them.
+const STATS_CACHE_TTL: Duration = Duration::from_secs(30);
+
+const NAMING_TEMPLATE_COLUMNS: &str = "id AS school_id, level_name_template, branch_name_template";
+
 pub struct LevelService;
 
 impl LevelService {
-    #[instrument(skip(cache))]
+    /// Create a level. Runs on the pool or on a transaction it is composed
+    /// in.
+    #[instrument(skip(db, cache))]
     pub async fn create_level(
-        db: &PgPool,
+        db: impl PgExecutor<'_>,
         cache: Option<&RedisCache>,
         school_id: SchoolId,
         dto: CreateLevelDto,
@@ -35,9 +51,7 @@ impl LevelService {
             if let sqlx::Error::Database(db_err) = &e
                 && db_err.is_unique_violation()
             {
-                return AppError::bad_request(anyhow::anyhow!(
-                    "A level with this name already exists in this school"
-                ));
+                return AppError::from_code(ErrorCode::LevelNameConflict);
             }
             AppError::from(e)
         })?;
@@ -122,42 +136,32 @@ impl LevelService {
         })
     }
 
-    #[instrument]
+    /// Get a level with its student count, cached with single-flight
+    /// recompute when the caller is scoped to one school
+    #[instrument(skip(db, cache))]
     pub async fn get_level_by_id(
         db: &PgPool,
+        cache: Option<&RedisCache>,
+        tenant: &TenantContext,
         level_id: LevelId,
-        school_id: SchoolId,
     ) -> Result<LevelWithStats, AppError> {
-        let student_role_id = system_roles::STUDENT;
-        let level = sqlx::query_as::<_, LevelWithStats>(
-            r#"SELECT
-                l.id,
-                l.name,
-                l.description,
-                l.school_id,
-                l.created_at,
-                l.updated_at,
-                COUNT(DISTINCT u.id) as student_count
-               FROM levels l
-               LEFT JOIN users u ON u.level_id = l.id
-               LEFT JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $3
-               WHERE l.id = $1 AND l.school_id = $2
-               GROUP BY l.id, l.name, l.description, l.school_id, l.created_at, l.updated_at"#,
-        )
-        .bind(level_id)
-        .bind(school_id)
-        .bind(student_role_id)
-        .fetch_optional(db)
-        .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Level not found")))?;
+        let fetch = || {
+            UnitOfWork::run_as(db, tenant, async |uow| {
+                Self::fetch_level_with_stats(uow.conn(), level_id).await
+            })
+        };
+        let (Some(cache), Some(&[school_id])) = (cache, tenant.school_ids()) else {
+            return fetch().await;
+        };
 
-        Ok(level)
+        let cache_key = keys::levels::stats(level_id.into(), school_id);
+        cache
+            .get_or_compute(&cache_key, STATS_CACHE_TTL, fetch)
+            .await
     }
 
-    /// Get level by ID without school filtering (for system admins)
-    #[instrument]
-    pub async fn get_level_by_id_no_school_filter(
-        db: &PgPool,
+    async fn fetch_level_with_stats(
+        db: impl PgExecutor<'_>,
         level_id: LevelId,
     ) -> Result<LevelWithStats, AppError> {
         let student_role_id = system_roles::STUDENT;
@@ -180,142 +184,95 @@ impl LevelService {
         .bind(student_role_id)
         .fetch_optional(db)
         .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Level not found")))?;
+        .ok_or_else(|| AppError::from_code(ErrorCode::LevelNotFound))?;
 
         Ok(level)
     }
 
-    #[instrument]
+    /// Update a level. Leaves it unchanged when it no longer matches
+    /// `if_match`.
+    #[instrument(skip(db, cache))]
     pub async fn update_level(
         db: &PgPool,
         cache: Option<&RedisCache>,
+        tenant: &TenantContext,
         level_id: LevelId,
-        school_id: SchoolId,
+        if_match: &IfMatch,
         dto: UpdateLevelDto,
-    ) -> Result<Level, AppError> {
-        let existing_level = sqlx::query_as::<_, Level>(
-            "SELECT id, name, description, school_id, created_at, updated_at FROM levels WHERE id = $1 AND school_id = $2",
-        )
-        .bind(level_id)
-        .bind(school_id)
-        .fetch_optional(db)
-        .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Level not found")))?;
-
-        let name = dto.name.unwrap_or(existing_level.name);
-        let description = if dto.description.is_some() {
-            dto.description
-        } else {
-            existing_level.description
-        };
+    ) -> Result<Conditional<Level>, AppError> {
+        let level = UnitOfWork::run_as(db, tenant, async |uow| {
+            let existing_level = sqlx::query_as::<_, Level>(
+                "SELECT id, name, description, school_id, created_at, updated_at FROM levels WHERE id = $1 FOR UPDATE",
+            )
+            .bind(level_id)
+            .fetch_optional(uow.conn())
+            .await?
+            .ok_or_else(|| AppError::from_code(ErrorCode::LevelNotFound))?;
 
-        let level = sqlx::query_as::<_, Level>(
-            r#"UPDATE levels
-               SET name = $1, description = $2, updated_at = NOW()
-               WHERE id = $3 AND school_id = $4
-               RETURNING id, name, description, school_id, created_at, updated_at"#,
-        )
-        .bind(&name)
-        .bind(&description)
-        .bind(level_id)
-        .bind(school_id)
-        .fetch_one(db)
-        .await
-        .map_err(|e| {
-            if let sqlx::Error::Database(db_err) = &e
-                && db_err.is_unique_violation()
-            {
-                return AppError::bad_request(anyhow::anyhow!(
-                    "A level with this name already exists in this school"
-                ));
+            if !if_match.matches(&existing_level) {
+                return Ok(Conditional::PreconditionFailed(existing_level));
             }
-            AppError::from(e)
-        })?;
-
-        // Invalidate level caches
-        chalkbyte_cache::keys::invalidate::level(
-            cache,
-            Some(level_id.into()),
-            Some(school_id.into()),
-        )
-        .await;
 
-        Ok(level)
-    }
-
-    /// Update level without school filtering (for system admins)
-    #[instrument(skip(cache))]
-    pub async fn update_level_no_school_filter(
-        db: &PgPool,
-        cache: Option<&RedisCache>,
-        level_id: LevelId,
-        dto: UpdateLevelDto,
-    ) -> Result<Level, AppError> {
-        let existing_level = sqlx::query_as::<_, Level>(
-            "SELECT id, name, description, school_id, created_at, updated_at FROM levels WHERE id = $1",
-        )
-        .bind(level_id)
-        .fetch_optional(db)
-        .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Level not found")))?;
-
-        let school_id = existing_level.school_id;
-        let name = dto.name.unwrap_or(existing_level.name);
-        let description = if dto.description.is_some() {
-            dto.description
-        } else {
-            existing_level.description
-        };
+            let name = dto.name.unwrap_or(existing_level.name);
+            let description = if dto.description.is_some() {
+                dto.description
+            } else {
+                existing_level.description
+            };
 
-        let level = sqlx::query_as::<_, Level>(
-            r#"UPDATE levels
-               SET name = $1, description = $2, updated_at = NOW()
-               WHERE id = $3
-               RETURNING id, name, description, school_id, created_at, updated_at"#,
-        )
-        .bind(&name)
-        .bind(&description)
-        .bind(level_id)
-        .fetch_one(db)
-        .await
-        .map_err(|e| {
-            if let sqlx::Error::Database(db_err) = &e
-                && db_err.is_unique_violation()
-            {
-                return AppError::bad_request(anyhow::anyhow!(
-                    "A level with this name already exists in this school"
-                ));
-            }
-            AppError::from(e)
-        })?;
+            sqlx::query_as::<_, Level>(
+                r#"UPDATE levels
+                   SET name = $1, description = $2, updated_at = NOW()
+                   WHERE id = $3
+                   RETURNING id, name, description, school_id, created_at, updated_at"#,
+            )
+            .bind(&name)
+            .bind(&description)
+            .bind(level_id)
+            .fetch_one(uow.conn())
+            .await
+            .map(Conditional::Updated)
+            .map_err(|e| {
+                if let sqlx::Error::Database(db_err) = &e
+                    && db_err.is_unique_violation()
+                {
+                    return AppError::from_code(ErrorCode::LevelNameConflict);
+                }
+                AppError::from(e)
+            })
+        })
+        .await?;
 
         // Invalidate level caches
-        chalkbyte_cache::keys::invalidate::level(
-            cache,
-            Some(level_id.into()),
-            Some(school_id.into()),
-        )
-        .await;
+        if let Conditional::Updated(level) = &level {
+            chalkbyte_cache::keys::invalidate::level(
+                cache,
+                Some(level_id.into()),
+                Some(level.school_id.into()),
+            )
+            .await;
+        }
 
         Ok(level)
     }
 
-    #[instrument(skip(cache))]
+    #[instrument(skip(db, cache))]
     pub async fn delete_level(
         db: &PgPool,
         cache: Option<&RedisCache>,
+        tenant: &TenantContext,
         level_id: LevelId,
-        school_id: SchoolId,
     ) -> Result<(), AppError> {
-        let result = sqlx::query("DELETE FROM levels WHERE id = $1 AND school_id = $2")
+        let school_id = UnitOfWork::run_as(db, tenant, async |uow| {
+            sqlx::query_scalar::<_, SchoolId>(
+                "DELETE FROM levels WHERE id = $1 RETURNING school_id",
+            )
             .bind(level_id)
-            .bind(school_id)
-            .execute(db)
-            .await?;
-
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!("Level not found")));
-        }
+            .fetch_optional(uow.conn())
+            .await?
+            .ok_or_else(|| AppError::from_code(ErrorCode::LevelNotFound))
+        })
+        .await?;
 
         // Invalidate level caches
         chalkbyte_cache::keys::invalidate::level(
@@ -328,401 +285,598 @@ impl LevelService {
         Ok(())
     }
 
-    /// Delete level without school filtering (for system admins)
-    #[instrument(skip(cache))]
-    pub async fn delete_level_no_school_filter(
+    /// Move students into a level with one role check and one batched update.
+    ///
+    /// IDs that are not students, or that belong to another school than the
+    /// level, are reported in `failed_ids`.
+    #[instrument(skip(db, cache))]
+    pub async fn assign_students_to_level(
         db: &PgPool,
         cache: Option<&RedisCache>,
+        tenant: &TenantContext,
         level_id: LevelId,
-    ) -> Result<(), AppError> {
-        // Get school_id before deletion for cache invalidation
-        let level = sqlx::query_as::<_, Level>(
-            "SELECT id, name, description, school_id, created_at, updated_at FROM levels WHERE id = $1",
-        )
-        .bind(level_id)
-        .fetch_optional(db)
+        dto: AssignStudentsToLevelDto,
+    ) -> Result<BulkAssignResponse, AppError> {
+        let (assigned_count, failed_ids) = UnitOfWork::run_as(db, tenant, async |uow| {
+            Self::ensure_level_exists(uow.conn(), level_id).await?;
+
+            StudentService::batch_assign_students(
+                uow.conn(),
+                &dto.student_ids,
+                AssignmentTarget::Level(level_id),
+            )
+            .await
+        })
         .await?;
 
-        let school_id = level.as_ref().map(|l| l.school_id);
+        keys::invalidate::user(cache, None, tenant.single_school()).await;
 
-        let result = sqlx::query("DELETE FROM levels WHERE id = $1")
-            .bind(level_id)
-            .execute(db)
-            .await?;
+        Ok(BulkAssignResponse {
+            assigned_count,
+            failed_ids,
+        })
+    }
 
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!("Level not found")));
-        }
+    async fn ensure_level_exists(
+        db: impl PgExecutor<'_>,
+        level_id: LevelId,
+    ) -> Result<(), AppError> {
+        let level_exists =
+            sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM levels WHERE id = $1)")
+                .bind(level_id)
+                .fetch_one(db)
+                .await?;
 
-        // Invalidate level caches
-        chalkbyte_cache::keys::invalidate::level(
-            cache,
-            Some(level_id.into()),
-            school_id.map(Into::into),
-        )
-        .await;
+        if !level_exists {
+            return Err(AppError::from_code(ErrorCode::LevelNotFound));
+        }
 
         Ok(())
     }
 
-    #[instrument]
-    pub async fn assign_students_to_level(
-        db: &PgPool,
-        level_id: LevelId,
-        school_id: SchoolId,
-        dto: AssignStudentsToLevelDto,
-    ) -> Result<BulkAssignResponse, AppError> {
-        let level_exists = sqlx::query_scalar::<_, bool>(
-            "SELECT EXISTS(SELECT 1 FROM levels WHERE id = $1 AND school_id = $2)",
+    async fn ensure_student(db: impl PgExecutor<'_>, student_id: UserId) -> Result<(), AppError> {
+        let is_student = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
         )
-        .bind(level_id)
-        .bind(school_id)
+        .bind(student_id)
+        .bind(system_roles::STUDENT)
         .fetch_one(db)
         .await?;
 
-        if !level_exists {
-            return Err(AppError::not_found(anyhow::anyhow!("Level not found")));
+        if !is_student {
+            return Err(AppError::from_code(ErrorCode::StudentNotFound));
         }
 
-        let mut assigned_count = 0;
-        let mut failed_ids = Vec::new();
-
-        let student_role_id = system_roles::STUDENT;
-        for student_id in dto.student_ids {
-            // First check if user is a student (has student role)
-            let is_student = sqlx::query_scalar::<_, bool>(
-                "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
-            )
-            .bind(student_id)
-            .bind(student_role_id)
-            .fetch_one(db)
-            .await
-            .unwrap_or(false);
+        Ok(())
+    }
 
-            if !is_student {
-                failed_ids.push(student_id);
-                continue;
+    /// Move a student into a level of their school, or out of their level
+    /// when `level_id` is empty.
+    #[instrument(skip(db, cache))]
+    pub async fn move_student_to_level(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        tenant: &TenantContext,
+        student_id: UserId,
+        dto: MoveStudentToLevelDto,
+    ) -> Result<(), AppError> {
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            if let Some(new_level_id) = dto.level_id {
+                Self::ensure_level_exists(uow.conn(), new_level_id).await?;
             }
+            Self::ensure_student(uow.conn(), student_id).await?;
 
             let result = sqlx::query(
                 r#"UPDATE users
                    SET level_id = $1, updated_at = NOW()
-                   WHERE id = $2 AND school_id = $3"#,
+                   WHERE id = $2
+                     AND ($1::uuid IS NULL OR school_id = (SELECT school_id FROM levels WHERE id = $1))"#,
             )
-            .bind(level_id)
+            .bind(dto.level_id)
             .bind(student_id)
-            .bind(school_id)
-            .execute(db)
-            .await;
+            .execute(uow.conn())
+            .await?;
 
-            match result {
-                Ok(res) if res.rows_affected() > 0 => assigned_count += 1,
-                _ => failed_ids.push(student_id),
+            if result.rows_affected() == 0 {
+                return Err(AppError::from_code(ErrorCode::StudentNotFound));
             }
-        }
 
-        Ok(BulkAssignResponse {
-            assigned_count,
-            failed_ids,
+            Ok(())
         })
+        .await?;
+
+        keys::invalidate::user(cache, Some(student_id.into()), tenant.single_school()).await;
+
+        Ok(())
     }
 
-    /// Assign students to level without school filtering (for system admins)
-    #[instrument]
-    pub async fn assign_students_to_level_no_school_filter(
+    #[instrument(skip(db))]
+    pub async fn get_students_in_level(
         db: &PgPool,
+        tenant: &TenantContext,
         level_id: LevelId,
-        dto: AssignStudentsToLevelDto,
-    ) -> Result<BulkAssignResponse, AppError> {
-        let level_exists =
-            sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM levels WHERE id = $1)")
-                .bind(level_id)
-                .fetch_one(db)
-                .await?;
+    ) -> Result<Vec<crate::modules::users::model::User>, AppError> {
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            Self::ensure_level_exists(uow.conn(), level_id).await?;
+
+            let students = sqlx::query_as::<_, crate::modules::users::model::User>(
+                r#"SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.level_id, u.branch_id, u.date_of_birth, u.grade_level, u.custom_fields, u.created_at, u.updated_at
+                   FROM users u
+                   INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $2
+                   WHERE u.level_id = $1
+                   ORDER BY u.last_name, u.first_name"#,
+            )
+            .bind(level_id)
+            .bind(system_roles::STUDENT)
+            .fetch_all(uow.conn())
+            .await?;
 
-        if !level_exists {
-            return Err(AppError::not_found(anyhow::anyhow!("Level not found")));
-        }
+            Ok(students)
+        })
+        .await
+    }
 
-        let mut assigned_count = 0;
-        let mut failed_ids = Vec::new();
+    #[instrument(skip(db, cache))]
+    pub async fn remove_student_from_level(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        tenant: &TenantContext,
+        student_id: UserId,
+    ) -> Result<(), AppError> {
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            Self::ensure_student(uow.conn(), student_id).await?;
 
-        let student_role_id = system_roles::STUDENT;
-        for student_id in dto.student_ids {
-            let is_student = sqlx::query_scalar::<_, bool>(
-                "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
+            let result = sqlx::query(
+                r#"UPDATE users SET level_id = NULL, updated_at = NOW() WHERE id = $1"#,
             )
             .bind(student_id)
-            .bind(student_role_id)
-            .fetch_one(db)
-            .await
-            .unwrap_or(false);
+            .execute(uow.conn())
+            .await?;
 
-            if !is_student {
-                failed_ids.push(student_id);
-                continue;
+            if result.rows_affected() == 0 {
+                return Err(AppError::from_code(ErrorCode::StudentNotFound));
             }
 
-            let result =
-                sqlx::query(r#"UPDATE users SET level_id = $1, updated_at = NOW() WHERE id = $2"#)
-                    .bind(level_id)
-                    .bind(student_id)
-                    .execute(db)
-                    .await;
+            Ok(())
+        })
+        .await?;
 
-            match result {
-                Ok(res) if res.rows_affected() > 0 => assigned_count += 1,
-                _ => failed_ids.push(student_id),
-            }
-        }
+        keys::invalidate::user(cache, Some(student_id.into()), tenant.single_school()).await;
 
-        Ok(BulkAssignResponse {
-            assigned_count,
-            failed_ids,
-        })
+        Ok(())
     }
 
-    #[instrument]
-    pub async fn move_student_to_level(
+    #[instrument(skip(db))]
+    pub async fn get_naming_templates(
         db: &PgPool,
-        student_id: UserId,
         school_id: SchoolId,
-        dto: MoveStudentToLevelDto,
-    ) -> Result<(), AppError> {
-        if let Some(new_level_id) = dto.level_id {
-            let level_exists = sqlx::query_scalar::<_, bool>(
-                "SELECT EXISTS(SELECT 1 FROM levels WHERE id = $1 AND school_id = $2)",
+    ) -> Result<NamingTemplates, AppError> {
+        sqlx::query_as::<_, NamingTemplates>(&format!(
+            "SELECT {NAMING_TEMPLATE_COLUMNS} FROM schools WHERE id = $1"
+        ))
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))
+    }
+
+    #[instrument(skip(db))]
+    pub async fn update_naming_templates(
+        db: &PgPool,
+        school_id: SchoolId,
+        dto: UpdateNamingTemplatesDto,
+    ) -> Result<NamingTemplates, AppError> {
+        sqlx::query_as::<_, NamingTemplates>(&format!(
+            "UPDATE schools
+             SET level_name_template = COALESCE($2, level_name_template),
+                 branch_name_template = COALESCE($3, branch_name_template),
+                 updated_at = NOW()
+             WHERE id = $1
+             RETURNING {NAMING_TEMPLATE_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.level_name_template)
+        .bind(&dto.branch_name_template)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))
+    }
+
+    /// Create consecutive levels, each with its branches, named by the
+    /// school's naming templates.
+    ///
+    /// Levels and branches whose names already exist are reused rather than
+    /// duplicated, so generating again after adding a level or branch only
+    /// creates what is missing.
+    #[instrument(skip(db, cache))]
+    pub async fn generate_levels(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        dto: GenerateLevelsDto,
+    ) -> Result<GenerateLevelsResponse, AppError> {
+        let templates = Self::get_naming_templates(db, school_id).await?;
+        let start = dto.start.unwrap_or(1);
+
+        let mut tx = db.begin().await?;
+        let mut levels = Vec::with_capacity(dto.count as usize);
+        let mut created_levels = 0;
+        let mut created_branches = 0;
+
+        for n in start..start + dto.count {
+            let name = render_name_template(&templates.level_name_template, n, None);
+
+            let inserted = sqlx::query_as::<_, Level>(
+                r#"INSERT INTO levels (name, school_id)
+                   VALUES ($1, $2)
+                   ON CONFLICT (name, school_id) DO NOTHING
+                   RETURNING id, name, description, school_id, created_at, updated_at"#,
             )
-            .bind(new_level_id)
+            .bind(&name)
             .bind(school_id)
-            .fetch_one(db)
+            .fetch_optional(&mut *tx)
             .await?;
 
-            if !level_exists {
-                return Err(AppError::not_found(anyhow::anyhow!("Level not found")));
+            let created = inserted.is_some();
+            let level = match inserted {
+                Some(level) => level,
+                None => {
+                    sqlx::query_as::<_, Level>(
+                        r#"SELECT id, name, description, school_id, created_at, updated_at
+                           FROM levels WHERE name = $1 AND school_id = $2"#,
+                    )
+                    .bind(&name)
+                    .bind(school_id)
+                    .fetch_one(&mut *tx)
+                    .await?
+                }
+            };
+            if created {
+                created_levels += 1;
             }
-        }
 
-        // Check if user is a student
-        let student_role_id = system_roles::STUDENT;
-        let is_student = sqlx::query_scalar::<_, bool>(
-            "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
-        )
-        .bind(student_id)
-        .bind(student_role_id)
-        .fetch_one(db)
-        .await?;
+            let branch_names: Vec<String> = (0..dto.branches_per_level)
+                .map(|i| render_name_template(&templates.branch_name_template, n, Some(i)))
+                .collect();
 
-        if !is_student {
-            return Err(AppError::not_found(anyhow::anyhow!(
-                "Student not found or not in this school"
-            )));
-        }
-
-        let result = sqlx::query(
-            r#"UPDATE users
-               SET level_id = $1, updated_at = NOW()
-               WHERE id = $2 AND school_id = $3"#,
-        )
-        .bind(dto.level_id)
-        .bind(student_id)
-        .bind(school_id)
-        .execute(db)
-        .await?;
+            created_branches += sqlx::query(
+                r#"INSERT INTO branches (name, level_id)
+                   SELECT name, $2 FROM UNNEST($1::text[]) AS name
+                   ON CONFLICT (name, level_id) DO NOTHING"#,
+            )
+            .bind(&branch_names)
+            .bind(level.id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected() as usize;
+
+            let branches = sqlx::query_as::<_, Branch>(
+                r#"SELECT id, name, description, level_id, created_at, updated_at
+                   FROM branches
+                   WHERE level_id = $1 AND name = ANY($2)
+                   ORDER BY name"#,
+            )
+            .bind(level.id)
+            .bind(&branch_names)
+            .fetch_all(&mut *tx)
+            .await?;
 
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!(
-                "Student not found or not in this school"
-            )));
+            levels.push(GeneratedLevel {
+                level,
+                created,
+                branches,
+            });
         }
 
-        Ok(())
-    }
+        tx.commit().await?;
 
-    /// Move student to level without school filtering (for system admins)
-    #[instrument]
-    pub async fn move_student_to_level_no_school_filter(
-        db: &PgPool,
-        student_id: UserId,
-        dto: MoveStudentToLevelDto,
-    ) -> Result<(), AppError> {
-        if let Some(new_level_id) = dto.level_id {
-            let level_exists =
-                sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM levels WHERE id = $1)")
-                    .bind(new_level_id)
-                    .fetch_one(db)
-                    .await?;
-
-            if !level_exists {
-                return Err(AppError::not_found(anyhow::anyhow!("Level not found")));
-            }
+        chalkbyte_cache::keys::invalidate::level(cache, None, Some(school_id.into())).await;
+        for generated in &levels {
+            chalkbyte_cache::keys::invalidate::branch(
+                cache,
+                None,
+                Some(generated.level.id.into()),
+                Some(school_id.into()),
+            )
+            .await;
         }
 
-        let student_role_id = system_roles::STUDENT;
-        let is_student = sqlx::query_scalar::<_, bool>(
-            "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
-        )
-        .bind(student_id)
-        .bind(student_role_id)
-        .fetch_one(db)
-        .await?;
-
-        if !is_student {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
-        }
+        Ok(GenerateLevelsResponse {
+            levels,
+            created_levels,
+            created_branches,
+        })
+    }
+}
 
-        let result =
-            sqlx::query(r#"UPDATE users SET level_id = $1, updated_at = NOW() WHERE id = $2"#)
-                .bind(dto.level_id)
-                .bind(student_id)
-                .execute(db)
-                .await?;
+/// A student's planned move at the end of a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PlannedPromotion {
+    student_id: UserId,
+    from_level_id: LevelId,
+    to_level_id: Option<LevelId>,
+    outcome: PromotionOutcome,
+}
 
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
-        }
+/// Work out where each student goes.
+///
+/// Students in a mapped level move to its target level, or graduate when the
+/// mapping has none; excluded students stay in their level to repeat it.
+fn plan_promotions(
+    mappings: &[LevelPromotionMapping],
+    students: &[(UserId, LevelId)],
+    excluded: &HashSet<UserId>,
+) -> Vec<PlannedPromotion> {
+    let targets: HashMap<LevelId, Option<LevelId>> = mappings
+        .iter()
+        .map(|m| (m.from_level_id, m.to_level_id))
+        .collect();
+
+    students
+        .iter()
+        .filter_map(|&(student_id, from_level_id)| {
+            let target = *targets.get(&from_level_id)?;
+            let (to_level_id, outcome) = if excluded.contains(&student_id) {
+                (Some(from_level_id), PromotionOutcome::Repeated)
+            } else if target.is_some() {
+                (target, PromotionOutcome::Promoted)
+            } else {
+                (None, PromotionOutcome::Graduated)
+            };
+            Some(PlannedPromotion {
+                student_id,
+                from_level_id,
+                to_level_id,
+                outcome,
+            })
+        })
+        .collect()
+}
 
-        Ok(())
+/// Count the planned outcomes per mapping, in mapping order.
+fn summarize_promotions(
+    academic_session_id: AcademicSessionId,
+    mappings: &[LevelPromotionMapping],
+    level_names: &HashMap<LevelId, String>,
+    plan: &[PlannedPromotion],
+    dry_run: bool,
+) -> PromotionReport {
+    let levels: Vec<LevelPromotionSummary> = mappings
+        .iter()
+        .map(|mapping| {
+            let count = |outcome: PromotionOutcome| {
+                plan.iter()
+                    .filter(|p| p.from_level_id == mapping.from_level_id && p.outcome == outcome)
+                    .count()
+            };
+            LevelPromotionSummary {
+                from_level_id: mapping.from_level_id,
+                from_level_name: level_names
+                    .get(&mapping.from_level_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                to_level_id: mapping.to_level_id,
+                to_level_name: mapping
+                    .to_level_id
+                    .and_then(|id| level_names.get(&id).cloned()),
+                promoted_count: count(PromotionOutcome::Promoted),
+                graduated_count: count(PromotionOutcome::Graduated),
+                repeating_count: count(PromotionOutcome::Repeated),
+            }
+        })
+        .collect();
+
+    PromotionReport {
+        dry_run,
+        academic_session_id,
+        promoted_count: levels.iter().map(|l| l.promoted_count).sum(),
+        graduated_count: levels.iter().map(|l| l.graduated_count).sum(),
+        repeating_count: levels.iter().map(|l| l.repeating_count).sum(),
+        levels,
     }
+}
 
-    #[instrument]
-    pub async fn get_students_in_level(
+pub struct PromotionService;
+
+impl PromotionService {
+    /// Promote a school's students to their next levels at the end of an
+    /// academic session.
+    ///
+    /// Everything happens in one transaction: students are moved, their
+    /// branch assignments cleared (branches belong to the old level), and a
+    /// history row recorded for every student in a mapped level, including
+    /// those repeating it. A session can only be promoted once. With
+    /// `dry_run` the same checks and plan are run but nothing is written.
+    #[instrument(skip(db, cache, dto))]
+    pub async fn promote_students(
         db: &PgPool,
-        level_id: LevelId,
+        cache: Option<&RedisCache>,
         school_id: SchoolId,
-    ) -> Result<Vec<crate::modules::users::model::User>, AppError> {
-        let level_exists = sqlx::query_scalar::<_, bool>(
-            "SELECT EXISTS(SELECT 1 FROM levels WHERE id = $1 AND school_id = $2)",
+        promoted_by: UserId,
+        dto: PromoteStudentsDto,
+        dry_run: bool,
+    ) -> Result<PromotionReport, AppError> {
+        let mut tx = db.begin().await?;
+
+        let session_exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM academic_sessions WHERE id = $1 AND school_id = $2)",
         )
-        .bind(level_id)
+        .bind(dto.academic_session_id)
         .bind(school_id)
-        .fetch_one(db)
+        .fetch_one(&mut *tx)
         .await?;
 
-        if !level_exists {
-            return Err(AppError::not_found(anyhow::anyhow!("Level not found")));
+        if !session_exists {
+            return Err(AppError::from_code(ErrorCode::AcademicSessionNotFound));
         }
 
-        let student_role_id = system_roles::STUDENT;
-        let students = sqlx::query_as::<_, crate::modules::users::model::User>(
-            r#"SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.level_id, u.branch_id, u.date_of_birth, u.grade_level, u.created_at, u.updated_at
-               FROM users u
-               INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $3
-               WHERE u.level_id = $1 AND u.school_id = $2
-               ORDER BY u.last_name, u.first_name"#,
+        let already_promoted = sqlx::query_scalar::<_, bool>(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM student_promotions
+                   WHERE school_id = $1 AND academic_session_id = $2
+               )"#,
         )
-        .bind(level_id)
         .bind(school_id)
-        .bind(student_role_id)
-        .fetch_all(db)
+        .bind(dto.academic_session_id)
+        .fetch_one(&mut *tx)
         .await?;
 
-        Ok(students)
-    }
+        if already_promoted {
+            return Err(Self::already_promoted_error());
+        }
 
-    /// Get students in level without school filtering (for system admins)
-    #[instrument]
-    pub async fn get_students_in_level_no_school_filter(
-        db: &PgPool,
-        level_id: LevelId,
-    ) -> Result<Vec<crate::modules::users::model::User>, AppError> {
-        let level_exists =
-            sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM levels WHERE id = $1)")
-                .bind(level_id)
-                .fetch_one(db)
-                .await?;
+        let level_ids: Vec<LevelId> = dto
+            .mappings
+            .iter()
+            .flat_map(|m| std::iter::once(m.from_level_id).chain(m.to_level_id))
+            .collect();
+        let level_names: HashMap<LevelId, String> = sqlx::query_as::<_, (LevelId, String)>(
+            "SELECT id, name FROM levels WHERE id = ANY($1) AND school_id = $2",
+        )
+        .bind(&level_ids)
+        .bind(school_id)
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .collect();
 
-        if !level_exists {
-            return Err(AppError::not_found(anyhow::anyhow!("Level not found")));
+        if level_ids.iter().any(|id| !level_names.contains_key(id)) {
+            return Err(AppError::from_code(ErrorCode::LevelNotFound));
         }
 
-        let student_role_id = system_roles::STUDENT;
-        let students = sqlx::query_as::<_, crate::modules::users::model::User>(
-            r#"SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.level_id, u.branch_id, u.date_of_birth, u.grade_level, u.created_at, u.updated_at
+        let from_level_ids: Vec<LevelId> = dto.mappings.iter().map(|m| m.from_level_id).collect();
+        let students = sqlx::query_as::<_, (UserId, LevelId)>(
+            r#"SELECT u.id, u.level_id
                FROM users u
-               INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $2
-               WHERE u.level_id = $1
-               ORDER BY u.last_name, u.first_name"#,
+               WHERE u.school_id = $1 AND u.level_id = ANY($2)
+                 AND EXISTS(SELECT 1 FROM user_roles ur WHERE ur.user_id = u.id AND ur.role_id = $3)
+               ORDER BY u.id
+               FOR UPDATE"#,
         )
-        .bind(level_id)
-        .bind(student_role_id)
-        .fetch_all(db)
+        .bind(school_id)
+        .bind(&from_level_ids)
+        .bind(system_roles::STUDENT)
+        .fetch_all(&mut *tx)
         .await?;
 
-        Ok(students)
-    }
+        let excluded: HashSet<UserId> = dto.excluded_student_ids.iter().copied().collect();
+        let in_mapped_levels: HashSet<UserId> = students.iter().map(|(id, _)| *id).collect();
+        let unknown: Vec<String> = excluded
+            .iter()
+            .filter(|id| !in_mapped_levels.contains(id))
+            .map(|id| id.to_string())
+            .collect();
+        if !unknown.is_empty() {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Excluded students are not in any of the promoted levels: {}",
+                unknown.join(", ")
+            )));
+        }
 
-    #[instrument]
-    pub async fn remove_student_from_level(
-        db: &PgPool,
-        student_id: UserId,
-        school_id: SchoolId,
-    ) -> Result<(), AppError> {
-        // Check if user is a student
-        let student_role_id = system_roles::STUDENT;
-        let is_student = sqlx::query_scalar::<_, bool>(
-            "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
-        )
-        .bind(student_id)
-        .bind(student_role_id)
-        .fetch_one(db)
-        .await?;
+        let plan = plan_promotions(&dto.mappings, &students, &excluded);
+        let report = summarize_promotions(
+            dto.academic_session_id,
+            &dto.mappings,
+            &level_names,
+            &plan,
+            dry_run,
+        );
 
-        if !is_student {
-            return Err(AppError::not_found(anyhow::anyhow!(
-                "Student not found or not in this school"
-            )));
+        if dry_run {
+            return Ok(report);
         }
 
-        let result = sqlx::query(
-            r#"UPDATE users
-               SET level_id = NULL, updated_at = NOW()
-               WHERE id = $1 AND school_id = $2"#,
+        // Targets come from the plan, so a level that is both a source and a
+        // target only moves the students who were in it before the update
+        let moving: Vec<&PlannedPromotion> = plan
+            .iter()
+            .filter(|p| p.outcome != PromotionOutcome::Repeated)
+            .collect();
+        sqlx::query(
+            r#"UPDATE users AS u
+               SET level_id = p.to_level_id, branch_id = NULL, updated_at = NOW()
+               FROM UNNEST($1::uuid[], $2::uuid[]) AS p(id, to_level_id)
+               WHERE u.id = p.id"#,
         )
-        .bind(student_id)
-        .bind(school_id)
-        .execute(db)
+        .bind(moving.iter().map(|p| p.student_id).collect::<Vec<_>>())
+        .bind(moving.iter().map(|p| p.to_level_id).collect::<Vec<_>>())
+        .execute(&mut *tx)
         .await?;
 
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!(
-                "Student not found or not in this school"
-            )));
+        sqlx::query(
+            r#"INSERT INTO student_promotions (school_id, academic_session_id, student_id,
+                                               from_level_id, to_level_id, outcome, promoted_by)
+               SELECT $1, $2, p.student_id, p.from_level_id, p.to_level_id, p.outcome, $7
+               FROM UNNEST($3::uuid[], $4::uuid[], $5::uuid[], $6::text[])
+                    AS p(student_id, from_level_id, to_level_id, outcome)"#,
+        )
+        .bind(school_id)
+        .bind(dto.academic_session_id)
+        .bind(plan.iter().map(|p| p.student_id).collect::<Vec<_>>())
+        .bind(plan.iter().map(|p| p.from_level_id).collect::<Vec<_>>())
+        .bind(plan.iter().map(|p| p.to_level_id).collect::<Vec<_>>())
+        .bind(plan.iter().map(|p| p.outcome).collect::<Vec<_>>())
+        .bind(promoted_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(db_err) = &e
+                && db_err.is_unique_violation()
+            {
+                return Self::already_promoted_error();
+            }
+            AppError::from(e)
+        })?;
+
+        tx.commit().await?;
+
+        keys::invalidate::user(cache, None, Some(school_id.into())).await;
+        keys::invalidate::level(cache, None, Some(school_id.into())).await;
+        for level_id in &from_level_ids {
+            keys::invalidate::branch(
+                cache,
+                None,
+                Some((*level_id).into()),
+                Some(school_id.into()),
+            )
+            .await;
         }
 
-        Ok(())
+        Ok(report)
     }
 
-    /// Remove student from level without school filtering (for system admins)
+    /// Get the recorded promotions of a school for an academic session,
+    /// ordered by source level and student name.
     #[instrument]
-    pub async fn remove_student_from_level_no_school_filter(
+    pub async fn get_promotion_history(
         db: &PgPool,
-        student_id: UserId,
-    ) -> Result<(), AppError> {
-        let student_role_id = system_roles::STUDENT;
-        let is_student = sqlx::query_scalar::<_, bool>(
-            "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
+        school_id: SchoolId,
+        academic_session_id: AcademicSessionId,
+    ) -> Result<Vec<StudentPromotion>, AppError> {
+        let promotions = sqlx::query_as::<_, StudentPromotion>(
+            r#"SELECT sp.id, sp.academic_session_id, sp.student_id, u.first_name, u.last_name,
+                      sp.from_level_id, fl.name AS from_level_name,
+                      sp.to_level_id, tl.name AS to_level_name,
+                      sp.outcome, sp.promoted_by, sp.created_at
+               FROM student_promotions sp
+               JOIN users u ON u.id = sp.student_id
+               LEFT JOIN levels fl ON fl.id = sp.from_level_id
+               LEFT JOIN levels tl ON tl.id = sp.to_level_id
+               WHERE sp.school_id = $1 AND sp.academic_session_id = $2
+               ORDER BY fl.name NULLS LAST, u.last_name, u.first_name"#,
         )
-        .bind(student_id)
-        .bind(student_role_id)
-        .fetch_one(db)
+        .bind(school_id)
+        .bind(academic_session_id)
+        .fetch_all(db)
         .await?;
 
-        if !is_student {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
-        }
-
-        let result =
-            sqlx::query(r#"UPDATE users SET level_id = NULL, updated_at = NOW() WHERE id = $1"#)
-                .bind(student_id)
-                .execute(db)
-                .await?;
-
-        if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
-        }
+        Ok(promotions)
+    }
 
-        Ok(())
+    fn already_promoted_error() -> AppError {
+        AppError::bad_request(anyhow::anyhow!(
+            "Students have already been promoted for this academic session"
+        ))
     }
 }
 
@@ -730,7 +884,7 @@ impl LevelService {
 mod tests {
     use super::*;
     use axum::http::StatusCode;
-    use chalkbyte_core::PaginationParams;
+    use chalkbyte_core::{PaginationParams, Versioned};
     use uuid::Uuid;
 
     async fn create_test_school(pool: &PgPool, name: &str) -> SchoolId {
@@ -968,7 +1122,13 @@ mod tests {
             .await
             .unwrap();
 
-        let result = LevelService::get_level_by_id(&pool, created.id, school_id).await;
+        let result = LevelService::get_level_by_id(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            created.id,
+        )
+        .await;
 
         assert!(result.is_ok());
         let level = result.unwrap();
@@ -982,7 +1142,13 @@ mod tests {
         let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
         let random_id = LevelId::new();
 
-        let result = LevelService::get_level_by_id(&pool, random_id, school_id).await;
+        let result = LevelService::get_level_by_id(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            random_id,
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -1004,7 +1170,13 @@ mod tests {
             .await
             .unwrap();
 
-        let result = LevelService::get_level_by_id(&pool, created.id, school2_id).await;
+        let result = LevelService::get_level_by_id(
+            &pool,
+            None,
+            &TenantContext::school(school2_id),
+            created.id,
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -1030,11 +1202,18 @@ mod tests {
             description: Some("Updated description".to_string()),
         };
 
-        let result =
-            LevelService::update_level(&pool, None, created.id, school_id, update_dto).await;
+        let result = LevelService::update_level(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            created.id,
+            &IfMatch::default(),
+            update_dto,
+        )
+        .await;
 
         assert!(result.is_ok());
-        let updated = result.unwrap();
+        let updated = result.unwrap().updated().unwrap();
         assert_eq!(updated.name, "Grade 11");
         assert_eq!(updated.description, Some("Updated description".to_string()));
     }
@@ -1058,11 +1237,18 @@ mod tests {
             description: None,
         };
 
-        let result =
-            LevelService::update_level(&pool, None, created.id, school_id, update_dto).await;
+        let result = LevelService::update_level(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            created.id,
+            &IfMatch::default(),
+            update_dto,
+        )
+        .await;
 
         assert!(result.is_ok());
-        let updated = result.unwrap();
+        let updated = result.unwrap().updated().unwrap();
         assert_eq!(updated.name, "Grade 11");
         assert_eq!(
             updated.description,
@@ -1075,17 +1261,61 @@ mod tests {
         let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
         let random_id = LevelId::new();
 
-        let update_dto = UpdateLevelDto {
-            name: Some("Grade 11".to_string()),
+        let update_dto = UpdateLevelDto {
+            name: Some("Grade 11".to_string()),
+            description: None,
+        };
+
+        let result = LevelService::update_level(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            random_id,
+            &IfMatch::default(),
+            update_dto,
+        )
+        .await;
+
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_update_level_with_stale_etag(pool: PgPool) {
+        let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
+        let tenant = TenantContext::school(school_id);
+
+        let dto = CreateLevelDto {
+            name: "Grade 10".to_string(),
+            description: None,
+            school_id: None,
+        };
+        let created = LevelService::create_level(&pool, None, school_id, dto)
+            .await
+            .unwrap();
+        let etag = IfMatch::parse(&created.etag());
+
+        let rename = |name: &str| UpdateLevelDto {
+            name: Some(name.to_string()),
             description: None,
         };
 
-        let result =
-            LevelService::update_level(&pool, None, random_id, school_id, update_dto).await;
+        let first =
+            LevelService::update_level(&pool, None, &tenant, created.id, &etag, rename("A"))
+                .await
+                .unwrap();
+        assert!(matches!(first, Conditional::Updated(ref level) if level.name == "A"));
 
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert_eq!(err.status, StatusCode::NOT_FOUND);
+        // The first update changed the version the ETag was taken from
+        let second =
+            LevelService::update_level(&pool, None, &tenant, created.id, &etag, rename("B"))
+                .await
+                .unwrap();
+        let Conditional::PreconditionFailed(current) = second else {
+            panic!("expected a failed precondition");
+        };
+        assert_eq!(current.name, "A");
     }
 
     #[sqlx::test(migrations = "./migrations")]
@@ -1102,11 +1332,19 @@ mod tests {
             .await
             .unwrap();
 
-        let result = LevelService::delete_level(&pool, None, created.id, school_id).await;
+        let result =
+            LevelService::delete_level(&pool, None, &TenantContext::school(school_id), created.id)
+                .await;
 
         assert!(result.is_ok());
 
-        let get_result = LevelService::get_level_by_id(&pool, created.id, school_id).await;
+        let get_result = LevelService::get_level_by_id(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            created.id,
+        )
+        .await;
         assert!(get_result.is_err());
     }
 
@@ -1115,7 +1353,9 @@ mod tests {
         let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
         let random_id = LevelId::new();
 
-        let result = LevelService::delete_level(&pool, None, random_id, school_id).await;
+        let result =
+            LevelService::delete_level(&pool, None, &TenantContext::school(school_id), random_id)
+                .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -1144,8 +1384,14 @@ mod tests {
             student_ids: vec![student1_id, student2_id],
         };
 
-        let result =
-            LevelService::assign_students_to_level(&pool, level.id, school_id, assign_dto).await;
+        let result = LevelService::assign_students_to_level(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level.id,
+            assign_dto,
+        )
+        .await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1153,6 +1399,65 @@ mod tests {
         assert_eq!(response.failed_ids.len(), 0);
     }
 
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_tenant_sees_only_its_schools(pool: PgPool) {
+        let school1_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
+        let school2_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
+        let outsider_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
+
+        let dto = CreateLevelDto {
+            name: "Grade 10".to_string(),
+            description: None,
+            school_id: None,
+        };
+        let level = LevelService::create_level(&pool, None, school2_id, dto)
+            .await
+            .unwrap();
+        let outsider = create_test_student(
+            &pool,
+            outsider_id,
+            &format!("o-{}@test.com", Uuid::new_v4()),
+        )
+        .await;
+
+        let group = TenantContext::schools([school1_id.into(), school2_id.into()]);
+        assert!(
+            LevelService::get_level_by_id(&pool, None, &group, level.id)
+                .await
+                .is_ok()
+        );
+
+        // Another school's student is neither assigned nor moved
+        let response = LevelService::assign_students_to_level(
+            &pool,
+            None,
+            &group,
+            level.id,
+            AssignStudentsToLevelDto {
+                student_ids: vec![outsider],
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.assigned_count, 0);
+        assert_eq!(response.failed_ids, vec![outsider]);
+
+        let err = LevelService::remove_student_from_level(&pool, None, &group, outsider)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        // Only a caller that can see the level deletes it
+        let err =
+            LevelService::delete_level(&pool, None, &TenantContext::school(outsider_id), level.id)
+                .await
+                .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+        LevelService::delete_level(&pool, None, &TenantContext::AllSchools, level.id)
+            .await
+            .unwrap();
+    }
+
     #[sqlx::test(migrations = "./migrations")]
     async fn test_assign_students_with_invalid_ids(pool: PgPool) {
         let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
@@ -1174,8 +1479,14 @@ mod tests {
             student_ids: vec![student_id, invalid_id],
         };
 
-        let result =
-            LevelService::assign_students_to_level(&pool, level.id, school_id, assign_dto).await;
+        let result = LevelService::assign_students_to_level(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            level.id,
+            assign_dto,
+        )
+        .await;
 
         assert!(result.is_ok());
         let response = result.unwrap();
@@ -1196,9 +1507,14 @@ mod tests {
             student_ids: vec![student_id],
         };
 
-        let result =
-            LevelService::assign_students_to_level(&pool, random_level_id, school_id, assign_dto)
-                .await;
+        let result = LevelService::assign_students_to_level(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            random_level_id,
+            assign_dto,
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -1240,8 +1556,9 @@ mod tests {
 
         LevelService::assign_students_to_level(
             &pool,
+            None,
+            &TenantContext::school(school_id),
             level1.id,
-            school_id,
             AssignStudentsToLevelDto {
                 student_ids: vec![student_id],
             },
@@ -1253,14 +1570,24 @@ mod tests {
             level_id: Some(level2.id),
         };
 
-        let result =
-            LevelService::move_student_to_level(&pool, student_id, school_id, move_dto).await;
+        let result = LevelService::move_student_to_level(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            student_id,
+            move_dto,
+        )
+        .await;
 
         assert!(result.is_ok());
 
-        let students = LevelService::get_students_in_level(&pool, level2.id, school_id)
-            .await
-            .unwrap();
+        let students = LevelService::get_students_in_level(
+            &pool,
+            &TenantContext::school(school_id),
+            level2.id,
+        )
+        .await
+        .unwrap();
         assert_eq!(students.len(), 1);
         assert_eq!(students[0].id, student_id);
     }
@@ -1287,8 +1614,9 @@ mod tests {
 
         LevelService::assign_students_to_level(
             &pool,
+            None,
+            &TenantContext::school(school_id),
             level.id,
-            school_id,
             AssignStudentsToLevelDto {
                 student_ids: vec![student_id],
             },
@@ -1298,8 +1626,14 @@ mod tests {
 
         let move_dto = MoveStudentToLevelDto { level_id: None };
 
-        let result =
-            LevelService::move_student_to_level(&pool, student_id, school_id, move_dto).await;
+        let result = LevelService::move_student_to_level(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            student_id,
+            move_dto,
+        )
+        .await;
 
         assert!(result.is_ok());
     }
@@ -1328,8 +1662,9 @@ mod tests {
 
         LevelService::assign_students_to_level(
             &pool,
+            None,
+            &TenantContext::school(school_id),
             level.id,
-            school_id,
             AssignStudentsToLevelDto {
                 student_ids: vec![student1_id, student2_id],
             },
@@ -1337,7 +1672,9 @@ mod tests {
         .await
         .unwrap();
 
-        let result = LevelService::get_students_in_level(&pool, level.id, school_id).await;
+        let result =
+            LevelService::get_students_in_level(&pool, &TenantContext::school(school_id), level.id)
+                .await;
 
         assert!(result.is_ok());
         let students = result.unwrap();
@@ -1349,7 +1686,12 @@ mod tests {
         let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
         let random_level_id = LevelId::new();
 
-        let result = LevelService::get_students_in_level(&pool, random_level_id, school_id).await;
+        let result = LevelService::get_students_in_level(
+            &pool,
+            &TenantContext::school(school_id),
+            random_level_id,
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -1378,8 +1720,9 @@ mod tests {
 
         LevelService::assign_students_to_level(
             &pool,
+            None,
+            &TenantContext::school(school_id),
             level.id,
-            school_id,
             AssignStudentsToLevelDto {
                 student_ids: vec![student_id],
             },
@@ -1387,13 +1730,20 @@ mod tests {
         .await
         .unwrap();
 
-        let result = LevelService::remove_student_from_level(&pool, student_id, school_id).await;
+        let result = LevelService::remove_student_from_level(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            student_id,
+        )
+        .await;
 
         assert!(result.is_ok());
 
-        let students = LevelService::get_students_in_level(&pool, level.id, school_id)
-            .await
-            .unwrap();
+        let students =
+            LevelService::get_students_in_level(&pool, &TenantContext::school(school_id), level.id)
+                .await
+                .unwrap();
         assert_eq!(students.len(), 0);
     }
 
@@ -1402,8 +1752,13 @@ mod tests {
         let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
         let random_student_id = UserId::new();
 
-        let result =
-            LevelService::remove_student_from_level(&pool, random_student_id, school_id).await;
+        let result = LevelService::remove_student_from_level(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            random_student_id,
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -1432,15 +1787,17 @@ mod tests {
         let student2_id =
             create_test_student(&pool, school_id, &format!("s2-{}@test.com", Uuid::new_v4())).await;
 
-        let level_with_stats = LevelService::get_level_by_id(&pool, level.id, school_id)
-            .await
-            .unwrap();
+        let level_with_stats =
+            LevelService::get_level_by_id(&pool, None, &TenantContext::school(school_id), level.id)
+                .await
+                .unwrap();
         assert_eq!(level_with_stats.student_count, 0);
 
         LevelService::assign_students_to_level(
             &pool,
+            None,
+            &TenantContext::school(school_id),
             level.id,
-            school_id,
             AssignStudentsToLevelDto {
                 student_ids: vec![student1_id, student2_id],
             },
@@ -1448,18 +1805,383 @@ mod tests {
         .await
         .unwrap();
 
-        let level_with_stats = LevelService::get_level_by_id(&pool, level.id, school_id)
+        let level_with_stats =
+            LevelService::get_level_by_id(&pool, None, &TenantContext::school(school_id), level.id)
+                .await
+                .unwrap();
+        assert_eq!(level_with_stats.student_count, 2);
+
+        LevelService::remove_student_from_level(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            student1_id,
+        )
+        .await
+        .unwrap();
+
+        let level_with_stats =
+            LevelService::get_level_by_id(&pool, None, &TenantContext::school(school_id), level.id)
+                .await
+                .unwrap();
+        assert_eq!(level_with_stats.student_count, 1);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_generate_levels_from_templates(pool: PgPool) {
+        let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
+
+        let templates = LevelService::get_naming_templates(&pool, school_id)
+            .await
+            .unwrap();
+        assert_eq!(templates.level_name_template, "Grade {n}");
+        assert_eq!(templates.branch_name_template, "{letter}");
+
+        LevelService::update_naming_templates(
+            &pool,
+            school_id,
+            UpdateNamingTemplatesDto {
+                level_name_template: Some("JSS {n}".to_string()),
+                branch_name_template: Some("JSS {n}{letter}".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        let dto = GenerateLevelsDto {
+            start: None,
+            count: 3,
+            branches_per_level: 2,
+        };
+        let generated = LevelService::generate_levels(&pool, None, school_id, dto.clone())
             .await
             .unwrap();
-        assert_eq!(level_with_stats.student_count, 2);
 
-        LevelService::remove_student_from_level(&pool, student1_id, school_id)
+        assert_eq!(generated.created_levels, 3);
+        assert_eq!(generated.created_branches, 6);
+        let names: Vec<_> = generated
+            .levels
+            .iter()
+            .map(|l| l.level.name.as_str())
+            .collect();
+        assert_eq!(names, ["JSS 1", "JSS 2", "JSS 3"]);
+        let branches: Vec<_> = generated.levels[1]
+            .branches
+            .iter()
+            .map(|b| b.name.as_str())
+            .collect();
+        assert_eq!(branches, ["JSS 2A", "JSS 2B"]);
+
+        // Generating again only adds what is missing
+        let generated = LevelService::generate_levels(
+            &pool,
+            None,
+            school_id,
+            GenerateLevelsDto {
+                branches_per_level: 3,
+                ..dto
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(generated.created_levels, 0);
+        assert_eq!(generated.created_branches, 3);
+        assert!(
+            generated
+                .levels
+                .iter()
+                .all(|l| !l.created && l.branches.len() == 3)
+        );
+    }
+
+    async fn create_test_session(pool: &PgPool, school_id: SchoolId) -> AcademicSessionId {
+        sqlx::query_scalar::<_, AcademicSessionId>(
+            r#"INSERT INTO academic_sessions (name, school_id, start_date, end_date)
+               VALUES ('2025/2026', $1, '2025-09-01', '2026-07-31') RETURNING id"#,
+        )
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn create_named_level(pool: &PgPool, school_id: SchoolId, name: &str) -> LevelId {
+        LevelService::create_level(
+            pool,
+            None,
+            school_id,
+            CreateLevelDto {
+                name: name.to_string(),
+                description: None,
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap()
+        .id
+    }
+
+    async fn place_student(pool: &PgPool, student_id: UserId, level_id: LevelId) {
+        sqlx::query("UPDATE users SET level_id = $1 WHERE id = $2")
+            .bind(level_id)
+            .bind(student_id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    async fn student_level(pool: &PgPool, student_id: UserId) -> Option<LevelId> {
+        sqlx::query_scalar("SELECT level_id FROM users WHERE id = $1")
+            .bind(student_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_plan_promotions_marks_excluded_students_as_repeating() {
+        let (grade_1, grade_2, grade_3) = (LevelId::new(), LevelId::new(), LevelId::new());
+        let (ada, bola, chidi, dayo) = (UserId::new(), UserId::new(), UserId::new(), UserId::new());
+        let mappings = [
+            LevelPromotionMapping {
+                from_level_id: grade_1,
+                to_level_id: Some(grade_2),
+            },
+            LevelPromotionMapping {
+                from_level_id: grade_2,
+                to_level_id: None,
+            },
+        ];
+        let students = [
+            (ada, grade_1),
+            (bola, grade_1),
+            (chidi, grade_2),
+            (dayo, grade_3),
+        ];
+
+        let plan = plan_promotions(&mappings, &students, &HashSet::from([bola]));
+
+        let outcomes: Vec<_> = plan
+            .iter()
+            .map(|p| (p.student_id, p.to_level_id, p.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                (ada, Some(grade_2), PromotionOutcome::Promoted),
+                (bola, Some(grade_1), PromotionOutcome::Repeated),
+                (chidi, None, PromotionOutcome::Graduated),
+            ]
+        );
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_promote_students_moves_chained_levels_once(pool: PgPool) {
+        let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
+        let session_id = create_test_session(&pool, school_id).await;
+        let grade_1 = create_named_level(&pool, school_id, "Grade 1").await;
+        let grade_2 = create_named_level(&pool, school_id, "Grade 2").await;
+        let ada = create_test_student(&pool, school_id, "ada@example.com").await;
+        let bola = create_test_student(&pool, school_id, "bola@example.com").await;
+        let chidi = create_test_student(&pool, school_id, "chidi@example.com").await;
+        place_student(&pool, ada, grade_1).await;
+        place_student(&pool, bola, grade_1).await;
+        place_student(&pool, chidi, grade_2).await;
+        let admin = create_test_student(&pool, school_id, "admin@example.com").await;
+
+        let dto = PromoteStudentsDto {
+            academic_session_id: session_id,
+            mappings: vec![
+                LevelPromotionMapping {
+                    from_level_id: grade_1,
+                    to_level_id: Some(grade_2),
+                },
+                LevelPromotionMapping {
+                    from_level_id: grade_2,
+                    to_level_id: None,
+                },
+            ],
+            excluded_student_ids: vec![bola],
+            school_id: None,
+        };
+
+        let preview =
+            PromotionService::promote_students(&pool, None, school_id, admin, dto.clone(), true)
+                .await
+                .unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(
+            (
+                preview.promoted_count,
+                preview.graduated_count,
+                preview.repeating_count
+            ),
+            (1, 1, 1)
+        );
+        assert_eq!(preview.levels[0].from_level_name, "Grade 1");
+        assert_eq!(preview.levels[0].to_level_name.as_deref(), Some("Grade 2"));
+        // The preview changes nothing
+        assert_eq!(student_level(&pool, ada).await, Some(grade_1));
+
+        let report =
+            PromotionService::promote_students(&pool, None, school_id, admin, dto.clone(), false)
+                .await
+                .unwrap();
+        assert!(!report.dry_run);
+        assert_eq!(report.promoted_count, 1);
+
+        // Grade 1 moves up without being graduated along with Grade 2
+        assert_eq!(student_level(&pool, ada).await, Some(grade_2));
+        assert_eq!(student_level(&pool, bola).await, Some(grade_1));
+        assert_eq!(student_level(&pool, chidi).await, None);
+
+        let history = PromotionService::get_promotion_history(&pool, school_id, session_id)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 3);
+        let chidi_row = history.iter().find(|h| h.student_id == chidi).unwrap();
+        assert_eq!(chidi_row.outcome, PromotionOutcome::Graduated);
+        assert_eq!(chidi_row.from_level_name.as_deref(), Some("Grade 2"));
+        assert_eq!(chidi_row.promoted_by, Some(admin));
+
+        // A session is only promoted once
+        let err = PromotionService::promote_students(&pool, None, school_id, admin, dto, false)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_promote_students_rejects_foreign_levels_and_stray_exclusions(pool: PgPool) {
+        let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
+        let other_school = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
+        let session_id = create_test_session(&pool, school_id).await;
+        let grade_1 = create_named_level(&pool, school_id, "Grade 1").await;
+        let foreign = create_named_level(&pool, other_school, "Grade 2").await;
+        let stray = create_test_student(&pool, school_id, "stray@example.com").await;
+
+        let dto = |to_level_id, excluded_student_ids| PromoteStudentsDto {
+            academic_session_id: session_id,
+            mappings: vec![LevelPromotionMapping {
+                from_level_id: grade_1,
+                to_level_id,
+            }],
+            excluded_student_ids,
+            school_id: None,
+        };
+
+        let err = PromotionService::promote_students(
+            &pool,
+            None,
+            school_id,
+            stray,
+            dto(Some(foreign), vec![]),
+            true,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let err = PromotionService::promote_students(
+            &pool,
+            None,
+            school_id,
+            stray,
+            dto(None, vec![stray]),
+            true,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let err = PromotionService::promote_students(
+            &pool,
+            None,
+            other_school,
+            stray,
+            dto(None, vec![]),
+            true,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_level_setup_composes_in_a_unit_of_work(pool: PgPool) {
+        use crate::config::database::UnitOfWork;
+        use crate::modules::branches::model::CreateBranchDto;
+        use crate::modules::branches::service::BranchService;
+        use crate::modules::students::model::CreateStudentDto;
+        use crate::modules::students::service::StudentService;
+        use crate::utils::password::PasswordPolicy;
+        use chalkbyte_models::Email;
+
+        let school_id = create_test_school(&pool, "Unit Of Work School").await;
+        create_test_student(&pool, school_id, "taken@example.com").await;
+
+        let set_up = async |email: &str| {
+            UnitOfWork::run(&pool, async |uow| {
+                let level = LevelService::create_level(
+                    uow.conn(),
+                    None,
+                    school_id,
+                    CreateLevelDto {
+                        name: "Grade 9".to_string(),
+                        description: None,
+                        school_id: None,
+                    },
+                )
+                .await?;
+                BranchService::create_branch(
+                    uow.conn(),
+                    None,
+                    &TenantContext::school(school_id),
+                    level.id,
+                    CreateBranchDto {
+                        name: "A".to_string(),
+                        description: None,
+                    },
+                )
+                .await?;
+                StudentService::create_student(
+                    uow.conn(),
+                    CreateStudentDto {
+                        first_name: "Ada".to_string(),
+                        last_name: "Obi".to_string(),
+                        email: Email::new(email).unwrap(),
+                        password: "testpass123".to_string(),
+                        date_of_birth: None,
+                        grade_level: None,
+                        custom_fields: None,
+                        school_id: None,
+                    },
+                    school_id.into_inner(),
+                    None,
+                    &PasswordPolicy::default(),
+                )
+                .await?;
+                Ok::<_, AppError>(level)
+            })
+            .await
+        };
+
+        // The student fails, so the level and branch are rolled back too
+        let err = set_up("taken@example.com").await.unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        let levels: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM levels WHERE school_id = $1")
+            .bind(school_id)
+            .fetch_one(&pool)
             .await
             .unwrap();
+        assert_eq!(levels, 0);
 
-        let level_with_stats = LevelService::get_level_by_id(&pool, level.id, school_id)
+        let level = set_up("ada@example.com").await.unwrap();
+        let branches: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM branches WHERE level_id = $1")
+            .bind(level.id)
+            .fetch_one(&pool)
             .await
             .unwrap();
-        assert_eq!(level_with_stats.student_count, 1);
+        assert_eq!(branches, 1);
     }
 }
@@ -0,0 +1,507 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::{LibraryBookId, LibraryCopyId, LibraryLoanId, UserId};
+
+use crate::middleware::auth::{
+    RequireLibraryCreate, RequireLibraryDelete, RequireLibraryLend, RequireLibraryRead,
+    RequireLibraryUpdate,
+};
+use crate::modules::library::model::{
+    BookFilterParams, BorrowingHistory, CheckoutDto, CreateBookDto, CreateCopyDto, LibraryBook,
+    LibraryCopy, LibraryFineCharge, LibraryFineFilterParams, LibraryLoan, LibraryPolicy,
+    LibraryPolicyQueryParams, LoanFilterParams, PaginatedBooksResponse, PaginatedLoansResponse,
+    ReturnLoanResponse, UpdateBookDto, UpdateCopyDto, UpdateLibraryPolicyDto,
+};
+use crate::modules::library::service::LibraryService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Add a title to the library catalog
+#[utoipa::path(
+    post,
+    path = "/api/library/books",
+    summary = "Create book",
+    request_body = CreateBookDto,
+    responses(
+        Created<LibraryBook>,
+        (status = 400, description = "Invalid input, duplicate ISBN, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:create permission")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_book(
+    State(state): State<AppState>,
+    RequireLibraryCreate(auth_user): RequireLibraryCreate,
+    ValidatedJson(dto): ValidatedJson<CreateBookDto>,
+) -> Result<Created<LibraryBook>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let book = LibraryService::create_book(&state.db, school_id, dto).await?;
+
+    Ok(Created(book))
+}
+
+/// Search the library catalog
+#[utoipa::path(
+    get,
+    path = "/api/library/books",
+    summary = "List books",
+    params(BookFilterParams),
+    responses(
+        (status = 200, description = "Books with copy availability", body = PaginatedBooksResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:read permission")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_books(
+    State(state): State<AppState>,
+    RequireLibraryRead(auth_user): RequireLibraryRead,
+    Query(filters): Query<BookFilterParams>,
+) -> Result<Json<PaginatedBooksResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let books = LibraryService::get_books(&state.db, school_id, filters).await?;
+
+    Ok(Json(books))
+}
+
+/// Get a book by ID
+#[utoipa::path(
+    get,
+    path = "/api/library/books/{id}",
+    summary = "Get book",
+    params(
+        ("id" = Uuid, Path, description = "Book ID")
+    ),
+    responses(
+        (status = 200, description = "Book", body = LibraryBook),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:read permission"),
+        (status = 404, description = "Book not found")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_book_by_id(
+    State(state): State<AppState>,
+    RequireLibraryRead(auth_user): RequireLibraryRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<LibraryBook>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let book =
+        LibraryService::get_book_by_id(&state.db, LibraryBookId::from(id), school_id).await?;
+
+    Ok(Json(book))
+}
+
+/// Update a book
+#[utoipa::path(
+    put,
+    path = "/api/library/books/{id}",
+    summary = "Update book",
+    params(
+        ("id" = Uuid, Path, description = "Book ID")
+    ),
+    request_body = UpdateBookDto,
+    responses(
+        (status = 200, description = "Book updated", body = LibraryBook),
+        (status = 400, description = "Invalid input or duplicate ISBN"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:update permission"),
+        (status = 404, description = "Book not found")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_book(
+    State(state): State<AppState>,
+    RequireLibraryUpdate(auth_user): RequireLibraryUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateBookDto>,
+) -> Result<Json<LibraryBook>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let book =
+        LibraryService::update_book(&state.db, LibraryBookId::from(id), school_id, dto).await?;
+
+    Ok(Json(book))
+}
+
+/// Delete a book and its copies
+#[utoipa::path(
+    delete,
+    path = "/api/library/books/{id}",
+    summary = "Delete book",
+    params(
+        ("id" = Uuid, Path, description = "Book ID")
+    ),
+    responses(
+        NoContent,
+        (status = 400, description = "Copies of the book are on loan"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:delete permission"),
+        (status = 404, description = "Book not found")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_book(
+    State(state): State<AppState>,
+    RequireLibraryDelete(auth_user): RequireLibraryDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    LibraryService::delete_book(&state.db, LibraryBookId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Add a copy of a book
+#[utoipa::path(
+    post,
+    path = "/api/library/books/{id}/copies",
+    summary = "Add copy",
+    params(
+        ("id" = Uuid, Path, description = "Book ID")
+    ),
+    request_body = CreateCopyDto,
+    responses(
+        Created<LibraryCopy>,
+        (status = 400, description = "Invalid input or duplicate barcode"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:create permission"),
+        (status = 404, description = "Book not found")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn add_copy(
+    State(state): State<AppState>,
+    RequireLibraryCreate(auth_user): RequireLibraryCreate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<CreateCopyDto>,
+) -> Result<Created<LibraryCopy>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let copy = LibraryService::add_copy(&state.db, LibraryBookId::from(id), school_id, dto).await?;
+
+    Ok(Created(copy))
+}
+
+/// List the copies of a book
+#[utoipa::path(
+    get,
+    path = "/api/library/books/{id}/copies",
+    summary = "List copies",
+    params(
+        ("id" = Uuid, Path, description = "Book ID")
+    ),
+    responses(
+        (status = 200, description = "Copies of the book", body = Vec<LibraryCopy>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:read permission"),
+        (status = 404, description = "Book not found")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_copies(
+    State(state): State<AppState>,
+    RequireLibraryRead(auth_user): RequireLibraryRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<LibraryCopy>>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let copies = LibraryService::get_copies(&state.db, LibraryBookId::from(id), school_id).await?;
+
+    Ok(Json(copies))
+}
+
+/// Update a copy's status or notes
+#[utoipa::path(
+    put,
+    path = "/api/library/copies/{id}",
+    summary = "Update copy",
+    params(
+        ("id" = Uuid, Path, description = "Copy ID")
+    ),
+    request_body = UpdateCopyDto,
+    responses(
+        (status = 200, description = "Copy updated", body = LibraryCopy),
+        (status = 400, description = "Invalid status change"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:update permission"),
+        (status = 404, description = "Copy not found")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_copy(
+    State(state): State<AppState>,
+    RequireLibraryUpdate(auth_user): RequireLibraryUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateCopyDto>,
+) -> Result<Json<LibraryCopy>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let copy =
+        LibraryService::update_copy(&state.db, LibraryCopyId::from(id), school_id, dto).await?;
+
+    Ok(Json(copy))
+}
+
+/// Delete a copy
+#[utoipa::path(
+    delete,
+    path = "/api/library/copies/{id}",
+    summary = "Delete copy",
+    params(
+        ("id" = Uuid, Path, description = "Copy ID")
+    ),
+    responses(
+        NoContent,
+        (status = 400, description = "Copy is on loan"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:delete permission"),
+        (status = 404, description = "Copy not found")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_copy(
+    State(state): State<AppState>,
+    RequireLibraryDelete(auth_user): RequireLibraryDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    LibraryService::delete_copy(&state.db, LibraryCopyId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Get the school's lending policy
+#[utoipa::path(
+    get,
+    path = "/api/library/policy",
+    summary = "Get lending policy",
+    params(LibraryPolicyQueryParams),
+    responses(
+        (status = 200, description = "Lending policy", body = LibraryPolicy),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:read permission")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_policy(
+    State(state): State<AppState>,
+    RequireLibraryRead(auth_user): RequireLibraryRead,
+    Query(params): Query<LibraryPolicyQueryParams>,
+) -> Result<Json<LibraryPolicy>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let policy = LibraryService::get_policy(&state.db, school_id).await?;
+
+    Ok(Json(policy))
+}
+
+/// Update the school's lending policy
+#[utoipa::path(
+    put,
+    path = "/api/library/policy",
+    summary = "Update lending policy",
+    request_body = UpdateLibraryPolicyDto,
+    responses(
+        (status = 200, description = "Lending policy updated", body = LibraryPolicy),
+        (status = 400, description = "Invalid input or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:update permission")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_policy(
+    State(state): State<AppState>,
+    RequireLibraryUpdate(auth_user): RequireLibraryUpdate,
+    ValidatedJson(dto): ValidatedJson<UpdateLibraryPolicyDto>,
+) -> Result<Json<LibraryPolicy>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let policy = LibraryService::update_policy(&state.db, school_id, dto).await?;
+
+    Ok(Json(policy))
+}
+
+/// Check a copy out to a borrower
+#[utoipa::path(
+    post,
+    path = "/api/library/loans",
+    summary = "Check out copy",
+    request_body = CheckoutDto,
+    responses(
+        Created<LibraryLoan>,
+        (status = 400, description = "Copy unavailable, borrower at loan limit, or borrower from another school"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:lend permission"),
+        (status = 404, description = "Copy not found")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn checkout(
+    State(state): State<AppState>,
+    RequireLibraryLend(auth_user): RequireLibraryLend,
+    ValidatedJson(dto): ValidatedJson<CheckoutDto>,
+) -> Result<Created<LibraryLoan>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let checked_out_by = auth_user.user_id()?;
+    let loan = LibraryService::checkout(&state.db, school_id, checked_out_by, dto).await?;
+
+    Ok(Created(loan))
+}
+
+/// List loans for a school
+#[utoipa::path(
+    get,
+    path = "/api/library/loans",
+    summary = "List loans",
+    params(LoanFilterParams),
+    responses(
+        (status = 200, description = "Loans, newest first", body = PaginatedLoansResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:read permission")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_loans(
+    State(state): State<AppState>,
+    RequireLibraryRead(auth_user): RequireLibraryRead,
+    Query(filters): Query<LoanFilterParams>,
+) -> Result<Json<PaginatedLoansResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let loans = LibraryService::get_loans(&state.db, school_id, filters).await?;
+
+    Ok(Json(loans))
+}
+
+/// Return a copy
+///
+/// Overdue returns raise a fine charge when the school's policy sets a daily fine.
+#[utoipa::path(
+    post,
+    path = "/api/library/loans/{id}/return",
+    summary = "Return copy",
+    params(
+        ("id" = Uuid, Path, description = "Loan ID")
+    ),
+    responses(
+        (status = 200, description = "Copy returned", body = ReturnLoanResponse),
+        (status = 400, description = "Loan has already been returned"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:lend permission"),
+        (status = 404, description = "Loan not found")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn return_loan(
+    State(state): State<AppState>,
+    RequireLibraryLend(auth_user): RequireLibraryLend,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReturnLoanResponse>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let returned_to = auth_user.user_id()?;
+    let result =
+        LibraryService::return_loan(&state.db, LibraryLoanId::from(id), school_id, returned_to)
+            .await?;
+
+    Ok(Json(result))
+}
+
+/// List overdue fine charges
+#[utoipa::path(
+    get,
+    path = "/api/library/fines",
+    summary = "List fines",
+    params(LibraryFineFilterParams),
+    responses(
+        (status = 200, description = "Fine charges, newest first", body = Vec<LibraryFineCharge>),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:read permission")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_fines(
+    State(state): State<AppState>,
+    RequireLibraryRead(auth_user): RequireLibraryRead,
+    Query(filters): Query<LibraryFineFilterParams>,
+) -> Result<Json<Vec<LibraryFineCharge>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let fines = LibraryService::get_fines(&state.db, school_id, filters).await?;
+
+    Ok(Json(fines))
+}
+
+/// Get a member's borrowing history
+#[utoipa::path(
+    get,
+    path = "/api/library/members/{user_id}/history",
+    summary = "Get borrowing history",
+    params(
+        ("user_id" = Uuid, Path, description = "Student or staff member ID")
+    ),
+    responses(
+        (status = 200, description = "Borrowing history", body = BorrowingHistory),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires library:read permission"),
+        (status = 404, description = "Member not found")
+    ),
+    tag = "Library",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_borrowing_history(
+    State(state): State<AppState>,
+    RequireLibraryRead(auth_user): RequireLibraryRead,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<BorrowingHistory>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let history =
+        LibraryService::get_borrowing_history(&state.db, UserId::from(user_id), school_id).await?;
+
+    Ok(Json(history))
+}
@@ -0,0 +1,12 @@
+//! Library module.
+//!
+//! This module runs school library lending: a catalog of titles with their
+//! physical copies, checkout and return at the library desk, due dates and
+//! borrowing limits from each school's lending policy, and per-member
+//! borrowing history. Copies returned after their due date raise a fine
+//! charge according to the policy's daily rate and cap.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Library data models and DTOs.
+//!
+//! This module re-exports library models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all library models from the shared crate
+pub use chalkbyte_models::library::*;
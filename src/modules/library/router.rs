@@ -0,0 +1,33 @@
+use axum::{
+    Router,
+    routing::{get, post, put},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    add_copy, checkout, create_book, delete_book, delete_copy, get_book_by_id, get_books,
+    get_borrowing_history, get_copies, get_fines, get_loans, get_policy, return_loan, update_book,
+    update_copy, update_policy,
+};
+
+/// Initialize the library router
+/// Routes: POST /books, GET /books, GET /books/{id}, PUT /books/{id}, DELETE /books/{id},
+/// POST /books/{id}/copies, GET /books/{id}/copies, PUT /copies/{id}, DELETE /copies/{id},
+/// GET /policy, PUT /policy, POST /loans, GET /loans, POST /loans/{id}/return,
+/// GET /fines, GET /members/{user_id}/history
+pub fn init_library_router() -> Router<AppState> {
+    Router::new()
+        .route("/books", post(create_book).get(get_books))
+        .route(
+            "/books/{id}",
+            get(get_book_by_id).put(update_book).delete(delete_book),
+        )
+        .route("/books/{id}/copies", post(add_copy).get(get_copies))
+        .route("/copies/{id}", put(update_copy).delete(delete_copy))
+        .route("/policy", get(get_policy).put(update_policy))
+        .route("/loans", post(checkout).get(get_loans))
+        .route("/loans/{id}/return", post(return_loan))
+        .route("/fines", get(get_fines))
+        .route("/members/{user_id}/history", get(get_borrowing_history))
+}
@@ -0,0 +1,987 @@
+use chrono::Utc;
+use sqlx::{PgExecutor, PgPool};
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_models::ids::{LibraryBookId, LibraryCopyId, LibraryLoanId, SchoolId, UserId};
+
+use crate::modules::library::model::{
+    BookFilterParams, BorrowingHistory, CheckoutDto, CopyStatus, CreateBookDto, CreateCopyDto,
+    LibraryBook, LibraryBookWithAvailability, LibraryCopy, LibraryFineCharge,
+    LibraryFineFilterParams, LibraryLoan, LibraryLoanWithDetails, LibraryPolicy, LoanFilterParams,
+    PaginatedBooksResponse, PaginatedLoansResponse, ReturnLoanResponse, UpdateBookDto,
+    UpdateCopyDto, UpdateLibraryPolicyDto,
+};
+use crate::modules::users::model::system_roles;
+
+const BOOK_COLUMNS: &str = "id, school_id, title, author, isbn, publisher, publication_year, \
+     category, created_at, updated_at";
+
+const COPY_COLUMNS: &str = "id, book_id, school_id, barcode, status, notes, created_at, updated_at";
+
+const POLICY_COLUMNS: &str =
+    "school_id, loan_days, student_loan_limit, staff_loan_limit, daily_fine, max_fine, updated_at";
+
+const LOAN_COLUMNS: &str = "id, school_id, copy_id, borrower_id, checked_out_at, due_date, \
+     returned_at, checked_out_by, returned_to, created_at";
+
+const FINE_COLUMNS: &str = "id, school_id, loan_id, borrower_id, days_overdue, amount, created_at";
+
+const LOAN_DETAILS_SELECT: &str = r#"SELECT l.id, l.copy_id, c.barcode, b.id AS book_id,
+       b.title AS book_title, l.borrower_id, u.first_name AS borrower_first_name,
+       u.last_name AS borrower_last_name, l.checked_out_at, l.due_date, l.returned_at,
+       f.amount AS fine_amount
+FROM library_loans l
+JOIN library_copies c ON c.id = l.copy_id
+JOIN library_books b ON b.id = c.book_id
+JOIN users u ON u.id = l.borrower_id
+LEFT JOIN library_fine_charges f ON f.loan_id = l.id"#;
+
+fn unique_violation_as(e: sqlx::Error, message: &'static str) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e
+        && db_err.is_unique_violation()
+    {
+        return AppError::bad_request(anyhow::anyhow!(message));
+    }
+    AppError::from(e)
+}
+
+/// Fine for a loan returned `days_overdue` days late under `policy`.
+fn overdue_fine(policy: &LibraryPolicy, days_overdue: i64) -> i64 {
+    let fine = policy.daily_fine.saturating_mul(days_overdue.max(0));
+    policy.max_fine.map_or(fine, |max| fine.min(max))
+}
+
+pub struct LibraryService;
+
+impl LibraryService {
+    async fn find_book(
+        db: &PgPool,
+        book_id: LibraryBookId,
+        school_id: Option<SchoolId>,
+    ) -> Result<LibraryBook, AppError> {
+        sqlx::query_as::<_, LibraryBook>(&format!(
+            "SELECT {BOOK_COLUMNS} FROM library_books
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(book_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Book not found")))
+    }
+
+    async fn find_copy(
+        db: &PgPool,
+        copy_id: LibraryCopyId,
+        school_id: Option<SchoolId>,
+    ) -> Result<LibraryCopy, AppError> {
+        sqlx::query_as::<_, LibraryCopy>(&format!(
+            "SELECT {COPY_COLUMNS} FROM library_copies
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(copy_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Copy not found")))
+    }
+
+    /// Load a school's policy, creating it with default values on first use.
+    async fn load_policy<'e>(
+        executor: impl PgExecutor<'e>,
+        school_id: SchoolId,
+    ) -> Result<LibraryPolicy, AppError> {
+        let policy = sqlx::query_as::<_, LibraryPolicy>(&format!(
+            "WITH inserted AS (
+                 INSERT INTO library_policies (school_id) VALUES ($1)
+                 ON CONFLICT (school_id) DO NOTHING
+                 RETURNING {POLICY_COLUMNS}
+             )
+             SELECT {POLICY_COLUMNS} FROM inserted
+             UNION ALL
+             SELECT {POLICY_COLUMNS} FROM library_policies WHERE school_id = $1"
+        ))
+        .bind(school_id)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(policy)
+    }
+
+    /// Maximum open loans for a borrower under a policy.
+    async fn loan_limit<'e>(
+        executor: impl PgExecutor<'e>,
+        policy: &LibraryPolicy,
+        borrower_id: UserId,
+    ) -> Result<i32, AppError> {
+        let is_student = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
+        )
+        .bind(borrower_id)
+        .bind(system_roles::STUDENT)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(if is_student {
+            policy.student_loan_limit
+        } else {
+            policy.staff_loan_limit
+        })
+    }
+
+    /// Add a title to a school's catalog.
+    #[instrument(skip(db, dto))]
+    pub async fn create_book(
+        db: &PgPool,
+        school_id: SchoolId,
+        dto: CreateBookDto,
+    ) -> Result<LibraryBook, AppError> {
+        let book = sqlx::query_as::<_, LibraryBook>(&format!(
+            "INSERT INTO library_books (school_id, title, author, isbn, publisher,
+                 publication_year, category)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             RETURNING {BOOK_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.title)
+        .bind(&dto.author)
+        .bind(&dto.isbn)
+        .bind(&dto.publisher)
+        .bind(dto.publication_year)
+        .bind(&dto.category)
+        .fetch_one(db)
+        .await
+        .map_err(|e| unique_violation_as(e, "A book with this ISBN already exists"))?;
+
+        Ok(book)
+    }
+
+    /// Search a school's catalog, with copy availability.
+    #[instrument(skip(db))]
+    pub async fn get_books(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: BookFilterParams,
+    ) -> Result<PaginatedBooksResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+        let search = filters.search.as_ref().map(|s| format!("%{}%", s));
+
+        let where_clause = r#"WHERE b.school_id = $1
+              AND ($2::text IS NULL OR b.title ILIKE $2 OR b.author ILIKE $2 OR b.isbn ILIKE $2)
+              AND ($3::text IS NULL OR b.category = $3)"#;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM library_books b {where_clause}"
+        ))
+        .bind(school_id)
+        .bind(&search)
+        .bind(&filters.category)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, LibraryBookWithAvailability>(&format!(
+            r#"SELECT b.id, b.title, b.author, b.isbn, b.category,
+                      COUNT(c.id) FILTER (WHERE c.status <> 'withdrawn') AS total_copies,
+                      COUNT(c.id) FILTER (WHERE c.status = 'available') AS available_copies
+               FROM library_books b
+               LEFT JOIN library_copies c ON c.book_id = b.id
+               {where_clause}
+               GROUP BY b.id
+               ORDER BY b.title
+               LIMIT $4 OFFSET $5"#
+        ))
+        .bind(school_id)
+        .bind(&search)
+        .bind(&filters.category)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedBooksResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Get a catalog entry by ID.
+    #[instrument(skip(db))]
+    pub async fn get_book_by_id(
+        db: &PgPool,
+        book_id: LibraryBookId,
+        school_id: Option<SchoolId>,
+    ) -> Result<LibraryBook, AppError> {
+        Self::find_book(db, book_id, school_id).await
+    }
+
+    /// Update a catalog entry.
+    #[instrument(skip(db, dto))]
+    pub async fn update_book(
+        db: &PgPool,
+        book_id: LibraryBookId,
+        school_id: Option<SchoolId>,
+        dto: UpdateBookDto,
+    ) -> Result<LibraryBook, AppError> {
+        Self::find_book(db, book_id, school_id).await?;
+
+        let book = sqlx::query_as::<_, LibraryBook>(&format!(
+            "UPDATE library_books
+             SET title = COALESCE($2, title),
+                 author = COALESCE($3, author),
+                 isbn = COALESCE($4, isbn),
+                 publisher = COALESCE($5, publisher),
+                 publication_year = COALESCE($6, publication_year),
+                 category = COALESCE($7, category)
+             WHERE id = $1
+             RETURNING {BOOK_COLUMNS}"
+        ))
+        .bind(book_id)
+        .bind(&dto.title)
+        .bind(&dto.author)
+        .bind(&dto.isbn)
+        .bind(&dto.publisher)
+        .bind(dto.publication_year)
+        .bind(&dto.category)
+        .fetch_one(db)
+        .await
+        .map_err(|e| unique_violation_as(e, "A book with this ISBN already exists"))?;
+
+        Ok(book)
+    }
+
+    /// Remove a title and its copies. Fails while any copy is on loan.
+    #[instrument(skip(db))]
+    pub async fn delete_book(
+        db: &PgPool,
+        book_id: LibraryBookId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        Self::find_book(db, book_id, school_id).await?;
+
+        let on_loan = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM library_copies WHERE book_id = $1 AND status = 'on_loan')",
+        )
+        .bind(book_id)
+        .fetch_one(db)
+        .await?;
+
+        if on_loan {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Cannot delete a book while copies are on loan"
+            )));
+        }
+
+        sqlx::query("DELETE FROM library_books WHERE id = $1")
+            .bind(book_id)
+            .execute(db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Add a physical copy of a book.
+    #[instrument(skip(db, dto))]
+    pub async fn add_copy(
+        db: &PgPool,
+        book_id: LibraryBookId,
+        school_id: Option<SchoolId>,
+        dto: CreateCopyDto,
+    ) -> Result<LibraryCopy, AppError> {
+        let book = Self::find_book(db, book_id, school_id).await?;
+
+        let copy = sqlx::query_as::<_, LibraryCopy>(&format!(
+            "INSERT INTO library_copies (book_id, school_id, barcode, notes)
+             VALUES ($1, $2, $3, $4)
+             RETURNING {COPY_COLUMNS}"
+        ))
+        .bind(book.id)
+        .bind(book.school_id)
+        .bind(&dto.barcode)
+        .bind(&dto.notes)
+        .fetch_one(db)
+        .await
+        .map_err(|e| unique_violation_as(e, "A copy with this barcode already exists"))?;
+
+        Ok(copy)
+    }
+
+    /// List the copies of a book.
+    #[instrument(skip(db))]
+    pub async fn get_copies(
+        db: &PgPool,
+        book_id: LibraryBookId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Vec<LibraryCopy>, AppError> {
+        Self::find_book(db, book_id, school_id).await?;
+
+        let copies = sqlx::query_as::<_, LibraryCopy>(&format!(
+            "SELECT {COPY_COLUMNS} FROM library_copies WHERE book_id = $1 ORDER BY barcode"
+        ))
+        .bind(book_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(copies)
+    }
+
+    /// Update a copy's status or notes.
+    #[instrument(skip(db, dto))]
+    pub async fn update_copy(
+        db: &PgPool,
+        copy_id: LibraryCopyId,
+        school_id: Option<SchoolId>,
+        dto: UpdateCopyDto,
+    ) -> Result<LibraryCopy, AppError> {
+        let copy = Self::find_copy(db, copy_id, school_id).await?;
+
+        if let Some(status) = dto.status {
+            if status == CopyStatus::OnLoan {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "Copies are put on loan by checking them out"
+                )));
+            }
+            if copy.status == CopyStatus::OnLoan {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "Return the copy before changing its status"
+                )));
+            }
+        }
+
+        let copy = sqlx::query_as::<_, LibraryCopy>(&format!(
+            "UPDATE library_copies
+             SET status = COALESCE($2, status),
+                 notes = COALESCE($3, notes)
+             WHERE id = $1
+             RETURNING {COPY_COLUMNS}"
+        ))
+        .bind(copy_id)
+        .bind(dto.status)
+        .bind(&dto.notes)
+        .fetch_one(db)
+        .await?;
+
+        Ok(copy)
+    }
+
+    /// Remove a copy. Fails while the copy is on loan.
+    #[instrument(skip(db))]
+    pub async fn delete_copy(
+        db: &PgPool,
+        copy_id: LibraryCopyId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let copy = Self::find_copy(db, copy_id, school_id).await?;
+
+        if copy.status == CopyStatus::OnLoan {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Cannot delete a copy while it is on loan"
+            )));
+        }
+
+        sqlx::query("DELETE FROM library_copies WHERE id = $1")
+            .bind(copy_id)
+            .execute(db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get a school's lending policy.
+    #[instrument(skip(db))]
+    pub async fn get_policy(db: &PgPool, school_id: SchoolId) -> Result<LibraryPolicy, AppError> {
+        Self::load_policy(db, school_id).await
+    }
+
+    /// Update a school's lending policy.
+    #[instrument(skip(db, dto))]
+    pub async fn update_policy(
+        db: &PgPool,
+        school_id: SchoolId,
+        dto: UpdateLibraryPolicyDto,
+    ) -> Result<LibraryPolicy, AppError> {
+        Self::load_policy(db, school_id).await?;
+
+        let policy = sqlx::query_as::<_, LibraryPolicy>(&format!(
+            "UPDATE library_policies
+             SET loan_days = COALESCE($2, loan_days),
+                 student_loan_limit = COALESCE($3, student_loan_limit),
+                 staff_loan_limit = COALESCE($4, staff_loan_limit),
+                 daily_fine = COALESCE($5, daily_fine),
+                 max_fine = COALESCE($6, max_fine)
+             WHERE school_id = $1
+             RETURNING {POLICY_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(dto.loan_days)
+        .bind(dto.student_loan_limit)
+        .bind(dto.staff_loan_limit)
+        .bind(dto.daily_fine)
+        .bind(dto.max_fine)
+        .fetch_one(db)
+        .await?;
+
+        Ok(policy)
+    }
+
+    /// Lend a copy to a student or staff member of the copy's school.
+    ///
+    /// Enforces the borrower's loan limit from the school's policy.
+    #[instrument(skip(db))]
+    pub async fn checkout(
+        db: &PgPool,
+        school_id: Option<SchoolId>,
+        checked_out_by: UserId,
+        dto: CheckoutDto,
+    ) -> Result<LibraryLoan, AppError> {
+        let today = Utc::now().date_naive();
+        if dto.due_date.is_some_and(|due| due < today) {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Due date cannot be in the past"
+            )));
+        }
+
+        let mut tx = db.begin().await?;
+
+        let copy = sqlx::query_as::<_, LibraryCopy>(&format!(
+            "SELECT {COPY_COLUMNS} FROM library_copies
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)
+             FOR UPDATE"
+        ))
+        .bind(dto.copy_id)
+        .bind(school_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Copy not found")))?;
+
+        if copy.status != CopyStatus::Available {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Copy is not available for loan"
+            )));
+        }
+
+        let borrower_in_school = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND school_id = $2)",
+        )
+        .bind(dto.borrower_id)
+        .bind(copy.school_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if !borrower_in_school {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Borrower must belong to the copy's school"
+            )));
+        }
+
+        let policy = Self::load_policy(&mut *tx, copy.school_id).await?;
+        let limit = Self::loan_limit(&mut *tx, &policy, dto.borrower_id).await?;
+
+        let open_loans = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM library_loans WHERE borrower_id = $1 AND returned_at IS NULL",
+        )
+        .bind(dto.borrower_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if open_loans >= i64::from(limit) {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Borrower has reached their limit of {} open loans",
+                limit
+            )));
+        }
+
+        let due_date = dto
+            .due_date
+            .unwrap_or(today + chrono::Days::new(policy.loan_days as u64));
+
+        let loan = sqlx::query_as::<_, LibraryLoan>(&format!(
+            "INSERT INTO library_loans (school_id, copy_id, borrower_id, due_date, checked_out_by)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {LOAN_COLUMNS}"
+        ))
+        .bind(copy.school_id)
+        .bind(copy.id)
+        .bind(dto.borrower_id)
+        .bind(due_date)
+        .bind(checked_out_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE library_copies SET status = 'on_loan' WHERE id = $1")
+            .bind(copy.id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(loan)
+    }
+
+    /// Return a copy, charging an overdue fine if the school's policy sets one.
+    #[instrument(skip(db))]
+    pub async fn return_loan(
+        db: &PgPool,
+        loan_id: LibraryLoanId,
+        school_id: Option<SchoolId>,
+        returned_to: UserId,
+    ) -> Result<ReturnLoanResponse, AppError> {
+        let mut tx = db.begin().await?;
+
+        let loan = sqlx::query_as::<_, LibraryLoan>(&format!(
+            "SELECT {LOAN_COLUMNS} FROM library_loans
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)
+             FOR UPDATE"
+        ))
+        .bind(loan_id)
+        .bind(school_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Loan not found")))?;
+
+        if loan.returned_at.is_some() {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Loan has already been returned"
+            )));
+        }
+
+        let loan = sqlx::query_as::<_, LibraryLoan>(&format!(
+            "UPDATE library_loans SET returned_at = NOW(), returned_to = $2
+             WHERE id = $1
+             RETURNING {LOAN_COLUMNS}"
+        ))
+        .bind(loan_id)
+        .bind(returned_to)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE library_copies SET status = 'available' WHERE id = $1 AND status = 'on_loan'",
+        )
+        .bind(loan.copy_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let days_overdue = (Utc::now().date_naive() - loan.due_date).num_days();
+        let mut fine = None;
+        if days_overdue > 0 {
+            let policy = Self::load_policy(&mut *tx, loan.school_id).await?;
+            let amount = overdue_fine(&policy, days_overdue);
+            if amount > 0 {
+                fine = Some(
+                    sqlx::query_as::<_, LibraryFineCharge>(&format!(
+                        "INSERT INTO library_fine_charges (school_id, loan_id, borrower_id,
+                             days_overdue, amount)
+                         VALUES ($1, $2, $3, $4, $5)
+                         RETURNING {FINE_COLUMNS}"
+                    ))
+                    .bind(loan.school_id)
+                    .bind(loan.id)
+                    .bind(loan.borrower_id)
+                    .bind(days_overdue as i32)
+                    .bind(amount)
+                    .fetch_one(&mut *tx)
+                    .await?,
+                );
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(ReturnLoanResponse { loan, fine })
+    }
+
+    /// Get paginated loans for a school, newest first.
+    #[instrument(skip(db))]
+    pub async fn get_loans(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: LoanFilterParams,
+    ) -> Result<PaginatedLoansResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+        let today = Utc::now().date_naive();
+
+        let where_clause = r#"WHERE l.school_id = $1
+              AND ($2::uuid IS NULL OR l.borrower_id = $2)
+              AND ($3::bool IS NULL OR (l.returned_at IS NOT NULL) = $3)
+              AND ($4::bool IS NULL OR (l.returned_at IS NULL AND l.due_date < $5) = $4)"#;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM library_loans l {where_clause}"
+        ))
+        .bind(school_id)
+        .bind(filters.borrower_id)
+        .bind(filters.returned)
+        .bind(filters.overdue)
+        .bind(today)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, LibraryLoanWithDetails>(&format!(
+            "{LOAN_DETAILS_SELECT}
+             {where_clause}
+             ORDER BY l.checked_out_at DESC
+             LIMIT $6 OFFSET $7"
+        ))
+        .bind(school_id)
+        .bind(filters.borrower_id)
+        .bind(filters.returned)
+        .bind(filters.overdue)
+        .bind(today)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedLoansResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// List overdue fine charges for a school.
+    #[instrument(skip(db))]
+    pub async fn get_fines(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: LibraryFineFilterParams,
+    ) -> Result<Vec<LibraryFineCharge>, AppError> {
+        let fines = sqlx::query_as::<_, LibraryFineCharge>(&format!(
+            "SELECT {FINE_COLUMNS} FROM library_fine_charges
+             WHERE school_id = $1 AND ($2::uuid IS NULL OR borrower_id = $2)
+             ORDER BY created_at DESC"
+        ))
+        .bind(school_id)
+        .bind(filters.borrower_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(fines)
+    }
+
+    /// Get a member's full borrowing history with their current limit and fines.
+    #[instrument(skip(db))]
+    pub async fn get_borrowing_history(
+        db: &PgPool,
+        borrower_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<BorrowingHistory, AppError> {
+        let borrower_school = sqlx::query_scalar::<_, Option<SchoolId>>(
+            "SELECT school_id FROM users WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(borrower_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .flatten()
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Member not found")))?;
+
+        let policy = Self::load_policy(db, borrower_school).await?;
+        let loan_limit = Self::loan_limit(db, &policy, borrower_id).await?;
+
+        let loans = sqlx::query_as::<_, LibraryLoanWithDetails>(&format!(
+            "{LOAN_DETAILS_SELECT}
+             WHERE l.borrower_id = $1
+             ORDER BY l.checked_out_at DESC"
+        ))
+        .bind(borrower_id)
+        .fetch_all(db)
+        .await?;
+
+        let open_loans = loans.iter().filter(|l| l.returned_at.is_none()).count() as i64;
+        let total_fines = loans.iter().filter_map(|l| l.fine_amount).sum();
+
+        Ok(BorrowingHistory {
+            borrower_id,
+            open_loans,
+            loan_limit,
+            total_fines,
+            loans,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use chalkbyte_core::PaginationParams;
+    use chalkbyte_models::ids::RoleId;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId, role_id: RoleId) -> UserId {
+        let user_id: UserId = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Test', 'User', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id.into_inner(),
+            role_id.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id
+    }
+
+    async fn create_copy(pool: &PgPool, school_id: SchoolId, barcode: &str) -> LibraryCopy {
+        let book = LibraryService::create_book(
+            pool,
+            school_id,
+            CreateBookDto {
+                title: format!("Book {barcode}"),
+                author: None,
+                isbn: None,
+                publisher: None,
+                publication_year: None,
+                category: None,
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        LibraryService::add_copy(
+            pool,
+            book.id,
+            Some(school_id),
+            CreateCopyDto {
+                barcode: barcode.to_string(),
+                notes: None,
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    fn checkout_dto(copy_id: LibraryCopyId, borrower_id: UserId) -> CheckoutDto {
+        CheckoutDto {
+            copy_id,
+            borrower_id,
+            due_date: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_checkout_enforces_availability_and_limit(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let librarian = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let other_student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+
+        LibraryService::update_policy(
+            &pool,
+            school_id,
+            UpdateLibraryPolicyDto {
+                loan_days: Some(7),
+                student_loan_limit: Some(1),
+                staff_loan_limit: None,
+                daily_fine: None,
+                max_fine: None,
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let first = create_copy(&pool, school_id, "LIB-001").await;
+        let second = create_copy(&pool, school_id, "LIB-002").await;
+
+        let loan = LibraryService::checkout(
+            &pool,
+            Some(school_id),
+            librarian,
+            checkout_dto(first.id, student),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            loan.due_date,
+            Utc::now().date_naive() + chrono::Days::new(7)
+        );
+
+        let err = LibraryService::checkout(
+            &pool,
+            Some(school_id),
+            librarian,
+            checkout_dto(first.id, other_student),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let err = LibraryService::checkout(
+            &pool,
+            Some(school_id),
+            librarian,
+            checkout_dto(second.id, student),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        // Staff use the staff limit
+        LibraryService::checkout(
+            &pool,
+            Some(school_id),
+            librarian,
+            checkout_dto(second.id, librarian),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_overdue_return_charges_capped_fine(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let librarian = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+
+        LibraryService::update_policy(
+            &pool,
+            school_id,
+            UpdateLibraryPolicyDto {
+                loan_days: None,
+                student_loan_limit: None,
+                staff_loan_limit: None,
+                daily_fine: Some(100),
+                max_fine: Some(500),
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let copy = create_copy(&pool, school_id, "LIB-100").await;
+        let loan = LibraryService::checkout(
+            &pool,
+            Some(school_id),
+            librarian,
+            checkout_dto(copy.id, student),
+        )
+        .await
+        .unwrap();
+
+        sqlx::query("UPDATE library_loans SET due_date = CURRENT_DATE - 10 WHERE id = $1")
+            .bind(loan.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let overdue = LibraryService::get_loans(
+            &pool,
+            school_id,
+            LoanFilterParams {
+                school_id: None,
+                borrower_id: None,
+                returned: None,
+                overdue: Some(true),
+                pagination: PaginationParams::default(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(overdue.meta.total, 1);
+
+        let returned = LibraryService::return_loan(&pool, loan.id, Some(school_id), librarian)
+            .await
+            .unwrap();
+        let fine = returned.fine.unwrap();
+        assert_eq!(fine.days_overdue, 10);
+        assert_eq!(fine.amount, 500);
+
+        let copy = LibraryService::find_copy(&pool, copy.id, None)
+            .await
+            .unwrap();
+        assert_eq!(copy.status, CopyStatus::Available);
+
+        let err = LibraryService::return_loan(&pool, loan.id, Some(school_id), librarian)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let history = LibraryService::get_borrowing_history(&pool, student, Some(school_id))
+            .await
+            .unwrap();
+        assert_eq!(history.loans.len(), 1);
+        assert_eq!(history.open_loans, 0);
+        assert_eq!(history.total_fines, 500);
+        assert_eq!(history.loan_limit, 3);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_copy_status_changes_are_guarded(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let librarian = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+
+        let copy = create_copy(&pool, school_id, "LIB-200").await;
+        LibraryService::checkout(
+            &pool,
+            Some(school_id),
+            librarian,
+            checkout_dto(copy.id, student),
+        )
+        .await
+        .unwrap();
+
+        let err = LibraryService::update_copy(
+            &pool,
+            copy.id,
+            Some(school_id),
+            UpdateCopyDto {
+                status: Some(CopyStatus::Lost),
+                notes: None,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let err = LibraryService::delete_book(&pool, copy.book_id, Some(school_id))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let other_school = create_test_school(&pool).await;
+        let err = LibraryService::delete_copy(&pool, copy.id, Some(other_school))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,334 @@
+use axum::{
+    Json,
+    extract::{Multipart, Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, PaginationParams};
+use chalkbyte_models::ids::{ConversationId, MessageId, StoredFileId};
+
+use crate::middleware::auth::{RequireMessagingRead, RequireMessagingSend};
+use crate::modules::messaging::model::{
+    AttachmentUploadForm, ConversationDetail, ConversationFilterParams, ConversationMessage,
+    PaginatedConversationsResponse, PaginatedMessagesResponse, SendMessageDto,
+    StartConversationDto, UnreadMessageCounts,
+};
+use crate::modules::messaging::service::MessagingService;
+use crate::modules::storage::controller::read_upload;
+use crate::modules::storage::model::DownloadUrl;
+use crate::state::AppState;
+use crate::utils::auth_helpers::get_admin_school_id;
+use crate::validator::ValidatedJson;
+
+/// Start a conversation
+///
+/// Teachers can message guardians of the school's students, optionally about
+/// one of the guardian's children, and school admins can message teachers
+/// and other admins. The recipient gets an in-app notification.
+#[utoipa::path(
+    post,
+    path = "/api/messaging/conversations",
+    summary = "Start conversation",
+    request_body = StartConversationDto,
+    responses(
+        Created<ConversationDetail>,
+        (status = 400, description = "Invalid input, or the recipient cannot be messaged"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires messaging:send permission"),
+        (status = 429, description = "Too many messages sent")
+    ),
+    tag = "Messaging",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn start_conversation(
+    State(state): State<AppState>,
+    RequireMessagingSend(auth_user): RequireMessagingSend,
+    ValidatedJson(dto): ValidatedJson<StartConversationDto>,
+) -> Result<Created<ConversationDetail>, AppError> {
+    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
+    let sender_id = auth_user.user_id()?;
+    let conversation =
+        MessagingService::start_conversation(&state.db, school_id, sender_id, dto).await?;
+
+    Ok(Created(conversation))
+}
+
+/// List my conversations
+#[utoipa::path(
+    get,
+    path = "/api/messaging/conversations",
+    summary = "List my conversations",
+    params(ConversationFilterParams),
+    responses(
+        (status = 200, description = "Conversations, most recently active first", body = PaginatedConversationsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires messaging:read permission")
+    ),
+    tag = "Messaging",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_conversations(
+    State(state): State<AppState>,
+    RequireMessagingRead(auth_user): RequireMessagingRead,
+    Query(filters): Query<ConversationFilterParams>,
+) -> Result<Json<PaginatedConversationsResponse>, AppError> {
+    let conversations =
+        MessagingService::get_conversations(&state.db, auth_user.user_id()?, filters).await?;
+
+    Ok(Json(conversations))
+}
+
+/// Get a conversation
+#[utoipa::path(
+    get,
+    path = "/api/messaging/conversations/{id}",
+    summary = "Get conversation",
+    params(
+        ("id" = Uuid, Path, description = "Conversation ID")
+    ),
+    responses(
+        (status = 200, description = "Conversation with its participants", body = ConversationDetail),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires messaging:read permission"),
+        (status = 404, description = "Conversation not found, or the caller is not in it")
+    ),
+    tag = "Messaging",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_conversation(
+    State(state): State<AppState>,
+    RequireMessagingRead(auth_user): RequireMessagingRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ConversationDetail>, AppError> {
+    let conversation = MessagingService::get_conversation(
+        &state.db,
+        ConversationId::from(id),
+        auth_user.user_id()?,
+    )
+    .await?;
+
+    Ok(Json(conversation))
+}
+
+/// List a conversation's messages
+#[utoipa::path(
+    get,
+    path = "/api/messaging/conversations/{id}/messages",
+    summary = "List messages",
+    params(
+        ("id" = Uuid, Path, description = "Conversation ID"),
+        ("limit" = Option<i64>, Query, description = "Number of items per page (1-100, default: 10)"),
+        ("offset" = Option<i64>, Query, description = "Number of items to skip (default: 0)")
+    ),
+    responses(
+        (status = 200, description = "Messages, newest first", body = PaginatedMessagesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires messaging:read permission"),
+        (status = 404, description = "Conversation not found, or the caller is not in it")
+    ),
+    tag = "Messaging",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_messages(
+    State(state): State<AppState>,
+    RequireMessagingRead(auth_user): RequireMessagingRead,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<Json<PaginatedMessagesResponse>, AppError> {
+    let messages = MessagingService::get_messages(
+        &state.db,
+        ConversationId::from(id),
+        auth_user.user_id()?,
+        pagination,
+    )
+    .await?;
+
+    Ok(Json(messages))
+}
+
+/// Send a message
+#[utoipa::path(
+    post,
+    path = "/api/messaging/conversations/{id}/messages",
+    summary = "Send message",
+    params(
+        ("id" = Uuid, Path, description = "Conversation ID")
+    ),
+    request_body = SendMessageDto,
+    responses(
+        Created<ConversationMessage>,
+        (status = 400, description = "Invalid input"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires messaging:send permission"),
+        (status = 404, description = "Conversation not found, or the caller is not in it"),
+        (status = 429, description = "Too many messages sent")
+    ),
+    tag = "Messaging",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn send_message(
+    State(state): State<AppState>,
+    RequireMessagingSend(auth_user): RequireMessagingSend,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<SendMessageDto>,
+) -> Result<Created<ConversationMessage>, AppError> {
+    let message = MessagingService::send_message(
+        &state.db,
+        ConversationId::from(id),
+        auth_user.user_id()?,
+        dto,
+    )
+    .await?;
+
+    Ok(Created(message))
+}
+
+/// Send a message with an attachment
+///
+/// The file counts towards the school's storage quota.
+#[utoipa::path(
+    post,
+    path = "/api/messaging/conversations/{id}/attachments",
+    summary = "Send attachment",
+    params(
+        ("id" = Uuid, Path, description = "Conversation ID")
+    ),
+    request_body(content = AttachmentUploadForm, content_type = "multipart/form-data"),
+    responses(
+        Created<ConversationMessage>,
+        (status = 400, description = "Missing 'file' field, or body too long"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires messaging:send permission"),
+        (status = 404, description = "Conversation not found, or the caller is not in it"),
+        (status = 413, description = "File over 5MB, or the school's storage quota is used up"),
+        (status = 415, description = "Not a PDF, PNG, or JPEG file"),
+        (status = 429, description = "Too many messages sent")
+    ),
+    tag = "Messaging",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, multipart))]
+pub async fn send_attachment(
+    State(state): State<AppState>,
+    RequireMessagingSend(auth_user): RequireMessagingSend,
+    Path(id): Path<Uuid>,
+    multipart: Multipart,
+) -> Result<Created<ConversationMessage>, AppError> {
+    let (file, body) = read_upload(multipart, "body").await?;
+
+    let message = MessagingService::send_attachment(
+        &state.db,
+        state.storage.as_ref(),
+        &state.storage_config,
+        ConversationId::from(id),
+        auth_user.user_id()?,
+        body,
+        &file.filename,
+        &file.content_type,
+        &file.content,
+    )
+    .await?;
+
+    Ok(Created(message))
+}
+
+/// Mark a conversation as read
+///
+/// Returns the caller's unread messages left across their conversations.
+#[utoipa::path(
+    post,
+    path = "/api/messaging/conversations/{id}/read",
+    summary = "Mark conversation read",
+    params(
+        ("id" = Uuid, Path, description = "Conversation ID")
+    ),
+    responses(
+        (status = 200, description = "Unread messages left", body = UnreadMessageCounts),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires messaging:read permission"),
+        (status = 404, description = "Conversation not found, or the caller is not in it")
+    ),
+    tag = "Messaging",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn mark_read(
+    State(state): State<AppState>,
+    RequireMessagingRead(auth_user): RequireMessagingRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<UnreadMessageCounts>, AppError> {
+    let counts =
+        MessagingService::mark_read(&state.db, ConversationId::from(id), auth_user.user_id()?)
+            .await?;
+
+    Ok(Json(counts))
+}
+
+/// Get my unread message counts
+#[utoipa::path(
+    get,
+    path = "/api/messaging/unread",
+    summary = "Get unread counts",
+    responses(
+        (status = 200, description = "Unread messages across the caller's conversations", body = UnreadMessageCounts),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires messaging:read permission")
+    ),
+    tag = "Messaging",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_unread_counts(
+    State(state): State<AppState>,
+    RequireMessagingRead(auth_user): RequireMessagingRead,
+) -> Result<Json<UnreadMessageCounts>, AppError> {
+    let counts = MessagingService::get_unread_counts(&state.db, auth_user.user_id()?).await?;
+
+    Ok(Json(counts))
+}
+
+/// Get an attachment download link
+///
+/// Returns a presigned link that downloads the file without further
+/// authentication until it expires.
+#[utoipa::path(
+    get,
+    path = "/api/messaging/messages/{message_id}/attachments/{file_id}/download",
+    summary = "Get attachment download link",
+    params(
+        ("message_id" = Uuid, Path, description = "Message ID"),
+        ("file_id" = Uuid, Path, description = "Attachment file ID")
+    ),
+    responses(
+        (status = 200, description = "Download link", body = DownloadUrl),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires messaging:read permission"),
+        (status = 404, description = "Attachment not found, or the caller is not in its conversation")
+    ),
+    tag = "Messaging",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_attachment_download(
+    State(state): State<AppState>,
+    RequireMessagingRead(auth_user): RequireMessagingRead,
+    Path((message_id, file_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<DownloadUrl>, AppError> {
+    let download = MessagingService::get_attachment_download(
+        &state.db,
+        state.storage.as_ref(),
+        &state.storage_config,
+        MessageId::from(message_id),
+        StoredFileId::from(file_id),
+        auth_user.user_id()?,
+    )
+    .await?;
+
+    Ok(Json(download))
+}
@@ -0,0 +1,18 @@
+//! Messaging module.
+//!
+//! This module lets teachers talk to the guardians of the school's students,
+//! and school admins talk to teachers and to each other. A conversation
+//! belongs to one school and is only visible to its participants; a
+//! teacher-guardian conversation may be about one of the guardian's
+//! children. Each participant has a read marker, from which the inbox and
+//! the unread summary count unread messages.
+//!
+//! Messages may carry a PDF or image attachment, kept in the storage backend
+//! against the school's storage quota and downloaded through short-lived
+//! links. Starting conversations and sending messages count against a
+//! per-user limit (`RATE_LIMIT_MESSAGES_PER_MINUTE`) to stop spam.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
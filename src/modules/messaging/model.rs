@@ -0,0 +1,7 @@
+//! Messaging data models and DTOs.
+//!
+//! This module re-exports messaging models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all messaging models from the shared crate
+pub use chalkbyte_models::messaging::*;
@@ -0,0 +1,44 @@
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    routing::{get, post},
+};
+
+use crate::modules::storage::model::ATTACHMENT_POLICY;
+use crate::modules::storage::router::MULTIPART_OVERHEAD;
+use crate::state::AppState;
+
+use super::controller::{
+    get_attachment_download, get_conversation, get_conversations, get_messages, get_unread_counts,
+    mark_read, send_attachment, send_message, start_conversation,
+};
+
+/// Initialize the messaging router
+/// Routes: POST /conversations, GET /conversations, GET /conversations/{id},
+/// GET /conversations/{id}/messages, POST /conversations/{id}/messages,
+/// POST /conversations/{id}/attachments, POST /conversations/{id}/read,
+/// GET /unread, GET /messages/{message_id}/attachments/{file_id}/download
+pub fn init_messaging_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/conversations",
+            post(start_conversation).get(get_conversations),
+        )
+        .route("/conversations/{id}", get(get_conversation))
+        .route(
+            "/conversations/{id}/messages",
+            get(get_messages).post(send_message),
+        )
+        .route(
+            "/conversations/{id}/attachments",
+            post(send_attachment).layer(DefaultBodyLimit::max(
+                ATTACHMENT_POLICY.max_bytes + MULTIPART_OVERHEAD,
+            )),
+        )
+        .route("/conversations/{id}/read", post(mark_read))
+        .route("/unread", get(get_unread_counts))
+        .route(
+            "/messages/{message_id}/attachments/{file_id}/download",
+            get(get_attachment_download),
+        )
+}
@@ -0,0 +1,860 @@
+use std::collections::HashMap;
+
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+
+use chalkbyte_config::StorageConfig;
+use chalkbyte_core::{AppError, PaginationMeta, PaginationParams};
+use chalkbyte_models::ids::{ConversationId, MessageId, SchoolId, StoredFileId, UserId};
+
+use crate::modules::messaging::model::{
+    Conversation, ConversationDetail, ConversationFilterParams, ConversationKind,
+    ConversationMessage, ConversationParticipant, ConversationSummary, MessageAttachment,
+    PaginatedConversationsResponse, PaginatedMessagesResponse, SendMessageDto,
+    StartConversationDto, UnreadMessageCounts,
+};
+use crate::modules::storage::backend::StorageBackend;
+use crate::modules::storage::model::DownloadUrl;
+use crate::modules::storage::service::StorageService;
+use crate::modules::users::model::system_roles;
+
+const CONVERSATION_COLUMNS: &str = "c.id, c.school_id, c.kind, c.subject, c.student_id, c.created_by, c.created_at, \
+     c.last_message_at";
+
+const MESSAGE_COLUMNS: &str = "id, conversation_id, sender_id, body, created_at";
+
+/// Messages in conversation `c` that participant `p` has not read.
+const UNREAD_COUNT: &str = "(SELECT COUNT(*) FROM messages m
+     WHERE m.conversation_id = c.id
+       AND m.sender_id IS DISTINCT FROM p.user_id
+       AND (p.last_read_at IS NULL OR m.created_at > p.last_read_at))";
+
+/// What a user is in a school, as far as messaging goes.
+#[derive(Debug, Clone, Copy, Default, sqlx::FromRow)]
+struct MessagingRoles {
+    admin: bool,
+    teacher: bool,
+    /// Linked to at least one student of the school
+    guardian: bool,
+}
+
+/// The kind of conversation two users may have, if any.
+///
+/// Teachers talk to guardians, and school admins to teachers and other
+/// admins. Students and unrelated pairs cannot message each other.
+fn conversation_kind(
+    sender: MessagingRoles,
+    recipient: MessagingRoles,
+) -> Option<ConversationKind> {
+    if (sender.teacher && recipient.guardian) || (sender.guardian && recipient.teacher) {
+        Some(ConversationKind::TeacherGuardian)
+    } else if (sender.admin && (recipient.admin || recipient.teacher))
+        || (sender.teacher && recipient.admin)
+    {
+        Some(ConversationKind::Staff)
+    } else {
+        None
+    }
+}
+
+pub struct MessagingService;
+
+impl MessagingService {
+    async fn messaging_roles(
+        db: &PgPool,
+        user_id: UserId,
+        school_id: SchoolId,
+    ) -> Result<MessagingRoles, AppError> {
+        let roles = sqlx::query_as::<_, MessagingRoles>(
+            r#"SELECT
+                   EXISTS(SELECT 1 FROM users u JOIN user_roles ur ON ur.user_id = u.id
+                          WHERE u.id = $1 AND u.school_id = $2 AND ur.role_id = $3) AS admin,
+                   EXISTS(SELECT 1 FROM users u JOIN user_roles ur ON ur.user_id = u.id
+                          WHERE u.id = $1 AND u.school_id = $2 AND ur.role_id = $4) AS teacher,
+                   EXISTS(SELECT 1 FROM student_guardians
+                          WHERE guardian_id = $1 AND school_id = $2) AS guardian"#,
+        )
+        .bind(user_id)
+        .bind(school_id)
+        .bind(system_roles::ADMIN)
+        .bind(system_roles::TEACHER)
+        .fetch_one(db)
+        .await?;
+
+        Ok(roles)
+    }
+
+    /// Check that a conversation about `student_id` makes sense: the
+    /// student is in the school and, between a teacher and a guardian, is
+    /// one of the guardian's children.
+    async fn check_student(
+        db: &PgPool,
+        school_id: SchoolId,
+        student_id: UserId,
+        guardian_id: Option<UserId>,
+    ) -> Result<(), AppError> {
+        let valid = match guardian_id {
+            Some(guardian_id) => {
+                sqlx::query_scalar::<_, bool>(
+                    "SELECT EXISTS(
+                         SELECT 1 FROM student_guardians
+                         WHERE student_id = $1 AND guardian_id = $2 AND school_id = $3
+                     )",
+                )
+                .bind(student_id)
+                .bind(guardian_id)
+                .bind(school_id)
+                .fetch_one(db)
+                .await?
+            }
+            None => {
+                sqlx::query_scalar::<_, bool>(
+                    "SELECT EXISTS(
+                         SELECT 1 FROM users u JOIN user_roles ur ON ur.user_id = u.id
+                         WHERE u.id = $1 AND u.school_id = $2 AND ur.role_id = $3
+                     )",
+                )
+                .bind(student_id)
+                .bind(school_id)
+                .bind(system_roles::STUDENT)
+                .fetch_one(db)
+                .await?
+            }
+        };
+
+        if !valid {
+            return Err(AppError::bad_request(anyhow::anyhow!(match guardian_id {
+                Some(_) => "The conversation's student must be one of the guardian's children",
+                None => "The conversation's student must be a student of the school",
+            })));
+        }
+        Ok(())
+    }
+
+    /// Get a conversation the user takes part in.
+    async fn find_conversation(
+        db: &PgPool,
+        conversation_id: ConversationId,
+        user_id: UserId,
+    ) -> Result<Conversation, AppError> {
+        sqlx::query_as::<_, Conversation>(&format!(
+            "SELECT {CONVERSATION_COLUMNS} FROM conversations c
+             JOIN conversation_participants p ON p.conversation_id = c.id
+             WHERE c.id = $1 AND p.user_id = $2"
+        ))
+        .bind(conversation_id)
+        .bind(user_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Conversation not found")))
+    }
+
+    /// Add a message to a conversation and move the conversation and the
+    /// sender's read marker up to it.
+    async fn insert_message(
+        conn: &mut PgConnection,
+        conversation_id: ConversationId,
+        sender_id: UserId,
+        body: &str,
+    ) -> Result<ConversationMessage, AppError> {
+        let message = sqlx::query_as::<_, ConversationMessage>(&format!(
+            "INSERT INTO messages (conversation_id, sender_id, body)
+             VALUES ($1, $2, $3)
+             RETURNING {MESSAGE_COLUMNS}"
+        ))
+        .bind(conversation_id)
+        .bind(sender_id)
+        .bind(body)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        sqlx::query("UPDATE conversations SET last_message_at = $2 WHERE id = $1")
+            .bind(conversation_id)
+            .bind(message.created_at)
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query(
+            "UPDATE conversation_participants SET last_read_at = $3
+             WHERE conversation_id = $1 AND user_id = $2",
+        )
+        .bind(conversation_id)
+        .bind(sender_id)
+        .bind(message.created_at)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(message)
+    }
+
+    /// Start a conversation with another user of the school and send its
+    /// first message.
+    ///
+    /// The recipient gets an in-app notification. Later messages only show
+    /// up in unread counts.
+    #[instrument(skip(db, dto))]
+    pub async fn start_conversation(
+        db: &PgPool,
+        school_id: SchoolId,
+        sender_id: UserId,
+        dto: StartConversationDto,
+    ) -> Result<ConversationDetail, AppError> {
+        if dto.recipient_id == sender_id {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Cannot start a conversation with yourself"
+            )));
+        }
+
+        let sender = Self::messaging_roles(db, sender_id, school_id).await?;
+        let recipient = Self::messaging_roles(db, dto.recipient_id, school_id).await?;
+        let kind = conversation_kind(sender, recipient).ok_or_else(|| {
+            AppError::bad_request(anyhow::anyhow!(
+                "Teachers can message guardians of the school's students, and school admins \
+                 can message teachers and other admins"
+            ))
+        })?;
+
+        if let Some(student_id) = dto.student_id {
+            let guardian_id = match kind {
+                ConversationKind::TeacherGuardian if recipient.guardian && sender.teacher => {
+                    Some(dto.recipient_id)
+                }
+                ConversationKind::TeacherGuardian => Some(sender_id),
+                ConversationKind::Staff => None,
+            };
+            Self::check_student(db, school_id, student_id, guardian_id).await?;
+        }
+
+        let mut tx = db.begin().await?;
+
+        let conversation = sqlx::query_as::<_, Conversation>(&format!(
+            "INSERT INTO conversations AS c (school_id, kind, subject, student_id, created_by)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {CONVERSATION_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(kind)
+        .bind(&dto.subject)
+        .bind(dto.student_id)
+        .bind(sender_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO conversation_participants (conversation_id, user_id)
+             VALUES ($1, $2), ($1, $3)",
+        )
+        .bind(conversation.id)
+        .bind(sender_id)
+        .bind(dto.recipient_id)
+        .execute(&mut *tx)
+        .await?;
+
+        Self::insert_message(&mut tx, conversation.id, sender_id, &dto.body).await?;
+
+        sqlx::query(
+            r#"INSERT INTO notifications (user_id, title, body)
+               SELECT $1, 'New message', u.first_name || ' ' || u.last_name || ': ' || $3
+               FROM users u WHERE u.id = $2"#,
+        )
+        .bind(dto.recipient_id)
+        .bind(sender_id)
+        .bind(&dto.subject)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Self::get_conversation(db, conversation.id, sender_id).await
+    }
+
+    /// Get the user's conversations, most recently active first.
+    #[instrument(skip(db))]
+    pub async fn get_conversations(
+        db: &PgPool,
+        user_id: UserId,
+        filters: ConversationFilterParams,
+    ) -> Result<PaginatedConversationsResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM conversation_participants p
+             JOIN conversations c ON c.id = p.conversation_id
+             WHERE p.user_id = $1 AND ($2::text IS NULL OR c.kind = $2)",
+        )
+        .bind(user_id)
+        .bind(filters.kind)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, ConversationSummary>(&format!(
+            r#"SELECT c.id, c.kind, c.subject, c.student_id, o.user_id AS with_user_id,
+                      ou.first_name AS with_first_name, ou.last_name AS with_last_name,
+                      c.last_message_at, {UNREAD_COUNT} AS unread_count
+               FROM conversation_participants p
+               JOIN conversations c ON c.id = p.conversation_id
+               LEFT JOIN LATERAL (
+                   SELECT user_id FROM conversation_participants
+                   WHERE conversation_id = c.id AND user_id <> p.user_id
+                   ORDER BY joined_at
+                   LIMIT 1
+               ) o ON TRUE
+               LEFT JOIN users ou ON ou.id = o.user_id
+               WHERE p.user_id = $1 AND ($2::text IS NULL OR c.kind = $2)
+               ORDER BY c.last_message_at DESC, c.id
+               LIMIT $3 OFFSET $4"#
+        ))
+        .bind(user_id)
+        .bind(filters.kind)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedConversationsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Get a conversation the user takes part in, with its participants.
+    #[instrument(skip(db))]
+    pub async fn get_conversation(
+        db: &PgPool,
+        conversation_id: ConversationId,
+        user_id: UserId,
+    ) -> Result<ConversationDetail, AppError> {
+        let conversation = Self::find_conversation(db, conversation_id, user_id).await?;
+
+        let participants = sqlx::query_as::<_, ConversationParticipant>(
+            "SELECT p.user_id, u.first_name, u.last_name, p.last_read_at
+             FROM conversation_participants p
+             JOIN users u ON u.id = p.user_id
+             WHERE p.conversation_id = $1
+             ORDER BY p.joined_at, u.last_name, u.first_name",
+        )
+        .bind(conversation_id)
+        .fetch_all(db)
+        .await?;
+
+        let unread_count = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT {UNREAD_COUNT} FROM conversations c
+             JOIN conversation_participants p ON p.conversation_id = c.id
+             WHERE c.id = $1 AND p.user_id = $2"
+        ))
+        .bind(conversation_id)
+        .bind(user_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(ConversationDetail {
+            conversation,
+            participants,
+            unread_count,
+        })
+    }
+
+    /// Get a page of a conversation's messages, newest first.
+    #[instrument(skip(db))]
+    pub async fn get_messages(
+        db: &PgPool,
+        conversation_id: ConversationId,
+        user_id: UserId,
+        pagination: PaginationParams,
+    ) -> Result<PaginatedMessagesResponse, AppError> {
+        Self::find_conversation(db, conversation_id, user_id).await?;
+
+        let limit = pagination.limit();
+        let offset = pagination.offset();
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM messages WHERE conversation_id = $1",
+        )
+        .bind(conversation_id)
+        .fetch_one(db)
+        .await?;
+
+        let mut data = sqlx::query_as::<_, ConversationMessage>(&format!(
+            "SELECT {MESSAGE_COLUMNS} FROM messages
+             WHERE conversation_id = $1
+             ORDER BY created_at DESC, id
+             LIMIT $2 OFFSET $3"
+        ))
+        .bind(conversation_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let message_ids: Vec<MessageId> = data.iter().map(|m| m.id).collect();
+        let rows = sqlx::query_as::<_, (MessageId, StoredFileId, String, String, i64)>(
+            "SELECT ma.message_id, f.id, f.filename, f.content_type, f.size_bytes
+             FROM message_attachments ma
+             JOIN stored_files f ON f.id = ma.file_id
+             WHERE ma.message_id = ANY($1)
+             ORDER BY f.created_at",
+        )
+        .bind(&message_ids)
+        .fetch_all(db)
+        .await?;
+
+        let mut attachments: HashMap<MessageId, Vec<MessageAttachment>> = HashMap::new();
+        for (message_id, file_id, filename, content_type, size_bytes) in rows {
+            attachments
+                .entry(message_id)
+                .or_default()
+                .push(MessageAttachment {
+                    file_id,
+                    filename,
+                    content_type,
+                    size_bytes,
+                });
+        }
+        for message in &mut data {
+            message.attachments = attachments.remove(&message.id).unwrap_or_default();
+        }
+
+        Ok(PaginatedMessagesResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Send a message in a conversation the user takes part in.
+    #[instrument(skip(db, dto))]
+    pub async fn send_message(
+        db: &PgPool,
+        conversation_id: ConversationId,
+        sender_id: UserId,
+        dto: SendMessageDto,
+    ) -> Result<ConversationMessage, AppError> {
+        Self::find_conversation(db, conversation_id, sender_id).await?;
+
+        let mut tx = db.begin().await?;
+        let message = Self::insert_message(&mut tx, conversation_id, sender_id, &dto.body).await?;
+        tx.commit().await?;
+
+        Ok(message)
+    }
+
+    /// Send a message with a file attached.
+    ///
+    /// The file is kept in the storage backend and counts towards the
+    /// conversation's school's storage quota.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(db, storage, config, body, content), fields(file.size = content.len()))]
+    pub async fn send_attachment(
+        db: &PgPool,
+        storage: &dyn StorageBackend,
+        config: &StorageConfig,
+        conversation_id: ConversationId,
+        sender_id: UserId,
+        body: Option<String>,
+        filename: &str,
+        content_type: &str,
+        content: &[u8],
+    ) -> Result<ConversationMessage, AppError> {
+        let body = body.unwrap_or_default();
+        if body.chars().count() > 5000 {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Message body must be at most 5000 characters"
+            )));
+        }
+        let conversation = Self::find_conversation(db, conversation_id, sender_id).await?;
+
+        let mut tx = db.begin().await?;
+        let mut message = Self::insert_message(&mut tx, conversation_id, sender_id, &body).await?;
+        let (file_id, storage_key) = StorageService::store_message_attachment(
+            &mut tx,
+            storage,
+            config,
+            conversation.school_id,
+            filename,
+            content_type,
+            content,
+            sender_id,
+        )
+        .await?;
+
+        let linked = sqlx::query_as::<_, MessageAttachment>(
+            r#"WITH linked AS (
+                   INSERT INTO message_attachments (message_id, file_id) VALUES ($1, $2)
+               )
+               SELECT id AS file_id, filename, content_type, size_bytes
+               FROM stored_files WHERE id = $2"#,
+        )
+        .bind(message.id)
+        .bind(file_id)
+        .fetch_one(&mut *tx)
+        .await;
+        let attachment = match linked {
+            Ok(attachment) => attachment,
+            Err(e) => {
+                StorageService::discard(storage, &storage_key).await;
+                return Err(e.into());
+            }
+        };
+
+        if let Err(e) = tx.commit().await {
+            StorageService::discard(storage, &storage_key).await;
+            return Err(e.into());
+        }
+
+        message.attachments.push(attachment);
+        Ok(message)
+    }
+
+    /// Mark everything in a conversation as read by the user, and return
+    /// what they still have unread elsewhere.
+    #[instrument(skip(db))]
+    pub async fn mark_read(
+        db: &PgPool,
+        conversation_id: ConversationId,
+        user_id: UserId,
+    ) -> Result<UnreadMessageCounts, AppError> {
+        Self::find_conversation(db, conversation_id, user_id).await?;
+
+        // Read up to the newest message rather than NOW(), so a message
+        // committed just after is not counted as read
+        sqlx::query(
+            r#"UPDATE conversation_participants
+               SET last_read_at = GREATEST(
+                   last_read_at,
+                   (SELECT MAX(created_at) FROM messages WHERE conversation_id = $1)
+               )
+               WHERE conversation_id = $1 AND user_id = $2"#,
+        )
+        .bind(conversation_id)
+        .bind(user_id)
+        .execute(db)
+        .await?;
+
+        Self::get_unread_counts(db, user_id).await
+    }
+
+    /// Count the user's unread messages across their conversations.
+    #[instrument(skip(db))]
+    pub async fn get_unread_counts(
+        db: &PgPool,
+        user_id: UserId,
+    ) -> Result<UnreadMessageCounts, AppError> {
+        let counts = sqlx::query_as::<_, UnreadMessageCounts>(&format!(
+            "SELECT COALESCE(SUM(unread), 0)::BIGINT AS unread_messages,
+                    COUNT(*) FILTER (WHERE unread > 0) AS unread_conversations
+             FROM (
+                 SELECT {UNREAD_COUNT} AS unread
+                 FROM conversation_participants p
+                 JOIN conversations c ON c.id = p.conversation_id
+                 WHERE p.user_id = $1
+             ) counts"
+        ))
+        .bind(user_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// A link to download a message's attachment, for participants of its
+    /// conversation.
+    #[instrument(skip(db, storage, config))]
+    pub async fn get_attachment_download(
+        db: &PgPool,
+        storage: &dyn StorageBackend,
+        config: &StorageConfig,
+        message_id: MessageId,
+        file_id: StoredFileId,
+        user_id: UserId,
+    ) -> Result<DownloadUrl, AppError> {
+        let visible = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(
+                 SELECT 1 FROM message_attachments ma
+                 JOIN messages m ON m.id = ma.message_id
+                 JOIN conversation_participants p ON p.conversation_id = m.conversation_id
+                 WHERE ma.message_id = $1 AND ma.file_id = $2 AND p.user_id = $3
+             )",
+        )
+        .bind(message_id)
+        .bind(file_id)
+        .bind(user_id)
+        .fetch_one(db)
+        .await?;
+        if !visible {
+            return Err(AppError::not_found(anyhow::anyhow!("Attachment not found")));
+        }
+
+        StorageService::get_message_attachment_download(db, storage, config, file_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::storage::backend::LocalStorageBackend;
+    use axum::http::StatusCode;
+    use chalkbyte_models::ids::RoleId;
+    use uuid::Uuid;
+
+    const PDF: &[u8] = b"%PDF-1.7\n%%EOF\n";
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId, role_id: RoleId) -> UserId {
+        let user_id: UserId = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Test', 'User', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id.into_inner(),
+            role_id.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id
+    }
+
+    async fn link_guardian(pool: &PgPool, school_id: SchoolId, student: UserId, guardian: UserId) {
+        sqlx::query!(
+            "INSERT INTO student_guardians (student_id, guardian_id, school_id) VALUES ($1, $2, $3)",
+            student.into_inner(),
+            guardian.into_inner(),
+            school_id.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    fn start_dto(recipient_id: UserId, student_id: Option<UserId>) -> StartConversationDto {
+        StartConversationDto {
+            recipient_id,
+            subject: "Homework".to_string(),
+            student_id,
+            body: "Could we talk about this week's homework?".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_conversation_kind() {
+        let admin = MessagingRoles {
+            admin: true,
+            ..Default::default()
+        };
+        let teacher = MessagingRoles {
+            teacher: true,
+            ..Default::default()
+        };
+        let guardian = MessagingRoles {
+            guardian: true,
+            ..Default::default()
+        };
+        let student = MessagingRoles::default();
+
+        assert_eq!(
+            conversation_kind(teacher, guardian),
+            Some(ConversationKind::TeacherGuardian)
+        );
+        assert_eq!(
+            conversation_kind(guardian, teacher),
+            Some(ConversationKind::TeacherGuardian)
+        );
+        assert_eq!(
+            conversation_kind(teacher, admin),
+            Some(ConversationKind::Staff)
+        );
+        assert_eq!(
+            conversation_kind(admin, admin),
+            Some(ConversationKind::Staff)
+        );
+        assert_eq!(conversation_kind(teacher, teacher), None);
+        assert_eq!(conversation_kind(admin, guardian), None);
+        assert_eq!(conversation_kind(guardian, student), None);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_conversation_unread_counts(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let teacher = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let guardian = create_test_user(&pool, school_id, system_roles::GUARDIAN).await;
+        let other_student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        link_guardian(&pool, school_id, student, guardian).await;
+
+        let err = MessagingService::start_conversation(
+            &pool,
+            school_id,
+            teacher,
+            start_dto(student, None),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let err = MessagingService::start_conversation(
+            &pool,
+            school_id,
+            teacher,
+            start_dto(guardian, Some(other_student)),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let conversation = MessagingService::start_conversation(
+            &pool,
+            school_id,
+            teacher,
+            start_dto(guardian, Some(student)),
+        )
+        .await
+        .unwrap();
+        let conversation_id = conversation.conversation.id;
+        assert_eq!(
+            conversation.conversation.kind,
+            ConversationKind::TeacherGuardian
+        );
+        assert_eq!(conversation.participants.len(), 2);
+        assert_eq!(conversation.unread_count, 0);
+
+        let unread = MessagingService::get_unread_counts(&pool, guardian)
+            .await
+            .unwrap();
+        assert_eq!(unread.unread_messages, 1);
+        assert_eq!(unread.unread_conversations, 1);
+
+        MessagingService::send_message(
+            &pool,
+            conversation_id,
+            teacher,
+            SendMessageDto {
+                body: "Also, the trip form is due Friday.".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let inbox = MessagingService::get_conversations(
+            &pool,
+            guardian,
+            ConversationFilterParams {
+                kind: None,
+                pagination: PaginationParams::default(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(inbox.meta.total, 1);
+        assert_eq!(inbox.data[0].unread_count, 2);
+        assert_eq!(inbox.data[0].with_user_id, Some(teacher));
+
+        let unread = MessagingService::mark_read(&pool, conversation_id, guardian)
+            .await
+            .unwrap();
+        assert_eq!(unread.unread_messages, 0);
+
+        // Outsiders cannot see the conversation
+        let err = MessagingService::get_messages(
+            &pool,
+            conversation_id,
+            other_student,
+            PaginationParams::default(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_send_attachment(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+        let teacher = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let storage = LocalStorageBackend::new(
+            std::env::temp_dir().join(format!("chalkbyte-messaging-{}", Uuid::new_v4())),
+            "http://localhost:3000/api/storage/files".to_string(),
+            "secret",
+        );
+        let config = StorageConfig::default();
+
+        let conversation =
+            MessagingService::start_conversation(&pool, school_id, admin, start_dto(teacher, None))
+                .await
+                .unwrap();
+        let conversation_id = conversation.conversation.id;
+        assert_eq!(conversation.conversation.kind, ConversationKind::Staff);
+
+        let message = MessagingService::send_attachment(
+            &pool,
+            &storage,
+            &config,
+            conversation_id,
+            teacher,
+            None,
+            "lesson-plan.pdf",
+            "application/pdf",
+            PDF,
+        )
+        .await
+        .unwrap();
+        assert_eq!(message.attachments.len(), 1);
+        let file_id = message.attachments[0].file_id;
+
+        let messages = MessagingService::get_messages(
+            &pool,
+            conversation_id,
+            admin,
+            PaginationParams::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(messages.meta.total, 2);
+        assert_eq!(messages.data[0].id, message.id);
+        assert_eq!(messages.data[0].attachments[0].filename, "lesson-plan.pdf");
+
+        MessagingService::get_attachment_download(
+            &pool, &storage, &config, message.id, file_id, admin,
+        )
+        .await
+        .unwrap();
+
+        let outsider = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let err = MessagingService::get_attachment_download(
+            &pool, &storage, &config, message.id, file_id, outsider,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+}
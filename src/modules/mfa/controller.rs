@@ -1,15 +1,17 @@
-use chalkbyte_core::AppError;
+use chalkbyte_core::{AppError, NoContent};
 
 use crate::middleware::auth::AuthUser;
 use crate::state::AppState;
 use crate::validator::ValidatedJson;
 use axum::Json;
-use axum::extract::State;
+use axum::extract::{Path, State};
+use chalkbyte_models::ids::WebauthnCredentialId;
 use tracing::instrument;
 
 use super::model::{
-    DisableMfaRequest, EnableMfaResponse, MessageResponse, MfaStatusResponse,
-    RegenerateMfaRecoveryCodesResponse, VerifyMfaRequest,
+    DisableMfaRequest, EnableMfaResponse, FinishPasskeyRegistrationRequest, MessageResponse,
+    MfaStatusResponse, PasskeyCredential, RegenerateMfaRecoveryCodesResponse, RemovePasskeyRequest,
+    StartPasskeyRegistrationResponse, UpdatePreferredMfaMethodRequest, VerifyMfaRequest,
 };
 use super::service::MfaService;
 
@@ -138,3 +140,138 @@ pub async fn regenerate_recovery_codes(
     let response = MfaService::regenerate_recovery_codes(&state.db, user_id).await?;
     Ok(Json(response))
 }
+
+/// List registered passkeys
+#[utoipa::path(
+    get,
+    path = "/api/mfa/passkeys",
+    summary = "List passkeys",
+    responses(
+        (status = 200, description = "Registered passkeys", body = Vec<PasskeyCredential>),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "MFA",
+    security(("bearer_auth" = []))
+)]
+#[instrument]
+pub async fn list_passkeys(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<Json<Vec<PasskeyCredential>>, AppError> {
+    let user_id = uuid::Uuid::parse_str(&auth_user.0.sub)
+        .map_err(|_| AppError::unauthorized("Invalid user ID".to_string()))?;
+    let passkeys = MfaService::list_passkeys(&state.db, user_id).await?;
+    Ok(Json(passkeys))
+}
+
+/// Start registering a passkey
+#[utoipa::path(
+    post,
+    path = "/api/mfa/passkeys/register/start",
+    summary = "Start passkey registration",
+    responses(
+        (status = 200, description = "Options for navigator.credentials.create()", body = StartPasskeyRegistrationResponse),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "MFA",
+    security(("bearer_auth" = []))
+)]
+#[instrument]
+pub async fn start_passkey_registration(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<Json<StartPasskeyRegistrationResponse>, AppError> {
+    let user_id = uuid::Uuid::parse_str(&auth_user.0.sub)
+        .map_err(|_| AppError::unauthorized("Invalid user ID".to_string()))?;
+    let response = MfaService::start_passkey_registration(
+        &state.db,
+        &state.webauthn_config,
+        user_id,
+        &auth_user.0.email,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+/// Finish registering a passkey
+#[utoipa::path(
+    post,
+    path = "/api/mfa/passkeys/register/finish",
+    summary = "Finish passkey registration",
+    request_body = FinishPasskeyRegistrationRequest,
+    responses(
+        (status = 200, description = "Passkey registered", body = PasskeyCredential),
+        (status = 400, description = "Invalid credential, or registration expired"),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "MFA",
+    security(("bearer_auth" = []))
+)]
+#[instrument]
+pub async fn finish_passkey_registration(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    ValidatedJson(dto): ValidatedJson<FinishPasskeyRegistrationRequest>,
+) -> Result<Json<PasskeyCredential>, AppError> {
+    let user_id = uuid::Uuid::parse_str(&auth_user.0.sub)
+        .map_err(|_| AppError::unauthorized("Invalid user ID".to_string()))?;
+    let passkey =
+        MfaService::finish_passkey_registration(&state.db, &state.webauthn_config, user_id, dto)
+            .await?;
+    Ok(Json(passkey))
+}
+
+/// Remove a passkey with password confirmation
+#[utoipa::path(
+    delete,
+    path = "/api/mfa/passkeys/{id}",
+    summary = "Remove passkey",
+    params(("id" = Uuid, Path, description = "Passkey ID")),
+    request_body = RemovePasskeyRequest,
+    responses(
+        NoContent,
+        (status = 400, description = "Invalid password"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Passkey not found")
+    ),
+    tag = "MFA",
+    security(("bearer_auth" = []))
+)]
+#[instrument]
+pub async fn remove_passkey(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(id): Path<WebauthnCredentialId>,
+    ValidatedJson(dto): ValidatedJson<RemovePasskeyRequest>,
+) -> Result<NoContent, AppError> {
+    let user_id = uuid::Uuid::parse_str(&auth_user.0.sub)
+        .map_err(|_| AppError::unauthorized("Invalid user ID".to_string()))?;
+    MfaService::remove_passkey(&state.db, user_id, id, &dto.password).await?;
+    Ok(NoContent)
+}
+
+/// Set the preferred second factor
+#[utoipa::path(
+    put,
+    path = "/api/mfa/preferred-method",
+    summary = "Set preferred MFA method",
+    request_body = UpdatePreferredMfaMethodRequest,
+    responses(
+        (status = 200, description = "Preferred method updated", body = MfaStatusResponse),
+        (status = 400, description = "Method is not set up for this user"),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "MFA",
+    security(("bearer_auth" = []))
+)]
+#[instrument]
+pub async fn update_preferred_method(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(dto): Json<UpdatePreferredMfaMethodRequest>,
+) -> Result<Json<MfaStatusResponse>, AppError> {
+    let user_id = uuid::Uuid::parse_str(&auth_user.0.sub)
+        .map_err(|_| AppError::unauthorized("Invalid user ID".to_string()))?;
+    let status = MfaService::update_preferred_method(&state.db, user_id, dto.method).await?;
+    Ok(Json(status))
+}
@@ -1,6 +1,6 @@
 use axum::{
     Router,
-    routing::{get, post},
+    routing::{delete, get, post, put},
 };
 
 use crate::state::AppState;
@@ -17,4 +17,18 @@ pub fn init_mfa_router() -> Router<AppState> {
             "/recovery-codes/regenerate",
             post(controller::regenerate_recovery_codes),
         )
+        .route("/passkeys", get(controller::list_passkeys))
+        .route(
+            "/passkeys/register/start",
+            post(controller::start_passkey_registration),
+        )
+        .route(
+            "/passkeys/register/finish",
+            post(controller::finish_passkey_registration),
+        )
+        .route("/passkeys/{id}", delete(controller::remove_passkey))
+        .route(
+            "/preferred-method",
+            put(controller::update_preferred_method),
+        )
 }
@@ -1,16 +1,58 @@
+use std::time::Duration;
+
 use anyhow::anyhow;
+use data_encoding::BASE64URL_NOPAD;
 use rayon::prelude::*;
 use sqlx::PgPool;
+use sqlx::types::Json;
 use totp_rs::{Algorithm, Secret, TOTP};
 use tracing::instrument;
 use uuid::Uuid;
+use webauthn_rs::prelude::{
+    CredentialID, Passkey, PasskeyAuthentication, PasskeyRegistration, PublicKeyCredential,
+    RegisterPublicKeyCredential, Url, Webauthn, WebauthnBuilder,
+};
 
+use chalkbyte_cache::SessionClient;
+use chalkbyte_config::WebauthnConfig;
 use chalkbyte_core::{AppError, hash_password, verify_password};
+use chalkbyte_models::auth::MfaMethod;
+use chalkbyte_models::ids::WebauthnCredentialId;
+
+use super::model::{
+    EnableMfaResponse, FinishPasskeyRegistrationRequest, MfaStatusResponse, PasskeyCredential,
+    RegenerateMfaRecoveryCodesResponse, StartPasskeyRegistrationResponse,
+};
 
-use super::model::{EnableMfaResponse, MfaStatusResponse, RegenerateMfaRecoveryCodesResponse};
+/// How long a passkey registration may take before it must be restarted
+const PASSKEY_REGISTRATION_TTL: Duration = Duration::from_secs(300);
 
 pub struct MfaService;
 
+/// Read a second factor stored in `users.preferred_mfa_method`
+pub fn mfa_method_from_db(value: &str) -> Option<MfaMethod> {
+    match value {
+        "totp" => Some(MfaMethod::Totp),
+        "passkey" => Some(MfaMethod::Passkey),
+        _ => None,
+    }
+}
+
+/// Build the WebAuthn relying party from configuration
+fn webauthn(config: &WebauthnConfig) -> Result<Webauthn, AppError> {
+    let origin = Url::parse(&config.rp_origin)
+        .map_err(|e| AppError::internal_error(format!("Invalid WebAuthn origin: {}", e)))?;
+
+    WebauthnBuilder::new(&config.rp_id, &origin)
+        .and_then(|builder| builder.rp_name(&config.rp_name).build())
+        .map_err(|e| AppError::internal_error(format!("Invalid WebAuthn configuration: {}", e)))
+}
+
+/// Stable text form of a credential ID, used to look credentials up
+fn encode_credential_id(id: &CredentialID) -> String {
+    BASE64URL_NOPAD.encode(id.as_ref())
+}
+
 impl MfaService {
     /// Get MFA status for a user
     #[instrument(skip(db))]
@@ -18,15 +60,34 @@ impl MfaService {
         #[derive(sqlx::FromRow)]
         struct MfaStatus {
             mfa_enabled: bool,
+            preferred_mfa_method: Option<String>,
+            passkeys: i64,
+            recovery_codes_remaining: i64,
         }
 
-        let status = sqlx::query_as::<_, MfaStatus>("SELECT mfa_enabled FROM users WHERE id = $1")
-            .bind(user_id)
-            .fetch_one(db)
-            .await?;
+        let status = sqlx::query_as::<_, MfaStatus>(
+            r#"
+            SELECT u.mfa_enabled, u.preferred_mfa_method,
+                   (SELECT COUNT(*) FROM webauthn_credentials c WHERE c.user_id = u.id) AS passkeys,
+                   (SELECT COUNT(*) FROM mfa_recovery_codes r
+                    WHERE r.user_id = u.id AND r.used = FALSE) AS recovery_codes_remaining
+            FROM users u
+            WHERE u.id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(db)
+        .await?;
 
         Ok(MfaStatusResponse {
-            mfa_enabled: status.mfa_enabled,
+            mfa_enabled: status.mfa_enabled || status.passkeys > 0,
+            totp_enabled: status.mfa_enabled,
+            passkeys: status.passkeys,
+            recovery_codes_remaining: status.recovery_codes_remaining,
+            preferred_method: status
+                .preferred_mfa_method
+                .as_deref()
+                .and_then(mfa_method_from_db),
         })
     }
 
@@ -37,9 +98,9 @@ impl MfaService {
         user_id: Uuid,
         email: &str,
     ) -> Result<EnableMfaResponse, AppError> {
-        // Check if MFA is already enabled
+        // Check if TOTP is already enabled
         let status = Self::get_mfa_status(db, user_id).await?;
-        if status.mfa_enabled {
+        if status.totp_enabled {
             return Err(AppError::bad_request(anyhow!("MFA is already enabled")));
         }
 
@@ -173,14 +234,18 @@ impl MfaService {
         Self::verify_totp(&secret, code, &user.email)
     }
 
-    /// Verify recovery code for login
-    #[instrument(skip(db, code))]
+    /// Verify and consume a recovery code for login
+    ///
+    /// Returns how many unused codes are left when the code was accepted, or
+    /// `None` when it did not match an unused code.
+    #[instrument(skip(db, code, client))]
     pub async fn verify_recovery_code_login(
         db: &PgPool,
         user_id: Uuid,
         code: &str,
-    ) -> Result<bool, AppError> {
-        Self::verify_recovery_code(db, user_id, code).await
+        client: &SessionClient,
+    ) -> Result<Option<i64>, AppError> {
+        Self::verify_recovery_code(db, user_id, code, client).await
     }
 
     /// Disable MFA with password confirmation
@@ -210,18 +275,344 @@ impl MfaService {
         }
 
         // Disable MFA and clear secret
-        sqlx::query("UPDATE users SET mfa_enabled = FALSE, mfa_secret = NULL WHERE id = $1")
-            .bind(user_id)
-            .execute(db)
-            .await?;
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET mfa_enabled = FALSE,
+                mfa_secret = NULL,
+                preferred_mfa_method = NULLIF(preferred_mfa_method, 'totp')
+            WHERE id = $1
+            "#,
+        )
+        .bind(user_id)
+        .execute(db)
+        .await?;
 
-        // Delete recovery codes
-        sqlx::query("DELETE FROM mfa_recovery_codes WHERE user_id = $1")
+        Self::delete_unused_recovery_codes(db, user_id).await
+    }
+
+    /// List the user's passkeys
+    #[instrument(skip(db))]
+    pub async fn list_passkeys(
+        db: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<PasskeyCredential>, AppError> {
+        let passkeys = sqlx::query_as::<_, PasskeyCredential>(
+            r#"
+            SELECT id, name, created_at, last_used_at
+            FROM webauthn_credentials
+            WHERE user_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(passkeys)
+    }
+
+    /// Start registering a passkey, returning the options for the browser
+    #[instrument(skip(db, config))]
+    pub async fn start_passkey_registration(
+        db: &PgPool,
+        config: &WebauthnConfig,
+        user_id: Uuid,
+        email: &str,
+    ) -> Result<StartPasskeyRegistrationResponse, AppError> {
+        let webauthn = webauthn(config)?;
+
+        // Stop the same authenticator from being registered twice
+        let exclude_credentials = Self::load_passkeys(db, user_id)
+            .await?
+            .iter()
+            .map(|(_, passkey)| passkey.cred_id().clone())
+            .collect();
+
+        let (options, registration) = webauthn
+            .start_passkey_registration(user_id, email, email, Some(exclude_credentials))
+            .map_err(|e| {
+                AppError::internal_error(format!("Failed to start passkey registration: {}", e))
+            })?;
+
+        let registration_id = Self::store_ceremony(
+            db,
+            user_id,
+            "registration",
+            None,
+            &registration,
+            PASSKEY_REGISTRATION_TTL,
+        )
+        .await?;
+
+        Ok(StartPasskeyRegistrationResponse {
+            registration_id,
+            options: serde_json::to_value(&options).map_err(AppError::internal)?,
+        })
+    }
+
+    /// Verify the authenticator's response and save the new passkey
+    #[instrument(skip(db, config, dto))]
+    pub async fn finish_passkey_registration(
+        db: &PgPool,
+        config: &WebauthnConfig,
+        user_id: Uuid,
+        dto: FinishPasskeyRegistrationRequest,
+    ) -> Result<PasskeyCredential, AppError> {
+        let webauthn = webauthn(config)?;
+
+        let credential: RegisterPublicKeyCredential = serde_json::from_value(dto.credential)
+            .map_err(|e| AppError::bad_request(anyhow!("Invalid passkey credential: {}", e)))?;
+
+        // Consume the registration so its challenge can only be answered once
+        let state = sqlx::query_scalar::<_, Json<PasskeyRegistration>>(
+            r#"
+            DELETE FROM webauthn_ceremonies
+            WHERE id = $1 AND user_id = $2 AND purpose = 'registration' AND expires_at > NOW()
+            RETURNING state
+            "#,
+        )
+        .bind(dto.registration_id)
+        .bind(user_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| {
+            AppError::bad_request(anyhow!(
+                "Passkey registration not found or expired, please start again"
+            ))
+        })?;
+
+        let passkey = webauthn
+            .finish_passkey_registration(&credential, &state.0)
+            .map_err(|e| AppError::bad_request(anyhow!("Passkey registration failed: {}", e)))?;
+
+        let saved = sqlx::query_as::<_, PasskeyCredential>(
+            r#"
+            INSERT INTO webauthn_credentials (user_id, name, credential_id, passkey)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, name, created_at, last_used_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(dto.name.trim())
+        .bind(encode_credential_id(passkey.cred_id()))
+        .bind(Json(&passkey))
+        .fetch_one(db)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(db_err) = &e
+                && db_err.is_unique_violation()
+            {
+                return AppError::bad_request(anyhow!("This passkey is already registered"));
+            }
+            AppError::from(e)
+        })?;
+
+        Ok(saved)
+    }
+
+    /// Remove a passkey with password confirmation
+    #[instrument(skip(db, password))]
+    pub async fn remove_passkey(
+        db: &PgPool,
+        user_id: Uuid,
+        passkey_id: WebauthnCredentialId,
+        password: &str,
+    ) -> Result<(), AppError> {
+        let password_hash =
+            sqlx::query_scalar::<_, String>("SELECT password FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_one(db)
+                .await?;
+
+        if !verify_password(password, &password_hash)? {
+            return Err(AppError::bad_request(anyhow!("Invalid password")));
+        }
+
+        let deleted =
+            sqlx::query("DELETE FROM webauthn_credentials WHERE id = $1 AND user_id = $2")
+                .bind(passkey_id)
+                .bind(user_id)
+                .execute(db)
+                .await?;
+
+        if deleted.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow!("Passkey not found")));
+        }
+
+        // Forget the preference once the last passkey is gone
+        sqlx::query(
+            r#"
+            UPDATE users SET preferred_mfa_method = NULL
+            WHERE id = $1
+              AND preferred_mfa_method = 'passkey'
+              AND NOT EXISTS (SELECT 1 FROM webauthn_credentials WHERE user_id = $1)
+            "#,
+        )
+        .bind(user_id)
+        .execute(db)
+        .await?;
+
+        Self::delete_unused_recovery_codes(db, user_id).await
+    }
+
+    /// Set or clear the second factor offered first at login
+    #[instrument(skip(db))]
+    pub async fn update_preferred_method(
+        db: &PgPool,
+        user_id: Uuid,
+        method: Option<MfaMethod>,
+    ) -> Result<MfaStatusResponse, AppError> {
+        let status = Self::get_mfa_status(db, user_id).await?;
+
+        let value = match method {
+            None => None,
+            Some(MfaMethod::Totp) if status.totp_enabled => Some("totp"),
+            Some(MfaMethod::Passkey) if status.passkeys > 0 => Some("passkey"),
+            Some(MfaMethod::Totp) => {
+                return Err(AppError::bad_request(anyhow!(
+                    "Set up an authenticator app before preferring it"
+                )));
+            }
+            Some(MfaMethod::Passkey) => {
+                return Err(AppError::bad_request(anyhow!(
+                    "Register a passkey before preferring it"
+                )));
+            }
+            Some(MfaMethod::RecoveryCode) => {
+                return Err(AppError::bad_request(anyhow!(
+                    "Recovery codes cannot be the preferred method"
+                )));
+            }
+        };
+
+        sqlx::query("UPDATE users SET preferred_mfa_method = $1 WHERE id = $2")
+            .bind(value)
             .bind(user_id)
             .execute(db)
             .await?;
 
-        Ok(())
+        Ok(MfaStatusResponse {
+            preferred_method: method,
+            ..status
+        })
+    }
+
+    /// Whether the user has any passkeys registered
+    #[instrument(skip(db))]
+    pub async fn has_passkeys(db: &PgPool, user_id: Uuid) -> Result<bool, AppError> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM webauthn_credentials WHERE user_id = $1)",
+        )
+        .bind(user_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Issue a passkey challenge for a pending MFA login.
+    ///
+    /// Asking again replaces the previous challenge for the same login.
+    #[instrument(skip(db, config, login_handle))]
+    pub async fn start_passkey_login(
+        db: &PgPool,
+        config: &WebauthnConfig,
+        user_id: Uuid,
+        login_handle: &str,
+        ttl: Duration,
+    ) -> Result<serde_json::Value, AppError> {
+        let webauthn = webauthn(config)?;
+
+        let passkeys: Vec<Passkey> = Self::load_passkeys(db, user_id)
+            .await?
+            .into_iter()
+            .map(|(_, passkey)| passkey)
+            .collect();
+        if passkeys.is_empty() {
+            return Err(AppError::bad_request(anyhow!("No passkeys registered")));
+        }
+
+        let (options, authentication) =
+            webauthn
+                .start_passkey_authentication(&passkeys)
+                .map_err(|e| {
+                    AppError::internal_error(format!("Failed to start passkey login: {}", e))
+                })?;
+
+        Self::store_ceremony(
+            db,
+            user_id,
+            "authentication",
+            Some(login_handle),
+            &authentication,
+            ttl,
+        )
+        .await?;
+
+        serde_json::to_value(&options).map_err(AppError::internal)
+    }
+
+    /// Verify a passkey assertion for a pending MFA login.
+    ///
+    /// The challenge is consumed either way, so a failed attempt needs a new one.
+    #[instrument(skip(db, config, login_handle, credential))]
+    pub async fn verify_passkey_login(
+        db: &PgPool,
+        config: &WebauthnConfig,
+        user_id: Uuid,
+        login_handle: &str,
+        credential: serde_json::Value,
+    ) -> Result<bool, AppError> {
+        let webauthn = webauthn(config)?;
+
+        let credential: PublicKeyCredential = serde_json::from_value(credential)
+            .map_err(|e| AppError::bad_request(anyhow!("Invalid passkey credential: {}", e)))?;
+
+        let state = sqlx::query_scalar::<_, Json<PasskeyAuthentication>>(
+            r#"
+            DELETE FROM webauthn_ceremonies
+            WHERE login_handle = $1 AND user_id = $2 AND expires_at > NOW()
+            RETURNING state
+            "#,
+        )
+        .bind(login_handle)
+        .bind(user_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| {
+            AppError::bad_request(anyhow!("Request a passkey challenge before verifying"))
+        })?;
+
+        let result = match webauthn.finish_passkey_authentication(&credential, &state.0) {
+            Ok(result) => result,
+            Err(_) => return Ok(false),
+        };
+
+        // Keep the signature counter current so cloned authenticators are detected
+        let credential_id = encode_credential_id(result.cred_id());
+        let stored = sqlx::query_as::<_, (Uuid, Json<Passkey>)>(
+            "SELECT id, passkey FROM webauthn_credentials WHERE user_id = $1 AND credential_id = $2",
+        )
+        .bind(user_id)
+        .bind(&credential_id)
+        .fetch_optional(db)
+        .await?;
+
+        let Some((id, Json(mut passkey))) = stored else {
+            return Ok(false);
+        };
+        passkey.update_credential(&result);
+
+        sqlx::query(
+            "UPDATE webauthn_credentials SET passkey = $1, last_used_at = NOW() WHERE id = $2",
+        )
+        .bind(Json(&passkey))
+        .bind(id)
+        .execute(db)
+        .await?;
+
+        Ok(true)
     }
 
     /// Regenerate recovery codes
@@ -245,6 +636,72 @@ impl MfaService {
 
     // Private helper methods
 
+    /// Load the user's stored passkeys
+    async fn load_passkeys(db: &PgPool, user_id: Uuid) -> Result<Vec<(Uuid, Passkey)>, AppError> {
+        let rows = sqlx::query_as::<_, (Uuid, Json<Passkey>)>(
+            "SELECT id, passkey FROM webauthn_credentials WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, passkey)| (id, passkey.0))
+            .collect())
+    }
+
+    /// Save WebAuthn ceremony state until the authenticator responds
+    async fn store_ceremony<T: serde::Serialize>(
+        db: &PgPool,
+        user_id: Uuid,
+        purpose: &str,
+        login_handle: Option<&str>,
+        state: &T,
+        ttl: Duration,
+    ) -> Result<Uuid, AppError> {
+        // Sweep expired ceremonies so abandoned ones don't accumulate
+        sqlx::query("DELETE FROM webauthn_ceremonies WHERE expires_at <= NOW()")
+            .execute(db)
+            .await?;
+
+        let id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO webauthn_ceremonies (user_id, purpose, login_handle, state, expires_at)
+            VALUES ($1, $2, $3, $4, NOW() + make_interval(secs => $5))
+            ON CONFLICT (login_handle)
+                DO UPDATE SET state = EXCLUDED.state, expires_at = EXCLUDED.expires_at
+            RETURNING id
+            "#,
+        )
+        .bind(user_id)
+        .bind(purpose)
+        .bind(login_handle)
+        .bind(Json(state))
+        .bind(ttl.as_secs_f64())
+        .fetch_one(db)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Delete recovery codes once no second factor is left to recover
+    async fn delete_unused_recovery_codes(db: &PgPool, user_id: Uuid) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            DELETE FROM mfa_recovery_codes
+            WHERE user_id = $1
+              AND NOT EXISTS (SELECT 1 FROM users WHERE id = $1 AND mfa_enabled)
+              AND NOT EXISTS (SELECT 1 FROM webauthn_credentials WHERE user_id = $1)
+            "#,
+        )
+        .bind(user_id)
+        .execute(db)
+        .await?;
+
+        Ok(())
+    }
+
     /// Verify TOTP code
     #[instrument(skip(secret))]
     fn verify_totp(secret: &str, code: &str, email: &str) -> Result<bool, AppError> {
@@ -289,24 +746,29 @@ impl MfaService {
     }
 
     /// Store recovery codes in database (hashed)
+    ///
+    /// Replaces any existing codes in one transaction, so a failed insert
+    /// leaves the previous set usable.
     #[instrument(skip(db, codes))]
     async fn store_recovery_codes(
         db: &PgPool,
         user_id: Uuid,
         codes: &[String],
     ) -> Result<(), AppError> {
-        // Delete existing recovery codes
-        sqlx::query("DELETE FROM mfa_recovery_codes WHERE user_id = $1")
-            .bind(user_id)
-            .execute(db)
-            .await?;
-
         // Hash all codes in parallel using rayon
         let code_hashes: Vec<String> = codes
             .par_iter()
             .map(|code| hash_password(code))
             .collect::<Result<Vec<_>, _>>()?;
 
+        let mut tx = db.begin().await?;
+
+        // Delete existing recovery codes
+        sqlx::query("DELETE FROM mfa_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
         // Batch insert all recovery codes
         sqlx::query(
             r#"
@@ -316,25 +778,33 @@ impl MfaService {
         )
         .bind(user_id)
         .bind(&code_hashes)
-        .execute(db)
+        .execute(&mut *tx)
         .await?;
 
+        tx.commit().await?;
+
         Ok(())
     }
 
     /// Verify and consume a recovery code
-    #[instrument(skip(db, code))]
+    ///
+    /// Only the update that flips `used` consumes the code, so two requests
+    /// racing with the same code cannot both succeed.
+    #[instrument(skip(db, code, client))]
     async fn verify_recovery_code(
         db: &PgPool,
         user_id: Uuid,
         code: &str,
-    ) -> Result<bool, AppError> {
+        client: &SessionClient,
+    ) -> Result<Option<i64>, AppError> {
         #[derive(sqlx::FromRow)]
         struct RecoveryCode {
             id: Uuid,
             code_hash: String,
         }
 
+        let code = normalize_recovery_code(code);
+
         // Get all unused recovery codes for user
         let codes = sqlx::query_as::<_, RecoveryCode>(
             "SELECT id, code_hash FROM mfa_recovery_codes WHERE user_id = $1 AND used = FALSE",
@@ -345,19 +815,47 @@ impl MfaService {
 
         // Check each code
         for recovery_code in codes {
-            if let Ok(true) = verify_password(code, &recovery_code.code_hash) {
-                // Mark as used
-                sqlx::query(
-                    "UPDATE mfa_recovery_codes SET used = TRUE, used_at = NOW() WHERE id = $1",
+            if let Ok(true) = verify_password(&code, &recovery_code.code_hash) {
+                // Mark as used, unless another request got there first
+                let consumed = sqlx::query(
+                    r#"
+                    UPDATE mfa_recovery_codes
+                    SET used = TRUE, used_at = NOW(), used_ip_address = $2, used_user_agent = $3
+                    WHERE id = $1 AND used = FALSE
+                    "#,
                 )
                 .bind(recovery_code.id)
+                .bind(&client.ip_address)
+                .bind(&client.user_agent)
                 .execute(db)
+                .await?
+                .rows_affected()
+                    == 1;
+
+                if !consumed {
+                    return Ok(None);
+                }
+
+                let remaining: i64 = sqlx::query_scalar(
+                    "SELECT COUNT(*) FROM mfa_recovery_codes WHERE user_id = $1 AND used = FALSE",
+                )
+                .bind(user_id)
+                .fetch_one(db)
                 .await?;
 
-                return Ok(true);
+                return Ok(Some(remaining));
             }
         }
 
-        Ok(false)
+        Ok(None)
     }
 }
+
+/// Codes are issued as uppercase letters and digits; accept them typed in
+/// lowercase or split up with spaces or dashes.
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
@@ -17,13 +17,60 @@
 //! - [`auth`] - Authentication (login, logout, token refresh, password reset)
 //! - [`users`] - User management and profile operations
 //! - [`schools`] - School CRUD operations
+//! - [`groups`] - School groups (districts) with group admins and aggregate reports
+//! - [`dashboard`] - One-call school dashboard with independently cached widgets
 //! - [`roles`] - Role and permission management
+//! - [`announcements`] - Announcements to a school audience with an in-app notification feed
+//! - [`broadcasts`] - Templated email/SMS broadcasts to a filtered school audience
+//! - [`messaging`] - Teacher-guardian and staff conversations with unread counts and attachments
+//! - [`changes`] - Ordered data change feed for incremental sync by integrations
+//! - [`public_directory`] - Opt-in public school profiles for the admissions page
+//! - [`email_branding`] - Per-school email sender name, logo, and colors with email previews
+//! - [`emails`] - Outbound email queue with retries, bounce suppression, and admin delivery status
+//! - [`saved_views`] - Per-user saved list filters, shareable within a school
+//! - [`profiles`] - Linked accounts, profile switching, and per-profile notification preferences
+//! - [`trash`] - Per-school recycle bin for deleted students, users, and branches
+//! - [`retention`] - Per-school retention periods with a scheduled purge job and purge audit trail
+//! - [`traces`] - Request log summaries and the admin request trace lookup
+//! - [`jobs`] - Background job run tracking and the admin job health check
+//! - [`performance`] - Slow request statistics for system admins
+//! - [`feature_flags`] - Runtime feature flags with per-school overrides
+//! - [`webhooks`] - Signed webhook deliveries of domain events to school endpoints
 //!
 //! ## Education Modules
 //!
 //! - [`levels`] - Educational levels (e.g., Grade 1, Grade 2)
 //! - [`branches`] - School branches or departments
 //! - [`students`] - Student-specific operations
+//! - [`imports`] - Dry-run validation of levels, students, and guardians import files
+//! - [`interop`] - OneRoster 1.1 CSV roster export and import
+//! - [`sync`] - Scheduled LMS roster sync with Google Classroom and conflict reporting
+//! - [`guardians`] - Guardian accounts created from student emergency contacts
+//! - [`reports`] - Session summary reports generated for board reporting
+//! - [`student_cards`] - Signed student ID cards with QR codes and scan verification
+//! - [`custom_fields`] - Per-school custom fields on students and users
+//! - [`billing`] - Fee structures, invoices, payments, balances, and arrears exports
+//! - [`boarding`] - Hostels, room allocation, and boarding fees
+//! - [`alumni`] - Graduation into alumni records and alumni mailing exports
+//! - [`clinic`] - Health-room visits, medication, and student medical profiles
+//! - [`discipline`] - Behaviour incidents, guardian notifications, and incident trends
+//! - [`library`] - Library catalog, lending, overdue fines, and borrowing history
+//! - [`attendance`] - Daily student attendance per branch
+//! - [`assessments`] - Exams, quizzes, and assignments, marks entry, and weighted term grades
+//! - [`exams`] - Exam seating plans with printable room lists
+//! - [`results`] - Term result moderation, publishing, and locking
+//!
+//! ## Staff Modules
+//!
+//! - [`staff_leave`] - Staff leave requests, approvals, and substitute hints
+//!
+//! ## Operations Modules
+//!
+//! - [`assets`] - Inventory/asset register with assignments and condition history
+//! - [`transport`] - Bus routes, stops, student assignments, and driver manifests
+//! - [`visitor_log`] - Visitor and gate log with kiosk check-in and daily reports
+//! - [`kiosk`] - Shared attendance devices unlocked with a staff PIN
+//! - [`storage`] - Avatars and student documents in local or S3 storage, with school quotas
 //!
 //! ## Security Modules
 //!
@@ -86,12 +133,53 @@
 //! ```
 
 pub mod academic_sessions;
+pub mod alumni;
+pub mod announcements;
+pub mod assessments;
+pub mod assets;
+pub mod attendance;
 pub mod auth;
+pub mod billing;
+pub mod boarding;
 pub mod branches;
+pub mod broadcasts;
+pub mod changes;
+pub mod clinic;
+pub mod custom_fields;
+pub mod dashboard;
+pub mod discipline;
+pub mod email_branding;
+pub mod emails;
+pub mod exams;
+pub mod feature_flags;
+pub mod groups;
+pub mod guardians;
+pub mod imports;
+pub mod interop;
+pub mod jobs;
+pub mod kiosk;
 pub mod levels;
+pub mod library;
+pub mod messaging;
 pub mod mfa;
+pub mod performance;
+pub mod profiles;
+pub mod public_directory;
+pub mod reports;
+pub mod results;
+pub mod retention;
 pub mod roles;
+pub mod saved_views;
 pub mod schools;
+pub mod staff_leave;
+pub mod storage;
+pub mod student_cards;
 pub mod students;
+pub mod sync;
 pub mod terms;
+pub mod traces;
+pub mod transport;
+pub mod trash;
 pub mod users;
+pub mod visitor_log;
+pub mod webhooks;
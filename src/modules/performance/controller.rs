@@ -0,0 +1,30 @@
+use axum::{Json, extract::Query};
+use tracing::instrument;
+
+use crate::modules::performance::model::{SlowEndpointParams, SlowEndpointsResponse};
+use crate::modules::performance::service::PerformanceService;
+
+/// Slowest endpoints
+///
+/// Lists the endpoints whose requests exceeded the slow request threshold
+/// most often since the server started, with their worst and average
+/// latency. Counts are per server instance. System admins only.
+#[utoipa::path(
+    get,
+    path = "/api/admin/performance/slow-endpoints",
+    summary = "Slowest endpoints",
+    params(SlowEndpointParams),
+    responses(
+        (status = 200, description = "Most frequently slow endpoints", body = SlowEndpointsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only")
+    ),
+    tag = "Performance",
+    security(("bearer_auth" = []))
+)]
+#[instrument]
+pub async fn get_slow_endpoints(
+    Query(params): Query<SlowEndpointParams>,
+) -> Json<SlowEndpointsResponse> {
+    Json(PerformanceService::slow_endpoints(params.limit() as usize))
+}
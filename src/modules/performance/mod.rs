@@ -0,0 +1,14 @@
+//! Slow request statistics module.
+//!
+//! Requests slower than `SLOW_REQUEST_MS` and queries slower than
+//! `SLOW_QUERY_MS` are logged as warnings by
+//! [`chalkbyte_observability::slow`], which also counts slow requests per
+//! endpoint.
+//!
+//! `GET /api/admin/performance/slow-endpoints` lets system admins see the
+//! endpoints that were slow most often since the server started.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
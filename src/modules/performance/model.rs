@@ -0,0 +1,7 @@
+//! Slow endpoint data models.
+//!
+//! This module re-exports slow endpoint models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all slow endpoint models from the shared crate
+pub use chalkbyte_models::performance::*;
@@ -0,0 +1,11 @@
+use axum::{Router, routing::get};
+
+use crate::state::AppState;
+
+use super::controller::get_slow_endpoints;
+
+/// Initialize the slow request statistics router
+/// Routes: GET /slow-endpoints
+pub fn init_performance_router() -> Router<AppState> {
+    Router::new().route("/slow-endpoints", get(get_slow_endpoints))
+}
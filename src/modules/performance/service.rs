@@ -0,0 +1,29 @@
+use chalkbyte_observability::slow;
+
+use crate::modules::performance::model::{SlowEndpoint, SlowEndpointsResponse};
+
+pub struct PerformanceService;
+
+impl PerformanceService {
+    /// Up to `limit` endpoints that were slow most often in this process.
+    pub fn slow_endpoints(limit: usize) -> SlowEndpointsResponse {
+        SlowEndpointsResponse {
+            threshold_ms: slow::slow_request_threshold().map(|t| t.as_millis() as u64),
+            endpoints: slow::slowest_endpoints(limit)
+                .into_iter()
+                .map(Self::slow_endpoint)
+                .collect(),
+        }
+    }
+
+    fn slow_endpoint(endpoint: slow::SlowEndpoint) -> SlowEndpoint {
+        SlowEndpoint {
+            method: endpoint.method,
+            route: endpoint.route,
+            slow_count: endpoint.count,
+            max_ms: endpoint.max.as_millis() as u64,
+            avg_ms: (endpoint.total.as_millis() / u128::from(endpoint.count.max(1))) as u64,
+            last_seen_at: endpoint.last_seen_at,
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::UserId;
+
+use crate::middleware::auth::{AuthUser, RequireUsersRead, RequireUsersUpdate};
+use crate::middleware::client::RequestClient;
+use crate::modules::auth::model::LoginResponse;
+use crate::modules::auth::service::AuthService;
+use crate::modules::profiles::model::{
+    LinkProfileDto, LinkedProfile, MyProfilesResponse, NotificationPreferences, SwitchProfileDto,
+    UpdateNotificationPreferencesDto,
+};
+use crate::modules::profiles::service::ProfileService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::get_optional_school_id_for_resource_operation;
+use crate::validator::ValidatedJson;
+
+/// List the accounts linked to a user
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/linked-profiles",
+    summary = "List linked profiles",
+    params(
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Linked accounts ordered by name", body = Vec<LinkedProfile>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires unscoped users:read permission"),
+        (status = 404, description = "User not found")
+    ),
+    tag = "Profiles",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_linked_profiles(
+    State(state): State<AppState>,
+    RequireUsersRead(auth_user, scope): RequireUsersRead,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<Vec<LinkedProfile>>, AppError> {
+    scope.ensure_school()?;
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+
+    let profiles =
+        ProfileService::get_linked_profiles(&state.db, UserId::from(user_id), school_id).await?;
+
+    Ok(Json(profiles))
+}
+
+/// Link another account in the same school to a user
+///
+/// The link works both ways: either account can switch to the other.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/linked-profiles",
+    summary = "Link profile",
+    params(
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    request_body = LinkProfileDto,
+    responses(
+        Created<LinkedProfile>,
+        (status = 400, description = "Same account, different schools, or already linked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires unscoped users:update permission"),
+        (status = 404, description = "User not found")
+    ),
+    tag = "Profiles",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn link_profile(
+    State(state): State<AppState>,
+    RequireUsersUpdate(auth_user, scope): RequireUsersUpdate,
+    Path(user_id): Path<Uuid>,
+    Json(dto): Json<LinkProfileDto>,
+) -> Result<Created<LinkedProfile>, AppError> {
+    scope.ensure_school()?;
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+
+    let profile = ProfileService::link(
+        &state.db,
+        UserId::from(user_id),
+        dto.linked_user_id,
+        school_id,
+        auth_user.user_id()?,
+    )
+    .await?;
+
+    Ok(Created(profile))
+}
+
+/// Unlink two accounts
+///
+/// Sessions already switched to the other account end at their next refresh.
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}/linked-profiles/{linked_user_id}",
+    summary = "Unlink profile",
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("linked_user_id" = Uuid, Path, description = "Linked account's user ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires unscoped users:update permission"),
+        (status = 404, description = "User or link not found")
+    ),
+    tag = "Profiles",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn unlink_profile(
+    State(state): State<AppState>,
+    RequireUsersUpdate(auth_user, scope): RequireUsersUpdate,
+    Path((user_id, linked_user_id)): Path<(Uuid, Uuid)>,
+) -> Result<NoContent, AppError> {
+    scope.ensure_school()?;
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+
+    ProfileService::unlink(
+        &state.db,
+        UserId::from(user_id),
+        UserId::from(linked_user_id),
+        school_id,
+    )
+    .await?;
+
+    Ok(NoContent)
+}
+
+/// List the profiles you can switch to
+#[utoipa::path(
+    get,
+    path = "/api/me/profiles",
+    summary = "List my profiles",
+    responses(
+        (status = 200, description = "The account you signed in with, then its linked accounts", body = MyProfilesResponse),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Profiles",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_my_profiles(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<Json<MyProfilesResponse>, AppError> {
+    let profiles =
+        ProfileService::get_profiles(&state.db, auth_user.login_id()?, auth_user.user_id()?)
+            .await?;
+
+    Ok(Json(profiles))
+}
+
+/// Get the active profile's notification preferences
+#[utoipa::path(
+    get,
+    path = "/api/me/notification-preferences",
+    summary = "Get notification preferences",
+    responses(
+        (status = 200, description = "Notification preferences", body = NotificationPreferences),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Profiles",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_notification_preferences(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<Json<NotificationPreferences>, AppError> {
+    let preferences =
+        ProfileService::get_notification_preferences(&state.db, auth_user.user_id()?).await?;
+
+    Ok(Json(preferences))
+}
+
+/// Update the active profile's notification preferences
+#[utoipa::path(
+    put,
+    path = "/api/me/notification-preferences",
+    summary = "Update notification preferences",
+    request_body = UpdateNotificationPreferencesDto,
+    responses(
+        (status = 200, description = "Notification preferences updated", body = NotificationPreferences),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Profiles",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_notification_preferences(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(dto): Json<UpdateNotificationPreferencesDto>,
+) -> Result<Json<NotificationPreferences>, AppError> {
+    let preferences =
+        ProfileService::update_notification_preferences(&state.db, auth_user.user_id()?, dto)
+            .await?;
+
+    Ok(Json(preferences))
+}
+
+/// Switch to a linked profile
+///
+/// Returns tokens for the profile, with its own roles and scope. Switch to
+/// the account you signed in with to stop acting as a linked profile.
+#[utoipa::path(
+    post,
+    path = "/api/auth/switch-profile",
+    summary = "Switch profile",
+    request_body = SwitchProfileDto,
+    responses(
+        (status = 200, description = "Tokens for the profile", body = LoginResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Profile is not linked to your account")
+    ),
+    tag = "Profiles",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn switch_profile(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    RequestClient(client): RequestClient,
+    ValidatedJson(dto): ValidatedJson<SwitchProfileDto>,
+) -> Result<Json<LoginResponse>, AppError> {
+    let response = AuthService::switch_profile(
+        &state.db,
+        state.token_store.as_ref(),
+        auth_user.login_id()?,
+        dto.user_id,
+        dto.device_id,
+        client,
+        &state.jwt_config,
+    )
+    .await?;
+
+    Ok(Json(response))
+}
@@ -0,0 +1,13 @@
+//! Linked profiles module.
+//!
+//! This module lets one person use several accounts at a school, such as a
+//! teacher account and a guardian account, from one login. School admins
+//! link accounts under `/api/users/{user_id}/linked-profiles`; the person
+//! lists them at `/api/me/profiles` and switches with
+//! `POST /api/auth/switch-profile`. Each profile keeps its own roles, scope,
+//! and notification preferences (`/api/me/notification-preferences`).
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Linked profile data models and DTOs.
+//!
+//! This module re-exports linked profile models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all linked profile models from the shared crate
+pub use chalkbyte_models::profiles::*;
@@ -0,0 +1,36 @@
+use axum::{
+    Router,
+    routing::{delete, get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    get_linked_profiles, get_my_profiles, get_notification_preferences, link_profile,
+    switch_profile, unlink_profile, update_notification_preferences,
+};
+
+/// Initialize the linked profiles router, nested under `/users/{user_id}/linked-profiles`
+/// Routes: GET /, POST /, DELETE /{linked_user_id}
+pub fn init_linked_profiles_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_linked_profiles).post(link_profile))
+        .route("/{linked_user_id}", delete(unlink_profile))
+}
+
+/// Initialize the signed-in user's profiles router, nested under `/me`
+/// Routes: GET /profiles, GET /notification-preferences, PUT /notification-preferences
+pub fn init_my_profiles_router() -> Router<AppState> {
+    Router::new()
+        .route("/profiles", get(get_my_profiles))
+        .route(
+            "/notification-preferences",
+            get(get_notification_preferences).put(update_notification_preferences),
+        )
+}
+
+/// Initialize the profile switch router, merged into the auth router
+/// Routes: POST /switch-profile
+pub fn init_switch_profile_router() -> Router<AppState> {
+    Router::new().route("/switch-profile", post(switch_profile))
+}
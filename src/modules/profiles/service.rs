@@ -0,0 +1,366 @@
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, ErrorCode};
+use chalkbyte_models::ids::{SchoolId, UserId};
+
+use crate::modules::profiles::model::{
+    LinkedProfile, MyProfilesResponse, NotificationPreferences, UpdateNotificationPreferencesDto,
+};
+
+/// Columns of a [`LinkedProfile`] for a query joining `users u`, without `linked_at`
+const PROFILE_COLUMNS: &str = "u.id AS user_id, u.first_name, u.last_name, u.email, u.school_id, \
+     ARRAY(SELECT r.name FROM user_roles ur JOIN roles r ON r.id = ur.role_id \
+           WHERE ur.user_id = u.id ORDER BY r.name) AS roles";
+
+/// Order a pair the way `linked_profiles` stores it
+fn ordered(a: UserId, b: UserId) -> (UserId, UserId) {
+    if a.into_inner() < b.into_inner() {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+pub struct ProfileService;
+
+impl ProfileService {
+    /// Find a user's school, limited to `school_id` unless it is `None`.
+    async fn find_user_school(
+        db: &PgPool,
+        user_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Option<SchoolId>, AppError> {
+        sqlx::query_scalar::<_, Option<SchoolId>>(
+            "SELECT school_id FROM users WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(user_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::UserNotFound))
+    }
+
+    /// Whether two accounts are linked to each other.
+    #[instrument(skip(db))]
+    pub async fn is_linked(db: &PgPool, a: UserId, b: UserId) -> Result<bool, AppError> {
+        let (user_id, linked_user_id) = ordered(a, b);
+
+        let linked = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM linked_profiles WHERE user_id = $1 AND linked_user_id = $2)",
+        )
+        .bind(user_id)
+        .bind(linked_user_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(linked)
+    }
+
+    /// Link two accounts in the same school and return the linked profile.
+    ///
+    /// `school_id` limits both accounts to the admin's school; `None` is for
+    /// system admins.
+    #[instrument(skip(db))]
+    pub async fn link(
+        db: &PgPool,
+        user_id: UserId,
+        linked_user_id: UserId,
+        school_id: Option<SchoolId>,
+        linked_by: UserId,
+    ) -> Result<LinkedProfile, AppError> {
+        if user_id == linked_user_id {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "An account cannot be linked to itself"
+            )));
+        }
+
+        let user_school = Self::find_user_school(db, user_id, school_id).await?;
+        let linked_school = Self::find_user_school(db, linked_user_id, school_id).await?;
+
+        if user_school.is_none() || user_school != linked_school {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only accounts in the same school can be linked"
+            )));
+        }
+
+        let (first, second) = ordered(user_id, linked_user_id);
+
+        sqlx::query(
+            "INSERT INTO linked_profiles (user_id, linked_user_id, linked_by) VALUES ($1, $2, $3)",
+        )
+        .bind(first)
+        .bind(second)
+        .bind(linked_by)
+        .execute(db)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(db_err) = &e
+                && db_err.is_unique_violation()
+            {
+                return AppError::bad_request(anyhow::anyhow!("The accounts are already linked"));
+            }
+            AppError::from(e)
+        })?;
+
+        let profile = Self::get_linked_profiles(db, user_id, school_id)
+            .await?
+            .into_iter()
+            .find(|p| p.user_id == linked_user_id)
+            .ok_or_else(|| AppError::from_code(ErrorCode::UserNotFound))?;
+
+        Ok(profile)
+    }
+
+    /// Remove the link between two accounts.
+    ///
+    /// Sessions already switched to the profile end at their next refresh.
+    #[instrument(skip(db))]
+    pub async fn unlink(
+        db: &PgPool,
+        user_id: UserId,
+        linked_user_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        Self::find_user_school(db, user_id, school_id).await?;
+
+        let (first, second) = ordered(user_id, linked_user_id);
+
+        let result =
+            sqlx::query("DELETE FROM linked_profiles WHERE user_id = $1 AND linked_user_id = $2")
+                .bind(first)
+                .bind(second)
+                .execute(db)
+                .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!("Link not found")));
+        }
+
+        Ok(())
+    }
+
+    /// List the accounts linked to a user, ordered by name.
+    #[instrument(skip(db))]
+    pub async fn get_linked_profiles(
+        db: &PgPool,
+        user_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Vec<LinkedProfile>, AppError> {
+        Self::find_user_school(db, user_id, school_id).await?;
+
+        let profiles = sqlx::query_as::<_, LinkedProfile>(&format!(
+            "SELECT {PROFILE_COLUMNS}, lp.created_at AS linked_at
+             FROM linked_profiles lp
+             JOIN users u ON u.id = CASE WHEN lp.user_id = $1
+                                         THEN lp.linked_user_id ELSE lp.user_id END
+             WHERE $1 IN (lp.user_id, lp.linked_user_id)
+             ORDER BY u.last_name, u.first_name, u.id"
+        ))
+        .bind(user_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(profiles)
+    }
+
+    /// List the profiles a login can act as: itself, then its linked accounts.
+    #[instrument(skip(db))]
+    pub async fn get_profiles(
+        db: &PgPool,
+        login_id: UserId,
+        active_profile_id: UserId,
+    ) -> Result<MyProfilesResponse, AppError> {
+        let login = sqlx::query_as::<_, LinkedProfile>(&format!(
+            "SELECT {PROFILE_COLUMNS}, NULL::timestamptz AS linked_at FROM users u WHERE u.id = $1"
+        ))
+        .bind(login_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::UserNotFound))?;
+
+        let mut profiles = vec![login];
+        profiles.extend(Self::get_linked_profiles(db, login_id, None).await?);
+
+        Ok(MyProfilesResponse {
+            active_profile_id,
+            profiles,
+        })
+    }
+
+    /// Get a profile's notification preferences, or the defaults if it has none.
+    #[instrument(skip(db))]
+    pub async fn get_notification_preferences(
+        db: &PgPool,
+        user_id: UserId,
+    ) -> Result<NotificationPreferences, AppError> {
+        let preferences = sqlx::query_as::<_, NotificationPreferences>(
+            "SELECT announcement_emails, updated_at FROM notification_preferences WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(db)
+        .await?
+        .unwrap_or(NotificationPreferences {
+            announcement_emails: true,
+            updated_at: None,
+        });
+
+        Ok(preferences)
+    }
+
+    /// Update a profile's notification preferences.
+    #[instrument(skip(db))]
+    pub async fn update_notification_preferences(
+        db: &PgPool,
+        user_id: UserId,
+        dto: UpdateNotificationPreferencesDto,
+    ) -> Result<NotificationPreferences, AppError> {
+        let preferences = sqlx::query_as::<_, NotificationPreferences>(
+            r#"INSERT INTO notification_preferences (user_id, announcement_emails)
+               VALUES ($1, COALESCE($2, TRUE))
+               ON CONFLICT (user_id) DO UPDATE SET
+                   announcement_emails = COALESCE($2, notification_preferences.announcement_emails),
+                   updated_at = NOW()
+               RETURNING announcement_emails, updated_at"#,
+        )
+        .bind(user_id)
+        .bind(dto.announcement_emails)
+        .fetch_one(db)
+        .await?;
+
+        Ok(preferences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: Option<SchoolId>) -> UserId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Test', 'User', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.map(SchoolId::into_inner)
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_link_is_symmetric_and_listed_for_both_accounts(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin = create_test_user(&pool, Some(school_id)).await;
+        let teacher = create_test_user(&pool, Some(school_id)).await;
+        let guardian = create_test_user(&pool, Some(school_id)).await;
+
+        let linked = ProfileService::link(&pool, guardian, teacher, Some(school_id), admin)
+            .await
+            .unwrap();
+        assert_eq!(linked.user_id, teacher);
+        assert!(linked.linked_at.is_some());
+
+        assert!(
+            ProfileService::is_linked(&pool, teacher, guardian)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !ProfileService::is_linked(&pool, teacher, admin)
+                .await
+                .unwrap()
+        );
+
+        let mine = ProfileService::get_profiles(&pool, teacher, teacher)
+            .await
+            .unwrap();
+        let ids: Vec<UserId> = mine.profiles.iter().map(|p| p.user_id).collect();
+        assert_eq!(ids, vec![teacher, guardian]);
+        assert!(mine.profiles[0].linked_at.is_none());
+
+        let err = ProfileService::link(&pool, teacher, guardian, None, admin)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        ProfileService::unlink(&pool, teacher, guardian, Some(school_id))
+            .await
+            .unwrap();
+        assert!(
+            !ProfileService::is_linked(&pool, guardian, teacher)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_link_requires_same_school(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let other_school_id = create_test_school(&pool).await;
+        let admin = create_test_user(&pool, Some(school_id)).await;
+        let teacher = create_test_user(&pool, Some(school_id)).await;
+        let outsider = create_test_user(&pool, Some(other_school_id)).await;
+
+        let err = ProfileService::link(&pool, teacher, outsider, None, admin)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let err = ProfileService::link(&pool, teacher, outsider, Some(school_id), admin)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let err = ProfileService::link(&pool, teacher, teacher, Some(school_id), admin)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_notification_preferences_default_and_update(pool: PgPool) {
+        let user = create_test_user(&pool, None).await;
+
+        let defaults = ProfileService::get_notification_preferences(&pool, user)
+            .await
+            .unwrap();
+        assert!(defaults.announcement_emails);
+        assert!(defaults.updated_at.is_none());
+
+        let updated = ProfileService::update_notification_preferences(
+            &pool,
+            user,
+            UpdateNotificationPreferencesDto {
+                announcement_emails: Some(false),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!updated.announcement_emails);
+
+        let unchanged = ProfileService::update_notification_preferences(
+            &pool,
+            user,
+            UpdateNotificationPreferencesDto::default(),
+        )
+        .await
+        .unwrap();
+        assert!(!unchanged.announcement_emails);
+    }
+}
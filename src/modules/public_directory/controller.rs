@@ -0,0 +1,123 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::SchoolId;
+
+use crate::middleware::auth::{RequireSchoolsRead, RequireSchoolsUpdate};
+use crate::modules::public_directory::model::{
+    PublicProfileSettings, PublicSchoolProfile, UpdatePublicProfileDto,
+};
+use crate::modules::public_directory::service::PublicDirectoryService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::verify_school_access;
+use crate::validator::ValidatedJson;
+
+/// Get a school's public profile
+///
+/// Unauthenticated. Only schools that have enabled their public profile are
+/// found. Responses are publicly cacheable.
+#[utoipa::path(
+    get,
+    path = "/api/public/schools/{slug}",
+    summary = "Get public school profile",
+    params(
+        ("slug" = String, Path, description = "School slug")
+    ),
+    responses(
+        (status = 200, description = "Public school profile", body = PublicSchoolProfile),
+        (status = 404, description = "No listed school with this slug"),
+        (status = 429, description = "Too many requests")
+    ),
+    tag = "Public Directory"
+)]
+#[instrument(skip(state))]
+pub async fn get_public_school_profile(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<Json<PublicSchoolProfile>, AppError> {
+    let profile = PublicDirectoryService::get_public_profile(
+        &state.db,
+        state.cache.as_ref(),
+        state.file_storage.as_ref(),
+        &slug,
+    )
+    .await?;
+
+    Ok(Json(profile))
+}
+
+/// Get a school's public profile settings
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/public-profile",
+    summary = "Get public profile settings",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    responses(
+        (status = 200, description = "Public profile settings", body = PublicProfileSettings),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:read permission for this school"),
+        (status = 404, description = "School not found")
+    ),
+    tag = "Public Directory",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_public_profile_settings(
+    State(state): State<AppState>,
+    RequireSchoolsRead(auth_user): RequireSchoolsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PublicProfileSettings>, AppError> {
+    let school_id = SchoolId::from(id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let settings = PublicDirectoryService::get_settings(&state.db, school_id).await?;
+
+    Ok(Json(settings))
+}
+
+/// Update a school's public profile settings
+///
+/// Enabling the public profile requires a slug. Changes show up on the
+/// public endpoint immediately, though browsers and CDNs may serve the old
+/// profile until their cached copy expires.
+#[utoipa::path(
+    put,
+    path = "/api/schools/{id}/public-profile",
+    summary = "Update public profile settings",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = UpdatePublicProfileDto,
+    responses(
+        (status = 200, description = "Public profile settings updated", body = PublicProfileSettings),
+        (status = 400, description = "Invalid or taken slug, or enabling without a slug"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:update permission for this school"),
+        (status = 404, description = "School not found")
+    ),
+    tag = "Public Directory",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_public_profile_settings(
+    State(state): State<AppState>,
+    RequireSchoolsUpdate(auth_user): RequireSchoolsUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdatePublicProfileDto>,
+) -> Result<Json<PublicProfileSettings>, AppError> {
+    let school_id = SchoolId::from(id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let settings =
+        PublicDirectoryService::update_settings(&state.db, state.cache.as_ref(), school_id, dto)
+            .await?;
+
+    Ok(Json(settings))
+}
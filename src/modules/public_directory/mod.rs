@@ -0,0 +1,12 @@
+//! Public school directory module.
+//!
+//! Serves the opt-in public profile of a school by slug, without
+//! authentication, for the public admissions page. School admins choose the
+//! slug, whether the school is listed, and whether admissions are open.
+//! Public responses are cached in Redis and by browsers and CDNs, and the
+//! public routes have their own stricter per-IP rate limit.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
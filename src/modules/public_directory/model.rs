@@ -0,0 +1,7 @@
+//! Public school directory data models and DTOs.
+//!
+//! This module re-exports public directory models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all public directory models from the shared crate
+pub use chalkbyte_models::public_directory::*;
@@ -0,0 +1,22 @@
+use axum::{Router, routing::get};
+
+use crate::state::AppState;
+
+use super::controller::{
+    get_public_profile_settings, get_public_school_profile, update_public_profile_settings,
+};
+
+/// Initialize the public school directory router (no authentication)
+/// Routes: GET /{slug}
+pub fn init_public_directory_router() -> Router<AppState> {
+    Router::new().route("/{slug}", get(get_public_school_profile))
+}
+
+/// Initialize the public profile settings router, merged into the schools router
+/// Routes: GET /{id}/public-profile, PUT /{id}/public-profile
+pub fn init_public_profile_settings_router() -> Router<AppState> {
+    Router::new().route(
+        "/{id}/public-profile",
+        get(get_public_profile_settings).put(update_public_profile_settings),
+    )
+}
@@ -0,0 +1,235 @@
+use sqlx::{FromRow, PgPool};
+use tracing::{instrument, warn};
+
+use chalkbyte_cache::{RedisCache, invalidate, keys};
+use chalkbyte_core::{AppError, ErrorCode, FileStorage};
+use chalkbyte_models::ids::SchoolId;
+
+use crate::modules::public_directory::model::{
+    PublicProfileSettings, PublicSchoolProfile, UpdatePublicProfileDto,
+};
+
+const SETTINGS_COLUMNS: &str = "id AS school_id, slug, public_profile_enabled, admissions_open";
+
+#[derive(FromRow)]
+struct PublicSchoolRow {
+    name: String,
+    slug: String,
+    address: Option<String>,
+    logo_path: Option<String>,
+    admissions_open: bool,
+}
+
+fn settings_error(e: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e {
+        if db_err.is_unique_violation() {
+            return AppError::bad_request(anyhow::anyhow!("This slug is already taken"));
+        }
+        if db_err.constraint() == Some("public_profile_requires_slug") {
+            return AppError::bad_request(anyhow::anyhow!(
+                "Set a slug before enabling the public profile"
+            ));
+        }
+    }
+    AppError::from(e)
+}
+
+pub struct PublicDirectoryService;
+
+impl PublicDirectoryService {
+    /// Get the public profile of a listed school. Unlisted schools are
+    /// reported as not found.
+    #[instrument(skip(db, cache, file_storage))]
+    pub async fn get_public_profile(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        file_storage: &dyn FileStorage,
+        slug: &str,
+    ) -> Result<PublicSchoolProfile, AppError> {
+        let cache_key = keys::schools::public_profile(slug);
+
+        if let Some(cache) = cache
+            && let Some(profile) = cache.get::<PublicSchoolProfile>(&cache_key).await
+        {
+            return Ok(profile);
+        }
+
+        let row = sqlx::query_as::<_, PublicSchoolRow>(
+            r#"SELECT name, slug, address, logo_path, admissions_open
+               FROM schools
+               WHERE slug = $1 AND public_profile_enabled"#,
+        )
+        .bind(slug)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))?;
+
+        let profile = PublicSchoolProfile {
+            name: row.name,
+            slug: row.slug,
+            address: row.address,
+            logo_url: row
+                .logo_path
+                .and_then(|path| file_storage.get_url(&path).ok()),
+            admissions_open: row.admissions_open,
+        };
+
+        if let Some(cache) = cache
+            && let Err(e) = cache.set(&cache_key, &profile).await
+        {
+            warn!(error = %e, "Failed to cache public school profile");
+        }
+
+        Ok(profile)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_settings(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<PublicProfileSettings, AppError> {
+        sqlx::query_as::<_, PublicProfileSettings>(&format!(
+            "SELECT {SETTINGS_COLUMNS} FROM schools WHERE id = $1"
+        ))
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))
+    }
+
+    #[instrument(skip(db, cache))]
+    pub async fn update_settings(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        dto: UpdatePublicProfileDto,
+    ) -> Result<PublicProfileSettings, AppError> {
+        let old_slug = Self::get_settings(db, school_id).await?.slug;
+
+        let settings = sqlx::query_as::<_, PublicProfileSettings>(&format!(
+            "UPDATE schools
+             SET slug = COALESCE($2, slug),
+                 public_profile_enabled = COALESCE($3, public_profile_enabled),
+                 admissions_open = COALESCE($4, admissions_open),
+                 updated_at = NOW()
+             WHERE id = $1
+             RETURNING {SETTINGS_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.slug)
+        .bind(dto.public_profile_enabled)
+        .bind(dto.admissions_open)
+        .fetch_one(db)
+        .await
+        .map_err(settings_error)?;
+
+        // Clears the public profile under both the old and the new slug
+        invalidate::school(cache, Some(school_id.into_inner())).await;
+        if let (Some(cache), Some(old_slug)) = (cache, old_slug)
+            && let Err(e) = cache
+                .invalidate(&keys::schools::public_profile(&old_slug))
+                .await
+        {
+            warn!(error = %e, "Failed to invalidate public school profile cache");
+        }
+
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chalkbyte_core::LocalFileStorage;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, '1 School Road') RETURNING id"#,
+            format!("School {}", Uuid::new_v4())
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    fn storage() -> LocalFileStorage {
+        LocalFileStorage::new(PathBuf::from("./test_uploads"), "/files".to_string())
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_public_profile_requires_opt_in(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+
+        let enable_without_slug = PublicDirectoryService::update_settings(
+            &pool,
+            None,
+            school_id,
+            UpdatePublicProfileDto {
+                slug: None,
+                public_profile_enabled: Some(true),
+                admissions_open: None,
+            },
+        )
+        .await;
+        assert!(enable_without_slug.is_err());
+
+        PublicDirectoryService::update_settings(
+            &pool,
+            None,
+            school_id,
+            UpdatePublicProfileDto {
+                slug: Some("greenfield".to_string()),
+                public_profile_enabled: None,
+                admissions_open: Some(true),
+            },
+        )
+        .await
+        .unwrap();
+
+        let unlisted =
+            PublicDirectoryService::get_public_profile(&pool, None, &storage(), "greenfield").await;
+        assert!(unlisted.is_err());
+
+        PublicDirectoryService::update_settings(
+            &pool,
+            None,
+            school_id,
+            UpdatePublicProfileDto {
+                slug: None,
+                public_profile_enabled: Some(true),
+                admissions_open: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let profile =
+            PublicDirectoryService::get_public_profile(&pool, None, &storage(), "greenfield")
+                .await
+                .unwrap();
+        assert_eq!(profile.address.as_deref(), Some("1 School Road"));
+        assert!(profile.admissions_open);
+        assert!(profile.logo_url.is_none());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_slug_must_be_unique(pool: PgPool) {
+        let first = create_test_school(&pool).await;
+        let second = create_test_school(&pool).await;
+
+        let dto = UpdatePublicProfileDto {
+            slug: Some("greenfield".to_string()),
+            public_profile_enabled: None,
+            admissions_open: None,
+        };
+
+        PublicDirectoryService::update_settings(&pool, None, first, dto.clone())
+            .await
+            .unwrap();
+        let taken = PublicDirectoryService::update_settings(&pool, None, second, dto).await;
+        assert!(taken.is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::{AcademicSessionId, SchoolId};
+
+use crate::middleware::auth::RequireReportsView;
+use crate::modules::reports::model::{
+    SessionReport, SessionReportFormat, SessionReportParams, SessionReportStatus,
+};
+use crate::modules::reports::service::ReportService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::verify_school_access;
+
+/// Get a school's session summary report
+///
+/// Summarizes an academic session for board reporting: enrollment movement
+/// (admissions, withdrawals, transfers, promotions), staff changes, and
+/// attendance and grade aggregates. The first request queues the report and
+/// returns it with status 202; poll until it completes, then it is returned
+/// as JSON or, with `format=pdf`, as a PDF download. Completed reports are
+/// kept; pass `refresh=true` to generate a new one.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/reports/session-summary",
+    summary = "Get session summary report",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        SessionReportParams
+    ),
+    responses(
+        (status = 200, description = "Completed or failed report; a completed report is a PDF with format=pdf", body = SessionReport,
+            content(
+                (SessionReport = "application/json"),
+                (Vec<u8> = "application/pdf")
+            )
+        ),
+        (status = 202, description = "Report queued or being generated", body = SessionReport),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires reports:view permission and access to the school"),
+        (status = 404, description = "Academic session not found in the school")
+    ),
+    tag = "Reports",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_session_summary_report(
+    State(state): State<AppState>,
+    RequireReportsView(auth_user): RequireReportsView,
+    Path(school_id): Path<Uuid>,
+    Query(params): Query<SessionReportParams>,
+) -> Result<Response, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let session_id = AcademicSessionId::from(params.session);
+    let report = ReportService::request_session_report(
+        &state.db,
+        school_id,
+        session_id,
+        auth_user.user_id()?,
+        params.refresh,
+    )
+    .await?;
+
+    match report.status {
+        SessionReportStatus::Pending | SessionReportStatus::Running => {
+            Ok((StatusCode::ACCEPTED, Json(report)).into_response())
+        }
+        SessionReportStatus::Completed if params.format == SessionReportFormat::Pdf => {
+            let pdf = ReportService::get_report_pdf(&state.db, report.id).await?;
+            Ok((
+                [
+                    (header::CONTENT_TYPE, "application/pdf".to_string()),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"session-summary-{session_id}.pdf\""),
+                    ),
+                ],
+                pdf,
+            )
+                .into_response())
+        }
+        SessionReportStatus::Completed | SessionReportStatus::Failed => {
+            Ok(Json(report).into_response())
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Reports module.
+//!
+//! End-of-session summary reports for board reporting. Requesting a school's
+//! session summary queues a report; a background job started with the server
+//! totals the session's enrollment movement, staff changes, attendance, and
+//! grades, and stores the summary with a PDF rendering of it. Later requests
+//! return the stored report until a refresh is asked for.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
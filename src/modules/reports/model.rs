@@ -0,0 +1,7 @@
+//! Session report data models and DTOs.
+//!
+//! This module re-exports report models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all report models from the shared crate
+pub use chalkbyte_models::reports::*;
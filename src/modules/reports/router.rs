@@ -0,0 +1,14 @@
+use axum::{Router, routing::get};
+
+use crate::state::AppState;
+
+use super::controller::get_session_summary_report;
+
+/// Initialize the school reports router, merged into the schools router
+/// Routes: GET /{id}/reports/session-summary
+pub fn init_school_reports_router() -> Router<AppState> {
+    Router::new().route(
+        "/{id}/reports/session-summary",
+        get(get_session_summary_report),
+    )
+}
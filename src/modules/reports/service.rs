@@ -0,0 +1,768 @@
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{FromRow, PgPool};
+use tracing::{info, instrument, warn};
+use uuid::Uuid;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::{AcademicSessionId, SchoolId, SessionReportId, UserId};
+use chalkbyte_observability::jobs::register_job;
+
+use crate::modules::reports::model::{
+    EnrollmentMovement, SessionAttendance, SessionGrades, SessionReport, SessionReportStatus,
+    SessionSummary, StaffChanges, TermGrades,
+};
+use crate::modules::users::model::system_roles;
+use crate::utils::pdf::TextPdf;
+
+/// How often the job looks for pending reports.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const REPORT_COLUMNS: &str = "id, school_id, academic_session_id, status, requested_by, summary,
+    error, created_at, started_at, completed_at";
+
+/// Start the background job that generates session reports.
+///
+/// Reports interrupted by a restart are generated again from the start.
+pub fn spawn_report_job(db: PgPool) {
+    tokio::spawn(async move {
+        if let Err(e) = ReportService::requeue_interrupted(&db).await {
+            warn!(error = %e, "Failed to requeue interrupted session reports");
+        }
+
+        let job = register_job("session_reports", POLL_INTERVAL);
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let run = job
+                .run(async {
+                    while let Some(report_id) = ReportService::process_next(&db).await? {
+                        info!(%report_id, "Generated session report");
+                    }
+                    Ok::<_, AppError>(())
+                })
+                .await;
+            if let Err(e) = run {
+                warn!(error = %e, "Failed to generate session report");
+            }
+            match ReportService::queue_depth(&db).await {
+                Ok(depth) => job.set_queue_depth(depth),
+                Err(e) => warn!(error = %e, "Failed to count pending session reports"),
+            }
+        }
+    });
+}
+
+/// The session a report covers, with its bounds in the school's timezone.
+#[derive(FromRow)]
+struct ReportSession {
+    school_name: String,
+    session_name: String,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    /// Midnight starting the first day of the session
+    window_start: DateTime<Utc>,
+    /// Midnight ending the last day of the session
+    window_end: DateTime<Utc>,
+}
+
+/// A report claimed by the job.
+#[derive(FromRow)]
+struct ClaimedReport {
+    id: SessionReportId,
+    school_id: SchoolId,
+    academic_session_id: AcademicSessionId,
+}
+
+pub struct ReportService;
+
+impl ReportService {
+    /// Return the latest report for the session, queueing one if there is
+    /// none yet or `refresh` is set and none is already waiting.
+    #[instrument(skip(db))]
+    pub async fn request_session_report(
+        db: &PgPool,
+        school_id: SchoolId,
+        session_id: AcademicSessionId,
+        requested_by: UserId,
+        refresh: bool,
+    ) -> Result<SessionReport, AppError> {
+        Self::get_session(db, school_id, session_id).await?;
+
+        let latest = Self::latest_report(db, session_id).await?;
+        if let Some(report) = latest
+            && (!refresh
+                || matches!(
+                    report.status,
+                    SessionReportStatus::Pending | SessionReportStatus::Running
+                ))
+        {
+            return Ok(report);
+        }
+
+        // A concurrent request may have queued one first; return that instead
+        let queued = sqlx::query_as::<_, SessionReport>(&format!(
+            "INSERT INTO session_reports (school_id, academic_session_id, requested_by)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (academic_session_id) WHERE status IN ('pending', 'running') DO NOTHING
+             RETURNING {REPORT_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(session_id)
+        .bind(requested_by)
+        .fetch_optional(db)
+        .await?;
+
+        let report = match queued {
+            Some(report) => {
+                info!(report.id = %report.id, "Session report queued");
+                report
+            }
+            None => Self::latest_report(db, session_id)
+                .await?
+                .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Session report not found")))?,
+        };
+
+        Ok(report)
+    }
+
+    /// The PDF rendering of a completed report.
+    #[instrument(skip(db))]
+    pub async fn get_report_pdf(
+        db: &PgPool,
+        report_id: SessionReportId,
+    ) -> Result<Vec<u8>, AppError> {
+        sqlx::query_scalar::<_, Option<Vec<u8>>>("SELECT pdf FROM session_reports WHERE id = $1")
+            .bind(report_id)
+            .fetch_optional(db)
+            .await?
+            .flatten()
+            .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Session report PDF not found")))
+    }
+
+    async fn latest_report(
+        db: &PgPool,
+        session_id: AcademicSessionId,
+    ) -> Result<Option<SessionReport>, AppError> {
+        let report = sqlx::query_as::<_, SessionReport>(&format!(
+            "SELECT {REPORT_COLUMNS} FROM session_reports
+             WHERE academic_session_id = $1
+             ORDER BY created_at DESC
+             LIMIT 1"
+        ))
+        .bind(session_id)
+        .fetch_optional(db)
+        .await?;
+
+        Ok(report)
+    }
+
+    async fn get_session(
+        db: &PgPool,
+        school_id: SchoolId,
+        session_id: AcademicSessionId,
+    ) -> Result<ReportSession, AppError> {
+        sqlx::query_as::<_, ReportSession>(
+            "SELECT sc.name AS school_name, s.name AS session_name, s.start_date, s.end_date,
+                    s.start_date::timestamp AT TIME ZONE sc.timezone AS window_start,
+                    (s.end_date + 1)::timestamp AT TIME ZONE sc.timezone AS window_end
+             FROM academic_sessions s
+             JOIN schools sc ON sc.id = s.school_id
+             WHERE s.id = $1 AND s.school_id = $2",
+        )
+        .bind(session_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Academic session not found")))
+    }
+
+    /// Number of reports waiting to be generated.
+    pub async fn queue_depth(db: &PgPool) -> Result<u64, AppError> {
+        let pending = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM session_reports WHERE status = 'pending'",
+        )
+        .fetch_one(db)
+        .await?;
+
+        Ok(pending.try_into().unwrap_or_default())
+    }
+
+    /// Put reports left in progress by a previous process back in the queue.
+    pub async fn requeue_interrupted(db: &PgPool) -> Result<u64, AppError> {
+        let requeued =
+            sqlx::query("UPDATE session_reports SET status = 'pending' WHERE status = 'running'")
+                .execute(db)
+                .await?
+                .rows_affected();
+
+        Ok(requeued)
+    }
+
+    /// Generate the oldest pending report.
+    ///
+    /// Returns the report generated, or `None` when none is pending. A report
+    /// that hits an unexpected error is marked failed with the error.
+    #[instrument(skip(db))]
+    pub async fn process_next(db: &PgPool) -> Result<Option<SessionReportId>, AppError> {
+        let Some(report) = sqlx::query_as::<_, ClaimedReport>(
+            "UPDATE session_reports SET status = 'running', started_at = NOW()
+             WHERE id = (
+                 SELECT id FROM session_reports
+                 WHERE status = 'pending'
+                 ORDER BY created_at
+                 LIMIT 1
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING id, school_id, academic_session_id",
+        )
+        .fetch_optional(db)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        match Self::build_summary(db, report.school_id, report.academic_session_id).await {
+            Ok(summary) => {
+                let pdf = render_pdf(&summary);
+                sqlx::query(
+                    "UPDATE session_reports
+                     SET status = 'completed', completed_at = NOW(), summary = $2, pdf = $3
+                     WHERE id = $1",
+                )
+                .bind(report.id)
+                .bind(sqlx::types::Json(&summary))
+                .bind(pdf)
+                .execute(db)
+                .await?;
+            }
+            Err(e) => {
+                warn!(report.id = %report.id, error = %e, "Session report failed");
+                sqlx::query(
+                    "UPDATE session_reports
+                     SET status = 'failed', completed_at = NOW(), error = $2
+                     WHERE id = $1",
+                )
+                .bind(report.id)
+                .bind(e.to_string())
+                .execute(db)
+                .await?;
+            }
+        }
+
+        Ok(Some(report.id))
+    }
+
+    /// Total a school's figures for an academic session.
+    #[instrument(skip(db))]
+    pub async fn build_summary(
+        db: &PgPool,
+        school_id: SchoolId,
+        session_id: AcademicSessionId,
+    ) -> Result<SessionSummary, AppError> {
+        let session = Self::get_session(db, school_id, session_id).await?;
+
+        // A student is admitted by the school they were created at: the one
+        // their first transfer left, or their current one. Deleted users keep
+        // their role IDs in the recycle bin snapshot, so students and staff
+        // who left are still counted until it is purged.
+        let enrollment = sqlx::query_as::<_, EnrollmentMovement>(
+            r#"SELECT
+                   (SELECT COUNT(*) FROM users u JOIN user_roles ur ON ur.user_id = u.id
+                    WHERE ur.role_id = $3 AND u.created_at >= $4 AND u.created_at < $5
+                      AND COALESCE(
+                          (SELECT st.from_school_id FROM student_transfers st
+                           WHERE st.student_id = u.id
+                             AND st.from_school_id IS DISTINCT FROM st.to_school_id
+                           ORDER BY st.transferred_at
+                           LIMIT 1),
+                          u.school_id
+                      ) = $1)
+                   + (SELECT COUNT(*) FROM trash_items t
+                      WHERE t.school_id = $1 AND t.data->'role_ids' ? $3::text
+                        AND (t.data->'record'->>'created_at')::timestamptz >= $4
+                        AND (t.data->'record'->>'created_at')::timestamptz < $5) AS admissions,
+                   (SELECT COUNT(*) FROM trash_items t
+                    WHERE t.school_id = $1 AND t.data->'role_ids' ? $3::text
+                      AND t.deleted_at >= $4 AND t.deleted_at < $5) AS withdrawals,
+                   (SELECT COUNT(*) FROM student_transfers
+                    WHERE to_school_id = $1 AND from_school_id IS DISTINCT FROM $1
+                      AND transferred_at >= $4 AND transferred_at < $5) AS transfers_in,
+                   (SELECT COUNT(*) FROM student_transfers
+                    WHERE from_school_id = $1 AND to_school_id IS DISTINCT FROM $1
+                      AND transferred_at >= $4 AND transferred_at < $5) AS transfers_out,
+                   (SELECT COUNT(*) FROM student_transfers
+                    WHERE from_school_id = $1 AND to_school_id = $1
+                      AND transferred_at >= $4 AND transferred_at < $5) AS internal_transfers,
+                   COUNT(*) FILTER (WHERE p.outcome = 'promoted') AS promoted,
+                   COUNT(*) FILTER (WHERE p.outcome = 'graduated') AS graduated,
+                   COUNT(*) FILTER (WHERE p.outcome = 'repeated') AS repeated,
+                   (SELECT COUNT(*) FROM users u JOIN user_roles ur ON ur.user_id = u.id
+                    WHERE u.school_id = $1 AND ur.role_id = $3) AS current_enrollment
+               FROM student_promotions p
+               WHERE p.school_id = $1 AND p.academic_session_id = $2"#,
+        )
+        .bind(school_id)
+        .bind(session_id)
+        .bind(system_roles::STUDENT)
+        .bind(session.window_start)
+        .bind(session.window_end)
+        .fetch_one(db)
+        .await?;
+
+        let staff_roles = [
+            system_roles::ADMIN.into_inner(),
+            system_roles::TEACHER.into_inner(),
+        ];
+        let staff_role_ids: Vec<String> = staff_roles.iter().map(Uuid::to_string).collect();
+        let staff = sqlx::query_as::<_, StaffChanges>(
+            r#"SELECT
+                   (SELECT COUNT(DISTINCT u.id) FROM users u JOIN user_roles ur ON ur.user_id = u.id
+                    WHERE u.school_id = $1 AND ur.role_id = ANY($2)
+                      AND ur.assigned_at >= $4 AND ur.assigned_at < $5) AS joined,
+                   (SELECT COUNT(*) FROM trash_items t
+                    WHERE t.school_id = $1 AND t.data->'role_ids' ?| $3
+                      AND t.deleted_at >= $4 AND t.deleted_at < $5) AS left,
+                   (SELECT COUNT(DISTINCT u.id) FROM users u JOIN user_roles ur ON ur.user_id = u.id
+                    WHERE u.school_id = $1 AND ur.role_id = ANY($2)) AS current_staff"#,
+        )
+        .bind(school_id)
+        .bind(&staff_roles[..])
+        .bind(&staff_role_ids)
+        .bind(session.window_start)
+        .bind(session.window_end)
+        .fetch_one(db)
+        .await?;
+
+        let attendance = sqlx::query_as::<_, SessionAttendance>(
+            "SELECT COUNT(*) FILTER (WHERE status = 'present') AS present,
+                    COUNT(*) FILTER (WHERE status = 'absent') AS absent,
+                    COUNT(*) FILTER (WHERE status = 'late') AS late,
+                    COUNT(*) FILTER (WHERE status = 'excused') AS excused
+             FROM attendance_records
+             WHERE school_id = $1 AND date BETWEEN $2 AND $3",
+        )
+        .bind(school_id)
+        .bind(session.start_date)
+        .bind(session.end_date)
+        .fetch_one(db)
+        .await?
+        .with_rate();
+
+        let terms = sqlx::query_as::<_, TermGrades>(
+            "SELECT t.id AS term_id, t.name AS term_name,
+                    COUNT(DISTINCT a.id) AS assessments,
+                    COUNT(sc.id) AS scores,
+                    AVG(sc.score * 100.0 / a.max_score) AS average_percentage
+             FROM terms t
+             LEFT JOIN assessments a ON a.term_id = t.id AND a.school_id = $2
+             LEFT JOIN assessment_scores sc ON sc.assessment_id = a.id
+             WHERE t.academic_session_id = $1
+             GROUP BY t.id, t.name, t.sequence, t.start_date
+             ORDER BY t.sequence, t.start_date",
+        )
+        .bind(session_id)
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(SessionSummary {
+            school_id,
+            school_name: session.school_name,
+            academic_session_id: session_id,
+            session_name: session.session_name,
+            start_date: session.start_date,
+            end_date: session.end_date,
+            enrollment,
+            staff,
+            attendance,
+            grades: SessionGrades::new(terms),
+            generated_at: Utc::now(),
+        })
+    }
+}
+
+fn percentage(value: Option<f64>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |value| format!("{value:.1}%"))
+}
+
+/// Render a summary as a printable PDF.
+pub fn render_pdf(summary: &SessionSummary) -> Vec<u8> {
+    let enrollment = &summary.enrollment;
+    let staff = &summary.staff;
+    let attendance = &summary.attendance;
+    let grades = &summary.grades;
+
+    let mut pdf = TextPdf::new(format!(
+        "{} - {} session summary",
+        summary.school_name, summary.session_name
+    ));
+    pdf.heading(&summary.school_name)
+        .line(format!(
+            "Session summary: {} ({} to {})",
+            summary.session_name, summary.start_date, summary.end_date
+        ))
+        .line(format!(
+            "Generated {}",
+            summary.generated_at.format("%Y-%m-%d %H:%M UTC")
+        ))
+        .blank()
+        .heading("Enrollment")
+        .line(format!("Admissions: {}", enrollment.admissions))
+        .line(format!("Withdrawals: {}", enrollment.withdrawals))
+        .line(format!("Transfers in: {}", enrollment.transfers_in))
+        .line(format!("Transfers out: {}", enrollment.transfers_out))
+        .line(format!(
+            "Transfers between branches: {}",
+            enrollment.internal_transfers
+        ))
+        .line(format!("Promoted: {}", enrollment.promoted))
+        .line(format!("Graduated: {}", enrollment.graduated))
+        .line(format!("Repeated: {}", enrollment.repeated))
+        .line(format!(
+            "Current enrollment: {}",
+            enrollment.current_enrollment
+        ))
+        .blank()
+        .heading("Staff")
+        .line(format!("Joined: {}", staff.joined))
+        .line(format!("Left: {}", staff.left))
+        .line(format!("Current staff: {}", staff.current_staff))
+        .blank()
+        .heading("Attendance")
+        .line(format!("Present: {}", attendance.present))
+        .line(format!("Absent: {}", attendance.absent))
+        .line(format!("Late: {}", attendance.late))
+        .line(format!("Excused: {}", attendance.excused))
+        .line(format!(
+            "Attendance rate: {}",
+            percentage(attendance.attendance_rate)
+        ))
+        .blank()
+        .heading("Grades")
+        .line(format!("Assessments: {}", grades.assessments))
+        .line(format!("Scores recorded: {}", grades.scores))
+        .line(format!(
+            "Average score: {}",
+            percentage(grades.average_percentage)
+        ));
+    for term in &grades.terms {
+        pdf.line(format!(
+            "{}: {} assessments, {} scores, average {}",
+            term.term_name,
+            term.assessments,
+            term.scores,
+            percentage(term.average_percentage)
+        ));
+    }
+
+    pdf.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use chalkbyte_models::ids::{BranchId, LevelId, TermId};
+    use chrono::Duration as ChronoDuration;
+
+    use super::*;
+    use crate::modules::reports::model::SessionReportStatus;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar::<_, SchoolId>(
+            "INSERT INTO schools (name, address) VALUES ($1, 'Test Address') RETURNING id",
+        )
+        .bind(format!("School {}", Uuid::new_v4()))
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    /// A session running from 60 days ago to 60 days ahead, with one term.
+    async fn create_session(pool: &PgPool, school_id: SchoolId) -> (AcademicSessionId, TermId) {
+        let today = Utc::now().date_naive();
+        let session_id = sqlx::query_scalar::<_, AcademicSessionId>(
+            "INSERT INTO academic_sessions (name, school_id, start_date, end_date)
+             VALUES ('2026/2027', $1, $2, $3) RETURNING id",
+        )
+        .bind(school_id)
+        .bind(today - ChronoDuration::days(60))
+        .bind(today + ChronoDuration::days(60))
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let term_id = sqlx::query_scalar::<_, TermId>(
+            "INSERT INTO terms (name, academic_session_id, start_date, end_date)
+             VALUES ('First Term', $1, $2, $3) RETURNING id",
+        )
+        .bind(session_id)
+        .bind(today - ChronoDuration::days(60))
+        .bind(today + ChronoDuration::days(60))
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        (session_id, term_id)
+    }
+
+    async fn create_branch(pool: &PgPool, level_id: LevelId, name: &str) -> BranchId {
+        sqlx::query_scalar::<_, BranchId>(
+            "INSERT INTO branches (name, level_id) VALUES ($1, $2) RETURNING id",
+        )
+        .bind(name)
+        .bind(level_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn create_user(
+        pool: &PgPool,
+        school_id: SchoolId,
+        role_id: chalkbyte_models::ids::RoleId,
+        branch_id: Option<BranchId>,
+    ) -> UserId {
+        let user_id = sqlx::query_scalar::<_, UserId>(
+            "INSERT INTO users (first_name, last_name, email, school_id, branch_id, level_id)
+             VALUES ('Test', 'User', $1, $2, $3, (SELECT level_id FROM branches WHERE id = $3))
+             RETURNING id",
+        )
+        .bind(format!("user-{}@example.com", Uuid::new_v4()))
+        .bind(school_id)
+        .bind(branch_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(role_id)
+            .execute(pool)
+            .await
+            .unwrap();
+
+        user_id
+    }
+
+    async fn trash_user(
+        pool: &PgPool,
+        school_id: SchoolId,
+        role_id: chalkbyte_models::ids::RoleId,
+    ) {
+        sqlx::query(
+            "INSERT INTO trash_items (school_id, item_type, entity_id, label, data)
+             VALUES ($1, 'users', uuid_generate_v4(), 'Deleted User',
+                     jsonb_build_object(
+                         'record', jsonb_build_object('created_at', NOW() - INTERVAL '2 years'),
+                         'role_ids', jsonb_build_array($2::text)
+                     ))",
+        )
+        .bind(school_id)
+        .bind(role_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_summary_counts_session_activity(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let other_school_id = create_test_school(&pool).await;
+        let (session_id, term_id) = create_session(&pool, school_id).await;
+
+        let level_id = sqlx::query_scalar::<_, LevelId>(
+            "INSERT INTO levels (name, school_id) VALUES ('JSS1', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let branch_a = create_branch(&pool, level_id, "A").await;
+        let branch_b = create_branch(&pool, level_id, "B").await;
+
+        // Admitted, then moved between branches and promoted
+        let admitted = create_user(&pool, school_id, system_roles::STUDENT, Some(branch_a)).await;
+        sqlx::query("UPDATE users SET branch_id = $2 WHERE id = $1")
+            .bind(admitted)
+            .bind(branch_b)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO student_promotions (school_id, academic_session_id, student_id, outcome)
+             VALUES ($1, $2, $3, 'promoted')",
+        )
+        .bind(school_id)
+        .bind(session_id)
+        .bind(admitted)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Transferred in from another school
+        let transferred_in = create_user(&pool, other_school_id, system_roles::STUDENT, None).await;
+        sqlx::query("UPDATE users SET school_id = $2 WHERE id = $1")
+            .bind(transferred_in)
+            .bind(school_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Admitted here, then transferred out
+        let transferred_out = create_user(&pool, school_id, system_roles::STUDENT, None).await;
+        sqlx::query("UPDATE users SET school_id = $2 WHERE id = $1")
+            .bind(transferred_out)
+            .bind(other_school_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        create_user(&pool, school_id, system_roles::TEACHER, None).await;
+        trash_user(&pool, school_id, system_roles::STUDENT).await;
+        trash_user(&pool, school_id, system_roles::TEACHER).await;
+
+        let today = Utc::now().date_naive();
+        for (student_id, status) in [(admitted, "present"), (transferred_in, "absent")] {
+            sqlx::query(
+                "INSERT INTO attendance_records (school_id, branch_id, student_id, date, status)
+                 VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(school_id)
+            .bind(branch_b)
+            .bind(student_id)
+            .bind(today)
+            .bind(status)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let subject_id = sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO subjects (school_id, name) VALUES ($1, 'Maths') RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let assessment_id = sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO assessments (school_id, term_id, level_id, subject_id, name, kind, max_score, weight)
+             VALUES ($1, $2, $3, $4, 'Quiz', 'quiz', 50, 10)
+             RETURNING id",
+        )
+        .bind(school_id)
+        .bind(term_id)
+        .bind(level_id)
+        .bind(subject_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        for (student_id, score) in [(admitted, 40.0), (transferred_in, 30.0)] {
+            sqlx::query(
+                "INSERT INTO assessment_scores (assessment_id, student_id, score) VALUES ($1, $2, $3)",
+            )
+            .bind(assessment_id)
+            .bind(student_id)
+            .bind(score)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let summary = ReportService::build_summary(&pool, school_id, session_id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            summary.enrollment,
+            EnrollmentMovement {
+                admissions: 2,
+                withdrawals: 1,
+                transfers_in: 1,
+                transfers_out: 1,
+                internal_transfers: 1,
+                promoted: 1,
+                graduated: 0,
+                repeated: 0,
+                current_enrollment: 2,
+            }
+        );
+        assert_eq!(
+            summary.staff,
+            StaffChanges {
+                joined: 1,
+                left: 1,
+                current_staff: 1,
+            }
+        );
+        assert_eq!(
+            (summary.attendance.present, summary.attendance.absent),
+            (1, 1)
+        );
+        assert_eq!(summary.attendance.attendance_rate, Some(50.0));
+        assert_eq!(summary.grades.scores, 2);
+        assert_eq!(summary.grades.average_percentage, Some(70.0));
+        assert_eq!(summary.grades.terms[0].term_name, "First Term");
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_report_is_queued_generated_and_refreshed(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let (session_id, _) = create_session(&pool, school_id).await;
+        let admin_id = create_user(&pool, school_id, system_roles::ADMIN, None).await;
+
+        let queued =
+            ReportService::request_session_report(&pool, school_id, session_id, admin_id, false)
+                .await
+                .unwrap();
+        assert_eq!(queued.status, SessionReportStatus::Pending);
+
+        // Asking again while it waits returns the same report, even with refresh
+        let again =
+            ReportService::request_session_report(&pool, school_id, session_id, admin_id, true)
+                .await
+                .unwrap();
+        assert_eq!(again.id, queued.id);
+
+        assert_eq!(
+            ReportService::process_next(&pool).await.unwrap(),
+            Some(queued.id)
+        );
+        assert_eq!(ReportService::process_next(&pool).await.unwrap(), None);
+
+        let completed =
+            ReportService::request_session_report(&pool, school_id, session_id, admin_id, false)
+                .await
+                .unwrap();
+        assert_eq!(completed.id, queued.id);
+        assert_eq!(completed.status, SessionReportStatus::Completed);
+        let summary = completed.summary.unwrap();
+        assert_eq!(summary.staff.current_staff, 1);
+
+        let pdf = ReportService::get_report_pdf(&pool, completed.id)
+            .await
+            .unwrap();
+        assert!(pdf.starts_with(b"%PDF-"));
+
+        let refreshed =
+            ReportService::request_session_report(&pool, school_id, session_id, admin_id, true)
+                .await
+                .unwrap();
+        assert_ne!(refreshed.id, queued.id);
+        assert_eq!(refreshed.status, SessionReportStatus::Pending);
+
+        // Sessions of other schools are not found
+        let other_school_id = create_test_school(&pool).await;
+        assert!(
+            ReportService::request_session_report(
+                &pool,
+                other_school_id,
+                session_id,
+                admin_id,
+                false
+            )
+            .await
+            .is_err()
+        );
+    }
+}
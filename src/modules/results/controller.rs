@@ -0,0 +1,146 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use chrono::Utc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::TermResultId;
+
+use crate::middleware::auth::{AuthUser, RequireResultsRead};
+use crate::middleware::role::is_branch_scoped_teacher_jwt;
+use crate::modules::branches::service::BranchService;
+use crate::modules::results::model::{
+    TermResult, TermResultParams, TermResultTransition, TransitionResultsDto,
+    TransitionResultsResponse,
+};
+use crate::modules::results::service::ResultService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// List result statuses for a level
+///
+/// One entry per subject assessed in the level and term. Subjects whose
+/// results have never been transitioned are open.
+#[utoipa::path(
+    get,
+    path = "/api/results",
+    summary = "Get term result statuses",
+    params(TermResultParams),
+    responses(
+        (status = 200, description = "Result status per subject, ordered by subject name", body = Vec<TermResult>),
+        (status = 400, description = "Term or level not in the school, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires results:read permission")
+    ),
+    tag = "Results",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_results(
+    State(state): State<AppState>,
+    RequireResultsRead(auth_user): RequireResultsRead,
+    Query(params): Query<TermResultParams>,
+) -> Result<Json<Vec<TermResult>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let results =
+        ResultService::get_results(&state.db, school_id, params.term_id, params.level_id).await?;
+
+    Ok(Json(results))
+}
+
+/// Transition term results
+///
+/// Applies one workflow action to the results of several subjects in a
+/// level: `submit` (results:submit), `moderate` and `return`
+/// (results:moderate), `publish` (results:publish), `lock` (results:lock),
+/// or `unlock` (results:unlock, with a reason). Results the action does not
+/// apply to are skipped. Teachers can only transition results for a level
+/// where they are assigned to a branch.
+#[utoipa::path(
+    post,
+    path = "/api/results/transitions",
+    summary = "Transition term results",
+    request_body = TransitionResultsDto,
+    responses(
+        (status = 200, description = "Results transitioned", body = TransitionResultsResponse),
+        (status = 400, description = "Invalid input, unlock without a reason, or term, level, or subject not in the school"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires the action's permission and, for teachers, an assignment in the level")
+    ),
+    tag = "Results",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn transition_results(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    ValidatedJson(dto): ValidatedJson<TransitionResultsDto>,
+) -> Result<Json<TransitionResultsResponse>, AppError> {
+    let permission = dto.action.permission();
+    if !auth_user.has_permission(permission) {
+        return Err(AppError::forbidden(format!(
+            "Access denied. Missing required permission: {}",
+            permission
+        )));
+    }
+
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let performed_by = auth_user.user_id()?;
+    if is_branch_scoped_teacher_jwt(&auth_user) {
+        let capacities = BranchService::teacher_capacities_in_level(
+            &state.db,
+            dto.level_id,
+            performed_by,
+            Utc::now().date_naive(),
+        )
+        .await?;
+        if capacities.is_empty() {
+            return Err(AppError::forbidden(
+                "You are not assigned to a branch in this level".to_string(),
+            ));
+        }
+    }
+
+    let response =
+        ResultService::transition_results(&state.db, school_id, performed_by, dto).await?;
+
+    Ok(Json(response))
+}
+
+/// Get the transition history of a subject's results
+#[utoipa::path(
+    get,
+    path = "/api/results/{id}/history",
+    summary = "Get term result history",
+    params(
+        ("id" = Uuid, Path, description = "Term result ID")
+    ),
+    responses(
+        (status = 200, description = "Transitions, oldest first", body = Vec<TermResultTransition>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires results:read permission"),
+        (status = 404, description = "Term result not found")
+    ),
+    tag = "Results",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_result_history(
+    State(state): State<AppState>,
+    RequireResultsRead(auth_user): RequireResultsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TermResultTransition>>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let history = ResultService::get_history(&state.db, TermResultId::from(id), school_id).await?;
+
+    Ok(Json(history))
+}
@@ -0,0 +1,13 @@
+//! Term result moderation module.
+//!
+//! Tracks the results of each subject in a level and term through the
+//! workflow open → submitted → moderated → published → locked. Transitions
+//! are made in bulk for a level, each needs its own permission, and every
+//! one is recorded. Assessments and scores only change while results are
+//! open, so locked results stay fixed until a senior admin unlocks them with
+//! a reason.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,8 @@
+//! Term result data models and DTOs.
+//!
+//! This module re-exports term result models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific
+//! types.
+
+// Re-export all term result models from the shared crate
+pub use chalkbyte_models::results::*;
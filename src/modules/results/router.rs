@@ -0,0 +1,17 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{get_result_history, get_results, transition_results};
+
+/// Initialize the term results router
+/// Routes: GET /, POST /transitions, GET /{id}/history
+pub fn init_results_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_results))
+        .route("/transitions", post(transition_results))
+        .route("/{id}/history", get(get_result_history))
+}
@@ -0,0 +1,536 @@
+use std::collections::HashSet;
+
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::{LevelId, SchoolId, SubjectId, TermId, TermResultId, UserId};
+
+use crate::modules::results::model::{
+    ResultStatus, SkippedResult, TermResult, TermResultTransition, TransitionResultsDto,
+    TransitionResultsResponse,
+};
+
+/// Every subject with assessments in the term and level or a recorded
+/// status, open unless recorded otherwise. Binds `$1` term, `$2` level, and
+/// `$3` an optional subject filter.
+const TERM_RESULTS_QUERY: &str = r#"
+    WITH subject_ids AS (
+        SELECT subject_id FROM assessments WHERE term_id = $1 AND level_id = $2
+        UNION
+        SELECT subject_id FROM term_results WHERE term_id = $1 AND level_id = $2
+    )
+    SELECT tr.id, $1 AS term_id, $2 AS level_id, s.id AS subject_id, s.name AS subject_name,
+           COALESCE(tr.status, 'open') AS status, tr.updated_by, tr.updated_at
+    FROM subject_ids si
+    INNER JOIN subjects s ON s.id = si.subject_id
+    LEFT JOIN term_results tr
+        ON tr.term_id = $1 AND tr.level_id = $2 AND tr.subject_id = s.id
+    WHERE ($3::uuid[] IS NULL OR s.id = ANY($3))
+    ORDER BY s.name"#;
+
+pub struct ResultService;
+
+impl ResultService {
+    /// Result statuses of every subject assessed in a level and term.
+    #[instrument(skip(db))]
+    pub async fn get_results(
+        db: &PgPool,
+        school_id: SchoolId,
+        term_id: TermId,
+        level_id: LevelId,
+    ) -> Result<Vec<TermResult>, AppError> {
+        Self::validate_scope(db, school_id, term_id, level_id).await?;
+
+        let results = sqlx::query_as::<_, TermResult>(TERM_RESULTS_QUERY)
+            .bind(term_id)
+            .bind(level_id)
+            .bind(None::<Vec<SubjectId>>)
+            .fetch_all(db)
+            .await?;
+
+        Ok(results)
+    }
+
+    /// Apply a workflow action to the results of several subjects.
+    ///
+    /// Results whose status the action does not apply to are skipped and
+    /// reported; the rest move in one transaction, each with a recorded
+    /// transition.
+    #[instrument(skip(db, dto))]
+    pub async fn transition_results(
+        db: &PgPool,
+        school_id: SchoolId,
+        performed_by: UserId,
+        dto: TransitionResultsDto,
+    ) -> Result<TransitionResultsResponse, AppError> {
+        Self::validate_scope(db, school_id, dto.term_id, dto.level_id).await?;
+
+        let mut tx = db.begin().await?;
+
+        let subject_ids = match dto.subject_ids {
+            Some(subject_ids) => {
+                let found: HashSet<SubjectId> = sqlx::query_scalar::<_, SubjectId>(
+                    "SELECT id FROM subjects WHERE id = ANY($1) AND school_id = $2",
+                )
+                .bind(&subject_ids)
+                .bind(school_id)
+                .fetch_all(&mut *tx)
+                .await?
+                .into_iter()
+                .collect();
+
+                if let Some(missing) = subject_ids.iter().find(|id| !found.contains(id)) {
+                    return Err(AppError::bad_request(anyhow::anyhow!(
+                        "Subject {} not found in this school",
+                        missing
+                    )));
+                }
+                subject_ids
+            }
+            None => sqlx::query_scalar::<_, SubjectId>(
+                "SELECT DISTINCT subject_id FROM assessments WHERE term_id = $1 AND level_id = $2",
+            )
+            .bind(dto.term_id)
+            .bind(dto.level_id)
+            .fetch_all(&mut *tx)
+            .await?,
+        };
+
+        sqlx::query(
+            r#"INSERT INTO term_results (school_id, term_id, level_id, subject_id)
+               SELECT $1, $2, $3, UNNEST($4::uuid[])
+               ON CONFLICT (term_id, level_id, subject_id) DO NOTHING"#,
+        )
+        .bind(school_id)
+        .bind(dto.term_id)
+        .bind(dto.level_id)
+        .bind(&subject_ids)
+        .execute(&mut *tx)
+        .await?;
+
+        let current = sqlx::query_as::<_, (TermResultId, SubjectId, ResultStatus)>(
+            r#"SELECT id, subject_id, status FROM term_results
+               WHERE term_id = $1 AND level_id = $2 AND subject_id = ANY($3)
+               ORDER BY id
+               FOR UPDATE"#,
+        )
+        .bind(dto.term_id)
+        .bind(dto.level_id)
+        .bind(&subject_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut moving = Vec::new();
+        let mut skipped = Vec::new();
+        for (id, subject_id, status) in current {
+            match dto.action.apply(status) {
+                Some(to_status) => moving.push((id, subject_id, status, to_status)),
+                None => skipped.push(SkippedResult { subject_id, status }),
+            }
+        }
+
+        if !moving.is_empty() {
+            sqlx::query(
+                r#"UPDATE term_results AS tr
+                   SET status = m.to_status, updated_by = $3, updated_at = NOW()
+                   FROM UNNEST($1::uuid[], $2::text[]) AS m(id, to_status)
+                   WHERE tr.id = m.id"#,
+            )
+            .bind(moving.iter().map(|m| m.0).collect::<Vec<_>>())
+            .bind(moving.iter().map(|m| m.3).collect::<Vec<_>>())
+            .bind(performed_by)
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query(
+                r#"INSERT INTO term_result_transitions
+                       (term_result_id, action, from_status, to_status, reason, performed_by)
+                   SELECT m.id, $4, m.from_status, m.to_status, $5, $6
+                   FROM UNNEST($1::uuid[], $2::text[], $3::text[]) AS m(id, from_status, to_status)"#,
+            )
+            .bind(moving.iter().map(|m| m.0).collect::<Vec<_>>())
+            .bind(moving.iter().map(|m| m.2).collect::<Vec<_>>())
+            .bind(moving.iter().map(|m| m.3).collect::<Vec<_>>())
+            .bind(dto.action)
+            .bind(dto.reason.as_deref().map(str::trim))
+            .bind(performed_by)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let moved_subjects: Vec<SubjectId> = moving.iter().map(|m| m.1).collect();
+        let transitioned = if moved_subjects.is_empty() {
+            Vec::new()
+        } else {
+            sqlx::query_as::<_, TermResult>(TERM_RESULTS_QUERY)
+                .bind(dto.term_id)
+                .bind(dto.level_id)
+                .bind(Some(&moved_subjects))
+                .fetch_all(&mut *tx)
+                .await?
+        };
+
+        tx.commit().await?;
+
+        Ok(TransitionResultsResponse {
+            transitioned,
+            skipped,
+        })
+    }
+
+    /// Recorded transitions of a subject's results, oldest first.
+    #[instrument(skip(db))]
+    pub async fn get_history(
+        db: &PgPool,
+        term_result_id: TermResultId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Vec<TermResultTransition>, AppError> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM term_results WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2))",
+        )
+        .bind(term_result_id)
+        .bind(school_id)
+        .fetch_one(db)
+        .await?;
+
+        if !exists {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Term result not found"
+            )));
+        }
+
+        let transitions = sqlx::query_as::<_, TermResultTransition>(
+            r#"SELECT id, action, from_status, to_status, reason, performed_by, created_at
+               FROM term_result_transitions
+               WHERE term_result_id = $1
+               ORDER BY created_at, id"#,
+        )
+        .bind(term_result_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(transitions)
+    }
+
+    /// Reject changes to assessments or scores of a subject whose results
+    /// are no longer open.
+    #[instrument(skip(db))]
+    pub async fn ensure_editable(
+        db: &PgPool,
+        term_id: TermId,
+        level_id: LevelId,
+        subject_id: SubjectId,
+    ) -> Result<(), AppError> {
+        let status = sqlx::query_scalar::<_, ResultStatus>(
+            "SELECT status FROM term_results WHERE term_id = $1 AND level_id = $2 AND subject_id = $3",
+        )
+        .bind(term_id)
+        .bind(level_id)
+        .bind(subject_id)
+        .fetch_optional(db)
+        .await?
+        .unwrap_or(ResultStatus::Open);
+
+        match status {
+            ResultStatus::Open => Ok(()),
+            ResultStatus::Locked => Err(AppError::bad_request(anyhow::anyhow!(
+                "Term results for this subject are locked and must be unlocked before they can change"
+            ))),
+            _ => Err(AppError::bad_request(anyhow::anyhow!(
+                "Term results for this subject have been submitted and must be returned before they can change"
+            ))),
+        }
+    }
+
+    /// Ensure the term and level belong to the school.
+    async fn validate_scope(
+        db: &PgPool,
+        school_id: SchoolId,
+        term_id: TermId,
+        level_id: LevelId,
+    ) -> Result<(), AppError> {
+        let (term_ok, level_ok) = sqlx::query_as::<_, (bool, bool)>(
+            r#"SELECT
+                   EXISTS(SELECT 1 FROM terms t
+                          INNER JOIN academic_sessions s ON s.id = t.academic_session_id
+                          WHERE t.id = $2 AND s.school_id = $1),
+                   EXISTS(SELECT 1 FROM levels WHERE id = $3 AND school_id = $1)"#,
+        )
+        .bind(school_id)
+        .bind(term_id)
+        .bind(level_id)
+        .fetch_one(db)
+        .await?;
+
+        if !term_ok {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Term not found in this school"
+            )));
+        }
+        if !level_ok {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Level not found in this school"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::assessments::model::{
+        AssessmentKind, CreateAssessmentDto, CreateSubjectDto, RecordScoresDto, ScoreEntryDto,
+    };
+    use crate::modules::assessments::service::{AssessmentService, GradeService};
+    use crate::modules::results::model::ResultAction;
+    use axum::http::StatusCode;
+    use uuid::Uuid;
+
+    struct Fixture {
+        school_id: SchoolId,
+        term_id: TermId,
+        level_id: LevelId,
+        subject_id: SubjectId,
+        teacher_id: UserId,
+    }
+
+    async fn create_fixture(pool: &PgPool) -> Fixture {
+        let school_id = sqlx::query_scalar::<_, SchoolId>(
+            "INSERT INTO schools (name) VALUES ($1) RETURNING id",
+        )
+        .bind(format!("School {}", Uuid::new_v4()))
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let term_id = sqlx::query_scalar::<_, TermId>(
+            r#"WITH session AS (
+                   INSERT INTO academic_sessions (name, school_id, start_date, end_date)
+                   VALUES ('2026/2027', $1, '2026-09-01', '2027-07-31') RETURNING id
+               )
+               INSERT INTO terms (name, academic_session_id, start_date, end_date)
+               SELECT 'First Term', id, '2026-09-01', '2026-12-15' FROM session
+               RETURNING id"#,
+        )
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let level_id = sqlx::query_scalar::<_, LevelId>(
+            "INSERT INTO levels (name, school_id) VALUES ('JSS 1', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let teacher_id = sqlx::query_scalar::<_, UserId>(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Tola', 'Teacher', $1, $2) RETURNING id"#,
+        )
+        .bind(format!("teacher-{}@example.com", Uuid::new_v4()))
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let subject = AssessmentService::create_subject(
+            pool,
+            school_id,
+            CreateSubjectDto {
+                name: "Mathematics".to_string(),
+                code: None,
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        Fixture {
+            school_id,
+            term_id,
+            level_id,
+            subject_id: subject.id,
+            teacher_id,
+        }
+    }
+
+    fn transition(fixture: &Fixture, action: ResultAction) -> TransitionResultsDto {
+        TransitionResultsDto {
+            action,
+            term_id: fixture.term_id,
+            level_id: fixture.level_id,
+            subject_ids: None,
+            reason: None,
+            school_id: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_results_move_through_workflow_and_lock_scores(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let assessment = AssessmentService::create_assessment(
+            &pool,
+            fixture.school_id,
+            fixture.teacher_id,
+            CreateAssessmentDto {
+                term_id: fixture.term_id,
+                level_id: fixture.level_id,
+                subject_id: fixture.subject_id,
+                name: "Mid-term exam".to_string(),
+                kind: AssessmentKind::Exam,
+                max_score: 100.0,
+                weight: 100,
+                due_date: None,
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let results =
+            ResultService::get_results(&pool, fixture.school_id, fixture.term_id, fixture.level_id)
+                .await
+                .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, ResultStatus::Open);
+        assert!(results[0].id.is_none());
+
+        let submitted = ResultService::transition_results(
+            &pool,
+            fixture.school_id,
+            fixture.teacher_id,
+            transition(&fixture, ResultAction::Submit),
+        )
+        .await
+        .unwrap();
+        assert_eq!(submitted.transitioned[0].status, ResultStatus::Submitted);
+        let result_id = submitted.transitioned[0].id.unwrap();
+
+        // Submitted results can no longer be changed
+        let err = GradeService::record_scores(
+            &pool,
+            &assessment,
+            fixture.teacher_id,
+            RecordScoresDto {
+                scores: vec![ScoreEntryDto {
+                    student_id: fixture.teacher_id,
+                    score: 50.0,
+                    remarks: None,
+                }],
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        // Publishing cannot skip moderation
+        let skipped = ResultService::transition_results(
+            &pool,
+            fixture.school_id,
+            fixture.teacher_id,
+            transition(&fixture, ResultAction::Publish),
+        )
+        .await
+        .unwrap();
+        assert!(skipped.transitioned.is_empty());
+        assert_eq!(skipped.skipped[0].status, ResultStatus::Submitted);
+
+        for action in [
+            ResultAction::Moderate,
+            ResultAction::Publish,
+            ResultAction::Lock,
+        ] {
+            ResultService::transition_results(
+                &pool,
+                fixture.school_id,
+                fixture.teacher_id,
+                transition(&fixture, action),
+            )
+            .await
+            .unwrap();
+        }
+
+        let returned = ResultService::transition_results(
+            &pool,
+            fixture.school_id,
+            fixture.teacher_id,
+            transition(&fixture, ResultAction::Return),
+        )
+        .await
+        .unwrap();
+        assert_eq!(returned.skipped[0].status, ResultStatus::Locked);
+        let err = AssessmentService::delete_assessment(&pool, assessment.id, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let unlocked = ResultService::transition_results(
+            &pool,
+            fixture.school_id,
+            fixture.teacher_id,
+            TransitionResultsDto {
+                reason: Some("Wrong script marked".to_string()),
+                ..transition(&fixture, ResultAction::Unlock)
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(unlocked.transitioned[0].status, ResultStatus::Open);
+        ResultService::ensure_editable(
+            &pool,
+            fixture.term_id,
+            fixture.level_id,
+            fixture.subject_id,
+        )
+        .await
+        .unwrap();
+
+        let history = ResultService::get_history(&pool, result_id, Some(fixture.school_id))
+            .await
+            .unwrap();
+        let actions: Vec<_> = history.iter().map(|t| t.action).collect();
+        assert_eq!(
+            actions,
+            [
+                ResultAction::Submit,
+                ResultAction::Moderate,
+                ResultAction::Publish,
+                ResultAction::Lock,
+                ResultAction::Unlock,
+            ]
+        );
+        assert_eq!(history[4].from_status, ResultStatus::Locked);
+        assert_eq!(history[4].reason.as_deref(), Some("Wrong script marked"));
+        assert_eq!(history[4].performed_by, Some(fixture.teacher_id));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_transition_rejects_subjects_from_other_schools(pool: PgPool) {
+        let fixture = create_fixture(&pool).await;
+        let other = create_fixture(&pool).await;
+
+        let err = ResultService::transition_results(
+            &pool,
+            fixture.school_id,
+            fixture.teacher_id,
+            TransitionResultsDto {
+                subject_ids: Some(vec![other.subject_id]),
+                ..transition(&fixture, ResultAction::Submit)
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let err =
+            ResultService::get_results(&pool, fixture.school_id, other.term_id, fixture.level_id)
+                .await
+                .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,213 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, NoContent};
+use chalkbyte_models::ids::SchoolId;
+
+use crate::middleware::auth::{RequireRetentionManage, RequireRetentionRead};
+use crate::modules::retention::model::{
+    PaginatedPurgeRunsResponse, PurgePreview, PurgeRun, PurgeRunFilterParams, RetentionCategory,
+    RetentionPolicy, UpdateRetentionPolicyDto,
+};
+use crate::modules::retention::service::RetentionService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::verify_school_access;
+use crate::validator::ValidatedJson;
+
+/// List a school's retention policies
+///
+/// Returns every retention category; categories the school has not set show
+/// the built-in default period.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/retention-policies",
+    summary = "List retention policies",
+    params(("id" = Uuid, Path, description = "School ID")),
+    responses(
+        (status = 200, description = "Retention period of each category", body = Vec<RetentionPolicy>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires retention:read permission and access to the school")
+    ),
+    tag = "Retention",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_retention_policies(
+    State(state): State<AppState>,
+    RequireRetentionRead(auth_user): RequireRetentionRead,
+    Path(school_id): Path<Uuid>,
+) -> Result<Json<Vec<RetentionPolicy>>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let policies = RetentionService::get_policies(&state.db, school_id).await?;
+
+    Ok(Json(policies))
+}
+
+/// Set a category's retention period
+#[utoipa::path(
+    put,
+    path = "/api/schools/{id}/retention-policies/{category}",
+    summary = "Set retention policy",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("category" = RetentionCategory, Path, description = "Retention category")
+    ),
+    request_body = UpdateRetentionPolicyDto,
+    responses(
+        (status = 200, description = "Retention policy updated", body = RetentionPolicy),
+        (status = 400, description = "Retention period out of range"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires retention:manage permission and access to the school")
+    ),
+    tag = "Retention",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_retention_policy(
+    State(state): State<AppState>,
+    RequireRetentionManage(auth_user): RequireRetentionManage,
+    Path((school_id, category)): Path<(Uuid, RetentionCategory)>,
+    ValidatedJson(dto): ValidatedJson<UpdateRetentionPolicyDto>,
+) -> Result<Json<RetentionPolicy>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let policy =
+        RetentionService::set_policy(&state.db, school_id, category, auth_user.user_id()?, dto)
+            .await?;
+
+    Ok(Json(policy))
+}
+
+/// Reset a category to its default retention period
+#[utoipa::path(
+    delete,
+    path = "/api/schools/{id}/retention-policies/{category}",
+    summary = "Reset retention policy",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("category" = RetentionCategory, Path, description = "Retention category")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires retention:manage permission and access to the school")
+    ),
+    tag = "Retention",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn reset_retention_policy(
+    State(state): State<AppState>,
+    RequireRetentionManage(auth_user): RequireRetentionManage,
+    Path((school_id, category)): Path<(Uuid, RetentionCategory)>,
+) -> Result<NoContent, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    RetentionService::reset_policy(&state.db, school_id, category).await?;
+
+    Ok(NoContent)
+}
+
+/// Preview the next purge
+///
+/// Counts, per category, the records a purge would delete if it ran now.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/retention-policies/preview",
+    summary = "Preview purge",
+    params(("id" = Uuid, Path, description = "School ID")),
+    responses(
+        (status = 200, description = "Records past their retention period", body = Vec<PurgePreview>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires retention:read permission and access to the school")
+    ),
+    tag = "Retention",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_purge_preview(
+    State(state): State<AppState>,
+    RequireRetentionRead(auth_user): RequireRetentionRead,
+    Path(school_id): Path<Uuid>,
+) -> Result<Json<Vec<PurgePreview>>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let preview = RetentionService::preview(&state.db, school_id).await?;
+
+    Ok(Json(preview))
+}
+
+/// List a school's purge runs
+///
+/// Every purge that deleted records, scheduled or manual, most recent first.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/purge-runs",
+    summary = "List purge runs",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        PurgeRunFilterParams
+    ),
+    responses(
+        (status = 200, description = "Purge runs", body = PaginatedPurgeRunsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires retention:read permission and access to the school")
+    ),
+    tag = "Retention",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_purge_runs(
+    State(state): State<AppState>,
+    RequireRetentionRead(auth_user): RequireRetentionRead,
+    Path(school_id): Path<Uuid>,
+    Query(params): Query<PurgeRunFilterParams>,
+) -> Result<Json<PaginatedPurgeRunsResponse>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let runs = RetentionService::get_purge_runs(&state.db, school_id, params).await?;
+
+    Ok(Json(runs))
+}
+
+/// Purge expired records now
+///
+/// Applies the school's retention policies immediately instead of waiting
+/// for the scheduled job. Returns the purge runs recorded.
+#[utoipa::path(
+    post,
+    path = "/api/schools/{id}/purge-runs",
+    summary = "Run purge",
+    params(("id" = Uuid, Path, description = "School ID")),
+    responses(
+        (status = 200, description = "Categories purged", body = Vec<PurgeRun>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires retention:manage permission and access to the school")
+    ),
+    tag = "Retention",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn run_purge(
+    State(state): State<AppState>,
+    RequireRetentionManage(auth_user): RequireRetentionManage,
+    Path(school_id): Path<Uuid>,
+) -> Result<Json<Vec<PurgeRun>>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let runs =
+        RetentionService::purge_school(&state.db, school_id, Some(auth_user.user_id()?)).await?;
+
+    Ok(Json(runs))
+}
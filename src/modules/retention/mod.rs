@@ -0,0 +1,11 @@
+//! Data retention module.
+//!
+//! Schools set how long audit logs, notifications, login history, and
+//! recycle bin items are kept. A background job started with the server
+//! purges older records every hour and records each purge as a purge run;
+//! admins can preview what the next run would delete.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Data retention models and DTOs.
+//!
+//! This module re-exports retention models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all retention models from the shared crate
+pub use chalkbyte_models::retention::*;
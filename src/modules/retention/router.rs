@@ -0,0 +1,26 @@
+use axum::{
+    Router,
+    routing::{get, put},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    get_purge_preview, get_purge_runs, get_retention_policies, reset_retention_policy, run_purge,
+    update_retention_policy,
+};
+
+/// Initialize the retention router, merged into the schools router
+/// Routes: GET /{id}/retention-policies, PUT /{id}/retention-policies/{category},
+/// DELETE /{id}/retention-policies/{category}, GET /{id}/retention-policies/preview,
+/// GET /{id}/purge-runs, POST /{id}/purge-runs
+pub fn init_retention_router() -> Router<AppState> {
+    Router::new()
+        .route("/{id}/retention-policies", get(get_retention_policies))
+        .route("/{id}/retention-policies/preview", get(get_purge_preview))
+        .route(
+            "/{id}/retention-policies/{category}",
+            put(update_retention_policy).delete(reset_retention_policy),
+        )
+        .route("/{id}/purge-runs", get(get_purge_runs).post(run_purge))
+}
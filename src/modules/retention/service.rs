@@ -0,0 +1,515 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
+use tracing::{info, instrument, warn};
+
+use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_models::ids::{SchoolId, UserId};
+use chalkbyte_observability::jobs::register_job;
+
+use crate::modules::emails::service::EmailQueueService;
+use crate::modules::retention::model::{
+    PaginatedPurgeRunsResponse, PurgePreview, PurgeRun, PurgeRunFilterParams, RetentionCategory,
+    RetentionPolicy, UpdateRetentionPolicyDto,
+};
+use crate::modules::traces::service::TraceService;
+use crate::modules::trash;
+
+/// How often the background job purges expired records.
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Default days change feed entries are kept.
+const DEFAULT_AUDIT_LOG_DAYS: i32 = 365;
+
+/// Default days notifications are kept.
+const DEFAULT_NOTIFICATION_DAYS: i32 = 180;
+
+/// Default days expired or revoked login sessions are kept.
+const DEFAULT_LOGIN_HISTORY_DAYS: i32 = 90;
+
+const PURGE_RUN_COLUMNS: &str =
+    "id, school_id, category, retention_days, cutoff, deleted_count, triggered_by, ran_at";
+
+/// Days a category is kept when the school has not set a policy.
+///
+/// The recycle bin default still honours `TRASH_RETENTION_DAYS`.
+pub fn default_retention_days(category: RetentionCategory) -> i32 {
+    match category {
+        RetentionCategory::AuditLogs => DEFAULT_AUDIT_LOG_DAYS,
+        RetentionCategory::Notifications => DEFAULT_NOTIFICATION_DAYS,
+        RetentionCategory::LoginHistory => DEFAULT_LOGIN_HISTORY_DAYS,
+        RetentionCategory::DeletedRecords => {
+            i32::try_from(trash::service::retention_days()).unwrap_or(i32::MAX)
+        }
+    }
+}
+
+/// Start a background task that applies every school's retention policies
+/// and drops old request logs and finished emails every hour.
+pub fn spawn_purge_job(db: PgPool) {
+    tokio::spawn(async move {
+        let purge_job = register_job("retention_purge", PURGE_INTERVAL);
+        let request_log_job = register_job("request_log_purge", PURGE_INTERVAL);
+        let email_job = register_job("email_outbox_purge", PURGE_INTERVAL);
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match purge_job
+                .run(RetentionService::purge_all_schools(&db))
+                .await
+            {
+                Ok(0) => {}
+                Ok(purged) => info!(purged, "Purged records past their retention period"),
+                Err(e) => warn!(error = %e, "Failed to purge records past their retention period"),
+            }
+            match request_log_job
+                .run(TraceService::purge_request_logs(&db))
+                .await
+            {
+                Ok(0) => {}
+                Ok(purged) => info!(purged, "Purged old request logs"),
+                Err(e) => warn!(error = %e, "Failed to purge old request logs"),
+            }
+            match email_job.run(EmailQueueService::purge_finished(&db)).await {
+                Ok(0) => {}
+                Ok(purged) => info!(purged, "Purged old outbound emails"),
+                Err(e) => warn!(error = %e, "Failed to purge old outbound emails"),
+            }
+        }
+    });
+}
+
+/// Records of `category` in a school that are older than the cutoff.
+///
+/// Every statement binds the school as `$1` and the cutoff as `$2`.
+/// Login sessions only count once they have expired or been revoked.
+fn expired_records_sql(category: RetentionCategory, select: bool) -> &'static str {
+    match (category, select) {
+        (RetentionCategory::AuditLogs, true) => {
+            "SELECT COUNT(*) FROM change_events WHERE school_id = $1 AND changed_at < $2"
+        }
+        (RetentionCategory::AuditLogs, false) => {
+            "DELETE FROM change_events WHERE school_id = $1 AND changed_at < $2"
+        }
+        (RetentionCategory::Notifications, true) => {
+            "SELECT COUNT(*) FROM notifications n
+             INNER JOIN users u ON u.id = n.user_id
+             WHERE u.school_id = $1 AND n.created_at < $2"
+        }
+        (RetentionCategory::Notifications, false) => {
+            "DELETE FROM notifications n USING users u
+             WHERE u.id = n.user_id AND u.school_id = $1 AND n.created_at < $2"
+        }
+        (RetentionCategory::LoginHistory, true) => {
+            "SELECT COUNT(*) FROM refresh_token_families f
+             INNER JOIN users u ON u.id = f.user_id
+             WHERE u.school_id = $1 AND COALESCE(f.revoked_at, f.expires_at) < $2"
+        }
+        (RetentionCategory::LoginHistory, false) => {
+            "DELETE FROM refresh_token_families f USING users u
+             WHERE u.id = f.user_id AND u.school_id = $1
+               AND COALESCE(f.revoked_at, f.expires_at) < $2"
+        }
+        (RetentionCategory::DeletedRecords, true) => {
+            "SELECT COUNT(*) FROM trash_items WHERE school_id = $1 AND deleted_at < $2"
+        }
+        (RetentionCategory::DeletedRecords, false) => {
+            "DELETE FROM trash_items WHERE school_id = $1 AND deleted_at < $2"
+        }
+    }
+}
+
+fn cutoff_for(retention_days: i32, now: DateTime<Utc>) -> DateTime<Utc> {
+    now - chrono::Duration::days(i64::from(retention_days))
+}
+
+pub struct RetentionService;
+
+impl RetentionService {
+    /// Every category's retention period for a school, with defaults filled in.
+    #[instrument(skip(db))]
+    pub async fn get_policies(
+        db: impl PgExecutor<'_>,
+        school_id: SchoolId,
+    ) -> Result<Vec<RetentionPolicy>, AppError> {
+        let rows = sqlx::query_as::<_, (RetentionCategory, i32, Option<UserId>, DateTime<Utc>)>(
+            "SELECT category, retention_days, updated_by, updated_at
+             FROM retention_policies WHERE school_id = $1",
+        )
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        let mut set: HashMap<RetentionCategory, (i32, Option<UserId>, DateTime<Utc>)> = rows
+            .into_iter()
+            .map(|(category, days, updated_by, updated_at)| {
+                (category, (days, updated_by, updated_at))
+            })
+            .collect();
+
+        Ok(RetentionCategory::ALL
+            .into_iter()
+            .map(|category| match set.remove(&category) {
+                Some((retention_days, updated_by, updated_at)) => RetentionPolicy {
+                    category,
+                    retention_days,
+                    is_default: false,
+                    updated_by,
+                    updated_at: Some(updated_at),
+                },
+                None => RetentionPolicy {
+                    category,
+                    retention_days: default_retention_days(category),
+                    is_default: true,
+                    updated_by: None,
+                    updated_at: None,
+                },
+            })
+            .collect())
+    }
+
+    /// Set how long a school keeps a category of data.
+    #[instrument(skip(db))]
+    pub async fn set_policy(
+        db: &PgPool,
+        school_id: SchoolId,
+        category: RetentionCategory,
+        updated_by: UserId,
+        dto: UpdateRetentionPolicyDto,
+    ) -> Result<RetentionPolicy, AppError> {
+        let updated_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+            "INSERT INTO retention_policies (school_id, category, retention_days, updated_by)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (school_id, category) DO UPDATE
+             SET retention_days = EXCLUDED.retention_days,
+                 updated_by = EXCLUDED.updated_by,
+                 updated_at = NOW()
+             RETURNING updated_at",
+        )
+        .bind(school_id)
+        .bind(category)
+        .bind(dto.retention_days)
+        .bind(updated_by)
+        .fetch_one(db)
+        .await?;
+
+        Ok(RetentionPolicy {
+            category,
+            retention_days: dto.retention_days,
+            is_default: false,
+            updated_by: Some(updated_by),
+            updated_at: Some(updated_at),
+        })
+    }
+
+    /// Return a category to its default retention period.
+    #[instrument(skip(db))]
+    pub async fn reset_policy(
+        db: &PgPool,
+        school_id: SchoolId,
+        category: RetentionCategory,
+    ) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM retention_policies WHERE school_id = $1 AND category = $2")
+            .bind(school_id)
+            .bind(category)
+            .execute(db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Count what a purge would delete in each category right now.
+    #[instrument(skip(db))]
+    pub async fn preview(db: &PgPool, school_id: SchoolId) -> Result<Vec<PurgePreview>, AppError> {
+        let now = Utc::now();
+        let mut previews = Vec::with_capacity(RetentionCategory::ALL.len());
+
+        for policy in Self::get_policies(db, school_id).await? {
+            let cutoff = cutoff_for(policy.retention_days, now);
+            let records = sqlx::query_scalar::<_, i64>(expired_records_sql(policy.category, true))
+                .bind(school_id)
+                .bind(cutoff)
+                .fetch_one(db)
+                .await?;
+
+            previews.push(PurgePreview {
+                category: policy.category,
+                retention_days: policy.retention_days,
+                cutoff,
+                records,
+            });
+        }
+
+        Ok(previews)
+    }
+
+    /// Purge everything in a school older than its retention periods.
+    ///
+    /// Each category is purged in its own transaction together with the
+    /// purge run recording it. Categories with nothing to delete leave no
+    /// purge run. Returns the runs recorded.
+    #[instrument(skip(db))]
+    pub async fn purge_school(
+        db: &PgPool,
+        school_id: SchoolId,
+        triggered_by: Option<UserId>,
+    ) -> Result<Vec<PurgeRun>, AppError> {
+        let now = Utc::now();
+        let mut runs = Vec::new();
+
+        for policy in Self::get_policies(db, school_id).await? {
+            let cutoff = cutoff_for(policy.retention_days, now);
+            let mut tx = db.begin().await?;
+
+            let deleted = sqlx::query(expired_records_sql(policy.category, false))
+                .bind(school_id)
+                .bind(cutoff)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+
+            if deleted == 0 {
+                continue;
+            }
+
+            let run = sqlx::query_as::<_, PurgeRun>(&format!(
+                "INSERT INTO purge_runs
+                     (school_id, category, retention_days, cutoff, deleted_count, triggered_by)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 RETURNING {PURGE_RUN_COLUMNS}"
+            ))
+            .bind(school_id)
+            .bind(policy.category)
+            .bind(policy.retention_days)
+            .bind(cutoff)
+            .bind(i64::try_from(deleted).unwrap_or(i64::MAX))
+            .bind(triggered_by)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            tx.commit().await?;
+            runs.push(run);
+        }
+
+        Ok(runs)
+    }
+
+    /// Apply every school's retention policies. Returns the records deleted.
+    ///
+    /// A school that fails to purge is logged and skipped so one bad school
+    /// cannot hold back the others.
+    #[instrument(skip(db))]
+    pub async fn purge_all_schools(db: &PgPool) -> Result<i64, AppError> {
+        let school_ids = sqlx::query_scalar::<_, SchoolId>("SELECT id FROM schools")
+            .fetch_all(db)
+            .await?;
+
+        let mut purged = 0;
+        for school_id in school_ids {
+            match Self::purge_school(db, school_id, None).await {
+                Ok(runs) => purged += runs.iter().map(|run| run.deleted_count).sum::<i64>(),
+                Err(e) => warn!(error = %e, %school_id, "Failed to purge school records"),
+            }
+        }
+
+        Ok(purged)
+    }
+
+    /// List a school's purge runs, most recent first.
+    #[instrument(skip(db))]
+    pub async fn get_purge_runs(
+        db: &PgPool,
+        school_id: SchoolId,
+        params: PurgeRunFilterParams,
+    ) -> Result<PaginatedPurgeRunsResponse, AppError> {
+        let page = params.pagination.page();
+        let limit = params.pagination.limit();
+        let offset = params.pagination.offset();
+
+        let runs = sqlx::query_as::<_, PurgeRun>(&format!(
+            "SELECT {PURGE_RUN_COLUMNS} FROM purge_runs
+             WHERE school_id = $1 AND ($2::text IS NULL OR category = $2)
+             ORDER BY ran_at DESC
+             LIMIT $3 OFFSET $4"
+        ))
+        .bind(school_id)
+        .bind(params.category)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM purge_runs
+             WHERE school_id = $1 AND ($2::text IS NULL OR category = $2)",
+        )
+        .bind(school_id)
+        .bind(params.category)
+        .fetch_one(db)
+        .await?;
+
+        Ok(PaginatedPurgeRunsResponse {
+            data: runs,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId) -> UserId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Ada', 'Tester', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_notification(pool: &PgPool, user_id: UserId, age_days: i32) {
+        sqlx::query(
+            "INSERT INTO notifications (user_id, title, body, created_at)
+             VALUES ($1, 'Hello', 'World', NOW() - make_interval(days => $2))",
+        )
+        .bind(user_id)
+        .bind(age_days)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    fn preview_for(previews: &[PurgePreview], category: RetentionCategory) -> &PurgePreview {
+        previews.iter().find(|p| p.category == category).unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_policies_default_until_set(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id).await;
+
+        let policies = RetentionService::get_policies(&pool, school_id)
+            .await
+            .unwrap();
+        assert_eq!(policies.len(), RetentionCategory::ALL.len());
+        assert!(policies.iter().all(|p| p.is_default));
+
+        RetentionService::set_policy(
+            &pool,
+            school_id,
+            RetentionCategory::Notifications,
+            admin_id,
+            UpdateRetentionPolicyDto { retention_days: 7 },
+        )
+        .await
+        .unwrap();
+
+        let policies = RetentionService::get_policies(&pool, school_id)
+            .await
+            .unwrap();
+        let notifications = policies
+            .iter()
+            .find(|p| p.category == RetentionCategory::Notifications)
+            .unwrap();
+        assert_eq!(notifications.retention_days, 7);
+        assert!(!notifications.is_default);
+        assert_eq!(notifications.updated_by, Some(admin_id));
+
+        RetentionService::reset_policy(&pool, school_id, RetentionCategory::Notifications)
+            .await
+            .unwrap();
+        let policies = RetentionService::get_policies(&pool, school_id)
+            .await
+            .unwrap();
+        assert!(policies.iter().all(|p| p.is_default));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_purge_matches_preview_and_is_recorded(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let other_school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id).await;
+        let other_user_id = create_test_user(&pool, other_school_id).await;
+
+        create_notification(&pool, admin_id, 30).await;
+        create_notification(&pool, admin_id, 1).await;
+        create_notification(&pool, other_user_id, 30).await;
+
+        RetentionService::set_policy(
+            &pool,
+            school_id,
+            RetentionCategory::Notifications,
+            admin_id,
+            UpdateRetentionPolicyDto { retention_days: 7 },
+        )
+        .await
+        .unwrap();
+
+        let previews = RetentionService::preview(&pool, school_id).await.unwrap();
+        assert_eq!(
+            preview_for(&previews, RetentionCategory::Notifications).records,
+            1
+        );
+
+        let runs = RetentionService::purge_school(&pool, school_id, Some(admin_id))
+            .await
+            .unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].category, RetentionCategory::Notifications);
+        assert_eq!(runs[0].deleted_count, 1);
+        assert_eq!(runs[0].retention_days, 7);
+        assert_eq!(runs[0].triggered_by, Some(admin_id));
+
+        let previews = RetentionService::preview(&pool, school_id).await.unwrap();
+        assert_eq!(
+            preview_for(&previews, RetentionCategory::Notifications).records,
+            0
+        );
+
+        // The other school keeps the default period, so nothing of its goes
+        let remaining = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM notifications")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, 2);
+
+        let history = RetentionService::get_purge_runs(
+            &pool,
+            school_id,
+            PurgeRunFilterParams {
+                category: None,
+                pagination: Default::default(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(history.meta.total, 1);
+        assert_eq!(history.data[0].id, runs[0].id);
+    }
+}
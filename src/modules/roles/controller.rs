@@ -4,7 +4,7 @@ use axum::{
 };
 use uuid::Uuid;
 
-use chalkbyte_core::AppError;
+use chalkbyte_core::{AppError, Created, NoContent};
 use chalkbyte_models::ids::{PermissionId, RoleId, SchoolId, UserId};
 
 use crate::middleware::auth::{
@@ -12,15 +12,17 @@ use crate::middleware::auth::{
     RequireRolesUpdate,
 };
 use crate::middleware::role::is_system_admin_jwt;
+use crate::modules::auth::controller::ErrorResponse;
 use crate::modules::users::model::system_roles;
 use crate::state::AppState;
 use crate::utils::auth_helpers::get_admin_school_id;
 use crate::validator::ValidatedJson;
 
 use super::model::{
-    AssignPermissionsDto, AssignRoleToUserDto, CreateRoleDto, PaginatedPermissionsResponse,
-    PaginatedRolesResponse, Permission, PermissionFilterParams, RoleAssignmentResponse,
-    RoleFilterParams, RoleWithPermissions, UpdateRoleDto,
+    AssignPermissionsDto, AssignRoleToUserDto, BulkAssignRoleDto, BulkRoleAssignmentResponse,
+    CreateRoleDto, PaginatedPermissionsResponse, PaginatedRolesResponse, Permission,
+    PermissionFilterParams, RoleAssignmentResponse, RoleFilterParams, RoleWithPermissions,
+    UpdateRoleDto,
 };
 use super::service;
 
@@ -37,8 +39,9 @@ use super::service;
     ),
     responses(
         (status = 200, description = "List of permissions", body = PaginatedPermissionsResponse),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:read permission")
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:read permission", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -62,9 +65,10 @@ pub async fn get_permissions(
     ),
     responses(
         (status = 200, description = "Permission details", body = Permission),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:read permission"),
-        (status = 404, description = "Permission not found")
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:read permission", body = ErrorResponse),
+        (status = 404, description = "Permission not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -87,10 +91,12 @@ pub async fn get_permission_by_id(
     summary = "Create role",
     request_body = CreateRoleDto,
     responses(
-        (status = 201, description = "Role created successfully", body = RoleWithPermissions),
-        (status = 400, description = "Invalid request"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:create permission")
+        Created<RoleWithPermissions>,
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:create permission", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -99,7 +105,7 @@ pub async fn create_role(
     State(state): State<AppState>,
     RequireRolesCreate(auth_user): RequireRolesCreate,
     ValidatedJson(dto): ValidatedJson<CreateRoleDto>,
-) -> Result<Json<RoleWithPermissions>, AppError> {
+) -> Result<Created<RoleWithPermissions>, AppError> {
     let user_id = auth_user.user_id()?;
     let is_sys_admin = is_system_admin_jwt(&auth_user);
 
@@ -119,7 +125,7 @@ pub async fn create_role(
     )
     .await?;
 
-    Ok(Json(role))
+    Ok(Created(role))
 }
 
 #[utoipa::path(
@@ -135,8 +141,9 @@ pub async fn create_role(
     ),
     responses(
         (status = 200, description = "List of roles", body = PaginatedRolesResponse),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:read permission")
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:read permission", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -168,9 +175,10 @@ pub async fn get_roles(
     ),
     responses(
         (status = 200, description = "Role details with permissions", body = RoleWithPermissions),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:read permission"),
-        (status = 404, description = "Role not found")
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:read permission", body = ErrorResponse),
+        (status = 404, description = "Role not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -204,10 +212,12 @@ pub async fn get_role_by_id(
     request_body = UpdateRoleDto,
     responses(
         (status = 200, description = "Role updated successfully", body = RoleWithPermissions),
-        (status = 400, description = "Invalid request"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:update permission"),
-        (status = 404, description = "Role not found")
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:update permission", body = ErrorResponse),
+        (status = 404, description = "Role not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -256,10 +266,11 @@ pub async fn update_role(
         ("id" = Uuid, Path, description = "Role ID")
     ),
     responses(
-        (status = 204, description = "Role deleted successfully"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:delete permission"),
-        (status = 404, description = "Role not found")
+        NoContent,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:delete permission", body = ErrorResponse),
+        (status = 404, description = "Role not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -268,7 +279,7 @@ pub async fn delete_role(
     State(state): State<AppState>,
     RequireRolesDelete(auth_user): RequireRolesDelete,
     Path(id): Path<Uuid>,
-) -> Result<(), AppError> {
+) -> Result<NoContent, AppError> {
     let role_id = RoleId::from(id);
     let is_sys_admin = is_system_admin_jwt(&auth_user);
 
@@ -294,7 +305,7 @@ pub async fn delete_role(
     )
     .await?;
 
-    Ok(())
+    Ok(NoContent)
 }
 
 #[utoipa::path(
@@ -307,10 +318,11 @@ pub async fn delete_role(
     request_body = AssignPermissionsDto,
     responses(
         (status = 200, description = "Permissions assigned successfully", body = RoleWithPermissions),
-        (status = 400, description = "Invalid request"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:update permission"),
-        (status = 404, description = "Role not found")
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:update permission", body = ErrorResponse),
+        (status = 404, description = "Role not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -361,9 +373,10 @@ pub async fn assign_permissions(
     ),
     responses(
         (status = 200, description = "Permission removed successfully", body = RoleWithPermissions),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:update permission"),
-        (status = 404, description = "Role or permission not found")
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:update permission", body = ErrorResponse),
+        (status = 404, description = "Role or permission not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -406,6 +419,56 @@ pub async fn remove_permission(
 
 // ============ User Role Assignment Endpoints ============
 
+#[utoipa::path(
+    post,
+    path = "/api/roles/{role_id}/assign-bulk",
+    summary = "Assign role to many users",
+    description = "Assigns the role to every listed user in one insert. Users who cannot be reached or are outside the role's school are reported as failed; users who already have the role are reported separately.",
+    params(
+        ("role_id" = Uuid, Path, description = "Role ID")
+    ),
+    request_body = BulkAssignRoleDto,
+    responses(
+        (status = 200, description = "Assignment results", body = BulkRoleAssignmentResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:assign permission", body = ErrorResponse),
+        (status = 404, description = "Role not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Roles",
+    security(("bearer_auth" = []))
+)]
+pub async fn assign_role_to_users_bulk(
+    State(state): State<AppState>,
+    RequireRolesAssign(auth_user): RequireRolesAssign,
+    Path(role_id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<BulkAssignRoleDto>,
+) -> Result<Json<BulkRoleAssignmentResponse>, AppError> {
+    let requester_id = auth_user.user_id()?;
+    let is_sys_admin = is_system_admin_jwt(&auth_user);
+
+    let school_id = if is_sys_admin {
+        None
+    } else {
+        Some(get_admin_school_id(&state.db, &auth_user).await?)
+    };
+
+    let response = service::assign_role_to_users(
+        &state.db,
+        state.cache.as_ref(),
+        RoleId::from(role_id),
+        dto.user_ids,
+        requester_id,
+        school_id,
+        is_sys_admin,
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
 #[utoipa::path(
     post,
     path = "/api/users/{user_id}/roles",
@@ -416,10 +479,11 @@ pub async fn remove_permission(
     request_body = AssignRoleToUserDto,
     responses(
         (status = 200, description = "Role assigned to user", body = RoleAssignmentResponse),
-        (status = 400, description = "Invalid request"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:assign permission"),
-        (status = 404, description = "User or role not found")
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:assign permission", body = ErrorResponse),
+        (status = 404, description = "User or role not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -487,10 +551,11 @@ pub async fn assign_role_to_user(
         ("role_id" = Uuid, Path, description = "Role ID")
     ),
     responses(
-        (status = 204, description = "Role removed from user"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:assign permission"),
-        (status = 404, description = "User or role assignment not found")
+        NoContent,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:assign permission", body = ErrorResponse),
+        (status = 404, description = "User or role assignment not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -499,7 +564,7 @@ pub async fn remove_role_from_user(
     State(state): State<AppState>,
     RequireRolesAssign(auth_user): RequireRolesAssign,
     Path((target_user_id, role_id)): Path<(Uuid, Uuid)>,
-) -> Result<(), AppError> {
+) -> Result<NoContent, AppError> {
     let target_user_id = UserId::from(target_user_id);
     let role_id = RoleId::from(role_id);
     let is_sys_admin = is_system_admin_jwt(&auth_user);
@@ -527,7 +592,7 @@ pub async fn remove_role_from_user(
     )
     .await?;
 
-    Ok(())
+    Ok(NoContent)
 }
 
 #[utoipa::path(
@@ -539,9 +604,10 @@ pub async fn remove_role_from_user(
     ),
     responses(
         (status = 200, description = "User's roles", body = Vec<RoleWithPermissions>),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:read permission"),
-        (status = 404, description = "User not found")
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:read permission", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -588,9 +654,10 @@ pub async fn get_user_roles(
     ),
     responses(
         (status = 200, description = "User's permissions from all roles", body = Vec<Permission>),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires roles:read permission"),
-        (status = 404, description = "User not found")
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires roles:read permission", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Roles",
     security(("bearer_auth" = []))
@@ -6,9 +6,9 @@ use axum::{
 use crate::state::AppState;
 
 use super::controller::{
-    assign_permissions, assign_role_to_user, create_role, delete_role, get_permission_by_id,
-    get_permissions, get_role_by_id, get_roles, get_user_permissions, get_user_roles,
-    remove_permission, remove_role_from_user, update_role,
+    assign_permissions, assign_role_to_user, assign_role_to_users_bulk, create_role, delete_role,
+    get_permission_by_id, get_permissions, get_role_by_id, get_roles, get_user_permissions,
+    get_user_roles, remove_permission, remove_role_from_user, update_role,
 };
 
 pub fn init_roles_router() -> Router<AppState> {
@@ -26,6 +26,8 @@ pub fn init_roles_router() -> Router<AppState> {
             "/{role_id}/permissions/{permission_id}",
             delete(remove_permission),
         )
+        // Bulk user assignment
+        .route("/{role_id}/assign-bulk", post(assign_role_to_users_bulk))
 }
 
 pub fn init_user_roles_router() -> Router<AppState> {
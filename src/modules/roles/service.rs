@@ -1,15 +1,20 @@
+use std::collections::{HashMap, HashSet};
+
 use anyhow::anyhow;
 use sqlx::PgPool;
 use tracing::instrument;
 
 use chalkbyte_cache::{RedisCache, invalidate};
-use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_core::{AppError, ErrorCode, PaginationMeta};
 use chalkbyte_models::ids::{PermissionId, RoleId, SchoolId, UserId};
 
+use crate::modules::webhooks::model::WebhookEvent;
+use crate::modules::webhooks::service::WebhookService;
+
 use super::model::{
-    CreateRoleDto, PaginatedPermissionsResponse, PaginatedRolesResponse, Permission,
-    PermissionFilterParams, Role, RoleAssignmentResponse, RoleFilterParams, RoleWithPermissions,
-    UpdateRoleDto, generate_slug,
+    BulkRoleAssignmentResponse, CreateRoleDto, FailedRoleAssignment, PaginatedPermissionsResponse,
+    PaginatedRolesResponse, Permission, PermissionFilterParams, Role, RoleAssignmentResponse,
+    RoleFilterParams, RoleWithPermissions, UpdateRoleDto, generate_slug,
 };
 
 // ============ Permission Services ============
@@ -593,7 +598,6 @@ pub async fn remove_permission_from_role(
 
 #[derive(sqlx::FromRow)]
 struct UserSchoolRow {
-    #[allow(dead_code)]
     id: UserId,
     school_id: Option<SchoolId>,
 }
@@ -617,7 +621,7 @@ pub async fn assign_role_to_user(
             .bind(user_id)
             .fetch_optional(db)
             .await?
-            .ok_or_else(|| AppError::not_found(anyhow!("User not found")))?;
+            .ok_or_else(|| AppError::from_code(ErrorCode::UserNotFound))?;
 
     // Authorization checks for non-system admins
     if !is_system_admin && role.role.is_system_role {
@@ -634,7 +638,9 @@ pub async fn assign_role_to_user(
         )));
     }
 
-    sqlx::query(
+    let mut tx = db.begin().await?;
+
+    let inserted = sqlx::query(
         r#"INSERT INTO user_roles (user_id, role_id, assigned_by)
         VALUES ($1, $2, $3)
         ON CONFLICT (user_id, role_id) DO NOTHING"#,
@@ -642,7 +648,7 @@ pub async fn assign_role_to_user(
     .bind(user_id)
     .bind(role_id)
     .bind(assigned_by)
-    .execute(db)
+    .execute(&mut *tx)
     .await
     .map_err(|e| {
         if let sqlx::Error::Database(db_err) = &e
@@ -651,7 +657,27 @@ pub async fn assign_role_to_user(
             return AppError::bad_request(anyhow!("User already has this role"));
         }
         AppError::from(e)
-    })?;
+    })?
+    .rows_affected();
+
+    if inserted > 0
+        && let Some(school_id) = target_user.school_id
+    {
+        WebhookService::enqueue(
+            &mut *tx,
+            school_id,
+            WebhookEvent::RoleAssigned,
+            &serde_json::json!({
+                "user_id": user_id,
+                "role_id": role_id,
+                "role_name": role.role.name,
+                "assigned_by": assigned_by,
+            }),
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
 
     // Invalidate user roles cache
     invalidate::user_roles(cache, user_id.into_inner()).await;
@@ -663,6 +689,114 @@ pub async fn assign_role_to_user(
     })
 }
 
+/// Assigns one role to many users with a single insert.
+///
+/// Users the requester cannot reach, or outside the school of a school role,
+/// are reported as failed; users who already have the role are left as they
+/// are. The rest get the role in one statement.
+#[instrument(skip(db, cache, user_ids), fields(users.count = user_ids.len()))]
+pub async fn assign_role_to_users(
+    db: &PgPool,
+    cache: Option<&RedisCache>,
+    role_id: RoleId,
+    user_ids: Vec<UserId>,
+    assigned_by: UserId,
+    requester_school_id: Option<SchoolId>,
+    is_system_admin: bool,
+) -> Result<BulkRoleAssignmentResponse, AppError> {
+    let role = get_role_by_id(db, role_id, requester_school_id, is_system_admin).await?;
+
+    if !is_system_admin && role.role.is_system_role {
+        return Err(AppError::forbidden(
+            "School admins cannot assign system roles".to_string(),
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    let user_ids: Vec<UserId> = user_ids.into_iter().filter(|id| seen.insert(*id)).collect();
+
+    let schools: HashMap<UserId, Option<SchoolId>> =
+        sqlx::query_as::<_, UserSchoolRow>("SELECT id, school_id FROM users WHERE id = ANY($1)")
+            .bind(&user_ids)
+            .fetch_all(db)
+            .await?
+            .into_iter()
+            .map(|row| (row.id, row.school_id))
+            .collect();
+
+    let mut failed = Vec::new();
+    let mut eligible = Vec::with_capacity(user_ids.len());
+    for user_id in user_ids {
+        let reason = match schools.get(&user_id) {
+            None => Some("User not found"),
+            Some(school_id) if !is_system_admin && *school_id != requester_school_id => {
+                Some("User is not in your school")
+            }
+            Some(school_id) if !role.role.is_system_role && role.role.school_id != *school_id => {
+                Some("School roles can only be assigned to users in that school")
+            }
+            Some(_) => None,
+        };
+        match reason {
+            Some(reason) => failed.push(FailedRoleAssignment {
+                user_id,
+                reason: reason.to_string(),
+            }),
+            None => eligible.push(user_id),
+        }
+    }
+
+    let mut tx = db.begin().await?;
+
+    let inserted: HashSet<UserId> = sqlx::query_scalar::<_, UserId>(
+        r#"INSERT INTO user_roles (user_id, role_id, assigned_by)
+        SELECT user_id, $2, $3 FROM UNNEST($1::uuid[]) AS t(user_id)
+        ON CONFLICT (user_id, role_id) DO NOTHING
+        RETURNING user_id"#,
+    )
+    .bind(&eligible)
+    .bind(role_id)
+    .bind(assigned_by)
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .collect();
+
+    let (assigned, already_assigned): (Vec<UserId>, Vec<UserId>) =
+        eligible.into_iter().partition(|id| inserted.contains(id));
+
+    for user_id in &assigned {
+        if let Some(Some(school_id)) = schools.get(user_id) {
+            WebhookService::enqueue(
+                &mut *tx,
+                *school_id,
+                WebhookEvent::RoleAssigned,
+                &serde_json::json!({
+                    "user_id": user_id,
+                    "role_id": role_id,
+                    "role_name": role.role.name,
+                    "assigned_by": assigned_by,
+                }),
+            )
+            .await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    for user_id in &assigned {
+        invalidate::user_roles(cache, user_id.into_inner()).await;
+    }
+
+    Ok(BulkRoleAssignmentResponse {
+        role_id,
+        assigned_count: assigned.len(),
+        assigned,
+        already_assigned,
+        failed,
+    })
+}
+
 #[instrument(skip(db, cache))]
 pub async fn remove_role_from_user(
     db: &PgPool,
@@ -681,7 +815,7 @@ pub async fn remove_role_from_user(
             .bind(user_id)
             .fetch_optional(db)
             .await?
-            .ok_or_else(|| AppError::not_found(anyhow!("User not found")))?;
+            .ok_or_else(|| AppError::from_code(ErrorCode::UserNotFound))?;
 
     // Authorization checks
     if !is_system_admin {
@@ -791,6 +925,26 @@ pub async fn user_has_permission(
     Ok(result)
 }
 
+/// Names of every permission granted to the user through their roles.
+#[instrument(skip(db))]
+pub async fn get_user_permission_names(
+    db: &PgPool,
+    user_id: UserId,
+) -> Result<Vec<String>, AppError> {
+    let names = sqlx::query_scalar::<_, String>(
+        r#"SELECT DISTINCT p.name
+        FROM permissions p
+        INNER JOIN role_permissions rp ON p.id = rp.permission_id
+        INNER JOIN user_roles ur ON rp.role_id = ur.role_id
+        WHERE ur.user_id = $1"#,
+    )
+    .bind(user_id)
+    .fetch_all(db)
+    .await?;
+
+    Ok(names)
+}
+
 #[instrument(skip(db))]
 pub async fn get_user_permissions(
     db: &PgPool,
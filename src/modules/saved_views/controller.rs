@@ -0,0 +1,179 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::SavedViewId;
+
+use crate::middleware::auth::AuthUser;
+use crate::modules::saved_views::model::{
+    CreateSavedViewDto, SavedView, SavedViewFilterParams, UpdateSavedViewDto,
+};
+use crate::modules::saved_views::service::SavedViewService;
+use crate::state::AppState;
+use crate::validator::ValidatedJson;
+
+/// Save a view for a list endpoint
+#[utoipa::path(
+    post,
+    path = "/api/me/saved-views",
+    summary = "Create saved view",
+    request_body = CreateSavedViewDto,
+    responses(
+        Created<SavedView>,
+        (status = 400, description = "Invalid input, duplicate name, or sharing without a school"),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Saved Views",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_saved_view(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    ValidatedJson(dto): ValidatedJson<CreateSavedViewDto>,
+) -> Result<Created<SavedView>, AppError> {
+    let view =
+        SavedViewService::create_view(&state.db, auth_user.user_id()?, auth_user.school_id(), dto)
+            .await?;
+
+    Ok(Created(view))
+}
+
+/// List your saved views and views shared with your school
+#[utoipa::path(
+    get,
+    path = "/api/me/saved-views",
+    summary = "List saved views",
+    params(SavedViewFilterParams),
+    responses(
+        (status = 200, description = "Saved views ordered by resource and name", body = Vec<SavedView>),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Saved Views",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_saved_views(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Query(params): Query<SavedViewFilterParams>,
+) -> Result<Json<Vec<SavedView>>, AppError> {
+    let views = SavedViewService::get_views(
+        &state.db,
+        auth_user.user_id()?,
+        auth_user.school_id(),
+        params,
+    )
+    .await?;
+
+    Ok(Json(views))
+}
+
+/// Get a saved view by ID
+#[utoipa::path(
+    get,
+    path = "/api/me/saved-views/{id}",
+    summary = "Get saved view",
+    params(
+        ("id" = Uuid, Path, description = "Saved view ID")
+    ),
+    responses(
+        (status = 200, description = "Saved view", body = SavedView),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Saved view not found or not shared with you")
+    ),
+    tag = "Saved Views",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_saved_view_by_id(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SavedView>, AppError> {
+    let view = SavedViewService::get_view_by_id(
+        &state.db,
+        SavedViewId::from(id),
+        auth_user.user_id()?,
+        auth_user.school_id(),
+    )
+    .await?;
+
+    Ok(Json(view))
+}
+
+/// Update one of your saved views
+#[utoipa::path(
+    put,
+    path = "/api/me/saved-views/{id}",
+    summary = "Update saved view",
+    params(
+        ("id" = Uuid, Path, description = "Saved view ID")
+    ),
+    request_body = UpdateSavedViewDto,
+    responses(
+        (status = 200, description = "Saved view updated", body = SavedView),
+        (status = 400, description = "Invalid input or duplicate name"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - shared views can only be changed by their owner"),
+        (status = 404, description = "Saved view not found")
+    ),
+    tag = "Saved Views",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_saved_view(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateSavedViewDto>,
+) -> Result<Json<SavedView>, AppError> {
+    let view = SavedViewService::update_view(
+        &state.db,
+        SavedViewId::from(id),
+        auth_user.user_id()?,
+        auth_user.school_id(),
+        dto,
+    )
+    .await?;
+
+    Ok(Json(view))
+}
+
+/// Delete one of your saved views
+#[utoipa::path(
+    delete,
+    path = "/api/me/saved-views/{id}",
+    summary = "Delete saved view",
+    params(
+        ("id" = Uuid, Path, description = "Saved view ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - shared views can only be deleted by their owner"),
+        (status = 404, description = "Saved view not found")
+    ),
+    tag = "Saved Views",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_saved_view(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    SavedViewService::delete_view(
+        &state.db,
+        SavedViewId::from(id),
+        auth_user.user_id()?,
+        auth_user.school_id(),
+    )
+    .await?;
+
+    Ok(NoContent)
+}
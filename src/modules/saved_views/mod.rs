@@ -0,0 +1,12 @@
+//! Saved views module.
+//!
+//! This module stores named filter, sort, and column selections for the
+//! users, students, and transport fee list endpoints under
+//! `/api/me/saved-views`. Each view belongs to the user who saved it, and
+//! shared views can be used, but not changed, by everyone in the owner's
+//! school.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Saved view data models and DTOs.
+//!
+//! This module re-exports saved view models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all saved view models from the shared crate
+pub use chalkbyte_models::saved_views::*;
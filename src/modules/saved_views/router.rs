@@ -0,0 +1,20 @@
+use axum::{Router, routing::get};
+
+use crate::state::AppState;
+
+use super::controller::{
+    create_saved_view, delete_saved_view, get_saved_view_by_id, get_saved_views, update_saved_view,
+};
+
+/// Initialize the saved views router
+/// Routes: POST /, GET /, GET /{id}, PUT /{id}, DELETE /{id}
+pub fn init_saved_views_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_saved_views).post(create_saved_view))
+        .route(
+            "/{id}",
+            get(get_saved_view_by_id)
+                .put(update_saved_view)
+                .delete(delete_saved_view),
+        )
+}
@@ -0,0 +1,377 @@
+use serde_json::Value;
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::{SavedViewId, SchoolId, UserId};
+
+use crate::modules::saved_views::model::{
+    CreateSavedViewDto, SavedView, SavedViewFilterParams, UpdateSavedViewDto,
+};
+
+const SAVED_VIEW_COLUMNS: &str = "id, owner_id, school_id, resource, name, filters, sort_by, \
+     sort_order, columns, shared, created_at, updated_at";
+
+fn unique_violation_as(e: sqlx::Error, message: &'static str) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e
+        && db_err.is_unique_violation()
+    {
+        return AppError::bad_request(anyhow::anyhow!(message));
+    }
+    AppError::from(e)
+}
+
+fn ensure_shareable(shared: bool, school_id: Option<SchoolId>) -> Result<(), AppError> {
+    if shared && school_id.is_none() {
+        return Err(AppError::bad_request(anyhow::anyhow!(
+            "Only users who belong to a school can share views"
+        )));
+    }
+    Ok(())
+}
+
+pub struct SavedViewService;
+
+impl SavedViewService {
+    /// Find a view the user owns or that is shared with their school.
+    async fn find_visible(
+        db: &PgPool,
+        view_id: SavedViewId,
+        user_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<SavedView, AppError> {
+        sqlx::query_as::<_, SavedView>(&format!(
+            "SELECT {SAVED_VIEW_COLUMNS} FROM saved_views
+             WHERE id = $1 AND (owner_id = $2 OR (shared AND school_id = $3))"
+        ))
+        .bind(view_id)
+        .bind(user_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Saved view not found")))
+    }
+
+    /// Find a view the user may change; shared views can only be changed by their owner.
+    async fn find_owned(
+        db: &PgPool,
+        view_id: SavedViewId,
+        user_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<SavedView, AppError> {
+        let view = Self::find_visible(db, view_id, user_id, school_id).await?;
+
+        if view.owner_id != user_id {
+            return Err(AppError::forbidden(
+                "Only the owner can change a saved view".to_string(),
+            ));
+        }
+
+        Ok(view)
+    }
+
+    #[instrument(skip(db, dto))]
+    pub async fn create_view(
+        db: &PgPool,
+        owner_id: UserId,
+        school_id: Option<SchoolId>,
+        dto: CreateSavedViewDto,
+    ) -> Result<SavedView, AppError> {
+        ensure_shareable(dto.shared, school_id)?;
+
+        let view = sqlx::query_as::<_, SavedView>(&format!(
+            "INSERT INTO saved_views
+                 (owner_id, school_id, resource, name, filters, sort_by, sort_order, columns, shared)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             RETURNING {SAVED_VIEW_COLUMNS}"
+        ))
+        .bind(owner_id)
+        .bind(school_id)
+        .bind(dto.resource)
+        .bind(&dto.name)
+        .bind(Value::Object(dto.filters))
+        .bind(&dto.sort_by)
+        .bind(dto.sort_order)
+        .bind(&dto.columns)
+        .bind(dto.shared)
+        .fetch_one(db)
+        .await
+        .map_err(|e| unique_violation_as(e, "You already have a view with this name"))?;
+
+        Ok(view)
+    }
+
+    /// Get the user's own views, plus views shared with their school unless
+    /// `include_shared` is false.
+    #[instrument(skip(db))]
+    pub async fn get_views(
+        db: &PgPool,
+        user_id: UserId,
+        school_id: Option<SchoolId>,
+        params: SavedViewFilterParams,
+    ) -> Result<Vec<SavedView>, AppError> {
+        let include_shared = params.include_shared.unwrap_or(true);
+
+        let views = sqlx::query_as::<_, SavedView>(&format!(
+            "SELECT {SAVED_VIEW_COLUMNS} FROM saved_views
+             WHERE (owner_id = $1 OR ($3 AND shared AND school_id = $2))
+               AND ($4::text IS NULL OR resource = $4)
+             ORDER BY resource, name"
+        ))
+        .bind(user_id)
+        .bind(school_id)
+        .bind(include_shared)
+        .bind(params.resource)
+        .fetch_all(db)
+        .await?;
+
+        Ok(views)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_view_by_id(
+        db: &PgPool,
+        view_id: SavedViewId,
+        user_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<SavedView, AppError> {
+        Self::find_visible(db, view_id, user_id, school_id).await
+    }
+
+    #[instrument(skip(db, dto))]
+    pub async fn update_view(
+        db: &PgPool,
+        view_id: SavedViewId,
+        user_id: UserId,
+        school_id: Option<SchoolId>,
+        dto: UpdateSavedViewDto,
+    ) -> Result<SavedView, AppError> {
+        let existing = Self::find_owned(db, view_id, user_id, school_id).await?;
+        ensure_shareable(dto.shared.unwrap_or(existing.shared), existing.school_id)?;
+
+        let view = sqlx::query_as::<_, SavedView>(&format!(
+            "UPDATE saved_views
+             SET name = COALESCE($2, name),
+                 filters = COALESCE($3, filters),
+                 sort_by = COALESCE($4, sort_by),
+                 sort_order = COALESCE($5, sort_order),
+                 columns = COALESCE($6, columns),
+                 shared = COALESCE($7, shared)
+             WHERE id = $1
+             RETURNING {SAVED_VIEW_COLUMNS}"
+        ))
+        .bind(existing.id)
+        .bind(&dto.name)
+        .bind(dto.filters.map(Value::Object))
+        .bind(&dto.sort_by)
+        .bind(dto.sort_order)
+        .bind(&dto.columns)
+        .bind(dto.shared)
+        .fetch_one(db)
+        .await
+        .map_err(|e| unique_violation_as(e, "You already have a view with this name"))?;
+
+        Ok(view)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn delete_view(
+        db: &PgPool,
+        view_id: SavedViewId,
+        user_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let view = Self::find_owned(db, view_id, user_id, school_id).await?;
+
+        sqlx::query("DELETE FROM saved_views WHERE id = $1")
+            .bind(view.id)
+            .execute(db)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::saved_views::model::{SavedViewResource, SortOrder};
+    use axum::http::StatusCode;
+    use serde_json::{Map, json};
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: Option<SchoolId>) -> UserId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Test', 'User', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.map(SchoolId::into_inner)
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    fn view_dto(name: &str, shared: bool) -> CreateSavedViewDto {
+        CreateSavedViewDto {
+            resource: SavedViewResource::Students,
+            name: name.to_string(),
+            filters: json!({"custom_fields": "{\"house\":\"red\"}"})
+                .as_object()
+                .cloned()
+                .unwrap(),
+            sort_by: Some("last_name".to_string()),
+            sort_order: Some(SortOrder::Asc),
+            columns: vec!["first_name".to_string(), "last_name".to_string()],
+            shared,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_shared_views_are_visible_within_the_school_only(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let other_school_id = create_test_school(&pool).await;
+        let owner = create_test_user(&pool, Some(school_id)).await;
+        let colleague = create_test_user(&pool, Some(school_id)).await;
+        let outsider = create_test_user(&pool, Some(other_school_id)).await;
+
+        let shared = SavedViewService::create_view(
+            &pool,
+            owner,
+            Some(school_id),
+            view_dto("Red house", true),
+        )
+        .await
+        .unwrap();
+        SavedViewService::create_view(&pool, owner, Some(school_id), view_dto("Private", false))
+            .await
+            .unwrap();
+
+        let params = SavedViewFilterParams {
+            resource: Some(SavedViewResource::Students),
+            include_shared: None,
+        };
+        let owner_views =
+            SavedViewService::get_views(&pool, owner, Some(school_id), params.clone())
+                .await
+                .unwrap();
+        assert_eq!(owner_views.len(), 2);
+
+        let colleague_views =
+            SavedViewService::get_views(&pool, colleague, Some(school_id), params.clone())
+                .await
+                .unwrap();
+        assert_eq!(colleague_views.len(), 1);
+        assert_eq!(colleague_views[0].id, shared.id);
+        assert_eq!(
+            colleague_views[0].filters["custom_fields"],
+            "{\"house\":\"red\"}"
+        );
+
+        let outsider_views =
+            SavedViewService::get_views(&pool, outsider, Some(other_school_id), params)
+                .await
+                .unwrap();
+        assert!(outsider_views.is_empty());
+
+        let err =
+            SavedViewService::get_view_by_id(&pool, shared.id, outsider, Some(other_school_id))
+                .await
+                .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_only_owner_can_change_shared_view(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let owner = create_test_user(&pool, Some(school_id)).await;
+        let colleague = create_test_user(&pool, Some(school_id)).await;
+
+        let view = SavedViewService::create_view(
+            &pool,
+            owner,
+            Some(school_id),
+            view_dto("Red house", true),
+        )
+        .await
+        .unwrap();
+
+        let rename = UpdateSavedViewDto {
+            name: Some("Mine now".to_string()),
+            filters: None,
+            sort_by: None,
+            sort_order: None,
+            columns: None,
+            shared: None,
+        };
+        let err = SavedViewService::update_view(
+            &pool,
+            view.id,
+            colleague,
+            Some(school_id),
+            rename.clone(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        let err = SavedViewService::delete_view(&pool, view.id, colleague, Some(school_id))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        let renamed = SavedViewService::update_view(&pool, view.id, owner, Some(school_id), rename)
+            .await
+            .unwrap();
+        assert_eq!(renamed.name, "Mine now");
+        assert_eq!(renamed.sort_by.as_deref(), Some("last_name"));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_duplicate_names_and_sharing_without_school_are_rejected(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let owner = create_test_user(&pool, Some(school_id)).await;
+        let system_admin = create_test_user(&pool, None).await;
+
+        SavedViewService::create_view(&pool, owner, Some(school_id), view_dto("Red house", false))
+            .await
+            .unwrap();
+        let err = SavedViewService::create_view(
+            &pool,
+            owner,
+            Some(school_id),
+            view_dto("Red house", false),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let err = SavedViewService::create_view(&pool, system_admin, None, view_dto("All", true))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let private = CreateSavedViewDto {
+            filters: Map::new(),
+            ..view_dto("All", false)
+        };
+        assert!(
+            SavedViewService::create_view(&pool, system_admin, None, private)
+                .await
+                .is_ok()
+        );
+    }
+}
@@ -3,13 +3,15 @@ use axum::{
     Json,
     body::Bytes,
     http::header,
+    response::Response,
 };
 use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
 
-use chalkbyte_core::AppError;
+use chalkbyte_core::{AppError, Created, ErrorCode, ExportFormat, ExportParams, NoContent};
 use chalkbyte_models::ids::{LevelId, SchoolId};
 
+use crate::config::database::TenantContext;
 use crate::middleware::auth::{
     AuthUser, RequireSchoolsCreate, RequireSchoolsDelete, RequireSchoolsRead, RequireSchoolsUpdate,
 };
@@ -20,10 +22,11 @@ use crate::modules::levels::model::{LevelFilterParams, PaginatedLevelsResponse};
 use crate::modules::levels::service::LevelService;
 use crate::modules::users::model::{
     CreateSchoolDto, PaginatedBasicUsersResponse, PaginatedSchoolsResponse, School,
-    SchoolFilterParams, SchoolFullInfo, UserFilterParams,
+    SchoolFilterParams, SchoolFullInfo, SchoolSettings, UpdateSchoolSettingsDto, UserFilterParams,
 };
 use crate::state::AppState;
-use crate::utils::auth_helpers::get_admin_school_id;
+use crate::utils::auth_helpers::{get_admin_school_id, verify_school_access};
+use crate::validator::ValidatedJson;
 
 use super::model::FileMetadata;
 use super::service::SchoolService;
@@ -34,7 +37,7 @@ use super::service::SchoolService;
     summary = "Create school",
     request_body = CreateSchoolDto,
     responses(
-        (status = 201, description = "School created successfully", body = School),
+        Created<School>,
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - requires schools:create permission")
     ),
@@ -45,8 +48,8 @@ use super::service::SchoolService;
 pub async fn create_school(
     State(state): State<AppState>,
     RequireSchoolsCreate(_auth_user): RequireSchoolsCreate,
-    Json(dto): Json<CreateSchoolDto>,
-) -> Result<Json<School>, AppError> {
+    ValidatedJson(dto): ValidatedJson<CreateSchoolDto>,
+) -> Result<Created<School>, AppError> {
     debug!(school.name = %dto.name, "Creating new school");
 
     let school = SchoolService::create_school(&state.db, state.cache.as_ref(), dto).await?;
@@ -57,7 +60,7 @@ pub async fn create_school(
         "School created successfully"
     );
 
-    Ok(Json(school))
+    Ok(Created(school))
 }
 
 #[utoipa::path(
@@ -68,10 +71,13 @@ pub async fn create_school(
         ("name" = Option<String>, Query, description = "Filter by school name (partial match)"),
         ("address" = Option<String>, Query, description = "Filter by address (partial match)"),
         ("limit" = Option<i64>, Query, description = "Limit number of results"),
-        ("offset" = Option<i64>, Query, description = "Offset for pagination")
+        ("offset" = Option<i64>, Query, description = "Offset for pagination"),
+        ("sort" = Option<String>, Query, description = "Comma-separated fields to sort by, `-` prefix for descending (default `-created_at`). Sortable: name, created_at, updated_at"),
+        ("filter" = Option<String>, Query, description = "Comma-separated `field:op:value` clauses, e.g. `name:contains:high,created_at:gte:2024-01-01`. Filterable: name, address (eq, ne, contains, in); id (eq, ne, in); created_at, updated_at (eq, ne, gt, gte, lt, lte)")
     ),
     responses(
         (status = 200, description = "Paginated list of schools", body = PaginatedSchoolsResponse),
+        (status = 400, description = "Invalid sort field or filter expression"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - requires schools:read permission")
     ),
@@ -104,6 +110,41 @@ pub async fn get_all_schools(
     Ok(Json(schools))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/schools/export",
+    summary = "Export schools",
+    params(
+        ExportParams,
+        ("name" = Option<String>, Query, description = "Filter by school name (partial match)"),
+        ("address" = Option<String>, Query, description = "Filter by address (partial match)")
+    ),
+    responses(
+        (status = 200, description = "CSV of matching schools, newest first", content_type = "text/csv", body = String),
+        (status = 400, description = "Invalid filter or format"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:read permission")
+    ),
+    tag = "Schools",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, filters))]
+pub async fn export_schools(
+    State(state): State<AppState>,
+    RequireSchoolsRead(_auth_user): RequireSchoolsRead,
+    export: Result<Query<ExportParams>, QueryRejection>,
+    filters: Result<Query<SchoolFilterParams>, QueryRejection>,
+) -> Result<Response, AppError> {
+    let Query(export) = export
+        .map_err(|e| AppError::bad_request(anyhow::anyhow!("Invalid query parameters: {}", e)))?;
+    let Query(filters) = filters
+        .map_err(|e| AppError::bad_request(anyhow::anyhow!("Invalid query parameters: {}", e)))?;
+
+    match export.format {
+        ExportFormat::Csv => SchoolService::export_schools_csv(&state.db, filters).await,
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/api/schools/{id}",
@@ -142,7 +183,7 @@ pub async fn get_school(
         ("id" = Uuid, Path, description = "School ID")
     ),
     responses(
-        (status = 204, description = "School deleted successfully"),
+        NoContent,
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - requires schools:delete permission"),
         (status = 404, description = "School not found")
@@ -155,14 +196,14 @@ pub async fn delete_school(
     State(state): State<AppState>,
     RequireSchoolsDelete(_auth_user): RequireSchoolsDelete,
     Path(id): Path<Uuid>,
-) -> Result<(), AppError> {
+) -> Result<NoContent, AppError> {
     debug!("Deleting school");
 
     SchoolService::delete_school(&state.db, state.cache.as_ref(), id).await?;
 
     info!(school.id = %id, "School deleted successfully");
 
-    Ok(())
+    Ok(NoContent)
 }
 
 #[utoipa::path(
@@ -265,7 +306,7 @@ pub async fn get_school_admins(
     if !is_system_admin_jwt(&auth_user) {
         let user_school_id = auth_user
             .school_id()
-            .ok_or_else(|| AppError::forbidden("User has no associated school".to_string()))?;
+            .ok_or_else(|| AppError::from_code(ErrorCode::NoAssociatedSchool))?;
         if user_school_id != school_id {
             warn!("School admin attempted to view admins for different school");
             return Err(AppError::forbidden(
@@ -343,6 +384,78 @@ pub async fn get_school_full_info(
     Ok(Json(school_info))
 }
 
+/// Get a school's settings
+///
+/// Returns the timezone dates such as "today" are read in for attendance and
+/// terms, and the locale.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/settings",
+    summary = "Get school settings",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    responses(
+        (status = 200, description = "School settings", body = SchoolSettings),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:read permission and access to the school"),
+        (status = 404, description = "School not found")
+    ),
+    tag = "Schools",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_school_settings(
+    State(state): State<AppState>,
+    RequireSchoolsRead(auth_user): RequireSchoolsRead,
+    Path(school_id): Path<Uuid>,
+) -> Result<Json<SchoolSettings>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let settings = SchoolService::get_settings(&state.db, state.cache.as_ref(), school_id).await?;
+
+    Ok(Json(settings))
+}
+
+/// Update a school's settings
+///
+/// Changing the timezone moves when the school's days start for attendance,
+/// scheduled broadcasts, and the current-term scheduler.
+#[utoipa::path(
+    put,
+    path = "/api/schools/{id}/settings",
+    summary = "Update school settings",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = UpdateSchoolSettingsDto,
+    responses(
+        (status = 200, description = "School settings updated", body = SchoolSettings),
+        (status = 400, description = "Unknown timezone or invalid locale"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires schools:update permission and access to the school"),
+        (status = 404, description = "School not found")
+    ),
+    tag = "Schools",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn update_school_settings(
+    State(state): State<AppState>,
+    RequireSchoolsUpdate(auth_user): RequireSchoolsUpdate,
+    Path(school_id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateSchoolSettingsDto>,
+) -> Result<Json<SchoolSettings>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let settings =
+        SchoolService::update_settings(&state.db, state.cache.as_ref(), school_id, dto).await?;
+
+    Ok(Json(settings))
+}
+
 #[utoipa::path(
     get,
     path = "/api/schools/{id}/levels",
@@ -460,8 +573,9 @@ pub async fn get_school_level_branches(
 
     debug!("Fetching branches for level");
 
+    let tenant = TenantContext::school(school_id);
     let branches =
-        BranchService::get_branches_by_level(&state.db, level_id, school_id, filters).await?;
+        BranchService::get_branches_by_level(&state.db, &tenant, level_id, filters).await?;
 
     debug!(
         total = %branches.meta.total,
@@ -502,7 +616,7 @@ pub async fn upload_school_logo(
     if !is_system_admin_jwt(&auth_user) {
         let user_school_id = auth_user
             .school_id()
-            .ok_or_else(|| AppError::forbidden("User has no associated school".to_string()))?;
+            .ok_or_else(|| AppError::from_code(ErrorCode::NoAssociatedSchool))?;
         if user_school_id != school_id {
             warn!(
                 user.school_id = %user_school_id,
@@ -556,7 +670,7 @@ pub async fn upload_school_logo(
         ("id" = Uuid, Path, description = "School ID")
     ),
     responses(
-        (status = 204, description = "Logo deleted successfully"),
+        NoContent,
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - requires schools:update permission"),
         (status = 404, description = "School not found")
@@ -569,14 +683,14 @@ pub async fn delete_school_logo(
     State(state): State<AppState>,
     RequireSchoolsUpdate(auth_user): RequireSchoolsUpdate,
     Path(id): Path<Uuid>,
-) -> Result<(), AppError> {
+) -> Result<NoContent, AppError> {
     let school_id = SchoolId::from(id);
 
     // Authorization: system admin OR school admin for own school
     if !is_system_admin_jwt(&auth_user) {
         let user_school_id = auth_user
             .school_id()
-            .ok_or_else(|| AppError::forbidden("User has no associated school".to_string()))?;
+            .ok_or_else(|| AppError::from_code(ErrorCode::NoAssociatedSchool))?;
         if user_school_id != school_id {
             warn!(
                 user.school_id = %user_school_id,
@@ -600,5 +714,5 @@ pub async fn delete_school_logo(
     .await?;
 
     info!(school.id = %id, "School logo deleted successfully");
-    Ok(())
+    Ok(NoContent)
 }
@@ -6,14 +6,15 @@ use axum::{
 use crate::state::AppState;
 
 use super::controller::{
-    create_school, delete_school, delete_school_logo, get_all_schools, get_school,
+    create_school, delete_school, delete_school_logo, export_schools, get_all_schools, get_school,
     get_school_admins, get_school_full_info, get_school_level_branches, get_school_levels,
-    get_school_students, upload_school_logo,
+    get_school_settings, get_school_students, update_school_settings, upload_school_logo,
 };
 
 pub fn init_schools_router() -> Router<AppState> {
     Router::new()
         .route("/", post(create_school).get(get_all_schools))
+        .route("/export", get(export_schools))
         .route("/{id}", get(get_school).delete(delete_school))
         .route(
             "/{id}/logo",
@@ -22,6 +23,10 @@ pub fn init_schools_router() -> Router<AppState> {
         .route("/{id}/students", get(get_school_students))
         .route("/{id}/admins", get(get_school_admins))
         .route("/{id}/full-info", get(get_school_full_info))
+        .route(
+            "/{id}/settings",
+            get(get_school_settings).put(update_school_settings),
+        )
         .route("/{id}/levels", get(get_school_levels))
         .route(
             "/{id}/levels/{level_id}/branches",
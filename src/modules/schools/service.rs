@@ -1,15 +1,42 @@
-use sqlx::PgPool;
+use axum::response::Response;
+use sqlx::{PgPool, postgres::PgArguments};
 use tracing::{debug, error, info, instrument, warn};
 use uuid::Uuid;
 
 use chalkbyte_cache::{RedisCache, invalidate, keys};
-use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_core::filtering::FieldType;
+use chalkbyte_core::{AppError, ErrorCode, Filterable, PaginationMeta, Sortable};
+use chalkbyte_models::ids::SchoolId;
 
 #[cfg(feature = "observability")]
 use chalkbyte_observability::metrics;
 use crate::modules::users::model::{
     CreateSchoolDto, PaginatedBasicUsersResponse, PaginatedSchoolsResponse, School,
-    SchoolFilterParams, SchoolFullInfo, User, UserFilterParams, system_roles,
+    SchoolExportRow, SchoolFilterParams, SchoolFullInfo, SchoolSettings, UpdateSchoolSettingsDto,
+    User, UserFilterParams, system_roles,
+};
+use crate::utils::csv_export::{push_arg, stream_csv};
+
+/// Fields the school list can be sorted by
+const SCHOOL_SORT: Sortable = Sortable {
+    columns: &[
+        ("name", "name"),
+        ("created_at", "created_at"),
+        ("updated_at", "updated_at"),
+    ],
+    default: "-created_at",
+    tiebreaker: "id",
+};
+
+/// Fields the school list can be filtered by with `filter`
+const SCHOOL_FILTER: Filterable = Filterable {
+    fields: &[
+        ("id", "id", FieldType::Uuid),
+        ("name", "name", FieldType::Text),
+        ("address", "address", FieldType::Text),
+        ("created_at", "created_at", FieldType::Timestamp),
+        ("updated_at", "updated_at", FieldType::Timestamp),
+    ],
 };
 
 pub struct SchoolService;
@@ -28,7 +55,7 @@ impl SchoolService {
 
         let school = sqlx::query_as::<_, School>(
             "INSERT INTO schools (name, address) VALUES ($1, $2)
-             RETURNING id, name, address, created_at, updated_at",
+             RETURNING id, name, address, logo_path, timezone, locale, created_at, updated_at",
         )
         .bind(&dto.name)
         .bind(&dto.address)
@@ -39,7 +66,7 @@ impl SchoolService {
                 && db_err.is_unique_violation()
             {
                 warn!(school.name = %dto.name, "Attempted to create school with existing name");
-                return AppError::bad_request(anyhow::anyhow!("School name already exists"));
+                return AppError::from_code(ErrorCode::SchoolNameConflict);
             }
             error!(error = %e, school.name = %dto.name, "Database error creating school");
             AppError::from(e)
@@ -64,12 +91,14 @@ impl SchoolService {
     ) -> Result<PaginatedSchoolsResponse, AppError> {
         let limit = filters.pagination.limit();
         let offset = filters.pagination.offset();
+        let order_by = SCHOOL_SORT.order_by(&filters.sort)?;
 
         debug!(
             limit = %limit,
             offset = %offset,
             filter.name = ?filters.name,
             filter.address = ?filters.address,
+            filter.expression = ?filters.filter.filter,
             "Fetching schools with pagination"
         );
 
@@ -87,6 +116,14 @@ impl SchoolService {
             where_clause.push_str(&format!(" AND address ILIKE ${}", params.len()));
         }
 
+        for (condition, value) in SCHOOL_FILTER
+            .parse(&filters.filter)?
+            .sql_conditions(params.len() + 1)
+        {
+            params.push(value);
+            where_clause.push_str(&format!(" AND {}", condition));
+        }
+
         count_query.push_str(&where_clause);
 
         let mut count_sql = sqlx::query_scalar::<_, i64>(&count_query);
@@ -99,9 +136,9 @@ impl SchoolService {
         })?;
 
         let mut data_query =
-            String::from("SELECT id, name, address, logo_path, created_at, updated_at FROM schools WHERE 1=1");
+            String::from("SELECT id, name, address, logo_path, timezone, locale, created_at, updated_at FROM schools WHERE 1=1");
         data_query.push_str(&where_clause);
-        data_query.push_str(" ORDER BY created_at DESC");
+        data_query.push_str(&format!(" ORDER BY {}", order_by));
         data_query.push_str(&format!(" LIMIT {} OFFSET {}", limit, offset));
 
         let mut data_sql = sqlx::query_as::<_, School>(&data_query);
@@ -134,6 +171,35 @@ impl SchoolService {
         })
     }
 
+    /// Stream every school matching the list filters as CSV, newest first.
+    #[instrument(skip(db, filters), fields(db.operation = "SELECT", db.table = "schools"))]
+    pub async fn export_schools_csv(
+        db: &PgPool,
+        filters: SchoolFilterParams,
+    ) -> Result<Response, AppError> {
+        let mut query = String::from("SELECT id, name, address, created_at FROM schools WHERE 1=1");
+        let mut args = PgArguments::default();
+        let mut param_count = 0;
+
+        for (column, value) in [("name", &filters.name), ("address", &filters.address)] {
+            if let Some(value) = value {
+                param_count += 1;
+                query.push_str(&format!(" AND {} ILIKE ${}", column, param_count));
+                push_arg(&mut args, format!("%{}%", value))?;
+            }
+        }
+        for (condition, value) in SCHOOL_FILTER
+            .parse(&filters.filter)?
+            .sql_conditions(param_count + 1)
+        {
+            query.push_str(&format!(" AND {}", condition));
+            push_arg(&mut args, value)?;
+        }
+        query.push_str(" ORDER BY created_at DESC, id DESC");
+
+        stream_csv::<SchoolExportRow>(db, &query, args, "schools").await
+    }
+
     #[instrument(skip(db, cache), fields(school.id = %school_id, db.operation = "SELECT", db.table = "schools"))]
     pub async fn get_school_by_id(
         db: &PgPool,
@@ -153,7 +219,7 @@ impl SchoolService {
         debug!("Fetching school by ID from database");
 
         let school = sqlx::query_as::<_, School>(
-            "SELECT id, name, address, logo_path, created_at, updated_at FROM schools WHERE id = $1",
+            "SELECT id, name, address, logo_path, timezone, locale, created_at, updated_at FROM schools WHERE id = $1",
         )
         .bind(school_id)
         .fetch_optional(db)
@@ -164,7 +230,7 @@ impl SchoolService {
         })?
         .ok_or_else(|| {
             debug!(school.id = %school_id, "School not found");
-            AppError::not_found(anyhow::anyhow!("School not found"))
+            AppError::from_code(ErrorCode::SchoolNotFound)
         })?;
 
         // Cache the result
@@ -179,6 +245,86 @@ impl SchoolService {
         Ok(school)
     }
 
+    /// Get a school's timezone and locale, cached until they change.
+    #[instrument(skip(db, cache), fields(school.id = %school_id, db.operation = "SELECT", db.table = "schools"))]
+    pub async fn get_settings(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+    ) -> Result<SchoolSettings, AppError> {
+        let cache_key = keys::schools::settings(school_id.into_inner());
+
+        if let Some(cache) = cache
+            && let Some(settings) = cache.get::<SchoolSettings>(&cache_key).await
+        {
+            return Ok(settings);
+        }
+
+        let settings = sqlx::query_as::<_, SchoolSettings>(
+            "SELECT id AS school_id, timezone, locale FROM schools WHERE id = $1",
+        )
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))?;
+
+        if let Some(cache) = cache
+            && let Err(e) = cache.set(&cache_key, &settings).await
+        {
+            warn!(error = %e, "Failed to cache school settings");
+        }
+
+        Ok(settings)
+    }
+
+    /// Update a school's timezone and locale.
+    ///
+    /// The timezone must also be known to Postgres, not just to chrono-tz,
+    /// since queries across all schools convert times with `AT TIME ZONE`
+    /// and one unknown name would fail them for every school.
+    #[instrument(skip(db, cache), fields(school.id = %school_id, db.operation = "UPDATE", db.table = "schools"))]
+    pub async fn update_settings(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        dto: UpdateSchoolSettingsDto,
+    ) -> Result<SchoolSettings, AppError> {
+        if let Some(timezone) = &dto.timezone {
+            let known = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM pg_timezone_names WHERE name = $1)",
+            )
+            .bind(timezone)
+            .fetch_one(db)
+            .await?;
+
+            if !known {
+                return Err(AppError::unprocessable(anyhow::anyhow!(
+                    "Unknown timezone: {timezone}"
+                )));
+            }
+        }
+
+        let settings = sqlx::query_as::<_, SchoolSettings>(
+            "UPDATE schools
+             SET timezone = COALESCE($2, timezone),
+                 locale = COALESCE($3, locale),
+                 updated_at = NOW()
+             WHERE id = $1
+             RETURNING id AS school_id, timezone, locale",
+        )
+        .bind(school_id)
+        .bind(&dto.timezone)
+        .bind(&dto.locale)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))?;
+
+        invalidate::school(cache, Some(school_id.into_inner())).await;
+        info!(timezone = %settings.timezone, locale = %settings.locale, "School settings updated");
+
+        Ok(settings)
+    }
+
     #[instrument(skip(db, cache), fields(school.id = %school_id, db.operation = "DELETE", db.table = "schools"))]
     pub async fn delete_school(
         db: &PgPool,
@@ -198,7 +344,7 @@ impl SchoolService {
 
         if result.rows_affected() == 0 {
             debug!(school.id = %school_id, "School not found for deletion");
-            return Err(AppError::not_found(anyhow::anyhow!("School not found")));
+            return Err(AppError::from_code(ErrorCode::SchoolNotFound));
         }
 
         // Invalidate cache
@@ -262,7 +408,7 @@ impl SchoolService {
         })?;
 
         let mut data_query = String::from(
-            "SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.level_id, u.branch_id, u.date_of_birth, u.grade_level, u.created_at, u.updated_at FROM users u INNER JOIN user_roles ur ON ur.user_id = u.id WHERE u.school_id = $1 AND ur.role_id = $2",
+            "SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.level_id, u.branch_id, u.date_of_birth, u.grade_level, u.custom_fields, u.created_at, u.updated_at FROM users u INNER JOIN user_roles ur ON ur.user_id = u.id WHERE u.school_id = $1 AND ur.role_id = $2",
         );
         data_query.push_str(&where_clause);
         data_query.push_str(" ORDER BY created_at DESC");
@@ -351,7 +497,7 @@ impl SchoolService {
         })?;
 
         let mut data_query = String::from(
-            "SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.level_id, u.branch_id, u.date_of_birth, u.grade_level, u.created_at, u.updated_at FROM users u INNER JOIN user_roles ur ON ur.user_id = u.id WHERE u.school_id = $1 AND ur.role_id = $2",
+            "SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.level_id, u.branch_id, u.date_of_birth, u.grade_level, u.custom_fields, u.created_at, u.updated_at FROM users u INNER JOIN user_roles ur ON ur.user_id = u.id WHERE u.school_id = $1 AND ur.role_id = $2",
         );
         data_query.push_str(&where_clause);
         data_query.push_str(" ORDER BY created_at DESC");
@@ -397,7 +543,7 @@ impl SchoolService {
         debug!("Fetching full school information with statistics");
 
         let school = sqlx::query_as::<_, School>(
-            "SELECT id, name, address, logo_path, created_at, updated_at FROM schools WHERE id = $1",
+            "SELECT id, name, address, logo_path, timezone, locale, created_at, updated_at FROM schools WHERE id = $1",
         )
         .bind(school_id)
         .fetch_optional(db)
@@ -408,7 +554,7 @@ impl SchoolService {
         })?
         .ok_or_else(|| {
             debug!(school.id = %school_id, "School not found");
-            AppError::not_found(anyhow::anyhow!("School not found"))
+            AppError::from_code(ErrorCode::SchoolNotFound)
         })?;
 
         debug!(school.name = %school.name, "School found, fetching statistics");
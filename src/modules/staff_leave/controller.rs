@@ -0,0 +1,317 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, PaginationParams};
+use chalkbyte_models::ids::StaffLeaveId;
+
+use crate::middleware::auth::{
+    RequireStaffLeaveApprove, RequireStaffLeaveRead, RequireStaffLeaveRequest,
+};
+use crate::modules::staff_leave::model::{
+    ApproveStaffLeaveDto, CreateStaffLeaveDto, LeaveCalendarEntry, LeaveCalendarParams,
+    PaginatedStaffLeaveResponse, RejectStaffLeaveDto, StaffLeaveFilterParams, StaffLeaveRequest,
+    SubstituteCandidate,
+};
+use crate::modules::staff_leave::service::StaffLeaveService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_admin_school_id, get_optional_school_id_for_resource_operation,
+    get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Submit a leave request for the current staff member
+#[utoipa::path(
+    post,
+    path = "/api/staff-leave",
+    summary = "Request leave",
+    request_body = CreateStaffLeaveDto,
+    responses(
+        Created<StaffLeaveRequest>,
+        (status = 400, description = "Invalid dates or overlapping leave"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires staff_leave:request permission")
+    ),
+    tag = "Staff Leave",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_leave_request(
+    State(state): State<AppState>,
+    RequireStaffLeaveRequest(auth_user): RequireStaffLeaveRequest,
+    ValidatedJson(dto): ValidatedJson<CreateStaffLeaveDto>,
+) -> Result<Created<StaffLeaveRequest>, AppError> {
+    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
+    let staff_id = auth_user.user_id()?;
+    let leave =
+        StaffLeaveService::create_leave_request(&state.db, school_id, staff_id, dto).await?;
+
+    Ok(Created(leave))
+}
+
+/// List leave requests for a school
+#[utoipa::path(
+    get,
+    path = "/api/staff-leave",
+    summary = "List leave requests",
+    params(StaffLeaveFilterParams),
+    responses(
+        (status = 200, description = "List of leave requests", body = PaginatedStaffLeaveResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires staff_leave:read permission")
+    ),
+    tag = "Staff Leave",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_leave_requests(
+    State(state): State<AppState>,
+    RequireStaffLeaveRead(auth_user): RequireStaffLeaveRead,
+    Query(filters): Query<StaffLeaveFilterParams>,
+) -> Result<Json<PaginatedStaffLeaveResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let leaves = StaffLeaveService::get_leave_requests(&state.db, school_id, filters).await?;
+
+    Ok(Json(leaves))
+}
+
+/// List the current staff member's leave requests
+#[utoipa::path(
+    get,
+    path = "/api/staff-leave/me",
+    summary = "List my leave requests",
+    params(
+        ("limit" = Option<i64>, Query, description = "Number of items per page (1-100, default: 10)"),
+        ("offset" = Option<i64>, Query, description = "Number of items to skip (default: 0)")
+    ),
+    responses(
+        (status = 200, description = "Own leave requests", body = PaginatedStaffLeaveResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires staff_leave:request permission")
+    ),
+    tag = "Staff Leave",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_my_leave_requests(
+    State(state): State<AppState>,
+    RequireStaffLeaveRequest(auth_user): RequireStaffLeaveRequest,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<Json<PaginatedStaffLeaveResponse>, AppError> {
+    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
+    let staff_id = auth_user.user_id()?;
+    let leaves =
+        StaffLeaveService::get_my_leave_requests(&state.db, school_id, staff_id, pagination)
+            .await?;
+
+    Ok(Json(leaves))
+}
+
+/// Get approved absences for the school leave calendar
+#[utoipa::path(
+    get,
+    path = "/api/staff-leave/calendar",
+    summary = "Leave calendar",
+    params(LeaveCalendarParams),
+    responses(
+        (status = 200, description = "Approved absences in the window", body = Vec<LeaveCalendarEntry>),
+        (status = 400, description = "Invalid window or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires staff_leave:read permission")
+    ),
+    tag = "Staff Leave",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_leave_calendar(
+    State(state): State<AppState>,
+    RequireStaffLeaveRead(auth_user): RequireStaffLeaveRead,
+    Query(params): Query<LeaveCalendarParams>,
+) -> Result<Json<Vec<LeaveCalendarEntry>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let entries =
+        StaffLeaveService::get_leave_calendar(&state.db, school_id, params.from, params.to).await?;
+
+    Ok(Json(entries))
+}
+
+/// Get a leave request by ID
+#[utoipa::path(
+    get,
+    path = "/api/staff-leave/{id}",
+    summary = "Get leave request",
+    params(
+        ("id" = Uuid, Path, description = "Leave request ID")
+    ),
+    responses(
+        (status = 200, description = "Leave request details", body = StaffLeaveRequest),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires staff_leave:read permission"),
+        (status = 404, description = "Leave request not found")
+    ),
+    tag = "Staff Leave",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_leave_request_by_id(
+    State(state): State<AppState>,
+    RequireStaffLeaveRead(auth_user): RequireStaffLeaveRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<StaffLeaveRequest>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let leave =
+        StaffLeaveService::get_leave_request_by_id(&state.db, StaffLeaveId::from(id), school_id)
+            .await?;
+
+    Ok(Json(leave))
+}
+
+/// Approve a pending leave request
+#[utoipa::path(
+    post,
+    path = "/api/staff-leave/{id}/approve",
+    summary = "Approve leave request",
+    params(
+        ("id" = Uuid, Path, description = "Leave request ID")
+    ),
+    request_body = ApproveStaffLeaveDto,
+    responses(
+        (status = 200, description = "Leave request approved", body = StaffLeaveRequest),
+        (status = 400, description = "Request is not pending or substitute is unavailable"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires staff_leave:approve permission"),
+        (status = 404, description = "Leave request not found")
+    ),
+    tag = "Staff Leave",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn approve_leave_request(
+    State(state): State<AppState>,
+    RequireStaffLeaveApprove(auth_user): RequireStaffLeaveApprove,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<ApproveStaffLeaveDto>,
+) -> Result<Json<StaffLeaveRequest>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let reviewer_id = auth_user.user_id()?;
+    let leave = StaffLeaveService::approve_leave_request(
+        &state.db,
+        StaffLeaveId::from(id),
+        school_id,
+        reviewer_id,
+        dto,
+    )
+    .await?;
+
+    Ok(Json(leave))
+}
+
+/// Reject a pending leave request
+#[utoipa::path(
+    post,
+    path = "/api/staff-leave/{id}/reject",
+    summary = "Reject leave request",
+    params(
+        ("id" = Uuid, Path, description = "Leave request ID")
+    ),
+    request_body = RejectStaffLeaveDto,
+    responses(
+        (status = 200, description = "Leave request rejected", body = StaffLeaveRequest),
+        (status = 400, description = "Request is not pending"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires staff_leave:approve permission"),
+        (status = 404, description = "Leave request not found")
+    ),
+    tag = "Staff Leave",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn reject_leave_request(
+    State(state): State<AppState>,
+    RequireStaffLeaveApprove(auth_user): RequireStaffLeaveApprove,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<RejectStaffLeaveDto>,
+) -> Result<Json<StaffLeaveRequest>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let reviewer_id = auth_user.user_id()?;
+    let leave = StaffLeaveService::reject_leave_request(
+        &state.db,
+        StaffLeaveId::from(id),
+        school_id,
+        reviewer_id,
+        dto,
+    )
+    .await?;
+
+    Ok(Json(leave))
+}
+
+/// Cancel one of the current staff member's pending leave requests
+#[utoipa::path(
+    post,
+    path = "/api/staff-leave/{id}/cancel",
+    summary = "Cancel leave request",
+    params(
+        ("id" = Uuid, Path, description = "Leave request ID")
+    ),
+    responses(
+        (status = 200, description = "Leave request cancelled", body = StaffLeaveRequest),
+        (status = 400, description = "Request is not pending"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires staff_leave:request permission"),
+        (status = 404, description = "Leave request not found")
+    ),
+    tag = "Staff Leave",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn cancel_leave_request(
+    State(state): State<AppState>,
+    RequireStaffLeaveRequest(auth_user): RequireStaffLeaveRequest,
+    Path(id): Path<Uuid>,
+) -> Result<Json<StaffLeaveRequest>, AppError> {
+    let staff_id = auth_user.user_id()?;
+    let leave =
+        StaffLeaveService::cancel_leave_request(&state.db, StaffLeaveId::from(id), staff_id)
+            .await?;
+
+    Ok(Json(leave))
+}
+
+/// Suggest substitute teachers for a leave request
+#[utoipa::path(
+    get,
+    path = "/api/staff-leave/{id}/substitutes",
+    summary = "Substitute teacher hints",
+    params(
+        ("id" = Uuid, Path, description = "Leave request ID")
+    ),
+    responses(
+        (status = 200, description = "Available teachers, least busy first", body = Vec<SubstituteCandidate>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires staff_leave:approve permission"),
+        (status = 404, description = "Leave request not found")
+    ),
+    tag = "Staff Leave",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_substitute_candidates(
+    State(state): State<AppState>,
+    RequireStaffLeaveApprove(auth_user): RequireStaffLeaveApprove,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<SubstituteCandidate>>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let candidates =
+        StaffLeaveService::get_substitute_candidates(&state.db, StaffLeaveId::from(id), school_id)
+            .await?;
+
+    Ok(Json(candidates))
+}
@@ -0,0 +1,9 @@
+//! Staff leave module.
+//!
+//! This module tracks staff leave and absence requests per school, with an
+//! admin approval workflow, a leave calendar, and substitute-teacher hints.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
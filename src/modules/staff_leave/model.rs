@@ -0,0 +1,7 @@
+//! Staff leave data models and DTOs.
+//!
+//! This module re-exports staff leave models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all staff leave models from the shared crate
+pub use chalkbyte_models::staff_leave::*;
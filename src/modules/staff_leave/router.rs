@@ -0,0 +1,27 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    approve_leave_request, cancel_leave_request, create_leave_request, get_leave_calendar,
+    get_leave_request_by_id, get_leave_requests, get_my_leave_requests, get_substitute_candidates,
+    reject_leave_request,
+};
+
+/// Initialize the staff leave router
+/// Routes: POST /, GET /, GET /me, GET /calendar, GET /{id},
+/// POST /{id}/approve, POST /{id}/reject, POST /{id}/cancel, GET /{id}/substitutes
+pub fn init_staff_leave_router() -> Router<AppState> {
+    Router::new()
+        .route("/", post(create_leave_request).get(get_leave_requests))
+        .route("/me", get(get_my_leave_requests))
+        .route("/calendar", get(get_leave_calendar))
+        .route("/{id}", get(get_leave_request_by_id))
+        .route("/{id}/approve", post(approve_leave_request))
+        .route("/{id}/reject", post(reject_leave_request))
+        .route("/{id}/cancel", post(cancel_leave_request))
+        .route("/{id}/substitutes", get(get_substitute_candidates))
+}
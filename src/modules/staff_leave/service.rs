@@ -0,0 +1,695 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, PaginationMeta, PaginationParams};
+use chalkbyte_models::ids::{SchoolId, StaffLeaveId, UserId};
+
+use crate::modules::staff_leave::model::{
+    ApproveStaffLeaveDto, CreateStaffLeaveDto, LeaveCalendarEntry, LeaveStatus,
+    PaginatedStaffLeaveResponse, RejectStaffLeaveDto, StaffLeaveFilterParams, StaffLeaveRequest,
+    StaffLeaveWithStaff, SubstituteCandidate,
+};
+use crate::modules::users::model::system_roles;
+
+const LEAVE_COLUMNS: &str = r#"id, school_id, staff_id, leave_type, start_date, end_date, reason, status,
+    substitute_id, reviewed_by, reviewed_at, review_note, created_at, updated_at"#;
+
+pub struct StaffLeaveService;
+
+impl StaffLeaveService {
+    /// Fetch a leave request, optionally restricted to a school.
+    async fn find_leave_request(
+        db: &PgPool,
+        leave_id: StaffLeaveId,
+        school_id: Option<SchoolId>,
+    ) -> Result<StaffLeaveRequest, AppError> {
+        sqlx::query_as::<_, StaffLeaveRequest>(&format!(
+            "SELECT {LEAVE_COLUMNS} FROM staff_leave_requests
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(leave_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Leave request not found")))
+    }
+
+    /// Check whether a staff member has pending or approved leave overlapping the range.
+    async fn has_overlapping_leave(
+        db: &PgPool,
+        staff_id: UserId,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        exclude_id: Option<StaffLeaveId>,
+        statuses: &[&str],
+    ) -> Result<bool, AppError> {
+        let statuses: Vec<String> = statuses.iter().map(|s| s.to_string()).collect();
+        let overlaps = sqlx::query_scalar::<_, bool>(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM staff_leave_requests
+                WHERE staff_id = $1
+                  AND start_date <= $3 AND end_date >= $2
+                  AND status = ANY($4)
+                  AND ($5::uuid IS NULL OR id <> $5)
+            )"#,
+        )
+        .bind(staff_id)
+        .bind(start_date)
+        .bind(end_date)
+        .bind(&statuses)
+        .bind(exclude_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(overlaps)
+    }
+
+    /// Submit a leave request for a staff member.
+    #[instrument(skip(db))]
+    pub async fn create_leave_request(
+        db: &PgPool,
+        school_id: SchoolId,
+        staff_id: UserId,
+        dto: CreateStaffLeaveDto,
+    ) -> Result<StaffLeaveRequest, AppError> {
+        if dto.start_date > dto.end_date {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Start date must not be after end date"
+            )));
+        }
+
+        if Self::has_overlapping_leave(
+            db,
+            staff_id,
+            dto.start_date,
+            dto.end_date,
+            None,
+            &["pending", "approved"],
+        )
+        .await?
+        {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "You already have a pending or approved leave request overlapping these dates"
+            )));
+        }
+
+        let leave = sqlx::query_as::<_, StaffLeaveRequest>(&format!(
+            "INSERT INTO staff_leave_requests (school_id, staff_id, leave_type, start_date, end_date, reason)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             RETURNING {LEAVE_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(staff_id)
+        .bind(dto.leave_type)
+        .bind(dto.start_date)
+        .bind(dto.end_date)
+        .bind(&dto.reason)
+        .fetch_one(db)
+        .await?;
+
+        Ok(leave)
+    }
+
+    /// Get paginated leave requests for a school.
+    #[instrument(skip(db))]
+    pub async fn get_leave_requests(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: StaffLeaveFilterParams,
+    ) -> Result<PaginatedStaffLeaveResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+
+        let where_clause = r#"WHERE l.school_id = $1
+              AND ($2::uuid IS NULL OR l.staff_id = $2)
+              AND ($3::text IS NULL OR l.status = $3)
+              AND ($4::text IS NULL OR l.leave_type = $4)
+              AND ($5::date IS NULL OR l.end_date >= $5)
+              AND ($6::date IS NULL OR l.start_date <= $6)"#;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM staff_leave_requests l {where_clause}"
+        ))
+        .bind(school_id)
+        .bind(filters.staff_id)
+        .bind(filters.status)
+        .bind(filters.leave_type)
+        .bind(filters.from)
+        .bind(filters.to)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, StaffLeaveWithStaff>(&format!(
+            r#"SELECT l.id, l.school_id, l.staff_id,
+                      u.first_name AS staff_first_name, u.last_name AS staff_last_name,
+                      l.leave_type, l.start_date, l.end_date, l.reason, l.status,
+                      l.substitute_id, l.reviewed_by, l.reviewed_at, l.review_note,
+                      l.created_at, l.updated_at
+               FROM staff_leave_requests l
+               JOIN users u ON u.id = l.staff_id
+               {where_clause}
+               ORDER BY l.start_date DESC, l.created_at DESC
+               LIMIT $7 OFFSET $8"#
+        ))
+        .bind(school_id)
+        .bind(filters.staff_id)
+        .bind(filters.status)
+        .bind(filters.leave_type)
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedStaffLeaveResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Get the leave requests submitted by a single staff member.
+    #[instrument(skip(db))]
+    pub async fn get_my_leave_requests(
+        db: &PgPool,
+        school_id: SchoolId,
+        staff_id: UserId,
+        pagination: PaginationParams,
+    ) -> Result<PaginatedStaffLeaveResponse, AppError> {
+        Self::get_leave_requests(
+            db,
+            school_id,
+            StaffLeaveFilterParams {
+                school_id: Some(school_id),
+                staff_id: Some(staff_id),
+                status: None,
+                leave_type: None,
+                from: None,
+                to: None,
+                pagination,
+            },
+        )
+        .await
+    }
+
+    /// Get a leave request by ID.
+    ///
+    /// When `school_id` is provided, the request must belong to that school.
+    #[instrument(skip(db))]
+    pub async fn get_leave_request_by_id(
+        db: &PgPool,
+        leave_id: StaffLeaveId,
+        school_id: Option<SchoolId>,
+    ) -> Result<StaffLeaveRequest, AppError> {
+        Self::find_leave_request(db, leave_id, school_id).await
+    }
+
+    /// Approve a pending leave request, optionally nominating a substitute.
+    #[instrument(skip(db))]
+    pub async fn approve_leave_request(
+        db: &PgPool,
+        leave_id: StaffLeaveId,
+        school_id: Option<SchoolId>,
+        reviewer_id: UserId,
+        dto: ApproveStaffLeaveDto,
+    ) -> Result<StaffLeaveRequest, AppError> {
+        let leave = Self::find_leave_request(db, leave_id, school_id).await?;
+
+        if leave.status != LeaveStatus::Pending {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only pending leave requests can be approved"
+            )));
+        }
+
+        if let Some(substitute_id) = dto.substitute_id {
+            Self::validate_substitute(db, &leave, substitute_id).await?;
+        }
+
+        let leave = sqlx::query_as::<_, StaffLeaveRequest>(&format!(
+            "UPDATE staff_leave_requests
+             SET status = 'approved', substitute_id = $2, reviewed_by = $3,
+                 reviewed_at = NOW(), review_note = $4
+             WHERE id = $1
+             RETURNING {LEAVE_COLUMNS}"
+        ))
+        .bind(leave_id)
+        .bind(dto.substitute_id)
+        .bind(reviewer_id)
+        .bind(&dto.review_note)
+        .fetch_one(db)
+        .await?;
+
+        Ok(leave)
+    }
+
+    /// Reject a pending leave request.
+    #[instrument(skip(db))]
+    pub async fn reject_leave_request(
+        db: &PgPool,
+        leave_id: StaffLeaveId,
+        school_id: Option<SchoolId>,
+        reviewer_id: UserId,
+        dto: RejectStaffLeaveDto,
+    ) -> Result<StaffLeaveRequest, AppError> {
+        let leave = Self::find_leave_request(db, leave_id, school_id).await?;
+
+        if leave.status != LeaveStatus::Pending {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only pending leave requests can be rejected"
+            )));
+        }
+
+        let leave = sqlx::query_as::<_, StaffLeaveRequest>(&format!(
+            "UPDATE staff_leave_requests
+             SET status = 'rejected', reviewed_by = $2, reviewed_at = NOW(), review_note = $3
+             WHERE id = $1
+             RETURNING {LEAVE_COLUMNS}"
+        ))
+        .bind(leave_id)
+        .bind(reviewer_id)
+        .bind(&dto.review_note)
+        .fetch_one(db)
+        .await?;
+
+        Ok(leave)
+    }
+
+    /// Cancel a pending leave request. Only the staff member who submitted it may cancel.
+    #[instrument(skip(db))]
+    pub async fn cancel_leave_request(
+        db: &PgPool,
+        leave_id: StaffLeaveId,
+        staff_id: UserId,
+    ) -> Result<StaffLeaveRequest, AppError> {
+        let leave = Self::find_leave_request(db, leave_id, None).await?;
+
+        if leave.staff_id != staff_id {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Leave request not found"
+            )));
+        }
+
+        if leave.status != LeaveStatus::Pending {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only pending leave requests can be cancelled"
+            )));
+        }
+
+        let leave = sqlx::query_as::<_, StaffLeaveRequest>(&format!(
+            "UPDATE staff_leave_requests SET status = 'cancelled'
+             WHERE id = $1
+             RETURNING {LEAVE_COLUMNS}"
+        ))
+        .bind(leave_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(leave)
+    }
+
+    /// Get approved absences overlapping a date window for the school calendar.
+    #[instrument(skip(db))]
+    pub async fn get_leave_calendar(
+        db: &PgPool,
+        school_id: SchoolId,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<LeaveCalendarEntry>, AppError> {
+        if from > to {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Calendar start date must not be after end date"
+            )));
+        }
+
+        let entries = sqlx::query_as::<_, LeaveCalendarEntry>(
+            r#"SELECT l.id AS leave_id,
+                      l.staff_id,
+                      u.first_name || ' ' || u.last_name AS staff_name,
+                      l.leave_type,
+                      l.start_date,
+                      l.end_date,
+                      l.substitute_id,
+                      CASE WHEN s.id IS NULL THEN NULL
+                           ELSE s.first_name || ' ' || s.last_name END AS substitute_name
+               FROM staff_leave_requests l
+               JOIN users u ON u.id = l.staff_id
+               LEFT JOIN users s ON s.id = l.substitute_id
+               WHERE l.school_id = $1
+                 AND l.status = 'approved'
+                 AND l.start_date <= $3 AND l.end_date >= $2
+               ORDER BY l.start_date ASC, staff_name ASC"#,
+        )
+        .bind(school_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(db)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// Suggest teachers who could cover a leave request.
+    ///
+    /// Candidates are teachers from the same school who are not on approved
+    /// leave during the requested dates, ordered by how many other absences
+    /// they are already covering in that window.
+    #[instrument(skip(db))]
+    pub async fn get_substitute_candidates(
+        db: &PgPool,
+        leave_id: StaffLeaveId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Vec<SubstituteCandidate>, AppError> {
+        let leave = Self::find_leave_request(db, leave_id, school_id).await?;
+
+        let candidates = sqlx::query_as::<_, SubstituteCandidate>(
+            r#"SELECT u.id AS user_id, u.first_name, u.last_name,
+                      (SELECT COUNT(*) FROM staff_leave_requests c
+                       WHERE c.substitute_id = u.id
+                         AND c.status = 'approved'
+                         AND c.start_date <= $4 AND c.end_date >= $3) AS cover_count
+               FROM users u
+               JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $5
+               WHERE u.school_id = $1
+                 AND u.id <> $2
+                 AND NOT EXISTS (
+                     SELECT 1 FROM staff_leave_requests o
+                     WHERE o.staff_id = u.id
+                       AND o.status = 'approved'
+                       AND o.start_date <= $4 AND o.end_date >= $3
+                 )
+               ORDER BY cover_count ASC, u.last_name ASC, u.first_name ASC"#,
+        )
+        .bind(leave.school_id)
+        .bind(leave.staff_id)
+        .bind(leave.start_date)
+        .bind(leave.end_date)
+        .bind(system_roles::TEACHER)
+        .fetch_all(db)
+        .await?;
+
+        Ok(candidates)
+    }
+
+    /// Ensure a nominated substitute is an available teacher in the same school.
+    async fn validate_substitute(
+        db: &PgPool,
+        leave: &StaffLeaveRequest,
+        substitute_id: UserId,
+    ) -> Result<(), AppError> {
+        if substitute_id == leave.staff_id {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "A staff member cannot substitute for their own leave"
+            )));
+        }
+
+        let is_teacher = sqlx::query_scalar::<_, bool>(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM users u
+                JOIN user_roles ur ON ur.user_id = u.id
+                WHERE u.id = $1 AND u.school_id = $2 AND ur.role_id = $3
+            )"#,
+        )
+        .bind(substitute_id)
+        .bind(leave.school_id)
+        .bind(system_roles::TEACHER)
+        .fetch_one(db)
+        .await?;
+
+        if !is_teacher {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Substitute must be a teacher in the same school"
+            )));
+        }
+
+        if Self::has_overlapping_leave(
+            db,
+            substitute_id,
+            leave.start_date,
+            leave.end_date,
+            None,
+            &["approved"],
+        )
+        .await?
+        {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Substitute is on approved leave during these dates"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::staff_leave::model::LeaveType;
+    use axum::http::StatusCode;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_teacher(pool: &PgPool, school_id: SchoolId, last_name: &str) -> UserId {
+        let user_id: UserId = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Test', $1, $2, $3) RETURNING id"#,
+            last_name,
+            format!("teacher-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id.into_inner(),
+            system_roles::TEACHER.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id
+    }
+
+    fn leave_dto(start: (i32, u32, u32), end: (i32, u32, u32)) -> CreateStaffLeaveDto {
+        CreateStaffLeaveDto {
+            leave_type: LeaveType::Annual,
+            start_date: NaiveDate::from_ymd_opt(start.0, start.1, start.2).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(end.0, end.1, end.2).unwrap(),
+            reason: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_create_leave_request(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let teacher_id = create_test_teacher(&pool, school_id, "Adams").await;
+
+        let leave = StaffLeaveService::create_leave_request(
+            &pool,
+            school_id,
+            teacher_id,
+            leave_dto((2025, 10, 6), (2025, 10, 10)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(leave.status, LeaveStatus::Pending);
+        assert_eq!(leave.leave_type, LeaveType::Annual);
+        assert_eq!(leave.staff_id, teacher_id);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_create_leave_request_rejects_overlap(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let teacher_id = create_test_teacher(&pool, school_id, "Adams").await;
+
+        StaffLeaveService::create_leave_request(
+            &pool,
+            school_id,
+            teacher_id,
+            leave_dto((2025, 10, 6), (2025, 10, 10)),
+        )
+        .await
+        .unwrap();
+
+        let err = StaffLeaveService::create_leave_request(
+            &pool,
+            school_id,
+            teacher_id,
+            leave_dto((2025, 10, 9), (2025, 10, 14)),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_approve_with_substitute_and_calendar(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let teacher_id = create_test_teacher(&pool, school_id, "Adams").await;
+        let substitute_id = create_test_teacher(&pool, school_id, "Baker").await;
+        let admin_id = create_test_teacher(&pool, school_id, "Admin").await;
+
+        let leave = StaffLeaveService::create_leave_request(
+            &pool,
+            school_id,
+            teacher_id,
+            leave_dto((2025, 10, 6), (2025, 10, 10)),
+        )
+        .await
+        .unwrap();
+
+        let approved = StaffLeaveService::approve_leave_request(
+            &pool,
+            leave.id,
+            Some(school_id),
+            admin_id,
+            ApproveStaffLeaveDto {
+                substitute_id: Some(substitute_id),
+                review_note: Some("Enjoy".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(approved.status, LeaveStatus::Approved);
+        assert_eq!(approved.substitute_id, Some(substitute_id));
+        assert_eq!(approved.reviewed_by, Some(admin_id));
+
+        let calendar = StaffLeaveService::get_leave_calendar(
+            &pool,
+            school_id,
+            NaiveDate::from_ymd_opt(2025, 10, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 10, 31).unwrap(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(calendar.len(), 1);
+        assert_eq!(calendar[0].staff_name, "Test Adams");
+        assert_eq!(calendar[0].substitute_name.as_deref(), Some("Test Baker"));
+
+        // Approving again is not allowed
+        let err = StaffLeaveService::approve_leave_request(
+            &pool,
+            leave.id,
+            Some(school_id),
+            admin_id,
+            ApproveStaffLeaveDto::default(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_substitute_candidates_exclude_staff_on_leave(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let teacher_id = create_test_teacher(&pool, school_id, "Adams").await;
+        let busy_id = create_test_teacher(&pool, school_id, "Busy").await;
+        let free_id = create_test_teacher(&pool, school_id, "Free").await;
+
+        let busy_leave = StaffLeaveService::create_leave_request(
+            &pool,
+            school_id,
+            busy_id,
+            leave_dto((2025, 10, 8), (2025, 10, 9)),
+        )
+        .await
+        .unwrap();
+        StaffLeaveService::approve_leave_request(
+            &pool,
+            busy_leave.id,
+            None,
+            free_id,
+            ApproveStaffLeaveDto::default(),
+        )
+        .await
+        .unwrap();
+
+        let leave = StaffLeaveService::create_leave_request(
+            &pool,
+            school_id,
+            teacher_id,
+            leave_dto((2025, 10, 6), (2025, 10, 10)),
+        )
+        .await
+        .unwrap();
+
+        let candidates =
+            StaffLeaveService::get_substitute_candidates(&pool, leave.id, Some(school_id))
+                .await
+                .unwrap();
+
+        let ids: Vec<UserId> = candidates.iter().map(|c| c.user_id).collect();
+        assert_eq!(ids, vec![free_id]);
+
+        let err = StaffLeaveService::approve_leave_request(
+            &pool,
+            leave.id,
+            Some(school_id),
+            free_id,
+            ApproveStaffLeaveDto {
+                substitute_id: Some(busy_id),
+                review_note: None,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_cancel_only_own_pending_request(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let teacher_id = create_test_teacher(&pool, school_id, "Adams").await;
+        let other_id = create_test_teacher(&pool, school_id, "Other").await;
+
+        let leave = StaffLeaveService::create_leave_request(
+            &pool,
+            school_id,
+            teacher_id,
+            leave_dto((2025, 10, 6), (2025, 10, 10)),
+        )
+        .await
+        .unwrap();
+
+        let err = StaffLeaveService::cancel_leave_request(&pool, leave.id, other_id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let cancelled = StaffLeaveService::cancel_leave_request(&pool, leave.id, teacher_id)
+            .await
+            .unwrap();
+        assert_eq!(cancelled.status, LeaveStatus::Cancelled);
+
+        let other_school = create_test_school(&pool).await;
+        let err = StaffLeaveService::get_leave_request_by_id(&pool, leave.id, Some(other_school))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,477 @@
+use axum::{
+    Json,
+    extract::{Multipart, Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::{SchoolId, StoredFileId, UserId};
+
+use crate::middleware::auth::{
+    AuthUser, RequireSchoolsRead, RequireSchoolsUpdate, RequireStudentsRead, RequireStudentsUpdate,
+};
+use crate::middleware::role::is_system_admin_jwt;
+use crate::modules::auth::controller::ErrorResponse;
+use crate::modules::storage::backend::attachment_disposition;
+use crate::modules::storage::model::{
+    Avatar, AvatarUploadForm, DocumentKind, DocumentUploadForm, DownloadSignature, DownloadUrl,
+    StorageUsage, StudentDocument, UpdateStorageQuotaDto,
+};
+use crate::modules::storage::service::StorageService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{tenant_context, verify_school_access};
+use crate::validator::ValidatedJson;
+
+/// A file read from a multipart upload.
+pub struct UploadedFile {
+    pub filename: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+fn multipart_error(e: axum::extract::multipart::MultipartError) -> AppError {
+    AppError::new(e.status(), anyhow::anyhow!("Invalid multipart body: {}", e))
+}
+
+/// Read the `file` field of an upload, and the text field named `text_field`
+/// if it has one.
+pub async fn read_upload(
+    mut multipart: Multipart,
+    text_field: &str,
+) -> Result<(UploadedFile, Option<String>), AppError> {
+    let mut file = None;
+    let mut text = None;
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        match field.name() {
+            Some("file") => {
+                let filename = field.file_name().unwrap_or_default().to_string();
+                let content_type = field
+                    .content_type()
+                    .unwrap_or("application/octet-stream")
+                    .to_string();
+                let content = field.bytes().await.map_err(multipart_error)?;
+                file = Some(UploadedFile {
+                    filename,
+                    content_type,
+                    content: content.to_vec(),
+                });
+            }
+            Some(name) if name == text_field => {
+                text = Some(field.text().await.map_err(multipart_error)?);
+            }
+            _ => {}
+        }
+    }
+
+    let file =
+        file.ok_or_else(|| AppError::bad_request(anyhow::anyhow!("Missing 'file' field")))?;
+    Ok((file, text))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/me/avatar",
+    summary = "Upload my avatar",
+    description = "Replaces the caller's avatar. The image counts towards their school's \
+        storage quota.",
+    request_body(content = AvatarUploadForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Avatar uploaded, with a download link", body = Avatar),
+        (status = 400, description = "Missing 'file' field", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 413, description = "File over 2MB, or the school's storage quota is used up", body = ErrorResponse),
+        (status = 415, description = "Not a PNG, JPEG, or WebP image", body = ErrorResponse)
+    ),
+    tag = "Storage",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, auth_user, multipart))]
+pub async fn upload_my_avatar(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    multipart: Multipart,
+) -> Result<Json<Avatar>, AppError> {
+    let user_id = auth_user.user_id()?;
+    let (file, _) = read_upload(multipart, "kind").await?;
+
+    let avatar = StorageService::upload_avatar(
+        &state.db,
+        state.storage.as_ref(),
+        &state.storage_config,
+        user_id,
+        &file.content_type,
+        &file.content,
+    )
+    .await?;
+
+    Ok(Json(avatar))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/me/avatar",
+    summary = "Get my avatar",
+    responses(
+        (status = 200, description = "Avatar with a download link", body = Avatar),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "No avatar uploaded", body = ErrorResponse)
+    ),
+    tag = "Storage",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, auth_user))]
+pub async fn get_my_avatar(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<Json<Avatar>, AppError> {
+    let avatar = StorageService::get_avatar(
+        &state.db,
+        state.storage.as_ref(),
+        &state.storage_config,
+        auth_user.user_id()?,
+    )
+    .await?;
+
+    Ok(Json(avatar))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/me/avatar",
+    summary = "Delete my avatar",
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "No avatar uploaded", body = ErrorResponse)
+    ),
+    tag = "Storage",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, auth_user))]
+pub async fn delete_my_avatar(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+) -> Result<NoContent, AppError> {
+    StorageService::delete_avatar(&state.db, state.storage.as_ref(), auth_user.user_id()?).await?;
+    Ok(NoContent)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/avatars/{user_id}",
+    summary = "Get user avatar",
+    description = "Any user of the same school may see another user's avatar.",
+    params(
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Avatar with a download link", body = Avatar),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 404, description = "User not found, or has no avatar", body = ErrorResponse)
+    ),
+    tag = "Storage",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, auth_user))]
+pub async fn get_user_avatar(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<Avatar>, AppError> {
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    let avatar = StorageService::get_user_avatar(
+        &state.db,
+        state.storage.as_ref(),
+        &state.storage_config,
+        &tenant,
+        UserId::from(user_id),
+    )
+    .await?;
+
+    Ok(Json(avatar))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/students/{student_id}/documents",
+    summary = "Upload student document",
+    description = "Uploads a birth certificate, transcript, or other document for a student. \
+        The file counts towards the school's storage quota.",
+    params(
+        ("student_id" = Uuid, Path, description = "Student ID")
+    ),
+    request_body(content = DocumentUploadForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Document uploaded", body = StudentDocument),
+        (status = 400, description = "Missing 'file' field or unknown kind", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires students:update permission, or students:update:own_branch for students in branches you teach", body = ErrorResponse),
+        (status = 404, description = "Student not found", body = ErrorResponse),
+        (status = 413, description = "File over 10MB, or the school's storage quota is used up", body = ErrorResponse),
+        (status = 415, description = "Not a PDF, PNG, or JPEG file", body = ErrorResponse)
+    ),
+    tag = "Storage",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, auth_user, scope, multipart))]
+pub async fn upload_student_document(
+    State(state): State<AppState>,
+    RequireStudentsUpdate(auth_user, scope): RequireStudentsUpdate,
+    Path(student_id): Path<Uuid>,
+    multipart: Multipart,
+) -> Result<Created<StudentDocument>, AppError> {
+    scope.ensure_user(&state.db, student_id).await?;
+
+    let (file, kind) = read_upload(multipart, "kind").await?;
+    let kind = kind
+        .ok_or_else(|| AppError::bad_request(anyhow::anyhow!("Missing 'kind' field")))?
+        .parse::<DocumentKind>()
+        .map_err(|e| AppError::bad_request(anyhow::anyhow!(e)))?;
+
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    let document = StorageService::upload_document(
+        &state.db,
+        state.storage.as_ref(),
+        &state.storage_config,
+        &tenant,
+        UserId::from(student_id),
+        kind,
+        &file.filename,
+        &file.content_type,
+        &file.content,
+        auth_user.user_id()?,
+    )
+    .await?;
+
+    Ok(Created(document))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/students/{student_id}/documents",
+    summary = "List student documents",
+    params(
+        ("student_id" = Uuid, Path, description = "Student ID")
+    ),
+    responses(
+        (status = 200, description = "The student's documents, newest first", body = Vec<StudentDocument>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires students:read permission, or students:read:own_branch for students in branches you teach", body = ErrorResponse),
+        (status = 404, description = "Student not found", body = ErrorResponse)
+    ),
+    tag = "Storage",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, auth_user, scope))]
+pub async fn list_student_documents(
+    State(state): State<AppState>,
+    RequireStudentsRead(auth_user, scope): RequireStudentsRead,
+    Path(student_id): Path<Uuid>,
+) -> Result<Json<Vec<StudentDocument>>, AppError> {
+    scope.ensure_user(&state.db, student_id).await?;
+
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    let documents =
+        StorageService::list_documents(&state.db, &tenant, UserId::from(student_id)).await?;
+
+    Ok(Json(documents))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/students/{student_id}/documents/{document_id}/download",
+    summary = "Get document download link",
+    description = "Returns a presigned link that downloads the document without further \
+        authentication until it expires.",
+    params(
+        ("student_id" = Uuid, Path, description = "Student ID"),
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    responses(
+        (status = 200, description = "Download link", body = DownloadUrl),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires students:read permission, or students:read:own_branch for students in branches you teach", body = ErrorResponse),
+        (status = 404, description = "Student or document not found", body = ErrorResponse)
+    ),
+    tag = "Storage",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, auth_user, scope))]
+pub async fn get_student_document_download(
+    State(state): State<AppState>,
+    RequireStudentsRead(auth_user, scope): RequireStudentsRead,
+    Path((student_id, document_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<DownloadUrl>, AppError> {
+    scope.ensure_user(&state.db, student_id).await?;
+
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    let download = StorageService::get_document_download(
+        &state.db,
+        state.storage.as_ref(),
+        &state.storage_config,
+        &tenant,
+        UserId::from(student_id),
+        StoredFileId::from(document_id),
+    )
+    .await?;
+
+    Ok(Json(download))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/students/{student_id}/documents/{document_id}",
+    summary = "Delete student document",
+    params(
+        ("student_id" = Uuid, Path, description = "Student ID"),
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires students:update permission, or students:update:own_branch for students in branches you teach", body = ErrorResponse),
+        (status = 404, description = "Student or document not found", body = ErrorResponse)
+    ),
+    tag = "Storage",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, auth_user, scope))]
+pub async fn delete_student_document(
+    State(state): State<AppState>,
+    RequireStudentsUpdate(auth_user, scope): RequireStudentsUpdate,
+    Path((student_id, document_id)): Path<(Uuid, Uuid)>,
+) -> Result<NoContent, AppError> {
+    scope.ensure_user(&state.db, student_id).await?;
+
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    StorageService::delete_document(
+        &state.db,
+        state.storage.as_ref(),
+        &tenant,
+        UserId::from(student_id),
+        StoredFileId::from(document_id),
+    )
+    .await?;
+
+    Ok(NoContent)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/storage",
+    summary = "Get storage usage",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    responses(
+        (status = 200, description = "Storage used by the school's avatars and documents, and its quota", body = StorageUsage),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires schools:read permission for this school", body = ErrorResponse),
+        (status = 404, description = "School not found", body = ErrorResponse)
+    ),
+    tag = "Storage",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, auth_user))]
+pub async fn get_storage_usage(
+    State(state): State<AppState>,
+    RequireSchoolsRead(auth_user): RequireSchoolsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<StorageUsage>, AppError> {
+    let school_id = SchoolId::from(id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let usage = StorageService::get_usage(&state.db, &state.storage_config, school_id).await?;
+    Ok(Json(usage))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/schools/{id}/storage",
+    summary = "Set storage quota",
+    description = "Sets the school's storage quota, or returns it to the default when \
+        `quota_bytes` is omitted. Files already stored are kept; uploads are refused while \
+        the school is over its quota. System admins only.",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = UpdateStorageQuotaDto,
+    responses(
+        (status = 200, description = "Quota updated", body = StorageUsage),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - system admins only", body = ErrorResponse),
+        (status = 404, description = "School not found", body = ErrorResponse),
+        (status = 422, description = "Negative quota", body = ErrorResponse)
+    ),
+    tag = "Storage",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, auth_user))]
+pub async fn update_storage_quota(
+    State(state): State<AppState>,
+    RequireSchoolsUpdate(auth_user): RequireSchoolsUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateStorageQuotaDto>,
+) -> Result<Json<StorageUsage>, AppError> {
+    if !is_system_admin_jwt(&auth_user) {
+        return Err(AppError::forbidden(
+            "Only system admins can change storage quotas".to_string(),
+        ));
+    }
+
+    let usage =
+        StorageService::set_quota(&state.db, &state.storage_config, SchoolId::from(id), dto)
+            .await?;
+    Ok(Json(usage))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/storage/files/{key}",
+    summary = "Download file",
+    description = "Serves a file from the local storage backend. Links come from the avatar \
+        and document endpoints, are signed, and expire after a few minutes; no bearer token \
+        is needed.",
+    params(
+        ("key" = String, Path, description = "Storage key of the file"),
+        DownloadSignature
+    ),
+    responses(
+        (status = 200, description = "The file, as an attachment", content_type = "application/octet-stream", body = Vec<u8>),
+        (status = 403, description = "Invalid or expired link", body = ErrorResponse),
+        (status = 404, description = "File not found", body = ErrorResponse)
+    ),
+    tag = "Storage"
+)]
+#[instrument(skip(state, params))]
+pub async fn download_file(
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+    Query(params): Query<DownloadSignature>,
+) -> Result<Response, AppError> {
+    let file = StorageService::download(
+        &state.db,
+        state.storage.as_ref(),
+        &key,
+        params.expires,
+        &params.signature,
+    )
+    .await?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, file.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                attachment_disposition(&file.filename),
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        file.content,
+    )
+        .into_response())
+}
@@ -0,0 +1,14 @@
+//! File storage module.
+//!
+//! Keeps user avatars, student documents (birth certificates,
+//! transcripts), and message attachments in the configured
+//! [`backend::StorageBackend`]: a local directory or an S3-compatible bucket. Uploads are multipart forms checked
+//! for type, contents, and size, and count towards the school's storage
+//! quota. Files are never public; the API hands out presigned download links
+//! that expire after a few minutes.
+
+pub mod backend;
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,160 @@
+//! File storage data models and DTOs.
+//!
+//! This module re-exports storage models from the `chalkbyte-models` crate
+//! and defines which files each upload accepts.
+
+use axum::http::StatusCode;
+use chalkbyte_core::AppError;
+
+// Re-export all storage models from the shared crate
+pub use chalkbyte_models::storage::*;
+
+/// Content types and size an upload must have.
+#[derive(Debug, Clone, Copy)]
+pub struct FilePolicy {
+    /// Allowed MIME types with the extension files of each type get
+    pub allowed: &'static [(&'static str, &'static str)],
+    pub max_bytes: usize,
+    /// How the limit is described in errors
+    pub max_label: &'static str,
+}
+
+/// Avatars: PNG, JPEG, or WebP images up to 2 MB.
+pub const AVATAR_POLICY: FilePolicy = FilePolicy {
+    allowed: &[
+        ("image/png", "png"),
+        ("image/jpeg", "jpg"),
+        ("image/webp", "webp"),
+    ],
+    max_bytes: 2 * 1024 * 1024,
+    max_label: "2MB",
+};
+
+/// Student documents: PDFs and scans up to 10 MB.
+pub const DOCUMENT_POLICY: FilePolicy = FilePolicy {
+    allowed: &[
+        ("application/pdf", "pdf"),
+        ("image/png", "png"),
+        ("image/jpeg", "jpg"),
+    ],
+    max_bytes: 10 * 1024 * 1024,
+    max_label: "10MB",
+};
+
+/// Message attachments: PDFs and images up to 5 MB.
+pub const ATTACHMENT_POLICY: FilePolicy = FilePolicy {
+    allowed: &[
+        ("application/pdf", "pdf"),
+        ("image/png", "png"),
+        ("image/jpeg", "jpg"),
+    ],
+    max_bytes: 5 * 1024 * 1024,
+    max_label: "5MB",
+};
+
+/// Whether `content` starts like a file of `content_type`, so a renamed
+/// file of another type is not accepted.
+fn has_signature(content_type: &str, content: &[u8]) -> bool {
+    match content_type {
+        "application/pdf" => content.starts_with(b"%PDF-"),
+        "image/png" => content.starts_with(b"\x89PNG\r\n\x1a\n"),
+        "image/jpeg" => content.starts_with(b"\xFF\xD8\xFF"),
+        "image/webp" => {
+            content.len() >= 12 && &content[..4] == b"RIFF" && &content[8..12] == b"WEBP"
+        }
+        _ => false,
+    }
+}
+
+impl FilePolicy {
+    /// Checks an upload's size, declared content type, and contents, and
+    /// returns the extension to store it with.
+    ///
+    /// # Errors
+    ///
+    /// `413` for files over the limit and `415` for other types or
+    /// contents that do not match the declared type.
+    pub fn validate(&self, content_type: &str, content: &[u8]) -> Result<&'static str, AppError> {
+        if content.len() > self.max_bytes {
+            return Err(AppError::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                anyhow::anyhow!(
+                    "File size {} bytes exceeds {} limit",
+                    content.len(),
+                    self.max_label
+                ),
+            ));
+        }
+
+        let Some((_, extension)) = self.allowed.iter().find(|(mime, _)| *mime == content_type)
+        else {
+            let allowed: Vec<&str> = self.allowed.iter().map(|(mime, _)| *mime).collect();
+            return Err(AppError::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                anyhow::anyhow!(
+                    "MIME type '{}' not allowed. Allowed types: {}",
+                    content_type,
+                    allowed.join(", ")
+                ),
+            ));
+        };
+
+        if !has_signature(content_type, content) {
+            return Err(AppError::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                anyhow::anyhow!("File contents are not a valid {}", content_type),
+            ));
+        }
+
+        Ok(extension)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    #[test]
+    fn test_accepts_allowed_types() {
+        assert_eq!(AVATAR_POLICY.validate("image/png", PNG).unwrap(), "png");
+        assert_eq!(
+            AVATAR_POLICY
+                .validate("image/webp", b"RIFF\0\0\0\0WEBPVP8 ")
+                .unwrap(),
+            "webp"
+        );
+        assert_eq!(
+            DOCUMENT_POLICY
+                .validate("application/pdf", b"%PDF-1.7\n")
+                .unwrap(),
+            "pdf"
+        );
+    }
+
+    #[test]
+    fn test_rejects_other_types() {
+        let error = AVATAR_POLICY
+            .validate("application/pdf", b"%PDF-1.7\n")
+            .unwrap_err();
+        assert_eq!(error.status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[test]
+    fn test_rejects_mismatched_contents() {
+        let error = DOCUMENT_POLICY
+            .validate("application/pdf", PNG)
+            .unwrap_err();
+        assert_eq!(error.status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[test]
+    fn test_rejects_oversized_files() {
+        let mut content = PNG.to_vec();
+        content.resize(AVATAR_POLICY.max_bytes + 1, 0);
+
+        let error = AVATAR_POLICY.validate("image/png", &content).unwrap_err();
+        assert_eq!(error.status, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
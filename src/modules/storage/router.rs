@@ -0,0 +1,71 @@
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    routing::{delete, get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    delete_my_avatar, delete_student_document, download_file, get_my_avatar, get_storage_usage,
+    get_student_document_download, get_user_avatar, list_student_documents, update_storage_quota,
+    upload_my_avatar, upload_student_document,
+};
+use super::model::{AVATAR_POLICY, DOCUMENT_POLICY};
+
+/// Room for the multipart framing around the largest file a route accepts.
+pub const MULTIPART_OVERHEAD: usize = 64 * 1024;
+
+/// Initialize the caller's avatar router
+/// Routes: PUT /, GET /, DELETE /
+pub fn init_my_avatar_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(get_my_avatar)
+                .put(upload_my_avatar)
+                .delete(delete_my_avatar),
+        )
+        .layer(DefaultBodyLimit::max(
+            AVATAR_POLICY.max_bytes + MULTIPART_OVERHEAD,
+        ))
+}
+
+/// Initialize the avatars router
+/// Routes: GET /{user_id}
+pub fn init_avatars_router() -> Router<AppState> {
+    Router::new().route("/{user_id}", get(get_user_avatar))
+}
+
+/// Initialize the student documents router, nested under a student
+/// Routes: POST /, GET /, GET /{document_id}/download, DELETE /{document_id}
+pub fn init_student_documents_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            post(upload_student_document).get(list_student_documents),
+        )
+        .route(
+            "/{document_id}/download",
+            get(get_student_document_download),
+        )
+        .route("/{document_id}", delete(delete_student_document))
+        .layer(DefaultBodyLimit::max(
+            DOCUMENT_POLICY.max_bytes + MULTIPART_OVERHEAD,
+        ))
+}
+
+/// Initialize the school storage router, merged into the schools router
+/// Routes: GET /{id}/storage, PUT /{id}/storage
+pub fn init_school_storage_router() -> Router<AppState> {
+    Router::new().route(
+        "/{id}/storage",
+        get(get_storage_usage).put(update_storage_quota),
+    )
+}
+
+/// Initialize the signed download router for the local backend
+/// Routes: GET /files/{*key}
+pub fn init_storage_files_router() -> Router<AppState> {
+    Router::new().route("/files/{*key}", get(download_file))
+}
@@ -0,0 +1,923 @@
+use anyhow::Context;
+use sqlx::{PgConnection, PgPool};
+use tracing::{error, instrument, warn};
+use uuid::Uuid;
+
+use chalkbyte_config::StorageConfig;
+use chalkbyte_core::{AppError, ErrorCode, StorageError};
+use chalkbyte_db::TenantContext;
+use chalkbyte_models::ids::{SchoolId, StoredFileId, UserId};
+
+use crate::modules::storage::backend::StorageBackend;
+use crate::modules::storage::model::{
+    ATTACHMENT_POLICY, AVATAR_POLICY, Avatar, DOCUMENT_POLICY, DocumentKind, DownloadUrl,
+    StorageUsage, StudentDocument, UpdateStorageQuotaDto,
+};
+use crate::modules::students::service::StudentService;
+
+/// Columns of a [`StudentDocument`], read from `stored_files`.
+const DOCUMENT_COLUMNS: &str = "id, owner_id AS student_id, school_id, kind, filename, \
+    content_type, size_bytes, uploaded_by, created_at";
+
+/// A file to add to `stored_files`.
+struct NewFile<'a> {
+    owner_id: UserId,
+    school_id: Option<SchoolId>,
+    /// `avatar`, or the document kind
+    kind: &'a str,
+    storage_key: String,
+    filename: String,
+    content_type: &'a str,
+    content: &'a [u8],
+    uploaded_by: UserId,
+}
+
+/// A file as it is stored.
+#[derive(sqlx::FromRow)]
+struct StoredFile {
+    storage_key: String,
+    filename: String,
+    content_type: String,
+    size_bytes: i64,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A file served from a local download link.
+#[derive(Debug)]
+pub struct DownloadedFile {
+    pub filename: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+fn storage_error(e: StorageError) -> AppError {
+    match e {
+        StorageError::NotFound => AppError::not_found(anyhow::anyhow!("File not found")),
+        e => {
+            error!(error = %e, "File storage failed");
+            AppError::internal(anyhow::anyhow!("File storage failed: {}", e))
+        }
+    }
+}
+
+pub struct StorageService;
+
+impl StorageService {
+    /// A school's storage use and quota.
+    #[instrument(skip(db, config))]
+    pub async fn get_usage(
+        db: &PgPool,
+        config: &StorageConfig,
+        school_id: SchoolId,
+    ) -> Result<StorageUsage, AppError> {
+        sqlx::query_as::<_, StorageUsage>(
+            r#"
+            SELECT s.id AS school_id,
+                   COALESCE((SELECT SUM(f.size_bytes) FROM stored_files f WHERE f.school_id = s.id), 0)::BIGINT AS used_bytes,
+                   COALESCE(s.storage_quota_bytes, $2) AS quota_bytes,
+                   s.storage_quota_bytes IS NOT NULL AS custom_quota
+            FROM schools s
+            WHERE s.id = $1
+            "#,
+        )
+        .bind(school_id)
+        .bind(config.default_school_quota_bytes)
+        .fetch_optional(db)
+        .await
+        .context("Failed to fetch storage usage")
+        .map_err(AppError::database)?
+        .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))
+    }
+
+    /// Sets a school's storage quota, or goes back to the default.
+    ///
+    /// Files already stored are kept even if they exceed the new quota;
+    /// uploads are refused until the school is under it again.
+    #[instrument(skip(db, config))]
+    pub async fn set_quota(
+        db: &PgPool,
+        config: &StorageConfig,
+        school_id: SchoolId,
+        dto: UpdateStorageQuotaDto,
+    ) -> Result<StorageUsage, AppError> {
+        let result = sqlx::query("UPDATE schools SET storage_quota_bytes = $2 WHERE id = $1")
+            .bind(school_id)
+            .bind(dto.quota_bytes)
+            .execute(db)
+            .await
+            .context("Failed to update storage quota")
+            .map_err(AppError::database)?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::from_code(ErrorCode::SchoolNotFound));
+        }
+
+        Self::get_usage(db, config, school_id).await
+    }
+
+    /// Stores a file and records it in `conn`'s transaction, refusing it if
+    /// the school would go over its quota.
+    ///
+    /// The school's row is locked until the transaction ends, so concurrent
+    /// uploads cannot both fit in the last of the quota.
+    async fn store(
+        conn: &mut PgConnection,
+        storage: &dyn StorageBackend,
+        config: &StorageConfig,
+        file: NewFile<'_>,
+    ) -> Result<StoredFileId, AppError> {
+        let size = file.content.len() as i64;
+
+        if let Some(school_id) = file.school_id {
+            let quota = sqlx::query_scalar::<_, i64>(
+                "SELECT COALESCE(storage_quota_bytes, $2) FROM schools WHERE id = $1 FOR UPDATE",
+            )
+            .bind(school_id)
+            .bind(config.default_school_quota_bytes)
+            .fetch_optional(&mut *conn)
+            .await
+            .context("Failed to lock school for upload")
+            .map_err(AppError::database)?
+            .ok_or_else(|| AppError::from_code(ErrorCode::SchoolNotFound))?;
+
+            let used = sqlx::query_scalar::<_, i64>(
+                "SELECT COALESCE(SUM(size_bytes), 0)::BIGINT FROM stored_files WHERE school_id = $1",
+            )
+            .bind(school_id)
+            .fetch_one(&mut *conn)
+            .await
+            .context("Failed to sum storage usage")
+            .map_err(AppError::database)?;
+
+            if used + size > quota {
+                return Err(AppError::new(
+                    ErrorCode::StorageQuotaExceeded.status(),
+                    anyhow::anyhow!(
+                        "Upload of {} bytes exceeds the school's storage quota ({} of {} bytes used)",
+                        size,
+                        used,
+                        quota
+                    ),
+                )
+                .with_code(ErrorCode::StorageQuotaExceeded));
+            }
+        }
+
+        storage
+            .put(&file.storage_key, file.content, file.content_type)
+            .await
+            .map_err(storage_error)?;
+
+        let inserted = sqlx::query_scalar::<_, StoredFileId>(
+            r#"
+            INSERT INTO stored_files (owner_id, school_id, kind, storage_key, filename, content_type, size_bytes, uploaded_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id
+            "#,
+        )
+        .bind(file.owner_id)
+        .bind(file.school_id)
+        .bind(file.kind)
+        .bind(&file.storage_key)
+        .bind(&file.filename)
+        .bind(file.content_type)
+        .bind(size)
+        .bind(file.uploaded_by)
+        .fetch_one(&mut *conn)
+        .await
+        .context("Failed to record stored file")
+        .map_err(AppError::database);
+
+        if inserted.is_err() {
+            Self::discard(storage, &file.storage_key).await;
+        }
+        inserted
+    }
+
+    /// Deletes a file whose record is gone, logging rather than failing,
+    /// since the record was the only way to reach it.
+    pub async fn discard(storage: &dyn StorageBackend, key: &str) {
+        if let Err(e) = storage.delete(key).await {
+            warn!(storage.key = %key, error = %e, "Failed to delete stored file");
+        }
+    }
+
+    fn download_url(
+        storage: &dyn StorageBackend,
+        config: &StorageConfig,
+        key: &str,
+        filename: &str,
+    ) -> Result<DownloadUrl, AppError> {
+        let expires_at = chrono::Utc::now()
+            + chrono::Duration::from_std(config.url_ttl).unwrap_or(chrono::Duration::zero());
+        let url = storage
+            .presigned_url(key, filename, config.url_ttl)
+            .map_err(storage_error)?;
+
+        Ok(DownloadUrl { url, expires_at })
+    }
+
+    async fn fetch_avatar(db: &PgPool, user_id: UserId) -> Result<Option<StoredFile>, AppError> {
+        sqlx::query_as::<_, StoredFile>(
+            r#"
+            SELECT storage_key, filename, content_type, size_bytes, created_at
+            FROM stored_files
+            WHERE owner_id = $1 AND kind = 'avatar'
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(db)
+        .await
+        .context("Failed to fetch avatar")
+        .map_err(AppError::database)
+    }
+
+    fn avatar(
+        storage: &dyn StorageBackend,
+        config: &StorageConfig,
+        user_id: UserId,
+        file: StoredFile,
+    ) -> Result<Avatar, AppError> {
+        Ok(Avatar {
+            user_id,
+            download: Self::download_url(storage, config, &file.storage_key, &file.filename)?,
+            content_type: file.content_type,
+            size_bytes: file.size_bytes,
+            uploaded_at: file.created_at,
+        })
+    }
+
+    /// Sets a user's avatar, replacing the previous one.
+    #[instrument(skip(db, storage, config, content), fields(file.size = content.len()))]
+    pub async fn upload_avatar(
+        db: &PgPool,
+        storage: &dyn StorageBackend,
+        config: &StorageConfig,
+        user_id: UserId,
+        content_type: &str,
+        content: &[u8],
+    ) -> Result<Avatar, AppError> {
+        let extension = AVATAR_POLICY.validate(content_type, content)?;
+
+        let school_id =
+            sqlx::query_scalar::<_, Option<SchoolId>>("SELECT school_id FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(db)
+                .await
+                .context("Failed to fetch user school")
+                .map_err(AppError::database)?
+                .ok_or_else(|| AppError::from_code(ErrorCode::UserNotFound))?;
+
+        let mut tx = db
+            .begin()
+            .await
+            .context("Failed to begin transaction")
+            .map_err(AppError::database)?;
+
+        // The previous avatar's space is freed before the quota is checked
+        let previous = sqlx::query_scalar::<_, String>(
+            "DELETE FROM stored_files WHERE owner_id = $1 AND kind = 'avatar' RETURNING storage_key",
+        )
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to replace avatar")
+        .map_err(AppError::database)?;
+
+        let storage_key = format!("avatars/{}/{}.{}", user_id, Uuid::new_v4(), extension);
+        Self::store(
+            &mut tx,
+            storage,
+            config,
+            NewFile {
+                owner_id: user_id,
+                school_id,
+                kind: "avatar",
+                storage_key: storage_key.clone(),
+                filename: format!("avatar.{extension}"),
+                content_type,
+                content,
+                uploaded_by: user_id,
+            },
+        )
+        .await?;
+
+        if let Err(e) = tx.commit().await {
+            Self::discard(storage, &storage_key).await;
+            return Err(AppError::database(
+                anyhow::Error::new(e).context("Failed to commit avatar"),
+            ));
+        }
+        if let Some(previous) = previous {
+            Self::discard(storage, &previous).await;
+        }
+
+        Self::get_avatar(db, storage, config, user_id).await
+    }
+
+    /// A user's avatar with a download link.
+    #[instrument(skip(db, storage, config))]
+    pub async fn get_avatar(
+        db: &PgPool,
+        storage: &dyn StorageBackend,
+        config: &StorageConfig,
+        user_id: UserId,
+    ) -> Result<Avatar, AppError> {
+        let file = Self::fetch_avatar(db, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found(anyhow::anyhow!("User has no avatar")))?;
+
+        Self::avatar(storage, config, user_id, file)
+    }
+
+    /// Another user's avatar, if they are in a school `tenant` covers.
+    #[instrument(skip(db, storage, config))]
+    pub async fn get_user_avatar(
+        db: &PgPool,
+        storage: &dyn StorageBackend,
+        config: &StorageConfig,
+        tenant: &TenantContext,
+        user_id: UserId,
+    ) -> Result<Avatar, AppError> {
+        let school_id =
+            sqlx::query_scalar::<_, Option<SchoolId>>("SELECT school_id FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(db)
+                .await
+                .context("Failed to fetch user school")
+                .map_err(AppError::database)?
+                .ok_or_else(|| AppError::from_code(ErrorCode::UserNotFound))?;
+
+        let allowed = match school_id {
+            Some(school_id) => tenant.allows(school_id),
+            None => tenant.school_ids().is_none(),
+        };
+        if !allowed {
+            return Err(AppError::from_code(ErrorCode::UserNotFound));
+        }
+
+        Self::get_avatar(db, storage, config, user_id).await
+    }
+
+    /// Removes a user's avatar.
+    #[instrument(skip(db, storage))]
+    pub async fn delete_avatar(
+        db: &PgPool,
+        storage: &dyn StorageBackend,
+        user_id: UserId,
+    ) -> Result<(), AppError> {
+        let key = sqlx::query_scalar::<_, String>(
+            "DELETE FROM stored_files WHERE owner_id = $1 AND kind = 'avatar' RETURNING storage_key",
+        )
+        .bind(user_id)
+        .fetch_optional(db)
+        .await
+        .context("Failed to delete avatar")
+        .map_err(AppError::database)?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("User has no avatar")))?;
+
+        Self::discard(storage, &key).await;
+        Ok(())
+    }
+
+    /// Checks that `tenant` may reach the student and returns their school,
+    /// which their documents count towards.
+    async fn student_school(
+        db: &PgPool,
+        tenant: &TenantContext,
+        student_id: UserId,
+    ) -> Result<SchoolId, AppError> {
+        StudentService::ensure_student_access(
+            db,
+            tenant,
+            student_id.into_inner(),
+            "Cannot access documents of a student from a different school",
+        )
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::NoAssociatedSchool))
+    }
+
+    /// Uploads a document for a student.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(db, storage, config, tenant, content), fields(file.size = content.len()))]
+    pub async fn upload_document(
+        db: &PgPool,
+        storage: &dyn StorageBackend,
+        config: &StorageConfig,
+        tenant: &TenantContext,
+        student_id: UserId,
+        kind: DocumentKind,
+        filename: &str,
+        content_type: &str,
+        content: &[u8],
+        uploaded_by: UserId,
+    ) -> Result<StudentDocument, AppError> {
+        let extension = DOCUMENT_POLICY.validate(content_type, content)?;
+        let school_id = Self::student_school(db, tenant, student_id).await?;
+
+        let kind_name = kind.as_str();
+        let filename = match filename.trim() {
+            "" => format!("{kind_name}.{extension}"),
+            name => name.chars().take(255).collect(),
+        };
+        let storage_key = format!(
+            "schools/{}/students/{}/{}.{}",
+            school_id,
+            student_id,
+            Uuid::new_v4(),
+            extension
+        );
+
+        let mut tx = db
+            .begin()
+            .await
+            .context("Failed to begin transaction")
+            .map_err(AppError::database)?;
+        let id = Self::store(
+            &mut tx,
+            storage,
+            config,
+            NewFile {
+                owner_id: student_id,
+                school_id: Some(school_id),
+                kind: kind_name,
+                storage_key: storage_key.clone(),
+                filename,
+                content_type,
+                content,
+                uploaded_by,
+            },
+        )
+        .await?;
+        if let Err(e) = tx.commit().await {
+            Self::discard(storage, &storage_key).await;
+            return Err(AppError::database(
+                anyhow::Error::new(e).context("Failed to commit document"),
+            ));
+        }
+
+        Self::fetch_document(db, student_id, id).await
+    }
+
+    async fn fetch_document(
+        db: &PgPool,
+        student_id: UserId,
+        document_id: StoredFileId,
+    ) -> Result<StudentDocument, AppError> {
+        sqlx::query_as::<_, StudentDocument>(&format!(
+            "SELECT {DOCUMENT_COLUMNS} FROM stored_files \
+             WHERE id = $1 AND owner_id = $2 AND kind IN ('birth_certificate', 'transcript', 'other')"
+        ))
+        .bind(document_id)
+        .bind(student_id)
+        .fetch_optional(db)
+        .await
+        .context("Failed to fetch document")
+        .map_err(AppError::database)?
+        .ok_or_else(|| AppError::from_code(ErrorCode::DocumentNotFound))
+    }
+
+    /// A student's documents, newest first.
+    #[instrument(skip(db, tenant))]
+    pub async fn list_documents(
+        db: &PgPool,
+        tenant: &TenantContext,
+        student_id: UserId,
+    ) -> Result<Vec<StudentDocument>, AppError> {
+        Self::student_school(db, tenant, student_id).await?;
+
+        sqlx::query_as::<_, StudentDocument>(&format!(
+            "SELECT {DOCUMENT_COLUMNS} FROM stored_files \
+             WHERE owner_id = $1 AND kind IN ('birth_certificate', 'transcript', 'other') \
+             ORDER BY created_at DESC, id"
+        ))
+        .bind(student_id)
+        .fetch_all(db)
+        .await
+        .context("Failed to list documents")
+        .map_err(AppError::database)
+    }
+
+    /// A link to download one of a student's documents.
+    #[instrument(skip(db, storage, config, tenant))]
+    pub async fn get_document_download(
+        db: &PgPool,
+        storage: &dyn StorageBackend,
+        config: &StorageConfig,
+        tenant: &TenantContext,
+        student_id: UserId,
+        document_id: StoredFileId,
+    ) -> Result<DownloadUrl, AppError> {
+        Self::student_school(db, tenant, student_id).await?;
+
+        let file = sqlx::query_as::<_, StoredFile>(
+            r#"
+            SELECT storage_key, filename, content_type, size_bytes, created_at
+            FROM stored_files
+            WHERE id = $1 AND owner_id = $2 AND kind IN ('birth_certificate', 'transcript', 'other')
+            "#,
+        )
+        .bind(document_id)
+        .bind(student_id)
+        .fetch_optional(db)
+        .await
+        .context("Failed to fetch document")
+        .map_err(AppError::database)?
+        .ok_or_else(|| AppError::from_code(ErrorCode::DocumentNotFound))?;
+
+        Self::download_url(storage, config, &file.storage_key, &file.filename)
+    }
+
+    /// Deletes one of a student's documents.
+    #[instrument(skip(db, storage, tenant))]
+    pub async fn delete_document(
+        db: &PgPool,
+        storage: &dyn StorageBackend,
+        tenant: &TenantContext,
+        student_id: UserId,
+        document_id: StoredFileId,
+    ) -> Result<(), AppError> {
+        Self::student_school(db, tenant, student_id).await?;
+
+        let key = sqlx::query_scalar::<_, String>(
+            r#"
+            DELETE FROM stored_files
+            WHERE id = $1 AND owner_id = $2 AND kind IN ('birth_certificate', 'transcript', 'other')
+            RETURNING storage_key
+            "#,
+        )
+        .bind(document_id)
+        .bind(student_id)
+        .fetch_optional(db)
+        .await
+        .context("Failed to delete document")
+        .map_err(AppError::database)?
+        .ok_or_else(|| AppError::from_code(ErrorCode::DocumentNotFound))?;
+
+        Self::discard(storage, &key).await;
+        Ok(())
+    }
+
+    /// Stores a message attachment in `conn`'s transaction and returns its
+    /// ID and storage key.
+    ///
+    /// The file counts towards the school's quota. If the transaction does
+    /// not commit, the caller must [`StorageService::discard`] the key.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(conn, storage, config, content), fields(file.size = content.len()))]
+    pub async fn store_message_attachment(
+        conn: &mut PgConnection,
+        storage: &dyn StorageBackend,
+        config: &StorageConfig,
+        school_id: SchoolId,
+        filename: &str,
+        content_type: &str,
+        content: &[u8],
+        uploaded_by: UserId,
+    ) -> Result<(StoredFileId, String), AppError> {
+        let extension = ATTACHMENT_POLICY.validate(content_type, content)?;
+
+        let filename = match filename.trim() {
+            "" => format!("attachment.{extension}"),
+            name => name.chars().take(255).collect(),
+        };
+        let storage_key = format!(
+            "schools/{}/messages/{}.{}",
+            school_id,
+            Uuid::new_v4(),
+            extension
+        );
+
+        let id = Self::store(
+            conn,
+            storage,
+            config,
+            NewFile {
+                owner_id: uploaded_by,
+                school_id: Some(school_id),
+                kind: "message_attachment",
+                storage_key: storage_key.clone(),
+                filename,
+                content_type,
+                content,
+                uploaded_by,
+            },
+        )
+        .await?;
+
+        Ok((id, storage_key))
+    }
+
+    /// A link to download a message attachment. Callers check that the
+    /// user may read the message first.
+    #[instrument(skip(db, storage, config))]
+    pub async fn get_message_attachment_download(
+        db: &PgPool,
+        storage: &dyn StorageBackend,
+        config: &StorageConfig,
+        file_id: StoredFileId,
+    ) -> Result<DownloadUrl, AppError> {
+        let file = sqlx::query_as::<_, StoredFile>(
+            r#"
+            SELECT storage_key, filename, content_type, size_bytes, created_at
+            FROM stored_files
+            WHERE id = $1 AND kind = 'message_attachment'
+            "#,
+        )
+        .bind(file_id)
+        .fetch_optional(db)
+        .await
+        .context("Failed to fetch attachment")
+        .map_err(AppError::database)?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Attachment not found")))?;
+
+        Self::download_url(storage, config, &file.storage_key, &file.filename)
+    }
+
+    /// The file behind a local download link, with its content type and
+    /// name, if the link's signature is valid and has not expired.
+    #[instrument(skip(db, storage, signature))]
+    pub async fn download(
+        db: &PgPool,
+        storage: &dyn StorageBackend,
+        key: &str,
+        expires: i64,
+        signature: &str,
+    ) -> Result<DownloadedFile, AppError> {
+        if !storage.verify_download(key, expires, signature) {
+            return Err(AppError::forbidden(
+                "Download link is invalid or has expired".to_string(),
+            ));
+        }
+
+        let file = sqlx::query_as::<_, StoredFile>(
+            r#"
+            SELECT storage_key, filename, content_type, size_bytes, created_at
+            FROM stored_files
+            WHERE storage_key = $1
+            "#,
+        )
+        .bind(key)
+        .fetch_optional(db)
+        .await
+        .context("Failed to fetch stored file")
+        .map_err(AppError::database)?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("File not found")))?;
+
+        let content = storage
+            .get(&file.storage_key)
+            .await
+            .map_err(storage_error)?;
+        Ok(DownloadedFile {
+            filename: file.filename,
+            content_type: file.content_type,
+            content,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::storage::backend::LocalStorageBackend;
+    use axum::http::StatusCode;
+    use chalkbyte_models::users::system_roles;
+
+    const PDF: &[u8] = b"%PDF-1.7\n%%EOF\n";
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    fn backend() -> LocalStorageBackend {
+        LocalStorageBackend::new(
+            std::env::temp_dir().join(format!("chalkbyte-storage-{}", Uuid::new_v4())),
+            "http://localhost:3000/api/storage/files".to_string(),
+            "secret",
+        )
+    }
+
+    async fn create_school(pool: &PgPool, name: &str) -> SchoolId {
+        sqlx::query_scalar("INSERT INTO schools (name) VALUES ($1) RETURNING id")
+            .bind(name)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn create_student(pool: &PgPool, school_id: SchoolId, name: &str) -> UserId {
+        let student_id: UserId = sqlx::query_scalar(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ($1, 'Student', $2, $3) RETURNING id"#,
+        )
+        .bind(name)
+        .bind(format!(
+            "{}-{}@example.com",
+            name.to_lowercase(),
+            Uuid::new_v4()
+        ))
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(student_id)
+            .bind(system_roles::STUDENT)
+            .execute(pool)
+            .await
+            .unwrap();
+        student_id
+    }
+
+    async fn storage_key(pool: &PgPool, id: StoredFileId) -> String {
+        sqlx::query_scalar("SELECT storage_key FROM stored_files WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn upload_pdf(
+        pool: &PgPool,
+        storage: &LocalStorageBackend,
+        config: &StorageConfig,
+        school_id: SchoolId,
+        student_id: UserId,
+    ) -> Result<StudentDocument, AppError> {
+        StorageService::upload_document(
+            pool,
+            storage,
+            config,
+            &TenantContext::school(school_id),
+            student_id,
+            DocumentKind::Transcript,
+            "transcript.pdf",
+            "application/pdf",
+            PDF,
+            student_id,
+        )
+        .await
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_quota_limits_uploads_until_space_is_freed(pool: PgPool) {
+        let storage = backend();
+        let config = StorageConfig::default();
+        let school_id = create_school(&pool, "Quota School").await;
+        let student_id = create_student(&pool, school_id, "Ada").await;
+
+        StorageService::set_quota(
+            &pool,
+            &config,
+            school_id,
+            UpdateStorageQuotaDto {
+                quota_bytes: Some(PDF.len() as i64 + 5),
+            },
+        )
+        .await
+        .unwrap();
+
+        let document = upload_pdf(&pool, &storage, &config, school_id, student_id)
+            .await
+            .unwrap();
+        assert_eq!(document.kind, DocumentKind::Transcript);
+        assert_eq!(document.filename, "transcript.pdf");
+
+        let error = upload_pdf(&pool, &storage, &config, school_id, student_id)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error.code, ErrorCode::StorageQuotaExceeded);
+
+        let usage = StorageService::get_usage(&pool, &config, school_id)
+            .await
+            .unwrap();
+        assert_eq!(usage.used_bytes, PDF.len() as i64);
+        assert!(usage.custom_quota);
+
+        let key = storage_key(&pool, document.id).await;
+        StorageService::delete_document(
+            &pool,
+            &storage,
+            &TenantContext::school(school_id),
+            student_id,
+            document.id,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            storage.get(&key).await,
+            Err(StorageError::NotFound)
+        ));
+
+        upload_pdf(&pool, &storage, &config, school_id, student_id)
+            .await
+            .unwrap();
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_upload_avatar_replaces_previous(pool: PgPool) {
+        let storage = backend();
+        let config = StorageConfig::default();
+        let school_id = create_school(&pool, "Avatar School").await;
+        let user_id = create_student(&pool, school_id, "Bola").await;
+
+        StorageService::upload_avatar(&pool, &storage, &config, user_id, "image/png", PNG)
+            .await
+            .unwrap();
+        let first_key: String = sqlx::query_scalar(
+            "SELECT storage_key FROM stored_files WHERE owner_id = $1 AND kind = 'avatar'",
+        )
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let avatar =
+            StorageService::upload_avatar(&pool, &storage, &config, user_id, "image/png", PNG)
+                .await
+                .unwrap();
+        assert_eq!(avatar.content_type, "image/png");
+        assert!(avatar.download.url.contains("signature="));
+
+        let keys: Vec<String> =
+            sqlx::query_scalar("SELECT storage_key FROM stored_files WHERE owner_id = $1")
+                .bind(user_id)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_ne!(keys[0], first_key);
+        assert!(matches!(
+            storage.get(&first_key).await,
+            Err(StorageError::NotFound)
+        ));
+        assert_eq!(storage.get(&keys[0]).await.unwrap(), PNG);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_documents_are_scoped_to_the_students_school(pool: PgPool) {
+        let storage = backend();
+        let config = StorageConfig::default();
+        let school_id = create_school(&pool, "Home School").await;
+        let other_school_id = create_school(&pool, "Other School").await;
+        let student_id = create_student(&pool, school_id, "Chidi").await;
+        let document = upload_pdf(&pool, &storage, &config, school_id, student_id)
+            .await
+            .unwrap();
+
+        let other = TenantContext::school(other_school_id);
+        let error = StorageService::list_documents(&pool, &other, student_id)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+        let error = StorageService::get_document_download(
+            &pool,
+            &storage,
+            &config,
+            &other,
+            student_id,
+            document.id,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+
+        let tenant = TenantContext::school(school_id);
+        let download = StorageService::get_document_download(
+            &pool,
+            &storage,
+            &config,
+            &tenant,
+            student_id,
+            document.id,
+        )
+        .await
+        .unwrap();
+        let query = download.url.split_once('?').unwrap().1;
+        let params: std::collections::HashMap<_, _> = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .collect();
+        let key = storage_key(&pool, document.id).await;
+
+        let file = StorageService::download(
+            &pool,
+            &storage,
+            &key,
+            params["expires"].parse().unwrap(),
+            params["signature"],
+        )
+        .await
+        .unwrap();
+        assert_eq!(file.filename, "transcript.pdf");
+        assert_eq!(file.content, PDF);
+
+        let error = StorageService::download(
+            &pool,
+            &storage,
+            &key,
+            params["expires"].parse().unwrap(),
+            "00",
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+    }
+}
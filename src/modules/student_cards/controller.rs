@@ -0,0 +1,98 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::UserId;
+
+use crate::middleware::auth::RequireStudentsRead;
+use crate::modules::student_cards::model::{
+    StudentCard, VerifiedStudentCard, VerifyStudentCardDto,
+};
+use crate::modules::student_cards::service::StudentCardService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::get_optional_school_id_for_resource_operation;
+use crate::validator::ValidatedJson;
+
+/// Generate a student's ID card
+///
+/// Returns the signed card code and a QR code PNG for printing. Cards are
+/// generated on demand; printing the same student again gives the same code.
+#[utoipa::path(
+    get,
+    path = "/api/student-cards/{student_id}",
+    summary = "Get student card",
+    params(
+        ("student_id" = Uuid, Path, description = "Student ID")
+    ),
+    responses(
+        (status = 200, description = "Student card with QR code", body = StudentCard),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires students:read permission, or students:read:own_branch for students in branches you teach"),
+        (status = 404, description = "Student not found")
+    ),
+    tag = "Student Cards",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_student_card(
+    State(state): State<AppState>,
+    RequireStudentsRead(auth_user, scope): RequireStudentsRead,
+    Path(student_id): Path<Uuid>,
+) -> Result<Json<StudentCard>, AppError> {
+    scope.ensure_user(&state.db, student_id).await?;
+
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let card = StudentCardService::get_card(
+        &state.db,
+        UserId::from(student_id),
+        school_id,
+        &state.jwt_config.card_secret,
+    )
+    .await?;
+
+    Ok(Json(card))
+}
+
+/// Verify a scanned student card
+///
+/// Resolves a scanned card code to its student, e.g. at the library desk.
+/// Cards from other schools are reported as not found.
+#[utoipa::path(
+    post,
+    path = "/api/student-cards/verify",
+    summary = "Verify student card",
+    request_body = VerifyStudentCardDto,
+    responses(
+        (status = 200, description = "Card is valid", body = VerifiedStudentCard),
+        (status = 400, description = "Malformed or tampered card code"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires students:read permission, or students:read:own_branch for students in branches you teach"),
+        (status = 404, description = "Student not found in your school")
+    ),
+    tag = "Student Cards",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn verify_student_card(
+    State(state): State<AppState>,
+    RequireStudentsRead(auth_user, scope): RequireStudentsRead,
+    ValidatedJson(dto): ValidatedJson<VerifyStudentCardDto>,
+) -> Result<Json<VerifiedStudentCard>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let student = StudentCardService::verify_code(
+        &state.db,
+        &dto.code,
+        school_id,
+        &state.jwt_config.card_secret,
+    )
+    .await?;
+    scope
+        .ensure_user(&state.db, student.student_id.into_inner())
+        .await?;
+
+    Ok(Json(student))
+}
@@ -0,0 +1,12 @@
+//! Student ID cards module.
+//!
+//! Generates printable student ID cards carrying a signed QR code, and
+//! verifies scanned codes for quick lookups at the library desk. The
+//! attendance kiosk verifies codes through its own scan endpoint. Codes are
+//! signed with the server's JWT secret, so rotating that secret invalidates
+//! printed cards.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Student ID card data models and DTOs.
+//!
+//! This module re-exports student card models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all student card models from the shared crate
+pub use chalkbyte_models::student_cards::*;
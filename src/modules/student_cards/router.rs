@@ -0,0 +1,16 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{get_student_card, verify_student_card};
+
+/// Initialize the student cards router
+/// Routes: POST /verify, GET /{student_id}
+pub fn init_student_cards_router() -> Router<AppState> {
+    Router::new()
+        .route("/verify", post(verify_student_card))
+        .route("/{student_id}", get(get_student_card))
+}
@@ -0,0 +1,220 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sqlx::PgPool;
+use totp_rs::qrcodegen_image;
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, ErrorCode};
+use chalkbyte_models::ids::{SchoolId, UserId};
+
+use crate::modules::student_cards::model::{StudentCard, VerifiedStudentCard};
+use crate::modules::users::model::system_roles;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Prefix of every card code; bump it to change the code format.
+const CARD_CODE_VERSION: &str = "CB1";
+
+/// Bytes of the HMAC kept in the code, to keep the QR code small.
+const SIGNATURE_LEN: usize = 16;
+
+fn mac(secret: &str, payload: &str) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    mac
+}
+
+pub struct StudentCardService;
+
+impl StudentCardService {
+    /// Build the signed card code for a student.
+    ///
+    /// The code is `CB1.<student>.<school>.<signature>`, where the signature
+    /// is a truncated HMAC-SHA256 of the rest keyed with `secret`.
+    pub fn sign_code(student_id: UserId, school_id: SchoolId, secret: &str) -> String {
+        let payload = format!(
+            "{CARD_CODE_VERSION}.{}.{}",
+            student_id.into_inner().simple(),
+            school_id.into_inner().simple()
+        );
+        let signature = mac(secret, &payload).finalize().into_bytes();
+
+        format!("{payload}.{}", hex::encode(&signature[..SIGNATURE_LEN]))
+    }
+
+    /// Check a card code's signature and return the student and school it
+    /// names. Returns `None` for malformed or tampered codes.
+    pub fn parse_code(code: &str, secret: &str) -> Option<(UserId, SchoolId)> {
+        let (payload, signature) = code.trim().rsplit_once('.')?;
+
+        let mut parts = payload.split('.');
+        if parts.next()? != CARD_CODE_VERSION {
+            return None;
+        }
+        let student_id = Uuid::parse_str(parts.next()?).ok()?;
+        let school_id = Uuid::parse_str(parts.next()?).ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let signature = hex::decode(signature).ok()?;
+        if signature.len() != SIGNATURE_LEN {
+            return None;
+        }
+        mac(secret, payload)
+            .verify_truncated_left(&signature)
+            .ok()?;
+
+        Some((student_id.into(), school_id.into()))
+    }
+
+    async fn find_student(
+        db: &PgPool,
+        student_id: UserId,
+        school_id: Option<SchoolId>,
+    ) -> Result<VerifiedStudentCard, AppError> {
+        sqlx::query_as::<_, VerifiedStudentCard>(
+            r#"SELECT u.id AS student_id, u.school_id, u.first_name, u.last_name,
+                      u.level_id, u.branch_id
+               FROM users u
+               INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $3
+               WHERE u.id = $1
+                 AND u.school_id IS NOT NULL
+                 AND ($2::uuid IS NULL OR u.school_id = $2)"#,
+        )
+        .bind(student_id)
+        .bind(school_id)
+        .bind(system_roles::STUDENT)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::from_code(ErrorCode::StudentNotFound))
+    }
+
+    /// Generate a student's ID card with its QR code.
+    #[instrument(skip(db, secret))]
+    pub async fn get_card(
+        db: &PgPool,
+        student_id: UserId,
+        school_id: Option<SchoolId>,
+        secret: &str,
+    ) -> Result<StudentCard, AppError> {
+        let student = Self::find_student(db, student_id, school_id).await?;
+
+        let code = Self::sign_code(student.student_id, student.school_id, secret);
+        let qr_code_base64 = qrcodegen_image::draw_base64(&code)
+            .map_err(|e| AppError::internal_error(format!("Failed to generate QR code: {e}")))?;
+
+        Ok(StudentCard {
+            student_id: student.student_id,
+            school_id: student.school_id,
+            first_name: student.first_name,
+            last_name: student.last_name,
+            code,
+            qr_code_base64,
+        })
+    }
+
+    /// Resolve a scanned card code to its student.
+    ///
+    /// With `school_id` set, cards from other schools are treated as unknown.
+    /// Cards for students who have since left the school no longer verify.
+    #[instrument(skip(db, code, secret))]
+    pub async fn verify_code(
+        db: &PgPool,
+        code: &str,
+        school_id: Option<SchoolId>,
+        secret: &str,
+    ) -> Result<VerifiedStudentCard, AppError> {
+        let (student_id, card_school_id) = Self::parse_code(code, secret)
+            .ok_or_else(|| AppError::bad_request(anyhow::anyhow!("Invalid student card")))?;
+
+        if school_id.is_some_and(|id| id != card_school_id) {
+            return Err(AppError::from_code(ErrorCode::StudentNotFound));
+        }
+
+        Self::find_student(db, student_id, Some(card_school_id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "test-secret-key-at-least-32-characters-long";
+
+    async fn create_test_student(pool: &PgPool) -> (SchoolId, UserId) {
+        let school_id = sqlx::query_scalar!(
+            r#"INSERT INTO schools (name) VALUES ($1) RETURNING id"#,
+            format!("School {}", Uuid::new_v4())
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let user_id = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Ada', 'Tester', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id,
+            system_roles::STUDENT.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        (school_id.into(), user_id.into())
+    }
+
+    #[test]
+    fn test_card_code_round_trip_and_tampering() {
+        let student_id = UserId::new();
+        let school_id = SchoolId::new();
+        let code = StudentCardService::sign_code(student_id, school_id, SECRET);
+
+        assert_eq!(
+            StudentCardService::parse_code(&code, SECRET),
+            Some((student_id, school_id))
+        );
+        assert_eq!(
+            StudentCardService::parse_code(&code, "another-secret"),
+            None
+        );
+
+        // Swapping in another student keeps the old signature, so it must fail
+        let forged = code.replace(
+            &student_id.into_inner().simple().to_string(),
+            &UserId::new().into_inner().simple().to_string(),
+        );
+        assert_eq!(StudentCardService::parse_code(&forged, SECRET), None);
+        assert_eq!(StudentCardService::parse_code("not-a-card", SECRET), None);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_verify_code_is_scoped_to_school(pool: PgPool) {
+        let (school_id, student_id) = create_test_student(&pool).await;
+
+        let card = StudentCardService::get_card(&pool, student_id, Some(school_id), SECRET)
+            .await
+            .unwrap();
+        assert!(!card.qr_code_base64.is_empty());
+
+        let verified = StudentCardService::verify_code(&pool, &card.code, Some(school_id), SECRET)
+            .await
+            .unwrap();
+        assert_eq!(verified.student_id, student_id);
+
+        let other_school =
+            StudentCardService::verify_code(&pool, &card.code, Some(SchoolId::new()), SECRET).await;
+        assert!(other_school.is_err());
+    }
+}
@@ -1,25 +1,34 @@
-use chalkbyte_core::AppError;
+use chalkbyte_core::{
+    AppError, Created, ExportFormat, ExportParams, NoContent, ResponseView, Visible, permissions,
+};
+
+#[cfg(feature = "observability")]
+use chalkbyte_observability::metrics;
 
+use crate::config::database::timed;
 use crate::middleware::auth::{
     RequireStudentsCreate, RequireStudentsDelete, RequireStudentsRead, RequireStudentsUpdate,
 };
-use crate::middleware::role::is_system_admin_jwt;
 use crate::modules::auth::controller::ErrorResponse;
+use crate::modules::custom_fields::service::CustomFieldService;
 use crate::modules::students::model::{
     CreateStudentDto, PaginatedStudentsResponse, PaginationMeta, QueryParams, Student,
-    UpdateStudentDto,
+    StudentImportForm, StudentImportParams, StudentImportReport, StudentListResponse, StudentLite,
+    StudentLiteListResponse, UpdateStudentDto,
 };
 use crate::modules::students::service::StudentService;
 use crate::state::AppState;
-use crate::utils::auth_helpers::{get_admin_school_id, get_school_id_for_scoped_operation};
+use crate::utils::auth_helpers::{get_school_id_for_scoped_operation, tenant_context};
+use crate::validator::ValidatedJson;
 use axum::{
     Json,
-    extract::{Path, Query, State},
+    extract::{Multipart, OriginalUri, Path, Query, State},
+    http::HeaderMap,
+    response::Response,
 };
-use serde_json::json;
+use chalkbyte_cache::{ListEtag, lists};
 use tracing::instrument;
 use uuid::Uuid;
-use validator::Validate;
 
 #[utoipa::path(
     post,
@@ -27,10 +36,11 @@ use validator::Validate;
     summary = "Create student",
     request_body = CreateStudentDto,
     responses(
-        (status = 200, description = "Student created successfully", body = Student),
+        Created<Student>,
         (status = 400, description = "Bad request", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 403, description = "Forbidden - requires students:create permission", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     security(
@@ -42,11 +52,8 @@ use validator::Validate;
 pub async fn create_student(
     State(state): State<AppState>,
     RequireStudentsCreate(auth_user): RequireStudentsCreate,
-    Json(dto): Json<CreateStudentDto>,
-) -> Result<Json<Student>, AppError> {
-    dto.validate()
-        .map_err(|e| AppError::unprocessable(anyhow::anyhow!("Validation failed: {}", e)))?;
-
+    ValidatedJson(dto): ValidatedJson<CreateStudentDto>,
+) -> Result<Created<Student>, AppError> {
     let school_id =
         get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
 
@@ -55,9 +62,92 @@ pub async fn create_student(
         dto,
         school_id.into_inner(),
         state.cache.as_ref(),
+        &state.password_policy,
+    )
+    .await?;
+
+    #[cfg(feature = "observability")]
+    metrics::track_students_created(school_id.into_inner(), 1);
+
+    Ok(Created(student))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/students/import",
+    summary = "Import students from CSV",
+    description = "Upload a CSV with `first_name`, `last_name`, and `email` columns and optional \
+        `date_of_birth` (YYYY-MM-DD), `grade_level`, `level`, `branch`, and `password` columns. \
+        Level and branch are matched by name. Every row is validated first and nothing is \
+        imported if any row has errors. Use `dry_run=true` to only get the validation report.",
+    params(StudentImportParams),
+    request_body(content = StudentImportForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Validation report, with the number of students imported", body = StudentImportReport),
+        (status = 400, description = "Missing or unreadable CSV file", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires students:create permission", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Students"
+)]
+#[instrument(skip(state, multipart))]
+pub async fn import_students(
+    State(state): State<AppState>,
+    RequireStudentsCreate(auth_user): RequireStudentsCreate,
+    Query(params): Query<StudentImportParams>,
+    mut multipart: Multipart,
+) -> Result<Json<StudentImportReport>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+
+    let mut file = None;
+    let mut default_password = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::bad_request(anyhow::anyhow!("Invalid multipart body: {}", e)))?
+    {
+        match field.name() {
+            Some("file") => {
+                let bytes = field.bytes().await.map_err(|e| {
+                    AppError::bad_request(anyhow::anyhow!("Could not read file: {}", e))
+                })?;
+                file = Some(bytes);
+            }
+            Some("default_password") => {
+                let text = field.text().await.map_err(|e| {
+                    AppError::bad_request(anyhow::anyhow!("Could not read default_password: {}", e))
+                })?;
+                if !text.is_empty() {
+                    default_password = Some(text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let file =
+        file.ok_or_else(|| AppError::bad_request(anyhow::anyhow!("Missing 'file' field")))?;
+
+    let report = StudentService::import_students(
+        &state.db,
+        state.cache.as_ref(),
+        school_id,
+        &file,
+        default_password,
+        params.dry_run,
+        &state.password_policy,
     )
     .await?;
-    Ok(Json(student))
+
+    #[cfg(feature = "observability")]
+    metrics::track_students_created(school_id.into_inner(), report.imported_count as u64);
+
+    Ok(Json(report))
 }
 
 #[utoipa::path(
@@ -65,12 +155,18 @@ pub async fn create_student(
     path = "/api/students",
     summary = "List students",
     params(
-        QueryParams
+        QueryParams,
+        ("view" = Option<String>, Query, description = "Set to `lite` for the slimmer mobile view"),
+        ("X-Client" = Option<String>, Header, description = "Set to `mobile-lite` for the slimmer mobile view"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a previously fetched list")
     ),
     responses(
-        (status = 200, description = "List of students", body = PaginatedStudentsResponse),
+        (status = 200, description = "List of students, page or cursor based", body = StudentListResponse),
+        (status = 200, description = "Lite view of the students list", body = StudentLiteListResponse),
+        (status = 304, description = "Not modified - the list still matches If-None-Match"),
+        (status = 400, description = "Invalid filter or pagination cursor", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
-        (status = 403, description = "Forbidden - requires students:read permission", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires students:read permission, or students:read:own_branch for students in branches you teach", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     security(
@@ -81,19 +177,77 @@ pub async fn create_student(
 #[instrument(skip(state))]
 pub async fn get_students(
     State(state): State<AppState>,
-    RequireStudentsRead(auth_user): RequireStudentsRead,
+    RequireStudentsRead(auth_user, scope): RequireStudentsRead,
+    view: ResponseView,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
     Query(params): Query<QueryParams>,
-) -> Result<Json<PaginatedStudentsResponse>, AppError> {
+) -> Result<Response, AppError> {
     let school_id =
         get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
 
+    let custom_fields = CustomFieldService::parse_filter(params.custom_fields.as_deref())?;
+    let branch_ids = scope.branch_ids();
+
+    // Teachers limited to their branches and callers with different field
+    // access see different lists, so both are part of the request
+    let etag = ListEtag::lookup(
+        state.cache.as_ref(),
+        lists::STUDENTS,
+        Some(school_id.into()),
+        &(uri.to_string(), view, auth_user.field_access(), &branch_ids),
+    )
+    .await;
+    if let Some(not_modified) = etag.not_modified(&headers) {
+        return Ok(not_modified);
+    }
+
+    if let Some(pagination) = params.cursor_pagination() {
+        let page = state
+            .pools
+            .read_only(async |db| {
+                timed(
+                    "students.list",
+                    StudentService::get_students_by_cursor(
+                        db,
+                        school_id.into_inner(),
+                        custom_fields.as_ref(),
+                        branch_ids.as_deref(),
+                        pagination,
+                    ),
+                )
+                .await
+            })
+            .await?;
+        return Ok(etag
+            .store(
+                view.render_visible(StudentListResponse::Cursor(page), &auth_user.field_access()),
+            )
+            .await);
+    }
+
     let limit = params.limit();
     let offset = params.offset();
     let page = params.page();
 
-    let (students, total) =
-        StudentService::get_students_by_school(&state.db, school_id.into_inner(), limit, offset)
-            .await?;
+    let (students, total) = state
+        .pools
+        .read_only(async |db| {
+            timed(
+                "students.list",
+                StudentService::get_students_by_school(
+                    db,
+                    school_id.into_inner(),
+                    custom_fields.as_ref(),
+                    branch_ids.as_deref(),
+                    &params.sort_params(),
+                    limit,
+                    offset,
+                ),
+            )
+            .await
+        })
+        .await?;
 
     let total_pages = (total as f64 / limit as f64).ceil() as i64;
 
@@ -107,7 +261,73 @@ pub async fn get_students(
         },
     };
 
-    Ok(Json(response))
+    Ok(etag
+        .store(view.render_visible(
+            StudentListResponse::Paged(response),
+            &auth_user.field_access(),
+        ))
+        .await)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/students/export",
+    summary = "Export students",
+    params(ExportParams, QueryParams),
+    responses(
+        (status = 200, description = "CSV of the school's matching students", content_type = "text/csv", body = String),
+        (status = 400, description = "Invalid filter or format", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires students:read permission, or students:read:own_branch for students in branches you teach, and contact_details:read", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Students"
+)]
+#[instrument(skip(state))]
+pub async fn export_students(
+    State(state): State<AppState>,
+    RequireStudentsRead(auth_user, scope): RequireStudentsRead,
+    Query(export): Query<ExportParams>,
+    Query(params): Query<QueryParams>,
+) -> Result<Response, AppError> {
+    // The CSV carries emails and dates of birth, which cannot be left out
+    // field by field the way JSON responses are
+    if !auth_user
+        .field_access()
+        .allows(permissions::CONTACT_DETAILS_READ)
+    {
+        return Err(AppError::forbidden(
+            "Exporting students requires the contact_details:read permission".to_string(),
+        ));
+    }
+
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+
+    let custom_fields = CustomFieldService::parse_filter(params.custom_fields.as_deref())?;
+
+    match export.format {
+        ExportFormat::Csv => {
+            state
+                .pools
+                .read_only(async |db| {
+                    timed(
+                        "students.export",
+                        StudentService::export_students_csv(
+                            db,
+                            school_id.into_inner(),
+                            custom_fields,
+                            scope.branch_ids(),
+                        ),
+                    )
+                    .await
+                })
+                .await
+        }
+    }
 }
 
 #[utoipa::path(
@@ -115,12 +335,15 @@ pub async fn get_students(
     path = "/api/students/{id}",
     summary = "Get student by ID",
     params(
-        ("id" = Uuid, Path, description = "Student ID")
+        ("id" = Uuid, Path, description = "Student ID"),
+        ("view" = Option<String>, Query, description = "Set to `lite` for the slimmer mobile view"),
+        ("X-Client" = Option<String>, Header, description = "Set to `mobile-lite` for the slimmer mobile view")
     ),
     responses(
         (status = 200, description = "Student details", body = Student),
+        (status = 200, description = "Lite view of the student", body = StudentLite),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
-        (status = 403, description = "Forbidden - requires students:read permission", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires students:read permission, or students:read:own_branch for students in branches you teach", body = ErrorResponse),
         (status = 404, description = "Student not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
@@ -132,19 +355,15 @@ pub async fn get_students(
 #[instrument(skip(state))]
 pub async fn get_student(
     State(state): State<AppState>,
-    RequireStudentsRead(auth_user): RequireStudentsRead,
+    RequireStudentsRead(auth_user, scope): RequireStudentsRead,
+    view: ResponseView,
     Path(id): Path<Uuid>,
-) -> Result<Json<Student>, AppError> {
-    // System admins can access any student
-    if is_system_admin_jwt(&auth_user) {
-        let student = StudentService::get_student_by_id_no_school_filter(&state.db, id).await?;
-        return Ok(Json(student));
-    }
+) -> Result<Response, AppError> {
+    scope.ensure_user(&state.db, id).await?;
 
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-
-    let student = StudentService::get_student_by_id(&state.db, id, school_id.into_inner()).await?;
-    Ok(Json(student))
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    let student = StudentService::get_student_by_id(&state.db, &tenant, id).await?;
+    Ok(view.render_visible(student, &auth_user.field_access()))
 }
 
 #[utoipa::path(
@@ -159,8 +378,9 @@ pub async fn get_student(
         (status = 200, description = "Student updated successfully", body = Student),
         (status = 400, description = "Bad request", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
-        (status = 403, description = "Forbidden - requires students:update permission", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires students:update permission, or students:update:own_branch for students in branches you teach", body = ErrorResponse),
         (status = 404, description = "Student not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     security(
@@ -171,36 +391,23 @@ pub async fn get_student(
 #[instrument(skip(state))]
 pub async fn update_student(
     State(state): State<AppState>,
-    RequireStudentsUpdate(auth_user): RequireStudentsUpdate,
+    RequireStudentsUpdate(auth_user, scope): RequireStudentsUpdate,
     Path(id): Path<Uuid>,
-    Json(dto): Json<UpdateStudentDto>,
-) -> Result<Json<Student>, AppError> {
-    dto.validate()
-        .map_err(|e| AppError::unprocessable(anyhow::anyhow!("Validation failed: {}", e)))?;
-
-    // System admins can update any student
-    if is_system_admin_jwt(&auth_user) {
-        let student = StudentService::update_student_no_school_filter(
-            &state.db,
-            id,
-            dto,
-            state.cache.as_ref(),
-        )
-        .await?;
-        return Ok(Json(student));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
+    ValidatedJson(dto): ValidatedJson<UpdateStudentDto>,
+) -> Result<Visible<Student>, AppError> {
+    scope.ensure_user(&state.db, id).await?;
 
+    let tenant = tenant_context(&state.db, &auth_user).await?;
     let student = StudentService::update_student(
         &state.db,
+        &tenant,
         id,
-        school_id.into_inner(),
         dto,
         state.cache.as_ref(),
+        &state.password_policy,
     )
     .await?;
-    Ok(Json(student))
+    Ok(Visible::new(student, auth_user.field_access()))
 }
 
 #[utoipa::path(
@@ -211,7 +418,7 @@ pub async fn update_student(
         ("id" = Uuid, Path, description = "Student ID")
     ),
     responses(
-        (status = 200, description = "Student deleted successfully"),
+        NoContent,
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 403, description = "Forbidden - requires students:delete permission", body = ErrorResponse),
         (status = 404, description = "Student not found", body = ErrorResponse),
@@ -227,17 +434,15 @@ pub async fn delete_student(
     State(state): State<AppState>,
     RequireStudentsDelete(auth_user): RequireStudentsDelete,
     Path(id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    // System admins can delete any student
-    if is_system_admin_jwt(&auth_user) {
-        StudentService::delete_student_no_school_filter(&state.db, id, state.cache.as_ref())
-            .await?;
-        return Ok(Json(json!({"message": "Student deleted successfully"})));
-    }
-
-    let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-
-    StudentService::delete_student(&state.db, id, school_id.into_inner(), state.cache.as_ref())
-        .await?;
-    Ok(Json(json!({"message": "Student deleted successfully"})))
+) -> Result<NoContent, AppError> {
+    let tenant = tenant_context(&state.db, &auth_user).await?;
+    StudentService::delete_student(
+        &state.db,
+        &tenant,
+        id,
+        auth_user.user_id()?,
+        state.cache.as_ref(),
+    )
+    .await?;
+    Ok(NoContent)
 }
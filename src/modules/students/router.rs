@@ -1,5 +1,6 @@
 use crate::modules::students::controller::{
-    create_student, delete_student, get_student, get_students, update_student,
+    create_student, delete_student, export_students, get_student, get_students, import_students,
+    update_student,
 };
 use crate::state::AppState;
 use axum::{
@@ -10,6 +11,8 @@ use axum::{
 pub fn init_students_router() -> Router<AppState> {
     Router::new()
         .route("/", post(create_student).get(get_students))
+        .route("/export", get(export_students))
+        .route("/import", post(import_students))
         .route(
             "/{id}",
             get(get_student).put(update_student).delete(delete_student),
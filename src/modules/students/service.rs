@@ -1,33 +1,291 @@
+use std::collections::{HashMap, HashSet};
+
 use crate::{
-    modules::students::model::{CreateStudentDto, Student, UpdateStudentDto},
+    config::database::{TenantContext, UnitOfWork},
+    modules::custom_fields::model::CustomFieldEntity,
+    modules::custom_fields::service::CustomFieldService,
+    modules::imports::model::ImportIssueCode,
+    modules::students::model::{
+        CreateStudentDto, Student, StudentExportRow, StudentImportReport, StudentImportRow,
+        StudentImportRowError, UpdateStudentDto,
+    },
+    modules::trash::model::TrashItemType,
+    modules::trash::service::TrashService,
     modules::users::model::system_roles,
-    utils::{errors::AppError, password::hash_password},
+    utils::{
+        csv_export::{push_arg, stream_csv},
+        csv_import::{CsvRow, read_csv_rows},
+        errors::AppError,
+        pagination::{Cursor, CursorPage, CursorPaginationParams},
+        password::{PasswordPolicy, hash_password},
+    },
 };
 use anyhow::Context;
+use axum::response::Response;
 use chalkbyte_cache::{RedisCache, invalidate};
+use chalkbyte_core::{ErrorCode, SortParams, Sortable};
 use chalkbyte_models::Email;
-use sqlx::PgPool;
+use chalkbyte_models::ids::{BranchId, LevelId, SchoolId, UserId};
+use chrono::{NaiveDate, Utc};
+use serde_json::Value;
+use sqlx::{Acquire, PgConnection, PgPool, Postgres, postgres::PgArguments};
 use tracing::instrument;
 use uuid::Uuid;
 
+/// Rows inserted per statement during a CSV import.
+const IMPORT_BATCH_SIZE: usize = 500;
+
+/// Fields the student list can be sorted by
+const STUDENT_SORT: Sortable = Sortable {
+    columns: &[
+        ("first_name", "u.first_name"),
+        ("last_name", "u.last_name"),
+        ("email", "u.email"),
+        ("date_of_birth", "u.date_of_birth"),
+        ("grade_level", "u.grade_level"),
+        ("created_at", "u.created_at"),
+    ],
+    default: "last_name,first_name",
+    tiebreaker: "u.id",
+};
+
+/// A student import row that passed validation.
+pub(crate) struct ValidImportRow {
+    first_name: String,
+    last_name: String,
+    pub(crate) email: String,
+    /// `None` when the row uses the import's default password
+    password: Option<String>,
+    date_of_birth: Option<NaiveDate>,
+    grade_level: Option<String>,
+    level_id: Option<LevelId>,
+    branch_id: Option<BranchId>,
+}
+
+/// School data rows are checked against during an import.
+pub(crate) struct ImportLookups {
+    /// Level IDs by lowercased name
+    pub(crate) levels: HashMap<String, LevelId>,
+    /// Branch IDs by level and lowercased name
+    pub(crate) branches: HashMap<(LevelId, String), BranchId>,
+    /// Lowercased emails that are already registered
+    pub(crate) existing_emails: HashSet<String>,
+}
+
+/// Read the data rows of a student import CSV with their line numbers.
+pub(crate) fn read_import_csv(bytes: &[u8]) -> Result<Vec<CsvRow<StudentImportRow>>, AppError> {
+    read_csv_rows(
+        bytes,
+        &StudentImportRow::REQUIRED_COLUMNS,
+        StudentImportRow::MAX_ROWS,
+    )
+}
+
+/// Check one import row, recording any problems in `errors`.
+///
+/// `seen_emails` tracks emails earlier in the file to catch duplicates.
+pub(crate) fn validate_import_row(
+    line: usize,
+    row: StudentImportRow,
+    lookups: &ImportLookups,
+    has_default_password: bool,
+    password_policy: &PasswordPolicy,
+    seen_emails: &mut HashMap<String, usize>,
+    errors: &mut Vec<StudentImportRowError>,
+) -> Option<ValidImportRow> {
+    let errors_before = errors.len();
+    let mut error = |column: &str, code: ImportIssueCode, message: String| {
+        errors.push(StudentImportRowError {
+            line,
+            column: Some(column.to_string()),
+            code,
+            message,
+        });
+    };
+
+    for (column, value) in [
+        ("first_name", &row.first_name),
+        ("last_name", &row.last_name),
+    ] {
+        if value.is_empty() || value.chars().count() > 100 {
+            error(
+                column,
+                ImportIssueCode::InvalidValue,
+                "must be 1-100 characters".to_string(),
+            );
+        }
+    }
+
+    let email_key = row.email.to_lowercase();
+    if let Err(e) = Email::new(row.email.as_str()) {
+        error("email", ImportIssueCode::InvalidValue, e.to_string());
+    } else if let Some(first_line) = seen_emails.get(&email_key) {
+        error(
+            "email",
+            ImportIssueCode::Duplicate,
+            format!("duplicates the email on line {}", first_line),
+        );
+    } else if lookups.existing_emails.contains(&email_key) {
+        error(
+            "email",
+            ImportIssueCode::AlreadyExists,
+            "is already registered".to_string(),
+        );
+    }
+    seen_emails.entry(email_key).or_insert(line);
+
+    let date_of_birth = match row.date_of_birth.as_deref() {
+        None => None,
+        Some(value) => match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            Ok(date) if date > Utc::now().date_naive() => {
+                error(
+                    "date_of_birth",
+                    ImportIssueCode::InvalidValue,
+                    "cannot be in the future".to_string(),
+                );
+                None
+            }
+            Ok(date) => Some(date),
+            Err(_) => {
+                error(
+                    "date_of_birth",
+                    ImportIssueCode::InvalidValue,
+                    "must be a date in YYYY-MM-DD format".to_string(),
+                );
+                None
+            }
+        },
+    };
+
+    if let Some(grade_level) = &row.grade_level
+        && grade_level.chars().count() > 10
+    {
+        error(
+            "grade_level",
+            ImportIssueCode::InvalidValue,
+            "must be at most 10 characters".to_string(),
+        );
+    }
+
+    let level_id = row.level.as_deref().and_then(|name| {
+        let level_id = lookups.levels.get(&name.to_lowercase()).copied();
+        if level_id.is_none() {
+            error(
+                "level",
+                ImportIssueCode::UnknownReference,
+                format!("no level named '{}' in this school", name),
+            );
+        }
+        level_id
+    });
+
+    let branch_id = match (row.branch.as_deref(), row.level.as_deref(), level_id) {
+        (None, _, _) => None,
+        (Some(_), None, _) => {
+            error(
+                "branch",
+                ImportIssueCode::MissingValue,
+                "requires a level".to_string(),
+            );
+            None
+        }
+        (Some(_), Some(_), None) => None,
+        (Some(name), Some(level), Some(level_id)) => {
+            let branch_id = lookups
+                .branches
+                .get(&(level_id, name.to_lowercase()))
+                .copied();
+            if branch_id.is_none() {
+                error(
+                    "branch",
+                    ImportIssueCode::UnknownReference,
+                    format!("no branch named '{}' in level '{}'", name, level),
+                );
+            }
+            branch_id
+        }
+    };
+
+    match row.password.as_deref() {
+        Some(password) => {
+            let violations = password_policy
+                .violations(password, &[&row.first_name, &row.last_name, &row.email]);
+            if !violations.is_empty() {
+                error(
+                    "password",
+                    ImportIssueCode::PolicyViolation,
+                    violations.join("; "),
+                );
+            }
+        }
+        None if !has_default_password => {
+            error(
+                "password",
+                ImportIssueCode::MissingValue,
+                "is required when no default_password is given".to_string(),
+            );
+        }
+        None => {}
+    }
+
+    if errors.len() > errors_before {
+        return None;
+    }
+
+    Some(ValidImportRow {
+        first_name: row.first_name,
+        last_name: row.last_name,
+        email: row.email,
+        password: row.password,
+        date_of_birth,
+        grade_level: row.grade_level,
+        level_id,
+        branch_id,
+    })
+}
+
+/// Where [`StudentService::batch_assign_students`] moves students.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum AssignmentTarget {
+    Level(LevelId),
+    Branch(BranchId),
+}
+
 pub struct StudentService;
 
 impl StudentService {
-    #[instrument(skip(db, dto, cache))]
+    /// Create a student and give them the student role. Runs on the pool or
+    /// on a transaction it is composed in.
+    #[instrument(skip(db, dto, cache, password_policy))]
     pub async fn create_student(
-        db: &PgPool,
+        db: impl Acquire<'_, Database = Postgres>,
         dto: CreateStudentDto,
         school_id: Uuid,
         cache: Option<&RedisCache>,
+        password_policy: &PasswordPolicy,
     ) -> Result<Student, AppError> {
+        password_policy.validate(
+            &dto.password,
+            &[&dto.first_name, &dto.last_name, dto.email.as_str()],
+        )?;
         let hashed_password = hash_password(&dto.password)?;
+        let mut conn = db.acquire().await?;
+        let custom_fields = CustomFieldService::resolve_values(
+            &mut *conn,
+            Some(school_id.into()),
+            CustomFieldEntity::Student,
+            &Value::Null,
+            &dto.custom_fields.unwrap_or_default(),
+            true,
+        )
+        .await?;
 
         // Insert user without role column
         let student = sqlx::query_as::<_, Student>(
             r#"
-            INSERT INTO users (first_name, last_name, email, password, school_id, date_of_birth, grade_level)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
-            RETURNING id, first_name, last_name, email, school_id, date_of_birth, grade_level, created_at, updated_at
+            INSERT INTO users (first_name, last_name, email, password, school_id, date_of_birth, grade_level, custom_fields)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, first_name, last_name, email, school_id, date_of_birth, grade_level, custom_fields, created_at, updated_at
             "#,
         )
         .bind(&dto.first_name)
@@ -37,7 +295,8 @@ impl StudentService {
         .bind(school_id)
         .bind(dto.date_of_birth)
         .bind(&dto.grade_level)
-        .fetch_one(db)
+        .bind(&custom_fields)
+        .fetch_one(&mut *conn)
         .await
         .map_err(|e| {
             if let sqlx::Error::Database(db_err) = &e
@@ -57,7 +316,7 @@ impl StudentService {
         )
         .bind(student.id)
         .bind(system_roles::STUDENT)
-        .execute(db)
+        .execute(&mut *conn)
         .await
         .map_err(|e| AppError::database(anyhow::Error::from(e)))?;
 
@@ -71,10 +330,14 @@ impl StudentService {
     pub async fn get_students_by_school(
         db: &PgPool,
         school_id: Uuid,
+        custom_fields: Option<&Value>,
+        branch_ids: Option<&[Uuid]>,
+        sort: &SortParams,
         limit: i64,
         offset: i64,
     ) -> Result<(Vec<Student>, i64), AppError> {
         let student_role_id = system_roles::STUDENT;
+        let order_by = STUDENT_SORT.order_by(sort)?;
 
         let total = sqlx::query_scalar::<_, i64>(
             r#"
@@ -82,27 +345,35 @@ impl StudentService {
             FROM users u
             INNER JOIN user_roles ur ON ur.user_id = u.id
             WHERE u.school_id = $1 AND ur.role_id = $2
+              AND ($3::jsonb IS NULL OR u.custom_fields @> $3)
+              AND ($4::uuid[] IS NULL OR u.branch_id = ANY($4))
             "#,
         )
         .bind(school_id)
         .bind(student_role_id)
+        .bind(custom_fields)
+        .bind(branch_ids)
         .fetch_one(db)
         .await
         .context("Failed to count students by school")
         .map_err(AppError::database)?;
 
-        let students = sqlx::query_as::<_, Student>(
+        let students = sqlx::query_as::<_, Student>(&format!(
             r#"
-            SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.date_of_birth, u.grade_level, u.created_at, u.updated_at
+            SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.date_of_birth, u.grade_level, u.custom_fields, u.created_at, u.updated_at
             FROM users u
             INNER JOIN user_roles ur ON ur.user_id = u.id
             WHERE u.school_id = $1 AND ur.role_id = $2
-            ORDER BY u.last_name, u.first_name
-            LIMIT $3 OFFSET $4
-            "#,
-        )
+              AND ($3::jsonb IS NULL OR u.custom_fields @> $3)
+              AND ($4::uuid[] IS NULL OR u.branch_id = ANY($4))
+            ORDER BY {order_by}
+            LIMIT $5 OFFSET $6
+            "#
+        ))
         .bind(school_id)
         .bind(student_role_id)
+        .bind(custom_fields)
+        .bind(branch_ids)
         .bind(limit)
         .bind(offset)
         .fetch_all(db)
@@ -113,144 +384,325 @@ impl StudentService {
         Ok((students, total))
     }
 
+    /// List a school's students newest first using keyset pagination on
+    /// `(created_at, id)`.
     #[instrument(skip(db))]
-    pub async fn get_student_by_id(
+    pub async fn get_students_by_cursor(
         db: &PgPool,
-        id: Uuid,
         school_id: Uuid,
-    ) -> Result<Student, AppError> {
-        let student_role_id = system_roles::STUDENT;
+        custom_fields: Option<&Value>,
+        branch_ids: Option<&[Uuid]>,
+        pagination: CursorPaginationParams,
+    ) -> Result<CursorPage<Student>, AppError> {
+        let limit = pagination.limit();
+        let after = pagination.after()?;
 
-        // Check if user exists and is a student
-        let student_exists = sqlx::query_scalar::<_, bool>(
+        let students = sqlx::query_as::<_, Student>(
             r#"
-            SELECT EXISTS(
-                SELECT 1 FROM users u
-                INNER JOIN user_roles ur ON ur.user_id = u.id
-                WHERE u.id = $1 AND ur.role_id = $2
+            SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.date_of_birth, u.grade_level, u.custom_fields, u.created_at, u.updated_at
+            FROM users u
+            INNER JOIN user_roles ur ON ur.user_id = u.id
+            WHERE u.school_id = $1 AND ur.role_id = $2
+              AND ($3::jsonb IS NULL OR u.custom_fields @> $3)
+              AND ($4::uuid[] IS NULL OR u.branch_id = ANY($4))
+              AND ($5::timestamptz IS NULL OR (u.created_at, u.id) < ($5, $6))
+            ORDER BY u.created_at DESC, u.id DESC
+            LIMIT $7
+            "#,
+        )
+        .bind(school_id)
+        .bind(system_roles::STUDENT)
+        .bind(custom_fields)
+        .bind(branch_ids)
+        .bind(after.map(|c| c.created_at))
+        .bind(after.map(|c| c.id))
+        .bind(limit + 1)
+        .fetch_all(db)
+        .await
+        .context("Failed to fetch students by cursor")
+        .map_err(AppError::database)?;
+
+        Ok(CursorPage::from_rows(students, limit, |student| {
+            Cursor::new(
+                student.created_at.unwrap_or_default(),
+                student.id.into_inner(),
             )
+        }))
+    }
+
+    /// Stream every student of a school matching the list filters as CSV,
+    /// ordered like the paginated list.
+    #[instrument(skip(db))]
+    pub async fn export_students_csv(
+        db: &PgPool,
+        school_id: Uuid,
+        custom_fields: Option<Value>,
+        branch_ids: Option<Vec<Uuid>>,
+    ) -> Result<Response, AppError> {
+        let mut args = PgArguments::default();
+        push_arg(&mut args, school_id)?;
+        push_arg(&mut args, system_roles::STUDENT)?;
+        push_arg(&mut args, custom_fields)?;
+        push_arg(&mut args, branch_ids)?;
+
+        stream_csv::<StudentExportRow>(
+            db,
+            r#"
+            SELECT u.id, u.first_name, u.last_name, u.email, u.date_of_birth, u.grade_level,
+                   l.name AS level, b.name AS branch, u.custom_fields::text AS custom_fields,
+                   u.created_at
+            FROM users u
+            INNER JOIN user_roles ur ON ur.user_id = u.id
+            LEFT JOIN levels l ON l.id = u.level_id
+            LEFT JOIN branches b ON b.id = u.branch_id
+            WHERE u.school_id = $1 AND ur.role_id = $2
+              AND ($3::jsonb IS NULL OR u.custom_fields @> $3)
+              AND ($4::uuid[] IS NULL OR u.branch_id = ANY($4))
+            ORDER BY u.last_name, u.first_name, u.id
+            "#,
+            args,
+            "students",
+        )
+        .await
+    }
+
+    /// Check that a student exists and belongs to one of the tenant's
+    /// schools, returning their school.
+    ///
+    /// Looked up unscoped, so that another school's student is reported as
+    /// forbidden rather than missing.
+    pub(crate) async fn ensure_student_access(
+        db: &PgPool,
+        tenant: &TenantContext,
+        id: Uuid,
+        denied: &str,
+    ) -> Result<Option<SchoolId>, AppError> {
+        let student_school_id = sqlx::query_scalar::<_, Option<SchoolId>>(
+            r#"
+            SELECT u.school_id FROM users u
+            INNER JOIN user_roles ur ON ur.user_id = u.id
+            WHERE u.id = $1 AND ur.role_id = $2
             "#,
         )
         .bind(id)
-        .bind(student_role_id)
-        .fetch_one(db)
+        .bind(system_roles::STUDENT)
+        .fetch_optional(db)
         .await
         .context("Failed to check student existence")
-        .map_err(AppError::database)?;
+        .map_err(AppError::database)?
+        .ok_or_else(|| AppError::from_code(ErrorCode::StudentNotFound))?;
 
-        if !student_exists {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
+        let allowed = match student_school_id {
+            Some(school_id) => tenant.allows(school_id),
+            None => tenant.school_ids().is_none(),
+        };
+        if !allowed {
+            return Err(AppError::forbidden(denied.to_string()));
         }
 
-        // Check school ownership
-        let student_school_id =
-            sqlx::query_scalar::<_, Option<Uuid>>("SELECT school_id FROM users WHERE id = $1")
-                .bind(id)
-                .fetch_one(db)
-                .await
-                .context("Failed to fetch student school")
-                .map_err(AppError::database)?;
+        Ok(student_school_id)
+    }
 
-        if student_school_id != Some(school_id) {
-            return Err(AppError::forbidden(
-                "Cannot access student from different school".to_string(),
-            ));
-        }
+    /// Move students into a level or branch with one role check and one
+    /// batched update.
+    ///
+    /// Only students of the target's school move. Returns how many moved
+    /// and the IDs that did not, either because they are not students or
+    /// because they belong to another school.
+    pub(crate) async fn batch_assign_students(
+        conn: &mut PgConnection,
+        student_ids: &[UserId],
+        target: AssignmentTarget,
+    ) -> Result<(usize, Vec<UserId>), AppError> {
+        let students = sqlx::query_scalar::<_, UserId>(
+            "SELECT user_id FROM user_roles WHERE user_id = ANY($1) AND role_id = $2",
+        )
+        .bind(student_ids)
+        .bind(system_roles::STUDENT)
+        .fetch_all(&mut *conn)
+        .await?;
 
-        let student = sqlx::query_as::<_, Student>(
+        let update = match target {
+            AssignmentTarget::Level(level_id) => sqlx::query_scalar::<_, UserId>(
+                r#"UPDATE users
+                   SET level_id = $1, updated_at = NOW()
+                   WHERE id = ANY($2)
+                     AND school_id = (SELECT school_id FROM levels WHERE id = $1)
+                   RETURNING id"#,
+            )
+            .bind(level_id),
+            AssignmentTarget::Branch(branch_id) => sqlx::query_scalar::<_, UserId>(
+                r#"UPDATE users
+                   SET branch_id = $1, updated_at = NOW()
+                   WHERE id = ANY($2) AND school_id = (
+                       SELECT l.school_id FROM branches b
+                       INNER JOIN levels l ON l.id = b.level_id
+                       WHERE b.id = $1
+                   )
+                   RETURNING id"#,
+            )
+            .bind(branch_id),
+        };
+        let assigned: HashSet<UserId> = update
+            .bind(&students)
+            .fetch_all(&mut *conn)
+            .await?
+            .into_iter()
+            .collect();
+
+        let failed_ids: Vec<UserId> = student_ids
+            .iter()
+            .copied()
+            .filter(|id| !assigned.contains(id))
+            .collect();
+
+        Ok((student_ids.len() - failed_ids.len(), failed_ids))
+    }
+
+    async fn fetch_student(conn: &mut PgConnection, id: Uuid) -> Result<Student, AppError> {
+        sqlx::query_as::<_, Student>(
             r#"
-            SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.date_of_birth, u.grade_level, u.created_at, u.updated_at
+            SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.date_of_birth, u.grade_level, u.custom_fields, u.created_at, u.updated_at
             FROM users u
             INNER JOIN user_roles ur ON ur.user_id = u.id
-            WHERE u.id = $1 AND u.school_id = $2 AND ur.role_id = $3
+            WHERE u.id = $1 AND ur.role_id = $2
             "#,
         )
         .bind(id)
-        .bind(school_id)
-        .bind(student_role_id)
-        .fetch_one(db)
+        .bind(system_roles::STUDENT)
+        .fetch_optional(conn)
         .await
         .context("Failed to fetch student by ID")
-        .map_err(AppError::database)?;
+        .map_err(AppError::database)?
+        .ok_or_else(|| AppError::from_code(ErrorCode::StudentNotFound))
+    }
 
-        Ok(student)
+    #[instrument(skip(db))]
+    pub async fn get_student_by_id(
+        db: &PgPool,
+        tenant: &TenantContext,
+        id: Uuid,
+    ) -> Result<Student, AppError> {
+        Self::ensure_student_access(
+            db,
+            tenant,
+            id,
+            "Cannot access student from different school",
+        )
+        .await?;
+
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            Self::fetch_student(uow.conn(), id).await
+        })
+        .await
     }
 
-    #[instrument(skip(db, dto, cache))]
+    #[instrument(skip(db, dto, cache, password_policy))]
     pub async fn update_student(
         db: &PgPool,
+        tenant: &TenantContext,
         id: Uuid,
-        school_id: Uuid,
         dto: UpdateStudentDto,
         cache: Option<&RedisCache>,
+        password_policy: &PasswordPolicy,
     ) -> Result<Student, AppError> {
-        let existing = Self::get_student_by_id(db, id, school_id).await?;
+        Self::ensure_student_access(
+            db,
+            tenant,
+            id,
+            "Cannot access student from different school",
+        )
+        .await?;
 
-        let first_name = dto.first_name.unwrap_or(existing.first_name);
-        let last_name = dto.last_name.unwrap_or(existing.last_name);
-        let email: Email = dto.email.unwrap_or(existing.email);
-        let date_of_birth = dto.date_of_birth.or(existing.date_of_birth);
-        let grade_level = dto.grade_level.or(existing.grade_level);
+        let updated_student = UnitOfWork::run_as(db, tenant, async |uow| {
+            let existing = Self::fetch_student(uow.conn(), id).await?;
 
-        let student_role_id = system_roles::STUDENT;
+            let custom_fields = match &dto.custom_fields {
+                Some(changes) => {
+                    CustomFieldService::resolve_values(
+                        uow.conn(),
+                        existing.school_id,
+                        CustomFieldEntity::Student,
+                        &existing.custom_fields,
+                        changes,
+                        false,
+                    )
+                    .await?
+                }
+                None => existing.custom_fields,
+            };
 
-        let updated_student = if let Some(password) = dto.password {
-            let hashed_password = hash_password(&password)?;
-            sqlx::query_as::<_, Student>(
-                r#"
-                UPDATE users u
-                SET first_name = $1, last_name = $2, email = $3, password = $4, date_of_birth = $5, grade_level = $6, updated_at = NOW()
-                FROM user_roles ur
-                WHERE u.id = ur.user_id AND u.id = $7 AND u.school_id = $8 AND ur.role_id = $9
-                RETURNING u.id, u.first_name, u.last_name, u.email, u.school_id, u.date_of_birth, u.grade_level, u.created_at, u.updated_at
-                "#,
-            )
-            .bind(&first_name)
-            .bind(&last_name)
-            .bind(email.as_str())
-            .bind(&hashed_password)
-            .bind(date_of_birth)
-            .bind(&grade_level)
-            .bind(id)
-            .bind(school_id)
-            .bind(student_role_id)
-            .fetch_one(db)
-            .await
-        } else {
-            sqlx::query_as::<_, Student>(
-                r#"
-                UPDATE users u
-                SET first_name = $1, last_name = $2, email = $3, date_of_birth = $4, grade_level = $5, updated_at = NOW()
-                FROM user_roles ur
-                WHERE u.id = ur.user_id AND u.id = $6 AND u.school_id = $7 AND ur.role_id = $8
-                RETURNING u.id, u.first_name, u.last_name, u.email, u.school_id, u.date_of_birth, u.grade_level, u.created_at, u.updated_at
-                "#,
-            )
-            .bind(&first_name)
-            .bind(&last_name)
-            .bind(email.as_str())
-            .bind(date_of_birth)
-            .bind(&grade_level)
-            .bind(id)
-            .bind(school_id)
-            .bind(student_role_id)
-            .fetch_one(db)
-            .await
-        }
-        .map_err(|e| {
-            if let sqlx::Error::Database(db_err) = &e
-                && db_err.is_unique_violation()
-            {
-                return AppError::bad_request(anyhow::anyhow!(
-                    "Student with email {} already exists",
-                    email
-                ));
+            let first_name = dto.first_name.unwrap_or(existing.first_name);
+            let last_name = dto.last_name.unwrap_or(existing.last_name);
+            let email: Email = dto.email.unwrap_or(existing.email);
+            let date_of_birth = dto.date_of_birth.or(existing.date_of_birth);
+            let grade_level = dto.grade_level.or(existing.grade_level);
+
+            let student_role_id = system_roles::STUDENT;
+
+            if let Some(password) = dto.password {
+                password_policy.validate(
+                    &password,
+                    &[&first_name, &last_name, email.as_str()],
+                )?;
+                let hashed_password = hash_password(&password)?;
+                sqlx::query_as::<_, Student>(
+                    r#"
+                    UPDATE users u
+                    SET first_name = $1, last_name = $2, email = $3, password = $4, date_of_birth = $5, grade_level = $6, custom_fields = $7, updated_at = NOW()
+                    FROM user_roles ur
+                    WHERE u.id = ur.user_id AND u.id = $8 AND ur.role_id = $9
+                    RETURNING u.id, u.first_name, u.last_name, u.email, u.school_id, u.date_of_birth, u.grade_level, u.custom_fields, u.created_at, u.updated_at
+                    "#,
+                )
+                .bind(&first_name)
+                .bind(&last_name)
+                .bind(email.as_str())
+                .bind(&hashed_password)
+                .bind(date_of_birth)
+                .bind(&grade_level)
+                .bind(&custom_fields)
+                .bind(id)
+                .bind(student_role_id)
+                .fetch_one(uow.conn())
+                .await
+            } else {
+                sqlx::query_as::<_, Student>(
+                    r#"
+                    UPDATE users u
+                    SET first_name = $1, last_name = $2, email = $3, date_of_birth = $4, grade_level = $5, custom_fields = $6, updated_at = NOW()
+                    FROM user_roles ur
+                    WHERE u.id = ur.user_id AND u.id = $7 AND ur.role_id = $8
+                    RETURNING u.id, u.first_name, u.last_name, u.email, u.school_id, u.date_of_birth, u.grade_level, u.custom_fields, u.created_at, u.updated_at
+                    "#,
+                )
+                .bind(&first_name)
+                .bind(&last_name)
+                .bind(email.as_str())
+                .bind(date_of_birth)
+                .bind(&grade_level)
+                .bind(&custom_fields)
+                .bind(id)
+                .bind(student_role_id)
+                .fetch_one(uow.conn())
+                .await
             }
-            AppError::database(anyhow::Error::from(e))
-        })?;
+            .map_err(|e| {
+                if let sqlx::Error::Database(db_err) = &e
+                    && db_err.is_unique_violation()
+                {
+                    return AppError::bad_request(anyhow::anyhow!(
+                        "Student with email {} already exists",
+                        email
+                    ));
+                }
+                AppError::database(anyhow::Error::from(e))
+            })
+        })
+        .await?;
 
         // Invalidate user caches
-        invalidate::user(cache, Some(id), Some(school_id)).await;
+        invalidate::user(cache, Some(id), updated_student.school_id.map(Into::into)).await;
 
         Ok(updated_student)
     }
@@ -258,223 +710,499 @@ impl StudentService {
     #[instrument(skip(db, cache))]
     pub async fn delete_student(
         db: &PgPool,
+        tenant: &TenantContext,
         id: Uuid,
-        school_id: Uuid,
+        deleted_by: UserId,
         cache: Option<&RedisCache>,
     ) -> Result<(), AppError> {
-        let student_role_id = system_roles::STUDENT;
-
-        // Check if user exists and is a student
-        let student_exists = sqlx::query_scalar::<_, bool>(
-            r#"
-            SELECT EXISTS(
-                SELECT 1 FROM users u
-                INNER JOIN user_roles ur ON ur.user_id = u.id
-                WHERE u.id = $1 AND ur.role_id = $2
-            )
-            "#,
+        let school_id = Self::ensure_student_access(
+            db,
+            tenant,
+            id,
+            "Cannot delete student from different school",
         )
-        .bind(id)
-        .bind(student_role_id)
-        .fetch_one(db)
-        .await
-        .context("Failed to check student existence")
-        .map_err(AppError::database)?;
+        .await?;
 
-        if !student_exists {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
-        }
+        UnitOfWork::run_as(db, tenant, async |uow| {
+            TrashService::trash_user(uow.conn(), id, TrashItemType::Students, deleted_by).await?;
 
-        // Check school ownership
-        let student_school_id =
-            sqlx::query_scalar::<_, Option<Uuid>>("SELECT school_id FROM users WHERE id = $1")
+            // Delete role assignment first
+            sqlx::query("DELETE FROM user_roles WHERE user_id = $1")
                 .bind(id)
-                .fetch_one(db)
+                .execute(uow.conn())
                 .await
-                .context("Failed to fetch student school")
+                .context("Failed to delete student role assignment")
                 .map_err(AppError::database)?;
 
-        if student_school_id != Some(school_id) {
-            return Err(AppError::forbidden(
-                "Cannot delete student from different school".to_string(),
-            ));
-        }
-
-        // Delete role assignment first
-        sqlx::query("DELETE FROM user_roles WHERE user_id = $1")
-            .bind(id)
-            .execute(db)
-            .await
-            .context("Failed to delete student role assignment")
-            .map_err(AppError::database)?;
+            // Delete the user
+            sqlx::query("DELETE FROM users WHERE id = $1")
+                .bind(id)
+                .execute(uow.conn())
+                .await
+                .context("Failed to delete student")
+                .map_err(AppError::database)?;
 
-        // Delete the user
-        sqlx::query("DELETE FROM users WHERE id = $1 AND school_id = $2")
-            .bind(id)
-            .bind(school_id)
-            .execute(db)
-            .await
-            .context("Failed to delete student")
-            .map_err(AppError::database)?;
+            Ok::<_, AppError>(())
+        })
+        .await?;
 
         // Invalidate user caches
-        invalidate::user(cache, Some(id), Some(school_id)).await;
+        invalidate::user(cache, Some(id), school_id.map(Into::into)).await;
 
         Ok(())
     }
 
-    // ============ No School Filter Methods (for System Admins) ============
-
-    #[instrument(skip(db))]
-    pub async fn get_student_by_id_no_school_filter(
+    /// Import students from a CSV file.
+    ///
+    /// Every row is validated before anything is written, and the import is
+    /// all or nothing: when any row has problems, the report lists them and
+    /// no students are created. Dry runs stop after validation. Valid imports
+    /// are inserted in batches in one transaction and given the student role.
+    #[instrument(skip(db, cache, csv_bytes, default_password, password_policy))]
+    pub async fn import_students(
         db: &PgPool,
-        id: Uuid,
-    ) -> Result<Student, AppError> {
-        let student_role_id = system_roles::STUDENT;
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        csv_bytes: &[u8],
+        default_password: Option<String>,
+        dry_run: bool,
+        password_policy: &PasswordPolicy,
+    ) -> Result<StudentImportReport, AppError> {
+        if let Some(password) = &default_password {
+            let violations = password_policy.violations(password, &[]);
+            if !violations.is_empty() {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "default_password {}",
+                    violations.join("; ")
+                )));
+            }
+        }
 
-        let student = sqlx::query_as::<_, Student>(
-            r#"
-            SELECT u.id, u.first_name, u.last_name, u.email, u.school_id, u.date_of_birth, u.grade_level, u.created_at, u.updated_at
-            FROM users u
-            INNER JOIN user_roles ur ON ur.user_id = u.id
-            WHERE u.id = $1 AND ur.role_id = $2
-            "#,
+        let custom_fields =
+            Self::import_custom_fields(db, school_id, CustomFieldEntity::Student).await?;
+
+        let rows = read_import_csv(csv_bytes)?;
+        let total_rows = rows.len();
+
+        let emails: Vec<String> = rows
+            .iter()
+            .filter_map(|(_, row)| row.as_ref().ok())
+            .map(|row| row.email.to_lowercase())
+            .collect();
+        let lookups = Self::load_import_lookups(db, school_id, &emails).await?;
+
+        let mut errors = Vec::new();
+        let mut seen_emails = HashMap::new();
+        let mut valid = Vec::with_capacity(total_rows);
+        for (line, row) in rows {
+            let row = match row {
+                Ok(row) => row,
+                Err(message) => {
+                    errors.push(StudentImportRowError {
+                        line,
+                        column: None,
+                        code: ImportIssueCode::UnreadableRow,
+                        message,
+                    });
+                    continue;
+                }
+            };
+            if let Some(row) = validate_import_row(
+                line,
+                row,
+                &lookups,
+                default_password.is_some(),
+                password_policy,
+                &mut seen_emails,
+                &mut errors,
+            ) {
+                valid.push(row);
+            }
+        }
+
+        let mut report = StudentImportReport {
+            dry_run,
+            total_rows,
+            valid_rows: valid.len(),
+            imported_count: 0,
+            errors,
+        };
+        if dry_run || !report.errors.is_empty() {
+            return Ok(report);
+        }
+
+        let mut tx = db.begin().await?;
+        let user_ids = Self::insert_import_rows(
+            &mut tx,
+            school_id,
+            &valid,
+            default_password.as_deref(),
+            &custom_fields,
         )
-        .bind(id)
-        .bind(student_role_id)
-        .fetch_optional(db)
-        .await
-        .context("Failed to fetch student by ID")
-        .map_err(AppError::database)?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Student not found")))?;
+        .await?;
+        tx.commit().await?;
+        report.imported_count = user_ids.len();
 
-        Ok(student)
+        invalidate::user(cache, None, Some(school_id.into())).await;
+
+        Ok(report)
     }
 
-    #[instrument(skip(db, dto, cache))]
-    pub async fn update_student_no_school_filter(
+    /// Custom field values for imported records, which leave every field at
+    /// its default. Fails when the school has a required field.
+    pub(crate) async fn import_custom_fields(
         db: &PgPool,
-        id: Uuid,
-        dto: UpdateStudentDto,
-        cache: Option<&RedisCache>,
-    ) -> Result<Student, AppError> {
-        let existing = Self::get_student_by_id_no_school_filter(db, id).await?;
-
-        let first_name = dto.first_name.unwrap_or(existing.first_name);
-        let last_name = dto.last_name.unwrap_or(existing.last_name);
-        let email: Email = dto.email.unwrap_or(existing.email);
-        let date_of_birth = dto.date_of_birth.or(existing.date_of_birth);
-        let grade_level = dto.grade_level.or(existing.grade_level);
+        school_id: SchoolId,
+        entity: CustomFieldEntity,
+    ) -> Result<Value, AppError> {
+        CustomFieldService::resolve_values(
+            db,
+            Some(school_id),
+            entity,
+            &Value::Null,
+            &Default::default(),
+            true,
+        )
+        .await
+        .map_err(|_| {
+            let records = match entity {
+                CustomFieldEntity::Student => "student",
+                CustomFieldEntity::User => "user",
+            };
+            AppError::bad_request(anyhow::anyhow!(
+                "This school has required {} custom fields, which CSV import does not set",
+                records
+            ))
+        })
+    }
 
-        let student_role_id = system_roles::STUDENT;
+    /// Create students from validated import rows and give them the student
+    /// role, inserting in batches. Returns the new IDs.
+    pub(crate) async fn insert_import_rows(
+        conn: &mut PgConnection,
+        school_id: SchoolId,
+        rows: &[ValidImportRow],
+        default_password: Option<&str>,
+        custom_fields: &Value,
+    ) -> Result<Vec<UserId>, AppError> {
+        // Hash each distinct password once; a shared default is common
+        let mut hashes: HashMap<&str, String> = HashMap::new();
+        let mut password_hashes = Vec::with_capacity(rows.len());
+        for row in rows {
+            let password = row
+                .password
+                .as_deref()
+                .or(default_password)
+                .unwrap_or_default();
+            if !hashes.contains_key(password) {
+                hashes.insert(password, hash_password(password)?);
+            }
+            password_hashes.push(hashes[password].clone());
+        }
 
-        let updated_student = if let Some(password) = dto.password {
-            let hashed_password = hash_password(&password)?;
-            sqlx::query_as::<_, Student>(
-                r#"
-                UPDATE users u
-                SET first_name = $1, last_name = $2, email = $3, password = $4, date_of_birth = $5, grade_level = $6, updated_at = NOW()
-                FROM user_roles ur
-                WHERE u.id = ur.user_id AND u.id = $7 AND ur.role_id = $8
-                RETURNING u.id, u.first_name, u.last_name, u.email, u.school_id, u.date_of_birth, u.grade_level, u.created_at, u.updated_at
-                "#,
+        let mut created = Vec::with_capacity(rows.len());
+        for (rows, password_hashes) in rows
+            .chunks(IMPORT_BATCH_SIZE)
+            .zip(password_hashes.chunks(IMPORT_BATCH_SIZE))
+        {
+            let user_ids = sqlx::query_scalar::<_, UserId>(
+                r#"INSERT INTO users (first_name, last_name, email, password, school_id,
+                                      date_of_birth, grade_level, custom_fields, level_id, branch_id)
+                   SELECT t.first_name, t.last_name, t.email, t.password, $5,
+                          t.date_of_birth, t.grade_level, $8, t.level_id, t.branch_id
+                   FROM UNNEST($1::text[], $2::text[], $3::text[], $4::text[],
+                               $6::date[], $7::text[], $9::uuid[], $10::uuid[])
+                        AS t(first_name, last_name, email, password,
+                             date_of_birth, grade_level, level_id, branch_id)
+                   RETURNING id"#,
             )
-            .bind(&first_name)
-            .bind(&last_name)
-            .bind(email.as_str())
-            .bind(&hashed_password)
-            .bind(date_of_birth)
-            .bind(&grade_level)
-            .bind(id)
-            .bind(student_role_id)
-            .fetch_one(db)
-            .await
-        } else {
-            sqlx::query_as::<_, Student>(
-                r#"
-                UPDATE users u
-                SET first_name = $1, last_name = $2, email = $3, date_of_birth = $4, grade_level = $5, updated_at = NOW()
-                FROM user_roles ur
-                WHERE u.id = ur.user_id AND u.id = $6 AND ur.role_id = $7
-                RETURNING u.id, u.first_name, u.last_name, u.email, u.school_id, u.date_of_birth, u.grade_level, u.created_at, u.updated_at
-                "#,
-            )
-            .bind(&first_name)
-            .bind(&last_name)
-            .bind(email.as_str())
-            .bind(date_of_birth)
-            .bind(&grade_level)
-            .bind(id)
-            .bind(student_role_id)
-            .fetch_one(db)
+            .bind(rows.iter().map(|r| r.first_name.as_str()).collect::<Vec<_>>())
+            .bind(rows.iter().map(|r| r.last_name.as_str()).collect::<Vec<_>>())
+            .bind(rows.iter().map(|r| r.email.as_str()).collect::<Vec<_>>())
+            .bind(password_hashes)
+            .bind(school_id)
+            .bind(rows.iter().map(|r| r.date_of_birth).collect::<Vec<_>>())
+            .bind(rows.iter().map(|r| r.grade_level.as_deref()).collect::<Vec<_>>())
+            .bind(custom_fields)
+            .bind(rows.iter().map(|r| r.level_id).collect::<Vec<_>>())
+            .bind(rows.iter().map(|r| r.branch_id).collect::<Vec<_>>())
+            .fetch_all(&mut *conn)
             .await
-        }
-        .map_err(|e| {
-            if let sqlx::Error::Database(db_err) = &e
-                && db_err.is_unique_violation()
-            {
-                return AppError::bad_request(anyhow::anyhow!(
-                    "Student with email {} already exists",
-                    email
-                ));
-            }
-            AppError::database(anyhow::Error::from(e))
-        })?;
+            .map_err(|e| {
+                if let sqlx::Error::Database(db_err) = &e
+                    && db_err.is_unique_violation()
+                {
+                    return AppError::bad_request(anyhow::anyhow!(
+                        "An email in the file was registered during the import; nothing was imported"
+                    ));
+                }
+                AppError::from(e)
+            })?;
 
-        // Invalidate user caches
-        invalidate::user(cache, Some(id), None).await;
+            sqlx::query("INSERT INTO user_roles (user_id, role_id) SELECT UNNEST($1::uuid[]), $2")
+                .bind(&user_ids)
+                .bind(system_roles::STUDENT)
+                .execute(&mut *conn)
+                .await?;
 
-        Ok(updated_student)
+            created.extend(user_ids);
+        }
+
+        Ok(created)
     }
 
-    #[instrument(skip(db, cache))]
-    pub async fn delete_student_no_school_filter(
+    /// Load the levels, branches, and already registered emails an import
+    /// is checked against.
+    pub(crate) async fn load_import_lookups(
         db: &PgPool,
-        id: Uuid,
-        cache: Option<&RedisCache>,
-    ) -> Result<(), AppError> {
-        let student_role_id = system_roles::STUDENT;
+        school_id: SchoolId,
+        emails: &[String],
+    ) -> Result<ImportLookups, AppError> {
+        let levels = sqlx::query_as::<_, (LevelId, String)>(
+            "SELECT id, LOWER(name) FROM levels WHERE school_id = $1",
+        )
+        .bind(school_id)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|(id, name)| (name, id))
+        .collect();
 
-        // Check if user exists and is a student
-        let student_exists = sqlx::query_scalar::<_, bool>(
-            r#"
-            SELECT EXISTS(
-                SELECT 1 FROM users u
-                INNER JOIN user_roles ur ON ur.user_id = u.id
-                WHERE u.id = $1 AND ur.role_id = $2
-            )
-            "#,
+        let branches = sqlx::query_as::<_, (BranchId, LevelId, String)>(
+            r#"SELECT b.id, b.level_id, LOWER(b.name)
+               FROM branches b
+               INNER JOIN levels l ON l.id = b.level_id
+               WHERE l.school_id = $1"#,
         )
-        .bind(id)
-        .bind(student_role_id)
-        .fetch_one(db)
+        .bind(school_id)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|(id, level_id, name)| ((level_id, name), id))
+        .collect();
+
+        let existing_emails = sqlx::query_scalar::<_, String>(
+            "SELECT LOWER(email) FROM users WHERE LOWER(email) = ANY($1)",
+        )
+        .bind(emails)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+        Ok(ImportLookups {
+            levels,
+            branches,
+            existing_emails,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    const CSV_HEADER: &str = "first_name,last_name,email,date_of_birth,level,branch\n";
+
+    async fn create_test_school(pool: &PgPool, name: &str) -> SchoolId {
+        sqlx::query_scalar("INSERT INTO schools (name) VALUES ($1) RETURNING id")
+            .bind(name)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn create_level_with_branch(pool: &PgPool, school_id: SchoolId) -> (LevelId, BranchId) {
+        let level_id: LevelId = sqlx::query_scalar(
+            "INSERT INTO levels (name, school_id) VALUES ('Grade 7', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(pool)
         .await
-        .context("Failed to check student existence")
-        .map_err(AppError::database)?;
+        .unwrap();
+        let branch_id: BranchId = sqlx::query_scalar(
+            "INSERT INTO branches (name, level_id) VALUES ('A', $1) RETURNING id",
+        )
+        .bind(level_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        (level_id, branch_id)
+    }
 
-        if !student_exists {
-            return Err(AppError::not_found(anyhow::anyhow!("Student not found")));
+    fn empty_lookups() -> ImportLookups {
+        ImportLookups {
+            levels: HashMap::new(),
+            branches: HashMap::new(),
+            existing_emails: HashSet::new(),
         }
+    }
 
-        // Delete role assignment first
-        sqlx::query("DELETE FROM user_roles WHERE user_id = $1")
-            .bind(id)
-            .execute(db)
-            .await
-            .context("Failed to delete student role assignment")
-            .map_err(AppError::database)?;
+    #[test]
+    fn test_read_import_csv_requires_columns() {
+        let err = read_import_csv(b"first_name,email\nAda,ada@example.com\n").unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert!(err.error.to_string().contains("last_name"));
+    }
+
+    #[test]
+    fn test_validate_import_row_reports_each_problem() {
+        let csv = format!(
+            "{}Ada,Obi,ada@example.com,2010-02-30,,\nAda,Obi,ADA@example.com,,Grade 9,\n,Obi,not-an-email,,,A\n",
+            CSV_HEADER
+        );
+        let rows = read_import_csv(csv.as_bytes()).unwrap();
+        assert_eq!(
+            rows.iter().map(|(line, _)| *line).collect::<Vec<_>>(),
+            [2, 3, 4]
+        );
+
+        let lookups = empty_lookups();
+        let mut seen = HashMap::new();
+        let mut errors = Vec::new();
+        let valid: Vec<_> = rows
+            .into_iter()
+            .filter_map(|(line, row)| {
+                validate_import_row(
+                    line,
+                    row.unwrap(),
+                    &lookups,
+                    true,
+                    &PasswordPolicy::default(),
+                    &mut seen,
+                    &mut errors,
+                )
+            })
+            .collect();
+
+        assert!(valid.is_empty());
+        let columns: Vec<_> = errors
+            .iter()
+            .map(|e| (e.line, e.column.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            columns,
+            [
+                (2, "date_of_birth"),
+                (3, "email"),
+                (3, "level"),
+                (4, "first_name"),
+                (4, "email"),
+                (4, "branch"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_import_row_requires_password_without_default() {
+        let csv = format!("{}Ada,Obi,ada@example.com,,,\n", CSV_HEADER);
+        let (line, row) = read_import_csv(csv.as_bytes()).unwrap().remove(0);
+        let mut errors = Vec::new();
+
+        let valid = validate_import_row(
+            line,
+            row.unwrap(),
+            &empty_lookups(),
+            false,
+            &PasswordPolicy::default(),
+            &mut HashMap::new(),
+            &mut errors,
+        );
+
+        assert!(valid.is_none());
+        assert_eq!(errors[0].column.as_deref(), Some("password"));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_import_students_dry_run_then_import(pool: PgPool) {
+        let school_id = create_test_school(&pool, "Import School").await;
+        let (level_id, branch_id) = create_level_with_branch(&pool, school_id).await;
+        let csv = format!(
+            "{}Ada,Obi,ada@example.com,2012-05-01,grade 7,a\nTunde,Bello,tunde@example.com,,,\n",
+            CSV_HEADER
+        );
+        let password = Some("testpass123".to_string());
+
+        let report = StudentService::import_students(
+            &pool,
+            None,
+            school_id,
+            csv.as_bytes(),
+            password.clone(),
+            true,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap();
+        assert!(report.dry_run);
+        assert_eq!((report.total_rows, report.valid_rows), (2, 2));
+        assert_eq!(report.imported_count, 0);
 
-        // Delete the user
-        sqlx::query("DELETE FROM users WHERE id = $1")
-            .bind(id)
-            .execute(db)
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE school_id = $1")
+            .bind(school_id)
+            .fetch_one(&pool)
             .await
-            .context("Failed to delete student")
-            .map_err(AppError::database)?;
+            .unwrap();
+        assert_eq!(count, 0);
 
-        // Invalidate user caches
-        invalidate::user(cache, Some(id), None).await;
+        let report = StudentService::import_students(
+            &pool,
+            None,
+            school_id,
+            csv.as_bytes(),
+            password,
+            false,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.imported_count, 2);
 
-        Ok(())
+        let (student_level, student_branch): (Option<LevelId>, Option<BranchId>) =
+            sqlx::query_as("SELECT level_id, branch_id FROM users WHERE email = 'ada@example.com'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(student_level, Some(level_id));
+        assert_eq!(student_branch, Some(branch_id));
+
+        let students: i64 = sqlx::query_scalar(
+            r#"SELECT COUNT(*) FROM users u
+               INNER JOIN user_roles ur ON ur.user_id = u.id
+               WHERE u.school_id = $1 AND ur.role_id = $2"#,
+        )
+        .bind(school_id)
+        .bind(system_roles::STUDENT)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(students, 2);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_import_students_is_all_or_nothing(pool: PgPool) {
+        let school_id = create_test_school(&pool, "Import School").await;
+        let csv = format!(
+            "{}Ada,Obi,ada@example.com,,,\nTunde,Bello,not-an-email,,,\n",
+            CSV_HEADER
+        );
+
+        let report = StudentService::import_students(
+            &pool,
+            None,
+            school_id,
+            csv.as_bytes(),
+            Some("testpass123".to_string()),
+            false,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(report.valid_rows, 1);
+        assert_eq!(report.imported_count, 0);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].line, 3);
     }
 }
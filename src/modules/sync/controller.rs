@@ -0,0 +1,329 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent, PaginationParams};
+use chalkbyte_models::ids::{SyncConnectionId, SyncEntityId};
+
+use crate::middleware::auth::{RequireSyncManage, RequireSyncRead};
+use crate::modules::sync::model::{
+    CreateSyncConnectionDto, PaginatedSyncEntitiesResponse, PaginatedSyncRunsResponse,
+    SyncConnection, SyncConnectionFilterParams, SyncEntity, SyncEntityFilterParams, SyncRun,
+    UpdateSyncConnectionDto,
+};
+use crate::modules::sync::service::SyncService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Connect an LMS
+///
+/// The credentials are checked for the fields the provider needs and are
+/// never returned. The first scheduled run is queued within a minute.
+#[utoipa::path(
+    post,
+    path = "/api/sync/connections",
+    summary = "Create sync connection",
+    request_body = CreateSyncConnectionDto,
+    responses(
+        Created<SyncConnection>,
+        (status = 400, description = "Invalid input, invalid credentials, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sync:manage permission"),
+        (status = 409, description = "The school is already connected to the provider")
+    ),
+    tag = "Sync",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn create_sync_connection(
+    State(state): State<AppState>,
+    RequireSyncManage(auth_user): RequireSyncManage,
+    ValidatedJson(dto): ValidatedJson<CreateSyncConnectionDto>,
+) -> Result<Created<SyncConnection>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+    let connection =
+        SyncService::create_connection(&state.db, school_id, auth_user.user_id()?, dto).await?;
+
+    Ok(Created(connection))
+}
+
+/// List a school's LMS connections
+#[utoipa::path(
+    get,
+    path = "/api/sync/connections",
+    summary = "List sync connections",
+    params(SyncConnectionFilterParams),
+    responses(
+        (status = 200, description = "Sync connections", body = Vec<SyncConnection>),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sync:read permission")
+    ),
+    tag = "Sync",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_sync_connections(
+    State(state): State<AppState>,
+    RequireSyncRead(auth_user): RequireSyncRead,
+    Query(filters): Query<SyncConnectionFilterParams>,
+) -> Result<Json<Vec<SyncConnection>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let connections = SyncService::get_connections(&state.db, school_id).await?;
+
+    Ok(Json(connections))
+}
+
+/// Get an LMS connection
+#[utoipa::path(
+    get,
+    path = "/api/sync/connections/{id}",
+    summary = "Get sync connection",
+    params(
+        ("id" = Uuid, Path, description = "Sync connection ID")
+    ),
+    responses(
+        (status = 200, description = "Sync connection", body = SyncConnection),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sync:read permission"),
+        (status = 404, description = "Sync connection not found")
+    ),
+    tag = "Sync",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_sync_connection(
+    State(state): State<AppState>,
+    RequireSyncRead(auth_user): RequireSyncRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SyncConnection>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let connection =
+        SyncService::get_connection(&state.db, SyncConnectionId::from(id), school_id).await?;
+
+    Ok(Json(connection))
+}
+
+/// Update an LMS connection
+///
+/// Deactivating a connection stops scheduled runs; runs can still be queued
+/// by hand.
+#[utoipa::path(
+    put,
+    path = "/api/sync/connections/{id}",
+    summary = "Update sync connection",
+    params(
+        ("id" = Uuid, Path, description = "Sync connection ID")
+    ),
+    request_body = UpdateSyncConnectionDto,
+    responses(
+        (status = 200, description = "Sync connection updated", body = SyncConnection),
+        (status = 400, description = "Invalid input or credentials"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sync:manage permission"),
+        (status = 404, description = "Sync connection not found")
+    ),
+    tag = "Sync",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn update_sync_connection(
+    State(state): State<AppState>,
+    RequireSyncManage(auth_user): RequireSyncManage,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateSyncConnectionDto>,
+) -> Result<Json<SyncConnection>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let connection =
+        SyncService::update_connection(&state.db, SyncConnectionId::from(id), school_id, dto)
+            .await?;
+
+    Ok(Json(connection))
+}
+
+/// Disconnect an LMS
+///
+/// Deletes the connection's runs and sync state. Courses already pushed are
+/// left in the LMS.
+#[utoipa::path(
+    delete,
+    path = "/api/sync/connections/{id}",
+    summary = "Delete sync connection",
+    params(
+        ("id" = Uuid, Path, description = "Sync connection ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sync:manage permission"),
+        (status = 404, description = "Sync connection not found")
+    ),
+    tag = "Sync",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_sync_connection(
+    State(state): State<AppState>,
+    RequireSyncManage(auth_user): RequireSyncManage,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    SyncService::delete_connection(&state.db, SyncConnectionId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Queue a sync run
+///
+/// The run is processed by a background job; poll the connection's runs for
+/// its progress.
+#[utoipa::path(
+    post,
+    path = "/api/sync/connections/{id}/runs",
+    summary = "Queue sync run",
+    params(
+        ("id" = Uuid, Path, description = "Sync connection ID")
+    ),
+    responses(
+        Created<SyncRun>,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sync:manage permission"),
+        (status = 404, description = "Sync connection not found"),
+        (status = 409, description = "A run is already waiting or in progress")
+    ),
+    tag = "Sync",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn queue_sync_run(
+    State(state): State<AppState>,
+    RequireSyncManage(auth_user): RequireSyncManage,
+    Path(id): Path<Uuid>,
+) -> Result<Created<SyncRun>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let run = SyncService::queue_run(
+        &state.db,
+        SyncConnectionId::from(id),
+        school_id,
+        auth_user.user_id()?,
+    )
+    .await?;
+
+    Ok(Created(run))
+}
+
+/// List a connection's sync runs
+#[utoipa::path(
+    get,
+    path = "/api/sync/connections/{id}/runs",
+    summary = "List sync runs",
+    params(
+        ("id" = Uuid, Path, description = "Sync connection ID"),
+        ("limit" = Option<i64>, Query, description = "Number of items per page (1-100, default: 10)"),
+        ("offset" = Option<i64>, Query, description = "Number of items to skip (default: 0)")
+    ),
+    responses(
+        (status = 200, description = "Runs, newest first", body = PaginatedSyncRunsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sync:read permission"),
+        (status = 404, description = "Sync connection not found")
+    ),
+    tag = "Sync",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_sync_runs(
+    State(state): State<AppState>,
+    RequireSyncRead(auth_user): RequireSyncRead,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<Json<PaginatedSyncRunsResponse>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let runs =
+        SyncService::get_runs(&state.db, SyncConnectionId::from(id), school_id, pagination).await?;
+
+    Ok(Json(runs))
+}
+
+/// List the sync state of a connection's courses and roster members
+///
+/// Filter by `status=conflict` for what needs resolving.
+#[utoipa::path(
+    get,
+    path = "/api/sync/connections/{id}/entities",
+    summary = "List sync entities",
+    params(
+        ("id" = Uuid, Path, description = "Sync connection ID"),
+        SyncEntityFilterParams
+    ),
+    responses(
+        (status = 200, description = "Sync entities, most recently updated first", body = PaginatedSyncEntitiesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sync:read permission"),
+        (status = 404, description = "Sync connection not found")
+    ),
+    tag = "Sync",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_sync_entities(
+    State(state): State<AppState>,
+    RequireSyncRead(auth_user): RequireSyncRead,
+    Path(id): Path<Uuid>,
+    Query(filters): Query<SyncEntityFilterParams>,
+) -> Result<Json<PaginatedSyncEntitiesResponse>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let entities =
+        SyncService::get_entities(&state.db, SyncConnectionId::from(id), school_id, filters)
+            .await?;
+
+    Ok(Json(entities))
+}
+
+/// Resolve a sync conflict
+///
+/// Keeps the school's version: the entity is marked pending and the next
+/// run pushes it over the LMS, recreating a deleted course, re-adding a
+/// removed member, or moving a member to the school's role.
+#[utoipa::path(
+    post,
+    path = "/api/sync/connections/{id}/entities/{entity_id}/resolve",
+    summary = "Resolve sync conflict",
+    params(
+        ("id" = Uuid, Path, description = "Sync connection ID"),
+        ("entity_id" = Uuid, Path, description = "Sync entity ID")
+    ),
+    responses(
+        (status = 200, description = "Conflict resolved", body = SyncEntity),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires sync:manage permission"),
+        (status = 404, description = "Sync connection or conflict not found")
+    ),
+    tag = "Sync",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn resolve_sync_entity(
+    State(state): State<AppState>,
+    RequireSyncManage(auth_user): RequireSyncManage,
+    Path((id, entity_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<SyncEntity>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let entity = SyncService::resolve_entity(
+        &state.db,
+        SyncConnectionId::from(id),
+        school_id,
+        SyncEntityId::from(entity_id),
+    )
+    .await?;
+
+    Ok(Json(entity))
+}
@@ -0,0 +1,24 @@
+//! LMS sync module.
+//!
+//! Pushes a school's roster to an LMS on a schedule: each branch becomes a
+//! course named after its level and branch, and the branch's students and
+//! current teachers become the course roster. A background job queues a run
+//! for every connection whose interval has passed, and admins can queue one
+//! by hand.
+//!
+//! Every course and roster member has a sync state recording what was last
+//! pushed, so runs only send changes, archive the courses of deleted
+//! branches, and remove only the members they added. When the LMS was
+//! changed behind a run's back, such as a course edited or a student removed
+//! there, the entity is reported as a conflict and left alone until an
+//! admin resolves it in the school's favour.
+//!
+//! Providers implement [`provider::SyncProvider`]; Google Classroom is the
+//! one available.
+
+pub mod controller;
+pub mod google_classroom;
+pub mod model;
+pub mod provider;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! LMS sync data models.
+//!
+//! This module re-exports sync models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all sync models from the shared crate
+pub use chalkbyte_models::sync::*;
@@ -0,0 +1,181 @@
+//! LMS providers a school's roster can be pushed to.
+//!
+//! A [`SyncProvider`] knows how to manage courses and course rosters in one
+//! LMS. It holds no sync state of its own: the sync service decides what
+//! to create, update, or remove from the state it records for every course
+//! and roster member, and calls the provider to do it.
+//!
+//! - [`GoogleClassroomProvider`] talks to the Google Classroom API as a
+//!   service account with domain-wide delegation.
+//!
+//! [`GoogleClassroomProvider`]: super::google_classroom::GoogleClassroomProvider
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use sha2::{Digest, Sha256};
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::ids::BranchId;
+
+use crate::modules::sync::google_classroom::GoogleClassroomProvider;
+use crate::modules::sync::model::{GoogleClassroomCredentials, SyncProviderKind};
+
+/// Future returned by [`SyncProvider`] operations.
+pub type SyncFuture<'a, T> =
+    Pin<Box<dyn Future<Output = Result<T, SyncProviderError>> + Send + 'a>>;
+
+/// Why an LMS request failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncProviderError {
+    /// The course or user does not exist in the LMS
+    NotFound(String),
+    /// The LMS already has the course or member
+    AlreadyExists(String),
+    /// The LMS refused the request for this course or member
+    Rejected(String),
+    /// The LMS could not be reached or refused the connection's
+    /// credentials; nothing else can be pushed until that is fixed
+    Unavailable(String),
+}
+
+impl fmt::Display for SyncProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(message)
+            | Self::AlreadyExists(message)
+            | Self::Rejected(message)
+            | Self::Unavailable(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for SyncProviderError {}
+
+/// A branch as a course.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CourseSpec {
+    pub branch_id: BranchId,
+    /// Level and branch name, e.g. `JSS 1 A`
+    pub name: String,
+    /// The level's name
+    pub section: String,
+}
+
+impl CourseSpec {
+    /// Digest of what is pushed for the course, to tell whether the branch
+    /// changed since the last push.
+    pub fn content_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.name.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.section.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// A course in the LMS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteCourse {
+    pub id: String,
+    /// Changes whenever the course is edited in the LMS
+    pub version: String,
+}
+
+/// Role on a course roster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RosterRole {
+    Student,
+    Teacher,
+}
+
+/// A member of a course roster in the LMS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteMember {
+    /// The user's ID in the LMS
+    pub user_id: String,
+    pub email: Option<String>,
+    pub role: RosterRole,
+}
+
+/// Course and roster operations of an LMS.
+pub trait SyncProvider: Send + Sync {
+    /// The course created for `branch_id` by an earlier push, found by a
+    /// key the LMS keeps (such as an alias), so losing sync state does not
+    /// duplicate courses.
+    fn find_course<'a>(&'a self, branch_id: BranchId) -> SyncFuture<'a, Option<RemoteCourse>>;
+
+    /// The course with ID `course_id`, or `None` if it was deleted.
+    fn get_course<'a>(&'a self, course_id: &'a str) -> SyncFuture<'a, Option<RemoteCourse>>;
+
+    /// Creates the course for a branch, findable later by
+    /// [`SyncProvider::find_course`].
+    fn create_course<'a>(&'a self, course: &'a CourseSpec) -> SyncFuture<'a, RemoteCourse>;
+
+    /// Replaces a course's name and section.
+    fn update_course<'a>(
+        &'a self,
+        course_id: &'a str,
+        course: &'a CourseSpec,
+    ) -> SyncFuture<'a, RemoteCourse>;
+
+    /// Archives a course whose branch was deleted. Archived courses stay
+    /// readable in the LMS.
+    fn archive_course<'a>(&'a self, course_id: &'a str) -> SyncFuture<'a, ()>;
+
+    /// Every student and teacher on a course's roster.
+    fn list_members<'a>(&'a self, course_id: &'a str) -> SyncFuture<'a, Vec<RemoteMember>>;
+
+    /// Adds the user with `email` to a course roster.
+    fn add_member<'a>(
+        &'a self,
+        course_id: &'a str,
+        role: RosterRole,
+        email: &'a str,
+    ) -> SyncFuture<'a, RemoteMember>;
+
+    /// Removes a user from a course roster.
+    fn remove_member<'a>(
+        &'a self,
+        course_id: &'a str,
+        role: RosterRole,
+        user_id: &'a str,
+    ) -> SyncFuture<'a, ()>;
+}
+
+/// Check a connection's credentials have what its provider needs.
+pub fn validate_credentials(
+    provider: SyncProviderKind,
+    credentials: &serde_json::Value,
+) -> Result<(), AppError> {
+    match provider {
+        SyncProviderKind::GoogleClassroom => {
+            let credentials: GoogleClassroomCredentials =
+                serde_json::from_value(credentials.clone()).map_err(|e| {
+                    AppError::bad_request(anyhow::anyhow!("Invalid credentials: {}", e))
+                })?;
+            validator::Validate::validate(&credentials).map_err(AppError::validation)?;
+            GoogleClassroomProvider::new(reqwest::Client::new(), credentials).map_err(|e| {
+                AppError::bad_request(anyhow::anyhow!("Invalid credentials: {}", e))
+            })?;
+            Ok(())
+        }
+    }
+}
+
+/// Builds the provider for a connection.
+pub fn init_sync_provider(
+    client: reqwest::Client,
+    provider: SyncProviderKind,
+    credentials: serde_json::Value,
+) -> Result<Box<dyn SyncProvider>, SyncProviderError> {
+    match provider {
+        SyncProviderKind::GoogleClassroom => {
+            let credentials = serde_json::from_value(credentials).map_err(|e| {
+                SyncProviderError::Unavailable(format!("Invalid credentials: {}", e))
+            })?;
+            Ok(Box::new(GoogleClassroomProvider::new(client, credentials)?))
+        }
+    }
+}
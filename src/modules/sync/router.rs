@@ -0,0 +1,38 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    create_sync_connection, delete_sync_connection, get_sync_connection, get_sync_connections,
+    get_sync_entities, get_sync_runs, queue_sync_run, resolve_sync_entity, update_sync_connection,
+};
+
+/// Initialize the LMS sync router
+/// Routes: GET/POST /connections, GET/PUT/DELETE /connections/{id},
+/// GET/POST /connections/{id}/runs, GET /connections/{id}/entities,
+/// POST /connections/{id}/entities/{entity_id}/resolve
+pub fn init_sync_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/connections",
+            get(get_sync_connections).post(create_sync_connection),
+        )
+        .route(
+            "/connections/{id}",
+            get(get_sync_connection)
+                .put(update_sync_connection)
+                .delete(delete_sync_connection),
+        )
+        .route(
+            "/connections/{id}/runs",
+            get(get_sync_runs).post(queue_sync_run),
+        )
+        .route("/connections/{id}/entities", get(get_sync_entities))
+        .route(
+            "/connections/{id}/entities/{entity_id}/resolve",
+            post(resolve_sync_entity),
+        )
+}
@@ -0,0 +1,1511 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use sqlx::{FromRow, PgPool};
+use tracing::{info, instrument, warn};
+
+use chalkbyte_core::{AppError, PaginationMeta, PaginationParams};
+use chalkbyte_models::ids::{
+    BranchId, SchoolId, SyncConnectionId, SyncEntityId, SyncRunId, UserId,
+};
+use chalkbyte_observability::jobs::register_job;
+
+use crate::modules::sync::model::{
+    CreateSyncConnectionDto, PaginatedSyncEntitiesResponse, PaginatedSyncRunsResponse,
+    SyncConflictKind, SyncConnection, SyncEntity, SyncEntityFilterParams, SyncEntityStatus,
+    SyncEntityType, SyncProviderKind, SyncRun, UpdateSyncConnectionDto,
+};
+use crate::modules::sync::provider::{
+    CourseSpec, RemoteMember, RosterRole, SyncProvider, SyncProviderError, init_sync_provider,
+    validate_credentials,
+};
+use crate::modules::users::model::system_roles;
+
+/// How often the job queues scheduled runs and looks for pending ones.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long an LMS request may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+const CONNECTION_COLUMNS: &str = "id, school_id, provider, is_active, interval_minutes,
+    next_run_at, created_by, created_at, updated_at";
+
+const RUN_COLUMNS: &str = "id, connection_id, trigger, status, requested_by, pushed, unchanged,
+    conflicts, failures, error, created_at, started_at, completed_at";
+
+const ENTITY_COLUMNS: &str = "id, connection_id, entity_type, branch_id, user_id, remote_id,
+    status, conflict, message, last_run_id, synced_at, updated_at";
+
+/// Start the background job that queues scheduled sync runs and processes
+/// pending ones.
+///
+/// Runs interrupted by a restart are processed again; a run only pushes
+/// what differs from the recorded sync state, so nothing is duplicated.
+pub fn spawn_sync_job(db: PgPool) {
+    tokio::spawn(async move {
+        let client = match reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(e) => {
+                warn!(error = %e, "Failed to build LMS sync HTTP client, sync disabled");
+                return;
+            }
+        };
+
+        if let Err(e) = SyncService::requeue_interrupted(&db).await {
+            warn!(error = %e, "Failed to requeue interrupted sync runs");
+        }
+
+        let job = register_job("lms_sync", POLL_INTERVAL);
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let run = job
+                .run(async {
+                    SyncService::queue_due_runs(&db).await?;
+                    while let Some(run_id) = SyncService::process_next(&db, &client).await? {
+                        info!(%run_id, "Processed sync run");
+                    }
+                    Ok::<_, AppError>(())
+                })
+                .await;
+            if let Err(e) = run {
+                warn!(error = %e, "Failed to process sync run");
+            }
+            match SyncService::queue_depth(&db).await {
+                Ok(depth) => job.set_queue_depth(depth),
+                Err(e) => warn!(error = %e, "Failed to count pending sync runs"),
+            }
+        }
+    });
+}
+
+/// A run claimed by the sync job, with its connection.
+#[derive(Debug, FromRow)]
+struct ClaimedRun {
+    id: SyncRunId,
+    connection_id: SyncConnectionId,
+    school_id: SchoolId,
+    provider: SyncProviderKind,
+    credentials: serde_json::Value,
+}
+
+/// A branch to push as a course.
+#[derive(Debug, FromRow)]
+struct BranchRecord {
+    id: BranchId,
+    name: String,
+    level_name: String,
+}
+
+/// A student or current teacher of a branch.
+#[derive(Debug, FromRow)]
+struct MemberRecord {
+    branch_id: BranchId,
+    user_id: UserId,
+    email: String,
+    entity_type: SyncEntityType,
+}
+
+/// What a connection last recorded for a course or roster member.
+#[derive(Debug, Clone, FromRow)]
+struct EntityState {
+    entity_type: SyncEntityType,
+    branch_id: BranchId,
+    user_id: Option<UserId>,
+    remote_id: Option<String>,
+    status: SyncEntityStatus,
+    content_hash: Option<String>,
+    remote_version: Option<String>,
+}
+
+/// What a run did to one course or roster member.
+enum Outcome {
+    /// Created, updated, added, or removed in the LMS
+    Pushed,
+    /// Already matched the school
+    Unchanged,
+    Conflict(SyncConflictKind, String),
+    Failed(String),
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct RunSummary {
+    pushed: i32,
+    unchanged: i32,
+    conflicts: i32,
+    failures: i32,
+}
+
+impl RunSummary {
+    fn count(&mut self, outcome: &Outcome) {
+        match outcome {
+            Outcome::Pushed => self.pushed += 1,
+            Outcome::Unchanged => self.unchanged += 1,
+            Outcome::Conflict(..) => self.conflicts += 1,
+            Outcome::Failed(_) => self.failures += 1,
+        }
+    }
+}
+
+/// Stops the run when the LMS cannot be used at all, and otherwise returns
+/// the message to record against the course or member being pushed.
+fn failure_message(e: SyncProviderError) -> Result<String, AppError> {
+    match e {
+        SyncProviderError::Unavailable(message) => Err(AppError::new(
+            StatusCode::BAD_GATEWAY,
+            anyhow::anyhow!(message),
+        )),
+        e => Ok(e.to_string()),
+    }
+}
+
+fn roster_role(entity_type: SyncEntityType) -> RosterRole {
+    match entity_type {
+        SyncEntityType::Teacher => RosterRole::Teacher,
+        _ => RosterRole::Student,
+    }
+}
+
+fn role_name(role: RosterRole) -> &'static str {
+    match role {
+        RosterRole::Student => "student",
+        RosterRole::Teacher => "teacher",
+    }
+}
+
+/// Sync state a run writes for one course or roster member.
+struct EntityWrite<'a> {
+    entity_type: SyncEntityType,
+    branch_id: BranchId,
+    user_id: Option<UserId>,
+    remote_id: Option<&'a str>,
+    content_hash: Option<&'a str>,
+    remote_version: Option<&'a str>,
+}
+
+pub struct SyncService;
+
+impl SyncService {
+    #[instrument(skip(db, dto))]
+    pub async fn create_connection(
+        db: &PgPool,
+        school_id: SchoolId,
+        created_by: UserId,
+        dto: CreateSyncConnectionDto,
+    ) -> Result<SyncConnection, AppError> {
+        validate_credentials(dto.provider, &dto.credentials)?;
+
+        let connection = sqlx::query_as::<_, SyncConnection>(&format!(
+            "INSERT INTO sync_connections (school_id, provider, credentials, interval_minutes, created_by)
+             VALUES ($1, $2, $3, COALESCE($4, 360), $5)
+             RETURNING {CONNECTION_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(dto.provider)
+        .bind(&dto.credentials)
+        .bind(dto.interval_minutes)
+        .bind(created_by)
+        .fetch_one(db)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(db_err) = &e
+                && db_err.is_unique_violation()
+            {
+                return AppError::new(
+                    StatusCode::CONFLICT,
+                    anyhow::anyhow!("The school already has a connection to this provider"),
+                );
+            }
+            AppError::from(e)
+        })?;
+
+        info!(sync_connection.id = %connection.id, "LMS sync connection created");
+
+        Ok(connection)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_connections(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<Vec<SyncConnection>, AppError> {
+        let connections = sqlx::query_as::<_, SyncConnection>(&format!(
+            "SELECT {CONNECTION_COLUMNS} FROM sync_connections
+             WHERE school_id = $1
+             ORDER BY created_at"
+        ))
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(connections)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_connection(
+        db: &PgPool,
+        id: SyncConnectionId,
+        school_id: Option<SchoolId>,
+    ) -> Result<SyncConnection, AppError> {
+        sqlx::query_as::<_, SyncConnection>(&format!(
+            "SELECT {CONNECTION_COLUMNS} FROM sync_connections
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Sync connection not found")))
+    }
+
+    #[instrument(skip(db, dto))]
+    pub async fn update_connection(
+        db: &PgPool,
+        id: SyncConnectionId,
+        school_id: Option<SchoolId>,
+        dto: UpdateSyncConnectionDto,
+    ) -> Result<SyncConnection, AppError> {
+        if let Some(credentials) = &dto.credentials {
+            let connection = Self::get_connection(db, id, school_id).await?;
+            validate_credentials(connection.provider, credentials)?;
+        }
+
+        sqlx::query_as::<_, SyncConnection>(&format!(
+            "UPDATE sync_connections
+             SET credentials = COALESCE($3, credentials),
+                 interval_minutes = COALESCE($4, interval_minutes),
+                 is_active = COALESCE($5, is_active)
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)
+             RETURNING {CONNECTION_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(school_id)
+        .bind(dto.credentials)
+        .bind(dto.interval_minutes)
+        .bind(dto.is_active)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Sync connection not found")))
+    }
+
+    /// Delete a connection with its runs and sync state. Courses already
+    /// pushed are left in the LMS.
+    #[instrument(skip(db))]
+    pub async fn delete_connection(
+        db: &PgPool,
+        id: SyncConnectionId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let deleted = sqlx::query(
+            "DELETE FROM sync_connections WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(id)
+        .bind(school_id)
+        .execute(db)
+        .await?
+        .rows_affected();
+
+        if deleted == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Sync connection not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Queue a run outside the connection's schedule.
+    #[instrument(skip(db))]
+    pub async fn queue_run(
+        db: &PgPool,
+        id: SyncConnectionId,
+        school_id: Option<SchoolId>,
+        requested_by: UserId,
+    ) -> Result<SyncRun, AppError> {
+        Self::get_connection(db, id, school_id).await?;
+
+        let run = sqlx::query_as::<_, SyncRun>(&format!(
+            "INSERT INTO sync_runs (connection_id, trigger, requested_by)
+             VALUES ($1, 'manual', $2)
+             RETURNING {RUN_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(requested_by)
+        .fetch_one(db)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(db_err) = &e
+                && db_err.is_unique_violation()
+            {
+                return AppError::new(
+                    StatusCode::CONFLICT,
+                    anyhow::anyhow!("A sync run is already waiting or in progress"),
+                );
+            }
+            AppError::from(e)
+        })?;
+
+        info!(sync_run.id = %run.id, "Sync run queued");
+
+        Ok(run)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_runs(
+        db: &PgPool,
+        id: SyncConnectionId,
+        school_id: Option<SchoolId>,
+        pagination: PaginationParams,
+    ) -> Result<PaginatedSyncRunsResponse, AppError> {
+        Self::get_connection(db, id, school_id).await?;
+
+        let limit = pagination.limit();
+        let offset = pagination.offset();
+
+        let data = sqlx::query_as::<_, SyncRun>(&format!(
+            "SELECT {RUN_COLUMNS} FROM sync_runs
+             WHERE connection_id = $1
+             ORDER BY created_at DESC, id
+             LIMIT $2 OFFSET $3"
+        ))
+        .bind(id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total =
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM sync_runs WHERE connection_id = $1")
+                .bind(id)
+                .fetch_one(db)
+                .await?;
+
+        Ok(PaginatedSyncRunsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: pagination.page(),
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_entities(
+        db: &PgPool,
+        id: SyncConnectionId,
+        school_id: Option<SchoolId>,
+        params: SyncEntityFilterParams,
+    ) -> Result<PaginatedSyncEntitiesResponse, AppError> {
+        Self::get_connection(db, id, school_id).await?;
+
+        let limit = params.pagination.limit();
+        let offset = params.pagination.offset();
+        let filter = "connection_id = $1
+             AND ($2::text IS NULL OR status = $2)
+             AND ($3::text IS NULL OR entity_type = $3)
+             AND ($4::uuid IS NULL OR branch_id = $4)";
+
+        let data = sqlx::query_as::<_, SyncEntity>(&format!(
+            "SELECT {ENTITY_COLUMNS} FROM sync_entities
+             WHERE {filter}
+             ORDER BY updated_at DESC, id
+             LIMIT $5 OFFSET $6"
+        ))
+        .bind(id)
+        .bind(params.status)
+        .bind(params.entity_type)
+        .bind(params.branch_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM sync_entities WHERE {filter}"
+        ))
+        .bind(id)
+        .bind(params.status)
+        .bind(params.entity_type)
+        .bind(params.branch_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(PaginatedSyncEntitiesResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: params.pagination.page(),
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Resolve a conflict in the school's favour: the next run pushes the
+    /// school's version over whatever the LMS holds.
+    #[instrument(skip(db))]
+    pub async fn resolve_entity(
+        db: &PgPool,
+        id: SyncConnectionId,
+        school_id: Option<SchoolId>,
+        entity_id: SyncEntityId,
+    ) -> Result<SyncEntity, AppError> {
+        Self::get_connection(db, id, school_id).await?;
+
+        sqlx::query_as::<_, SyncEntity>(&format!(
+            "UPDATE sync_entities
+             SET status = 'pending', conflict = NULL, message = NULL, updated_at = NOW()
+             WHERE id = $1 AND connection_id = $2 AND status = 'conflict'
+             RETURNING {ENTITY_COLUMNS}"
+        ))
+        .bind(entity_id)
+        .bind(id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Sync conflict not found")))
+    }
+
+    /// Queue a run for every active connection whose next run is due, and
+    /// schedule the one after. Connections with a run already waiting or in
+    /// progress are skipped until their next slot.
+    pub async fn queue_due_runs(db: &PgPool) -> Result<u64, AppError> {
+        let queued = sqlx::query(
+            "WITH due AS (
+                 UPDATE sync_connections
+                 SET next_run_at = NOW() + make_interval(mins => interval_minutes)
+                 WHERE is_active AND next_run_at <= NOW()
+                 RETURNING id
+             )
+             INSERT INTO sync_runs (connection_id, trigger)
+             SELECT id, 'schedule' FROM due
+             ON CONFLICT DO NOTHING",
+        )
+        .execute(db)
+        .await?
+        .rows_affected();
+
+        Ok(queued)
+    }
+
+    /// Number of runs waiting to be processed.
+    pub async fn queue_depth(db: &PgPool) -> Result<u64, AppError> {
+        let pending =
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM sync_runs WHERE status = 'pending'")
+                .fetch_one(db)
+                .await?;
+
+        Ok(pending.try_into().unwrap_or_default())
+    }
+
+    /// Put runs left in progress by a previous process back in the queue.
+    pub async fn requeue_interrupted(db: &PgPool) -> Result<u64, AppError> {
+        let requeued =
+            sqlx::query("UPDATE sync_runs SET status = 'pending' WHERE status = 'running'")
+                .execute(db)
+                .await?
+                .rows_affected();
+
+        Ok(requeued)
+    }
+
+    /// Process the oldest pending run.
+    ///
+    /// Returns the run processed, or `None` when none is pending. A run that
+    /// cannot reach the LMS is marked failed with the error.
+    #[instrument(skip(db, client))]
+    pub async fn process_next(
+        db: &PgPool,
+        client: &reqwest::Client,
+    ) -> Result<Option<SyncRunId>, AppError> {
+        let Some(run) = sqlx::query_as::<_, ClaimedRun>(
+            "UPDATE sync_runs r SET status = 'running', started_at = NOW()
+             FROM sync_connections c
+             WHERE c.id = r.connection_id
+               AND r.id = (
+                   SELECT id FROM sync_runs
+                   WHERE status = 'pending'
+                   ORDER BY created_at
+                   LIMIT 1
+                   FOR UPDATE SKIP LOCKED
+               )
+             RETURNING r.id, r.connection_id, c.school_id, c.provider, c.credentials",
+        )
+        .fetch_optional(db)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let result = match init_sync_provider(client.clone(), run.provider, run.credentials.clone())
+        {
+            Ok(provider) => Self::sync(db, &run, provider.as_ref()).await,
+            Err(e) => Err(AppError::new(StatusCode::BAD_GATEWAY, anyhow::anyhow!(e))),
+        };
+
+        match result {
+            Ok(summary) => {
+                sqlx::query(
+                    "UPDATE sync_runs
+                     SET status = 'completed', completed_at = NOW(),
+                         pushed = $2, unchanged = $3, conflicts = $4, failures = $5
+                     WHERE id = $1",
+                )
+                .bind(run.id)
+                .bind(summary.pushed)
+                .bind(summary.unchanged)
+                .bind(summary.conflicts)
+                .bind(summary.failures)
+                .execute(db)
+                .await?;
+            }
+            Err(e) => {
+                warn!(sync_run.id = %run.id, error = %e, "Sync run failed");
+                sqlx::query(
+                    "UPDATE sync_runs
+                     SET status = 'failed', completed_at = NOW(), error = $2
+                     WHERE id = $1",
+                )
+                .bind(run.id)
+                .bind(e.to_string())
+                .execute(db)
+                .await?;
+            }
+        }
+
+        Ok(Some(run.id))
+    }
+
+    /// Push the school's branches and rosters, recording the outcome for
+    /// each course and roster member.
+    async fn sync(
+        db: &PgPool,
+        run: &ClaimedRun,
+        provider: &dyn SyncProvider,
+    ) -> Result<RunSummary, AppError> {
+        let branches = sqlx::query_as::<_, BranchRecord>(
+            r#"SELECT b.id, b.name, l.name AS level_name
+               FROM branches b
+               INNER JOIN levels l ON l.id = b.level_id
+               WHERE l.school_id = $1
+               ORDER BY l.name, b.name, b.id"#,
+        )
+        .bind(run.school_id)
+        .fetch_all(db)
+        .await?;
+
+        let mut members: HashMap<BranchId, Vec<MemberRecord>> = HashMap::new();
+        for member in sqlx::query_as::<_, MemberRecord>(
+            r#"SELECT u.branch_id, u.id AS user_id, u.email, 'student' AS entity_type
+               FROM users u
+               INNER JOIN user_roles ur ON ur.user_id = u.id AND ur.role_id = $2
+               WHERE u.school_id = $1 AND u.branch_id IS NOT NULL
+               UNION
+               SELECT bt.branch_id, u.id, u.email, 'teacher'
+               FROM branch_teachers bt
+               INNER JOIN users u ON u.id = bt.teacher_id
+               INNER JOIN branches b ON b.id = bt.branch_id
+               INNER JOIN levels l ON l.id = b.level_id
+               INNER JOIN schools s ON s.id = l.school_id
+               WHERE l.school_id = $1
+                 AND bt.starts_on <= (NOW() AT TIME ZONE s.timezone)::date
+                 AND (bt.ends_on IS NULL OR bt.ends_on >= (NOW() AT TIME ZONE s.timezone)::date)
+               ORDER BY 1, 4, 3"#,
+        )
+        .bind(run.school_id)
+        .bind(system_roles::STUDENT)
+        .fetch_all(db)
+        .await?
+        {
+            members.entry(member.branch_id).or_default().push(member);
+        }
+
+        let mut courses: HashMap<BranchId, EntityState> = HashMap::new();
+        let mut member_states: HashMap<BranchId, Vec<EntityState>> = HashMap::new();
+        for state in sqlx::query_as::<_, EntityState>(
+            "SELECT entity_type, branch_id, user_id, remote_id, status, content_hash, remote_version
+             FROM sync_entities WHERE connection_id = $1",
+        )
+        .bind(run.connection_id)
+        .fetch_all(db)
+        .await?
+        {
+            if state.entity_type == SyncEntityType::Course {
+                courses.insert(state.branch_id, state);
+            } else {
+                member_states.entry(state.branch_id).or_default().push(state);
+            }
+        }
+
+        let mut summary = RunSummary::default();
+        for branch in &branches {
+            let state = courses.remove(&branch.id);
+            let mut states = member_states.remove(&branch.id).unwrap_or_default();
+            let spec = CourseSpec {
+                branch_id: branch.id,
+                name: format!("{} {}", branch.level_name, branch.name),
+                section: branch.level_name.clone(),
+            };
+
+            let Some(course_id) =
+                Self::sync_course(db, run, provider, &spec, state.as_ref(), &mut summary).await?
+            else {
+                continue;
+            };
+            if state
+                .and_then(|s| s.remote_id)
+                .is_some_and(|id| id != course_id)
+            {
+                // A recreated course starts with an empty roster
+                sqlx::query(
+                    "DELETE FROM sync_entities
+                     WHERE connection_id = $1 AND branch_id = $2 AND entity_type <> 'course'",
+                )
+                .bind(run.connection_id)
+                .bind(branch.id)
+                .execute(db)
+                .await?;
+                states.clear();
+            }
+
+            let desired = members.remove(&branch.id).unwrap_or_default();
+            Self::sync_roster(
+                db,
+                run,
+                provider,
+                branch.id,
+                &course_id,
+                &desired,
+                states,
+                &mut summary,
+            )
+            .await?;
+        }
+
+        // Courses whose branch was deleted are archived
+        for (branch_id, state) in courses {
+            let outcome = match &state.remote_id {
+                Some(remote_id) => match provider.archive_course(remote_id).await {
+                    Ok(()) => Outcome::Pushed,
+                    Err(SyncProviderError::NotFound(_)) => Outcome::Unchanged,
+                    Err(e) => Outcome::Failed(failure_message(e)?),
+                },
+                None => Outcome::Unchanged,
+            };
+            if let Outcome::Failed(_) = outcome {
+                summary.count(&outcome);
+                Self::record(db, run, &Self::course_write(branch_id, None), &outcome).await?;
+                continue;
+            }
+            if let Outcome::Pushed = outcome {
+                summary.count(&outcome);
+            }
+            sqlx::query("DELETE FROM sync_entities WHERE connection_id = $1 AND branch_id = $2")
+                .bind(run.connection_id)
+                .bind(branch_id)
+                .execute(db)
+                .await?;
+        }
+
+        // Roster states of deleted branches whose course is gone
+        sqlx::query(
+            "DELETE FROM sync_entities e
+             WHERE e.connection_id = $1
+               AND e.entity_type <> 'course'
+               AND NOT (e.branch_id = ANY($2))
+               AND NOT EXISTS (
+                   SELECT 1 FROM sync_entities c
+                   WHERE c.connection_id = e.connection_id
+                     AND c.entity_type = 'course'
+                     AND c.branch_id = e.branch_id
+               )",
+        )
+        .bind(run.connection_id)
+        .bind(branches.iter().map(|b| b.id).collect::<Vec<_>>())
+        .execute(db)
+        .await?;
+
+        Ok(summary)
+    }
+
+    fn course_write(branch_id: BranchId, remote_id: Option<&str>) -> EntityWrite<'_> {
+        EntityWrite {
+            entity_type: SyncEntityType::Course,
+            branch_id,
+            user_id: None,
+            remote_id,
+            content_hash: None,
+            remote_version: None,
+        }
+    }
+
+    /// Push one branch's course. Returns the course's ID in the LMS when it
+    /// matches the branch, so its roster can be pushed.
+    async fn sync_course(
+        db: &PgPool,
+        run: &ClaimedRun,
+        provider: &dyn SyncProvider,
+        spec: &CourseSpec,
+        state: Option<&EntityState>,
+        summary: &mut RunSummary,
+    ) -> Result<Option<String>, AppError> {
+        let hash = spec.content_hash();
+
+        let result = match state {
+            Some(state) if state.status == SyncEntityStatus::Conflict => {
+                // Left alone until an admin resolves it
+                summary.conflicts += 1;
+                return Ok(None);
+            }
+            Some(EntityState {
+                status: SyncEntityStatus::Synced,
+                remote_id: Some(remote_id),
+                content_hash,
+                remote_version,
+                ..
+            }) => match provider.get_course(remote_id).await {
+                Ok(None) => Ok((
+                    Outcome::Conflict(
+                        SyncConflictKind::RemoteDeleted,
+                        "The course was deleted in the LMS".to_string(),
+                    ),
+                    None,
+                )),
+                Ok(Some(remote)) => {
+                    let local_changed = content_hash.as_deref() != Some(hash.as_str());
+                    let remote_changed = remote_version.as_deref() != Some(remote.version.as_str());
+                    if local_changed && remote_changed {
+                        Ok((
+                            Outcome::Conflict(
+                                SyncConflictKind::RemoteChanged,
+                                "The course was edited in the LMS since the branch was last pushed"
+                                    .to_string(),
+                            ),
+                            None,
+                        ))
+                    } else if local_changed {
+                        provider
+                            .update_course(&remote.id, spec)
+                            .await
+                            .map(|course| (Outcome::Pushed, Some(course)))
+                    } else {
+                        Ok((Outcome::Unchanged, Some(remote)))
+                    }
+                }
+                Err(e) => Err(e),
+            },
+            // New, failed, or resolved courses get the branch as it is now
+            _ => {
+                let existing = match state.and_then(|s| s.remote_id.as_deref()) {
+                    Some(remote_id) => provider.get_course(remote_id).await,
+                    None => Ok(None),
+                };
+                let existing = match existing {
+                    Ok(None) => provider.find_course(spec.branch_id).await,
+                    existing => existing,
+                };
+                match existing {
+                    Ok(Some(course)) => provider.update_course(&course.id, spec).await,
+                    Ok(None) => provider.create_course(spec).await,
+                    Err(e) => Err(e),
+                }
+                .map(|course| (Outcome::Pushed, Some(course)))
+            }
+        };
+
+        let (outcome, course) = match result {
+            Ok(result) => result,
+            Err(e) => (Outcome::Failed(failure_message(e)?), None),
+        };
+        summary.count(&outcome);
+
+        let write = match &course {
+            Some(course) => EntityWrite {
+                remote_id: Some(&course.id),
+                content_hash: Some(&hash),
+                remote_version: Some(&course.version),
+                ..Self::course_write(spec.branch_id, None)
+            },
+            None => Self::course_write(spec.branch_id, None),
+        };
+        Self::record(db, run, &write, &outcome).await?;
+
+        Ok(course.map(|course| course.id))
+    }
+
+    /// Push one course's roster: add the branch's students and current
+    /// teachers, and remove members an earlier run added who no longer
+    /// belong. Members added in the LMS by hand are left alone.
+    #[allow(clippy::too_many_arguments)]
+    async fn sync_roster(
+        db: &PgPool,
+        run: &ClaimedRun,
+        provider: &dyn SyncProvider,
+        branch_id: BranchId,
+        course_id: &str,
+        desired: &[MemberRecord],
+        states: Vec<EntityState>,
+        summary: &mut RunSummary,
+    ) -> Result<(), AppError> {
+        let remote = match provider.list_members(course_id).await {
+            Ok(remote) => remote,
+            Err(e) => {
+                let outcome = Outcome::Failed(format!(
+                    "Could not read the course roster: {}",
+                    failure_message(e)?
+                ));
+                summary.count(&outcome);
+                Self::record(db, run, &Self::course_write(branch_id, None), &outcome).await?;
+                return Ok(());
+            }
+        };
+        let find_remote = |remote_id: Option<&str>, email: &str| -> Option<&RemoteMember> {
+            remote
+                .iter()
+                .find(|m| Some(m.user_id.as_str()) == remote_id)
+                .or_else(|| {
+                    remote.iter().find(|m| {
+                        m.email
+                            .as_deref()
+                            .is_some_and(|e| e.eq_ignore_ascii_case(email))
+                    })
+                })
+        };
+
+        let mut states: HashMap<(SyncEntityType, UserId), EntityState> = states
+            .into_iter()
+            .filter_map(|state| Some(((state.entity_type, state.user_id?), state)))
+            .collect();
+
+        for member in desired {
+            let role = roster_role(member.entity_type);
+            let state = states.remove(&(member.entity_type, member.user_id));
+            let status = state.as_ref().map(|s| s.status);
+            if status == Some(SyncEntityStatus::Conflict) {
+                summary.conflicts += 1;
+                continue;
+            }
+
+            let mut remote_id: Option<String> = None;
+            let outcome = match find_remote(
+                state.as_ref().and_then(|s| s.remote_id.as_deref()),
+                &member.email,
+            ) {
+                Some(found) if found.role == role => {
+                    remote_id = Some(found.user_id.clone());
+                    Outcome::Unchanged
+                }
+                Some(found) if status != Some(SyncEntityStatus::Pending) => Outcome::Conflict(
+                    SyncConflictKind::RoleMismatch,
+                    format!(
+                        "{} is a {} of the course in the LMS",
+                        member.email,
+                        role_name(found.role)
+                    ),
+                ),
+                None if status == Some(SyncEntityStatus::Synced) => Outcome::Conflict(
+                    SyncConflictKind::RemoteRemoved,
+                    format!("{} was removed from the course in the LMS", member.email),
+                ),
+                found => {
+                    // A resolved role mismatch moves the member to the
+                    // school's role
+                    let moved = match found {
+                        Some(found) => provider
+                            .remove_member(course_id, found.role, &found.user_id)
+                            .await
+                            .or_else(|e| match e {
+                                SyncProviderError::NotFound(_) => Ok(()),
+                                e => Err(e),
+                            }),
+                        None => Ok(()),
+                    };
+                    match moved {
+                        Ok(()) => match provider.add_member(course_id, role, &member.email).await {
+                            Ok(added) => {
+                                remote_id = Some(added.user_id);
+                                Outcome::Pushed
+                            }
+                            Err(SyncProviderError::AlreadyExists(_)) => Outcome::Unchanged,
+                            Err(SyncProviderError::NotFound(_)) => Outcome::Failed(format!(
+                                "{} has no account in the LMS",
+                                member.email
+                            )),
+                            Err(e) => Outcome::Failed(failure_message(e)?),
+                        },
+                        Err(e) => Outcome::Failed(failure_message(e)?),
+                    }
+                }
+            };
+            summary.count(&outcome);
+
+            let write = EntityWrite {
+                entity_type: member.entity_type,
+                branch_id,
+                user_id: Some(member.user_id),
+                remote_id: remote_id.as_deref(),
+                content_hash: None,
+                remote_version: None,
+            };
+            Self::record(db, run, &write, &outcome).await?;
+        }
+
+        // Members an earlier run added who no longer belong to the branch
+        for ((entity_type, user_id), state) in states {
+            let role = roster_role(entity_type);
+            let on_roster = state.remote_id.as_deref().filter(|remote_id| {
+                state.status != SyncEntityStatus::Conflict
+                    && remote
+                        .iter()
+                        .any(|m| m.user_id == *remote_id && m.role == role)
+            });
+            if let Some(remote_id) = on_roster {
+                let outcome = match provider.remove_member(course_id, role, remote_id).await {
+                    Ok(()) => Outcome::Pushed,
+                    Err(SyncProviderError::NotFound(_)) => Outcome::Unchanged,
+                    Err(e) => Outcome::Failed(failure_message(e)?),
+                };
+                if let Outcome::Failed(_) = outcome {
+                    summary.count(&outcome);
+                    let write = EntityWrite {
+                        entity_type,
+                        branch_id,
+                        user_id: Some(user_id),
+                        remote_id: None,
+                        content_hash: None,
+                        remote_version: None,
+                    };
+                    Self::record(db, run, &write, &outcome).await?;
+                    continue;
+                }
+                if let Outcome::Pushed = outcome {
+                    summary.count(&outcome);
+                }
+            }
+            sqlx::query(
+                "DELETE FROM sync_entities
+                 WHERE connection_id = $1 AND entity_type = $2 AND branch_id = $3 AND user_id = $4",
+            )
+            .bind(run.connection_id)
+            .bind(entity_type)
+            .bind(branch_id)
+            .bind(user_id)
+            .execute(db)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Record a run's outcome for a course or roster member. The LMS IDs
+    /// and versions from earlier runs are kept when the outcome has none.
+    async fn record(
+        db: &PgPool,
+        run: &ClaimedRun,
+        write: &EntityWrite<'_>,
+        outcome: &Outcome,
+    ) -> Result<(), AppError> {
+        let (status, conflict, message) = match outcome {
+            Outcome::Pushed | Outcome::Unchanged => (SyncEntityStatus::Synced, None, None),
+            Outcome::Conflict(kind, message) => (
+                SyncEntityStatus::Conflict,
+                Some(*kind),
+                Some(message.as_str()),
+            ),
+            Outcome::Failed(message) => (SyncEntityStatus::Failed, None, Some(message.as_str())),
+        };
+
+        sqlx::query(
+            "INSERT INTO sync_entities (connection_id, entity_type, branch_id, user_id, remote_id,
+                 status, conflict, message, content_hash, remote_version, last_run_id, synced_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
+                 CASE WHEN $6 = 'synced' THEN NOW() END)
+             ON CONFLICT ON CONSTRAINT unique_sync_entity DO UPDATE SET
+                 remote_id = COALESCE(EXCLUDED.remote_id, sync_entities.remote_id),
+                 status = EXCLUDED.status,
+                 conflict = EXCLUDED.conflict,
+                 message = EXCLUDED.message,
+                 content_hash = COALESCE(EXCLUDED.content_hash, sync_entities.content_hash),
+                 remote_version = COALESCE(EXCLUDED.remote_version, sync_entities.remote_version),
+                 last_run_id = EXCLUDED.last_run_id,
+                 synced_at = COALESCE(EXCLUDED.synced_at, sync_entities.synced_at),
+                 updated_at = NOW()",
+        )
+        .bind(run.connection_id)
+        .bind(write.entity_type)
+        .bind(write.branch_id)
+        .bind(write.user_id)
+        .bind(write.remote_id)
+        .bind(status)
+        .bind(conflict)
+        .bind(message)
+        .bind(write.content_hash)
+        .bind(write.remote_version)
+        .bind(run.id)
+        .execute(db)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::sync::model::{SyncRunStatus, SyncRunTrigger};
+    use crate::modules::sync::provider::{RemoteCourse, SyncFuture};
+    use chalkbyte_models::ids::{LevelId, RoleId};
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    struct FakeCourse {
+        branch_id: BranchId,
+        name: String,
+        version: u32,
+        archived: bool,
+    }
+
+    /// In-memory LMS where only the emails in `accounts` have an account.
+    #[derive(Default)]
+    struct FakeLms {
+        courses: Mutex<HashMap<String, FakeCourse>>,
+        members: Mutex<Vec<(String, RemoteMember)>>,
+        accounts: HashSet<String>,
+    }
+
+    impl FakeLms {
+        fn remote(id: &str, course: &FakeCourse) -> RemoteCourse {
+            RemoteCourse {
+                id: id.to_string(),
+                version: course.version.to_string(),
+            }
+        }
+
+        fn course_name(&self, id: &str) -> String {
+            self.courses.lock().unwrap()[id].name.clone()
+        }
+
+        fn edit_course(&self, id: &str) {
+            self.courses.lock().unwrap().get_mut(id).unwrap().version += 1;
+        }
+
+        fn roster(&self) -> Vec<(String, RosterRole)> {
+            let mut roster: Vec<_> = self
+                .members
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(_, m)| (m.email.clone().unwrap(), m.role))
+                .collect();
+            roster.sort_by(|a, b| a.0.cmp(&b.0));
+            roster
+        }
+
+        fn drop_member(&self, email: &str) {
+            self.members
+                .lock()
+                .unwrap()
+                .retain(|(_, m)| m.email.as_deref() != Some(email));
+        }
+    }
+
+    impl SyncProvider for FakeLms {
+        fn find_course<'a>(&'a self, branch_id: BranchId) -> SyncFuture<'a, Option<RemoteCourse>> {
+            Box::pin(async move {
+                let courses = self.courses.lock().unwrap();
+                Ok(courses
+                    .iter()
+                    .find(|(_, c)| c.branch_id == branch_id)
+                    .map(|(id, c)| Self::remote(id, c)))
+            })
+        }
+
+        fn get_course<'a>(&'a self, course_id: &'a str) -> SyncFuture<'a, Option<RemoteCourse>> {
+            Box::pin(async move {
+                let courses = self.courses.lock().unwrap();
+                Ok(courses.get(course_id).map(|c| Self::remote(course_id, c)))
+            })
+        }
+
+        fn create_course<'a>(&'a self, course: &'a CourseSpec) -> SyncFuture<'a, RemoteCourse> {
+            Box::pin(async move {
+                let mut courses = self.courses.lock().unwrap();
+                let id = format!("course-{}", courses.len() + 1);
+                let created = FakeCourse {
+                    branch_id: course.branch_id,
+                    name: course.name.clone(),
+                    version: 1,
+                    archived: false,
+                };
+                let remote = Self::remote(&id, &created);
+                courses.insert(id, created);
+                Ok(remote)
+            })
+        }
+
+        fn update_course<'a>(
+            &'a self,
+            course_id: &'a str,
+            course: &'a CourseSpec,
+        ) -> SyncFuture<'a, RemoteCourse> {
+            Box::pin(async move {
+                let mut courses = self.courses.lock().unwrap();
+                let existing = courses
+                    .get_mut(course_id)
+                    .ok_or_else(|| SyncProviderError::NotFound("No course".to_string()))?;
+                existing.name = course.name.clone();
+                existing.version += 1;
+                Ok(Self::remote(course_id, existing))
+            })
+        }
+
+        fn archive_course<'a>(&'a self, course_id: &'a str) -> SyncFuture<'a, ()> {
+            Box::pin(async move {
+                let mut courses = self.courses.lock().unwrap();
+                let existing = courses
+                    .get_mut(course_id)
+                    .ok_or_else(|| SyncProviderError::NotFound("No course".to_string()))?;
+                existing.archived = true;
+                Ok(())
+            })
+        }
+
+        fn list_members<'a>(&'a self, course_id: &'a str) -> SyncFuture<'a, Vec<RemoteMember>> {
+            Box::pin(async move {
+                let members = self.members.lock().unwrap();
+                Ok(members
+                    .iter()
+                    .filter(|(course, _)| course == course_id)
+                    .map(|(_, member)| member.clone())
+                    .collect())
+            })
+        }
+
+        fn add_member<'a>(
+            &'a self,
+            course_id: &'a str,
+            role: RosterRole,
+            email: &'a str,
+        ) -> SyncFuture<'a, RemoteMember> {
+            Box::pin(async move {
+                if !self.accounts.contains(email) {
+                    return Err(SyncProviderError::NotFound("No such user".to_string()));
+                }
+                let member = RemoteMember {
+                    user_id: format!("user-{email}"),
+                    email: Some(email.to_string()),
+                    role,
+                };
+                let mut members = self.members.lock().unwrap();
+                if members
+                    .iter()
+                    .any(|(course, m)| course == course_id && m.user_id == member.user_id)
+                {
+                    return Err(SyncProviderError::AlreadyExists("On roster".to_string()));
+                }
+                members.push((course_id.to_string(), member.clone()));
+                Ok(member)
+            })
+        }
+
+        fn remove_member<'a>(
+            &'a self,
+            course_id: &'a str,
+            role: RosterRole,
+            user_id: &'a str,
+        ) -> SyncFuture<'a, ()> {
+            Box::pin(async move {
+                let mut members = self.members.lock().unwrap();
+                let before = members.len();
+                members.retain(|(course, m)| {
+                    !(course == course_id && m.user_id == user_id && m.role == role)
+                });
+                if members.len() == before {
+                    return Err(SyncProviderError::NotFound("Not on roster".to_string()));
+                }
+                Ok(())
+            })
+        }
+    }
+
+    async fn create_user(
+        pool: &PgPool,
+        school_id: SchoolId,
+        name: &str,
+        role_id: RoleId,
+        branch_id: Option<BranchId>,
+    ) -> UserId {
+        let user_id = sqlx::query_scalar(
+            r#"INSERT INTO users (first_name, last_name, email, school_id, branch_id)
+               VALUES ($1, 'Test', $2, $3, $4) RETURNING id"#,
+        )
+        .bind(name)
+        .bind(format!("{}@example.com", name.to_lowercase()))
+        .bind(school_id)
+        .bind(branch_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(role_id)
+            .execute(pool)
+            .await
+            .unwrap();
+        user_id
+    }
+
+    async fn create_connection(pool: &PgPool) -> (SchoolId, SyncConnectionId) {
+        let school_id: SchoolId =
+            sqlx::query_scalar("INSERT INTO schools (name) VALUES ('Sync School') RETURNING id")
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        let connection_id = sqlx::query_scalar(
+            "INSERT INTO sync_connections (school_id, provider, credentials)
+             VALUES ($1, 'google_classroom', '{}') RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        (school_id, connection_id)
+    }
+
+    async fn run_sync(
+        pool: &PgPool,
+        school_id: SchoolId,
+        connection_id: SyncConnectionId,
+        lms: &FakeLms,
+    ) -> RunSummary {
+        let run_id = sqlx::query_scalar(
+            "INSERT INTO sync_runs (connection_id, trigger, status)
+             VALUES ($1, 'manual', 'completed') RETURNING id",
+        )
+        .bind(connection_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        let run = ClaimedRun {
+            id: run_id,
+            connection_id,
+            school_id,
+            provider: SyncProviderKind::GoogleClassroom,
+            credentials: serde_json::json!({}),
+        };
+        SyncService::sync(pool, &run, lms).await.unwrap()
+    }
+
+    fn summary(pushed: i32, unchanged: i32, conflicts: i32, failures: i32) -> RunSummary {
+        RunSummary {
+            pushed,
+            unchanged,
+            conflicts,
+            failures,
+        }
+    }
+
+    async fn conflicts(pool: &PgPool, connection_id: SyncConnectionId) -> Vec<SyncEntity> {
+        let params = SyncEntityFilterParams {
+            status: Some(SyncEntityStatus::Conflict),
+            entity_type: None,
+            branch_id: None,
+            pagination: PaginationParams::default(),
+        };
+        SyncService::get_entities(pool, connection_id, None, params)
+            .await
+            .unwrap()
+            .data
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_sync_pushes_changes_and_reports_conflicts(pool: PgPool) {
+        let (school_id, connection_id) = create_connection(&pool).await;
+        let level_id: LevelId = sqlx::query_scalar(
+            "INSERT INTO levels (name, school_id) VALUES ('JSS 1', $1) RETURNING id",
+        )
+        .bind(school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let branch_id: BranchId = sqlx::query_scalar(
+            "INSERT INTO branches (name, level_id) VALUES ('A', $1) RETURNING id",
+        )
+        .bind(level_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        create_user(
+            &pool,
+            school_id,
+            "Ada",
+            system_roles::STUDENT,
+            Some(branch_id),
+        )
+        .await;
+        create_user(
+            &pool,
+            school_id,
+            "Bola",
+            system_roles::STUDENT,
+            Some(branch_id),
+        )
+        .await;
+        let teacher = create_user(&pool, school_id, "Tunde", system_roles::TEACHER, None).await;
+        sqlx::query(
+            "INSERT INTO branch_teachers (branch_id, teacher_id, capacity, starts_on)
+             VALUES ($1, $2, 'lead', '2020-01-01')",
+        )
+        .bind(branch_id)
+        .bind(teacher)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Bola has no LMS account
+        let lms = FakeLms {
+            accounts: ["ada@example.com", "tunde@example.com"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            ..Default::default()
+        };
+
+        let first = run_sync(&pool, school_id, connection_id, &lms).await;
+        assert_eq!(first, summary(3, 0, 0, 1));
+        assert_eq!(lms.course_name("course-1"), "JSS 1 A");
+        assert_eq!(
+            lms.roster(),
+            vec![
+                ("ada@example.com".to_string(), RosterRole::Student),
+                ("tunde@example.com".to_string(), RosterRole::Teacher),
+            ]
+        );
+
+        // Nothing changed; the missing account is retried
+        let second = run_sync(&pool, school_id, connection_id, &lms).await;
+        assert_eq!(second, summary(0, 3, 0, 1));
+
+        sqlx::query("UPDATE branches SET name = 'B' WHERE id = $1")
+            .bind(branch_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let renamed = run_sync(&pool, school_id, connection_id, &lms).await;
+        assert_eq!(renamed, summary(1, 2, 0, 1));
+        assert_eq!(lms.course_name("course-1"), "JSS 1 B");
+
+        // Edited on both sides: the LMS's edit is not overwritten
+        lms.edit_course("course-1");
+        sqlx::query("UPDATE branches SET name = 'C' WHERE id = $1")
+            .bind(branch_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let edited = run_sync(&pool, school_id, connection_id, &lms).await;
+        assert_eq!(edited, summary(0, 0, 1, 0));
+        assert_eq!(lms.course_name("course-1"), "JSS 1 B");
+        let found = conflicts(&pool, connection_id).await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].conflict, Some(SyncConflictKind::RemoteChanged));
+
+        // Resolving pushes the branch; a member removed in the LMS is a conflict
+        let resolved = SyncService::resolve_entity(&pool, connection_id, None, found[0].id)
+            .await
+            .unwrap();
+        assert_eq!(resolved.status, SyncEntityStatus::Pending);
+        lms.drop_member("ada@example.com");
+        let after_resolve = run_sync(&pool, school_id, connection_id, &lms).await;
+        assert_eq!(after_resolve, summary(1, 1, 1, 1));
+        assert_eq!(lms.course_name("course-1"), "JSS 1 C");
+        let found = conflicts(&pool, connection_id).await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].conflict, Some(SyncConflictKind::RemoteRemoved));
+
+        // Resolved members are re-added, and teachers who left are removed
+        SyncService::resolve_entity(&pool, connection_id, None, found[0].id)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM branch_teachers WHERE teacher_id = $1")
+            .bind(teacher)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let readded = run_sync(&pool, school_id, connection_id, &lms).await;
+        assert_eq!(readded, summary(2, 1, 0, 1));
+        assert_eq!(
+            lms.roster(),
+            vec![("ada@example.com".to_string(), RosterRole::Student)]
+        );
+
+        // Deleted branches have their course archived and their state dropped
+        sqlx::query("DELETE FROM branches WHERE id = $1")
+            .bind(branch_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let deleted = run_sync(&pool, school_id, connection_id, &lms).await;
+        assert_eq!(deleted, summary(1, 0, 0, 0));
+        assert!(lms.courses.lock().unwrap()["course-1"].archived);
+        let remaining: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM sync_entities WHERE connection_id = $1")
+                .bind(connection_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(remaining, 0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_due_connections_are_queued_once(pool: PgPool) {
+        let (school_id, connection_id) = create_connection(&pool).await;
+
+        assert_eq!(SyncService::queue_due_runs(&pool).await.unwrap(), 1);
+        // Rescheduled, so the next poll queues nothing
+        assert_eq!(SyncService::queue_due_runs(&pool).await.unwrap(), 0);
+
+        let requester = create_user(&pool, school_id, "Admin", system_roles::ADMIN, None).await;
+        let err = SyncService::queue_run(&pool, connection_id, Some(school_id), requester)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        // A run that cannot use the connection's credentials fails
+        let run_id = SyncService::process_next(&pool, &reqwest::Client::new())
+            .await
+            .unwrap()
+            .unwrap();
+        let runs = SyncService::get_runs(
+            &pool,
+            connection_id,
+            Some(school_id),
+            PaginationParams::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(runs.data[0].id, run_id);
+        assert_eq!(runs.data[0].trigger, SyncRunTrigger::Schedule);
+        assert_eq!(runs.data[0].status, SyncRunStatus::Failed);
+        assert!(runs.data[0].error.is_some());
+
+        // With the scheduled run finished, one can be queued by hand
+        let run = SyncService::queue_run(&pool, connection_id, Some(school_id), requester)
+            .await
+            .unwrap();
+        assert_eq!(run.trigger, SyncRunTrigger::Manual);
+
+        let other_school: SchoolId =
+            sqlx::query_scalar("INSERT INTO schools (name) VALUES ('Other') RETURNING id")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let err = SyncService::get_connection(&pool, connection_id, Some(other_school))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+}
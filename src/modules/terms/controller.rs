@@ -1,19 +1,18 @@
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
 };
 use tracing::instrument;
 use uuid::Uuid;
-use validator::Validate;
 
-use chalkbyte_core::AppError;
+use chalkbyte_core::{AppError, Conditional, Created, IfMatch, NoContent, WithEtag};
 use chalkbyte_models::ids::{AcademicSessionId, TermId};
 
 use crate::middleware::auth::{
     RequireTermsCreate, RequireTermsDelete, RequireTermsRead, RequireTermsUpdate,
 };
 use crate::middleware::role::is_system_admin_jwt;
+use crate::modules::auth::controller::ErrorResponse;
 use crate::modules::terms::model::{
     CreateTermDto, PaginatedTermsResponse, Term, TermFilterParams, TermWithSessionInfo,
     UpdateTermDto,
@@ -21,6 +20,7 @@ use crate::modules::terms::model::{
 use crate::modules::terms::service::TermService;
 use crate::state::AppState;
 use crate::utils::auth_helpers::{get_admin_school_id, get_school_id_for_scoped_operation};
+use crate::validator::ValidatedJson;
 
 /// Create a new term within an academic session
 #[utoipa::path(
@@ -32,11 +32,13 @@ use crate::utils::auth_helpers::{get_admin_school_id, get_school_id_for_scoped_o
     ),
     request_body = CreateTermDto,
     responses(
-        (status = 201, description = "Term created successfully", body = Term),
-        (status = 400, description = "Invalid input or date validation failed"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires terms:create permission"),
-        (status = 404, description = "Academic session not found")
+        Created<Term>,
+        (status = 400, description = "Invalid input or date validation failed", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires terms:create permission", body = ErrorResponse),
+        (status = 404, description = "Academic session not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Terms",
     security(("bearer_auth" = []))
@@ -46,14 +48,12 @@ pub async fn create_session_term(
     State(state): State<AppState>,
     RequireTermsCreate(_auth_user): RequireTermsCreate,
     Path(session_id): Path<Uuid>,
-    Json(dto): Json<CreateTermDto>,
-) -> Result<(StatusCode, Json<Term>), AppError> {
-    dto.validate()?;
-
+    ValidatedJson(dto): ValidatedJson<CreateTermDto>,
+) -> Result<Created<Term>, AppError> {
     let session_id = AcademicSessionId::from(session_id);
     let term = TermService::create_term(&state.db, session_id, dto).await?;
 
-    Ok((StatusCode::CREATED, Json(term)))
+    Ok(Created(term))
 }
 
 /// Get all terms for an academic session
@@ -67,9 +67,10 @@ pub async fn create_session_term(
     ),
     responses(
         (status = 200, description = "List of terms", body = PaginatedTermsResponse),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires terms:read permission"),
-        (status = 404, description = "Academic session not found")
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires terms:read permission", body = ErrorResponse),
+        (status = 404, description = "Academic session not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Terms",
     security(("bearer_auth" = []))
@@ -97,9 +98,10 @@ pub async fn get_session_terms(
     ),
     responses(
         (status = 200, description = "Current term", body = Option<TermWithSessionInfo>),
-        (status = 400, description = "Missing school_id for system admin"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires terms:read permission")
+        (status = 400, description = "Missing school_id for system admin", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires terms:read permission", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Terms",
     security(("bearer_auth" = []))
@@ -127,10 +129,11 @@ pub async fn get_current_term(
         ("id" = Uuid, Path, description = "Term ID")
     ),
     responses(
-        (status = 200, description = "Term details", body = TermWithSessionInfo),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires terms:read permission"),
-        (status = 404, description = "Term not found")
+        WithEtag<TermWithSessionInfo>,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires terms:read permission", body = ErrorResponse),
+        (status = 404, description = "Term not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Terms",
     security(("bearer_auth" = []))
@@ -140,19 +143,19 @@ pub async fn get_term_by_id(
     State(state): State<AppState>,
     RequireTermsRead(auth_user): RequireTermsRead,
     Path(id): Path<Uuid>,
-) -> Result<Json<TermWithSessionInfo>, AppError> {
+) -> Result<WithEtag<TermWithSessionInfo>, AppError> {
     let term_id = TermId::from(id);
 
     if is_system_admin_jwt(&auth_user) {
         let term = TermService::get_term_by_id(&state.db, term_id).await?;
-        return Ok(Json(term));
+        return Ok(WithEtag(term));
     }
 
     let school_id = get_admin_school_id(&state.db, &auth_user).await?;
     let term =
         TermService::get_term_by_id_with_school_filter(&state.db, term_id, school_id).await?;
 
-    Ok(Json(term))
+    Ok(WithEtag(term))
 }
 
 /// Update a term
@@ -161,15 +164,18 @@ pub async fn get_term_by_id(
     path = "/api/terms/{id}",
     summary = "Update term",
     params(
-        ("id" = Uuid, Path, description = "Term ID")
+        ("id" = Uuid, Path, description = "Term ID"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the term as last read; the update is refused if it has changed since")
     ),
     request_body = UpdateTermDto,
     responses(
-        (status = 200, description = "Term updated successfully", body = Term),
-        (status = 400, description = "Invalid input or date validation failed"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires terms:update permission"),
-        (status = 404, description = "Term not found")
+        Conditional<Term>,
+        (status = 400, description = "Invalid input or date validation failed", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires terms:update permission", body = ErrorResponse),
+        (status = 404, description = "Term not found", body = ErrorResponse),
+        (status = 422, description = "Validation failed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Terms",
     security(("bearer_auth" = []))
@@ -179,21 +185,17 @@ pub async fn update_term(
     State(state): State<AppState>,
     RequireTermsUpdate(auth_user): RequireTermsUpdate,
     Path(id): Path<Uuid>,
-    Json(dto): Json<UpdateTermDto>,
-) -> Result<Json<Term>, AppError> {
-    dto.validate()?;
+    if_match: IfMatch,
+    ValidatedJson(dto): ValidatedJson<UpdateTermDto>,
+) -> Result<Conditional<Term>, AppError> {
     let term_id = TermId::from(id);
 
     if is_system_admin_jwt(&auth_user) {
-        let term = TermService::update_term(&state.db, term_id, dto).await?;
-        return Ok(Json(term));
+        return TermService::update_term(&state.db, term_id, &if_match, dto).await;
     }
 
     let school_id = get_admin_school_id(&state.db, &auth_user).await?;
-    let term =
-        TermService::update_term_with_school_filter(&state.db, term_id, school_id, dto).await?;
-
-    Ok(Json(term))
+    TermService::update_term_with_school_filter(&state.db, term_id, school_id, &if_match, dto).await
 }
 
 /// Delete a term
@@ -205,10 +207,11 @@ pub async fn update_term(
         ("id" = Uuid, Path, description = "Term ID")
     ),
     responses(
-        (status = 204, description = "Term deleted successfully"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires terms:delete permission"),
-        (status = 404, description = "Term not found")
+        NoContent,
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires terms:delete permission", body = ErrorResponse),
+        (status = 404, description = "Term not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Terms",
     security(("bearer_auth" = []))
@@ -218,18 +221,18 @@ pub async fn delete_term(
     State(state): State<AppState>,
     RequireTermsDelete(auth_user): RequireTermsDelete,
     Path(id): Path<Uuid>,
-) -> Result<StatusCode, AppError> {
+) -> Result<NoContent, AppError> {
     let term_id = TermId::from(id);
 
     if is_system_admin_jwt(&auth_user) {
         TermService::delete_term(&state.db, term_id).await?;
-        return Ok(StatusCode::NO_CONTENT);
+        return Ok(NoContent);
     }
 
     let school_id = get_admin_school_id(&state.db, &auth_user).await?;
     TermService::delete_term_with_school_filter(&state.db, term_id, school_id).await?;
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok(NoContent)
 }
 
 /// Set a term as the current term
@@ -242,10 +245,11 @@ pub async fn delete_term(
     ),
     responses(
         (status = 200, description = "Term set as current successfully", body = Term),
-        (status = 400, description = "Session is not active"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - requires terms:update permission"),
-        (status = 404, description = "Term not found")
+        (status = 400, description = "Session is not active", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires terms:update permission", body = ErrorResponse),
+        (status = 404, description = "Term not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "Terms",
     security(("bearer_auth" = []))
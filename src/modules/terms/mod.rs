@@ -2,6 +2,10 @@
 //!
 //! This module provides functionality for managing terms/semesters within academic sessions.
 //! Terms are flexible, school-defined periods nested within academic sessions.
+//!
+//! A background job moves each school's active session and current term to
+//! match their dates shortly after midnight in the school's timezone, queueing
+//! `term.started` and `term.ended` webhooks and notifying the school's admins.
 
 pub mod controller;
 pub mod model;
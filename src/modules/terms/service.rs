@@ -1,15 +1,70 @@
+use std::time::Duration;
+
 use chrono::NaiveDate;
-use sqlx::PgPool;
-use tracing::instrument;
+use sqlx::{FromRow, PgConnection, PgPool};
+use tracing::{info, instrument, warn};
 
-use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_core::{AppError, Conditional, ErrorCode, IfMatch, PaginationMeta};
 use chalkbyte_models::ids::{AcademicSessionId, SchoolId, TermId};
+use chalkbyte_observability::jobs::register_job;
 
 use crate::modules::academic_sessions::model::AcademicSession;
 use crate::modules::terms::model::{
     CreateTermDto, PaginatedTermsResponse, Term, TermFilterParams, TermWithSessionInfo,
     UpdateTermDto,
 };
+use crate::modules::users::model::{SchoolSettings, system_roles};
+use crate::modules::webhooks::model::WebhookEvent;
+use crate::modules::webhooks::service::WebhookService;
+
+/// How often the transition job looks for schools past their midnight.
+const TRANSITION_POLL_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Start the background job that moves each school's active session and
+/// current term to match the calendar shortly after midnight in the
+/// school's timezone.
+pub fn spawn_transition_job(db: PgPool) {
+    tokio::spawn(async move {
+        let job = register_job("term_transitions", TRANSITION_POLL_INTERVAL);
+        let mut interval = tokio::time::interval(TRANSITION_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            match job.run(TermService::apply_due_transitions(&db)).await {
+                Ok(0) => {}
+                Ok(changed) => info!(changed, "Started and ended terms"),
+                Err(e) => warn!(error = %e, "Failed to apply term transitions"),
+            }
+        }
+    });
+}
+
+/// A session or term as the transition job sees it.
+#[derive(FromRow)]
+struct CalendarPeriod {
+    id: uuid::Uuid,
+    name: String,
+    academic_session_id: AcademicSessionId,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    is_on: bool,
+}
+
+impl CalendarPeriod {
+    /// The period `today` falls in, the latest starting if several do;
+    /// failing that, the one already on unless it has ended.
+    fn scheduled<'a>(
+        periods: impl IntoIterator<Item = &'a Self>,
+        today: NaiveDate,
+    ) -> Option<&'a Self> {
+        let periods: Vec<&Self> = periods.into_iter().collect();
+        periods
+            .iter()
+            .filter(|p| p.start_date <= today && today <= p.end_date)
+            .max_by_key(|p| p.start_date)
+            .or_else(|| periods.iter().find(|p| p.is_on && today <= p.end_date))
+            .copied()
+    }
+}
 
 pub struct TermService;
 
@@ -93,7 +148,7 @@ impl TermService {
         .bind(session_id)
         .fetch_optional(db)
         .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Academic session not found")))?;
+        .ok_or_else(|| AppError::from_code(ErrorCode::AcademicSessionNotFound))?;
 
         // Validate dates
         Self::validate_term_dates(db, &session, dto.start_date, dto.end_date, None).await?;
@@ -164,9 +219,7 @@ impl TermService {
         .await?;
 
         if !session_exists {
-            return Err(AppError::not_found(anyhow::anyhow!(
-                "Academic session not found"
-            )));
+            return Err(AppError::from_code(ErrorCode::AcademicSessionNotFound));
         }
 
         let mut count_query =
@@ -239,7 +292,7 @@ impl TermService {
         .bind(term_id)
         .fetch_optional(db)
         .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Term not found")))?;
+        .ok_or_else(|| AppError::from_code(ErrorCode::TermNotFound))?;
 
         Ok(term)
     }
@@ -274,7 +327,7 @@ impl TermService {
         .bind(school_id)
         .fetch_optional(db)
         .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Term not found")))?;
+        .ok_or_else(|| AppError::from_code(ErrorCode::TermNotFound))?;
 
         Ok(term)
     }
@@ -311,13 +364,16 @@ impl TermService {
         Ok(term)
     }
 
-    /// Update a term.
+    /// Update a term. Leaves it unchanged when it no longer matches
+    /// `if_match`, including when it changes between the check and the
+    /// update.
     #[instrument(skip(db))]
     pub async fn update_term(
         db: &PgPool,
         term_id: TermId,
+        if_match: &IfMatch,
         dto: UpdateTermDto,
-    ) -> Result<Term, AppError> {
+    ) -> Result<Conditional<Term>, AppError> {
         // Get existing term with session info
         let existing = sqlx::query_as::<_, TermWithSessionInfo>(
             r#"SELECT
@@ -341,7 +397,12 @@ impl TermService {
         .bind(term_id)
         .fetch_optional(db)
         .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Term not found")))?;
+        .ok_or_else(|| AppError::from_code(ErrorCode::TermNotFound))?;
+
+        if !if_match.matches(&existing) {
+            return Ok(Conditional::PreconditionFailed(existing.into()));
+        }
+        let expected_version = (!if_match.is_any()).then_some(existing.updated_at);
 
         let name = dto.name.unwrap_or(existing.name);
         let description = if dto.description.is_some() {
@@ -368,7 +429,7 @@ impl TermService {
         let term = sqlx::query_as::<_, Term>(
             r#"UPDATE terms
                SET name = $1, description = $2, start_date = $3, end_date = $4, sequence = $5, updated_at = NOW()
-               WHERE id = $6
+               WHERE id = $6 AND ($7::timestamptz IS NULL OR updated_at = $7)
                RETURNING id, name, description, academic_session_id, start_date, end_date, sequence, is_current, created_at, updated_at"#,
         )
         .bind(&name)
@@ -377,7 +438,8 @@ impl TermService {
         .bind(end_date)
         .bind(sequence)
         .bind(term_id)
-        .fetch_one(db)
+        .bind(expected_version)
+        .fetch_optional(db)
         .await
         .map_err(|e| {
             if let sqlx::Error::Database(db_err) = &e
@@ -397,7 +459,14 @@ impl TermService {
             AppError::from(e)
         })?;
 
-        Ok(term)
+        match term {
+            Some(term) => Ok(Conditional::Updated(term)),
+            // Changed or deleted since it was read
+            None => {
+                let current = Self::get_term_by_id(db, term_id).await?;
+                Ok(Conditional::PreconditionFailed(current.into()))
+            }
+        }
     }
 
     /// Update a term with school filtering.
@@ -406,8 +475,9 @@ impl TermService {
         db: &PgPool,
         term_id: TermId,
         school_id: SchoolId,
+        if_match: &IfMatch,
         dto: UpdateTermDto,
-    ) -> Result<Term, AppError> {
+    ) -> Result<Conditional<Term>, AppError> {
         // Verify term belongs to school
         let term_exists = sqlx::query_scalar::<_, bool>(
             r#"SELECT EXISTS(
@@ -422,10 +492,10 @@ impl TermService {
         .await?;
 
         if !term_exists {
-            return Err(AppError::not_found(anyhow::anyhow!("Term not found")));
+            return Err(AppError::from_code(ErrorCode::TermNotFound));
         }
 
-        Self::update_term(db, term_id, dto).await
+        Self::update_term(db, term_id, if_match, dto).await
     }
 
     /// Delete a term.
@@ -437,7 +507,7 @@ impl TermService {
             .await?;
 
         if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!("Term not found")));
+            return Err(AppError::from_code(ErrorCode::TermNotFound));
         }
 
         Ok(())
@@ -461,7 +531,7 @@ impl TermService {
         .await?;
 
         if result.rows_affected() == 0 {
-            return Err(AppError::not_found(anyhow::anyhow!("Term not found")));
+            return Err(AppError::from_code(ErrorCode::TermNotFound));
         }
 
         Ok(())
@@ -496,7 +566,7 @@ impl TermService {
         .bind(term_id)
         .fetch_optional(db)
         .await?
-        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Term not found")))?;
+        .ok_or_else(|| AppError::from_code(ErrorCode::TermNotFound))?;
 
         // Verify the session is active
         if !term_info.session_is_active {
@@ -505,10 +575,12 @@ impl TermService {
             )));
         }
 
+        let mut tx = db.begin().await?;
+
         // Unset current term for this session
         sqlx::query("UPDATE terms SET is_current = FALSE, updated_at = NOW() WHERE academic_session_id = $1")
             .bind(term_info.academic_session_id)
-            .execute(db)
+            .execute(&mut *tx)
             .await?;
 
         // Set this term as current
@@ -519,12 +591,185 @@ impl TermService {
                RETURNING id, name, description, academic_session_id, start_date, end_date, sequence, is_current, created_at, updated_at"#,
         )
         .bind(term_id)
-        .fetch_one(db)
+        .fetch_one(&mut *tx)
         .await?;
 
+        if !term_info.is_current {
+            WebhookService::enqueue(
+                &mut *tx,
+                term_info.school_id,
+                WebhookEvent::TermStarted,
+                &serde_json::json!({
+                    "term_id": term.id,
+                    "name": term.name,
+                    "academic_session_id": term.academic_session_id,
+                    "start_date": term.start_date,
+                    "end_date": term.end_date,
+                }),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
         Ok(term)
     }
 
+    /// Apply the calendar to every school whose local date has moved on
+    /// since its last transition. Returns how many terms started or ended.
+    #[instrument(skip(db))]
+    pub async fn apply_due_transitions(db: &PgPool) -> Result<usize, AppError> {
+        let mut changed = 0;
+
+        loop {
+            let mut tx = db.begin().await?;
+
+            let Some(settings) = sqlx::query_as::<_, SchoolSettings>(
+                r#"SELECT id AS school_id, timezone, locale
+                   FROM schools
+                   WHERE term_transitions_date IS NULL
+                      OR term_transitions_date < (NOW() AT TIME ZONE timezone)::date
+                   LIMIT 1
+                   FOR UPDATE SKIP LOCKED"#,
+            )
+            .fetch_optional(&mut *tx)
+            .await?
+            else {
+                return Ok(changed);
+            };
+
+            changed +=
+                Self::transition_school(&mut tx, settings.school_id, settings.today()).await?;
+
+            // Recorded with the same clock the school was picked by, so it
+            // is not picked again today
+            sqlx::query(
+                "UPDATE schools SET term_transitions_date = (NOW() AT TIME ZONE timezone)::date WHERE id = $1",
+            )
+            .bind(settings.school_id)
+            .execute(&mut *tx)
+            .await?;
+
+            tx.commit().await?;
+        }
+    }
+
+    /// Activate the session and mark current the term `today` falls in,
+    /// queueing `term.started` and `term.ended` webhooks and notifying the
+    /// school's admins of each term that changes.
+    async fn transition_school(
+        conn: &mut PgConnection,
+        school_id: SchoolId,
+        today: NaiveDate,
+    ) -> Result<usize, AppError> {
+        let sessions = sqlx::query_as::<_, CalendarPeriod>(
+            r#"SELECT id, name, id AS academic_session_id, start_date, end_date, is_active AS is_on
+               FROM academic_sessions
+               WHERE school_id = $1"#,
+        )
+        .bind(school_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let active = sessions
+            .iter()
+            .find(|s| s.is_on)
+            .map(|s| s.academic_session_id);
+        let scheduled = CalendarPeriod::scheduled(&sessions, today).map(|s| s.academic_session_id);
+        if scheduled != active {
+            sqlx::query(
+                "UPDATE academic_sessions SET is_active = FALSE, updated_at = NOW() WHERE school_id = $1 AND is_active",
+            )
+            .bind(school_id)
+            .execute(&mut *conn)
+            .await?;
+            if let Some(session_id) = scheduled {
+                sqlx::query(
+                    "UPDATE academic_sessions SET is_active = TRUE, updated_at = NOW() WHERE id = $1",
+                )
+                .bind(session_id)
+                .execute(&mut *conn)
+                .await?;
+            }
+            info!(%school_id, from = ?active, to = ?scheduled, "Changed active academic session");
+        }
+
+        let terms = sqlx::query_as::<_, CalendarPeriod>(
+            r#"SELECT t.id, t.name, t.academic_session_id, t.start_date, t.end_date, t.is_current AS is_on
+               FROM terms t
+               JOIN academic_sessions s ON s.id = t.academic_session_id
+               WHERE s.school_id = $1"#,
+        )
+        .bind(school_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let current = CalendarPeriod::scheduled(
+            terms
+                .iter()
+                .filter(|t| Some(t.academic_session_id) == scheduled),
+            today,
+        )
+        .map(|t| t.id);
+
+        // Ended terms first, so the events arrive in calendar order
+        let mut changes: Vec<(&CalendarPeriod, bool)> = terms
+            .iter()
+            .filter(|t| t.is_on != (Some(t.id) == current))
+            .map(|t| (t, !t.is_on))
+            .collect();
+        changes.sort_by_key(|(_, started)| *started);
+
+        for &(term, started) in &changes {
+            sqlx::query("UPDATE terms SET is_current = $2, updated_at = NOW() WHERE id = $1")
+                .bind(term.id)
+                .bind(started)
+                .execute(&mut *conn)
+                .await?;
+
+            let (event, title) = if started {
+                (
+                    WebhookEvent::TermStarted,
+                    format!("{} has started", term.name),
+                )
+            } else {
+                (WebhookEvent::TermEnded, format!("{} has ended", term.name))
+            };
+            WebhookService::enqueue(
+                &mut *conn,
+                school_id,
+                event,
+                &serde_json::json!({
+                    "term_id": term.id,
+                    "name": term.name,
+                    "academic_session_id": term.academic_session_id,
+                    "start_date": term.start_date,
+                    "end_date": term.end_date,
+                }),
+            )
+            .await?;
+
+            sqlx::query(
+                r#"INSERT INTO notifications (user_id, title, body)
+                   SELECT u.id, $2, $3
+                   FROM users u
+                   JOIN user_roles ur ON ur.user_id = u.id
+                   WHERE u.school_id = $1 AND ur.role_id = $4"#,
+            )
+            .bind(school_id)
+            .bind(&title)
+            .bind(format!(
+                "{title} ({} to {}).",
+                term.start_date, term.end_date
+            ))
+            .bind(system_roles::ADMIN)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(changes.len())
+    }
+
     /// Set a term as the current term with school filtering.
     #[instrument(skip(db))]
     pub async fn set_current_term_with_school_filter(
@@ -546,7 +791,7 @@ impl TermService {
         .await?;
 
         if !term_exists {
-            return Err(AppError::not_found(anyhow::anyhow!("Term not found")));
+            return Err(AppError::from_code(ErrorCode::TermNotFound));
         }
 
         Self::set_current_term(db, term_id).await
@@ -556,6 +801,7 @@ impl TermService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::database::TenantContext;
     use axum::http::StatusCode;
     use chalkbyte_core::PaginationParams;
     use uuid::Uuid;
@@ -744,9 +990,13 @@ mod tests {
         let session_id = create_test_session(&pool, school_id).await;
 
         // Activate session first
-        AcademicSessionService::activate_academic_session(&pool, session_id, school_id)
-            .await
-            .unwrap();
+        AcademicSessionService::activate_academic_session(
+            &pool,
+            &TenantContext::school(school_id),
+            session_id,
+        )
+        .await
+        .unwrap();
 
         let term = TermService::create_term(
             &pool,
@@ -767,6 +1017,56 @@ mod tests {
         assert!(current.is_current);
     }
 
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_set_current_term_queues_webhook_once(pool: PgPool) {
+        let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
+        let session_id = create_test_session(&pool, school_id).await;
+        AcademicSessionService::activate_academic_session(
+            &pool,
+            &TenantContext::school(school_id),
+            session_id,
+        )
+        .await
+        .unwrap();
+
+        sqlx::query(
+            "INSERT INTO webhook_endpoints (school_id, url, secret, events)
+             VALUES ($1, 'https://example.com/hook', 'whsec_test', ARRAY['term.started'])",
+        )
+        .bind(school_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let term = TermService::create_term(
+            &pool,
+            session_id,
+            CreateTermDto {
+                name: "Fall Semester".to_string(),
+                description: None,
+                academic_session_id: None,
+                start_date: NaiveDate::from_ymd_opt(2025, 9, 1).unwrap(),
+                end_date: NaiveDate::from_ymd_opt(2025, 12, 20).unwrap(),
+                sequence: Some(1),
+            },
+        )
+        .await
+        .unwrap();
+
+        TermService::set_current_term(&pool, term.id).await.unwrap();
+        // Already current, so the term has not started again
+        TermService::set_current_term(&pool, term.id).await.unwrap();
+
+        let queued: Vec<serde_json::Value> = sqlx::query_scalar(
+            "SELECT payload FROM webhook_outbox WHERE event_type = 'term.started'",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0]["data"]["term_id"], term.id.to_string());
+    }
+
     #[sqlx::test(migrations = "./migrations")]
     async fn test_set_current_term_inactive_session(pool: PgPool) {
         let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
@@ -808,9 +1108,13 @@ mod tests {
         assert!(current.is_none());
 
         // Activate session and set current term
-        AcademicSessionService::activate_academic_session(&pool, session_id, school_id)
-            .await
-            .unwrap();
+        AcademicSessionService::activate_academic_session(
+            &pool,
+            &TenantContext::school(school_id),
+            session_id,
+        )
+        .await
+        .unwrap();
 
         let term = TermService::create_term(
             &pool,
@@ -886,4 +1190,89 @@ mod tests {
         assert_eq!(result.meta.total, 4);
         assert!(result.meta.has_more);
     }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_transitions_follow_the_calendar_once_a_day(pool: PgPool) {
+        let school_id = create_test_school(&pool, &format!("School {}", Uuid::new_v4())).await;
+        let today = chrono::Utc::now().date_naive();
+        let days = chrono::Days::new;
+
+        sqlx::query(
+            "INSERT INTO webhook_endpoints (school_id, url, secret, events)
+             VALUES ($1, 'https://example.com/hook', 'whsec_test', ARRAY['term.started', 'term.ended'])",
+        )
+        .bind(school_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let session_id = sqlx::query_scalar::<_, AcademicSessionId>(
+            "INSERT INTO academic_sessions (name, school_id, start_date, end_date)
+             VALUES ('2025/2026', $1, $2, $3) RETURNING id",
+        )
+        .bind(school_id)
+        .bind(today - days(60))
+        .bind(today + days(60))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let insert_term = async |name: &str, sequence: i32, start, end, is_current: bool| {
+            sqlx::query_scalar::<_, TermId>(
+                "INSERT INTO terms (name, academic_session_id, sequence, start_date, end_date, is_current)
+                 VALUES ($1, $2, $3, $4, $5, $6) RETURNING id",
+            )
+            .bind(name)
+            .bind(session_id)
+            .bind(sequence)
+            .bind(start)
+            .bind(end)
+            .bind(is_current)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+        };
+        let first = insert_term("First Term", 1, today - days(60), today - days(1), true).await;
+        let second = insert_term("Second Term", 2, today, today + days(60), false).await;
+
+        let changed = TermService::apply_due_transitions(&pool).await.unwrap();
+        assert_eq!(changed, 2);
+
+        let is_active: bool =
+            sqlx::query_scalar("SELECT is_active FROM academic_sessions WHERE id = $1")
+                .bind(session_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert!(is_active);
+        assert!(
+            !TermService::get_term_by_id(&pool, first)
+                .await
+                .unwrap()
+                .is_current
+        );
+        assert!(
+            TermService::get_term_by_id(&pool, second)
+                .await
+                .unwrap()
+                .is_current
+        );
+
+        let events: Vec<String> =
+            sqlx::query_scalar("SELECT event_type FROM webhook_outbox ORDER BY event_type")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(events, ["term.ended", "term.started"]);
+
+        // A change made later in the day is left alone until the next day
+        TermService::set_current_term(&pool, first).await.unwrap();
+        assert_eq!(TermService::apply_due_transitions(&pool).await.unwrap(), 0);
+        assert!(
+            TermService::get_term_by_id(&pool, first)
+                .await
+                .unwrap()
+                .is_current
+        );
+    }
 }
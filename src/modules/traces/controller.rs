@@ -0,0 +1,40 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use tracing::instrument;
+
+use chalkbyte_core::AppError;
+
+use crate::modules::traces::model::RequestTrace;
+use crate::modules::traces::service::TraceService;
+use crate::state::AppState;
+
+/// Trace a request
+///
+/// Looks up a request ID quoted from an error response or email and returns
+/// the request's log summary along with the audit entries for every record it
+/// changed. System admins only.
+#[utoipa::path(
+    get,
+    path = "/api/admin/trace/{id}",
+    summary = "Trace request",
+    params(("id" = String, Path, description = "Request ID")),
+    responses(
+        (status = 200, description = "Log summary and audit entries", body = RequestTrace),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - system admin only"),
+        (status = 404, description = "Nothing recorded for the request ID")
+    ),
+    tag = "Traces",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_request_trace(
+    State(state): State<AppState>,
+    Path(request_id): Path<String>,
+) -> Result<Json<RequestTrace>, AppError> {
+    let trace = TraceService::get_trace(&state.db, &request_id).await?;
+
+    Ok(Json(trace))
+}
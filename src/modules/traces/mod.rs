@@ -0,0 +1,16 @@
+//! Request trace module.
+//!
+//! Every request gets an ID, taken from a valid `X-Request-Id` header or
+//! generated, which is echoed in the response header, in `AppError` bodies,
+//! and in outgoing emails. Write requests and failed requests leave a log
+//! summary in `request_logs`, and the change event trigger stamps each event
+//! with the request that caused it.
+//!
+//! `GET /api/admin/trace/{id}` lets system admins look up both when a user
+//! quotes a request ID to support. Log summaries are kept for
+//! [`service::REQUEST_LOG_RETENTION_DAYS`] days.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
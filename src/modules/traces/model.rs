@@ -0,0 +1,7 @@
+//! Request trace data models.
+//!
+//! This module re-exports request trace models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all request trace models from the shared crate
+pub use chalkbyte_models::traces::*;
@@ -0,0 +1,11 @@
+use axum::{Router, routing::get};
+
+use crate::state::AppState;
+
+use super::controller::get_request_trace;
+
+/// Initialize the request trace router
+/// Routes: GET /{id}
+pub fn init_traces_router() -> Router<AppState> {
+    Router::new().route("/{id}", get(get_request_trace))
+}
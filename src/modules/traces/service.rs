@@ -0,0 +1,145 @@
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::AppError;
+
+use crate::modules::changes::model::ChangeEvent;
+use crate::modules::traces::model::{NewRequestLog, RequestLog, RequestTrace};
+
+/// Days request log summaries are kept.
+pub const REQUEST_LOG_RETENTION_DAYS: i32 = 30;
+
+pub struct TraceService;
+
+impl TraceService {
+    /// Records the log summary of a handled request.
+    #[instrument(skip(db, log), fields(request_id = %log.request_id))]
+    pub async fn record_request(db: &PgPool, log: NewRequestLog) -> Result<(), AppError> {
+        sqlx::query(
+            r#"INSERT INTO request_logs
+                   (request_id, method, path, status, latency_ms, user_id, login_user_id,
+                    school_id, error)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+        )
+        .bind(&log.request_id)
+        .bind(&log.method)
+        .bind(&log.path)
+        .bind(log.status as i16)
+        .bind(log.latency_ms)
+        .bind(log.user_id)
+        .bind(log.login_user_id)
+        .bind(log.school_id)
+        .bind(&log.error)
+        .execute(db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Gathers the log summaries and change events recorded for a request ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns not found if nothing was recorded for the ID.
+    #[instrument(skip(db))]
+    pub async fn get_trace(db: &PgPool, request_id: &str) -> Result<RequestTrace, AppError> {
+        let requests = sqlx::query_as::<_, RequestLog>(
+            r#"SELECT method, path, status, latency_ms, user_id, login_user_id, school_id, error,
+                      created_at
+               FROM request_logs
+               WHERE request_id = $1
+               ORDER BY created_at"#,
+        )
+        .bind(request_id)
+        .fetch_all(db)
+        .await?;
+
+        let changes = sqlx::query_as::<_, ChangeEvent>(
+            r#"SELECT entity_type, entity_id, operation, version, school_id, changed_at
+               FROM change_events
+               WHERE request_id = $1
+               ORDER BY tx_id, id"#,
+        )
+        .bind(request_id)
+        .fetch_all(db)
+        .await?;
+
+        if requests.is_empty() && changes.is_empty() {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Nothing recorded for this request ID"
+            )));
+        }
+
+        Ok(RequestTrace {
+            request_id: request_id.to_string(),
+            requests,
+            changes,
+        })
+    }
+
+    /// Deletes log summaries older than [`REQUEST_LOG_RETENTION_DAYS`].
+    ///
+    /// Returns the number of summaries deleted.
+    #[instrument(skip(db))]
+    pub async fn purge_request_logs(db: &PgPool) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "DELETE FROM request_logs WHERE created_at < NOW() - make_interval(days => $1)",
+        )
+        .bind(REQUEST_LOG_RETENTION_DAYS)
+        .execute(db)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    fn log(request_id: &str, status: u16) -> NewRequestLog {
+        NewRequestLog {
+            request_id: request_id.to_string(),
+            method: "POST".to_string(),
+            path: "/api/levels".to_string(),
+            status,
+            latency_ms: 12,
+            user_id: None,
+            login_user_id: None,
+            school_id: None,
+            error: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_trace_collects_requests_and_changes(pool: PgPool) {
+        TraceService::record_request(&pool, log("req-1", 201))
+            .await
+            .unwrap();
+        TraceService::record_request(&pool, log("req-2", 201))
+            .await
+            .unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        sqlx::query("SELECT set_config('chalkbyte.request_id', 'req-1', true)")
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO schools (name) VALUES ('Traced School')")
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let trace = TraceService::get_trace(&pool, "req-1").await.unwrap();
+        assert_eq!(trace.requests.len(), 1);
+        assert_eq!(trace.requests[0].status, 201);
+        assert_eq!(trace.changes.len(), 1);
+
+        let err = TraceService::get_trace(&pool, "req-unknown")
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,642 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::{RouteAssignmentId, RouteStopId, TransportRouteId, VehicleId};
+
+use crate::middleware::auth::{
+    RequireTransportAssign, RequireTransportCreate, RequireTransportDelete,
+    RequireTransportManifest, RequireTransportRead, RequireTransportUpdate,
+};
+use crate::modules::transport::model::{
+    AssignRouteDto, CreateRouteStopDto, CreateTransportRouteDto, CreateVehicleDto,
+    GenerateTransportFeesResponse, PaginatedRouteAssignmentsResponse,
+    PaginatedTransportRoutesResponse, RouteAssignment, RouteAssignmentFilterParams, RouteManifest,
+    RouteStop, TransportFeeCharge, TransportFeeFilterParams, TransportRoute,
+    TransportRouteFilterParams, TransportTermParams, UpdateTransportRouteDto, UpdateVehicleDto,
+    Vehicle, VehicleQueryParams,
+};
+use crate::modules::transport::service::TransportService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Register a vehicle
+#[utoipa::path(
+    post,
+    path = "/api/transport/vehicles",
+    summary = "Create vehicle",
+    request_body = CreateVehicleDto,
+    responses(
+        Created<Vehicle>,
+        (status = 400, description = "Invalid input, duplicate registration, or driver outside the school"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:create permission")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_vehicle(
+    State(state): State<AppState>,
+    RequireTransportCreate(auth_user): RequireTransportCreate,
+    ValidatedJson(dto): ValidatedJson<CreateVehicleDto>,
+) -> Result<Created<Vehicle>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let vehicle = TransportService::create_vehicle(&state.db, school_id, dto).await?;
+
+    Ok(Created(vehicle))
+}
+
+/// List vehicles for a school
+#[utoipa::path(
+    get,
+    path = "/api/transport/vehicles",
+    summary = "List vehicles",
+    params(VehicleQueryParams),
+    responses(
+        (status = 200, description = "List of vehicles", body = Vec<Vehicle>),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:read permission")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_vehicles(
+    State(state): State<AppState>,
+    RequireTransportRead(auth_user): RequireTransportRead,
+    Query(params): Query<VehicleQueryParams>,
+) -> Result<Json<Vec<Vehicle>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let vehicles = TransportService::get_vehicles(&state.db, school_id).await?;
+
+    Ok(Json(vehicles))
+}
+
+/// Update a vehicle
+#[utoipa::path(
+    put,
+    path = "/api/transport/vehicles/{id}",
+    summary = "Update vehicle",
+    params(
+        ("id" = Uuid, Path, description = "Vehicle ID")
+    ),
+    request_body = UpdateVehicleDto,
+    responses(
+        (status = 200, description = "Vehicle updated", body = Vehicle),
+        (status = 400, description = "Invalid input, duplicate registration, or driver outside the school"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:update permission"),
+        (status = 404, description = "Vehicle not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_vehicle(
+    State(state): State<AppState>,
+    RequireTransportUpdate(auth_user): RequireTransportUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateVehicleDto>,
+) -> Result<Json<Vehicle>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let vehicle =
+        TransportService::update_vehicle(&state.db, VehicleId::from(id), school_id, dto).await?;
+
+    Ok(Json(vehicle))
+}
+
+/// Delete a vehicle
+#[utoipa::path(
+    delete,
+    path = "/api/transport/vehicles/{id}",
+    summary = "Delete vehicle",
+    params(
+        ("id" = Uuid, Path, description = "Vehicle ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:delete permission"),
+        (status = 404, description = "Vehicle not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_vehicle(
+    State(state): State<AppState>,
+    RequireTransportDelete(auth_user): RequireTransportDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    TransportService::delete_vehicle(&state.db, VehicleId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Create a route
+#[utoipa::path(
+    post,
+    path = "/api/transport/routes",
+    summary = "Create route",
+    request_body = CreateTransportRouteDto,
+    responses(
+        Created<TransportRoute>,
+        (status = 400, description = "Invalid input, duplicate name, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:create permission"),
+        (status = 404, description = "Vehicle not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_route(
+    State(state): State<AppState>,
+    RequireTransportCreate(auth_user): RequireTransportCreate,
+    ValidatedJson(dto): ValidatedJson<CreateTransportRouteDto>,
+) -> Result<Created<TransportRoute>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let route = TransportService::create_route(&state.db, school_id, dto).await?;
+
+    Ok(Created(route))
+}
+
+/// List routes for a school
+#[utoipa::path(
+    get,
+    path = "/api/transport/routes",
+    summary = "List routes",
+    params(TransportRouteFilterParams),
+    responses(
+        (status = 200, description = "List of routes", body = PaginatedTransportRoutesResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:read permission")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_routes(
+    State(state): State<AppState>,
+    RequireTransportRead(auth_user): RequireTransportRead,
+    Query(filters): Query<TransportRouteFilterParams>,
+) -> Result<Json<PaginatedTransportRoutesResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let routes = TransportService::get_routes(&state.db, school_id, filters).await?;
+
+    Ok(Json(routes))
+}
+
+/// Get a route by ID
+#[utoipa::path(
+    get,
+    path = "/api/transport/routes/{id}",
+    summary = "Get route",
+    params(
+        ("id" = Uuid, Path, description = "Route ID")
+    ),
+    responses(
+        (status = 200, description = "Route details", body = TransportRoute),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:read permission"),
+        (status = 404, description = "Route not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_route_by_id(
+    State(state): State<AppState>,
+    RequireTransportRead(auth_user): RequireTransportRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TransportRoute>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let route =
+        TransportService::get_route_by_id(&state.db, TransportRouteId::from(id), school_id).await?;
+
+    Ok(Json(route))
+}
+
+/// Update a route
+#[utoipa::path(
+    put,
+    path = "/api/transport/routes/{id}",
+    summary = "Update route",
+    params(
+        ("id" = Uuid, Path, description = "Route ID")
+    ),
+    request_body = UpdateTransportRouteDto,
+    responses(
+        (status = 200, description = "Route updated", body = TransportRoute),
+        (status = 400, description = "Invalid input or duplicate name"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:update permission"),
+        (status = 404, description = "Route or vehicle not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn update_route(
+    State(state): State<AppState>,
+    RequireTransportUpdate(auth_user): RequireTransportUpdate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<UpdateTransportRouteDto>,
+) -> Result<Json<TransportRoute>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let route =
+        TransportService::update_route(&state.db, TransportRouteId::from(id), school_id, dto)
+            .await?;
+
+    Ok(Json(route))
+}
+
+/// Delete a route
+#[utoipa::path(
+    delete,
+    path = "/api/transport/routes/{id}",
+    summary = "Delete route",
+    params(
+        ("id" = Uuid, Path, description = "Route ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:delete permission"),
+        (status = 404, description = "Route not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_route(
+    State(state): State<AppState>,
+    RequireTransportDelete(auth_user): RequireTransportDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    TransportService::delete_route(&state.db, TransportRouteId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Add a stop to a route
+#[utoipa::path(
+    post,
+    path = "/api/transport/routes/{id}/stops",
+    summary = "Create route stop",
+    params(
+        ("id" = Uuid, Path, description = "Route ID")
+    ),
+    request_body = CreateRouteStopDto,
+    responses(
+        Created<RouteStop>,
+        (status = 400, description = "Invalid input or duplicate sequence"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:create permission"),
+        (status = 404, description = "Route not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_stop(
+    State(state): State<AppState>,
+    RequireTransportCreate(auth_user): RequireTransportCreate,
+    Path(id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<CreateRouteStopDto>,
+) -> Result<Created<RouteStop>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let stop = TransportService::create_stop(&state.db, TransportRouteId::from(id), school_id, dto)
+        .await?;
+
+    Ok(Created(stop))
+}
+
+/// List a route's stops
+#[utoipa::path(
+    get,
+    path = "/api/transport/routes/{id}/stops",
+    summary = "List route stops",
+    params(
+        ("id" = Uuid, Path, description = "Route ID")
+    ),
+    responses(
+        (status = 200, description = "Stops in route order", body = Vec<RouteStop>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:read permission"),
+        (status = 404, description = "Route not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_stops(
+    State(state): State<AppState>,
+    RequireTransportRead(auth_user): RequireTransportRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<RouteStop>>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let stops =
+        TransportService::get_stops(&state.db, TransportRouteId::from(id), school_id).await?;
+
+    Ok(Json(stops))
+}
+
+/// Delete a route stop
+#[utoipa::path(
+    delete,
+    path = "/api/transport/stops/{id}",
+    summary = "Delete route stop",
+    params(
+        ("id" = Uuid, Path, description = "Stop ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:delete permission"),
+        (status = 404, description = "Stop not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_stop(
+    State(state): State<AppState>,
+    RequireTransportDelete(auth_user): RequireTransportDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    TransportService::delete_stop(&state.db, RouteStopId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Get the manifest for a route
+#[utoipa::path(
+    get,
+    path = "/api/transport/routes/{id}/manifest",
+    summary = "Route manifest",
+    params(
+        ("id" = Uuid, Path, description = "Route ID"),
+        TransportTermParams
+    ),
+    responses(
+        (status = 200, description = "Students grouped by stop for the term", body = RouteManifest),
+        (status = 400, description = "No current term"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:read permission"),
+        (status = 404, description = "Route not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_route_manifest(
+    State(state): State<AppState>,
+    RequireTransportRead(auth_user): RequireTransportRead,
+    Path(id): Path<Uuid>,
+    Query(params): Query<TransportTermParams>,
+) -> Result<Json<RouteManifest>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let manifest = TransportService::get_route_manifest(
+        &state.db,
+        TransportRouteId::from(id),
+        school_id,
+        params.term_id,
+    )
+    .await?;
+
+    Ok(Json(manifest))
+}
+
+/// Generate transport fee charges for a route
+#[utoipa::path(
+    post,
+    path = "/api/transport/routes/{id}/fees/generate",
+    summary = "Generate transport fees",
+    params(
+        ("id" = Uuid, Path, description = "Route ID"),
+        TransportTermParams
+    ),
+    responses(
+        (status = 200, description = "Charges generated for assigned students", body = GenerateTransportFeesResponse),
+        (status = 400, description = "Route has no fee or no current term"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:update permission"),
+        (status = 404, description = "Route not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn generate_fees(
+    State(state): State<AppState>,
+    RequireTransportUpdate(auth_user): RequireTransportUpdate,
+    Path(id): Path<Uuid>,
+    Query(params): Query<TransportTermParams>,
+) -> Result<Json<GenerateTransportFeesResponse>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let result = TransportService::generate_fees(
+        &state.db,
+        TransportRouteId::from(id),
+        school_id,
+        params.term_id,
+    )
+    .await?;
+
+    Ok(Json(result))
+}
+
+/// Assign a student to a route
+#[utoipa::path(
+    post,
+    path = "/api/transport/assignments",
+    summary = "Assign route",
+    request_body = AssignRouteDto,
+    responses(
+        Created<RouteAssignment>,
+        (status = 400, description = "Vehicle full, not a student, stop not on route, or already assigned this term"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:assign permission"),
+        (status = 404, description = "Route not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn assign_route(
+    State(state): State<AppState>,
+    RequireTransportAssign(auth_user): RequireTransportAssign,
+    ValidatedJson(dto): ValidatedJson<AssignRouteDto>,
+) -> Result<Created<RouteAssignment>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let assigned_by = auth_user.user_id()?;
+    let assignment = TransportService::assign_route(&state.db, school_id, assigned_by, dto).await?;
+
+    Ok(Created(assignment))
+}
+
+/// List route assignments for a school
+#[utoipa::path(
+    get,
+    path = "/api/transport/assignments",
+    summary = "List route assignments",
+    params(RouteAssignmentFilterParams),
+    responses(
+        (status = 200, description = "List of assignments", body = PaginatedRouteAssignmentsResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:read permission")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_assignments(
+    State(state): State<AppState>,
+    RequireTransportRead(auth_user): RequireTransportRead,
+    Query(filters): Query<RouteAssignmentFilterParams>,
+) -> Result<Json<PaginatedRouteAssignmentsResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let assignments = TransportService::get_assignments(&state.db, school_id, filters).await?;
+
+    Ok(Json(assignments))
+}
+
+/// Remove a route assignment
+#[utoipa::path(
+    delete,
+    path = "/api/transport/assignments/{id}",
+    summary = "Remove route assignment",
+    params(
+        ("id" = Uuid, Path, description = "Assignment ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:assign permission"),
+        (status = 404, description = "Assignment not found")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_assignment(
+    State(state): State<AppState>,
+    RequireTransportAssign(auth_user): RequireTransportAssign,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    TransportService::delete_assignment(&state.db, RouteAssignmentId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// List transport fee charges for a school
+#[utoipa::path(
+    get,
+    path = "/api/transport/fees",
+    summary = "List transport fee charges",
+    params(TransportFeeFilterParams),
+    responses(
+        (status = 200, description = "List of fee charges", body = Vec<TransportFeeCharge>),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:read permission")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_fee_charges(
+    State(state): State<AppState>,
+    RequireTransportRead(auth_user): RequireTransportRead,
+    Query(filters): Query<TransportFeeFilterParams>,
+) -> Result<Json<Vec<TransportFeeCharge>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let charges = TransportService::get_fee_charges(&state.db, school_id, filters).await?;
+
+    Ok(Json(charges))
+}
+
+/// List the routes the current user drives
+#[utoipa::path(
+    get,
+    path = "/api/transport/me/routes",
+    summary = "My routes",
+    responses(
+        (status = 200, description = "Routes served by the caller's vehicles", body = Vec<TransportRoute>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:manifest permission")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_my_routes(
+    State(state): State<AppState>,
+    RequireTransportManifest(auth_user): RequireTransportManifest,
+) -> Result<Json<Vec<TransportRoute>>, AppError> {
+    let driver_id = auth_user.user_id()?;
+    let routes = TransportService::get_driver_routes(&state.db, driver_id).await?;
+
+    Ok(Json(routes))
+}
+
+/// Get the manifest for a route the current user drives
+#[utoipa::path(
+    get,
+    path = "/api/transport/me/routes/{id}/manifest",
+    summary = "My route manifest",
+    params(
+        ("id" = Uuid, Path, description = "Route ID"),
+        TransportTermParams
+    ),
+    responses(
+        (status = 200, description = "Students grouped by stop for the term", body = RouteManifest),
+        (status = 400, description = "No current term"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires transport:manifest permission"),
+        (status = 404, description = "Route not found or not driven by the caller")
+    ),
+    tag = "Transport",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_my_manifest(
+    State(state): State<AppState>,
+    RequireTransportManifest(auth_user): RequireTransportManifest,
+    Path(id): Path<Uuid>,
+    Query(params): Query<TransportTermParams>,
+) -> Result<Json<RouteManifest>, AppError> {
+    let driver_id = auth_user.user_id()?;
+    let manifest = TransportService::get_driver_manifest(
+        &state.db,
+        TransportRouteId::from(id),
+        driver_id,
+        params.term_id,
+    )
+    .await?;
+
+    Ok(Json(manifest))
+}
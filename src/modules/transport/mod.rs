@@ -0,0 +1,10 @@
+//! Transport module.
+//!
+//! This module manages school transport: vehicles and their drivers, bus
+//! routes with ordered stops, per-term student route assignments, driver
+//! manifests, and optional transport fee charges.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
@@ -0,0 +1,7 @@
+//! Transport data models and DTOs.
+//!
+//! This module re-exports transport models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all transport models from the shared crate
+pub use chalkbyte_models::transport::*;
@@ -0,0 +1,40 @@
+use axum::{
+    Router,
+    routing::{delete, get, post, put},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    assign_route, create_route, create_stop, create_vehicle, delete_assignment, delete_route,
+    delete_stop, delete_vehicle, generate_fees, get_assignments, get_fee_charges, get_my_manifest,
+    get_my_routes, get_route_by_id, get_route_manifest, get_routes, get_stops, get_vehicles,
+    update_route, update_vehicle,
+};
+
+/// Initialize the transport router
+/// Routes: POST /vehicles, GET /vehicles, PUT /vehicles/{id}, DELETE /vehicles/{id},
+/// POST /routes, GET /routes, GET /routes/{id}, PUT /routes/{id}, DELETE /routes/{id},
+/// POST /routes/{id}/stops, GET /routes/{id}/stops, DELETE /stops/{id},
+/// GET /routes/{id}/manifest, POST /routes/{id}/fees/generate, POST /assignments,
+/// GET /assignments, DELETE /assignments/{id}, GET /fees, GET /me/routes,
+/// GET /me/routes/{id}/manifest
+pub fn init_transport_router() -> Router<AppState> {
+    Router::new()
+        .route("/vehicles", post(create_vehicle).get(get_vehicles))
+        .route("/vehicles/{id}", put(update_vehicle).delete(delete_vehicle))
+        .route("/routes", post(create_route).get(get_routes))
+        .route(
+            "/routes/{id}",
+            get(get_route_by_id).put(update_route).delete(delete_route),
+        )
+        .route("/routes/{id}/stops", post(create_stop).get(get_stops))
+        .route("/stops/{id}", delete(delete_stop))
+        .route("/routes/{id}/manifest", get(get_route_manifest))
+        .route("/routes/{id}/fees/generate", post(generate_fees))
+        .route("/assignments", post(assign_route).get(get_assignments))
+        .route("/assignments/{id}", delete(delete_assignment))
+        .route("/fees", get(get_fee_charges))
+        .route("/me/routes", get(get_my_routes))
+        .route("/me/routes/{id}/manifest", get(get_my_manifest))
+}
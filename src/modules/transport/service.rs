@@ -0,0 +1,1146 @@
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_models::ids::{
+    RouteAssignmentId, RouteStopId, SchoolId, TermId, TransportRouteId, UserId, VehicleId,
+};
+
+use crate::modules::transport::model::{
+    AssignRouteDto, CreateRouteStopDto, CreateTransportRouteDto, CreateVehicleDto,
+    GenerateTransportFeesResponse, ManifestStop, ManifestStudent,
+    PaginatedRouteAssignmentsResponse, PaginatedTransportRoutesResponse, RouteAssignment,
+    RouteAssignmentFilterParams, RouteAssignmentWithDetails, RouteManifest, RouteStop,
+    TransportFeeCharge, TransportFeeFilterParams, TransportRoute, TransportRouteFilterParams,
+    UpdateTransportRouteDto, UpdateVehicleDto, Vehicle,
+};
+use crate::modules::users::model::system_roles;
+
+const VEHICLE_COLUMNS: &str =
+    "id, school_id, registration_number, description, capacity, driver_id, created_at, updated_at";
+
+const ROUTE_COLUMNS: &str =
+    "id, school_id, name, description, vehicle_id, fee_amount, created_at, updated_at";
+
+const STOP_COLUMNS: &str = "id, route_id, name, sequence, pickup_time, dropoff_time, created_at";
+
+fn unique_violation_as(e: sqlx::Error, message: &'static str) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e
+        && db_err.is_unique_violation()
+    {
+        return AppError::bad_request(anyhow::anyhow!(message));
+    }
+    AppError::from(e)
+}
+
+/// Row used to build manifests; `stop_id` is `None` for students without a stop.
+#[derive(sqlx::FromRow)]
+struct ManifestRow {
+    stop_id: Option<RouteStopId>,
+    student_id: UserId,
+    first_name: String,
+    last_name: String,
+}
+
+pub struct TransportService;
+
+impl TransportService {
+    /// Fetch a vehicle, optionally restricted to a school.
+    async fn find_vehicle(
+        db: &PgPool,
+        vehicle_id: VehicleId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Vehicle, AppError> {
+        sqlx::query_as::<_, Vehicle>(&format!(
+            "SELECT {VEHICLE_COLUMNS} FROM transport_vehicles
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(vehicle_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Vehicle not found")))
+    }
+
+    /// Fetch a route, optionally restricted to a school.
+    async fn find_route(
+        db: &PgPool,
+        route_id: TransportRouteId,
+        school_id: Option<SchoolId>,
+    ) -> Result<TransportRoute, AppError> {
+        sqlx::query_as::<_, TransportRoute>(&format!(
+            "SELECT {ROUTE_COLUMNS} FROM transport_routes
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(route_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Route not found")))
+    }
+
+    /// Resolve the term to use, falling back to the school's current term.
+    async fn resolve_term(
+        db: &PgPool,
+        school_id: SchoolId,
+        term_id: Option<TermId>,
+    ) -> Result<TermId, AppError> {
+        match term_id {
+            Some(term_id) => {
+                let belongs = sqlx::query_scalar::<_, bool>(
+                    r#"SELECT EXISTS(
+                        SELECT 1 FROM terms t
+                        JOIN academic_sessions s ON s.id = t.academic_session_id
+                        WHERE t.id = $1 AND s.school_id = $2
+                    )"#,
+                )
+                .bind(term_id)
+                .bind(school_id)
+                .fetch_one(db)
+                .await?;
+
+                if !belongs {
+                    return Err(AppError::bad_request(anyhow::anyhow!(
+                        "Term not found in this school"
+                    )));
+                }
+                Ok(term_id)
+            }
+            None => sqlx::query_scalar::<_, TermId>(
+                r#"SELECT t.id FROM terms t
+                   JOIN academic_sessions s ON s.id = t.academic_session_id
+                   WHERE s.school_id = $1 AND t.is_current = true"#,
+            )
+            .bind(school_id)
+            .fetch_optional(db)
+            .await?
+            .ok_or_else(|| {
+                AppError::bad_request(anyhow::anyhow!("No current term; specify term_id"))
+            }),
+        }
+    }
+
+    /// Ensure a driver is a member of the vehicle's school.
+    async fn validate_driver(
+        db: &PgPool,
+        school_id: SchoolId,
+        driver_id: UserId,
+    ) -> Result<(), AppError> {
+        let in_school = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND school_id = $2)",
+        )
+        .bind(driver_id)
+        .bind(school_id)
+        .fetch_one(db)
+        .await?;
+
+        if !in_school {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Driver must be a staff member of the vehicle's school"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Register a vehicle for a school.
+    #[instrument(skip(db))]
+    pub async fn create_vehicle(
+        db: &PgPool,
+        school_id: SchoolId,
+        dto: CreateVehicleDto,
+    ) -> Result<Vehicle, AppError> {
+        if let Some(driver_id) = dto.driver_id {
+            Self::validate_driver(db, school_id, driver_id).await?;
+        }
+
+        let vehicle = sqlx::query_as::<_, Vehicle>(&format!(
+            "INSERT INTO transport_vehicles (school_id, registration_number, description, capacity, driver_id)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {VEHICLE_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.registration_number)
+        .bind(&dto.description)
+        .bind(dto.capacity)
+        .bind(dto.driver_id)
+        .fetch_one(db)
+        .await
+        .map_err(|e| {
+            unique_violation_as(e, "A vehicle with this registration number already exists")
+        })?;
+
+        Ok(vehicle)
+    }
+
+    /// List a school's vehicles.
+    #[instrument(skip(db))]
+    pub async fn get_vehicles(db: &PgPool, school_id: SchoolId) -> Result<Vec<Vehicle>, AppError> {
+        let vehicles = sqlx::query_as::<_, Vehicle>(&format!(
+            "SELECT {VEHICLE_COLUMNS} FROM transport_vehicles
+             WHERE school_id = $1
+             ORDER BY registration_number ASC"
+        ))
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(vehicles)
+    }
+
+    /// Update a vehicle.
+    #[instrument(skip(db))]
+    pub async fn update_vehicle(
+        db: &PgPool,
+        vehicle_id: VehicleId,
+        school_id: Option<SchoolId>,
+        dto: UpdateVehicleDto,
+    ) -> Result<Vehicle, AppError> {
+        let existing = Self::find_vehicle(db, vehicle_id, school_id).await?;
+
+        if let Some(driver_id) = dto.driver_id {
+            Self::validate_driver(db, existing.school_id, driver_id).await?;
+        }
+
+        let vehicle = sqlx::query_as::<_, Vehicle>(&format!(
+            "UPDATE transport_vehicles
+             SET registration_number = COALESCE($2, registration_number),
+                 description = COALESCE($3, description),
+                 capacity = COALESCE($4, capacity),
+                 driver_id = COALESCE($5, driver_id)
+             WHERE id = $1
+             RETURNING {VEHICLE_COLUMNS}"
+        ))
+        .bind(vehicle_id)
+        .bind(&dto.registration_number)
+        .bind(&dto.description)
+        .bind(dto.capacity)
+        .bind(dto.driver_id)
+        .fetch_one(db)
+        .await
+        .map_err(|e| {
+            unique_violation_as(e, "A vehicle with this registration number already exists")
+        })?;
+
+        Ok(vehicle)
+    }
+
+    /// Delete a vehicle. Routes it served are left without a vehicle.
+    #[instrument(skip(db))]
+    pub async fn delete_vehicle(
+        db: &PgPool,
+        vehicle_id: VehicleId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "DELETE FROM transport_vehicles WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(vehicle_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!("Vehicle not found")));
+        }
+
+        Ok(())
+    }
+
+    /// Create a route for a school.
+    #[instrument(skip(db))]
+    pub async fn create_route(
+        db: &PgPool,
+        school_id: SchoolId,
+        dto: CreateTransportRouteDto,
+    ) -> Result<TransportRoute, AppError> {
+        if let Some(vehicle_id) = dto.vehicle_id {
+            Self::find_vehicle(db, vehicle_id, Some(school_id)).await?;
+        }
+
+        let route = sqlx::query_as::<_, TransportRoute>(&format!(
+            "INSERT INTO transport_routes (school_id, name, description, vehicle_id, fee_amount)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {ROUTE_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.name)
+        .bind(&dto.description)
+        .bind(dto.vehicle_id)
+        .bind(dto.fee_amount)
+        .fetch_one(db)
+        .await
+        .map_err(|e| unique_violation_as(e, "A route with this name already exists"))?;
+
+        Ok(route)
+    }
+
+    /// Get paginated routes for a school.
+    #[instrument(skip(db))]
+    pub async fn get_routes(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: TransportRouteFilterParams,
+    ) -> Result<PaginatedTransportRoutesResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+
+        let total = sqlx::query_scalar::<_, i64>(
+            r#"SELECT COUNT(*) FROM transport_routes
+               WHERE school_id = $1 AND ($2::uuid IS NULL OR vehicle_id = $2)"#,
+        )
+        .bind(school_id)
+        .bind(filters.vehicle_id)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, TransportRoute>(&format!(
+            "SELECT {ROUTE_COLUMNS} FROM transport_routes
+             WHERE school_id = $1 AND ($2::uuid IS NULL OR vehicle_id = $2)
+             ORDER BY name ASC
+             LIMIT $3 OFFSET $4"
+        ))
+        .bind(school_id)
+        .bind(filters.vehicle_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedTransportRoutesResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Get a route by ID.
+    ///
+    /// When `school_id` is provided, the route must belong to that school.
+    #[instrument(skip(db))]
+    pub async fn get_route_by_id(
+        db: &PgPool,
+        route_id: TransportRouteId,
+        school_id: Option<SchoolId>,
+    ) -> Result<TransportRoute, AppError> {
+        Self::find_route(db, route_id, school_id).await
+    }
+
+    /// Update a route.
+    #[instrument(skip(db))]
+    pub async fn update_route(
+        db: &PgPool,
+        route_id: TransportRouteId,
+        school_id: Option<SchoolId>,
+        dto: UpdateTransportRouteDto,
+    ) -> Result<TransportRoute, AppError> {
+        let existing = Self::find_route(db, route_id, school_id).await?;
+
+        if let Some(vehicle_id) = dto.vehicle_id {
+            Self::find_vehicle(db, vehicle_id, Some(existing.school_id)).await?;
+        }
+
+        let route = sqlx::query_as::<_, TransportRoute>(&format!(
+            "UPDATE transport_routes
+             SET name = COALESCE($2, name),
+                 description = COALESCE($3, description),
+                 vehicle_id = COALESCE($4, vehicle_id),
+                 fee_amount = COALESCE($5, fee_amount)
+             WHERE id = $1
+             RETURNING {ROUTE_COLUMNS}"
+        ))
+        .bind(route_id)
+        .bind(&dto.name)
+        .bind(&dto.description)
+        .bind(dto.vehicle_id)
+        .bind(dto.fee_amount)
+        .fetch_one(db)
+        .await
+        .map_err(|e| unique_violation_as(e, "A route with this name already exists"))?;
+
+        Ok(route)
+    }
+
+    /// Delete a route with its stops, assignments, and fee charges.
+    #[instrument(skip(db))]
+    pub async fn delete_route(
+        db: &PgPool,
+        route_id: TransportRouteId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "DELETE FROM transport_routes WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(route_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!("Route not found")));
+        }
+
+        Ok(())
+    }
+
+    /// Add a stop to a route.
+    #[instrument(skip(db))]
+    pub async fn create_stop(
+        db: &PgPool,
+        route_id: TransportRouteId,
+        school_id: Option<SchoolId>,
+        dto: CreateRouteStopDto,
+    ) -> Result<RouteStop, AppError> {
+        Self::find_route(db, route_id, school_id).await?;
+
+        let stop = sqlx::query_as::<_, RouteStop>(&format!(
+            "INSERT INTO route_stops (route_id, name, sequence, pickup_time, dropoff_time)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {STOP_COLUMNS}"
+        ))
+        .bind(route_id)
+        .bind(&dto.name)
+        .bind(dto.sequence)
+        .bind(dto.pickup_time)
+        .bind(dto.dropoff_time)
+        .fetch_one(db)
+        .await
+        .map_err(|e| unique_violation_as(e, "A stop with this sequence already exists"))?;
+
+        Ok(stop)
+    }
+
+    /// List a route's stops in order.
+    #[instrument(skip(db))]
+    pub async fn get_stops(
+        db: &PgPool,
+        route_id: TransportRouteId,
+        school_id: Option<SchoolId>,
+    ) -> Result<Vec<RouteStop>, AppError> {
+        Self::find_route(db, route_id, school_id).await?;
+        Self::stops_for(db, route_id).await
+    }
+
+    async fn stops_for(
+        db: &PgPool,
+        route_id: TransportRouteId,
+    ) -> Result<Vec<RouteStop>, AppError> {
+        let stops = sqlx::query_as::<_, RouteStop>(&format!(
+            "SELECT {STOP_COLUMNS} FROM route_stops WHERE route_id = $1 ORDER BY sequence ASC"
+        ))
+        .bind(route_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(stops)
+    }
+
+    /// Delete a stop. Students boarding there keep their route without a stop.
+    #[instrument(skip(db))]
+    pub async fn delete_stop(
+        db: &PgPool,
+        stop_id: RouteStopId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"DELETE FROM route_stops s
+               USING transport_routes r
+               WHERE s.route_id = r.id
+                 AND s.id = $1 AND ($2::uuid IS NULL OR r.school_id = $2)"#,
+        )
+        .bind(stop_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!("Stop not found")));
+        }
+
+        Ok(())
+    }
+
+    /// Assign a student to a route for a term.
+    ///
+    /// When the route has a vehicle, assignments are capped at its seat count.
+    #[instrument(skip(db))]
+    pub async fn assign_route(
+        db: &PgPool,
+        school_id: Option<SchoolId>,
+        assigned_by: UserId,
+        dto: AssignRouteDto,
+    ) -> Result<RouteAssignment, AppError> {
+        let route = Self::find_route(db, dto.route_id, school_id).await?;
+        let term_id = Self::resolve_term(db, route.school_id, dto.term_id).await?;
+
+        if let Some(stop_id) = dto.stop_id {
+            let on_route = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM route_stops WHERE id = $1 AND route_id = $2)",
+            )
+            .bind(stop_id)
+            .bind(route.id)
+            .fetch_one(db)
+            .await?;
+
+            if !on_route {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "Stop is not on this route"
+                )));
+            }
+        }
+
+        let is_student = sqlx::query_scalar::<_, bool>(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM users u
+                JOIN user_roles ur ON ur.user_id = u.id
+                WHERE u.id = $1 AND u.school_id = $2 AND ur.role_id = $3
+            )"#,
+        )
+        .bind(dto.student_id)
+        .bind(route.school_id)
+        .bind(system_roles::STUDENT)
+        .fetch_one(db)
+        .await?;
+
+        if !is_student {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Only students of the route's school can be assigned"
+            )));
+        }
+
+        let mut tx = db.begin().await?;
+
+        if let Some(vehicle_id) = route.vehicle_id {
+            let capacity = sqlx::query_scalar::<_, i32>(
+                "SELECT capacity FROM transport_vehicles WHERE id = $1 FOR UPDATE",
+            )
+            .bind(vehicle_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            let riders = sqlx::query_scalar::<_, i64>(
+                "SELECT COUNT(*) FROM route_assignments WHERE route_id = $1 AND term_id = $2",
+            )
+            .bind(route.id)
+            .bind(term_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            if riders >= i64::from(capacity) {
+                return Err(AppError::bad_request(anyhow::anyhow!(
+                    "Route is at vehicle capacity"
+                )));
+            }
+        }
+
+        let assignment = sqlx::query_as::<_, RouteAssignment>(
+            r#"INSERT INTO route_assignments (route_id, stop_id, student_id, term_id, assigned_by)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id, route_id, stop_id, student_id, term_id, assigned_by, created_at"#,
+        )
+        .bind(route.id)
+        .bind(dto.stop_id)
+        .bind(dto.student_id)
+        .bind(term_id)
+        .bind(assigned_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| unique_violation_as(e, "Student is already assigned a route this term"))?;
+
+        tx.commit().await?;
+
+        Ok(assignment)
+    }
+
+    /// Get paginated route assignments for a school.
+    #[instrument(skip(db))]
+    pub async fn get_assignments(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: RouteAssignmentFilterParams,
+    ) -> Result<PaginatedRouteAssignmentsResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+
+        let where_clause = r#"WHERE r.school_id = $1
+              AND ($2::uuid IS NULL OR a.route_id = $2)
+              AND ($3::uuid IS NULL OR a.term_id = $3)"#;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            r#"SELECT COUNT(*) FROM route_assignments a
+               JOIN transport_routes r ON r.id = a.route_id
+               {where_clause}"#
+        ))
+        .bind(school_id)
+        .bind(filters.route_id)
+        .bind(filters.term_id)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, RouteAssignmentWithDetails>(&format!(
+            r#"SELECT a.id, a.route_id, r.name AS route_name,
+                      a.stop_id, s.name AS stop_name,
+                      a.student_id, u.first_name AS student_first_name,
+                      u.last_name AS student_last_name,
+                      a.term_id, a.created_at
+               FROM route_assignments a
+               JOIN transport_routes r ON r.id = a.route_id
+               LEFT JOIN route_stops s ON s.id = a.stop_id
+               JOIN users u ON u.id = a.student_id
+               {where_clause}
+               ORDER BY r.name, s.sequence NULLS LAST, u.last_name, u.first_name
+               LIMIT $4 OFFSET $5"#
+        ))
+        .bind(school_id)
+        .bind(filters.route_id)
+        .bind(filters.term_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedRouteAssignmentsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Remove a route assignment.
+    #[instrument(skip(db))]
+    pub async fn delete_assignment(
+        db: &PgPool,
+        assignment_id: RouteAssignmentId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"DELETE FROM route_assignments a
+               USING transport_routes r
+               WHERE a.route_id = r.id
+                 AND a.id = $1 AND ($2::uuid IS NULL OR r.school_id = $2)"#,
+        )
+        .bind(assignment_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Route assignment not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Build the manifest for a route and term.
+    async fn build_manifest(
+        db: &PgPool,
+        route: TransportRoute,
+        term_id: TermId,
+    ) -> Result<RouteManifest, AppError> {
+        let vehicle = match route.vehicle_id {
+            Some(vehicle_id) => Some(Self::find_vehicle(db, vehicle_id, None).await?),
+            None => None,
+        };
+
+        let stops = Self::stops_for(db, route.id).await?;
+
+        let rows = sqlx::query_as::<_, ManifestRow>(
+            r#"SELECT a.stop_id, u.id AS student_id, u.first_name, u.last_name
+               FROM route_assignments a
+               JOIN users u ON u.id = a.student_id
+               WHERE a.route_id = $1 AND a.term_id = $2
+               ORDER BY u.last_name, u.first_name"#,
+        )
+        .bind(route.id)
+        .bind(term_id)
+        .fetch_all(db)
+        .await?;
+
+        let total_students = rows.len() as i64;
+        let mut unassigned_stop = Vec::new();
+        let mut stops: Vec<ManifestStop> = stops
+            .into_iter()
+            .map(|stop| ManifestStop {
+                stop,
+                students: Vec::new(),
+            })
+            .collect();
+
+        for row in rows {
+            let student = ManifestStudent {
+                student_id: row.student_id,
+                first_name: row.first_name,
+                last_name: row.last_name,
+            };
+            match stops.iter_mut().find(|s| Some(s.stop.id) == row.stop_id) {
+                Some(stop) => stop.students.push(student),
+                None => unassigned_stop.push(student),
+            }
+        }
+
+        Ok(RouteManifest {
+            route,
+            vehicle,
+            term_id,
+            stops,
+            unassigned_stop,
+            total_students,
+        })
+    }
+
+    /// Get the manifest for a route and term.
+    #[instrument(skip(db))]
+    pub async fn get_route_manifest(
+        db: &PgPool,
+        route_id: TransportRouteId,
+        school_id: Option<SchoolId>,
+        term_id: Option<TermId>,
+    ) -> Result<RouteManifest, AppError> {
+        let route = Self::find_route(db, route_id, school_id).await?;
+        let term_id = Self::resolve_term(db, route.school_id, term_id).await?;
+
+        Self::build_manifest(db, route, term_id).await
+    }
+
+    /// List the routes served by vehicles a driver is assigned to.
+    #[instrument(skip(db))]
+    pub async fn get_driver_routes(
+        db: &PgPool,
+        driver_id: UserId,
+    ) -> Result<Vec<TransportRoute>, AppError> {
+        let routes = sqlx::query_as::<_, TransportRoute>(
+            r#"SELECT r.id, r.school_id, r.name, r.description, r.vehicle_id, r.fee_amount,
+                      r.created_at, r.updated_at
+               FROM transport_routes r
+               JOIN transport_vehicles v ON v.id = r.vehicle_id
+               WHERE v.driver_id = $1
+               ORDER BY r.name ASC"#,
+        )
+        .bind(driver_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(routes)
+    }
+
+    /// Get the manifest for a route the driver is assigned to.
+    ///
+    /// Routes driven by someone else are reported as not found.
+    #[instrument(skip(db))]
+    pub async fn get_driver_manifest(
+        db: &PgPool,
+        route_id: TransportRouteId,
+        driver_id: UserId,
+        term_id: Option<TermId>,
+    ) -> Result<RouteManifest, AppError> {
+        let route = Self::find_route(db, route_id, None).await?;
+
+        let drives_route = match route.vehicle_id {
+            Some(vehicle_id) => sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM transport_vehicles WHERE id = $1 AND driver_id = $2)",
+            )
+            .bind(vehicle_id)
+            .bind(driver_id)
+            .fetch_one(db)
+            .await?,
+            None => false,
+        };
+
+        if !drives_route {
+            return Err(AppError::not_found(anyhow::anyhow!("Route not found")));
+        }
+
+        let term_id = Self::resolve_term(db, route.school_id, term_id).await?;
+        Self::build_manifest(db, route, term_id).await
+    }
+
+    /// Generate transport fee charges for every student assigned to a route in a term.
+    ///
+    /// Students who already have a charge for the route and term are skipped,
+    /// so this can safely be re-run after late assignments.
+    #[instrument(skip(db))]
+    pub async fn generate_fees(
+        db: &PgPool,
+        route_id: TransportRouteId,
+        school_id: Option<SchoolId>,
+        term_id: Option<TermId>,
+    ) -> Result<GenerateTransportFeesResponse, AppError> {
+        let route = Self::find_route(db, route_id, school_id).await?;
+        let term_id = Self::resolve_term(db, route.school_id, term_id).await?;
+
+        let Some(fee_amount) = route.fee_amount else {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Route has no transport fee configured"
+            )));
+        };
+
+        let result = sqlx::query(
+            r#"INSERT INTO transport_fee_charges (route_id, student_id, term_id, amount)
+               SELECT route_id, student_id, term_id, $3
+               FROM route_assignments
+               WHERE route_id = $1 AND term_id = $2
+               ON CONFLICT (route_id, student_id, term_id) DO NOTHING"#,
+        )
+        .bind(route_id)
+        .bind(term_id)
+        .bind(fee_amount)
+        .execute(db)
+        .await?;
+
+        let assigned = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM route_assignments WHERE route_id = $1 AND term_id = $2",
+        )
+        .bind(route_id)
+        .bind(term_id)
+        .fetch_one(db)
+        .await?;
+
+        let created = result.rows_affected() as i64;
+
+        Ok(GenerateTransportFeesResponse {
+            route_id,
+            term_id,
+            created,
+            already_charged: assigned - created,
+        })
+    }
+
+    /// List transport fee charges for a school.
+    #[instrument(skip(db))]
+    pub async fn get_fee_charges(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: TransportFeeFilterParams,
+    ) -> Result<Vec<TransportFeeCharge>, AppError> {
+        let charges = sqlx::query_as::<_, TransportFeeCharge>(
+            r#"SELECT c.id, c.route_id, c.student_id, c.term_id, c.amount, c.created_at
+               FROM transport_fee_charges c
+               JOIN transport_routes r ON r.id = c.route_id
+               WHERE r.school_id = $1
+                 AND ($2::uuid IS NULL OR c.route_id = $2)
+                 AND ($3::uuid IS NULL OR c.term_id = $3)
+                 AND ($4::uuid IS NULL OR c.student_id = $4)
+               ORDER BY c.created_at DESC"#,
+        )
+        .bind(school_id)
+        .bind(filters.route_id)
+        .bind(filters.term_id)
+        .bind(filters.student_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(charges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use chalkbyte_models::ids::RoleId;
+    use chrono::{NaiveDate, NaiveTime};
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_current_term(pool: &PgPool, school_id: SchoolId) -> TermId {
+        let session_id: Uuid = sqlx::query_scalar(
+            r#"INSERT INTO academic_sessions (name, school_id, start_date, end_date, is_active)
+               VALUES ('2025/2026', $1, $2, $3, true) RETURNING id"#,
+        )
+        .bind(school_id)
+        .bind(NaiveDate::from_ymd_opt(2025, 9, 1).unwrap())
+        .bind(NaiveDate::from_ymd_opt(2026, 7, 31).unwrap())
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        sqlx::query_scalar::<_, TermId>(
+            r#"INSERT INTO terms (name, academic_session_id, start_date, end_date, is_current)
+               VALUES ('First Term', $1, $2, $3, true) RETURNING id"#,
+        )
+        .bind(session_id)
+        .bind(NaiveDate::from_ymd_opt(2025, 9, 1).unwrap())
+        .bind(NaiveDate::from_ymd_opt(2025, 12, 15).unwrap())
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId, role_id: RoleId) -> UserId {
+        let user_id: UserId = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Test', 'User', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id.into_inner(),
+            role_id.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id
+    }
+
+    async fn create_route_with_bus(
+        pool: &PgPool,
+        school_id: SchoolId,
+        capacity: i32,
+        driver_id: Option<UserId>,
+        fee_amount: Option<i64>,
+    ) -> TransportRoute {
+        let vehicle = TransportService::create_vehicle(
+            pool,
+            school_id,
+            CreateVehicleDto {
+                registration_number: format!("BUS-{}", &Uuid::new_v4().to_string()[..6]),
+                description: None,
+                capacity,
+                driver_id,
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        TransportService::create_route(
+            pool,
+            school_id,
+            CreateTransportRouteDto {
+                name: format!("Route {}", Uuid::new_v4()),
+                description: None,
+                vehicle_id: Some(vehicle.id),
+                fee_amount,
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    fn assign_dto(route_id: TransportRouteId, student_id: UserId) -> AssignRouteDto {
+        AssignRouteDto {
+            route_id,
+            stop_id: None,
+            student_id,
+            term_id: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_assign_route_respects_vehicle_capacity(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        create_current_term(&pool, school_id).await;
+        let route = create_route_with_bus(&pool, school_id, 1, None, None).await;
+        let first = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let second = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+
+        TransportService::assign_route(&pool, Some(school_id), first, assign_dto(route.id, first))
+            .await
+            .unwrap();
+
+        let err = TransportService::assign_route(
+            &pool,
+            Some(school_id),
+            first,
+            assign_dto(route.id, second),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_assign_route_rejects_stop_from_other_route(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        create_current_term(&pool, school_id).await;
+        let route = create_route_with_bus(&pool, school_id, 30, None, None).await;
+        let other = create_route_with_bus(&pool, school_id, 30, None, None).await;
+        let stop = TransportService::create_stop(
+            &pool,
+            other.id,
+            Some(school_id),
+            CreateRouteStopDto {
+                name: "Market".to_string(),
+                sequence: 1,
+                pickup_time: None,
+                dropoff_time: None,
+            },
+        )
+        .await
+        .unwrap();
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+
+        let err = TransportService::assign_route(
+            &pool,
+            Some(school_id),
+            student,
+            AssignRouteDto {
+                stop_id: Some(stop.id),
+                ..assign_dto(route.id, student)
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_driver_manifest(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        create_current_term(&pool, school_id).await;
+        let driver = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let other_driver = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+        let route = create_route_with_bus(&pool, school_id, 30, Some(driver), None).await;
+
+        let mut stops = Vec::new();
+        for (sequence, name) in [(1, "Market"), (2, "Church")] {
+            stops.push(
+                TransportService::create_stop(
+                    &pool,
+                    route.id,
+                    Some(school_id),
+                    CreateRouteStopDto {
+                        name: name.to_string(),
+                        sequence,
+                        pickup_time: NaiveTime::from_hms_opt(7, sequence as u32 * 10, 0),
+                        dropoff_time: None,
+                    },
+                )
+                .await
+                .unwrap(),
+            );
+        }
+
+        let at_market = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let no_stop = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        TransportService::assign_route(
+            &pool,
+            Some(school_id),
+            driver,
+            AssignRouteDto {
+                stop_id: Some(stops[0].id),
+                ..assign_dto(route.id, at_market)
+            },
+        )
+        .await
+        .unwrap();
+        TransportService::assign_route(
+            &pool,
+            Some(school_id),
+            driver,
+            assign_dto(route.id, no_stop),
+        )
+        .await
+        .unwrap();
+
+        let routes = TransportService::get_driver_routes(&pool, driver)
+            .await
+            .unwrap();
+        assert_eq!(routes.len(), 1);
+
+        let manifest = TransportService::get_driver_manifest(&pool, route.id, driver, None)
+            .await
+            .unwrap();
+        assert_eq!(manifest.total_students, 2);
+        assert_eq!(manifest.stops.len(), 2);
+        assert_eq!(manifest.stops[0].students.len(), 1);
+        assert_eq!(manifest.stops[0].students[0].student_id, at_market);
+        assert!(manifest.stops[1].students.is_empty());
+        assert_eq!(manifest.unassigned_stop.len(), 1);
+
+        let err = TransportService::get_driver_manifest(&pool, route.id, other_driver, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_generate_fees_is_idempotent(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let term_id = create_current_term(&pool, school_id).await;
+        let route = create_route_with_bus(&pool, school_id, 30, None, Some(2_500_000)).await;
+        let free_route = create_route_with_bus(&pool, school_id, 30, None, None).await;
+
+        for _ in 0..2 {
+            let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+            TransportService::assign_route(
+                &pool,
+                Some(school_id),
+                student,
+                assign_dto(route.id, student),
+            )
+            .await
+            .unwrap();
+        }
+
+        let first = TransportService::generate_fees(&pool, route.id, Some(school_id), None)
+            .await
+            .unwrap();
+        assert_eq!(first.term_id, term_id);
+        assert_eq!(first.created, 2);
+        assert_eq!(first.already_charged, 0);
+
+        let late = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        TransportService::assign_route(&pool, Some(school_id), late, assign_dto(route.id, late))
+            .await
+            .unwrap();
+
+        let second = TransportService::generate_fees(&pool, route.id, Some(school_id), None)
+            .await
+            .unwrap();
+        assert_eq!(second.created, 1);
+        assert_eq!(second.already_charged, 2);
+
+        let charges = TransportService::get_fee_charges(
+            &pool,
+            school_id,
+            TransportFeeFilterParams {
+                school_id: None,
+                route_id: Some(route.id),
+                term_id: None,
+                student_id: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(charges.len(), 3);
+        assert!(charges.iter().all(|c| c.amount == 2_500_000));
+
+        let err = TransportService::generate_fees(&pool, free_route.id, Some(school_id), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,123 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, NoContent};
+use chalkbyte_models::ids::{SchoolId, TrashItemId};
+
+use crate::middleware::auth::{RequireTrashPurge, RequireTrashRead, RequireTrashRestore};
+use crate::modules::trash::model::{PaginatedTrashResponse, TrashFilterParams};
+use crate::modules::trash::service::TrashService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::verify_school_access;
+
+/// List a school's recycle bin
+///
+/// Items are ordered by deletion time, most recent first.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/trash",
+    summary = "List recycle bin",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        TrashFilterParams
+    ),
+    responses(
+        (status = 200, description = "Deleted items with who deleted them and when", body = PaginatedTrashResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires trash:read permission and access to the school")
+    ),
+    tag = "Recycle Bin",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_trash(
+    State(state): State<AppState>,
+    RequireTrashRead(auth_user): RequireTrashRead,
+    Path(school_id): Path<Uuid>,
+    Query(params): Query<TrashFilterParams>,
+) -> Result<Json<PaginatedTrashResponse>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let items = TrashService::get_items(&state.db, school_id, params).await?;
+
+    Ok(Json(items))
+}
+
+/// Restore a deleted record
+///
+/// The record comes back under its original ID and the item leaves the
+/// recycle bin.
+#[utoipa::path(
+    post,
+    path = "/api/schools/{id}/trash/{item_id}/restore",
+    summary = "Restore deleted record",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("item_id" = Uuid, Path, description = "Trash item ID")
+    ),
+    responses(
+        NoContent,
+        (status = 400, description = "Conflicts with an existing record, or its parent no longer exists"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires trash:restore permission and access to the school"),
+        (status = 404, description = "Trash item not found")
+    ),
+    tag = "Recycle Bin",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn restore_trash_item(
+    State(state): State<AppState>,
+    RequireTrashRestore(auth_user): RequireTrashRestore,
+    Path((school_id, item_id)): Path<(Uuid, Uuid)>,
+) -> Result<NoContent, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    TrashService::restore_item(
+        &state.db,
+        state.cache.as_ref(),
+        school_id,
+        TrashItemId::from(item_id),
+    )
+    .await?;
+
+    Ok(NoContent)
+}
+
+/// Permanently purge a deleted record
+#[utoipa::path(
+    delete,
+    path = "/api/schools/{id}/trash/{item_id}",
+    summary = "Purge deleted record",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("item_id" = Uuid, Path, description = "Trash item ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires trash:purge permission and access to the school"),
+        (status = 404, description = "Trash item not found")
+    ),
+    tag = "Recycle Bin",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn purge_trash_item(
+    State(state): State<AppState>,
+    RequireTrashPurge(auth_user): RequireTrashPurge,
+    Path((school_id, item_id)): Path<(Uuid, Uuid)>,
+) -> Result<NoContent, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    TrashService::purge_item(&state.db, school_id, TrashItemId::from(item_id)).await?;
+
+    Ok(NoContent)
+}
@@ -0,0 +1,14 @@
+//! Recycle bin module.
+//!
+//! Deleting a student or branch moves a snapshot of it into the school's
+//! recycle bin instead of losing it outright. Admins can list what was deleted,
+//! by whom and when, restore an item under its original ID, or purge it.
+//!
+//! Items are purged automatically by the retention job once the school's
+//! `deleted_records` retention period has passed (default
+//! `TRASH_RETENTION_DAYS` days, or 30).
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
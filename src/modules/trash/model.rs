@@ -0,0 +1,7 @@
+//! Recycle bin data models and DTOs.
+//!
+//! This module re-exports recycle bin models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all recycle bin models from the shared crate
+pub use chalkbyte_models::trash::*;
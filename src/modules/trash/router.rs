@@ -0,0 +1,17 @@
+use axum::{
+    Router,
+    routing::{delete, get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{get_trash, purge_trash_item, restore_trash_item};
+
+/// Initialize the recycle bin router, merged into the schools router
+/// Routes: GET /{id}/trash, POST /{id}/trash/{item_id}/restore, DELETE /{id}/trash/{item_id}
+pub fn init_trash_router() -> Router<AppState> {
+    Router::new()
+        .route("/{id}/trash", get(get_trash))
+        .route("/{id}/trash/{item_id}/restore", post(restore_trash_item))
+        .route("/{id}/trash/{item_id}", delete(purge_trash_item))
+}
@@ -0,0 +1,521 @@
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_cache::{RedisCache, invalidate};
+use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_models::ids::{BranchId, SchoolId, TrashItemId, UserId};
+
+use crate::modules::trash::model::{
+    PaginatedTrashResponse, TrashFilterParams, TrashItem, TrashItemType,
+};
+
+/// Days a deleted record stays in the recycle bin when `TRASH_RETENTION_DAYS` is not set.
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+/// Days a deleted record stays in the recycle bin before it is purged, for
+/// schools without a `deleted_records` retention policy.
+pub fn retention_days() -> i64 {
+    std::env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(DEFAULT_RETENTION_DAYS)
+}
+
+/// Columns of a listed item. The purge date follows the school's
+/// `deleted_records` retention policy, falling back to `default_retention_days`.
+fn trash_item_columns(default_retention_days: i64) -> String {
+    format!(
+        "t.id, t.school_id, t.item_type, t.entity_id, t.label, t.deleted_by, \
+         d.first_name || ' ' || d.last_name AS deleted_by_name, t.deleted_at, \
+         t.deleted_at + make_interval(days => COALESCE(( \
+             SELECT rp.retention_days FROM retention_policies rp \
+             WHERE rp.school_id = t.school_id AND rp.category = 'deleted_records' \
+         ), {default_retention_days})) AS purge_at"
+    )
+}
+
+fn restore_conflict(e: sqlx::Error, conflict: &'static str, missing: &'static str) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e {
+        if db_err.is_unique_violation() {
+            return AppError::bad_request(anyhow::anyhow!(conflict));
+        }
+        if db_err.is_foreign_key_violation() {
+            return AppError::bad_request(anyhow::anyhow!(missing));
+        }
+    }
+    AppError::from(e)
+}
+
+pub struct TrashService;
+
+impl TrashService {
+    /// Move a snapshot of a user and their role assignments into the recycle bin.
+    ///
+    /// Must run in the same transaction as the delete. Users without a school
+    /// have no recycle bin and are not kept.
+    pub async fn trash_user(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        item_type: TrashItemType,
+        deleted_by: UserId,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"INSERT INTO trash_items (school_id, item_type, entity_id, label, data, deleted_by)
+               SELECT u.school_id, $2, u.id, u.first_name || ' ' || u.last_name,
+                      jsonb_build_object(
+                          'record', to_jsonb(u),
+                          'role_ids', COALESCE(
+                              (SELECT jsonb_agg(ur.role_id) FROM user_roles ur WHERE ur.user_id = u.id),
+                              '[]'::jsonb
+                          )
+                      ),
+                      $3
+               FROM users u
+               WHERE u.id = $1 AND u.school_id IS NOT NULL"#,
+        )
+        .bind(user_id)
+        .bind(item_type)
+        .bind(deleted_by)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Move a snapshot of a branch and its member students into the recycle bin.
+    ///
+    /// Must run in the same transaction as the delete.
+    pub async fn trash_branch(
+        conn: &mut PgConnection,
+        branch_id: BranchId,
+        deleted_by: UserId,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"INSERT INTO trash_items (school_id, item_type, entity_id, label, data, deleted_by)
+               SELECT l.school_id, $2, b.id, b.name || ' (' || l.name || ')',
+                      jsonb_build_object(
+                          'record', to_jsonb(b),
+                          'student_ids', COALESCE(
+                              (SELECT jsonb_agg(u.id) FROM users u WHERE u.branch_id = b.id),
+                              '[]'::jsonb
+                          )
+                      ),
+                      $3
+               FROM branches b
+               INNER JOIN levels l ON l.id = b.level_id
+               WHERE b.id = $1"#,
+        )
+        .bind(branch_id)
+        .bind(TrashItemType::Branches)
+        .bind(deleted_by)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List a school's recycle bin, most recently deleted first.
+    #[instrument(skip(db))]
+    pub async fn get_items(
+        db: &PgPool,
+        school_id: SchoolId,
+        params: TrashFilterParams,
+    ) -> Result<PaginatedTrashResponse, AppError> {
+        let page = params.pagination.page();
+        let limit = params.pagination.limit();
+        let offset = params.pagination.offset();
+
+        let items = sqlx::query_as::<_, TrashItem>(&format!(
+            "SELECT {} FROM trash_items t
+             LEFT JOIN users d ON d.id = t.deleted_by
+             WHERE t.school_id = $1 AND ($2::text IS NULL OR t.item_type = $2)
+             ORDER BY t.deleted_at DESC
+             LIMIT $3 OFFSET $4",
+            trash_item_columns(retention_days())
+        ))
+        .bind(school_id)
+        .bind(params.item_type)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM trash_items
+             WHERE school_id = $1 AND ($2::text IS NULL OR item_type = $2)",
+        )
+        .bind(school_id)
+        .bind(params.item_type)
+        .fetch_one(db)
+        .await?;
+
+        Ok(PaginatedTrashResponse {
+            data: items,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Put a deleted record back under its original ID.
+    ///
+    /// Users get back the roles that still exist and any level or branch that
+    /// still exists; branches get back those of their students who have not
+    /// been placed in another branch since.
+    #[instrument(skip(db, cache))]
+    pub async fn restore_item(
+        db: &PgPool,
+        cache: Option<&RedisCache>,
+        school_id: SchoolId,
+        item_id: TrashItemId,
+    ) -> Result<(), AppError> {
+        let mut tx = db.begin().await?;
+
+        let (item_type, entity_id, data) =
+            sqlx::query_as::<_, (TrashItemType, Uuid, serde_json::Value)>(
+                "SELECT item_type, entity_id, data FROM trash_items
+                 WHERE id = $1 AND school_id = $2
+                 FOR UPDATE",
+            )
+            .bind(item_id)
+            .bind(school_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Trash item not found")))?;
+
+        match item_type {
+            TrashItemType::Students | TrashItemType::Users => {
+                sqlx::query(
+                    r#"INSERT INTO users
+                       SELECT * FROM jsonb_populate_record(
+                           NULL::users,
+                           $1::jsonb || jsonb_build_object(
+                               'level_id', (SELECT id FROM levels WHERE id = ($1::jsonb->>'level_id')::uuid),
+                               'branch_id', (SELECT id FROM branches WHERE id = ($1::jsonb->>'branch_id')::uuid)
+                           )
+                       )"#,
+                )
+                .bind(&data["record"])
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    restore_conflict(
+                        e,
+                        "A user with this email already exists",
+                        "The user's school no longer exists",
+                    )
+                })?;
+
+                sqlx::query(
+                    "INSERT INTO user_roles (user_id, role_id)
+                     SELECT $1, r.id FROM roles r
+                     WHERE r.id IN (SELECT jsonb_array_elements_text($2::jsonb)::uuid)",
+                )
+                .bind(entity_id)
+                .bind(&data["role_ids"])
+                .execute(&mut *tx)
+                .await?;
+            }
+            TrashItemType::Branches => {
+                sqlx::query(
+                    "INSERT INTO branches
+                     SELECT * FROM jsonb_populate_record(NULL::branches, $1::jsonb)",
+                )
+                .bind(&data["record"])
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    restore_conflict(
+                        e,
+                        "A branch with this name already exists in the level",
+                        "The branch's level no longer exists",
+                    )
+                })?;
+
+                sqlx::query(
+                    "UPDATE users SET branch_id = $1, updated_at = NOW()
+                     WHERE branch_id IS NULL
+                       AND id IN (SELECT jsonb_array_elements_text($2::jsonb)::uuid)",
+                )
+                .bind(entity_id)
+                .bind(&data["student_ids"])
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        sqlx::query("DELETE FROM trash_items WHERE id = $1")
+            .bind(item_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        match item_type {
+            TrashItemType::Students | TrashItemType::Users => {
+                invalidate::user(cache, Some(entity_id), Some(school_id.into_inner())).await;
+            }
+            TrashItemType::Branches => {
+                let level_id = data["record"]["level_id"]
+                    .as_str()
+                    .and_then(|id| id.parse::<Uuid>().ok());
+                invalidate::branch(
+                    cache,
+                    Some(entity_id),
+                    level_id,
+                    Some(school_id.into_inner()),
+                )
+                .await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Permanently remove an item from the recycle bin.
+    #[instrument(skip(db))]
+    pub async fn purge_item(
+        db: &PgPool,
+        school_id: SchoolId,
+        item_id: TrashItemId,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM trash_items WHERE id = $1 AND school_id = $2")
+            .bind(item_id)
+            .bind(school_id)
+            .execute(db)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!("Trash item not found")));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::database::TenantContext;
+    use crate::modules::branches::service::BranchService;
+    use crate::modules::retention::model::RetentionCategory;
+    use crate::modules::retention::service::RetentionService;
+    use crate::modules::students::service::StudentService;
+    use crate::modules::users::model::system_roles;
+    use axum::http::StatusCode;
+    use chalkbyte_core::PaginationParams;
+    use chalkbyte_models::ids::LevelId;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId, first_name: &str) -> UserId {
+        let user_id = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ($1, 'Tester', $2, $3) RETURNING id"#,
+            first_name,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id,
+            system_roles::STUDENT.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id.into()
+    }
+
+    fn all_items() -> TrashFilterParams {
+        TrashFilterParams {
+            item_type: None,
+            pagination: PaginationParams::default(),
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_deleted_student_can_be_restored(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id, "Admin").await;
+        let student_id = create_test_user(&pool, school_id, "Ada").await;
+
+        StudentService::delete_student(
+            &pool,
+            &TenantContext::school(school_id),
+            student_id.into_inner(),
+            admin_id,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let trash = TrashService::get_items(&pool, school_id, all_items())
+            .await
+            .unwrap();
+        assert_eq!(trash.meta.total, 1);
+        let item = &trash.data[0];
+        assert_eq!(item.item_type, TrashItemType::Students);
+        assert_eq!(item.entity_id, student_id.into_inner());
+        assert_eq!(item.label, "Ada Tester");
+        assert_eq!(item.deleted_by, Some(admin_id));
+        assert_eq!(item.deleted_by_name.as_deref(), Some("Admin Tester"));
+        assert!(item.purge_at > item.deleted_at);
+
+        TrashService::restore_item(&pool, None, school_id, item.id)
+            .await
+            .unwrap();
+
+        let student = StudentService::get_student_by_id(
+            &pool,
+            &TenantContext::school(school_id),
+            student_id.into_inner(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(student.first_name, "Ada");
+
+        let trash = TrashService::get_items(&pool, school_id, all_items())
+            .await
+            .unwrap();
+        assert_eq!(trash.meta.total, 0);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_restored_branch_gets_its_students_back(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id, "Admin").await;
+        let student_id = create_test_user(&pool, school_id, "Ada").await;
+        let level_id: LevelId = sqlx::query_scalar!(
+            "INSERT INTO levels (name, school_id) VALUES ('Grade 1', $1) RETURNING id",
+            school_id.into_inner()
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .into();
+        let branch_id: BranchId = sqlx::query_scalar!(
+            "INSERT INTO branches (name, level_id) VALUES ('A', $1) RETURNING id",
+            level_id.into_inner()
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .into();
+        sqlx::query!(
+            "UPDATE users SET branch_id = $1 WHERE id = $2",
+            branch_id.into_inner(),
+            student_id.into_inner()
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        BranchService::delete_branch(
+            &pool,
+            None,
+            &TenantContext::school(school_id),
+            branch_id,
+            None,
+            admin_id,
+        )
+        .await
+        .unwrap();
+
+        let params = TrashFilterParams {
+            item_type: Some(TrashItemType::Branches),
+            pagination: PaginationParams::default(),
+        };
+        let trash = TrashService::get_items(&pool, school_id, params)
+            .await
+            .unwrap();
+        assert_eq!(trash.data.len(), 1);
+        assert_eq!(trash.data[0].label, "A (Grade 1)");
+
+        TrashService::restore_item(&pool, None, school_id, trash.data[0].id)
+            .await
+            .unwrap();
+
+        let restored_branch = sqlx::query_scalar!(
+            "SELECT branch_id FROM users WHERE id = $1",
+            student_id.into_inner()
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(restored_branch, Some(branch_id.into_inner()));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_purge_is_scoped_and_expires_old_items(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let other_school_id = create_test_school(&pool).await;
+        let admin_id = create_test_user(&pool, school_id, "Admin").await;
+        let student_id = create_test_user(&pool, school_id, "Ada").await;
+
+        StudentService::delete_student(
+            &pool,
+            &TenantContext::school(school_id),
+            student_id.into_inner(),
+            admin_id,
+            None,
+        )
+        .await
+        .unwrap();
+        let item_id = TrashService::get_items(&pool, school_id, all_items())
+            .await
+            .unwrap()
+            .data[0]
+            .id;
+
+        let err = TrashService::purge_item(&pool, other_school_id, item_id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let purged = RetentionService::purge_school(&pool, school_id, None)
+            .await
+            .unwrap();
+        assert!(purged.is_empty());
+
+        sqlx::query!(
+            "UPDATE trash_items SET deleted_at = NOW() - INTERVAL '31 days' WHERE id = $1",
+            item_id.into_inner()
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let purged = RetentionService::purge_school(&pool, school_id, None)
+            .await
+            .unwrap();
+        assert_eq!(purged.len(), 1);
+        assert_eq!(purged[0].category, RetentionCategory::DeletedRecords);
+        assert_eq!(purged[0].deleted_count, 1);
+
+        let err = TrashService::restore_item(&pool, None, school_id, item_id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+}
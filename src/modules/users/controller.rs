@@ -1,25 +1,35 @@
-use chalkbyte_core::AppError;
+use chalkbyte_core::{
+    AppError, Conditional, Created, ExportFormat, ExportParams, IfMatch, ResponseView, WithEtag,
+};
 use chalkbyte_models::ids::UserId;
 
-use crate::middleware::auth::{AuthUser, RequireUsersCreate, RequireUsersRead};
+use crate::config::database::timed;
+use crate::middleware::auth::{AuthUser, RequireUsersCreate, RequireUsersRead, RequireUsersUpdate};
 use crate::middleware::role::is_system_admin_jwt;
 use crate::modules::auth::controller::ErrorResponse;
 use crate::modules::users::model::{
-    ChangePasswordDto, CreateUserDto, PaginatedUsersResponse, UpdateProfileDto, User,
-    UserFilterParams, UserWithSchool, system_roles,
+    BulkCreateUsersDto, BulkCreateUsersResponse, ChangePasswordDto, CreateUserDto,
+    UpdateProfileDto, UpdateUserCustomFieldsDto, User, UserFilterParams, UserListResponse,
+    UserLiteListResponse, UserWithSchool, system_roles,
 };
 use crate::modules::users::service::UserService;
 use crate::state::AppState;
-use crate::utils::auth_helpers::get_admin_school_id;
+use crate::utils::auth_helpers::{
+    get_admin_school_id, get_optional_school_id_for_resource_operation,
+};
+use crate::validator::ValidatedJson;
 use axum::{
     Json,
-    extract::{Query, State, rejection::QueryRejection},
+    extract::{Path, Query, State, rejection::QueryRejection},
+    response::Response,
 };
 use serde::Serialize;
 use tracing::{debug, info, instrument, warn};
 use utoipa::ToSchema;
-use validator::Validate;
+use uuid::Uuid;
 
+// Only referenced from the OpenAPI docs
+#[cfg_attr(not(feature = "scalar"), allow(dead_code))]
 #[derive(Serialize, ToSchema)]
 pub struct ProfileResponse {
     #[serde(flatten)]
@@ -33,7 +43,7 @@ pub struct ProfileResponse {
     summary = "Create user",
     request_body = CreateUserDto,
     responses(
-        (status = 200, description = "User created successfully", body = User),
+        Created<User>,
         (status = 400, description = "Bad request", body = ErrorResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
         (status = 403, description = "Forbidden - requires users:create permission", body = ErrorResponse),
@@ -51,14 +61,10 @@ pub struct ProfileResponse {
 pub async fn create_user(
     State(state): State<AppState>,
     RequireUsersCreate(auth_user): RequireUsersCreate,
-    Json(mut dto): Json<CreateUserDto>,
-) -> Result<Json<User>, AppError> {
+    ValidatedJson(mut dto): ValidatedJson<CreateUserDto>,
+) -> Result<Created<User>, AppError> {
     debug!(email = %dto.email, "Processing user creation request");
 
-    // Validate DTO
-    dto.validate()
-        .map_err(|e| AppError::unprocessable(anyhow::anyhow!("Validation error: {}", e)))?;
-
     let is_sys_admin = is_system_admin_jwt(&auth_user);
 
     // School admins can only create users for their school
@@ -68,14 +74,16 @@ pub async fn create_user(
         // Ensure the new user is assigned to the same school
         dto.school_id = Some(requester_school_id.into_inner().into());
 
-        // School admins cannot create system admins
-        if dto.role_ids.contains(&system_roles::SYSTEM_ADMIN) {
+        // School admins cannot create system or group admins
+        if dto.role_ids.contains(&system_roles::SYSTEM_ADMIN)
+            || dto.role_ids.contains(&system_roles::GROUP_ADMIN)
+        {
             warn!(
                 user.id = %auth_user.0.sub,
-                "School admin attempted to create system admin"
+                "School admin attempted to create system or group admin"
             );
             return Err(AppError::forbidden(
-                "School admins cannot create system admins".to_string(),
+                "School admins cannot create system or group admins".to_string(),
             ));
         }
     }
@@ -87,7 +95,9 @@ pub async fn create_user(
             .await?;
     }
 
-    let user = UserService::create_user(&state.db, dto, state.cache.as_ref()).await?;
+    let user =
+        UserService::create_user(&state.db, dto, state.cache.as_ref(), &state.password_policy)
+            .await?;
 
     info!(
         created_user.id = %user.id,
@@ -95,10 +105,129 @@ pub async fn create_user(
         "User created successfully"
     );
 
-    Ok(Json(user))
+    Ok(Created(user))
+}
+
+/// Create many users at once (requires users:create permission)
+///
+/// Each user is validated and created or rejected on its own; the response
+/// lists the outcome of every item in request order.
+#[utoipa::path(
+    post,
+    path = "/api/users/bulk",
+    summary = "Bulk create users",
+    description = "Creates up to 500 users in one request. Items that fail validation, the \
+        password policy, or an existing email are reported with their reason while the rest \
+        are created. School admins create users in their own school and cannot create system \
+        or group admins.",
+    request_body = BulkCreateUsersDto,
+    responses(
+        (status = 200, description = "Per-user results; some users may have failed", body = BulkCreateUsersResponse),
+        (status = 400, description = "No users, more than 500, or a malformed body", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires users:create permission", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Users"
+)]
+#[instrument(skip(state, auth_user, dto), fields(
+    user.id = %auth_user.0.sub,
+    users.count = dto.users.len()
+))]
+pub async fn create_users_bulk(
+    State(state): State<AppState>,
+    RequireUsersCreate(auth_user): RequireUsersCreate,
+    Json(dto): Json<BulkCreateUsersDto>,
+) -> Result<Json<BulkCreateUsersResponse>, AppError> {
+    // School admins can only create users for their school
+    let school_scope = if is_system_admin_jwt(&auth_user) {
+        None
+    } else {
+        Some(get_admin_school_id(&state.db, &auth_user).await?)
+    };
+
+    let response = UserService::create_users_bulk(
+        &state.db,
+        dto.users,
+        school_scope,
+        state.cache.as_ref(),
+        &state.password_policy,
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+/// Export users matching the list filters (requires users:read permission)
+///
+/// Streams every matching user, ignoring pagination parameters.
+#[utoipa::path(
+    get,
+    path = "/api/users/export",
+    summary = "Export users",
+    params(
+        ExportParams,
+        ("first_name" = Option<String>, Query, description = "Filter by first name (partial match)"),
+        ("last_name" = Option<String>, Query, description = "Filter by last name (partial match)"),
+        ("email" = Option<String>, Query, description = "Filter by email (partial match)"),
+        ("role_id" = Option<String>, Query, description = "Filter by role ID"),
+        ("school_id" = Option<String>, Query, description = "Filter by school ID"),
+        ("custom_fields" = Option<String>, Query, description = "JSON object of custom field values to match, e.g. {\"house\":\"red\"}")
+    ),
+    responses(
+        (status = 200, description = "CSV of matching users, newest first", content_type = "text/csv", body = String),
+        (status = 400, description = "Invalid filter or format", body = ErrorResponse),
+        (status = 401, description = "Unauthorized - missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires users:read permission; users:read:self lists only your own record", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Users"
+)]
+#[instrument(skip(state, auth_user, filters), fields(
+    user.id = %auth_user.0.sub
+))]
+pub async fn export_users(
+    State(state): State<AppState>,
+    RequireUsersRead(auth_user, scope): RequireUsersRead,
+    export: Result<Query<ExportParams>, QueryRejection>,
+    filters: Result<Query<UserFilterParams>, QueryRejection>,
+) -> Result<Response, AppError> {
+    let Query(export) = export.map_err(AppError::query_rejection)?;
+    let Query(mut filters) = filters.map_err(AppError::query_rejection)?;
+    filters.only_user_id = scope.user_id();
+
+    let school_id_filter = if is_system_admin_jwt(&auth_user) {
+        None
+    } else {
+        Some(get_admin_school_id(&state.db, &auth_user).await?)
+    };
+
+    match export.format {
+        ExportFormat::Csv => {
+            state
+                .pools
+                .read_only(async |db| {
+                    timed(
+                        "users.export",
+                        UserService::export_users_csv(db, filters, school_id_filter),
+                    )
+                    .await
+                })
+                .await
+        }
+    }
 }
 
 /// Get all users with pagination and filtering (requires users:read permission)
+///
+/// Pass `cursor` (empty for the first page) to switch to cursor pagination,
+/// which stays fast on large tables but returns no total.
 #[utoipa::path(
     get,
     path = "/api/users",
@@ -109,13 +238,21 @@ pub async fn create_user(
         ("email" = Option<String>, Query, description = "Filter by email (partial match)"),
         ("role" = Option<String>, Query, description = "Filter by role (system_admin, admin, teacher, student)"),
         ("school_id" = Option<String>, Query, description = "Filter by school ID"),
+        ("custom_fields" = Option<String>, Query, description = "JSON object of custom field values to match, e.g. {\"house\":\"red\"}"),
         ("limit" = Option<i64>, Query, description = "Number of items per page (1-100, default: 10)"),
-        ("offset" = Option<i64>, Query, description = "Number of items to skip (default: 0)")
+        ("offset" = Option<i64>, Query, description = "Number of items to skip (default: 0)"),
+        ("sort" = Option<String>, Query, description = "Comma-separated fields to sort by, `-` prefix for descending (default `-created_at`). Sortable: first_name, last_name, email, created_at, updated_at. Ignored with `cursor`"),
+        ("filter" = Option<String>, Query, description = "Comma-separated `field:op:value` clauses, e.g. `school_id:eq:<uuid>,created_at:gte:2024-01-01`. Filterable: first_name, last_name, email, grade_level (eq, ne, contains, in); school_id, level_id, branch_id (eq, ne, in); mfa_enabled (eq, ne); created_at, updated_at (eq, ne, gt, gte, lt, lte)"),
+        ("cursor" = Option<String>, Query, description = "Use cursor pagination: empty for the first page, then the previous page's next_cursor"),
+        ("view" = Option<String>, Query, description = "Set to `lite` for the slimmer mobile view"),
+        ("X-Client" = Option<String>, Header, description = "Set to `mobile-lite` for the slimmer mobile view")
     ),
     responses(
-        (status = 200, description = "Paginated list of users", body = PaginatedUsersResponse),
+        (status = 200, description = "Paginated list of users, offset or cursor based", body = UserListResponse),
+        (status = 200, description = "Lite view of the users list", body = UserLiteListResponse),
+        (status = 400, description = "Invalid filter, sort, or pagination cursor", body = ErrorResponse),
         (status = 401, description = "Unauthorized - missing or invalid token", body = ErrorResponse),
-        (status = 403, description = "Forbidden - requires users:read permission", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires users:read permission; users:read:self lists only your own record", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     security(
@@ -128,10 +265,12 @@ pub async fn create_user(
 ))]
 pub async fn get_users(
     State(state): State<AppState>,
-    RequireUsersRead(auth_user): RequireUsersRead,
+    RequireUsersRead(auth_user, scope): RequireUsersRead,
+    view: ResponseView,
     filters: Result<Query<UserFilterParams>, QueryRejection>,
-) -> Result<Json<PaginatedUsersResponse>, AppError> {
-    let Query(filters) = filters.map_err(AppError::query_rejection)?;
+) -> Result<Response, AppError> {
+    let Query(mut filters) = filters.map_err(AppError::query_rejection)?;
+    filters.only_user_id = scope.user_id();
     debug!(filters = ?filters, "Fetching users with filters");
 
     let is_sys_admin = is_system_admin_jwt(&auth_user);
@@ -142,13 +281,35 @@ pub async fn get_users(
         Some(get_admin_school_id(&state.db, &auth_user).await?)
     };
 
-    let response = UserService::get_users_paginated(
-        &state.db,
-        filters,
-        school_id_filter,
-        state.cache.as_ref(),
-    )
-    .await?;
+    if let Some(pagination) = filters.cursor_pagination() {
+        let page = state
+            .pools
+            .read_only(async |db| {
+                timed(
+                    "users.list",
+                    UserService::get_users_by_cursor(db, filters, pagination, school_id_filter),
+                )
+                .await
+            })
+            .await?;
+        return Ok(view.render(UserListResponse::Cursor(page)));
+    }
+
+    let response = state
+        .pools
+        .read_only(async |db| {
+            timed(
+                "users.list",
+                UserService::get_users_paginated(
+                    db,
+                    filters,
+                    school_id_filter,
+                    state.cache.as_ref(),
+                ),
+            )
+            .await
+        })
+        .await?;
 
     debug!(
         total = %response.meta.total,
@@ -156,7 +317,51 @@ pub async fn get_users(
         "Users fetched successfully"
     );
 
-    Ok(Json(response))
+    Ok(view.render(UserListResponse::Offset(response)))
+}
+
+/// Set a user's custom field values (requires users:update permission)
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}/custom-fields",
+    summary = "Update user custom fields",
+    params(
+        ("id" = Uuid, Path, description = "User ID"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the user as last read; the update is refused if they have changed since")
+    ),
+    request_body = UpdateUserCustomFieldsDto,
+    responses(
+        Conditional<User>,
+        (status = 400, description = "Unknown field or invalid value", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden - requires users:update permission, or users:update:self for your own record", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Users"
+)]
+#[instrument(skip(state, auth_user, dto), fields(user.id = %auth_user.0.sub))]
+pub async fn update_user_custom_fields(
+    State(state): State<AppState>,
+    RequireUsersUpdate(auth_user, scope): RequireUsersUpdate,
+    Path(id): Path<Uuid>,
+    if_match: IfMatch,
+    Json(dto): Json<UpdateUserCustomFieldsDto>,
+) -> Result<Conditional<User>, AppError> {
+    scope.ensure_user(&state.db, id).await?;
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+
+    UserService::update_custom_fields(
+        &state.db,
+        UserId::from(id),
+        school_id,
+        &if_match,
+        dto,
+        state.cache.as_ref(),
+    )
+    .await
 }
 
 /// Get current user profile from JWT token
@@ -165,7 +370,7 @@ pub async fn get_users(
     path = "/api/users/profile",
     summary = "Get current user profile",
     responses(
-        (status = 200, description = "User profile", body = UserWithSchool),
+        WithEtag<UserWithSchool>,
         (status = 401, description = "Unauthorized - missing or invalid token", body = ErrorResponse),
     ),
     security(
@@ -177,7 +382,7 @@ pub async fn get_users(
 pub async fn get_profile(
     State(state): State<AppState>,
     auth_user: AuthUser,
-) -> Result<Json<UserWithSchool>, AppError> {
+) -> Result<WithEtag<UserWithSchool>, AppError> {
     debug!("Fetching user profile");
 
     let user_id = UserId::from(
@@ -186,7 +391,7 @@ pub async fn get_profile(
     );
     let user = UserService::get_user_with_school(&state.db, user_id, state.cache.as_ref()).await?;
 
-    Ok(Json(user))
+    Ok(WithEtag(user))
 }
 
 /// Update current user profile (name only)
@@ -194,9 +399,12 @@ pub async fn get_profile(
     put,
     path = "/api/users/profile",
     summary = "Update user profile",
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag of the profile as last read; the update is refused if it has changed since")
+    ),
     request_body = UpdateProfileDto,
     responses(
-        (status = 200, description = "Profile updated successfully", body = UserWithSchool),
+        Conditional<UserWithSchool>,
         (status = 400, description = "Bad request - validation error", body = ErrorResponse),
         (status = 401, description = "Unauthorized - missing or invalid token", body = ErrorResponse),
     ),
@@ -213,25 +421,27 @@ pub async fn get_profile(
 pub async fn update_profile(
     State(state): State<AppState>,
     auth_user: AuthUser,
-    Json(dto): Json<UpdateProfileDto>,
-) -> Result<Json<UserWithSchool>, AppError> {
+    if_match: IfMatch,
+    ValidatedJson(dto): ValidatedJson<UpdateProfileDto>,
+) -> Result<Conditional<UserWithSchool>, AppError> {
     debug!("Processing profile update request");
 
-    dto.validate()
-        .map_err(|e| AppError::bad_request(anyhow::anyhow!("Validation error: {}", e)))?;
-
     let user_id = UserId::from(
         uuid::Uuid::parse_str(&auth_user.0.sub)
             .map_err(|_| AppError::bad_request(anyhow::anyhow!("Invalid user ID")))?,
     );
 
-    UserService::update_profile(&state.db, user_id, dto, state.cache.as_ref()).await?;
+    let outcome =
+        UserService::update_profile(&state.db, user_id, &if_match, dto, state.cache.as_ref())
+            .await?;
 
     let user = UserService::get_user_with_school(&state.db, user_id, state.cache.as_ref()).await?;
 
-    info!(user.id = %user_id, "Profile updated successfully");
+    if matches!(outcome, Conditional::Updated(_)) {
+        info!(user.id = %user_id, "Profile updated successfully");
+    }
 
-    Ok(Json(user))
+    Ok(outcome.map(|_| user))
 }
 
 /// Change current user password
@@ -254,19 +464,23 @@ pub async fn update_profile(
 pub async fn change_password(
     State(state): State<AppState>,
     auth_user: AuthUser,
-    Json(dto): Json<ChangePasswordDto>,
+    ValidatedJson(dto): ValidatedJson<ChangePasswordDto>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     debug!("Processing password change request");
 
-    dto.validate()
-        .map_err(|e| AppError::unprocessable(anyhow::anyhow!("Validation error: {}", e)))?;
-
     let user_id = UserId::from(
         uuid::Uuid::parse_str(&auth_user.0.sub)
             .map_err(|_| AppError::bad_request(anyhow::anyhow!("Invalid user ID")))?,
     );
 
-    UserService::change_password(&state.db, user_id, dto, state.cache.as_ref()).await?;
+    UserService::change_password(
+        &state.db,
+        user_id,
+        dto,
+        state.cache.as_ref(),
+        &state.password_policy,
+    )
+    .await?;
 
     info!(user.id = %user_id, "Password changed successfully");
 
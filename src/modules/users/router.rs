@@ -1,15 +1,19 @@
 use crate::modules::users::controller::{
-    change_password, create_user, get_profile, get_users, update_profile,
+    change_password, create_user, create_users_bulk, export_users, get_profile, get_users,
+    update_profile, update_user_custom_fields,
 };
 use crate::state::AppState;
 use axum::{
     Router,
-    routing::{get, post},
+    routing::{get, post, put},
 };
 
 pub fn init_users_router() -> Router<AppState> {
     Router::new()
         .route("/", get(get_users).post(create_user))
+        .route("/bulk", post(create_users_bulk))
+        .route("/export", get(export_users))
         .route("/profile", get(get_profile).put(update_profile))
         .route("/profile/change-password", post(change_password))
+        .route("/{id}/custom-fields", put(update_user_custom_fields))
 }
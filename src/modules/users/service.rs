@@ -1,43 +1,114 @@
 use crate::{
+    modules::custom_fields::model::CustomFieldEntity,
+    modules::custom_fields::service::CustomFieldService,
     modules::users::model::{
-        BranchInfo, ChangePasswordDto, CreateUserDto, LevelInfo, PaginatedUsersResponse, RoleInfo,
-        School, SchoolInfo, UpdateProfileDto, User, UserFilterParams, UserWithRelations,
-        UserWithSchool, system_roles,
+        BranchInfo, BulkCreateUsersResponse, BulkItemStatus, BulkUserResult, ChangePasswordDto,
+        CreateUserDto, LevelInfo, MAX_BULK_USERS, PaginatedUsersResponse, RoleInfo, School,
+        SchoolInfo, UpdateProfileDto, UpdateUserCustomFieldsDto, User, UserExportRow,
+        UserFilterParams, UserWithRelations, UserWithSchool, system_roles,
     },
+    modules::webhooks::model::WebhookEvent,
+    modules::webhooks::service::WebhookService,
     utils::{
+        csv_export::{push_arg, stream_csv},
         errors::AppError,
-        pagination::PaginationMeta,
-        password::{hash_password, verify_password},
+        pagination::{Cursor, CursorPage, CursorPaginationParams, PaginationMeta},
+        password::{PasswordPolicy, hash_password, verify_password},
     },
 };
 #[cfg(feature = "observability")]
 use chalkbyte_observability::metrics;
 use anyhow::Context;
+use axum::response::Response;
 use chalkbyte_cache::{RedisCache, hash_filters, invalidate, keys};
+use chalkbyte_core::filtering::FieldType;
+use chalkbyte_core::{Conditional, ErrorCode, Filterable, IfMatch, Sortable};
 use chalkbyte_models::ids::{BranchId, LevelId, RoleId, SchoolId, UserId};
-use sqlx::{PgPool, Row};
-use std::collections::HashMap;
+use rayon::prelude::*;
+use sqlx::{
+    PgPool, Row,
+    postgres::{PgArguments, PgRow},
+};
+use std::collections::{HashMap, HashSet};
 use tracing::{debug, error, info, instrument, warn};
 use uuid::Uuid;
+use validator::Validate;
+
+/// Fields the user list can be sorted by
+const USER_SORT: Sortable = Sortable {
+    columns: &[
+        ("first_name", "u.first_name"),
+        ("last_name", "u.last_name"),
+        ("email", "u.email"),
+        ("created_at", "u.created_at"),
+        ("updated_at", "u.updated_at"),
+    ],
+    default: "-created_at",
+    tiebreaker: "u.id",
+};
+
+/// Fields the user list can be filtered by with `filter`
+const USER_FILTER: Filterable = Filterable {
+    fields: &[
+        ("first_name", "u.first_name", FieldType::Text),
+        ("last_name", "u.last_name", FieldType::Text),
+        ("email", "u.email", FieldType::Text),
+        ("grade_level", "u.grade_level", FieldType::Text),
+        ("school_id", "u.school_id", FieldType::Uuid),
+        ("level_id", "u.level_id", FieldType::Uuid),
+        ("branch_id", "u.branch_id", FieldType::Uuid),
+        ("mfa_enabled", "u.mfa_enabled", FieldType::Boolean),
+        ("created_at", "u.created_at", FieldType::Timestamp),
+        ("updated_at", "u.updated_at", FieldType::Timestamp),
+    ],
+};
+
+/// Records why an item of a bulk create request failed.
+fn record_failure(results: &mut [Option<BulkUserResult>], index: usize, email: &str, e: AppError) {
+    results[index] = Some(BulkUserResult {
+        index,
+        email: email.to_string(),
+        status: BulkItemStatus::Failed,
+        user: None,
+        error: Some(e.error.to_string()),
+        code: Some(e.code),
+    });
+}
 
 pub struct UserService;
 
 impl UserService {
-    #[instrument(skip(db, dto), fields(user.email = %dto.email))]
+    #[instrument(skip(db, dto, password_policy), fields(user.email = %dto.email))]
     pub async fn create_user(
         db: &PgPool,
         dto: CreateUserDto,
         cache: Option<&RedisCache>,
+        password_policy: &PasswordPolicy,
     ) -> Result<User, AppError> {
         debug!(email = %dto.email, "Creating new user");
 
+        password_policy.validate(
+            &dto.password,
+            &[&dto.first_name, &dto.last_name, dto.email.as_str()],
+        )?;
         let password_hash = hash_password(&dto.password)?;
+        let custom_fields = CustomFieldService::resolve_values(
+            db,
+            dto.school_id,
+            CustomFieldEntity::User,
+            &serde_json::Value::Null,
+            &dto.custom_fields.clone().unwrap_or_default(),
+            true,
+        )
+        .await?;
+
+        let mut tx = db.begin().await?;
 
         let user = sqlx::query_as::<_, User>(
             r#"
-            INSERT INTO users (first_name, last_name, email, password, school_id)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, first_name, last_name, email, school_id, level_id, branch_id, date_of_birth, grade_level, created_at, updated_at
+            INSERT INTO users (first_name, last_name, email, password, school_id, custom_fields)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, first_name, last_name, email, school_id, level_id, branch_id, date_of_birth, grade_level, custom_fields, created_at, updated_at
             "#,
         )
         .bind(&dto.first_name)
@@ -45,16 +116,15 @@ impl UserService {
         .bind(dto.email.as_str())
         .bind(&password_hash)
         .bind(dto.school_id)
-        .fetch_one(db)
+        .bind(&custom_fields)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| {
             if let sqlx::Error::Database(db_err) = &e
                 && db_err.is_unique_violation()
             {
                 warn!(email = %dto.email, "Attempted to create user with existing email");
-                return AppError::unprocessable(anyhow::anyhow!(
-                    "User with this email already exists"
-                ));
+                return AppError::from_code(ErrorCode::EmailTaken);
             }
             error!(error = %e, "Failed to create user");
             AppError::database(anyhow::Error::new(e).context("Failed to insert user"))
@@ -68,7 +138,7 @@ impl UserService {
                 )
                 .bind(user.id)
                 .bind(role_id)
-                .execute(db)
+                .execute(&mut *tx)
                 .await
                 .map_err(|e| {
                     error!(error = %e, "Failed to assign role to user");
@@ -77,6 +147,24 @@ impl UserService {
             }
         }
 
+        if let Some(school_id) = user.school_id {
+            WebhookService::enqueue(
+                &mut *tx,
+                school_id,
+                WebhookEvent::UserCreated,
+                &serde_json::json!({
+                    "user_id": user.id,
+                    "email": user.email,
+                    "first_name": user.first_name,
+                    "last_name": user.last_name,
+                    "role_ids": dto.role_ids,
+                }),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
         // Track metrics based on first role assigned
         #[cfg(feature = "observability")]
         let role_name = if let Some(first_role_id) = dto.role_ids.first() {
@@ -96,6 +184,321 @@ impl UserService {
         Ok(user)
     }
 
+    /// Creates many users in one statement, each item succeeding or failing
+    /// on its own.
+    ///
+    /// Items are validated like [`Self::create_user`], then checked together
+    /// against registered emails, schools, and roles. Passwords of the items
+    /// left are hashed in parallel on the rayon pool and the users inserted
+    /// in a single multi-row statement; an email registered meanwhile fails
+    /// only its item. With `school_scope`, every user goes into that school
+    /// and items asking for system or group admin roles fail.
+    #[instrument(skip(db, dtos, cache, password_policy), fields(users.count = dtos.len()))]
+    pub async fn create_users_bulk(
+        db: &PgPool,
+        dtos: Vec<CreateUserDto>,
+        school_scope: Option<SchoolId>,
+        cache: Option<&RedisCache>,
+        password_policy: &PasswordPolicy,
+    ) -> Result<BulkCreateUsersResponse, AppError> {
+        if dtos.is_empty() || dtos.len() > MAX_BULK_USERS {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Between 1 and {} users can be created at once",
+                MAX_BULK_USERS
+            )));
+        }
+
+        let mut results: Vec<Option<BulkUserResult>> = vec![None; dtos.len()];
+
+        // Checks each item can make on its own
+        let mut seen_emails = HashSet::new();
+        let mut pending = Vec::with_capacity(dtos.len());
+        for (index, mut dto) in dtos.into_iter().enumerate() {
+            if let Some(school_id) = school_scope {
+                dto.school_id = Some(school_id);
+            }
+            let email = dto.email.as_str().to_string();
+
+            let checked = dto
+                .validate()
+                .map_err(AppError::validation)
+                .and_then(|()| {
+                    if school_scope.is_some()
+                        && (dto.role_ids.contains(&system_roles::SYSTEM_ADMIN)
+                            || dto.role_ids.contains(&system_roles::GROUP_ADMIN))
+                    {
+                        return Err(AppError::forbidden(
+                            "School admins cannot create system or group admins".to_string(),
+                        ));
+                    }
+                    password_policy.validate(
+                        &dto.password,
+                        &[&dto.first_name, &dto.last_name, dto.email.as_str()],
+                    )
+                })
+                .and_then(|()| {
+                    if seen_emails.insert(email.to_lowercase()) {
+                        Ok(())
+                    } else {
+                        Err(AppError::bad_request(anyhow::anyhow!(
+                            "Email appears more than once in the request"
+                        )))
+                    }
+                });
+
+            match checked {
+                Ok(()) => pending.push((index, dto)),
+                Err(e) => record_failure(&mut results, index, &email, e),
+            }
+        }
+
+        // Checks against what is already stored, one query each
+        let emails: Vec<String> = pending
+            .iter()
+            .map(|(_, dto)| dto.email.as_str().to_lowercase())
+            .collect();
+        let taken: HashSet<String> = sqlx::query_scalar::<_, String>(
+            "SELECT LOWER(email) FROM users WHERE LOWER(email) = ANY($1)",
+        )
+        .bind(&emails)
+        .fetch_all(db)
+        .await
+        .context("Failed to check existing emails")
+        .map_err(AppError::database)?
+        .into_iter()
+        .collect();
+
+        let school_ids: Vec<SchoolId> = pending
+            .iter()
+            .filter_map(|(_, dto)| dto.school_id)
+            .collect();
+        let schools: HashSet<SchoolId> =
+            sqlx::query_scalar::<_, SchoolId>("SELECT id FROM schools WHERE id = ANY($1)")
+                .bind(&school_ids)
+                .fetch_all(db)
+                .await
+                .context("Failed to check schools")
+                .map_err(AppError::database)?
+                .into_iter()
+                .collect();
+
+        let role_ids: Vec<RoleId> = pending
+            .iter()
+            .flat_map(|(_, dto)| dto.role_ids.iter().copied())
+            .collect();
+        // Role ID to the school of a custom role, or None for system roles
+        let roles: HashMap<RoleId, Option<SchoolId>> =
+            sqlx::query_as::<_, (RoleId, Option<SchoolId>)>(
+                "SELECT id, school_id FROM roles WHERE id = ANY($1)",
+            )
+            .bind(&role_ids)
+            .fetch_all(db)
+            .await
+            .context("Failed to check roles")
+            .map_err(AppError::database)?
+            .into_iter()
+            .collect();
+
+        let mut ready = Vec::with_capacity(pending.len());
+        for (index, dto) in pending {
+            let email = dto.email.as_str().to_string();
+            if taken.contains(&email.to_lowercase()) {
+                record_failure(
+                    &mut results,
+                    index,
+                    &email,
+                    AppError::from_code(ErrorCode::EmailTaken),
+                );
+                continue;
+            }
+            if let Some(school_id) = dto.school_id
+                && !schools.contains(&school_id)
+            {
+                record_failure(
+                    &mut results,
+                    index,
+                    &email,
+                    AppError::from_code(ErrorCode::SchoolNotFound),
+                );
+                continue;
+            }
+            if let Some(role_id) = dto.role_ids.iter().find(|role_id| {
+                !roles
+                    .get(role_id)
+                    .is_some_and(|school| school.is_none() || *school == dto.school_id)
+            }) {
+                let e = AppError::bad_request(anyhow::anyhow!(
+                    "Role {} does not exist in the user's school",
+                    role_id
+                ));
+                record_failure(&mut results, index, &email, e);
+                continue;
+            }
+
+            match CustomFieldService::resolve_values(
+                db,
+                dto.school_id,
+                CustomFieldEntity::User,
+                &serde_json::Value::Null,
+                &dto.custom_fields.clone().unwrap_or_default(),
+                true,
+            )
+            .await
+            {
+                Ok(custom_fields) => ready.push((index, dto, custom_fields)),
+                Err(e) => record_failure(&mut results, index, &email, e),
+            }
+        }
+
+        // bcrypt is slow on purpose; hash on the rayon pool, off the runtime
+        let passwords: Vec<String> = ready
+            .iter()
+            .map(|(_, dto, _)| dto.password.clone())
+            .collect();
+        let hashes = tokio::task::spawn_blocking(move || {
+            passwords
+                .par_iter()
+                .map(|password| hash_password(password))
+                .collect::<Vec<_>>()
+        })
+        .await
+        .context("Password hashing task failed")
+        .map_err(AppError::internal)?;
+
+        let mut rows = Vec::with_capacity(ready.len());
+        for ((index, dto, custom_fields), hash) in ready.into_iter().zip(hashes) {
+            match hash {
+                Ok(hash) => rows.push((index, dto, custom_fields, hash)),
+                Err(e) => {
+                    let email = dto.email.as_str().to_string();
+                    record_failure(&mut results, index, &email, e);
+                }
+            }
+        }
+
+        let mut tx = db.begin().await?;
+
+        let users = sqlx::query_as::<_, User>(
+            r#"
+            INSERT INTO users (first_name, last_name, email, password, school_id, custom_fields)
+            SELECT * FROM UNNEST($1::text[], $2::text[], $3::text[], $4::text[], $5::uuid[], $6::jsonb[])
+            ON CONFLICT (email) DO NOTHING
+            RETURNING id, first_name, last_name, email, school_id, level_id, branch_id, date_of_birth, grade_level, custom_fields, created_at, updated_at
+            "#,
+        )
+        .bind(rows.iter().map(|(_, dto, _, _)| dto.first_name.as_str()).collect::<Vec<_>>())
+        .bind(rows.iter().map(|(_, dto, _, _)| dto.last_name.as_str()).collect::<Vec<_>>())
+        .bind(rows.iter().map(|(_, dto, _, _)| dto.email.as_str()).collect::<Vec<_>>())
+        .bind(rows.iter().map(|(_, _, _, hash)| hash.as_str()).collect::<Vec<_>>())
+        .bind(rows.iter().map(|(_, dto, _, _)| dto.school_id).collect::<Vec<_>>())
+        .bind(rows.iter().map(|(_, _, fields, _)| fields).collect::<Vec<_>>())
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to insert users")
+        .map_err(AppError::database)?;
+
+        let mut created: HashMap<String, User> = users
+            .into_iter()
+            .map(|user| (user.email.as_str().to_string(), user))
+            .collect();
+
+        let mut role_user_ids = Vec::new();
+        let mut role_role_ids = Vec::new();
+        let mut created_users = Vec::with_capacity(created.len());
+        for (index, dto, _, _) in rows {
+            let email = dto.email.as_str().to_string();
+            let Some(user) = created.remove(&email) else {
+                // Registered between the check and the insert
+                record_failure(
+                    &mut results,
+                    index,
+                    &email,
+                    AppError::from_code(ErrorCode::EmailTaken),
+                );
+                continue;
+            };
+
+            for role_id in &dto.role_ids {
+                role_user_ids.push(user.id);
+                role_role_ids.push(*role_id);
+            }
+            if let Some(school_id) = user.school_id {
+                WebhookService::enqueue(
+                    &mut *tx,
+                    school_id,
+                    WebhookEvent::UserCreated,
+                    &serde_json::json!({
+                        "user_id": user.id,
+                        "email": user.email,
+                        "first_name": user.first_name,
+                        "last_name": user.last_name,
+                        "role_ids": dto.role_ids,
+                    }),
+                )
+                .await?;
+            }
+            created_users.push((index, dto.role_ids, user));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_roles (user_id, role_id)
+            SELECT * FROM UNNEST($1::uuid[], $2::uuid[])
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(&role_user_ids)
+        .bind(&role_role_ids)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to assign roles")
+        .map_err(AppError::database)?;
+
+        tx.commit().await?;
+
+        let mut schools = HashSet::new();
+        for (index, _role_ids, user) in created_users {
+            #[cfg(feature = "observability")]
+            metrics::track_user_created(
+                &_role_ids
+                    .first()
+                    .map_or("none", |role_id| {
+                        system_roles::get_name(role_id).unwrap_or("custom")
+                    })
+                    .to_lowercase(),
+            );
+            schools.insert(user.school_id);
+            results[index] = Some(BulkUserResult {
+                index,
+                email: user.email.as_str().to_string(),
+                status: BulkItemStatus::Created,
+                user: Some(user),
+                error: None,
+                code: None,
+            });
+        }
+        for school_id in schools {
+            invalidate::user(cache, None, school_id.map(Into::into)).await;
+        }
+
+        let results: Vec<BulkUserResult> = results.into_iter().flatten().collect();
+        let created_count = results
+            .iter()
+            .filter(|r| r.status == BulkItemStatus::Created)
+            .count();
+        info!(
+            users.created = created_count,
+            users.failed = results.len() - created_count,
+            "Bulk user creation finished"
+        );
+
+        Ok(BulkCreateUsersResponse {
+            created_count,
+            failed_count: results.len() - created_count,
+            results,
+        })
+    }
+
     #[instrument(skip(db, cache), fields(school_id = ?school_id_filter))]
     pub async fn get_users_paginated(
         db: &PgPool,
@@ -128,13 +531,216 @@ impl UserService {
             "Fetching paginated users"
         );
 
-        let mut conditions = vec![];
-        let mut param_count = 0;
+        let order_by = USER_SORT.order_by(&filters.sort)?;
+        let conditions = Self::user_filter_conditions(&filters, school_id_filter)?;
+        let where_clause = conditions
+            .iter()
+            .map(|(_, c, _)| format!(" AND {}", c))
+            .collect::<String>();
+        let param_count = conditions.len();
+
+        let mut query = Self::user_list_query(&filters);
+        query.push_str(&where_clause);
+
+        // Build count query
+        let count_query = if filters.role_id.is_some() {
+            format!(
+                "SELECT COUNT(DISTINCT u.id) FROM users u INNER JOIN user_roles ur ON u.id = ur.user_id WHERE 1=1{}",
+                where_clause
+            )
+        } else {
+            format!("SELECT COUNT(*) FROM users u WHERE 1=1{}", where_clause)
+        };
+
+        query.push_str(&format!(
+            " ORDER BY {} LIMIT ${} OFFSET ${}",
+            order_by,
+            param_count + 1,
+            param_count + 2
+        ));
+
+        let mut query_builder = sqlx::query(&query);
+        let mut count_query_builder = sqlx::query_scalar::<_, i64>(&count_query);
+
+        for (_, _, value) in &conditions {
+            if value.starts_with('%') {
+                query_builder = query_builder.bind(value);
+                count_query_builder = count_query_builder.bind(value);
+            } else if let Ok(uuid_val) = Uuid::parse_str(value) {
+                query_builder = query_builder.bind(uuid_val);
+                count_query_builder = count_query_builder.bind(uuid_val);
+            } else {
+                query_builder = query_builder.bind(value);
+                count_query_builder = count_query_builder.bind(value);
+            }
+        }
+
+        query_builder = query_builder.bind(limit).bind(offset);
+
+        let rows = query_builder
+            .fetch_all(db)
+            .await
+            .context("Failed to fetch paginated users")
+            .map_err(|e| {
+                error!(error = %e, "Database error fetching users");
+                AppError::database(e)
+            })?;
+
+        let users = Self::users_with_relations(db, rows).await?;
+
+        let total = count_query_builder
+            .fetch_one(db)
+            .await
+            .context("Failed to count users")
+            .map_err(|e| {
+                error!(error = %e, "Database error counting users");
+                AppError::database(e)
+            })?;
+
+        let meta = PaginationMeta {
+            total,
+            limit,
+            offset: if page.is_none() { Some(offset) } else { None },
+            page,
+            has_more: offset + limit < total,
+        };
+
+        debug!(
+            total = %total,
+            returned = %users.len(),
+            "Users fetched successfully"
+        );
+
+        let response = PaginatedUsersResponse { data: users, meta };
+
+        // Cache the result
+        if let Some(cache) = cache
+            && let Err(e) = cache.set(&cache_key, &response).await
+        {
+            warn!(error = %e, "Failed to cache users list");
+        }
+
+        Ok(response)
+    }
+
+    /// List users newest first using keyset pagination on `(created_at, id)`.
+    ///
+    /// Takes the same filters as [`Self::get_users_paginated`] but does not
+    /// count the total, so deep pages stay cheap on large tables.
+    #[instrument(skip(db), fields(school_id = ?school_id_filter))]
+    pub async fn get_users_by_cursor(
+        db: &PgPool,
+        filters: UserFilterParams,
+        pagination: CursorPaginationParams,
+        school_id_filter: Option<SchoolId>,
+    ) -> Result<CursorPage<UserWithRelations>, AppError> {
+        let limit = pagination.limit();
+        let after = pagination.after()?;
+
+        let conditions = Self::user_filter_conditions(&filters, school_id_filter)?;
+        let mut param_count = conditions.len();
+
+        let mut query = Self::user_list_query(&filters);
+        for (_, condition, _) in &conditions {
+            query.push_str(&format!(" AND {}", condition));
+        }
+        if after.is_some() {
+            query.push_str(&format!(
+                " AND (u.created_at, u.id) < (${}, ${})",
+                param_count + 1,
+                param_count + 2
+            ));
+            param_count += 2;
+        }
+        query.push_str(&format!(
+            " ORDER BY u.created_at DESC, u.id DESC LIMIT ${}",
+            param_count + 1
+        ));
+
+        let mut query_builder = sqlx::query(&query);
+        for (_, _, value) in &conditions {
+            if value.starts_with('%') {
+                query_builder = query_builder.bind(value);
+            } else if let Ok(uuid_val) = Uuid::parse_str(value) {
+                query_builder = query_builder.bind(uuid_val);
+            } else {
+                query_builder = query_builder.bind(value);
+            }
+        }
+        if let Some(after) = after {
+            query_builder = query_builder.bind(after.created_at).bind(after.id);
+        }
+        query_builder = query_builder.bind(limit + 1);
+
+        let rows = query_builder
+            .fetch_all(db)
+            .await
+            .context("Failed to fetch users by cursor")
+            .map_err(|e| {
+                error!(error = %e, "Database error fetching users");
+                AppError::database(e)
+            })?;
+
+        let users = Self::users_with_relations(db, rows).await?;
+
+        Ok(CursorPage::from_rows(users, limit, |user| {
+            Cursor::new(user.created_at, user.id.into_inner())
+        }))
+    }
+
+    /// Stream every user matching the list filters as CSV, newest first.
+    #[instrument(skip(db), fields(school_id = ?school_id_filter))]
+    pub async fn export_users_csv(
+        db: &PgPool,
+        filters: UserFilterParams,
+        school_id_filter: Option<SchoolId>,
+    ) -> Result<Response, AppError> {
+        let conditions = Self::user_filter_conditions(&filters, school_id_filter)?;
+
+        let mut query = String::from(
+            r#"SELECT u.id, u.first_name, u.last_name, u.email,
+                s.name AS school, l.name AS level, b.name AS branch,
+                COALESCE((
+                    SELECT string_agg(r.name, ';' ORDER BY r.name)
+                    FROM user_roles uro
+                    INNER JOIN roles r ON r.id = uro.role_id
+                    WHERE uro.user_id = u.id
+                ), '') AS roles,
+                u.created_at
+            FROM users u
+            LEFT JOIN schools s ON u.school_id = s.id
+            LEFT JOIN levels l ON u.level_id = l.id
+            LEFT JOIN branches b ON u.branch_id = b.id"#,
+        );
+        if filters.role_id.is_some() {
+            query.push_str(" INNER JOIN user_roles ur ON u.id = ur.user_id");
+        }
+        query.push_str(" WHERE 1=1");
+        for (_, condition, _) in &conditions {
+            query.push_str(&format!(" AND {}", condition));
+        }
+        query.push_str(" ORDER BY u.created_at DESC, u.id DESC");
+
+        let mut args = PgArguments::default();
+        for (_, _, value) in &conditions {
+            if value.starts_with('%') {
+                push_arg(&mut args, value)?;
+            } else if let Ok(uuid_val) = Uuid::parse_str(value) {
+                push_arg(&mut args, uuid_val)?;
+            } else {
+                push_arg(&mut args, value)?;
+            }
+        }
 
+        stream_csv::<UserExportRow>(db, &query, args, "users").await
+    }
+
+    /// Build the `SELECT` for listing users, up to and including `WHERE 1=1`.
+    fn user_list_query(filters: &UserFilterParams) -> String {
         // Main query with LEFT JOINs for school, level, branch
         let mut query = String::from(
             r#"SELECT DISTINCT
-                u.id, u.first_name, u.last_name, u.email, u.date_of_birth, u.grade_level, u.created_at, u.updated_at,
+                u.id, u.first_name, u.last_name, u.email, u.date_of_birth, u.grade_level, u.custom_fields, u.created_at, u.updated_at,
                 s.id as school_id, s.name as school_name, s.address as school_address,
                 l.id as level_id, l.name as level_name, l.description as level_description,
                 b.id as branch_id, b.name as branch_name, b.description as branch_description
@@ -150,7 +756,17 @@ impl UserService {
         }
 
         query.push_str(" WHERE 1=1");
+        query
+    }
 
+    /// Build the `WHERE` conditions for the user list filters as
+    /// `(parameter number, condition, value)`.
+    fn user_filter_conditions(
+        filters: &UserFilterParams,
+        school_id_filter: Option<SchoolId>,
+    ) -> Result<Vec<(usize, String, String)>, AppError> {
+        let mut conditions = vec![];
+        let mut param_count = 0;
         if let Some(ref first_name) = filters.first_name {
             param_count += 1;
             conditions.push((
@@ -187,6 +803,17 @@ impl UserService {
             ));
         }
 
+        if let Some(custom_fields) =
+            CustomFieldService::parse_filter(filters.custom_fields.as_deref())?
+        {
+            param_count += 1;
+            conditions.push((
+                param_count,
+                format!("u.custom_fields @> ${}::jsonb", param_count),
+                custom_fields.to_string(),
+            ));
+        }
+
         if let Some(sid) = filters.school_id {
             param_count += 1;
             conditions.push((
@@ -205,62 +832,31 @@ impl UserService {
             ));
         }
 
-        for (_, condition, _) in &conditions {
-            query.push_str(&format!(" AND {}", condition));
+        if let Some(user_id) = filters.only_user_id {
+            param_count += 1;
+            conditions.push((
+                param_count,
+                format!("u.id = ${}", param_count),
+                user_id.to_string(),
+            ));
         }
 
-        // Build count query
-        let count_query = if filters.role_id.is_some() {
-            format!(
-                "SELECT COUNT(DISTINCT u.id) FROM users u INNER JOIN user_roles ur ON u.id = ur.user_id WHERE 1=1{}",
-                conditions
-                    .iter()
-                    .map(|(_, c, _)| format!(" AND {}", c))
-                    .collect::<String>()
-            )
-        } else {
-            format!(
-                "SELECT COUNT(*) FROM users u WHERE 1=1{}",
-                conditions
-                    .iter()
-                    .map(|(_, c, _)| format!(" AND {}", c))
-                    .collect::<String>()
-            )
-        };
-
-        query.push_str(&format!(
-            " ORDER BY u.created_at DESC LIMIT ${} OFFSET ${}",
-            param_count + 1,
-            param_count + 2
-        ));
-
-        let mut query_builder = sqlx::query(&query);
-        let mut count_query_builder = sqlx::query_scalar::<_, i64>(&count_query);
-
-        for (_, _, value) in &conditions {
-            if value.starts_with('%') {
-                query_builder = query_builder.bind(value);
-                count_query_builder = count_query_builder.bind(value);
-            } else if let Ok(uuid_val) = Uuid::parse_str(value) {
-                query_builder = query_builder.bind(uuid_val);
-                count_query_builder = count_query_builder.bind(uuid_val);
-            } else {
-                query_builder = query_builder.bind(value);
-                count_query_builder = count_query_builder.bind(value);
-            }
+        for (condition, value) in USER_FILTER
+            .parse(&filters.filter)?
+            .sql_conditions(param_count + 1)
+        {
+            param_count += 1;
+            conditions.push((param_count, condition, value));
         }
 
-        query_builder = query_builder.bind(limit).bind(offset);
-
-        let rows = query_builder
-            .fetch_all(db)
-            .await
-            .context("Failed to fetch paginated users")
-            .map_err(|e| {
-                error!(error = %e, "Database error fetching users");
-                AppError::database(e)
-            })?;
+        Ok(conditions)
+    }
 
+    /// Attach roles to user list rows and convert them to [`UserWithRelations`].
+    async fn users_with_relations(
+        db: &PgPool,
+        rows: Vec<PgRow>,
+    ) -> Result<Vec<UserWithRelations>, AppError> {
         // Collect user IDs for batch role fetch
         let user_ids: Vec<UserId> = rows
             .iter()
@@ -372,6 +968,11 @@ impl UserService {
                             anyhow::Error::new(e).context("Failed to get grade_level"),
                         )
                     })?,
+                    custom_fields: row.try_get("custom_fields").map_err(|e| {
+                        AppError::database(
+                            anyhow::Error::new(e).context("Failed to get custom_fields"),
+                        )
+                    })?,
                     school,
                     level,
                     branch,
@@ -390,41 +991,7 @@ impl UserService {
             })
             .collect();
 
-        let users = users?;
-
-        let total = count_query_builder
-            .fetch_one(db)
-            .await
-            .context("Failed to count users")
-            .map_err(|e| {
-                error!(error = %e, "Database error counting users");
-                AppError::database(e)
-            })?;
-
-        let meta = PaginationMeta {
-            total,
-            limit,
-            offset: if page.is_none() { Some(offset) } else { None },
-            page,
-            has_more: offset + limit < total,
-        };
-
-        debug!(
-            total = %total,
-            returned = %users.len(),
-            "Users fetched successfully"
-        );
-
-        let response = PaginatedUsersResponse { data: users, meta };
-
-        // Cache the result
-        if let Some(cache) = cache
-            && let Err(e) = cache.set(&cache_key, &response).await
-        {
-            warn!(error = %e, "Failed to cache users list");
-        }
-
-        Ok(response)
+        users
     }
 
     #[instrument(skip(db, cache), fields(user.id = %id))]
@@ -446,7 +1013,7 @@ impl UserService {
 
         let user = sqlx::query_as::<_, User>(
             r#"
-            SELECT id, first_name, last_name, email, school_id, level_id, branch_id, date_of_birth, grade_level, created_at, updated_at
+            SELECT id, first_name, last_name, email, school_id, level_id, branch_id, date_of_birth, grade_level, custom_fields, created_at, updated_at
             FROM users WHERE id = $1
             "#,
         )
@@ -460,7 +1027,7 @@ impl UserService {
         })?
         .ok_or_else(|| {
             warn!(user.id = %id, "User not found");
-            AppError::not_found(anyhow::anyhow!("User not found"))
+            AppError::from_code(ErrorCode::UserNotFound)
         })?;
 
         debug!(user.email = %user.email, "User fetched successfully");
@@ -475,6 +1042,67 @@ impl UserService {
         Ok(user)
     }
 
+    /// Set a user's custom field values.
+    ///
+    /// `school_id` limits the update to users of that school. The user is
+    /// left unchanged when they no longer match `if_match`.
+    #[instrument(skip(db, dto, cache), fields(user.id = %id))]
+    pub async fn update_custom_fields(
+        db: &PgPool,
+        id: UserId,
+        school_id: Option<SchoolId>,
+        if_match: &IfMatch,
+        dto: UpdateUserCustomFieldsDto,
+        cache: Option<&RedisCache>,
+    ) -> Result<Conditional<User>, AppError> {
+        let user = Self::get_user(db, id, None).await?;
+
+        if school_id.is_some_and(|sid| user.school_id != Some(sid)) {
+            return Err(AppError::from_code(ErrorCode::UserNotFound));
+        }
+        if !if_match.matches(&user) {
+            return Ok(Conditional::PreconditionFailed(user));
+        }
+        let expected_version = (!if_match.is_any()).then_some(user.updated_at);
+
+        let custom_fields = CustomFieldService::resolve_values(
+            db,
+            user.school_id,
+            CustomFieldEntity::User,
+            &user.custom_fields,
+            &dto.custom_fields,
+            false,
+        )
+        .await?;
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users SET custom_fields = $2, updated_at = NOW()
+            WHERE id = $1 AND ($3::timestamptz IS NULL OR updated_at = $3)
+            RETURNING id, first_name, last_name, email, school_id, level_id, branch_id, date_of_birth, grade_level, custom_fields, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(&custom_fields)
+        .bind(expected_version)
+        .fetch_optional(db)
+        .await
+        .context("Failed to update user custom fields")
+        .map_err(AppError::database)?;
+
+        // Changed or deleted since it was read
+        let Some(user) = user else {
+            return Ok(Conditional::PreconditionFailed(
+                Self::get_user(db, id, None).await?,
+            ));
+        };
+
+        invalidate::user(cache, Some(id.into()), user.school_id.map(Into::into)).await;
+
+        info!(user.id = %id, "User custom fields updated");
+        Ok(Conditional::Updated(user))
+    }
+
     #[instrument(skip(db, cache), fields(user.id = %id))]
     pub async fn get_user_with_school(
         db: &PgPool,
@@ -487,7 +1115,7 @@ impl UserService {
 
         let school = if let Some(school_id) = user.school_id {
             sqlx::query_as::<_, School>(
-                r#"SELECT id, name, address, created_at, updated_at FROM schools WHERE id = $1"#,
+                r#"SELECT id, name, address, logo_path, timezone, locale, created_at, updated_at FROM schools WHERE id = $1"#,
             )
             .bind(school_id)
             .fetch_optional(db)
@@ -510,15 +1138,23 @@ impl UserService {
         Ok(UserWithSchool { user, school })
     }
 
+    /// Update a user's name. The user is left unchanged when they no longer
+    /// match `if_match`.
     #[instrument(skip(db, dto), fields(user.id = %user_id))]
     pub async fn update_profile(
         db: &PgPool,
         user_id: UserId,
+        if_match: &IfMatch,
         dto: UpdateProfileDto,
         cache: Option<&RedisCache>,
-    ) -> Result<User, AppError> {
+    ) -> Result<Conditional<User>, AppError> {
         debug!("Updating user profile");
 
+        let existing = Self::get_user(db, user_id, None).await?;
+        if !if_match.matches(&existing) {
+            return Ok(Conditional::PreconditionFailed(existing));
+        }
+
         // Build dynamic update query
         let mut updates = vec![];
         let mut param_count = 1; // $1 is user_id
@@ -534,13 +1170,14 @@ impl UserService {
         }
 
         if updates.is_empty() {
-            return Self::get_user(db, user_id, cache).await;
+            return Ok(Conditional::Updated(existing));
         }
 
         updates.push("updated_at = NOW()".to_string());
+        param_count += 1;
 
         let query = format!(
-            "UPDATE users SET {} WHERE id = $1 RETURNING id, first_name, last_name, email, school_id, level_id, branch_id, date_of_birth, grade_level, created_at, updated_at",
+            "UPDATE users SET {} WHERE id = $1 AND (${param_count}::timestamptz IS NULL OR updated_at = ${param_count}) RETURNING id, first_name, last_name, email, school_id, level_id, branch_id, date_of_birth, grade_level, custom_fields, created_at, updated_at",
             updates.join(", ")
         );
 
@@ -555,7 +1192,8 @@ impl UserService {
         }
 
         let user = query_builder
-            .fetch_one(db)
+            .bind((!if_match.is_any()).then_some(existing.updated_at))
+            .fetch_optional(db)
             .await
             .context("Failed to update profile")
             .map_err(|e| {
@@ -563,19 +1201,27 @@ impl UserService {
                 AppError::database(e)
             })?;
 
+        // Changed or deleted since it was read
+        let Some(user) = user else {
+            return Ok(Conditional::PreconditionFailed(
+                Self::get_user(db, user_id, None).await?,
+            ));
+        };
+
         // Invalidate user caches
         invalidate::user(cache, Some(user.id.into()), user.school_id.map(Into::into)).await;
 
         info!(user.id = %user.id, "Profile updated successfully");
-        Ok(user)
+        Ok(Conditional::Updated(user))
     }
 
-    #[instrument(skip(db, dto, cache), fields(user.id = %user_id))]
+    #[instrument(skip(db, dto, cache, password_policy), fields(user.id = %user_id))]
     pub async fn change_password(
         db: &PgPool,
         user_id: UserId,
         dto: ChangePasswordDto,
         cache: Option<&RedisCache>,
+        password_policy: &PasswordPolicy,
     ) -> Result<(), AppError> {
         debug!("Changing user password");
 
@@ -598,13 +1244,72 @@ impl UserService {
             ));
         }
 
-        // Hash and update new password
-        let new_hash = hash_password(&dto.new_password)?;
+        Self::set_password(db, user_id, &dto.new_password, password_policy).await?;
+
+        // Invalidate user caches
+        invalidate::user(cache, Some(user_id.into()), None).await;
+
+        info!(user.id = %user_id, "Password changed successfully");
+        Ok(())
+    }
 
+    /// Replace a user's password, enforcing the password policy.
+    ///
+    /// The new password is checked against the user's name and email and,
+    /// when the policy keeps a history, against their current and recent
+    /// passwords. The replaced hash is moved into `password_history`, which
+    /// is pruned to what the policy needs.
+    #[instrument(skip(db, new_password, password_policy), fields(user.id = %user_id))]
+    pub async fn set_password(
+        db: &PgPool,
+        user_id: UserId,
+        new_password: &str,
+        password_policy: &PasswordPolicy,
+    ) -> Result<(), AppError> {
+        let user =
+            sqlx::query("SELECT first_name, last_name, email, password FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(db)
+                .await?
+                .ok_or_else(|| AppError::from_code(ErrorCode::UserNotFound))?;
+        let first_name: String = user.get("first_name");
+        let last_name: String = user.get("last_name");
+        let email: String = user.get("email");
+        let current_hash: Option<String> = user.get("password");
+
+        password_policy.validate(new_password, &[&first_name, &last_name, &email])?;
+
+        // The current password counts as the most recent entry
+        let kept = password_policy.history_size.saturating_sub(1) as i64;
+        if password_policy.history_size > 0 {
+            let previous: Vec<String> = sqlx::query_scalar(
+                r#"SELECT password_hash FROM password_history
+                   WHERE user_id = $1
+                   ORDER BY created_at DESC
+                   LIMIT $2"#,
+            )
+            .bind(user_id)
+            .bind(kept)
+            .fetch_all(db)
+            .await?;
+
+            for hash in current_hash.iter().chain(&previous) {
+                if verify_password(new_password, hash)? {
+                    return Err(AppError::unprocessable(anyhow::anyhow!(
+                        "Password must not match any of your last {} passwords",
+                        password_policy.history_size
+                    )));
+                }
+            }
+        }
+
+        let new_hash = hash_password(new_password)?;
+
+        let mut tx = db.begin().await?;
         sqlx::query("UPDATE users SET password = $1, updated_at = NOW() WHERE id = $2")
             .bind(&new_hash)
             .bind(user_id)
-            .execute(db)
+            .execute(&mut *tx)
             .await
             .context("Failed to update password")
             .map_err(|e| {
@@ -612,10 +1317,31 @@ impl UserService {
                 AppError::database(e)
             })?;
 
-        // Invalidate user caches
-        invalidate::user(cache, Some(user_id.into()), None).await;
+        if let Some(current_hash) = current_hash
+            && kept > 0
+        {
+            sqlx::query("INSERT INTO password_history (user_id, password_hash) VALUES ($1, $2)")
+                .bind(user_id)
+                .bind(current_hash)
+                .execute(&mut *tx)
+                .await?;
+        }
+        sqlx::query(
+            r#"DELETE FROM password_history
+               WHERE user_id = $1
+                 AND id NOT IN (
+                     SELECT id FROM password_history
+                     WHERE user_id = $1
+                     ORDER BY created_at DESC
+                     LIMIT $2
+                 )"#,
+        )
+        .bind(user_id)
+        .bind(kept)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
 
-        info!(user.id = %user_id, "Password changed successfully");
         Ok(())
     }
 
@@ -676,6 +1402,19 @@ impl UserService {
         Self::user_has_system_role(db, user_id, system_roles::ADMIN).await
     }
 
+    /// Get the IDs of every role assigned to the user
+    pub async fn get_user_role_ids(db: &PgPool, user_id: UserId) -> Result<Vec<RoleId>, AppError> {
+        let role_ids =
+            sqlx::query_scalar::<_, RoleId>("SELECT role_id FROM user_roles WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_all(db)
+                .await
+                .context("Failed to fetch user roles")
+                .map_err(AppError::database)?;
+
+        Ok(role_ids)
+    }
+
     /// Check if user has any of the specified roles
     pub async fn user_has_any_role(
         db: &PgPool,
@@ -700,3 +1439,182 @@ impl UserService {
         Ok(has_role)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    async fn create_test_user(pool: &PgPool, password: &str) -> UserId {
+        sqlx::query_scalar::<_, UserId>(
+            "INSERT INTO users (first_name, last_name, email, password)
+             VALUES ('Ada', 'Obi', $1, $2) RETURNING id",
+        )
+        .bind(format!("user-{}@example.com", Uuid::new_v4()))
+        .bind(hash_password(password).unwrap())
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn history_len(pool: &PgPool, user_id: UserId) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM password_history WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_set_password_rejects_recent_passwords(pool: PgPool) {
+        let policy = PasswordPolicy {
+            history_size: 3,
+            ..PasswordPolicy::default()
+        };
+        let user_id = create_test_user(&pool, "first-pass1").await;
+
+        for password in ["second-pass2", "third-pass3"] {
+            UserService::set_password(&pool, user_id, password, &policy)
+                .await
+                .unwrap();
+        }
+        assert_eq!(history_len(&pool, user_id).await, 2);
+
+        for reused in ["third-pass3", "second-pass2", "first-pass1"] {
+            let err = UserService::set_password(&pool, user_id, reused, &policy)
+                .await
+                .unwrap_err();
+            assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+        }
+
+        UserService::set_password(&pool, user_id, "fourth-pass4", &policy)
+            .await
+            .unwrap();
+        assert_eq!(history_len(&pool, user_id).await, 2);
+
+        // The oldest password has dropped out of the history
+        UserService::set_password(&pool, user_id, "first-pass1", &policy)
+            .await
+            .unwrap();
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_set_password_applies_policy(pool: PgPool) {
+        let user_id = create_test_user(&pool, "first-pass1").await;
+        let policy = PasswordPolicy::default();
+
+        for weak in ["short", "password123", "ada-is-here-1"] {
+            let err = UserService::set_password(&pool, user_id, weak, &policy)
+                .await
+                .unwrap_err();
+            assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+        }
+    }
+
+    fn bulk_item(email: &str, school_id: Option<SchoolId>, role_ids: Vec<RoleId>) -> CreateUserDto {
+        CreateUserDto {
+            first_name: "Bulk".to_string(),
+            last_name: "User".to_string(),
+            email: email.parse().unwrap(),
+            password: "brightpass123".to_string(),
+            role_ids,
+            school_id,
+            custom_fields: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_create_users_bulk_reports_each_item(pool: PgPool) {
+        let school_id: SchoolId =
+            sqlx::query_scalar("INSERT INTO schools (name) VALUES ('Bulk School') RETURNING id")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let other_school_id: SchoolId =
+            sqlx::query_scalar("INSERT INTO schools (name) VALUES ('Other School') RETURNING id")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let foreign_role: RoleId = sqlx::query_scalar(
+            "INSERT INTO roles (name, slug, school_id) VALUES ('Bursar', 'bursar', $1) RETURNING id",
+        )
+        .bind(other_school_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let existing = create_test_user(&pool, "existing-pass1").await;
+        let existing_email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+            .bind(existing)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        let mut weak = bulk_item("weak@example.com", None, vec![]);
+        weak.password = "short".to_string();
+        let dtos = vec![
+            bulk_item("teacher@example.com", None, vec![system_roles::TEACHER]),
+            bulk_item("TEACHER@example.com", None, vec![]),
+            weak,
+            bulk_item(&existing_email, None, vec![]),
+            bulk_item("admin@example.com", None, vec![system_roles::SYSTEM_ADMIN]),
+            bulk_item("bursar@example.com", None, vec![foreign_role]),
+            bulk_item("student@example.com", None, vec![system_roles::STUDENT]),
+        ];
+
+        let response = UserService::create_users_bulk(
+            &pool,
+            dtos,
+            Some(school_id),
+            None,
+            &PasswordPolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.created_count, 2);
+        assert_eq!(response.failed_count, 5);
+        let statuses: Vec<_> = response.results.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                BulkItemStatus::Created,
+                BulkItemStatus::Failed,
+                BulkItemStatus::Failed,
+                BulkItemStatus::Failed,
+                BulkItemStatus::Failed,
+                BulkItemStatus::Failed,
+                BulkItemStatus::Created,
+            ]
+        );
+        assert_eq!(response.results[3].code, Some(ErrorCode::EmailTaken));
+        assert_eq!(response.results[4].code, Some(ErrorCode::Forbidden));
+        assert_eq!(response.results[5].code, Some(ErrorCode::BadRequest));
+
+        let teacher = response.results[0].user.as_ref().unwrap();
+        assert_eq!(teacher.school_id, Some(school_id));
+        let roles: Vec<RoleId> =
+            sqlx::query_scalar("SELECT role_id FROM user_roles WHERE user_id = $1")
+                .bind(teacher.id)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(roles, [system_roles::TEACHER]);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_create_users_bulk_rejects_empty_and_oversized_requests(pool: PgPool) {
+        let policy = PasswordPolicy::default();
+        let err = UserService::create_users_bulk(&pool, vec![], None, None, &policy)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let dtos = (0..=MAX_BULK_USERS)
+            .map(|i| bulk_item(&format!("user{i}@example.com"), None, vec![]))
+            .collect();
+        let err = UserService::create_users_bulk(&pool, dtos, None, None, &policy)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,345 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::{VisitorKioskKeyId, VisitorLogId};
+
+use crate::middleware::auth::{
+    RequireVisitorsCreate, RequireVisitorsDelete, RequireVisitorsKiosk, RequireVisitorsRead,
+    RequireVisitorsUpdate, VisitorKiosk,
+};
+use crate::modules::visitor_log::model::{
+    CheckInVisitorDto, CreateVisitorKioskKeyDto, CreatedVisitorKioskKey, DailyVisitorReport,
+    DailyVisitorReportParams, PaginatedVisitorLogsResponse, VisitorKioskKey,
+    VisitorKioskKeyQueryParams, VisitorLog, VisitorLogFilterParams,
+};
+use crate::modules::visitor_log::service::VisitorLogService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::{
+    get_optional_school_id_for_resource_operation, get_school_id_for_scoped_operation,
+};
+use crate::validator::ValidatedJson;
+
+/// Check a visitor in at the front desk
+#[utoipa::path(
+    post,
+    path = "/api/visitors",
+    summary = "Check in visitor",
+    request_body = CheckInVisitorDto,
+    responses(
+        Created<VisitorLog>,
+        (status = 400, description = "Invalid input, host is not staff, or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires visitors:create permission")
+    ),
+    tag = "Visitors",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn check_in_visitor(
+    State(state): State<AppState>,
+    RequireVisitorsCreate(auth_user): RequireVisitorsCreate,
+    ValidatedJson(dto): ValidatedJson<CheckInVisitorDto>,
+) -> Result<Created<VisitorLog>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let checked_in_by = auth_user.user_id()?;
+    let visit =
+        VisitorLogService::check_in(&state.db, school_id, Some(checked_in_by), None, dto).await?;
+
+    Ok(Created(visit))
+}
+
+/// List the visitor log for a school
+#[utoipa::path(
+    get,
+    path = "/api/visitors",
+    summary = "List visitors",
+    params(VisitorLogFilterParams),
+    responses(
+        (status = 200, description = "Visitor log entries, newest first", body = PaginatedVisitorLogsResponse),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires visitors:read permission")
+    ),
+    tag = "Visitors",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_visits(
+    State(state): State<AppState>,
+    RequireVisitorsRead(auth_user): RequireVisitorsRead,
+    Query(filters): Query<VisitorLogFilterParams>,
+) -> Result<Json<PaginatedVisitorLogsResponse>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, filters.school_id).await?;
+    let visits = VisitorLogService::get_visits(&state.db, school_id, filters).await?;
+
+    Ok(Json(visits))
+}
+
+/// Get a visitor log entry by ID
+#[utoipa::path(
+    get,
+    path = "/api/visitors/{id}",
+    summary = "Get visitor log entry",
+    params(
+        ("id" = Uuid, Path, description = "Visitor log entry ID")
+    ),
+    responses(
+        (status = 200, description = "Visitor log entry", body = VisitorLog),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires visitors:read permission"),
+        (status = 404, description = "Visitor log entry not found")
+    ),
+    tag = "Visitors",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_visit_by_id(
+    State(state): State<AppState>,
+    RequireVisitorsRead(auth_user): RequireVisitorsRead,
+    Path(id): Path<Uuid>,
+) -> Result<Json<VisitorLog>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let visit =
+        VisitorLogService::get_visit_by_id(&state.db, VisitorLogId::from(id), school_id).await?;
+
+    Ok(Json(visit))
+}
+
+/// Check a visitor out
+#[utoipa::path(
+    post,
+    path = "/api/visitors/{id}/check-out",
+    summary = "Check out visitor",
+    params(
+        ("id" = Uuid, Path, description = "Visitor log entry ID")
+    ),
+    responses(
+        (status = 200, description = "Visitor checked out", body = VisitorLog),
+        (status = 400, description = "Visitor has already checked out"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires visitors:update permission"),
+        (status = 404, description = "Visitor log entry not found")
+    ),
+    tag = "Visitors",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn check_out_visitor(
+    State(state): State<AppState>,
+    RequireVisitorsUpdate(auth_user): RequireVisitorsUpdate,
+    Path(id): Path<Uuid>,
+) -> Result<Json<VisitorLog>, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    let visit = VisitorLogService::check_out(&state.db, VisitorLogId::from(id), school_id).await?;
+
+    Ok(Json(visit))
+}
+
+/// Delete a visitor log entry
+#[utoipa::path(
+    delete,
+    path = "/api/visitors/{id}",
+    summary = "Delete visitor log entry",
+    params(
+        ("id" = Uuid, Path, description = "Visitor log entry ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires visitors:delete permission"),
+        (status = 404, description = "Visitor log entry not found")
+    ),
+    tag = "Visitors",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_visit(
+    State(state): State<AppState>,
+    RequireVisitorsDelete(auth_user): RequireVisitorsDelete,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    VisitorLogService::delete_visit(&state.db, VisitorLogId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Get the daily visitor report for a school
+#[utoipa::path(
+    get,
+    path = "/api/visitors/reports/daily",
+    summary = "Daily visitor report",
+    params(DailyVisitorReportParams),
+    responses(
+        (status = 200, description = "Visitor totals, purposes, and open visits for the day", body = DailyVisitorReport),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires visitors:read permission")
+    ),
+    tag = "Visitors",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_daily_report(
+    State(state): State<AppState>,
+    RequireVisitorsRead(auth_user): RequireVisitorsRead,
+    Query(params): Query<DailyVisitorReportParams>,
+) -> Result<Json<DailyVisitorReport>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let report = VisitorLogService::get_daily_report(&state.db, school_id, params.date).await?;
+
+    Ok(Json(report))
+}
+
+/// Issue a visitor kiosk API key
+#[utoipa::path(
+    post,
+    path = "/api/visitors/kiosk-keys",
+    summary = "Create kiosk key",
+    request_body = CreateVisitorKioskKeyDto,
+    responses(
+        Created<CreatedVisitorKioskKey>,
+        (status = 400, description = "Invalid input or missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires visitors:kiosk permission")
+    ),
+    tag = "Visitors",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn create_kiosk_key(
+    State(state): State<AppState>,
+    RequireVisitorsKiosk(auth_user): RequireVisitorsKiosk,
+    ValidatedJson(dto): ValidatedJson<CreateVisitorKioskKeyDto>,
+) -> Result<Created<CreatedVisitorKioskKey>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, dto.school_id).await?;
+
+    let created_by = auth_user.user_id()?;
+    let key = VisitorLogService::create_kiosk_key(&state.db, school_id, created_by, dto).await?;
+
+    Ok(Created(key))
+}
+
+/// List visitor kiosk API keys
+#[utoipa::path(
+    get,
+    path = "/api/visitors/kiosk-keys",
+    summary = "List kiosk keys",
+    params(VisitorKioskKeyQueryParams),
+    responses(
+        (status = 200, description = "Kiosk keys, active first", body = Vec<VisitorKioskKey>),
+        (status = 400, description = "Missing school_id for system admin"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires visitors:kiosk permission")
+    ),
+    tag = "Visitors",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_kiosk_keys(
+    State(state): State<AppState>,
+    RequireVisitorsKiosk(auth_user): RequireVisitorsKiosk,
+    Query(params): Query<VisitorKioskKeyQueryParams>,
+) -> Result<Json<Vec<VisitorKioskKey>>, AppError> {
+    let school_id =
+        get_school_id_for_scoped_operation(&state.db, &auth_user, params.school_id).await?;
+    let keys = VisitorLogService::get_kiosk_keys(&state.db, school_id).await?;
+
+    Ok(Json(keys))
+}
+
+/// Revoke a visitor kiosk API key
+#[utoipa::path(
+    delete,
+    path = "/api/visitors/kiosk-keys/{id}",
+    summary = "Revoke kiosk key",
+    params(
+        ("id" = Uuid, Path, description = "Kiosk key ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires visitors:kiosk permission"),
+        (status = 404, description = "Kiosk key not found")
+    ),
+    tag = "Visitors",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn revoke_kiosk_key(
+    State(state): State<AppState>,
+    RequireVisitorsKiosk(auth_user): RequireVisitorsKiosk,
+    Path(id): Path<Uuid>,
+) -> Result<NoContent, AppError> {
+    let school_id = get_optional_school_id_for_resource_operation(&state.db, &auth_user).await?;
+    VisitorLogService::revoke_kiosk_key(&state.db, VisitorKioskKeyId::from(id), school_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Check a visitor in from a self-service kiosk
+#[utoipa::path(
+    post,
+    path = "/api/visitors/kiosk/check-in",
+    summary = "Kiosk check in",
+    request_body = CheckInVisitorDto,
+    responses(
+        Created<VisitorLog>,
+        (status = 400, description = "Invalid input or host is not staff"),
+        (status = 401, description = "Missing, invalid, or revoked kiosk key")
+    ),
+    tag = "Visitors",
+    security(("kiosk_key" = []))
+)]
+#[instrument(skip(state))]
+pub async fn kiosk_check_in(
+    State(state): State<AppState>,
+    kiosk: VisitorKiosk,
+    ValidatedJson(dto): ValidatedJson<CheckInVisitorDto>,
+) -> Result<Created<VisitorLog>, AppError> {
+    let visit =
+        VisitorLogService::check_in(&state.db, kiosk.school_id, None, Some(kiosk.key_id), dto)
+            .await?;
+
+    Ok(Created(visit))
+}
+
+/// Check a visitor out from a self-service kiosk
+#[utoipa::path(
+    post,
+    path = "/api/visitors/kiosk/{id}/check-out",
+    summary = "Kiosk check out",
+    params(
+        ("id" = Uuid, Path, description = "Visitor log entry ID")
+    ),
+    responses(
+        (status = 200, description = "Visitor checked out", body = VisitorLog),
+        (status = 400, description = "Visitor has already checked out"),
+        (status = 401, description = "Missing, invalid, or revoked kiosk key"),
+        (status = 404, description = "Visitor log entry not found")
+    ),
+    tag = "Visitors",
+    security(("kiosk_key" = []))
+)]
+#[instrument(skip(state))]
+pub async fn kiosk_check_out(
+    State(state): State<AppState>,
+    kiosk: VisitorKiosk,
+    Path(id): Path<Uuid>,
+) -> Result<Json<VisitorLog>, AppError> {
+    let visit =
+        VisitorLogService::check_out(&state.db, VisitorLogId::from(id), Some(kiosk.school_id))
+            .await?;
+
+    Ok(Json(visit))
+}
@@ -0,0 +1,12 @@
+//! Visitor log module.
+//!
+//! This module records visitors at the school gate: who they are, why they
+//! came, which staff member they are visiting, and when they checked in and
+//! out. Check-ins can be made by front-desk staff or by self-service kiosks
+//! authenticated with a school-issued API key, and a daily summary report
+//! supports security compliance reviews.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
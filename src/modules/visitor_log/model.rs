@@ -0,0 +1,7 @@
+//! Visitor log data models and DTOs.
+//!
+//! This module re-exports visitor log models from the `chalkbyte-models` crate
+//! for backward compatibility and provides any controller-specific types.
+
+// Re-export all visitor log models from the shared crate
+pub use chalkbyte_models::visitor_log::*;
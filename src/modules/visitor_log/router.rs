@@ -0,0 +1,33 @@
+use axum::{
+    Router,
+    routing::{delete, get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    check_in_visitor, check_out_visitor, create_kiosk_key, delete_visit, get_daily_report,
+    get_kiosk_keys, get_visit_by_id, get_visits, kiosk_check_in, kiosk_check_out, revoke_kiosk_key,
+};
+
+/// Initialize the visitor log router for staff
+/// Routes: POST /, GET /, GET /{id}, DELETE /{id}, POST /{id}/check-out,
+/// GET /reports/daily, POST /kiosk-keys, GET /kiosk-keys, DELETE /kiosk-keys/{id}
+pub fn init_visitor_log_router() -> Router<AppState> {
+    Router::new()
+        .route("/", post(check_in_visitor).get(get_visits))
+        .route("/reports/daily", get(get_daily_report))
+        .route("/kiosk-keys", post(create_kiosk_key).get(get_kiosk_keys))
+        .route("/kiosk-keys/{id}", delete(revoke_kiosk_key))
+        .route("/{id}", get(get_visit_by_id).delete(delete_visit))
+        .route("/{id}/check-out", post(check_out_visitor))
+}
+
+/// Initialize the visitor kiosk router
+/// Authenticated with an `X-Kiosk-Key` header rather than a user token.
+/// Routes: POST /check-in, POST /{id}/check-out
+pub fn init_visitor_kiosk_router() -> Router<AppState> {
+    Router::new()
+        .route("/check-in", post(kiosk_check_in))
+        .route("/{id}/check-out", post(kiosk_check_out))
+}
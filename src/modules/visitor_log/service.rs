@@ -0,0 +1,593 @@
+use chrono::{NaiveDate, Utc};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use tracing::instrument;
+
+use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_models::ids::{SchoolId, UserId, VisitorKioskKeyId, VisitorLogId};
+
+use crate::modules::users::model::system_roles;
+use crate::modules::visitor_log::model::{
+    CheckInVisitorDto, CreateVisitorKioskKeyDto, CreatedVisitorKioskKey, DailyVisitorReport,
+    PaginatedVisitorLogsResponse, VisitPurposeCount, VisitorKioskKey, VisitorLog,
+    VisitorLogFilterParams, VisitorLogWithHost,
+};
+
+const VISITOR_LOG_COLUMNS: &str = "id, school_id, visitor_name, phone, id_document, organization, \
+     purpose, purpose_details, host_id, badge_number, check_in_at, check_out_at, checked_in_by, \
+     kiosk_key_id, created_at, updated_at";
+
+const VISITOR_LOG_WITH_HOST_COLUMNS: &str = "v.id, v.visitor_name, v.phone, v.organization, \
+     v.purpose, v.purpose_details, v.host_id, h.first_name AS host_first_name, \
+     h.last_name AS host_last_name, v.badge_number, v.check_in_at, v.check_out_at";
+
+const KIOSK_KEY_COLUMNS: &str =
+    "id, school_id, name, key_prefix, created_by, last_used_at, revoked_at, created_at";
+
+/// Prefix that marks a string as a visitor kiosk key.
+const KIOSK_KEY_PREFIX: &str = "vk_";
+/// Number of random characters following the prefix.
+const KIOSK_KEY_LENGTH: usize = 40;
+/// Number of leading characters stored in the clear to identify a key.
+const KIOSK_KEY_DISPLAY_LENGTH: usize = 10;
+
+pub struct VisitorLogService;
+
+impl VisitorLogService {
+    fn generate_kiosk_key() -> String {
+        let random: String = rand::thread_rng()
+            .sample_iter(&rand::distributions::Alphanumeric)
+            .take(KIOSK_KEY_LENGTH)
+            .map(char::from)
+            .collect();
+        format!("{KIOSK_KEY_PREFIX}{random}")
+    }
+
+    fn hash_kiosk_key(key: &str) -> String {
+        hex::encode(Sha256::digest(key.as_bytes()))
+    }
+
+    /// Ensure a host is a non-student member of the school.
+    async fn validate_host(
+        db: &PgPool,
+        school_id: SchoolId,
+        host_id: UserId,
+    ) -> Result<(), AppError> {
+        let is_staff = sqlx::query_scalar::<_, bool>(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM users u
+                WHERE u.id = $1 AND u.school_id = $2
+                  AND NOT EXISTS(
+                      SELECT 1 FROM user_roles ur WHERE ur.user_id = u.id AND ur.role_id = $3
+                  )
+            )"#,
+        )
+        .bind(host_id)
+        .bind(school_id)
+        .bind(system_roles::STUDENT)
+        .fetch_one(db)
+        .await?;
+
+        if !is_staff {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Host must be a staff member of the school"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Issue a new kiosk key. The plaintext key is only returned here.
+    #[instrument(skip(db))]
+    pub async fn create_kiosk_key(
+        db: &PgPool,
+        school_id: SchoolId,
+        created_by: UserId,
+        dto: CreateVisitorKioskKeyDto,
+    ) -> Result<CreatedVisitorKioskKey, AppError> {
+        let api_key = Self::generate_kiosk_key();
+
+        let key = sqlx::query_as::<_, VisitorKioskKey>(&format!(
+            "INSERT INTO visitor_kiosk_keys (school_id, name, key_prefix, key_hash, created_by)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {KIOSK_KEY_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.name)
+        .bind(&api_key[..KIOSK_KEY_DISPLAY_LENGTH])
+        .bind(Self::hash_kiosk_key(&api_key))
+        .bind(created_by)
+        .fetch_one(db)
+        .await?;
+
+        Ok(CreatedVisitorKioskKey { key, api_key })
+    }
+
+    /// List a school's kiosk keys, including revoked ones.
+    #[instrument(skip(db))]
+    pub async fn get_kiosk_keys(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<Vec<VisitorKioskKey>, AppError> {
+        let keys = sqlx::query_as::<_, VisitorKioskKey>(&format!(
+            "SELECT {KIOSK_KEY_COLUMNS} FROM visitor_kiosk_keys
+             WHERE school_id = $1
+             ORDER BY revoked_at NULLS FIRST, created_at DESC"
+        ))
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(keys)
+    }
+
+    /// Revoke a kiosk key. Revoking an already revoked key is a no-op.
+    #[instrument(skip(db))]
+    pub async fn revoke_kiosk_key(
+        db: &PgPool,
+        key_id: VisitorKioskKeyId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"UPDATE visitor_kiosk_keys
+               SET revoked_at = COALESCE(revoked_at, NOW())
+               WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"#,
+        )
+        .bind(key_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!("Kiosk key not found")));
+        }
+
+        Ok(())
+    }
+
+    /// Resolve an active kiosk key and record its use.
+    #[instrument(skip(db, api_key))]
+    pub async fn authenticate_kiosk_key(
+        db: &PgPool,
+        api_key: &str,
+    ) -> Result<VisitorKioskKey, AppError> {
+        sqlx::query_as::<_, VisitorKioskKey>(&format!(
+            "UPDATE visitor_kiosk_keys SET last_used_at = NOW()
+             WHERE key_hash = $1 AND revoked_at IS NULL
+             RETURNING {KIOSK_KEY_COLUMNS}"
+        ))
+        .bind(Self::hash_kiosk_key(api_key))
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::unauthorized("Invalid or revoked kiosk key".to_string()))
+    }
+
+    /// Check a visitor in, either from the front desk or a kiosk.
+    #[instrument(skip(db))]
+    pub async fn check_in(
+        db: &PgPool,
+        school_id: SchoolId,
+        checked_in_by: Option<UserId>,
+        kiosk_key_id: Option<VisitorKioskKeyId>,
+        dto: CheckInVisitorDto,
+    ) -> Result<VisitorLog, AppError> {
+        if let Some(host_id) = dto.host_id {
+            Self::validate_host(db, school_id, host_id).await?;
+        }
+
+        let visit = sqlx::query_as::<_, VisitorLog>(&format!(
+            "INSERT INTO visitor_logs (school_id, visitor_name, phone, id_document, organization,
+                 purpose, purpose_details, host_id, badge_number, checked_in_by, kiosk_key_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             RETURNING {VISITOR_LOG_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.visitor_name)
+        .bind(&dto.phone)
+        .bind(&dto.id_document)
+        .bind(&dto.organization)
+        .bind(dto.purpose)
+        .bind(&dto.purpose_details)
+        .bind(dto.host_id)
+        .bind(&dto.badge_number)
+        .bind(checked_in_by)
+        .bind(kiosk_key_id)
+        .fetch_one(db)
+        .await?;
+
+        Ok(visit)
+    }
+
+    /// Check a visitor out.
+    #[instrument(skip(db))]
+    pub async fn check_out(
+        db: &PgPool,
+        visit_id: VisitorLogId,
+        school_id: Option<SchoolId>,
+    ) -> Result<VisitorLog, AppError> {
+        let existing = Self::get_visit_by_id(db, visit_id, school_id).await?;
+
+        if existing.check_out_at.is_some() {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "Visitor has already checked out"
+            )));
+        }
+
+        let visit = sqlx::query_as::<_, VisitorLog>(&format!(
+            "UPDATE visitor_logs SET check_out_at = NOW()
+             WHERE id = $1 AND check_out_at IS NULL
+             RETURNING {VISITOR_LOG_COLUMNS}"
+        ))
+        .bind(visit_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::bad_request(anyhow::anyhow!("Visitor has already checked out")))?;
+
+        Ok(visit)
+    }
+
+    /// Get a visitor log entry by ID.
+    ///
+    /// When `school_id` is provided, the entry must belong to that school.
+    #[instrument(skip(db))]
+    pub async fn get_visit_by_id(
+        db: &PgPool,
+        visit_id: VisitorLogId,
+        school_id: Option<SchoolId>,
+    ) -> Result<VisitorLog, AppError> {
+        sqlx::query_as::<_, VisitorLog>(&format!(
+            "SELECT {VISITOR_LOG_COLUMNS} FROM visitor_logs
+             WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)"
+        ))
+        .bind(visit_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Visitor log entry not found")))
+    }
+
+    /// Get paginated visitor log entries for a school, newest first.
+    #[instrument(skip(db))]
+    pub async fn get_visits(
+        db: &PgPool,
+        school_id: SchoolId,
+        filters: VisitorLogFilterParams,
+    ) -> Result<PaginatedVisitorLogsResponse, AppError> {
+        let limit = filters.pagination.limit();
+        let offset = filters.pagination.offset();
+
+        let where_clause = r#"WHERE v.school_id = $1
+              AND ($2::date IS NULL OR (v.check_in_at AT TIME ZONE 'UTC')::date = $2)
+              AND ($3::uuid IS NULL OR v.host_id = $3)
+              AND ($4::text IS NULL OR v.purpose = $4)
+              AND ($5::boolean IS NULL OR (v.check_out_at IS NULL) = $5)"#;
+
+        let total = sqlx::query_scalar::<_, i64>(&format!(
+            "SELECT COUNT(*) FROM visitor_logs v {where_clause}"
+        ))
+        .bind(school_id)
+        .bind(filters.date)
+        .bind(filters.host_id)
+        .bind(filters.purpose)
+        .bind(filters.on_site)
+        .fetch_one(db)
+        .await?;
+
+        let data = sqlx::query_as::<_, VisitorLogWithHost>(&format!(
+            "SELECT {VISITOR_LOG_WITH_HOST_COLUMNS}
+             FROM visitor_logs v
+             LEFT JOIN users h ON h.id = v.host_id
+             {where_clause}
+             ORDER BY v.check_in_at DESC
+             LIMIT $6 OFFSET $7"
+        ))
+        .bind(school_id)
+        .bind(filters.date)
+        .bind(filters.host_id)
+        .bind(filters.purpose)
+        .bind(filters.on_site)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        Ok(PaginatedVisitorLogsResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: None,
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Delete a visitor log entry.
+    #[instrument(skip(db))]
+    pub async fn delete_visit(
+        db: &PgPool,
+        visit_id: VisitorLogId,
+        school_id: Option<SchoolId>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "DELETE FROM visitor_logs WHERE id = $1 AND ($2::uuid IS NULL OR school_id = $2)",
+        )
+        .bind(visit_id)
+        .bind(school_id)
+        .execute(db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Visitor log entry not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Summarize a day's visitor traffic for a school.
+    ///
+    /// Days are UTC calendar days and default to today.
+    #[instrument(skip(db))]
+    pub async fn get_daily_report(
+        db: &PgPool,
+        school_id: SchoolId,
+        date: Option<NaiveDate>,
+    ) -> Result<DailyVisitorReport, AppError> {
+        let date = date.unwrap_or_else(|| Utc::now().date_naive());
+
+        let (total_visits, checked_out, average_visit_minutes) =
+            sqlx::query_as::<_, (i64, i64, Option<f64>)>(
+                r#"SELECT COUNT(*),
+                          COUNT(check_out_at),
+                          (AVG(EXTRACT(EPOCH FROM (check_out_at - check_in_at))) / 60)::float8
+                   FROM visitor_logs
+                   WHERE school_id = $1 AND (check_in_at AT TIME ZONE 'UTC')::date = $2"#,
+            )
+            .bind(school_id)
+            .bind(date)
+            .fetch_one(db)
+            .await?;
+
+        let by_purpose = sqlx::query_as::<_, VisitPurposeCount>(
+            r#"SELECT purpose, COUNT(*) AS count
+               FROM visitor_logs
+               WHERE school_id = $1 AND (check_in_at AT TIME ZONE 'UTC')::date = $2
+               GROUP BY purpose
+               ORDER BY count DESC, purpose"#,
+        )
+        .bind(school_id)
+        .bind(date)
+        .fetch_all(db)
+        .await?;
+
+        let open_visits = sqlx::query_as::<_, VisitorLogWithHost>(&format!(
+            "SELECT {VISITOR_LOG_WITH_HOST_COLUMNS}
+             FROM visitor_logs v
+             LEFT JOIN users h ON h.id = v.host_id
+             WHERE v.school_id = $1 AND (v.check_in_at AT TIME ZONE 'UTC')::date = $2
+               AND v.check_out_at IS NULL
+             ORDER BY v.check_in_at"
+        ))
+        .bind(school_id)
+        .bind(date)
+        .fetch_all(db)
+        .await?;
+
+        Ok(DailyVisitorReport {
+            date,
+            total_visits,
+            checked_out,
+            still_on_site: total_visits - checked_out,
+            average_visit_minutes,
+            by_purpose,
+            open_visits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::visitor_log::model::VisitPurpose;
+    use axum::http::StatusCode;
+    use chalkbyte_models::ids::RoleId;
+    use uuid::Uuid;
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name, address) VALUES ($1, $2) RETURNING id"#,
+            format!("School {}", Uuid::new_v4()),
+            Some("Test Address")
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId, role_id: RoleId) -> UserId {
+        let user_id: UserId = sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Test', 'User', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into();
+
+        sqlx::query!(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+            user_id.into_inner(),
+            role_id.into_inner()
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+
+        user_id
+    }
+
+    fn check_in_dto(purpose: VisitPurpose, host_id: Option<UserId>) -> CheckInVisitorDto {
+        CheckInVisitorDto {
+            visitor_name: "Ada Obi".to_string(),
+            phone: None,
+            id_document: None,
+            organization: None,
+            purpose,
+            purpose_details: None,
+            host_id,
+            badge_number: None,
+            school_id: None,
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_check_in_rejects_student_host(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let student = create_test_user(&pool, school_id, system_roles::STUDENT).await;
+        let teacher = create_test_user(&pool, school_id, system_roles::TEACHER).await;
+
+        let err = VisitorLogService::check_in(
+            &pool,
+            school_id,
+            None,
+            None,
+            check_in_dto(VisitPurpose::Meeting, Some(student)),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let visit = VisitorLogService::check_in(
+            &pool,
+            school_id,
+            Some(teacher),
+            None,
+            check_in_dto(VisitPurpose::Meeting, Some(teacher)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(visit.host_id, Some(teacher));
+        assert!(visit.check_out_at.is_none());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_check_out_only_once(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let visit = VisitorLogService::check_in(
+            &pool,
+            school_id,
+            None,
+            None,
+            check_in_dto(VisitPurpose::Delivery, None),
+        )
+        .await
+        .unwrap();
+
+        let checked_out = VisitorLogService::check_out(&pool, visit.id, Some(school_id))
+            .await
+            .unwrap();
+        assert!(checked_out.check_out_at.is_some());
+
+        let err = VisitorLogService::check_out(&pool, visit.id, Some(school_id))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let other_school = create_test_school(&pool).await;
+        let err = VisitorLogService::get_visit_by_id(&pool, visit.id, Some(other_school))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_kiosk_key_lifecycle(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let admin = create_test_user(&pool, school_id, system_roles::ADMIN).await;
+
+        let created = VisitorLogService::create_kiosk_key(
+            &pool,
+            school_id,
+            admin,
+            CreateVisitorKioskKeyDto {
+                name: "Main gate".to_string(),
+                school_id: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(created.api_key.starts_with(KIOSK_KEY_PREFIX));
+        assert!(created.api_key.starts_with(&created.key.key_prefix));
+
+        let key = VisitorLogService::authenticate_kiosk_key(&pool, &created.api_key)
+            .await
+            .unwrap();
+        assert_eq!(key.school_id, school_id);
+        assert!(key.last_used_at.is_some());
+
+        let err = VisitorLogService::authenticate_kiosk_key(&pool, "vk_not-a-real-key")
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNAUTHORIZED);
+
+        VisitorLogService::revoke_kiosk_key(&pool, key.id, Some(school_id))
+            .await
+            .unwrap();
+        let err = VisitorLogService::authenticate_kiosk_key(&pool, &created.api_key)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_daily_report(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+
+        let mut visits = Vec::new();
+        for purpose in [
+            VisitPurpose::ParentVisit,
+            VisitPurpose::ParentVisit,
+            VisitPurpose::Delivery,
+        ] {
+            visits.push(
+                VisitorLogService::check_in(
+                    &pool,
+                    school_id,
+                    None,
+                    None,
+                    check_in_dto(purpose, None),
+                )
+                .await
+                .unwrap(),
+            );
+        }
+        VisitorLogService::check_out(&pool, visits[0].id, Some(school_id))
+            .await
+            .unwrap();
+
+        let report = VisitorLogService::get_daily_report(&pool, school_id, None)
+            .await
+            .unwrap();
+        assert_eq!(report.total_visits, 3);
+        assert_eq!(report.checked_out, 1);
+        assert_eq!(report.still_on_site, 2);
+        assert!(report.average_visit_minutes.is_some());
+        assert_eq!(report.by_purpose[0].purpose, VisitPurpose::ParentVisit);
+        assert_eq!(report.by_purpose[0].count, 2);
+        assert_eq!(report.open_visits.len(), 2);
+
+        let yesterday = Utc::now().date_naive().pred_opt().unwrap();
+        let empty = VisitorLogService::get_daily_report(&pool, school_id, Some(yesterday))
+            .await
+            .unwrap();
+        assert_eq!(empty.total_visits, 0);
+        assert!(empty.average_visit_minutes.is_none());
+    }
+}
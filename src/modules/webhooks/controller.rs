@@ -0,0 +1,283 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, Created, NoContent};
+use chalkbyte_models::ids::{SchoolId, WebhookEndpointId};
+
+use crate::middleware::auth::{RequireWebhooksManage, RequireWebhooksRead};
+use crate::modules::webhooks::model::{
+    CreateWebhookEndpointDto, PaginatedWebhookDeliveriesResponse, UpdateWebhookEndpointDto,
+    WebhookDeliveryFilterParams, WebhookEndpoint, WebhookEndpointWithSecret,
+};
+use crate::modules::webhooks::service::WebhookService;
+use crate::state::AppState;
+use crate::utils::auth_helpers::verify_school_access;
+use crate::validator::ValidatedJson;
+
+/// Register a webhook endpoint
+///
+/// Subscribed events are POSTed to the URL as JSON. Each request carries an
+/// `X-Chalkbyte-Signature: t=<unix time>,v1=<hex>` header, where the hex is an
+/// HMAC-SHA256 of `<unix time>.<body>` keyed with the secret returned here.
+/// The secret is not shown again.
+#[utoipa::path(
+    post,
+    path = "/api/schools/{id}/webhooks",
+    summary = "Create webhook endpoint",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    request_body = CreateWebhookEndpointDto,
+    responses(
+        Created<WebhookEndpointWithSecret>,
+        (status = 400, description = "Invalid URL or no events"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires webhooks:manage permission and access to the school"),
+        (status = 422, description = "URL is not https or does not resolve to public addresses")
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    RequireWebhooksManage(auth_user): RequireWebhooksManage,
+    Path(school_id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<CreateWebhookEndpointDto>,
+) -> Result<Created<WebhookEndpointWithSecret>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let endpoint =
+        WebhookService::create_endpoint(&state.db, school_id, auth_user.user_id()?, dto).await?;
+
+    Ok(Created(endpoint))
+}
+
+/// List a school's webhook endpoints
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/webhooks",
+    summary = "List webhook endpoints",
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook endpoints", body = Vec<WebhookEndpoint>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires webhooks:read permission and access to the school")
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_webhooks(
+    State(state): State<AppState>,
+    RequireWebhooksRead(auth_user): RequireWebhooksRead,
+    Path(school_id): Path<Uuid>,
+) -> Result<Json<Vec<WebhookEndpoint>>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let endpoints = WebhookService::get_endpoints(&state.db, school_id).await?;
+
+    Ok(Json(endpoints))
+}
+
+/// Get a webhook endpoint
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/webhooks/{webhook_id}",
+    summary = "Get webhook endpoint",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook endpoint ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook endpoint", body = WebhookEndpoint),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires webhooks:read permission and access to the school"),
+        (status = 404, description = "Webhook endpoint not found")
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    RequireWebhooksRead(auth_user): RequireWebhooksRead,
+    Path((school_id, webhook_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<WebhookEndpoint>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let endpoint =
+        WebhookService::get_endpoint(&state.db, school_id, WebhookEndpointId::from(webhook_id))
+            .await?;
+
+    Ok(Json(endpoint))
+}
+
+/// Update a webhook endpoint
+///
+/// Deactivating an endpoint stops new events from being queued for it and
+/// pauses deliveries already queued until it is reactivated.
+#[utoipa::path(
+    put,
+    path = "/api/schools/{id}/webhooks/{webhook_id}",
+    summary = "Update webhook endpoint",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook endpoint ID")
+    ),
+    request_body = UpdateWebhookEndpointDto,
+    responses(
+        (status = 200, description = "Webhook endpoint updated", body = WebhookEndpoint),
+        (status = 400, description = "Invalid URL or no events"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires webhooks:manage permission and access to the school"),
+        (status = 404, description = "Webhook endpoint not found"),
+        (status = 422, description = "URL is not https or does not resolve to public addresses")
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state, dto))]
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    RequireWebhooksManage(auth_user): RequireWebhooksManage,
+    Path((school_id, webhook_id)): Path<(Uuid, Uuid)>,
+    ValidatedJson(dto): ValidatedJson<UpdateWebhookEndpointDto>,
+) -> Result<Json<WebhookEndpoint>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let endpoint = WebhookService::update_endpoint(
+        &state.db,
+        school_id,
+        WebhookEndpointId::from(webhook_id),
+        dto,
+    )
+    .await?;
+
+    Ok(Json(endpoint))
+}
+
+/// Rotate a webhook endpoint's signing secret
+///
+/// Returns the new secret, which is not shown again. Deliveries sent from now
+/// on, including retries, are signed with it.
+#[utoipa::path(
+    post,
+    path = "/api/schools/{id}/webhooks/{webhook_id}/rotate-secret",
+    summary = "Rotate webhook secret",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook endpoint ID")
+    ),
+    responses(
+        (status = 200, description = "Secret rotated", body = WebhookEndpointWithSecret),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires webhooks:manage permission and access to the school"),
+        (status = 404, description = "Webhook endpoint not found")
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn rotate_webhook_secret(
+    State(state): State<AppState>,
+    RequireWebhooksManage(auth_user): RequireWebhooksManage,
+    Path((school_id, webhook_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<WebhookEndpointWithSecret>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let endpoint =
+        WebhookService::rotate_secret(&state.db, school_id, WebhookEndpointId::from(webhook_id))
+            .await?;
+
+    Ok(Json(endpoint))
+}
+
+/// Delete a webhook endpoint
+///
+/// Also deletes its delivery history and any deliveries still queued.
+#[utoipa::path(
+    delete,
+    path = "/api/schools/{id}/webhooks/{webhook_id}",
+    summary = "Delete webhook endpoint",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook endpoint ID")
+    ),
+    responses(
+        NoContent,
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires webhooks:manage permission and access to the school"),
+        (status = 404, description = "Webhook endpoint not found")
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    RequireWebhooksManage(auth_user): RequireWebhooksManage,
+    Path((school_id, webhook_id)): Path<(Uuid, Uuid)>,
+) -> Result<NoContent, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    WebhookService::delete_endpoint(&state.db, school_id, WebhookEndpointId::from(webhook_id))
+        .await?;
+
+    Ok(NoContent)
+}
+
+/// List a webhook endpoint's deliveries
+///
+/// Shows each queued or sent event with its attempts, the last response
+/// status or error, and when the next retry is due.
+#[utoipa::path(
+    get,
+    path = "/api/schools/{id}/webhooks/{webhook_id}/deliveries",
+    summary = "List webhook deliveries",
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook endpoint ID"),
+        WebhookDeliveryFilterParams
+    ),
+    responses(
+        (status = 200, description = "Deliveries, newest first", body = PaginatedWebhookDeliveriesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - requires webhooks:read permission and access to the school"),
+        (status = 404, description = "Webhook endpoint not found")
+    ),
+    tag = "Webhooks",
+    security(("bearer_auth" = []))
+)]
+#[instrument(skip(state))]
+pub async fn get_webhook_deliveries(
+    State(state): State<AppState>,
+    RequireWebhooksRead(auth_user): RequireWebhooksRead,
+    Path((school_id, webhook_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<WebhookDeliveryFilterParams>,
+) -> Result<Json<PaginatedWebhookDeliveriesResponse>, AppError> {
+    let school_id = SchoolId::from(school_id);
+    verify_school_access(&state.db, &auth_user, school_id).await?;
+
+    let deliveries = WebhookService::get_deliveries(
+        &state.db,
+        school_id,
+        WebhookEndpointId::from(webhook_id),
+        params,
+    )
+    .await?;
+
+    Ok(Json(deliveries))
+}
@@ -0,0 +1,14 @@
+//! Webhooks module.
+//!
+//! School admins register HTTP endpoints that receive domain events such as
+//! `user.created`, `student.moved_branch`, `role.assigned`, `term.started`,
+//! `term.ended`, and `incident.recorded`. Services queue an event in the
+//! `webhook_outbox` table in the same transaction as the change it describes;
+//! a background job started with the server then POSTs each delivery as JSON,
+//! signed with the endpoint's secret in the `X-Chalkbyte-Signature` header,
+//! and retries failures with exponential backoff before giving up.
+
+pub mod controller;
+pub mod model;
+pub mod router;
+pub mod service;
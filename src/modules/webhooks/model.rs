@@ -0,0 +1,7 @@
+//! Webhook data models and DTOs.
+//!
+//! This module re-exports webhook models from the `chalkbyte-models`
+//! crate for backward compatibility and provides any controller-specific types.
+
+// Re-export all webhook models from the shared crate
+pub use chalkbyte_models::webhooks::*;
@@ -0,0 +1,32 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+use super::controller::{
+    create_webhook, delete_webhook, get_webhook, get_webhook_deliveries, get_webhooks,
+    rotate_webhook_secret, update_webhook,
+};
+
+/// Initialize the webhooks router, merged into the schools router
+/// Routes: GET/POST /{id}/webhooks, GET/PUT/DELETE /{id}/webhooks/{webhook_id},
+/// POST /{id}/webhooks/{webhook_id}/rotate-secret,
+/// GET /{id}/webhooks/{webhook_id}/deliveries
+pub fn init_webhooks_router() -> Router<AppState> {
+    Router::new()
+        .route("/{id}/webhooks", get(get_webhooks).post(create_webhook))
+        .route(
+            "/{id}/webhooks/{webhook_id}",
+            get(get_webhook).put(update_webhook).delete(delete_webhook),
+        )
+        .route(
+            "/{id}/webhooks/{webhook_id}/rotate-secret",
+            post(rotate_webhook_secret),
+        )
+        .route(
+            "/{id}/webhooks/{webhook_id}/deliveries",
+            get(get_webhook_deliveries),
+        )
+}
@@ -0,0 +1,800 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use data_encoding::BASE64URL_NOPAD;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::Serialize;
+use sha2::Sha256;
+use sqlx::{FromRow, PgExecutor, PgPool};
+use tracing::{info, instrument, warn};
+use uuid::Uuid;
+
+use chalkbyte_core::{AppError, PaginationMeta};
+use chalkbyte_models::ids::{SchoolId, UserId, WebhookDeliveryId, WebhookEndpointId};
+use chalkbyte_observability::jobs::register_job;
+
+use crate::modules::webhooks::model::{
+    CreateWebhookEndpointDto, PaginatedWebhookDeliveriesResponse, UpdateWebhookEndpointDto,
+    WebhookDelivery, WebhookDeliveryFilterParams, WebhookEndpoint, WebhookEndpointWithSecret,
+    WebhookEvent,
+};
+use crate::utils::outbound::{OutboundClient, ensure_public_url};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How often the delivery job looks for due deliveries.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Deliveries claimed per round trip.
+const DELIVERY_BATCH_SIZE: i64 = 20;
+
+/// How long an endpoint has to answer before the attempt fails.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Attempts made before a delivery is marked failed.
+pub const MAX_ATTEMPTS: i32 = 8;
+
+/// Delay before the first retry; doubled after every further failure.
+const BASE_RETRY_DELAY_SECS: i64 = 30;
+
+/// Longest delay between two attempts.
+const MAX_RETRY_DELAY_SECS: i64 = 6 * 60 * 60;
+
+/// Header carrying the payload signature.
+pub const SIGNATURE_HEADER: &str = "X-Chalkbyte-Signature";
+
+const ENDPOINT_COLUMNS: &str =
+    "id, school_id, url, description, events, is_active, created_by, created_at, updated_at";
+
+const DELIVERY_COLUMNS: &str = "id, endpoint_id, event_type AS event, payload, status, attempts,
+    next_attempt_at, last_response_status, last_error, delivered_at, created_at";
+
+/// Start the background job that delivers queued webhook events.
+pub fn spawn_delivery_job(db: PgPool) {
+    tokio::spawn(async move {
+        let client = match OutboundClient::new(REQUEST_TIMEOUT) {
+            Ok(client) => client,
+            Err(e) => {
+                warn!(error = %e, "Failed to build webhook HTTP client, deliveries disabled");
+                return;
+            }
+        };
+
+        let job = register_job("webhook_delivery", POLL_INTERVAL);
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let run = job
+                .run(async {
+                    loop {
+                        match WebhookService::deliver_due(&db, &client).await? {
+                            0 => return Ok::<_, AppError>(()),
+                            attempted => info!(attempted, "Attempted webhook deliveries"),
+                        }
+                    }
+                })
+                .await;
+            if let Err(e) = run {
+                warn!(error = %e, "Failed to deliver webhooks");
+            }
+            match WebhookService::queue_depth(&db).await {
+                Ok(depth) => job.set_queue_depth(depth),
+                Err(e) => warn!(error = %e, "Failed to count pending webhook deliveries"),
+            }
+        }
+    });
+}
+
+/// Delay before retrying a delivery that has failed `attempts` times.
+fn retry_delay_secs(attempts: i32) -> i64 {
+    let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+    BASE_RETRY_DELAY_SECS
+        .saturating_mul(1 << exponent)
+        .min(MAX_RETRY_DELAY_SECS)
+}
+
+/// Signature header value for a payload sent at `timestamp`.
+///
+/// The value is `t=<timestamp>,v1=<hex HMAC-SHA256 of "<timestamp>.<body>">`
+/// keyed with the endpoint's secret. Receivers should recompute it and reject
+/// stale timestamps to guard against replays.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{timestamp}.{body}").as_bytes());
+    let signature = mac.finalize().into_bytes();
+
+    format!("t={timestamp},v1={}", hex::encode(signature))
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", BASE64URL_NOPAD.encode(&bytes))
+}
+
+/// Envelope POSTed to endpoints.
+#[derive(Serialize)]
+struct EventPayload<'a, T: Serialize> {
+    id: Uuid,
+    #[serde(rename = "type")]
+    event: WebhookEvent,
+    school_id: SchoolId,
+    occurred_at: chrono::DateTime<Utc>,
+    data: &'a T,
+}
+
+/// A delivery claimed by the delivery job.
+#[derive(FromRow)]
+struct ClaimedDelivery {
+    id: WebhookDeliveryId,
+    event_type: WebhookEvent,
+    payload: serde_json::Value,
+    attempts: i32,
+    url: String,
+    secret: String,
+}
+
+pub struct WebhookService;
+
+impl WebhookService {
+    /// Queue an event for every active endpoint of the school subscribed to it.
+    ///
+    /// Pass the transaction that makes the change so the event is queued
+    /// if, and only if, the change commits. Returns the deliveries queued.
+    pub async fn enqueue<'e, T: Serialize>(
+        executor: impl PgExecutor<'e>,
+        school_id: SchoolId,
+        event: WebhookEvent,
+        data: &T,
+    ) -> Result<u64, AppError> {
+        let payload = serde_json::to_value(EventPayload {
+            id: Uuid::new_v4(),
+            event,
+            school_id,
+            occurred_at: Utc::now(),
+            data,
+        })
+        .map_err(|e| AppError::internal_error(format!("Failed to serialize webhook event: {e}")))?;
+
+        let queued = sqlx::query(
+            "INSERT INTO webhook_outbox (endpoint_id, event_type, payload)
+             SELECT id, $2, $3 FROM webhook_endpoints
+             WHERE school_id = $1 AND is_active AND $2 = ANY(events)",
+        )
+        .bind(school_id)
+        .bind(event)
+        .bind(payload)
+        .execute(executor)
+        .await?
+        .rows_affected();
+
+        Ok(queued)
+    }
+
+    #[instrument(skip(db, dto))]
+    pub async fn create_endpoint(
+        db: &PgPool,
+        school_id: SchoolId,
+        created_by: UserId,
+        dto: CreateWebhookEndpointDto,
+    ) -> Result<WebhookEndpointWithSecret, AppError> {
+        ensure_public_url(&dto.url)
+            .await
+            .map_err(AppError::unprocessable)?;
+        let secret = generate_secret();
+
+        let endpoint = sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "INSERT INTO webhook_endpoints (school_id, url, description, secret, events, created_by)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             RETURNING {ENDPOINT_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(&dto.url)
+        .bind(&dto.description)
+        .bind(&secret)
+        .bind(dedup_events(dto.events))
+        .bind(created_by)
+        .fetch_one(db)
+        .await?;
+
+        info!(webhook.id = %endpoint.id, "Webhook endpoint registered");
+
+        Ok(WebhookEndpointWithSecret { endpoint, secret })
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_endpoints(
+        db: &PgPool,
+        school_id: SchoolId,
+    ) -> Result<Vec<WebhookEndpoint>, AppError> {
+        let endpoints = sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "SELECT {ENDPOINT_COLUMNS} FROM webhook_endpoints
+             WHERE school_id = $1
+             ORDER BY created_at"
+        ))
+        .bind(school_id)
+        .fetch_all(db)
+        .await?;
+
+        Ok(endpoints)
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_endpoint(
+        db: &PgPool,
+        school_id: SchoolId,
+        endpoint_id: WebhookEndpointId,
+    ) -> Result<WebhookEndpoint, AppError> {
+        sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "SELECT {ENDPOINT_COLUMNS} FROM webhook_endpoints WHERE id = $1 AND school_id = $2"
+        ))
+        .bind(endpoint_id)
+        .bind(school_id)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Webhook endpoint not found")))
+    }
+
+    #[instrument(skip(db, dto))]
+    pub async fn update_endpoint(
+        db: &PgPool,
+        school_id: SchoolId,
+        endpoint_id: WebhookEndpointId,
+        dto: UpdateWebhookEndpointDto,
+    ) -> Result<WebhookEndpoint, AppError> {
+        if let Some(url) = &dto.url {
+            ensure_public_url(url)
+                .await
+                .map_err(AppError::unprocessable)?;
+        }
+
+        sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "UPDATE webhook_endpoints
+             SET url = COALESCE($3, url),
+                 description = COALESCE($4, description),
+                 events = COALESCE($5, events),
+                 is_active = COALESCE($6, is_active)
+             WHERE id = $1 AND school_id = $2
+             RETURNING {ENDPOINT_COLUMNS}"
+        ))
+        .bind(endpoint_id)
+        .bind(school_id)
+        .bind(dto.url)
+        .bind(dto.description)
+        .bind(dto.events.map(dedup_events))
+        .bind(dto.is_active)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Webhook endpoint not found")))
+    }
+
+    /// Replace an endpoint's signing secret. Deliveries still queued are
+    /// signed with the new secret.
+    #[instrument(skip(db))]
+    pub async fn rotate_secret(
+        db: &PgPool,
+        school_id: SchoolId,
+        endpoint_id: WebhookEndpointId,
+    ) -> Result<WebhookEndpointWithSecret, AppError> {
+        let secret = generate_secret();
+
+        let endpoint = sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "UPDATE webhook_endpoints SET secret = $3
+             WHERE id = $1 AND school_id = $2
+             RETURNING {ENDPOINT_COLUMNS}"
+        ))
+        .bind(endpoint_id)
+        .bind(school_id)
+        .bind(&secret)
+        .fetch_optional(db)
+        .await?
+        .ok_or_else(|| AppError::not_found(anyhow::anyhow!("Webhook endpoint not found")))?;
+
+        Ok(WebhookEndpointWithSecret { endpoint, secret })
+    }
+
+    /// Delete an endpoint along with its delivery history.
+    #[instrument(skip(db))]
+    pub async fn delete_endpoint(
+        db: &PgPool,
+        school_id: SchoolId,
+        endpoint_id: WebhookEndpointId,
+    ) -> Result<(), AppError> {
+        let deleted = sqlx::query("DELETE FROM webhook_endpoints WHERE id = $1 AND school_id = $2")
+            .bind(endpoint_id)
+            .bind(school_id)
+            .execute(db)
+            .await?
+            .rows_affected();
+
+        if deleted == 0 {
+            return Err(AppError::not_found(anyhow::anyhow!(
+                "Webhook endpoint not found"
+            )));
+        }
+
+        Ok(())
+    }
+
+    #[instrument(skip(db))]
+    pub async fn get_deliveries(
+        db: &PgPool,
+        school_id: SchoolId,
+        endpoint_id: WebhookEndpointId,
+        params: WebhookDeliveryFilterParams,
+    ) -> Result<PaginatedWebhookDeliveriesResponse, AppError> {
+        // Confirms the endpoint belongs to the school
+        Self::get_endpoint(db, school_id, endpoint_id).await?;
+
+        let limit = params.pagination.limit();
+        let offset = params.pagination.offset();
+
+        let data = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "SELECT {DELIVERY_COLUMNS} FROM webhook_outbox
+             WHERE endpoint_id = $1 AND ($2::text IS NULL OR status = $2)
+             ORDER BY created_at DESC
+             LIMIT $3 OFFSET $4"
+        ))
+        .bind(endpoint_id)
+        .bind(params.status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db)
+        .await?;
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM webhook_outbox
+             WHERE endpoint_id = $1 AND ($2::text IS NULL OR status = $2)",
+        )
+        .bind(endpoint_id)
+        .bind(params.status)
+        .fetch_one(db)
+        .await?;
+
+        Ok(PaginatedWebhookDeliveriesResponse {
+            data,
+            meta: PaginationMeta {
+                total,
+                limit,
+                offset: Some(offset),
+                page: params.pagination.page(),
+                has_more: offset + limit < total,
+            },
+        })
+    }
+
+    /// Number of deliveries waiting for an attempt.
+    pub async fn queue_depth(db: &PgPool) -> Result<u64, AppError> {
+        let pending = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM webhook_outbox WHERE status = 'pending'",
+        )
+        .fetch_one(db)
+        .await?;
+
+        Ok(pending.try_into().unwrap_or_default())
+    }
+
+    /// Attempt a batch of due deliveries and return how many were attempted.
+    ///
+    /// Claiming a delivery pushes its next attempt out, so a delivery whose
+    /// attempt is interrupted is retried later rather than lost, and several
+    /// servers can run the job without sending the same attempt twice.
+    pub async fn deliver_due(db: &PgPool, client: &OutboundClient) -> Result<usize, AppError> {
+        let claimed = sqlx::query_as::<_, ClaimedDelivery>(
+            r#"UPDATE webhook_outbox o
+               SET attempts = o.attempts + 1,
+                   next_attempt_at = NOW() + make_interval(secs => $2)
+               FROM webhook_endpoints e
+               WHERE e.id = o.endpoint_id
+                 AND o.id IN (
+                     SELECT d.id FROM webhook_outbox d
+                     JOIN webhook_endpoints de ON de.id = d.endpoint_id
+                     WHERE d.status = 'pending' AND d.next_attempt_at <= NOW() AND de.is_active
+                     ORDER BY d.next_attempt_at
+                     LIMIT $1
+                     FOR UPDATE OF d SKIP LOCKED
+                 )
+               RETURNING o.id, o.event_type, o.payload, o.attempts, e.url, e.secret"#,
+        )
+        .bind(DELIVERY_BATCH_SIZE)
+        .bind((REQUEST_TIMEOUT.as_secs() * 6) as f64)
+        .fetch_all(db)
+        .await?;
+
+        for delivery in &claimed {
+            Self::attempt(db, client, delivery).await?;
+        }
+
+        Ok(claimed.len())
+    }
+
+    async fn attempt(
+        db: &PgPool,
+        client: &OutboundClient,
+        delivery: &ClaimedDelivery,
+    ) -> Result<(), AppError> {
+        let body = delivery.payload.to_string();
+        let signature = sign_payload(&delivery.secret, Utc::now().timestamp(), &body);
+
+        // The host is checked again here, since its DNS records may have
+        // changed since the endpoint was registered
+        let outcome = match client.post(&delivery.url).await {
+            Ok(request) => request
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(SIGNATURE_HEADER, signature)
+                .header("X-Chalkbyte-Event", delivery.event_type.as_str())
+                .header("X-Chalkbyte-Delivery", delivery.id.to_string())
+                .body(body)
+                .send()
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        let (response_status, error) = match outcome {
+            Ok(response) if response.status().is_success() => {
+                sqlx::query(
+                    "UPDATE webhook_outbox
+                     SET status = 'delivered', delivered_at = NOW(),
+                         last_response_status = $2, last_error = NULL
+                     WHERE id = $1",
+                )
+                .bind(delivery.id)
+                .bind(i32::from(response.status().as_u16()))
+                .execute(db)
+                .await?;
+                return Ok(());
+            }
+            Ok(response) => (
+                Some(i32::from(response.status().as_u16())),
+                format!("Endpoint responded with {}", response.status()),
+            ),
+            Err(error) => (None, error),
+        };
+
+        let exhausted = delivery.attempts >= MAX_ATTEMPTS;
+        sqlx::query(
+            "UPDATE webhook_outbox
+             SET status = CASE WHEN $2 THEN 'failed' ELSE 'pending' END,
+                 next_attempt_at = NOW() + make_interval(secs => $3),
+                 last_response_status = $4, last_error = $5
+             WHERE id = $1",
+        )
+        .bind(delivery.id)
+        .bind(exhausted)
+        .bind(retry_delay_secs(delivery.attempts) as f64)
+        .bind(response_status)
+        .bind(&error)
+        .execute(db)
+        .await?;
+
+        warn!(
+            webhook.delivery_id = %delivery.id,
+            attempts = delivery.attempts,
+            exhausted,
+            error = %error,
+            "Webhook delivery failed"
+        );
+
+        Ok(())
+    }
+}
+
+fn dedup_events(mut events: Vec<WebhookEvent>) -> Vec<WebhookEvent> {
+    events.sort_by_key(|event| event.as_str());
+    events.dedup();
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::webhooks::model::WebhookDeliveryStatus;
+    use axum::{
+        Router,
+        extract::State,
+        http::{HeaderMap, StatusCode},
+        routing::post,
+    };
+    use std::sync::{Arc, Mutex};
+
+    async fn create_test_school(pool: &PgPool) -> SchoolId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO schools (name) VALUES ($1) RETURNING id"#,
+            format!("School {}", Uuid::new_v4())
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    async fn create_test_user(pool: &PgPool, school_id: SchoolId) -> UserId {
+        sqlx::query_scalar!(
+            r#"INSERT INTO users (first_name, last_name, email, school_id)
+               VALUES ('Ada', 'Obi', $1, $2) RETURNING id"#,
+            format!("user-{}@example.com", Uuid::new_v4()),
+            school_id.into_inner()
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+
+    /// Insert an endpoint directly, skipping the public address check so
+    /// tests can target local receivers.
+    async fn create_endpoint(
+        pool: &PgPool,
+        school_id: SchoolId,
+        url: &str,
+        events: Vec<WebhookEvent>,
+    ) -> WebhookEndpointWithSecret {
+        let created_by = create_test_user(pool, school_id).await;
+        let secret = generate_secret();
+
+        let endpoint = sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "INSERT INTO webhook_endpoints (school_id, url, secret, events, created_by)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {ENDPOINT_COLUMNS}"
+        ))
+        .bind(school_id)
+        .bind(url)
+        .bind(&secret)
+        .bind(events)
+        .bind(created_by)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        WebhookEndpointWithSecret { endpoint, secret }
+    }
+
+    type Received = Arc<Mutex<Vec<(HeaderMap, String)>>>;
+
+    /// Serve a receiver on a random local port answering with `status`.
+    async fn spawn_receiver(status: StatusCode) -> (String, Received) {
+        let received = Received::default();
+        let app =
+            Router::new()
+                .route(
+                    "/hook",
+                    post(
+                        move |State(received): State<Received>,
+                              headers: HeaderMap,
+                              body: String| async move {
+                            received.lock().unwrap().push((headers, body));
+                            status
+                        },
+                    ),
+                )
+                .with_state(received.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (format!("http://{addr}/hook"), received)
+    }
+
+    async fn delivery(pool: &PgPool, endpoint_id: WebhookEndpointId) -> WebhookDelivery {
+        sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "SELECT {DELIVERY_COLUMNS} FROM webhook_outbox WHERE endpoint_id = $1"
+        ))
+        .bind(endpoint_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_exponentially() {
+        assert_eq!(retry_delay_secs(1), 30);
+        assert_eq!(retry_delay_secs(2), 60);
+        assert_eq!(retry_delay_secs(3), 120);
+        assert_eq!(retry_delay_secs(30), MAX_RETRY_DELAY_SECS);
+    }
+
+    #[test]
+    fn test_sign_payload() {
+        let signature = sign_payload("whsec_test", 1_700_000_000, r#"{"a":1}"#);
+        assert!(signature.starts_with("t=1700000000,v1="));
+        assert_eq!(signature.len(), "t=1700000000,v1=".len() + 64);
+        assert_ne!(
+            signature,
+            sign_payload("whsec_other", 1_700_000_000, r#"{"a":1}"#)
+        );
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_enqueue_targets_active_subscribed_endpoints(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let other_school_id = create_test_school(&pool).await;
+        let url = "https://example.com/hook";
+
+        let subscribed =
+            create_endpoint(&pool, school_id, url, vec![WebhookEvent::UserCreated]).await;
+        create_endpoint(&pool, school_id, url, vec![WebhookEvent::TermStarted]).await;
+        create_endpoint(&pool, other_school_id, url, vec![WebhookEvent::UserCreated]).await;
+        let inactive =
+            create_endpoint(&pool, school_id, url, vec![WebhookEvent::UserCreated]).await;
+        WebhookService::update_endpoint(
+            &pool,
+            school_id,
+            inactive.endpoint.id,
+            UpdateWebhookEndpointDto {
+                is_active: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let data = serde_json::json!({ "user_id": Uuid::new_v4() });
+        let queued = WebhookService::enqueue(&pool, school_id, WebhookEvent::UserCreated, &data)
+            .await
+            .unwrap();
+        assert_eq!(queued, 1);
+
+        let queued = delivery(&pool, subscribed.endpoint.id).await;
+        assert_eq!(queued.event, WebhookEvent::UserCreated);
+        assert_eq!(queued.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(queued.payload["type"], "user.created");
+        assert_eq!(queued.payload["data"], data);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_delivery_is_signed(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let (url, received) = spawn_receiver(StatusCode::NO_CONTENT).await;
+        let endpoint =
+            create_endpoint(&pool, school_id, &url, vec![WebhookEvent::TermStarted]).await;
+
+        let data = serde_json::json!({ "term_id": Uuid::new_v4() });
+        WebhookService::enqueue(&pool, school_id, WebhookEvent::TermStarted, &data)
+            .await
+            .unwrap();
+
+        let client = OutboundClient::unrestricted(REQUEST_TIMEOUT).unwrap();
+        assert_eq!(
+            WebhookService::deliver_due(&pool, &client).await.unwrap(),
+            1
+        );
+
+        let (headers, body) = received.lock().unwrap().remove(0);
+        let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+        let timestamp: i64 = signature
+            .strip_prefix("t=")
+            .and_then(|rest| rest.split(',').next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(signature, sign_payload(&endpoint.secret, timestamp, &body));
+        assert_eq!(headers["x-chalkbyte-event"], "term.started");
+
+        let delivered = delivery(&pool, endpoint.endpoint.id).await;
+        assert_eq!(delivered.status, WebhookDeliveryStatus::Delivered);
+        assert_eq!(delivered.last_response_status, Some(204));
+        assert!(delivered.delivered_at.is_some());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_failed_delivery_retries_then_gives_up(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let (url, received) = spawn_receiver(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let endpoint =
+            create_endpoint(&pool, school_id, &url, vec![WebhookEvent::RoleAssigned]).await;
+
+        WebhookService::enqueue(
+            &pool,
+            school_id,
+            WebhookEvent::RoleAssigned,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+
+        let client = OutboundClient::unrestricted(REQUEST_TIMEOUT).unwrap();
+        WebhookService::deliver_due(&pool, &client).await.unwrap();
+
+        let failed = delivery(&pool, endpoint.endpoint.id).await;
+        assert_eq!(failed.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(failed.attempts, 1);
+        assert_eq!(failed.last_response_status, Some(500));
+        assert!(failed.next_attempt_at > Utc::now() + chrono::Duration::seconds(20));
+
+        // Not due yet, so nothing is attempted
+        assert_eq!(
+            WebhookService::deliver_due(&pool, &client).await.unwrap(),
+            0
+        );
+
+        sqlx::query("UPDATE webhook_outbox SET attempts = $1, next_attempt_at = NOW()")
+            .bind(MAX_ATTEMPTS - 1)
+            .execute(&pool)
+            .await
+            .unwrap();
+        WebhookService::deliver_due(&pool, &client).await.unwrap();
+
+        let exhausted = delivery(&pool, endpoint.endpoint.id).await;
+        assert_eq!(exhausted.status, WebhookDeliveryStatus::Failed);
+        assert_eq!(exhausted.attempts, MAX_ATTEMPTS);
+        assert_eq!(received.lock().unwrap().len(), 2);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_endpoints_must_be_public(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let created_by = create_test_user(&pool, school_id).await;
+
+        for url in [
+            "https://127.0.0.1/hook",
+            "https://169.254.169.254/latest/meta-data",
+            "https://localhost/hook",
+        ] {
+            let dto = CreateWebhookEndpointDto {
+                url: url.to_string(),
+                description: None,
+                events: vec![WebhookEvent::UserCreated],
+            };
+            let err = WebhookService::create_endpoint(&pool, school_id, created_by, dto)
+                .await
+                .unwrap_err();
+            assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY, "{url}");
+        }
+
+        let endpoint = create_endpoint(
+            &pool,
+            school_id,
+            "https://8.8.8.8/hook",
+            vec![WebhookEvent::UserCreated],
+        )
+        .await;
+        let err = WebhookService::update_endpoint(
+            &pool,
+            school_id,
+            endpoint.endpoint.id,
+            UpdateWebhookEndpointDto {
+                url: Some("https://10.0.0.1/hook".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_delivery_to_private_address_is_refused(pool: PgPool) {
+        let school_id = create_test_school(&pool).await;
+        let (url, received) = spawn_receiver(StatusCode::NO_CONTENT).await;
+        let url = url.replace("http://", "https://");
+        let endpoint =
+            create_endpoint(&pool, school_id, &url, vec![WebhookEvent::TermStarted]).await;
+
+        WebhookService::enqueue(
+            &pool,
+            school_id,
+            WebhookEvent::TermStarted,
+            &serde_json::json!({}),
+        )
+        .await
+        .unwrap();
+
+        let client = OutboundClient::new(REQUEST_TIMEOUT).unwrap();
+        WebhookService::deliver_due(&pool, &client).await.unwrap();
+
+        let refused = delivery(&pool, endpoint.endpoint.id).await;
+        assert_eq!(refused.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(refused.last_response_status, None);
+        assert!(
+            refused
+                .last_error
+                .unwrap()
+                .contains("does not resolve to a public address")
+        );
+        assert!(received.lock().unwrap().is_empty());
+    }
+}
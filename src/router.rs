@@ -1,39 +1,155 @@
+use crate::middleware::api_version::{
+    DEPRECATION_HEADER, SUNSET_HEADER, deprecated_api_alias, set_api_version,
+};
+use crate::middleware::auth::KIOSK_KEY_HEADER;
+use crate::middleware::error_reporting::report_errors;
+use crate::middleware::feature_flags::require_attendance_enabled;
 #[cfg(feature = "observability")]
 use chalkbyte_observability::{logging_middleware, metrics_middleware, is_observability_enabled};
 #[cfg(not(feature = "observability"))]
 use crate::middleware::observability_stubs::{logging_middleware, metrics_middleware, is_observability_enabled};
-use crate::middleware::role::require_admin;
+use crate::middleware::rate_limit::rate_limit;
+use crate::middleware::request_id::{REQUEST_ID_HEADER, assign_request_id};
+use crate::middleware::role::{
+    require_admin, require_group_admin, require_system_admin, require_teacher,
+};
 use crate::modules::academic_sessions::router::init_academic_sessions_router;
+use crate::modules::alumni::router::init_alumni_router;
+use crate::modules::announcements::router::{init_announcements_router, init_notifications_router};
+use crate::modules::assessments::router::{init_assessments_router, init_my_results_router};
+use crate::modules::assets::router::init_assets_router;
+use crate::modules::attendance::router::{init_attendance_router, init_school_attendance_router};
+use crate::modules::auth::controller::get_jwks;
+use crate::modules::auth::oidc::router::init_sso_providers_router;
 use crate::modules::auth::router::init_auth_router;
+use crate::modules::billing::router::init_billing_router;
+use crate::modules::boarding::router::init_boarding_router;
 use crate::modules::branches::router::{init_branches_router, init_level_branches_router};
-use crate::modules::levels::router::init_levels_router;
+use crate::modules::broadcasts::router::init_broadcasts_router;
+use crate::modules::changes::router::init_changes_router;
+use crate::modules::clinic::router::init_clinic_router;
+use crate::modules::custom_fields::router::init_custom_fields_router;
+use crate::modules::dashboard::router::init_dashboard_router;
+use crate::modules::discipline::router::init_discipline_router;
+use crate::modules::email_branding::router::init_email_branding_router;
+use crate::modules::emails::router::init_emails_router;
+use crate::modules::exams::router::init_exams_router;
+use crate::modules::feature_flags::router::{
+    init_feature_flags_admin_router, init_feature_flags_router,
+};
+use crate::modules::groups::router::init_groups_router;
+use crate::modules::guardians::router::init_guardian_account_runs_router;
+use crate::modules::imports::router::init_imports_router;
+use crate::modules::interop::router::init_interop_router;
+use crate::modules::jobs::router::init_jobs_router;
+use crate::modules::kiosk::router::{
+    init_kiosk_devices_router, init_kiosk_pin_router, init_kiosk_router,
+};
+use crate::modules::levels::router::{init_levels_router, init_naming_templates_router};
+use crate::modules::library::router::init_library_router;
+use crate::modules::messaging::router::init_messaging_router;
 use crate::modules::mfa::router::init_mfa_router;
+use crate::modules::performance::router::init_performance_router;
+use crate::modules::profiles::router::{init_linked_profiles_router, init_my_profiles_router};
+use crate::modules::public_directory::router::{
+    init_public_directory_router, init_public_profile_settings_router,
+};
+use crate::modules::reports::router::init_school_reports_router;
+use crate::modules::results::router::init_results_router;
+use crate::modules::retention::router::init_retention_router;
 use crate::modules::roles::router::{
     init_roles_router, init_user_permissions_router, init_user_roles_router,
 };
+use crate::modules::saved_views::router::init_saved_views_router;
 use crate::modules::schools::router::init_schools_router;
+use crate::modules::staff_leave::router::init_staff_leave_router;
+use crate::modules::storage::router::{
+    init_avatars_router, init_my_avatar_router, init_school_storage_router,
+    init_storage_files_router, init_student_documents_router,
+};
+use crate::modules::student_cards::router::init_student_cards_router;
 use crate::modules::students::router::init_students_router;
+use crate::modules::sync::router::init_sync_router;
 use crate::modules::terms::router::{init_session_terms_router, init_terms_router};
+use crate::modules::traces::router::init_traces_router;
+use crate::modules::transport::router::init_transport_router;
+use crate::modules::trash::router::init_trash_router;
 use crate::modules::users::router::init_users_router;
+use crate::modules::visitor_log::router::{init_visitor_kiosk_router, init_visitor_log_router};
+use crate::modules::webhooks::router::init_webhooks_router;
 use crate::state::AppState;
 
-use axum::http::{HeaderValue, Method};
-use axum::response::IntoResponse;
+use axum::extract::State;
+use axum::http::{HeaderName, HeaderValue, Method, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{MethodRouter, get};
 use axum::{Router, middleware};
+use chalkbyte_cache::rate_limit::{RATE_LIMIT_LIMIT, RATE_LIMIT_REMAINING, RATE_LIMIT_RESET};
+use chalkbyte_config::DocsAccess;
+use chalkbyte_core::versioning::{API_VERSION_HEADER, ApiVersion};
+use chalkbyte_db::check_health;
 use std::path::PathBuf;
 
+use crate::docs::{openapi, public_openapi};
 use tower_http::LatencyUnit;
 use tower_http::cors::CorsLayer;
+use tower_http::sensitive_headers::{
+    SetSensitiveRequestHeadersLayer, SetSensitiveResponseHeadersLayer,
+};
 use tower_http::services::ServeDir;
 use tower_http::trace::{DefaultMakeSpan, DefaultOnResponse, TraceLayer};
 use tracing::Level;
 #[cfg(feature = "scalar")]
-use crate::docs::ApiDoc;
-#[cfg(feature = "scalar")]
-use utoipa::OpenApi;
-#[cfg(feature = "scalar")]
 use utoipa_scalar::{Scalar, Servable as _};
 
+/// Serves the OpenAPI specs and the Scalar UI as allowed by the state's
+/// [`DocsConfig`](chalkbyte_config::DocsConfig).
+///
+/// - `/api-docs/public.json`: the spec without admin-only endpoints
+/// - `/api-docs/openapi.json`: the full spec, admin-only under `authenticated`
+/// - `/scalar`: the UI, showing the full spec only under `public` access
+///
+/// Nothing is served when access is `disabled`.
+fn init_docs_router(state: &AppState) -> Router<AppState> {
+    let access = state.docs_config.access;
+    if access == DocsAccess::Disabled {
+        return Router::new();
+    }
+
+    let full = openapi();
+    let public = public_openapi();
+
+    let full_routes = Router::new().route("/api-docs/openapi.json", spec_route(&full));
+    let full_routes = if access == DocsAccess::Authenticated {
+        full_routes.route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+    } else {
+        full_routes
+    };
+
+    #[cfg(feature = "scalar")]
+    let ui = Router::from(Scalar::with_url(
+        "/scalar",
+        if access == DocsAccess::Public {
+            full
+        } else {
+            public.clone()
+        },
+    ));
+    #[cfg(not(feature = "scalar"))]
+    let ui = Router::new();
+
+    Router::new()
+        .route("/api-docs/public.json", spec_route(&public))
+        .merge(full_routes)
+        .merge(ui)
+}
+
+/// Serves a spec as JSON, serialized once when the router is built.
+fn spec_route(spec: &utoipa::openapi::OpenApi) -> MethodRouter<AppState> {
+    let body = spec.to_json().expect("OpenAPI spec serializes to JSON");
+    get(move || std::future::ready(([(header::CONTENT_TYPE, "application/json")], body.clone())))
+}
+
 async fn health_handler() -> impl IntoResponse {
     axum::Json(serde_json::json!({
         "status": "healthy",
@@ -42,6 +158,31 @@ async fn health_handler() -> impl IntoResponse {
     }))
 }
 
+/// Readiness probe: 200 while the database answers, 503 otherwise.
+///
+/// `/health` only shows the process is up; load balancers should route
+/// traffic by this one.
+async fn readiness_handler(State(state): State<AppState>) -> Response {
+    match check_health(&state.db).await {
+        Ok(database) => axum::Json(serde_json::json!({
+            "status": "ready",
+            "database": database,
+        }))
+        .into_response(),
+        Err(e) => {
+            tracing::warn!(error = %e, "Readiness check failed");
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                axum::Json(serde_json::json!({
+                    "status": "unavailable",
+                    "database": { "error": "Database unavailable" },
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// Builds the API router with all routes and middleware (shared between prod and test)
 #[allow(unused_variables)]
 fn build_api_router(state: AppState, apply_rate_limiting: bool) -> Router {
@@ -55,6 +196,12 @@ fn build_api_router(state: AppState, apply_rate_limiting: bool) -> Router {
     let private_medium = cache_control(CacheControlConfig::private(300).with_must_revalidate());
     // For frequently changing data - always revalidate but still use ETags
     let revalidate_always = cache_control(CacheControlConfig::no_cache());
+    // For unauthenticated public data - shared caches (CDNs) may serve it too
+    let public_medium = cache_control(
+        CacheControlConfig::public(300)
+            .with_s_maxage(600)
+            .with_stale_while_revalidate(60),
+    );
 
     let api_routes = Router::new()
         .nest(
@@ -62,6 +209,7 @@ fn build_api_router(state: AppState, apply_rate_limiting: bool) -> Router {
             init_users_router()
                 .nest("/{user_id}/roles", init_user_roles_router())
                 .nest("/{user_id}/permissions", init_user_permissions_router())
+                .nest("/{user_id}/linked-profiles", init_linked_profiles_router())
                 .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
                 // Users list: private cache, short TTL with ETag
                 .layer(private_short.clone())
@@ -107,18 +255,158 @@ fn build_api_router(state: AppState, apply_rate_limiting: bool) -> Router {
                 }
             },
         )
+        // Public school directory - no auth, publicly cached, stricter rate limit
+        .nest(
+            "/public/schools",
+            {
+                let public_router = init_public_directory_router()
+                    .layer(public_medium.clone())
+                    .layer(middleware::from_fn(etag_middleware));
+                #[cfg(not(test))]
+                {
+                    if apply_rate_limiting {
+                        let public_governor_config =
+                            state.rate_limit_config.public_governor_config();
+                        public_router.layer(GovernorLayer::new(public_governor_config))
+                    } else {
+                        public_router
+                    }
+                }
+                #[cfg(test)]
+                {
+                    public_router
+                }
+            },
+        )
         .nest(
             "/schools",
             init_schools_router()
+                .merge(init_trash_router())
+                .merge(init_retention_router())
+                .merge(init_public_profile_settings_router())
+                .merge(init_email_branding_router())
+                .merge(init_broadcasts_router())
+                .merge(init_dashboard_router())
+                .merge(init_webhooks_router())
+                .merge(init_sso_providers_router())
+                .merge(init_guardian_account_runs_router())
+                .merge(init_school_reports_router())
+                .merge(init_school_attendance_router())
+                .merge(init_naming_templates_router())
+                .merge(init_school_storage_router())
                 .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
                 // Schools: private cache, medium TTL with ETag
                 .layer(private_medium.clone())
                 .layer(middleware::from_fn(etag_middleware)),
         )
+        // School groups - system admins and group admins; group reports are
+        // live aggregates, so always revalidate
+        .nest(
+            "/groups",
+            init_groups_router()
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_group_admin,
+                ))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        // Change feed - always fresh, integrations poll it with a cursor
+        .nest(
+            "/changes",
+            init_changes_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+                .layer(no_cache.clone()),
+        )
+        // Request traces - support lookups by system admins, never cached
+        .nest(
+            "/admin/trace",
+            init_traces_router()
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_system_admin,
+                ))
+                .layer(no_cache.clone()),
+        )
+        // Background job health - polled by alerting, never cached
+        .nest(
+            "/admin/jobs",
+            init_jobs_router()
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_system_admin,
+                ))
+                .layer(no_cache.clone()),
+        )
+        // Outbound email status - live delivery state, never cached
+        .nest(
+            "/admin/emails",
+            init_emails_router()
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_system_admin,
+                ))
+                .layer(no_cache.clone()),
+        )
+        // Slow endpoint statistics - live counters, never cached
+        .nest(
+            "/admin/performance",
+            init_performance_router()
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_system_admin,
+                ))
+                .layer(no_cache.clone()),
+        )
+        // Feature flags - toggles must apply on the next request, never cached
+        .nest(
+            "/admin/feature-flags",
+            init_feature_flags_admin_router()
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_system_admin,
+                ))
+                .layer(no_cache.clone()),
+        )
+        .nest(
+            "/feature-flags",
+            init_feature_flags_router().layer(no_cache.clone()),
+        )
+        // Import validation - results depend on the uploaded files, never cached
+        .nest(
+            "/imports",
+            init_imports_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+                .layer(no_cache.clone()),
+        )
+        // Roster exchange with LMS products - exports hold the whole roster
+        .nest(
+            "/interop",
+            init_interop_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+                .layer(no_cache.clone()),
+        )
+        // LMS connections hold provider credentials
+        .nest(
+            "/sync",
+            init_sync_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+                .layer(no_cache.clone()),
+        )
         .nest(
             "/students",
             init_students_router()
-                .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+                // Document responses carry short-lived download links
+                .nest(
+                    "/{student_id}/documents",
+                    init_student_documents_router().layer(no_cache.clone()),
+                )
+                // Teachers pass the gate; scoped permissions limit them to
+                // students in branches they teach
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_teacher,
+                ))
                 // Students: private cache, short TTL
                 .layer(private_short.clone())
                 .layer(middleware::from_fn(etag_middleware)),
@@ -165,7 +453,205 @@ fn build_api_router(state: AppState, apply_rate_limiting: bool) -> Router {
                 .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
                 .layer(revalidate_always.clone())
                 .layer(middleware::from_fn(etag_middleware)),
-        );
+        )
+        // Staff leave - open to teachers so they can request their own leave;
+        // approval and school-wide views are gated by permissions
+        .nest(
+            "/staff-leave",
+            init_staff_leave_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_teacher))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        // Asset register - admin only, revalidated since assignments change often
+        .nest(
+            "/assets",
+            init_assets_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        // Fees and invoices - admin only; billing permissions gate the rest
+        .nest(
+            "/billing",
+            init_billing_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        .nest(
+            "/boarding",
+            init_boarding_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        // Transport - teachers reach their driver manifests; permissions gate the rest
+        .nest(
+            "/transport",
+            init_transport_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_teacher))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        // Visitor log - front desk staff; permissions gate the rest
+        .nest(
+            "/visitors",
+            init_visitor_log_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_teacher))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        // Visitor kiosks authenticate with an API key instead of a user token
+        .nest(
+            "/visitors/kiosk",
+            init_visitor_kiosk_router().layer(no_cache.clone()),
+        )
+        // Clinic records are health data - never cached; clinic permissions gate access
+        .nest(
+            "/clinic",
+            init_clinic_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_teacher))
+                .layer(no_cache.clone()),
+        )
+        // Discipline records - never cached; open to students and guardians, with
+        // scoped discipline permissions and guardian links gating access
+        .nest(
+            "/discipline",
+            init_discipline_router().layer(no_cache.clone()),
+        )
+        // Library - teachers staff the lending desk; permissions gate the rest
+        .nest(
+            "/library",
+            init_library_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_teacher))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        .nest(
+            "/alumni",
+            init_alumni_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        .nest(
+            "/custom-fields",
+            init_custom_fields_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_teacher))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        .nest(
+            "/me/saved-views",
+            init_saved_views_router()
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        // Attendance - teachers mark it; the attendance flag can turn it off per school
+        .nest(
+            "/attendance",
+            init_attendance_router()
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_attendance_enabled,
+                ))
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_teacher))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        .nest(
+            "/assessments",
+            init_assessments_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_teacher))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        .nest(
+            "/exams",
+            init_exams_router()
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_teacher,
+                ))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        .nest(
+            "/results",
+            init_results_router()
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_teacher,
+                ))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        .nest(
+            "/announcements",
+            init_announcements_router()
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_teacher,
+                ))
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        .nest(
+            "/notifications",
+            init_notifications_router()
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        // Private messages - never cached; open to guardians, with messaging
+        // permissions and conversation membership gating access
+        .nest(
+            "/messaging",
+            init_messaging_router().layer(no_cache.clone()),
+        )
+        .nest(
+            "/me/results",
+            init_my_results_router()
+                .layer(revalidate_always.clone())
+                .layer(middleware::from_fn(etag_middleware)),
+        )
+        // Student cards - teachers scan cards at the library desk
+        .nest(
+            "/student-cards",
+            init_student_cards_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_teacher))
+                .layer(no_cache.clone()),
+        )
+        .nest(
+            "/kiosk-devices",
+            init_kiosk_devices_router()
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_admin))
+                .layer(no_cache.clone()),
+        )
+        .nest("/me/kiosk-pin", init_kiosk_pin_router().layer(no_cache.clone()))
+        .nest(
+            "/me/avatar",
+            init_my_avatar_router().layer(no_cache.clone()),
+        )
+        .nest("/me", init_my_profiles_router().layer(no_cache.clone()))
+        .nest("/avatars", init_avatars_router().layer(no_cache.clone()))
+        // Signed download links from the local storage backend; the
+        // signature stands in for a user token
+        .nest(
+            "/storage",
+            init_storage_files_router().layer(no_cache.clone()),
+        )
+        // Attendance kiosks authenticate with a device token and a staff PIN
+        // session instead of a user token
+        .nest("/kiosk", init_kiosk_router().layer(no_cache.clone()));
+
+    // Per-user, per-school, and login and password reset limits in Redis,
+    // shared across instances
+    let api_routes = if apply_rate_limiting {
+        api_routes.layer(middleware::from_fn_with_state(state.clone(), rate_limit))
+    } else {
+        api_routes
+    };
 
     // Apply general rate limiting to all API routes (production only)
     #[cfg(not(test))]
@@ -176,22 +662,40 @@ fn build_api_router(state: AppState, apply_rate_limiting: bool) -> Router {
         api_routes
     };
 
-    #[cfg(feature = "scalar")]
     let router = Router::new()
-        .merge(Scalar::with_url("/scalar", ApiDoc::openapi()))
         .route("/health", axum::routing::get(health_handler))
-        .nest("/api", api_routes)
-        .nest_service("/files", ServeDir::new(PathBuf::from("./uploads")))
-        .with_state(state.clone());
+        .route("/health/ready", axum::routing::get(readiness_handler))
+        .route(
+            "/.well-known/jwks.json",
+            axum::routing::get(get_jwks).layer(public_medium),
+        )
+        .merge(init_docs_router(&state));
 
-    #[cfg(not(feature = "scalar"))]
-    let router = Router::new()
-        .route("/health", axum::routing::get(health_handler))
-        .nest("/api", api_routes)
+    // Every version shares the module routers; handlers whose DTOs differ
+    // between versions read the version with the `ApiVersion` extractor
+    let router = ApiVersion::ALL.iter().fold(router, |router, &version| {
+        router.nest(
+            version.prefix(),
+            api_routes
+                .clone()
+                .layer(middleware::from_fn_with_state(version, set_api_version)),
+        )
+    });
+
+    let router = router
+        // Unversioned alias kept for clients that predate versioning, with
+        // `Deprecation` and `Sunset` headers on every response
+        .nest(
+            "/api",
+            api_routes.layer(middleware::from_fn(deprecated_api_alias)),
+        )
         .nest_service("/files", ServeDir::new(PathBuf::from("./uploads")))
         .with_state(state.clone());
 
     let router = router
+        // Innermost, so tracing, metrics, and request logging see a caught
+        // panic as an ordinary 500 response
+        .layer(middleware::from_fn_with_state(state.clone(), report_errors))
         .layer({
             let allowed_origins = state
                 .cors_config
@@ -215,8 +719,22 @@ fn build_api_router(state: AppState, apply_rate_limiting: bool) -> Router {
                     axum::http::header::CONTENT_TYPE,
                     axum::http::header::ACCEPT,
                     axum::http::header::IF_NONE_MATCH,
+                    REQUEST_ID_HEADER,
+                    API_VERSION_HEADER,
+                ])
+                .expose_headers([
+                    axum::http::header::ETAG,
+                    axum::http::header::CACHE_CONTROL,
+                    axum::http::header::RETRY_AFTER,
+                    RATE_LIMIT_LIMIT,
+                    RATE_LIMIT_REMAINING,
+                    RATE_LIMIT_RESET,
+                    REQUEST_ID_HEADER,
+                    API_VERSION_HEADER,
+                    DEPRECATION_HEADER,
+                    SUNSET_HEADER,
+                    axum::http::header::LINK,
                 ])
-                .expose_headers([axum::http::header::ETAG, axum::http::header::CACHE_CONTROL])
                 .allow_credentials(true)
         })
         .layer(
@@ -232,16 +750,26 @@ fn build_api_router(state: AppState, apply_rate_limiting: bool) -> Router {
                         .latency_unit(LatencyUnit::Millis)
                         .include_headers(true),
                 ),
-        );
+        )
+        // Outside the trace layer, so logged headers show credentials as `Sensitive`
+        .layer(SetSensitiveRequestHeadersLayer::new([
+            header::AUTHORIZATION,
+            header::COOKIE,
+            HeaderName::from_static(KIOSK_KEY_HEADER),
+        ]))
+        .layer(SetSensitiveResponseHeadersLayer::new([header::SET_COOKIE]));
 
     // Conditionally apply observability middleware
-    if is_observability_enabled() {
+    let router = if is_observability_enabled() {
         router
             .layer(middleware::from_fn(metrics_middleware))
             .layer(middleware::from_fn(logging_middleware))
     } else {
         router
-    }
+    };
+
+    // Outermost, so every layer and handler sees the request ID
+    router.layer(middleware::from_fn_with_state(state, assign_request_id))
 }
 
 /// Initialize router with rate limiting (for production use)
@@ -255,3 +783,13 @@ pub fn init_router(state: AppState) -> Router {
 pub fn init_router(state: AppState) -> Router {
     build_api_router(state, false)
 }
+
+/// Initialize router without rate limiting (for integration tests)
+///
+/// Integration tests link against the library built without `cfg(test)`, and
+/// the per-IP governor rejects `oneshot` requests because they carry no peer
+/// address.
+#[allow(dead_code)]
+pub fn init_router_without_rate_limiting(state: AppState) -> Router {
+    build_api_router(state, false)
+}
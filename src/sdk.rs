@@ -0,0 +1,1018 @@
+//! # Client SDK Models
+//!
+//! Generates TypeScript and Dart models for the web and mobile apps from the
+//! OpenAPI document built by [`ApiDoc`], so the apps are typed against the
+//! same schemas the handlers are annotated with. The canonical document
+//! itself is written next to them as `openapi.json`, for client generators
+//! that run outside this repository.
+//!
+//! [`check`] is the drift guard: it lists every schema reference that does not
+//! resolve (a type that was used in a handler annotation but never derived or
+//! registered `ToSchema`) and every JSON body documented without a schema. The
+//! `chalkbyte-sdk` binary refuses to generate while it reports problems, and a
+//! unit test runs it against [`ApiDoc`] so `cargo test` fails first.
+//!
+//! ```bash
+//! cargo run --bin chalkbyte-sdk -- generate --out sdk
+//! cargo run --bin chalkbyte-sdk -- check --out sdk   # also fails on stale files
+//! cargo run --bin chalkbyte-sdk -- openapi --out openapi.json
+//! ```
+//!
+//! [`ApiDoc`]: crate::docs::ApiDoc
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use serde_json::Value;
+
+use crate::docs;
+
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+
+/// A generated file, relative to the output directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    pub path: &'static str,
+    pub contents: String,
+}
+
+/// The API's canonical OpenAPI document as JSON; see [`docs::openapi`].
+pub fn document() -> Value {
+    serde_json::to_value(docs::openapi()).expect("OpenAPI document serializes to JSON")
+}
+
+/// `doc` as the contents of `openapi.json`: pretty-printed, with object keys
+/// sorted and a trailing newline, so regenerating an unchanged API rewrites
+/// the file byte for byte.
+pub fn openapi_json(doc: &Value) -> String {
+    let mut json = serde_json::to_string_pretty(doc).expect("JSON values serialize");
+    json.push('\n');
+    json
+}
+
+/// Problems that would leave the generated models incomplete, one per line.
+pub fn check(doc: &Value) -> Vec<String> {
+    let schemas = doc
+        .pointer("/components/schemas")
+        .and_then(Value::as_object);
+    let mut problems = Vec::new();
+
+    let mut refs = Vec::new();
+    collect_refs(doc, "#", &mut refs);
+    for (location, reference) in refs {
+        let Some(name) = reference.strip_prefix(SCHEMA_REF_PREFIX) else {
+            continue;
+        };
+        if !schemas.is_some_and(|schemas| schemas.contains_key(name)) {
+            problems.push(format!(
+                "{location}: schema `{name}` is referenced but not registered (missing ToSchema?)"
+            ));
+        }
+    }
+
+    let paths = doc.get("paths").and_then(Value::as_object);
+    for (path, operations) in paths.into_iter().flatten() {
+        let Some(operations) = operations.as_object() else {
+            continue;
+        };
+        for (method, operation) in operations {
+            let method = method.to_uppercase();
+            if let Some(content) = operation.pointer("/requestBody/content") {
+                for media_type in json_bodies_without_schema(content) {
+                    problems.push(format!(
+                        "{method} {path}: request body ({media_type}) has no schema"
+                    ));
+                }
+            }
+            let responses = operation.get("responses").and_then(Value::as_object);
+            for (status, response) in responses.into_iter().flatten() {
+                if let Some(content) = response.get("content") {
+                    for media_type in json_bodies_without_schema(content) {
+                        problems.push(format!(
+                            "{method} {path}: {status} response ({media_type}) has no schema"
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    problems
+}
+
+/// Every generated file for `doc`.
+pub fn artifacts(doc: &Value) -> Vec<Artifact> {
+    vec![
+        Artifact {
+            path: "openapi.json",
+            contents: openapi_json(doc),
+        },
+        Artifact {
+            path: "typescript/models.ts",
+            contents: typescript(doc),
+        },
+        Artifact {
+            path: "dart/models.dart",
+            contents: dart(doc),
+        },
+    ]
+}
+
+/// TypeScript declarations for every schema in `doc`.
+pub fn typescript(doc: &Value) -> String {
+    let models = Models::from_document(doc);
+    let mut out = header(doc);
+
+    for (name, model) in &models.0 {
+        out.push('\n');
+        write_ts_doc(&mut out, "", model.description.as_deref());
+        match &model.kind {
+            Kind::Object(fields) => {
+                let _ = writeln!(out, "export interface {name} {{");
+                for field in fields {
+                    write_ts_doc(&mut out, "  ", field.description.as_deref());
+                    let optional = if field.required { "" } else { "?" };
+                    let _ = writeln!(
+                        out,
+                        "  {}{optional}: {};",
+                        ts_key(&field.name),
+                        ts_type(&field.ty)
+                    );
+                }
+                out.push_str("}\n");
+            }
+            Kind::Enum(values) => {
+                let _ = writeln!(out, "export type {name} = {};", ts_literals(values));
+            }
+            Kind::Alias(ty) => {
+                let _ = writeln!(out, "export type {name} = {};", ts_type(ty));
+            }
+        }
+    }
+
+    out
+}
+
+/// Dart classes, with JSON conversion, for every schema in `doc`.
+pub fn dart(doc: &Value) -> String {
+    let models = Models::from_document(doc);
+    let mut out = header(doc);
+
+    for (name, model) in &models.0 {
+        out.push('\n');
+        write_dart_doc(&mut out, "", model.description.as_deref());
+        match &model.kind {
+            Kind::Object(fields) => write_dart_class(&mut out, &models, name, fields),
+            Kind::Enum(values) => write_dart_enum(&mut out, name, values),
+            Kind::Alias(ty) => {
+                let _ = writeln!(out, "typedef {name} = {};", dart_type(ty));
+            }
+        }
+    }
+
+    out
+}
+
+fn header(doc: &Value) -> String {
+    let title = doc.pointer("/info/title").and_then(Value::as_str);
+    let version = doc.pointer("/info/version").and_then(Value::as_str);
+    format!(
+        "// Generated by chalkbyte-sdk from the OpenAPI document of {} {}.\n\
+         // Do not edit by hand; run `cargo run --bin chalkbyte-sdk -- generate`.\n",
+        title.unwrap_or("the API"),
+        version.unwrap_or_default(),
+    )
+}
+
+fn collect_refs(value: &Value, location: &str, refs: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                if key == "$ref"
+                    && let Some(reference) = child.as_str()
+                {
+                    refs.push((location.to_string(), reference.to_string()));
+                } else {
+                    collect_refs(child, &format!("{location}/{key}"), refs);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                collect_refs(child, &format!("{location}/{index}"), refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn json_bodies_without_schema(content: &Value) -> Vec<&str> {
+    content
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(media_type, body)| media_type.contains("json") && body.get("schema").is_none())
+        .map(|(media_type, _)| media_type.as_str())
+        .collect()
+}
+
+/// A field or item type, resolved from a JSON schema.
+#[derive(Debug, Clone, PartialEq)]
+enum Ty {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    /// A string restricted to these values.
+    Literals(Vec<String>),
+    Named(String),
+    Array(Box<Ty>),
+    Map(Box<Ty>),
+    Nullable(Box<Ty>),
+    Any,
+}
+
+impl Ty {
+    fn from_schema(schema: &Value) -> Self {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            return match reference.strip_prefix(SCHEMA_REF_PREFIX) {
+                Some(name) => Ty::Named(type_name(name)),
+                None => Ty::Any,
+            };
+        }
+
+        for key in ["oneOf", "anyOf"] {
+            if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+                let (nulls, others): (Vec<_>, Vec<_>) = variants
+                    .iter()
+                    .partition(|variant| variant.get("type") == Some(&Value::from("null")));
+                return match (nulls.is_empty(), others.as_slice()) {
+                    (false, [other]) => Ty::from_schema(other).nullable(),
+                    (true, [other]) => Ty::from_schema(other),
+                    _ => Ty::Any,
+                };
+            }
+        }
+        if let Some([only]) = schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+        {
+            return Ty::from_schema(only);
+        }
+
+        match schema.get("type") {
+            Some(Value::String(ty)) => Ty::from_type(ty, schema),
+            Some(Value::Array(types)) => {
+                let types: Vec<&str> = types.iter().filter_map(Value::as_str).collect();
+                let nullable = types.contains(&"null");
+                let ty = match types.iter().find(|ty| **ty != "null") {
+                    Some(ty) => Ty::from_type(ty, schema),
+                    None => Ty::Any,
+                };
+                if nullable { ty.nullable() } else { ty }
+            }
+            _ => Ty::Any,
+        }
+    }
+
+    fn from_type(ty: &str, schema: &Value) -> Self {
+        match ty {
+            "string" => match schema.get("enum").and_then(Value::as_array) {
+                Some(values) => Ty::Literals(
+                    values
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect(),
+                ),
+                None => Ty::String,
+            },
+            "integer" => Ty::Integer,
+            "number" => Ty::Number,
+            "boolean" => Ty::Boolean,
+            "array" => Ty::Array(Box::new(
+                schema.get("items").map_or(Ty::Any, Ty::from_schema),
+            )),
+            "object" => match schema.get("additionalProperties") {
+                Some(values @ Value::Object(_)) => Ty::Map(Box::new(Ty::from_schema(values))),
+                _ => Ty::Map(Box::new(Ty::Any)),
+            },
+            _ => Ty::Any,
+        }
+    }
+
+    fn nullable(self) -> Self {
+        match self {
+            Ty::Nullable(_) | Ty::Any => self,
+            ty => Ty::Nullable(Box::new(ty)),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Field {
+    name: String,
+    ty: Ty,
+    required: bool,
+    description: Option<String>,
+}
+
+#[derive(Debug)]
+enum Kind {
+    Object(Vec<Field>),
+    Enum(Vec<String>),
+    Alias(Ty),
+}
+
+#[derive(Debug)]
+struct Model {
+    kind: Kind,
+    description: Option<String>,
+}
+
+/// The document's named schemas, keyed by generated type name.
+struct Models(BTreeMap<String, Model>);
+
+impl Models {
+    fn from_document(doc: &Value) -> Self {
+        let mut schemas = doc
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+
+        // Generic wrappers such as `CursorPage<T>` inline their item schema;
+        // Dart has no anonymous classes, so those become named models too.
+        let mut hoisted = Vec::new();
+        for (name, schema) in schemas.iter_mut() {
+            hoist_inline_objects(name, schema, true, &mut hoisted);
+        }
+        for (name, schema) in hoisted {
+            let mut unique = name.clone();
+            let mut n = 2;
+            while schemas.contains_key(&unique) {
+                unique = format!("{name}{n}");
+                n += 1;
+            }
+            schemas.insert(unique, schema);
+        }
+        let schemas = &schemas;
+
+        let models = schemas
+            .iter()
+            .map(|(name, schema)| {
+                let kind = match object_fields(schemas, schema) {
+                    Some(fields) => Kind::Object(fields),
+                    None => match Ty::from_schema(schema) {
+                        Ty::Literals(values) => Kind::Enum(values),
+                        ty => Kind::Alias(ty),
+                    },
+                };
+                let model = Model {
+                    kind,
+                    description: description(schema),
+                };
+                (type_name(name), model)
+            })
+            .collect();
+
+        Models(models)
+    }
+
+    /// Follows aliases to the type a named type is built from.
+    fn resolve<'a>(&'a self, ty: &'a Ty) -> &'a Ty {
+        let mut ty = ty;
+        // Bounded, in case of an alias cycle.
+        for _ in 0..16 {
+            match ty {
+                Ty::Named(name) => match self.0.get(name).map(|model| &model.kind) {
+                    Some(Kind::Alias(target)) => ty = target,
+                    _ => return ty,
+                },
+                _ => return ty,
+            }
+        }
+        ty
+    }
+
+    fn kind(&self, name: &str) -> Option<&Kind> {
+        self.0.get(name).map(|model| &model.kind)
+    }
+}
+
+/// Moves object schemas nested below `schema` into `hoisted`, named after
+/// `owner` and the property they were found under, leaving a `$ref` in place.
+fn hoist_inline_objects(
+    owner: &str,
+    schema: &mut Value,
+    top_level: bool,
+    hoisted: &mut Vec<(String, Value)>,
+) {
+    if !top_level && schema.get("properties").is_some() {
+        let mut inline = schema.take();
+        hoist_inline_objects(owner, &mut inline, true, hoisted);
+        *schema = serde_json::json!({ "$ref": format!("{SCHEMA_REF_PREFIX}{owner}") });
+        hoisted.push((owner.to_string(), inline));
+        return;
+    }
+
+    let Some(map) = schema.as_object_mut() else {
+        return;
+    };
+    if let Some(properties) = map.get_mut("properties").and_then(Value::as_object_mut) {
+        for (name, property) in properties.iter_mut() {
+            let owner = format!("{owner}{}", pascal_case(name));
+            hoist_inline_objects(&owner, property, false, hoisted);
+        }
+    }
+    if let Some(items) = map.get_mut("items") {
+        hoist_inline_objects(&format!("{owner}Item"), items, false, hoisted);
+    }
+    if let Some(values) = map.get_mut("additionalProperties") {
+        hoist_inline_objects(&format!("{owner}Value"), values, false, hoisted);
+    }
+    // `allOf` members are flattened into the owner's own fields.
+    if let Some(members) = map.get_mut("allOf").and_then(Value::as_array_mut) {
+        for member in members {
+            hoist_inline_objects(owner, member, top_level, hoisted);
+        }
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(variants) = map.get_mut(key).and_then(Value::as_array_mut) {
+            let several = variants
+                .iter()
+                .filter(|variant| variant.get("type") != Some(&Value::from("null")))
+                .count()
+                > 1;
+            for (index, variant) in variants.iter_mut().enumerate() {
+                let owner = if several {
+                    format!("{owner}Variant{}", index + 1)
+                } else {
+                    owner.to_string()
+                };
+                hoist_inline_objects(&owner, variant, false, hoisted);
+            }
+        }
+    }
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// The fields of an object schema, flattening `allOf` members; `None` when the
+/// schema is not an object with named properties.
+fn object_fields(schemas: &serde_json::Map<String, Value>, schema: &Value) -> Option<Vec<Field>> {
+    if let Some(members) = schema.get("allOf").and_then(Value::as_array) {
+        let mut fields = Vec::new();
+        for member in members {
+            let member = match member
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix(SCHEMA_REF_PREFIX))
+            {
+                Some(name) => schemas.get(name)?,
+                None => member,
+            };
+            fields.extend(object_fields(schemas, member)?);
+        }
+        return Some(fields);
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object)?;
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+
+    Some(
+        properties
+            .iter()
+            .map(|(name, property)| Field {
+                name: name.clone(),
+                ty: Ty::from_schema(property),
+                required: required.contains(&name.as_str()),
+                description: description(property),
+            })
+            .collect(),
+    )
+}
+
+fn description(schema: &Value) -> Option<String> {
+    let from_variants = || {
+        ["oneOf", "anyOf", "allOf"]
+            .iter()
+            .filter_map(|key| schema.get(key).and_then(Value::as_array))
+            .flatten()
+            .find_map(|variant| variant.get("description"))
+    };
+    schema
+        .get("description")
+        .or_else(from_variants)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+/// Schema names as valid identifiers in both languages.
+fn type_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn ts_type(ty: &Ty) -> String {
+    match ty {
+        Ty::String => "string".to_string(),
+        Ty::Integer | Ty::Number => "number".to_string(),
+        Ty::Boolean => "boolean".to_string(),
+        Ty::Literals(values) => ts_literals(values),
+        Ty::Named(name) => name.clone(),
+        Ty::Array(item) => format!("Array<{}>", ts_type(item)),
+        Ty::Map(value) => format!("Record<string, {}>", ts_type(value)),
+        Ty::Nullable(inner) => format!("{} | null", ts_type(inner)),
+        Ty::Any => "unknown".to_string(),
+    }
+}
+
+fn ts_literals(values: &[String]) -> String {
+    if values.is_empty() {
+        return "never".to_string();
+    }
+    values
+        .iter()
+        .map(|value| serde_json::to_string(value).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+fn ts_key(name: &str) -> String {
+    let is_identifier = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        serde_json::to_string(name).unwrap_or_default()
+    }
+}
+
+fn write_ts_doc(out: &mut String, indent: &str, text: Option<&str>) {
+    let Some(text) = text else {
+        return;
+    };
+    let text = text.replace("*/", "*\\/");
+    let lines: Vec<&str> = text.lines().collect();
+    if let [line] = lines.as_slice() {
+        let _ = writeln!(out, "{indent}/** {line} */");
+        return;
+    }
+    let _ = writeln!(out, "{indent}/**");
+    for line in lines {
+        let _ = writeln!(out, "{indent} * {line}");
+    }
+    let _ = writeln!(out, "{indent} */");
+}
+
+const DART_KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "assert",
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "covariant",
+    "default",
+    "deferred",
+    "do",
+    "dynamic",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "extension",
+    "external",
+    "factory",
+    "false",
+    "final",
+    "finally",
+    "for",
+    "Function",
+    "get",
+    "hide",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "interface",
+    "is",
+    "late",
+    "library",
+    "mixin",
+    "new",
+    "null",
+    "on",
+    "operator",
+    "part",
+    "required",
+    "rethrow",
+    "return",
+    "set",
+    "show",
+    "static",
+    "super",
+    "switch",
+    "sync",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Names an enum constant may not take, because `enum` or the generated
+/// members already use them.
+const DART_ENUM_RESERVED: &[&str] = &["values", "index", "name", "hashCode", "value"];
+
+/// `snake_case` and `kebab-case` JSON names as Dart `lowerCamelCase`.
+fn dart_identifier(name: &str, reserved: &[&str]) -> String {
+    let mut identifier = String::new();
+    let mut upper_next = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if upper_next && !identifier.is_empty() {
+                identifier.push(c.to_ascii_uppercase());
+            } else if identifier.is_empty() {
+                identifier.push(c.to_ascii_lowercase());
+            } else {
+                identifier.push(c);
+            }
+            upper_next = false;
+        } else {
+            upper_next = true;
+        }
+    }
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert_str(0, "value");
+    }
+    if DART_KEYWORDS.contains(&identifier.as_str()) || reserved.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+    identifier
+}
+
+fn dart_type(ty: &Ty) -> String {
+    match ty {
+        Ty::String | Ty::Literals(_) => "String".to_string(),
+        Ty::Integer => "int".to_string(),
+        Ty::Number => "double".to_string(),
+        Ty::Boolean => "bool".to_string(),
+        Ty::Named(name) => name.clone(),
+        Ty::Array(item) => format!("List<{}>", dart_type(item)),
+        Ty::Map(value) => format!("Map<String, {}>", dart_type(value)),
+        Ty::Nullable(inner) => dart_optional(inner),
+        Ty::Any => "dynamic".to_string(),
+    }
+}
+
+fn dart_optional(ty: &Ty) -> String {
+    match ty {
+        Ty::Any | Ty::Nullable(_) => dart_type(ty),
+        ty => format!("{}?", dart_type(ty)),
+    }
+}
+
+/// A Dart expression decoding the JSON value `expr` as `ty`.
+fn dart_decode(models: &Models, expr: &str, ty: &Ty, depth: usize) -> String {
+    match models.resolve(ty) {
+        Ty::String | Ty::Literals(_) => format!("{expr} as String"),
+        Ty::Integer => format!("({expr} as num).toInt()"),
+        Ty::Number => format!("({expr} as num).toDouble()"),
+        Ty::Boolean => format!("{expr} as bool"),
+        Ty::Any => expr.to_string(),
+        Ty::Named(name) => match models.kind(name) {
+            Some(Kind::Enum(_)) => format!("{name}.fromJson({expr} as String)"),
+            Some(Kind::Object(_)) => {
+                format!("{name}.fromJson({expr} as Map<String, dynamic>)")
+            }
+            _ => expr.to_string(),
+        },
+        Ty::Array(item) => {
+            let var = format!("e{depth}");
+            format!(
+                "({expr} as List<dynamic>).map(({var}) => {}).toList()",
+                dart_decode(models, &var, item, depth + 1)
+            )
+        }
+        Ty::Map(value) => {
+            let var = format!("v{depth}");
+            format!(
+                "({expr} as Map<String, dynamic>).map((k{depth}, {var}) => MapEntry(k{depth}, {}))",
+                dart_decode(models, &var, value, depth + 1)
+            )
+        }
+        Ty::Nullable(inner) => format!(
+            "{expr} == null ? null : {}",
+            dart_decode(models, expr, inner, depth)
+        ),
+    }
+}
+
+/// A Dart expression encoding the non-null value `expr` of type `ty` as JSON.
+fn dart_encode(models: &Models, expr: &str, ty: &Ty, depth: usize) -> String {
+    match models.resolve(ty) {
+        Ty::Named(name) => match models.kind(name) {
+            Some(Kind::Enum(_) | Kind::Object(_)) => format!("{expr}.toJson()"),
+            _ => expr.to_string(),
+        },
+        Ty::Array(item) => {
+            let var = format!("e{depth}");
+            let encoded = dart_encode(models, &var, item, depth + 1);
+            if encoded == var {
+                expr.to_string()
+            } else {
+                format!("{expr}.map(({var}) => {encoded}).toList()")
+            }
+        }
+        Ty::Map(value) => {
+            let var = format!("v{depth}");
+            let encoded = dart_encode(models, &var, value, depth + 1);
+            if encoded == var {
+                expr.to_string()
+            } else {
+                format!("{expr}.map((k{depth}, {var}) => MapEntry(k{depth}, {encoded}))")
+            }
+        }
+        Ty::Nullable(inner) => {
+            let encoded = dart_encode(models, &format!("{expr}!"), inner, depth);
+            if encoded == format!("{expr}!") {
+                expr.to_string()
+            } else {
+                format!("{expr} == null ? null : {encoded}")
+            }
+        }
+        _ => expr.to_string(),
+    }
+}
+
+fn write_dart_class(out: &mut String, models: &Models, name: &str, fields: &[Field]) {
+    let fields: Vec<(String, &Field)> = fields
+        .iter()
+        .map(|field| (dart_identifier(&field.name, &[]), field))
+        .collect();
+    // Absent fields decode to null, so only required ones keep their type.
+    let field_type = |field: &Field| {
+        if field.required {
+            dart_type(&field.ty)
+        } else {
+            dart_optional(&field.ty)
+        }
+    };
+
+    let _ = writeln!(out, "class {name} {{");
+    for (identifier, field) in &fields {
+        write_dart_doc(out, "  ", field.description.as_deref());
+        let _ = writeln!(out, "  final {} {identifier};", field_type(field));
+    }
+    if !fields.is_empty() {
+        out.push('\n');
+    }
+
+    if fields.is_empty() {
+        let _ = writeln!(out, "  const {name}();");
+    } else {
+        let _ = writeln!(out, "  const {name}({{");
+        for (identifier, field) in &fields {
+            let required = if field_type(field).ends_with('?') || field_type(field) == "dynamic" {
+                ""
+            } else {
+                "required "
+            };
+            let _ = writeln!(out, "    {required}this.{identifier},");
+        }
+        out.push_str("  });\n");
+    }
+
+    out.push('\n');
+    if fields.is_empty() {
+        let _ = writeln!(
+            out,
+            "  factory {name}.fromJson(Map<String, dynamic> json) => const {name}();"
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "  factory {name}.fromJson(Map<String, dynamic> json) => {name}("
+        );
+        for (identifier, field) in &fields {
+            let key = format!("json[{}]", dart_string(&field.name));
+            let ty = if field.required {
+                field.ty.clone()
+            } else {
+                field.ty.clone().nullable()
+            };
+            let _ = writeln!(
+                out,
+                "        {identifier}: {},",
+                dart_decode(models, &key, &ty, 0)
+            );
+        }
+        out.push_str("      );\n");
+    }
+
+    out.push('\n');
+    out.push_str("  Map<String, dynamic> toJson() => <String, dynamic>{\n");
+    for (identifier, field) in &fields {
+        let key = dart_string(&field.name);
+        if field.required {
+            let _ = writeln!(
+                out,
+                "        {key}: {},",
+                dart_encode(models, identifier, &field.ty, 0)
+            );
+        } else {
+            let value = match &field.ty {
+                Ty::Nullable(inner) => inner.as_ref(),
+                ty => ty,
+            };
+            let _ = writeln!(
+                out,
+                "        if ({identifier} != null) {key}: {},",
+                dart_encode(models, &format!("{identifier}!"), value, 0)
+            );
+        }
+    }
+    out.push_str("      };\n");
+    out.push_str("}\n");
+}
+
+fn write_dart_enum(out: &mut String, name: &str, values: &[String]) {
+    let mut identifiers: Vec<String> = Vec::new();
+    for value in values {
+        let base = dart_identifier(value, DART_ENUM_RESERVED);
+        let mut identifier = base.clone();
+        let mut n = 2;
+        while identifiers.contains(&identifier) {
+            identifier = format!("{base}{n}");
+            n += 1;
+        }
+        identifiers.push(identifier);
+    }
+
+    let _ = writeln!(out, "enum {name} {{");
+    for (index, (identifier, value)) in identifiers.iter().zip(values).enumerate() {
+        let separator = if index + 1 == values.len() { ';' } else { ',' };
+        let _ = writeln!(out, "  {identifier}({}){separator}", dart_string(value));
+    }
+    out.push('\n');
+    let _ = writeln!(out, "  const {name}(this.value);");
+    out.push('\n');
+    out.push_str("  final String value;\n");
+    out.push('\n');
+    let _ = writeln!(
+        out,
+        "  static {name} fromJson(String value) => values.firstWhere((e) => e.value == value);"
+    );
+    out.push('\n');
+    out.push_str("  String toJson() => value;\n");
+    out.push_str("}\n");
+}
+
+fn dart_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('\'', "\\'")
+        .replace('$', "\\$");
+    format!("'{escaped}'")
+}
+
+fn write_dart_doc(out: &mut String, indent: &str, text: Option<&str>) {
+    for line in text.into_iter().flat_map(str::lines) {
+        let line = format!("{indent}/// {line}");
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Value {
+        json!({
+            "info": { "title": "Test", "version": "1.0.0" },
+            "paths": {},
+            "components": { "schemas": {
+                "Status": { "type": "string", "enum": ["in_progress", "done"] },
+                "TaskId": { "type": "string", "format": "uuid" },
+                "Task": {
+                    "type": "object",
+                    "required": ["id", "status", "tags"],
+                    "properties": {
+                        "id": { "$ref": "#/components/schemas/TaskId" },
+                        "status": { "$ref": "#/components/schemas/Status" },
+                        "tags": { "type": "array", "items": { "type": "string" } },
+                        "parent": { "oneOf": [
+                            { "type": "null" },
+                            { "$ref": "#/components/schemas/Task" }
+                        ] }
+                    }
+                }
+            } }
+        })
+    }
+
+    #[test]
+    fn test_api_doc_has_no_missing_schemas() {
+        let problems = check(&document());
+        assert!(problems.is_empty(), "{}", problems.join("\n"));
+    }
+
+    #[test]
+    fn test_openapi_json_is_canonical() {
+        let doc = document();
+        let paths = doc["paths"].as_object().unwrap();
+        assert!(paths.contains_key("/api/v1/students"));
+        assert!(paths.contains_key("/.well-known/jwks.json"));
+        assert!(
+            paths
+                .keys()
+                .all(|path| !path.starts_with("/api/") || path.starts_with("/api/v1/"))
+        );
+
+        let json = openapi_json(&doc);
+        assert!(json.ends_with("}\n"));
+        assert_eq!(json, openapi_json(&document()));
+        assert_eq!(serde_json::from_str::<Value>(&json).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_check_reports_unregistered_schemas_and_bodies() {
+        let mut doc = sample();
+        doc["components"]["schemas"]["Task"]["properties"]["owner"] =
+            json!({ "$ref": "#/components/schemas/User" });
+        doc["paths"] = json!({ "/tasks": { "post": {
+            "requestBody": { "content": { "application/json": {} } },
+            "responses": {}
+        } } });
+
+        let problems = check(&doc);
+
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(problems[0].contains("schema `User`"));
+        assert!(problems[1].starts_with("POST /tasks: request body"));
+    }
+
+    #[test]
+    fn test_typescript_models() {
+        let ts = typescript(&sample());
+
+        assert!(ts.contains("export type Status = \"in_progress\" | \"done\";"));
+        assert!(ts.contains("export type TaskId = string;"));
+        assert!(ts.contains("  id: TaskId;\n"));
+        assert!(ts.contains("  parent?: Task | null;\n"));
+        assert!(ts.contains("  tags: Array<string>;\n"));
+    }
+
+    #[test]
+    fn test_dart_models() {
+        let dart = dart(&sample());
+
+        assert!(dart.contains("  inProgress('in_progress'),\n"));
+        assert!(dart.contains("typedef TaskId = String;"));
+        assert!(dart.contains("  final Task? parent;\n"));
+        assert!(dart.contains("        status: Status.fromJson(json['status'] as String),\n"));
+        assert!(dart.contains(
+            "        parent: json['parent'] == null ? null : Task.fromJson(json['parent'] as Map<String, dynamic>),\n"
+        ));
+        assert!(dart.contains("        if (parent != null) 'parent': parent!.toJson(),\n"));
+    }
+}
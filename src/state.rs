@@ -11,7 +11,8 @@
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     let state = init_app_state().await;
+//!     let config = ChalkbyteConfig::load(None).expect("invalid configuration");
+//!     let state = init_app_state(&config).await.expect("database unavailable");
 //!     // Pass state to router
 //!     let app = Router::new().with_state(state);
 //! }
@@ -21,13 +22,23 @@ use std::sync::Arc;
 use std::time::Duration;
 use std::fmt;
 
-use chalkbyte_cache::{CacheConfig, RedisCache};
-use chalkbyte_config::{CorsConfig, EmailConfig, JwtConfig, RateLimitConfig};
-use chalkbyte_core::{FileStorage, LocalFileStorage};
-use chalkbyte_db::{PgPool, init_db_pool};
+use chalkbyte_cache::{
+    CacheConfig, CachedTokenStore, MfaChallengeStore, RedisCache, RedisMfaChallengeStore,
+    TokenStore,
+};
+use chalkbyte_config::{
+    ChalkbyteConfig, CorsConfig, DocsConfig, EmailConfig, JwtConfig, OidcConfig, RateLimitConfig,
+    StorageConfig, WebauthnConfig,
+};
+use chalkbyte_core::{FileStorage, LocalFileStorage, PasswordPolicy};
+use chalkbyte_db::{DbError, DbPools, PgPool, init_db_pools};
 use std::path::PathBuf;
 use tracing::{info, warn};
 
+use crate::modules::storage::backend::{StorageBackend, init_storage_backend};
+use crate::utils::mfa_challenge::PgMfaChallengeStore;
+use crate::utils::token_store::PgTokenStore;
+
 /// Shared application state passed to all request handlers.
 ///
 /// This struct is cloned for each request, so all fields must be cheaply
@@ -36,12 +47,21 @@ use tracing::{info, warn};
 /// # Fields
 ///
 /// - `db`: PostgreSQL connection pool for database operations
+/// - `pools`: The same primary pool plus read replicas for heavy reads
 /// - `jwt_config`: JWT configuration for token creation/verification
 /// - `email_config`: Email/SMTP configuration for sending emails
 /// - `cors_config`: CORS configuration for cross-origin requests
 /// - `rate_limit_config`: Rate limiting configuration (reserved for future use)
+/// - `password_policy`: Rules new passwords must meet
+/// - `webauthn_config`: Relying party settings for passkeys
+/// - `oidc_config`: Callback settings for SSO identity providers
+/// - `docs_config`: Who may read the OpenAPI spec and UI
 /// - `cache`: Optional Redis cache for distributed caching
 /// - `file_storage`: File storage backend for uploads (local filesystem, S3, etc.)
+/// - `storage`: Private storage for avatars and student documents (local or S3)
+/// - `storage_config`: Download link lifetime and the default school quota
+/// - `token_store`: Refresh token store (PostgreSQL, with revocations cached in Redis when available)
+/// - `mfa_challenges`: Pending MFA login store (Redis when available, otherwise PostgreSQL)
 #[derive(Clone)]
 pub struct AppState {
     /// PostgreSQL connection pool.
@@ -49,6 +69,13 @@ pub struct AppState {
     /// Use this pool to execute database queries in handlers and services.
     pub db: PgPool,
 
+    /// Primary and read replica pools.
+    ///
+    /// The primary is the same pool as `db`. Heavy list and search queries
+    /// run on a replica through [`DbPools::read_only`]; writes always use
+    /// `db`.
+    pub pools: DbPools,
+
     /// JWT configuration for authentication.
     ///
     /// Contains the secret key and token expiry settings.
@@ -71,6 +98,28 @@ pub struct AppState {
     #[allow(dead_code)]
     pub rate_limit_config: RateLimitConfig,
 
+    /// Password policy.
+    ///
+    /// Checked wherever a new password is set, along with the user's
+    /// password history on change and reset.
+    pub password_policy: PasswordPolicy,
+
+    /// WebAuthn relying party configuration.
+    ///
+    /// Identifies the site passkeys are registered for and checked against.
+    pub webauthn_config: WebauthnConfig,
+
+    /// OpenID Connect configuration.
+    ///
+    /// Builds the callback URLs registered with schools' identity providers.
+    pub oidc_config: OidcConfig,
+
+    /// API documentation access.
+    ///
+    /// Decides whether the full OpenAPI spec is public, admin-only, or not
+    /// served at all.
+    pub docs_config: DocsConfig,
+
     /// Redis cache configuration.
     ///
     /// Used for cache key generation and TTL settings.
@@ -80,51 +129,94 @@ pub struct AppState {
     /// Redis cache client for distributed caching.
     ///
     /// Optional - if Redis is unavailable, the application continues without caching.
+    /// See [`chalkbyte_cache::health`] for how an outage after startup is handled.
     pub cache: Option<RedisCache>,
 
     /// File storage backend for handling uploads.
     ///
     /// Abstracted trait allowing different storage implementations (local FS, S3, etc.).
     pub file_storage: Arc<dyn FileStorage>,
+
+    /// Private file storage for avatars and student documents.
+    ///
+    /// Files are only reachable through presigned links, unlike `file_storage`.
+    pub storage: Arc<dyn StorageBackend>,
+
+    /// File storage configuration.
+    ///
+    /// Sets how long download links work and the default school quota.
+    pub storage_config: StorageConfig,
+
+    /// Refresh token store tracking token families for rotation and logout.
+    ///
+    /// Backed by Redis when the cache is connected, otherwise by PostgreSQL.
+    pub token_store: Arc<dyn TokenStore>,
+
+    /// Store holding pending MFA logins, their allowed methods and attempts.
+    ///
+    /// Backed by Redis when the cache is connected, otherwise by PostgreSQL.
+    pub mfa_challenges: Arc<dyn MfaChallengeStore>,
 }
 
 impl fmt::Debug for AppState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("AppState")
             .field("db", &"<PgPool>")
+            .field("replicas", &self.pools.replicas().len())
             .field("jwt_config", &"<JwtConfig>")
             .field("email_config", &"<EmailConfig>")
             .field("cors_config", &"<CorsConfig>")
             .field("rate_limit_config", &"<RateLimitConfig>")
+            .field("password_policy", &self.password_policy)
+            .field("webauthn_config", &self.webauthn_config)
+            .field("oidc_config", &self.oidc_config)
+            .field("docs_config", &self.docs_config)
             .field("cache_config", &"<CacheConfig>")
             .field("cache", &self.cache.as_ref().map(|_| "<RedisCache>"))
             .field("file_storage", &"<FileStorage>")
+            .field("storage", &self.storage_config.driver)
+            .field("token_store", &"<TokenStore>")
+            .field("mfa_challenges", &"<MfaChallengeStore>")
             .finish()
     }
 }
 
-/// Initializes the application state with all required configurations.
+/// Initializes the application state from a loaded configuration.
 ///
 /// This function:
-/// 1. Initializes the database connection pool
-/// 2. Loads JWT configuration from environment variables
-/// 3. Loads email configuration from environment variables
-/// 4. Loads CORS configuration from environment variables
-/// 5. Loads rate limit configuration from environment variables
-/// 6. Initializes Redis cache (optional, continues without if unavailable)
+/// 1. Initializes the primary and replica pools from `config.database`
+/// 2. Initializes Redis cache (optional, continues without if unavailable)
+/// 3. Copies the JWT, email, CORS, rate limit, password, passkey, SSO,
+///    docs, and storage settings into the state
+/// 4. Selects the refresh token and MFA challenge stores (Redis if connected, otherwise PostgreSQL)
 ///
-/// # Panics
+/// # Errors
 ///
-/// Panics if the database connection cannot be established.
+/// Returns a [`DbError`] if the primary or a replica pool cannot be set up.
 ///
 /// # Example
 ///
 /// ```ignore
-/// let state = init_app_state().await;
+/// let config = ChalkbyteConfig::load(None)?;
+/// let state = init_app_state(&config).await?;
 /// ```
-pub async fn init_app_state() -> AppState {
-    let cache_config = CacheConfig::from_env();
-    let cache = init_cache(&cache_config).await;
+pub async fn init_app_state(config: &ChalkbyteConfig) -> Result<AppState, DbError> {
+    let cache = init_cache(&config.cache).await;
+    let pools = init_db_pools(&config.database).await?;
+
+    Ok(build_app_state(pools, cache, config))
+}
+
+/// Assembles the application state around existing pools and cache.
+///
+/// Settings come from `config` as in [`init_app_state`]; the MFA store
+/// follows the cache, and refresh token revocations are cached in it.
+pub fn build_app_state(
+    pools: DbPools,
+    cache: Option<RedisCache>,
+    config: &ChalkbyteConfig,
+) -> AppState {
+    let db = pools.primary().clone();
 
     // Initialize file storage (local filesystem)
     let uploads_dir = PathBuf::from("./uploads");
@@ -132,23 +224,49 @@ pub async fn init_app_state() -> AppState {
         .unwrap_or_else(|_| "http://localhost:3000/files".to_string());
 
     let file_storage = Arc::new(LocalFileStorage::new(uploads_dir, base_url));
+    let storage = init_storage_backend(&config.storage, &config.storage.signing_secret);
+
+    // Refresh token families always live in the database; Redis only caches
+    // revocations in front of it
+    let pg_token_store: Arc<dyn TokenStore> = Arc::new(PgTokenStore::new(db.clone()));
+    let (token_store, mfa_challenges): (Arc<dyn TokenStore>, Arc<dyn MfaChallengeStore>) =
+        match &cache {
+            Some(cache) => (
+                Arc::new(CachedTokenStore::new(pg_token_store, cache)),
+                Arc::new(RedisMfaChallengeStore::from_cache(cache)),
+            ),
+            None => (
+                pg_token_store,
+                Arc::new(PgMfaChallengeStore::new(db.clone())),
+            ),
+        };
 
     AppState {
-        db: init_db_pool().await,
-        jwt_config: JwtConfig::from_env(),
-        email_config: EmailConfig::from_env(),
-        cors_config: CorsConfig::from_env(),
-        rate_limit_config: RateLimitConfig::from_env(),
-        cache_config,
+        db,
+        pools,
+        jwt_config: config.jwt.clone(),
+        email_config: config.email.clone(),
+        cors_config: config.cors.clone(),
+        rate_limit_config: config.rate_limit.clone(),
+        password_policy: config.password_policy.clone(),
+        webauthn_config: config.webauthn.clone(),
+        oidc_config: config.oidc.clone(),
+        docs_config: config.docs.clone(),
+        cache_config: config.cache.clone(),
         cache,
         file_storage,
+        storage,
+        storage_config: config.storage.clone(),
+        token_store,
+        mfa_challenges,
     }
 }
 
 /// Initializes the Redis cache client.
 ///
 /// Returns `None` if Redis connection fails, allowing the application
-/// to continue without caching.
+/// to continue without caching. If Redis goes away later, the cache degrades
+/// to serving uncached until the health probe sees it again.
 async fn init_cache(config: &CacheConfig) -> Option<RedisCache> {
     match RedisCache::new(
         &config.redis_url,
@@ -158,6 +276,7 @@ async fn init_cache(config: &CacheConfig) -> Option<RedisCache> {
     {
         Ok(cache) => {
             info!(redis_url = %config.redis_url, "Redis cache initialized");
+            cache.spawn_health_probe();
             Some(cache)
         }
         Err(e) => {
@@ -1,15 +1,16 @@
 use chalkbyte_core::AppError;
-use chalkbyte_db::PgPool;
-use chalkbyte_models::ids::{SchoolId, UserId};
+use chalkbyte_db::{PgPool, TenantContext};
+use chalkbyte_models::ids::{SchoolGroupId, SchoolId, UserId};
 
 use crate::middleware::auth::AuthUser;
-use crate::middleware::role::is_system_admin_jwt;
+use crate::middleware::role::{is_group_admin_jwt, is_system_admin_jwt};
+use crate::modules::groups::service::GroupService;
 
 /// Get the school_id for operations that require school scoping.
 ///
 /// Priority:
 /// 1. Check JWT claims for school_id (fast, no DB query)
-/// 2. For system admins without school_id, return error (they must specify school)
+/// 2. For system and group admins without school_id, return error (they must specify school)
 /// 3. Fallback to database lookup (for edge cases)
 pub async fn get_admin_school_id(db: &PgPool, auth_user: &AuthUser) -> Result<SchoolId, AppError> {
     // First, try to get school_id from JWT claims (fast path)
@@ -25,6 +26,13 @@ pub async fn get_admin_school_id(db: &PgPool, auth_user: &AuthUser) -> Result<Sc
         )));
     }
 
+    // Group admins act on their group's member schools, never a school of their own
+    if is_group_admin_jwt(auth_user) {
+        return Err(AppError::bad_request(anyhow::anyhow!(
+            "Group admin must specify a school_id for this operation"
+        )));
+    }
+
     // Fallback: fetch from database (should rarely happen if JWT is properly populated)
     let user_id = auth_user.user_id()?;
 
@@ -40,8 +48,32 @@ pub async fn get_admin_school_id(db: &PgPool, auth_user: &AuthUser) -> Result<Sc
     Ok(school_id)
 }
 
+/// The schools a request's queries are limited to.
+///
+/// System admins see every school, group admins their group's member
+/// schools, and everyone else their own school.
+pub async fn tenant_context(db: &PgPool, auth_user: &AuthUser) -> Result<TenantContext, AppError> {
+    if is_system_admin_jwt(auth_user) {
+        return Ok(TenantContext::AllSchools);
+    }
+
+    if is_group_admin_jwt(auth_user) {
+        let group_id = auth_user.group_id().ok_or_else(|| {
+            AppError::forbidden("Group admin must be associated with a school group".to_string())
+        })?;
+        let school_ids = GroupService::member_school_ids(db, group_id).await?;
+        return Ok(TenantContext::schools(
+            school_ids.into_iter().map(Into::into),
+        ));
+    }
+
+    let school_id = get_admin_school_id(db, auth_user).await?;
+    Ok(TenantContext::school(school_id))
+}
+
 /// Get school_id for operations that require scoping (create, list).
-/// System admins MUST provide school_id, school admins use their own.
+/// System admins MUST provide school_id, group admins MUST provide one of
+/// their group's member schools, school admins use their own.
 pub async fn get_school_id_for_scoped_operation(
     db: &PgPool,
     auth_user: &AuthUser,
@@ -56,6 +88,17 @@ pub async fn get_school_id_for_scoped_operation(
         });
     }
 
+    // Group admin must specify a school in their group
+    if is_group_admin_jwt(auth_user) {
+        let school_id = specified_school_id.ok_or_else(|| {
+            AppError::bad_request(anyhow::anyhow!(
+                "Group admin must specify school_id for this operation"
+            ))
+        })?;
+        verify_school_access(db, auth_user, school_id).await?;
+        return Ok(school_id);
+    }
+
     // School admins use their own school (ignore any specified school_id)
     get_admin_school_id(db, auth_user).await
 }
@@ -63,7 +106,6 @@ pub async fn get_school_id_for_scoped_operation(
 /// Get optional school_id for operations on existing resources (get by id, update, delete).
 /// System admins get None (no school scoping), school admins get their school_id.
 /// This allows system admins to operate on any resource without specifying school.
-#[allow(dead_code)]
 pub async fn get_optional_school_id_for_resource_operation(
     db: &PgPool,
     auth_user: &AuthUser,
@@ -102,7 +144,8 @@ pub async fn get_school_id_with_override(
     get_admin_school_id(db, auth_user).await
 }
 
-/// Verify that a resource belongs to the user's school (for school admins).
+/// Verify that a resource belongs to the user's school (for school admins)
+/// or to a member school of their group (for group admins).
 /// System admins bypass this check.
 #[allow(dead_code)]
 pub async fn verify_school_access(
@@ -115,6 +158,19 @@ pub async fn verify_school_access(
         return Ok(());
     }
 
+    // Group admins can access their member schools' resources
+    if is_group_admin_jwt(auth_user) {
+        let group_id = auth_user.group_id().ok_or_else(|| {
+            AppError::forbidden("Group admin must be assigned to a school group".to_string())
+        })?;
+        if !GroupService::is_member_school(db, group_id, resource_school_id).await? {
+            return Err(AppError::forbidden(
+                "You can only access resources from your group's schools".to_string(),
+            ));
+        }
+        return Ok(());
+    }
+
     // School admins can only access their own school's resources
     let user_school_id = get_admin_school_id(db, auth_user).await?;
     if user_school_id != resource_school_id {
@@ -126,6 +182,22 @@ pub async fn verify_school_access(
     Ok(())
 }
 
+/// Verify that the user may act on a school group.
+/// System admins can act on any group, group admins only on their own.
+pub fn verify_group_access(auth_user: &AuthUser, group_id: SchoolGroupId) -> Result<(), AppError> {
+    if is_system_admin_jwt(auth_user) {
+        return Ok(());
+    }
+
+    if is_group_admin_jwt(auth_user) && auth_user.group_id() == Some(group_id) {
+        return Ok(());
+    }
+
+    Err(AppError::forbidden(
+        "You can only access your own school group".to_string(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,6 +214,8 @@ mod tests {
             sub: Uuid::new_v4().to_string(),
             email: "test@example.com".to_string(),
             school_id: school_id.map(|s| s.into_inner()),
+            group_id: None,
+            login_id: None,
             role_ids: uuid_role_ids,
             permissions: vec![],
             exp: 9999999999,
@@ -193,4 +267,23 @@ mod tests {
         // System admin should pass verification for any school (mocked - no DB)
         assert!(is_system_admin_jwt(&auth_user));
     }
+
+    #[test]
+    fn test_verify_group_access() {
+        let group_id = SchoolGroupId::new();
+        let mut group_admin = create_test_auth_user(None, vec![system_roles::GROUP_ADMIN]);
+        group_admin.0.group_id = Some(group_id.into_inner());
+
+        assert!(verify_group_access(&group_admin, group_id).is_ok());
+        assert!(verify_group_access(&group_admin, SchoolGroupId::new()).is_err());
+
+        let sys_admin = create_test_auth_user(None, vec![system_roles::SYSTEM_ADMIN]);
+        assert!(verify_group_access(&sys_admin, group_id).is_ok());
+
+        let school_admin = create_test_auth_user(
+            Some(SchoolId::from(Uuid::new_v4())),
+            vec![system_roles::ADMIN],
+        );
+        assert!(verify_group_access(&school_admin, group_id).is_err());
+    }
 }
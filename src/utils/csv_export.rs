@@ -0,0 +1,204 @@
+//! Streaming CSV exports read through a server-side cursor.
+//!
+//! [`stream_csv`] declares a PostgreSQL cursor over the export query inside a
+//! transaction and fetches it in batches as the response body is polled, so
+//! memory use stays flat no matter how many rows match. The transaction is
+//! committed after the last batch, or rolled back if the client disconnects.
+
+use axum::{
+    body::{Body, Bytes},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use chalkbyte_core::{AppError, ExportFormat};
+use futures_util::stream;
+use serde::Serialize;
+use sqlx::{
+    Arguments, Encode, FromRow, PgPool, Postgres, Transaction, Type,
+    postgres::{PgArguments, PgRow},
+};
+use tracing::error;
+
+/// Rows fetched from the cursor per response chunk.
+pub const EXPORT_FETCH_SIZE: usize = 500;
+
+const CURSOR_NAME: &str = "export_cursor";
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// An open export cursor and whether the header row still has to be written.
+struct ExportCursor {
+    tx: Transaction<'static, Postgres>,
+    write_headers: bool,
+}
+
+/// Append a bind parameter for an export query.
+pub fn push_arg<'q, T>(args: &mut PgArguments, value: T) -> Result<(), AppError>
+where
+    T: 'q + Encode<'q, Postgres> + Type<Postgres>,
+{
+    args.add(value)
+        .map_err(|e| AppError::internal(anyhow::anyhow!(e)))
+}
+
+/// Stream the rows of `sql` as a CSV attachment named `{filename}.csv`.
+///
+/// `sql` must be a complete `SELECT`, including its `ORDER BY`, with
+/// placeholders matching `args`. The cursor is opened before returning so
+/// query errors still produce an error response; failures after that can
+/// only cut the download short and are logged.
+pub async fn stream_csv<T>(
+    db: &PgPool,
+    sql: &str,
+    args: PgArguments,
+    filename: &str,
+) -> Result<Response, AppError>
+where
+    T: for<'r> FromRow<'r, PgRow> + Serialize + Send + Unpin + 'static,
+{
+    let mut tx = db.begin().await?;
+    sqlx::query_with(
+        &format!("DECLARE {} NO SCROLL CURSOR FOR {}", CURSOR_NAME, sql),
+        args,
+    )
+    .persistent(false)
+    .execute(&mut *tx)
+    .await?;
+
+    let cursor = ExportCursor {
+        tx,
+        write_headers: true,
+    };
+    let body = stream::try_unfold(Some(cursor), |cursor| async move {
+        let Some(cursor) = cursor else {
+            return Ok(None);
+        };
+        next_chunk::<T>(cursor).await.inspect_err(|e| {
+            error!(error = %e, "CSV export failed mid-stream");
+        })
+    });
+
+    let format = ExportFormat::Csv;
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}.{}\"",
+                    filename,
+                    format.extension()
+                ),
+            ),
+        ],
+        Body::from_stream(body),
+    )
+        .into_response())
+}
+
+/// Fetch the next batch from the cursor and render it as CSV.
+///
+/// Returns the cursor back while more rows may follow, and commits once the
+/// cursor is exhausted.
+async fn next_chunk<T>(
+    mut cursor: ExportCursor,
+) -> Result<Option<(Bytes, Option<ExportCursor>)>, BoxError>
+where
+    T: for<'r> FromRow<'r, PgRow> + Serialize + Send + Unpin,
+{
+    // Not cached: the same FETCH returns differently shaped rows per export
+    let rows = sqlx::query_as::<_, T>(&format!("FETCH {} FROM {}", EXPORT_FETCH_SIZE, CURSOR_NAME))
+        .persistent(false)
+        .fetch_all(&mut *cursor.tx)
+        .await?;
+
+    if rows.is_empty() {
+        cursor.tx.commit().await?;
+        return Ok(None);
+    }
+
+    let chunk = write_rows(&rows, cursor.write_headers)?;
+    cursor.write_headers = false;
+
+    if rows.len() < EXPORT_FETCH_SIZE {
+        cursor.tx.commit().await?;
+        return Ok(Some((chunk, None)));
+    }
+    Ok(Some((chunk, Some(cursor))))
+}
+
+/// Render rows as CSV, with the header row first if requested.
+fn write_rows<T: Serialize>(rows: &[T], with_headers: bool) -> Result<Bytes, BoxError> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(with_headers)
+        .from_writer(Vec::new());
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    Ok(Bytes::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    #[derive(Debug, Serialize, FromRow)]
+    struct NumberRow {
+        n: i32,
+        label: String,
+    }
+
+    #[test]
+    fn test_write_rows_headers_only_when_requested() {
+        let rows = [NumberRow {
+            n: 1,
+            label: "one".to_string(),
+        }];
+
+        assert_eq!(&write_rows(&rows, true).unwrap()[..], b"n,label\n1,one\n");
+        assert_eq!(&write_rows(&rows, false).unwrap()[..], b"1,one\n");
+    }
+
+    #[sqlx::test]
+    async fn test_stream_csv_fetches_every_batch(pool: PgPool) {
+        let mut args = PgArguments::default();
+        push_arg(&mut args, 1_201_i32).unwrap();
+
+        let response = stream_csv::<NumberRow>(
+            &pool,
+            "SELECT n, 'row ' || n AS label FROM generate_series(1, $1) AS n ORDER BY n",
+            args,
+            "numbers",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"numbers.csv\""
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        let lines: Vec<&str> = body.lines().collect();
+
+        assert_eq!(lines.len(), 1_202);
+        assert_eq!(lines[0], "n,label");
+        assert_eq!(lines[1], "1,row 1");
+        assert_eq!(lines[1_201], "1201,row 1201");
+    }
+
+    #[sqlx::test]
+    async fn test_stream_csv_reports_query_errors(pool: PgPool) {
+        let result = stream_csv::<NumberRow>(
+            &pool,
+            "SELECT n FROM missing_table",
+            PgArguments::default(),
+            "numbers",
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+}
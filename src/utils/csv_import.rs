@@ -0,0 +1,73 @@
+//! Reading uploaded CSV files for imports.
+//!
+//! [`read_csv_rows`] checks the header for required columns and returns each
+//! data row with its line number. Rows that cannot be read are returned as
+//! errors rather than failing the whole file, so they can be reported
+//! alongside validation problems.
+
+use chalkbyte_core::AppError;
+use serde::de::DeserializeOwned;
+
+/// A CSV data row with its line number, or the reason it could not be read.
+pub type CsvRow<T> = (usize, Result<T, String>);
+
+/// Read the data rows of a CSV file with their line numbers, counting the
+/// header as line 1. Values are trimmed and unknown columns are ignored.
+///
+/// # Errors
+///
+/// Returns a bad request when the header cannot be read, a required column
+/// is missing, or the file has more than `max_rows` data rows.
+pub fn read_csv_rows<T: DeserializeOwned>(
+    bytes: &[u8],
+    required_columns: &[&str],
+    max_rows: usize,
+) -> Result<Vec<CsvRow<T>>, AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(bytes);
+
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::bad_request(anyhow::anyhow!("Could not read CSV header: {}", e)))?
+        .clone();
+
+    let missing: Vec<&str> = required_columns
+        .iter()
+        .copied()
+        .filter(|column| !headers.iter().any(|h| h == *column))
+        .collect();
+    if !missing.is_empty() {
+        return Err(AppError::bad_request(anyhow::anyhow!(
+            "CSV is missing required columns: {}",
+            missing.join(", ")
+        )));
+    }
+
+    let mut rows = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        if rows.len() == max_rows {
+            return Err(AppError::bad_request(anyhow::anyhow!(
+                "CSV has more than {} rows",
+                max_rows
+            )));
+        }
+
+        let row = match record {
+            Ok(record) => {
+                let line = record.position().map_or(index + 2, |p| p.line() as usize);
+                let row = record
+                    .deserialize::<T>(Some(&headers))
+                    .map_err(|e| e.to_string());
+                (line, row)
+            }
+            Err(e) => {
+                let line = e.position().map_or(index + 2, |p| p.line() as usize);
+                (line, Err(e.to_string()))
+            }
+        };
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
@@ -0,0 +1,367 @@
+//! Email sending over SMTP.
+//!
+//! [`EmailService`] renders each email from the templates in [`templates`]
+//! with its [`EmailBranding`] and queues it; the delivery job in
+//! [`crate::modules::emails`] sends it with [`smtp_transport`]. When SMTP is
+//! disabled, emails are logged instead of queued.
+
+pub mod templates;
+
+use lettre::message::{MultiPart, SinglePart, header};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport};
+use sqlx::PgPool;
+use tracing::{debug, info, instrument};
+
+use chalkbyte_config::EmailConfig;
+use chalkbyte_core::AppError;
+use chalkbyte_core::request_context::current_request_id;
+
+use crate::modules::emails::service::EmailQueueService;
+
+use templates::{
+    Announcement, ClinicVisit, EmailTemplate, GuardianInvitation, PasswordReset,
+    PasswordResetConfirmation, RecoveryCodeUsed,
+};
+pub use templates::{EmailBranding, RenderedEmail};
+
+#[allow(dead_code)]
+pub struct EmailService {
+    db: PgPool,
+    config: EmailConfig,
+    branding: EmailBranding,
+}
+
+/// Appends a support reference line with the request ID to both bodies.
+///
+/// In HTML the line goes just before `</body>` so it sits under the footer.
+fn with_reference(text_body: &str, html_body: &str, request_id: &str) -> (String, String) {
+    let text = format!("{text_body}\n\nReference: {request_id}");
+
+    let reference = format!(
+        r#"<p style="color: #999999; font-size: 12px; text-align: center;">Reference: {request_id}</p>"#
+    );
+    let html = match html_body.rfind("</body>") {
+        Some(index) => format!(
+            "{}{reference}\n{}",
+            &html_body[..index],
+            &html_body[index..]
+        ),
+        None => format!("{html_body}\n{reference}"),
+    };
+
+    (text, html)
+}
+
+#[allow(dead_code)]
+impl EmailService {
+    pub fn new(db: PgPool, config: EmailConfig) -> Self {
+        Self {
+            db,
+            config,
+            branding: EmailBranding::default(),
+        }
+    }
+
+    /// A copy of this service that sends with a school's branding
+    pub fn with_branding(&self, branding: EmailBranding) -> Self {
+        Self {
+            db: self.db.clone(),
+            config: self.config.clone(),
+            branding,
+        }
+    }
+
+    /// Name emails are sent from
+    pub fn sender_name(&self) -> &str {
+        self.branding
+            .sender_name
+            .as_deref()
+            .unwrap_or(&self.config.from_name)
+    }
+
+    pub fn branding(&self) -> &EmailBranding {
+        &self.branding
+    }
+
+    pub fn frontend_url(&self) -> &str {
+        &self.config.frontend_url
+    }
+
+    #[instrument(skip(self))]
+    pub async fn send_password_reset_email(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        reset_token: &str,
+    ) -> Result<(), AppError> {
+        if !self.config.enabled {
+            info!(
+                email = %to_email,
+                token = %reset_token,
+                "SMTP disabled - password reset token generated (not sent)"
+            );
+            return Ok(());
+        }
+
+        let reset_link = format!(
+            "{}/reset-password?token={}",
+            self.config.frontend_url, reset_token
+        );
+
+        let email = PasswordReset {
+            branding: &self.branding,
+            name: to_name,
+            reset_link: &reset_link,
+        }
+        .render_email()?;
+        self.send_email(to_email, email).await
+    }
+
+    #[instrument(skip(self))]
+    pub async fn send_password_reset_confirmation(
+        &self,
+        to_email: &str,
+        to_name: &str,
+    ) -> Result<(), AppError> {
+        if !self.config.enabled {
+            info!(
+                email = %to_email,
+                "SMTP disabled - password reset confirmation (not sent)"
+            );
+            return Ok(());
+        }
+
+        let email = PasswordResetConfirmation {
+            branding: &self.branding,
+            name: to_name,
+        }
+        .render_email()?;
+        self.send_email(to_email, email).await
+    }
+
+    #[instrument(skip(self, summary))]
+    pub async fn send_clinic_visit_notification(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        student_name: &str,
+        summary: &str,
+    ) -> Result<(), AppError> {
+        if !self.config.enabled {
+            info!(
+                email = %to_email,
+                "SMTP disabled - clinic visit notification (not sent)"
+            );
+            return Ok(());
+        }
+
+        let email = ClinicVisit {
+            branding: &self.branding,
+            name: to_name,
+            student_name,
+            summary,
+        }
+        .render_email()?;
+        self.send_email(to_email, email).await
+    }
+
+    #[instrument(skip(self, body))]
+    pub async fn send_broadcast_email(
+        &self,
+        to_email: &str,
+        subject: &str,
+        body: &str,
+    ) -> Result<(), AppError> {
+        if !self.config.enabled {
+            info!(
+                email = %to_email,
+                "SMTP disabled - broadcast email (not sent)"
+            );
+            return Ok(());
+        }
+
+        let email = Announcement {
+            branding: &self.branding,
+            subject,
+            body,
+        }
+        .render_email()?;
+        self.send_email(to_email, email).await
+    }
+
+    #[instrument(skip(self, invite_token))]
+    pub async fn send_guardian_invitation(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        school_name: &str,
+        invite_token: &str,
+        expires_in_days: i64,
+    ) -> Result<(), AppError> {
+        if !self.config.enabled {
+            info!(
+                email = %to_email,
+                "SMTP disabled - guardian invitation (not sent)"
+            );
+            return Ok(());
+        }
+
+        let invite_link = format!(
+            "{}/reset-password?token={}",
+            self.config.frontend_url, invite_token
+        );
+
+        let email = GuardianInvitation {
+            branding: &self.branding,
+            name: to_name,
+            school_name,
+            invite_link: &invite_link,
+            expires_in_days,
+        }
+        .render_email()?;
+        self.send_email(to_email, email).await
+    }
+
+    #[instrument(skip(self))]
+    pub async fn send_recovery_code_used_notification(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        remaining: i64,
+        ip_address: Option<&str>,
+        user_agent: Option<&str>,
+    ) -> Result<(), AppError> {
+        if !self.config.enabled {
+            info!(
+                email = %to_email,
+                "SMTP disabled - recovery code used notification (not sent)"
+            );
+            return Ok(());
+        }
+
+        let email = RecoveryCodeUsed {
+            branding: &self.branding,
+            name: to_name,
+            remaining,
+            ip_address: ip_address.unwrap_or("unknown"),
+            user_agent: user_agent.unwrap_or("unknown"),
+        }
+        .render_email()?;
+        self.send_email(to_email, email).await
+    }
+
+    /// Queue an email for the delivery job.
+    #[instrument(skip(self, email), fields(subject = %email.subject))]
+    async fn send_email(&self, to_email: &str, email: RenderedEmail) -> Result<(), AppError> {
+        // Emails sent while handling a request carry its ID, so a recipient
+        // can quote it to support
+        let request_id = current_request_id();
+        let email = match &request_id {
+            Some(request_id) => {
+                let (text, html) = with_reference(&email.text, &email.html, request_id);
+                RenderedEmail {
+                    text,
+                    html,
+                    ..email
+                }
+            }
+            None => email,
+        };
+
+        EmailQueueService::enqueue(
+            &self.db,
+            self.branding.school_id,
+            to_email,
+            self.sender_name(),
+            &email,
+            request_id.as_deref(),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Build a multipart message for a queued email.
+pub fn build_message(
+    config: &EmailConfig,
+    from_name: &str,
+    to_email: &str,
+    email: RenderedEmail,
+    request_id: Option<&str>,
+) -> Result<Message, AppError> {
+    let from = format!("{} <{}>", from_name, config.from_email);
+
+    let mut builder = Message::builder();
+    if let Some(request_id) = request_id {
+        builder = builder.raw_header(header::HeaderValue::new(
+            header::HeaderName::new_from_ascii_str("X-Request-Id"),
+            request_id.to_string(),
+        ));
+    }
+
+    builder
+        .from(
+            from.parse()
+                .map_err(|e| AppError::internal_error(format!("Invalid from email: {}", e)))?,
+        )
+        .to(to_email
+            .parse()
+            .map_err(|e| AppError::internal_error(format!("Invalid to email: {}", e)))?)
+        .subject(email.subject)
+        .multipart(
+            MultiPart::alternative()
+                .singlepart(
+                    SinglePart::builder()
+                        .header(header::ContentType::TEXT_PLAIN)
+                        .body(email.text),
+                )
+                .singlepart(
+                    SinglePart::builder()
+                        .header(header::ContentType::TEXT_HTML)
+                        .body(email.html),
+                ),
+        )
+        .map_err(|e| AppError::internal_error(format!("Failed to build email: {}", e)))
+}
+
+/// Build the SMTP transport described by the email configuration.
+pub fn smtp_transport(config: &EmailConfig) -> Result<SmtpTransport, AppError> {
+    debug!(
+        smtp_host = %config.smtp_host,
+        smtp_port = %config.smtp_port,
+        has_username = !config.smtp_username.is_empty(),
+        "Building SMTP transport"
+    );
+
+    // Use dangerous (no TLS) for local development (localhost/127.0.0.1)
+    // or when no credentials are provided
+    let is_local = config.smtp_host == "localhost" || config.smtp_host == "127.0.0.1";
+    let has_credentials = !config.smtp_username.is_empty();
+
+    let mailer = if is_local || !has_credentials {
+        debug!("Using SMTP transport without TLS (local or no credentials)");
+        let mut builder =
+            SmtpTransport::builder_dangerous(&config.smtp_host).port(config.smtp_port);
+
+        if has_credentials {
+            let creds =
+                Credentials::new(config.smtp_username.clone(), config.smtp_password.clone());
+            builder = builder.credentials(creds);
+        }
+
+        builder.build()
+    } else {
+        debug!("Using SMTP transport with TLS");
+        let creds = Credentials::new(config.smtp_username.clone(), config.smtp_password.clone());
+
+        SmtpTransport::relay(&config.smtp_host)
+            .map_err(|e| AppError::internal_error(format!("Failed to create SMTP relay: {}", e)))?
+            .port(config.smtp_port)
+            .credentials(creds)
+            .build()
+    };
+
+    Ok(mailer)
+}
@@ -0,0 +1,396 @@
+//! Email templates.
+//!
+//! Each email is an askama template pair under `templates/email/`: an HTML
+//! body extending `layout.html` and a plain text body extending `layout.txt`.
+//! Both layouts are styled with an [`EmailBranding`], so a school's emails
+//! carry its name, logo, and colors. Templates are compiled into the binary
+//! and HTML values are escaped by askama.
+
+use askama::Template;
+
+use chalkbyte_core::AppError;
+use chalkbyte_models::email_branding::{EmailTemplateKind, SchoolEmailBranding};
+use chalkbyte_models::ids::SchoolId;
+
+/// Header and button color used when a school has not chosen one
+pub const DEFAULT_PRIMARY_COLOR: &str = "#4F46E5";
+
+/// Branding applied to an email's layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailBranding {
+    /// School the email is sent for; `None` for platform emails
+    pub school_id: Option<SchoolId>,
+    pub school_name: Option<String>,
+    /// Overrides the configured sender name
+    pub sender_name: Option<String>,
+    pub logo_url: Option<String>,
+    pub primary_color: String,
+}
+
+impl Default for EmailBranding {
+    fn default() -> Self {
+        Self {
+            school_id: None,
+            school_name: None,
+            sender_name: None,
+            logo_url: None,
+            primary_color: DEFAULT_PRIMARY_COLOR.to_string(),
+        }
+    }
+}
+
+impl EmailBranding {
+    /// Branding for a school, with the defaults in place of unset fields.
+    pub fn for_school(school_name: String, stored: &SchoolEmailBranding) -> Self {
+        Self {
+            school_id: Some(stored.school_id),
+            school_name: Some(school_name),
+            sender_name: stored.sender_name.clone(),
+            logo_url: stored.logo_url.clone(),
+            primary_color: stored
+                .primary_color
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PRIMARY_COLOR.to_string()),
+        }
+    }
+
+    /// Name shown in the email header
+    pub fn name(&self) -> &str {
+        self.school_name.as_deref().unwrap_or("Chalkbyte")
+    }
+
+    /// Sign-off line of plain text emails
+    pub fn signature(&self) -> String {
+        match &self.school_name {
+            Some(school_name) => school_name.clone(),
+            None => "Chalkbyte Team".to_string(),
+        }
+    }
+}
+
+/// An email ready to send.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+/// An HTML email template with a plain text counterpart.
+pub trait EmailTemplate: Template {
+    fn subject(&self) -> String;
+
+    fn text(&self) -> Result<String, askama::Error>;
+
+    fn render_email(&self) -> Result<RenderedEmail, AppError> {
+        let rendered = (|| {
+            Ok::<_, askama::Error>(RenderedEmail {
+                subject: self.subject(),
+                text: self.text()?,
+                html: self.render()?,
+            })
+        })();
+
+        rendered.map_err(|e| AppError::internal_error(format!("Failed to render email: {}", e)))
+    }
+}
+
+/// Implements [`EmailTemplate`] for an HTML template whose plain text
+/// template borrows it as `email`.
+macro_rules! email_template {
+    ($html:ident, $text:ident, |$email:ident| $subject:expr) => {
+        impl EmailTemplate for $html<'_> {
+            fn subject(&self) -> String {
+                let $email = self;
+                $subject
+            }
+
+            fn text(&self) -> Result<String, askama::Error> {
+                $text { email: self }.render()
+            }
+        }
+    };
+}
+
+#[derive(Template)]
+#[template(path = "email/password_reset.html")]
+pub struct PasswordReset<'a> {
+    pub branding: &'a EmailBranding,
+    pub name: &'a str,
+    pub reset_link: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/password_reset.txt")]
+struct PasswordResetText<'a> {
+    email: &'a PasswordReset<'a>,
+}
+
+email_template!(PasswordReset, PasswordResetText, |_email| {
+    "Password Reset Request".to_string()
+});
+
+#[derive(Template)]
+#[template(path = "email/password_reset_confirmation.html")]
+pub struct PasswordResetConfirmation<'a> {
+    pub branding: &'a EmailBranding,
+    pub name: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/password_reset_confirmation.txt")]
+struct PasswordResetConfirmationText<'a> {
+    email: &'a PasswordResetConfirmation<'a>,
+}
+
+email_template!(
+    PasswordResetConfirmation,
+    PasswordResetConfirmationText,
+    |_email| "Password Reset Successful".to_string()
+);
+
+#[derive(Template)]
+#[template(path = "email/clinic_visit.html")]
+pub struct ClinicVisit<'a> {
+    pub branding: &'a EmailBranding,
+    pub name: &'a str,
+    pub student_name: &'a str,
+    pub summary: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/clinic_visit.txt")]
+struct ClinicVisitText<'a> {
+    email: &'a ClinicVisit<'a>,
+}
+
+email_template!(ClinicVisit, ClinicVisitText, |email| format!(
+    "Clinic visit: {}",
+    email.student_name
+));
+
+#[derive(Template)]
+#[template(path = "email/announcement.html")]
+pub struct Announcement<'a> {
+    pub branding: &'a EmailBranding,
+    pub subject: &'a str,
+    /// Plain text; blank lines separate paragraphs
+    pub body: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/announcement.txt")]
+struct AnnouncementText<'a> {
+    email: &'a Announcement<'a>,
+}
+
+email_template!(Announcement, AnnouncementText, |email| email
+    .subject
+    .to_string());
+
+#[derive(Template)]
+#[template(path = "email/guardian_invitation.html")]
+pub struct GuardianInvitation<'a> {
+    pub branding: &'a EmailBranding,
+    pub name: &'a str,
+    pub school_name: &'a str,
+    pub invite_link: &'a str,
+    pub expires_in_days: i64,
+}
+
+#[derive(Template)]
+#[template(path = "email/guardian_invitation.txt")]
+struct GuardianInvitationText<'a> {
+    email: &'a GuardianInvitation<'a>,
+}
+
+email_template!(GuardianInvitation, GuardianInvitationText, |email| format!(
+    "Your {} guardian account",
+    email.school_name
+));
+
+#[derive(Template)]
+#[template(path = "email/recovery_code_used.html")]
+pub struct RecoveryCodeUsed<'a> {
+    pub branding: &'a EmailBranding,
+    pub name: &'a str,
+    pub remaining: i64,
+    pub ip_address: &'a str,
+    pub user_agent: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "email/recovery_code_used.txt")]
+struct RecoveryCodeUsedText<'a> {
+    email: &'a RecoveryCodeUsed<'a>,
+}
+
+email_template!(RecoveryCodeUsed, RecoveryCodeUsedText, |_email| {
+    "Recovery code used to sign in".to_string()
+});
+
+/// Render an email with sample content, for previewing branding.
+pub fn render_sample(
+    kind: EmailTemplateKind,
+    branding: &EmailBranding,
+    frontend_url: &str,
+) -> Result<RenderedEmail, AppError> {
+    let school_name = branding.name();
+    let link = format!("{}/reset-password?token=preview", frontend_url);
+
+    match kind {
+        EmailTemplateKind::PasswordReset => PasswordReset {
+            branding,
+            name: "Ada",
+            reset_link: &link,
+        }
+        .render_email(),
+        EmailTemplateKind::PasswordResetConfirmation => PasswordResetConfirmation {
+            branding,
+            name: "Ada",
+        }
+        .render_email(),
+        EmailTemplateKind::Announcement => Announcement {
+            branding,
+            subject: "Sports day on Friday",
+            body: "Sports day starts at 9am on the main field.\n\n\
+                   Students should wear their house colors and bring water.",
+        }
+        .render_email(),
+        EmailTemplateKind::GuardianInvitation => GuardianInvitation {
+            branding,
+            name: "Ada",
+            school_name,
+            invite_link: &link,
+            expires_in_days: 7,
+        }
+        .render_email(),
+        EmailTemplateKind::ClinicVisit => ClinicVisit {
+            branding,
+            name: "Ada",
+            student_name: "Grace Lovelace",
+            summary: "Complaint: Headache\nTreatment: Rested for 30 minutes\nOutcome: Returned to class",
+        }
+        .render_email(),
+        EmailTemplateKind::RecoveryCodeUsed => RecoveryCodeUsed {
+            branding,
+            name: "Ada",
+            remaining: 9,
+            ip_address: "203.0.113.7",
+            user_agent: "Mozilla/5.0",
+        }
+        .render_email(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn school_branding() -> EmailBranding {
+        EmailBranding {
+            school_id: None,
+            school_name: Some("Greenfield <Academy>".to_string()),
+            sender_name: Some("Greenfield Office".to_string()),
+            logo_url: Some("https://cdn.example.com/logo.png".to_string()),
+            primary_color: "#0F766E".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_layout_applies_branding() {
+        let branding = school_branding();
+        let email = PasswordResetConfirmation {
+            branding: &branding,
+            name: "Ada",
+        }
+        .render_email()
+        .unwrap();
+
+        assert_eq!(email.subject, "Password Reset Successful");
+        assert!(email.html.contains("background-color: #0F766E"));
+        assert!(
+            email
+                .html
+                .contains(r#"<img src="https://cdn.example.com/logo.png""#)
+        );
+        assert!(email.html.contains("Greenfield &#60;Academy&#62;"));
+        assert!(
+            email
+                .html
+                .contains("Sent by Greenfield &#60;Academy&#62; via Chalkbyte")
+        );
+        assert!(email.html.contains("Security Notice:"));
+        assert!(
+            email
+                .text
+                .starts_with("Hi Ada,\n\nYour password has been successfully reset.")
+        );
+        assert!(email.text.ends_with("Best regards,\nGreenfield <Academy>"));
+    }
+
+    #[test]
+    fn test_default_branding() {
+        let branding = EmailBranding::default();
+        let email = PasswordReset {
+            branding: &branding,
+            name: "Ada",
+            reset_link: "https://app.example.com/reset-password?token=abc",
+        }
+        .render_email()
+        .unwrap();
+
+        assert!(email.html.contains("background-color: #4F46E5"));
+        assert!(!email.html.contains("<img"));
+        assert!(
+            email
+                .html
+                .contains("This is an automated email from Chalkbyte")
+        );
+        assert!(
+            email
+                .text
+                .contains("https://app.example.com/reset-password?token=abc\n")
+        );
+        assert!(email.text.ends_with("Best regards,\nChalkbyte Team"));
+    }
+
+    #[test]
+    fn test_user_content_is_escaped() {
+        let branding = EmailBranding::default();
+        let email = Announcement {
+            branding: &branding,
+            subject: "Fees & <dates>",
+            body: "Line one\nline <two>\n\nSecond paragraph",
+        }
+        .render_email()
+        .unwrap();
+
+        assert_eq!(email.subject, "Fees & <dates>");
+        assert!(email.html.contains("Fees &#38; &#60;dates&#62;"));
+        assert!(email.html.contains("Line one<br/>line &#60;two&#62;</p>"));
+        assert!(email.html.contains(">Second paragraph</p>"));
+        assert_eq!(
+            email.text.trim_end(),
+            "Line one\nline <two>\n\nSecond paragraph"
+        );
+    }
+
+    #[test]
+    fn test_every_sample_renders() {
+        let branding = school_branding();
+        for kind in [
+            EmailTemplateKind::PasswordReset,
+            EmailTemplateKind::PasswordResetConfirmation,
+            EmailTemplateKind::Announcement,
+            EmailTemplateKind::GuardianInvitation,
+            EmailTemplateKind::ClinicVisit,
+            EmailTemplateKind::RecoveryCodeUsed,
+        ] {
+            let email = render_sample(kind, &branding, "https://app.example.com").unwrap();
+            assert!(!email.subject.is_empty());
+            assert!(email.html.contains("#0F766E"), "{kind:?} is not branded");
+            assert!(!email.text.contains("{{"), "{kind:?} left a placeholder");
+        }
+    }
+}
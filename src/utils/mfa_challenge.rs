@@ -0,0 +1,140 @@
+//! PostgreSQL-backed MFA login challenge store.
+//!
+//! Used when Redis is unavailable so in-progress MFA logins are still held
+//! server-side. Each challenge is one row in `mfa_login_challenges`.
+
+use std::time::Duration;
+
+use chalkbyte_cache::{MfaChallenge, MfaChallengeStore, TokenStoreError, TokenStoreFuture};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// [`MfaChallengeStore`] persisting challenges in the database.
+#[derive(Debug, Clone)]
+pub struct PgMfaChallengeStore {
+    db: PgPool,
+}
+
+impl PgMfaChallengeStore {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ChallengeRow {
+    user_id: Uuid,
+    allow_recovery_code: bool,
+    attempts_left: i32,
+}
+
+impl From<ChallengeRow> for MfaChallenge {
+    fn from(row: ChallengeRow) -> Self {
+        Self {
+            user_id: row.user_id,
+            allow_recovery_code: row.allow_recovery_code,
+            attempts_left: row.attempts_left.max(0) as u32,
+        }
+    }
+}
+
+impl MfaChallengeStore for PgMfaChallengeStore {
+    fn create<'a>(
+        &'a self,
+        handle: &'a str,
+        challenge: &'a MfaChallenge,
+        ttl: Duration,
+    ) -> TokenStoreFuture<'a, ()> {
+        Box::pin(async move {
+            // Sweep expired challenges so abandoned logins don't accumulate
+            sqlx::query("DELETE FROM mfa_login_challenges WHERE expires_at <= NOW()")
+                .execute(&self.db)
+                .await
+                .map_err(TokenStoreError::new)?;
+
+            sqlx::query(
+                "INSERT INTO mfa_login_challenges
+                     (handle, user_id, allow_recovery_code, attempts_left, expires_at)
+                 VALUES ($1, $2, $3, $4, NOW() + make_interval(secs => $5))",
+            )
+            .bind(handle)
+            .bind(challenge.user_id)
+            .bind(challenge.allow_recovery_code)
+            .bind(challenge.attempts_left as i32)
+            .bind(ttl.as_secs() as f64)
+            .execute(&self.db)
+            .await
+            .map_err(TokenStoreError::new)?;
+
+            Ok(())
+        })
+    }
+
+    fn get<'a>(&'a self, handle: &'a str) -> TokenStoreFuture<'a, Option<MfaChallenge>> {
+        Box::pin(async move {
+            let row = sqlx::query_as::<_, ChallengeRow>(
+                "SELECT user_id, allow_recovery_code, attempts_left FROM mfa_login_challenges
+                 WHERE handle = $1 AND expires_at > NOW()",
+            )
+            .bind(handle)
+            .fetch_optional(&self.db)
+            .await
+            .map_err(TokenStoreError::new)?;
+
+            Ok(row.map(MfaChallenge::from))
+        })
+    }
+
+    fn record_failure<'a>(&'a self, handle: &'a str) -> TokenStoreFuture<'a, Option<u32>> {
+        Box::pin(async move {
+            let left = sqlx::query_scalar::<_, i32>(
+                "UPDATE mfa_login_challenges SET attempts_left = attempts_left - 1
+                 WHERE handle = $1 AND expires_at > NOW() AND attempts_left > 0
+                 RETURNING attempts_left",
+            )
+            .bind(handle)
+            .fetch_optional(&self.db)
+            .await
+            .map_err(TokenStoreError::new)?;
+
+            if left == Some(0) {
+                sqlx::query("DELETE FROM mfa_login_challenges WHERE handle = $1")
+                    .bind(handle)
+                    .execute(&self.db)
+                    .await
+                    .map_err(TokenStoreError::new)?;
+            }
+
+            Ok(left.map(|left| left as u32))
+        })
+    }
+
+    fn complete<'a>(&'a self, handle: &'a str) -> TokenStoreFuture<'a, Option<MfaChallenge>> {
+        Box::pin(async move {
+            let row = sqlx::query_as::<_, ChallengeRow>(
+                "DELETE FROM mfa_login_challenges WHERE handle = $1 AND expires_at > NOW()
+                 RETURNING user_id, allow_recovery_code, attempts_left",
+            )
+            .bind(handle)
+            .fetch_optional(&self.db)
+            .await
+            .map_err(TokenStoreError::new)?;
+
+            Ok(row.map(MfaChallenge::from))
+        })
+    }
+
+    fn cancel_all<'a>(&'a self, user_id: Uuid) -> TokenStoreFuture<'a, ()> {
+        Box::pin(async move {
+            sqlx::query(
+                "DELETE FROM mfa_login_challenges WHERE user_id = $1 OR expires_at <= NOW()",
+            )
+            .bind(user_id)
+            .execute(&self.db)
+            .await
+            .map_err(TokenStoreError::new)?;
+
+            Ok(())
+        })
+    }
+}
@@ -15,8 +15,15 @@
 //! ## Local modules
 //!
 //! - [`auth_helpers`]: Helper functions for authentication and authorization
+//! - [`csv_export`]: Streaming CSV exports read through a server-side cursor
+//! - [`csv_import`]: Reading uploaded CSV files with per-row errors
 //! - [`email`]: Email sending utilities using SMTP
 //! - [`jwt`]: JWT token creation and verification (re-exports from `chalkbyte-auth`)
+//! - [`mfa_challenge`]: PostgreSQL-backed MFA login challenge store
+//! - [`outbound`]: HTTP requests to admin-configured URLs, limited to public hosts
+//! - [`pdf`]: Plain-text PDF documents
+//! - [`token_store`]: PostgreSQL-backed refresh token store
+//! - [`zip`]: Uncompressed ZIP archives
 //!
 //! For tracing utilities, see [`chalkbyte_observability`].
 
@@ -34,4 +41,11 @@ pub mod jwt {
 
 // Local modules
 pub mod auth_helpers;
+pub mod csv_export;
+pub mod csv_import;
 pub mod email;
+pub mod mfa_challenge;
+pub mod outbound;
+pub mod pdf;
+pub mod token_store;
+pub mod zip;
@@ -0,0 +1,259 @@
+//! HTTP requests to URLs that school admins configure, such as webhook
+//! endpoints and SSO issuers.
+//!
+//! Those URLs must use https and reach only public addresses, so an admin
+//! cannot point the server at loopback, private networks, or cloud metadata
+//! services like `169.254.169.254`. [`OutboundClient`] checks every URL
+//! before sending, resolves hostnames only to public addresses when it
+//! connects, which also covers DNS records that change after the check, and
+//! never follows redirects.
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::{Method, RequestBuilder, Url, redirect};
+
+/// Why a URL may not be requested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutboundError {
+    /// Not an absolute URL with a host.
+    InvalidUrl,
+    /// Anything but https.
+    InsecureScheme,
+    /// The host has no address.
+    Unresolvable(String),
+    /// The host is, or resolves to, a non-public address.
+    NonPublicAddress(String),
+}
+
+impl fmt::Display for OutboundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUrl => f.write_str("URL is not valid"),
+            Self::InsecureScheme => f.write_str("URL must use https"),
+            Self::Unresolvable(host) => write!(f, "Host '{host}' could not be resolved"),
+            Self::NonPublicAddress(host) => {
+                write!(f, "Host '{host}' does not resolve to a public address")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OutboundError {}
+
+/// Whether `ip` is routable on the public internet.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // 0.0.0.0/8 "this network"
+        || a == 0
+        // 100.64.0.0/10 carrier-grade NAT
+        || (a == 100 && (b & 0b1100_0000) == 64)
+        // 192.0.0.0/24 protocol assignments
+        || (a == 192 && b == 0 && c == 0)
+        // 198.18.0.0/15 benchmarking
+        || (a == 198 && (b & 0xfe) == 18)
+        // 240.0.0.0/4 reserved
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let segments = ip.segments();
+    // 64:ff9b::/96 NAT64 carries an IPv4 address in its last 32 bits
+    if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        let [a, b] = segments[6].to_be_bytes();
+        let [c, d] = segments[7].to_be_bytes();
+        return is_public_ipv4(Ipv4Addr::new(a, b, c, d));
+    }
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // fc00::/7 unique local
+        || (segments[0] & 0xfe00) == 0xfc00
+        // fe80::/10 link-local
+        || (segments[0] & 0xffc0) == 0xfe80
+        // 2001:db8::/32 documentation
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8))
+}
+
+/// Public addresses of `host`, failing when it has none.
+async fn resolve_public(host: &str, port: u16) -> Result<Vec<SocketAddr>, OutboundError> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|_| OutboundError::Unresolvable(host.to_string()))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(OutboundError::Unresolvable(host.to_string()));
+    }
+    if !addrs.iter().all(|addr| is_public_ip(addr.ip())) {
+        return Err(OutboundError::NonPublicAddress(host.to_string()));
+    }
+    Ok(addrs)
+}
+
+/// Checks that `url` uses https and its host resolves only to public
+/// addresses.
+pub async fn ensure_public_url(url: &str) -> Result<Url, OutboundError> {
+    let url = Url::parse(url).map_err(|_| OutboundError::InvalidUrl)?;
+    if url.scheme() != "https" {
+        return Err(OutboundError::InsecureScheme);
+    }
+    let host = url.host_str().ok_or(OutboundError::InvalidUrl)?;
+    // IPv6 literals keep their brackets in `host_str`
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    match host.parse::<IpAddr>() {
+        Ok(ip) if is_public_ip(ip) => {}
+        Ok(_) => return Err(OutboundError::NonPublicAddress(host.to_string())),
+        Err(_) => {
+            resolve_public(host, url.port_or_known_default().unwrap_or(443)).await?;
+        }
+    }
+
+    Ok(url)
+}
+
+/// DNS resolver that drops every non-public address.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = resolve_public(name.as_str(), 0).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// HTTP client for admin-configured URLs.
+#[derive(Clone)]
+pub struct OutboundClient {
+    client: reqwest::Client,
+    restricted: bool,
+}
+
+impl OutboundClient {
+    /// A client that only reaches public https URLs and fails requests that
+    /// take longer than `timeout`.
+    pub fn new(timeout: Duration) -> reqwest::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(redirect::Policy::none())
+            .dns_resolver(Arc::new(PublicResolver))
+            .build()?;
+        Ok(Self {
+            client,
+            restricted: true,
+        })
+    }
+
+    /// A client that reaches any http or https URL, for local development
+    /// and tests against local servers. It still never follows redirects.
+    pub fn unrestricted(timeout: Duration) -> reqwest::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(redirect::Policy::none())
+            .build()?;
+        Ok(Self {
+            client,
+            restricted: false,
+        })
+    }
+
+    /// Starts a request to `url` after checking it may be reached.
+    pub async fn request(
+        &self,
+        method: Method,
+        url: &str,
+    ) -> Result<RequestBuilder, OutboundError> {
+        let url = if self.restricted {
+            ensure_public_url(url).await?
+        } else {
+            Url::parse(url).map_err(|_| OutboundError::InvalidUrl)?
+        };
+        Ok(self.client.request(method, url))
+    }
+
+    pub async fn get(&self, url: &str) -> Result<RequestBuilder, OutboundError> {
+        self.request(Method::GET, url).await
+    }
+
+    pub async fn post(&self, url: &str) -> Result<RequestBuilder, OutboundError> {
+        self.request(Method::POST, url).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_public_addresses() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{ip} is not public");
+        }
+
+        for ip in ["8.8.8.8", "1.1.1.1", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip} is public");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ensure_public_url() {
+        assert_eq!(
+            ensure_public_url("http://8.8.8.8/hook").await,
+            Err(OutboundError::InsecureScheme)
+        );
+        assert_eq!(
+            ensure_public_url("not a url").await,
+            Err(OutboundError::InvalidUrl)
+        );
+        assert!(matches!(
+            ensure_public_url("https://169.254.169.254/latest/meta-data").await,
+            Err(OutboundError::NonPublicAddress(_))
+        ));
+        assert!(matches!(
+            ensure_public_url("https://[::1]:8443/hook").await,
+            Err(OutboundError::NonPublicAddress(_))
+        ));
+        assert!(matches!(
+            ensure_public_url("https://localhost/hook").await,
+            Err(OutboundError::NonPublicAddress(_))
+        ));
+        assert!(ensure_public_url("https://8.8.8.8/hook").await.is_ok());
+    }
+}
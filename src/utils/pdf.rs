@@ -0,0 +1,229 @@
+//! Plain-text PDF documents.
+//!
+//! [`TextPdf`] lays out headings and lines of text on A4 pages using the
+//! standard Helvetica fonts, which every PDF reader provides, so no fonts are
+//! embedded and no PDF library is needed. Text is encoded as WinAnsi; other
+//! characters are printed as `?`.
+
+use std::fmt::Write as _;
+
+const PAGE_WIDTH: u32 = 595;
+const PAGE_HEIGHT: u32 = 842;
+const MARGIN: u32 = 56;
+const FONT_SIZE: u32 = 11;
+const HEADING_SIZE: u32 = 14;
+const LEADING: u32 = 16;
+
+/// One line of a document.
+enum Line {
+    Heading(String),
+    Text(String),
+    Blank,
+}
+
+/// A text document rendered to PDF by [`TextPdf::finish`].
+pub struct TextPdf {
+    title: String,
+    lines: Vec<Line>,
+}
+
+impl TextPdf {
+    /// Start a document; the title is set as its metadata title.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Add a bold heading.
+    pub fn heading(&mut self, text: impl Into<String>) -> &mut Self {
+        self.lines.push(Line::Heading(text.into()));
+        self
+    }
+
+    /// Add a line of text.
+    pub fn line(&mut self, text: impl Into<String>) -> &mut Self {
+        self.lines.push(Line::Text(text.into()));
+        self
+    }
+
+    /// Add an empty line.
+    pub fn blank(&mut self) -> &mut Self {
+        self.lines.push(Line::Blank);
+        self
+    }
+
+    /// Lines that fit on one page.
+    const fn lines_per_page() -> usize {
+        ((PAGE_HEIGHT - 2 * MARGIN) / LEADING) as usize
+    }
+
+    /// Render the document.
+    pub fn finish(&self) -> Vec<u8> {
+        let pages: Vec<&[Line]> = if self.lines.is_empty() {
+            vec![&[]]
+        } else {
+            self.lines.chunks(Self::lines_per_page()).collect()
+        };
+
+        // Objects 1-4 are the catalog, page tree, fonts; each page then
+        // takes a page object and a content stream, and the info dictionary
+        // comes last.
+        let page_ids: Vec<usize> = (0..pages.len()).map(|i| 5 + 2 * i).collect();
+        let info_id = 5 + 2 * pages.len();
+
+        let mut objects: Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                page_ids
+                    .iter()
+                    .map(|id| format!("{id} 0 R"))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                pages.len()
+            )
+            .into_bytes(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        ];
+
+        for (page, page_id) in pages.iter().zip(&page_ids) {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    page_id + 1
+                )
+                .into_bytes(),
+            );
+
+            let content = page_content(page);
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            stream.extend_from_slice(&content);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut info = b"<< /Title (".to_vec();
+        info.extend(encode_text(&self.title));
+        info.extend_from_slice(b") /Producer (Chalkbyte) >>");
+        objects.push(info);
+
+        let mut out = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+            out.extend_from_slice(object);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = out.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(xref, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            xref,
+            "trailer\n<< /Size {} /Root 1 0 R /Info {info_id} 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            objects.len() + 1
+        );
+        out.extend_from_slice(xref.as_bytes());
+        out
+    }
+}
+
+/// The drawing operators for one page of lines.
+fn page_content(lines: &[Line]) -> Vec<u8> {
+    let mut content = format!(
+        "BT\n{LEADING} TL\n{MARGIN} {} Td\n",
+        PAGE_HEIGHT - MARGIN - HEADING_SIZE
+    )
+    .into_bytes();
+
+    for line in lines {
+        match line {
+            Line::Heading(text) => {
+                content.extend_from_slice(format!("/F2 {HEADING_SIZE} Tf\n(").as_bytes());
+                content.extend(encode_text(text));
+                content.extend_from_slice(b") Tj T*\n");
+            }
+            Line::Text(text) => {
+                content.extend_from_slice(format!("/F1 {FONT_SIZE} Tf\n(").as_bytes());
+                content.extend(encode_text(text));
+                content.extend_from_slice(b") Tj T*\n");
+            }
+            Line::Blank => content.extend_from_slice(b"T*\n"),
+        }
+    }
+
+    content.extend_from_slice(b"ET");
+    content
+}
+
+/// Encode text for a PDF string literal: WinAnsi bytes with `(`, `)`, and
+/// `\` escaped and control characters dropped.
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push(b'\\');
+                out.push(c as u8);
+            }
+            c if c.is_control() => {}
+            // Latin-1 matches WinAnsi outside 0x80-0x9F, which are controls
+            c if (c as u32) < 0x100 => out.push(c as u8),
+            '\u{2013}' => out.push(0x96),
+            '\u{2014}' => out.push(0x97),
+            '\u{2022}' => out.push(0x95),
+            '\u{20AC}' => out.push(0x80),
+            _ => out.push(b'?'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    #[test]
+    fn test_encode_text_escapes_and_replaces() {
+        assert_eq!(
+            encode_text("a (b) \\ é – ✓\n"),
+            b"a \\(b\\) \\\\ \xe9 \x96 ?".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_finish_paginates_and_indexes_objects() {
+        let mut pdf = TextPdf::new("Report");
+        pdf.heading("Summary");
+        for i in 0..TextPdf::lines_per_page() {
+            pdf.line(format!("Line {i}"));
+        }
+        let bytes = pdf.finish();
+
+        assert!(bytes.starts_with(b"%PDF-1.4\n"));
+        assert!(bytes.ends_with(b"%%EOF\n"));
+        assert!(contains(&bytes, b"/Count 2"));
+        assert!(contains(&bytes, b"(Summary) Tj"));
+
+        // Every xref entry points at the start of its object
+        let text = String::from_utf8_lossy(&bytes);
+        let xref = text.rfind("xref\n").unwrap();
+        for (i, entry) in text[xref..].lines().skip(3).take(8).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(text[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+    }
+}
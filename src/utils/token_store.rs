@@ -0,0 +1,202 @@
+//! PostgreSQL-backed refresh token store.
+//!
+//! The source of truth for refresh token families: each is one row in
+//! `refresh_token_families`. When Redis is configured it sits behind
+//! [`chalkbyte_cache::CachedTokenStore`], which caches revocations only.
+
+use std::time::Duration;
+
+use chalkbyte_cache::{
+    ActiveFamily, Rotation, SessionClient, TokenFamily, TokenStore, TokenStoreError,
+    TokenStoreFuture,
+};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+/// [`TokenStore`] persisting token families in the database.
+#[derive(Debug, Clone)]
+pub struct PgTokenStore {
+    db: PgPool,
+}
+
+impl PgTokenStore {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+/// A live row of `refresh_token_families`.
+#[derive(sqlx::FromRow)]
+struct FamilyRow {
+    id: Uuid,
+    device_id: String,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    created_at: DateTime<Utc>,
+    last_seen_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl From<FamilyRow> for ActiveFamily {
+    fn from(row: FamilyRow) -> Self {
+        Self {
+            family_id: row.id,
+            device_id: row.device_id,
+            client: SessionClient {
+                user_agent: row.user_agent,
+                ip_address: row.ip_address,
+            },
+            created_at: row.created_at,
+            last_seen_at: row.last_seen_at,
+            expires_at: row.expires_at,
+        }
+    }
+}
+
+fn ttl_secs(ttl: Duration) -> f64 {
+    ttl.as_secs() as f64
+}
+
+impl TokenStore for PgTokenStore {
+    fn start_family<'a>(
+        &'a self,
+        family: &'a TokenFamily,
+        jti: &'a str,
+        ttl: Duration,
+    ) -> TokenStoreFuture<'a, ()> {
+        Box::pin(async move {
+            let mut tx = self.db.begin().await.map_err(TokenStoreError::new)?;
+
+            sqlx::query(
+                "UPDATE refresh_token_families SET revoked_at = NOW(), updated_at = NOW()
+                 WHERE user_id = $1 AND device_id = $2 AND revoked_at IS NULL",
+            )
+            .bind(family.user_id)
+            .bind(&family.device_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(TokenStoreError::new)?;
+
+            sqlx::query(
+                "INSERT INTO refresh_token_families
+                     (id, user_id, device_id, current_jti, expires_at, user_agent, ip_address)
+                 VALUES ($1, $2, $3, $4, NOW() + make_interval(secs => $5), $6, $7)",
+            )
+            .bind(family.family_id)
+            .bind(family.user_id)
+            .bind(&family.device_id)
+            .bind(jti)
+            .bind(ttl_secs(ttl))
+            .bind(&family.client.user_agent)
+            .bind(&family.client.ip_address)
+            .execute(&mut *tx)
+            .await
+            .map_err(TokenStoreError::new)?;
+
+            tx.commit().await.map_err(TokenStoreError::new)
+        })
+    }
+
+    fn rotate<'a>(
+        &'a self,
+        user_id: Uuid,
+        family_id: Uuid,
+        presented_jti: &'a str,
+        next_jti: &'a str,
+        client: &'a SessionClient,
+        ttl: Duration,
+    ) -> TokenStoreFuture<'a, Rotation> {
+        Box::pin(async move {
+            let rotated = sqlx::query(
+                "UPDATE refresh_token_families
+                 SET current_jti = $3, expires_at = NOW() + make_interval(secs => $5),
+                     user_agent = $6, ip_address = $7, last_seen_at = NOW(), updated_at = NOW()
+                 WHERE id = $1 AND user_id = $2 AND current_jti = $4
+                   AND revoked_at IS NULL AND expires_at > NOW()",
+            )
+            .bind(family_id)
+            .bind(user_id)
+            .bind(next_jti)
+            .bind(presented_jti)
+            .bind(ttl_secs(ttl))
+            .bind(&client.user_agent)
+            .bind(&client.ip_address)
+            .execute(&self.db)
+            .await
+            .map_err(TokenStoreError::new)?;
+
+            if rotated.rows_affected() > 0 {
+                return Ok(Rotation::Rotated);
+            }
+
+            // The family is live but the token was not its current one: a
+            // superseded token is being replayed
+            let reused = sqlx::query(
+                "UPDATE refresh_token_families SET revoked_at = NOW(), updated_at = NOW()
+                 WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL AND expires_at > NOW()",
+            )
+            .bind(family_id)
+            .bind(user_id)
+            .execute(&self.db)
+            .await
+            .map_err(TokenStoreError::new)?;
+
+            if reused.rows_affected() > 0 {
+                warn!(auth.family_id = %family_id, "Refresh token reuse detected");
+                Ok(Rotation::Reused)
+            } else {
+                Ok(Rotation::Revoked)
+            }
+        })
+    }
+
+    fn list_families<'a>(&'a self, user_id: Uuid) -> TokenStoreFuture<'a, Vec<ActiveFamily>> {
+        Box::pin(async move {
+            let rows = sqlx::query_as::<_, FamilyRow>(
+                "SELECT id, device_id, user_agent, ip_address, created_at, last_seen_at, expires_at
+                 FROM refresh_token_families
+                 WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+                 ORDER BY last_seen_at DESC",
+            )
+            .bind(user_id)
+            .fetch_all(&self.db)
+            .await
+            .map_err(TokenStoreError::new)?;
+
+            Ok(rows.into_iter().map(ActiveFamily::from).collect())
+        })
+    }
+
+    fn revoke_family<'a>(&'a self, user_id: Uuid, family_id: Uuid) -> TokenStoreFuture<'a, ()> {
+        Box::pin(async move {
+            sqlx::query(
+                "UPDATE refresh_token_families SET revoked_at = NOW(), updated_at = NOW()
+                 WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+            )
+            .bind(family_id)
+            .bind(user_id)
+            .execute(&self.db)
+            .await
+            .map_err(TokenStoreError::new)?;
+
+            Ok(())
+        })
+    }
+
+    fn revoke_all<'a>(&'a self, user_id: Uuid) -> TokenStoreFuture<'a, ()> {
+        Box::pin(async move {
+            sqlx::query(
+                "UPDATE refresh_token_families SET revoked_at = NOW(), updated_at = NOW()
+                 WHERE user_id = $1 AND revoked_at IS NULL",
+            )
+            .bind(user_id)
+            .execute(&self.db)
+            .await
+            .map_err(TokenStoreError::new)?;
+
+            Ok(())
+        })
+    }
+}
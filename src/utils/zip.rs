@@ -0,0 +1,187 @@
+//! Uncompressed ZIP archives.
+//!
+//! [`StoredZip`] packs files into a ZIP archive without compressing them
+//! ("stored" entries), which every unzip tool reads, so no compression
+//! library is needed. It suits bundles of small text files such as CSV
+//! exports; archives over 4 GiB, which need ZIP64, are not supported.
+
+use chrono::{Datelike, NaiveDateTime, Timelike};
+
+/// CRC-32 lookup table for the IEEE polynomial.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, byte| {
+        CRC_TABLE[((crc ^ u32::from(*byte)) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// One file in the archive, with what its central directory entry needs.
+struct Entry {
+    name: String,
+    crc: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// A ZIP archive built in memory by [`StoredZip::add`] and
+/// [`StoredZip::finish`].
+pub struct StoredZip {
+    /// MS-DOS time and date stamped on every entry
+    time: u16,
+    date: u16,
+    body: Vec<u8>,
+    entries: Vec<Entry>,
+}
+
+impl StoredZip {
+    /// Start an archive whose files are all dated `modified`, a local time.
+    /// Times before 1980, which ZIP cannot store, are stamped 1980-01-01.
+    pub fn new(modified: NaiveDateTime) -> Self {
+        let (time, date) = if modified.year() < 1980 {
+            (0, (1 << 5) | 1)
+        } else {
+            (
+                ((modified.hour() << 11) | (modified.minute() << 5) | (modified.second() / 2))
+                    as u16,
+                (((modified.year() - 1980) as u32) << 9 | (modified.month() << 5) | modified.day())
+                    as u16,
+            )
+        };
+        Self {
+            time,
+            date,
+            body: Vec::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add a file. Names use `/` between directories.
+    pub fn add(&mut self, name: impl Into<String>, content: &[u8]) -> &mut Self {
+        let name = name.into();
+        let entry = Entry {
+            crc: crc32(content),
+            size: content.len() as u32,
+            offset: self.body.len() as u32,
+            name,
+        };
+
+        self.body.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        self.write_common(&entry);
+        self.body.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+        self.body.extend_from_slice(entry.name.as_bytes());
+        self.body.extend_from_slice(content);
+
+        self.entries.push(entry);
+        self
+    }
+
+    /// The fields local headers and central directory entries share, from
+    /// the version needed to extract up to the file name length.
+    fn write_common(&mut self, entry: &Entry) {
+        let out = &mut self.body;
+        out.extend_from_slice(&20u16.to_le_bytes()); // version needed: 2.0
+        out.extend_from_slice(&0x0800u16.to_le_bytes()); // names are UTF-8
+        out.extend_from_slice(&0u16.to_le_bytes()); // stored
+        out.extend_from_slice(&self.time.to_le_bytes());
+        out.extend_from_slice(&self.date.to_le_bytes());
+        out.extend_from_slice(&entry.crc.to_le_bytes());
+        out.extend_from_slice(&entry.size.to_le_bytes()); // compressed size
+        out.extend_from_slice(&entry.size.to_le_bytes());
+        out.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+    }
+
+    /// Write the central directory and return the archive.
+    pub fn finish(mut self) -> Vec<u8> {
+        let directory_offset = self.body.len() as u32;
+        let entries = std::mem::take(&mut self.entries);
+        for entry in &entries {
+            self.body.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+            self.body.extend_from_slice(&20u16.to_le_bytes()); // version made by
+            self.write_common(entry);
+            // Extra field and comment lengths, disk number, and attributes
+            self.body.extend_from_slice(&[0; 12]);
+            self.body.extend_from_slice(&entry.offset.to_le_bytes());
+            self.body.extend_from_slice(entry.name.as_bytes());
+        }
+        let directory_size = self.body.len() as u32 - directory_offset;
+
+        self.body.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        self.body.extend_from_slice(&[0; 4]); // disk numbers
+        self.body
+            .extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        self.body
+            .extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        self.body.extend_from_slice(&directory_size.to_le_bytes());
+        self.body.extend_from_slice(&directory_offset.to_le_bytes());
+        self.body.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        self.body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn u16_at(bytes: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes([bytes[at], bytes[at + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn test_finish_indexes_entries() {
+        let modified = NaiveDate::from_ymd_opt(2026, 10, 18)
+            .unwrap()
+            .and_hms_opt(9, 30, 10)
+            .unwrap();
+        let mut zip = StoredZip::new(modified);
+        zip.add("a.csv", b"id\n1\n").add("b.csv", b"");
+        let bytes = zip.finish();
+
+        // End of central directory record
+        let end = bytes.len() - 22;
+        assert_eq!(u32_at(&bytes, end), 0x0605_4b50);
+        assert_eq!(u16_at(&bytes, end + 10), 2);
+        let directory = u32_at(&bytes, end + 16) as usize;
+        assert_eq!(u32_at(&bytes, end + 12) as usize, end - directory);
+
+        // The first central directory entry points at its local header
+        assert_eq!(u32_at(&bytes, directory), 0x0201_4b50);
+        assert_eq!(u32_at(&bytes, directory + 16), crc32(b"id\n1\n"));
+        assert_eq!(&bytes[directory + 46..directory + 51], b"a.csv");
+        let local = u32_at(&bytes, directory + 42) as usize;
+        assert_eq!(u32_at(&bytes, local), 0x0403_4b50);
+        assert_eq!(u16_at(&bytes, local + 10), (9 << 11) | (30 << 5) | 5);
+        assert_eq!(u16_at(&bytes, local + 12), (46 << 9) | (10 << 5) | 18);
+        assert_eq!(&bytes[local + 30..local + 35], b"a.csv");
+        assert_eq!(&bytes[local + 35..local + 40], b"id\n1\n");
+    }
+}
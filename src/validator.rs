@@ -5,27 +5,16 @@ use axum::{
     http::StatusCode,
 };
 use serde::de::DeserializeOwned;
-use validator::{Validate, ValidationErrors};
+use validator::Validate;
 
 use chalkbyte_core::AppError;
 
-fn format_errors(errors: &ValidationErrors) -> String {
-    errors
-        .field_errors()
-        .iter()
-        .flat_map(|(field, errors)| {
-            errors.iter().filter_map(move |error| {
-                error
-                    .message
-                    .as_ref()
-                    .map(|msg| msg.to_string())
-                    .or_else(|| Some(format!("{} is invalid", field)))
-            })
-        })
-        .collect::<Vec<_>>()
-        .join(", ")
-}
-
+/// JSON body extractor that runs the DTO's [`Validate`] constraints.
+///
+/// Malformed bodies are rejected with a 400. Bodies that parse but break a
+/// constraint are rejected with a 422 whose `fields` list each offending
+/// field, the constraint that failed, and its message (see
+/// [`AppError::validation`]).
 #[derive(Debug, Clone, Copy, Default)]
 pub struct ValidatedJson<T>(pub T);
 
@@ -79,12 +68,7 @@ where
                 AppError::new(StatusCode::BAD_REQUEST, anyhow!("Invalid request body"))
             })?;
 
-        value.validate().map_err(|errors| {
-            AppError::new(
-                StatusCode::UNPROCESSABLE_ENTITY,
-                anyhow!("{}", format_errors(&errors)),
-            )
-        })?;
+        value.validate().map_err(AppError::validation)?;
 
         Ok(ValidatedJson(value))
     }
@@ -10,6 +10,8 @@ pub mod system_roles {
     pub const ADMIN: Uuid = Uuid::from_u128(0x00000000_0000_0000_0000_000000000002);
     pub const TEACHER: Uuid = Uuid::from_u128(0x00000000_0000_0000_0000_000000000003);
     pub const STUDENT: Uuid = Uuid::from_u128(0x00000000_0000_0000_0000_000000000004);
+    pub const AUDITOR: Uuid = Uuid::from_u128(0x00000000_0000_0000_0000_000000000006);
+    pub const GUARDIAN: Uuid = Uuid::from_u128(0x00000000_0000_0000_0000_000000000007);
 }
 
 #[allow(dead_code)]
@@ -61,6 +63,8 @@ pub async fn create_test_user(
         "admin" => system_roles::ADMIN,
         "teacher" => system_roles::TEACHER,
         "student" => system_roles::STUDENT,
+        "auditor" => system_roles::AUDITOR,
+        "guardian" => system_roles::GUARDIAN,
         _ => panic!("Invalid role: {}", role),
     };
 
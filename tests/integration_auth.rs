@@ -3,14 +3,23 @@ mod common;
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use chalkbyte::config::cors::CorsConfig;
+use chalkbyte::config::database::DbPools;
+use chalkbyte::config::docs::{DocsAccess, DocsConfig};
 use chalkbyte::config::email::EmailConfig;
 use chalkbyte::config::jwt::JwtConfig;
+use chalkbyte::config::oidc::OidcConfig;
 use chalkbyte::config::rate_limit::RateLimitConfig;
-use chalkbyte::router::init_router;
+use chalkbyte::config::storage::StorageConfig;
+use chalkbyte::config::webauthn::WebauthnConfig;
+use chalkbyte::modules::storage::backend::init_storage_backend;
+use chalkbyte::router::init_router_without_rate_limiting;
 use chalkbyte::state::AppState;
+use chalkbyte::utils::mfa_challenge::PgMfaChallengeStore;
+use chalkbyte::utils::token_store::PgTokenStore;
 use chalkbyte_cache::CacheConfig;
+use chalkbyte_core::PasswordPolicy;
 use chalkbyte_core::file_storage::LocalFileStorage;
-use common::{create_test_user, generate_unique_email};
+use common::{create_test_school, create_test_user, generate_unique_email};
 use http_body_util::BodyExt;
 use serde_json::json;
 use sqlx::PgPool;
@@ -19,6 +28,10 @@ use std::sync::Arc;
 use tower::ServiceExt;
 
 async fn setup_test_app(pool: PgPool) -> axum::Router {
+    setup_test_app_with_docs(pool, DocsConfig::from_env()).await
+}
+
+async fn setup_test_app_with_docs(pool: PgPool, docs_config: DocsConfig) -> axum::Router {
     dotenvy::dotenv().ok();
     
     let test_uploads_dir = PathBuf::from("./test_uploads");
@@ -29,17 +42,35 @@ async fn setup_test_app(pool: PgPool) -> axum::Router {
         "http://localhost:3000/files".to_string(),
     ));
     
+    let storage_config = StorageConfig {
+        local_dir: PathBuf::from("./test_uploads/storage"),
+        ..StorageConfig::default()
+    };
+
     let state = AppState {
-        db: pool,
+        db: pool.clone(),
+        pools: DbPools::new(pool.clone()),
         jwt_config: JwtConfig::from_env(),
         email_config: EmailConfig::from_env(),
         cors_config: CorsConfig::from_env(),
         rate_limit_config: RateLimitConfig::default(),
+        password_policy: PasswordPolicy::default(),
+        webauthn_config: WebauthnConfig::from_env(),
+        // The SSO tests serve their issuer from a local address
+        oidc_config: OidcConfig {
+            allow_local_issuers: true,
+            ..OidcConfig::from_env()
+        },
+        docs_config,
         cache_config: CacheConfig::default(),
         cache: None,
         file_storage,
+        storage: init_storage_backend(&storage_config, "test-storage-secret"),
+        storage_config,
+        token_store: Arc::new(PgTokenStore::new(pool.clone())),
+        mfa_challenges: Arc::new(PgMfaChallengeStore::new(pool)),
     };
-    init_router(state)
+    init_router_without_rate_limiting(state)
 }
 
 #[sqlx::test(migrations = "./migrations")]
@@ -211,3 +242,740 @@ async fn test_login_returns_correct_role(pool: PgPool) {
     assert!(!roles.is_empty(), "user should have at least one role");
     assert_eq!(roles[0]["name"], "Admin");
 }
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_versioned_routes_and_deprecated_alias(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let email = generate_unique_email();
+    create_test_user(&mut tx, &email, "testpass123", "student", None).await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = login(app.clone(), &email, "testpass123").await;
+
+    let get = |uri: &str, version: Option<&str>| {
+        let mut builder = Request::builder()
+            .uri(uri)
+            .header("authorization", format!("Bearer {}", token));
+        if let Some(version) = version {
+            builder = builder.header("api-version", version);
+        }
+        builder.body(Body::empty()).unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(get("/api/v1/auth/sessions", None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["api-version"], "v1");
+    assert!(response.headers().get("deprecation").is_none());
+    assert!(response.headers().get("sunset").is_none());
+
+    let response = app
+        .clone()
+        .oneshot(get("/api/auth/sessions", None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["api-version"], "v1");
+    assert!(
+        response.headers()["deprecation"]
+            .to_str()
+            .unwrap()
+            .starts_with('@')
+    );
+    assert!(response.headers().contains_key("sunset"));
+    assert_eq!(
+        response.headers()["link"],
+        "</api/v1/auth/sessions>; rel=\"successor-version\""
+    );
+
+    let response = app
+        .clone()
+        .oneshot(get("/api/auth/sessions", Some("v1")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["api-version"], "v1");
+
+    let response = app
+        .oneshot(get("/api/auth/sessions", Some("v9")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+async fn login(app: axum::Router, email: &str, password: &str) -> String {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/auth/login")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::to_string(&json!({ "email": email, "password": password })).unwrap(),
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    body["access_token"].as_str().unwrap().to_string()
+}
+
+async fn send_json(
+    app: axum::Router,
+    method: &str,
+    uri: &str,
+    token: &str,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+    (status, body)
+}
+
+/// Serves an OIDC discovery document for an issuer at a local address.
+async fn spawn_discovery_server() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let issuer = format!("http://{}", listener.local_addr().unwrap());
+
+    let document = json!({
+        "issuer": issuer,
+        "authorization_endpoint": format!("{issuer}/authorize"),
+        "token_endpoint": format!("{issuer}/token"),
+        "jwks_uri": format!("{issuer}/jwks"),
+    });
+    let app = axum::Router::new().route(
+        "/.well-known/openid-configuration",
+        axum::routing::get(move || async move { axum::Json(document) }),
+    );
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    issuer
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_sso_provider_crud_as_school_admin(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, "SSO School").await;
+    let email = generate_unique_email();
+    create_test_user(&mut tx, &email, "testpass123", "admin", Some(school.id)).await;
+    tx.commit().await.unwrap();
+
+    let token = login(setup_test_app(pool.clone()).await, &email, "testpass123").await;
+    let base = format!("/api/schools/{}/sso-providers", school.id);
+
+    let (status, created) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        &base,
+        &token,
+        Some(json!({
+            "slug": "sso-school-google",
+            "name": "Google Workspace",
+            "issuer": "https://accounts.google.com/",
+            "client_id": "client-id",
+            "client_secret": "client-secret",
+            "allowed_domains": ["SSO-School.edu"],
+            "jit_provisioning": true
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["issuer"], "https://accounts.google.com");
+    assert_eq!(created["allowed_domains"], json!(["sso-school.edu"]));
+    assert!(
+        created["callback_url"]
+            .as_str()
+            .unwrap()
+            .ends_with("/api/auth/oidc/sso-school-google/callback")
+    );
+    assert!(created.get("client_secret").is_none());
+    let provider_id = created["id"].as_str().unwrap().to_string();
+
+    // Slugs are unique across schools
+    let (status, _) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        &base,
+        &token,
+        Some(json!({
+            "slug": "sso-school-google",
+            "name": "Duplicate",
+            "issuer": "https://accounts.google.com",
+            "client_id": "client-id",
+            "client_secret": "client-secret"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, updated) = send_json(
+        setup_test_app(pool.clone()).await,
+        "PUT",
+        &format!("{base}/{provider_id}"),
+        &token,
+        Some(json!({ "is_active": false })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["is_active"], false);
+
+    // Inactive providers cannot be signed in with
+    let request = Request::builder()
+        .uri("/api/auth/oidc/sso-school-google/authorize")
+        .body(Body::empty())
+        .unwrap();
+    let response = setup_test_app(pool.clone())
+        .await
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let (status, listed) = send_json(
+        setup_test_app(pool.clone()).await,
+        "GET",
+        &base,
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+
+    let (status, _) = send_json(
+        setup_test_app(pool.clone()).await,
+        "DELETE",
+        &format!("{base}/{provider_id}"),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_sso_provider_requires_permission(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, "SSO Teacher School").await;
+    let email = generate_unique_email();
+    create_test_user(&mut tx, &email, "testpass123", "teacher", Some(school.id)).await;
+    tx.commit().await.unwrap();
+
+    let token = login(setup_test_app(pool.clone()).await, &email, "testpass123").await;
+
+    let (status, _) = send_json(
+        setup_test_app(pool).await,
+        "GET",
+        &format!("/api/schools/{}/sso-providers", school.id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_sso_authorize_redirects_with_pkce(pool: PgPool) {
+    let issuer = spawn_discovery_server().await;
+
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, "SSO Redirect School").await;
+    let email = generate_unique_email();
+    create_test_user(&mut tx, &email, "testpass123", "admin", Some(school.id)).await;
+    tx.commit().await.unwrap();
+
+    let token = login(setup_test_app(pool.clone()).await, &email, "testpass123").await;
+    let (status, _) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        &format!("/api/schools/{}/sso-providers", school.id),
+        &token,
+        Some(json!({
+            "slug": "sso-redirect",
+            "name": "Local IdP",
+            "issuer": issuer,
+            "client_id": "client-id",
+            "client_secret": "client-secret"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let request = Request::builder()
+        .uri("/api/auth/oidc/sso-redirect/authorize")
+        .body(Body::empty())
+        .unwrap();
+    let response = setup_test_app(pool.clone())
+        .await
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+
+    let location = response.headers()["location"].to_str().unwrap();
+    assert!(location.starts_with(&format!("{issuer}/authorize?")));
+    assert!(location.contains("client_id=client-id"));
+    assert!(location.contains("code_challenge_method=S256"));
+    assert!(location.contains("scope=openid+email+profile"));
+
+    let pending: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM oidc_login_states")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(pending, 1);
+
+    // A callback with a state that was never issued is rejected
+    let request = Request::builder()
+        .uri("/api/auth/oidc/sso-redirect/callback?code=abc&state=forged")
+        .body(Body::empty())
+        .unwrap();
+    let response = setup_test_app(pool.clone())
+        .await
+        .oneshot(request)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Signed-in users link an identity by starting a sign-in tied to them
+    let (status, link) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        "/api/auth/oidc/sso-redirect/link",
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        link["authorization_url"]
+            .as_str()
+            .unwrap()
+            .starts_with(&format!("{issuer}/authorize?"))
+    );
+
+    let linking: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM oidc_login_states WHERE link_user_id IS NOT NULL")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(linking, 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_switch_to_linked_profile_and_back(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, "Linked School").await;
+    let admin_email = generate_unique_email();
+    create_test_user(
+        &mut tx,
+        &admin_email,
+        "testpass123",
+        "admin",
+        Some(school.id),
+    )
+    .await;
+    let teacher_email = generate_unique_email();
+    let teacher = create_test_user(
+        &mut tx,
+        &teacher_email,
+        "testpass123",
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    let guardian = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        "testpass123",
+        "guardian",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let admin_token = login(
+        setup_test_app(pool.clone()).await,
+        &admin_email,
+        "testpass123",
+    )
+    .await;
+    let teacher_token = login(
+        setup_test_app(pool.clone()).await,
+        &teacher_email,
+        "testpass123",
+    )
+    .await;
+
+    // Not linked yet
+    let (status, _) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        "/api/auth/switch-profile",
+        &teacher_token,
+        Some(json!({ "user_id": guardian.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, linked) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        &format!("/api/users/{}/linked-profiles", teacher.id),
+        &admin_token,
+        Some(json!({ "linked_user_id": guardian.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(linked["roles"], json!(["Guardian"]));
+
+    let (status, switched) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        "/api/auth/switch-profile",
+        &teacher_token,
+        Some(json!({ "user_id": guardian.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(switched["user"]["id"], json!(guardian.id));
+    let guardian_token = switched["access_token"].as_str().unwrap().to_string();
+
+    // The profile keeps its own notification preferences
+    let (status, preferences) = send_json(
+        setup_test_app(pool.clone()).await,
+        "PUT",
+        "/api/me/notification-preferences",
+        &guardian_token,
+        Some(json!({ "announcement_emails": false })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(preferences["announcement_emails"], false);
+
+    let (_, preferences) = send_json(
+        setup_test_app(pool.clone()).await,
+        "GET",
+        "/api/me/notification-preferences",
+        &teacher_token,
+        None,
+    )
+    .await;
+    assert_eq!(preferences["announcement_emails"], true);
+
+    let (status, profiles) = send_json(
+        setup_test_app(pool.clone()).await,
+        "GET",
+        "/api/me/profiles",
+        &guardian_token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(profiles["active_profile_id"], json!(guardian.id));
+    assert_eq!(profiles["profiles"][0]["user_id"], json!(teacher.id));
+    assert_eq!(profiles["profiles"][1]["user_id"], json!(guardian.id));
+
+    // Switching back to the login account from the profile is allowed
+    let (status, switched_back) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        "/api/auth/switch-profile",
+        &guardian_token,
+        Some(json!({ "user_id": teacher.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(switched_back["user"]["id"], json!(teacher.id));
+
+    // Unlinking ends the profile's session at its next refresh
+    let (status, _) = send_json(
+        setup_test_app(pool.clone()).await,
+        "DELETE",
+        &format!("/api/users/{}/linked-profiles/{}", guardian.id, teacher.id),
+        &admin_token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        "/api/auth/refresh",
+        &guardian_token,
+        Some(json!({ "refresh_token": switched["refresh_token"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+async fn get_spec(
+    app: axum::Router,
+    uri: &str,
+    token: Option<&str>,
+) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::builder().uri(uri);
+    if let Some(token) = token {
+        request = request.header("authorization", format!("Bearer {}", token));
+    }
+    let response = app
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+    (status, body)
+}
+
+#[test]
+fn test_admin_path_prefixes_match_documented_paths() {
+    use utoipa::OpenApi;
+
+    let spec = chalkbyte::docs::ApiDoc::openapi();
+    for prefix in chalkbyte::docs::ADMIN_PATH_PREFIXES {
+        assert!(
+            spec.paths
+                .paths
+                .keys()
+                .any(|path| chalkbyte::docs::is_admin_path(path) && path.starts_with(prefix)),
+            "no documented path under {prefix}"
+        );
+    }
+}
+
+#[test]
+fn test_created_responses_reference_registered_schemas() {
+    use utoipa::OpenApi;
+
+    let spec = serde_json::to_value(chalkbyte::docs::ApiDoc::openapi()).unwrap();
+    let schemas = spec["components"]["schemas"].as_object().unwrap();
+    for (path, operations) in spec["paths"].as_object().unwrap() {
+        for (method, operation) in operations.as_object().unwrap() {
+            let Some(created) = operation["responses"].get("201") else {
+                continue;
+            };
+            let reference = created["content"]["application/json"]["schema"]["$ref"]
+                .as_str()
+                .unwrap_or_default();
+            if let Some(name) = reference.strip_prefix("#/components/schemas/") {
+                assert!(
+                    schemas.contains_key(name),
+                    "{method} {path} returns unregistered schema {name}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_core_module_operations_document_errors_and_security() {
+    const TAGS: &[&str] = &["Branches", "Levels", "Terms", "Roles", "Students"];
+
+    let spec = serde_json::to_value(chalkbyte::docs::openapi()).unwrap();
+    let mut checked = 0;
+    for (path, operations) in spec["paths"].as_object().unwrap() {
+        for (method, operation) in operations.as_object().unwrap() {
+            let tagged = operation["tags"]
+                .as_array()
+                .is_some_and(|tags| tags.iter().any(|tag| TAGS.contains(&tag.as_str().unwrap())));
+            if !tagged {
+                continue;
+            }
+            checked += 1;
+
+            assert!(
+                operation["security"]
+                    .as_array()
+                    .is_some_and(|security| !security.is_empty()),
+                "{method} {path} has no security requirement"
+            );
+            let responses = operation["responses"].as_object().unwrap();
+            for status in ["401", "500"] {
+                assert!(
+                    responses.contains_key(status),
+                    "{method} {path} does not document {status}"
+                );
+            }
+            for (status, response) in responses {
+                // 412 carries the current representation, not an error
+                if !(status.starts_with('4') || status.starts_with('5')) || status == "412" {
+                    continue;
+                }
+                assert_eq!(
+                    response["content"]["application/json"]["schema"]["$ref"],
+                    "#/components/schemas/ErrorResponse",
+                    "{method} {path} {status} has no error schema"
+                );
+            }
+        }
+    }
+    assert!(checked > 50, "only {checked} operations checked");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_public_spec_omits_admin_endpoints(pool: PgPool) {
+    let docs_config = DocsConfig {
+        access: DocsAccess::Authenticated,
+    };
+    let app = setup_test_app_with_docs(pool, docs_config).await;
+
+    let (status, spec) = get_spec(app, "/api-docs/public.json", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let paths = spec["paths"].as_object().unwrap();
+    assert!(paths.contains_key("/api/v1/auth/login"));
+    assert!(paths.contains_key("/api/v1/students"));
+    assert!(!paths.keys().any(|path| path.starts_with("/api/v1/users")));
+    assert!(!paths.keys().any(|path| path.starts_with("/api/v1/schools")));
+    assert!(!paths.keys().any(|path| path.starts_with("/api/v1/admin")));
+
+    let tags: Vec<&str> = spec["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tag| tag["name"].as_str().unwrap())
+        .collect();
+    assert!(tags.contains(&"Authentication"));
+    assert!(!tags.contains(&"Roles"));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_full_spec_requires_admin_when_authenticated(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, "Docs School").await;
+    let admin_email = generate_unique_email();
+    create_test_user(
+        &mut tx,
+        &admin_email,
+        "testpass123",
+        "admin",
+        Some(school.id),
+    )
+    .await;
+    let teacher_email = generate_unique_email();
+    create_test_user(
+        &mut tx,
+        &teacher_email,
+        "testpass123",
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let docs_config = DocsConfig {
+        access: DocsAccess::Authenticated,
+    };
+    let app = setup_test_app_with_docs(pool, docs_config).await;
+
+    let (status, _) = get_spec(app.clone(), "/api-docs/openapi.json", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let teacher_token = login(app.clone(), &teacher_email, "testpass123").await;
+    let (status, _) = get_spec(app.clone(), "/api-docs/openapi.json", Some(&teacher_token)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let admin_token = login(app.clone(), &admin_email, "testpass123").await;
+    let (status, spec) = get_spec(app, "/api-docs/openapi.json", Some(&admin_token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        spec["paths"]
+            .as_object()
+            .unwrap()
+            .contains_key("/api/v1/users")
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_docs_access_public_and_disabled(pool: PgPool) {
+    let public = setup_test_app_with_docs(
+        pool.clone(),
+        DocsConfig {
+            access: DocsAccess::Public,
+        },
+    )
+    .await;
+    let (status, _) = get_spec(public, "/api-docs/openapi.json", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let disabled = setup_test_app_with_docs(
+        pool,
+        DocsConfig {
+            access: DocsAccess::Disabled,
+        },
+    )
+    .await;
+    let (status, _) = get_spec(disabled.clone(), "/api-docs/openapi.json", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get_spec(disabled, "/api-docs/public.json", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_health_ready_reports_database(pool: PgPool) {
+    let app = setup_test_app(pool.clone()).await;
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/health/ready")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["status"], "ready");
+    assert!(json["database"]["connections"].as_u64().unwrap() >= 1);
+
+    pool.close().await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/health/ready")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_jwks_is_public_and_omits_secrets(pool: PgPool) {
+    let app = setup_test_app(pool).await;
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/.well-known/jwks.json")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let keys = json["keys"].as_array().unwrap();
+    assert!(
+        keys.iter()
+            .all(|key| key["kty"] != "oct" && key["kid"].is_string())
+    );
+}
@@ -0,0 +1,401 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use chalkbyte::config::cors::CorsConfig;
+use chalkbyte::config::database::DbPools;
+use chalkbyte::config::docs::DocsConfig;
+use chalkbyte::config::email::EmailConfig;
+use chalkbyte::config::jwt::JwtConfig;
+use chalkbyte::config::oidc::OidcConfig;
+use chalkbyte::config::rate_limit::RateLimitConfig;
+use chalkbyte::config::storage::StorageConfig;
+use chalkbyte::config::webauthn::WebauthnConfig;
+use chalkbyte::modules::storage::backend::init_storage_backend;
+use chalkbyte::router::init_router_without_rate_limiting;
+use chalkbyte::state::AppState;
+use chalkbyte::utils::mfa_challenge::PgMfaChallengeStore;
+use chalkbyte::utils::token_store::PgTokenStore;
+use chalkbyte_cache::CacheConfig;
+use chalkbyte_core::PasswordPolicy;
+use chalkbyte_core::file_storage::LocalFileStorage;
+use common::{
+    create_test_school, create_test_user, generate_unique_email, generate_unique_school_name,
+};
+use http_body_util::BodyExt;
+use serde_json::json;
+use sqlx::PgPool;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app(pool: PgPool) -> axum::Router {
+    dotenvy::dotenv().ok();
+
+    let test_uploads_dir = PathBuf::from("./test_uploads");
+    let _ = tokio::fs::create_dir_all(&test_uploads_dir).await;
+
+    let file_storage = Arc::new(LocalFileStorage::new(
+        test_uploads_dir,
+        "http://localhost:3000/files".to_string(),
+    ));
+    let storage_config = StorageConfig {
+        local_dir: PathBuf::from("./test_uploads/storage"),
+        ..StorageConfig::default()
+    };
+
+    let state = AppState {
+        db: pool.clone(),
+        pools: DbPools::new(pool.clone()),
+        jwt_config: JwtConfig::from_env(),
+        email_config: EmailConfig::from_env(),
+        cors_config: CorsConfig::from_env(),
+        rate_limit_config: RateLimitConfig::default(),
+        password_policy: PasswordPolicy::default(),
+        webauthn_config: WebauthnConfig::from_env(),
+        oidc_config: OidcConfig::from_env(),
+        docs_config: DocsConfig::from_env(),
+        cache_config: CacheConfig::default(),
+        cache: None,
+        file_storage,
+        storage: init_storage_backend(&storage_config, "test-storage-secret"),
+        storage_config,
+        token_store: Arc::new(PgTokenStore::new(pool.clone())),
+        mfa_challenges: Arc::new(PgMfaChallengeStore::new(pool)),
+    };
+    init_router_without_rate_limiting(state)
+}
+
+async fn get_auth_token(app: axum::Router, email: &str, password: &str) -> String {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/auth/login")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::to_string(&json!({
+                "email": email,
+                "password": password
+            }))
+            .unwrap(),
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    body["access_token"].as_str().unwrap().to_string()
+}
+
+async fn send_json(
+    app: axum::Router,
+    method: &str,
+    uri: &str,
+    token: &str,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+    (status, body)
+}
+
+async fn create_test_session(pool: &PgPool, school_id: Uuid) -> Uuid {
+    sqlx::query_scalar(
+        r#"INSERT INTO academic_sessions (name, school_id, start_date, end_date, is_active)
+           VALUES ('2025/2026', $1, '2025-09-01', '2026-07-31', true) RETURNING id"#,
+    )
+    .bind(school_id)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+/// Creates a hostel with one room through the API, returning their IDs.
+async fn create_hostel_with_room(pool: &PgPool, token: &str, capacity: i32) -> (Uuid, Uuid) {
+    let (status, hostel) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        "/api/boarding/hostels",
+        token,
+        Some(json!({ "name": "Blue House", "gender": "mixed" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let hostel_id: Uuid = hostel["id"].as_str().unwrap().parse().unwrap();
+
+    let (status, room) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        &format!("/api/boarding/hostels/{}/rooms", hostel_id),
+        token,
+        Some(json!({ "name": "A1", "capacity": capacity })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let room_id = room["id"].as_str().unwrap().parse().unwrap();
+
+    (hostel_id, room_id)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_create_hostel_and_room_as_admin(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    tx.commit().await.unwrap();
+    create_test_session(&pool, school.id).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let (hostel_id, room_id) = create_hostel_with_room(&pool, &token, 4).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(
+        app,
+        "GET",
+        &format!("/api/boarding/hostels/{}/rooms", hostel_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let rooms = body.as_array().unwrap();
+    assert_eq!(rooms.len(), 1);
+    assert_eq!(rooms[0]["id"], room_id.to_string());
+    assert_eq!(rooms[0]["capacity"], 4);
+    assert_eq!(rooms[0]["occupied"], 0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_boarding_as_teacher_forbidden(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let teacher_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(
+        &mut tx,
+        &teacher_email,
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &teacher_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(app, "GET", "/api/boarding/hostels", &token, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/boarding/hostels",
+        &token,
+        Some(json!({ "name": "Blue House" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_hostel_from_other_school_not_found(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let other_school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let other_admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    create_test_user(
+        &mut tx,
+        &other_admin_email,
+        password,
+        "admin",
+        Some(other_school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let other_token = get_auth_token(app, &other_admin_email, password).await;
+
+    let (hostel_id, room_id) = create_hostel_with_room(&pool, &token, 2).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "GET",
+        &format!("/api/boarding/hostels/{}", hostel_id),
+        &other_token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "PUT",
+        &format!("/api/boarding/rooms/{}", room_id),
+        &other_token,
+        Some(json!({ "capacity": 10 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(app, "GET", "/api/boarding/hostels", &other_token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"].as_array().unwrap().is_empty());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_allocate_room_rejects_when_full(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    let first = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(school.id),
+    )
+    .await;
+    let second = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+    let session_id = create_test_session(&pool, school.id).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let (_, room_id) = create_hostel_with_room(&pool, &token, 1).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, allocation) = send_json(
+        app,
+        "POST",
+        "/api/boarding/allocations",
+        &token,
+        Some(json!({
+            "room_id": room_id,
+            "student_id": first.id,
+            "academic_session_id": session_id
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/boarding/allocations",
+        &token,
+        Some(json!({
+            "room_id": room_id,
+            "student_id": second.id,
+            "academic_session_id": session_id
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Freeing the bed lets the next student in
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "DELETE",
+        &format!(
+            "/api/boarding/allocations/{}",
+            allocation["id"].as_str().unwrap()
+        ),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/boarding/allocations",
+        &token,
+        Some(json!({
+            "room_id": room_id,
+            "student_id": second.id,
+            "academic_session_id": session_id
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_allocate_room_rejects_student_from_other_school(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let other_school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    let outsider = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(other_school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+    let session_id = create_test_session(&pool, school.id).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let (_, room_id) = create_hostel_with_room(&pool, &token, 2).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/boarding/allocations",
+        &token,
+        Some(json!({
+            "room_id": room_id,
+            "student_id": outsider.id,
+            "academic_session_id": session_id
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
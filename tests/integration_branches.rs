@@ -3,12 +3,21 @@ mod common;
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use chalkbyte::config::cors::CorsConfig;
+use chalkbyte::config::database::DbPools;
+use chalkbyte::config::docs::DocsConfig;
 use chalkbyte::config::email::EmailConfig;
 use chalkbyte::config::jwt::JwtConfig;
+use chalkbyte::config::oidc::OidcConfig;
 use chalkbyte::config::rate_limit::RateLimitConfig;
-use chalkbyte::router::init_router;
+use chalkbyte::config::storage::StorageConfig;
+use chalkbyte::config::webauthn::WebauthnConfig;
+use chalkbyte::modules::storage::backend::init_storage_backend;
+use chalkbyte::router::init_router_without_rate_limiting;
 use chalkbyte::state::AppState;
+use chalkbyte::utils::mfa_challenge::PgMfaChallengeStore;
+use chalkbyte::utils::token_store::PgTokenStore;
 use chalkbyte_cache::CacheConfig;
+use chalkbyte_core::PasswordPolicy;
 use chalkbyte_core::file_storage::LocalFileStorage;
 use common::{
     create_test_branch, create_test_level, create_test_school, create_test_user,
@@ -34,17 +43,31 @@ async fn setup_test_app(pool: PgPool) -> axum::Router {
         "http://localhost:3000/files".to_string(),
     ));
     
+    let storage_config = StorageConfig {
+        local_dir: PathBuf::from("./test_uploads/storage"),
+        ..StorageConfig::default()
+    };
+
     let state = AppState {
         db: pool.clone(),
+        pools: DbPools::new(pool.clone()),
         jwt_config: JwtConfig::from_env(),
         email_config: EmailConfig::from_env(),
         cors_config: CorsConfig::from_env(),
         rate_limit_config: RateLimitConfig::default(),
+        password_policy: PasswordPolicy::default(),
+        webauthn_config: WebauthnConfig::from_env(),
+        oidc_config: OidcConfig::from_env(),
+        docs_config: DocsConfig::from_env(),
         cache_config: CacheConfig::default(),
         cache: None,
         file_storage,
+        storage: init_storage_backend(&storage_config, "test-storage-secret"),
+        storage_config,
+        token_store: Arc::new(PgTokenStore::new(pool.clone())),
+        mfa_challenges: Arc::new(PgMfaChallengeStore::new(pool)),
     };
-    init_router(state)
+    init_router_without_rate_limiting(state)
 }
 
 async fn get_auth_token(app: axum::Router, email: &str, password: &str) -> String {
@@ -119,6 +142,42 @@ async fn test_create_branch_as_admin(pool: PgPool) {
     assert!(body["id"].is_string());
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn test_create_branch_invalid_fields_lists_each_field(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let level = create_test_level(&mut tx, &generate_unique_level_name(), school.id).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let description = "x".repeat(2001);
+    let (status, body) = create_branch(app, &token, level.id, "", Some(&description)).await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "VALIDATION_FAILED");
+    assert_eq!(
+        body["fields"],
+        json!([
+            {
+                "field": "description",
+                "code": "length",
+                "message": "Description must not exceed 2000 characters"
+            },
+            {
+                "field": "name",
+                "code": "length",
+                "message": "Name must be between 1 and 100 characters"
+            }
+        ])
+    );
+}
+
 #[sqlx::test(migrations = "./migrations")]
 async fn test_create_branch_as_student_forbidden(pool: PgPool) {
     let mut tx = pool.begin().await.unwrap();
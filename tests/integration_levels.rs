@@ -3,12 +3,21 @@ mod common;
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use chalkbyte::config::cors::CorsConfig;
+use chalkbyte::config::database::DbPools;
+use chalkbyte::config::docs::DocsConfig;
 use chalkbyte::config::email::EmailConfig;
 use chalkbyte::config::jwt::JwtConfig;
+use chalkbyte::config::oidc::OidcConfig;
 use chalkbyte::config::rate_limit::RateLimitConfig;
-use chalkbyte::router::init_router;
+use chalkbyte::config::storage::StorageConfig;
+use chalkbyte::config::webauthn::WebauthnConfig;
+use chalkbyte::modules::storage::backend::init_storage_backend;
+use chalkbyte::router::init_router_without_rate_limiting;
 use chalkbyte::state::AppState;
+use chalkbyte::utils::mfa_challenge::PgMfaChallengeStore;
+use chalkbyte::utils::token_store::PgTokenStore;
 use chalkbyte_cache::CacheConfig;
+use chalkbyte_core::PasswordPolicy;
 use chalkbyte_core::file_storage::LocalFileStorage;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -32,17 +41,31 @@ async fn setup_test_app(pool: PgPool) -> axum::Router {
         "http://localhost:3000/files".to_string(),
     ));
     
+    let storage_config = StorageConfig {
+        local_dir: PathBuf::from("./test_uploads/storage"),
+        ..StorageConfig::default()
+    };
+
     let state = AppState {
         db: pool.clone(),
+        pools: DbPools::new(pool.clone()),
         jwt_config: JwtConfig::from_env(),
         email_config: EmailConfig::from_env(),
         cors_config: CorsConfig::from_env(),
         rate_limit_config: RateLimitConfig::default(),
+        password_policy: PasswordPolicy::default(),
+        webauthn_config: WebauthnConfig::from_env(),
+        oidc_config: OidcConfig::from_env(),
+        docs_config: DocsConfig::from_env(),
         cache_config: CacheConfig::default(),
         cache: None,
         file_storage,
+        storage: init_storage_backend(&storage_config, "test-storage-secret"),
+        storage_config,
+        token_store: Arc::new(PgTokenStore::new(pool.clone())),
+        mfa_challenges: Arc::new(PgMfaChallengeStore::new(pool)),
     };
-    init_router(state)
+    init_router_without_rate_limiting(state)
 }
 
 async fn get_auth_token(app: axum::Router, email: &str, password: &str) -> String {
@@ -222,6 +245,105 @@ async fn test_get_levels_by_school(pool: PgPool) {
     assert!(body["meta"]["total"].as_i64().unwrap() >= 2);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn test_get_levels_not_modified(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    create_level(app, &token, "Grade 9", None).await;
+
+    let list = |etag: Option<String>| {
+        let mut request = Request::builder()
+            .method("GET")
+            .uri("/api/levels")
+            .header("authorization", format!("Bearer {}", token));
+        if let Some(etag) = etag {
+            request = request.header("if-none-match", etag);
+        }
+        request.body(Body::empty()).unwrap()
+    };
+
+    let app = setup_test_app(pool.clone()).await;
+    let response = app.oneshot(list(None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = response.headers()["etag"].to_str().unwrap().to_string();
+
+    let app = setup_test_app(pool.clone()).await;
+    let response = app.oneshot(list(Some(etag.clone()))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    // A new level changes the list
+    let app = setup_test_app(pool.clone()).await;
+    create_level(app, &token, "Grade 10", None).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let response = app.oneshot(list(Some(etag.clone()))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_ne!(response.headers()["etag"].to_str().unwrap(), etag);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_update_level_if_match(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (_, level) = create_level(app, &token, "Grade 9", None).await;
+    let uri = format!("/api/levels/{}", level["id"].as_str().unwrap());
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri(&uri)
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = response.headers()["etag"].to_str().unwrap().to_string();
+
+    let rename = |name: &str| {
+        Request::builder()
+            .method("PUT")
+            .uri(&uri)
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {}", token))
+            .header("if-match", &etag)
+            .body(Body::from(json!({ "name": name }).to_string()))
+            .unwrap()
+    };
+
+    let app = setup_test_app(pool.clone()).await;
+    let response = app.oneshot(rename("Grade 10")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let updated_etag = response.headers()["etag"].to_str().unwrap().to_string();
+    assert_ne!(updated_etag, etag);
+
+    // The ETag is stale after the first update
+    let app = setup_test_app(pool.clone()).await;
+    let response = app.oneshot(rename("Grade 11")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    assert_eq!(response.headers()["etag"], updated_etag.as_str());
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["name"], "Grade 10");
+}
+
 #[sqlx::test(migrations = "./migrations")]
 async fn test_get_levels_scoped_by_school(pool: PgPool) {
     let mut tx = pool.begin().await.unwrap();
@@ -327,6 +449,11 @@ async fn test_get_level_from_different_school_not_found(pool: PgPool) {
 
     let response = app.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "LEVEL_NOT_FOUND");
+    assert_eq!(body["error"], "Level not found");
 }
 
 #[sqlx::test(migrations = "./migrations")]
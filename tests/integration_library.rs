@@ -0,0 +1,462 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use chalkbyte::config::cors::CorsConfig;
+use chalkbyte::config::database::DbPools;
+use chalkbyte::config::docs::DocsConfig;
+use chalkbyte::config::email::EmailConfig;
+use chalkbyte::config::jwt::JwtConfig;
+use chalkbyte::config::oidc::OidcConfig;
+use chalkbyte::config::rate_limit::RateLimitConfig;
+use chalkbyte::config::storage::StorageConfig;
+use chalkbyte::config::webauthn::WebauthnConfig;
+use chalkbyte::modules::storage::backend::init_storage_backend;
+use chalkbyte::router::init_router_without_rate_limiting;
+use chalkbyte::state::AppState;
+use chalkbyte::utils::mfa_challenge::PgMfaChallengeStore;
+use chalkbyte::utils::token_store::PgTokenStore;
+use chalkbyte_cache::CacheConfig;
+use chalkbyte_core::PasswordPolicy;
+use chalkbyte_core::file_storage::LocalFileStorage;
+use common::{
+    create_test_school, create_test_user, generate_unique_email, generate_unique_school_name,
+};
+use http_body_util::BodyExt;
+use serde_json::json;
+use sqlx::PgPool;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app(pool: PgPool) -> axum::Router {
+    dotenvy::dotenv().ok();
+
+    let test_uploads_dir = PathBuf::from("./test_uploads");
+    let _ = tokio::fs::create_dir_all(&test_uploads_dir).await;
+
+    let file_storage = Arc::new(LocalFileStorage::new(
+        test_uploads_dir,
+        "http://localhost:3000/files".to_string(),
+    ));
+    let storage_config = StorageConfig {
+        local_dir: PathBuf::from("./test_uploads/storage"),
+        ..StorageConfig::default()
+    };
+
+    let state = AppState {
+        db: pool.clone(),
+        pools: DbPools::new(pool.clone()),
+        jwt_config: JwtConfig::from_env(),
+        email_config: EmailConfig::from_env(),
+        cors_config: CorsConfig::from_env(),
+        rate_limit_config: RateLimitConfig::default(),
+        password_policy: PasswordPolicy::default(),
+        webauthn_config: WebauthnConfig::from_env(),
+        oidc_config: OidcConfig::from_env(),
+        docs_config: DocsConfig::from_env(),
+        cache_config: CacheConfig::default(),
+        cache: None,
+        file_storage,
+        storage: init_storage_backend(&storage_config, "test-storage-secret"),
+        storage_config,
+        token_store: Arc::new(PgTokenStore::new(pool.clone())),
+        mfa_challenges: Arc::new(PgMfaChallengeStore::new(pool)),
+    };
+    init_router_without_rate_limiting(state)
+}
+
+async fn get_auth_token(app: axum::Router, email: &str, password: &str) -> String {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/auth/login")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::to_string(&json!({
+                "email": email,
+                "password": password
+            }))
+            .unwrap(),
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    body["access_token"].as_str().unwrap().to_string()
+}
+
+async fn send_json(
+    app: axum::Router,
+    method: &str,
+    uri: &str,
+    token: &str,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+    (status, body)
+}
+
+/// Catalogs a book with `copies` copies through the API, returning the book
+/// and copy IDs.
+async fn create_book_with_copies(pool: &PgPool, token: &str, copies: usize) -> (Uuid, Vec<Uuid>) {
+    let (status, book) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        "/api/library/books",
+        token,
+        Some(json!({ "title": "Things Fall Apart", "author": "Chinua Achebe" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let book_id: Uuid = book["id"].as_str().unwrap().parse().unwrap();
+
+    let mut copy_ids = Vec::new();
+    for n in 0..copies {
+        let (status, copy) = send_json(
+            setup_test_app(pool.clone()).await,
+            "POST",
+            &format!("/api/library/books/{}/copies", book_id),
+            token,
+            Some(json!({ "barcode": format!("LIB-{}-{}", book_id, n) })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        copy_ids.push(copy["id"].as_str().unwrap().parse().unwrap());
+    }
+
+    (book_id, copy_ids)
+}
+
+async fn update_policy(pool: &PgPool, token: &str, policy: serde_json::Value) {
+    let (status, _) = send_json(
+        setup_test_app(pool.clone()).await,
+        "PUT",
+        "/api/library/policy",
+        token,
+        Some(policy),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_teacher_lends_but_cannot_manage_catalog(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let teacher_email = generate_unique_email();
+    let student_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    create_test_user(
+        &mut tx,
+        &teacher_email,
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    let student = create_test_user(
+        &mut tx,
+        &student_email,
+        password,
+        "student",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let teacher_token = get_auth_token(app, &teacher_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let student_token = get_auth_token(app, &student_email, password).await;
+
+    let (_, copy_ids) = create_book_with_copies(&pool, &token, 1).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/library/books",
+        &teacher_token,
+        Some(json!({ "title": "Arrow of God" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "PUT",
+        "/api/library/policy",
+        &teacher_token,
+        Some(json!({ "student_loan_limit": 10 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/library/loans",
+        &teacher_token,
+        Some(json!({ "copy_id": copy_ids[0], "borrower_id": student.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(app, "GET", "/api/library/books", &student_token, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_book_from_other_school_not_found(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let other_school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let other_admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    create_test_user(
+        &mut tx,
+        &other_admin_email,
+        password,
+        "admin",
+        Some(other_school.id),
+    )
+    .await;
+    let other_student = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(other_school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let other_token = get_auth_token(app, &other_admin_email, password).await;
+
+    let (book_id, copy_ids) = create_book_with_copies(&pool, &token, 1).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "GET",
+        &format!("/api/library/books/{}", book_id),
+        &other_token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/library/loans",
+        &other_token,
+        Some(json!({ "copy_id": copy_ids[0], "borrower_id": other_student.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(app, "GET", "/api/library/books", &other_token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"].as_array().unwrap().is_empty());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_checkout_rejects_borrower_from_other_school(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let other_school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    let outsider = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(other_school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let (_, copy_ids) = create_book_with_copies(&pool, &token, 1).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/library/loans",
+        &token,
+        Some(json!({ "copy_id": copy_ids[0], "borrower_id": outsider.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_checkout_enforces_borrowing_limit(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    let student = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    update_policy(&pool, &token, json!({ "student_loan_limit": 1 })).await;
+    let (_, copy_ids) = create_book_with_copies(&pool, &token, 2).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, loan) = send_json(
+        app,
+        "POST",
+        "/api/library/loans",
+        &token,
+        Some(json!({ "copy_id": copy_ids[0], "borrower_id": student.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/library/loans",
+        &token,
+        Some(json!({ "copy_id": copy_ids[1], "borrower_id": student.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Returning the first book frees a place under the limit
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        &format!("/api/library/loans/{}/return", loan["id"].as_str().unwrap()),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/library/loans",
+        &token,
+        Some(json!({ "copy_id": copy_ids[1], "borrower_id": student.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_overdue_return_charges_capped_fine(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    let student = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    update_policy(&pool, &token, json!({ "daily_fine": 50, "max_fine": 120 })).await;
+    let (_, copy_ids) = create_book_with_copies(&pool, &token, 1).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, loan) = send_json(
+        app,
+        "POST",
+        "/api/library/loans",
+        &token,
+        Some(json!({ "copy_id": copy_ids[0], "borrower_id": student.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let loan_id: Uuid = loan["id"].as_str().unwrap().parse().unwrap();
+
+    sqlx::query("UPDATE library_loans SET due_date = CURRENT_DATE - 5 WHERE id = $1")
+        .bind(loan_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(
+        app,
+        "POST",
+        &format!("/api/library/loans/{}/return", loan_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["fine"]["days_overdue"], 5);
+    assert_eq!(body["fine"]["amount"], 120);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(
+        app,
+        "GET",
+        &format!("/api/library/fines?borrower_id={}", student.id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let fines = body.as_array().unwrap();
+    assert_eq!(fines.len(), 1);
+    assert_eq!(fines[0]["loan_id"], loan_id.to_string());
+}
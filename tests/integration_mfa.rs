@@ -3,13 +3,22 @@ mod common;
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use chalkbyte::config::cors::CorsConfig;
+use chalkbyte::config::database::DbPools;
+use chalkbyte::config::docs::DocsConfig;
 use chalkbyte::config::email::EmailConfig;
 use chalkbyte::config::jwt::JwtConfig;
+use chalkbyte::config::oidc::OidcConfig;
 use chalkbyte::config::rate_limit::RateLimitConfig;
-use chalkbyte::router::init_router;
+use chalkbyte::config::storage::StorageConfig;
+use chalkbyte::config::webauthn::WebauthnConfig;
+use chalkbyte::modules::storage::backend::init_storage_backend;
+use chalkbyte::router::init_router_without_rate_limiting;
 use chalkbyte::state::AppState;
+use chalkbyte::utils::mfa_challenge::PgMfaChallengeStore;
+use chalkbyte::utils::token_store::PgTokenStore;
 use chalkbyte_cache::CacheConfig;
 use chalkbyte_core::file_storage::LocalFileStorage;
+use chalkbyte_core::{PasswordPolicy, hash_password};
 use std::path::PathBuf;
 use std::sync::Arc;
 use common::{create_test_user, generate_unique_email};
@@ -29,17 +38,31 @@ async fn setup_test_app(pool: PgPool) -> axum::Router {
         "http://localhost:3000/files".to_string(),
     ));
     dotenvy::dotenv().ok();
+    let storage_config = StorageConfig {
+        local_dir: PathBuf::from("./test_uploads/storage"),
+        ..StorageConfig::default()
+    };
+
     let state = AppState {
-        db: pool,
+        db: pool.clone(),
+        pools: DbPools::new(pool.clone()),
         jwt_config: JwtConfig::from_env(),
         email_config: EmailConfig::from_env(),
         cors_config: CorsConfig::from_env(),
         rate_limit_config: RateLimitConfig::from_env(),
+        password_policy: PasswordPolicy::default(),
+        webauthn_config: WebauthnConfig::from_env(),
+        oidc_config: OidcConfig::from_env(),
+        docs_config: DocsConfig::from_env(),
         cache_config: CacheConfig::default(),
         cache: None,
         file_storage,
+        storage: init_storage_backend(&storage_config, "test-storage-secret"),
+        storage_config,
+        token_store: Arc::new(PgTokenStore::new(pool.clone())),
+        mfa_challenges: Arc::new(PgMfaChallengeStore::new(pool)),
     };
-    init_router(state)
+    init_router_without_rate_limiting(state)
 }
 
 async fn get_auth_token(app: axum::Router, email: &str, password: &str) -> String {
@@ -339,7 +362,7 @@ async fn test_mfa_login_verification_returns_all_user_fields(pool: PgPool) {
     // Test that the SQL query used in verify_mfa_login returns all required User fields
     // This is the exact query from src/modules/auth/service.rs line 151
     let user_result = sqlx::query_as::<_, User>(
-        "SELECT id, first_name, last_name, email, school_id, level_id, branch_id, date_of_birth, grade_level, created_at, updated_at FROM users WHERE id = $1"
+        "SELECT id, first_name, last_name, email, school_id, level_id, branch_id, date_of_birth, grade_level, custom_fields, created_at, updated_at FROM users WHERE id = $1"
     )
     .bind(user_id)
     .fetch_one(&pool)
@@ -364,3 +387,355 @@ async fn test_mfa_login_verification_returns_all_user_fields(pool: PgPool) {
     assert!(user.created_at <= chrono::Utc::now());
     assert!(user.updated_at <= chrono::Utc::now());
 }
+
+async fn post_json(
+    pool: &PgPool,
+    uri: &str,
+    body: serde_json::Value,
+) -> (StatusCode, serde_json::Value) {
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_string(&body).unwrap()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_mfa_login_attempts_are_limited(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let email = generate_unique_email();
+    let password = "testpass123";
+    let test_user = create_test_user(&mut tx, &email, password, "student", None).await;
+
+    sqlx::query("UPDATE users SET mfa_enabled = true, mfa_secret = $1 WHERE id = $2")
+        .bind("JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP")
+        .bind(test_user.id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    tx.commit().await.unwrap();
+
+    let (status, body) = post_json(
+        &pool,
+        "/api/auth/login",
+        json!({ "email": email, "password": password }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["mfa_required"], true);
+    assert_eq!(body["methods"], json!(["totp"]));
+    assert_eq!(body["expires_in"], 600);
+    let temp_token = body["temp_token"].as_str().unwrap().to_string();
+
+    // No recovery codes exist, so the challenge rejects that method outright
+    let (status, _) = post_json(
+        &pool,
+        "/api/auth/mfa/recovery",
+        json!({ "temp_token": temp_token, "recovery_code": "ABCD1234" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    for attempt in 1..=5 {
+        let (status, body) = post_json(
+            &pool,
+            "/api/auth/mfa/verify",
+            json!({ "temp_token": temp_token, "code": "000000" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        if attempt == 5 {
+            assert!(body["error"].as_str().unwrap().contains("sign in again"));
+        }
+    }
+
+    // The challenge is gone once attempts run out
+    let remaining: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM mfa_login_challenges WHERE user_id = $1")
+            .bind(test_user.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(remaining, 0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_recovery_code_login_consumes_code_once(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let email = generate_unique_email();
+    let password = "testpass123";
+    let test_user = create_test_user(&mut tx, &email, password, "student", None).await;
+
+    sqlx::query("UPDATE users SET mfa_enabled = true, mfa_secret = $1 WHERE id = $2")
+        .bind("JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP")
+        .bind(test_user.id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    for code in ["ABCD2345", "WXYZ6789"] {
+        sqlx::query("INSERT INTO mfa_recovery_codes (user_id, code_hash) VALUES ($1, $2)")
+            .bind(test_user.id)
+            .bind(hash_password(code).unwrap())
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+    }
+
+    tx.commit().await.unwrap();
+
+    let login = json!({ "email": email, "password": password });
+    let (_, body) = post_json(&pool, "/api/auth/login", login.clone()).await;
+    let temp_token = body["temp_token"].as_str().unwrap().to_string();
+
+    // Codes are accepted however the user types them
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/auth/mfa/recovery")
+        .header("content-type", "application/json")
+        .header("user-agent", "Chalkbyte/2.1 (iOS 18)")
+        .header("x-forwarded-for", "203.0.113.7")
+        .body(Body::from(
+            json!({ "temp_token": temp_token, "recovery_code": "abcd-2345" }).to_string(),
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let token = body["access_token"].as_str().unwrap().to_string();
+
+    let (used_ip, used_agent): (Option<String>, Option<String>) = sqlx::query_as(
+        "SELECT used_ip_address, used_user_agent FROM mfa_recovery_codes
+         WHERE user_id = $1 AND used = TRUE",
+    )
+    .bind(test_user.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(used_ip.as_deref(), Some("203.0.113.7"));
+    assert_eq!(used_agent.as_deref(), Some("Chalkbyte/2.1 (iOS 18)"));
+
+    let (status, body) = send_authed(&pool, "GET", "/api/mfa/status", &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["recovery_codes_remaining"], 1);
+
+    // The same code cannot sign in twice
+    let (_, body) = post_json(&pool, "/api/auth/login", login).await;
+    let temp_token = body["temp_token"].as_str().unwrap().to_string();
+    let (status, _) = post_json(
+        &pool,
+        "/api/auth/mfa/recovery",
+        json!({ "temp_token": temp_token, "recovery_code": "ABCD2345" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+async fn send_authed(
+    pool: &PgPool,
+    method: &str,
+    uri: &str,
+    token: &str,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(match body {
+            Some(body) => Body::from(serde_json::to_string(&body).unwrap()),
+            None => Body::empty(),
+        })
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null),
+    )
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_start_passkey_registration_returns_options(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &email, password, "student", None).await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &email, password).await;
+
+    let (status, body) = send_authed(
+        &pool,
+        "POST",
+        "/api/mfa/passkeys/register/start",
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["registration_id"].is_string());
+    assert!(body["options"]["publicKey"]["challenge"].is_string());
+    assert_eq!(body["options"]["publicKey"]["user"]["name"], email);
+
+    // Nothing is registered until the ceremony is finished
+    let (status, body) = send_authed(&pool, "GET", "/api/mfa/passkeys", &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!([]));
+
+    let (status, body) = send_authed(&pool, "GET", "/api/mfa/status", &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["mfa_enabled"], false);
+    assert_eq!(body["passkeys"], 0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_finish_passkey_registration_unknown_registration(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &email, password, "student", None).await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &email, password).await;
+
+    let (status, _) = send_authed(
+        &pool,
+        "POST",
+        "/api/mfa/passkeys/register/finish",
+        &token,
+        Some(json!({
+            "registration_id": uuid::Uuid::new_v4(),
+            "name": "Work laptop",
+            "credential": {}
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_preferred_method_requires_factor(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let email = generate_unique_email();
+    let password = "testpass123";
+    let test_user = create_test_user(&mut tx, &email, password, "student", None).await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &email, password).await;
+
+    let (status, _) = send_authed(
+        &pool,
+        "PUT",
+        "/api/mfa/preferred-method",
+        &token,
+        Some(json!({ "method": "passkey" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    sqlx::query("UPDATE users SET mfa_enabled = true, mfa_secret = $1 WHERE id = $2")
+        .bind("JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP")
+        .bind(test_user.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (status, body) = send_authed(
+        &pool,
+        "PUT",
+        "/api/mfa/preferred-method",
+        &token,
+        Some(json!({ "method": "totp" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["preferred_method"], "totp");
+
+    // The login challenge reports the preference
+    let (status, body) = post_json(
+        &pool,
+        "/api/auth/login",
+        json!({ "email": email, "password": password }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["methods"], json!(["totp"]));
+    assert_eq!(body["preferred_method"], "totp");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_passkey_challenge_requires_pending_login_with_passkeys(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let email = generate_unique_email();
+    let password = "testpass123";
+    let test_user = create_test_user(&mut tx, &email, password, "student", None).await;
+
+    sqlx::query("UPDATE users SET mfa_enabled = true, mfa_secret = $1 WHERE id = $2")
+        .bind("JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP")
+        .bind(test_user.id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    tx.commit().await.unwrap();
+
+    let (status, _) = post_json(
+        &pool,
+        "/api/auth/mfa/passkey/challenge",
+        json!({ "temp_token": "not-a-real-handle" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (_, body) = post_json(
+        &pool,
+        "/api/auth/login",
+        json!({ "email": email, "password": password }),
+    )
+    .await;
+    let temp_token = body["temp_token"].as_str().unwrap().to_string();
+
+    let (status, body) = post_json(
+        &pool,
+        "/api/auth/mfa/passkey/challenge",
+        json!({ "temp_token": temp_token }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("No passkeys"));
+
+    // Verifying without a challenge does not complete the login
+    let (status, _) = post_json(
+        &pool,
+        "/api/auth/mfa/passkey/verify",
+        json!({ "temp_token": temp_token, "credential": {} }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -3,12 +3,21 @@ mod common;
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use chalkbyte::config::cors::CorsConfig;
+use chalkbyte::config::database::DbPools;
+use chalkbyte::config::docs::DocsConfig;
 use chalkbyte::config::email::EmailConfig;
 use chalkbyte::config::jwt::JwtConfig;
+use chalkbyte::config::oidc::OidcConfig;
 use chalkbyte::config::rate_limit::RateLimitConfig;
-use chalkbyte::router::init_router;
+use chalkbyte::config::storage::StorageConfig;
+use chalkbyte::config::webauthn::WebauthnConfig;
+use chalkbyte::modules::storage::backend::init_storage_backend;
+use chalkbyte::router::init_router_without_rate_limiting;
 use chalkbyte::state::AppState;
+use chalkbyte::utils::mfa_challenge::PgMfaChallengeStore;
+use chalkbyte::utils::token_store::PgTokenStore;
 use chalkbyte_cache::CacheConfig;
+use chalkbyte_core::PasswordPolicy;
 use chalkbyte_core::file_storage::LocalFileStorage;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -33,17 +42,31 @@ async fn setup_test_app(pool: PgPool) -> axum::Router {
         "http://localhost:3000/files".to_string(),
     ));
     dotenvy::dotenv().ok();
+    let storage_config = StorageConfig {
+        local_dir: PathBuf::from("./test_uploads/storage"),
+        ..StorageConfig::default()
+    };
+
     let state = AppState {
         db: pool.clone(),
+        pools: DbPools::new(pool.clone()),
         jwt_config: JwtConfig::from_env(),
         email_config: EmailConfig::from_env(),
         cors_config: CorsConfig::from_env(),
         rate_limit_config: RateLimitConfig::default(),
+        password_policy: PasswordPolicy::default(),
+        webauthn_config: WebauthnConfig::from_env(),
+        oidc_config: OidcConfig::from_env(),
+        docs_config: DocsConfig::from_env(),
         cache_config: CacheConfig::default(),
         cache: None,
         file_storage,
+        storage: init_storage_backend(&storage_config, "test-storage-secret"),
+        storage_config,
+        token_store: Arc::new(PgTokenStore::new(pool.clone())),
+        mfa_challenges: Arc::new(PgMfaChallengeStore::new(pool)),
     };
-    init_router(state)
+    init_router_without_rate_limiting(state)
 }
 
 async fn get_auth_token(app: axum::Router, email: &str, password: &str) -> String {
@@ -189,7 +212,7 @@ async fn test_create_system_role_as_system_admin(pool: PgPool) {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::CREATED);
 
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
@@ -233,7 +256,7 @@ async fn test_create_school_role_as_system_admin(pool: PgPool) {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::CREATED);
 
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
@@ -275,7 +298,7 @@ async fn test_create_school_role_as_school_admin(pool: PgPool) {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::CREATED);
 
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
@@ -320,7 +343,7 @@ async fn test_create_role_with_permissions(pool: PgPool) {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::CREATED);
 
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
@@ -394,7 +417,7 @@ async fn test_create_duplicate_role_fails(pool: PgPool) {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::CREATED);
 
     // Try to create duplicate
     let app = setup_test_app(pool.clone()).await;
@@ -683,8 +706,8 @@ async fn test_delete_role(pool: PgPool) {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    // delete_role returns () which becomes 200 OK with empty body
-    assert_eq!(response.status(), StatusCode::OK);
+    // delete_role answers 204 No Content with an empty body
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
 
     // Verify role is deleted
     let deleted = sqlx::query!("SELECT id FROM roles WHERE id = $1", role_id)
@@ -917,6 +940,102 @@ async fn test_assign_duplicate_role_is_idempotent(pool: PgPool) {
     assert_eq!(response.status(), StatusCode::OK);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn test_bulk_assign_role_reports_each_user(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school1 = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let school2 = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let admin_password = "testpass123";
+    create_test_user(
+        &mut tx,
+        &admin_email,
+        admin_password,
+        "admin",
+        Some(school1.id),
+    )
+    .await;
+
+    let new_user = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        "userpass",
+        "teacher",
+        Some(school1.id),
+    )
+    .await;
+    let existing_user = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        "userpass",
+        "teacher",
+        Some(school1.id),
+    )
+    .await;
+    let other_school_user = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        "userpass",
+        "teacher",
+        Some(school2.id),
+    )
+    .await;
+
+    let role_name = format!("Bulk Role {}", Uuid::new_v4());
+    let role = create_test_role(&mut tx, &role_name, Some(school1.id), false).await;
+    sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)")
+        .bind(existing_user.id)
+        .bind(role.id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, admin_password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let missing_user = Uuid::new_v4();
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/roles/{}/assign-bulk", role.id))
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::to_string(&json!({
+                "user_ids": [new_user.id, existing_user.id, other_school_user.id, missing_user]
+            }))
+            .unwrap(),
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(body["assigned_count"], 1);
+    assert_eq!(body["assigned"], json!([new_user.id]));
+    assert_eq!(body["already_assigned"], json!([existing_user.id]));
+    let failed = body["failed"].as_array().unwrap();
+    assert_eq!(failed.len(), 2);
+    assert_eq!(failed[0]["user_id"], other_school_user.id.to_string());
+    assert_eq!(failed[1]["user_id"], missing_user.to_string());
+
+    let has_role: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM user_roles WHERE user_id = $1 AND role_id = $2)",
+    )
+    .bind(other_school_user.id)
+    .bind(role.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert!(!has_role);
+}
+
 #[sqlx::test(migrations = "./migrations")]
 async fn test_school_admin_cannot_assign_role_to_other_school_user(pool: PgPool) {
     let mut tx = pool.begin().await.unwrap();
@@ -1075,7 +1194,7 @@ async fn test_remove_role_from_user(pool: PgPool) {
 
     let response = app.oneshot(request).await.unwrap();
     // remove_role_from_user returns () which becomes 200 OK
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
 
     // Verify role is removed
     let assignment = sqlx::query!(
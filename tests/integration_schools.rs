@@ -3,12 +3,21 @@ mod common;
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use chalkbyte::config::cors::CorsConfig;
+use chalkbyte::config::database::DbPools;
+use chalkbyte::config::docs::DocsConfig;
 use chalkbyte::config::email::EmailConfig;
 use chalkbyte::config::jwt::JwtConfig;
+use chalkbyte::config::oidc::OidcConfig;
 use chalkbyte::config::rate_limit::RateLimitConfig;
-use chalkbyte::router::init_router;
+use chalkbyte::config::storage::StorageConfig;
+use chalkbyte::config::webauthn::WebauthnConfig;
+use chalkbyte::modules::storage::backend::init_storage_backend;
+use chalkbyte::router::init_router_without_rate_limiting;
 use chalkbyte::state::AppState;
+use chalkbyte::utils::mfa_challenge::PgMfaChallengeStore;
+use chalkbyte::utils::token_store::PgTokenStore;
 use chalkbyte_cache::CacheConfig;
+use chalkbyte_core::PasswordPolicy;
 use chalkbyte_core::file_storage::LocalFileStorage;
 use common::{
     create_test_school, create_test_user, generate_unique_email, generate_unique_school_name,
@@ -32,17 +41,31 @@ async fn setup_test_app(pool: PgPool) -> axum::Router {
         "http://localhost:3000/files".to_string(),
     ));
     
+    let storage_config = StorageConfig {
+        local_dir: PathBuf::from("./test_uploads/storage"),
+        ..StorageConfig::default()
+    };
+
     let state = AppState {
-        db: pool,
+        db: pool.clone(),
+        pools: DbPools::new(pool.clone()),
         jwt_config: JwtConfig::from_env(),
         email_config: EmailConfig::from_env(),
         cors_config: CorsConfig::from_env(),
         rate_limit_config: RateLimitConfig::default(),
+        password_policy: PasswordPolicy::default(),
+        webauthn_config: WebauthnConfig::from_env(),
+        oidc_config: OidcConfig::from_env(),
+        docs_config: DocsConfig::from_env(),
         cache_config: CacheConfig::default(),
         cache: None,
         file_storage,
+        storage: init_storage_backend(&storage_config, "test-storage-secret"),
+        storage_config,
+        token_store: Arc::new(PgTokenStore::new(pool.clone())),
+        mfa_challenges: Arc::new(PgMfaChallengeStore::new(pool)),
     };
-    init_router(state)
+    init_router_without_rate_limiting(state)
 }
 
 async fn get_auth_token(app: axum::Router, email: &str, password: &str) -> String {
@@ -104,7 +127,7 @@ async fn test_create_school_as_system_admin(pool: PgPool) {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::CREATED);
 
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
@@ -303,7 +326,7 @@ async fn test_delete_school_as_system_admin(pool: PgPool) {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
 }
 
 #[sqlx::test(migrations = "./migrations")]
@@ -723,3 +746,339 @@ async fn test_upload_logo_replaces_existing(pool: PgPool) {
     assert_ne!(first_logo_path, second_logo_path);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn test_public_school_profile_opt_in(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &email, password, "admin", Some(school.id)).await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &email, password).await;
+
+    // Not listed yet
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/public/schools/greenfield")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/api/schools/{}/public-profile", school.id))
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({
+                "slug": "greenfield",
+                "public_profile_enabled": true,
+                "admissions_open": true
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Listed, readable without a token, and publicly cacheable
+    let app = setup_test_app(pool).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/public/schools/greenfield")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let cache_control = response.headers()["cache-control"].to_str().unwrap();
+    assert!(cache_control.contains("public"));
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["name"], school.name);
+    assert_eq!(body["admissions_open"], true);
+    assert!(body.get("id").is_none());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_error_request_id_traced_by_system_admin(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "system_admin", None).await;
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let school_admin_email = generate_unique_email();
+    create_test_user(
+        &mut tx,
+        &school_admin_email,
+        password,
+        "admin",
+        Some(school.id),
+    )
+    .await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    // The client's request ID is echoed in the header and the error body
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/schools/{}", uuid::Uuid::new_v4()))
+        .header("authorization", format!("Bearer {}", token))
+        .header("x-request-id", "support-case-42")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()["x-request-id"], "support-case-42");
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["request_id"], "support-case-42");
+
+    // The log summary is recorded in the background
+    let mut trace = serde_json::Value::Null;
+    for _ in 0..50 {
+        let app = setup_test_app(pool.clone()).await;
+        let request = Request::builder()
+            .method("GET")
+            .uri("/api/admin/trace/support-case-42")
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        if response.status() == StatusCode::OK {
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            trace = serde_json::from_slice(&body).unwrap();
+            break;
+        }
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+
+    let requests = trace["requests"].as_array().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0]["status"], 404);
+    assert_eq!(requests[0]["method"], "GET");
+
+    // School admins cannot trace requests
+    let app = setup_test_app(pool.clone()).await;
+    let school_admin_token = get_auth_token(app, &school_admin_email, password).await;
+
+    let app = setup_test_app(pool).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/admin/trace/support-case-42")
+        .header("authorization", format!("Bearer {}", school_admin_token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_email_branding_update_and_preview(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &email, password, "admin", Some(school.id)).await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &email, password).await;
+
+    // Invalid colors are rejected
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/api/schools/{}/email-branding", school.id))
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "primary_color": "red" }).to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/api/schools/{}/email-branding", school.id))
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({
+                "sender_name": "Greenfield Office",
+                "primary_color": "#0F766E"
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The preview uses the saved branding
+    let app = setup_test_app(pool).await;
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/schools/{}/email-branding/preview", school.id))
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "template": "announcement" }).to_string(),
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["sender_name"], "Greenfield Office");
+    assert!(body["html"].as_str().unwrap().contains("#0F766E"));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_email_suppressions_are_system_admin_only(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let password = "testpass123";
+    let admin_email = generate_unique_email();
+    create_test_user(&mut tx, &admin_email, password, "system_admin", None).await;
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let school_admin_email = generate_unique_email();
+    create_test_user(
+        &mut tx,
+        &school_admin_email,
+        password,
+        "admin",
+        Some(school.id),
+    )
+    .await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let school_admin_token = get_auth_token(app, &school_admin_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/admin/emails")
+        .header("authorization", format!("Bearer {}", school_admin_token))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/emails/suppressions")
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "email": "Parent@Example.com", "reason": "complaint" }).to_string(),
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let app = setup_test_app(pool).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/admin/emails/suppressions")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["data"][0]["email"], "parent@example.com");
+    assert_eq!(body["data"][0]["reason"], "complaint");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_school_settings_update(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &email, password, "admin", Some(school.id)).await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &email, password).await;
+
+    // New schools default to UTC and English
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/schools/{}/settings", school.id))
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["timezone"], "UTC");
+    assert_eq!(body["locale"], "en");
+
+    // Unknown timezones are rejected
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/api/schools/{}/settings", school.id))
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "timezone": "Mars/Olympus" }).to_string(),
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let app = setup_test_app(pool).await;
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/api/schools/{}/settings", school.id))
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "timezone": "Africa/Lagos", "locale": "en-NG" }).to_string(),
+        ))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["timezone"], "Africa/Lagos");
+    assert_eq!(body["locale"], "en-NG");
+}
@@ -0,0 +1,384 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use chalkbyte::config::cors::CorsConfig;
+use chalkbyte::config::database::DbPools;
+use chalkbyte::config::docs::DocsConfig;
+use chalkbyte::config::email::EmailConfig;
+use chalkbyte::config::jwt::JwtConfig;
+use chalkbyte::config::oidc::OidcConfig;
+use chalkbyte::config::rate_limit::RateLimitConfig;
+use chalkbyte::config::storage::StorageConfig;
+use chalkbyte::config::webauthn::WebauthnConfig;
+use chalkbyte::modules::storage::backend::init_storage_backend;
+use chalkbyte::router::init_router_without_rate_limiting;
+use chalkbyte::state::AppState;
+use chalkbyte::utils::mfa_challenge::PgMfaChallengeStore;
+use chalkbyte::utils::token_store::PgTokenStore;
+use chalkbyte_cache::CacheConfig;
+use chalkbyte_core::PasswordPolicy;
+use chalkbyte_core::file_storage::LocalFileStorage;
+use common::{
+    create_test_school, create_test_user, generate_unique_email, generate_unique_school_name,
+};
+use http_body_util::BodyExt;
+use serde_json::json;
+use sqlx::PgPool;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+async fn setup_test_app(pool: PgPool) -> axum::Router {
+    dotenvy::dotenv().ok();
+
+    let test_uploads_dir = PathBuf::from("./test_uploads");
+    let _ = tokio::fs::create_dir_all(&test_uploads_dir).await;
+
+    let file_storage = Arc::new(LocalFileStorage::new(
+        test_uploads_dir,
+        "http://localhost:3000/files".to_string(),
+    ));
+    let storage_config = StorageConfig {
+        local_dir: PathBuf::from("./test_uploads/storage"),
+        ..StorageConfig::default()
+    };
+
+    let state = AppState {
+        db: pool.clone(),
+        pools: DbPools::new(pool.clone()),
+        jwt_config: JwtConfig::from_env(),
+        email_config: EmailConfig::from_env(),
+        cors_config: CorsConfig::from_env(),
+        rate_limit_config: RateLimitConfig::default(),
+        password_policy: PasswordPolicy::default(),
+        webauthn_config: WebauthnConfig::from_env(),
+        oidc_config: OidcConfig::from_env(),
+        docs_config: DocsConfig::from_env(),
+        cache_config: CacheConfig::default(),
+        cache: None,
+        file_storage,
+        storage: init_storage_backend(&storage_config, "test-storage-secret"),
+        storage_config,
+        token_store: Arc::new(PgTokenStore::new(pool.clone())),
+        mfa_challenges: Arc::new(PgMfaChallengeStore::new(pool)),
+    };
+    init_router_without_rate_limiting(state)
+}
+
+async fn get_auth_token(app: axum::Router, email: &str, password: &str) -> String {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/auth/login")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::to_string(&json!({
+                "email": email,
+                "password": password
+            }))
+            .unwrap(),
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    body["access_token"].as_str().unwrap().to_string()
+}
+
+async fn send_json(
+    app: axum::Router,
+    method: &str,
+    uri: &str,
+    token: &str,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+    (status, body)
+}
+
+async fn request_leave(
+    pool: &PgPool,
+    token: &str,
+    start_date: &str,
+    end_date: &str,
+) -> (StatusCode, serde_json::Value) {
+    send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        "/api/staff-leave",
+        token,
+        Some(json!({
+            "leave_type": "annual",
+            "start_date": start_date,
+            "end_date": end_date
+        })),
+    )
+    .await
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_teacher_requests_leave_but_cannot_approve(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let teacher_email = generate_unique_email();
+    let student_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(
+        &mut tx,
+        &teacher_email,
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    create_test_user(
+        &mut tx,
+        &student_email,
+        password,
+        "student",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &teacher_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let student_token = get_auth_token(app, &student_email, password).await;
+
+    let (status, leave) = request_leave(&pool, &token, "2030-03-04", "2030-03-06").await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(leave["status"], "pending");
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(app, "GET", "/api/staff-leave/me", &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        &format!("/api/staff-leave/{}/approve", leave["id"].as_str().unwrap()),
+        &token,
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(app, "GET", "/api/staff-leave", &token, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = request_leave(&pool, &student_token, "2030-03-04", "2030-03-06").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_leave_from_other_school_not_found(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let other_school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let teacher_email = generate_unique_email();
+    let other_admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(
+        &mut tx,
+        &teacher_email,
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    create_test_user(
+        &mut tx,
+        &other_admin_email,
+        password,
+        "admin",
+        Some(other_school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &teacher_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let other_token = get_auth_token(app, &other_admin_email, password).await;
+
+    let (status, leave) = request_leave(&pool, &token, "2030-03-04", "2030-03-06").await;
+    assert_eq!(status, StatusCode::CREATED);
+    let leave_id = leave["id"].as_str().unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "GET",
+        &format!("/api/staff-leave/{}", leave_id),
+        &other_token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        &format!("/api/staff-leave/{}/approve", leave_id),
+        &other_token,
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(app, "GET", "/api/staff-leave", &other_token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"].as_array().unwrap().is_empty());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_overlapping_leave_rejected_until_cancelled(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let teacher_email = generate_unique_email();
+    let colleague_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(
+        &mut tx,
+        &teacher_email,
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    create_test_user(
+        &mut tx,
+        &colleague_email,
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &teacher_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let colleague_token = get_auth_token(app, &colleague_email, password).await;
+
+    let (status, leave) = request_leave(&pool, &token, "2030-03-04", "2030-03-08").await;
+    assert_eq!(status, StatusCode::CREATED);
+    let cancel_uri = format!("/api/staff-leave/{}/cancel", leave["id"].as_str().unwrap());
+
+    let (status, _) = request_leave(&pool, &token, "2030-03-07", "2030-03-10").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Only the requester may cancel
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(app, "POST", &cancel_uri, &colleague_token, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(app, "POST", &cancel_uri, &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "cancelled");
+
+    let (status, _) = request_leave(&pool, &token, "2030-03-07", "2030-03-10").await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_approve_requires_available_substitute(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let teacher_email = generate_unique_email();
+    let absent_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    let teacher = create_test_user(
+        &mut tx,
+        &teacher_email,
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    let absent =
+        create_test_user(&mut tx, &absent_email, password, "teacher", Some(school.id)).await;
+    let available = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let admin_token = get_auth_token(app, &admin_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &teacher_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let absent_token = get_auth_token(app, &absent_email, password).await;
+
+    let (status, absence) = request_leave(&pool, &absent_token, "2030-03-01", "2030-03-10").await;
+    assert_eq!(status, StatusCode::CREATED);
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        &format!(
+            "/api/staff-leave/{}/approve",
+            absence["id"].as_str().unwrap()
+        ),
+        &admin_token,
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, leave) = request_leave(&pool, &token, "2030-03-04", "2030-03-06").await;
+    assert_eq!(status, StatusCode::CREATED);
+    let approve_uri = format!("/api/staff-leave/{}/approve", leave["id"].as_str().unwrap());
+
+    for substitute_id in [teacher.id, absent.id] {
+        let app = setup_test_app(pool.clone()).await;
+        let (status, _) = send_json(
+            app,
+            "POST",
+            &approve_uri,
+            &admin_token,
+            Some(json!({ "substitute_id": substitute_id })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(
+        app,
+        "POST",
+        &approve_uri,
+        &admin_token,
+        Some(json!({ "substitute_id": available.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "approved");
+    assert_eq!(body["substitute_id"], available.id.to_string());
+}
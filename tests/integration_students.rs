@@ -3,17 +3,27 @@ mod common;
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use chalkbyte::config::cors::CorsConfig;
+use chalkbyte::config::database::DbPools;
+use chalkbyte::config::docs::DocsConfig;
 use chalkbyte::config::email::EmailConfig;
 use chalkbyte::config::jwt::JwtConfig;
+use chalkbyte::config::oidc::OidcConfig;
 use chalkbyte::config::rate_limit::RateLimitConfig;
-use chalkbyte::router::init_router;
+use chalkbyte::config::storage::StorageConfig;
+use chalkbyte::config::webauthn::WebauthnConfig;
+use chalkbyte::modules::storage::backend::init_storage_backend;
+use chalkbyte::router::init_router_without_rate_limiting;
 use chalkbyte::state::AppState;
+use chalkbyte::utils::mfa_challenge::PgMfaChallengeStore;
+use chalkbyte::utils::token_store::PgTokenStore;
 use chalkbyte_cache::CacheConfig;
+use chalkbyte_core::PasswordPolicy;
 use chalkbyte_core::file_storage::LocalFileStorage;
 use std::path::PathBuf;
 use std::sync::Arc;
 use common::{
-    create_test_school, create_test_user, generate_unique_email, generate_unique_school_name,
+    create_test_role, create_test_school, create_test_user, generate_unique_email,
+    generate_unique_role_name, generate_unique_school_name,
 };
 use http_body_util::BodyExt;
 use serde_json::json;
@@ -31,17 +41,31 @@ async fn setup_test_app(pool: PgPool) -> axum::Router {
         "http://localhost:3000/files".to_string(),
     ));
     dotenvy::dotenv().ok();
+    let storage_config = StorageConfig {
+        local_dir: PathBuf::from("./test_uploads/storage"),
+        ..StorageConfig::default()
+    };
+
     let state = AppState {
         db: pool.clone(),
+        pools: DbPools::new(pool.clone()),
         jwt_config: JwtConfig::from_env(),
         email_config: EmailConfig::from_env(),
         cors_config: CorsConfig::from_env(),
         rate_limit_config: RateLimitConfig::default(),
+        password_policy: PasswordPolicy::default(),
+        webauthn_config: WebauthnConfig::from_env(),
+        oidc_config: OidcConfig::from_env(),
+        docs_config: DocsConfig::from_env(),
         cache_config: CacheConfig::default(),
         cache: None,
         file_storage,
+        storage: init_storage_backend(&storage_config, "test-storage-secret"),
+        storage_config,
+        token_store: Arc::new(PgTokenStore::new(pool.clone())),
+        mfa_challenges: Arc::new(PgMfaChallengeStore::new(pool)),
     };
-    init_router(state)
+    init_router_without_rate_limiting(state)
 }
 
 async fn get_auth_token(app: axum::Router, email: &str, password: &str) -> String {
@@ -93,7 +117,7 @@ async fn test_create_student_as_admin(pool: PgPool) {
                 "first_name": "Student",
                 "last_name": "Test",
                 "email": student_email,
-                "password": "studentpass123",
+                "password": "learnpass123",
                 "date_of_birth": "2010-01-15",
                 "grade_level": "10"
             }))
@@ -102,7 +126,7 @@ async fn test_create_student_as_admin(pool: PgPool) {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::CREATED);
 
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
@@ -146,7 +170,7 @@ async fn test_create_student_as_student_forbidden(pool: PgPool) {
                 "first_name": "Student",
                 "last_name": "Test",
                 "email": new_student_email,
-                "password": "studentpass123",
+                "password": "learnpass123",
                 "date_of_birth": "2010-01-15",
                 "grade_level": "10"
             }))
@@ -317,6 +341,54 @@ async fn test_get_student_by_id(pool: PgPool) {
 
 #[sqlx::test(migrations = "./migrations")]
 
+async fn test_get_student_lite_view(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+
+    let student_email = generate_unique_email();
+    let student = create_test_user(
+        &mut tx,
+        &student_email,
+        "pass123",
+        "student",
+        Some(school.id),
+    )
+    .await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/students/{}?view=lite", student.id))
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["vary"], "x-client");
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["id"], student.id.to_string());
+    assert_eq!(body["school_id"], school.id.to_string());
+    assert!(body.get("email").is_none());
+    assert!(body.get("custom_fields").is_none());
+    assert!(body.get("created_at").is_none());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+
 async fn test_get_student_from_different_school_forbidden(pool: PgPool) {
     let mut tx = pool.begin().await.unwrap();
 
@@ -444,7 +516,7 @@ async fn test_delete_student(pool: PgPool) {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
 }
 
 #[sqlx::test(migrations = "./migrations")]
@@ -515,7 +587,7 @@ async fn test_create_student_invalid_email(pool: PgPool) {
                 "first_name": "Student",
                 "last_name": "Test",
                 "email": "not-an-email",
-                "password": "studentpass123",
+                "password": "learnpass123",
                 "date_of_birth": "2010-01-15",
                 "grade_level": "10"
             }))
@@ -578,6 +650,78 @@ async fn test_get_students_with_pagination(pool: PgPool) {
 
 #[sqlx::test(migrations = "./migrations")]
 
+async fn test_get_students_with_cursor_pagination(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+
+    for _ in 0..3 {
+        let student_email = generate_unique_email();
+        create_test_user(
+            &mut tx,
+            &student_email,
+            "pass123",
+            "student",
+            Some(school.id),
+        )
+        .await;
+    }
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/students?limit=2&cursor=")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let first: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(first["data"].as_array().unwrap().len(), 2);
+    assert_eq!(first["meta"]["has_more"], true);
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/api/students?limit=2&cursor={}",
+            first["meta"]["next_cursor"].as_str().unwrap()
+        ))
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let second: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let data = second["data"].as_array().unwrap();
+    assert_eq!(data.len(), 1);
+    assert_eq!(second["meta"]["has_more"], false);
+    assert!(
+        first["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|student| student["id"] != data[0]["id"])
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+
 async fn test_unauthorized_access_to_students(pool: PgPool) {
     let app = setup_test_app(pool.clone()).await;
 
@@ -590,3 +734,519 @@ async fn test_unauthorized_access_to_students(pool: PgPool) {
     let response = app.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 }
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_export_students_csv(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let other_school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+
+    let student_email = generate_unique_email();
+    create_test_user(
+        &mut tx,
+        &student_email,
+        "pass123",
+        "student",
+        Some(school.id),
+    )
+    .await;
+
+    let other_student_email = generate_unique_email();
+    create_test_user(
+        &mut tx,
+        &other_student_email,
+        "pass123",
+        "student",
+        Some(other_school.id),
+    )
+    .await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/students/export?format=csv")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/csv; charset=utf-8"
+    );
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let mut lines = body.lines();
+
+    assert!(
+        lines
+            .next()
+            .unwrap()
+            .starts_with("id,first_name,last_name,email,")
+    );
+    let rows: Vec<&str> = lines.collect();
+    assert_eq!(rows.len(), 1);
+    assert!(rows[0].contains(&student_email));
+    assert!(!body.contains(&other_student_email));
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/students/export?format=pdf")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_auditor_reads_students_without_restricted_fields(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+
+    let auditor_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(
+        &mut tx,
+        &auditor_email,
+        password,
+        "auditor",
+        Some(school.id),
+    )
+    .await;
+
+    let student_email = generate_unique_email();
+    create_test_user(
+        &mut tx,
+        &student_email,
+        "pass123",
+        "student",
+        Some(school.id),
+    )
+    .await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &auditor_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/students")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let students = body["data"].as_array().unwrap();
+    assert_eq!(students.len(), 1);
+    assert!(students[0].get("email").is_none());
+    assert!(students[0].get("date_of_birth").is_none());
+    assert_eq!(students[0]["first_name"], "Test");
+
+    // Auditors cannot write or export
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/students")
+        .header("content-type", "application/json")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::from(
+            serde_json::to_string(&json!({
+                "first_name": "New",
+                "last_name": "Student",
+                "email": generate_unique_email(),
+                "password": "learnpass123"
+            }))
+            .unwrap(),
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/students/export?format=csv")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_start_guardian_account_run(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+
+    let admin_email = generate_unique_email();
+    let teacher_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    create_test_user(
+        &mut tx,
+        &teacher_email,
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let admin_token = get_auth_token(app.clone(), &admin_email, password).await;
+    let teacher_token = get_auth_token(app.clone(), &teacher_email, password).await;
+    let uri = format!("/api/schools/{}/guardian-account-runs", school.id);
+
+    let start = |token: String| {
+        Request::builder()
+            .method("POST")
+            .uri(&uri)
+            .header("authorization", format!("Bearer {}", token))
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "send_invitations": true }).to_string()))
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(start(teacher_token)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(start(admin_token.clone()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let run: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(run["status"], "pending");
+    assert_eq!(run["send_invitations"], true);
+
+    // Only one run per school may be queued at a time
+    let response = app
+        .clone()
+        .oneshot(start(admin_token.clone()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let request = Request::builder()
+        .method("GET")
+        .uri(&uri)
+        .header("authorization", format!("Bearer {}", admin_token))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let runs: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(runs["meta"]["total"], 1);
+    assert_eq!(runs["data"][0]["id"], run["id"]);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_teacher_sees_only_students_in_taught_branches(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+
+    let teacher_email = generate_unique_email();
+    let password = "testpass123";
+    let teacher = create_test_user(
+        &mut tx,
+        &teacher_email,
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    let taught = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(school.id),
+    )
+    .await;
+    let other = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(school.id),
+    )
+    .await;
+
+    let level_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO levels (name, school_id) VALUES ('Grade 1', $1) RETURNING id",
+    )
+    .bind(school.id)
+    .fetch_one(&mut *tx)
+    .await
+    .unwrap();
+    let branch_id: uuid::Uuid =
+        sqlx::query_scalar("INSERT INTO branches (name, level_id) VALUES ('A', $1) RETURNING id")
+            .bind(level_id)
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+    sqlx::query("UPDATE users SET level_id = $1, branch_id = $2 WHERE id = $3")
+        .bind(level_id)
+        .bind(branch_id)
+        .bind(taught.id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO branch_teachers (branch_id, teacher_id, capacity, starts_on)
+         VALUES ($1, $2, 'lead', CURRENT_DATE)",
+    )
+    .bind(branch_id)
+    .bind(teacher.id)
+    .execute(&mut *tx)
+    .await
+    .unwrap();
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app.clone(), &teacher_email, password).await;
+    let get = |uri: String| {
+        Request::builder()
+            .method("GET")
+            .uri(uri)
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(get("/api/students".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["meta"]["total"], 1);
+    assert_eq!(body["data"][0]["id"], taught.id.to_string());
+
+    let response = app
+        .clone()
+        .oneshot(get(format!("/api/students/{}", taught.id)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(get(format!("/api/students/{}", other.id)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_contact_details_omitted_without_permission(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+
+    let teacher_email = generate_unique_email();
+    let password = "testpass123";
+    let teacher = create_test_user(
+        &mut tx,
+        &teacher_email,
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+
+    let student = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        "pass123",
+        "student",
+        Some(school.id),
+    )
+    .await;
+
+    // The teacher reads every student but not their contact details
+    sqlx::query!(
+        "DELETE FROM role_permissions
+         WHERE role_id = '00000000-0000-0000-0000-000000000003'
+           AND permission_id = (SELECT id FROM permissions WHERE name = 'contact_details:read')"
+    )
+    .execute(&mut *tx)
+    .await
+    .unwrap();
+
+    let role = create_test_role(
+        &mut tx,
+        &generate_unique_role_name(),
+        Some(school.id),
+        false,
+    )
+    .await;
+    sqlx::query!(
+        "INSERT INTO role_permissions (role_id, permission_id)
+         SELECT $1, id FROM permissions WHERE name = 'students:read'",
+        role.id
+    )
+    .execute(&mut *tx)
+    .await
+    .unwrap();
+    sqlx::query!(
+        "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)",
+        teacher.id,
+        role.id
+    )
+    .execute(&mut *tx)
+    .await
+    .unwrap();
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &teacher_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/students/{}", student.id))
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["first_name"], "Test");
+    assert!(body.get("email").is_none());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_upload_and_download_student_document(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+
+    let student = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        "pass123",
+        "student",
+        Some(school.id),
+    )
+    .await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let pdf = b"%PDF-1.7\n%%EOF\n";
+    let boundary = "chalkbyte-test-boundary";
+    let mut body = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"kind\"\r\n\r\nbirth_certificate\r\n\
+         --{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"birth.pdf\"\r\n\
+         Content-Type: application/pdf\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(pdf);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/students/{}/documents", student.id))
+        .header("authorization", format!("Bearer {}", token))
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={boundary}"),
+        )
+        .body(Body::from(body))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let document: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(document["kind"], "birth_certificate");
+    assert_eq!(document["filename"], "birth.pdf");
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/api/students/{}/documents/{}/download",
+            student.id,
+            document["id"].as_str().unwrap()
+        ))
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let download: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let url = download["url"].as_str().unwrap();
+    let path = url.strip_prefix("http://localhost:3000").unwrap();
+
+    // The signed link works without a token
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri(path)
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/pdf");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], pdf);
+
+    // A tampered link does not
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("{}0", path))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
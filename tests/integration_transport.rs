@@ -0,0 +1,434 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use chalkbyte::config::cors::CorsConfig;
+use chalkbyte::config::database::DbPools;
+use chalkbyte::config::docs::DocsConfig;
+use chalkbyte::config::email::EmailConfig;
+use chalkbyte::config::jwt::JwtConfig;
+use chalkbyte::config::oidc::OidcConfig;
+use chalkbyte::config::rate_limit::RateLimitConfig;
+use chalkbyte::config::storage::StorageConfig;
+use chalkbyte::config::webauthn::WebauthnConfig;
+use chalkbyte::modules::storage::backend::init_storage_backend;
+use chalkbyte::router::init_router_without_rate_limiting;
+use chalkbyte::state::AppState;
+use chalkbyte::utils::mfa_challenge::PgMfaChallengeStore;
+use chalkbyte::utils::token_store::PgTokenStore;
+use chalkbyte_cache::CacheConfig;
+use chalkbyte_core::PasswordPolicy;
+use chalkbyte_core::file_storage::LocalFileStorage;
+use common::{
+    create_test_school, create_test_user, generate_unique_email, generate_unique_school_name,
+};
+use http_body_util::BodyExt;
+use serde_json::json;
+use sqlx::PgPool;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app(pool: PgPool) -> axum::Router {
+    dotenvy::dotenv().ok();
+
+    let test_uploads_dir = PathBuf::from("./test_uploads");
+    let _ = tokio::fs::create_dir_all(&test_uploads_dir).await;
+
+    let file_storage = Arc::new(LocalFileStorage::new(
+        test_uploads_dir,
+        "http://localhost:3000/files".to_string(),
+    ));
+    let storage_config = StorageConfig {
+        local_dir: PathBuf::from("./test_uploads/storage"),
+        ..StorageConfig::default()
+    };
+
+    let state = AppState {
+        db: pool.clone(),
+        pools: DbPools::new(pool.clone()),
+        jwt_config: JwtConfig::from_env(),
+        email_config: EmailConfig::from_env(),
+        cors_config: CorsConfig::from_env(),
+        rate_limit_config: RateLimitConfig::default(),
+        password_policy: PasswordPolicy::default(),
+        webauthn_config: WebauthnConfig::from_env(),
+        oidc_config: OidcConfig::from_env(),
+        docs_config: DocsConfig::from_env(),
+        cache_config: CacheConfig::default(),
+        cache: None,
+        file_storage,
+        storage: init_storage_backend(&storage_config, "test-storage-secret"),
+        storage_config,
+        token_store: Arc::new(PgTokenStore::new(pool.clone())),
+        mfa_challenges: Arc::new(PgMfaChallengeStore::new(pool)),
+    };
+    init_router_without_rate_limiting(state)
+}
+
+async fn get_auth_token(app: axum::Router, email: &str, password: &str) -> String {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/auth/login")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::to_string(&json!({
+                "email": email,
+                "password": password
+            }))
+            .unwrap(),
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    body["access_token"].as_str().unwrap().to_string()
+}
+
+async fn send_json(
+    app: axum::Router,
+    method: &str,
+    uri: &str,
+    token: &str,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+    (status, body)
+}
+
+/// Creates an academic session with a current term, returning the term's ID.
+async fn create_test_term(pool: &PgPool, school_id: Uuid) -> Uuid {
+    let session_id: Uuid = sqlx::query_scalar(
+        r#"INSERT INTO academic_sessions (name, school_id, start_date, end_date, is_active)
+           VALUES ('2025/2026', $1, '2025-09-01', '2026-07-31', true) RETURNING id"#,
+    )
+    .bind(school_id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+
+    sqlx::query_scalar(
+        r#"INSERT INTO terms (name, academic_session_id, start_date, end_date, is_current)
+           VALUES ('First Term', $1, '2025-09-01', '2025-12-15', true) RETURNING id"#,
+    )
+    .bind(session_id)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+/// Creates a vehicle and a route it serves through the API, returning the route's ID.
+async fn create_route_with_vehicle(
+    pool: &PgPool,
+    token: &str,
+    capacity: i32,
+    driver_id: Option<Uuid>,
+) -> Uuid {
+    let (status, vehicle) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        "/api/transport/vehicles",
+        token,
+        Some(json!({
+            "registration_number": "BUS-001",
+            "capacity": capacity,
+            "driver_id": driver_id
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, route) = send_json(
+        setup_test_app(pool.clone()).await,
+        "POST",
+        "/api/transport/routes",
+        token,
+        Some(json!({
+            "name": "North Loop",
+            "vehicle_id": vehicle["id"],
+            "fee_amount": 15000
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    route["id"].as_str().unwrap().parse().unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_create_route_with_vehicle_as_admin(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let route_id = create_route_with_vehicle(&pool, &token, 30, None).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(
+        app,
+        "GET",
+        &format!("/api/transport/routes/{}", route_id),
+        &token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["name"], "North Loop");
+    assert_eq!(body["school_id"], school.id.to_string());
+    assert_eq!(body["fee_amount"], 15000);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_transport_management_as_teacher_forbidden(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let teacher_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(
+        &mut tx,
+        &teacher_email,
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &teacher_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/transport/vehicles",
+        &token,
+        Some(json!({ "registration_number": "BUS-001", "capacity": 30 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(app, "GET", "/api/transport/routes", &token, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Teachers may still look up the routes they drive
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(app, "GET", "/api/transport/me/routes", &token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.as_array().unwrap().is_empty());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_route_from_other_school_not_found(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let other_school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let other_admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    create_test_user(
+        &mut tx,
+        &other_admin_email,
+        password,
+        "admin",
+        Some(other_school.id),
+    )
+    .await;
+    let other_student = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(other_school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+    create_test_term(&pool, other_school.id).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let other_token = get_auth_token(app, &other_admin_email, password).await;
+
+    let route_id = create_route_with_vehicle(&pool, &token, 30, None).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "GET",
+        &format!("/api/transport/routes/{}", route_id),
+        &other_token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/transport/assignments",
+        &other_token,
+        Some(json!({ "route_id": route_id, "student_id": other_student.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(app, "GET", "/api/transport/routes", &other_token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"].as_array().unwrap().is_empty());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_assign_route_rejects_when_vehicle_full(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    let first = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(school.id),
+    )
+    .await;
+    let second = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+    create_test_term(&pool, school.id).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let route_id = create_route_with_vehicle(&pool, &token, 1, None).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/transport/assignments",
+        &token,
+        Some(json!({ "route_id": route_id, "student_id": first.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/transport/assignments",
+        &token,
+        Some(json!({ "route_id": route_id, "student_id": second.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_driver_manifest_limited_to_own_routes(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let driver_email = generate_unique_email();
+    let other_teacher_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    let driver =
+        create_test_user(&mut tx, &driver_email, password, "teacher", Some(school.id)).await;
+    create_test_user(
+        &mut tx,
+        &other_teacher_email,
+        password,
+        "teacher",
+        Some(school.id),
+    )
+    .await;
+    let student = create_test_user(
+        &mut tx,
+        &generate_unique_email(),
+        password,
+        "student",
+        Some(school.id),
+    )
+    .await;
+    tx.commit().await.unwrap();
+    create_test_term(&pool, school.id).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let driver_token = get_auth_token(app, &driver_email, password).await;
+    let app = setup_test_app(pool.clone()).await;
+    let other_teacher_token = get_auth_token(app, &other_teacher_email, password).await;
+
+    let route_id = create_route_with_vehicle(&pool, &token, 30, Some(driver.id)).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "POST",
+        "/api/transport/assignments",
+        &token,
+        Some(json!({ "route_id": route_id, "student_id": student.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, body) = send_json(
+        app,
+        "GET",
+        &format!("/api/transport/me/routes/{}/manifest", route_id),
+        &driver_token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_students"], 1);
+    assert_eq!(
+        body["unassigned_stop"][0]["student_id"],
+        student.id.to_string()
+    );
+
+    let app = setup_test_app(pool.clone()).await;
+    let (status, _) = send_json(
+        app,
+        "GET",
+        &format!("/api/transport/me/routes/{}/manifest", route_id),
+        &other_teacher_token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -3,12 +3,21 @@ mod common;
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use chalkbyte::config::cors::CorsConfig;
+use chalkbyte::config::database::DbPools;
+use chalkbyte::config::docs::DocsConfig;
 use chalkbyte::config::email::EmailConfig;
 use chalkbyte::config::jwt::JwtConfig;
+use chalkbyte::config::oidc::OidcConfig;
 use chalkbyte::config::rate_limit::RateLimitConfig;
-use chalkbyte::router::init_router;
+use chalkbyte::config::storage::StorageConfig;
+use chalkbyte::config::webauthn::WebauthnConfig;
+use chalkbyte::modules::storage::backend::init_storage_backend;
+use chalkbyte::router::init_router_without_rate_limiting;
 use chalkbyte::state::AppState;
+use chalkbyte::utils::mfa_challenge::PgMfaChallengeStore;
+use chalkbyte::utils::token_store::PgTokenStore;
 use chalkbyte_cache::CacheConfig;
+use chalkbyte_core::PasswordPolicy;
 use chalkbyte_core::file_storage::LocalFileStorage;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -32,17 +41,31 @@ async fn setup_test_app(pool: PgPool) -> axum::Router {
         "http://localhost:3000/files".to_string(),
     ));
     dotenvy::dotenv().ok();
+    let storage_config = StorageConfig {
+        local_dir: PathBuf::from("./test_uploads/storage"),
+        ..StorageConfig::default()
+    };
+
     let state = AppState {
         db: pool.clone(),
+        pools: DbPools::new(pool.clone()),
         jwt_config: JwtConfig::from_env(),
         email_config: EmailConfig::from_env(),
         cors_config: CorsConfig::from_env(),
         rate_limit_config: RateLimitConfig::default(),
+        password_policy: PasswordPolicy::default(),
+        webauthn_config: WebauthnConfig::from_env(),
+        oidc_config: OidcConfig::from_env(),
+        docs_config: DocsConfig::from_env(),
         cache_config: CacheConfig::default(),
         cache: None,
         file_storage,
+        storage: init_storage_backend(&storage_config, "test-storage-secret"),
+        storage_config,
+        token_store: Arc::new(PgTokenStore::new(pool.clone())),
+        mfa_challenges: Arc::new(PgMfaChallengeStore::new(pool)),
     };
-    init_router(state)
+    init_router_without_rate_limiting(state)
 }
 
 async fn get_auth_token(app: axum::Router, email: &str, password: &str) -> String {
@@ -240,7 +263,7 @@ async fn test_create_user_as_system_admin(pool: PgPool) {
                 "first_name": "New",
                 "last_name": "User",
                 "email": new_user_email,
-                "password": "newpass123",
+                "password": "brightpass123",
                 "role_ids": [system_roles::ADMIN.to_string()],
                 "school_id": school.id
             }))
@@ -249,7 +272,7 @@ async fn test_create_user_as_system_admin(pool: PgPool) {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::CREATED);
 
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
@@ -287,7 +310,7 @@ async fn test_create_user_as_school_admin(pool: PgPool) {
                 "first_name": "New",
                 "last_name": "Teacher",
                 "email": new_user_email,
-                "password": "newpass123",
+                "password": "brightpass123",
                 "role_ids": [system_roles::TEACHER.to_string()]
             }))
             .unwrap(),
@@ -295,7 +318,7 @@ async fn test_create_user_as_school_admin(pool: PgPool) {
         .unwrap();
 
     let response = app.oneshot(request).await.unwrap();
-    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.status(), StatusCode::CREATED);
 
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
@@ -340,7 +363,7 @@ async fn test_create_user_as_teacher_forbidden(pool: PgPool) {
                 "first_name": "New",
                 "last_name": "User",
                 "email": new_user_email,
-                "password": "newpass123",
+                "password": "brightpass123",
                 "role_ids": [system_roles::STUDENT.to_string()]
             }))
             .unwrap(),
@@ -380,7 +403,7 @@ async fn test_create_user_duplicate_email(pool: PgPool) {
                 "first_name": "New",
                 "last_name": "User",
                 "email": existing_email,
-                "password": "newpass123",
+                "password": "brightpass123",
                 "role": "student"
             }))
             .unwrap(),
@@ -527,6 +550,248 @@ async fn test_get_users_with_pagination(pool: PgPool) {
     assert!(body["meta"]["total"].as_i64().unwrap() >= 5);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn test_get_users_sorted(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    for _ in 0..3 {
+        let user_email = generate_unique_email();
+        create_test_user(&mut tx, &user_email, "pass123", "student", Some(school.id)).await;
+    }
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/users?sort=-email&limit=100")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let emails: Vec<&str> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|u| u["email"].as_str().unwrap())
+        .collect();
+    let mut expected = emails.clone();
+    expected.sort_unstable_by(|a, b| b.cmp(a));
+    assert_eq!(emails.len(), 4);
+    assert_eq!(emails, expected);
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/users?sort=password")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "INVALID_SORT");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_get_users_filtered(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+    let mut student_emails = Vec::new();
+    for _ in 0..3 {
+        let user_email = generate_unique_email();
+        create_test_user(&mut tx, &user_email, "pass123", "student", Some(school.id)).await;
+        student_emails.push(user_email);
+    }
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let list = |query: String| {
+        let pool = pool.clone();
+        let token = token.clone();
+        async move {
+            let app = setup_test_app(pool).await;
+            let request = Request::builder()
+                .method("GET")
+                .uri(format!("/api/users?{}", query))
+                .header("authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap();
+            let response = app.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            (status, body)
+        }
+    };
+
+    let (status, body) = list(format!(
+        "filter=email:in:{}%7C{},school_id:eq:{}",
+        student_emails[0], student_emails[1], school.id
+    ))
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let mut emails: Vec<&str> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|u| u["email"].as_str().unwrap())
+        .collect();
+    emails.sort_unstable();
+    let mut expected = vec![student_emails[0].as_str(), student_emails[1].as_str()];
+    expected.sort_unstable();
+    assert_eq!(emails, expected);
+    assert_eq!(body["meta"]["total"], 2);
+
+    let (status, body) = list("filter=created_at:gte:2999-01-01".to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 0);
+
+    let (status, body) = list(format!("cursor=&filter=email:eq:{}", student_emails[2])).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    for query in [
+        "filter=password:eq:x",
+        "filter=created_at:gte:yesterday",
+        "filter=school_id:contains:abc",
+    ] {
+        let (status, body) = list(query.to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{query}");
+        assert_eq!(body["code"], "INVALID_FILTER", "{query}");
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+
+async fn test_get_users_with_cursor_pagination(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+
+    // Created in one transaction, so they share created_at and are ordered by id
+    for _ in 0..4 {
+        let user_email = generate_unique_email();
+        create_test_user(&mut tx, &user_email, "pass123", "student", Some(school.id)).await;
+    }
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let mut seen = std::collections::HashSet::new();
+    let mut cursor = String::new();
+    let mut pages = 0;
+
+    loop {
+        let app = setup_test_app(pool.clone()).await;
+        let request = Request::builder()
+            .method("GET")
+            .uri(format!("/api/users?limit=2&cursor={}", cursor))
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert!(body["meta"].get("total").is_none());
+        for user in body["data"].as_array().unwrap() {
+            assert!(seen.insert(user["id"].as_str().unwrap().to_string()));
+        }
+        pages += 1;
+
+        match body["meta"]["next_cursor"].as_str() {
+            Some(next) => cursor = next.to_string(),
+            None => break,
+        }
+    }
+
+    assert_eq!(seen.len(), 5);
+    assert_eq!(pages, 3);
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/users?cursor=garbage")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+
+async fn test_get_users_mobile_lite_view(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/users")
+        .header("authorization", format!("Bearer {}", token))
+        .header("x-client", "mobile-lite")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(body["meta"]["total"], 1);
+    let user = &body["data"][0];
+    assert_eq!(user["email"], admin_email);
+    assert_eq!(user["school_id"], school.id.to_string());
+    assert!(user["roles"][0].is_string());
+    assert!(user.get("school").is_none());
+    assert!(user.get("custom_fields").is_none());
+}
+
 #[sqlx::test(migrations = "./migrations")]
 
 async fn test_unauthorized_access_to_profile(pool: PgPool) {
@@ -568,7 +833,7 @@ async fn test_create_user_invalid_email(pool: PgPool) {
                 "first_name": "New",
                 "last_name": "User",
                 "email": "not-an-email",
-                "password": "newpass123",
+                "password": "brightpass123",
                 "role": "student"
             }))
             .unwrap(),
@@ -578,3 +843,122 @@ async fn test_create_user_invalid_email(pool: PgPool) {
     let response = app.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
 }
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_export_users_csv_scoped_and_filtered(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school1 = create_test_school(&mut tx, &generate_unique_school_name()).await;
+    let school2 = create_test_school(&mut tx, &generate_unique_school_name()).await;
+
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school1.id)).await;
+
+    let user1_email = generate_unique_email();
+    create_test_user(
+        &mut tx,
+        &user1_email,
+        "pass123",
+        "student",
+        Some(school1.id),
+    )
+    .await;
+
+    let user2_email = generate_unique_email();
+    create_test_user(
+        &mut tx,
+        &user2_email,
+        "pass123",
+        "student",
+        Some(school2.id),
+    )
+    .await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/users/export?email={}", user1_email))
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let lines: Vec<&str> = body.lines().collect();
+
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].contains(&user1_email));
+    assert!(lines[1].contains(&school1.name));
+    assert!(!body.contains(&admin_email));
+    assert!(!body.contains(&user2_email));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_bulk_create_users_as_school_admin(pool: PgPool) {
+    let mut tx = pool.begin().await.unwrap();
+
+    let school = create_test_school(&mut tx, &generate_unique_school_name()).await;
+
+    let admin_email = generate_unique_email();
+    let password = "testpass123";
+    create_test_user(&mut tx, &admin_email, password, "admin", Some(school.id)).await;
+
+    tx.commit().await.unwrap();
+
+    let app = setup_test_app(pool.clone()).await;
+    let token = get_auth_token(app, &admin_email, password).await;
+
+    let app = setup_test_app(pool.clone()).await;
+    let new_email = generate_unique_email();
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/users/bulk")
+        .header("content-type", "application/json")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::from(
+            serde_json::to_string(&json!({
+                "users": [
+                    {
+                        "first_name": "New",
+                        "last_name": "Teacher",
+                        "email": new_email,
+                        "password": "brightpass123",
+                        "role_ids": [system_roles::TEACHER.to_string()]
+                    },
+                    {
+                        "first_name": "Existing",
+                        "last_name": "Admin",
+                        "email": admin_email,
+                        "password": "brightpass123"
+                    }
+                ]
+            }))
+            .unwrap(),
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["created_count"], 1);
+    assert_eq!(body["failed_count"], 1);
+    assert_eq!(body["results"][0]["status"], "created");
+    assert_eq!(
+        body["results"][0]["user"]["school_id"],
+        school.id.to_string()
+    );
+    assert_eq!(body["results"][1]["status"], "failed");
+    assert_eq!(body["results"][1]["code"], "EMAIL_TAKEN");
+}